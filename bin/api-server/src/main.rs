@@ -1,15 +1,11 @@
 //! API server binary
 
-use std::net::SocketAddr;
-
 use clap::Parser;
-use clickhouse::ClickhouseReader;
 use config::Opts;
 use dotenvy::dotenv;
-use runtime::shutdown::{ShutdownSignal, run_until_shutdown};
-use server::run;
+use runtime::logging;
+use server::run_with_opts;
 use tracing::info;
-use tracing_subscriber::filter::EnvFilter;
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
@@ -22,35 +18,12 @@ async fn main() -> eyre::Result<()> {
 
     let opts = Opts::parse();
 
-    tracing_subscriber::fmt()
-        .with_file(true)
-        .with_line_number(true)
-        .with_target(true)
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
-
-    let client = ClickhouseReader::new(
-        opts.clickhouse.url,
-        opts.clickhouse.db,
-        opts.clickhouse.username,
-        opts.clickhouse.password,
-    )?;
-
-    let addr: SocketAddr = format!("{}:{}", opts.api.host, opts.api.port).parse()?;
+    logging::init(&opts.log);
 
     info!("🔭 API server starting...");
 
-    let shutdown_signal = ShutdownSignal::new();
-    let on_shutdown = || {
-        info!("👋 API server shutting down...");
-    };
-
-    let max_requests = opts.api.rate_limit_max_requests;
-    let period = std::time::Duration::from_secs(opts.api.rate_limit_period_secs);
-    let run_server =
-        async { run(addr, client, opts.api.allowed_origins, max_requests, period).await };
-
-    run_until_shutdown(run_server, shutdown_signal, on_shutdown).await
+    // The server drains in-flight requests on SIGINT or SIGTERM and returns once done
+    run_with_opts(opts).await?;
+    info!("👋 API server shut down");
+    Ok(())
 }
@@ -5,11 +5,18 @@ use std::time::Duration;
 use clap::Parser;
 use config::Opts;
 use dotenvy::dotenv;
-use driver::driver::Driver;
-use runtime::shutdown::{ShutdownSignal, run_until_shutdown_graceful};
-use tokio::sync::broadcast;
+use driver::{
+    driver::{Driver, run_migrations, seed_fixtures},
+    preflight::check_config,
+    reprocess::run_reprocess,
+    snapshot::{run_snapshot_export, run_snapshot_import},
+};
+use runtime::{
+    logging,
+    shutdown::{ShutdownSignal, run_until_shutdown_graceful},
+};
+use tokio::{sync::broadcast, task::JoinHandle};
 use tracing::info;
-use tracing_subscriber::filter::EnvFilter;
 
 #[tokio::main]
 async fn main() -> eyre::Result<()> {
@@ -21,17 +28,48 @@ async fn main() -> eyre::Result<()> {
 
     let opts = Opts::parse();
 
-    tracing_subscriber::fmt()
-        .with_file(true)
-        .with_line_number(true)
-        .with_target(true)
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
+    logging::init(&opts.log);
+
+    if opts.migrate_only {
+        info!("Running Taikoscope migrations only");
+        return run_migrations(&opts).await;
+    }
+
+    if opts.check_config {
+        info!("Checking Taikoscope configuration");
+        let report = check_config(&opts).await;
+        println!("{}", report.render(opts.log.format));
+        if !report.passed() {
+            eyre::bail!("{} configuration check(s) failed", report.failures());
+        }
+        return Ok(());
+    }
+
+    if opts.reprocess {
+        info!("Reprocessing Taikoscope derived tables");
+        run_migrations(&opts).await?;
+        return run_reprocess(&opts).await;
+    }
+
+    if opts.snapshot_export {
+        info!("Exporting Taikoscope snapshot");
+        return run_snapshot_export(&opts).await;
+    }
+
+    if opts.snapshot_import {
+        info!("Importing Taikoscope snapshot");
+        run_migrations(&opts).await?;
+        return run_snapshot_import(&opts).await;
+    }
+
+    if opts.seed_fixtures {
+        info!("Seeding Taikoscope fixtures");
+        return seed_fixtures(&opts).await;
+    }
 
     info!("Starting Taikoscope");
 
+    let api = spawn_dev_api(&opts)?;
     let driver = Driver::new(opts).await?;
 
     // Create broadcast channel for graceful shutdown communication
@@ -46,10 +84,39 @@ async fn main() -> eyre::Result<()> {
     };
 
     run_until_shutdown_graceful(
-        async move { driver.start_with_shutdown(Some(shutdown_rx)).await },
+        async move {
+            let Some(api) = api else {
+                return driver.start_with_shutdown(Some(shutdown_rx)).await;
+            };
+            tokio::select! {
+                result = driver.start_with_shutdown(Some(shutdown_rx)) => result,
+                result = api => {
+                    result??;
+                    eyre::bail!("API server stopped")
+                }
+            }
+        },
         shutdown_signal,
         shutdown_timeout,
         on_shutdown,
     )
     .await
 }
+
+/// Start the API server next to the indexer when running with `--dev`
+#[cfg(feature = "dev")]
+fn spawn_dev_api(opts: &Opts) -> eyre::Result<Option<JoinHandle<eyre::Result<()>>>> {
+    Ok(opts.dev.then(|| {
+        info!(host = %opts.api.host, port = opts.api.port, "🔭 Dev mode: starting API server");
+        tokio::spawn(server::run_with_opts(opts.clone()))
+    }))
+}
+
+/// Reject `--dev` in builds without the API server
+#[cfg(not(feature = "dev"))]
+fn spawn_dev_api(opts: &Opts) -> eyre::Result<Option<JoinHandle<eyre::Result<()>>>> {
+    if opts.dev {
+        eyre::bail!("--dev requires building taikoscope with `--features dev`");
+    }
+    Ok(None)
+}
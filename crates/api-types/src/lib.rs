@@ -3,30 +3,95 @@
 //! These structs define the JSON responses returned by the API server. They
 //! are provided in a separate crate so that consumers such as the dashboard can
 //! depend on them without pulling in the rest of the server implementation.
+//! Every response also implements `Deserialize`, which `taikoscope-client` relies on.
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
 
 use clickhouse_lib::{
-    BatchBlobCountRow, BatchPostingTimeRow, BatchProveTimeRow, BatchVerifyTimeRow,
+    BatchAnomalyRow, BatchBlobCountRow, BatchPostingTimeRow, BatchProveTimeRow,
     BlockFeeComponentRow, ForcedInclusionProcessedRow, L1BlockTimeRow, L1DataCostRow,
     L2BlockTimeRow, L2GasUsedRow, L2TpsRow, ProveCostRow, SlashingEventRow,
 };
 
-use axum::{Json, http::StatusCode, response::IntoResponse};
+use axum::{
+    Json,
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-/// Error response following a condensed version of RFC 7807.
+/// Media type of [`ErrorResponse`] bodies, as defined by RFC 7807.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Machine readable code identifying the kind of an [`ErrorResponse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// A query parameter or request body is malformed or inconsistent.
+    InvalidParams,
+    /// A time, block or value range is empty, reversed or out of bounds.
+    InvalidRange,
+    /// A requested limit exceeds the maximum supported by the endpoint.
+    LimitExceeded,
+    /// The requested resource does not exist.
+    NotFound,
+    /// The request lacks valid credentials.
+    Unauthorized,
+    /// The request conflicts with the current configuration of the server.
+    Conflict,
+    /// The client sent too many requests and should retry later.
+    RateLimited,
+    /// An upstream service such as the database did not answer in time.
+    UpstreamTimeout,
+    /// An upstream service is overloaded or unavailable and should be retried later.
+    UpstreamUnavailable,
+    /// An upstream service returned an error.
+    UpstreamError,
+    /// The server failed unexpectedly.
+    InternalError,
+    /// A code not known to this version of the types.
+    #[serde(other)]
+    Unknown,
+}
+
+impl ErrorCode {
+    /// Identifier used in the `type` field.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidParams => "invalid_params",
+            Self::InvalidRange => "invalid_range",
+            Self::LimitExceeded => "limit_exceeded",
+            Self::NotFound => "not_found",
+            Self::Unauthorized => "unauthorized",
+            Self::Conflict => "conflict",
+            Self::RateLimited => "rate_limited",
+            Self::UpstreamTimeout => "upstream_timeout",
+            Self::UpstreamUnavailable => "upstream_unavailable",
+            Self::UpstreamError => "upstream_error",
+            Self::InternalError => "internal_error",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error response following RFC 7807.
 ///
-/// This structure is returned when API calls fail and provides
-/// machine readable details about the error. The `type` field uses a
-/// short identifier instead of a full URL.
+/// This structure is returned with the `application/problem+json` content type when API calls
+/// fail and provides machine readable details about the error. The `type` field uses a short
+/// [`ErrorCode`] instead of a full URL.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
-    /// Identifier for the error type.
+    /// Code identifying the kind of error.
     #[serde(rename = "type")]
-    pub r#type: String,
+    pub r#type: ErrorCode,
     /// Short, human readable summary of the problem.
     pub title: String,
     /// HTTP status code generated by the server.
@@ -38,23 +103,28 @@ pub struct ErrorResponse {
 impl ErrorResponse {
     /// Create a new [`ErrorResponse`].
     pub fn new(
-        r#type: impl Into<String>,
+        r#type: ErrorCode,
         title: impl Into<String>,
         status: StatusCode,
         detail: impl Into<String>,
     ) -> Self {
-        Self {
-            r#type: r#type.into(),
-            title: title.into(),
-            status: status.as_u16(),
-            detail: detail.into(),
-        }
+        Self { r#type, title: title.into(), status: status.as_u16(), detail: detail.into() }
+    }
+
+    /// Create a `400 Bad Request` error.
+    pub fn bad_request(r#type: ErrorCode, detail: impl Into<String>) -> Self {
+        Self::new(r#type, "Bad Request", StatusCode::BAD_REQUEST, detail)
+    }
+
+    /// Create a `404 Not Found` error.
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, "Not Found", StatusCode::NOT_FOUND, detail)
     }
 
     /// Create a generic database error without exposing internals.
     pub fn database_error() -> Self {
         Self::new(
-            "database-error",
+            ErrorCode::InternalError,
             "Database error",
             StatusCode::INTERNAL_SERVER_ERROR,
             "internal error",
@@ -62,38 +132,228 @@ impl ErrorResponse {
     }
 }
 
+impl std::fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}): {}", self.title, self.status, self.detail)
+    }
+}
+
+impl std::error::Error for ErrorResponse {}
+
 impl IntoResponse for ErrorResponse {
     fn into_response(self) -> axum::response::Response {
         let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-        (status, Json(self)).into_response()
+        let mut response = (status, Json(self)).into_response();
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, header::HeaderValue::from_static(PROBLEM_JSON));
+        response
+    }
+}
+
+/// Standard envelope of paginated responses.
+///
+/// Paginated endpoints return their items in this envelope unless requested with
+/// `envelope=false`, which selects the endpoint specific legacy response.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Paginated<T> {
+    /// Items of the page, newest first.
+    pub items: Vec<T>,
+    /// Whether older items exist beyond this page.
+    pub has_more: bool,
+    /// Cursor for the next (older) page, if there may be one.
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    pub prev_cursor: Option<String>,
+    /// Estimated number of items in the requested time range, ignoring other filters, or `null`
+    /// if it cannot be estimated cheaply.
+    pub total_estimate: Option<u64>,
+}
+
+/// Named time range ending at the time of the request, selected with the `range` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum TimeRangePreset {
+    /// The last 15 minutes.
+    #[serde(rename = "15m")]
+    Last15Min,
+    /// The last hour.
+    #[serde(rename = "1h")]
+    LastHour,
+    /// The last 24 hours.
+    #[serde(rename = "24h")]
+    Last24Hours,
+    /// The last 7 days.
+    #[serde(rename = "7d")]
+    Last7Days,
+    /// The last 30 days.
+    #[serde(rename = "30d")]
+    Last30Days,
+}
+
+impl TimeRangePreset {
+    /// Length of the range in seconds.
+    pub const fn seconds(self) -> u64 {
+        match self {
+            Self::Last15Min => 15 * 60,
+            Self::LastHour => 3600,
+            Self::Last24Hours => 24 * 3600,
+            Self::Last7Days => 7 * 24 * 3600,
+            Self::Last30Days => 30 * 24 * 3600,
+        }
+    }
+
+    /// Value of the `range` parameter selecting this preset.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Last15Min => "15m",
+            Self::LastHour => "1h",
+            Self::Last24Hours => "24h",
+            Self::Last7Days => "7d",
+            Self::Last30Days => "30d",
+        }
     }
 }
 
 /// Basic health status response.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
     /// Health status string.
     pub status: String,
+    /// RPC endpoints of the indexer; omitted by the API server.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rpc_endpoints: Vec<RpcEndpointHealth>,
+    /// Channels between the event subscriptions of the indexer and its writer; omitted by the
+    /// API server.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stream_channels: Vec<StreamChannelHealth>,
+    /// Counters of the indexer's data-quality checker; omitted when it is disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_quality: Option<DataQualityHealth>,
+}
+
+/// Counters of the batch data-quality checker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DataQualityHealth {
+    /// Completed checks since startup.
+    pub checks: u64,
+    /// Failed checks since startup.
+    pub failures: u64,
+    /// Missing events recovered from L1 since startup.
+    pub backfilled: u64,
+    /// Proved batches without a proposal record after the last check.
+    pub proved_without_proposal: u64,
+    /// Verified batches without a proof record after the last check.
+    pub verified_without_proof: u64,
+}
+
+/// Depth of the channel queueing the events of an indexer subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct StreamChannelHealth {
+    /// Name of the event stream, e.g. `l2_headers`.
+    pub stream: String,
+    /// What the stream does when the channel is full (`block` or `drop-oldest`).
+    pub policy: String,
+    /// Events the channel queues at most.
+    pub capacity: u64,
+    /// Events currently queued.
+    pub depth: u64,
+    /// Events dropped to make room for newer ones since startup.
+    pub dropped: u64,
+    /// Sends that waited for the writer to make room since startup.
+    pub blocked: u64,
+    /// Whether the channel is more than 80% full.
+    pub backpressured: bool,
+}
+
+/// Health of a configured RPC endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RpcEndpointHealth {
+    /// Chain the endpoint serves (`l1` or `l2`).
+    pub chain: String,
+    /// Host of the endpoint; paths and credentials are left out.
+    pub host: String,
+    /// Whether this is the chain's primary endpoint.
+    pub primary: bool,
+    /// Whether the indexer is currently connected to this endpoint.
+    pub active: bool,
+    /// Whether the endpoint is considered for connections.
+    pub healthy: bool,
+    /// Failures since startup.
+    pub failures: u64,
+    /// Share of successful RPC calls within the recent error budget window, from 0 to 100.
+    pub score: u8,
+    /// Whether the endpoint is quarantined for failing too many RPC calls.
+    pub quarantined: bool,
 }
 
 // Removed legacy L2HeadResponse and L1HeadResponse
 
 /// List of validator slashing events.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SlashingEventsResponse {
     /// List of slashing event rows.
     pub events: Vec<SlashingEventRow>,
 }
 
+/// Soft block that differed from the canonical L2 block at its height.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PreconfMismatchEvent {
+    /// L2 block number.
+    pub l2_block_number: u64,
+    /// Hash of the soft block, if the feed provided one.
+    pub soft_block_hash: Option<String>,
+    /// Hash of the canonical block, or `None` if no canonical block appeared in time.
+    pub canonical_block_hash: Option<String>,
+    /// Number of transactions in the soft block.
+    pub soft_tx_count: u32,
+    /// Number of transactions in the canonical block.
+    pub canonical_tx_count: u32,
+    /// Preconfirmed transactions missing from the canonical block.
+    pub missing_tx_hashes: Vec<String>,
+    /// Canonical transactions that were not preconfirmed.
+    pub extra_tx_hashes: Vec<String>,
+    /// Whether the shared transactions were included in a different order.
+    pub reordered: bool,
+}
+
+/// Soft block mismatches detected within the requested range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PreconfMismatchesResponse {
+    /// Mismatch events, oldest first.
+    pub events: Vec<PreconfMismatchEvent>,
+}
+
+/// Batch whose anchor block hash differed from the indexed L1 block at its anchor block id.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnchorMismatchEvent {
+    /// Batch ID.
+    pub batch_id: u64,
+    /// L1 block number the batch was proposed in.
+    pub l1_block_number: u64,
+    /// L1 block number the batch is anchored to.
+    pub anchor_block_id: u64,
+    /// Anchor block hash committed to by the batch.
+    pub anchor_block_hash: String,
+    /// Hash of the indexed L1 block at the anchor block id.
+    pub l1_block_hash: String,
+}
+
+/// Anchor block hash mismatches detected within the requested range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnchorMismatchesResponse {
+    /// Mismatch events, oldest first.
+    pub events: Vec<AnchorMismatchEvent>,
+}
+
 /// Forced inclusion events that were processed.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ForcedInclusionEventsResponse {
     /// Forced inclusion events that were processed.
     pub events: Vec<ForcedInclusionProcessedRow>,
 }
 
 /// Single L2 reorg event with sequencer addresses.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L2ReorgEvent {
     /// Block number that was replaced.
     pub from_block_number: u64,
@@ -105,19 +365,54 @@ pub struct L2ReorgEvent {
     pub old_sequencer: String,
     /// Address of the sequencer that produced the new block.
     pub new_sequencer: String,
+    /// Event the reorg coincided with: `l1_reorg`, `operator_change`, `forced_inclusion` or
+    /// `unknown`.
+    pub probable_cause: String,
     /// Time the reorg was recorded.
     pub inserted_at: DateTime<Utc>,
 }
 
 /// Detected L2 reorg events.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ReorgEventsResponse {
     /// Detected L2 reorg events.
     pub events: Vec<L2ReorgEvent>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
+}
+
+/// Number of reorgs with a given depth.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReorgDepthBucket {
+    /// Reorg depth in blocks.
+    pub depth: u16,
+    /// Number of reorgs with this depth.
+    pub count: u64,
+}
+
+/// Reorg statistics over a time range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReorgStatsResponse {
+    /// Number of reorgs in the range.
+    pub reorgs: u64,
+    /// Number of block hashes orphaned by reorgs.
+    pub orphaned_blocks: u64,
+    /// Number of distinct L2 blocks observed in the range.
+    pub l2_blocks: u64,
+    /// Orphaned blocks per 1000 observed blocks.
+    pub orphan_rate_per_1000: Option<f64>,
+    /// Mean time between consecutive reorgs in milliseconds.
+    pub mean_time_between_reorgs_ms: Option<u64>,
+    /// Number of reorgs per depth, shallowest first.
+    pub depth_histogram: Vec<ReorgDepthBucket>,
 }
 
 /// Event where a sequencer failed to post its batch and another proposer posted it
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct FailedProposalEvent {
     /// Batch ID that was originally produced (by the sequencer of the batch's blocks)
     pub batch_id: u64,
@@ -132,16 +427,22 @@ pub struct FailedProposalEvent {
 }
 
 /// Failed proposal events
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct FailedProposalEventsResponse {
     /// Failed proposal events
     pub events: Vec<FailedProposalEvent>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 // Removed legacy ActiveGatewaysResponse, CurrentOperatorResponse, NextOperatorResponse
 
 /// Preconfiguration data containing sequencer candidates and operators.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PreconfDataResponse {
     /// Candidates included in the latest preconfiguration.
     pub candidates: Vec<String>,
@@ -155,7 +456,7 @@ pub struct PreconfDataResponse {
 // AvgL2TpsResponse
 
 /// Total L2 fees broken down by component.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L2FeesResponse {
     /// Sum of priority fees for the range.
     pub priority_fee: Option<u128>,
@@ -171,64 +472,130 @@ pub struct L2FeesResponse {
 
 // Removed legacy CloudCostResponse
 
+/// Time it took to prove a batch, with the L1 transactions that proposed and proved it.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchProveTimeItem {
+    /// Batch ID.
+    pub batch_id: u64,
+    /// Seconds between proposal and proof.
+    pub seconds_to_prove: u64,
+    /// Transaction hash that proposed the batch, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposal_l1_tx_hash: Option<String>,
+    /// Transaction hash that first proved the batch, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_l1_tx_hash: Option<String>,
+}
+
+/// Time it took to verify a batch, with the L1 transactions that proved and verified it.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchVerifyTimeItem {
+    /// Batch ID.
+    pub batch_id: u64,
+    /// Seconds between proof and verification.
+    pub seconds_to_verify: u64,
+    /// Transaction hash that first proved the batch, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_l1_tx_hash: Option<String>,
+    /// Transaction hash that verified the batch, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_l1_tx_hash: Option<String>,
+}
+
 /// Time to prove individual batches.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProveTimesResponse {
     /// Time to prove individual batches.
-    pub batches: Vec<BatchProveTimeRow>,
+    pub batches: Vec<BatchProveTimeItem>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// Time to verify individual batches.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct VerifyTimesResponse {
     /// Time to verify individual batches.
-    pub batches: Vec<BatchVerifyTimeRow>,
+    pub batches: Vec<BatchVerifyTimeItem>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// L1 block numbers grouped by minute.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L1BlockTimesResponse {
     /// L1 block numbers grouped by minute.
     pub blocks: Vec<L1BlockTimeRow>,
 }
 
 /// Timestamp data for L2 blocks.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L2BlockTimesResponse {
     /// Timestamp data for L2 blocks.
     pub blocks: Vec<L2BlockTimeRow>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// Gas usage for each L2 block.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L2GasUsedResponse {
     /// Gas usage for each L2 block.
     pub blocks: Vec<L2GasUsedRow>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// L1 data posting cost per block.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L1DataCostResponse {
     /// Cost per block.
     pub blocks: Vec<L1DataCostRow>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// Prover cost per batch.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProveCostResponse {
     /// Cost information for each proved batch.
     pub batches: Vec<ProveCostRow>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// Fee components for each L2 block
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct FeeComponentsResponse {
     /// Fee components per block
     pub blocks: Vec<BlockFeeComponentRow>,
 }
 
 /// Fee components for a batch
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BatchFeeComponentRow {
     /// Batch ID
     pub batch_id: u64,
@@ -242,26 +609,41 @@ pub struct BatchFeeComponentRow {
     pub priority_fee: u128,
     /// Total base fee for the batch
     pub base_fee: u128,
+    /// Part of the base fee paid to the sequencer under the protocol's base fee sharing
+    pub shared_base_fee: u128,
     /// L1 data posting cost associated with the batch, if available
     pub l1_data_cost: Option<u128>,
     /// Prover cost for the batch, if available
     pub prove_cost: Option<u128>,
+    /// Priority fee plus the shared base fee, minus the known L1 data and proving costs
+    pub net_revenue: i128,
+    /// Gas weighted average effective gas price of the batch's blocks in wei, 0 if unknown
+    pub avg_gas_price: u128,
 }
 
 // Removed legacy BatchFeeComponentsResponse
 
 /// TPS values for each L2 block.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L2TpsResponse {
     /// TPS values for each L2 block.
     pub blocks: Vec<L2TpsRow>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// Number of blocks and batches produced by a sequencer.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SequencerDistributionItem {
     /// Sequencer address.
     pub address: String,
+    /// Human readable label of the address, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
     /// Number of blocks produced by the sequencer.
     pub blocks: u64,
     /// Number of batches proposed by the sequencer.
@@ -271,14 +653,32 @@ pub struct SequencerDistributionItem {
 }
 
 /// Distribution of blocks across sequencers.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SequencerDistributionResponse {
     /// Distribution of blocks across sequencers.
     pub sequencers: Vec<SequencerDistributionItem>,
 }
 
+/// Number of L2 blocks built by a builder.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BuilderDistributionItem {
+    /// Builder name, or the extra data of its blocks if the builder is not known.
+    pub builder: String,
+    /// Number of blocks built by the builder.
+    pub blocks: u64,
+    /// Share of the blocks in the range built by the builder, between 0 and 1.
+    pub share: f64,
+}
+
+/// Distribution of L2 blocks across builders, identified by the extra data of their blocks.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BuilderDistributionResponse {
+    /// Builders ordered by the number of blocks they built, largest first.
+    pub builders: Vec<BuilderDistributionItem>,
+}
+
 /// Blocks proposed by a sequencer.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SequencerBlocksItem {
     /// Sequencer address.
     pub address: String,
@@ -287,14 +687,14 @@ pub struct SequencerBlocksItem {
 }
 
 /// Mapping of sequencers to their blocks.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SequencerBlocksResponse {
     /// Mapping of sequencers to their blocks.
     pub sequencers: Vec<SequencerBlocksItem>,
 }
 
 /// Transaction count for a block.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BlockTransactionsItem {
     /// Block number.
     pub block_number: u64,
@@ -305,54 +705,84 @@ pub struct BlockTransactionsItem {
 }
 
 /// Collection of block transaction counts.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BlockTransactionsResponse {
     /// Collection of block transaction counts.
     pub blocks: Vec<BlockTransactionsItem>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 // removed: BlockProfitItem and BlockProfitsResponse (no longer exposed)
 
 /// Aggregated cost attributed to a proposer.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProposerCostItem {
     /// Proposer address.
     pub address: String,
+    /// Human readable label of the address, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
     /// Total cost in gwei.
     pub cost: u128,
+    /// Total cost in USD at the time each cost was incurred, when requested with
+    /// `denomination=usd`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+    /// Total cost in TAIKO at the TAIKO/ETH price when each cost was incurred, when requested
+    /// with `denomination=taiko`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_taiko: Option<f64>,
 }
 
 /// Aggregated cost results grouped by proposer.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProposerCostsResponse {
     /// Cost entries for each proposer.
     pub proposers: Vec<ProposerCostItem>,
 }
 
 /// Aggregated L2 fees for a sequencer.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SequencerFeeRow {
     /// Sequencer address.
     pub address: String,
+    /// Human readable label of the address, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
     /// Sum of priority fees for the sequencer.
     pub priority_fee: u128,
     /// Sum of base fees for the sequencer.
     pub base_fee: u128,
+    /// Part of the base fees paid to the sequencer under the protocol's base fee sharing.
+    pub shared_base_fee: u128,
     /// Total L1 data posting cost for the sequencer.
     pub l1_data_cost: u128,
     /// Total proving cost for the sequencer.
     pub prove_cost: u128,
+    /// Priority fees plus the shared base fees, minus the L1 data and proving costs.
+    pub net_revenue: i128,
 }
 
 /// Blob count per batch.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BatchBlobsResponse {
     /// Blob count per batch.
     pub batches: Vec<BatchBlobCountRow>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// Aggregated blob count per batch.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AvgBatchBlobCountRow {
     /// L1 block number
     pub l1_block_number: u64,
@@ -365,28 +795,34 @@ pub struct AvgBatchBlobCountRow {
 // Removed legacy AvgBatchBlobsResponse, BatchBlockItem, BatchBlocksResponse
 
 /// Interval between consecutive batch postings.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BatchPostingTimesResponse {
     /// Interval data for each batch.
     pub batches: Vec<BatchPostingTimeRow>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
 /// Number of the most recent L2 block.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L2HeadBlockResponse {
     /// Number of the most recent L2 block.
     pub l2_head_block: Option<u64>,
 }
 
 /// Number of the most recent L1 block.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L1HeadBlockResponse {
     /// Number of the most recent L1 block.
     pub l1_head_block: Option<u64>,
 }
 
 /// Aggregated data for the main dashboard.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct DashboardDataResponse {
     /// Average delay between L2 blocks in milliseconds.
     pub l2_block_cadence_ms: Option<u64>,
@@ -401,39 +837,1205 @@ pub struct DashboardDataResponse {
     /// Latest preconfiguration data.
     pub preconf_data: Option<PreconfDataResponse>,
     /// Number of L2 reorg events in the selected range.
-    pub l2_reorgs: usize,
+    pub l2_reorgs: Option<usize>,
     /// Number of slashing events in the selected range.
-    pub slashings: usize,
+    pub slashings: Option<usize>,
     /// Number of forced inclusion events in the selected range.
-    pub forced_inclusions: usize,
+    pub forced_inclusions: Option<usize>,
     /// Number of failed proposal events in the selected range.
-    pub failed_proposals: usize,
+    pub failed_proposals: Option<usize>,
     /// Number of the most recent L2 block.
     pub l2_head_block: Option<u64>,
     /// Number of the most recent L1 block.
     pub l1_head_block: Option<u64>,
+    /// Sections that failed or timed out and are `null` in this response.
+    pub errors: Vec<DashboardSectionError>,
+    /// Whether sections were left out because the request's query budget ran out.
+    #[serde(default)]
+    pub budget_exhausted: bool,
+}
+
+/// Dashboard section that could not be loaded.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DashboardSectionError {
+    /// Name of the response field that is missing.
+    pub section: String,
+    /// Why the section is missing.
+    pub error: String,
 }
 
 /// Current ETH price in USD.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct EthPriceResponse {
     /// Price of ETH in USD.
     pub price: f64,
 }
 
+/// Batches whose lifecycle records are still inconsistent after backfill.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DataQualityResponse {
+    /// Number of proved batches without a proposal record.
+    pub proved_without_proposal_count: u64,
+    /// Number of verified batches without a proof record.
+    pub verified_without_proof_count: u64,
+    /// Proved batches without a proposal record, capped at the table limit.
+    pub proved_without_proposal: Vec<BatchAnomalyRow>,
+    /// Verified batches without a proof record, capped at the table limit.
+    pub verified_without_proof: Vec<BatchAnomalyRow>,
+}
+
+/// L2 block entry in a leaderboard.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardBlockItem {
+    /// Block number.
+    pub block_number: u64,
+    /// Timestamp of the block.
+    pub block_time: DateTime<Utc>,
+    /// Sequencer that produced the block.
+    pub sequencer: String,
+    /// Gas used in the block.
+    pub gas_used: u64,
+    /// Number of transactions in the block.
+    pub tx_count: u32,
+}
+
+/// Sequencer entry in a leaderboard.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardSequencerItem {
+    /// Sequencer address.
+    pub address: String,
+    /// Number of blocks produced by the sequencer.
+    pub blocks: u64,
+    /// Sum of priority fees for the sequencer.
+    pub priority_fee: u128,
+    /// Sum of base fees for the sequencer.
+    pub base_fee: u128,
+}
+
+/// Top-N rankings for the selected time range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LeaderboardsResponse {
+    /// L2 blocks with the highest gas usage.
+    pub top_blocks_by_gas: Vec<LeaderboardBlockItem>,
+    /// L2 blocks with the most transactions.
+    pub top_blocks_by_tx_count: Vec<LeaderboardBlockItem>,
+    /// Batches that took longest to prove.
+    pub slowest_proofs: Vec<BatchProveTimeRow>,
+    /// Sequencers ranked by number of blocks produced.
+    pub sequencers_by_blocks: Vec<LeaderboardSequencerItem>,
+    /// Sequencers ranked by total fees (priority plus base).
+    pub sequencers_by_fees: Vec<LeaderboardSequencerItem>,
+}
+
+/// Response cache counters for a route group.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CacheGroupStatsItem {
+    /// Responses served from the cache.
+    pub hits: u64,
+    /// Responses computed because no fresh entry existed.
+    pub misses: u64,
+    /// Responses computed because the client sent `Cache-Control: no-cache`.
+    pub bypasses: u64,
+    /// Hits served from an entry refreshed by the pre-warmer.
+    pub warm_hits: u64,
+    /// Entries refreshed by the pre-warmer.
+    pub prewarms: u64,
+    /// Share of the hits and misses served from a pre-warmed entry, `null` before the first
+    /// lookup.
+    pub warm_hit_rate: Option<f64>,
+}
+
+/// Response cache counters since the server started.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CacheStatsResponse {
+    /// Counters for aggregate dashboard endpoints.
+    pub dashboard: CacheGroupStatsItem,
+    /// Counters for paginated table endpoints.
+    pub table: CacheGroupStatsItem,
+}
+
+/// Result of dropping cached responses.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CacheInvalidateResponse {
+    /// Number of cached responses removed.
+    pub invalidated: usize,
+}
+
+/// Request counters of an `Origin` header value.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OriginUsageItem {
+    /// Origin, `(none)` for requests without an `Origin` header and `(other)` for origins seen
+    /// after the tracking limit was reached.
+    pub origin: String,
+    /// Whether the CORS policy allows the origin.
+    pub allowed: bool,
+    /// Requests received.
+    pub requests: u64,
+    /// Requests rejected by the rate limiter.
+    pub rate_limited: u64,
+    /// Other requests answered with a 4xx status.
+    pub client_errors: u64,
+    /// Time of the first request.
+    pub first_seen: DateTime<Utc>,
+    /// Time of the latest request.
+    pub last_seen: DateTime<Utc>,
+}
+
+/// API usage per origin since the server started.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ApiUsageByOriginResponse {
+    /// Time counting started at.
+    pub since: DateTime<Utc>,
+    /// Requests received from all origins.
+    pub requests: u64,
+    /// Requests from origins the CORS policy does not allow.
+    pub rejected_requests: u64,
+    /// Counters per origin, most requests first.
+    pub origins: Vec<OriginUsageItem>,
+}
+
+/// Query counters of a `ClickHouse` endpoint.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReplicaStatsItem {
+    /// Endpoint URL.
+    pub url: String,
+    /// Whether the endpoint is currently used for routing.
+    pub healthy: bool,
+    /// Queries sent to the endpoint.
+    pub queries: u64,
+    /// Queries that failed.
+    pub errors: u64,
+    /// Average query latency in milliseconds.
+    pub avg_latency_ms: f64,
+}
+
+/// Query counters of the primary and every read replica since the server started.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReplicaStatsResponse {
+    /// Primary endpoint first, followed by the replicas.
+    pub replicas: Vec<ReplicaStatsItem>,
+}
+
+/// L1 blob fee market state when a batch was proposed.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BlobFeeHistoryItem {
+    /// Batch ID.
+    pub batch_id: u64,
+    /// L1 block number the batch was proposed in.
+    pub l1_block_number: u64,
+    /// Timestamp of the L1 block.
+    pub block_ts: u64,
+    /// Excess blob gas of the L1 block.
+    pub excess_blob_gas: u64,
+    /// Blob base fee of the L1 block in wei.
+    pub blob_base_fee: u128,
+}
+
+/// Blob fee history per batch.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BlobFeeHistoryResponse {
+    /// Blob fee market state per batch.
+    pub batches: Vec<BlobFeeHistoryItem>,
+    /// Cursor for the next (older) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (newer) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
+}
+
+/// `TaikoInbox` protocol configuration discovered by the driver.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProtocolConfigItem {
+    /// Address of the `TaikoInbox` contract.
+    pub inbox_address: String,
+    /// Chain ID of the network where the Taiko contracts are deployed.
+    pub chain_id: u64,
+    /// Maximum number of unverified batches.
+    pub max_unverified_batches: u64,
+    /// Size of the batch ring buffer.
+    pub batch_ring_buffer_size: u64,
+    /// Maximum number of batches verified per proposal or proof.
+    pub max_batches_to_verify: u64,
+    /// Maximum gas limit of an L2 block.
+    pub block_max_gas_limit: u32,
+    /// Liveness bond per batch in wei of the bond token.
+    pub liveness_bond_base: u128,
+    /// Liveness bond per block in wei of the bond token.
+    pub liveness_bond_per_block: u128,
+    /// Number of batches between two L2-to-L1 state root syncs.
+    pub state_root_sync_internal: u8,
+    /// Maximum difference between the anchor height and the current block number.
+    pub max_anchor_height_offset: u64,
+    /// Percentage of the base fee shared with the proposer.
+    pub base_fee_sharing_pctg: u8,
+    /// Proving window in seconds.
+    pub proving_window: u16,
+    /// Cooldown window in seconds.
+    pub cooldown_window: u32,
+    /// Maximum number of signals received by `TaikoL2`.
+    pub max_signals_to_receive: u8,
+    /// Maximum number of blocks per batch.
+    pub max_blocks_per_batch: u16,
+    /// Ontake fork height.
+    pub ontake_fork_height: u64,
+    /// Pacaya fork height.
+    pub pacaya_fork_height: u64,
+}
+
+/// Protocol configuration response.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProtocolConfigResponse {
+    /// Latest stored configuration, or `null` if none has been discovered yet.
+    pub config: Option<ProtocolConfigItem>,
+}
+
+/// Distribution of the L1 -> L2 anchor lag within a time bucket.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnchorLagItem {
+    /// Start of the bucket.
+    pub bucket_start: DateTime<Utc>,
+    /// Number of L2 blocks in the bucket.
+    pub blocks: u64,
+    /// Average lag in L1 blocks.
+    pub avg_lag: f64,
+    /// Median lag in L1 blocks.
+    pub p50_lag: u64,
+    /// 95th percentile lag in L1 blocks.
+    pub p95_lag: u64,
+    /// Maximum lag in L1 blocks.
+    pub max_lag: u64,
+}
+
+/// L1 -> L2 anchor lag over time, measured as the L1 head minus the anchor block id of each L2
+/// block.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnchorLagResponse {
+    /// Lag distribution per time bucket, oldest first.
+    pub buckets: Vec<AnchorLagItem>,
+}
+
+/// Distribution of L2 block gas saturation within a time bucket.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GasSaturationItem {
+    /// Start of the bucket.
+    pub bucket_start: DateTime<Utc>,
+    /// Number of L2 blocks in the bucket.
+    pub blocks: u64,
+    /// Average saturation, between 0 and 1.
+    pub avg_saturation: f64,
+    /// 95th percentile saturation.
+    pub p95_saturation: f64,
+    /// Maximum saturation.
+    pub max_saturation: f64,
+}
+
+/// L2 block gas saturation over time, measured as the gas used over the gas limit of each block.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GasSaturationResponse {
+    /// Saturation distribution per time bucket, oldest first.
+    pub buckets: Vec<GasSaturationItem>,
+}
+
+/// Blob usage, block count and transaction count of a batch.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchEfficiencyItem {
+    /// Batch ID.
+    pub batch_id: u64,
+    /// L1 block number the batch was proposed in.
+    pub l1_block_number: u64,
+    /// Where the batch posted its transactions: `blob` or `calldata`.
+    pub da_mode: String,
+    /// Number of blobs in the batch.
+    pub blob_count: u8,
+    /// Bytes of blob data used by the batch.
+    pub blob_total_bytes: u32,
+    /// Bytes of `txList` calldata posted by the batch.
+    pub calldata_bytes: u32,
+    /// Share of the posted blob space used by the batch, or `None` for batches without blobs.
+    pub blob_utilization: Option<f64>,
+    /// Number of L2 blocks in the batch.
+    pub blocks: u16,
+    /// Number of transactions in the batch.
+    pub txs: u64,
+}
+
+/// Number of batches containing a given number of L2 blocks.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BlocksPerBatchBucket {
+    /// L2 blocks per batch.
+    pub blocks: u16,
+    /// Number of batches with that many blocks.
+    pub batches: u64,
+}
+
+/// Batches per data availability mode within a time bucket.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DaModeShareItem {
+    /// Start of the bucket as a UNIX timestamp in seconds.
+    pub bucket_ts: u64,
+    /// Batches posted in blobs.
+    pub blob_batches: u64,
+    /// Batches posted as calldata.
+    pub calldata_batches: u64,
+    /// Share of the batches posted as calldata, or `None` for buckets without batches.
+    pub calldata_share: Option<f64>,
+    /// Bytes of blob data used by the blob batches.
+    pub blob_bytes: u64,
+    /// Bytes of calldata posted by the calldata batches.
+    pub calldata_bytes: u64,
+}
+
+/// Batch size efficiency over a time range, for tuning batch posting parameters.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchEfficiencyResponse {
+    /// Number of batches in the range.
+    pub batches: u64,
+    /// Bytes used divided by blob space posted, over all batches with blobs.
+    pub blob_utilization: Option<f64>,
+    /// Average number of L2 blocks per batch.
+    pub avg_blocks_per_batch: Option<f64>,
+    /// Average number of transactions per batch.
+    pub avg_txs_per_batch: Option<f64>,
+    /// Distribution of L2 blocks per batch, by ascending block count.
+    pub blocks_per_batch: Vec<BlocksPerBatchBucket>,
+    /// Batches per data availability mode over time, oldest first. Not affected by the
+    /// `da_mode` filter.
+    pub da_mode_share: Vec<DaModeShareItem>,
+    /// Efficiency per batch, by ascending batch id.
+    pub items: Vec<BatchEfficiencyItem>,
+}
+
+/// L1 gas spent on one protocol operation.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProtocolGasOperationItem {
+    /// Protocol operation: `propose`, `prove` or `verify`.
+    pub operation: String,
+    /// Number of transactions.
+    pub txs: u64,
+    /// Total gas used.
+    pub gas_used: u64,
+    /// Total fees paid in gwei, including blob fees.
+    pub cost: u128,
+}
+
+/// L1 gas spent by an address on one protocol operation.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProtocolGasAddressItem {
+    /// Protocol operation: `propose`, `prove` or `verify`.
+    pub operation: String,
+    /// Sender of the transactions.
+    pub address: String,
+    /// Number of transactions.
+    pub txs: u64,
+    /// Total gas used.
+    pub gas_used: u64,
+    /// Total fees paid in gwei, including blob fees.
+    pub cost: u128,
+}
+
+/// L1 gas spent on running the rollup over a time range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProtocolGasResponse {
+    /// Total fees paid in gwei across all operations.
+    pub total_cost: u128,
+    /// Spend per operation, most expensive first.
+    pub operations: Vec<ProtocolGasOperationItem>,
+    /// Spend per operation and address, most expensive first.
+    pub addresses: Vec<ProtocolGasAddressItem>,
+}
+
+/// Distribution of batch prove or verify times within a day.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TimePercentilesItem {
+    /// Start of the day (UTC).
+    pub day: DateTime<Utc>,
+    /// Number of batches proved or verified during the day.
+    pub batches: u64,
+    /// Median time in milliseconds.
+    pub p50_ms: u64,
+    /// 90th percentile time in milliseconds.
+    pub p90_ms: u64,
+    /// 99th percentile time in milliseconds.
+    pub p99_ms: u64,
+    /// Maximum time in milliseconds.
+    pub max_ms: u64,
+}
+
+/// Daily prove or verify time percentiles, for SLA reporting.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TimePercentilesResponse {
+    /// Percentiles per day, oldest first.
+    pub days: Vec<TimePercentilesItem>,
+}
+
+/// Prove times of the batches proved with one proof type.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProofTypeProveTimesItem {
+    /// Proof type: `sgx`, `zk`, `guardian` or `unknown` for proofs whose verifier was not
+    /// identified.
+    pub proof_type: String,
+    /// Number of proofs of this type.
+    pub batches: u64,
+    /// Average prove time in milliseconds.
+    pub avg_ms: u64,
+    /// Median prove time in milliseconds.
+    pub p50_ms: u64,
+    /// 90th percentile prove time in milliseconds.
+    pub p90_ms: u64,
+    /// 99th percentile prove time in milliseconds.
+    pub p99_ms: u64,
+    /// Maximum prove time in milliseconds.
+    pub max_ms: u64,
+}
+
+/// Batch prove times broken down by proof type.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProofTypeProveTimesResponse {
+    /// Prove times per proof type, for the types that proved at least one batch.
+    pub proof_types: Vec<ProofTypeProveTimesItem>,
+}
+
+/// Uptime of a sequencer within a day.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SequencerUptimeItem {
+    /// Start of the day (UTC).
+    pub day: DateTime<Utc>,
+    /// Sequencer address.
+    pub sequencer: String,
+    /// Seconds the sequencer was the scheduled operator.
+    pub scheduled_secs: u64,
+    /// Seconds in which the sequencer produced no blocks while scheduled.
+    pub downtime_secs: u64,
+    /// Number of continuous downtime spans.
+    pub downtime_intervals: u64,
+    /// Share of the scheduled time the sequencer was producing blocks, in percent.
+    pub uptime_pct: f64,
+}
+
+/// Daily uptime of every scheduled sequencer.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SequencerUptimeResponse {
+    /// Uptime per sequencer and day, most recent day first.
+    pub days: Vec<SequencerUptimeItem>,
+}
+
+/// L2 mempool activity within a minute.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MempoolStatsItem {
+    /// Start of the minute (UTC).
+    pub minute: DateTime<Utc>,
+    /// Transactions first seen in the mempool during the minute.
+    pub seen_txs: u32,
+    /// Seen transactions included in a block during the minute.
+    pub included_txs: u32,
+    /// Largest number of pending transactions during the minute.
+    pub max_depth: u32,
+    /// Pending transactions at the end of the minute.
+    pub end_depth: u32,
+    /// Average time from first seen to inclusion in milliseconds.
+    pub avg_inclusion_ms: u64,
+    /// Median time from first seen to inclusion in milliseconds.
+    pub p50_inclusion_ms: u64,
+    /// 95th percentile time from first seen to inclusion in milliseconds.
+    pub p95_inclusion_ms: u64,
+    /// Longest time from first seen to inclusion in milliseconds.
+    pub max_inclusion_ms: u64,
+}
+
+/// Per-minute L2 mempool depth and inclusion latency.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MempoolStatsResponse {
+    /// Mempool activity per minute, oldest minute first.
+    pub minutes: Vec<MempoolStatsItem>,
+}
+
+/// Sample of a core metric that deviated sharply from its moving average.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnomalyItem {
+    /// Metric the sample belongs to (`"tps"`, `"gas_used"`, `"batch_cadence"` or `"prove_time"`).
+    pub metric: String,
+    /// Unix timestamp of the sample in seconds.
+    pub detected_ts: u64,
+    /// Sampled value, in the unit of the metric.
+    pub value: f64,
+    /// Moving average of the previous samples.
+    pub expected: f64,
+    /// Moving standard deviation of the previous samples.
+    pub std_dev: f64,
+    /// Number of standard deviations between the sample and the moving average.
+    pub z_score: f64,
+}
+
+/// Anomalies of core metrics detected within the requested range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AnomaliesResponse {
+    /// Anomalies, oldest first.
+    pub anomalies: Vec<AnomalyItem>,
+}
+
+/// Finality stage of an L2 block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FinalityStage {
+    /// Observed on L2 but not yet proposed on L1.
+    Preconfirmed,
+    /// Proposed in a batch on L1.
+    Proposed,
+    /// The batch containing the block has been proved.
+    Proved,
+    /// The batch containing the block has been verified.
+    Verified,
+}
+
+/// Current finality stage of an L2 block and when each stage was reached.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BlockStatusResponse {
+    /// L2 block number.
+    pub l2_block_number: u64,
+    /// Current finality stage.
+    pub stage: FinalityStage,
+    /// Batch the block was proposed in.
+    pub batch_id: Option<u64>,
+    /// Time the L2 block was produced.
+    pub preconfirmed_at: Option<DateTime<Utc>>,
+    /// Time of the L1 block that proposed the batch.
+    pub proposed_at: Option<DateTime<Utc>>,
+    /// Time of the L1 block that proved the batch.
+    pub proved_at: Option<DateTime<Utc>>,
+    /// Time of the L1 block that verified the batch.
+    pub verified_at: Option<DateTime<Utc>>,
+}
+
+/// Role an L1 transaction played in the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TxRole {
+    /// Proposed a batch.
+    Proposal,
+    /// Proved one or more batches.
+    Prove,
+    /// Verified batches up to a batch.
+    Verify,
+    /// Processed a forced inclusion.
+    ForcedInclusion,
+}
+
+/// Role an L1 transaction played for a batch.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TxRoleItem {
+    /// Role the transaction played.
+    pub role: TxRole,
+    /// Batch the role relates to, absent for forced inclusions.
+    pub batch_id: Option<u64>,
+}
+
+/// Protocol roles of an indexed L1 transaction.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TxLookupResponse {
+    /// Transaction hash.
+    pub tx_hash: String,
+    /// L1 block the transaction was included in.
+    pub l1_block_number: u64,
+    /// Time of the L1 block, if the block was indexed.
+    pub block_time: Option<DateTime<Utc>>,
+    /// Time the transaction was first indexed.
+    pub indexed_at: Option<DateTime<Utc>>,
+    /// Roles the transaction played, one per related batch.
+    pub roles: Vec<TxRoleItem>,
+}
+
+/// L2 block of a batch.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchBlockItem {
+    /// L2 block number.
+    pub l2_block_number: u64,
+    /// Time the L2 block was produced.
+    pub block_time: DateTime<Utc>,
+    /// Gas used by the block.
+    pub gas_used: u128,
+    /// Gas limit of the block, absent when it was not recorded.
+    pub gas_limit: Option<u64>,
+    /// Number of transactions in the block.
+    pub tx_count: u32,
+    /// Sequencer that produced the block.
+    pub sequencer: String,
+}
+
+/// L2 blocks of a batch.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchBlocksResponse {
+    /// Batch ID.
+    pub batch_id: u64,
+    /// Blocks, highest block number first.
+    pub blocks: Vec<BatchBlockItem>,
+    /// Cursor for the next (lower) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Cursor for the previous (higher) page, if there may be one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
+}
+
+/// Number of recent L2 blocks in each finality stage.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BlockStatusSummaryResponse {
+    /// Latest observed L2 block number.
+    pub head_block: Option<u64>,
+    /// Number of blocks in the window.
+    pub blocks: u64,
+    /// Blocks not yet proposed.
+    pub preconfirmed: u64,
+    /// Blocks proposed but not proved.
+    pub proposed: u64,
+    /// Blocks proved but not verified.
+    pub proved: u64,
+    /// Blocks verified.
+    pub verified: u64,
+}
+
+/// SLA summary of a probed public RPC endpoint.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RpcStatusItem {
+    /// Endpoint URL.
+    pub url: String,
+    /// Whether the latest probe was healthy.
+    pub healthy: bool,
+    /// Blocks behind the L2 head at the latest probe.
+    pub block_lag: Option<u64>,
+    /// Time of the latest probe.
+    pub last_probed_at: DateTime<Utc>,
+    /// Number of probes in the range.
+    pub probes: u64,
+    /// Share of unhealthy probes in the range.
+    pub error_rate: f64,
+    /// Average latency of successful probes in milliseconds.
+    pub avg_latency_ms: Option<f64>,
+    /// 95th percentile latency of successful probes in milliseconds.
+    pub p95_latency_ms: Option<f64>,
+}
+
+/// Health of the probed public RPC endpoints.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RpcStatusResponse {
+    /// SLA summary per endpoint.
+    pub endpoints: Vec<RpcStatusItem>,
+}
+
+/// Ingestion latency of an event type, from receipt by the extractor to its rows being stored.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PipelineLatencyItem {
+    /// Event type, or `all` for the total over every type.
+    pub kind: String,
+    /// Number of events in the range.
+    pub events: u64,
+    /// Median time between receipt and the start of processing in milliseconds.
+    pub queue_p50_ms: f64,
+    /// 99th percentile time between receipt and the start of processing in milliseconds.
+    pub queue_p99_ms: f64,
+    /// Median processing time in milliseconds.
+    pub process_p50_ms: f64,
+    /// 99th percentile processing time in milliseconds.
+    pub process_p99_ms: f64,
+    /// Median end-to-end latency in milliseconds.
+    pub total_p50_ms: f64,
+    /// 99th percentile end-to-end latency in milliseconds.
+    pub total_p99_ms: f64,
+    /// Maximum end-to-end latency in milliseconds.
+    pub total_max_ms: f64,
+}
+
+/// End-to-end ingestion latency per event type.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PipelineLatencyResponse {
+    /// Latency per event type, followed by the total.
+    pub kinds: Vec<PipelineLatencyItem>,
+}
+
+/// Propagation delay of a chain's headers: the time from a block's timestamp until the indexer
+/// received its header.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PropagationDelayItem {
+    /// Chain of the headers (`l1` or `l2`).
+    pub chain: String,
+    /// Number of headers received in the range.
+    pub blocks: u64,
+    /// Headers received before their own timestamp, a sign of clock skew.
+    pub early_blocks: u64,
+    /// Smallest delay in milliseconds.
+    pub min_ms: i64,
+    /// Average delay in milliseconds.
+    pub avg_ms: f64,
+    /// Median delay in milliseconds.
+    pub p50_ms: f64,
+    /// 90th percentile delay in milliseconds.
+    pub p90_ms: f64,
+    /// 99th percentile delay in milliseconds.
+    pub p99_ms: f64,
+    /// Largest delay in milliseconds.
+    pub max_ms: i64,
+}
+
+/// Header propagation delay per chain.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PropagationDelayResponse {
+    /// Delay distribution per chain.
+    pub chains: Vec<PropagationDelayItem>,
+}
+
+/// Latest gap backfill progress of a chain.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BackfillStatusItem {
+    /// Chain being backfilled (`l1` or `l2`).
+    pub chain: String,
+    /// Blocks scheduled in the current or last run.
+    pub scheduled_blocks: u64,
+    /// Blocks written so far.
+    pub filled_blocks: u64,
+    /// Blocks that could not be fetched.
+    pub failed_blocks: u64,
+    /// Blocks not processed yet.
+    pub remaining_blocks: u64,
+    /// Scheduled block closest to the head, filled first.
+    pub newest_block: Option<u64>,
+    /// Scheduled block furthest from the head, filled last.
+    pub oldest_block: Option<u64>,
+    /// Block processed most recently.
+    pub current_block: Option<u64>,
+    /// RPC request budget of the backfill, 0 when unlimited.
+    pub max_requests_per_sec: u32,
+    /// UNIX timestamp in seconds of the latest progress snapshot.
+    pub updated_at: u64,
+}
+
+/// Gap backfill progress per chain.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BackfillStatusResponse {
+    /// Progress per chain.
+    pub chains: Vec<BackfillStatusItem>,
+}
+
+/// Change of the driver instance ingesting events.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LeaderChangeItem {
+    /// Lease that changed hands, e.g. `ingest`.
+    pub lease: String,
+    /// Instance that became leader.
+    pub holder: String,
+    /// Leader before the change, if one was known.
+    pub previous_holder: Option<String>,
+    /// UNIX timestamp in seconds of the change.
+    pub changed_at: u64,
+}
+
+/// Driver leadership changes over a time range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LeaderChangesResponse {
+    /// Number of changes in the range.
+    pub changes: u64,
+    /// Instance that became leader most recently, if any change is in the range.
+    pub current_leader: Option<String>,
+    /// Changes, most recent first.
+    pub items: Vec<LeaderChangeItem>,
+}
+
+/// Inbox bond balance of an account.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BondBalanceItem {
+    /// Account address.
+    pub address: String,
+    /// Latest known bond balance.
+    pub balance: u128,
+    /// L1 block the balance was last updated in.
+    pub l1_block_number: u64,
+    /// Total liveness bonds taken from the account.
+    pub total_debited: u128,
+    /// Total liveness bonds returned to the account.
+    pub total_credited: u128,
+    /// Whether the balance is below the bond required for a full batch.
+    pub at_risk: bool,
+    /// Balance in ETH at the latest TAIKO/ETH price, if a price snapshot exists.
+    pub balance_eth: Option<f64>,
+    /// Balance in USD at the latest TAIKO/USD price, if a price snapshot exists.
+    pub balance_usd: Option<f64>,
+}
+
+/// Inbox bond balances, lowest first.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BondBalancesResponse {
+    /// Liveness bond taken for a batch with the maximum number of blocks, if the protocol
+    /// configuration is known.
+    pub required_bond: Option<u128>,
+    /// Latest price of one TAIKO in ETH, if a price snapshot exists.
+    pub taiko_eth: Option<f64>,
+    /// Latest price of one TAIKO in USD, if a price snapshot exists.
+    pub taiko_usd: Option<f64>,
+    /// Balance per account.
+    pub accounts: Vec<BondBalanceItem>,
+}
+
+/// Inbox bond balance change of an account.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BondHistoryItem {
+    /// L1 block the change was emitted in.
+    pub l1_block_number: u64,
+    /// Transaction that emitted the change.
+    pub tx_hash: String,
+    /// Index of the event log within the block.
+    pub log_index: u64,
+    /// Kind of change: `deposited`, `withdrawn`, `debited` or `credited`.
+    pub kind: String,
+    /// Amount added to or removed from the balance.
+    pub amount: u128,
+    /// Bond balance at the end of the L1 block.
+    pub balance: u128,
+}
+
+/// Inbox bond history of an account.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BondHistoryResponse {
+    /// Account address.
+    pub address: String,
+    /// Latest known bond balance, if the account has any ledger entry.
+    pub balance: Option<u128>,
+    /// Liveness bond taken for a batch with the maximum number of blocks, if the protocol
+    /// configuration is known.
+    pub required_bond: Option<u128>,
+    /// Whether the balance is below the bond required for a full batch.
+    pub at_risk: bool,
+    /// Balance changes, most recent first.
+    pub items: Vec<BondHistoryItem>,
+}
+
+/// L1 wallet balances of a whitelisted operator.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OperatorBalanceItem {
+    /// Operator address.
+    pub address: String,
+    /// L1 block the balances were read at.
+    pub l1_block_number: u64,
+    /// ETH balance in wei.
+    pub eth_balance: u128,
+    /// TAIKO token balance, if token balances are tracked.
+    pub taiko_balance: Option<u128>,
+    /// Whether a balance is below its configured threshold.
+    pub low_balance: bool,
+}
+
+/// Wallet balances of the whitelisted operators in the latest snapshot, lowest ETH balance first.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OperatorBalancesResponse {
+    /// Unix timestamp of the snapshot in seconds, if one was taken.
+    pub snapshot_ts: Option<u64>,
+    /// Balances per operator.
+    pub operators: Vec<OperatorBalanceItem>,
+}
+
+/// Snapshot of the forced inclusion queue.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ForcedInclusionQueueItem {
+    /// Unix timestamp of the snapshot in seconds.
+    pub snapshot_ts: u64,
+    /// L1 block the queue was read at.
+    pub l1_block_number: u64,
+    /// Number of queued forced inclusions.
+    pub pending: u64,
+    /// Seconds the oldest queued forced inclusion has been waiting, if any is queued.
+    pub oldest_age_secs: Option<u64>,
+    /// Batch id by which the oldest queued forced inclusion must be included.
+    pub deadline_batch_id: Option<u64>,
+    /// Id of the next batch to be proposed.
+    pub next_batch_id: u64,
+    /// Whether the oldest queued forced inclusion is close to its deadline.
+    pub near_deadline: bool,
+}
+
+/// Depth of the forced inclusion queue.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ForcedInclusionQueueResponse {
+    /// Latest snapshot, if one was taken.
+    pub latest: Option<ForcedInclusionQueueItem>,
+    /// Snapshots within the requested range, oldest first.
+    pub snapshots: Vec<ForcedInclusionQueueItem>,
+}
+
+/// Snapshot of how many batches verification lags behind the latest proposed batch.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VerificationBacklogItem {
+    /// Unix timestamp of the snapshot in seconds.
+    pub snapshot_ts: u64,
+    /// L1 block the batch ids were read at.
+    pub l1_block_number: u64,
+    /// Id of the latest proposed batch.
+    pub last_proposed_batch_id: u64,
+    /// Id of the latest verified batch.
+    pub last_verified_batch_id: u64,
+    /// Number of proposed batches not verified yet.
+    pub backlog: u64,
+    /// Whether the backlog exceeded the configured threshold.
+    pub over_threshold: bool,
+}
+
+/// Backlog of batches waiting for verification.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct VerificationBacklogResponse {
+    /// Latest snapshot, if one was taken.
+    pub latest: Option<VerificationBacklogItem>,
+    /// Snapshots within the requested range, oldest first.
+    pub snapshots: Vec<VerificationBacklogItem>,
+}
+
+/// Batch proposal of a proposer nonce, timed from the L1 mempool to inclusion.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProposalInclusionItem {
+    /// Sender of the proposal.
+    pub proposer: String,
+    /// Nonce of the proposal transaction.
+    pub nonce: u64,
+    /// Hash of the latest pending or the included transaction.
+    pub tx_hash: String,
+    /// Time in milliseconds the proposal was first seen in the mempool.
+    pub first_seen_ms: u64,
+    /// Maximum fee per gas of the latest transaction in wei.
+    pub max_fee_per_gas: u128,
+    /// Maximum priority fee per gas of the latest transaction in wei, unset for legacy
+    /// transactions.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// Times the pending transaction was replaced.
+    pub replacements: u32,
+    /// Batch proposed by the transaction once included.
+    pub batch_id: Option<u64>,
+    /// L1 block the transaction was included in.
+    pub l1_block_number: Option<u64>,
+    /// Time in milliseconds from first seen to inclusion.
+    pub inclusion_delay_ms: Option<u64>,
+    /// Whether the nonce was used without proposing a batch.
+    pub dropped: bool,
+}
+
+/// Mempool timing of the batch proposals first seen within a range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProposalInclusionDelayResponse {
+    /// Proposals, oldest first.
+    pub proposals: Vec<ProposalInclusionItem>,
+    /// Number of included proposals.
+    pub included: usize,
+    /// Number of proposals neither included nor dropped.
+    pub pending: usize,
+    /// Number of proposals whose nonce was used without proposing a batch.
+    pub dropped: usize,
+    /// Average inclusion delay in milliseconds.
+    pub avg_delay_ms: Option<u64>,
+    /// 95th percentile inclusion delay in milliseconds.
+    pub p95_delay_ms: Option<u64>,
+    /// Longest inclusion delay in milliseconds.
+    pub max_delay_ms: Option<u64>,
+}
+
+/// Change of the L2 block sequencer at an operator handover.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HandoverItem {
+    /// Sequencer handing over.
+    pub outgoing: String,
+    /// Sequencer taking over.
+    pub incoming: String,
+    /// Last block of the outgoing sequencer.
+    pub last_block_number: u64,
+    /// First block of the incoming sequencer.
+    pub first_block_number: u64,
+    /// Time of the first block of the incoming sequencer.
+    pub handover_time: DateTime<Utc>,
+    /// Seconds between the last block of the outgoing and the first block of the incoming
+    /// sequencer.
+    pub latency_secs: u64,
+}
+
+/// Latency of the sequencer handovers within a range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HandoverLatencyResponse {
+    /// Handovers, oldest first.
+    pub handovers: Vec<HandoverItem>,
+    /// Median latency in seconds.
+    pub p50_secs: Option<u64>,
+    /// 90th percentile latency in seconds.
+    pub p90_secs: Option<u64>,
+    /// 99th percentile latency in seconds.
+    pub p99_secs: Option<u64>,
+    /// Longest latency in seconds.
+    pub max_secs: Option<u64>,
+}
+
+/// L1 epoch and its preconfirmation operator.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct OperatorEpochItem {
+    /// L1 epoch number.
+    pub epoch: u64,
+    /// First slot of the epoch.
+    pub start_slot: u64,
+    /// Start of the epoch as a UNIX timestamp in seconds.
+    pub start_ts: u64,
+    /// End of the epoch (exclusive) as a UNIX timestamp in seconds.
+    pub end_ts: u64,
+    /// First L1 block seen within the epoch, unset for epochs without indexed blocks.
+    pub first_l1_block: Option<u64>,
+    /// Last L1 block seen within the epoch, unset for epochs without indexed blocks.
+    pub last_l1_block: Option<u64>,
+    /// Operator scheduled for the epoch, unset when unknown.
+    pub operator: Option<String>,
+    /// Known label of the operator.
+    pub label: Option<String>,
+}
+
+/// Preconfirmation operators of the current, next and past L1 epochs.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct OperatorScheduleResponse {
+    /// Latest L1 slot with preconfirmation data.
+    pub slot: Option<u64>,
+    /// Epoch of the latest slot.
+    pub current: Option<OperatorEpochItem>,
+    /// Epoch following the current one.
+    pub next: Option<OperatorEpochItem>,
+    /// Operator candidates at the latest slot.
+    pub candidates: Vec<String>,
+    /// Epochs within the range, most recent first.
+    pub history: Vec<OperatorEpochItem>,
+}
+
+/// Operating costs and transactions of the L2 blocks within a wallclock aligned bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct CostPerTxBucketItem {
+    /// Start of the bucket.
+    pub bucket_start: DateTime<Utc>,
+    /// Number of sequencers that produced blocks within the bucket.
+    pub sequencers: u64,
+    /// Number of transactions.
+    pub tx_count: u64,
+    /// L1 data posting cost in gwei.
+    pub l1_data_cost: u128,
+    /// Proving cost in gwei.
+    pub prove_cost: u128,
+    /// Estimated hardware cost of the active sequencers in gwei.
+    pub hardware_cost: u128,
+    /// Sum of the L1 data, proving and hardware costs in gwei.
+    pub total_cost: u128,
+    /// Total cost per transaction in gwei, unset without transactions.
+    pub cost_per_tx: Option<f64>,
+}
+
+/// Operating costs and transactions of the L2 blocks of a sequencer within a range.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct SequencerCostPerTxItem {
+    /// Sequencer address.
+    pub address: String,
+    /// Known label of the sequencer.
+    pub label: Option<String>,
+    /// Number of transactions.
+    pub tx_count: u64,
+    /// L1 data posting cost in gwei.
+    pub l1_data_cost: u128,
+    /// Proving cost in gwei.
+    pub prove_cost: u128,
+    /// Estimated hardware cost over the buckets the sequencer was active in, in gwei.
+    pub hardware_cost: u128,
+    /// Sum of the L1 data, proving and hardware costs in gwei.
+    pub total_cost: u128,
+    /// Total cost per transaction in gwei, unset without transactions.
+    pub cost_per_tx: Option<f64>,
+}
+
+/// Operating cost per L2 transaction over time and per sequencer.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
+pub struct CostPerTxResponse {
+    /// Bucket size in seconds.
+    pub bucket_secs: u64,
+    /// Monthly hardware cost of one sequencer in USD used for the estimate.
+    pub hardware_cost_usd: f64,
+    /// ETH price in USD used to convert the hardware cost, unset when unavailable, in which
+    /// case hardware costs are reported as zero.
+    pub eth_price: Option<f64>,
+    /// Number of transactions within the range.
+    pub tx_count: u64,
+    /// Total operating cost within the range in gwei.
+    pub total_cost: u128,
+    /// Total cost per transaction in gwei, unset without transactions.
+    pub cost_per_tx: Option<f64>,
+    /// Total cost per transaction in USD, unset without transactions or ETH price.
+    pub cost_per_tx_usd: Option<f64>,
+    /// Costs per bucket, oldest first.
+    pub buckets: Vec<CostPerTxBucketItem>,
+    /// Costs per sequencer, highest total cost first.
+    pub sequencers: Vec<SequencerCostPerTxItem>,
+}
+
+/// Incident operation performed by a monitor against Instatus.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IncidentLogItem {
+    /// Unix timestamp of the operation in milliseconds.
+    pub logged_at_ms: u64,
+    /// Operation performed ("create", "update" or "resolve").
+    pub action: String,
+    /// Instatus incident ID, empty when the creation failed.
+    pub incident_id: String,
+    /// Components affected by the incident.
+    pub component_ids: Vec<String>,
+    /// Incident name, empty for updates and resolutions.
+    pub incident_name: String,
+    /// Incident status sent to Instatus.
+    pub status: String,
+    /// JSON request payload sent to Instatus.
+    pub payload: String,
+    /// HTTP status code returned by Instatus, 0 when no response was received.
+    pub response_code: u16,
+    /// Error of a failed operation.
+    pub error: Option<String>,
+    /// Whether reporting was disabled and the operation was only logged.
+    pub dry_run: bool,
+}
+
+/// Incident history response.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IncidentHistoryResponse {
+    /// Incident operations, oldest first.
+    pub operations: Vec<IncidentLogItem>,
+}
+
 /// Combined L2 fees and batch components response.
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct L2FeesComponentsResponse {
     /// Sum of priority fees for the range.
     pub priority_fee: Option<u128>,
     /// Sum of base fees for the range.
     pub base_fee: Option<u128>,
+    /// Percentage of the base fee shared with the sequencer, from the protocol configuration.
+    pub base_fee_sharing_pctg: u8,
+    /// Part of the base fees paid to the sequencers for the range.
+    pub shared_base_fee: u128,
     /// Total L1 data posting cost for the range.
     pub l1_data_cost: u128,
     /// Total proving cost for the range.
     pub prove_cost: u128,
+    /// Priority fees plus the shared base fees, minus the L1 data and proving costs.
+    pub net_revenue: i128,
     /// Fee breakdown for each sequencer.
     pub sequencers: Vec<SequencerFeeRow>,
     /// Detailed fee components per batch.
     pub batches: Vec<BatchFeeComponentRow>,
+    /// Fees over wallclock aligned buckets, only returned with `bucket_by=time`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_buckets: Option<Vec<FeeTimeBucketItem>>,
+}
+
+/// L2 fees collected within a wallclock aligned bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct FeeTimeBucketItem {
+    /// Start of the bucket.
+    pub bucket_start: DateTime<Utc>,
+    /// Last L2 block number in the bucket.
+    pub last_block: u64,
+    /// Sum of priority fees in gwei.
+    pub priority_fee: u128,
+    /// Sum of base fees in gwei.
+    pub base_fee: u128,
+    /// Part of the base fees paid to the sequencers in gwei.
+    pub shared_base_fee: u128,
 }
@@ -0,0 +1,111 @@
+//! Per-request `ClickHouse` query budget
+//!
+//! Handlers that fan out into many sub-queries, such as `/dashboard-data`, draw every sub-query
+//! from a [`QueryBudgetTracker`] created for the request. Once the number of sub-queries or the
+//! time spent on them exceeds the configured [`QueryBudget`], the remaining sub-queries are
+//! aborted and the handler returns the partial results it already has.
+
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration as StdDuration, Instant},
+};
+
+/// Limits on the sub-queries of a single request. A zero limit disables it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueryBudget {
+    /// Maximum number of sub-queries issued per request
+    pub max_queries: usize,
+    /// Maximum time spent on the sub-queries of a request, measured from the first one
+    pub max_time: StdDuration,
+}
+
+/// Query budget of a single request.
+#[derive(Debug)]
+pub struct QueryBudgetTracker {
+    budget: QueryBudget,
+    started: Instant,
+    issued: AtomicUsize,
+    exhausted: AtomicBool,
+}
+
+impl QueryBudgetTracker {
+    /// Start tracking `budget` for a new request.
+    pub fn new(budget: QueryBudget) -> Self {
+        Self {
+            budget,
+            started: Instant::now(),
+            issued: AtomicUsize::new(0),
+            exhausted: AtomicBool::new(false),
+        }
+    }
+
+    /// Reserve one sub-query. Returns the time the sub-query may take before the budget runs
+    /// out, [`StdDuration::MAX`] without a time limit, or `None` when the budget is exhausted.
+    pub fn acquire(&self) -> Option<StdDuration> {
+        let issued = self.issued.fetch_add(1, Ordering::Relaxed);
+        if self.budget.max_queries > 0 && issued >= self.budget.max_queries {
+            self.exhaust();
+            return None;
+        }
+        if self.budget.max_time.is_zero() {
+            return Some(StdDuration::MAX);
+        }
+        match self.budget.max_time.checked_sub(self.started.elapsed()) {
+            Some(left) if !left.is_zero() => Some(left),
+            _ => {
+                self.exhaust();
+                None
+            }
+        }
+    }
+
+    /// Mark the budget as exhausted, e.g. because a sub-query ran out of time.
+    pub fn exhaust(&self) {
+        self.exhausted.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether any sub-query was aborted because the budget ran out.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_beyond_the_limit_are_rejected() {
+        let tracker = QueryBudgetTracker::new(QueryBudget { max_queries: 2, ..Default::default() });
+        assert_eq!(tracker.acquire(), Some(StdDuration::MAX));
+        assert_eq!(tracker.acquire(), Some(StdDuration::MAX));
+        assert!(!tracker.is_exhausted());
+        assert_eq!(tracker.acquire(), None);
+        assert!(tracker.is_exhausted());
+    }
+
+    #[test]
+    fn time_limit_bounds_each_query() {
+        let budget = QueryBudget { max_queries: 0, max_time: StdDuration::from_secs(60) };
+        let tracker = QueryBudgetTracker::new(budget);
+        let left = tracker.acquire().unwrap();
+        assert!(left <= budget.max_time && left > StdDuration::from_secs(59));
+
+        let tracker = QueryBudgetTracker::new(QueryBudget {
+            max_queries: 0,
+            max_time: StdDuration::from_nanos(1),
+        });
+        std::thread::sleep(StdDuration::from_millis(1));
+        assert_eq!(tracker.acquire(), None);
+        assert!(tracker.is_exhausted());
+    }
+
+    #[test]
+    fn default_budget_is_unlimited() {
+        let tracker = QueryBudgetTracker::new(QueryBudget::default());
+        for _ in 0..100 {
+            assert_eq!(tracker.acquire(), Some(StdDuration::MAX));
+        }
+        assert!(!tracker.is_exhausted());
+    }
+}
@@ -0,0 +1,412 @@
+//! In-memory TTL cache for JSON responses
+
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration as StdDuration, Instant},
+};
+
+use axum::{
+    body::{Body, Bytes, HttpBody, to_bytes},
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tokio::sync::RwLock;
+
+use crate::state::ApiState;
+
+/// Maximum number of cached responses before expired entries are purged.
+const MAX_CACHE_ENTRIES: usize = 10_000;
+/// Maximum total size of the cached bodies before expired entries are purged.
+const MAX_CACHE_BYTES: usize = 256 * 1024 * 1024;
+/// Bodies larger than this are served but not cached.
+const MAX_CACHED_BODY_BYTES: usize = 16 * 1024 * 1024;
+/// Header reporting whether a response was served from the cache.
+pub const CACHE_STATUS_HEADER: &str = "x-cache";
+
+/// Route groups that share a cache TTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheGroup {
+    /// Expensive aggregate endpoints backing the dashboard
+    Dashboard,
+    /// Paginated table endpoints
+    Table,
+}
+
+impl CacheGroup {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dashboard => "dashboard",
+            Self::Table => "table",
+        }
+    }
+}
+
+/// Cache TTLs per route group. A zero TTL disables caching for that group.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheTtls {
+    /// TTL for [`CacheGroup::Dashboard`] routes
+    pub dashboard: StdDuration,
+    /// TTL for [`CacheGroup::Table`] routes
+    pub table: StdDuration,
+}
+
+impl CacheTtls {
+    const fn for_group(&self, group: CacheGroup) -> StdDuration {
+        match group {
+            CacheGroup::Dashboard => self.dashboard,
+            CacheGroup::Table => self.table,
+        }
+    }
+}
+
+/// Request extension marking a request issued by the cache pre-warmer.
+///
+/// Such requests skip the lookup and refresh the entry, which is then counted as warm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prewarm;
+
+/// Hit and miss counters for a route group.
+#[derive(Debug, Default)]
+struct GroupStats {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    bypasses: AtomicU64,
+    warm_hits: AtomicU64,
+    prewarms: AtomicU64,
+}
+
+/// Snapshot of the counters of a route group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheGroupStats {
+    /// Responses served from the cache
+    pub hits: u64,
+    /// Responses computed because no fresh entry existed
+    pub misses: u64,
+    /// Responses computed because the client sent `Cache-Control: no-cache`
+    pub bypasses: u64,
+    /// Hits served from an entry refreshed by the pre-warmer
+    pub warm_hits: u64,
+    /// Entries refreshed by the pre-warmer
+    pub prewarms: u64,
+}
+
+impl CacheGroupStats {
+    /// Share of the lookups served from a pre-warmed entry, `None` before the first lookup.
+    pub fn warm_hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.warm_hits as f64 / lookups as f64)
+    }
+}
+
+#[derive(Debug)]
+struct CachedResponse {
+    body: Bytes,
+    expires_at: Instant,
+    warm: bool,
+}
+
+/// Cached responses and the total size of their bodies.
+#[derive(Debug, Default)]
+struct CacheEntries {
+    map: HashMap<String, CachedResponse>,
+    bytes: usize,
+}
+
+impl CacheEntries {
+    fn retain(&mut self, mut keep: impl FnMut(&str, &CachedResponse) -> bool) {
+        self.map.retain(|key, entry| keep(key, entry));
+        self.bytes = self.map.values().map(|e| e.body.len()).sum();
+    }
+
+    fn is_full(&self, incoming: usize) -> bool {
+        self.map.len() >= MAX_CACHE_ENTRIES || self.bytes + incoming > MAX_CACHE_BYTES
+    }
+}
+
+/// TTL cache of successful JSON responses, keyed by request path and query.
+#[derive(Debug, Default)]
+pub struct ResponseCache {
+    ttls: CacheTtls,
+    entries: RwLock<CacheEntries>,
+    dashboard: GroupStats,
+    table: GroupStats,
+}
+
+impl ResponseCache {
+    /// Create a cache with the given TTLs.
+    pub fn new(ttls: CacheTtls) -> Self {
+        Self { ttls, ..Default::default() }
+    }
+
+    /// Counters for `group`.
+    pub fn stats(&self, group: CacheGroup) -> CacheGroupStats {
+        let stats = self.group_stats(group);
+        CacheGroupStats {
+            hits: stats.hits.load(Ordering::Relaxed),
+            misses: stats.misses.load(Ordering::Relaxed),
+            bypasses: stats.bypasses.load(Ordering::Relaxed),
+            warm_hits: stats.warm_hits.load(Ordering::Relaxed),
+            prewarms: stats.prewarms.load(Ordering::Relaxed),
+        }
+    }
+
+    const fn group_stats(&self, group: CacheGroup) -> &GroupStats {
+        match group {
+            CacheGroup::Dashboard => &self.dashboard,
+            CacheGroup::Table => &self.table,
+        }
+    }
+
+    /// Drop every cached response whose key starts with `prefix` (all of them when `None`).
+    ///
+    /// Returns the number of entries removed.
+    pub async fn invalidate(&self, prefix: Option<&str>) -> usize {
+        let mut entries = self.entries.write().await;
+        let before = entries.map.len();
+        match prefix {
+            Some(prefix) => entries.retain(|key, _| !key.starts_with(prefix)),
+            None => *entries = CacheEntries::default(),
+        }
+        before - entries.map.len()
+    }
+
+    /// Fresh body cached under `key` and whether the pre-warmer stored it.
+    async fn get(&self, key: &str) -> Option<(Bytes, bool)> {
+        let entries = self.entries.read().await;
+        entries
+            .map
+            .get(key)
+            .filter(|e| e.expires_at > Instant::now())
+            .map(|e| (e.body.clone(), e.warm))
+    }
+
+    async fn insert(&self, key: String, body: Bytes, ttl: StdDuration, warm: bool) {
+        if body.len() > MAX_CACHED_BODY_BYTES {
+            tracing::debug!(key, bytes = body.len(), "Response too large to cache");
+            return;
+        }
+        let now = Instant::now();
+        let mut entries = self.entries.write().await;
+        if let Some(old) = entries.map.remove(&key) {
+            entries.bytes -= old.body.len();
+        }
+        if entries.is_full(body.len()) {
+            entries.retain(|_, e| e.expires_at > now);
+            if entries.is_full(body.len()) {
+                *entries = CacheEntries::default();
+            }
+        }
+        entries.bytes += body.len();
+        entries.map.insert(key, CachedResponse { body, expires_at: now + ttl, warm });
+    }
+}
+
+/// Middleware serving cached responses for `group` and caching successful ones.
+///
+/// Requests carrying `Cache-Control: no-cache` or the [`Prewarm`] extension skip the lookup but
+/// still refresh the entry. Responses marked `Cache-Control: no-store`, such as partial dashboard
+/// data, and streamed responses, whose length is only known once sent, are passed through without
+/// being buffered or stored.
+pub async fn cache_response(
+    State((state, group)): State<(ApiState, CacheGroup)>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let cache = state.response_cache();
+    let ttl = cache.ttls.for_group(group);
+    if ttl.is_zero() {
+        return next.run(req).await;
+    }
+
+    let key = req.uri().path_and_query().map_or_else(|| req.uri().to_string(), |pq| pq.to_string());
+    let bypass = has_cache_directive(req.headers(), "no-cache");
+    let prewarm = req.extensions().get::<Prewarm>().is_some();
+    let stats = cache.group_stats(group);
+
+    if prewarm {
+        stats.prewarms.fetch_add(1, Ordering::Relaxed);
+    } else if bypass {
+        stats.bypasses.fetch_add(1, Ordering::Relaxed);
+    } else if let Some((body, warm)) = cache.get(&key).await {
+        stats.hits.fetch_add(1, Ordering::Relaxed);
+        if warm {
+            stats.warm_hits.fetch_add(1, Ordering::Relaxed);
+        }
+        tracing::debug!(group = group.as_str(), key, "Serving cached response");
+        return json_response(body, "HIT");
+    } else {
+        stats.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let response = next.run(req).await;
+    let status = if bypass || prewarm { "BYPASS" } else { "MISS" };
+    store_response(cache, key, response, ttl, prewarm, status).await
+}
+
+/// Buffer a successful response, store it under `key` and tag it with the cache `status`.
+async fn store_response(
+    cache: &ResponseCache,
+    key: String,
+    mut response: Response,
+    ttl: StdDuration,
+    warm: bool,
+    status: &'static str,
+) -> Response {
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+    // No-store responses and streamed bodies, e.g. CSV exports, are passed through unbuffered
+    if has_cache_directive(response.headers(), "no-store") ||
+        response.body().size_hint().exact().is_none()
+    {
+        response.headers_mut().insert(CACHE_STATUS_HEADER, HeaderValue::from_static("BYPASS"));
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to buffer response for caching");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    cache.insert(key, body.clone(), ttl, warm).await;
+
+    let mut response = Response::from_parts(parts, Body::from(body));
+    response.headers_mut().insert(CACHE_STATUS_HEADER, HeaderValue::from_static(status));
+    response
+}
+
+/// Whether the `Cache-Control` header lists `directive`
+fn has_cache_directive(headers: &HeaderMap, directive: &str) -> bool {
+    headers
+        .get(header::CACHE_CONTROL)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|d| d.trim().eq_ignore_ascii_case(directive)))
+}
+
+fn json_response(body: Bytes, status: &'static str) -> Response {
+    let mut response = Response::new(Body::from(body));
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(CACHE_STATUS_HEADER, HeaderValue::from_static(status));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, middleware, routing::get};
+    use clickhouse_lib::ClickhouseReader;
+    use futures::stream;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn entries_expire_after_ttl() {
+        let cache = ResponseCache::new(CacheTtls::default());
+        cache
+            .insert("/a".to_owned(), Bytes::from_static(b"{}"), StdDuration::from_secs(60), false)
+            .await;
+        cache.insert("/b".to_owned(), Bytes::from_static(b"{}"), StdDuration::ZERO, false).await;
+
+        assert_eq!(cache.get("/a").await, Some((Bytes::from_static(b"{}"), false)));
+        assert_eq!(cache.get("/b").await, None);
+        assert_eq!(cache.get("/c").await, None);
+    }
+
+    #[tokio::test]
+    async fn purges_when_byte_cap_is_reached() {
+        let cache = ResponseCache::new(CacheTtls::default());
+        let ttl = StdDuration::from_secs(60);
+        let body = Bytes::from(vec![b'0'; MAX_CACHED_BODY_BYTES]);
+        for i in 0..MAX_CACHE_BYTES / MAX_CACHED_BODY_BYTES {
+            cache.insert(format!("/{i}"), body.clone(), ttl, false).await;
+        }
+        cache.insert("/0".to_owned(), body.clone(), ttl, false).await;
+        assert_eq!(cache.entries.read().await.bytes, MAX_CACHE_BYTES);
+
+        cache.insert("/next".to_owned(), Bytes::from_static(b"{}"), ttl, false).await;
+        let entries = cache.entries.read().await;
+        assert_eq!(entries.map.len(), 1);
+        assert_eq!(entries.bytes, 2);
+    }
+
+    #[tokio::test]
+    async fn skips_oversized_bodies() {
+        let cache = ResponseCache::new(CacheTtls::default());
+        let body = Bytes::from(vec![b'0'; MAX_CACHED_BODY_BYTES + 1]);
+        cache.insert("/a".to_owned(), body, StdDuration::from_secs(60), false).await;
+        assert_eq!(cache.get("/a").await, None);
+    }
+
+    #[test]
+    fn warm_hit_rate_counts_lookups() {
+        let stats = CacheGroupStats { hits: 6, misses: 2, bypasses: 5, warm_hits: 4, prewarms: 3 };
+        assert_eq!(stats.warm_hit_rate(), Some(0.5));
+        assert_eq!(CacheGroupStats { hits: 0, misses: 0, ..stats }.warm_hit_rate(), None);
+    }
+
+    #[tokio::test]
+    async fn streamed_responses_bypass_the_cache() {
+        let url = "http://localhost:8123".parse().unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let ttls = CacheTtls { dashboard: StdDuration::ZERO, table: StdDuration::from_secs(60) };
+        let state = ApiState::new(client, 100, StdDuration::from_secs(60)).with_cache_ttls(ttls);
+        let app = Router::new()
+            .route("/buffered", get(|| async { "{}" }))
+            .route(
+                "/streamed",
+                get(|| async {
+                    let chunks = [Ok::<_, std::io::Error>(Bytes::from_static(b"{}"))];
+                    Body::from_stream(stream::iter(chunks))
+                }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                (state.clone(), CacheGroup::Table),
+                cache_response,
+            ));
+
+        for (path, statuses) in
+            [("/buffered", ["MISS", "HIT"]), ("/streamed", ["BYPASS", "BYPASS"])]
+        {
+            for status in statuses {
+                let request = axum::http::Request::get(path).body(Body::empty()).unwrap();
+                let response = app.clone().oneshot(request).await.unwrap();
+                assert_eq!(response.headers()[CACHE_STATUS_HEADER], status);
+            }
+        }
+        assert!(state.response_cache().get("/streamed").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn degraded_responses_are_not_stored() {
+        let url = "http://localhost:8123".parse().unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let ttls = CacheTtls { dashboard: StdDuration::from_secs(60), table: StdDuration::ZERO };
+        let state = ApiState::new(client, 100, StdDuration::from_secs(60)).with_cache_ttls(ttls);
+        let degraded = r#"{"errors":[{"section":"avg_tps","error":"timed out"}]}"#;
+        let app = Router::new()
+            .route(
+                "/dashboard-data",
+                get(move || async move { ([(header::CACHE_CONTROL, "no-store")], degraded) }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                (state.clone(), CacheGroup::Dashboard),
+                cache_response,
+            ));
+
+        for _ in 0..2 {
+            let request = axum::http::Request::get("/dashboard-data").body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.headers()[CACHE_STATUS_HEADER], "BYPASS");
+        }
+        assert!(state.response_cache().get("/dashboard-data").await.is_none());
+    }
+}
@@ -0,0 +1,156 @@
+//! Request extractors whose rejections are returned as `application/problem+json`
+//!
+//! axum's own extractors reject malformed requests with a plain text body. These wrappers
+//! convert the rejection into an [`ErrorResponse`] so that every error of the API shares the
+//! same format.
+
+use crate::{ErrorCode, ErrorResponse};
+use axum::{
+    async_trait,
+    extract::{
+        FromRequestParts,
+        rejection::{PathRejection, QueryRejection},
+    },
+    http::{StatusCode, request::Parts},
+};
+use serde::de::DeserializeOwned;
+
+/// Query string extractor, see [`axum::extract::Query`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Query<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        axum::extract::Query::from_request_parts(parts, state)
+            .await
+            .map(|axum::extract::Query(value)| Self(value))
+            .map_err(query_rejection)
+    }
+}
+
+/// Path parameter extractor, see [`axum::extract::Path`]
+#[derive(Debug, Clone, Copy)]
+pub struct Path<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for Path<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = ErrorResponse;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        axum::extract::Path::from_request_parts(parts, state)
+            .await
+            .map(|axum::extract::Path(value)| Self(value))
+            .map_err(path_rejection)
+    }
+}
+
+/// Error response of a query string that cannot be deserialized
+fn query_rejection(rejection: QueryRejection) -> ErrorResponse {
+    ErrorResponse::bad_request(ErrorCode::InvalidParams, rejection.body_text())
+}
+
+/// Error response of path parameters that cannot be deserialized. Failures caused by the
+/// router rather than the request are internal errors.
+fn path_rejection(rejection: PathRejection) -> ErrorResponse {
+    match rejection {
+        PathRejection::FailedToDeserializePathParams(e) => {
+            ErrorResponse::bad_request(ErrorCode::InvalidParams, e.body_text())
+        }
+        other => ErrorResponse::new(
+            ErrorCode::InternalError,
+            "Internal Server Error",
+            StatusCode::INTERNAL_SERVER_ERROR,
+            other.body_text(),
+        ),
+    }
+}
+
+/// Fallback of unknown routes
+pub async fn not_found() -> ErrorResponse {
+    ErrorResponse::not_found("no such endpoint")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router,
+        body::{self, Body},
+        http::{Request, header},
+        response::IntoResponse,
+        routing::get,
+    };
+    use serde::Deserialize;
+    use tower::ServiceExt;
+
+    #[derive(Debug, Deserialize)]
+    struct LimitQuery {
+        limit: u64,
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/items", get(|Query(q): Query<LimitQuery>| async move { q.limit.to_string() }))
+            .route("/items/:id", get(|Path(id): Path<u64>| async move { id.to_string() }))
+            .fallback(not_found)
+    }
+
+    async fn problem(uri: &str) -> (StatusCode, ErrorResponse) {
+        let response =
+            app().oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+        let status = response.status();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], api_types::PROBLEM_JSON);
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn malformed_query_is_a_problem() {
+        let (status, error) = problem("/items?limit=many").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.r#type, ErrorCode::InvalidParams);
+        assert!(error.detail.starts_with("Failed to deserialize query string"));
+    }
+
+    #[tokio::test]
+    async fn malformed_path_is_a_problem() {
+        let (status, error) = problem("/items/abc").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(error.r#type, ErrorCode::InvalidParams);
+    }
+
+    #[tokio::test]
+    async fn unknown_route_is_not_found() {
+        let (status, error) = problem("/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(error.r#type, ErrorCode::NotFound);
+    }
+
+    #[test]
+    fn error_code_serializes_in_snake_case() {
+        let error = ErrorResponse::bad_request(ErrorCode::LimitExceeded, "too many");
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["type"], "limit_exceeded");
+        assert_eq!(json["status"], 400);
+        let parsed: ErrorResponse = serde_json::from_value(serde_json::json!({
+            "type": "brand_new", "title": "", "status": 500, "detail": ""
+        }))
+        .unwrap();
+        assert_eq!(parsed.r#type, ErrorCode::Unknown);
+        assert_eq!(
+            ErrorResponse::not_found("x").into_response().headers()[header::CONTENT_TYPE],
+            api_types::PROBLEM_JSON
+        );
+    }
+}
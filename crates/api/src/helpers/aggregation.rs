@@ -1,8 +1,23 @@
 //! Data aggregation utilities
 
-use api_types::{AvgBatchBlobCountRow, BatchFeeComponentRow};
-use clickhouse_lib::{BatchBlobCountRow, L2BlockTimeRow, L2TpsRow, TimeRange};
-use std::collections::BTreeMap;
+use super::common::{format_address, format_address_bytes, wei_to_gwei};
+use alloy_primitives::hex;
+use api_types::{
+    AvgBatchBlobCountRow, BatchFeeComponentRow, BuilderDistributionItem, CostPerTxBucketItem,
+    CostPerTxResponse, HandoverItem, HandoverLatencyResponse, OperatorEpochItem,
+    OperatorScheduleResponse, ProposalInclusionDelayResponse, ProposalInclusionItem,
+    SequencerCostPerTxItem,
+};
+use chrono::{TimeZone, Utc};
+use clickhouse_lib::{
+    AddressBytes, BatchBlobCountRow, ExtraDataCountRow, L2BlockTimeRow, L2TpsRow, OperatorEpochRow,
+    PreconfData, ProposalInclusionRow, SequencerCostBucketRow, SequencerHandoverRow, TimeRange,
+};
+use primitives::{
+    beacon::{epoch_of, epoch_start_slot, slot_start_ts},
+    builder::BuilderFingerprints,
+};
+use std::collections::{BTreeMap, HashMap};
 
 /// Determine bucket size based on time range
 pub const fn bucket_size_from_range(range: &TimeRange) -> u64 {
@@ -24,6 +39,12 @@ pub const fn bucket_size_from_range(range: &TimeRange) -> u64 {
     }
 }
 
+/// Determine the width in seconds of wallclock aligned buckets: one minute up to six hours,
+/// one hour beyond.
+pub const fn time_bucket_secs_from_range(range: &TimeRange) -> u64 {
+    if range.seconds() <= 6 * 3600 { 60 } else { 3600 }
+}
+
 /// Determine bucket size for prove time aggregation. Uses a smaller
 /// bucket than [`bucket_size_from_range`] to avoid over-aggregation
 /// when prove events are infrequent.
@@ -87,7 +108,7 @@ pub fn aggregate_l2_block_times(rows: Vec<L2BlockTimeRow>, bucket: u64) -> Vec<L
             sum += r.s_since_prev_block;
         }
 
-        let avg = if count > 0 { sum / count } else { 0 };
+        let avg = sum.checked_div(count).unwrap_or(0);
         result.push(L2BlockTimeRow {
             l2_block_number: g * bucket,
             block_time: last_time,
@@ -125,8 +146,12 @@ pub fn aggregate_batch_fee_components(
     for (g, rs) in groups {
         let mut sum_priority = 0u128;
         let mut sum_base = 0u128;
+        let mut sum_shared_base = 0u128;
+        let mut sum_net_revenue = 0i128;
         let mut sum_l1 = 0u128;
         let mut sum_prove = 0u128;
+        let mut sum_gas_price = 0u128;
+        let mut priced = 0u128;
         let mut any_l1 = false;
         let mut any_prove = false;
         let mut last_l1 = 0u64;
@@ -138,6 +163,13 @@ pub fn aggregate_batch_fee_components(
         for r in &rs {
             sum_priority += r.priority_fee;
             sum_base += r.base_fee;
+            sum_shared_base += r.shared_base_fee;
+            sum_net_revenue += r.net_revenue;
+
+            if r.avg_gas_price > 0 {
+                sum_gas_price += r.avg_gas_price;
+                priced += 1;
+            }
 
             if let Some(l1_cost) = r.l1_data_cost {
                 sum_l1 += l1_cost;
@@ -170,8 +202,11 @@ pub fn aggregate_batch_fee_components(
             sequencer: last_seq,
             priority_fee: sum_priority,
             base_fee: sum_base,
+            shared_base_fee: sum_shared_base,
             l1_data_cost: any_l1.then_some(sum_l1),
             prove_cost: any_prove.then_some(sum_prove),
+            net_revenue: sum_net_revenue,
+            avg_gas_price: sum_gas_price.checked_div(priced).unwrap_or(0),
         });
     }
 
@@ -208,7 +243,7 @@ pub fn aggregate_l2_tps(rows: Vec<L2TpsRow>, bucket: u64) -> Vec<L2TpsRow> {
         }
 
         let avg = if count > 0.0 { sum / count } else { 0.0 };
-        result.push(L2TpsRow { l2_block_number: g * bucket, tps: avg });
+        result.push(L2TpsRow { l2_block_number: g * bucket, tps: avg, block_time: None });
     }
 
     result
@@ -264,6 +299,276 @@ pub fn aggregate_blobs_per_batch(
     result
 }
 
+/// Group block counts per extra data by the builder `fingerprints` identify, largest first
+pub fn aggregate_builder_distribution(
+    rows: Vec<ExtraDataCountRow>,
+    fingerprints: &BuilderFingerprints,
+) -> Vec<BuilderDistributionItem> {
+    let total: u64 = rows.iter().map(|r| r.blocks).sum();
+    let mut blocks: HashMap<String, u64> = HashMap::new();
+    for row in rows {
+        let extra_data = hex::decode(&row.extra_data).unwrap_or_default();
+        *blocks.entry(fingerprints.identify(&extra_data)).or_default() += row.blocks;
+    }
+
+    let mut builders: Vec<BuilderDistributionItem> = blocks
+        .into_iter()
+        .map(|(builder, blocks)| BuilderDistributionItem {
+            builder,
+            blocks,
+            share: blocks as f64 / total as f64,
+        })
+        .collect();
+    builders.sort_by(|a, b| b.blocks.cmp(&a.blocks).then_with(|| a.builder.cmp(&b.builder)));
+    builders
+}
+
+/// Summarize the latency of the sequencer handovers in `rows`, oldest first
+pub fn summarize_handovers(rows: Vec<SequencerHandoverRow>) -> HandoverLatencyResponse {
+    let mut latencies: Vec<u64> = rows.iter().map(|r| r.latency_secs).collect();
+    latencies.sort_unstable();
+    let percentile = |pct: usize| {
+        (!latencies.is_empty()).then(|| {
+            let rank = (latencies.len() * pct).div_ceil(100).max(1);
+            latencies[rank - 1]
+        })
+    };
+
+    let handovers: Vec<HandoverItem> = rows
+        .into_iter()
+        .filter_map(|r| {
+            Some(HandoverItem {
+                outgoing: format_address(r.outgoing),
+                incoming: format_address(r.incoming),
+                last_block_number: r.last_block_number,
+                first_block_number: r.first_block_number,
+                handover_time: Utc.timestamp_opt(r.first_block_ts as i64, 0).single()?,
+                latency_secs: r.latency_secs,
+            })
+        })
+        .collect();
+
+    HandoverLatencyResponse {
+        handovers,
+        p50_secs: percentile(50),
+        p90_secs: percentile(90),
+        p99_secs: percentile(99),
+        max_secs: latencies.last().copied(),
+    }
+}
+
+/// Summarize the mempool timing of the proposals in `rows`, oldest first
+pub fn summarize_proposal_inclusions(
+    rows: Vec<ProposalInclusionRow>,
+) -> ProposalInclusionDelayResponse {
+    let mut delays: Vec<u64> = rows.iter().filter_map(|r| r.inclusion_delay_ms).collect();
+    delays.sort_unstable();
+    let dropped = rows.iter().filter(|r| r.dropped == 1).count();
+    let included = delays.len();
+
+    let avg_delay_ms = (!delays.is_empty()).then(|| delays.iter().sum::<u64>() / included as u64);
+    let p95_delay_ms = (!delays.is_empty()).then(|| {
+        let rank = (included * 95).div_ceil(100).max(1);
+        delays[rank - 1]
+    });
+
+    let proposals: Vec<ProposalInclusionItem> = rows
+        .into_iter()
+        .map(|r| ProposalInclusionItem {
+            proposer: format_address(r.proposer),
+            nonce: r.nonce,
+            tx_hash: format_address_bytes(r.tx_hash.as_bytes()),
+            first_seen_ms: r.first_seen_ms,
+            max_fee_per_gas: r.max_fee_per_gas,
+            max_priority_fee_per_gas: r.max_priority_fee_per_gas,
+            replacements: r.replacements,
+            batch_id: r.batch_id,
+            l1_block_number: r.l1_block_number,
+            inclusion_delay_ms: r.inclusion_delay_ms,
+            dropped: r.dropped == 1,
+        })
+        .collect();
+
+    ProposalInclusionDelayResponse {
+        pending: proposals.len() - included - dropped,
+        proposals,
+        included,
+        dropped,
+        avg_delay_ms,
+        p95_delay_ms,
+        max_delay_ms: delays.last().copied(),
+    }
+}
+
+/// Epoch `epoch` operated by `operator`, with the L1 blocks seen within it if any
+fn operator_epoch(
+    epoch: u64,
+    operator: Option<AddressBytes>,
+    blocks: Option<(u64, u64)>,
+    labels: &HashMap<AddressBytes, String>,
+) -> OperatorEpochItem {
+    let start_slot = epoch_start_slot(epoch);
+    OperatorEpochItem {
+        epoch,
+        start_slot,
+        start_ts: slot_start_ts(start_slot),
+        end_ts: slot_start_ts(epoch_start_slot(epoch + 1)),
+        first_l1_block: blocks.map(|(first, _)| first),
+        last_l1_block: blocks.map(|(_, last)| last),
+        operator: operator.map(format_address),
+        label: operator.and_then(|operator| labels.get(&operator).cloned()),
+    }
+}
+
+/// Combine the latest preconfirmation data and the operators of past epochs into the schedule
+/// of the current and next epoch and the history of the range
+pub fn operator_schedule_response(
+    latest: Option<PreconfData>,
+    rows: Vec<OperatorEpochRow>,
+    labels: &HashMap<AddressBytes, String>,
+) -> OperatorScheduleResponse {
+    let blocks_of = |epoch: u64| {
+        rows.iter().find(|r| r.epoch == epoch).map(|r| (r.first_l1_block, r.last_l1_block))
+    };
+    let (current, next) = latest.as_ref().map_or((None, None), |data| {
+        let epoch = epoch_of(data.slot);
+        (
+            Some(operator_epoch(epoch, data.current_operator, blocks_of(epoch), labels)),
+            Some(operator_epoch(epoch + 1, data.next_operator, blocks_of(epoch + 1), labels)),
+        )
+    });
+
+    let history = rows
+        .iter()
+        .map(|r| {
+            operator_epoch(
+                r.epoch,
+                Some(r.operator),
+                Some((r.first_l1_block, r.last_l1_block)),
+                labels,
+            )
+        })
+        .collect();
+
+    OperatorScheduleResponse {
+        slot: latest.as_ref().map(|data| data.slot),
+        current,
+        next,
+        candidates: latest
+            .map(|data| data.candidates.into_iter().map(format_address).collect())
+            .unwrap_or_default(),
+        history,
+    }
+}
+
+/// Seconds in the 30-day month the hardware cost estimate is given for
+const SECONDS_PER_MONTH: f64 = 30.0 * 24.0 * 3600.0;
+
+/// Transactions and costs in wei summed over sequencer buckets
+#[derive(Debug, Default)]
+struct CostTotals {
+    buckets: u64,
+    tx_count: u64,
+    l1_data_cost: u128,
+    prove_cost: u128,
+}
+
+impl CostTotals {
+    const fn add(&mut self, row: &SequencerCostBucketRow) {
+        self.buckets += 1;
+        self.tx_count += row.tx_count;
+        self.l1_data_cost += row.l1_data_cost;
+        self.prove_cost += row.prove_cost;
+    }
+
+    /// L1 data, proving and hardware costs in gwei, given the hardware cost of one sequencer
+    /// over one bucket in gwei
+    fn gwei(&self, hardware_gwei_per_bucket: f64) -> (u128, u128, u128) {
+        let hardware = (self.buckets as f64 * hardware_gwei_per_bucket).round() as u128;
+        (wei_to_gwei(self.l1_data_cost), wei_to_gwei(self.prove_cost), hardware)
+    }
+}
+
+/// Total cost per transaction, unset without transactions
+fn per_tx(total_cost: u128, tx_count: u64) -> Option<f64> {
+    (tx_count > 0).then(|| total_cost as f64 / tx_count as f64)
+}
+
+/// Divide the L1 data, proving and estimated hardware costs of the sequencer buckets in `rows`
+/// by their transaction counts, per bucket and per sequencer. Every sequencer active in a
+/// bucket is charged `hardware_cost_usd` per month for the bucket, converted at `eth_price`.
+pub fn summarize_cost_per_tx(
+    rows: Vec<SequencerCostBucketRow>,
+    bucket_secs: u64,
+    hardware_cost_usd: f64,
+    eth_price: Option<f64>,
+    labels: &HashMap<AddressBytes, String>,
+) -> CostPerTxResponse {
+    let hardware_gwei_per_bucket = eth_price.filter(|p| *p > 0.0).map_or(0.0, |price| {
+        hardware_cost_usd / SECONDS_PER_MONTH * bucket_secs as f64 / price * 1e9
+    });
+
+    let mut by_bucket: BTreeMap<u64, CostTotals> = BTreeMap::new();
+    let mut by_sequencer: HashMap<AddressBytes, CostTotals> = HashMap::new();
+    for row in &rows {
+        by_bucket.entry(row.bucket_ts).or_default().add(row);
+        by_sequencer.entry(row.sequencer).or_default().add(row);
+    }
+
+    let buckets: Vec<CostPerTxBucketItem> = by_bucket
+        .into_iter()
+        .map(|(ts, totals)| {
+            let (l1_data_cost, prove_cost, hardware_cost) = totals.gwei(hardware_gwei_per_bucket);
+            let total_cost = l1_data_cost + prove_cost + hardware_cost;
+            CostPerTxBucketItem {
+                bucket_start: Utc.timestamp_opt(ts as i64, 0).single().unwrap_or_default(),
+                sequencers: totals.buckets,
+                tx_count: totals.tx_count,
+                l1_data_cost,
+                prove_cost,
+                hardware_cost,
+                total_cost,
+                cost_per_tx: per_tx(total_cost, totals.tx_count),
+            }
+        })
+        .collect();
+
+    let mut sequencers: Vec<SequencerCostPerTxItem> = by_sequencer
+        .into_iter()
+        .map(|(address, totals)| {
+            let (l1_data_cost, prove_cost, hardware_cost) = totals.gwei(hardware_gwei_per_bucket);
+            let total_cost = l1_data_cost + prove_cost + hardware_cost;
+            SequencerCostPerTxItem {
+                address: format_address(address),
+                label: labels.get(&address).cloned(),
+                tx_count: totals.tx_count,
+                l1_data_cost,
+                prove_cost,
+                hardware_cost,
+                total_cost,
+                cost_per_tx: per_tx(total_cost, totals.tx_count),
+            }
+        })
+        .collect();
+    sequencers
+        .sort_by(|a, b| b.total_cost.cmp(&a.total_cost).then_with(|| a.address.cmp(&b.address)));
+
+    let tx_count: u64 = buckets.iter().map(|b| b.tx_count).sum();
+    let total_cost: u128 = buckets.iter().map(|b| b.total_cost).sum();
+    let cost_per_tx = per_tx(total_cost, tx_count);
+    CostPerTxResponse {
+        bucket_secs,
+        hardware_cost_usd,
+        eth_price,
+        tx_count,
+        total_cost,
+        cost_per_tx,
+        cost_per_tx_usd: cost_per_tx.zip(eth_price).map(|(gwei, price)| gwei / 1e9 * price),
+        buckets,
+        sequencers,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,13 +605,17 @@ mod tests {
             sequencer: sequencer.to_owned(),
             priority_fee,
             base_fee,
+            shared_base_fee: base_fee * 3 / 4,
             l1_data_cost: l1_cost,
             prove_cost,
+            net_revenue: (priority_fee + base_fee * 3 / 4) as i128 -
+                (l1_cost.unwrap_or(0) + prove_cost.unwrap_or(0)) as i128,
+            avg_gas_price: 0,
         }
     }
 
     fn create_l2_tps_row(block_num: u64, tps: f64) -> L2TpsRow {
-        L2TpsRow { l2_block_number: block_num, tps }
+        L2TpsRow { l2_block_number: block_num, tps, block_time: None }
     }
 
     fn create_batch_blob_count_row(
@@ -372,6 +681,14 @@ mod tests {
         assert_eq!(bucket_size_from_range(&range), 1);
     }
 
+    #[test]
+    fn test_time_bucket_secs_from_range() {
+        assert_eq!(time_bucket_secs_from_range(&TimeRange::LastHour), 60);
+        assert_eq!(time_bucket_secs_from_range(&TimeRange::Custom(6 * 3600)), 60);
+        assert_eq!(time_bucket_secs_from_range(&TimeRange::Custom(6 * 3600 + 1)), 3600);
+        assert_eq!(time_bucket_secs_from_range(&TimeRange::Last7Days), 3600);
+    }
+
     #[test]
     fn test_prove_bucket_size_smaller() {
         let range = TimeRange::Custom(6 * 3600); // 6 hours
@@ -509,11 +826,27 @@ mod tests {
         assert_eq!(result[0].base_fee, 4500); // 2000 + 2500
         assert_eq!(result[0].l1_data_cost, Some(1100)); // 500 + 600
         assert_eq!(result[0].prove_cost, Some(700)); // 300 + 400
+        assert_eq!(result[0].shared_base_fee, 3375); // 1500 + 1875
+        assert_eq!(result[0].net_revenue, 4075); // 1700 + 2375
         assert_eq!(result[0].l1_block_number, 101); // Last value
         assert_eq!(result[0].l1_tx_hash, "0x1"); // Last value
         assert_eq!(result[0].sequencer, "seq2"); // Last value
     }
 
+    #[test]
+    fn test_aggregate_batch_fee_components_averages_known_gas_prices() {
+        let mut rows: Vec<_> = (0..3)
+            .map(|i| create_batch_fee_component_row(i, 100, "0x0".into(), "seq1", 0, 0, None, None))
+            .collect();
+        rows[0].avg_gas_price = 10;
+        rows[1].avg_gas_price = 30;
+
+        let result = aggregate_batch_fee_components(rows, 5);
+
+        // The batch without a recorded price is left out of the average
+        assert_eq!(result[0].avg_gas_price, 20);
+    }
+
     #[test]
     fn test_aggregate_batch_fee_components_mixed_optional_fields() {
         let rows = vec![
@@ -711,4 +1044,183 @@ mod tests {
         assert_eq!(result[0].l1_block_number, 5002);
         assert_eq!(result[0].blob_count, 185.0); // (255 + 200 + 100) / 3 = 185
     }
+
+    #[test]
+    fn test_aggregate_builder_distribution() {
+        let fingerprints = BuilderFingerprints::parse(&["0x7461696b6f=Taiko".to_owned()]).unwrap();
+        let row = |extra_data: &str, blocks| ExtraDataCountRow {
+            extra_data: extra_data.to_owned(),
+            blocks,
+        };
+        let rows = vec![
+            row("0x7461696b6f2d67657468", 5),
+            row("0x6e65746865726d696e64", 4),
+            row("0x7461696b6f2d72657468", 1),
+        ];
+
+        let builders = aggregate_builder_distribution(rows, &fingerprints);
+
+        assert_eq!(builders.len(), 2);
+        assert_eq!(builders[0].builder, "Taiko");
+        assert_eq!(builders[0].blocks, 6);
+        assert!((builders[0].share - 0.6).abs() < f64::EPSILON);
+        assert_eq!(builders[1].builder, "nethermind");
+        assert!(aggregate_builder_distribution(Vec::new(), &fingerprints).is_empty());
+    }
+
+    #[test]
+    fn summarize_proposal_inclusions_counts_outcomes() {
+        use clickhouse_lib::{AddressBytes, HashBytes};
+
+        let row = |nonce: u64, delay: Option<u64>, dropped: u8| ProposalInclusionRow {
+            proposer: AddressBytes([1u8; 20]),
+            nonce,
+            tx_hash: HashBytes([2u8; 32]),
+            first_seen_ms: 1_700_000_000_000 + nonce,
+            max_fee_per_gas: 30_000_000_000,
+            max_priority_fee_per_gas: Some(1_000_000_000),
+            replacements: 0,
+            batch_id: delay.map(|_| 100 + nonce),
+            l1_block_number: delay.map(|_| 21_000_000),
+            included_at_ms: delay.map(|d| 1_700_000_000_000 + nonce + d),
+            inclusion_delay_ms: delay,
+            dropped,
+        };
+        let rows = vec![
+            row(1, Some(12_000), 0),
+            row(2, Some(36_000), 0),
+            row(3, None, 1),
+            row(4, None, 0),
+        ];
+
+        let summary = summarize_proposal_inclusions(rows);
+
+        assert_eq!(summary.proposals.len(), 4);
+        assert_eq!(summary.included, 2);
+        assert_eq!(summary.dropped, 1);
+        assert_eq!(summary.pending, 1);
+        assert_eq!(summary.avg_delay_ms, Some(24_000));
+        assert_eq!(summary.p95_delay_ms, Some(36_000));
+        assert_eq!(summary.max_delay_ms, Some(36_000));
+        assert!(summary.proposals[2].dropped);
+
+        let empty = summarize_proposal_inclusions(Vec::new());
+        assert_eq!(empty.avg_delay_ms, None);
+        assert_eq!(empty.pending, 0);
+    }
+
+    #[test]
+    fn summarize_handovers_computes_percentiles() {
+        let row = |n: u64, latency_secs: u64| SequencerHandoverRow {
+            outgoing: AddressBytes([1u8; 20]),
+            incoming: AddressBytes([2u8; 20]),
+            last_block_number: n,
+            last_block_ts: 1_700_000_000 + n * 100,
+            first_block_number: n + 1,
+            first_block_ts: 1_700_000_000 + n * 100 + latency_secs,
+            latency_secs,
+        };
+        let rows: Vec<_> = (1..=10).map(|n| row(n, n * 2)).collect();
+
+        let summary = summarize_handovers(rows);
+
+        assert_eq!(summary.handovers.len(), 10);
+        assert_eq!(summary.p50_secs, Some(10));
+        assert_eq!(summary.p90_secs, Some(18));
+        assert_eq!(summary.p99_secs, Some(20));
+        assert_eq!(summary.max_secs, Some(20));
+        assert_eq!(summary.handovers[0].handover_time.timestamp(), 1_700_000_102);
+
+        let empty = summarize_handovers(Vec::new());
+        assert!(empty.handovers.is_empty());
+        assert_eq!(empty.p50_secs, None);
+    }
+
+    #[test]
+    fn operator_schedule_response_derives_epoch_boundaries() {
+        use primitives::beacon::{GENESIS_TIMESTAMP, SECONDS_PER_SLOT};
+
+        let operator = |b: u8| AddressBytes([b; 20]);
+        let latest = PreconfData {
+            slot: 12_805,
+            candidates: vec![operator(1), operator(2)],
+            current_operator: Some(operator(1)),
+            next_operator: Some(operator(2)),
+        };
+        let rows = vec![
+            OperatorEpochRow {
+                epoch: 400,
+                operator: operator(1),
+                first_l1_block: 1_000,
+                last_l1_block: 1_005,
+            },
+            OperatorEpochRow {
+                epoch: 399,
+                operator: operator(2),
+                first_l1_block: 970,
+                last_l1_block: 999,
+            },
+        ];
+        let labels = HashMap::from([(operator(1), "Alpha".to_owned())]);
+
+        let schedule = operator_schedule_response(Some(latest), rows, &labels);
+
+        let current = schedule.current.unwrap();
+        assert_eq!((current.epoch, current.start_slot), (400, 12_800));
+        assert_eq!(current.start_ts, GENESIS_TIMESTAMP + 12_800 * SECONDS_PER_SLOT);
+        assert_eq!(current.end_ts - current.start_ts, 32 * SECONDS_PER_SLOT);
+        assert_eq!((current.first_l1_block, current.last_l1_block), (Some(1_000), Some(1_005)));
+        assert_eq!(current.label.as_deref(), Some("Alpha"));
+
+        let next = schedule.next.unwrap();
+        assert_eq!(next.epoch, 401);
+        assert_eq!(next.start_ts, current.end_ts);
+        assert_eq!(next.first_l1_block, None);
+        assert_eq!(next.operator, Some(format_address(operator(2))));
+
+        assert_eq!(schedule.candidates.len(), 2);
+        assert_eq!(schedule.history.len(), 2);
+        assert_eq!(schedule.history[1].epoch, 399);
+
+        let empty = operator_schedule_response(None, Vec::new(), &labels);
+        assert_eq!((empty.slot, empty.current, empty.next), (None, None, None));
+    }
+
+    #[test]
+    fn summarize_cost_per_tx_charges_hardware_per_active_sequencer() {
+        let row = |bucket_ts: u64, sequencer: u8, tx_count: u64| SequencerCostBucketRow {
+            bucket_ts,
+            sequencer: AddressBytes([sequencer; 20]),
+            tx_count,
+            l1_data_cost: 2_000_000_000_000,
+            prove_cost: 1_000_000_000_000,
+        };
+        let rows = vec![row(3_600, 1, 100), row(3_600, 2, 50), row(7_200, 1, 0)];
+        let labels = HashMap::from([(AddressBytes([1; 20]), "Alpha".to_owned())]);
+
+        // 2,592 USD per month is 3.6 USD per hour, or 1,000,000 gwei at 3,600 USD per ETH
+        let summary = summarize_cost_per_tx(rows, 3_600, 2_592.0, Some(3_600.0), &labels);
+
+        assert_eq!(summary.buckets.len(), 2);
+        let first = &summary.buckets[0];
+        assert_eq!(first.bucket_start.timestamp(), 3_600);
+        assert_eq!(first.sequencers, 2);
+        assert_eq!(first.tx_count, 150);
+        assert_eq!(first.l1_data_cost, 4_000);
+        assert_eq!(first.prove_cost, 2_000);
+        assert_eq!(first.hardware_cost, 2_000_000);
+        assert_eq!(first.cost_per_tx, Some(2_006_000.0 / 150.0));
+        assert_eq!(summary.buckets[1].cost_per_tx, None);
+
+        assert_eq!(summary.sequencers[0].label.as_deref(), Some("Alpha"));
+        assert_eq!(summary.sequencers[0].hardware_cost, 2_000_000);
+        assert_eq!(summary.sequencers[1].tx_count, 50);
+        assert_eq!(summary.tx_count, 150);
+        assert_eq!(summary.total_cost, 3_009_000);
+        assert_eq!(summary.cost_per_tx_usd, Some(3_009_000.0 / 150.0 / 1e9 * 3_600.0));
+
+        let no_price = summarize_cost_per_tx(Vec::new(), 3_600, 2_592.0, None, &labels);
+        assert_eq!(no_price.cost_per_tx, None);
+        assert_eq!(no_price.cost_per_tx_usd, None);
+    }
 }
@@ -1,9 +1,11 @@
 //! Common helper functions used across API endpoints
 
-use crate::ErrorResponse;
-use alloy_primitives::Address;
+use std::collections::HashMap;
+
+use crate::{ErrorCode, ErrorResponse};
+use alloy_primitives::{Address, B256};
 use axum::http::StatusCode;
-use clickhouse_lib::AddressBytes;
+use clickhouse_lib::{AddressBytes, ClickhouseReader, HashBytes, QueryError};
 use hex::encode;
 use primitives::WEI_PER_GWEI;
 
@@ -13,16 +15,28 @@ pub fn parse_address(addr_str: &str) -> Result<AddressBytes, ErrorResponse> {
         Ok(a) => Ok(AddressBytes::from(a)),
         Err(e) => {
             tracing::warn!(error = %e, address = addr_str, "Failed to parse address");
-            Err(ErrorResponse::new(
-                "invalid-params",
-                "Bad Request",
-                StatusCode::BAD_REQUEST,
+            Err(ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
                 format!("Invalid address format: {}", e),
             ))
         }
     }
 }
 
+/// Parse and validate a transaction hash from a string
+pub fn parse_tx_hash(hash_str: &str) -> Result<HashBytes, ErrorResponse> {
+    match hash_str.parse::<B256>() {
+        Ok(h) => Ok(HashBytes::from(h)),
+        Err(e) => {
+            tracing::warn!(error = %e, hash = hash_str, "Failed to parse transaction hash");
+            Err(ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
+                format!("Invalid transaction hash format: {}", e),
+            ))
+        }
+    }
+}
+
 /// Parse an optional address string
 pub fn parse_optional_address(
     addr_str: Option<&String>,
@@ -38,6 +52,18 @@ pub fn format_address(addr: AddressBytes) -> String {
     Address::from(addr).to_string()
 }
 
+/// Labels of the known addresses. Labels are cosmetic, so a failed query is logged and yields
+/// no labels instead of failing the request.
+pub async fn address_labels(client: &ClickhouseReader) -> HashMap<AddressBytes, String> {
+    match client.get_address_labels().await {
+        Ok(rows) => rows.into_iter().map(|r| (r.address, r.label)).collect(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to get address labels");
+            HashMap::new()
+        }
+    }
+}
+
 /// Format an address bytes as a hex string with 0x prefix
 pub fn format_address_bytes(bytes: &[u8]) -> String {
     format!("0x{}", encode(bytes))
@@ -48,6 +74,11 @@ pub fn format_address_bytes_type(addr: &AddressBytes) -> String {
     format_address(*addr)
 }
 
+/// Format a transaction hash as a hex string with 0x prefix, or `None` if it was not recorded
+pub fn format_tx_hash(hash: HashBytes) -> Option<String> {
+    (hash != HashBytes::default()).then(|| format_address_bytes(hash.as_bytes()))
+}
+
 /// Convert Wei to Gwei
 pub const fn wei_to_gwei(wei: u128) -> u128 {
     wei / WEI_PER_GWEI
@@ -58,14 +89,51 @@ pub fn wei_to_gwei_opt(wei: Option<u128>) -> Option<u128> {
     wei.map(wei_to_gwei)
 }
 
-/// Create a database error response with logging
-pub fn database_error(operation: &str, error: impl std::fmt::Display) -> ErrorResponse {
-    tracing::error!(operation = operation, error = %error, "Database operation failed");
-    ErrorResponse::database_error()
+/// Part of `base_fee` paid to the sequencer when `sharing_pctg` percent of it is shared; the
+/// rest goes to the protocol
+pub const fn shared_base_fee(base_fee: u128, sharing_pctg: u8) -> u128 {
+    base_fee.saturating_mul(sharing_pctg as u128) / 100
+}
+
+/// Sequencer revenue, i.e. priority fees plus the shared base fee, minus its L1 data and
+/// proving costs, converted from Wei to Gwei. Negative when the costs exceed the revenue.
+pub const fn net_revenue_gwei(priority_fee: u128, shared_base_fee: u128, costs: u128) -> i128 {
+    let revenue = priority_fee.saturating_add(shared_base_fee) as i128;
+    revenue.saturating_sub(costs as i128) / WEI_PER_GWEI as i128
+}
+
+/// Create a database error response with logging. Queries rejected or abandoned by the
+/// reader's limits map to 503 and 504 so clients know to retry later.
+pub fn database_error(operation: &str, error: impl Into<eyre::Report>) -> ErrorResponse {
+    let error = error.into();
+    match error.downcast_ref::<QueryError>() {
+        Some(QueryError::Saturated) => {
+            tracing::warn!(operation = operation, "Database saturated, rejecting request");
+            ErrorResponse::new(
+                ErrorCode::UpstreamUnavailable,
+                "Service Unavailable",
+                StatusCode::SERVICE_UNAVAILABLE,
+                "too many concurrent queries, retry later",
+            )
+        }
+        Some(QueryError::TimedOut(timeout)) => {
+            tracing::warn!(operation = operation, ?timeout, "Database query timed out");
+            ErrorResponse::new(
+                ErrorCode::UpstreamTimeout,
+                "Gateway Timeout",
+                StatusCode::GATEWAY_TIMEOUT,
+                format!("query did not finish within {}s", timeout.as_secs()),
+            )
+        }
+        None => {
+            tracing::error!(operation = operation, error = %error, "Database operation failed");
+            ErrorResponse::database_error()
+        }
+    }
 }
 
 /// Create a database error response for a specific query type
-pub fn query_error(query_type: &str, error: impl std::fmt::Display) -> ErrorResponse {
+pub fn query_error(query_type: &str, error: impl Into<eyre::Report>) -> ErrorResponse {
     database_error(&format!("get {}", query_type), error)
 }
 
@@ -73,6 +141,15 @@ pub fn query_error(query_type: &str, error: impl std::fmt::Display) -> ErrorResp
 mod tests {
     use super::*;
 
+    #[test]
+    fn base_fee_is_split_by_sharing_percentage() {
+        assert_eq!(shared_base_fee(4_000, 75), 3_000);
+        assert_eq!(shared_base_fee(4_000, 0), 0);
+        assert_eq!(shared_base_fee(4_000, 100), 4_000);
+        assert_eq!(net_revenue_gwei(2 * WEI_PER_GWEI, 3 * WEI_PER_GWEI, WEI_PER_GWEI), 4);
+        assert_eq!(net_revenue_gwei(WEI_PER_GWEI, 0, 3 * WEI_PER_GWEI), -2);
+    }
+
     #[test]
     fn test_parse_address_valid() {
         let addr = "0x742d35Cc6634C0532925a3b844Bc9e7595f8e3A1";
@@ -81,16 +158,45 @@ mod tests {
         assert_eq!(formatted.to_lowercase(), addr.to_lowercase());
     }
 
+    #[test]
+    fn test_query_limit_errors_are_retryable() {
+        let saturated = query_error("blocks", eyre::Report::new(QueryError::Saturated));
+        assert_eq!(saturated.status, StatusCode::SERVICE_UNAVAILABLE.as_u16());
+        assert_eq!(saturated.r#type, ErrorCode::UpstreamUnavailable);
+
+        let timed_out = query_error(
+            "blocks",
+            eyre::Report::new(QueryError::TimedOut(std::time::Duration::from_secs(30))),
+        );
+        assert_eq!(timed_out.status, StatusCode::GATEWAY_TIMEOUT.as_u16());
+        assert_eq!(timed_out.r#type, ErrorCode::UpstreamTimeout);
+        assert_eq!(timed_out.detail, "query did not finish within 30s");
+
+        let other = query_error("blocks", eyre::eyre!("connection refused"));
+        assert_eq!(other.status, StatusCode::INTERNAL_SERVER_ERROR.as_u16());
+    }
+
     #[test]
     fn test_parse_address_invalid() {
         let addr = "invalid_address";
         let result = parse_address(addr);
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.r#type, "invalid-params");
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
         assert!(err.detail.contains("Invalid address format"));
     }
 
+    #[test]
+    fn test_parse_tx_hash() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        let parsed = parse_tx_hash(&hash).unwrap();
+        assert_eq!(format_tx_hash(parsed), Some(hash));
+
+        let err = parse_tx_hash("0x1234").unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
+        assert!(err.detail.contains("Invalid transaction hash format"));
+    }
+
     #[test]
     fn test_parse_optional_address_some() {
         let addr = String::from("0x742d35Cc6634C0532925a3b844Bc9e7595f8e3A1");
@@ -117,6 +223,12 @@ mod tests {
         assert_eq!(wei_to_gwei_opt(None), None);
     }
 
+    #[test]
+    fn test_format_tx_hash() {
+        assert_eq!(format_tx_hash(HashBytes([0xab; 32])), Some(format!("0x{}", "ab".repeat(32))));
+        assert_eq!(format_tx_hash(HashBytes::default()), None);
+    }
+
     #[test]
     fn test_format_address_bytes() {
         let bytes = vec![0x74, 0x2d, 0x35];
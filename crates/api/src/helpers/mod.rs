@@ -2,6 +2,8 @@
 
 pub mod aggregation;
 pub mod common;
+pub mod pagination;
 
 pub use aggregation::*;
 pub use common::{format_address_bytes_type, *};
+pub use pagination::{PageResponse, RowCount, paginate, stream_page, unpaged};
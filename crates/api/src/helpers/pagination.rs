@@ -0,0 +1,283 @@
+//! Standard envelope of paginated responses
+//!
+//! Paginated endpoints respond with [`Paginated`] unless the request sets `envelope=false`, in
+//! which case the endpoint specific legacy response is returned while clients migrate.
+//!
+//! Endpoints that may return tens of thousands of rows use [`stream_page`], which writes the
+//! items to the response body while they are read from `ClickHouse` rather than collecting them.
+
+use api_types::Paginated;
+use axum::{
+    Json,
+    body::{Body, Bytes},
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use clickhouse_lib::RowStream;
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+
+use crate::{
+    state::ApiState,
+    validation::{EnvelopeQuery, Page},
+};
+
+/// Rows counted for the `total_estimate` of a page
+#[derive(Debug, Clone, Copy)]
+pub struct RowCount {
+    /// Table the items are read from
+    pub table: &'static str,
+    /// Start of the requested time range (exclusive)
+    pub since: DateTime<Utc>,
+    /// End of the requested time range (inclusive)
+    pub until: DateTime<Utc>,
+}
+
+impl RowCount {
+    /// Count rows of `table` inserted since `since`
+    pub fn since(table: &'static str, since: DateTime<Utc>) -> Self {
+        Self { table, since, until: Utc::now() }
+    }
+}
+
+/// Response of a paginated endpoint, either the standard envelope or the legacy shape `L`
+#[derive(Debug)]
+pub enum PageResponse<T, L> {
+    /// Standard pagination envelope
+    Envelope(Paginated<T>),
+    /// Endpoint specific legacy response
+    Legacy(L),
+}
+
+impl<T: Serialize, L: Serialize> IntoResponse for PageResponse<T, L> {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Envelope(page) => Json(page).into_response(),
+            Self::Legacy(legacy) => Json(legacy).into_response(),
+        }
+    }
+}
+
+/// Build the response for `items` of `page`, returned in descending `key` order.
+///
+/// `legacy` builds the legacy response from the items and the next and previous cursors. The
+/// total is only estimated for enveloped responses; a failed estimate is logged and left out.
+pub async fn paginate<T, L>(
+    state: &ApiState,
+    envelope: &EnvelopeQuery,
+    page: &Page,
+    items: Vec<T>,
+    key: impl Fn(&T) -> u64,
+    count: Option<RowCount>,
+    legacy: impl FnOnce(Vec<T>, Option<String>, Option<String>) -> L,
+) -> PageResponse<T, L> {
+    let (next_cursor, prev_cursor) = page.cursors(&items, key);
+    if !envelope.enabled() {
+        return PageResponse::Legacy(legacy(items, next_cursor, prev_cursor));
+    }
+
+    let total_estimate = estimate_total(state, count).await;
+    PageResponse::Envelope(Paginated {
+        items,
+        has_more: next_cursor.is_some(),
+        next_cursor,
+        prev_cursor,
+        total_estimate,
+    })
+}
+
+/// Estimate the rows of `count`, logging and leaving out a failed estimate
+async fn estimate_total(state: &ApiState, count: Option<RowCount>) -> Option<u64> {
+    let count = count?;
+    match state.client.estimate_row_count(count.table, count.since, count.until).await {
+        Ok(rows) => Some(rows),
+        Err(e) => {
+            tracing::warn!(table = count.table, err = %e, "Failed to estimate page total");
+            None
+        }
+    }
+}
+
+/// Build the response for `items` returned in full, such as aggregated buckets.
+pub fn unpaged<T, L>(
+    envelope: &EnvelopeQuery,
+    items: Vec<T>,
+    legacy: impl FnOnce(Vec<T>) -> L,
+) -> PageResponse<T, L> {
+    if !envelope.enabled() {
+        return PageResponse::Legacy(legacy(items));
+    }
+    PageResponse::Envelope(Paginated {
+        total_estimate: Some(items.len() as u64),
+        items,
+        has_more: false,
+        next_cursor: None,
+        prev_cursor: None,
+    })
+}
+
+/// Size at which a chunk of the streamed body is sent
+const STREAM_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Fields written after the items of a streamed envelope
+#[derive(Serialize)]
+struct EnvelopeTail {
+    has_more: bool,
+    next_cursor: Option<String>,
+    prev_cursor: Option<String>,
+    total_estimate: Option<u64>,
+}
+
+/// Fields written after the items of a streamed legacy response
+#[derive(Serialize)]
+struct LegacyTail {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prev_cursor: Option<String>,
+}
+
+/// Stream the `rows` of `page`, returned in descending `key` order, into the response body as
+/// they are read.
+///
+/// The body has the same shape as [`paginate`]'s: the items are written first, and the cursors
+/// and total, which depend on the last item, after them. `legacy_items` names the items array of
+/// the legacy response. A failure reading the first row is returned as an error; later failures
+/// abort the body, as the status has already been sent.
+pub async fn stream_page<T>(
+    state: &ApiState,
+    envelope: &EnvelopeQuery,
+    page: &Page,
+    mut rows: RowStream<T>,
+    key: impl Fn(&T) -> u64 + Send + 'static,
+    count: Option<RowCount>,
+    legacy_items: &'static str,
+) -> eyre::Result<Response>
+where
+    T: Serialize + Send + 'static,
+{
+    let first = rows.next().await.transpose()?;
+    let enveloped = envelope.enabled();
+    let total_estimate = if enveloped { estimate_total(state, count).await } else { None };
+    let items = if enveloped { "items" } else { legacy_items };
+    let tail = move |next_cursor: Option<String>, prev_cursor: Option<String>| {
+        if enveloped {
+            serde_json::to_vec(&EnvelopeTail {
+                has_more: next_cursor.is_some(),
+                next_cursor,
+                prev_cursor,
+                total_estimate,
+            })
+        } else {
+            serde_json::to_vec(&LegacyTail { next_cursor, prev_cursor })
+        }
+    };
+
+    let body = page_body(first, rows, key, *page, items, tail);
+    Ok(([(header::CONTENT_TYPE, "application/json")], Body::from_stream(body)).into_response())
+}
+
+/// JSON object holding the `items` array, starting with `first` and followed by `rows`, then the
+/// fields of the object built by `tail` from the next and previous cursors.
+fn page_body<T, K, F>(
+    first: Option<T>,
+    mut rows: RowStream<T>,
+    key: K,
+    page: Page,
+    items: &'static str,
+    tail: F,
+) -> impl Stream<Item = std::io::Result<Bytes>> + Send
+where
+    T: Serialize + Send + 'static,
+    K: Fn(&T) -> u64 + Send + 'static,
+    F: FnOnce(Option<String>, Option<String>) -> serde_json::Result<Vec<u8>> + Send + 'static,
+{
+    async_stream::try_stream! {
+        let mut chunk = Vec::with_capacity(STREAM_CHUNK_BYTES);
+        chunk.extend_from_slice(format!("{{\"{items}\":[").as_bytes());
+
+        let mut keys = None;
+        let mut len = 0_u64;
+        let mut next = first;
+        while let Some(item) = next {
+            let item_key = key(&item);
+            keys = Some((keys.map_or(item_key, |(head, _)| head), item_key));
+            if len > 0 {
+                chunk.push(b',');
+            }
+            serde_json::to_writer(&mut chunk, &item)?;
+            len += 1;
+            if chunk.len() >= STREAM_CHUNK_BYTES {
+                let full = std::mem::replace(&mut chunk, Vec::with_capacity(STREAM_CHUNK_BYTES));
+                yield Bytes::from(full);
+            }
+            next = rows.next().await.transpose().map_err(|e| {
+                tracing::error!(err = %e, "Streamed page aborted");
+                std::io::Error::other(e.to_string())
+            })?;
+        }
+        chunk.push(b']');
+
+        let (next_cursor, prev_cursor) =
+            keys.map_or((None, None), |(head, last)| page.cursors_between(head, last, len));
+        // Append the fields of the tail object to the response object
+        let tail = tail(next_cursor, prev_cursor)?;
+        if tail.len() > 2 {
+            chunk.push(b',');
+            chunk.extend_from_slice(&tail[1..]);
+        } else {
+            chunk.push(b'}');
+        }
+        tracing::info!(count = len, "Streamed page");
+        yield Bytes::from(chunk);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpaged_items_fill_a_single_page() {
+        let envelope = EnvelopeQuery::default();
+        let PageResponse::Envelope(page) = unpaged(&envelope, vec![3u64, 2, 1], |items| items)
+        else {
+            panic!("expected an envelope");
+        };
+        assert_eq!(page.items, vec![3, 2, 1]);
+        assert!(!page.has_more);
+        assert_eq!(page.next_cursor, None);
+        assert_eq!(page.total_estimate, Some(3));
+    }
+
+    #[tokio::test]
+    async fn streamed_page_has_the_legacy_shape() {
+        use futures::TryStreamExt;
+
+        let legacy =
+            |next_cursor, prev_cursor| serde_json::to_vec(&LegacyTail { next_cursor, prev_cursor });
+        let page = crate::validation::resolve_pagination(None, None, None, Some(&2), 100, 0)
+            .expect("valid page");
+
+        let rows: RowStream<u64> = futures::stream::iter(vec![Ok(9)]).boxed();
+        let chunks: Vec<Bytes> =
+            page_body(Some(10), rows, |n| *n, page, "blocks", legacy).try_collect().await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&chunks.concat()).unwrap();
+        assert_eq!(json["blocks"], serde_json::json!([10, 9]));
+        assert!(json["next_cursor"].is_string());
+        assert!(json.get("prev_cursor").is_none());
+
+        let empty: RowStream<u64> = futures::stream::empty().boxed();
+        let chunks: Vec<Bytes> =
+            page_body(None, empty, |n| *n, page, "blocks", legacy).try_collect().await.unwrap();
+        assert_eq!(chunks.concat(), br#"{"blocks":[]}"#);
+    }
+
+    #[test]
+    fn envelope_can_be_disabled() {
+        let envelope = EnvelopeQuery { envelope: Some(false) };
+        let response = unpaged(&envelope, vec![1u64], |items| items.len());
+        assert!(matches!(response, PageResponse::Legacy(1)));
+    }
+}
@@ -2,16 +2,22 @@
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::needless_for_each)]
 
+pub mod budget;
+pub mod cache;
+pub mod extract;
 pub mod helpers;
 pub mod routes;
 pub mod state;
+pub mod usage;
 pub mod validation;
 
 // Re-export public items
-pub use routes::router;
+pub use budget::QueryBudget;
+pub use cache::CacheTtls;
+pub use routes::{admin_router, router, status_router};
 pub use state::{
     ApiState, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD, MAX_BLOCK_TRANSACTIONS_LIMIT,
-    MAX_TABLE_LIMIT,
+    MAX_TABLE_LIMIT, RateLimitConfig, StatusPageConfig,
 };
 
 use api_types::*;
@@ -26,9 +32,12 @@ use utoipa::OpenApi;
         routes::core::preconf_data,
         routes::table::reorgs,
         routes::table::slashings,
+        routes::table::preconf_mismatches,
+        routes::table::anchor_mismatches,
         routes::table::forced_inclusions,
         routes::table::failed_proposals,
         routes::core::batch_posting_times,
+        routes::core::batch_blocks,
 
         routes::table::blobs_per_batch,
         routes::core::prove_times,
@@ -39,37 +48,91 @@ use utoipa::OpenApi;
         routes::table::l2_tps,
         routes::table::block_transactions,
         routes::core::sequencer_distribution,
+        routes::core::builder_distribution,
         routes::core::sequencer_blocks,
         routes::core::l2_fees_components,
         routes::aggregated::dashboard_data,
         routes::aggregated::prove_costs,
         routes::core::prove_cost,
         routes::core::l1_data_cost,
-        routes::core::eth_price
+        routes::core::eth_price,
+        routes::core::data_quality,
+        routes::aggregated::leaderboards,
+        routes::core::protocol_config,
+        routes::table::blob_fee_history,
+        routes::aggregated::anchor_lag,
+        routes::aggregated::gas_saturation,
+        routes::aggregated::batch_efficiency,
+        routes::aggregated::protocol_gas,
+        routes::core::rpc_status,
+        routes::core::pipeline_latency,
+        routes::core::propagation_delay,
+        routes::core::backfill_status,
+        routes::core::leader_changes,
+        routes::core::bond_balances,
+        routes::core::bond_history,
+        routes::core::operator_balances,
+        routes::core::operator_schedule,
+        routes::core::forced_inclusion_queue,
+        routes::core::verification_backlog,
+        routes::core::proposal_inclusion_delay,
+        routes::core::handover_latency,
+        routes::core::incident_history,
+        routes::aggregated::cost_per_tx,
+        routes::aggregated::prove_time_percentiles,
+        routes::aggregated::prove_times_by_proof_type,
+        routes::aggregated::verify_time_percentiles,
+        routes::aggregated::sequencer_uptime,
+        routes::aggregated::mempool_stats,
+        routes::aggregated::anomalies,
+        routes::core::block_status,
+        routes::core::block_status_summary,
+        routes::core::tx_lookup,
+        routes::aggregated::reorg_stats
     ),
     components(
         schemas(
             validation::CommonQuery,
             validation::PaginatedQuery,
             validation::BlockPaginatedQuery,
+            validation::BatchBlocksQuery,
             validation::TimeRangeParams,
+            TimeRangePreset,
             validation::BlockRangeParams,
+            validation::LeaderboardQuery,
+            validation::CostQuery,
+            validation::BatchEfficiencyQuery,
+            validation::CostPerTxQuery,
+            validation::BlockStatusSummaryQuery,
+            validation::BondHistoryQuery,
+            validation::AnomalyQuery,
+            validation::IncidentHistoryQuery,
             L2HeadBlockResponse,
             L1HeadBlockResponse,
             ReorgEventsResponse,
             SlashingEventsResponse,
+            PreconfMismatchesResponse,
+            PreconfMismatchEvent,
+            AnchorMismatchesResponse,
+            AnchorMismatchEvent,
             ForcedInclusionEventsResponse,
             FailedProposalEventsResponse,
             BatchPostingTimesResponse,
+            BatchBlocksResponse,
+            BatchBlockItem,
             BatchBlobsResponse,
             ProveTimesResponse,
+            BatchProveTimeItem,
             VerifyTimesResponse,
+            BatchVerifyTimeItem,
             L1BlockTimesResponse,
             L2BlockTimesResponse,
             L2GasUsedResponse,
             L2TpsResponse,
             SequencerDistributionResponse,
             SequencerDistributionItem,
+            BuilderDistributionResponse,
+            BuilderDistributionItem,
             SequencerBlocksResponse,
             SequencerBlocksItem,
             BlockTransactionsResponse,
@@ -85,17 +148,92 @@ use utoipa::OpenApi;
             clickhouse_lib::L2TpsRow,
             clickhouse_lib::BatchBlobCountRow,
             clickhouse_lib::BatchPostingTimeRow,
+            clickhouse_lib::BatchAnomalyRow,
             HealthResponse,
+            RpcEndpointHealth,
+            StreamChannelHealth,
+            DataQualityHealth,
             PreconfDataResponse,
             L2FeesResponse,
             L2FeesComponentsResponse,
+            FeeTimeBucketItem,
             SequencerFeeRow,
             DashboardDataResponse,
+            DashboardSectionError,
             EthPriceResponse,
             ProposerCostsResponse,
             ProveCostResponse,
             api_types::ErrorResponse,
-            L1DataCostResponse
+            api_types::ErrorCode,
+            L1DataCostResponse,
+            DataQualityResponse,
+            LeaderboardsResponse,
+            LeaderboardBlockItem,
+            LeaderboardSequencerItem,
+            BondBalancesResponse,
+            BondBalanceItem,
+            BondHistoryResponse,
+            BondHistoryItem,
+            OperatorBalancesResponse,
+            OperatorBalanceItem,
+            OperatorScheduleResponse,
+            OperatorEpochItem,
+            ForcedInclusionQueueResponse,
+            ForcedInclusionQueueItem,
+            VerificationBacklogResponse,
+            VerificationBacklogItem,
+            ProposalInclusionDelayResponse,
+            HandoverLatencyResponse,
+            HandoverItem,
+            ProposalInclusionItem,
+            CostPerTxResponse,
+            CostPerTxBucketItem,
+            SequencerCostPerTxItem,
+            IncidentHistoryResponse,
+            IncidentLogItem,
+            ProtocolConfigResponse,
+            ProtocolConfigItem,
+            BlobFeeHistoryResponse,
+            BlobFeeHistoryItem,
+            AnchorLagResponse,
+            AnchorLagItem,
+            GasSaturationResponse,
+            GasSaturationItem,
+            BatchEfficiencyResponse,
+            BatchEfficiencyItem,
+            DaModeShareItem,
+            BlocksPerBatchBucket,
+            ProtocolGasResponse,
+            ProtocolGasOperationItem,
+            ProtocolGasAddressItem,
+            RpcStatusResponse,
+            RpcStatusItem,
+            PipelineLatencyResponse,
+            PipelineLatencyItem,
+            PropagationDelayResponse,
+            PropagationDelayItem,
+            BackfillStatusResponse,
+            BackfillStatusItem,
+            LeaderChangesResponse,
+            LeaderChangeItem,
+            TimePercentilesResponse,
+            TimePercentilesItem,
+            ProofTypeProveTimesResponse,
+            ProofTypeProveTimesItem,
+            SequencerUptimeResponse,
+            SequencerUptimeItem,
+            MempoolStatsResponse,
+            MempoolStatsItem,
+            AnomaliesResponse,
+            AnomalyItem,
+            BlockStatusResponse,
+            BlockStatusSummaryResponse,
+            FinalityStage,
+            TxLookupResponse,
+            TxRoleItem,
+            TxRole,
+            ReorgStatsResponse,
+            ReorgDepthBucket
         )
     ),
     tags(
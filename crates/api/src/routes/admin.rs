@@ -0,0 +1,78 @@
+//! Operational endpoints
+//!
+//! These routes are mounted only when an admin token is configured and are kept out of the
+//! public `OpenAPI` document. Authentication is applied by the router that mounts them.
+
+use crate::{cache::CacheGroup, extract::Query, state::ApiState};
+use api_types::{
+    ApiUsageByOriginResponse, CacheGroupStatsItem, CacheInvalidateResponse, CacheStatsResponse,
+    ReplicaStatsItem, ReplicaStatsResponse,
+};
+use axum::{Json, extract::State};
+use serde::Deserialize;
+use tracing::info;
+
+/// Query parameters of [`invalidate_cache`]
+#[derive(Debug, Default, Deserialize)]
+pub struct InvalidateCacheQuery {
+    /// Only drop responses whose path starts with this prefix, e.g. `/reorgs`
+    pub prefix: Option<String>,
+}
+
+/// Drop cached responses so that the next requests are served fresh
+pub async fn invalidate_cache(
+    State(state): State<ApiState>,
+    Query(query): Query<InvalidateCacheQuery>,
+) -> Json<CacheInvalidateResponse> {
+    let invalidated = state.response_cache().invalidate(query.prefix.as_deref()).await;
+    info!(prefix = query.prefix.as_deref(), invalidated, "Invalidated response cache");
+    Json(CacheInvalidateResponse { invalidated })
+}
+
+/// Report the requests received per `Origin` header since the server started
+pub async fn api_usage_by_origin(State(state): State<ApiState>) -> Json<ApiUsageByOriginResponse> {
+    let usage = state.origin_usage();
+    let origins = usage.snapshot();
+    Json(ApiUsageByOriginResponse {
+        since: usage.since(),
+        requests: origins.iter().map(|o| o.requests).sum(),
+        rejected_requests: origins.iter().filter(|o| !o.allowed).map(|o| o.requests).sum(),
+        origins,
+    })
+}
+
+/// Report response cache hit, miss, bypass and pre-warm counters per route group
+pub async fn cache_stats(State(state): State<ApiState>) -> Json<CacheStatsResponse> {
+    let item = |group| {
+        let stats = state.response_cache().stats(group);
+        CacheGroupStatsItem {
+            hits: stats.hits,
+            misses: stats.misses,
+            bypasses: stats.bypasses,
+            warm_hits: stats.warm_hits,
+            prewarms: stats.prewarms,
+            warm_hit_rate: stats.warm_hit_rate(),
+        }
+    };
+    Json(CacheStatsResponse {
+        dashboard: item(CacheGroup::Dashboard),
+        table: item(CacheGroup::Table),
+    })
+}
+
+/// Report query, error and latency counters of the primary `ClickHouse` and its read replicas
+pub async fn replica_stats(State(state): State<ApiState>) -> Json<ReplicaStatsResponse> {
+    let replicas = state
+        .client
+        .replica_stats()
+        .into_iter()
+        .map(|stats| ReplicaStatsItem {
+            url: stats.url.to_string(),
+            healthy: stats.healthy,
+            queries: stats.queries,
+            errors: stats.errors,
+            avg_latency_ms: stats.avg_latency_ms,
+        })
+        .collect();
+    Json(ReplicaStatsResponse { replicas })
+}
@@ -1,18 +1,38 @@
 //! Aggregated data endpoints with complex processing
 
 use crate::{
-    helpers::{format_address, parse_optional_address, query_error, wei_to_gwei},
-    state::ApiState,
+    budget::QueryBudgetTracker,
+    extract::Query,
+    helpers::{
+        address_labels, format_address, parse_optional_address, query_error, summarize_cost_per_tx,
+        time_bucket_secs_from_range, wei_to_gwei,
+    },
+    state::{
+        ApiState, DASHBOARD_SECTION_TIMEOUT, DEFAULT_LEADERBOARD_LIMIT, MAX_LEADERBOARD_LIMIT,
+    },
     validation::{
-        CommonQuery, has_time_range_params, resolve_time_range_enum, resolve_time_range_since,
-        validate_range_exclusivity, validate_time_range,
+        AnomalyQuery, BatchEfficiencyQuery, CommonQuery, CostPerTxQuery, CostQuery, Denomination,
+        LeaderboardQuery, validate_anomaly_metric, validate_da_mode, validate_denomination,
+        validate_hardware_cost, validate_limit, validate_range_exclusivity,
     },
 };
 use api_types::*;
 use axum::{
     Json,
-    extract::{Query, State},
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use chrono::{TimeZone, Utc};
+use clickhouse_lib::{
+    BatchEfficiencyRow, DaModeShareRow, DailyTimePercentilesRow, L2BlockLeaderboardRow,
+    ProtocolGasSpendRow, SequencerLeaderboardRow,
 };
+use primitives::{
+    BYTES_PER_BLOB, anomaly::AnomalyMetric, da::DaMode, hardware::TOTAL_HARDWARE_COST_USD,
+    proof::ProofType,
+};
+use std::{cmp::Reverse, collections::BTreeMap, future::Future};
 
 // Legacy type aliases for backward compatibility
 type RangeQuery = CommonQuery;
@@ -21,42 +41,671 @@ type RangeQuery = CommonQuery;
     get,
     path = "/prove-costs",
     params(
-        RangeQuery
+        CostQuery
     ),
     responses(
         (status = 200, description = "Aggregated prover costs", body = ProposerCostsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 400, description = "Invalid denomination", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
-/// Get aggregated prover costs grouped by proposer
+/// Get aggregated prover costs grouped by proposer, optionally with their value in USD or TAIKO
 pub async fn prove_costs(
-    Query(params): Query<RangeQuery>,
+    Query(params): Query<CostQuery>,
     State(state): State<ApiState>,
 ) -> Result<Json<ProposerCostsResponse>, ErrorResponse> {
-    validate_time_range(&params.time_range)?;
+    params.common.time_range.validate()?;
+    let denomination = validate_denomination(params.denomination.as_deref())?;
+
+    let has_time_range = params.common.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.common.time_range.to_time_range();
+
+    let labels = address_labels(&state.client).await;
+    let proposers: Vec<ProposerCostItem> = match denomination {
+        Denomination::Gwei => state
+            .client
+            .get_prove_costs_by_proposer(time_range)
+            .await
+            .map_err(|e| query_error("prover costs", e))?
+            .into_iter()
+            .map(|(addr, cost)| ProposerCostItem {
+                address: format_address(addr),
+                label: labels.get(&addr).cloned(),
+                cost: wei_to_gwei(cost),
+                cost_usd: None,
+                cost_taiko: None,
+            })
+            .collect(),
+        Denomination::Usd => state
+            .client
+            .get_prove_costs_by_proposer_usd(time_range)
+            .await
+            .map_err(|e| query_error("prover costs", e))?
+            .into_iter()
+            .map(|(addr, cost, cost_usd)| ProposerCostItem {
+                address: format_address(addr),
+                label: labels.get(&addr).cloned(),
+                cost: wei_to_gwei(cost),
+                cost_usd: Some(cost_usd),
+                cost_taiko: None,
+            })
+            .collect(),
+        Denomination::Taiko => state
+            .client
+            .get_prove_costs_by_proposer_taiko(time_range)
+            .await
+            .map_err(|e| query_error("prover costs", e))?
+            .into_iter()
+            .map(|(addr, cost, cost_taiko)| ProposerCostItem {
+                address: format_address(addr),
+                label: labels.get(&addr).cloned(),
+                cost: wei_to_gwei(cost),
+                cost_usd: None,
+                cost_taiko: Some(cost_taiko),
+            })
+            .collect(),
+    };
+
+    tracing::info!(count = proposers.len(), "Returning prover costs");
+    Ok(Json(ProposerCostsResponse { proposers }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/anchor-lag",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "L1 -> L2 anchor lag distribution over time", body = AnchorLagResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the distribution of the L1 head minus the anchor block id of L2 blocks over time
+pub async fn anchor_lag(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<AnchorLagResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows =
+        state.client.get_anchor_lag(time_range).await.map_err(|e| query_error("anchor lag", e))?;
+
+    let buckets: Vec<AnchorLagItem> = rows
+        .into_iter()
+        .map(|r| AnchorLagItem {
+            bucket_start: Utc.timestamp_opt(r.bucket_ts as i64, 0).single().unwrap_or_default(),
+            blocks: r.blocks,
+            avg_lag: r.avg_lag,
+            p50_lag: r.p50_lag,
+            p95_lag: r.p95_lag,
+            max_lag: r.max_lag,
+        })
+        .collect();
+
+    tracing::info!(count = buckets.len(), "Returning anchor lag");
+    Ok(Json(AnchorLagResponse { buckets }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/gas-saturation",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "L2 block gas saturation distribution over time", body = GasSaturationResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the distribution of L2 block gas used over gas limit over time
+pub async fn gas_saturation(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<GasSaturationResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_gas_saturation(time_range)
+        .await
+        .map_err(|e| query_error("gas saturation", e))?;
+
+    let buckets: Vec<GasSaturationItem> = rows
+        .into_iter()
+        .map(|r| GasSaturationItem {
+            bucket_start: Utc.timestamp_opt(r.bucket_ts as i64, 0).single().unwrap_or_default(),
+            blocks: r.blocks,
+            avg_saturation: r.avg_saturation,
+            p95_saturation: r.p95_saturation,
+            max_saturation: r.max_saturation,
+        })
+        .collect();
+
+    tracing::info!(count = buckets.len(), "Returning gas saturation");
+    Ok(Json(GasSaturationResponse { buckets }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/cost-per-tx",
+    params(
+        CostPerTxQuery
+    ),
+    responses(
+        (status = 200, description = "Operating cost per L2 transaction over time and per sequencer", body = CostPerTxResponse),
+        (status = 400, description = "Invalid hardware cost", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the L1 data, proving and estimated hardware costs divided by the transaction count, per
+/// time bucket and per sequencer
+pub async fn cost_per_tx(
+    Query(params): Query<CostPerTxQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<CostPerTxResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let hardware_cost_usd =
+        validate_hardware_cost(params.hardware_cost_usd, TOTAL_HARDWARE_COST_USD)?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+    let bucket_secs = time_bucket_secs_from_range(&time_range);
+
+    let rows = state
+        .client
+        .get_sequencer_cost_buckets(time_range, bucket_secs)
+        .await
+        .map_err(|e| query_error("sequencer cost buckets", e))?;
+    let eth_price = match state.eth_price().await {
+        Ok(price) => Some(price),
+        Err(e) => {
+            tracing::warn!(error = %e, "ETH price unavailable, omitting hardware costs");
+            None
+        }
+    };
+    let labels = address_labels(&state.client).await;
+
+    let response = summarize_cost_per_tx(rows, bucket_secs, hardware_cost_usd, eth_price, &labels);
+    tracing::info!(buckets = response.buckets.len(), "Returning cost per transaction");
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/batch-efficiency",
+    params(
+        BatchEfficiencyQuery
+    ),
+    responses(
+        (status = 200, description = "Blob utilization, blocks per batch and transactions per batch", body = BatchEfficiencyResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get blob utilization, the blocks per batch distribution, average transactions per batch and
+/// the share of batches posted as calldata
+pub async fn batch_efficiency(
+    Query(params): Query<BatchEfficiencyQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<BatchEfficiencyResponse>, ErrorResponse> {
+    params.common.time_range.validate()?;
+    let da_mode = validate_da_mode(params.da_mode.as_deref())?;
 
-    let has_time_range = has_time_range_params(&params.time_range);
+    let has_time_range = params.common.time_range.is_set();
     validate_range_exclusivity(has_time_range, false)?;
 
-    let time_range = resolve_time_range_enum(&params.time_range);
+    let time_range = params.common.time_range.to_time_range();
 
     let rows = state
         .client
-        .get_prove_costs_by_proposer(time_range)
+        .get_batch_efficiency(time_range, da_mode)
         .await
-        .map_err(|e| query_error("prover costs", e))?;
+        .map_err(|e| query_error("batch efficiency", e))?;
+    let share = state
+        .client
+        .get_da_mode_share(time_range)
+        .await
+        .map_err(|e| query_error("DA mode share", e))?;
 
-    let proposers: Vec<ProposerCostItem> = rows
+    let response = batch_efficiency_response(rows, share);
+    tracing::info!(batches = response.batches, "Returning batch efficiency");
+    Ok(Json(response))
+}
+
+fn batch_efficiency_response(
+    rows: Vec<BatchEfficiencyRow>,
+    share: Vec<DaModeShareRow>,
+) -> BatchEfficiencyResponse {
+    let batches = rows.len() as u64;
+    let average = |total: u64| (batches > 0).then(|| total as f64 / batches as f64);
+
+    let used_bytes: u64 = rows.iter().map(|r| u64::from(r.blob_total_bytes)).sum();
+    let posted_bytes: u64 = rows.iter().map(|r| u64::from(r.blob_count) * BYTES_PER_BLOB).sum();
+    let total_blocks: u64 = rows.iter().map(|r| u64::from(r.blocks)).sum();
+    let total_txs: u64 = rows.iter().map(|r| r.txs).sum();
+
+    let mut distribution = BTreeMap::<u16, u64>::new();
+    for r in &rows {
+        *distribution.entry(r.blocks).or_default() += 1;
+    }
+
+    BatchEfficiencyResponse {
+        batches,
+        blob_utilization: (posted_bytes > 0).then(|| used_bytes as f64 / posted_bytes as f64),
+        avg_blocks_per_batch: average(total_blocks),
+        avg_txs_per_batch: average(total_txs),
+        blocks_per_batch: distribution
+            .into_iter()
+            .map(|(blocks, batches)| BlocksPerBatchBucket { blocks, batches })
+            .collect(),
+        da_mode_share: share
+            .into_iter()
+            .map(|s| {
+                let total = s.blob_batches + s.calldata_batches;
+                DaModeShareItem {
+                    bucket_ts: s.bucket_ts,
+                    blob_batches: s.blob_batches,
+                    calldata_batches: s.calldata_batches,
+                    calldata_share: (total > 0).then(|| s.calldata_batches as f64 / total as f64),
+                    blob_bytes: s.blob_bytes,
+                    calldata_bytes: s.calldata_bytes,
+                }
+            })
+            .collect(),
+        items: rows
+            .into_iter()
+            .map(|r| BatchEfficiencyItem {
+                batch_id: r.batch_id,
+                l1_block_number: r.l1_block_number,
+                da_mode: DaMode::from_code(r.da_mode).as_str().to_owned(),
+                blob_count: r.blob_count,
+                blob_total_bytes: r.blob_total_bytes,
+                calldata_bytes: r.calldata_bytes,
+                blob_utilization: (r.blob_count > 0).then(|| {
+                    f64::from(r.blob_total_bytes) /
+                        (u64::from(r.blob_count) * BYTES_PER_BLOB) as f64
+                }),
+                blocks: r.blocks,
+                txs: r.txs,
+            })
+            .collect(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/protocol-gas",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "L1 gas spent on protocol transactions per operation and address", body = ProtocolGasResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the L1 gas spent on proposing, proving and verifying batches, per operation and address
+pub async fn protocol_gas(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<ProtocolGasResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_protocol_gas_spend(time_range)
+        .await
+        .map_err(|e| query_error("protocol gas spend", e))?;
+
+    let response = protocol_gas_response(rows);
+    tracing::info!(operations = response.operations.len(), "Returning protocol gas spend");
+    Ok(Json(response))
+}
+
+fn protocol_gas_response(rows: Vec<ProtocolGasSpendRow>) -> ProtocolGasResponse {
+    let mut operations = BTreeMap::<String, (u64, u64, u128)>::new();
+    for r in &rows {
+        let (txs, gas_used, cost) = operations.entry(r.operation.clone()).or_default();
+        *txs += r.txs;
+        *gas_used += r.gas_used;
+        *cost += r.cost;
+    }
+    let mut operations: Vec<_> = operations
         .into_iter()
-        .map(|(addr, cost)| ProposerCostItem {
-            address: format_address(addr),
+        .map(|(operation, (txs, gas_used, cost))| ProtocolGasOperationItem {
+            operation,
+            txs,
+            gas_used,
             cost: wei_to_gwei(cost),
         })
         .collect();
+    operations.sort_by_key(|op| Reverse(op.cost));
 
-    tracing::info!(count = proposers.len(), "Returning prover costs");
-    Ok(Json(ProposerCostsResponse { proposers }))
+    ProtocolGasResponse {
+        total_cost: wei_to_gwei(rows.iter().map(|r| r.cost).sum()),
+        operations,
+        addresses: rows
+            .into_iter()
+            .map(|r| ProtocolGasAddressItem {
+                operation: r.operation,
+                address: format_address(r.address),
+                txs: r.txs,
+                gas_used: r.gas_used,
+                cost: wei_to_gwei(r.cost),
+            })
+            .collect(),
+    }
+}
+
+fn time_percentiles_response(rows: Vec<DailyTimePercentilesRow>) -> TimePercentilesResponse {
+    let days = rows
+        .into_iter()
+        .map(|r| TimePercentilesItem {
+            day: Utc.timestamp_opt(r.day_ts as i64, 0).single().unwrap_or_default(),
+            batches: r.batches,
+            p50_ms: r.p50_ms,
+            p90_ms: r.p90_ms,
+            p99_ms: r.p99_ms,
+            max_ms: r.max_ms,
+        })
+        .collect();
+    TimePercentilesResponse { days }
+}
+
+#[utoipa::path(
+    get,
+    path = "/prove-time-percentiles",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Daily batch prove time percentiles", body = TimePercentilesResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the daily p50/p90/p99 and maximum batch prove times
+pub async fn prove_time_percentiles(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<TimePercentilesResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_prove_time_percentiles(time_range)
+        .await
+        .map_err(|e| query_error("prove time percentiles", e))?;
+
+    let response = time_percentiles_response(rows);
+    tracing::info!(count = response.days.len(), "Returning prove time percentiles");
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/prove-times-by-proof-type",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Batch prove times per proof type", body = ProofTypeProveTimesResponse),
+        (status = 400, description = "Invalid time range", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the average, p50/p90/p99 and maximum batch prove times of each proof type (SGX, ZK or
+/// guardian)
+pub async fn prove_times_by_proof_type(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<ProofTypeProveTimesResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_prove_times_by_proof_type(time_range)
+        .await
+        .map_err(|e| query_error("prove times by proof type", e))?;
+
+    let proof_types: Vec<ProofTypeProveTimesItem> = rows
+        .into_iter()
+        .map(|r| ProofTypeProveTimesItem {
+            proof_type: ProofType::from_code(r.proof_type).as_str().to_owned(),
+            batches: r.batches,
+            avg_ms: r.avg_ms,
+            p50_ms: r.p50_ms,
+            p90_ms: r.p90_ms,
+            p99_ms: r.p99_ms,
+            max_ms: r.max_ms,
+        })
+        .collect();
+    tracing::info!(count = proof_types.len(), "Returning prove times by proof type");
+    Ok(Json(ProofTypeProveTimesResponse { proof_types }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/verify-time-percentiles",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Daily batch verify time percentiles", body = TimePercentilesResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the daily p50/p90/p99 and maximum batch verify times
+pub async fn verify_time_percentiles(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<TimePercentilesResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_verify_time_percentiles(time_range)
+        .await
+        .map_err(|e| query_error("verify time percentiles", e))?;
+
+    let response = time_percentiles_response(rows);
+    tracing::info!(count = response.days.len(), "Returning verify time percentiles");
+    Ok(Json(response))
+}
+
+/// Share of `scheduled_secs` not spent in downtime, in percent
+fn uptime_pct(scheduled_secs: u64, downtime_secs: u64) -> f64 {
+    if scheduled_secs == 0 {
+        return 100.0;
+    }
+    let up = scheduled_secs.saturating_sub(downtime_secs);
+    up as f64 * 100.0 / scheduled_secs as f64
+}
+
+#[utoipa::path(
+    get,
+    path = "/sequencer-uptime",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Daily uptime of every scheduled sequencer", body = SequencerUptimeResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the share of their scheduled time each sequencer was producing blocks, per day
+pub async fn sequencer_uptime(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<SequencerUptimeResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_sequencer_uptime(time_range)
+        .await
+        .map_err(|e| query_error("sequencer uptime", e))?;
+
+    let days: Vec<SequencerUptimeItem> = rows
+        .into_iter()
+        .map(|r| SequencerUptimeItem {
+            day: Utc.timestamp_opt(r.day_ts as i64, 0).single().unwrap_or_default(),
+            sequencer: format_address(r.sequencer),
+            scheduled_secs: r.scheduled_secs,
+            downtime_secs: r.downtime_secs,
+            downtime_intervals: r.downtime_intervals,
+            uptime_pct: uptime_pct(r.scheduled_secs, r.downtime_secs),
+        })
+        .collect();
+
+    tracing::info!(count = days.len(), "Returning sequencer uptime");
+    Ok(Json(SequencerUptimeResponse { days }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/mempool-stats",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Per-minute L2 mempool depth and inclusion latency", body = MempoolStatsResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the L2 mempool depth and the time from first seen to inclusion, per minute
+pub async fn mempool_stats(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<MempoolStatsResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_mempool_stats(time_range)
+        .await
+        .map_err(|e| query_error("mempool stats", e))?;
+
+    let minutes: Vec<MempoolStatsItem> = rows
+        .into_iter()
+        .map(|r| MempoolStatsItem {
+            minute: Utc.timestamp_opt(r.minute_ts as i64, 0).single().unwrap_or_default(),
+            seen_txs: r.seen_txs,
+            included_txs: r.included_txs,
+            max_depth: r.max_depth,
+            end_depth: r.end_depth,
+            avg_inclusion_ms: r.avg_inclusion_ms,
+            p50_inclusion_ms: r.p50_inclusion_ms,
+            p95_inclusion_ms: r.p95_inclusion_ms,
+            max_inclusion_ms: r.max_inclusion_ms,
+        })
+        .collect();
+
+    tracing::info!(count = minutes.len(), "Returning mempool stats");
+    Ok(Json(MempoolStatsResponse { minutes }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/anomalies",
+    params(
+        AnomalyQuery
+    ),
+    responses(
+        (status = 200, description = "Sudden changes of TPS, gas used, batch cadence and prove times", body = AnomaliesResponse),
+        (status = 400, description = "Invalid metric", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the samples of core metrics that deviated sharply from their moving average, used to
+/// annotate the dashboard charts
+pub async fn anomalies(
+    Query(params): Query<AnomalyQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<AnomaliesResponse>, ErrorResponse> {
+    params.common.time_range.validate()?;
+    let has_time_range = params.common.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+    let metric = validate_anomaly_metric(params.metric.as_deref())?;
+
+    let (since, until) = params.common.time_range.bounds();
+    let rows = state
+        .client
+        .get_anomalies_range(since, until)
+        .await
+        .map_err(|e| query_error("anomalies", e))?;
+
+    let anomalies: Vec<AnomalyItem> = rows
+        .into_iter()
+        .filter_map(|r| {
+            let row_metric = AnomalyMetric::from_code(r.metric)?;
+            metric.is_none_or(|m| m == row_metric).then(|| AnomalyItem {
+                metric: row_metric.as_str().to_owned(),
+                detected_ts: r.detected_ts,
+                value: r.value,
+                expected: r.expected,
+                std_dev: r.std_dev,
+                z_score: r.z_score,
+            })
+        })
+        .collect();
+
+    tracing::info!(count = anomalies.len(), "Returning anomalies");
+    Ok(Json(AnomaliesResponse { anomalies }))
 }
 
 #[utoipa::path(
@@ -67,7 +716,7 @@ pub async fn prove_costs(
     ),
     responses(
         (status = 200, description = "Aggregated dashboard data", body = DashboardDataResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -75,16 +724,18 @@ pub async fn prove_costs(
 pub async fn dashboard_data(
     Query(params): Query<RangeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<DashboardDataResponse>, ErrorResponse> {
-    validate_time_range(&params.time_range)?;
+) -> Result<Response, ErrorResponse> {
+    params.time_range.validate()?;
 
-    let has_time_range = has_time_range_params(&params.time_range);
+    let has_time_range = params.time_range.is_set();
     validate_range_exclusivity(has_time_range, false)?;
 
-    let time_range = resolve_time_range_enum(&params.time_range);
-    let since = resolve_time_range_since(&params.time_range);
+    let time_range = params.time_range.to_time_range();
+    let since = params.time_range.since();
     let address = parse_optional_address(params.address.as_ref()).ok().flatten();
 
+    let client = &state.client;
+    let budget = QueryBudgetTracker::new(state.query_budget());
     let (
         l2_block_cadence,
         batch_posting_cadence,
@@ -98,53 +749,355 @@ pub async fn dashboard_data(
         failed_proposals,
         l2_head_block,
         l1_head_block,
-    ) = tokio::try_join!(
-        state.client.get_l2_block_cadence(address, time_range),
-        state.client.get_batch_posting_cadence(time_range),
-        state.client.get_avg_prove_time(time_range),
-        state.client.get_avg_verify_time(time_range),
-        state.client.get_avg_l2_tps(address, time_range),
-        state.client.get_last_preconf_data(),
-        state.client.get_l2_reorgs_since(since),
-        state.client.get_slashing_events_since(since),
-        state.client.get_forced_inclusions_since(since),
-        state.client.get_failed_proposals_since(since),
-        state.client.get_last_l2_block_number(),
-        state.client.get_last_l1_block_number()
-    )
-    .map_err(|e| {
-        tracing::error!(error = %e, "Failed to get dashboard data");
-        ErrorResponse::database_error()
-    })?;
+    ) = tokio::join!(
+        dashboard_section(
+            &budget,
+            "l2_block_cadence_ms",
+            client.get_l2_block_cadence(address, time_range)
+        ),
+        dashboard_section(
+            &budget,
+            "batch_posting_cadence_ms",
+            client.get_batch_posting_cadence(time_range)
+        ),
+        dashboard_section(&budget, "avg_prove_time_ms", client.get_avg_prove_time(time_range)),
+        dashboard_section(&budget, "avg_verify_time_ms", client.get_avg_verify_time(time_range)),
+        dashboard_section(&budget, "avg_tps", client.get_avg_l2_tps(address, time_range)),
+        dashboard_section(&budget, "preconf_data", client.get_last_preconf_data()),
+        dashboard_section(&budget, "l2_reorgs", client.get_l2_reorgs_since(since)),
+        dashboard_section(&budget, "slashings", client.get_slashing_events_since(since)),
+        dashboard_section(&budget, "forced_inclusions", client.get_forced_inclusions_since(since)),
+        dashboard_section(&budget, "failed_proposals", client.get_failed_proposals_since(since)),
+        dashboard_section(&budget, "l2_head_block", client.get_last_l2_block_number()),
+        dashboard_section(&budget, "l1_head_block", client.get_last_l1_block_number())
+    );
+
+    let mut errors = Vec::new();
+    let response = DashboardDataResponse {
+        l2_block_cadence_ms: section_value(l2_block_cadence, &mut errors).flatten(),
+        batch_posting_cadence_ms: section_value(batch_posting_cadence, &mut errors).flatten(),
+        avg_prove_time_ms: section_value(avg_prove_time, &mut errors).flatten(),
+        avg_verify_time_ms: section_value(avg_verify_time, &mut errors).flatten(),
+        avg_tps: section_value(avg_tps, &mut errors).flatten(),
+        preconf_data: section_value(preconf, &mut errors).flatten().map(|d| PreconfDataResponse {
+            candidates: d.candidates.into_iter().map(format_address).collect(),
+            current_operator: d.current_operator.map(format_address),
+            next_operator: d.next_operator.map(format_address),
+        }),
+        l2_reorgs: section_value(reorgs, &mut errors).map(|r| r.len()),
+        slashings: section_value(slashings, &mut errors).map(|r| r.len()),
+        forced_inclusions: section_value(forced_inclusions, &mut errors).map(|r| r.len()),
+        failed_proposals: section_value(failed_proposals, &mut errors).map(|r| r.len()),
+        l2_head_block: section_value(l2_head_block, &mut errors).flatten(),
+        l1_head_block: section_value(l1_head_block, &mut errors).flatten(),
+        errors,
+        budget_exhausted: budget.is_exhausted(),
+    };
 
-    let preconf_data = preconf.map(|d| PreconfDataResponse {
-        candidates: d.candidates.into_iter().map(format_address).collect(),
-        current_operator: d.current_operator.map(format_address),
-        next_operator: d.next_operator.map(format_address),
-    });
+    if response.errors.len() == DASHBOARD_SECTIONS && !response.budget_exhausted {
+        tracing::error!("Failed to get dashboard data");
+        return Err(ErrorResponse::database_error());
+    }
 
     tracing::info!(
-        l2_head_block,
-        l1_head_block,
-        reorgs = reorgs.len(),
-        slashings = slashings.len(),
-        forced_inclusions = forced_inclusions.len(),
-        failed_proposals = failed_proposals.len(),
+        l2_head_block = response.l2_head_block,
+        l1_head_block = response.l1_head_block,
+        reorgs = response.l2_reorgs,
+        slashings = response.slashings,
+        forced_inclusions = response.forced_inclusions,
+        failed_proposals = response.failed_proposals,
+        failed_sections = response.errors.len(),
+        budget_exhausted = response.budget_exhausted,
         "Returning dashboard data"
     );
 
-    Ok(Json(DashboardDataResponse {
-        l2_block_cadence_ms: l2_block_cadence,
-        batch_posting_cadence_ms: batch_posting_cadence,
-        avg_prove_time_ms: avg_prove_time,
-        avg_verify_time_ms: avg_verify_time,
-        avg_tps,
-        preconf_data,
-        l2_reorgs: reorgs.len(),
-        slashings: slashings.len(),
-        forced_inclusions: forced_inclusions.len(),
-        failed_proposals: failed_proposals.len(),
-        l2_head_block,
-        l1_head_block,
+    // Partial data is not cached, so the next request retries the failed sections
+    if !response.errors.is_empty() || response.budget_exhausted {
+        return Ok(([(header::CACHE_CONTROL, "no-store")], Json(response)).into_response());
+    }
+    Ok(Json(response).into_response())
+}
+
+/// Number of independently loaded sections of the dashboard data response
+const DASHBOARD_SECTIONS: usize = 12;
+
+/// Load one dashboard section, giving up after [`DASHBOARD_SECTION_TIMEOUT`] so a slow aggregate
+/// leaves only its own section empty. Sections are skipped, or aborted while running, once the
+/// request's query budget is exhausted.
+#[allow(clippy::cognitive_complexity)]
+async fn dashboard_section<T>(
+    budget: &QueryBudgetTracker,
+    section: &'static str,
+    query: impl Future<Output = eyre::Result<T>>,
+) -> Result<T, DashboardSectionError> {
+    let error = match budget.acquire() {
+        None => {
+            tracing::warn!(section, "Query budget exhausted, skipping dashboard section");
+            "query budget exhausted"
+        }
+        Some(time_left) => {
+            let timeout = DASHBOARD_SECTION_TIMEOUT.min(time_left);
+            match tokio::time::timeout(timeout, query).await {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(e)) => {
+                    tracing::error!(section, error = %e, "Failed to get dashboard section");
+                    "database error"
+                }
+                Err(_) if timeout < DASHBOARD_SECTION_TIMEOUT => {
+                    budget.exhaust();
+                    tracing::warn!(
+                        section,
+                        timeout_ms = timeout.as_millis(),
+                        "Query budget exhausted, aborted dashboard section"
+                    );
+                    "query budget exhausted"
+                }
+                Err(_) => {
+                    tracing::warn!(
+                        section,
+                        timeout_ms = DASHBOARD_SECTION_TIMEOUT.as_millis(),
+                        "Dashboard section timed out"
+                    );
+                    "timed out"
+                }
+            }
+        }
+    };
+    Err(DashboardSectionError { section: section.to_owned(), error: error.to_owned() })
+}
+
+/// Value of a loaded dashboard section, recording the error note if it failed.
+fn section_value<T>(
+    result: Result<T, DashboardSectionError>,
+    errors: &mut Vec<DashboardSectionError>,
+) -> Option<T> {
+    result.map_err(|e| errors.push(e)).ok()
+}
+
+#[utoipa::path(
+    get,
+    path = "/leaderboards",
+    params(
+        LeaderboardQuery
+    ),
+    responses(
+        (status = 200, description = "Top blocks, slowest proofs and sequencer rankings", body = LeaderboardsResponse),
+        (status = 400, description = "Invalid parameters", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get top-N leaderboards of blocks, proofs and sequencers for the selected time range
+pub async fn leaderboards(
+    Query(params): Query<LeaderboardQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<LeaderboardsResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let limit =
+        validate_limit(params.limit.as_ref(), DEFAULT_LEADERBOARD_LIMIT, MAX_LEADERBOARD_LIMIT)?;
+    let (since, until) = params.time_range.bounds();
+
+    let (by_gas, by_tx_count, slowest_proofs, sequencers) = tokio::try_join!(
+        state.client.get_top_l2_blocks_by_gas(since, until, limit),
+        state.client.get_top_l2_blocks_by_tx_count(since, until, limit),
+        state.client.get_slowest_prove_times(since, until, limit),
+        state.client.get_sequencer_leaderboard(since, until)
+    )
+    .map_err(|e| query_error("leaderboards", e))?;
+
+    let block_item = |r: L2BlockLeaderboardRow| LeaderboardBlockItem {
+        block_number: r.l2_block_number,
+        block_time: Utc.timestamp_opt(r.block_ts as i64, 0).single().unwrap_or_default(),
+        sequencer: format_address(r.sequencer),
+        gas_used: r.gas_used,
+        tx_count: r.tx_count,
+    };
+    let sequencer_item = |r: &SequencerLeaderboardRow| LeaderboardSequencerItem {
+        address: format_address(r.sequencer),
+        blocks: r.blocks,
+        priority_fee: r.priority_fee,
+        base_fee: r.base_fee,
+    };
+
+    let limit = limit as usize;
+    let sequencers_by_blocks = sequencers.iter().take(limit).map(sequencer_item).collect();
+    let mut by_fees: Vec<&SequencerLeaderboardRow> = sequencers.iter().collect();
+    by_fees.sort_by_key(|s| Reverse(s.priority_fee + s.base_fee));
+    let sequencers_by_fees = by_fees.into_iter().take(limit).map(sequencer_item).collect();
+
+    Ok(Json(LeaderboardsResponse {
+        top_blocks_by_gas: by_gas.into_iter().map(block_item).collect(),
+        top_blocks_by_tx_count: by_tx_count.into_iter().map(block_item).collect(),
+        slowest_proofs,
+        sequencers_by_blocks,
+        sequencers_by_fees,
     }))
 }
+
+#[utoipa::path(
+    get,
+    path = "/reorg-stats",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Reorg depth histogram, orphan rate and reorg frequency", body = ReorgStatsResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the reorg depth distribution, orphaned block rate and mean time between reorgs
+pub async fn reorg_stats(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<ReorgStatsResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let (since, until) = params.time_range.bounds();
+
+    let (histogram, totals) = tokio::try_join!(
+        state.client.get_reorg_depth_histogram(since, until),
+        state.client.get_reorg_totals(since, until),
+    )
+    .map_err(|e| query_error("reorg stats", e))?;
+
+    let orphan_rate_per_1000 = (totals.l2_blocks > 0)
+        .then(|| totals.orphaned_blocks as f64 * 1000.0 / totals.l2_blocks as f64);
+    let mean_time_between_reorgs_ms = (totals.reorgs > 1)
+        .then(|| totals.last_reorg_ms.saturating_sub(totals.first_reorg_ms) / (totals.reorgs - 1));
+    let depth_histogram = histogram
+        .into_iter()
+        .map(|r| ReorgDepthBucket { depth: r.depth, count: r.count })
+        .collect();
+
+    tracing::info!(reorgs = totals.reorgs, "Returning reorg stats");
+    Ok(Json(ReorgStatsResponse {
+        reorgs: totals.reorgs,
+        orphaned_blocks: totals.orphaned_blocks,
+        l2_blocks: totals.l2_blocks,
+        orphan_rate_per_1000,
+        mean_time_between_reorgs_ms,
+        depth_histogram,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(
+        batch_id: u64,
+        blob_count: u8,
+        blob_total_bytes: u32,
+        blocks: u16,
+        txs: u64,
+    ) -> BatchEfficiencyRow {
+        BatchEfficiencyRow {
+            batch_id,
+            l1_block_number: 100 + batch_id,
+            blob_count,
+            blob_total_bytes,
+            da_mode: if blob_count == 0 { DaMode::Calldata } else { DaMode::Blob } as u8,
+            calldata_bytes: if blob_count == 0 { 2_048 } else { 0 },
+            blocks,
+            txs,
+        }
+    }
+
+    #[test]
+    fn batch_efficiency_summarizes_batches() {
+        let response = batch_efficiency_response(
+            vec![row(1, 1, 65_536, 4, 10), row(2, 2, 131_072, 4, 30), row(3, 0, 0, 8, 20)],
+            vec![DaModeShareRow {
+                bucket_ts: 3_600,
+                blob_batches: 2,
+                calldata_batches: 1,
+                blob_bytes: 196_608,
+                calldata_bytes: 2_048,
+            }],
+        );
+
+        assert_eq!(response.batches, 3);
+        assert_eq!(response.blob_utilization, Some(0.5));
+        assert_eq!(response.avg_blocks_per_batch, Some(16.0 / 3.0));
+        assert_eq!(response.avg_txs_per_batch, Some(20.0));
+        let distribution: Vec<_> =
+            response.blocks_per_batch.iter().map(|b| (b.blocks, b.batches)).collect();
+        assert_eq!(distribution, vec![(4, 2), (8, 1)]);
+        assert_eq!(response.items[0].blob_utilization, Some(0.5));
+        assert_eq!(response.items[2].blob_utilization, None);
+        assert_eq!(response.items[0].da_mode, "blob");
+        assert_eq!(response.items[2].da_mode, "calldata");
+        assert_eq!(response.items[2].calldata_bytes, 2_048);
+        assert_eq!(response.da_mode_share[0].calldata_share, Some(1.0 / 3.0));
+    }
+
+    #[test]
+    fn uptime_is_share_of_scheduled_time() {
+        assert_eq!(uptime_pct(3_600, 360), 90.0);
+        assert_eq!(uptime_pct(3_600, 0), 100.0);
+        assert_eq!(uptime_pct(0, 0), 100.0);
+        assert_eq!(uptime_pct(100, 200), 0.0);
+    }
+
+    #[test]
+    fn protocol_gas_sums_operations() {
+        let row = |operation: &str, address: u8, txs: u64, cost: u128| ProtocolGasSpendRow {
+            operation: operation.to_owned(),
+            address: clickhouse_lib::AddressBytes([address; 20]),
+            txs,
+            gas_used: txs * 100_000,
+            cost,
+        };
+        let response = protocol_gas_response(vec![
+            row("prove", 1, 2, 3_000_000_000),
+            row("propose", 2, 4, 2_000_000_000),
+            row("propose", 3, 1, 2_000_000_000),
+        ]);
+
+        assert_eq!(response.total_cost, 7);
+        let operations: Vec<_> = response
+            .operations
+            .iter()
+            .map(|o| (o.operation.as_str(), o.txs, o.gas_used, o.cost))
+            .collect();
+        assert_eq!(operations, vec![("propose", 5, 500_000, 4), ("prove", 2, 200_000, 3)]);
+        assert_eq!(response.addresses.len(), 3);
+        assert_eq!(response.addresses[0].cost, 3);
+    }
+
+    #[test]
+    fn batch_efficiency_handles_empty_range() {
+        let response = batch_efficiency_response(Vec::new(), Vec::new());
+        assert_eq!(response.batches, 0);
+        assert_eq!(response.blob_utilization, None);
+        assert_eq!(response.avg_txs_per_batch, None);
+        assert!(response.items.is_empty());
+        assert!(response.da_mode_share.is_empty());
+    }
+
+    #[tokio::test]
+    async fn dashboard_sections_stop_when_budget_is_exhausted() {
+        use crate::budget::QueryBudget;
+        use std::time::Duration as StdDuration;
+
+        let budget = QueryBudgetTracker::new(QueryBudget { max_queries: 1, ..Default::default() });
+        assert_eq!(dashboard_section(&budget, "first", async { Ok(1) }).await.unwrap(), 1);
+        let skipped = dashboard_section(&budget, "second", async { Ok(2) }).await.unwrap_err();
+        assert_eq!(skipped.error, "query budget exhausted");
+        assert!(budget.is_exhausted());
+
+        let budget = QueryBudgetTracker::new(QueryBudget {
+            max_queries: 0,
+            max_time: StdDuration::from_millis(10),
+        });
+        let slow = async {
+            tokio::time::sleep(StdDuration::from_secs(1)).await;
+            Ok(())
+        };
+        let aborted = dashboard_section(&budget, "slow", slow).await.unwrap_err();
+        assert_eq!(aborted.section, "slow");
+        assert_eq!(aborted.error, "query budget exhausted");
+        assert!(budget.is_exhausted());
+    }
+}
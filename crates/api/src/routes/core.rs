@@ -1,32 +1,52 @@
 //! Core simple API endpoints
 
 use crate::{
+    extract::{Path, Query},
     helpers::{
-        database_error, format_address, parse_address, prove_bucket_size, query_error,
-        verify_bucket_size, wei_to_gwei, wei_to_gwei_opt,
+        PageResponse, RowCount, address_labels, aggregate_builder_distribution, database_error,
+        format_address, format_address_bytes, format_tx_hash, net_revenue_gwei,
+        operator_schedule_response, paginate, parse_address, parse_tx_hash, prove_bucket_size,
+        query_error, shared_base_fee, summarize_handovers, summarize_proposal_inclusions,
+        time_bucket_secs_from_range, unpaged, verify_bucket_size, wei_to_gwei, wei_to_gwei_opt,
+    },
+    state::{
+        ApiState, DEFAULT_BASE_FEE_SHARING_PCTG, DEFAULT_BLOCK_STATUS_WINDOW,
+        DEFAULT_BOND_HISTORY_LIMIT, MAX_BATCH_BLOCKS_LIMIT, MAX_BLOCK_STATUS_WINDOW,
+        MAX_TABLE_LIMIT,
     },
-    state::{ApiState, MAX_TABLE_LIMIT},
     validation::{
-        CommonQuery, PaginatedQuery, QueryMode, UnifiedQuery, has_time_range_params,
-        resolve_time_range_bounds, resolve_time_range_enum, resolve_time_range_since,
-        validate_pagination, validate_range_exclusivity, validate_time_range,
-        validate_unified_query,
+        AsOfQuery, BatchBlocksQuery, BlockStatusSummaryQuery, BondHistoryQuery, BucketBy,
+        BucketQuery, CommonQuery, EnvelopeQuery, IncidentHistoryQuery, PaginatedQuery, QueryMode,
+        UnifiedQuery, resolve_as_of, validate_bucket_by, validate_incident_action, validate_limit,
+        validate_range_exclusivity, validate_unified_query,
     },
 };
 use alloy_primitives::B256;
 use api_types::{
-    BatchFeeComponentRow, BatchPostingTimesResponse, ErrorResponse, EthPriceResponse,
-    L1BlockTimesResponse, L1DataCostResponse, L1HeadBlockResponse, L2FeesComponentsResponse,
-    L2HeadBlockResponse, PreconfDataResponse, ProveCostResponse, ProveTimesResponse,
-    SequencerBlocksItem, SequencerBlocksResponse, SequencerDistributionItem,
-    SequencerDistributionResponse, SequencerFeeRow, VerifyTimesResponse,
+    BackfillStatusItem, BackfillStatusResponse, BatchBlockItem, BatchBlocksResponse,
+    BatchFeeComponentRow, BatchPostingTimesResponse, BatchProveTimeItem, BatchVerifyTimeItem,
+    BlockStatusResponse, BlockStatusSummaryResponse, BondBalanceItem, BondBalancesResponse,
+    BondHistoryItem, BondHistoryResponse, BuilderDistributionResponse, DataQualityResponse,
+    ErrorCode, ErrorResponse, EthPriceResponse, FeeTimeBucketItem, FinalityStage,
+    ForcedInclusionQueueItem, ForcedInclusionQueueResponse, HandoverLatencyResponse,
+    IncidentHistoryResponse, IncidentLogItem, L1BlockTimesResponse, L1DataCostResponse,
+    L1HeadBlockResponse, L2FeesComponentsResponse, L2HeadBlockResponse, LeaderChangeItem,
+    LeaderChangesResponse, OperatorBalanceItem, OperatorBalancesResponse, OperatorScheduleResponse,
+    Paginated, PipelineLatencyItem, PipelineLatencyResponse, PreconfDataResponse,
+    PropagationDelayItem, PropagationDelayResponse, ProposalInclusionDelayResponse,
+    ProtocolConfigItem, ProtocolConfigResponse, ProveCostResponse, ProveTimesResponse,
+    RpcStatusItem, RpcStatusResponse, SequencerBlocksItem, SequencerBlocksResponse,
+    SequencerDistributionItem, SequencerDistributionResponse, SequencerFeeRow, TxLookupResponse,
+    TxRole, TxRoleItem, VerificationBacklogItem, VerificationBacklogResponse, VerifyTimesResponse,
 };
-use axum::{
-    Json,
-    extract::{Query, State},
-    http::StatusCode,
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::{TimeZone, Utc};
+use clickhouse_lib::{
+    BatchPostingTimeRow, BatchProveTimeRow, BatchTxHashesRow, BatchVerifyTimeRow,
+    ForcedInclusionQueueRow, L1DataCostRow, ProveCostRow, VerificationBacklogRow,
 };
-use clickhouse_lib::{L1DataCostRow, ProveCostRow};
+use primitives::{incident::IncidentAction, tx_role::L1TxRole};
+use std::collections::HashMap;
 
 // Legacy type aliases for backward compatibility
 type RangeQuery = CommonQuery;
@@ -36,7 +56,7 @@ type RangeQuery = CommonQuery;
     path = "/l2-head-block",
     responses(
         (status = 200, description = "L2 head block number", body = L2HeadBlockResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -57,7 +77,7 @@ pub async fn l2_head_block(
     path = "/l1-head-block",
     responses(
         (status = 200, description = "L1 head block number", body = L1HeadBlockResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -78,7 +98,7 @@ pub async fn l1_head_block(
     path = "/preconf-data",
     responses(
         (status = 200, description = "Latest preconfiguration data", body = PreconfDataResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -104,15 +124,58 @@ pub async fn preconf_data(
     Ok(Json(resp))
 }
 
+#[utoipa::path(
+    get,
+    path = "/protocol-config",
+    responses(
+        (status = 200, description = "Latest TaikoInbox protocol configuration", body = ProtocolConfigResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the `TaikoInbox` protocol configuration most recently read by the driver
+pub async fn protocol_config(
+    State(state): State<ApiState>,
+) -> Result<Json<ProtocolConfigResponse>, ErrorResponse> {
+    let config = state
+        .client
+        .get_protocol_config()
+        .await
+        .map_err(|e| database_error("get protocol config", e))?;
+
+    let config = config.map(|c| ProtocolConfigItem {
+        inbox_address: format_address(c.inbox_address),
+        chain_id: c.chain_id,
+        max_unverified_batches: c.max_unverified_batches,
+        batch_ring_buffer_size: c.batch_ring_buffer_size,
+        max_batches_to_verify: c.max_batches_to_verify,
+        block_max_gas_limit: c.block_max_gas_limit,
+        liveness_bond_base: c.liveness_bond_base,
+        liveness_bond_per_block: c.liveness_bond_per_block,
+        state_root_sync_internal: c.state_root_sync_internal,
+        max_anchor_height_offset: c.max_anchor_height_offset,
+        base_fee_sharing_pctg: c.base_fee_sharing_pctg,
+        proving_window: c.proving_window,
+        cooldown_window: c.cooldown_window,
+        max_signals_to_receive: c.max_signals_to_receive,
+        max_blocks_per_batch: c.max_blocks_per_batch,
+        ontake_fork_height: c.ontake_fork_height,
+        pacaya_fork_height: c.pacaya_fork_height,
+    });
+
+    Ok(Json(ProtocolConfigResponse { config }))
+}
+
 #[utoipa::path(
     get,
     path = "/batch-posting-times",
     params(
-        PaginatedQuery
+        PaginatedQuery,
+        EnvelopeQuery
     ),
     responses(
-        (status = 200, description = "Batch posting times", body = BatchPostingTimesResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "Batch posting times", body = Paginated<BatchPostingTimeRow>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -121,30 +184,26 @@ pub async fn preconf_data(
 /// Results are ordered by batch id in descending order.
 pub async fn batch_posting_times(
     Query(params): Query<PaginatedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<BatchPostingTimesResponse>, ErrorResponse> {
+) -> Result<PageResponse<BatchPostingTimeRow, BatchPostingTimesResponse>, ErrorResponse> {
     // Validate time range parameters
-    validate_time_range(&params.common.time_range)?;
+    params.common.time_range.validate()?;
 
     // Check for range exclusivity
-    let limit = validate_pagination(
-        params.starting_after.as_ref(),
-        params.ending_before.as_ref(),
-        params.limit.as_ref(),
-        MAX_TABLE_LIMIT,
-    )?;
-    let has_time_range = has_time_range_params(&params.common.time_range);
+    let page = params.page(MAX_TABLE_LIMIT)?;
+    let has_time_range = params.common.time_range.is_set();
     let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
     validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-    let since = resolve_time_range_since(&params.common.time_range);
+    let since = params.common.time_range.since();
     let rows = match state
         .client
         .get_batch_posting_times_paginated(
             since,
-            limit,
-            params.starting_after,
-            params.ending_before,
+            page.limit,
+            page.starting_after,
+            page.ending_before,
         )
         .await
     {
@@ -152,63 +211,95 @@ pub async fn batch_posting_times(
         Err(e) => return Err(query_error("batch posting times", e)),
     };
     tracing::info!(count = rows.len(), "Returning batch posting times");
-    Ok(Json(BatchPostingTimesResponse { batches: rows }))
+    let count = RowCount::since("batches", since);
+    Ok(paginate(
+        &state,
+        &envelope,
+        &page,
+        rows,
+        |r| r.batch_id,
+        Some(count),
+        |batches, next_cursor, prev_cursor| BatchPostingTimesResponse {
+            batches,
+            next_cursor,
+            prev_cursor,
+        },
+    )
+    .await)
 }
 
 #[utoipa::path(
     get,
     path = "/prove-times",
     params(
-        UnifiedQuery
+        UnifiedQuery,
+        EnvelopeQuery,
+        AsOfQuery
     ),
     responses(
-        (status = 200, description = "Prove times (regular or aggregated)", body = ProveTimesResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "Prove times (regular or aggregated)", body = Paginated<BatchProveTimeItem>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
 /// Get batch proving time metrics.
 ///
 /// Use ?aggregated for aggregated data with automatic bucketing based on time range.
-/// Without ?aggregated, returns paginated results ordered by batch id in descending order.
+/// Without ?aggregated, returns paginated results ordered by batch id in descending order,
+/// with the L1 transactions that proposed and proved each batch.
+/// In aggregated mode, `as_of` evaluates the prove times as they were known at that moment.
 #[allow(clippy::cognitive_complexity)]
 pub async fn prove_times(
     Query(params): Query<UnifiedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
+    Query(as_of): Query<AsOfQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<ProveTimesResponse>, ErrorResponse> {
+) -> Result<PageResponse<BatchProveTimeItem, ProveTimesResponse>, ErrorResponse> {
     let query_mode = validate_unified_query(&params, MAX_TABLE_LIMIT)?;
+    let as_of = resolve_as_of(&as_of)?;
 
     match query_mode {
         QueryMode::Aggregated => {
             // Aggregated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             validate_range_exclusivity(has_time_range, false)?;
 
-            let time_range = resolve_time_range_enum(&params.common.time_range);
+            let now = as_of.unwrap_or_else(Utc::now);
+            let time_range = params.common.time_range.to_time_range_at(now);
             let bucket = prove_bucket_size(&time_range);
-            let batches = match state.client.get_prove_times(time_range, Some(bucket)).await {
+            let batches = match state.client.get_prove_times(time_range, Some(bucket), as_of).await
+            {
                 Ok(rows) => rows,
                 Err(e) => return Err(query_error("prove times", e)),
             };
             tracing::info!(count = batches.len(), "Returning aggregated prove times");
-            Ok(Json(ProveTimesResponse { batches }))
+            let batches = prove_time_items(batches, &HashMap::new());
+            Ok(unpaged(&envelope, batches, |batches| ProveTimesResponse {
+                batches,
+                next_cursor: None,
+                prev_cursor: None,
+            }))
         }
-        QueryMode::Regular { limit } => {
+        QueryMode::Regular { .. } if as_of.is_some() => Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
+            "as_of is only supported in aggregated mode",
+        )),
+        QueryMode::Regular { page } => {
             // Regular paginated mode
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
             validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-            let since = resolve_time_range_since(&params.common.time_range);
+            let since = params.common.time_range.since();
             let batches = match state
                 .client
                 .get_prove_times_paginated(
                     since,
-                    limit,
-                    params.starting_after,
-                    params.ending_before,
+                    page.limit,
+                    page.starting_after,
+                    page.ending_before,
                 )
                 .await
             {
@@ -216,7 +307,23 @@ pub async fn prove_times(
                 Err(e) => return Err(query_error("prove times", e)),
             };
             tracing::info!(count = batches.len(), "Returning paginated prove times");
-            Ok(Json(ProveTimesResponse { batches }))
+            let tx_hashes = batch_tx_hashes(&state, batches.iter().map(|r| r.batch_id)).await;
+            let batches = prove_time_items(batches, &tx_hashes);
+            let count = RowCount::since("proved_batches", since);
+            Ok(paginate(
+                &state,
+                &envelope,
+                &page,
+                batches,
+                |r| r.batch_id,
+                Some(count),
+                |batches, next_cursor, prev_cursor| ProveTimesResponse {
+                    batches,
+                    next_cursor,
+                    prev_cursor,
+                },
+            )
+            .await)
         }
     }
 }
@@ -225,56 +332,64 @@ pub async fn prove_times(
     get,
     path = "/verify-times",
     params(
-        UnifiedQuery
+        UnifiedQuery,
+        EnvelopeQuery
     ),
     responses(
-        (status = 200, description = "Verify times (regular or aggregated)", body = VerifyTimesResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "Verify times (regular or aggregated)", body = Paginated<BatchVerifyTimeItem>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
 /// Get batch verification time metrics.
 ///
 /// Use ?aggregated for aggregated data with automatic bucketing based on time range.
-/// Without ?aggregated, returns paginated results ordered by batch id in descending order.
+/// Without ?aggregated, returns paginated results ordered by batch id in descending order,
+/// with the L1 transactions that proved and verified each batch.
 #[allow(clippy::cognitive_complexity)]
 pub async fn verify_times(
     Query(params): Query<UnifiedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<VerifyTimesResponse>, ErrorResponse> {
+) -> Result<PageResponse<BatchVerifyTimeItem, VerifyTimesResponse>, ErrorResponse> {
     let query_mode = validate_unified_query(&params, MAX_TABLE_LIMIT)?;
 
     match query_mode {
         QueryMode::Aggregated => {
             // Aggregated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             validate_range_exclusivity(has_time_range, false)?;
 
-            let time_range = resolve_time_range_enum(&params.common.time_range);
+            let time_range = params.common.time_range.to_time_range();
             let bucket = verify_bucket_size(&time_range);
             let batches = match state.client.get_verify_times(time_range, Some(bucket)).await {
                 Ok(rows) => rows,
                 Err(e) => return Err(query_error("verify times", e)),
             };
             tracing::info!(count = batches.len(), "Returning aggregated verify times");
-            Ok(Json(VerifyTimesResponse { batches }))
+            let batches = verify_time_items(batches, &HashMap::new());
+            Ok(unpaged(&envelope, batches, |batches| VerifyTimesResponse {
+                batches,
+                next_cursor: None,
+                prev_cursor: None,
+            }))
         }
-        QueryMode::Regular { limit } => {
+        QueryMode::Regular { page } => {
             // Regular paginated mode
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
             validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-            let since = resolve_time_range_since(&params.common.time_range);
+            let since = params.common.time_range.since();
             let batches = match state
                 .client
                 .get_verify_times_paginated(
                     since,
-                    limit,
-                    params.starting_after,
-                    params.ending_before,
+                    page.limit,
+                    page.starting_after,
+                    page.ending_before,
                 )
                 .await
             {
@@ -282,11 +397,80 @@ pub async fn verify_times(
                 Err(e) => return Err(query_error("verify times", e)),
             };
             tracing::info!(count = batches.len(), "Returning paginated verify times");
-            Ok(Json(VerifyTimesResponse { batches }))
+            let tx_hashes = batch_tx_hashes(&state, batches.iter().map(|r| r.batch_id)).await;
+            let batches = verify_time_items(batches, &tx_hashes);
+            let count = RowCount::since("verified_batches", since);
+            Ok(paginate(
+                &state,
+                &envelope,
+                &page,
+                batches,
+                |r| r.batch_id,
+                Some(count),
+                |batches, next_cursor, prev_cursor| VerifyTimesResponse {
+                    batches,
+                    next_cursor,
+                    prev_cursor,
+                },
+            )
+            .await)
+        }
+    }
+}
+
+/// L1 transactions of the given batches by batch id. A failed lookup is logged and leaves the
+/// hashes out, as the times are still useful without them.
+async fn batch_tx_hashes(
+    state: &ApiState,
+    batch_ids: impl Iterator<Item = u64>,
+) -> HashMap<u64, BatchTxHashesRow> {
+    let batch_ids: Vec<u64> = batch_ids.collect();
+    match state.client.get_batch_tx_hashes(&batch_ids).await {
+        Ok(rows) => rows.into_iter().map(|r| (r.batch_id, r)).collect(),
+        Err(e) => {
+            tracing::warn!(err = %e, "Failed to fetch batch tx hashes");
+            HashMap::new()
         }
     }
 }
 
+/// Prove times with the transactions that proposed and proved each batch
+fn prove_time_items(
+    rows: Vec<BatchProveTimeRow>,
+    tx_hashes: &HashMap<u64, BatchTxHashesRow>,
+) -> Vec<BatchProveTimeItem> {
+    rows.into_iter()
+        .map(|r| {
+            let hashes = tx_hashes.get(&r.batch_id);
+            BatchProveTimeItem {
+                batch_id: r.batch_id,
+                seconds_to_prove: r.seconds_to_prove,
+                proposal_l1_tx_hash: hashes.and_then(|h| format_tx_hash(h.proposal_l1_tx_hash)),
+                proof_l1_tx_hash: hashes.and_then(|h| format_tx_hash(h.proof_l1_tx_hash)),
+            }
+        })
+        .collect()
+}
+
+/// Verify times with the transactions that proved and verified each batch
+fn verify_time_items(
+    rows: Vec<BatchVerifyTimeRow>,
+    tx_hashes: &HashMap<u64, BatchTxHashesRow>,
+) -> Vec<BatchVerifyTimeItem> {
+    rows.into_iter()
+        .map(|r| {
+            let hashes = tx_hashes.get(&r.batch_id);
+            BatchVerifyTimeItem {
+                batch_id: r.batch_id,
+                seconds_to_verify: r.seconds_to_verify,
+                proof_l1_tx_hash: hashes.and_then(|h| format_tx_hash(h.proof_l1_tx_hash)),
+                verification_l1_tx_hash: hashes
+                    .and_then(|h| format_tx_hash(h.verification_l1_tx_hash)),
+            }
+        })
+        .collect()
+}
+
 #[utoipa::path(
     get,
     path = "/l1-block-times",
@@ -295,7 +479,7 @@ pub async fn verify_times(
     ),
     responses(
         (status = 200, description = "L1 block times", body = L1BlockTimesResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -305,13 +489,13 @@ pub async fn l1_block_times(
     State(state): State<ApiState>,
 ) -> Result<Json<L1BlockTimesResponse>, ErrorResponse> {
     // Validate time range parameters
-    validate_time_range(&params.time_range)?;
+    params.time_range.validate()?;
 
     // Check for range exclusivity
-    let has_time_range = has_time_range_params(&params.time_range);
+    let has_time_range = params.time_range.is_set();
     validate_range_exclusivity(has_time_range, false)?;
 
-    let time_range = resolve_time_range_enum(&params.time_range);
+    let time_range = params.time_range.to_time_range();
     let blocks = match state.client.get_l1_block_times(time_range).await {
         Ok(rows) => rows,
         Err(e) => return Err(query_error("L1 block times", e)),
@@ -324,34 +508,40 @@ pub async fn l1_block_times(
     get,
     path = "/sequencer-distribution",
     params(
-        RangeQuery
+        RangeQuery,
+        AsOfQuery
     ),
     responses(
         (status = 200, description = "Sequencer distribution", body = SequencerDistributionResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
-/// Get the distribution of blocks, batches, and TPS across different sequencers
+/// Get the distribution of blocks, batches, and TPS across different sequencers, optionally as
+/// it was at `as_of`
 pub async fn sequencer_distribution(
     Query(params): Query<RangeQuery>,
+    Query(as_of): Query<AsOfQuery>,
     State(state): State<ApiState>,
 ) -> Result<Json<SequencerDistributionResponse>, ErrorResponse> {
+    let as_of = resolve_as_of(&as_of)?;
     // Validate time range parameters
-    validate_time_range(&params.time_range)?;
+    params.time_range.validate()?;
 
     // Check for range exclusivity
-    let has_time_range = has_time_range_params(&params.time_range);
+    let has_time_range = params.time_range.is_set();
     validate_range_exclusivity(has_time_range, false)?;
 
     // Determine the exact start and end timestamps for the range
-    let (since, until) = resolve_time_range_bounds(&params.time_range);
+    let now = as_of.unwrap_or_else(Utc::now);
+    let (since, until) = params.time_range.bounds_at(now);
     // Fetch distribution within the specified window
     let rows = state
         .client
-        .get_sequencer_distribution_range(since, until)
+        .get_sequencer_distribution_range(since, until, as_of)
         .await
         .map_err(|e| query_error("sequencer distribution", e))?;
+    let labels = address_labels(&state.client).await;
     let sequencers: Vec<SequencerDistributionItem> = rows
         .into_iter()
         .map(|r| {
@@ -359,6 +549,7 @@ pub async fn sequencer_distribution(
                 .then(|| r.tx_sum as f64 / (r.max_ts - r.min_ts) as f64);
             SequencerDistributionItem {
                 address: format_address(r.sequencer),
+                label: labels.get(&r.sequencer).cloned(),
                 blocks: r.blocks,
                 batches: r.batches,
                 tps,
@@ -369,6 +560,44 @@ pub async fn sequencer_distribution(
     Ok(Json(SequencerDistributionResponse { sequencers }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/builder-distribution",
+    params(
+        RangeQuery,
+        AsOfQuery
+    ),
+    responses(
+        (status = 200, description = "Builder distribution", body = BuilderDistributionResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the share of L2 blocks built by each builder, identified by the extra data of its
+/// blocks, optionally as it was at `as_of`
+pub async fn builder_distribution(
+    Query(params): Query<RangeQuery>,
+    Query(as_of): Query<AsOfQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<BuilderDistributionResponse>, ErrorResponse> {
+    let as_of = resolve_as_of(&as_of)?;
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let now = as_of.unwrap_or_else(Utc::now);
+    let (since, until) = params.time_range.bounds_at(now);
+    let rows = state
+        .client
+        .get_l2_extra_data_counts(since, until, as_of)
+        .await
+        .map_err(|e| query_error("builder distribution", e))?;
+    let builders = aggregate_builder_distribution(rows, state.builder_fingerprints());
+    tracing::info!(count = builders.len(), "Returning builder distribution");
+    Ok(Json(BuilderDistributionResponse { builders }))
+}
+
 // Legacy type aliases for backward compatibility
 type SequencerBlocksQuery = CommonQuery;
 
@@ -380,7 +609,7 @@ type SequencerBlocksQuery = CommonQuery;
     ),
     responses(
         (status = 200, description = "Sequencer blocks", body = SequencerBlocksResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -390,13 +619,13 @@ pub async fn sequencer_blocks(
     State(state): State<ApiState>,
 ) -> Result<Json<SequencerBlocksResponse>, ErrorResponse> {
     // Validate time range parameters
-    validate_time_range(&params.time_range)?;
+    params.time_range.validate()?;
 
     // Check for range exclusivity
-    let has_time_range = has_time_range_params(&params.time_range);
+    let has_time_range = params.time_range.is_set();
     validate_range_exclusivity(has_time_range, false)?;
 
-    let since = resolve_time_range_since(&params.time_range);
+    let since = params.time_range.since();
     let rows = state
         .client
         .get_sequencer_blocks_grouped_since(since)
@@ -424,36 +653,36 @@ pub async fn sequencer_blocks(
     get,
     path = "/l1-data-cost",
     params(
-        PaginatedQuery
+        PaginatedQuery,
+        EnvelopeQuery
     ),
     responses(
-        (status = 200, description = "L1 data posting cost", body = L1DataCostResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "L1 data posting cost", body = Paginated<L1DataCostRow>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
 /// Get L1 data posting cost information for the specified time range.
 ///
+/// Each block reports the total cost along with its blob and calldata components. The calldata
+/// component is priced from the transaction input at the EIP-7623 floor (40 gas per non-zero
+/// byte, 10 per zero byte); the remainder of the total is intrinsic and execution gas.
 /// Results are ordered by L1 block number in descending order.
 pub async fn l1_data_cost(
     Query(params): Query<PaginatedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<L1DataCostResponse>, ErrorResponse> {
-    validate_time_range(&params.common.time_range)?;
-    let limit = validate_pagination(
-        params.starting_after.as_ref(),
-        params.ending_before.as_ref(),
-        params.limit.as_ref(),
-        MAX_TABLE_LIMIT,
-    )?;
-    let has_time_range = has_time_range_params(&params.common.time_range);
+) -> Result<PageResponse<L1DataCostRow, L1DataCostResponse>, ErrorResponse> {
+    params.common.time_range.validate()?;
+    let page = params.page(MAX_TABLE_LIMIT)?;
+    let has_time_range = params.common.time_range.is_set();
     let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
     validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-    let since = resolve_time_range_since(&params.common.time_range);
+    let since = params.common.time_range.since();
     let rows = match state
         .client
-        .get_l1_data_costs_paginated(since, limit, params.starting_after, params.ending_before)
+        .get_l1_data_costs_paginated(since, page.limit, page.starting_after, page.ending_before)
         .await
     {
         Ok(r) => r,
@@ -461,21 +690,37 @@ pub async fn l1_data_cost(
     };
     let rows: Vec<L1DataCostRow> = rows
         .into_iter()
-        .map(|r| L1DataCostRow { l1_block_number: r.l1_block_number, cost: wei_to_gwei(r.cost) })
+        .map(|r| L1DataCostRow {
+            l1_block_number: r.l1_block_number,
+            cost: wei_to_gwei(r.cost),
+            blob_cost: wei_to_gwei(r.blob_cost),
+            calldata_cost: wei_to_gwei(r.calldata_cost),
+        })
         .collect();
     tracing::info!(count = rows.len(), "Returning L1 data cost");
-    Ok(Json(L1DataCostResponse { blocks: rows }))
+    let count = RowCount::since("l1_data_costs", since);
+    Ok(paginate(
+        &state,
+        &envelope,
+        &page,
+        rows,
+        |r| r.l1_block_number,
+        Some(count),
+        |blocks, next_cursor, prev_cursor| L1DataCostResponse { blocks, next_cursor, prev_cursor },
+    )
+    .await)
 }
 
 #[utoipa::path(
     get,
     path = "/prove-cost",
     params(
-        PaginatedQuery
+        PaginatedQuery,
+        EnvelopeQuery
     ),
     responses(
-        (status = 200, description = "Prover cost", body = ProveCostResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "Prover cost", body = Paginated<ProveCostRow>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -484,23 +729,19 @@ pub async fn l1_data_cost(
 /// Results are ordered by batch id in descending order.
 pub async fn prove_cost(
     Query(params): Query<PaginatedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<ProveCostResponse>, ErrorResponse> {
-    validate_time_range(&params.common.time_range)?;
-    let limit = validate_pagination(
-        params.starting_after.as_ref(),
-        params.ending_before.as_ref(),
-        params.limit.as_ref(),
-        MAX_TABLE_LIMIT,
-    )?;
-    let has_time_range = has_time_range_params(&params.common.time_range);
+) -> Result<PageResponse<ProveCostRow, ProveCostResponse>, ErrorResponse> {
+    params.common.time_range.validate()?;
+    let page = params.page(MAX_TABLE_LIMIT)?;
+    let has_time_range = params.common.time_range.is_set();
     let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
     validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-    let since = resolve_time_range_since(&params.common.time_range);
+    let since = params.common.time_range.since();
     let rows = match state
         .client
-        .get_prove_costs_paginated(since, limit, params.starting_after, params.ending_before)
+        .get_prove_costs_paginated(since, page.limit, page.starting_after, page.ending_before)
         .await
     {
         Ok(r) => r,
@@ -515,7 +756,17 @@ pub async fn prove_cost(
         })
         .collect();
     tracing::info!(count = rows.len(), "Returning prove cost");
-    Ok(Json(ProveCostResponse { batches: rows }))
+    let count = RowCount::since("prove_costs", since);
+    Ok(paginate(
+        &state,
+        &envelope,
+        &page,
+        rows,
+        |r| r.batch_id,
+        Some(count),
+        |batches, next_cursor, prev_cursor| ProveCostResponse { batches, next_cursor, prev_cursor },
+    )
+    .await)
 }
 
 // removed: block_profits endpoint (unused by dashboard)
@@ -525,7 +776,7 @@ pub async fn prove_cost(
     path = "/eth-price",
     responses(
         (status = 200, description = "Current ETH price", body = EthPriceResponse),
-        (status = 503, description = "Price fetch error", body = ErrorResponse)
+        (status = 503, description = "Price fetch error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -538,7 +789,7 @@ pub async fn eth_price(
         Err(e) => {
             tracing::error!(error = %e, "Failed to fetch ETH price");
             Err(ErrorResponse::new(
-                "price-error",
+                ErrorCode::UpstreamUnavailable,
                 "Failed to fetch ETH price",
                 StatusCode::SERVICE_UNAVAILABLE,
                 e.to_string(),
@@ -547,77 +798,1030 @@ pub async fn eth_price(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/data-quality",
+    responses(
+        (status = 200, description = "Unresolved batch data-quality anomalies", body = DataQualityResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get batches that are verified without a proof record or proved without a proposal record.
+///
+/// These are left over when events arrive out of order and could not be backfilled.
+pub async fn data_quality(
+    State(state): State<ApiState>,
+) -> Result<Json<DataQualityResponse>, ErrorResponse> {
+    let limit = MAX_TABLE_LIMIT;
+    let (proved_without_proposal, verified_without_proof) = tokio::try_join!(
+        state.client.get_proved_batches_without_proposal(limit),
+        state.client.get_verified_batches_without_proof(limit),
+    )
+    .map_err(|e| query_error("data quality", e))?;
+    // The lists are capped, so only count in the database when a cap was reached.
+    let proved_without_proposal_count = if proved_without_proposal.len() as u64 >= limit {
+        state.client.count_proved_batches_without_proposal().await
+    } else {
+        Ok(proved_without_proposal.len() as u64)
+    }
+    .map_err(|e| query_error("data quality", e))?;
+    let verified_without_proof_count = if verified_without_proof.len() as u64 >= limit {
+        state.client.count_verified_batches_without_proof().await
+    } else {
+        Ok(verified_without_proof.len() as u64)
+    }
+    .map_err(|e| query_error("data quality", e))?;
+    tracing::info!(
+        proved_without_proposal = proved_without_proposal_count,
+        verified_without_proof = verified_without_proof_count,
+        "Returning data quality"
+    );
+    Ok(Json(DataQualityResponse {
+        proved_without_proposal_count,
+        verified_without_proof_count,
+        proved_without_proposal,
+        verified_without_proof,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/rpc-status",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Latency, block lag and error rate of the public RPC endpoints", body = RpcStatusResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the SLA summary of every probed public RPC endpoint
+pub async fn rpc_status(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<RpcStatusResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows =
+        state.client.get_rpc_status(time_range).await.map_err(|e| query_error("rpc status", e))?;
+
+    let endpoints: Vec<RpcStatusItem> = rows
+        .into_iter()
+        .map(|r| RpcStatusItem {
+            url: r.url,
+            healthy: r.last_healthy != 0,
+            block_lag: r.last_block_lag,
+            last_probed_at: Utc
+                .timestamp_opt(r.last_probed_at as i64, 0)
+                .single()
+                .unwrap_or_default(),
+            probes: r.probes,
+            error_rate: if r.probes == 0 { 0.0 } else { r.failures as f64 / r.probes as f64 },
+            avg_latency_ms: r.avg_latency_ms,
+            p95_latency_ms: r.p95_latency_ms,
+        })
+        .collect();
+
+    tracing::info!(count = endpoints.len(), "Returning rpc status");
+    Ok(Json(RpcStatusResponse { endpoints }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/pipeline-latency",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "End-to-end ingestion latency per event type", body = PipelineLatencyResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get p50/p99 latency of each ingestion stage per event type
+pub async fn pipeline_latency(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<PipelineLatencyResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_pipeline_latency(time_range)
+        .await
+        .map_err(|e| query_error("pipeline latency", e))?;
+
+    let us_to_ms = |us: f64| us / 1000.0;
+    let kinds: Vec<PipelineLatencyItem> = rows
+        .into_iter()
+        .map(|r| PipelineLatencyItem {
+            kind: if r.kind.is_empty() { "all".to_owned() } else { r.kind },
+            events: r.events,
+            queue_p50_ms: us_to_ms(r.queue_p50_us),
+            queue_p99_ms: us_to_ms(r.queue_p99_us),
+            process_p50_ms: us_to_ms(r.process_p50_us),
+            process_p99_ms: us_to_ms(r.process_p99_us),
+            total_p50_ms: us_to_ms(r.total_p50_us),
+            total_p99_ms: us_to_ms(r.total_p99_us),
+            total_max_ms: us_to_ms(r.total_max_us as f64),
+        })
+        .collect();
+
+    tracing::info!(count = kinds.len(), "Returning pipeline latency");
+    Ok(Json(PipelineLatencyResponse { kinds }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/propagation-delay",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Delay between block timestamps and header receipt per chain", body = PropagationDelayResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the distribution of the delay between each block's timestamp and the indexer receiving
+/// its header, per chain
+pub async fn propagation_delay(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<PropagationDelayResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_header_propagation(time_range)
+        .await
+        .map_err(|e| query_error("propagation delay", e))?;
+
+    let chains: Vec<PropagationDelayItem> = rows
+        .into_iter()
+        .map(|r| PropagationDelayItem {
+            chain: r.chain,
+            blocks: r.blocks,
+            early_blocks: r.early_blocks,
+            min_ms: r.min_ms,
+            avg_ms: r.avg_ms,
+            p50_ms: r.p50_ms,
+            p90_ms: r.p90_ms,
+            p99_ms: r.p99_ms,
+            max_ms: r.max_ms,
+        })
+        .collect();
+
+    tracing::info!(count = chains.len(), "Returning propagation delay");
+    Ok(Json(PropagationDelayResponse { chains }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/backfill-status",
+    responses(
+        (status = 200, description = "Latest gap backfill progress per chain", body = BackfillStatusResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the latest progress of the gap backfill of each chain
+pub async fn backfill_status(
+    State(state): State<ApiState>,
+) -> Result<Json<BackfillStatusResponse>, ErrorResponse> {
+    let rows =
+        state.client.get_backfill_status().await.map_err(|e| query_error("backfill status", e))?;
+
+    let chains: Vec<BackfillStatusItem> = rows
+        .into_iter()
+        .map(|r| BackfillStatusItem {
+            chain: r.chain,
+            scheduled_blocks: r.scheduled_blocks,
+            filled_blocks: r.filled_blocks,
+            failed_blocks: r.failed_blocks,
+            remaining_blocks: r
+                .scheduled_blocks
+                .saturating_sub(r.filled_blocks)
+                .saturating_sub(r.failed_blocks),
+            newest_block: r.newest_block,
+            oldest_block: r.oldest_block,
+            current_block: r.current_block,
+            max_requests_per_sec: r.max_requests_per_sec,
+            updated_at: r.updated_at,
+        })
+        .collect();
+
+    tracing::info!(count = chains.len(), "Returning backfill status");
+    Ok(Json(BackfillStatusResponse { chains }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/leader-changes",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Changes of the driver instance ingesting events", body = LeaderChangesResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the changes of the driver instance ingesting events, when leader election is enabled
+pub async fn leader_changes(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<LeaderChangesResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let time_range = params.time_range.to_time_range();
+
+    let rows = state
+        .client
+        .get_leader_changes(time_range)
+        .await
+        .map_err(|e| query_error("leader changes", e))?;
+
+    let items: Vec<LeaderChangeItem> = rows
+        .into_iter()
+        .map(|r| LeaderChangeItem {
+            lease: r.lease,
+            holder: r.holder,
+            previous_holder: r.previous_holder,
+            changed_at: r.changed_at,
+        })
+        .collect();
+
+    tracing::info!(count = items.len(), "Returning leader changes");
+    Ok(Json(LeaderChangesResponse {
+        changes: items.len() as u64,
+        current_leader: items.first().map(|i| i.holder.clone()),
+        items,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bond-balances",
+    responses(
+        (status = 200, description = "Latest inbox bond balance per account", body = BondBalancesResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the latest inbox bond balance of every account, flagging those that cannot cover the
+/// liveness bond of a full batch. Balances are also valued at the latest TAIKO price.
+pub async fn bond_balances(
+    State(state): State<ApiState>,
+) -> Result<Json<BondBalancesResponse>, ErrorResponse> {
+    let config =
+        state.client.get_protocol_config().await.map_err(|e| query_error("protocol config", e))?;
+    let required_bond = config.map(|c| c.full_batch_bond());
+
+    let rows =
+        state.client.get_bond_balances().await.map_err(|e| query_error("bond balances", e))?;
+    let price =
+        state.client.get_latest_taiko_price().await.map_err(|e| query_error("TAIKO price", e))?;
+    let value = |balance: u128, per_token: Option<f64>| {
+        per_token.map(|per_token| balance as f64 / 1e18 * per_token)
+    };
+    let taiko_eth = price.as_ref().map(|p| p.eth);
+    let taiko_usd = price.as_ref().map(|p| p.usd);
+
+    let accounts: Vec<BondBalanceItem> = rows
+        .into_iter()
+        .map(|r| BondBalanceItem {
+            address: format_address(r.address),
+            balance: r.balance,
+            l1_block_number: r.l1_block_number,
+            total_debited: r.total_debited,
+            total_credited: r.total_credited,
+            at_risk: required_bond.is_some_and(|required| r.balance < required),
+            balance_eth: value(r.balance, taiko_eth),
+            balance_usd: value(r.balance, taiko_usd),
+        })
+        .collect();
+
+    tracing::info!(count = accounts.len(), "Returning bond balances");
+    Ok(Json(BondBalancesResponse { required_bond, taiko_eth, taiko_usd, accounts }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bond-history",
+    params(
+        BondHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Inbox bond ledger entries of an account", body = BondHistoryResponse),
+        (status = 400, description = "Invalid address or limit", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the latest inbox bond balance changes of an account, flagging whether its balance can
+/// cover the liveness bond of a full batch
+pub async fn bond_history(
+    Query(params): Query<BondHistoryQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<BondHistoryResponse>, ErrorResponse> {
+    let address = parse_address(&params.address)?;
+    let limit = validate_limit(params.limit.as_ref(), DEFAULT_BOND_HISTORY_LIMIT, MAX_TABLE_LIMIT)?;
+
+    let config =
+        state.client.get_protocol_config().await.map_err(|e| query_error("protocol config", e))?;
+    let required_bond = config.map(|c| c.full_batch_bond());
+    let rows = state
+        .client
+        .get_bond_history(address, limit)
+        .await
+        .map_err(|e| query_error("bond history", e))?;
+
+    let balance = rows.first().map(|r| r.balance);
+    let items: Vec<BondHistoryItem> = rows
+        .into_iter()
+        .map(|r| BondHistoryItem {
+            l1_block_number: r.l1_block_number,
+            tx_hash: format_address_bytes(r.tx_hash.as_bytes()),
+            log_index: r.log_index,
+            kind: r.kind,
+            amount: r.amount,
+            balance: r.balance,
+        })
+        .collect();
+
+    tracing::info!(count = items.len(), "Returning bond history");
+    Ok(Json(BondHistoryResponse {
+        address: format_address(address),
+        balance,
+        required_bond,
+        at_risk: balance.zip(required_bond).is_some_and(|(balance, required)| balance < required),
+        items,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/operator-balances",
+    responses(
+        (status = 200, description = "Latest L1 wallet balances of the whitelisted operators", body = OperatorBalancesResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the ETH and TAIKO wallet balances of the whitelisted operators from the latest snapshot,
+/// flagging those below the configured thresholds
+pub async fn operator_balances(
+    State(state): State<ApiState>,
+) -> Result<Json<OperatorBalancesResponse>, ErrorResponse> {
+    let rows = state
+        .client
+        .get_operator_balances()
+        .await
+        .map_err(|e| query_error("operator balances", e))?;
+
+    let snapshot_ts = rows.first().map(|r| r.snapshot_ts);
+    let operators: Vec<OperatorBalanceItem> = rows
+        .into_iter()
+        .map(|r| OperatorBalanceItem {
+            address: format_address(r.address),
+            l1_block_number: r.l1_block_number,
+            eth_balance: r.eth_balance,
+            taiko_balance: r.taiko_balance,
+            low_balance: r.low_balance == 1,
+        })
+        .collect();
+
+    tracing::info!(count = operators.len(), "Returning operator balances");
+    Ok(Json(OperatorBalancesResponse { snapshot_ts, operators }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/operator-schedule",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Preconfirmation operators of the current, next and past L1 epochs", body = OperatorScheduleResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the preconfirmation operators of the current and next L1 epoch and of every epoch within
+/// the range, with the epoch boundaries in timestamps and L1 block numbers
+pub async fn operator_schedule(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<OperatorScheduleResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let (since, until) = params.time_range.bounds();
+    let (latest, rows) = tokio::try_join!(
+        state.client.get_last_preconf_data(),
+        state.client.get_operator_epochs(since.timestamp() as u64, until.timestamp() as u64 + 1),
+    )
+    .map_err(|e| query_error("operator schedule", e))?;
+    let labels = address_labels(&state.client).await;
+
+    let response = operator_schedule_response(latest, rows, &labels);
+    tracing::info!(epochs = response.history.len(), "Returning operator schedule");
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/forced-inclusion-queue",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Latest and historical depth of the forced inclusion queue", body = ForcedInclusionQueueResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the number of queued forced inclusions and the age of the oldest one, from the latest
+/// snapshot and the snapshots within the range
+pub async fn forced_inclusion_queue(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<ForcedInclusionQueueResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let (since, until) = params.time_range.bounds();
+    let (latest, rows) = tokio::try_join!(
+        state.client.get_latest_forced_inclusion_queue(),
+        state.client.get_forced_inclusion_queue_range(since, until),
+    )
+    .map_err(|e| query_error("forced inclusion queue", e))?;
+
+    let to_item = |r: ForcedInclusionQueueRow| ForcedInclusionQueueItem {
+        snapshot_ts: r.snapshot_ts,
+        l1_block_number: r.l1_block_number,
+        pending: r.pending,
+        oldest_age_secs: r.oldest_age_secs,
+        deadline_batch_id: r.deadline_batch_id,
+        next_batch_id: r.next_batch_id,
+        near_deadline: r.near_deadline == 1,
+    };
+    let snapshots: Vec<ForcedInclusionQueueItem> = rows.into_iter().map(to_item).collect();
+
+    tracing::info!(count = snapshots.len(), "Returning forced inclusion queue");
+    Ok(Json(ForcedInclusionQueueResponse { latest: latest.map(to_item), snapshots }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/verification-backlog",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Latest and historical number of proposed batches waiting for verification", body = VerificationBacklogResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get how many batches the latest verified batch lags behind the latest proposed batch, from the
+/// latest snapshot and the snapshots within the range
+pub async fn verification_backlog(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<VerificationBacklogResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let (since, until) = params.time_range.bounds();
+    let (latest, rows) = tokio::try_join!(
+        state.client.get_latest_verification_backlog(),
+        state.client.get_verification_backlog_range(since, until),
+    )
+    .map_err(|e| query_error("verification backlog", e))?;
+
+    let to_item = |r: VerificationBacklogRow| VerificationBacklogItem {
+        snapshot_ts: r.snapshot_ts,
+        l1_block_number: r.l1_block_number,
+        last_proposed_batch_id: r.last_proposed_batch_id,
+        last_verified_batch_id: r.last_verified_batch_id,
+        backlog: r.backlog,
+        over_threshold: r.over_threshold == 1,
+    };
+    let snapshots: Vec<VerificationBacklogItem> = rows.into_iter().map(to_item).collect();
+
+    tracing::info!(count = snapshots.len(), "Returning verification backlog");
+    Ok(Json(VerificationBacklogResponse { latest: latest.map(to_item), snapshots }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/proposal-inclusion-delay",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Time batch proposals spent in the L1 mempool before inclusion", body = ProposalInclusionDelayResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get when each batch proposal first seen within the range entered the L1 mempool and how long
+/// it took to be included, with summary statistics of the delays
+pub async fn proposal_inclusion_delay(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<ProposalInclusionDelayResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let (since, until) = params.time_range.bounds();
+    let rows = state
+        .client
+        .get_proposal_inclusions(since, until)
+        .await
+        .map_err(|e| query_error("proposal inclusions", e))?;
+    let response = summarize_proposal_inclusions(rows);
+
+    tracing::info!(count = response.proposals.len(), "Returning proposal inclusion delays");
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/handover-latency",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Gaps between the outgoing and incoming sequencer at operator handovers", body = HandoverLatencyResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the handovers between sequencers within the range and the seconds between the last block
+/// of the outgoing and the first block of the incoming sequencer, with latency percentiles
+pub async fn handover_latency(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<HandoverLatencyResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let (since, until) = params.time_range.bounds();
+    let rows = state
+        .client
+        .get_sequencer_handovers(since, until)
+        .await
+        .map_err(|e| query_error("sequencer handovers", e))?;
+    let response = summarize_handovers(rows);
+
+    tracing::info!(count = response.handovers.len(), "Returning handover latency");
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/incident-history",
+    params(
+        IncidentHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Incident creations, updates and resolutions performed by the monitors", body = IncidentHistoryResponse),
+        (status = 400, description = "Invalid action", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the incident operations the monitors performed against Instatus within the range, with
+/// the request payloads and response codes, to audit alerting behavior after an outage
+pub async fn incident_history(
+    Query(params): Query<IncidentHistoryQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<IncidentHistoryResponse>, ErrorResponse> {
+    params.common.time_range.validate()?;
+    let has_time_range = params.common.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+    let action = validate_incident_action(params.action.as_deref())?;
+
+    let (since, until) = params.common.time_range.bounds();
+    let rows = state
+        .client
+        .get_incident_log_range(since, until)
+        .await
+        .map_err(|e| query_error("incident history", e))?;
+
+    let operations: Vec<IncidentLogItem> = rows
+        .into_iter()
+        .filter(|r| params.component_id.as_ref().is_none_or(|id| r.component_ids.contains(id)))
+        .filter_map(|r| {
+            let row_action = IncidentAction::from_code(r.action)?;
+            action.is_none_or(|a| a == row_action).then(|| IncidentLogItem {
+                logged_at_ms: r.logged_at_ms,
+                action: row_action.as_str().to_owned(),
+                incident_id: r.incident_id,
+                component_ids: r.component_ids,
+                incident_name: r.incident_name,
+                status: r.status,
+                payload: r.payload,
+                response_code: r.response_code,
+                error: r.error,
+                dry_run: r.dry_run == 1,
+            })
+        })
+        .collect();
+
+    tracing::info!(count = operations.len(), "Returning incident history");
+    Ok(Json(IncidentHistoryResponse { operations }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/block-status/{block_number}",
+    params(
+        ("block_number" = u64, Path, description = "L2 block number")
+    ),
+    responses(
+        (status = 200, description = "Finality stage of the block", body = BlockStatusResponse),
+        (status = 404, description = "Block not found", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the finality stage of an L2 block and the time each stage was reached
+pub async fn block_status(
+    Path(block_number): Path<u64>,
+    State(state): State<ApiState>,
+) -> Result<Json<BlockStatusResponse>, ErrorResponse> {
+    let row = state
+        .client
+        .get_l2_block_status(block_number)
+        .await
+        .map_err(|e| query_error("l2 block status", e))?
+        .ok_or_else(|| {
+            ErrorResponse::not_found(format!("L2 block {block_number} has not been observed"))
+        })?;
+
+    let stage = if row.verified_at.is_some() {
+        FinalityStage::Verified
+    } else if row.proved_at.is_some() {
+        FinalityStage::Proved
+    } else if row.batch_id.is_some() {
+        FinalityStage::Proposed
+    } else {
+        FinalityStage::Preconfirmed
+    };
+    let to_time = |ts: Option<u64>| Utc.timestamp_opt(ts? as i64, 0).single();
+
+    Ok(Json(BlockStatusResponse {
+        l2_block_number: row.l2_block_number,
+        stage,
+        batch_id: row.batch_id,
+        preconfirmed_at: to_time(row.block_ts),
+        proposed_at: to_time(row.proposed_at),
+        proved_at: to_time(row.proved_at),
+        verified_at: to_time(row.verified_at),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/batch/{batch_id}/blocks",
+    params(
+        ("batch_id" = u64, Path, description = "Batch ID"),
+        BatchBlocksQuery,
+        EnvelopeQuery
+    ),
+    responses(
+        (status = 200, description = "L2 blocks of the batch", body = Paginated<BatchBlockItem>),
+        (status = 404, description = "Batch not found", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the L2 blocks of a batch with their gas, transaction count and timestamp.
+///
+/// Results are ordered by block number in descending order.
+pub async fn batch_blocks(
+    Path(batch_id): Path<u64>,
+    Query(params): Query<BatchBlocksQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
+    State(state): State<ApiState>,
+) -> Result<PageResponse<BatchBlockItem, BatchBlocksResponse>, ErrorResponse> {
+    let page = params.page(batch_id, MAX_BATCH_BLOCKS_LIMIT)?;
+    let rows = state
+        .client
+        .get_batch_blocks(batch_id, page.limit, page.starting_after, page.ending_before)
+        .await
+        .map_err(|e| query_error("batch blocks", e))?;
+    if rows.is_empty() && !page.has_cursor() {
+        return Err(ErrorResponse::not_found(format!("Batch {batch_id} has no observed blocks")));
+    }
+
+    let blocks: Vec<BatchBlockItem> = rows
+        .into_iter()
+        .filter_map(|r| {
+            Some(BatchBlockItem {
+                l2_block_number: r.l2_block_number,
+                block_time: Utc.timestamp_opt(r.block_ts as i64, 0).single()?,
+                gas_used: r.sum_gas_used,
+                gas_limit: (r.gas_limit > 0).then_some(r.gas_limit),
+                tx_count: r.sum_tx,
+                sequencer: format_address(r.sequencer),
+            })
+        })
+        .collect();
+
+    tracing::info!(batch_id, count = blocks.len(), "Returning batch blocks");
+    Ok(paginate(
+        &state,
+        &envelope,
+        &page,
+        blocks,
+        |b| b.l2_block_number,
+        None,
+        |blocks, next_cursor, prev_cursor| BatchBlocksResponse {
+            batch_id,
+            blocks,
+            next_cursor,
+            prev_cursor,
+        },
+    )
+    .await)
+}
+
+#[utoipa::path(
+    get,
+    path = "/tx/{hash}",
+    params(
+        ("hash" = String, Path, description = "L1 transaction hash")
+    ),
+    responses(
+        (status = 200, description = "Protocol roles of the transaction", body = TxLookupResponse),
+        (status = 400, description = "Invalid transaction hash", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "Transaction not indexed", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Resolve an L1 transaction to the protocol roles it played and the batches they relate to
+pub async fn tx_lookup(
+    Path(hash): Path<String>,
+    State(state): State<ApiState>,
+) -> Result<Json<TxLookupResponse>, ErrorResponse> {
+    let tx_hash = parse_tx_hash(&hash)?;
+    let rows =
+        state.client.get_l1_tx_roles(tx_hash).await.map_err(|e| query_error("l1 tx roles", e))?;
+    let Some(first) = rows.first() else {
+        return Err(ErrorResponse::not_found(format!("L1 transaction {hash} has not been indexed")));
+    };
+
+    let l1_block_number = first.l1_block_number;
+    let block_time = (first.block_ts > 0)
+        .then(|| Utc.timestamp_opt(first.block_ts as i64, 0).single())
+        .flatten();
+    let indexed_at = rows
+        .iter()
+        .map(|r| r.indexed_at_ms)
+        .min()
+        .and_then(|ms| Utc.timestamp_millis_opt(ms as i64).single());
+    let roles = rows
+        .iter()
+        .filter_map(|r| {
+            let role = match L1TxRole::from_code(r.role)? {
+                L1TxRole::Proposal => TxRole::Proposal,
+                L1TxRole::Prove => TxRole::Prove,
+                L1TxRole::Verify => TxRole::Verify,
+                L1TxRole::ForcedInclusion => TxRole::ForcedInclusion,
+            };
+            let batch_id = (role != TxRole::ForcedInclusion).then_some(r.batch_id);
+            Some(TxRoleItem { role, batch_id })
+        })
+        .collect();
+
+    Ok(Json(TxLookupResponse {
+        tx_hash: B256::from(tx_hash).to_string(),
+        l1_block_number,
+        block_time,
+        indexed_at,
+        roles,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/block-status-summary",
+    params(
+        BlockStatusSummaryQuery
+    ),
+    responses(
+        (status = 200, description = "Blocks per finality stage", body = BlockStatusSummaryResponse),
+        (status = 400, description = "Invalid limit", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the number of blocks in each finality stage among the latest L2 blocks
+pub async fn block_status_summary(
+    Query(params): Query<BlockStatusSummaryQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<BlockStatusSummaryResponse>, ErrorResponse> {
+    let window = validate_limit(
+        params.limit.as_ref(),
+        DEFAULT_BLOCK_STATUS_WINDOW,
+        MAX_BLOCK_STATUS_WINDOW,
+    )?;
+
+    let row = state
+        .client
+        .get_block_status_summary(window)
+        .await
+        .map_err(|e| query_error("block status summary", e))?;
+
+    let response = row.map_or(
+        BlockStatusSummaryResponse {
+            head_block: None,
+            blocks: 0,
+            preconfirmed: 0,
+            proposed: 0,
+            proved: 0,
+            verified: 0,
+        },
+        |r| BlockStatusSummaryResponse {
+            head_block: Some(r.head_block),
+            blocks: r.preconfirmed + r.proposed + r.proved + r.verified,
+            preconfirmed: r.preconfirmed,
+            proposed: r.proposed,
+            proved: r.proved,
+            verified: r.verified,
+        },
+    );
+    Ok(Json(response))
+}
+
 // Removed legacy l2_fees and l2_fee_components endpoints (use l2_fees_components)
 
 #[utoipa::path(
     get,
     path = "/l2-fees-components",
     params(
-        RangeQuery
+        RangeQuery,
+        AsOfQuery,
+        BucketQuery
     ),
     responses(
         (status = 200, description = "Combined L2 fees and batch components", body = L2FeesComponentsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
-/// Get combined L2 fees summary and detailed batch components for all sequencers
+/// Get combined L2 fees summary and detailed batch components for all sequencers, optionally as
+/// they were at `as_of`. Base fees are attributed to the sequencers according to the base fee
+/// sharing percentage of the latest protocol configuration. With `bucket_by=time` the fees are
+/// also returned over wallclock aligned minutes or hours.
 pub async fn l2_fees_components(
     Query(params): Query<RangeQuery>,
+    Query(as_of): Query<AsOfQuery>,
+    Query(bucket_query): Query<BucketQuery>,
     State(state): State<ApiState>,
 ) -> Result<Json<L2FeesComponentsResponse>, ErrorResponse> {
-    validate_time_range(&params.time_range)?;
+    params.time_range.validate()?;
+    let as_of = resolve_as_of(&as_of)?;
+    let bucket_by = validate_bucket_by(bucket_query.bucket_by.as_deref())?;
 
-    let has_time_range = has_time_range_params(&params.time_range);
+    let has_time_range = params.time_range.is_set();
     validate_range_exclusivity(has_time_range, false)?;
 
-    let time_range = resolve_time_range_enum(&params.time_range);
+    let time_range = params.time_range.to_time_range_at(as_of.unwrap_or_else(Utc::now));
 
     let (sequencer_fees, batch_components) = state
         .client
-        .get_l2_fees_and_components(None, time_range)
+        .get_l2_fees_and_components(None, time_range, as_of)
         .await
         .map_err(|e| query_error("L2 fees and components", e))?;
+    let sharing_pctg = state
+        .client
+        .get_protocol_config()
+        .await
+        .map_err(|e| query_error("protocol config", e))?
+        .map_or(DEFAULT_BASE_FEE_SHARING_PCTG, |c| c.base_fee_sharing_pctg);
 
     // Calculate aggregated totals from sequencer fees
     let priority_fee = sequencer_fees.iter().map(|s| s.priority_fee).sum::<u128>();
     let base_fee = sequencer_fees.iter().map(|s| s.base_fee).sum::<u128>();
+    let shared_base =
+        sequencer_fees.iter().map(|s| shared_base_fee(s.base_fee, sharing_pctg)).sum();
     let l1_data_cost = sequencer_fees.iter().map(|s| s.l1_data_cost).sum::<u128>();
     let prove_cost = sequencer_fees.iter().map(|s| s.prove_cost).sum::<u128>();
 
     // Convert sequencer fees to gwei
+    let labels = address_labels(&state.client).await;
     let sequencers: Vec<SequencerFeeRow> = sequencer_fees
         .into_iter()
-        .map(|s| SequencerFeeRow {
-            address: format_address(s.sequencer),
-            priority_fee: wei_to_gwei(s.priority_fee),
-            base_fee: wei_to_gwei(s.base_fee),
-            l1_data_cost: wei_to_gwei(s.l1_data_cost),
-            prove_cost: wei_to_gwei(s.prove_cost),
+        .map(|s| {
+            let shared = shared_base_fee(s.base_fee, sharing_pctg);
+            SequencerFeeRow {
+                address: format_address(s.sequencer),
+                label: labels.get(&s.sequencer).cloned(),
+                priority_fee: wei_to_gwei(s.priority_fee),
+                base_fee: wei_to_gwei(s.base_fee),
+                shared_base_fee: wei_to_gwei(shared),
+                l1_data_cost: wei_to_gwei(s.l1_data_cost),
+                prove_cost: wei_to_gwei(s.prove_cost),
+                net_revenue: net_revenue_gwei(
+                    s.priority_fee,
+                    shared,
+                    s.l1_data_cost.saturating_add(s.prove_cost),
+                ),
+            }
         })
         .collect();
 
     // Convert batch components to gwei
     let batches: Vec<BatchFeeComponentRow> = batch_components
         .into_iter()
-        .map(|r| BatchFeeComponentRow {
-            batch_id: r.batch_id,
-            l1_block_number: r.l1_block_number,
-            l1_tx_hash: B256::from(r.l1_tx_hash).to_string(),
-            sequencer: format_address(r.sequencer),
-            priority_fee: wei_to_gwei(r.priority_fee),
-            base_fee: wei_to_gwei(r.base_fee),
-            l1_data_cost: wei_to_gwei_opt(r.l1_data_cost),
-            prove_cost: wei_to_gwei_opt(r.prove_cost),
+        .map(|r| {
+            let shared = shared_base_fee(r.base_fee, sharing_pctg);
+            let costs = r.l1_data_cost.unwrap_or(0).saturating_add(r.prove_cost.unwrap_or(0));
+            BatchFeeComponentRow {
+                batch_id: r.batch_id,
+                l1_block_number: r.l1_block_number,
+                l1_tx_hash: B256::from(r.l1_tx_hash).to_string(),
+                sequencer: format_address(r.sequencer),
+                priority_fee: wei_to_gwei(r.priority_fee),
+                base_fee: wei_to_gwei(r.base_fee),
+                shared_base_fee: wei_to_gwei(shared),
+                l1_data_cost: wei_to_gwei_opt(r.l1_data_cost),
+                prove_cost: wei_to_gwei_opt(r.prove_cost),
+                net_revenue: net_revenue_gwei(r.priority_fee, shared, costs),
+                avg_gas_price: r.avg_gas_price,
+            }
         })
         .collect();
 
+    let time_buckets = match bucket_by {
+        BucketBy::Block => None,
+        BucketBy::Time => {
+            let secs = time_bucket_secs_from_range(&time_range);
+            let rows = state
+                .client
+                .get_l2_time_buckets(None, time_range, secs, as_of)
+                .await
+                .map_err(|e| query_error("L2 fee time buckets", e))?;
+            Some(
+                rows.iter()
+                    .map(|r| FeeTimeBucketItem {
+                        bucket_start: r.bucket_start(),
+                        last_block: r.last_block,
+                        priority_fee: wei_to_gwei(r.priority_fee),
+                        base_fee: wei_to_gwei(r.base_fee),
+                        shared_base_fee: wei_to_gwei(shared_base_fee(r.base_fee, sharing_pctg)),
+                    })
+                    .collect(),
+            )
+        }
+    };
+
     Ok(Json(L2FeesComponentsResponse {
         priority_fee: (priority_fee > 0).then_some(wei_to_gwei(priority_fee)),
         base_fee: (base_fee > 0).then_some(wei_to_gwei(base_fee)),
+        base_fee_sharing_pctg: sharing_pctg,
+        shared_base_fee: wei_to_gwei(shared_base),
         l1_data_cost: wei_to_gwei(l1_data_cost),
         prove_cost: wei_to_gwei(prove_cost),
+        net_revenue: net_revenue_gwei(
+            priority_fee,
+            shared_base,
+            l1_data_cost.saturating_add(prove_cost),
+        ),
         sequencers,
         batches,
+        time_buckets,
     }))
 }
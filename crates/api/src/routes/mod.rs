@@ -1,26 +1,54 @@
 //! API route definitions
 
+pub mod admin;
 pub mod aggregated;
 pub mod core;
+pub mod status;
 pub mod table;
 
-use crate::{ApiDoc, state::ApiState};
-use axum::{Router, routing::get};
+use crate::{
+    ApiDoc,
+    cache::{CacheGroup, cache_response},
+    extract::not_found,
+    state::ApiState,
+};
+use axum::{
+    Router, middleware,
+    routing::{get, post},
+};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-use aggregated::{dashboard_data, prove_costs};
+use aggregated::{
+    anchor_lag, anomalies, batch_efficiency, cost_per_tx, dashboard_data, gas_saturation,
+    leaderboards, mempool_stats, protocol_gas, prove_costs, prove_time_percentiles,
+    prove_times_by_proof_type, reorg_stats, sequencer_uptime, verify_time_percentiles,
+};
 use core::*;
 use table::*;
 
-/// Build the router with all API endpoints.
+/// Build the router with all API endpoints. When `state` carries an admin token, the usage per
+/// origin is also served at `/api-usage-by-origin`, authenticated with the token and kept out of
+/// the public `OpenAPI` document.
+///
+/// Aggregate and table routes sit behind the response cache; live endpoints such as head
+/// blocks are always served fresh.
 pub fn router(state: ApiState) -> Router {
-    let api_routes = Router::new()
+    let live_routes = Router::new()
         .route("/l2-head-block", get(l2_head_block))
         .route("/l1-head-block", get(l1_head_block))
         .route("/preconf-data", get(preconf_data))
+        .route("/protocol-config", get(protocol_config))
+        .route("/eth-price", get(eth_price))
+        .route("/block-status/:block_number", get(block_status))
+        .route("/block-status-summary", get(block_status_summary))
+        .route("/tx/:hash", get(tx_lookup));
+
+    let table_routes = Router::new()
         .route("/reorgs", get(reorgs))
         .route("/slashings", get(slashings))
+        .route("/preconf-mismatches", get(preconf_mismatches))
+        .route("/anchor-mismatches", get(anchor_mismatches))
         .route("/forced-inclusions", get(forced_inclusions))
         .route("/failed-proposals", get(failed_proposals))
         .route("/batch-posting-times", get(batch_posting_times))
@@ -31,20 +59,85 @@ pub fn router(state: ApiState) -> Router {
         .route("/l2-block-times", get(l2_block_times))
         .route("/l2-gas-used", get(l2_gas_used))
         .route("/l2-tps", get(l2_tps))
+        .route("/block-transactions", get(block_transactions))
+        .route("/batch/:batch_id/blocks", get(batch_blocks))
+        .route("/l1-data-cost", get(l1_data_cost))
+        .route("/prove-cost", get(prove_cost))
+        .route("/blob-fee-history", get(blob_fee_history))
+        .route_layer(middleware::from_fn_with_state(
+            (state.clone(), CacheGroup::Table),
+            cache_response,
+        ));
+
+    let dashboard_routes = Router::new()
         .route("/sequencer-distribution", get(sequencer_distribution))
+        .route("/builder-distribution", get(builder_distribution))
         .route("/sequencer-blocks", get(sequencer_blocks))
-        .route("/block-transactions", get(block_transactions))
         // Removed legacy /l2-fees and /l2-fee-components endpoints (use /l2-fees-components
         // instead)
         .route("/l2-fees-components", get(l2_fees_components))
         .route("/dashboard-data", get(dashboard_data))
-        .route("/l1-data-cost", get(l1_data_cost))
         .route("/prove-costs", get(prove_costs))
-        .route("/prove-cost", get(prove_cost))
-        .route("/eth-price", get(eth_price));
+        .route("/data-quality", get(data_quality))
+        .route("/leaderboards", get(leaderboards))
+        .route("/anchor-lag", get(anchor_lag))
+        .route("/gas-saturation", get(gas_saturation))
+        .route("/batch-efficiency", get(batch_efficiency))
+        .route("/cost-per-tx", get(cost_per_tx))
+        .route("/protocol-gas", get(protocol_gas))
+        .route("/prove-time-percentiles", get(prove_time_percentiles))
+        .route("/prove-times-by-proof-type", get(prove_times_by_proof_type))
+        .route("/verify-time-percentiles", get(verify_time_percentiles))
+        .route("/sequencer-uptime", get(sequencer_uptime))
+        .route("/mempool-stats", get(mempool_stats))
+        .route("/anomalies", get(anomalies))
+        .route("/rpc-status", get(rpc_status))
+        .route("/pipeline-latency", get(pipeline_latency))
+        .route("/propagation-delay", get(propagation_delay))
+        .route("/backfill-status", get(backfill_status))
+        .route("/leader-changes", get(leader_changes))
+        .route("/bond-balances", get(bond_balances))
+        .route("/bond-history", get(bond_history))
+        .route("/operator-balances", get(operator_balances))
+        .route("/operator-schedule", get(operator_schedule))
+        .route("/forced-inclusion-queue", get(forced_inclusion_queue))
+        .route("/verification-backlog", get(verification_backlog))
+        .route("/proposal-inclusion-delay", get(proposal_inclusion_delay))
+        .route("/handover-latency", get(handover_latency))
+        .route("/incident-history", get(incident_history))
+        .route("/reorg-stats", get(reorg_stats))
+        .route_layer(middleware::from_fn_with_state(
+            (state.clone(), CacheGroup::Dashboard),
+            cache_response,
+        ));
+
+    let mut api_routes = live_routes.merge(table_routes).merge(dashboard_routes);
+    if let Some(token) = state.admin_token() {
+        let usage_routes =
+            Router::new().route("/api-usage-by-origin", get(admin::api_usage_by_origin));
+        api_routes = api_routes.merge(runtime::admin::protect(usage_routes, token));
+    }
 
     Router::new()
         .merge(SwaggerUi::new("/swagger-ui").url("/api-doc/openapi.json", ApiDoc::openapi()))
         .merge(api_routes)
+        .fallback(not_found)
+        .with_state(state)
+}
+
+/// Build the router serving the HTML status page at `/status`.
+pub fn status_router(state: ApiState) -> Router {
+    Router::new().route("/status", get(status::status_page)).with_state(state)
+}
+
+/// Build the router of operational endpoints, e.g. `POST /admin/cache/invalidate` and the cache
+/// and replica counters.
+///
+/// The routes are unauthenticated; callers must wrap them in an auth layer before serving.
+pub fn admin_router(state: ApiState) -> Router {
+    Router::new()
+        .route("/admin/cache/invalidate", post(admin::invalidate_cache))
+        .route("/admin/cache-stats", get(admin::cache_stats))
+        .route("/admin/replica-stats", get(admin::replica_stats))
         .with_state(state)
 }
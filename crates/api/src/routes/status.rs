@@ -0,0 +1,258 @@
+//! Server-rendered HTML status page
+//!
+//! A lightweight alternative to an external status provider: the lag of both chain heads, the
+//! incidents the monitors currently have open and the batches still waiting for a proof.
+
+use crate::{helpers::query_error, state::ApiState};
+use api_types::ErrorResponse;
+use axum::{extract::State, response::Html};
+use chrono::{DateTime, Utc};
+use incident::state::{MonitorState, load_all};
+use serde_json::Value;
+use std::fmt::Write;
+use tracing::warn;
+
+/// Seconds between automatic reloads of the page
+const REFRESH_SECS: u64 = 30;
+
+/// Latest head of one layer
+#[derive(Debug, Default)]
+struct HeadStatus {
+    block_number: Option<u64>,
+    block_time: Option<DateTime<Utc>>,
+}
+
+/// Persisted monitor states by monitor name, or the error that prevented reading them
+type MonitorStates = Result<Vec<(String, MonitorState<Value>)>, String>;
+
+/// Everything shown on the status page
+#[derive(Debug)]
+struct StatusSnapshot {
+    generated_at: DateTime<Utc>,
+    l1: HeadStatus,
+    l2: HeadStatus,
+    unproved_batches: usize,
+    /// Batch id and proposal time of the oldest unproved batch
+    oldest_unproved: Option<(u64, DateTime<Utc>)>,
+    /// Persisted monitor states, `None` when no incident state directory is configured
+    monitors: Option<MonitorStates>,
+}
+
+/// Render the HTML status page
+pub async fn status_page(State(state): State<ApiState>) -> Result<Html<String>, ErrorResponse> {
+    let now = Utc::now();
+    let (l1_block, l1_time, l2_block, l2_time, unproved) = tokio::try_join!(
+        state.client.get_last_l1_block_number(),
+        state.client.get_last_l1_head_time(),
+        state.client.get_last_l2_block_number(),
+        state.client.get_last_l2_head_time(),
+        state.client.get_unproved_batches_older_than(now),
+    )
+    .map_err(|e| query_error("status", e))?;
+
+    let incident_state_dir = state.status_page().and_then(|page| page.incident_state_dir.clone());
+    let monitors = match incident_state_dir {
+        Some(dir) => {
+            let loaded = tokio::task::spawn_blocking(move || load_all::<Value>(&dir))
+                .await
+                .map_err(eyre::Report::from)
+                .and_then(|states| states);
+            Some(loaded.map_err(|e| {
+                warn!(error = %e, "Failed to read monitor state for the status page");
+                e.to_string()
+            }))
+        }
+        None => None,
+    };
+
+    let snapshot = StatusSnapshot {
+        generated_at: now,
+        l1: HeadStatus { block_number: l1_block, block_time: l1_time },
+        l2: HeadStatus { block_number: l2_block, block_time: l2_time },
+        unproved_batches: unproved.len(),
+        oldest_unproved: unproved
+            .first()
+            .map(|(_, batch_id, proposed_at)| (*batch_id, *proposed_at)),
+        monitors,
+    };
+    Ok(Html(render(&snapshot)))
+}
+
+/// Render `snapshot` as a self-contained HTML document.
+fn render(snapshot: &StatusSnapshot) -> String {
+    let now = snapshot.generated_at;
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <meta http-equiv=\"refresh\" content=\"{REFRESH_SECS}\">\
+         <title>Taikoscope status</title><style>\
+         body{{font-family:system-ui,sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem;color:#1f2937}}\
+         table{{border-collapse:collapse;width:100%;margin-bottom:1.5rem}}\
+         th,td{{text-align:left;padding:.4rem .6rem;border-bottom:1px solid #e5e7eb}}\
+         .muted{{color:#6b7280}}</style></head><body>\
+         <h1>Taikoscope status</h1><p class=\"muted\">Generated at {}</p>",
+        now.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+
+    html.push_str("<h2>Chain heads</h2><table><tr><th>Layer</th><th>Block</th><th>Lag</th></tr>");
+    for (layer, head) in [("L1", &snapshot.l1), ("L2", &snapshot.l2)] {
+        let block = head.block_number.map_or_else(|| "unknown".to_owned(), |n| n.to_string());
+        let _ = write!(
+            html,
+            "<tr><td>{layer}</td><td>{block}</td><td>{}</td></tr>",
+            format_age(now, head.block_time)
+        );
+    }
+    html.push_str("</table>");
+
+    html.push_str("<h2>Open incidents</h2>");
+    match &snapshot.monitors {
+        None => html.push_str("<p class=\"muted\">Incident tracking is not configured.</p>"),
+        Some(Err(e)) => {
+            let _ = write!(html, "<p>Failed to read monitor state: {}</p>", escape(e));
+        }
+        Some(Ok(monitors)) if monitors.iter().all(|(_, s)| s.active_incidents.is_empty()) => {
+            html.push_str("<p>No open incidents.</p>");
+        }
+        Some(Ok(monitors)) => {
+            html.push_str("<table><tr><th>Monitor</th><th>Incident</th><th>Last healthy</th></tr>");
+            for (name, state) in monitors {
+                for (_, incident_id) in &state.active_incidents {
+                    let _ = write!(
+                        html,
+                        "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                        escape(name),
+                        escape(incident_id),
+                        format_age(now, state.last_healthy)
+                    );
+                }
+            }
+            html.push_str("</table>");
+        }
+    }
+
+    html.push_str("<h2>Proving backlog</h2>");
+    match snapshot.oldest_unproved {
+        Some((batch_id, proposed_at)) => {
+            let _ = write!(
+                html,
+                "<p>{} batches awaiting proof, oldest is batch {batch_id} proposed {}.</p>",
+                snapshot.unproved_batches,
+                format_age(now, Some(proposed_at))
+            );
+        }
+        None => html.push_str("<p>All batches are proven.</p>"),
+    }
+
+    html.push_str("</body></html>");
+    html
+}
+
+/// Human readable time elapsed since `time`, e.g. `3m 12s ago`.
+fn format_age(now: DateTime<Utc>, time: Option<DateTime<Utc>>) -> String {
+    let Some(time) = time else {
+        return "unknown".to_owned();
+    };
+    let secs = (now - time).num_seconds().max(0);
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes}m ago")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s ago")
+    } else {
+        format!("{seconds}s ago")
+    }
+}
+
+/// Escape text for inclusion in HTML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn snapshot(now: DateTime<Utc>) -> StatusSnapshot {
+        StatusSnapshot {
+            generated_at: now,
+            l1: HeadStatus {
+                block_number: Some(100),
+                block_time: Some(now - Duration::seconds(7)),
+            },
+            l2: HeadStatus::default(),
+            unproved_batches: 0,
+            oldest_unproved: None,
+            monitors: None,
+        }
+    }
+
+    #[test]
+    fn format_age_picks_largest_units() {
+        let now = Utc::now();
+        assert_eq!(format_age(now, None), "unknown");
+        assert_eq!(format_age(now, Some(now - Duration::seconds(42))), "42s ago");
+        assert_eq!(format_age(now, Some(now - Duration::seconds(192))), "3m 12s ago");
+        assert_eq!(format_age(now, Some(now - Duration::minutes(125))), "2h 5m ago");
+        assert_eq!(format_age(now, Some(now + Duration::seconds(5))), "0s ago");
+    }
+
+    #[test]
+    fn renders_heads_and_backlog() {
+        let now = Utc::now();
+        let mut snapshot = snapshot(now);
+        snapshot.unproved_batches = 3;
+        snapshot.oldest_unproved = Some((42, now - Duration::minutes(90)));
+
+        let html = render(&snapshot);
+        assert!(html.contains("<td>L1</td><td>100</td><td>7s ago</td>"));
+        assert!(html.contains("<td>L2</td><td>unknown</td><td>unknown</td>"));
+        assert!(html.contains("3 batches awaiting proof, oldest is batch 42 proposed 1h 30m ago"));
+        assert!(html.contains("Incident tracking is not configured."));
+    }
+
+    #[test]
+    fn renders_open_incidents_escaped() {
+        let now = Utc::now();
+        let mut snapshot = snapshot(now);
+        snapshot.monitors = Some(Ok(vec![
+            (
+                "<script>".to_owned(),
+                MonitorState {
+                    active_incidents: vec![(Value::from(1), "inc&1".to_owned())],
+                    last_healthy: None,
+                },
+            ),
+            ("quiet".to_owned(), MonitorState { active_incidents: vec![], last_healthy: None }),
+        ]));
+
+        let html = render(&snapshot);
+        assert!(html.contains("<td>&lt;script&gt;</td><td>inc&amp;1</td><td>unknown</td>"));
+        assert!(!html.contains("quiet"));
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn reports_no_incidents() {
+        let mut snapshot = snapshot(Utc::now());
+        snapshot.monitors = Some(Ok(vec![(
+            "quiet".to_owned(),
+            MonitorState { active_incidents: vec![], last_healthy: None },
+        )]));
+        assert!(render(&snapshot).contains("No open incidents."));
+    }
+}
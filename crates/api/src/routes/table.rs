@@ -1,23 +1,28 @@
 //! Paginated table endpoints
 
 use crate::{
+    extract::Query,
     helpers::{
-        blobs_bucket_size, bucket_size_from_range, format_address, parse_optional_address,
-        query_error,
+        PageResponse, RowCount, blobs_bucket_size, bucket_size_from_range, format_address,
+        paginate, parse_optional_address, query_error, stream_page, time_bucket_secs_from_range,
+        unpaged,
     },
     state::{ApiState, MAX_TABLE_LIMIT},
     validation::{
-        CommonQuery, PaginatedQuery, QueryMode, UnifiedQuery, has_time_range_params,
-        resolve_time_range_bounds, resolve_time_range_enum, resolve_time_range_since,
-        validate_pagination, validate_range_exclusivity, validate_time_range,
-        validate_unified_query,
+        BucketBy, BucketQuery, CommonQuery, EnvelopeQuery, PaginatedQuery, QueryMode, UnifiedQuery,
+        validate_bucket_by, validate_range_exclusivity, validate_unified_query,
     },
 };
+use alloy_primitives::B256;
 use api_types::*;
 use axum::{
     Json,
-    extract::{Query, State},
+    extract::State,
+    response::{IntoResponse, Response},
 };
+use clickhouse_lib::{BatchBlobCountRow, L2BlockTimeRow, L2GasUsedRow, L2TpsRow};
+use futures::{StreamExt, TryStreamExt};
+use primitives::reorg::ReorgCause;
 
 // Legacy type aliases for backward compatibility
 // type BlockTransactionsQuery = BlockPaginatedQuery; // Removed - not used anymore
@@ -27,11 +32,12 @@ type RangeQuery = CommonQuery;
     get,
     path = "/reorgs",
     params(
-        PaginatedQuery
+        PaginatedQuery,
+        EnvelopeQuery
     ),
     responses(
-        (status = 200, description = "Reorg events", body = ReorgEventsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "Reorg events", body = Paginated<L2ReorgEvent>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -40,31 +46,29 @@ type RangeQuery = CommonQuery;
 /// Results are ordered by insertion time in descending order.
 pub async fn reorgs(
     Query(params): Query<PaginatedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<ReorgEventsResponse>, ErrorResponse> {
-    validate_time_range(&params.common.time_range)?;
-    let limit = validate_pagination(
-        params.starting_after.as_ref(),
-        params.ending_before.as_ref(),
-        params.limit.as_ref(),
-        MAX_TABLE_LIMIT,
-    )?;
-    let has_time_range = has_time_range_params(&params.common.time_range);
+) -> Result<Response, ErrorResponse> {
+    params.common.time_range.validate()?;
+    let page = params.page(MAX_TABLE_LIMIT)?;
+    let has_time_range = params.common.time_range.is_set();
     let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
     validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-    let (since, until) = resolve_time_range_bounds(&params.common.time_range);
-    let rows = match state
+    let (since, until) = params.common.time_range.bounds();
+    let rows = state
         .client
-        .get_l2_reorgs_paginated(since, until, limit, params.starting_after, params.ending_before)
+        .stream_l2_reorgs_paginated(
+            since,
+            until,
+            page.limit,
+            page.starting_after,
+            page.ending_before,
+        )
         .await
-    {
-        Ok(rows) => rows,
-        Err(e) => return Err(query_error("reorg events", e)),
-    };
-    let events: Vec<L2ReorgEvent> = rows
-        .into_iter()
-        .map(|e| {
+        .map_err(|e| query_error("reorg events", e))?;
+    let events = rows
+        .map_ok(|e| {
             let from_block_number = e.l2_block_number + u64::from(e.depth);
             L2ReorgEvent {
                 from_block_number,
@@ -72,12 +76,15 @@ pub async fn reorgs(
                 depth: e.depth,
                 old_sequencer: format_address(e.old_sequencer),
                 new_sequencer: format_address(e.new_sequencer),
+                probable_cause: ReorgCause::from_code(e.probable_cause).as_str().to_owned(),
                 inserted_at: e.inserted_at,
             }
         })
-        .collect();
-    tracing::info!(count = events.len(), "Returning reorg events");
-    Ok(Json(ReorgEventsResponse { events }))
+        .boxed();
+    let count = RowCount { table: "l2_reorgs", since, until };
+    stream_page(&state, &envelope, &page, events, |r| r.to_block_number, Some(count), "events")
+        .await
+        .map_err(|e| query_error("reorg events", e))
 }
 
 #[utoipa::path(
@@ -88,7 +95,7 @@ pub async fn reorgs(
     ),
     responses(
         (status = 200, description = "Slashing events", body = SlashingEventsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -97,11 +104,11 @@ pub async fn slashings(
     Query(params): Query<RangeQuery>,
     State(state): State<ApiState>,
 ) -> Result<Json<SlashingEventsResponse>, ErrorResponse> {
-    validate_time_range(&params.time_range)?;
-    let has_time_range = has_time_range_params(&params.time_range);
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
     validate_range_exclusivity(has_time_range, false)?;
 
-    let (since, until) = resolve_time_range_bounds(&params.time_range);
+    let (since, until) = params.time_range.bounds();
     let events = match state.client.get_slashing_events_range(since, until).await {
         Ok(rows) => rows,
         Err(e) => return Err(query_error("slashing events", e)),
@@ -110,6 +117,96 @@ pub async fn slashings(
     Ok(Json(SlashingEventsResponse { events }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/preconf-mismatches",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Soft blocks that differed from their canonical block", body = PreconfMismatchesResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get soft blocks whose transactions differed from the canonical block within the requested
+/// time range.
+pub async fn preconf_mismatches(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<PreconfMismatchesResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let (since, until) = params.time_range.bounds();
+    let rows = state
+        .client
+        .get_preconf_mismatches_range(since, until)
+        .await
+        .map_err(|e| query_error("preconf mismatches", e))?;
+
+    let format_hash = |hash| B256::from(hash).to_string();
+    let events: Vec<PreconfMismatchEvent> = rows
+        .into_iter()
+        .map(|r| PreconfMismatchEvent {
+            l2_block_number: r.l2_block_number,
+            soft_block_hash: r.soft_block_hash.map(format_hash),
+            canonical_block_hash: r.canonical_block_hash.map(format_hash),
+            soft_tx_count: r.soft_tx_count,
+            canonical_tx_count: r.canonical_tx_count,
+            missing_tx_hashes: r.missing_tx_hashes.into_iter().map(format_hash).collect(),
+            extra_tx_hashes: r.extra_tx_hashes.into_iter().map(format_hash).collect(),
+            reordered: r.reordered != 0,
+        })
+        .collect();
+    tracing::info!(count = events.len(), "Returning preconf mismatches");
+    Ok(Json(PreconfMismatchesResponse { events }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/anchor-mismatches",
+    params(
+        RangeQuery
+    ),
+    responses(
+        (status = 200, description = "Batches whose anchor block hash differed from the indexed L1 block", body = AnchorMismatchesResponse),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get batches whose anchor block hash differed from the indexed L1 block at their anchor block
+/// id, detected within the requested time range.
+pub async fn anchor_mismatches(
+    Query(params): Query<RangeQuery>,
+    State(state): State<ApiState>,
+) -> Result<Json<AnchorMismatchesResponse>, ErrorResponse> {
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
+    validate_range_exclusivity(has_time_range, false)?;
+
+    let (since, until) = params.time_range.bounds();
+    let rows = state
+        .client
+        .get_anchor_mismatches_range(since, until)
+        .await
+        .map_err(|e| query_error("anchor mismatches", e))?;
+
+    let events: Vec<AnchorMismatchEvent> = rows
+        .into_iter()
+        .map(|r| AnchorMismatchEvent {
+            batch_id: r.batch_id,
+            l1_block_number: r.l1_block_number,
+            anchor_block_id: r.anchor_block_id,
+            anchor_block_hash: B256::from(r.anchor_block_hash).to_string(),
+            l1_block_hash: B256::from(r.l1_block_hash).to_string(),
+        })
+        .collect();
+    tracing::info!(count = events.len(), "Returning anchor mismatches");
+    Ok(Json(AnchorMismatchesResponse { events }))
+}
+
 #[utoipa::path(
     get,
     path = "/forced-inclusions",
@@ -118,7 +215,7 @@ pub async fn slashings(
     ),
     responses(
         (status = 200, description = "Forced inclusion events", body = ForcedInclusionEventsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -127,11 +224,11 @@ pub async fn forced_inclusions(
     Query(params): Query<RangeQuery>,
     State(state): State<ApiState>,
 ) -> Result<Json<ForcedInclusionEventsResponse>, ErrorResponse> {
-    validate_time_range(&params.time_range)?;
-    let has_time_range = has_time_range_params(&params.time_range);
+    params.time_range.validate()?;
+    let has_time_range = params.time_range.is_set();
     validate_range_exclusivity(has_time_range, false)?;
 
-    let (since, until) = resolve_time_range_bounds(&params.time_range);
+    let (since, until) = params.time_range.bounds();
     let events = match state.client.get_forced_inclusions_range(since, until).await {
         Ok(rows) => rows,
         Err(e) => return Err(query_error("forced inclusion events", e)),
@@ -144,11 +241,12 @@ pub async fn forced_inclusions(
     get,
     path = "/failed-proposals",
     params(
-        PaginatedQuery
+        PaginatedQuery,
+        EnvelopeQuery
     ),
     responses(
-        (status = 200, description = "Failed proposal events", body = FailedProposalEventsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "Failed proposal events", body = Paginated<FailedProposalEvent>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -156,27 +254,23 @@ pub async fn forced_inclusions(
 /// Results are ordered by insertion time (desc), then batch id (desc).
 pub async fn failed_proposals(
     Query(params): Query<PaginatedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<FailedProposalEventsResponse>, ErrorResponse> {
-    validate_time_range(&params.common.time_range)?;
-    let limit = validate_pagination(
-        params.starting_after.as_ref(),
-        params.ending_before.as_ref(),
-        params.limit.as_ref(),
-        MAX_TABLE_LIMIT,
-    )?;
+) -> Result<PageResponse<FailedProposalEvent, FailedProposalEventsResponse>, ErrorResponse> {
+    params.common.time_range.validate()?;
+    let page = params.page(MAX_TABLE_LIMIT)?;
     // Allow combining time range parameters with cursors for failed-proposals
     // to support stable pagination (timestamp + block-number tie-breaker).
 
-    let (since, until) = resolve_time_range_bounds(&params.common.time_range);
+    let (since, until) = params.common.time_range.bounds();
     let rows = state
         .client
         .get_failed_proposals_paginated(
             since,
             until,
-            limit,
-            params.starting_after,
-            params.ending_before,
+            page.limit,
+            page.starting_after,
+            page.ending_before,
         )
         .await
         .map_err(|e| query_error("failed proposal events", e))?;
@@ -192,66 +286,106 @@ pub async fn failed_proposals(
         })
         .collect();
     tracing::info!(count = events.len(), "Returning failed proposal events");
-    Ok(Json(FailedProposalEventsResponse { events }))
+    // Failed proposals are found by joining batches with their blocks, so they are not counted
+    Ok(paginate(
+        &state,
+        &envelope,
+        &page,
+        events,
+        |r| r.batch_id,
+        None,
+        |events, next_cursor, prev_cursor| FailedProposalEventsResponse {
+            events,
+            next_cursor,
+            prev_cursor,
+        },
+    )
+    .await)
 }
 
 #[utoipa::path(
     get,
     path = "/l2-tps",
     params(
-        UnifiedQuery
+        UnifiedQuery,
+        EnvelopeQuery,
+        BucketQuery
     ),
     responses(
-        (status = 200, description = "L2 TPS (regular or aggregated)", body = L2TpsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "L2 TPS (regular or aggregated)", body = Paginated<L2TpsRow>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
 /// Get L2 transactions per second data.
 ///
 /// Use ?aggregated for aggregated data with automatic bucketing based on time range.
+/// Aggregated data is bucketed over block numbers, or over wallclock aligned minutes or hours
+/// with `bucket_by=time`.
 /// Without ?aggregated, returns paginated results ordered by block number in descending order.
 #[allow(clippy::cognitive_complexity)]
 pub async fn l2_tps(
     Query(params): Query<UnifiedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
+    Query(bucket_query): Query<BucketQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<L2TpsResponse>, ErrorResponse> {
+) -> Result<PageResponse<L2TpsRow, L2TpsResponse>, ErrorResponse> {
     let query_mode = validate_unified_query(&params, MAX_TABLE_LIMIT)?;
 
     match query_mode {
         QueryMode::Aggregated => {
             // Aggregated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             validate_range_exclusivity(has_time_range, false)?;
 
-            let time_range = resolve_time_range_enum(&params.common.time_range);
+            let time_range = params.common.time_range.to_time_range();
             let address = parse_optional_address(params.common.address.as_ref())?;
-            let bucket = bucket_size_from_range(&time_range);
-            let blocks = match state.client.get_l2_tps(address, time_range, Some(bucket)).await {
-                Ok(rows) => rows,
-                Err(e) => return Err(query_error("L2 TPS", e)),
-            };
+            let blocks = match validate_bucket_by(bucket_query.bucket_by.as_deref())? {
+                BucketBy::Block => {
+                    let bucket = bucket_size_from_range(&time_range);
+                    state.client.get_l2_tps(address, time_range, Some(bucket)).await
+                }
+                BucketBy::Time => {
+                    let secs = time_bucket_secs_from_range(&time_range);
+                    state.client.get_l2_time_buckets(address, time_range, secs, None).await.map(
+                        |rows| {
+                            rows.iter()
+                                .map(|r| L2TpsRow {
+                                    l2_block_number: r.last_block,
+                                    tps: r.tps(secs),
+                                    block_time: Some(r.bucket_start()),
+                                })
+                                .collect()
+                        },
+                    )
+                }
+            }
+            .map_err(|e| query_error("L2 TPS", e))?;
             tracing::info!(count = blocks.len(), "Returning aggregated L2 TPS");
-            Ok(Json(L2TpsResponse { blocks }))
+            Ok(unpaged(&envelope, blocks, |blocks| L2TpsResponse {
+                blocks,
+                next_cursor: None,
+                prev_cursor: None,
+            }))
         }
-        QueryMode::Regular { limit } => {
+        QueryMode::Regular { page } => {
             // Regular paginated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
             validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-            let since = resolve_time_range_since(&params.common.time_range);
+            let since = params.common.time_range.since();
             let address = parse_optional_address(params.common.address.as_ref())?;
 
             let blocks = match state
                 .client
                 .get_l2_tps_paginated(
                     since,
-                    limit,
-                    params.starting_after,
-                    params.ending_before,
+                    page.limit,
+                    page.starting_after,
+                    page.ending_before,
                     address,
                 )
                 .await
@@ -261,7 +395,21 @@ pub async fn l2_tps(
             };
 
             tracing::info!(count = blocks.len(), "Returning paginated L2 TPS");
-            Ok(Json(L2TpsResponse { blocks }))
+            let count = RowCount::since("l2_head_events", since);
+            Ok(paginate(
+                &state,
+                &envelope,
+                &page,
+                blocks,
+                |r| r.l2_block_number,
+                Some(count),
+                |blocks, next_cursor, prev_cursor| L2TpsResponse {
+                    blocks,
+                    next_cursor,
+                    prev_cursor,
+                },
+            )
+            .await)
         }
     }
 }
@@ -270,72 +418,107 @@ pub async fn l2_tps(
     get,
     path = "/l2-block-times",
     params(
-        UnifiedQuery
+        UnifiedQuery,
+        EnvelopeQuery,
+        BucketQuery
     ),
     responses(
-        (status = 200, description = "L2 block times (regular or aggregated)", body = L2BlockTimesResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "L2 block times (regular or aggregated)", body = Paginated<L2BlockTimeRow>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
 /// Get L2 block timing information.
 ///
 /// Use ?aggregated for aggregated data with automatic bucketing based on time range.
+/// Aggregated data is bucketed over block numbers, or over wallclock aligned minutes or hours
+/// with `bucket_by=time`.
 /// Without ?aggregated, returns paginated results ordered by block number in descending order.
 #[allow(clippy::cognitive_complexity)]
 pub async fn l2_block_times(
     Query(params): Query<UnifiedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
+    Query(bucket_query): Query<BucketQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<L2BlockTimesResponse>, ErrorResponse> {
+) -> Result<Response, ErrorResponse> {
     let query_mode = validate_unified_query(&params, MAX_TABLE_LIMIT)?;
 
     match query_mode {
         QueryMode::Aggregated => {
             // Aggregated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             validate_range_exclusivity(has_time_range, false)?;
 
-            let time_range = resolve_time_range_enum(&params.common.time_range);
+            let time_range = params.common.time_range.to_time_range();
             let address = parse_optional_address(params.common.address.as_ref())?;
-            let bucket = bucket_size_from_range(&time_range);
-            let blocks =
-                match state.client.get_l2_block_times(address, time_range, Some(bucket)).await {
-                    Ok(rows) => rows,
-                    Err(e) => return Err(query_error("L2 block times", e)),
-                };
+            let blocks = match validate_bucket_by(bucket_query.bucket_by.as_deref())? {
+                BucketBy::Block => {
+                    let bucket = bucket_size_from_range(&time_range);
+                    state.client.get_l2_block_times(address, time_range, Some(bucket)).await
+                }
+                BucketBy::Time => {
+                    let secs = time_bucket_secs_from_range(&time_range);
+                    state.client.get_l2_time_buckets(address, time_range, secs, None).await.map(
+                        |rows| {
+                            rows.iter()
+                                .filter_map(|r| {
+                                    Some(L2BlockTimeRow {
+                                        l2_block_number: r.last_block,
+                                        block_time: r.bucket_start(),
+                                        s_since_prev_block: r.avg_block_time_secs()?,
+                                    })
+                                })
+                                .collect()
+                        },
+                    )
+                }
+            }
+            .map_err(|e| query_error("L2 block times", e))?;
             tracing::info!(count = blocks.len(), "Returning aggregated L2 block times");
-            Ok(Json(L2BlockTimesResponse { blocks }))
+            Ok(unpaged(&envelope, blocks, |blocks| L2BlockTimesResponse {
+                blocks,
+                next_cursor: None,
+                prev_cursor: None,
+            })
+            .into_response())
         }
-        QueryMode::Regular { limit } => {
+        QueryMode::Regular { page } => {
             // Regular paginated mode - use block range parameters
             // For regular mode, we need to support both time-based and block-based queries
             // For now, we'll use time-based queries (like the original table endpoint)
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
             validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-            let (since, _until) = resolve_time_range_bounds(&params.common.time_range);
+            let (since, _until) = params.common.time_range.bounds();
             let address = parse_optional_address(params.common.address.as_ref())?;
 
-            let rows = match state
+            let rows = state
                 .client
-                .get_l2_block_times_paginated(
+                .stream_l2_block_times_paginated(
                     since,
-                    limit,
-                    params.starting_after,
-                    params.ending_before,
+                    page.limit,
+                    page.starting_after,
+                    page.ending_before,
                     address,
                 )
                 .await
-            {
-                Ok(r) => r,
-                Err(e) => return Err(query_error("L2 block times", e)),
-            };
-
-            tracing::info!(count = rows.len(), "Returning paginated L2 block times");
-            Ok(Json(L2BlockTimesResponse { blocks: rows }))
+                .map_err(|e| query_error("L2 block times", e))?;
+
+            let count = RowCount::since("l2_head_events", since);
+            stream_page(
+                &state,
+                &envelope,
+                &page,
+                rows,
+                |r| r.l2_block_number,
+                Some(count),
+                "blocks",
+            )
+            .await
+            .map_err(|e| query_error("L2 block times", e))
         }
     }
 }
@@ -344,60 +527,85 @@ pub async fn l2_block_times(
     get,
     path = "/l2-gas-used",
     params(
-        UnifiedQuery
+        UnifiedQuery,
+        EnvelopeQuery,
+        BucketQuery
     ),
     responses(
-        (status = 200, description = "L2 gas used (regular or aggregated)", body = L2GasUsedResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "L2 gas used (regular or aggregated)", body = Paginated<L2GasUsedRow>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
 /// Get L2 gas usage information per block.
 ///
 /// Use ?aggregated for aggregated data with automatic bucketing based on time range.
+/// Aggregated data is bucketed over block numbers, or over wallclock aligned minutes or hours
+/// with `bucket_by=time`.
 /// Without ?aggregated, returns paginated results ordered by block number in descending order.
 #[allow(clippy::cognitive_complexity)]
 pub async fn l2_gas_used(
     Query(params): Query<UnifiedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
+    Query(bucket_query): Query<BucketQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<L2GasUsedResponse>, ErrorResponse> {
+) -> Result<PageResponse<L2GasUsedRow, L2GasUsedResponse>, ErrorResponse> {
     let query_mode = validate_unified_query(&params, MAX_TABLE_LIMIT)?;
 
     match query_mode {
         QueryMode::Aggregated => {
             // Aggregated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             validate_range_exclusivity(has_time_range, false)?;
 
-            let time_range = resolve_time_range_enum(&params.common.time_range);
+            let time_range = params.common.time_range.to_time_range();
             let address = parse_optional_address(params.common.address.as_ref())?;
-            let bucket = bucket_size_from_range(&time_range);
-            let blocks = match state.client.get_l2_gas_used(address, time_range, Some(bucket)).await
-            {
-                Ok(rows) => rows,
-                Err(e) => return Err(query_error("L2 gas used", e)),
-            };
+            let blocks = match validate_bucket_by(bucket_query.bucket_by.as_deref())? {
+                BucketBy::Block => {
+                    let bucket = bucket_size_from_range(&time_range);
+                    state.client.get_l2_gas_used(address, time_range, Some(bucket)).await
+                }
+                BucketBy::Time => {
+                    let secs = time_bucket_secs_from_range(&time_range);
+                    state.client.get_l2_time_buckets(address, time_range, secs, None).await.map(
+                        |rows| {
+                            rows.iter()
+                                .map(|r| L2GasUsedRow {
+                                    l2_block_number: r.last_block,
+                                    block_time: r.bucket_start(),
+                                    gas_used: r.avg_gas_used(),
+                                })
+                                .collect()
+                        },
+                    )
+                }
+            }
+            .map_err(|e| query_error("L2 gas used", e))?;
             tracing::info!(count = blocks.len(), "Returning aggregated L2 gas used");
-            Ok(Json(L2GasUsedResponse { blocks }))
+            Ok(unpaged(&envelope, blocks, |blocks| L2GasUsedResponse {
+                blocks,
+                next_cursor: None,
+                prev_cursor: None,
+            }))
         }
-        QueryMode::Regular { limit } => {
+        QueryMode::Regular { page } => {
             // Regular paginated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
             validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-            let since = resolve_time_range_since(&params.common.time_range);
+            let since = params.common.time_range.since();
             let address = parse_optional_address(params.common.address.as_ref())?;
 
             let rows = match state
                 .client
                 .get_l2_gas_used_paginated(
                     since,
-                    limit,
-                    params.starting_after,
-                    params.ending_before,
+                    page.limit,
+                    page.starting_after,
+                    page.ending_before,
                     address,
                 )
                 .await
@@ -407,7 +615,21 @@ pub async fn l2_gas_used(
             };
 
             tracing::info!(count = rows.len(), "Returning paginated L2 gas used");
-            Ok(Json(L2GasUsedResponse { blocks: rows }))
+            let count = RowCount::since("l2_head_events", since);
+            Ok(paginate(
+                &state,
+                &envelope,
+                &page,
+                rows,
+                |r| r.l2_block_number,
+                Some(count),
+                |blocks, next_cursor, prev_cursor| L2GasUsedResponse {
+                    blocks,
+                    next_cursor,
+                    prev_cursor,
+                },
+            )
+            .await)
         }
     }
 }
@@ -416,11 +638,12 @@ pub async fn l2_gas_used(
     get,
     path = "/block-transactions",
     params(
-        UnifiedQuery
+        UnifiedQuery,
+        EnvelopeQuery
     ),
     responses(
-        (status = 200, description = "Block transactions (regular or aggregated)", body = BlockTransactionsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "Block transactions (regular or aggregated)", body = Paginated<BlockTransactionsItem>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -431,18 +654,19 @@ pub async fn l2_gas_used(
 #[allow(clippy::cognitive_complexity)]
 pub async fn block_transactions(
     Query(params): Query<UnifiedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<BlockTransactionsResponse>, ErrorResponse> {
+) -> Result<Response, ErrorResponse> {
     let query_mode = validate_unified_query(&params, MAX_TABLE_LIMIT)?;
 
     match query_mode {
         QueryMode::Aggregated => {
             // Aggregated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             validate_range_exclusivity(has_time_range, false)?;
 
-            let time_range = resolve_time_range_enum(&params.common.time_range);
+            let time_range = params.common.time_range.to_time_range();
             let address = parse_optional_address(params.common.address.as_ref())?;
             let bucket = bucket_size_from_range(&time_range);
             let rows = match state
@@ -464,45 +688,47 @@ pub async fn block_transactions(
                 .collect();
 
             tracing::info!(count = blocks.len(), "Returning aggregated block transactions");
-            Ok(Json(BlockTransactionsResponse { blocks }))
+            Ok(unpaged(&envelope, blocks, |blocks| BlockTransactionsResponse {
+                blocks,
+                next_cursor: None,
+                prev_cursor: None,
+            })
+            .into_response())
         }
-        QueryMode::Regular { limit } => {
+        QueryMode::Regular { page } => {
             // Regular paginated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
             validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-            let since = resolve_time_range_since(&params.common.time_range);
+            let since = params.common.time_range.since();
             let address = parse_optional_address(params.common.address.as_ref())?;
 
-            let rows = match state
+            let rows = state
                 .client
-                .get_block_transactions_paginated(
+                .stream_block_transactions_paginated(
                     since,
-                    limit,
-                    params.starting_after,
-                    params.ending_before,
+                    page.limit,
+                    page.starting_after,
+                    page.ending_before,
                     address,
-                    None, // No bucketing for regular mode
                 )
                 .await
-            {
-                Ok(r) => r,
-                Err(e) => return Err(query_error("block transactions", e)),
-            };
+                .map_err(|e| query_error("block transactions", e))?;
 
-            let blocks: Vec<BlockTransactionsItem> = rows
-                .into_iter()
-                .map(|r| BlockTransactionsItem {
+            let blocks = rows
+                .map_ok(|r| BlockTransactionsItem {
                     block_number: r.l2_block_number,
                     txs: r.sum_tx,
                     block_time: r.block_time,
                 })
-                .collect();
+                .boxed();
 
-            tracing::info!(count = blocks.len(), "Returning paginated block transactions");
-            Ok(Json(BlockTransactionsResponse { blocks }))
+            let count = RowCount::since("l2_head_events", since);
+            stream_page(&state, &envelope, &page, blocks, |r| r.block_number, Some(count), "blocks")
+                .await
+                .map_err(|e| query_error("block transactions", e))
         }
     }
 }
@@ -511,11 +737,12 @@ pub async fn block_transactions(
     get,
     path = "/blobs-per-batch",
     params(
-        UnifiedQuery
+        UnifiedQuery,
+        EnvelopeQuery
     ),
     responses(
-        (status = 200, description = "Blobs per batch (regular or aggregated)", body = BatchBlobsResponse),
-        (status = 500, description = "Database error", body = ErrorResponse)
+        (status = 200, description = "Blobs per batch (regular or aggregated)", body = Paginated<BatchBlobCountRow>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
     ),
     tag = "taikoscope"
 )]
@@ -526,41 +753,46 @@ pub async fn block_transactions(
 #[allow(clippy::cognitive_complexity)]
 pub async fn blobs_per_batch(
     Query(params): Query<UnifiedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
     State(state): State<ApiState>,
-) -> Result<Json<BatchBlobsResponse>, ErrorResponse> {
+) -> Result<PageResponse<BatchBlobCountRow, BatchBlobsResponse>, ErrorResponse> {
     let query_mode = validate_unified_query(&params, MAX_TABLE_LIMIT)?;
 
     match query_mode {
         QueryMode::Aggregated => {
             // Aggregated mode - use time range parameters
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             validate_range_exclusivity(has_time_range, false)?;
 
-            let time_range = resolve_time_range_enum(&params.common.time_range);
+            let time_range = params.common.time_range.to_time_range();
             let _bucket = blobs_bucket_size(&time_range);
             let batches = match state.client.get_blobs_per_batch(time_range).await {
                 Ok(rows) => rows,
                 Err(e) => return Err(query_error("blobs per batch", e)),
             };
             tracing::info!(count = batches.len(), "Returning aggregated blobs per batch");
-            Ok(Json(BatchBlobsResponse { batches }))
+            Ok(unpaged(&envelope, batches, |batches| BatchBlobsResponse {
+                batches,
+                next_cursor: None,
+                prev_cursor: None,
+            }))
         }
-        QueryMode::Regular { limit } => {
+        QueryMode::Regular { page } => {
             // Regular paginated mode
-            validate_time_range(&params.common.time_range)?;
-            let has_time_range = has_time_range_params(&params.common.time_range);
+            params.common.time_range.validate()?;
+            let has_time_range = params.common.time_range.is_set();
             let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
             validate_range_exclusivity(has_time_range, has_slot_range)?;
 
-            let since = resolve_time_range_since(&params.common.time_range);
+            let since = params.common.time_range.since();
             let batches = match state
                 .client
                 .get_blobs_per_batch_paginated(
                     since,
-                    limit,
-                    params.starting_after,
-                    params.ending_before,
+                    page.limit,
+                    page.starting_after,
+                    page.ending_before,
                 )
                 .await
             {
@@ -568,7 +800,85 @@ pub async fn blobs_per_batch(
                 Err(e) => return Err(query_error("blobs per batch", e)),
             };
             tracing::info!(count = batches.len(), "Returning paginated blobs per batch");
-            Ok(Json(BatchBlobsResponse { batches }))
+            let count = RowCount::since("batches", since);
+            Ok(paginate(
+                &state,
+                &envelope,
+                &page,
+                batches,
+                |r| r.batch_id,
+                Some(count),
+                |batches, next_cursor, prev_cursor| BatchBlobsResponse {
+                    batches,
+                    next_cursor,
+                    prev_cursor,
+                },
+            )
+            .await)
         }
     }
 }
+
+#[utoipa::path(
+    get,
+    path = "/blob-fee-history",
+    params(
+        PaginatedQuery,
+        EnvelopeQuery
+    ),
+    responses(
+        (status = 200, description = "L1 blob fee market state per batch", body = Paginated<BlobFeeHistoryItem>),
+        (status = 500, description = "Database error", body = ErrorResponse, content_type = "application/problem+json")
+    ),
+    tag = "taikoscope"
+)]
+/// Get the L1 excess blob gas and blob base fee at the time each batch was proposed.
+///
+/// Results are ordered by batch id in descending order.
+pub async fn blob_fee_history(
+    Query(params): Query<PaginatedQuery>,
+    Query(envelope): Query<EnvelopeQuery>,
+    State(state): State<ApiState>,
+) -> Result<PageResponse<BlobFeeHistoryItem, BlobFeeHistoryResponse>, ErrorResponse> {
+    params.common.time_range.validate()?;
+    let page = params.page(MAX_TABLE_LIMIT)?;
+    let has_time_range = params.common.time_range.is_set();
+    let has_slot_range = params.starting_after.is_some() || params.ending_before.is_some();
+    validate_range_exclusivity(has_time_range, has_slot_range)?;
+
+    let since = params.common.time_range.since();
+    let rows = match state
+        .client
+        .get_blob_fee_history_paginated(since, page.limit, page.starting_after, page.ending_before)
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => return Err(query_error("blob fee history", e)),
+    };
+    let batches: Vec<BlobFeeHistoryItem> = rows
+        .into_iter()
+        .map(|r| BlobFeeHistoryItem {
+            batch_id: r.batch_id,
+            l1_block_number: r.l1_block_number,
+            block_ts: r.block_ts,
+            excess_blob_gas: r.excess_blob_gas,
+            blob_base_fee: r.blob_base_fee,
+        })
+        .collect();
+    tracing::info!(count = batches.len(), "Returning blob fee history");
+    let count = RowCount::since("batch_blob_fees", since);
+    Ok(paginate(
+        &state,
+        &envelope,
+        &page,
+        batches,
+        |b| b.batch_id,
+        Some(count),
+        |batches, next_cursor, prev_cursor| BlobFeeHistoryResponse {
+            batches,
+            next_cursor,
+            prev_cursor,
+        },
+    )
+    .await)
+}
@@ -1,9 +1,17 @@
 //! Shared state for API handlers and constants
 
+use crate::{
+    budget::QueryBudget,
+    cache::{CacheTtls, ResponseCache},
+    usage::OriginUsage,
+};
 use clickhouse_lib::ClickhouseReader;
+use primitives::builder::BuilderFingerprints;
 // use network::http_retry; // no longer used for price fetch retries
 
 use std::{
+    collections::HashMap,
+    path::PathBuf,
     sync::Arc,
     time::{Duration as StdDuration, Instant},
 };
@@ -21,15 +29,65 @@ pub const DEFAULT_RATE_PERIOD: StdDuration = StdDuration::from_secs(1);
 pub const MAX_BLOCK_TRANSACTIONS_LIMIT: u64 = 50000;
 /// Maximum number of records returned by table endpoints.
 pub const MAX_TABLE_LIMIT: u64 = 50000;
+/// Maximum number of blocks returned per page by `/batch/{batch_id}/blocks`, the protocol's
+/// `maxBlocksPerBatch`.
+pub const MAX_BATCH_BLOCKS_LIMIT: u64 = 768;
+/// Default number of entries per leaderboard.
+pub const DEFAULT_LEADERBOARD_LIMIT: u64 = 10;
+/// Maximum number of entries per leaderboard.
+pub const MAX_LEADERBOARD_LIMIT: u64 = 100;
+/// Default number of head blocks summarised by `/block-status-summary`.
+pub const DEFAULT_BLOCK_STATUS_WINDOW: u64 = 1000;
+/// Maximum number of head blocks summarised by `/block-status-summary`.
+pub const MAX_BLOCK_STATUS_WINDOW: u64 = 50000;
+/// Default number of ledger entries returned by `/bond-history`.
+pub const DEFAULT_BOND_HISTORY_LIMIT: u64 = 1000;
+/// Percentage of the base fee shared with the sequencer, assumed until the protocol configuration
+/// has been recorded.
+pub const DEFAULT_BASE_FEE_SHARING_PCTG: u8 = 75;
+/// Time each `/dashboard-data` section query may take before it is left out of the response.
+pub const DASHBOARD_SECTION_TIMEOUT: StdDuration = StdDuration::from_secs(5);
+
+/// Token bucket settings of the API rate limiter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    /// Tokens added to the bucket per `period`
+    pub max_requests: u64,
+    /// Refill period of the bucket
+    pub period: StdDuration,
+    /// Bucket size, i.e. the largest burst allowed
+    pub burst: u64,
+    /// Token cost per route path; routes not listed cost one token
+    pub route_weights: HashMap<String, u64>,
+}
+
+impl RateLimitConfig {
+    /// Create a configuration with a burst of `max_requests` and equal route weights.
+    pub fn new(max_requests: u64, period: StdDuration) -> Self {
+        Self { max_requests, period, burst: max_requests, route_weights: HashMap::new() }
+    }
+}
+
+/// Settings of the HTML status page served at `/status`.
+#[derive(Debug, Clone, Default)]
+pub struct StatusPageConfig {
+    /// Directory the monitors persist their open incidents in (incidents are omitted when unset)
+    pub incident_state_dir: Option<PathBuf>,
+}
 
 /// Shared state for API handlers.
 #[derive(Clone)]
 pub struct ApiState {
     pub(crate) client: ClickhouseReader,
     pub(crate) http_client: Client,
-    max_requests: u64,
-    rate_period: StdDuration,
+    rate_limit: RateLimitConfig,
+    status_page: Option<StatusPageConfig>,
+    admin_token: Option<Arc<str>>,
     price_cache: Arc<RwLock<CachedPrice>>,
+    response_cache: Arc<ResponseCache>,
+    origin_usage: Arc<OriginUsage>,
+    query_budget: QueryBudget,
+    builder_fingerprints: Arc<BuilderFingerprints>,
 }
 
 #[derive(Debug)]
@@ -53,25 +111,103 @@ impl ApiState {
         Self {
             client,
             http_client: Client::new(),
-            max_requests,
-            rate_period,
+            rate_limit: RateLimitConfig::new(max_requests, rate_period),
+            status_page: None,
+            admin_token: None,
             price_cache: Arc::new(RwLock::new(CachedPrice {
                 value: 0.0,
                 // Force initial fetch by setting updated_at before TTL
                 updated_at: Instant::now() - ttl - StdDuration::from_secs(1),
                 backoff_until: None,
             })),
+            response_cache: Arc::new(ResponseCache::new(CacheTtls::default())),
+            origin_usage: Arc::new(OriginUsage::new()),
+            query_budget: QueryBudget::default(),
+            builder_fingerprints: Arc::new(BuilderFingerprints::default()),
         }
     }
 
+    /// Enable response caching with the given per-group TTLs.
+    pub fn with_cache_ttls(mut self, ttls: CacheTtls) -> Self {
+        self.response_cache = Arc::new(ResponseCache::new(ttls));
+        self
+    }
+
+    /// Replace the rate limiter settings.
+    pub fn with_rate_limit(mut self, rate_limit: RateLimitConfig) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Limit the sub-queries of requests that fan out into many queries.
+    pub const fn with_query_budget(mut self, query_budget: QueryBudget) -> Self {
+        self.query_budget = query_budget;
+        self
+    }
+
+    /// Sub-query budget of a single request.
+    pub const fn query_budget(&self) -> QueryBudget {
+        self.query_budget
+    }
+
+    /// Identify L2 block builders by the given fingerprints.
+    pub fn with_builder_fingerprints(mut self, fingerprints: BuilderFingerprints) -> Self {
+        self.builder_fingerprints = Arc::new(fingerprints);
+        self
+    }
+
+    /// Fingerprints of the known L2 block builders.
+    pub fn builder_fingerprints(&self) -> &BuilderFingerprints {
+        &self.builder_fingerprints
+    }
+
+    /// Serve the HTML status page.
+    pub fn with_status_page(mut self, status_page: StatusPageConfig) -> Self {
+        self.status_page = Some(status_page);
+        self
+    }
+
+    /// Status page settings, `None` when the page is disabled.
+    pub const fn status_page(&self) -> Option<&StatusPageConfig> {
+        self.status_page.as_ref()
+    }
+
+    /// Serve the admin endpoints, authenticated with `token`. An empty token leaves them
+    /// disabled.
+    pub fn with_admin_token(mut self, token: impl Into<Arc<str>>) -> Self {
+        let token = token.into();
+        self.admin_token = (!token.trim().is_empty()).then_some(token);
+        self
+    }
+
+    /// Token required by the admin endpoints, `None` when they are disabled.
+    pub fn admin_token(&self) -> Option<&str> {
+        self.admin_token.as_deref()
+    }
+
+    /// Cache of JSON responses shared by all handlers.
+    pub fn response_cache(&self) -> &ResponseCache {
+        &self.response_cache
+    }
+
+    /// Request counters per origin shared with the server's tracking layer.
+    pub const fn origin_usage(&self) -> &Arc<OriginUsage> {
+        &self.origin_usage
+    }
+
     /// Maximum number of requests allowed per [`rate_period`].
     pub const fn max_requests(&self) -> u64 {
-        self.max_requests
+        self.rate_limit.max_requests
     }
 
     /// Time window for rate limiting.
     pub const fn rate_period(&self) -> StdDuration {
-        self.rate_period
+        self.rate_limit.period
+    }
+
+    /// Rate limiter settings.
+    pub const fn rate_limit(&self) -> &RateLimitConfig {
+        &self.rate_limit
     }
 
     /// Get the current ETH price in USD, with caching and rate-limit aware backoff.
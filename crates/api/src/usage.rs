@@ -0,0 +1,146 @@
+//! Per-origin request counters of the public API
+//!
+//! The server records every API request under the value of its `Origin` header, so that the
+//! consumers of the public API can be told apart and scrapers spotted before they exhaust the
+//! shared rate limit. Counters live in memory and start over when the server restarts.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use api_types::OriginUsageItem;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+
+/// Maximum number of distinct origins tracked; requests from further origins are counted under
+/// [`OTHER_ORIGINS`] so that spoofed `Origin` headers cannot grow the map without bound.
+const MAX_TRACKED_ORIGINS: usize = 1_000;
+/// Key of requests without an `Origin` header, e.g. from scripts and server-side clients.
+pub const NO_ORIGIN: &str = "(none)";
+/// Key of requests from origins seen after [`MAX_TRACKED_ORIGINS`] was reached.
+pub const OTHER_ORIGINS: &str = "(other)";
+
+/// Counters of one origin.
+#[derive(Debug, Clone)]
+struct OriginCounters {
+    allowed: bool,
+    requests: u64,
+    rate_limited: u64,
+    client_errors: u64,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+/// Request counters per `Origin` header value.
+#[derive(Debug)]
+pub struct OriginUsage {
+    since: DateTime<Utc>,
+    origins: Mutex<HashMap<String, OriginCounters>>,
+}
+
+impl Default for OriginUsage {
+    fn default() -> Self {
+        Self { since: Utc::now(), origins: Mutex::default() }
+    }
+}
+
+impl OriginUsage {
+    /// Start counting now.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time counting started at.
+    pub const fn since(&self) -> DateTime<Utc> {
+        self.since
+    }
+
+    /// Record a request from `origin` that was answered with `status`. `allowed` tells whether
+    /// the CORS policy accepts the origin. Returns `true` for the first request of an origin.
+    pub fn record(&self, origin: Option<&str>, allowed: bool, status: StatusCode) -> bool {
+        let now = Utc::now();
+        let mut origins = self.origins.lock().unwrap_or_else(|e| e.into_inner());
+        let mut key = origin.unwrap_or(NO_ORIGIN);
+        if !origins.contains_key(key) && origins.len() >= MAX_TRACKED_ORIGINS {
+            key = OTHER_ORIGINS;
+        }
+
+        let mut first = false;
+        let counters = origins.entry(key.to_owned()).or_insert_with(|| {
+            first = true;
+            OriginCounters {
+                allowed,
+                requests: 0,
+                rate_limited: 0,
+                client_errors: 0,
+                first_seen: now,
+                last_seen: now,
+            }
+        });
+        counters.requests += 1;
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            counters.rate_limited += 1;
+        } else if status.is_client_error() {
+            counters.client_errors += 1;
+        }
+        counters.last_seen = now;
+        first
+    }
+
+    /// Counters of every origin, most requests first.
+    pub fn snapshot(&self) -> Vec<OriginUsageItem> {
+        let origins = self.origins.lock().unwrap_or_else(|e| e.into_inner());
+        let mut items: Vec<OriginUsageItem> = origins
+            .iter()
+            .map(|(origin, c)| OriginUsageItem {
+                origin: origin.clone(),
+                allowed: c.allowed,
+                requests: c.requests,
+                rate_limited: c.rate_limited,
+                client_errors: c.client_errors,
+                first_seen: c.first_seen,
+                last_seen: c.last_seen,
+            })
+            .collect();
+        items.sort_by(|a, b| b.requests.cmp(&a.requests).then_with(|| a.origin.cmp(&b.origin)));
+        items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_requests_per_origin() {
+        let usage = OriginUsage::new();
+        assert!(usage.record(Some("https://taikoscope.xyz"), true, StatusCode::OK));
+        assert!(!usage.record(Some("https://taikoscope.xyz"), true, StatusCode::TOO_MANY_REQUESTS));
+        assert!(usage.record(Some("https://scraper.example"), false, StatusCode::BAD_REQUEST));
+        assert!(usage.record(None, true, StatusCode::OK));
+
+        let items = usage.snapshot();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].origin, "https://taikoscope.xyz");
+        assert_eq!((items[0].requests, items[0].rate_limited, items[0].client_errors), (2, 1, 0));
+        assert_eq!(items[1].origin, NO_ORIGIN);
+        assert_eq!(items[2].origin, "https://scraper.example");
+        assert!(!items[2].allowed);
+        assert_eq!(items[2].client_errors, 1);
+    }
+
+    #[test]
+    fn folds_origins_beyond_the_limit() {
+        let usage = OriginUsage::new();
+        for i in 0..MAX_TRACKED_ORIGINS + 5 {
+            usage.record(Some(&format!("https://{i}.example")), false, StatusCode::OK);
+        }
+        // Origins seen before the limit was reached keep their own counters
+        usage.record(Some("https://0.example"), false, StatusCode::OK);
+
+        let items = usage.snapshot();
+        assert_eq!(items.len(), MAX_TRACKED_ORIGINS + 1);
+        assert_eq!(items[0].origin, OTHER_ORIGINS);
+        assert_eq!(items[0].requests, 5);
+        assert_eq!(items[1].origin, "https://0.example");
+        assert_eq!(items[1].requests, 2);
+    }
+}
@@ -1,18 +1,31 @@
 //! Validation functions for API query parameters
 
-use crate::ErrorResponse;
-use axum::http::StatusCode;
-use chrono::{Duration as ChronoDuration, TimeZone};
+use crate::{ErrorCode, ErrorResponse, TimeRangePreset};
+use chrono::{DateTime, Duration as ChronoDuration, TimeZone, Utc};
 use clickhouse_lib::TimeRange;
+use primitives::{anomaly::AnomalyMetric, da::DaMode, incident::IncidentAction};
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
 /// Maximum allowed timestamp (reasonable upper bound to prevent overflow)
 const MAX_TIMESTAMP_MS: u64 = 4_102_444_800_000; // Year 2100
 
-/// Base time range filtering parameters
-#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+/// Length of the range used when no lower bound is given
+const DEFAULT_RANGE_MS: u64 = 3_600_000;
+
+/// Base time range filtering parameters.
+///
+/// A range is given either as a named preset ending now (`range`), as RFC3339 timestamps
+/// (`from`/`to`), or as millisecond timestamps (`created[...]`). Without a lower bound the range
+/// spans the hour before its end, and ranges never end after the current time.
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
 pub struct TimeRangeParams {
+    /// Named range ending now: `15m`, `1h`, `24h`, `7d` or `30d`
+    pub range: Option<TimeRangePreset>,
+    /// Start of the range as an RFC3339 timestamp (inclusive)
+    pub from: Option<DateTime<Utc>>,
+    /// End of the range as an RFC3339 timestamp (inclusive)
+    pub to: Option<DateTime<Utc>>,
     /// Filter for timestamps greater than this value (exclusive)
     #[serde(rename = "created[gt]", deserialize_with = "crate::validation::de_u64_opt", default)]
     pub created_gt: Option<u64>,
@@ -27,6 +40,183 @@ pub struct TimeRangeParams {
     pub created_lte: Option<u64>,
 }
 
+impl TimeRangeParams {
+    /// Whether any time range parameter is set
+    pub const fn is_set(&self) -> bool {
+        self.range.is_some() ||
+            self.from.is_some() ||
+            self.to.is_some() ||
+            self.created_gt.is_some() ||
+            self.created_gte.is_some() ||
+            self.created_lt.is_some() ||
+            self.created_lte.is_some()
+    }
+
+    /// Validate the parameters for logical consistency
+    pub fn validate(&self) -> Result<(), ErrorResponse> {
+        let has_created = self.created_gt.is_some() ||
+            self.created_gte.is_some() ||
+            self.created_lt.is_some() ||
+            self.created_lte.is_some();
+        let has_rfc3339 = self.from.is_some() || self.to.is_some();
+        if [self.range.is_some(), has_rfc3339, has_created].into_iter().filter(|&set| set).count() >
+            1
+        {
+            return Err(ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
+                "range, from/to and created[...] cannot be used together",
+            ));
+        }
+
+        // Check for mutually exclusive parameters
+        if let (Some(_), Some(_)) = (self.created_gt, self.created_gte) {
+            return Err(ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
+                "created[gt] and created[gte] cannot be used together",
+            ));
+        }
+
+        if let (Some(_), Some(_)) = (self.created_lt, self.created_lte) {
+            return Err(ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
+                "created[lt] and created[lte] cannot be used together",
+            ));
+        }
+
+        // Validate timestamp bounds
+        for &timestamp in
+            [self.created_gt, self.created_gte, self.created_lt, self.created_lte].iter().flatten()
+        {
+            if timestamp > MAX_TIMESTAMP_MS {
+                return Err(ErrorResponse::bad_request(
+                    ErrorCode::InvalidRange,
+                    format!("Timestamp {} is too large (max: {})", timestamp, MAX_TIMESTAMP_MS),
+                ));
+            }
+        }
+        for timestamp in [self.from, self.to].into_iter().flatten() {
+            let ms = timestamp.timestamp_millis();
+            if ms < 0 || ms as u64 > MAX_TIMESTAMP_MS {
+                return Err(ErrorResponse::bad_request(
+                    ErrorCode::InvalidRange,
+                    format!("Timestamp {} is out of range", timestamp.to_rfc3339()),
+                ));
+            }
+        }
+
+        // Validate logical ranges
+        let lower_bound =
+            self.created_gt.map(|v| v + 1).or(self.created_gte).or_else(|| self.from.map(millis));
+        let upper_bound = self.created_lt.or(self.created_lte).or_else(|| self.to.map(millis));
+
+        if let (Some(lower), Some(upper)) = (lower_bound, upper_bound) {
+            let is_inclusive = self.created_lt.is_none();
+            if (is_inclusive && lower > upper) || (!is_inclusive && lower >= upper) {
+                return Err(ErrorResponse::bad_request(
+                    ErrorCode::InvalidRange,
+                    "Invalid time range: start time must be before end time",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start (inclusive) and end (exclusive) of the range in milliseconds, evaluated at `now`.
+    /// The end is clamped to `now` and the start to the end.
+    fn span_ms(&self, now: DateTime<Utc>) -> (u64, u64) {
+        let now_end = millis(now).saturating_add(1);
+        let end = match (self.created_lt, self.created_lte, self.to) {
+            (Some(lt), _, _) => lt,
+            (None, Some(lte), _) => lte.saturating_add(1),
+            (None, None, Some(to)) => millis(to).saturating_add(1),
+            (None, None, None) => now_end,
+        }
+        .min(now_end);
+        let length = self.range.map_or(DEFAULT_RANGE_MS, |preset| preset.seconds() * 1000);
+        let start = self
+            .created_gt
+            .map(|v| v.saturating_add(1))
+            .or(self.created_gte)
+            .or_else(|| self.from.map(millis))
+            .unwrap_or_else(|| end.saturating_sub(1).saturating_sub(length))
+            .min(end);
+        (start, end)
+    }
+
+    /// Start and end of the range, both inclusive
+    pub fn bounds(&self) -> (DateTime<Utc>, DateTime<Utc>) {
+        self.bounds_at(Utc::now())
+    }
+
+    /// [`Self::bounds`] evaluated as if the current time were `now`
+    pub fn bounds_at(&self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let (start, end) = self.span_ms(now);
+        // Downstream queries use "inserted_at <= end", so the inclusive end is one millisecond
+        // before the exclusive one. An empty range collapses onto that millisecond.
+        let last = end.saturating_sub(1);
+        (from_millis(start.min(last), now), from_millis(last, now))
+    }
+
+    /// Start of the range
+    pub fn since(&self) -> DateTime<Utc> {
+        self.bounds().0
+    }
+
+    /// Length of the range as a `TimeRange`
+    pub fn to_time_range(&self) -> TimeRange {
+        self.to_time_range_at(Utc::now())
+    }
+
+    /// [`Self::to_time_range`] evaluated as if the current time were `now`
+    pub fn to_time_range_at(&self, now: DateTime<Utc>) -> TimeRange {
+        let (start, end) = self.span_ms(now);
+        TimeRange::from_duration(ChronoDuration::milliseconds((end - start) as i64))
+    }
+}
+
+/// Milliseconds since the epoch of `timestamp`, zero before the epoch
+fn millis(timestamp: DateTime<Utc>) -> u64 {
+    u64::try_from(timestamp.timestamp_millis()).unwrap_or_default()
+}
+
+/// Timestamp of `ms` milliseconds since the epoch, or `fallback` if out of range
+fn from_millis(ms: u64, fallback: DateTime<Utc>) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(ms as i64).single().unwrap_or(fallback)
+}
+
+/// Point-in-time parameter of analytics endpoints that support reproducible reports
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+pub struct AsOfQuery {
+    /// Evaluate the response as it would have been at this timestamp (ms): relative time ranges
+    /// end here and rows inserted later are ignored
+    #[serde(deserialize_with = "crate::validation::de_u64_opt", default)]
+    pub as_of: Option<u64>,
+}
+
+/// Response shape parameter of paginated endpoints
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+pub struct EnvelopeQuery {
+    /// Return the standard pagination envelope (default), or the legacy endpoint specific
+    /// response when `false`
+    pub envelope: Option<bool>,
+}
+
+impl EnvelopeQuery {
+    /// Whether the response should use the standard pagination envelope
+    pub fn enabled(&self) -> bool {
+        self.envelope.unwrap_or(true)
+    }
+}
+
+/// Downsampling parameter of chart endpoints
+#[derive(Debug, Default, Deserialize, ToSchema, IntoParams)]
+pub struct BucketQuery {
+    /// Aggregate the series over block number buckets ("block", default) or over wallclock
+    /// aligned time buckets of a minute or an hour ("time")
+    pub bucket_by: Option<String>,
+}
+
 /// Base block range filtering parameters
 #[derive(Debug, Deserialize, ToSchema, IntoParams)]
 pub struct BlockRangeParams {
@@ -54,6 +244,29 @@ pub struct CommonQuery {
     pub time_range: TimeRangeParams,
 }
 
+impl CommonQuery {
+    /// Stable hash of the filters, embedded in cursors so that a cursor cannot be
+    /// replayed against a different filter set
+    pub fn filter_hash(&self) -> u64 {
+        let tr = &self.time_range;
+        let canonical = format!(
+            "{}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}|{:?}",
+            self.address.as_deref().unwrap_or_default().to_lowercase(),
+            tr.created_gt,
+            tr.created_gte,
+            tr.created_lt,
+            tr.created_lte,
+            tr.range.map(TimeRangePreset::as_str),
+            tr.from.map(|t| t.timestamp_millis()),
+            tr.to.map(|t| t.timestamp_millis()),
+        );
+        // FNV-1a, so tokens stay valid across builds and restarts
+        canonical.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+}
+
 /// Extended query with pagination for endpoints that need it
 #[derive(Debug, Deserialize, ToSchema, IntoParams)]
 pub struct PaginatedQuery {
@@ -66,6 +279,8 @@ pub struct PaginatedQuery {
     pub starting_after: Option<u64>,
     /// Return items before this cursor (exclusive)
     pub ending_before: Option<u64>,
+    /// Opaque cursor taken from `next_cursor` or `prev_cursor` of a previous response
+    pub cursor: Option<String>,
 }
 
 /// Query parameters that combine block range filters with pagination
@@ -82,6 +297,48 @@ pub struct BlockPaginatedQuery {
     pub ending_before: Option<u64>,
 }
 
+impl PaginatedQuery {
+    /// Resolve the pagination window, decoding `cursor` if present
+    pub fn page(&self, max_limit: u64) -> Result<Page, ErrorResponse> {
+        resolve_pagination(
+            self.cursor.as_deref(),
+            self.starting_after.as_ref(),
+            self.ending_before.as_ref(),
+            self.limit.as_ref(),
+            max_limit,
+            self.common.filter_hash(),
+        )
+    }
+}
+
+/// Pagination of the blocks of a batch
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct BatchBlocksQuery {
+    /// Maximum number of blocks to return
+    pub limit: Option<u64>,
+    /// Return blocks below this block number (exclusive)
+    pub starting_after: Option<u64>,
+    /// Return blocks above this block number (exclusive)
+    pub ending_before: Option<u64>,
+    /// Opaque cursor taken from `next_cursor` or `prev_cursor` of a previous response
+    pub cursor: Option<String>,
+}
+
+impl BatchBlocksQuery {
+    /// Resolve the pagination window of the blocks of `batch_id`, decoding `cursor` if present.
+    /// Cursors are only valid for the batch they were issued for.
+    pub fn page(&self, batch_id: u64, max_limit: u64) -> Result<Page, ErrorResponse> {
+        resolve_pagination(
+            self.cursor.as_deref(),
+            self.starting_after.as_ref(),
+            self.ending_before.as_ref(),
+            self.limit.as_ref(),
+            max_limit,
+            batch_id,
+        )
+    }
+}
+
 /// Query parameters for block profit ranking endpoints
 #[derive(Debug, Deserialize, ToSchema, IntoParams)]
 pub struct ProfitQuery {
@@ -94,6 +351,105 @@ pub struct ProfitQuery {
     pub order: Option<String>,
 }
 
+/// Query parameters for cost endpoints that can report values in USD or TAIKO
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct CostQuery {
+    /// Common query parameters
+    #[serde(flatten)]
+    pub common: CommonQuery,
+    /// Unit costs are reported in ("gwei", "usd" or "taiko", defaults to "gwei")
+    pub denomination: Option<String>,
+}
+
+/// Query parameters for the cost per transaction endpoint
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct CostPerTxQuery {
+    /// Time range filtering parameters
+    #[serde(flatten)]
+    pub time_range: TimeRangeParams,
+    /// Monthly hardware cost of one sequencer in USD (defaults to the built-in estimate)
+    pub hardware_cost_usd: Option<f64>,
+}
+
+/// Query parameters for the batch efficiency endpoint
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct BatchEfficiencyQuery {
+    /// Common query parameters
+    #[serde(flatten)]
+    pub common: CommonQuery,
+    /// Only include batches posted in this mode ("blob" or "calldata", defaults to both)
+    pub da_mode: Option<String>,
+}
+
+/// Query parameters for the anomalies endpoint
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct AnomalyQuery {
+    /// Common query parameters
+    #[serde(flatten)]
+    pub common: CommonQuery,
+    /// Only include anomalies of this metric (`"tps"`, `"gas_used"`, `"batch_cadence"` or
+    /// `"prove_time"`, defaults to all)
+    pub metric: Option<String>,
+}
+
+/// Query parameters for the incident history endpoint
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct IncidentHistoryQuery {
+    /// Common query parameters
+    #[serde(flatten)]
+    pub common: CommonQuery,
+    /// Only include operations on incidents affecting this Instatus component
+    pub component_id: Option<String>,
+    /// Only include operations of this kind ("create", "update" or "resolve", defaults to all)
+    pub action: Option<String>,
+}
+
+/// Unit cost values are reported in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Denomination {
+    /// Gwei, the default
+    Gwei,
+    /// USD, converted at the ETH price when the cost was incurred
+    Usd,
+    /// TAIKO, converted at the TAIKO/ETH price when the cost was incurred
+    Taiko,
+}
+
+/// Dimension aggregated series are bucketed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BucketBy {
+    /// Fixed number of consecutive blocks, the default
+    Block,
+    /// Wallclock aligned time intervals
+    Time,
+}
+
+/// Query parameters for leaderboard endpoints
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct LeaderboardQuery {
+    /// Time range filtering parameters
+    #[serde(flatten)]
+    pub time_range: TimeRangeParams,
+    /// Number of entries returned per leaderboard
+    pub limit: Option<u64>,
+}
+
+/// Query parameters for the block status summary endpoint
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct BlockStatusSummaryQuery {
+    /// Number of most recent L2 blocks to summarise
+    pub limit: Option<u64>,
+}
+
+/// Query parameters for the bond history endpoint
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct BondHistoryQuery {
+    /// Account whose bond ledger entries are returned
+    pub address: String,
+    /// Number of most recent ledger entries to return
+    pub limit: Option<u64>,
+}
+
 /// Unified query parameters that support both regular and aggregated modes
 #[derive(Debug, Deserialize, ToSchema, IntoParams)]
 pub struct UnifiedQuery {
@@ -108,6 +464,8 @@ pub struct UnifiedQuery {
     pub starting_after: Option<u64>,
     /// Return items before this cursor (only for regular mode)
     pub ending_before: Option<u64>,
+    /// Opaque cursor from a previous response (only for regular mode)
+    pub cursor: Option<String>,
 }
 
 /// Query mode determined from parameters
@@ -115,8 +473,8 @@ pub struct UnifiedQuery {
 pub enum QueryMode {
     /// Regular paginated mode with specified limit
     Regular {
-        /// Maximum number of items to return
-        limit: u64,
+        /// Resolved pagination window
+        page: Page,
     },
     /// Aggregated mode with automatic bucketing
     Aggregated,
@@ -128,7 +486,7 @@ pub fn validate_unified_query(
     max_limit: u64,
 ) -> Result<QueryMode, ErrorResponse> {
     // Validate common time range parameters
-    validate_time_range(&params.common.time_range)?;
+    params.common.time_range.validate()?;
 
     // Check if aggregated mode is enabled (parameter present)
     let is_aggregated = params.aggregated.is_some();
@@ -137,99 +495,41 @@ pub fn validate_unified_query(
         // In aggregated mode, pagination parameters are not allowed
         if params.limit.is_some() ||
             params.starting_after.is_some() ||
-            params.ending_before.is_some()
+            params.ending_before.is_some() ||
+            params.cursor.is_some()
         {
-            return Err(ErrorResponse::new(
-                "invalid-params",
-                "Bad Request",
-                StatusCode::BAD_REQUEST,
-                "Pagination parameters (limit, starting_after, ending_before) cannot be used with aggregated mode",
+            return Err(ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
+                "Pagination parameters (limit, starting_after, ending_before, cursor) cannot be used with aggregated mode",
             ));
         }
         Ok(QueryMode::Aggregated)
     } else {
         // In regular mode, validate pagination parameters
-        let limit = validate_pagination(
+        let page = resolve_pagination(
+            params.cursor.as_deref(),
             params.starting_after.as_ref(),
             params.ending_before.as_ref(),
             params.limit.as_ref(),
             max_limit,
+            params.common.filter_hash(),
         )?;
-        Ok(QueryMode::Regular { limit })
-    }
-}
-
-/// Validate time range parameters for logical consistency
-pub fn validate_time_range(params: &TimeRangeParams) -> Result<(), ErrorResponse> {
-    // Check for mutually exclusive parameters
-    if let (Some(_), Some(_)) = (params.created_gt, params.created_gte) {
-        return Err(ErrorResponse::new(
-            "invalid-params",
-            "Bad Request",
-            StatusCode::BAD_REQUEST,
-            "created[gt] and created[gte] cannot be used together",
-        ));
-    }
-
-    if let (Some(_), Some(_)) = (params.created_lt, params.created_lte) {
-        return Err(ErrorResponse::new(
-            "invalid-params",
-            "Bad Request",
-            StatusCode::BAD_REQUEST,
-            "created[lt] and created[lte] cannot be used together",
-        ));
-    }
-
-    // Validate timestamp bounds
-    for &timestamp in [params.created_gt, params.created_gte, params.created_lt, params.created_lte]
-        .iter()
-        .flatten()
-    {
-        if timestamp > MAX_TIMESTAMP_MS {
-            return Err(ErrorResponse::new(
-                "invalid-params",
-                "Bad Request",
-                StatusCode::BAD_REQUEST,
-                format!("Timestamp {} is too large (max: {})", timestamp, MAX_TIMESTAMP_MS),
-            ));
-        }
-    }
-
-    // Validate logical ranges
-    let lower_bound = params.created_gt.map(|v| v + 1).or(params.created_gte);
-    let upper_bound = params.created_lt.or(params.created_lte);
-
-    if let (Some(lower), Some(upper)) = (lower_bound, upper_bound) {
-        let is_inclusive = params.created_lte.is_some();
-        if (is_inclusive && lower > upper) || (!is_inclusive && lower >= upper) {
-            return Err(ErrorResponse::new(
-                "invalid-params",
-                "Bad Request",
-                StatusCode::BAD_REQUEST,
-                "Invalid time range: start time must be before end time",
-            ));
-        }
+        Ok(QueryMode::Regular { page })
     }
-
-    Ok(())
 }
 
 /// Validate block range parameters for logical consistency
 pub fn validate_block_range(params: &BlockRangeParams) -> Result<(), ErrorResponse> {
     if let (Some(_), Some(_)) = (params.block_gt, params.block_gte) {
-        return Err(ErrorResponse::new(
-            "invalid-params",
-            "Bad Request",
-            StatusCode::BAD_REQUEST,
+        return Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
             "block[gt] and block[gte] cannot be used together",
         ));
     }
 
     if let (Some(_), Some(_)) = (params.block_lt, params.block_lte) {
-        return Err(ErrorResponse::new(
-            "invalid-params",
-            "Bad Request",
-            StatusCode::BAD_REQUEST,
+        return Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
             "block[lt] and block[lte] cannot be used together",
         ));
     }
@@ -238,10 +538,8 @@ pub fn validate_block_range(params: &BlockRangeParams) -> Result<(), ErrorRespon
         match gt.checked_add(1) {
             Some(v) => Some(v),
             None => {
-                return Err(ErrorResponse::new(
-                    "invalid-params",
-                    "Bad Request",
-                    StatusCode::BAD_REQUEST,
+                return Err(ErrorResponse::bad_request(
+                    ErrorCode::InvalidRange,
                     "block[gt] value is too large",
                 ))
             }
@@ -254,10 +552,8 @@ pub fn validate_block_range(params: &BlockRangeParams) -> Result<(), ErrorRespon
     if let (Some(lower), Some(upper)) = (lower_bound, upper_bound) {
         let is_inclusive = params.block_lte.is_some();
         if (is_inclusive && lower > upper) || (!is_inclusive && lower >= upper) {
-            return Err(ErrorResponse::new(
-                "invalid-params",
-                "Bad Request",
-                StatusCode::BAD_REQUEST,
+            return Err(ErrorResponse::bad_request(
+                ErrorCode::InvalidRange,
                 "Invalid block range: start block must be before end block",
             ));
         }
@@ -274,10 +570,8 @@ pub fn validate_pagination(
     max_limit: u64,
 ) -> Result<u64, ErrorResponse> {
     if starting_after.is_some() && ending_before.is_some() {
-        return Err(ErrorResponse::new(
-            "invalid-params",
-            "Bad Request",
-            StatusCode::BAD_REQUEST,
+        return Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
             "starting_after and ending_before parameters are mutually exclusive",
         ));
     }
@@ -287,109 +581,291 @@ pub fn validate_pagination(
     Ok(effective_limit)
 }
 
-/// Validate that time range and slot range parameters are not mixed
-pub fn validate_range_exclusivity(
-    has_time_range: bool,
-    has_slot_range: bool,
-) -> Result<(), ErrorResponse> {
-    if has_time_range && has_slot_range {
-        return Err(ErrorResponse::new(
-            "invalid-params",
-            "Bad Request",
-            StatusCode::BAD_REQUEST,
-            "Time range params cannot be combined with slot range params",
-        ));
+/// Validate an explicit `limit` parameter, falling back to `default` when absent.
+/// Unlike pagination limits, out-of-range values are rejected rather than clamped.
+pub fn validate_limit(limit: Option<&u64>, default: u64, max: u64) -> Result<u64, ErrorResponse> {
+    match limit.copied() {
+        None => Ok(default),
+        Some(0) => Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
+            "limit must be greater than 0",
+        )),
+        Some(l) if l > max => Err(ErrorResponse::bad_request(
+            ErrorCode::LimitExceeded,
+            format!("limit must not exceed {}", max),
+        )),
+        Some(l) => Ok(l),
     }
-    Ok(())
 }
 
-/// Check if `TimeRangeParams` has any values set
-pub const fn has_time_range_params(params: &TimeRangeParams) -> bool {
-    params.created_gt.is_some() ||
-        params.created_gte.is_some() ||
-        params.created_lt.is_some() ||
-        params.created_lte.is_some()
+/// Validate a `hardware_cost_usd` override, falling back to `default` when absent.
+pub fn validate_hardware_cost(cost: Option<f64>, default: f64) -> Result<f64, ErrorResponse> {
+    match cost {
+        None => Ok(default),
+        Some(c) if c.is_finite() && c >= 0.0 => Ok(c),
+        Some(_) => Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
+            "hardware_cost_usd must be a non-negative number",
+        )),
+    }
 }
 
-/// Check if `BlockRangeParams` has any values set
-pub const fn has_block_range_params(params: &BlockRangeParams) -> bool {
-    params.block_gt.is_some() ||
-        params.block_gte.is_some() ||
-        params.block_lt.is_some() ||
-        params.block_lte.is_some()
+/// Validate the `denomination` parameter, falling back to gwei when absent.
+pub fn validate_denomination(denomination: Option<&str>) -> Result<Denomination, ErrorResponse> {
+    match denomination {
+        None | Some("gwei") => Ok(Denomination::Gwei),
+        Some("usd") => Ok(Denomination::Usd),
+        Some("taiko") => Ok(Denomination::Taiko),
+        Some(other) => Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
+            format!("denomination must be 'gwei', 'usd' or 'taiko', got '{}'", other),
+        )),
+    }
 }
 
-/// Resolve time range to `TimeRange` enum from explicit time range params
-pub fn resolve_time_range_enum(time_params: &TimeRangeParams) -> TimeRange {
-    // If explicit time range parameters are provided, derive the duration from them
-    if has_time_range_params(time_params) {
-        let now = chrono::Utc::now();
+/// Validate the `action` parameter, `None` when operations of every kind are requested.
+pub fn validate_incident_action(
+    action: Option<&str>,
+) -> Result<Option<IncidentAction>, ErrorResponse> {
+    match action {
+        None => Ok(None),
+        Some(name) => IncidentAction::from_name(name).map(Some).ok_or_else(|| {
+            ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
+                format!("action must be 'create', 'update' or 'resolve', got '{}'", name),
+            )
+        }),
+    }
+}
 
-        let start = time_params
-            .created_gt
-            .map(|v| v + 1)
-            .or(time_params.created_gte)
-            .and_then(|ms| chrono::Utc.timestamp_millis_opt(ms as i64).single())
-            .unwrap_or_else(|| now - chrono::Duration::hours(1));
+/// Validate the `bucket_by` parameter, falling back to block buckets when absent.
+pub fn validate_bucket_by(bucket_by: Option<&str>) -> Result<BucketBy, ErrorResponse> {
+    match bucket_by {
+        None | Some("block") => Ok(BucketBy::Block),
+        Some("time") => Ok(BucketBy::Time),
+        Some(other) => Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
+            format!("bucket_by must be 'time' or 'block', got '{}'", other),
+        )),
+    }
+}
 
-        let end = time_params
-            .created_lt
-            .or(time_params.created_lte)
-            .and_then(|ms| chrono::Utc.timestamp_millis_opt(ms as i64).single())
-            .unwrap_or(now);
+/// Validate the `da_mode` parameter, `None` when batches of both modes are requested.
+pub fn validate_da_mode(da_mode: Option<&str>) -> Result<Option<DaMode>, ErrorResponse> {
+    match da_mode {
+        None => Ok(None),
+        Some(name) => DaMode::from_name(name).map(Some).ok_or_else(|| {
+            ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
+                format!("da_mode must be 'blob' or 'calldata', got '{}'", name),
+            )
+        }),
+    }
+}
 
-        let duration = end.signed_duration_since(start).max(chrono::Duration::zero());
-        return TimeRange::from_duration(duration);
+/// Validate the `metric` parameter, `None` when anomalies of every metric are requested.
+pub fn validate_anomaly_metric(
+    metric: Option<&str>,
+) -> Result<Option<AnomalyMetric>, ErrorResponse> {
+    match metric {
+        None => Ok(None),
+        Some(name) => AnomalyMetric::from_name(name).map(Some).ok_or_else(|| {
+            ErrorResponse::bad_request(
+                ErrorCode::InvalidParams,
+                format!(
+                    "metric must be 'tps', 'gas_used', 'batch_cadence' or 'prove_time', got '{}'",
+                    name
+                ),
+            )
+        }),
     }
+}
 
-    // Default to 1 hour when no time range parameters are provided
-    TimeRange::from_duration(ChronoDuration::hours(1))
+/// Direction in which an opaque cursor pages through results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+    /// Items that sort after the key (older items, equivalent to `starting_after`)
+    Next,
+    /// Items that sort before the key (newer items, equivalent to `ending_before`)
+    Prev,
 }
 
-/// Resolve time range to `DateTime` for endpoints that need since timestamps
-pub fn resolve_time_range_since(time_params: &TimeRangeParams) -> chrono::DateTime<chrono::Utc> {
-    let now = chrono::Utc::now();
+/// Opaque pagination cursor encoding the sort key, direction and a hash of the filters
+/// it was issued for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    /// Sort key (block number or batch id) of the boundary item
+    pub key: u64,
+    /// Paging direction relative to `key`
+    pub direction: CursorDirection,
+    /// Hash of the filters the cursor was issued for
+    pub filter_hash: u64,
+}
 
-    // If explicit time range parameters are provided, use them
-    let lower_bound = time_params.created_gt.map(|v| v + 1).or(time_params.created_gte);
+impl Cursor {
+    const VERSION: u8 = 1;
+
+    /// Encode the cursor into an opaque URL-safe token
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(18);
+        bytes.push(Self::VERSION);
+        bytes.push(match self.direction {
+            CursorDirection::Next => 0,
+            CursorDirection::Prev => 1,
+        });
+        bytes.extend_from_slice(&self.key.to_be_bytes());
+        bytes.extend_from_slice(&self.filter_hash.to_be_bytes());
+        hex::encode(bytes)
+    }
 
-    if let Some(timestamp_ms) = lower_bound &&
-        let Some(dt) = chrono::Utc.timestamp_millis_opt(timestamp_ms as i64).single()
-    {
-        return dt;
+    /// Decode a token produced by [`Cursor::encode`]
+    pub fn decode(token: &str) -> Result<Self, ErrorResponse> {
+        let invalid =
+            || ErrorResponse::bad_request(ErrorCode::InvalidParams, "cursor is malformed");
+        let bytes = hex::decode(token).map_err(|_| invalid())?;
+        if bytes.len() != 18 || bytes[0] != Self::VERSION {
+            return Err(invalid());
+        }
+        let direction = match bytes[1] {
+            0 => CursorDirection::Next,
+            1 => CursorDirection::Prev,
+            _ => return Err(invalid()),
+        };
+        let key = u64::from_be_bytes(bytes[2..10].try_into().map_err(|_| invalid())?);
+        let filter_hash = u64::from_be_bytes(bytes[10..18].try_into().map_err(|_| invalid())?);
+        Ok(Self { key, direction, filter_hash })
     }
+}
 
-    // Default to 1 hour ago when no time range parameters are provided
-    now - ChronoDuration::hours(1)
+/// Resolved pagination window for a single request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Page {
+    /// Maximum number of items to return
+    pub limit: u64,
+    /// Return items after this key (exclusive)
+    pub starting_after: Option<u64>,
+    /// Return items before this key (exclusive)
+    pub ending_before: Option<u64>,
+    filter_hash: u64,
 }
 
-/// Resolve time range to start and end `DateTime` values
-pub fn resolve_time_range_bounds(
-    time_params: &TimeRangeParams,
-) -> (chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>) {
-    let now = chrono::Utc::now();
+impl Page {
+    /// Whether the request targets a slot range rather than the newest items
+    pub const fn has_cursor(&self) -> bool {
+        self.starting_after.is_some() || self.ending_before.is_some()
+    }
 
-    let start = time_params
-        .created_gt
-        .map(|v| v + 1)
-        .or(time_params.created_gte)
-        .and_then(|ms| chrono::Utc.timestamp_millis_opt(ms as i64).single())
-        .unwrap_or_else(|| now - ChronoDuration::hours(1));
+    /// Build the `(next_cursor, prev_cursor)` pair for rows returned in descending key order
+    pub fn cursors<T>(
+        &self,
+        rows: &[T],
+        key: impl Fn(&T) -> u64,
+    ) -> (Option<String>, Option<String>) {
+        let (Some(first), Some(last)) = (rows.first(), rows.last()) else {
+            return (None, None);
+        };
+        self.cursors_between(key(first), key(last), rows.len() as u64)
+    }
 
-    // Preserve exclusivity semantics for created[lt] by subtracting 1 ms
-    // from the computed upper bound, since downstream queries use
-    // "inserted_at <= end". This makes the effective condition
-    // "inserted_at < created[lt]" when created[lt] is provided.
-    let end = if let Some(ms_lt) = time_params.created_lt {
-        let adjusted = ms_lt.saturating_sub(1);
-        chrono::Utc.timestamp_millis_opt(adjusted as i64).single().unwrap_or(now)
-    } else if let Some(ms_lte) = time_params.created_lte {
-        chrono::Utc.timestamp_millis_opt(ms_lte as i64).single().unwrap_or(now)
-    } else {
-        now
+    /// [`Self::cursors`] for a non-empty page of `rows` items from key `first` to key `last`
+    pub fn cursors_between(
+        &self,
+        first: u64,
+        last: u64,
+        rows: u64,
+    ) -> (Option<String>, Option<String>) {
+        let full = rows >= self.limit;
+        let cursor = |key, direction| Cursor { key, direction, filter_hash: self.filter_hash };
+        let next = (full || self.ending_before.is_some())
+            .then(|| cursor(last, CursorDirection::Next).encode());
+        let prev = (self.starting_after.is_some() || (self.ending_before.is_some() && full))
+            .then(|| cursor(first, CursorDirection::Prev).encode());
+        (next, prev)
+    }
+}
+
+/// Resolve pagination parameters into a [`Page`].
+///
+/// `cursor` supersedes the raw `starting_after`/`ending_before` keys and must have been
+/// issued for the same filters, identified by `filter_hash`.
+pub fn resolve_pagination(
+    cursor: Option<&str>,
+    starting_after: Option<&u64>,
+    ending_before: Option<&u64>,
+    limit: Option<&u64>,
+    max_limit: u64,
+    filter_hash: u64,
+) -> Result<Page, ErrorResponse> {
+    let limit = validate_pagination(starting_after, ending_before, limit, max_limit)?;
+    let Some(token) = cursor else {
+        return Ok(Page {
+            limit,
+            starting_after: starting_after.copied(),
+            ending_before: ending_before.copied(),
+            filter_hash,
+        });
+    };
+
+    if starting_after.is_some() || ending_before.is_some() {
+        return Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
+            "cursor cannot be combined with starting_after or ending_before",
+        ));
+    }
+    let cursor = Cursor::decode(token)?;
+    if cursor.filter_hash != filter_hash {
+        return Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
+            "cursor was issued for different filters",
+        ));
+    }
+    let (starting_after, ending_before) = match cursor.direction {
+        CursorDirection::Next => (Some(cursor.key), None),
+        CursorDirection::Prev => (None, Some(cursor.key)),
     };
+    Ok(Page { limit, starting_after, ending_before, filter_hash })
+}
+
+/// Validate that time range and slot range parameters are not mixed
+pub fn validate_range_exclusivity(
+    has_time_range: bool,
+    has_slot_range: bool,
+) -> Result<(), ErrorResponse> {
+    if has_time_range && has_slot_range {
+        return Err(ErrorResponse::bad_request(
+            ErrorCode::InvalidParams,
+            "Time range params cannot be combined with slot range params",
+        ));
+    }
+    Ok(())
+}
+
+/// Check if `BlockRangeParams` has any values set
+pub const fn has_block_range_params(params: &BlockRangeParams) -> bool {
+    params.block_gt.is_some() ||
+        params.block_gte.is_some() ||
+        params.block_lt.is_some() ||
+        params.block_lte.is_some()
+}
 
-    (start, end)
+/// Validate the `as_of` parameter and convert it to a timestamp. It must not lie in the future.
+pub fn resolve_as_of(
+    params: &AsOfQuery,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, ErrorResponse> {
+    let Some(ms) = params.as_of else {
+        return Ok(None);
+    };
+    let as_of = chrono::Utc
+        .timestamp_millis_opt(ms as i64)
+        .single()
+        .filter(|as_of| ms <= MAX_TIMESTAMP_MS && *as_of <= chrono::Utc::now())
+        .ok_or_else(|| {
+            ErrorResponse::bad_request(
+                ErrorCode::InvalidRange,
+                format!("as_of {ms} must be a past timestamp in milliseconds"),
+            )
+        })?;
+    Ok(Some(as_of))
 }
 
 /// Custom deserializer that converts a URL-encoded form value into a `u64`.
@@ -426,12 +902,13 @@ mod tests {
             created_gte: Some(200),
             created_lt: None,
             created_lte: None,
+            ..Default::default()
         };
 
-        let result = validate_time_range(&params);
+        let result = params.validate();
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.r#type, "invalid-params");
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
         assert!(err.detail.contains("created[gt] and created[gte] cannot be used together"));
     }
 
@@ -442,12 +919,13 @@ mod tests {
             created_gte: None,
             created_lt: Some(100),
             created_lte: Some(200),
+            ..Default::default()
         };
 
-        let result = validate_time_range(&params);
+        let result = params.validate();
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.r#type, "invalid-params");
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
         assert!(err.detail.contains("created[lt] and created[lte] cannot be used together"));
     }
 
@@ -458,12 +936,13 @@ mod tests {
             created_gte: None,
             created_lt: Some(100),
             created_lte: None,
+            ..Default::default()
         };
 
-        let result = validate_time_range(&params);
+        let result = params.validate();
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.r#type, "invalid-params");
+        assert_eq!(err.r#type, ErrorCode::InvalidRange);
         assert!(err.detail.contains("Invalid time range: start time must be before end time"));
     }
 
@@ -474,12 +953,13 @@ mod tests {
             created_gte: Some(200),
             created_lt: None,
             created_lte: Some(100),
+            ..Default::default()
         };
 
-        let result = validate_time_range(&params);
+        let result = params.validate();
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.r#type, "invalid-params");
+        assert_eq!(err.r#type, ErrorCode::InvalidRange);
         assert!(err.detail.contains("Invalid time range: start time must be before end time"));
     }
 
@@ -490,9 +970,10 @@ mod tests {
             created_gte: None,
             created_lt: Some(200),
             created_lte: None,
+            ..Default::default()
         };
 
-        let result = validate_time_range(&params);
+        let result = params.validate();
         assert!(result.is_ok());
     }
 
@@ -503,9 +984,10 @@ mod tests {
             created_gte: Some(100),
             created_lt: None,
             created_lte: Some(100),
+            ..Default::default()
         };
 
-        let result = validate_time_range(&params);
+        let result = params.validate();
         assert!(result.is_ok());
     }
 
@@ -516,12 +998,13 @@ mod tests {
             created_gte: None,
             created_lt: None,
             created_lte: None,
+            ..Default::default()
         };
 
-        let result = validate_time_range(&params);
+        let result = params.validate();
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.r#type, "invalid-params");
+        assert_eq!(err.r#type, ErrorCode::InvalidRange);
         assert!(err.detail.contains("Timestamp"));
         assert!(err.detail.contains("is too large"));
     }
@@ -531,7 +1014,7 @@ mod tests {
         let result = validate_pagination(Some(&100), Some(&200), None, 10000);
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.r#type, "invalid-params");
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
         assert!(
             err.detail
                 .contains("starting_after and ending_before parameters are mutually exclusive")
@@ -560,7 +1043,7 @@ mod tests {
         let result = validate_range_exclusivity(true, true);
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert_eq!(err.r#type, "invalid-params");
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
         assert!(err.detail.contains("Time range params cannot be combined with slot range params"));
     }
 
@@ -571,16 +1054,88 @@ mod tests {
             created_gte: None,
             created_lt: None,
             created_lte: None,
+            ..Default::default()
         };
-        assert!(!has_time_range_params(&empty_params));
+        assert!(!empty_params.is_set());
 
         let with_gt = TimeRangeParams {
             created_gt: Some(100),
             created_gte: None,
             created_lt: None,
             created_lte: None,
+            ..Default::default()
         };
-        assert!(has_time_range_params(&with_gt));
+        assert!(with_gt.is_set());
+
+        let with_range =
+            TimeRangeParams { range: Some(TimeRangePreset::Last24Hours), ..Default::default() };
+        assert!(with_range.is_set());
+    }
+
+    fn time_range_query(query: &str) -> Result<TimeRangeParams, serde_urlencoded::de::Error> {
+        serde_urlencoded::from_str(query)
+    }
+
+    #[test]
+    fn test_time_range_preset_ends_now() {
+        let now = chrono::Utc.timestamp_millis_opt(1_700_000_000_000).unwrap();
+        let params = time_range_query("range=7d").unwrap();
+        params.validate().unwrap();
+
+        let (since, until) = params.bounds_at(now);
+        assert_eq!(until, now);
+        assert_eq!(since, now - ChronoDuration::days(7));
+        assert_eq!(params.to_time_range_at(now).seconds(), 7 * 24 * 3600);
+
+        assert!(time_range_query("range=1y").is_err());
+    }
+
+    #[test]
+    fn test_time_range_rfc3339_bounds() {
+        let now = chrono::Utc.timestamp_millis_opt(1_700_000_000_000).unwrap();
+        let params =
+            time_range_query("from=2023-11-14T20:00:00Z&to=2023-11-14T21:00:00%2B00:00").unwrap();
+        params.validate().unwrap();
+
+        let (since, until) = params.bounds_at(now);
+        assert_eq!(since.to_rfc3339(), "2023-11-14T20:00:00+00:00");
+        assert_eq!(until.to_rfc3339(), "2023-11-14T21:00:00+00:00");
+        assert_eq!(params.to_time_range_at(now).seconds(), 3600);
+
+        let reversed = time_range_query("from=2023-11-14T21:00:00Z&to=2023-11-14T20:00:00Z");
+        assert_eq!(reversed.unwrap().validate().unwrap_err().r#type, ErrorCode::InvalidRange);
+        assert!(time_range_query("from=yesterday").is_err());
+    }
+
+    #[test]
+    fn test_time_range_styles_cannot_be_mixed() {
+        for query in [
+            "range=1h&from=2023-11-14T20:00:00Z",
+            "range=1h&created[gte]=0",
+            "to=2023-11-14T20:00:00Z&created[gte]=0",
+        ] {
+            let err = time_range_query(query).unwrap().validate().unwrap_err();
+            assert_eq!(err.r#type, ErrorCode::InvalidParams, "{query}");
+        }
+    }
+
+    #[test]
+    fn test_time_range_clamps_to_now() {
+        let now = chrono::Utc.timestamp_millis_opt(1_700_000_000_000).unwrap();
+
+        // Ranges never end in the future
+        let params =
+            time_range_query("created[gte]=1699999000000&created[lte]=1800000000000").unwrap();
+        assert_eq!(params.bounds_at(now), (now - ChronoDuration::seconds(1000), now));
+
+        // Without a lower bound the range spans the hour before its end
+        let params = time_range_query("to=2023-11-14T20:00:00Z").unwrap();
+        let (since, until) = params.bounds_at(now);
+        assert_eq!(until - since, ChronoDuration::hours(1));
+
+        // A range starting in the future is empty
+        let params = time_range_query("created[gte]=1800000000000").unwrap();
+        assert_eq!(params.bounds_at(now), (now, now));
     }
 
     #[test]
@@ -722,4 +1277,162 @@ mod tests {
         let res: Wrapper = serde_urlencoded::from_str("value=42").unwrap();
         assert_eq!(res.value, Some(42));
     }
+
+    #[test]
+    fn test_validate_limit_defaults_when_absent() {
+        assert_eq!(validate_limit(None, 10, 100).unwrap(), 10);
+        assert_eq!(validate_limit(Some(&100), 10, 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn test_validate_limit_rejects_out_of_range() {
+        let err = validate_limit(Some(&0), 10, 100).unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
+        assert!(err.detail.contains("greater than 0"));
+
+        let err = validate_limit(Some(&101), 10, 100).unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::LimitExceeded);
+        assert!(err.detail.contains("must not exceed 100"));
+    }
+
+    #[test]
+    fn test_validate_denomination() {
+        assert_eq!(validate_denomination(None).unwrap(), Denomination::Gwei);
+        assert_eq!(validate_denomination(Some("gwei")).unwrap(), Denomination::Gwei);
+        assert_eq!(validate_denomination(Some("usd")).unwrap(), Denomination::Usd);
+        assert_eq!(validate_denomination(Some("taiko")).unwrap(), Denomination::Taiko);
+
+        let err = validate_denomination(Some("eur")).unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
+        assert!(err.detail.contains("'eur'"));
+    }
+
+    #[test]
+    fn test_validate_incident_action() {
+        assert_eq!(validate_incident_action(None).unwrap(), None);
+        assert_eq!(
+            validate_incident_action(Some("resolve")).unwrap(),
+            Some(IncidentAction::Resolve)
+        );
+
+        let err = validate_incident_action(Some("delete")).unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
+        assert!(err.detail.contains("'delete'"));
+    }
+
+    #[test]
+    fn test_validate_bucket_by() {
+        assert_eq!(validate_bucket_by(None).unwrap(), BucketBy::Block);
+        assert_eq!(validate_bucket_by(Some("block")).unwrap(), BucketBy::Block);
+        assert_eq!(validate_bucket_by(Some("time")).unwrap(), BucketBy::Time);
+
+        let err = validate_bucket_by(Some("day")).unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
+        assert!(err.detail.contains("'day'"));
+    }
+
+    #[test]
+    fn test_validate_da_mode() {
+        assert_eq!(validate_da_mode(None).unwrap(), None);
+        assert_eq!(validate_da_mode(Some("blob")).unwrap(), Some(DaMode::Blob));
+        assert_eq!(validate_da_mode(Some("calldata")).unwrap(), Some(DaMode::Calldata));
+
+        let err = validate_da_mode(Some("blobs")).unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
+        assert!(err.detail.contains("'blobs'"));
+    }
+
+    #[test]
+    fn test_validate_anomaly_metric() {
+        assert_eq!(validate_anomaly_metric(None).unwrap(), None);
+        assert_eq!(validate_anomaly_metric(Some("tps")).unwrap(), Some(AnomalyMetric::Tps));
+        assert_eq!(
+            validate_anomaly_metric(Some("prove_time")).unwrap(),
+            Some(AnomalyMetric::ProveTime)
+        );
+
+        let err = validate_anomaly_metric(Some("latency")).unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::InvalidParams);
+        assert!(err.detail.contains("'latency'"));
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor { key: 42, direction: CursorDirection::Prev, filter_hash: 7 };
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(Cursor::decode("not-a-cursor").is_err());
+    }
+
+    #[test]
+    fn test_resolve_pagination_rejects_cursor_for_other_filters() {
+        let token = Cursor { key: 10, direction: CursorDirection::Next, filter_hash: 1 }.encode();
+
+        let page = resolve_pagination(Some(&token), None, None, Some(&5), 100, 1).unwrap();
+        assert_eq!(page.starting_after, Some(10));
+        assert_eq!(page.ending_before, None);
+
+        let err = resolve_pagination(Some(&token), None, None, None, 100, 2).unwrap_err();
+        assert!(err.detail.contains("different filters"));
+
+        let err = resolve_pagination(Some(&token), Some(&3), None, None, 100, 1).unwrap_err();
+        assert!(err.detail.contains("cannot be combined"));
+    }
+
+    #[test]
+    fn test_batch_blocks_cursor_is_bound_to_its_batch() {
+        let query = |cursor: Option<String>| BatchBlocksQuery {
+            limit: Some(2),
+            starting_after: None,
+            ending_before: None,
+            cursor,
+        };
+        let first = query(None).page(7, 768).unwrap();
+        let (next, _) = first.cursors(&[120u64, 119], |k| *k);
+
+        let page = query(next.clone()).page(7, 768).unwrap();
+        assert_eq!(page.starting_after, Some(119));
+
+        let err = query(next).page(8, 768).unwrap_err();
+        assert!(err.detail.contains("different filters"));
+    }
+
+    #[test]
+    fn test_page_cursors() {
+        let first = resolve_pagination(None, None, None, Some(&2), 100, 9).unwrap();
+        let (next, prev) = first.cursors(&[30u64, 20], |k| *k);
+        assert!(prev.is_none());
+        let next = resolve_pagination(next.as_deref(), None, None, Some(&2), 100, 9).unwrap();
+        assert_eq!(next.starting_after, Some(20));
+
+        let (more, prev) = next.cursors(&[10u64], |k| *k);
+        assert!(more.is_none());
+        let prev = resolve_pagination(prev.as_deref(), None, None, Some(&2), 100, 9).unwrap();
+        assert_eq!(prev.ending_before, Some(10));
+    }
+
+    #[test]
+    fn test_as_of_anchors_default_range() {
+        let as_of = chrono::Utc.timestamp_millis_opt(1_700_000_000_000).unwrap();
+        let resolved = resolve_as_of(&AsOfQuery { as_of: Some(1_700_000_000_000) }).unwrap();
+        assert_eq!(resolved, Some(as_of));
+
+        let params = TimeRangeParams {
+            created_gt: None,
+            created_gte: None,
+            created_lt: None,
+            created_lte: None,
+            ..Default::default()
+        };
+        let (since, until) = params.bounds_at(as_of);
+        assert_eq!(until, as_of);
+        assert_eq!(since, as_of - ChronoDuration::hours(1));
+    }
+
+    #[test]
+    fn test_as_of_rejects_future_timestamps() {
+        assert_eq!(resolve_as_of(&AsOfQuery::default()).unwrap(), None);
+        let future = (chrono::Utc::now() + ChronoDuration::days(1)).timestamp_millis() as u64;
+        let err = resolve_as_of(&AsOfQuery { as_of: Some(future) }).unwrap_err();
+        assert_eq!(err.r#type, ErrorCode::InvalidRange);
+    }
 }
@@ -0,0 +1,116 @@
+//! ENS registry and resolver contracts
+use IENSRegistry::IENSRegistryInstance;
+use alloy::{
+    contract::Result as ContractResult,
+    primitives::{Address, B256, address, hex, keccak256},
+    sol,
+};
+
+use crate::DefaultProvider;
+
+/// Address of the ENS registry, identical on mainnet and the public testnets.
+pub const ENS_REGISTRY: Address = address!("00000000000C2E074eC69A0dFb2997BA6C7d2e1e");
+
+/// Compute the ENS namehash of `name`, as specified by EIP-137.
+pub fn namehash(name: &str) -> B256 {
+    if name.is_empty() {
+        return B256::ZERO;
+    }
+    name.rsplit('.').fold(B256::ZERO, |node, label| {
+        keccak256([node.as_slice(), keccak256(label.as_bytes()).as_slice()].concat())
+    })
+}
+
+/// Node of the reverse record of `address`, i.e. the namehash of `<address>.addr.reverse`.
+pub fn reverse_node(address: Address) -> B256 {
+    namehash(&format!("{}.addr.reverse", hex::encode(address)))
+}
+
+/// A wrapper over the ENS registry used for reverse resolution.
+#[derive(Debug, Clone)]
+pub struct Ens {
+    registry: IENSRegistryInstance<DefaultProvider>,
+    provider: DefaultProvider,
+}
+
+impl Ens {
+    /// Create a new `Ens` instance using the registry at [`ENS_REGISTRY`].
+    pub fn new_readonly(provider: DefaultProvider) -> Self {
+        Self { registry: IENSRegistryInstance::new(ENS_REGISTRY, provider.clone()), provider }
+    }
+
+    /// Resolve the primary ENS name of `address`.
+    ///
+    /// The reverse record is only trusted if the name resolves back to `address`, as anyone can
+    /// set the reverse record of their own address to an arbitrary name.
+    pub async fn lookup_address(&self, address: Address) -> ContractResult<Option<String>> {
+        let node = reverse_node(address);
+        let resolver = self.registry.resolver(node).call().await?;
+        if resolver.is_zero() {
+            return Ok(None);
+        }
+        let name = IENSResolver::IENSResolverInstance::new(resolver, self.provider.clone())
+            .name(node)
+            .call()
+            .await?;
+        if name.is_empty() {
+            return Ok(None);
+        }
+
+        let forward = namehash(&name);
+        let resolver = self.registry.resolver(forward).call().await?;
+        if resolver.is_zero() {
+            return Ok(None);
+        }
+        let resolved = IENSResolver::IENSResolverInstance::new(resolver, self.provider.clone())
+            .addr(forward)
+            .call()
+            .await?;
+        Ok((resolved == address).then_some(name))
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IENSRegistry {
+        function resolver(bytes32 node) external view returns (address);
+    }
+
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IENSResolver {
+        function name(bytes32 node) external view returns (string memory);
+        function addr(bytes32 node) external view returns (address);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::b256;
+
+    #[test]
+    fn namehash_matches_eip137_vectors() {
+        assert_eq!(namehash(""), B256::ZERO);
+        assert_eq!(
+            namehash("eth"),
+            b256!("93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae")
+        );
+        assert_eq!(
+            namehash("foo.eth"),
+            b256!("de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f")
+        );
+    }
+
+    #[test]
+    fn reverse_node_uses_lowercase_hex_without_prefix() {
+        let address = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        assert_eq!(
+            reverse_node(address),
+            namehash("d8da6bf26964af9d7eed9e03e53415d37aa96045.addr.reverse")
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! ERC-20 token contract
+use IERC20::IERC20Instance;
+use alloy::{
+    contract::Result as ContractResult,
+    eips::BlockId,
+    primitives::{Address, U256},
+    sol,
+};
+
+use crate::DefaultProvider;
+
+/// A wrapper over an `IERC20` token contract.
+#[derive(Debug, Clone)]
+pub struct Erc20(IERC20Instance<DefaultProvider>);
+
+impl Erc20 {
+    /// Create a new `Erc20` instance at the given token address.
+    pub const fn new_readonly(address: Address, provider: DefaultProvider) -> Self {
+        Self(IERC20Instance::new(address, provider))
+    }
+
+    /// Reads the token balance of `owner` at the given L1 block via `balanceOf()`.
+    pub async fn balance_of(&self, owner: Address, block: u64) -> ContractResult<U256> {
+        self.0.balanceOf(owner).block(BlockId::number(block)).call().await
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
@@ -1,15 +1,27 @@
 //! `ChainIO` is a library for interacting with on-chain contracts.
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
+/// ENS reverse resolution
+pub mod ens;
+/// ERC-20 token bindings
+pub mod erc20;
+/// Multicall3 batched reads
+pub mod multicall;
 pub mod taiko;
 
-use ITaikoInbox::{BatchProposed, BatchesProved, ITaikoInboxInstance, Transition};
+use ITaikoInbox::{
+    BatchProposed, BatchesProved, BondCredited, BondDebited, BondDeposited, BondWithdrawn,
+    ITaikoInboxInstance, Transition,
+};
 
 use alloy::{
-    primitives::Address,
+    contract::Result as ContractResult,
+    eips::BlockId,
+    primitives::{Address, LogData, U256},
     providers::{RootProvider, fillers::FillProvider, utils::JoinedRecommendedFillers},
     rpc::types::Filter,
     sol,
+    sol_types::SolEvent,
 };
 use derive_more::derive::Deref;
 use serde::{Deserialize, Serialize};
@@ -39,6 +51,39 @@ impl TaikoInbox {
     pub fn batches_verified_filter(&self) -> Filter {
         self.0.BatchesVerified_filter().filter
     }
+
+    /// Returns a log [`Filter`] matching every bond balance change event.
+    pub fn bond_events_filter(&self) -> Filter {
+        Filter::new().address(*self.0.address()).event_signature(vec![
+            BondDeposited::SIGNATURE_HASH,
+            BondWithdrawn::SIGNATURE_HASH,
+            BondDebited::SIGNATURE_HASH,
+            BondCredited::SIGNATURE_HASH,
+        ])
+    }
+
+    /// Reads the current protocol configuration via `pacayaConfig()`.
+    pub async fn protocol_config(&self) -> ContractResult<ITaikoInbox::ProtocolConfig> {
+        self.0.pacayaConfig().call().await
+    }
+
+    /// Reads the number of proposed batches, i.e. the id of the next batch, at the given L1 block
+    /// via `getStats2()`.
+    pub async fn num_batches(&self, block: u64) -> ContractResult<u64> {
+        Ok(self.0.getStats2().block(BlockId::number(block)).call().await?.numBatches)
+    }
+
+    /// Reads the number of proposed batches and the id of the last verified batch at the given
+    /// L1 block via `getStats2()`.
+    pub async fn batch_heads(&self, block: u64) -> ContractResult<(u64, u64)> {
+        let stats = self.0.getStats2().block(BlockId::number(block)).call().await?;
+        Ok((stats.numBatches, stats.lastVerifiedBatchId))
+    }
+
+    /// Reads the bond balance of `user` at the given L1 block via `bondBalanceOf()`.
+    pub async fn bond_balance_of(&self, user: Address, block: u64) -> ContractResult<U256> {
+        self.0.bondBalanceOf(user).block(BlockId::number(block)).call().await
+    }
 }
 
 sol! {
@@ -111,6 +156,10 @@ sol! {
         event BatchesProved(address verifier, uint64[] batchIds, Transition[] transitions);
         #[derive(Default)]
         event BatchesVerified(uint64 batchId, bytes32 blockHash);
+        event BondCredited(address indexed user, uint256 amount);
+        event BondDebited(address indexed user, uint256 amount);
+        event BondDeposited(address indexed user, uint256 amount);
+        event BondWithdrawn(address indexed user, uint256 amount);
 
         #[derive(Copy, Default)]
         struct BaseFeeConfig {
@@ -281,6 +330,11 @@ sol! {
             external
             returns (BatchInfo memory info_, BatchMetadata memory meta_);
 
+        /// @notice Proves state transitions for multiple batches with a single aggregated proof.
+        /// @param _params ABI-encoded parameter containing the batch metadata and transitions.
+        /// @param _proof The aggregated cryptographic proof proving the batches transitions.
+        function proveBatches(bytes calldata _params, bytes calldata _proof) external;
+
         /// @notice Retrieves the current protocol configuration.
         /// @return The current configuration.
         function pacayaConfig() external view returns (ProtocolConfig memory);
@@ -293,6 +347,11 @@ sol! {
         /// @param batchId The ID of the batch to retrieve.
         /// @return The batch.
         function getBatch(uint64 batchId) public view returns (Batch memory);
+
+        /// @notice Returns the bond balance of a user.
+        /// @param _user The user address.
+        /// @return The bond balance.
+        function bondBalanceOf(address _user) external view returns (uint256);
     }
 }
 
@@ -361,6 +420,68 @@ impl BatchesVerified {
     }
 }
 
+/// Kind of bond balance change emitted by the inbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondEventKind {
+    /// Bond deposited by the account
+    Deposited,
+    /// Bond withdrawn by the account
+    Withdrawn,
+    /// Liveness bond taken when a batch is proposed
+    Debited,
+    /// Liveness bond returned when a batch is proved
+    Credited,
+}
+
+impl BondEventKind {
+    /// Name of the kind as stored in the database
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Deposited => "deposited",
+            Self::Withdrawn => "withdrawn",
+            Self::Debited => "debited",
+            Self::Credited => "credited",
+        }
+    }
+}
+
+/// A change of an account's bond balance in the inbox
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BondEvent {
+    /// Kind of change
+    pub kind: BondEventKind,
+    /// Account whose balance changed
+    pub user: Address,
+    /// Amount added to or removed from the balance
+    pub amount: U256,
+}
+
+impl BondEvent {
+    /// Decode a bond event from the data of an inbox log, or `None` if it is not one.
+    pub fn decode(data: &LogData) -> Option<Self> {
+        let (kind, user, amount) = match *data.topics().first()? {
+            BondDeposited::SIGNATURE_HASH => {
+                let e = BondDeposited::decode_log_data(data).ok()?;
+                (BondEventKind::Deposited, e.user, e.amount)
+            }
+            BondWithdrawn::SIGNATURE_HASH => {
+                let e = BondWithdrawn::decode_log_data(data).ok()?;
+                (BondEventKind::Withdrawn, e.user, e.amount)
+            }
+            BondDebited::SIGNATURE_HASH => {
+                let e = BondDebited::decode_log_data(data).ok()?;
+                (BondEventKind::Debited, e.user, e.amount)
+            }
+            BondCredited::SIGNATURE_HASH => {
+                let e = BondCredited::decode_log_data(data).ok()?;
+                (BondEventKind::Credited, e.user, e.amount)
+            }
+            _ => return None,
+        };
+        Some(Self { kind, user, amount })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,4 +560,20 @@ mod tests {
         let actual = batch.block_numbers_proposed();
         assert_eq!(actual, vec![0]);
     }
+
+    #[test]
+    fn decodes_bond_events() {
+        let user = Address::repeat_byte(7);
+        let debited = BondDebited { user, amount: U256::from(125u64) }.encode_log_data();
+        assert_eq!(
+            BondEvent::decode(&debited),
+            Some(BondEvent { kind: BondEventKind::Debited, user, amount: U256::from(125u64) })
+        );
+
+        let credited = BondCredited { user, amount: U256::from(5u64) }.encode_log_data();
+        assert_eq!(BondEvent::decode(&credited).map(|e| e.kind), Some(BondEventKind::Credited));
+
+        let verified = ITaikoInbox::BatchesVerified::default().encode_log_data();
+        assert_eq!(BondEvent::decode(&verified), None);
+    }
 }
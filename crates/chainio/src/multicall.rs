@@ -0,0 +1,175 @@
+//! Multicall3 batched contract reads
+use IMulticall3::{Call3, CallResult, IMulticall3Instance};
+use alloy::{
+    contract::Result as ContractResult,
+    eips::BlockId,
+    primitives::{Address, address},
+    sol,
+    sol_types::SolCall,
+};
+
+use crate::{
+    DefaultProvider, ITaikoInbox::getStats2Call, taiko::preconf_whitelist::IPreconfWhitelist,
+};
+
+/// Address of Multicall3, deployed at the same address on every major chain.
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// Preconfirmation state of the whitelist and the inbox, read in a single RPC round trip.
+///
+/// Every read is allowed to fail on its own, leaving its field empty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreconfSnapshot {
+    /// Operator candidates for the current epoch
+    pub candidates: Option<Vec<Address>>,
+    /// Operator for the current epoch
+    pub current_operator: Option<Address>,
+    /// Operator for the next epoch
+    pub next_operator: Option<Address>,
+    /// Number of proposed batches, i.e. the id of the next batch
+    pub num_batches: Option<u64>,
+    /// Whether the inbox is paused
+    pub paused: Option<bool>,
+}
+
+/// A wrapper over the `Multicall3` contract that batches several reads into one `eth_call`.
+#[derive(Debug, Clone)]
+pub struct Multicall(IMulticall3Instance<DefaultProvider>);
+
+impl Multicall {
+    /// Create a new `Multicall` instance using the contract at [`MULTICALL3_ADDRESS`].
+    pub const fn new_readonly(provider: DefaultProvider) -> Self {
+        Self(IMulticall3Instance::new(MULTICALL3_ADDRESS, provider))
+    }
+
+    /// Read the operators and candidates of the `whitelist` together with the `getStats2()` of
+    /// the `inbox` at the given L1 block.
+    pub async fn preconf_snapshot(
+        &self,
+        whitelist: Address,
+        inbox: Address,
+        block: u64,
+    ) -> ContractResult<PreconfSnapshot> {
+        let results = self
+            .0
+            .aggregate3(preconf_calls(whitelist, inbox))
+            .block(BlockId::number(block))
+            .call()
+            .await?;
+        Ok(decode_preconf_snapshot(&results))
+    }
+}
+
+/// Calls read by [`Multicall::preconf_snapshot`], in the order [`decode_preconf_snapshot`]
+/// expects their results.
+pub fn preconf_calls(whitelist: Address, inbox: Address) -> Vec<Call3> {
+    let call = |target: Address, data: Vec<u8>| Call3 {
+        target,
+        allowFailure: true,
+        callData: data.into(),
+    };
+    vec![
+        call(
+            whitelist,
+            IPreconfWhitelist::getOperatorCandidatesForCurrentEpochCall {}.abi_encode(),
+        ),
+        call(whitelist, IPreconfWhitelist::getOperatorForCurrentEpochCall {}.abi_encode()),
+        call(whitelist, IPreconfWhitelist::getOperatorForNextEpochCall {}.abi_encode()),
+        call(inbox, getStats2Call {}.abi_encode()),
+    ]
+}
+
+/// Decode the results of the [`preconf_calls`], leaving failed or undecodable reads empty.
+pub fn decode_preconf_snapshot(results: &[CallResult]) -> PreconfSnapshot {
+    let stats = decode::<getStats2Call>(results.get(3));
+    PreconfSnapshot {
+        candidates: decode::<IPreconfWhitelist::getOperatorCandidatesForCurrentEpochCall>(
+            results.first(),
+        ),
+        current_operator: decode::<IPreconfWhitelist::getOperatorForCurrentEpochCall>(
+            results.get(1),
+        ),
+        next_operator: decode::<IPreconfWhitelist::getOperatorForNextEpochCall>(results.get(2)),
+        num_batches: stats.as_ref().map(|stats| stats.numBatches),
+        paused: stats.map(|stats| stats.paused),
+    }
+}
+
+/// Decode the return value of `C` from a successful call result.
+fn decode<C: SolCall>(result: Option<&CallResult>) -> Option<C::Return> {
+    let result = result.filter(|result| result.success)?;
+    C::abi_decode_returns(&result.returnData).ok()
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct CallResult {
+            bool success;
+            bytes returnData;
+        }
+
+        /// @notice Aggregate calls, ensuring each returns success if required.
+        function aggregate3(Call3[] calldata calls) external payable returns (CallResult[] memory returnData);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::aliases::U56;
+
+    use crate::ITaikoInbox::Stats2;
+
+    fn ok<C: SolCall>(ret: &C::Return) -> CallResult {
+        CallResult { success: true, returnData: C::abi_encode_returns(ret).into() }
+    }
+
+    #[test]
+    fn calls_target_whitelist_and_inbox() {
+        let calls = preconf_calls(Address::repeat_byte(1), Address::repeat_byte(2));
+        assert_eq!(calls.len(), 4);
+        assert!(calls.iter().all(|call| call.allowFailure));
+        assert!(calls[..3].iter().all(|call| call.target == Address::repeat_byte(1)));
+        assert_eq!(calls[3].target, Address::repeat_byte(2));
+        assert_eq!(calls[3].callData[..4], getStats2Call::SELECTOR);
+    }
+
+    #[test]
+    fn decodes_results_and_tolerates_failures() {
+        let candidates = vec![Address::repeat_byte(3), Address::repeat_byte(4)];
+        let stats = Stats2 {
+            numBatches: 1_234,
+            lastVerifiedBatchId: 1_200,
+            paused: false,
+            lastProposedIn: U56::from(21_000_000),
+            lastUnpausedAt: 0,
+        };
+        let results = vec![
+            ok::<IPreconfWhitelist::getOperatorCandidatesForCurrentEpochCall>(&candidates),
+            ok::<IPreconfWhitelist::getOperatorForCurrentEpochCall>(&Address::repeat_byte(3)),
+            CallResult { success: false, returnData: Default::default() },
+            ok::<getStats2Call>(&stats),
+        ];
+
+        assert_eq!(
+            decode_preconf_snapshot(&results),
+            PreconfSnapshot {
+                candidates: Some(candidates),
+                current_operator: Some(Address::repeat_byte(3)),
+                next_operator: None,
+                num_batches: Some(1_234),
+                paused: Some(false),
+            }
+        );
+        assert_eq!(decode_preconf_snapshot(&[]), PreconfSnapshot::default());
+    }
+}
@@ -0,0 +1,81 @@
+//! Taiko anchor contract
+//!
+//! The first transaction of every L2 block calls the anchor contract with the L1 block the L2
+//! chain is synced to.
+use alloy_sol_macro::sol;
+use alloy_sol_types::SolInterface;
+
+use ITaikoAnchor::ITaikoAnchorCalls;
+
+sol! {
+    #[allow(missing_docs)]
+    #[derive(Debug)]
+    interface ITaikoAnchor {
+        struct BaseFeeConfig {
+            uint8 adjustmentQuotient;
+            uint8 sharingPctg;
+            uint32 gasIssuancePerSecond;
+            uint64 minGasExcess;
+            uint32 maxGasIssuancePerBlock;
+        }
+
+        /// Ontake anchor transaction
+        function anchorV2(
+            uint64 _anchorBlockId,
+            bytes32 _anchorStateRoot,
+            uint32 _parentGasUsed,
+            BaseFeeConfig calldata _baseFeeConfig
+        ) external;
+
+        /// Pacaya anchor transaction
+        function anchorV3(
+            uint64 _anchorBlockId,
+            bytes32 _anchorStateRoot,
+            uint32 _parentGasUsed,
+            BaseFeeConfig calldata _baseFeeConfig,
+            bytes32[] calldata _signalSlots
+        ) external;
+    }
+}
+
+/// Decode the anchor block id from the calldata of an anchor transaction.
+///
+/// Returns `None` if `input` is not a call to a known anchor function.
+pub fn decode_anchor_block_id(input: &[u8]) -> Option<u64> {
+    match ITaikoAnchorCalls::abi_decode(input).ok()? {
+        ITaikoAnchorCalls::anchorV2(call) => Some(call._anchorBlockId),
+        ITaikoAnchorCalls::anchorV3(call) => Some(call._anchorBlockId),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_sol_types::SolCall;
+
+    #[test]
+    fn decodes_anchor_v3() {
+        let call = ITaikoAnchor::anchorV3Call {
+            _anchorBlockId: 21_000_000,
+            _anchorStateRoot: B256::repeat_byte(1),
+            _parentGasUsed: 42,
+            _baseFeeConfig: ITaikoAnchor::BaseFeeConfig {
+                adjustmentQuotient: 8,
+                sharingPctg: 75,
+                gasIssuancePerSecond: 5_000_000,
+                minGasExcess: 1,
+                maxGasIssuancePerBlock: 600_000_000,
+            },
+            _signalSlots: vec![B256::ZERO],
+        };
+
+        assert_eq!(decode_anchor_block_id(&call.abi_encode()), Some(21_000_000));
+    }
+
+    #[test]
+    fn rejects_unknown_calldata() {
+        assert_eq!(decode_anchor_block_id(&[]), None);
+        assert_eq!(decode_anchor_block_id(&[0xde, 0xad, 0xbe, 0xef]), None);
+    }
+}
@@ -0,0 +1,72 @@
+//! Taiko forced inclusion store contract
+use IForcedInclusionStore::IForcedInclusionStoreInstance;
+use alloy::{
+    contract::Result as ContractResult,
+    eips::BlockId,
+    primitives::{Address, U256},
+    sol,
+};
+
+use crate::DefaultProvider;
+
+/// A wrapper over the `IForcedInclusionStore` contract queuing forced inclusions until a
+/// proposer includes them.
+#[derive(Debug, Clone)]
+pub struct ForcedInclusionStore(IForcedInclusionStoreInstance<DefaultProvider>);
+
+impl ForcedInclusionStore {
+    /// Create a new `ForcedInclusionStore` instance at the given contract address.
+    pub const fn new_readonly(address: Address, provider: DefaultProvider) -> Self {
+        Self(IForcedInclusionStoreInstance::new(address, provider))
+    }
+
+    /// Reads the index of the oldest queued forced inclusion at the given L1 block via `head()`.
+    pub async fn head(&self, block: u64) -> ContractResult<u64> {
+        self.0.head().block(BlockId::number(block)).call().await
+    }
+
+    /// Reads the index after the newest queued forced inclusion at the given L1 block via
+    /// `tail()`.
+    pub async fn tail(&self, block: u64) -> ContractResult<u64> {
+        self.0.tail().block(BlockId::number(block)).call().await
+    }
+
+    /// Reads the forced inclusion at `index` at the given L1 block via `getForcedInclusion()`.
+    pub async fn forced_inclusion(
+        &self,
+        index: u64,
+        block: u64,
+    ) -> ContractResult<IForcedInclusionStore::ForcedInclusion> {
+        self.0.getForcedInclusion(U256::from(index)).block(BlockId::number(block)).call().await
+    }
+
+    /// Reads the batch id by which the oldest queued forced inclusion must be included at the
+    /// given L1 block via `getOldestForcedInclusionDeadline()`.
+    pub async fn oldest_deadline(&self, block: u64) -> ContractResult<U256> {
+        self.0.getOldestForcedInclusionDeadline().block(BlockId::number(block)).call().await
+    }
+}
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IForcedInclusionStore {
+        struct ForcedInclusion {
+            bytes32 blobHash;
+            uint64 feeInGwei;
+            uint64 createdAtBatchId;
+            uint32 blobByteOffset;
+            uint32 blobByteSize;
+            uint64 blobCreatedIn;
+        }
+
+        function head() external view returns (uint64);
+
+        function tail() external view returns (uint64);
+
+        function getForcedInclusion(uint256 index) external view returns (ForcedInclusion memory);
+
+        function getOldestForcedInclusionDeadline() external view returns (uint256);
+    }
+}
@@ -1,5 +1,11 @@
 //! Taiko contract bindings
+/// Anchor transaction decoding
+pub mod anchor;
+/// Queue of forced inclusions
+pub mod forced_inclusion_store;
 // Preconf whitelist
 pub mod preconf_whitelist;
+/// Proof verifiers
+pub mod verifier;
 /// Contract for delayed inbox
 pub mod wrapper;
@@ -1,6 +1,10 @@
 //! Taiko preconf whitelist contract
 use IPreconfWhitelist::{IPreconfWhitelistErrors, IPreconfWhitelistInstance};
-use alloy::contract::{Error as ContractError, Result as ContractResult};
+use alloy::{
+    contract::{Error as ContractError, Result as ContractResult},
+    eips::BlockId,
+    rpc::types::Filter,
+};
 use alloy_primitives::Address;
 use alloy_sol_macro::sol;
 use alloy_sol_types::{Error as SolError, SolInterface};
@@ -10,6 +14,38 @@ use crate::DefaultProvider;
 /// A UNIX timestamp in seconds.
 pub type Timestamp = u64;
 
+/// Why an operator was removed from the whitelist, stored as its `u8` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RemovalReason {
+    /// The removing account could not be determined
+    Unknown = 0,
+    /// Removed by an account that is not an ejecter, e.g. the owner rotating operators
+    Removed = 1,
+    /// Ejected by an authorized ejecter, e.g. for failing to fulfil its preconfirmations
+    Ejected = 2,
+}
+
+impl RemovalReason {
+    /// Reason stored for the given code, [`Self::Unknown`] for unassigned codes.
+    pub const fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::Removed,
+            2 => Self::Ejected,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Short lowercase name of the reason.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Removed => "removed",
+            Self::Ejected => "ejected",
+        }
+    }
+}
+
 /// A wrapper over a `IPreconfWhitelist` contract that exposes various utility methods.
 #[derive(Debug, Clone)]
 pub struct TaikoPreconfWhitelist(IPreconfWhitelistInstance<DefaultProvider>);
@@ -20,6 +56,21 @@ impl TaikoPreconfWhitelist {
         Self(IPreconfWhitelistInstance::new(address, provider))
     }
 
+    /// Address of the whitelist contract.
+    pub const fn address(&self) -> Address {
+        *self.0.address()
+    }
+
+    /// Returns a log [`Filter`] based on the `OperatorRemoved` event.
+    pub fn operator_removed_filter(&self) -> Filter {
+        self.0.OperatorRemoved_filter().filter
+    }
+
+    /// Check whether `account` was allowed to eject operators at the given L1 block.
+    pub async fn is_ejecter(&self, account: Address, block: u64) -> ContractResult<bool> {
+        self.0.ejecters(account).block(BlockId::number(block)).call().await
+    }
+
     /// Get the operator for the current epoch.
     pub async fn get_operator_for_current_epoch(&self) -> ContractResult<Address> {
         match self.0.getOperatorForCurrentEpoch().call().await {
@@ -80,7 +131,7 @@ impl TaikoPreconfWhitelist {
 sol! {
     #[allow(missing_docs)]
     #[sol(rpc)]
-    #[derive(Debug)]
+    #[derive(Debug, serde::Serialize, serde::Deserialize)]
     interface IPreconfWhitelist {
         error InvalidOperatorIndex();
         error InvalidOperatorCount();
@@ -88,6 +139,16 @@ sol! {
         error OperatorAlreadyExists();
         error OperatorNotAvailableYet();
 
+        /// @notice Emitted when an operator is removed from the whitelist, either by the owner
+        ///         or by an ejecter.
+        /// @param proposer The proposer address of the removed operator.
+        /// @param sequencer The sequencer address of the removed operator.
+        /// @param inactiveSince The timestamp from which the operator is no longer active.
+        event OperatorRemoved(address indexed proposer, address indexed sequencer, uint256 inactiveSince);
+
+        /// @notice Whether an address is allowed to eject operators.
+        function ejecters(address ejecter) external view returns (bool);
+
         /// @notice Adds a new operator to the whitelist.
         /// @param _operatorAddress The address of the operator to be added.
         /// @dev Only callable by the owner or an authorized address.
@@ -131,3 +192,16 @@ sol! {
 pub fn try_parse_contract_error<I: SolInterface>(error: ContractError) -> Result<I, ContractError> {
     error.as_decoded_interface_error::<I>().ok_or(error)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removal_reason_codes_round_trip() {
+        for reason in [RemovalReason::Unknown, RemovalReason::Removed, RemovalReason::Ejected] {
+            assert_eq!(RemovalReason::from_code(reason as u8), reason);
+        }
+        assert_eq!(RemovalReason::from_code(42), RemovalReason::Unknown);
+    }
+}
@@ -0,0 +1,117 @@
+//! Taiko proof verifiers
+//!
+//! Batches are proved by calling `proveBatches` on the inbox with a list of sub-proofs, each
+//! naming the verifier contract that checks it. The inbox hands them to a compose verifier,
+//! which exposes the address of every sub-proof verifier it accepts.
+use IComposeVerifier::IComposeVerifierInstance;
+use alloy_primitives::Address;
+use alloy_sol_macro::sol;
+use alloy_sol_types::{SolCall, SolValue};
+
+use crate::{DefaultProvider, ITaikoInbox::proveBatchesCall};
+
+sol! {
+    #[allow(missing_docs)]
+    #[sol(rpc)]
+    #[derive(Debug)]
+    interface IComposeVerifier {
+        struct SubProof {
+            address verifier;
+            bytes proof;
+        }
+
+        function sgxGethVerifier() external view returns (address);
+        function tdxGethVerifier() external view returns (address);
+        function sgxRethVerifier() external view returns (address);
+        function risc0RethVerifier() external view returns (address);
+        function sp1RethVerifier() external view returns (address);
+    }
+}
+
+/// Sub-proof verifiers accepted by a compose verifier, grouped by the kind of proof they check.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComposeVerifiers {
+    /// Verifiers of trusted execution environment proofs, i.e. SGX and TDX
+    pub tee: Vec<Address>,
+    /// Verifiers of zero-knowledge proofs, i.e. RISC Zero and SP1
+    pub zk: Vec<Address>,
+}
+
+impl ComposeVerifiers {
+    /// Read the sub-proof verifiers of the compose verifier at `address`.
+    ///
+    /// Getters that fail or return the zero address are skipped, so a contract that is not a
+    /// compose verifier yields an empty set.
+    pub async fn read(address: Address, provider: &DefaultProvider) -> Self {
+        let contract = IComposeVerifierInstance::new(address, provider.clone());
+        let tee = [
+            contract.sgxGethVerifier().call().await,
+            contract.tdxGethVerifier().call().await,
+            contract.sgxRethVerifier().call().await,
+        ];
+        let zk =
+            [contract.risc0RethVerifier().call().await, contract.sp1RethVerifier().call().await];
+        let known = |results: &[alloy::contract::Result<Address>]| {
+            results
+                .iter()
+                .filter_map(|result| result.as_ref().ok().copied())
+                .filter(|verifier| !verifier.is_zero())
+                .collect()
+        };
+        Self { tee: known(&tee), zk: known(&zk) }
+    }
+
+    /// Whether no sub-proof verifier is known.
+    pub const fn is_empty(&self) -> bool {
+        self.tee.is_empty() && self.zk.is_empty()
+    }
+}
+
+/// Decode the verifiers of the sub-proofs from the calldata of a `proveBatches` transaction.
+///
+/// Returns `None` if `input` is not a call to `proveBatches` or its proof is not a list of
+/// sub-proofs.
+pub fn decode_sub_proof_verifiers(input: &[u8]) -> Option<Vec<Address>> {
+    let call = proveBatchesCall::abi_decode(input).ok()?;
+    let sub_proofs = Vec::<IComposeVerifier::SubProof>::abi_decode(&call._proof).ok()?;
+    Some(sub_proofs.into_iter().map(|sub_proof| sub_proof.verifier).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+
+    #[test]
+    fn decodes_sub_proof_verifiers() {
+        let sub_proofs = vec![
+            IComposeVerifier::SubProof {
+                verifier: Address::repeat_byte(1),
+                proof: Bytes::from_static(b"sgx"),
+            },
+            IComposeVerifier::SubProof {
+                verifier: Address::repeat_byte(2),
+                proof: Bytes::from_static(b"sp1"),
+            },
+        ];
+        let call = proveBatchesCall {
+            _params: Bytes::from_static(b"params"),
+            _proof: sub_proofs.abi_encode().into(),
+        };
+
+        assert_eq!(
+            decode_sub_proof_verifiers(&call.abi_encode()),
+            Some(vec![Address::repeat_byte(1), Address::repeat_byte(2)])
+        );
+    }
+
+    #[test]
+    fn rejects_other_calldata() {
+        assert_eq!(decode_sub_proof_verifiers(&[]), None);
+        let call = proveBatchesCall {
+            _params: Bytes::new(),
+            _proof: Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]),
+        };
+        assert_eq!(decode_sub_proof_verifiers(&call.abi_encode()), None);
+    }
+}
@@ -1,5 +1,5 @@
 //! Taiko wrapper contract
-use alloy::rpc::types::Filter;
+use alloy::{contract::Result as ContractResult, eips::BlockId, rpc::types::Filter};
 use alloy_primitives::Address;
 use alloy_sol_macro::sol;
 use derive_more::derive::Deref;
@@ -22,6 +22,12 @@ impl TaikoWrapper {
     pub fn forced_inclusion_processed_filter(&self) -> Filter {
         self.0.ForcedInclusionProcessed_filter().filter
     }
+
+    /// Reads the address of the forced inclusion store at the given L1 block via
+    /// `forcedInclusionStore()`.
+    pub async fn forced_inclusion_store(&self, block: u64) -> ContractResult<Address> {
+        self.0.forcedInclusionStore().block(BlockId::number(block)).call().await
+    }
 }
 
 sol! {
@@ -41,5 +47,7 @@ sol! {
         event ForcedInclusionProcessed(
             ForcedInclusion forcedInclusion
         );
+
+        function forcedInclusionStore() external view returns (address);
     }
 }
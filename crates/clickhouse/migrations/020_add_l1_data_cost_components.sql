@@ -0,0 +1,7 @@
+-- Migration 020: split l1_data_costs into blob and calldata components
+-- Rows written before this migration keep their total in `cost` and report 0 for both
+-- components.
+
+ALTER TABLE ${DB}.l1_data_costs
+ADD COLUMN IF NOT EXISTS blob_cost UInt128 DEFAULT 0 AFTER cost,
+ADD COLUMN IF NOT EXISTS calldata_cost UInt128 DEFAULT 0 AFTER blob_cost;
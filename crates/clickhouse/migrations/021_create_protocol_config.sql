@@ -0,0 +1,24 @@
+-- Migration 021: Create protocol_config table holding the TaikoInbox configuration
+-- A row is appended on every driver startup; readers use the most recent one.
+
+CREATE TABLE IF NOT EXISTS ${DB}.protocol_config (
+    inbox_address FixedString(20),
+    chain_id UInt64,
+    max_unverified_batches UInt64,
+    batch_ring_buffer_size UInt64,
+    max_batches_to_verify UInt64,
+    block_max_gas_limit UInt32,
+    liveness_bond_base UInt128,
+    liveness_bond_per_block UInt128,
+    state_root_sync_internal UInt8,
+    max_anchor_height_offset UInt64,
+    base_fee_sharing_pctg UInt8,
+    proving_window UInt16,
+    cooldown_window UInt32,
+    max_signals_to_receive UInt8,
+    max_blocks_per_batch UInt16,
+    ontake_fork_height UInt64,
+    pacaya_fork_height UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (inbox_address, inserted_at);
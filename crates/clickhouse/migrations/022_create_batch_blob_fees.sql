@@ -0,0 +1,11 @@
+-- Migration 022: Create batch_blob_fees table tracking the L1 blob fee market
+-- Stores the excess blob gas and blob base fee of the L1 block each batch was proposed in.
+
+CREATE TABLE IF NOT EXISTS ${DB}.batch_blob_fees (
+    l1_block_number UInt64,
+    batch_id UInt64,
+    excess_blob_gas UInt64,
+    blob_base_fee UInt128,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (l1_block_number, batch_id);
@@ -0,0 +1,11 @@
+-- Migration 023: Create l2_anchor_blocks table tracking the L1 -> L2 sync lag
+-- Stores the anchor block id of each L2 block together with the L1 head known when it was ingested.
+
+CREATE TABLE IF NOT EXISTS ${DB}.l2_anchor_blocks (
+    l2_block_number UInt64,
+    block_ts UInt64,
+    anchor_block_id UInt64,
+    l1_head UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (l2_block_number);
@@ -0,0 +1,13 @@
+-- Migration 024: Create rpc_health table storing public RPC probe results
+-- Each row is one probe of a public RPC endpoint: latency, block height lag and whether it failed.
+
+CREATE TABLE IF NOT EXISTS ${DB}.rpc_health (
+    url String,
+    healthy UInt8,
+    latency_ms Nullable(UInt64),
+    block_number Nullable(UInt64),
+    block_lag Nullable(UInt64),
+    error Nullable(String),
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (url, inserted_at);
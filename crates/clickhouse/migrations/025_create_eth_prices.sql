@@ -0,0 +1,9 @@
+-- Migration 025: Create eth_prices table storing periodic ETH/USD price snapshots
+-- Costs are converted to USD with the latest snapshot taken at or before the event.
+
+CREATE TABLE IF NOT EXISTS ${DB}.eth_prices (
+    ts UInt64,
+    usd Float64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY ts;
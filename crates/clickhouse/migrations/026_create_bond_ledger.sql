@@ -0,0 +1,15 @@
+-- Migration 026: Create bond_ledger table tracking inbox bond balance changes
+-- Each row is one BondDeposited/BondWithdrawn/BondDebited/BondCredited event together with the
+-- account's bond balance at the end of the L1 block it was emitted in.
+
+CREATE TABLE IF NOT EXISTS ${DB}.bond_ledger (
+    address FixedString(20),
+    l1_block_number UInt64,
+    tx_hash FixedString(32),
+    log_index UInt64,
+    kind LowCardinality(String),
+    amount UInt128,
+    balance UInt128,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (address, l1_block_number, log_index);
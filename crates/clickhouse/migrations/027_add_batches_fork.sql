@@ -0,0 +1,3 @@
+-- Migration 027: Record the fork whose inbox emitted each batch
+ALTER TABLE ${DB}.batches
+ADD COLUMN IF NOT EXISTS fork LowCardinality(String) DEFAULT '' AFTER blob_total_bytes;
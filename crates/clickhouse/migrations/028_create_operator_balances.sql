@@ -0,0 +1,14 @@
+-- Migration 028: Create operator_balances table storing periodic L1 wallet balance snapshots
+-- Each snapshot round records the ETH and TAIKO balances of every whitelisted operator, and
+-- whether either fell below its configured threshold.
+
+CREATE TABLE IF NOT EXISTS ${DB}.operator_balances (
+    snapshot_ts UInt64,
+    address FixedString(20),
+    l1_block_number UInt64,
+    eth_balance UInt128,
+    taiko_balance Nullable(UInt128),
+    low_balance UInt8,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (snapshot_ts, address);
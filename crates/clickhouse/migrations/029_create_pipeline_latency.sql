@@ -0,0 +1,12 @@
+-- Migration 029: Create pipeline_latency table storing per-event ingestion stage timings
+-- queue_us is the time from receipt by the extractor until the driver started processing the
+-- event, process_us the time the driver took to enrich it and write or buffer its rows.
+
+CREATE TABLE IF NOT EXISTS ${DB}.pipeline_latency (
+    kind LowCardinality(String),
+    queue_us UInt64,
+    process_us UInt64,
+    total_us UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (kind, inserted_at);
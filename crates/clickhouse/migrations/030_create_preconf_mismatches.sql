@@ -0,0 +1,17 @@
+-- Migration 030: Create preconf_mismatches table storing differences between the soft blocks
+-- advertised by the sequencer and the canonical L2 blocks at the same height.
+-- A missing canonical block hash means no canonical block appeared within the comparison
+-- timeout.
+
+CREATE TABLE IF NOT EXISTS ${DB}.preconf_mismatches (
+    l2_block_number UInt64,
+    soft_block_hash Nullable(FixedString(32)),
+    canonical_block_hash Nullable(FixedString(32)),
+    soft_tx_count UInt32,
+    canonical_tx_count UInt32,
+    missing_tx_hashes Array(FixedString(32)),
+    extra_tx_hashes Array(FixedString(32)),
+    reordered UInt8,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (l2_block_number, inserted_at);
@@ -0,0 +1,4 @@
+-- Migration 031: Record why an operator was removed from the preconf whitelist
+-- 0 = unknown, 1 = removed, 2 = ejected
+ALTER TABLE ${DB}.slashing_events
+ADD COLUMN IF NOT EXISTS reason UInt8 DEFAULT 0 AFTER validator_addr;
@@ -0,0 +1,14 @@
+-- Migration 032: Create protocol_gas_spend table recording the L1 gas spent by every protocol
+-- transaction (batch proposals, proofs and verifications), attributed to the sending address.
+-- The cost includes blob fees.
+
+CREATE TABLE IF NOT EXISTS ${DB}.protocol_gas_spend (
+    l1_block_number UInt64,
+    tx_hash FixedString(32),
+    address FixedString(20),
+    operation LowCardinality(String),
+    gas_used UInt64,
+    cost UInt128,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (operation, l1_block_number, tx_hash);
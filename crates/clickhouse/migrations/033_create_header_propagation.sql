@@ -0,0 +1,13 @@
+-- Migration 033: Create header_propagation table storing when each live L1/L2 header was
+-- received next to its chain timestamp. delay_ms is the receive time minus the block timestamp
+-- and may be negative when the sequencer's clock runs ahead of ours.
+
+CREATE TABLE IF NOT EXISTS ${DB}.header_propagation (
+    chain LowCardinality(String),
+    block_number UInt64,
+    block_ts UInt64,
+    observed_at DateTime64(3),
+    delay_ms Int64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (chain, observed_at);
@@ -0,0 +1,4 @@
+-- Migration 034: add probable_cause column to l2_reorgs
+
+ALTER TABLE ${DB}.l2_reorgs
+ADD COLUMN IF NOT EXISTS probable_cause UInt8 DEFAULT 0 AFTER new_sequencer;
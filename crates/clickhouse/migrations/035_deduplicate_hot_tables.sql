@@ -0,0 +1,170 @@
+-- Migration 035: Move the hot tables to ReplacingMergeTree keyed on their natural keys
+-- Reconnect replays and redelivered events insert the same row twice. ReplacingMergeTree
+-- collapses rows with the same sorting key on merge, keeping the most recently inserted copy;
+-- readers query with FINAL so duplicates awaiting a merge are not counted.
+-- The previous tables are kept as *_old until the swap has been verified.
+
+-- l1_head_events: keyed on block hash; block_ts leads so time-range scans stay cheap
+CREATE TABLE IF NOT EXISTS ${DB}.l1_head_events_dedup (
+    l1_block_number UInt64,
+    block_hash      FixedString(32),
+    slot            UInt64,
+    block_ts        UInt64,
+    inserted_at     DateTime64(3) DEFAULT now64(),
+    INDEX idx_l1_block_hash_bf block_hash TYPE bloom_filter(0.01) GRANULARITY 1
+) ENGINE = ReplacingMergeTree(inserted_at)
+PARTITION BY toDate(fromUnixTimestamp(block_ts))
+ORDER BY (block_ts, l1_block_number, block_hash);
+
+INSERT INTO ${DB}.l1_head_events_dedup (l1_block_number, block_hash, slot, block_ts, inserted_at)
+SELECT l1_block_number, block_hash, slot, block_ts, inserted_at
+FROM ${DB}.l1_head_events;
+
+DROP TABLE IF EXISTS ${DB}.l1_head_events_old;
+RENAME TABLE ${DB}.l1_head_events TO ${DB}.l1_head_events_old;
+RENAME TABLE ${DB}.l1_head_events_dedup TO ${DB}.l1_head_events;
+
+-- l2_head_events: keyed on block hash, so blocks replaced by a reorg are kept
+CREATE TABLE IF NOT EXISTS ${DB}.l2_head_events_dedup (
+    l2_block_number  UInt64,
+    block_hash       FixedString(32),
+    block_ts         UInt64,
+    sum_gas_used     UInt128,
+    sum_tx           UInt32,
+    sum_priority_fee UInt128,
+    sum_base_fee     UInt128,
+    sequencer        FixedString(20),
+    inserted_at      DateTime64(3) DEFAULT now64(),
+    INDEX idx_l2_block_hash_bf block_hash TYPE bloom_filter(0.01) GRANULARITY 1,
+    INDEX idx_l2_sequencer_bf  sequencer  TYPE bloom_filter(0.01) GRANULARITY 1
+) ENGINE = ReplacingMergeTree(inserted_at)
+PARTITION BY toDate(fromUnixTimestamp(block_ts))
+ORDER BY (block_ts, l2_block_number, block_hash);
+
+INSERT INTO ${DB}.l2_head_events_dedup (
+    l2_block_number, block_hash, block_ts, sum_gas_used, sum_tx, sum_priority_fee, sum_base_fee,
+    sequencer, inserted_at
+)
+SELECT
+    l2_block_number, block_hash, block_ts, sum_gas_used, sum_tx, sum_priority_fee, sum_base_fee,
+    sequencer, inserted_at
+FROM ${DB}.l2_head_events;
+
+DROP TABLE IF EXISTS ${DB}.l2_head_events_old;
+RENAME TABLE ${DB}.l2_head_events TO ${DB}.l2_head_events_old;
+RENAME TABLE ${DB}.l2_head_events_dedup TO ${DB}.l2_head_events;
+
+-- batches: keyed on batch id and proposal block. Not partitioned by insertion day, as
+-- duplicates in different partitions are never merged
+CREATE TABLE IF NOT EXISTS ${DB}.batches_dedup (
+    l1_block_number      UInt64,
+    l1_tx_hash           FixedString(32),
+    batch_id             UInt64,
+    batch_size           UInt16,
+    last_l2_block_number UInt64,
+    proposer_addr        FixedString(20),
+    blob_count           UInt8,
+    blob_total_bytes     UInt32,
+    fork                 LowCardinality(String) DEFAULT '',
+    inserted_at          DateTime64(3) DEFAULT now64(),
+    INDEX idx_batches_l1_tx_bf    l1_tx_hash    TYPE bloom_filter(0.01) GRANULARITY 1,
+    INDEX idx_batches_proposer_bf proposer_addr TYPE bloom_filter(0.01) GRANULARITY 1,
+    INDEX idx_batches_inserted_at inserted_at   TYPE minmax GRANULARITY 1
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (batch_id, l1_block_number);
+
+INSERT INTO ${DB}.batches_dedup (
+    l1_block_number, l1_tx_hash, batch_id, batch_size, last_l2_block_number, proposer_addr,
+    blob_count, blob_total_bytes, fork, inserted_at
+)
+SELECT
+    l1_block_number, l1_tx_hash, batch_id, batch_size, last_l2_block_number, proposer_addr,
+    blob_count, blob_total_bytes, fork, inserted_at
+FROM ${DB}.batches;
+
+DROP TABLE IF EXISTS ${DB}.batches_old;
+RENAME TABLE ${DB}.batches TO ${DB}.batches_old;
+RENAME TABLE ${DB}.batches_dedup TO ${DB}.batches;
+
+-- proved_batches: keyed on batch id and proof block
+CREATE TABLE IF NOT EXISTS ${DB}.proved_batches_dedup (
+    l1_block_number UInt64,
+    batch_id        UInt64,
+    verifier_addr   FixedString(20),
+    parent_hash     FixedString(32),
+    block_hash      FixedString(32),
+    state_root      FixedString(32),
+    inserted_at     DateTime64(3) DEFAULT now64(),
+    INDEX idx_proved_batches_batch_id_bf batch_id   TYPE bloom_filter(0.01) GRANULARITY 1,
+    INDEX idx_proved_batches_block_bf    block_hash TYPE bloom_filter(0.01) GRANULARITY 1
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (l1_block_number, batch_id);
+
+INSERT INTO ${DB}.proved_batches_dedup (
+    l1_block_number, batch_id, verifier_addr, parent_hash, block_hash, state_root, inserted_at
+)
+SELECT l1_block_number, batch_id, verifier_addr, parent_hash, block_hash, state_root, inserted_at
+FROM ${DB}.proved_batches;
+
+DROP TABLE IF EXISTS ${DB}.proved_batches_old;
+RENAME TABLE ${DB}.proved_batches TO ${DB}.proved_batches_old;
+RENAME TABLE ${DB}.proved_batches_dedup TO ${DB}.proved_batches;
+
+-- verified_batches: keyed on batch id and verification block
+CREATE TABLE IF NOT EXISTS ${DB}.verified_batches_dedup (
+    l1_block_number UInt64,
+    batch_id        UInt64,
+    block_hash      FixedString(32),
+    inserted_at     DateTime64(3) DEFAULT now64(),
+    INDEX idx_verified_batches_batch_id_bf batch_id   TYPE bloom_filter(0.01) GRANULARITY 1,
+    INDEX idx_verified_batches_block_bf    block_hash TYPE bloom_filter(0.01) GRANULARITY 1
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (l1_block_number, batch_id);
+
+INSERT INTO ${DB}.verified_batches_dedup (l1_block_number, batch_id, block_hash, inserted_at)
+SELECT l1_block_number, batch_id, block_hash, inserted_at
+FROM ${DB}.verified_batches;
+
+DROP TABLE IF EXISTS ${DB}.verified_batches_old;
+RENAME TABLE ${DB}.verified_batches TO ${DB}.verified_batches_old;
+RENAME TABLE ${DB}.verified_batches_dedup TO ${DB}.verified_batches;
+
+-- l1_data_costs: keyed on batch id and proposal block
+CREATE TABLE IF NOT EXISTS ${DB}.l1_data_costs_dedup (
+    l1_block_number UInt64,
+    batch_id        UInt64,
+    cost            UInt128,
+    blob_cost       UInt128 DEFAULT 0,
+    calldata_cost   UInt128 DEFAULT 0,
+    inserted_at     DateTime64(3) DEFAULT now64(),
+    INDEX idx_l1_data_costs_batch_id_bf batch_id TYPE bloom_filter(0.01) GRANULARITY 1
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (l1_block_number, batch_id);
+
+INSERT INTO ${DB}.l1_data_costs_dedup (
+    l1_block_number, batch_id, cost, blob_cost, calldata_cost, inserted_at
+)
+SELECT l1_block_number, batch_id, cost, blob_cost, calldata_cost, inserted_at
+FROM ${DB}.l1_data_costs;
+
+DROP TABLE IF EXISTS ${DB}.l1_data_costs_old;
+RENAME TABLE ${DB}.l1_data_costs TO ${DB}.l1_data_costs_old;
+RENAME TABLE ${DB}.l1_data_costs_dedup TO ${DB}.l1_data_costs;
+
+-- prove_costs: keyed on batch id and proof block; a recomputed cost replaces the earlier one
+CREATE TABLE IF NOT EXISTS ${DB}.prove_costs_dedup (
+    l1_block_number UInt64,
+    batch_id        UInt64,
+    cost            UInt128,
+    inserted_at     DateTime64(3) DEFAULT now64(),
+    INDEX idx_prove_costs_batch_id_bf batch_id TYPE bloom_filter(0.01) GRANULARITY 1
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (l1_block_number, batch_id);
+
+INSERT INTO ${DB}.prove_costs_dedup (l1_block_number, batch_id, cost, inserted_at)
+SELECT l1_block_number, batch_id, cost, inserted_at
+FROM ${DB}.prove_costs;
+
+DROP TABLE IF EXISTS ${DB}.prove_costs_old;
+RENAME TABLE ${DB}.prove_costs TO ${DB}.prove_costs_old;
+RENAME TABLE ${DB}.prove_costs_dedup TO ${DB}.prove_costs;
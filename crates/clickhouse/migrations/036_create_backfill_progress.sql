@@ -0,0 +1,16 @@
+-- Migration 036: Create backfill_progress table storing snapshots of the gap backfill of each
+-- chain. A row is written when a backfill run starts, periodically while it runs and when it
+-- ends; the latest row per chain is its current status.
+
+CREATE TABLE IF NOT EXISTS ${DB}.backfill_progress (
+    chain LowCardinality(String),
+    scheduled_blocks UInt64,
+    filled_blocks UInt64,
+    failed_blocks UInt64,
+    newest_block Nullable(UInt64),
+    oldest_block Nullable(UInt64),
+    current_block Nullable(UInt64),
+    max_requests_per_sec UInt32,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (chain, inserted_at);
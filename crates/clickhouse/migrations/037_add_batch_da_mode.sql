@@ -0,0 +1,10 @@
+-- Migration 037: record the data availability mode and calldata size of each batch
+-- da_mode is 0 for blob batches and 1 for batches posting their txList as calldata. Batches
+-- proposed before this migration without blobs must have used calldata; their calldata size is
+-- unknown and stays 0.
+
+ALTER TABLE ${DB}.batches
+ADD COLUMN IF NOT EXISTS da_mode UInt8 DEFAULT 0 AFTER blob_total_bytes,
+ADD COLUMN IF NOT EXISTS calldata_bytes UInt32 DEFAULT 0 AFTER da_mode;
+
+ALTER TABLE ${DB}.batches UPDATE da_mode = 1 WHERE blob_count = 0;
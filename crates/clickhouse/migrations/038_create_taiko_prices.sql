@@ -0,0 +1,11 @@
+-- Migration 038: Create taiko_prices table storing periodic TAIKO/ETH and TAIKO/USD snapshots
+-- Prove costs and bonds paid in TAIKO are converted with the latest snapshot taken at or before
+-- the event, like eth_prices.
+
+CREATE TABLE IF NOT EXISTS ${DB}.taiko_prices (
+    ts UInt64,
+    eth Float64,
+    usd Float64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY ts;
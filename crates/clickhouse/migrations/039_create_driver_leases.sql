@@ -0,0 +1,22 @@
+-- Migration 039: Create driver_leases and leader_changes tables for driver leader election
+-- Every driver instance renews a lease row periodically; the live holder that started contending
+-- first is the leader and the only instance ingesting events. A released row or a missed renewal
+-- hands leadership to the next holder. Leadership changes are recorded by the new leader.
+
+CREATE TABLE IF NOT EXISTS ${DB}.driver_leases (
+    lease LowCardinality(String),
+    holder String,
+    started_at UInt64,
+    released UInt8 DEFAULT 0,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (lease, inserted_at)
+TTL toDateTime(inserted_at) + INTERVAL 1 DAY;
+
+CREATE TABLE IF NOT EXISTS ${DB}.leader_changes (
+    lease LowCardinality(String),
+    holder String,
+    previous_holder Nullable(String),
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = MergeTree()
+ORDER BY (lease, inserted_at);
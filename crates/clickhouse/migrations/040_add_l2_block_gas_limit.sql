@@ -0,0 +1,6 @@
+-- Migration 040: record the gas limit of each L2 block
+-- Blocks ingested before this migration keep a gas limit of 0 and are left out of the gas
+-- saturation series.
+
+ALTER TABLE ${DB}.l2_head_events
+ADD COLUMN IF NOT EXISTS gas_limit UInt64 DEFAULT 0 AFTER sum_gas_used;
@@ -0,0 +1,21 @@
+-- Migration 041: Create sequencer_downtime and sequencer_schedule tables for sequencer uptime
+-- sequencer_downtime holds the spans in which the scheduled sequencer produced no L2 blocks for
+-- longer than the configured gap, and sequencer_schedule the seconds each sequencer was
+-- scheduled per hour. Both are split at hour boundaries and recomputed over a lookback window,
+-- so recomputed rows replace the earlier ones on merge.
+
+CREATE TABLE IF NOT EXISTS ${DB}.sequencer_downtime (
+    sequencer FixedString(20),
+    start_ts UInt64,
+    end_ts UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (sequencer, start_ts);
+
+CREATE TABLE IF NOT EXISTS ${DB}.sequencer_schedule (
+    sequencer FixedString(20),
+    hour_ts UInt64,
+    scheduled_secs UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (sequencer, hour_ts);
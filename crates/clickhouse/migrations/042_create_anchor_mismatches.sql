@@ -0,0 +1,13 @@
+-- Migration 042: Create anchor_mismatches table for batches anchored to an unknown L1 block hash
+-- Each row is a proposed batch whose anchorBlockHash differs from the hash of the indexed L1
+-- block at its anchorBlockId. Reprocessing a batch replaces its earlier row on merge.
+
+CREATE TABLE IF NOT EXISTS ${DB}.anchor_mismatches (
+    batch_id UInt64,
+    l1_block_number UInt64,
+    anchor_block_id UInt64,
+    anchor_block_hash FixedString(32),
+    l1_block_hash FixedString(32),
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (batch_id, l1_block_number);
@@ -0,0 +1,8 @@
+-- Migration 043: record the L1 transaction that proved or verified each batch
+-- Rows ingested before this migration keep a zero hash, which readers report as unknown.
+
+ALTER TABLE ${DB}.proved_batches
+ADD COLUMN IF NOT EXISTS l1_tx_hash FixedString(32) AFTER state_root;
+
+ALTER TABLE ${DB}.verified_batches
+ADD COLUMN IF NOT EXISTS l1_tx_hash FixedString(32) AFTER block_hash;
@@ -0,0 +1,18 @@
+-- Migration 044: Create mempool_stats table for per-minute L2 mempool aggregates
+-- Each row summarizes one minute of the L2 node's pending transaction feed: how many transactions
+-- entered the mempool and were included, how deep the mempool grew and how long inclusion took.
+-- Re-flushing a minute replaces its earlier row on merge.
+
+CREATE TABLE IF NOT EXISTS ${DB}.mempool_stats (
+    minute_ts UInt64,
+    seen_txs UInt32,
+    included_txs UInt32,
+    max_depth UInt32,
+    end_depth UInt32,
+    avg_inclusion_ms UInt64,
+    p50_inclusion_ms UInt64,
+    p95_inclusion_ms UInt64,
+    max_inclusion_ms UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY minute_ts;
@@ -0,0 +1,14 @@
+-- Migration 045: Create operator_history table for changes to the whitelist candidate set
+-- Each row is a snapshot of the operator candidates of the current epoch that differs from the
+-- previous one, with the candidates added and removed since. The first snapshot taken by a fresh
+-- deployment has no previous one and lists no changes.
+
+CREATE TABLE IF NOT EXISTS ${DB}.operator_history (
+    observed_ts UInt64,
+    l1_block_number UInt64,
+    candidates Array(FixedString(20)),
+    added Array(FixedString(20)),
+    removed Array(FixedString(20)),
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (observed_ts, l1_block_number);
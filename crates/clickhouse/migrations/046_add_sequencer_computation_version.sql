@@ -0,0 +1,10 @@
+-- Migration 046: version the rows of the sequencer uptime tables
+-- computation_version records the version of the logic that derived each row, so that rows derived
+-- by an older version can be found and recomputed with `--reprocess`. Readers only use the rows of
+-- the latest version of every hour. Rows derived before this migration are version 1.
+
+ALTER TABLE ${DB}.sequencer_downtime
+ADD COLUMN IF NOT EXISTS computation_version UInt16 DEFAULT 1 AFTER end_ts;
+
+ALTER TABLE ${DB}.sequencer_schedule
+ADD COLUMN IF NOT EXISTS computation_version UInt16 DEFAULT 1 AFTER scheduled_secs;
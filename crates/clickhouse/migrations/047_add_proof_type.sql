@@ -0,0 +1,6 @@
+-- Migration 047: record the proof type of each proved batch
+-- proof_type is 0 when unknown, 1 for SGX, 2 for ZK and 3 for guardian proofs. Batches proved
+-- before this migration keep 0.
+
+ALTER TABLE ${DB}.proved_batches
+ADD COLUMN IF NOT EXISTS proof_type UInt8 DEFAULT 0 AFTER l1_tx_hash;
@@ -0,0 +1,19 @@
+-- Migration 048: Create orphaned_block_details table for the content of reorged L2 blocks
+-- Each orphaned block is fetched by hash as soon as the reorg is detected, before the node prunes
+-- it. The row is written again once its transactions were checked for re-inclusion in the
+-- canonical chain; the latest version wins.
+
+CREATE TABLE IF NOT EXISTS ${DB}.orphaned_block_details (
+    block_hash FixedString(32),
+    l2_block_number UInt64,
+    parent_hash FixedString(32),
+    block_ts UInt64,
+    sequencer FixedString(20),
+    gas_used UInt64,
+    tx_count UInt32,
+    tx_hashes Array(FixedString(32)),
+    reinclusion_checked UInt8,
+    reincluded_txs UInt32,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (l2_block_number, block_hash);
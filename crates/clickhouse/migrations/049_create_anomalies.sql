@@ -0,0 +1,15 @@
+-- Migration 049: Create anomalies table for sudden changes of core metrics
+-- Each row is a sample of a metric, identified by its `AnomalyMetric` code, that deviated from
+-- the exponentially weighted moving average of the previous samples by more than the configured
+-- number of standard deviations.
+
+CREATE TABLE IF NOT EXISTS ${DB}.anomalies (
+    detected_ts UInt64,
+    metric UInt8,
+    value Float64,
+    expected Float64,
+    std_dev Float64,
+    z_score Float64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (metric, detected_ts);
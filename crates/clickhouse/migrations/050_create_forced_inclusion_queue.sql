@@ -0,0 +1,17 @@
+-- Migration 050: Create forced_inclusion_queue table storing periodic snapshots of the
+-- ForcedInclusionStore queue
+-- Each snapshot records the number of queued forced inclusions, the age of the oldest one, and
+-- whether it is within the configured number of batches of its inclusion deadline.
+
+CREATE TABLE IF NOT EXISTS ${DB}.forced_inclusion_queue (
+    snapshot_ts UInt64,
+    l1_block_number UInt64,
+    pending UInt64,
+    oldest_created_ts Nullable(UInt64),
+    oldest_age_secs Nullable(UInt64),
+    deadline_batch_id Nullable(UInt64),
+    next_batch_id UInt64,
+    near_deadline UInt8,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (snapshot_ts);
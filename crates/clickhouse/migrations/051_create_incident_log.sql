@@ -0,0 +1,19 @@
+-- Migration 051: Create incident_log table auditing the incident operations of the monitors
+-- Every creation, update and resolution of an Instatus incident is recorded with the request
+-- payload and the HTTP status code returned by Instatus (0 when no response was received).
+-- Operations skipped in dry-run mode are recorded with dry_run = 1.
+
+CREATE TABLE IF NOT EXISTS ${DB}.incident_log (
+    logged_at_ms UInt64,
+    action UInt8,
+    incident_id String,
+    component_ids Array(String),
+    incident_name String,
+    status String,
+    payload String,
+    response_code UInt16,
+    error Nullable(String),
+    dry_run UInt8,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (logged_at_ms, action, incident_id);
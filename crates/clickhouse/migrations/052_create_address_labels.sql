@@ -0,0 +1,13 @@
+-- Migration 052: Create address_labels table storing human readable names of addresses
+-- Labels come from the configured static label map or from verified ENS reverse records on L1
+-- and are joined into API responses listing sequencers and proposers. The latest label of an
+-- address wins.
+
+CREATE TABLE IF NOT EXISTS ${DB}.address_labels (
+    address FixedString(20),
+    label String,
+    source UInt8,
+    updated_ts UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (address);
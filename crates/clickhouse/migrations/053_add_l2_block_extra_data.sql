@@ -0,0 +1,7 @@
+-- Migration 053: record the extra data of each L2 block
+-- Blocks ingested before this migration keep an empty string and are left out of the builder
+-- distribution. Recorded extra data is hex encoded with a 0x prefix, so blocks with empty extra
+-- data store '0x'.
+
+ALTER TABLE ${DB}.l2_head_events
+ADD COLUMN IF NOT EXISTS extra_data String DEFAULT '' AFTER sequencer;
@@ -0,0 +1,21 @@
+-- Migration 054: Create proposal_inclusions table timing batch proposals from the L1 mempool
+-- A row is written when a proposal transaction of a proposer nonce is first seen pending, again
+-- when it is replaced with a new fee, and once more when its BatchProposed event lands or the nonce
+-- is used without proposing a batch; the latest version wins.
+
+CREATE TABLE IF NOT EXISTS ${DB}.proposal_inclusions (
+    proposer FixedString(20),
+    nonce UInt64,
+    tx_hash FixedString(32),
+    first_seen_ms UInt64,
+    max_fee_per_gas UInt128,
+    max_priority_fee_per_gas Nullable(UInt128),
+    replacements UInt32,
+    batch_id Nullable(UInt64),
+    l1_block_number Nullable(UInt64),
+    included_at_ms Nullable(UInt64),
+    inclusion_delay_ms Nullable(UInt64),
+    dropped UInt8,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (proposer, nonce);
@@ -0,0 +1,6 @@
+-- Migration 055: record the average effective gas price of each L2 block
+-- The price is weighted by the gas used of the non-anchor transactions. Blocks ingested before
+-- this migration keep 0.
+
+ALTER TABLE ${DB}.l2_head_events
+ADD COLUMN IF NOT EXISTS avg_gas_price UInt128 DEFAULT 0 AFTER sum_base_fee;
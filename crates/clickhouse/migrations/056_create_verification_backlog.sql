@@ -0,0 +1,15 @@
+-- Migration 056: Create verification_backlog table storing periodic snapshots of how many
+-- batches the latest verified batch lags behind the latest proposed batch
+-- Each snapshot records both batch ids read from the inbox and whether the backlog exceeded the
+-- configured threshold.
+
+CREATE TABLE IF NOT EXISTS ${DB}.verification_backlog (
+    snapshot_ts UInt64,
+    l1_block_number UInt64,
+    last_proposed_batch_id UInt64,
+    last_verified_batch_id UInt64,
+    backlog UInt64,
+    over_threshold UInt8,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (snapshot_ts);
@@ -0,0 +1,13 @@
+-- Migration 057: Create l1_tx_index table mapping L1 transaction hashes to the protocol role
+-- they played and the batch they relate to
+-- A prove transaction covering several batches gets one row per batch; forced inclusions are not
+-- tied to a batch id and store 0.
+
+CREATE TABLE IF NOT EXISTS ${DB}.l1_tx_index (
+    tx_hash FixedString(32),
+    role UInt8,
+    batch_id UInt64,
+    l1_block_number UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (tx_hash, role, batch_id);
@@ -0,0 +1,16 @@
+-- Migration 058: Create sequencer_handovers table storing every change of the L2 block sequencer
+-- Each row links the last block of the outgoing sequencer to the first block of the incoming one
+-- and the seconds between them. Handovers are recomputed with the sequencer downtime over a
+-- lookback window, so recomputed rows replace the earlier ones on merge.
+
+CREATE TABLE IF NOT EXISTS ${DB}.sequencer_handovers (
+    outgoing FixedString(20),
+    incoming FixedString(20),
+    last_block_number UInt64,
+    last_block_ts UInt64,
+    first_block_number UInt64,
+    first_block_ts UInt64,
+    latency_secs UInt64,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (first_block_number);
@@ -0,0 +1,20 @@
+-- Migration 059: Create fee_reconciliations table comparing attributed sequencer fees with balances
+-- Each row holds, for one sequencer and chain over a period, the balance change implied by the
+-- indexed revenues (L2) or proposal costs (L1) next to the change of the address's on-chain
+-- balance between the period's first and last block. Rows whose difference exceeds the
+-- tolerance are flagged.
+
+CREATE TABLE IF NOT EXISTS ${DB}.fee_reconciliations (
+    period_start UInt64,
+    period_end UInt64,
+    sequencer FixedString(20),
+    chain LowCardinality(String),
+    from_block UInt64,
+    to_block UInt64,
+    expected_delta Int128,
+    actual_delta Int128,
+    discrepancy Int128,
+    flagged UInt8,
+    inserted_at DateTime64(3) DEFAULT now64()
+) ENGINE = ReplacingMergeTree(inserted_at)
+ORDER BY (period_end, sequencer, chain);
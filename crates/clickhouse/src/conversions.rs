@@ -1,10 +1,13 @@
 use crate::{
-    models::{BatchRow, ForcedInclusionProcessedRow, ProvedBatchRow, VerifiedBatchRow},
+    models::{
+        BatchRow, ForcedInclusionProcessedRow, ProtocolConfigRow, ProvedBatchRow, VerifiedBatchRow,
+    },
     types::{AddressBytes, HashBytes},
 };
-use alloy::primitives::B256;
+use alloy::primitives::{Address, B256};
 use chainio::{ITaikoInbox, taiko::wrapper::ITaikoWrapper};
 use eyre::{Error, Result, eyre};
+use primitives::{da::DaMode, proof::ProofType};
 use std::convert::TryFrom;
 
 // Conversion from L2Header to L2HeadEvent is intentionally omitted. The
@@ -20,6 +23,7 @@ impl TryFrom<(&ITaikoInbox::BatchProposed, B256)> for BatchRow {
         let (batch, tx_hash) = input;
         let batch_size = u16::try_from(batch.info.blocks.len())?;
         let blob_count = u8::try_from(batch.info.blobHashes.len())?;
+        let calldata_bytes = u32::try_from(batch.txList.len())?;
 
         let proposer_addr = AddressBytes::from(batch.meta.proposer);
 
@@ -32,16 +36,19 @@ impl TryFrom<(&ITaikoInbox::BatchProposed, B256)> for BatchRow {
             proposer_addr,
             blob_count,
             blob_total_bytes: batch.info.blobByteSize,
+            da_mode: DaMode::from_tx_list_len(batch.txList.len()) as u8,
+            calldata_bytes,
+            fork: String::new(),
         })
     }
 }
 
-// Conversion from (BatchesProved, u64) to ProvedBatchRow
-impl TryFrom<(&ITaikoInbox::BatchesProved, u64)> for ProvedBatchRow {
+// Conversion from (BatchesProved, u64, B256) to ProvedBatchRow
+impl TryFrom<(&ITaikoInbox::BatchesProved, u64, B256)> for ProvedBatchRow {
     type Error = Error;
 
-    fn try_from(input: (&ITaikoInbox::BatchesProved, u64)) -> Result<Self, Self::Error> {
-        let (proved, l1_block_number) = input;
+    fn try_from(input: (&ITaikoInbox::BatchesProved, u64, B256)) -> Result<Self, Self::Error> {
+        let (proved, l1_block_number, l1_tx_hash) = input;
 
         if proved.batchIds.is_empty() || proved.transitions.is_empty() {
             return Err(eyre!("Empty batch IDs or transitions"));
@@ -67,6 +74,8 @@ impl TryFrom<(&ITaikoInbox::BatchesProved, u64)> for ProvedBatchRow {
             parent_hash: HashBytes::from(parent),
             block_hash: HashBytes::from(block),
             state_root: HashBytes::from(state),
+            l1_tx_hash: HashBytes::from(l1_tx_hash),
+            proof_type: ProofType::Unknown as u8,
         })
     }
 }
@@ -83,21 +92,49 @@ impl TryFrom<&ITaikoWrapper::ForcedInclusionProcessed> for ForcedInclusionProces
     }
 }
 
-// Conversion from (BatchesVerified, u64) to VerifiedBatchRow
-impl TryFrom<(&chainio::BatchesVerified, u64)> for VerifiedBatchRow {
+// Conversion from (BatchesVerified, u64, B256) to VerifiedBatchRow
+impl TryFrom<(&chainio::BatchesVerified, u64, B256)> for VerifiedBatchRow {
     type Error = Error;
 
-    fn try_from(input: (&chainio::BatchesVerified, u64)) -> Result<Self, Self::Error> {
-        let (verified, l1_block_number) = input;
+    fn try_from(input: (&chainio::BatchesVerified, u64, B256)) -> Result<Self, Self::Error> {
+        let (verified, l1_block_number, l1_tx_hash) = input;
 
         Ok(Self {
             l1_block_number,
             batch_id: verified.batch_id,
             block_hash: HashBytes::from(verified.block_hash),
+            l1_tx_hash: HashBytes::from(l1_tx_hash),
         })
     }
 }
 
+// Conversion from (inbox address, ProtocolConfig) to ProtocolConfigRow
+impl From<(Address, &ITaikoInbox::ProtocolConfig)> for ProtocolConfigRow {
+    fn from(input: (Address, &ITaikoInbox::ProtocolConfig)) -> Self {
+        let (inbox_address, config) = input;
+
+        Self {
+            inbox_address: AddressBytes::from(inbox_address),
+            chain_id: config.chainId,
+            max_unverified_batches: config.maxUnverifiedBatches,
+            batch_ring_buffer_size: config.batchRingBufferSize,
+            max_batches_to_verify: config.maxBatchesToVerify,
+            block_max_gas_limit: config.blockMaxGasLimit,
+            liveness_bond_base: config.livenessBondBase.to(),
+            liveness_bond_per_block: config.livenessBondPerBlock.to(),
+            state_root_sync_internal: config.stateRootSyncInternal,
+            max_anchor_height_offset: config.maxAnchorHeightOffset,
+            base_fee_sharing_pctg: config.baseFeeConfig.sharingPctg,
+            proving_window: config.provingWindow,
+            cooldown_window: config.cooldownWindow.to(),
+            max_signals_to_receive: config.maxSignalsToReceive,
+            max_blocks_per_batch: config.maxBlocksPerBatch,
+            ontake_fork_height: config.forkHeights.ontake,
+            pacaya_fork_height: config.forkHeights.pacaya,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,10 +172,30 @@ mod tests {
                 proposer_addr: AddressBytes::from(Address::repeat_byte(9)),
                 blob_count: 1,
                 blob_total_bytes: 100,
+                da_mode: 0,
+                calldata_bytes: 0,
+                fork: String::new(),
             }
         );
     }
 
+    #[test]
+    fn batch_proposed_with_tx_list_uses_calldata() {
+        let batch = ITaikoInbox::BatchProposed {
+            info: ITaikoInbox::BatchInfo {
+                blocks: vec![ITaikoInbox::BlockParams::default(); 1],
+                ..Default::default()
+            },
+            txList: vec![0u8; 300].into(),
+            ..Default::default()
+        };
+
+        let row = BatchRow::try_from((&batch, B256::ZERO)).unwrap();
+        assert_eq!(row.blob_count, 0);
+        assert_eq!(row.da_mode, DaMode::Calldata as u8);
+        assert_eq!(row.calldata_bytes, 300);
+    }
+
     #[test]
     fn batches_proved_into_row() {
         let transition = ITaikoInbox::Transition {
@@ -153,7 +210,7 @@ mod tests {
             transitions: vec![transition],
         };
 
-        let row = ProvedBatchRow::try_from((&proved, 11)).unwrap();
+        let row = ProvedBatchRow::try_from((&proved, 11, B256::repeat_byte(8))).unwrap();
         assert_eq!(
             row,
             ProvedBatchRow {
@@ -163,6 +220,8 @@ mod tests {
                 parent_hash: HashBytes::from([1u8; 32]),
                 block_hash: HashBytes::from([2u8; 32]),
                 state_root: HashBytes::from([3u8; 32]),
+                l1_tx_hash: HashBytes::from([8u8; 32]),
+                proof_type: 0,
             }
         );
     }
@@ -188,14 +247,38 @@ mod tests {
     fn batches_verified_into_row() {
         let verified = chainio::BatchesVerified { batch_id: 9, block_hash: [6u8; 32] };
 
-        let row = VerifiedBatchRow::try_from((&verified, 15)).unwrap();
+        let row = VerifiedBatchRow::try_from((&verified, 15, B256::repeat_byte(8))).unwrap();
         assert_eq!(
             row,
             VerifiedBatchRow {
                 l1_block_number: 15,
                 batch_id: 9,
-                block_hash: HashBytes::from([6u8; 32])
+                block_hash: HashBytes::from([6u8; 32]),
+                l1_tx_hash: HashBytes::from([8u8; 32]),
             }
         );
     }
+
+    #[test]
+    fn protocol_config_into_row() {
+        let config = ITaikoInbox::ProtocolConfig {
+            chainId: 167000,
+            livenessBondBase: alloy::primitives::Uint::from(125_000_000_000_000_000_000u128),
+            provingWindow: 7200,
+            cooldownWindow: alloy::primitives::Uint::from(14_400u32),
+            maxBlocksPerBatch: 768,
+            forkHeights: ITaikoInbox::ForkHeights { ontake: 1, pacaya: 2 },
+            ..Default::default()
+        };
+
+        let row = ProtocolConfigRow::from((Address::repeat_byte(3), &config));
+        assert_eq!(row.inbox_address, AddressBytes::from(Address::repeat_byte(3)));
+        assert_eq!(row.chain_id, 167000);
+        assert_eq!(row.liveness_bond_base, 125_000_000_000_000_000_000);
+        assert_eq!(row.proving_window, 7200);
+        assert_eq!(row.cooldown_window, 14_400);
+        assert_eq!(row.max_blocks_per_batch, 768);
+        assert_eq!(row.ontake_fork_height, 1);
+        assert_eq!(row.pacaya_fork_height, 2);
+    }
 }
@@ -27,14 +27,20 @@ pub mod types;
 pub mod writer;
 
 // Re-export main types for convenience
-pub use reader::{ClickhouseReader, TimeRange};
-pub use writer::ClickhouseWriter;
+pub use reader::{
+    ClickhouseReader, QueryError, ReplicaStats, RowStream, TimeRange, current_request_id,
+    with_request_id,
+};
+pub use writer::{
+    ClickhouseWriter, DualWriteMismatch, DualWriteStats, DuplicateReport, SnapshotFormat,
+    SnapshotLocation, SnapshotTableRow, SpillConfig, SpillStats, WriteBufferConfig,
+};
 
 // Re-export all models for backward compatibility and ease of use
 pub use models::*;
 
 // Re-export schema constants
-pub use schema::{TABLE_SCHEMAS, TABLES, VIEWS};
+pub use schema::{DEDUPLICATED_TABLES, TABLE_SCHEMAS, TABLES, VIEWS};
 
 // Re-export byte wrappers
 pub use types::{AddressBytes, HashBytes};
@@ -33,7 +33,7 @@ pub struct PreconfData {
 }
 
 /// L2 head event
-#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
 pub struct L2HeadEvent {
     /// L2 block number
     pub l2_block_number: u64,
@@ -43,14 +43,21 @@ pub struct L2HeadEvent {
     pub block_ts: u64,
     /// Sum of gas used in the block
     pub sum_gas_used: u128,
+    /// Gas limit of the block, 0 for blocks ingested before it was recorded
+    pub gas_limit: u64,
     /// Number of transactions
     pub sum_tx: u32,
     /// Sum of priority fees paid
     pub sum_priority_fee: u128,
-    /// Sum of base fees paid
+    /// Sum of base fees burned
     pub sum_base_fee: u128,
+    /// Gas weighted average effective gas price, 0 for blocks ingested before it was recorded
+    pub avg_gas_price: u128,
     /// Sequencer sequencing the block
     pub sequencer: AddressBytes,
+    /// Extra data of the block, hex encoded with a `0x` prefix; empty for blocks ingested before
+    /// it was recorded
+    pub extra_data: String,
 }
 
 /// Batch row
@@ -72,6 +79,12 @@ pub struct BatchRow {
     pub blob_count: u8,
     /// Blob total bytes
     pub blob_total_bytes: u32,
+    /// Data availability mode code, see [`primitives::da::DaMode`]
+    pub da_mode: u8,
+    /// Size of the `txList` calldata in bytes, 0 for blob batches
+    pub calldata_bytes: u32,
+    /// Name of the fork whose inbox emitted the batch
+    pub fork: String,
 }
 
 impl BatchRow {
@@ -113,6 +126,23 @@ pub struct BatchBlockRow {
     pub l2_block_number: u64,
 }
 
+/// L2 block of a batch with its gas and transaction count
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchL2BlockRow {
+    /// L2 block number
+    pub l2_block_number: u64,
+    /// L2 block timestamp in seconds
+    pub block_ts: u64,
+    /// Gas used by the block
+    pub sum_gas_used: u128,
+    /// Gas limit of the block, 0 when it was not recorded
+    pub gas_limit: u64,
+    /// Number of transactions in the block
+    pub sum_tx: u32,
+    /// Sequencer that produced the block
+    pub sequencer: AddressBytes,
+}
+
 /// Proved batch row
 #[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ProvedBatchRow {
@@ -128,6 +158,10 @@ pub struct ProvedBatchRow {
     pub block_hash: HashBytes,
     /// State root
     pub state_root: HashBytes,
+    /// Transaction hash that proved the batch
+    pub l1_tx_hash: HashBytes,
+    /// Proof type code, see [`primitives::proof::ProofType`]
+    pub proof_type: u8,
 }
 
 /// L2 reorg row for insertion (without `inserted_at`)
@@ -141,6 +175,8 @@ pub struct L2ReorgInsertRow {
     pub old_sequencer: AddressBytes,
     /// Sequencer that produced the new block
     pub new_sequencer: AddressBytes,
+    /// Code of the [`primitives::reorg::ReorgCause`] the reorg coincided with
+    pub probable_cause: u8,
 }
 
 /// L2 reorg row
@@ -154,6 +190,8 @@ pub struct L2ReorgRow {
     pub old_sequencer: AddressBytes,
     /// Sequencer that produced the new block
     pub new_sequencer: AddressBytes,
+    /// Code of the [`primitives::reorg::ReorgCause`] the reorg coincided with
+    pub probable_cause: u8,
     /// Time the reorg was recorded.
     /// This is populated when reading from the database.
     pub inserted_at: DateTime<Utc>,
@@ -175,6 +213,125 @@ pub struct OrphanedL2HashRow {
     pub l2_block_number: u64,
 }
 
+/// Content of an L2 block orphaned by a reorg
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OrphanedBlockDetailsRow {
+    /// Block hash of the orphaned block
+    pub block_hash: HashBytes,
+    /// L2 block number of the orphaned block
+    pub l2_block_number: u64,
+    /// Hash of the parent block
+    pub parent_hash: HashBytes,
+    /// Block timestamp in seconds
+    pub block_ts: u64,
+    /// Beneficiary of the block
+    pub sequencer: AddressBytes,
+    /// Gas used by the block
+    pub gas_used: u64,
+    /// Number of transactions in the block
+    pub tx_count: u32,
+    /// Hashes of the transactions in the block
+    pub tx_hashes: Vec<HashBytes>,
+    /// Whether the transactions were checked for re-inclusion in the canonical chain
+    pub reinclusion_checked: u8,
+    /// Number of transactions included in a canonical block after the reorg
+    pub reincluded_txs: u32,
+}
+
+/// Sample of a core metric that deviated sharply from its moving average
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq)]
+pub struct AnomalyRow {
+    /// Unix timestamp of the sample in seconds
+    pub detected_ts: u64,
+    /// Code of the [`primitives::anomaly::AnomalyMetric`] the sample belongs to
+    pub metric: u8,
+    /// Sampled value
+    pub value: f64,
+    /// Moving average of the previous samples
+    pub expected: f64,
+    /// Moving standard deviation of the previous samples
+    pub std_dev: f64,
+    /// Number of standard deviations between the sample and the moving average
+    pub z_score: f64,
+}
+
+/// Snapshot of the `ForcedInclusionStore` queue
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForcedInclusionQueueRow {
+    /// Unix timestamp of the snapshot in seconds
+    pub snapshot_ts: u64,
+    /// L1 block the queue was read at
+    pub l1_block_number: u64,
+    /// Number of queued forced inclusions
+    pub pending: u64,
+    /// Timestamp of the L1 block the oldest queued forced inclusion was stored in
+    pub oldest_created_ts: Option<u64>,
+    /// Seconds the oldest queued forced inclusion has been waiting at the snapshot
+    pub oldest_age_secs: Option<u64>,
+    /// Batch id by which the oldest queued forced inclusion must be included
+    pub deadline_batch_id: Option<u64>,
+    /// Id of the next batch to be proposed
+    pub next_batch_id: u64,
+    /// Whether the oldest queued forced inclusion is within the configured margin of its
+    /// deadline (1) or not (0)
+    pub near_deadline: u8,
+}
+
+/// Snapshot of how many batches the latest verified batch lags behind the latest proposed batch
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VerificationBacklogRow {
+    /// Unix timestamp of the snapshot in seconds
+    pub snapshot_ts: u64,
+    /// L1 block the batch ids were read at
+    pub l1_block_number: u64,
+    /// Id of the latest proposed batch
+    pub last_proposed_batch_id: u64,
+    /// Id of the latest verified batch
+    pub last_verified_batch_id: u64,
+    /// Number of proposed batches not verified yet
+    pub backlog: u64,
+    /// Whether the backlog exceeded the configured threshold (1) or not (0)
+    pub over_threshold: u8,
+}
+
+/// Incident operation performed by a monitor against Instatus
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IncidentLogRow {
+    /// Unix timestamp of the operation in milliseconds
+    pub logged_at_ms: u64,
+    /// Code of the [`primitives::incident::IncidentAction`] performed
+    pub action: u8,
+    /// Instatus incident ID, empty when the creation failed
+    pub incident_id: String,
+    /// Components affected by the incident
+    pub component_ids: Vec<String>,
+    /// Incident name, empty for updates and resolutions
+    pub incident_name: String,
+    /// Incident status sent to Instatus
+    pub status: String,
+    /// JSON request payload
+    pub payload: String,
+    /// HTTP status code returned by Instatus, 0 when no response was received
+    pub response_code: u16,
+    /// Error of a failed operation
+    pub error: Option<String>,
+    /// Whether reporting was disabled and the operation only logged (1) or not (0)
+    pub dry_run: u8,
+}
+
+/// Human readable label of an address
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AddressLabelRow {
+    /// Labelled address
+    pub address: AddressBytes,
+    /// Label of the address
+    pub label: String,
+    /// Code of the [`primitives::label::LabelSource`] of the label
+    pub source: u8,
+    /// Unix timestamp in seconds the label was last refreshed
+    pub updated_ts: u64,
+}
+
 /// Verified batch row
 #[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
 pub struct VerifiedBatchRow {
@@ -184,6 +341,8 @@ pub struct VerifiedBatchRow {
     pub batch_id: u64,
     /// Block hash
     pub block_hash: HashBytes,
+    /// Transaction hash that verified the batch
+    pub l1_tx_hash: HashBytes,
 }
 
 /// Slashing event row
@@ -193,6 +352,8 @@ pub struct SlashingEventRow {
     pub l1_block_number: u64,
     /// Address of the validator that was slashed
     pub validator_addr: AddressBytes,
+    /// Reason code: 0 = unknown, 1 = removed, 2 = ejected
+    pub reason: u8,
 }
 
 /// Row representing a failed proposal where a batch was posted by a different sequencer
@@ -227,6 +388,15 @@ pub struct SequencerDistributionRow {
     pub tx_sum: u64,
 }
 
+/// Number of L2 blocks sharing the same extra data
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExtraDataCountRow {
+    /// Extra data of the blocks, hex encoded with a `0x` prefix
+    pub extra_data: String,
+    /// Number of blocks
+    pub blocks: u64,
+}
+
 /// Row representing a single block proposed by a sequencer
 #[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SequencerBlockRow {
@@ -276,6 +446,20 @@ pub struct BatchVerifyTimeRow {
     pub seconds_to_verify: u64,
 }
 
+/// Row representing the L1 transactions that proposed, proved and verified a batch. Hashes of
+/// missing or unrecorded transactions are zero.
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchTxHashesRow {
+    /// Batch ID
+    pub batch_id: u64,
+    /// Transaction hash that proposed the batch
+    pub proposal_l1_tx_hash: HashBytes,
+    /// Transaction hash that first proved the batch
+    pub proof_l1_tx_hash: HashBytes,
+    /// Transaction hash that verified the batch
+    pub verification_l1_tx_hash: HashBytes,
+}
+
 /// Row representing the block number seen at a given minute
 #[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub struct L1BlockTimeRow {
@@ -314,6 +498,11 @@ pub struct L1DataCostRow {
     pub l1_block_number: u64,
     /// Total cost in gwei for data posting transactions
     pub cost: u128,
+    /// Portion of the cost paid for blob gas
+    pub blob_cost: u128,
+    /// Portion of the cost paid for the calldata gas of the transaction input; the rest of the
+    /// non-blob cost is intrinsic and execution gas
+    pub calldata_cost: u128,
 }
 
 /// Row used for inserting L1 data cost for a batch
@@ -325,6 +514,11 @@ pub struct L1DataCostInsertRow {
     pub batch_id: u64,
     /// Total cost in gwei for data posting transactions
     pub cost: u128,
+    /// Portion of the cost paid for blob gas
+    pub blob_cost: u128,
+    /// Portion of the cost paid for the calldata gas of the transaction input; the rest of the
+    /// non-blob cost is intrinsic and execution gas
+    pub calldata_cost: u128,
 }
 
 /// Row representing the prover cost for a batch
@@ -371,6 +565,852 @@ pub struct VerifyCostInsertRow {
     pub cost: u128,
 }
 
+/// Row used for inserting the L1 blob fee market state of a batch proposal
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchBlobFeeInsertRow {
+    /// L1 block number the batch was proposed in
+    pub l1_block_number: u64,
+    /// Batch ID
+    pub batch_id: u64,
+    /// Excess blob gas of the L1 block
+    pub excess_blob_gas: u64,
+    /// Blob base fee of the L1 block in wei
+    pub blob_base_fee: u128,
+}
+
+/// Row representing the L1 blob fee market state when a batch was proposed
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct BlobFeeHistoryRow {
+    /// Batch ID
+    pub batch_id: u64,
+    /// L1 block number the batch was proposed in
+    pub l1_block_number: u64,
+    /// Timestamp of the L1 block
+    pub block_ts: u64,
+    /// Excess blob gas of the L1 block
+    pub excess_blob_gas: u64,
+    /// Blob base fee of the L1 block in wei
+    pub blob_base_fee: u128,
+}
+
+/// Row used for inserting the anchor block id of an L2 block
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct L2AnchorBlockInsertRow {
+    /// L2 block number
+    pub l2_block_number: u64,
+    /// Timestamp of the L2 block
+    pub block_ts: u64,
+    /// L1 block id referenced by the anchor transaction
+    pub anchor_block_id: u64,
+    /// Latest L1 block number known when the L2 block was ingested
+    pub l1_head: u64,
+}
+
+/// Distribution of the L1 -> L2 anchor lag within a time bucket
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct AnchorLagRow {
+    /// Start of the bucket as a UNIX timestamp in seconds
+    pub bucket_ts: u64,
+    /// Number of L2 blocks in the bucket
+    pub blocks: u64,
+    /// Average lag in L1 blocks
+    pub avg_lag: f64,
+    /// Median lag in L1 blocks
+    pub p50_lag: u64,
+    /// 95th percentile lag in L1 blocks
+    pub p95_lag: u64,
+    /// Maximum lag in L1 blocks
+    pub max_lag: u64,
+}
+
+/// Distribution of L2 block gas saturation (gas used over gas limit) within a time bucket
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct GasSaturationRow {
+    /// Start of the bucket as a UNIX timestamp in seconds
+    pub bucket_ts: u64,
+    /// Number of L2 blocks in the bucket
+    pub blocks: u64,
+    /// Average saturation, between 0 and 1
+    pub avg_saturation: f64,
+    /// 95th percentile saturation
+    pub p95_saturation: f64,
+    /// Maximum saturation
+    pub max_saturation: f64,
+}
+
+/// Average L2 block gas saturation over a range
+#[derive(Debug, Default, Clone, Copy, Row, Serialize, Deserialize, PartialEq)]
+pub struct GasSaturationSummaryRow {
+    /// Number of L2 blocks with a known gas limit in the range
+    pub blocks: u64,
+    /// Average saturation, between 0 and 1
+    pub avg_saturation: f64,
+}
+
+/// Row used for inserting the result of a public RPC probe
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RpcHealthInsertRow {
+    /// Probed endpoint
+    pub url: String,
+    /// Whether the endpoint was considered healthy
+    pub healthy: u8,
+    /// Latency of the probe in milliseconds, if it succeeded
+    pub latency_ms: Option<u64>,
+    /// Latest block number reported by the endpoint
+    pub block_number: Option<u64>,
+    /// Number of blocks the endpoint is behind the reference L2 head
+    pub block_lag: Option<u64>,
+    /// Error message if the probe failed
+    pub error: Option<String>,
+}
+
+/// Row used for inserting an ETH/USD price snapshot
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq)]
+pub struct EthPriceInsertRow {
+    /// Unix timestamp of the snapshot in seconds
+    pub ts: u64,
+    /// Price of one ETH in USD
+    pub usd: f64,
+}
+
+/// Row used for inserting a TAIKO price snapshot
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq)]
+pub struct TaikoPriceInsertRow {
+    /// Unix timestamp of the snapshot in seconds
+    pub ts: u64,
+    /// Price of one TAIKO in ETH
+    pub eth: f64,
+    /// Price of one TAIKO in USD
+    pub usd: f64,
+}
+
+/// Row used for inserting an inbox bond balance change
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BondLedgerInsertRow {
+    /// Account whose bond balance changed
+    pub address: AddressBytes,
+    /// L1 block the event was emitted in
+    pub l1_block_number: u64,
+    /// Transaction that emitted the event
+    pub tx_hash: HashBytes,
+    /// Index of the event log within the block
+    pub log_index: u64,
+    /// Kind of change: `deposited`, `withdrawn`, `debited` or `credited`
+    pub kind: String,
+    /// Amount added to or removed from the balance
+    pub amount: u128,
+    /// Bond balance of the account at the end of the L1 block
+    pub balance: u128,
+}
+
+/// Latest bond balance of an account
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BondBalanceRow {
+    /// Account address
+    pub address: AddressBytes,
+    /// Latest known bond balance
+    pub balance: u128,
+    /// L1 block the balance was last updated in
+    pub l1_block_number: u64,
+    /// Total liveness bonds taken from the account
+    pub total_debited: u128,
+    /// Total liveness bonds returned to the account
+    pub total_credited: u128,
+}
+
+/// Transactions and operating costs of the L2 blocks of a sequencer in a time bucket
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequencerCostBucketRow {
+    /// Unix timestamp in seconds of the start of the bucket
+    pub bucket_ts: u64,
+    /// Sequencer that produced the blocks
+    pub sequencer: AddressBytes,
+    /// Number of transactions in the blocks
+    pub tx_count: u64,
+    /// L1 data posting cost of the blocks, the cost of each batch split evenly over its blocks
+    pub l1_data_cost: u128,
+    /// Proving cost of the blocks, the cost of each batch split evenly over its blocks
+    pub prove_cost: u128,
+}
+
+/// L1 wallet balances of a whitelisted operator in a snapshot round
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OperatorBalanceRow {
+    /// Unix timestamp of the snapshot round in seconds
+    pub snapshot_ts: u64,
+    /// Operator address
+    pub address: AddressBytes,
+    /// L1 block the balances were read at
+    pub l1_block_number: u64,
+    /// ETH balance in wei
+    pub eth_balance: u128,
+    /// TAIKO token balance, if a token address is configured
+    pub taiko_balance: Option<u128>,
+    /// Whether a balance is below its configured threshold (1) or not (0)
+    pub low_balance: u8,
+}
+
+/// L1 block timestamp and the preconfirmation operator scheduled at its slot
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OperatorScheduleRow {
+    /// L1 block timestamp in seconds
+    pub block_ts: u64,
+    /// Operator scheduled for the epoch of the slot
+    pub operator: AddressBytes,
+}
+
+/// Preconfirmation operator of an L1 epoch and the L1 blocks seen within it
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OperatorEpochRow {
+    /// L1 epoch number
+    pub epoch: u64,
+    /// Operator scheduled at the first slot of the epoch with preconfirmation data
+    pub operator: AddressBytes,
+    /// First L1 block seen within the epoch
+    pub first_l1_block: u64,
+    /// Last L1 block seen within the epoch
+    pub last_l1_block: u64,
+}
+
+/// L2 block timestamp and the sequencer that produced the block
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct L2BlockProducerRow {
+    /// L2 block number
+    pub l2_block_number: u64,
+    /// L2 block timestamp in seconds
+    pub block_ts: u64,
+    /// Sequencer that produced the block
+    pub sequencer: AddressBytes,
+}
+
+/// Continuous span in which the scheduled sequencer produced no blocks. Spans are split at
+/// hour boundaries
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequencerDowntimeRow {
+    /// Scheduled sequencer
+    pub sequencer: AddressBytes,
+    /// Start of the span in seconds, the last block or the start of the term
+    pub start_ts: u64,
+    /// End of the span in seconds, the next block or the end of the term
+    pub end_ts: u64,
+    /// Version of the computation that derived the span
+    pub computation_version: u16,
+}
+
+/// Change of the L2 block sequencer, from the last block of the outgoing sequencer to the first
+/// block of the incoming one
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequencerHandoverRow {
+    /// Sequencer handing over
+    pub outgoing: AddressBytes,
+    /// Sequencer taking over
+    pub incoming: AddressBytes,
+    /// Last block of the outgoing sequencer
+    pub last_block_number: u64,
+    /// Timestamp of the last block of the outgoing sequencer in seconds
+    pub last_block_ts: u64,
+    /// First block of the incoming sequencer
+    pub first_block_number: u64,
+    /// Timestamp of the first block of the incoming sequencer in seconds
+    pub first_block_ts: u64,
+    /// Seconds between the two blocks
+    pub latency_secs: u64,
+}
+
+/// First and last indexed L1 and L2 blocks of a reconciliation period
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReconciliationBlocksRow {
+    /// Last L1 block before the period
+    pub l1_from: u64,
+    /// Last L1 block of the period
+    pub l1_to: u64,
+    /// Last L2 block before the period
+    pub l2_from: u64,
+    /// Last L2 block of the period
+    pub l2_to: u64,
+}
+
+/// Fees attributed to a sequencer over a block range on each chain
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequencerFeeTotalsRow {
+    /// Sequencer address, the L2 block beneficiary and the L1 batch proposer
+    pub sequencer: AddressBytes,
+    /// Priority fees of the L2 blocks the sequencer produced
+    pub priority_fee: u128,
+    /// Base fees of the L2 blocks the sequencer produced
+    pub base_fee: u128,
+    /// L1 cost of the batches the sequencer proposed
+    pub l1_data_cost: u128,
+}
+
+/// Comparison of the balance change implied by the attributed fees of a sequencer with the
+/// change of its on-chain balance over a period
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeeReconciliationRow {
+    /// Start of the period in seconds
+    pub period_start: u64,
+    /// End of the period in seconds
+    pub period_end: u64,
+    /// Sequencer address
+    pub sequencer: AddressBytes,
+    /// Chain of the balance, `l1` or `l2`
+    pub chain: String,
+    /// Block the balance is read at before the period
+    pub from_block: u64,
+    /// Block the balance is read at after the period
+    pub to_block: u64,
+    /// Balance change in wei implied by the attributed revenues or costs
+    pub expected_delta: i128,
+    /// Balance change in wei between the two blocks
+    pub actual_delta: i128,
+    /// `actual_delta - expected_delta`
+    pub discrepancy: i128,
+    /// Whether the discrepancy exceeds the tolerance
+    pub flagged: u8,
+}
+
+/// Seconds a sequencer was the scheduled operator within an hour
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequencerScheduleRow {
+    /// Scheduled sequencer
+    pub sequencer: AddressBytes,
+    /// Start of the hour in seconds
+    pub hour_ts: u64,
+    /// Seconds the sequencer was scheduled within the hour
+    pub scheduled_secs: u64,
+    /// Version of the computation that derived the row
+    pub computation_version: u16,
+}
+
+/// Scheduled time and downtime of a sequencer within a day
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequencerUptimeRow {
+    /// Start of the day as a UNIX timestamp in seconds
+    pub day_ts: u64,
+    /// Scheduled sequencer
+    pub sequencer: AddressBytes,
+    /// Seconds the sequencer was scheduled during the day
+    pub scheduled_secs: u64,
+    /// Seconds of downtime during the day
+    pub downtime_secs: u64,
+    /// Number of downtime spans during the day
+    pub downtime_intervals: u64,
+}
+
+/// Soft block advertised by the sequencer that differs from the canonical L2 block at its height
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PreconfMismatchRow {
+    /// L2 block number
+    pub l2_block_number: u64,
+    /// Hash of the soft block, if the feed provided one
+    pub soft_block_hash: Option<HashBytes>,
+    /// Hash of the canonical block, or `None` if it did not appear in time
+    pub canonical_block_hash: Option<HashBytes>,
+    /// Number of transactions in the soft block
+    pub soft_tx_count: u32,
+    /// Number of transactions in the canonical block
+    pub canonical_tx_count: u32,
+    /// Preconfirmed transactions missing from the canonical block
+    pub missing_tx_hashes: Vec<HashBytes>,
+    /// Canonical transactions that were not preconfirmed
+    pub extra_tx_hashes: Vec<HashBytes>,
+    /// Whether the transactions in both blocks were included in a different order (1) or not (0)
+    pub reordered: u8,
+}
+
+/// Batch whose anchor block hash differs from the indexed L1 block at its anchor block id
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnchorMismatchRow {
+    /// Batch ID
+    pub batch_id: u64,
+    /// L1 block number the batch was proposed in
+    pub l1_block_number: u64,
+    /// L1 block number the batch is anchored to
+    pub anchor_block_id: u64,
+    /// Anchor block hash committed to by the batch
+    pub anchor_block_hash: HashBytes,
+    /// Hash of the indexed L1 block at `anchor_block_id`
+    pub l1_block_hash: HashBytes,
+}
+
+/// Change to the whitelist operator candidates of the current epoch
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OperatorHistoryRow {
+    /// Time the change was observed in seconds
+    pub observed_ts: u64,
+    /// Latest L1 block when the change was observed
+    pub l1_block_number: u64,
+    /// Candidates after the change
+    pub candidates: Vec<AddressBytes>,
+    /// Candidates that joined since the previous snapshot
+    pub added: Vec<AddressBytes>,
+    /// Candidates that left since the previous snapshot
+    pub removed: Vec<AddressBytes>,
+}
+
+impl OperatorHistoryRow {
+    /// Whether any candidate joined or left
+    pub const fn is_churn(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty()
+    }
+}
+
+/// L2 mempool activity within a minute
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MempoolStatsRow {
+    /// Start of the minute in seconds
+    pub minute_ts: u64,
+    /// Transactions first seen in the mempool during the minute
+    pub seen_txs: u32,
+    /// Seen transactions included in a block during the minute
+    pub included_txs: u32,
+    /// Largest number of pending transactions during the minute
+    pub max_depth: u32,
+    /// Pending transactions at the end of the minute
+    pub end_depth: u32,
+    /// Average time from first seen to inclusion in milliseconds
+    pub avg_inclusion_ms: u64,
+    /// Median time from first seen to inclusion in milliseconds
+    pub p50_inclusion_ms: u64,
+    /// 95th percentile time from first seen to inclusion in milliseconds
+    pub p95_inclusion_ms: u64,
+    /// Longest time from first seen to inclusion in milliseconds
+    pub max_inclusion_ms: u64,
+}
+
+/// Batch proposal transaction of a proposer nonce, timed from the L1 mempool to inclusion
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProposalInclusionRow {
+    /// Sender of the proposal
+    pub proposer: AddressBytes,
+    /// Nonce of the proposal transaction
+    pub nonce: u64,
+    /// Hash of the latest pending or the included transaction
+    pub tx_hash: HashBytes,
+    /// Time in milliseconds the first transaction of the nonce was seen in the mempool
+    pub first_seen_ms: u64,
+    /// Maximum fee per gas of the latest transaction
+    pub max_fee_per_gas: u128,
+    /// Maximum priority fee per gas of the latest transaction, `None` for legacy transactions
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// Times the pending transaction was replaced
+    pub replacements: u32,
+    /// Batch proposed by the transaction once included
+    pub batch_id: Option<u64>,
+    /// L1 block the transaction was included in
+    pub l1_block_number: Option<u64>,
+    /// Time in milliseconds the `BatchProposed` event was received
+    pub included_at_ms: Option<u64>,
+    /// Time in milliseconds from first seen to inclusion
+    pub inclusion_delay_ms: Option<u64>,
+    /// Whether the nonce was used without proposing a batch, e.g. by a reverted or cancelled
+    /// transaction (1) or not (0)
+    pub dropped: u8,
+}
+
+/// Entry of the L1 transaction index, linking a protocol transaction to a role it played
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct L1TxIndexRow {
+    /// Hash of the L1 transaction
+    pub tx_hash: HashBytes,
+    /// Code of the [`primitives::tx_role::L1TxRole`] the transaction played
+    pub role: u8,
+    /// Batch the role relates to, 0 for forced inclusions
+    pub batch_id: u64,
+    /// L1 block the transaction was included in
+    pub l1_block_number: u64,
+}
+
+/// Indexed role of an L1 transaction together with the time it happened and was indexed
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct L1TxLookupRow {
+    /// Code of the [`primitives::tx_role::L1TxRole`] the transaction played
+    pub role: u8,
+    /// Batch the role relates to, 0 for forced inclusions
+    pub batch_id: u64,
+    /// L1 block the transaction was included in
+    pub l1_block_number: u64,
+    /// Timestamp of the L1 block in seconds, 0 when the block is not stored
+    pub block_ts: u64,
+    /// Time in milliseconds the entry was indexed
+    pub indexed_at_ms: u64,
+}
+
+/// Row used for inserting the L1 gas spent by a protocol transaction
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProtocolGasSpendInsertRow {
+    /// L1 block the transaction was included in
+    pub l1_block_number: u64,
+    /// Transaction hash
+    pub tx_hash: HashBytes,
+    /// Sender of the transaction
+    pub address: AddressBytes,
+    /// Protocol operation performed: `propose`, `prove` or `verify`
+    pub operation: String,
+    /// Gas used by the transaction
+    pub gas_used: u64,
+    /// Fee paid in wei, including blob fees
+    pub cost: u128,
+}
+
+/// L1 gas spent by an address on a protocol operation over a time range
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProtocolGasSpendRow {
+    /// Protocol operation: `propose`, `prove` or `verify`
+    pub operation: String,
+    /// Sender of the transactions
+    pub address: AddressBytes,
+    /// Number of transactions
+    pub txs: u64,
+    /// Total gas used
+    pub gas_used: u64,
+    /// Total fees paid in wei, including blob fees
+    pub cost: u128,
+}
+
+/// Receive time of a live header next to its chain timestamp
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct HeaderPropagationInsertRow {
+    /// Chain of the header: `l1` or `l2`
+    pub chain: String,
+    /// Block number
+    pub block_number: u64,
+    /// Block timestamp in seconds
+    pub block_ts: u64,
+    /// Milliseconds since the Unix epoch at which the header was received
+    pub observed_at: u64,
+    /// Milliseconds from the block timestamp until the header was received; negative when the
+    /// header arrived before its own timestamp
+    pub delay_ms: i64,
+}
+
+/// Propagation delay distribution of a chain's headers over a time range
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq)]
+pub struct HeaderPropagationRow {
+    /// Chain of the headers: `l1` or `l2`
+    pub chain: String,
+    /// Number of headers
+    pub blocks: u64,
+    /// Headers received before their own timestamp
+    pub early_blocks: u64,
+    /// Smallest delay in milliseconds
+    pub min_ms: i64,
+    /// Average delay in milliseconds
+    pub avg_ms: f64,
+    /// Median delay in milliseconds
+    pub p50_ms: f64,
+    /// 90th percentile delay in milliseconds
+    pub p90_ms: f64,
+    /// 99th percentile delay in milliseconds
+    pub p99_ms: f64,
+    /// Largest delay in milliseconds
+    pub max_ms: i64,
+}
+
+/// Progress snapshot of a gap backfill run
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackfillProgressInsertRow {
+    /// Chain being backfilled: `l1` or `l2`
+    pub chain: String,
+    /// Blocks scheduled in the run
+    pub scheduled_blocks: u64,
+    /// Blocks written so far
+    pub filled_blocks: u64,
+    /// Blocks that could not be fetched
+    pub failed_blocks: u64,
+    /// Scheduled block closest to the head, filled first
+    pub newest_block: Option<u64>,
+    /// Scheduled block furthest from the head, filled last
+    pub oldest_block: Option<u64>,
+    /// Block processed most recently
+    pub current_block: Option<u64>,
+    /// RPC request budget of the backfill, 0 when unlimited
+    pub max_requests_per_sec: u32,
+}
+
+/// Latest backfill progress snapshot of a chain
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BackfillStatusRow {
+    /// Chain being backfilled: `l1` or `l2`
+    pub chain: String,
+    /// Blocks scheduled in the run
+    pub scheduled_blocks: u64,
+    /// Blocks written so far
+    pub filled_blocks: u64,
+    /// Blocks that could not be fetched
+    pub failed_blocks: u64,
+    /// Scheduled block closest to the head
+    pub newest_block: Option<u64>,
+    /// Scheduled block furthest from the head
+    pub oldest_block: Option<u64>,
+    /// Block processed most recently
+    pub current_block: Option<u64>,
+    /// RPC request budget of the backfill, 0 when unlimited
+    pub max_requests_per_sec: u32,
+    /// UNIX timestamp in seconds of the snapshot
+    pub updated_at: u64,
+}
+
+/// Row used for renewing or releasing a driver lease
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DriverLeaseInsertRow {
+    /// Name of the lease, e.g. `ingest`
+    pub lease: String,
+    /// Instance holding the lease
+    pub holder: String,
+    /// UNIX timestamp in milliseconds at which the holder started contending for the lease
+    pub started_at: u64,
+    /// 1 if the holder gave up the lease
+    pub released: u8,
+}
+
+/// Live holder of a driver lease
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeaseHolderRow {
+    /// Instance holding the lease
+    pub holder: String,
+    /// UNIX timestamp in milliseconds at which the holder started contending for the lease
+    pub started_at: u64,
+}
+
+/// Row used for inserting a leadership change
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeaderChangeInsertRow {
+    /// Name of the lease
+    pub lease: String,
+    /// Instance that became leader
+    pub holder: String,
+    /// Leader before the change, if one was known
+    pub previous_holder: Option<String>,
+}
+
+/// Leadership change of a driver lease
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LeaderChangeRow {
+    /// Name of the lease
+    pub lease: String,
+    /// Instance that became leader
+    pub holder: String,
+    /// Leader before the change, if one was known
+    pub previous_holder: Option<String>,
+    /// UNIX timestamp in seconds of the change
+    pub changed_at: u64,
+}
+
+/// Ingestion stage timings of a single event
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PipelineLatencyInsertRow {
+    /// Event type, e.g. `l2_header`
+    pub kind: String,
+    /// Microseconds from receipt by the extractor until processing started
+    pub queue_us: u64,
+    /// Microseconds spent enriching the event and writing its rows
+    pub process_us: u64,
+    /// Microseconds from receipt until the rows were written
+    pub total_us: u64,
+}
+
+/// End-to-end ingestion latency percentiles of an event type over a time range
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq)]
+pub struct PipelineLatencyRow {
+    /// Event type, empty for the total over all types
+    pub kind: String,
+    /// Number of events
+    pub events: u64,
+    /// Median time spent queued in microseconds
+    pub queue_p50_us: f64,
+    /// 99th percentile time spent queued in microseconds
+    pub queue_p99_us: f64,
+    /// Median processing time in microseconds
+    pub process_p50_us: f64,
+    /// 99th percentile processing time in microseconds
+    pub process_p99_us: f64,
+    /// Median end-to-end latency in microseconds
+    pub total_p50_us: f64,
+    /// 99th percentile end-to-end latency in microseconds
+    pub total_p99_us: f64,
+    /// Maximum end-to-end latency in microseconds
+    pub total_max_us: u64,
+}
+
+/// SLA summary of a public RPC endpoint over a time range
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct RpcStatusRow {
+    /// Probed endpoint
+    pub url: String,
+    /// Number of probes in the range
+    pub probes: u64,
+    /// Number of unhealthy probes in the range
+    pub failures: u64,
+    /// Average latency of successful probes in milliseconds
+    pub avg_latency_ms: Option<f64>,
+    /// 95th percentile latency of successful probes in milliseconds
+    pub p95_latency_ms: Option<f64>,
+    /// Whether the latest probe was healthy
+    pub last_healthy: u8,
+    /// Block lag reported by the latest probe
+    pub last_block_lag: Option<u64>,
+    /// Time of the latest probe as a UNIX timestamp in seconds
+    pub last_probed_at: u64,
+}
+
+/// Daily distribution of batch prove or verify times
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct DailyTimePercentilesRow {
+    /// Start of the day as a UNIX timestamp in seconds
+    pub day_ts: u64,
+    /// Number of batches proved or verified during the day
+    pub batches: u64,
+    /// Median time in milliseconds
+    pub p50_ms: u64,
+    /// 90th percentile time in milliseconds
+    pub p90_ms: u64,
+    /// 99th percentile time in milliseconds
+    pub p99_ms: u64,
+    /// Maximum time in milliseconds
+    pub max_ms: u64,
+}
+
+/// Distribution of batch prove times for one proof type
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProofTypeProveTimeRow {
+    /// Proof type code, see [`primitives::proof::ProofType`]
+    pub proof_type: u8,
+    /// Number of proofs of this type
+    pub batches: u64,
+    /// Average prove time in milliseconds
+    pub avg_ms: u64,
+    /// Median prove time in milliseconds
+    pub p50_ms: u64,
+    /// 90th percentile prove time in milliseconds
+    pub p90_ms: u64,
+    /// 99th percentile prove time in milliseconds
+    pub p99_ms: u64,
+    /// Maximum prove time in milliseconds
+    pub max_ms: u64,
+}
+
+/// Finality progress of a single L2 block. Transition timestamps are L1 block timestamps in
+/// seconds and are `None` until the block reaches that stage
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct L2BlockStatusRow {
+    /// L2 block number
+    pub l2_block_number: u64,
+    /// Timestamp of the L2 block, if its header was observed
+    pub block_ts: Option<u64>,
+    /// Batch the block was proposed in
+    pub batch_id: Option<u64>,
+    /// Timestamp of the L1 block that included the `BatchProposed` event
+    pub proposed_at: Option<u64>,
+    /// Timestamp of the L1 block that included the first proof of the batch
+    pub proved_at: Option<u64>,
+    /// Timestamp of the L1 block that verified the batch
+    pub verified_at: Option<u64>,
+}
+
+/// Number of L2 blocks in each finality stage within the head window
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct BlockStatusSummaryRow {
+    /// Highest L2 block number in the window
+    pub head_block: u64,
+    /// Blocks observed but not yet proposed in a batch
+    pub preconfirmed: u64,
+    /// Blocks proposed in a batch that has not been proved
+    pub proposed: u64,
+    /// Blocks in a proved batch that has not been verified
+    pub proved: u64,
+    /// Blocks in a verified batch
+    pub verified: u64,
+}
+
+/// Number of L2 reorgs of a given depth
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct ReorgDepthCountRow {
+    /// Reorg depth in blocks
+    pub depth: u16,
+    /// Number of reorgs with this depth
+    pub count: u64,
+}
+
+/// Reorg and orphaned block totals within a time range
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct ReorgTotalsRow {
+    /// Number of reorgs
+    pub reorgs: u64,
+    /// Time of the first reorg in milliseconds since the UNIX epoch
+    pub first_reorg_ms: u64,
+    /// Time of the last reorg in milliseconds since the UNIX epoch
+    pub last_reorg_ms: u64,
+    /// Number of block hashes orphaned by reorgs
+    pub orphaned_blocks: u64,
+    /// Number of distinct L2 blocks observed
+    pub l2_blocks: u64,
+}
+
+/// Batch proposal and verification progress over a time window
+#[derive(Debug, Clone, Default, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchCadenceRow {
+    /// Highest proposed batch id
+    pub last_proposed_batch_id: u64,
+    /// Batches proposed within the window
+    pub proposed_batches: u64,
+    /// Highest verified batch id
+    pub last_verified_batch_id: u64,
+    /// Batches verified within the window
+    pub verified_batches: u64,
+    /// Largest distance in L1 blocks between a batch proposed within the window and the oldest
+    /// anchor block of its L2 blocks
+    pub max_anchor_age: Option<u64>,
+}
+
+/// Row storing the `TaikoInbox` protocol configuration read at startup
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct ProtocolConfigRow {
+    /// Address of the `TaikoInbox` contract the configuration was read from
+    pub inbox_address: AddressBytes,
+    /// Chain ID of the network where the Taiko contracts are deployed
+    pub chain_id: u64,
+    /// Maximum number of unverified batches
+    pub max_unverified_batches: u64,
+    /// Size of the batch ring buffer
+    pub batch_ring_buffer_size: u64,
+    /// Maximum number of batches verified per proposal or proof
+    pub max_batches_to_verify: u64,
+    /// Maximum gas limit of an L2 block
+    pub block_max_gas_limit: u32,
+    /// Liveness bond per batch, in wei of the bond token
+    pub liveness_bond_base: u128,
+    /// Liveness bond per block, in wei of the bond token
+    pub liveness_bond_per_block: u128,
+    /// Number of batches between two L2-to-L1 state root syncs
+    pub state_root_sync_internal: u8,
+    /// Maximum difference between the anchor height and the current block number
+    pub max_anchor_height_offset: u64,
+    /// Percentage of the base fee shared with the proposer
+    pub base_fee_sharing_pctg: u8,
+    /// Proving window in seconds
+    pub proving_window: u16,
+    /// Cooldown window in seconds before a transition can verify a batch
+    pub cooldown_window: u32,
+    /// Maximum number of signals received by `TaikoL2`
+    pub max_signals_to_receive: u8,
+    /// Maximum number of blocks per batch
+    pub max_blocks_per_batch: u16,
+    /// Ontake fork height
+    pub ontake_fork_height: u64,
+    /// Pacaya fork height
+    pub pacaya_fork_height: u64,
+}
+
+impl ProtocolConfigRow {
+    /// Liveness bond taken for a batch with the maximum number of blocks
+    pub fn full_batch_bond(&self) -> u128 {
+        self.liveness_bond_base.saturating_add(
+            self.liveness_bond_per_block.saturating_mul(self.max_blocks_per_batch.into()),
+        )
+    }
+}
+
 /// Row representing the fee components for an L2 block
 #[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub struct BlockFeeComponentRow {
@@ -382,6 +1422,8 @@ pub struct BlockFeeComponentRow {
     pub base_fee: u128,
     /// L1 data posting cost associated with the block, if available
     pub l1_data_cost: Option<u128>,
+    /// Gas weighted average effective gas price, 0 for blocks ingested before it was recorded
+    pub avg_gas_price: u128,
 }
 
 /// Row representing aggregated L2 fees for a sequencer
@@ -418,6 +1460,8 @@ pub struct BatchFeeComponentRow {
     pub l1_data_cost: Option<u128>,
     /// Prover cost associated with the batch, if available
     pub prove_cost: Option<u128>,
+    /// Gas weighted average effective gas price of the blocks that recorded it, 0 if none did
+    pub avg_gas_price: u128,
 }
 
 /// Row representing the transactions per second for an L2 block
@@ -427,6 +1471,63 @@ pub struct L2TpsRow {
     pub l2_block_number: u64,
     /// Transactions per second between this and the previous block
     pub tps: f64,
+    /// Start of the time bucket, only set for series bucketed by time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_time: Option<DateTime<Utc>>,
+}
+
+/// L2 blocks aggregated over a wallclock-aligned time bucket
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct L2TimeBucketRow {
+    /// Unix timestamp in seconds of the start of the bucket
+    pub bucket_ts: u64,
+    /// First L2 block number in the bucket
+    pub first_block: u64,
+    /// Last L2 block number in the bucket
+    pub last_block: u64,
+    /// Timestamp of the first L2 block in the bucket
+    pub first_block_ts: u64,
+    /// Timestamp of the last L2 block in the bucket
+    pub last_block_ts: u64,
+    /// Number of L2 blocks in the bucket
+    pub blocks: u64,
+    /// Number of transactions in the bucket
+    pub tx_count: u64,
+    /// Gas used by the blocks in the bucket
+    pub gas_used: u64,
+    /// Sum of priority fees in the bucket
+    pub priority_fee: u128,
+    /// Sum of base fees in the bucket
+    pub base_fee: u128,
+}
+
+impl L2TimeBucketRow {
+    /// Start of the bucket
+    pub fn bucket_start(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(self.bucket_ts as i64, 0).unwrap_or_default()
+    }
+
+    /// Transactions per second over a bucket of `bucket_secs` seconds
+    pub fn tps(&self, bucket_secs: u64) -> f64 {
+        self.tx_count as f64 / bucket_secs.max(1) as f64
+    }
+
+    /// Average gas used per block
+    pub const fn avg_gas_used(&self) -> u64 {
+        match self.gas_used.checked_div(self.blocks) {
+            Some(avg) => avg,
+            None => 0,
+        }
+    }
+
+    /// Average seconds between consecutive blocks of the bucket, `None` with fewer than two
+    /// blocks
+    pub const fn avg_block_time_secs(&self) -> Option<u64> {
+        if self.blocks < 2 {
+            return None;
+        }
+        Some(self.last_block_ts.saturating_sub(self.first_block_ts) / (self.blocks - 1))
+    }
 }
 
 /// Row representing the blob count for each batch
@@ -440,6 +1541,92 @@ pub struct BatchBlobCountRow {
     pub blob_count: u8,
 }
 
+/// Blob usage, block count and transaction count of a batch
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchEfficiencyRow {
+    /// Batch ID
+    pub batch_id: u64,
+    /// L1 block number the batch was proposed in
+    pub l1_block_number: u64,
+    /// Number of blobs in the batch
+    pub blob_count: u8,
+    /// Bytes of blob data used by the batch
+    pub blob_total_bytes: u32,
+    /// Data availability mode code, see [`primitives::da::DaMode`]
+    pub da_mode: u8,
+    /// Size of the `txList` calldata in bytes
+    pub calldata_bytes: u32,
+    /// Number of L2 blocks in the batch
+    pub blocks: u16,
+    /// Number of transactions in the batch's L2 blocks
+    pub txs: u64,
+}
+
+/// Batches per data availability mode within a time bucket
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DaModeShareRow {
+    /// Start of the bucket as a UNIX timestamp in seconds
+    pub bucket_ts: u64,
+    /// Batches posted in blobs
+    pub blob_batches: u64,
+    /// Batches posted as calldata
+    pub calldata_batches: u64,
+    /// Bytes of blob data used by the blob batches
+    pub blob_bytes: u64,
+    /// Bytes of calldata posted by the calldata batches
+    pub calldata_bytes: u64,
+}
+
+/// Row representing a batch whose lifecycle records are inconsistent
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct BatchAnomalyRow {
+    /// Batch ID
+    pub batch_id: u64,
+    /// L1 block number of the record that references the missing one
+    pub l1_block_number: u64,
+}
+
+/// Row representing a run of batch IDs missing from `batches`, bounded by indexed proposals
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct BatchIdGapRow {
+    /// First missing batch ID
+    pub first_missing: u64,
+    /// Last missing batch ID
+    pub last_missing: u64,
+    /// L1 block number of the proposal preceding the gap
+    pub from_l1_block: u64,
+    /// L1 block number of the proposal following the gap
+    pub to_l1_block: u64,
+}
+
+/// Row representing an L2 block ranked in a leaderboard
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct L2BlockLeaderboardRow {
+    /// L2 block number
+    pub l2_block_number: u64,
+    /// Block timestamp in seconds since the Unix epoch
+    pub block_ts: u64,
+    /// Sequencer that produced the block
+    pub sequencer: AddressBytes,
+    /// Total gas used in the block
+    pub gas_used: u64,
+    /// Number of transactions in the block
+    pub tx_count: u32,
+}
+
+/// Row representing block production and fees of a sequencer in a leaderboard
+#[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct SequencerLeaderboardRow {
+    /// Sequencer address
+    pub sequencer: AddressBytes,
+    /// Number of blocks produced by the sequencer
+    pub blocks: u64,
+    /// Sum of priority fees collected by the sequencer
+    pub priority_fee: u128,
+    /// Sum of base fees of the sequencer's blocks
+    pub base_fee: u128,
+}
+
 /// Row representing the interval between consecutive batch proposals
 #[derive(Debug, Row, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub struct BatchPostingTimeRow {
@@ -491,6 +1678,9 @@ mod tests {
             proposer_addr: AddressBytes([0u8; 20]),
             blob_count: 1,
             blob_total_bytes: 100,
+            da_mode: 0,
+            calldata_bytes: 0,
+            fork: String::new(),
         };
         assert_eq!(batch.l2_block_numbers(), vec![3, 4, 5]);
         assert_eq!(batch.first_l2_block_number(), 3);
@@ -505,6 +1695,9 @@ mod tests {
             proposer_addr: AddressBytes([0u8; 20]),
             blob_count: 1,
             blob_total_bytes: 100,
+            da_mode: 0,
+            calldata_bytes: 0,
+            fork: String::new(),
         };
         assert_eq!(genesis_batch.l2_block_numbers(), vec![0]);
         assert_eq!(genesis_batch.first_l2_block_number(), 0);
@@ -519,6 +1712,9 @@ mod tests {
             proposer_addr: AddressBytes([0u8; 20]),
             blob_count: 1,
             blob_total_bytes: 100,
+            da_mode: 0,
+            calldata_bytes: 0,
+            fork: String::new(),
         };
         assert_eq!(single_batch.l2_block_numbers(), vec![10]);
         assert_eq!(single_batch.first_l2_block_number(), 10);
@@ -533,6 +1729,9 @@ mod tests {
             proposer_addr: AddressBytes([0u8; 20]),
             blob_count: 1,
             blob_total_bytes: 100,
+            da_mode: 0,
+            calldata_bytes: 0,
+            fork: String::new(),
         };
         assert_eq!(empty_batch.l2_block_numbers(), Vec::<u64>::new());
         assert_eq!(empty_batch.first_l2_block_number(), 6);
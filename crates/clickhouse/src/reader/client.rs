@@ -1,26 +1,54 @@
 //! `ClickHouse` reader functionality for API
 //! Handles read-only operations and analytics queries
 
-use super::TimeRange;
+use super::{
+    ReplicaStats, TimeRange,
+    limits::{QueryError, QueryLimits},
+    replicas::ReplicaPool,
+};
 use chrono::{DateTime, LocalResult, TimeZone, Utc};
-use clickhouse::{Client, Row, sql::Identifier};
+use clickhouse::{Client, Row, query::RowCursor, sql::Identifier};
 use derive_more::Debug;
 use eyre::{Context, Result};
+use futures::stream::{BoxStream, StreamExt};
 use hex::encode;
+use primitives::{beacon::SLOTS_PER_EPOCH, da::DaMode};
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeSet, time::Instant};
-use tokio::try_join;
-use tracing::{debug, error};
+use std::{
+    collections::BTreeSet,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    try_join,
+};
+use tracing::{debug, error, warn};
 use url::Url;
 
 use crate::{
     models::{
-        BatchBlobCountRow, BatchFeeComponentRow, BatchPostingTimeRow, BatchProveTimeRow,
-        BatchVerifyTimeRow, BlockFeeComponentRow, BlockTransactionRow, FailedProposalRow,
-        ForcedInclusionProcessedRow, L1BlockTimeRow, L1DataCostRow, L2BlockTimeRow, L2GasUsedRow,
-        L2ReorgRow, L2TpsRow, PreconfData, ProveCostRow, SequencerBlockRow, SequencerBlocksGrouped,
-        SequencerDistributionRow, SequencerFeeRow, SlashingEventRow,
+        AddressLabelRow, AnchorLagRow, AnchorMismatchRow, AnomalyRow, BackfillStatusRow,
+        BatchAnomalyRow, BatchBlobCountRow, BatchCadenceRow, BatchEfficiencyRow,
+        BatchFeeComponentRow, BatchIdGapRow, BatchL2BlockRow, BatchPostingTimeRow,
+        BatchProveTimeRow, BatchTxHashesRow, BatchVerifyTimeRow, BlobFeeHistoryRow,
+        BlockFeeComponentRow, BlockStatusSummaryRow, BlockTransactionRow, BondBalanceRow,
+        BondLedgerInsertRow, DaModeShareRow, DailyTimePercentilesRow, ExtraDataCountRow,
+        FailedProposalRow, ForcedInclusionProcessedRow, ForcedInclusionQueueRow, GasSaturationRow,
+        GasSaturationSummaryRow, HeaderPropagationRow, IncidentLogRow, L1BlockTimeRow,
+        L1DataCostRow, L1TxLookupRow, L2BlockLeaderboardRow, L2BlockProducerRow, L2BlockStatusRow,
+        L2BlockTimeRow, L2GasUsedRow, L2ReorgRow, L2TimeBucketRow, L2TpsRow, LeaderChangeRow,
+        MempoolStatsRow, OperatorBalanceRow, OperatorEpochRow, OperatorHistoryRow,
+        OperatorScheduleRow, PipelineLatencyRow, PreconfData, PreconfMismatchRow,
+        ProofTypeProveTimeRow, ProposalInclusionRow, ProtocolConfigRow, ProtocolGasSpendRow,
+        ProveCostRow, ReconciliationBlocksRow, ReorgDepthCountRow, ReorgTotalsRow, RpcStatusRow,
+        SequencerBlockRow, SequencerBlocksGrouped, SequencerCostBucketRow,
+        SequencerDistributionRow, SequencerFeeRow, SequencerFeeTotalsRow, SequencerHandoverRow,
+        SequencerLeaderboardRow, SequencerUptimeRow, SlashingEventRow, TaikoPriceInsertRow,
+        VerificationBacklogRow,
     },
+    schema::DEDUPLICATED_TABLES,
     types::{AddressBytes, HashBytes},
 };
 
@@ -29,32 +57,296 @@ struct MaxTs {
     block_ts: u64,
 }
 
+/// `DateTime64` expression for the moment a query is evaluated at: `as_of` if given, else now.
+fn now_as_of(as_of: Option<DateTime<Utc>>) -> String {
+    as_of.map_or_else(
+        || "now64()".to_owned(),
+        |as_of| format!("toDateTime64({:.3}, 3)", as_of.timestamp_millis() as f64 / 1000.0),
+    )
+}
+
+/// Rows of a streamed query, read as `ClickHouse` sends them
+pub type RowStream<T> = BoxStream<'static, Result<T>>;
+
+/// State of a query streamed by [`ClickhouseReader::stream`]
+struct StreamState<R> {
+    cursor: RowCursor<R>,
+    /// Query slot, released when the stream is dropped
+    _permit: Option<OwnedSemaphorePermit>,
+    pool: Arc<ReplicaPool>,
+    index: usize,
+    timeout: Option<Duration>,
+    query: String,
+    start: Instant,
+    rows: usize,
+}
+
+impl<R> StreamState<R>
+where
+    R: Row + for<'b> Deserialize<'b>,
+{
+    /// Read the next row accepted by `convert`, or `None` once the query is exhausted
+    async fn next<T>(mut self, convert: fn(R) -> Option<T>) -> Result<Option<(T, Self)>> {
+        loop {
+            let next = match self.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, self.cursor.next())
+                    .await
+                    .unwrap_or(Err(clickhouse::error::Error::TimedOut)),
+                None => self.cursor.next().await,
+            };
+            let elapsed = self.start.elapsed();
+            let duration_ms = elapsed.as_millis();
+            match next {
+                Ok(Some(row)) => {
+                    self.rows += 1;
+                    if let Some(item) = convert(row) {
+                        return Ok(Some((item, self)));
+                    }
+                }
+                Ok(None) => {
+                    self.pool.record(self.index, elapsed, true);
+                    debug!(
+                        query = %self.query,
+                        duration_ms,
+                        rows = self.rows,
+                        "ClickHouse query streamed"
+                    );
+                    return Ok(None);
+                }
+                Err(e) => {
+                    self.pool.record(self.index, elapsed, false);
+                    error!(
+                        query = %self.query,
+                        duration_ms,
+                        error = %e,
+                        "ClickHouse stream failed"
+                    );
+                    return Err(match (e, self.timeout) {
+                        (clickhouse::error::Error::TimedOut, Some(timeout)) => {
+                            QueryError::TimedOut(timeout).into()
+                        }
+                        (e, _) => e.into(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// L2 reorg as stored, with the insertion time in milliseconds
+#[derive(Row, Deserialize)]
+struct RawReorgRow {
+    l2_block_number: u64,
+    depth: u16,
+    old_sequencer: AddressBytes,
+    new_sequencer: AddressBytes,
+    probable_cause: u8,
+    ts: u64,
+}
+
+impl RawReorgRow {
+    fn into_row(self) -> Option<L2ReorgRow> {
+        let ts = Utc.timestamp_millis_opt(self.ts as i64).single()?;
+        Some(L2ReorgRow {
+            l2_block_number: self.l2_block_number,
+            depth: self.depth,
+            old_sequencer: self.old_sequencer,
+            new_sequencer: self.new_sequencer,
+            probable_cause: self.probable_cause,
+            inserted_at: ts,
+        })
+    }
+}
+
+/// Transaction count of an L2 block, with the block time in seconds
+#[derive(Row, Deserialize)]
+struct RawBlockTransactionRow {
+    sequencer: AddressBytes,
+    l2_block_number: u64,
+    block_time: u64,
+    sum_tx: u32,
+}
+
+impl RawBlockTransactionRow {
+    fn into_row(self) -> Option<BlockTransactionRow> {
+        Some(BlockTransactionRow {
+            sequencer: self.sequencer,
+            l2_block_number: self.l2_block_number,
+            block_time: Utc.timestamp_opt(self.block_time as i64, 0).single()?,
+            sum_tx: self.sum_tx,
+        })
+    }
+}
+
+/// Time since the previous L2 block, `None` for the first block
+#[derive(Row, Deserialize)]
+struct RawBlockTimeRow {
+    l2_block_number: u64,
+    block_time: u64,
+    s_since_prev_block: Option<u64>,
+}
+
+impl RawBlockTimeRow {
+    fn into_row(self) -> Option<L2BlockTimeRow> {
+        Some(L2BlockTimeRow {
+            l2_block_number: self.l2_block_number,
+            block_time: Utc.timestamp_opt(self.block_time as i64, 0).single()?,
+            s_since_prev_block: self.s_since_prev_block?,
+        })
+    }
+}
+
 /// `ClickHouse` reader client for API (read-only operations)
 #[derive(Clone, Debug)]
 pub struct ClickhouseReader {
-    /// Base client
-    #[debug(skip)]
-    base: Client,
+    /// Primary endpoint and read replicas queries are routed to
+    pool: Arc<ReplicaPool>,
+    /// Route queries with a `GROUP BY` clause to the same replica every time
+    sticky_aggregates: bool,
     /// Database name
     db_name: String,
+    /// Prefix prepended to every table and view name
+    table_prefix: String,
+    /// Timeout and concurrency limit applied to every query
+    limits: QueryLimits,
 }
 
 impl ClickhouseReader {
     /// Create a new `ClickHouse` reader client
     pub fn new(url: Url, db_name: String, username: String, password: String) -> Result<Self> {
-        let client = Client::default().with_url(url).with_user(username).with_password(password);
+        // Abandoned queries (timed out or the API client disconnected) drop their HTTP request;
+        // have the server cancel them instead of running them to completion. `final` reads
+        // `ReplacingMergeTree` tables as if fully merged, so duplicate rows awaiting a merge are
+        // never counted; other tables are unaffected. Queries reading as of a past moment turn it
+        // off and deduplicate themselves, see `execute_as_of`.
+        let client = Client::default()
+            .with_url(url.clone())
+            .with_user(username)
+            .with_password(password)
+            .with_option("cancel_http_readonly_queries_on_client_close", "1")
+            .with_option("final", "1");
+
+        Ok(Self {
+            pool: Arc::new(ReplicaPool::new(url, client)),
+            sticky_aggregates: false,
+            db_name,
+            table_prefix: String::new(),
+            limits: QueryLimits::default(),
+        })
+    }
+
+    /// Use the given table prefix for all queries, e.g. `staging_` to read from
+    /// `staging_l2_head_events`. Must match the prefix the writer was configured with.
+    pub fn with_table_prefix(mut self, table_prefix: impl Into<String>) -> Self {
+        self.table_prefix = table_prefix.into();
+        self
+    }
+
+    /// Spread queries over the given read replicas in addition to the primary. Replicas use the
+    /// primary's credentials.
+    pub fn with_replicas(mut self, replica_urls: Vec<Url>) -> Self {
+        self.pool = Arc::new(self.pool.with_replicas(replica_urls));
+        self
+    }
+
+    /// Always route identical aggregate queries (those with a `GROUP BY` clause) to the same
+    /// replica, so repeated dashboard queries hit a warm cache.
+    pub const fn with_sticky_aggregates(mut self, sticky_aggregates: bool) -> Self {
+        self.sticky_aggregates = sticky_aggregates;
+        self
+    }
+
+    /// Abandon queries that take longer than `timeout`, failing over to the next replica if
+    /// there is one. `None` disables the timeout.
+    pub const fn with_query_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.limits.timeout = timeout;
+        self
+    }
+
+    /// Allow at most `max` queries in flight at once across all clones of this reader. Queries
+    /// that cannot get a slot fail with [`QueryError::Saturated`]. `0` disables the limit.
+    pub fn with_max_concurrent_queries(mut self, max: usize) -> Self {
+        self.limits.slots = (max > 0).then(|| Arc::new(Semaphore::new(max)));
+        self
+    }
+
+    /// Query count, error count and average latency of the primary and every replica.
+    pub fn replica_stats(&self) -> Vec<ReplicaStats> {
+        self.pool.stats()
+    }
 
-        Ok(Self { base: client, db_name })
+    /// Run `query` on the first available replica, failing over to the next one on connection
+    /// errors and timeouts.
+    async fn run<T, F, Fut>(&self, query: &str, f: F) -> Result<T>
+    where
+        F: Fn(&Client) -> Fut,
+        Fut: Future<Output = clickhouse::error::Result<T>>,
+    {
+        let sticky_key = (self.sticky_aggregates && query.contains("GROUP BY")).then_some(query);
+        let candidates = self.pool.candidates(sticky_key);
+        let last = candidates.len() - 1;
+        let request_id = super::current_request_id();
+        let _permit = self.limits.acquire().await?;
+
+        for (attempt, index) in candidates.into_iter().enumerate() {
+            let start = Instant::now();
+            let query = async {
+                match &request_id {
+                    Some(id) => {
+                        let client = self.pool.client(index).clone().with_option("log_comment", id);
+                        f(&client).await
+                    }
+                    None => f(self.pool.client(index)).await,
+                }
+            };
+            let result = match self.limits.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, query)
+                    .await
+                    .unwrap_or(Err(clickhouse::error::Error::TimedOut)),
+                None => query.await,
+            };
+            let failover = matches!(
+                result,
+                Err(clickhouse::error::Error::Network(_) | clickhouse::error::Error::TimedOut)
+            );
+            self.pool.record(index, start.elapsed(), !failover);
+            if failover && attempt < last {
+                warn!(replica = %self.pool.url(index), "ClickHouse replica unavailable, failing over");
+                continue;
+            }
+            return result.map_err(|e| match (e, self.limits.timeout) {
+                (clickhouse::error::Error::TimedOut, Some(timeout)) => {
+                    QueryError::TimedOut(timeout).into()
+                }
+                (e, _) => e.into(),
+            });
+        }
+        unreachable!("replica pool is never empty")
     }
 
     async fn execute<R>(&self, query: &str) -> Result<Vec<R>>
     where
         R: Row + for<'b> Deserialize<'b>,
     {
-        let client = self.base.clone();
+        self.execute_as_of(query, None).await
+    }
+
+    /// [`Self::execute`] for a query reading tables through [`Self::table_as_of`]. With `as_of`,
+    /// the `final` setting is turned off: it would deduplicate before the `inserted_at` filter
+    /// and drop rows re-inserted after `as_of`, so the subqueries deduplicate instead.
+    async fn execute_as_of<R>(&self, query: &str, as_of: Option<DateTime<Utc>>) -> Result<Vec<R>>
+    where
+        R: Row + for<'b> Deserialize<'b>,
+    {
         let start = Instant::now();
 
-        let result = client.query(query).fetch_all::<R>().await;
+        let result = self
+            .run(query, |client| {
+                let query = client.query(query);
+                let query = if as_of.is_some() { query.with_option("final", "0") } else { query };
+                query.fetch_all::<R>()
+            })
+            .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
@@ -63,35 +355,102 @@ impl ClickhouseReader {
             }
             Err(e) => error!(query = %query, duration_ms, error = %e, "ClickHouse query failed"),
         }
-        result.map_err(Into::into)
+        result
+    }
+
+    /// Stream the rows of `query` as `ClickHouse` sends them instead of collecting them first,
+    /// converting each with `convert` and skipping the rows it rejects.
+    ///
+    /// Rows may already have been handed out when the query fails, so it runs on the first
+    /// candidate replica without failover. The query timeout applies to the wait for each row,
+    /// and the query slot is held until the stream ends or is dropped.
+    async fn stream<R, T>(&self, query: String, convert: fn(R) -> Option<T>) -> Result<RowStream<T>>
+    where
+        R: Row + for<'b> Deserialize<'b> + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = self.limits.acquire().await?;
+        let index = self.pool.candidates(None)[0];
+        let client = match super::current_request_id() {
+            Some(id) => self.pool.client(index).clone().with_option("log_comment", id),
+            None => self.pool.client(index).clone(),
+        };
+        let cursor = client.query(&query).fetch::<R>()?;
+
+        let state = StreamState {
+            cursor,
+            _permit: permit,
+            pool: Arc::clone(&self.pool),
+            index,
+            timeout: self.limits.timeout,
+            query,
+            start: Instant::now(),
+            rows: 0,
+        };
+        let rows = futures::stream::try_unfold(state, move |state| state.next(convert));
+        Ok(rows.boxed())
     }
 
     /// Anti-subquery that hides blocks later rolled back by a reorg.
     /// Use with `NOT IN (SELECT block_hash FROM ...)`
     fn reorg_filter(&self, table_alias: &str) -> String {
+        self.reorg_filter_as_of(table_alias, None)
+    }
+
+    /// [`Self::reorg_filter`] that only considers blocks known to be orphaned at `as_of`
+    fn reorg_filter_as_of(&self, table_alias: &str, as_of: Option<DateTime<Utc>>) -> String {
         format!(
             "{table_alias}.block_hash NOT IN ( \
                 SELECT block_hash \
-                FROM {db}.orphaned_l2_hashes\
+                FROM {orphaned}\
             )",
+            orphaned = self.table_as_of("orphaned_l2_hashes", as_of),
+        )
+    }
+
+    /// Reference to `table` for `FROM` and `JOIN` clauses. With `as_of`, only rows inserted at or
+    /// before that moment are visible, so aggregates read as they would have at the time.
+    ///
+    /// Deduplicated tables keep the latest copy of each key inserted by `as_of`, as `FINAL` would
+    /// have at the time. Queries using them must run through [`Self::execute_as_of`].
+    fn table_as_of(&self, table: &str, as_of: Option<DateTime<Utc>>) -> String {
+        let Some(as_of) = as_of else {
+            return format!("{db}.{prefix}{table}", db = self.db_name, prefix = self.table_prefix);
+        };
+        let dedup = DEDUPLICATED_TABLES
+            .iter()
+            .find(|(name, _)| *name == table)
+            .map_or_else(String::new, |(_, key)| {
+                format!(" ORDER BY inserted_at DESC LIMIT 1 BY {key}")
+            });
+        format!(
+            "(SELECT * FROM {db}.{prefix}{table} WHERE inserted_at <= {as_of}{dedup})",
             db = self.db_name,
+            prefix = self.table_prefix,
+            as_of = now_as_of(Some(as_of)),
         )
     }
 
     /// Get last L2 head time
     pub async fn get_last_l2_head_time(&self) -> Result<Option<DateTime<Utc>>> {
-        let client = self.base.clone();
-        let sql = "SELECT max(block_ts) AS block_ts FROM ?.l2_head_events";
+        let sql = format!(
+            "SELECT max(block_ts) AS block_ts FROM ?.{prefix}l2_head_events",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client.query(sql).bind(Identifier(&self.db_name)).fetch_all::<MaxTs>().await;
+        let result = self
+            .run(&sql, |client| {
+                client.query(&sql).bind(Identifier(&self.db_name)).fetch_all::<MaxTs>()
+            })
+            .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result.context("fetching max(block_ts) failed")?;
@@ -111,18 +470,24 @@ impl ClickhouseReader {
 
     /// Get timestamp of the latest L1 head event in UTC
     pub async fn get_last_l1_head_time(&self) -> Result<Option<DateTime<Utc>>> {
-        let client = self.base.clone();
-        let sql = "SELECT max(block_ts) AS block_ts FROM ?.l1_head_events";
+        let sql = format!(
+            "SELECT max(block_ts) AS block_ts FROM ?.{prefix}l1_head_events",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client.query(sql).bind(Identifier(&self.db_name)).fetch_all::<MaxTs>().await;
+        let result = self
+            .run(&sql, |client| {
+                client.query(&sql).bind(Identifier(&self.db_name)).fetch_all::<MaxTs>()
+            })
+            .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result.context("fetching max(block_ts) failed")?;
@@ -144,6 +509,64 @@ impl ClickhouseReader {
         Ok(ts_opt)
     }
 
+    /// Get the time of the most recent insert into `l1_head_events`
+    pub async fn get_last_l1_head_insert_time(&self) -> Result<Option<DateTime<Utc>>> {
+        self.get_last_insert_time("l1_head_events").await
+    }
+
+    /// Get the time of the most recent insert into `l2_head_events`
+    pub async fn get_last_l2_head_insert_time(&self) -> Result<Option<DateTime<Utc>>> {
+        self.get_last_insert_time("l2_head_events").await
+    }
+
+    /// Estimate the number of rows of `table` inserted within `(since, until]`. Duplicates awaiting
+    /// a merge are counted as well, so the estimate may exceed the number of distinct rows.
+    pub async fn estimate_row_count(
+        &self,
+        table: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<u64> {
+        #[derive(Row, Deserialize)]
+        struct CountRow {
+            rows: u64,
+        }
+
+        let query = format!(
+            "SELECT count() AS rows FROM {db}.{prefix}{table} \
+             WHERE inserted_at > toDateTime64({since}, 3) \
+               AND inserted_at <= toDateTime64({until}, 3)",
+            since = since.timestamp_millis() as f64 / 1000.0,
+            until = until.timestamp_millis() as f64 / 1000.0,
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<CountRow>(&query).await?;
+        Ok(rows.into_iter().next().map_or(0, |r| r.rows))
+    }
+
+    async fn get_last_insert_time(&self, table: &str) -> Result<Option<DateTime<Utc>>> {
+        #[derive(Row, Deserialize)]
+        struct MaxInsertedAt {
+            inserted_at: u64,
+        }
+
+        let query = format!(
+            "SELECT toUInt64(max(toUnixTimestamp64Milli(inserted_at))) AS inserted_at \
+             FROM {db}.{prefix}{table}",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<MaxInsertedAt>(&query).await?;
+        Ok(rows
+            .into_iter()
+            .next()
+            .filter(|r| r.inserted_at != 0)
+            .and_then(|r| Utc.timestamp_millis_opt(r.inserted_at as i64).single()))
+    }
+
     /// Get the latest L2 block number.
     /// Uses an optimized query that should be faster on large tables.
     pub async fn get_last_l2_block_number(&self) -> Result<Option<u64>> {
@@ -152,20 +575,25 @@ impl ClickhouseReader {
             l2_block_number: u64,
         }
 
-        let client = self.base.clone();
-        let sql =
-            "SELECT l2_block_number FROM ?.l2_head_events ORDER BY l2_block_number DESC LIMIT 1";
+        let sql = format!(
+            "SELECT l2_block_number FROM ?.{prefix}l2_head_events \
+             ORDER BY l2_block_number DESC LIMIT 1",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result =
-            client.query(sql).bind(Identifier(&self.db_name)).fetch_all::<BlockNumber>().await;
+        let result = self
+            .run(&sql, |client| {
+                client.query(&sql).bind(Identifier(&self.db_name)).fetch_all::<BlockNumber>()
+            })
+            .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result?;
@@ -179,6 +607,12 @@ impl ClickhouseReader {
         Ok(Some(row.l2_block_number))
     }
 
+    /// Whether the user is restricted to reads by the `readonly` setting of its profile
+    pub async fn is_read_only(&self) -> Result<bool> {
+        let rows = self.execute::<u8>("SELECT toUInt8(getSetting('readonly'))").await?;
+        Ok(rows.first().is_some_and(|&readonly| readonly > 0))
+    }
+
     /// Get the latest L1 block number.
     /// Uses an optimized query that should be faster on large tables.
     pub async fn get_last_l1_block_number(&self) -> Result<Option<u64>> {
@@ -187,20 +621,25 @@ impl ClickhouseReader {
             l1_block_number: u64,
         }
 
-        let client = self.base.clone();
-        let sql =
-            "SELECT l1_block_number FROM ?.l1_head_events ORDER BY l1_block_number DESC LIMIT 1";
+        let sql = format!(
+            "SELECT l1_block_number FROM ?.{prefix}l1_head_events \
+             ORDER BY l1_block_number DESC LIMIT 1",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result =
-            client.query(sql).bind(Identifier(&self.db_name)).fetch_all::<BlockNumber>().await;
+        let result = self
+            .run(&sql, |client| {
+                client.query(&sql).bind(Identifier(&self.db_name)).fetch_all::<BlockNumber>()
+            })
+            .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result?;
@@ -216,26 +655,31 @@ impl ClickhouseReader {
 
     /// Get timestamp of the latest `BatchProposed` event based on L1 block timestamp in UTC
     pub async fn get_last_batch_time(&self) -> Result<Option<DateTime<Utc>>> {
-        let client = self.base.clone();
-        let sql = "SELECT max(l1_events.block_ts) AS block_ts \
-             FROM ?.batches b \
-             INNER JOIN ?.l1_head_events l1_events \
-               ON b.l1_block_number = l1_events.l1_block_number";
+        let sql = format!(
+            "SELECT max(l1_events.block_ts) AS block_ts \
+             FROM ?.{prefix}batches b \
+             INNER JOIN ?.{prefix}l1_head_events l1_events \
+               ON b.l1_block_number = l1_events.l1_block_number",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .bind(Identifier(&self.db_name))
-            .fetch_all::<MaxTs>()
+        let result = self
+            .run(&sql, |client| {
+                client
+                    .query(&sql)
+                    .bind(Identifier(&self.db_name))
+                    .bind(Identifier(&self.db_name))
+                    .fetch_all::<MaxTs>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result.context("fetching max batch L1 block timestamp failed")?;
@@ -259,55 +703,84 @@ impl ClickhouseReader {
 
     /// Get the most recent preconfiguration data
     pub async fn get_last_preconf_data(&self) -> Result<Option<PreconfData>> {
-        let client = self.base.clone();
-        let sql = "SELECT slot, candidates, current_operator, next_operator FROM ?.preconf_data ORDER BY inserted_at DESC LIMIT 1";
+        let sql = format!(
+            "SELECT slot, candidates, current_operator, next_operator FROM ?.{prefix}preconf_data ORDER BY inserted_at DESC LIMIT 1",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result =
-            client.query(sql).bind(Identifier(&self.db_name)).fetch_all::<PreconfData>().await;
+        let result = self
+            .run(&sql, |client| {
+                client.query(&sql).bind(Identifier(&self.db_name)).fetch_all::<PreconfData>()
+            })
+            .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result?;
         Ok(rows.into_iter().next())
     }
 
+    /// Get the most recently stored `TaikoInbox` protocol configuration
+    pub async fn get_protocol_config(&self) -> Result<Option<ProtocolConfigRow>> {
+        let query = format!(
+            "SELECT inbox_address, chain_id, max_unverified_batches, batch_ring_buffer_size, \
+                    max_batches_to_verify, block_max_gas_limit, liveness_bond_base, \
+                    liveness_bond_per_block, state_root_sync_internal, max_anchor_height_offset, \
+                    base_fee_sharing_pctg, proving_window, cooldown_window, max_signals_to_receive, \
+                    max_blocks_per_batch, ontake_fork_height, pacaya_fork_height \
+             FROM {db}.{prefix}protocol_config \
+             ORDER BY inserted_at DESC \
+             LIMIT 1",
+            db = self.db_name,
+            prefix = self.table_prefix
+        );
+
+        let rows = self.execute::<ProtocolConfigRow>(&query).await?;
+        Ok(rows.into_iter().next())
+    }
+
     /// Get all batches that have not been proven and are older than the given cutoff time
     pub async fn get_unproved_batches_older_than(
         &self,
         cutoff: DateTime<Utc>,
     ) -> Result<Vec<(u64, u64, DateTime<Utc>)>> {
-        let client = self.base.clone();
-        let sql = "SELECT b.l1_block_number, b.batch_id, toUnixTimestamp64Milli(b.inserted_at) as inserted_at \
+        let sql = format!(
+            "SELECT b.l1_block_number, b.batch_id, toUnixTimestamp64Milli(b.inserted_at) as inserted_at \
              FROM (SELECT l1_block_number, batch_id, inserted_at \
-                   FROM ?.batches \
+                   FROM ?.{prefix}batches \
                    WHERE inserted_at < toDateTime64(?, 3)) AS b \
-             LEFT JOIN ?.proved_batches p \
+             LEFT JOIN ?.{prefix}proved_batches p \
                ON b.l1_block_number = p.l1_block_number AND b.batch_id = p.batch_id \
              WHERE p.batch_id IS NULL \
-             ORDER BY b.inserted_at ASC";
+             ORDER BY b.inserted_at ASC",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .bind(cutoff.timestamp_millis() as f64 / 1000.0)
-            .bind(Identifier(&self.db_name))
-            .fetch_all::<(u64, u64, u64)>()
+        let result = self
+            .run(&sql, |client| {
+                client
+                    .query(&sql)
+                    .bind(Identifier(&self.db_name))
+                    .bind(cutoff.timestamp_millis() as f64 / 1000.0)
+                    .bind(Identifier(&self.db_name))
+                    .fetch_all::<(u64, u64, u64)>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
         let rows = result.context("fetching unproved batches failed")?;
         Ok(rows
@@ -327,19 +800,22 @@ impl ClickhouseReader {
         struct ProvedBatchIdRow {
             batch_id: u64,
         }
-        let client = self.base.clone();
-        let sql = "SELECT batch_id FROM ?.proved_batches";
+        let sql =
+            format!("SELECT batch_id FROM ?.{prefix}proved_batches", prefix = self.table_prefix);
 
         let start = Instant::now();
-        let result =
-            client.query(sql).bind(Identifier(&self.db_name)).fetch_all::<ProvedBatchIdRow>().await;
+        let result = self
+            .run(&sql, |client| {
+                client.query(&sql).bind(Identifier(&self.db_name)).fetch_all::<ProvedBatchIdRow>()
+            })
+            .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result?;
@@ -351,31 +827,36 @@ impl ClickhouseReader {
         &self,
         cutoff: DateTime<Utc>,
     ) -> Result<Vec<(u64, u64, DateTime<Utc>)>> {
-        let client = self.base.clone();
-        let sql = "SELECT b.l1_block_number, b.batch_id, toUnixTimestamp64Milli(b.inserted_at) as inserted_at \
+        let sql = format!(
+            "SELECT b.l1_block_number, b.batch_id, toUnixTimestamp64Milli(b.inserted_at) as inserted_at \
              FROM (SELECT l1_block_number, batch_id, inserted_at \
-                   FROM ?.batches \
+                   FROM ?.{prefix}batches \
                    WHERE inserted_at < toDateTime64(?, 3)) AS b \
-             LEFT JOIN ?.verified_batches v \
+             LEFT JOIN ?.{prefix}verified_batches v \
                ON b.l1_block_number = v.l1_block_number AND b.batch_id = v.batch_id \
              WHERE v.batch_id IS NULL \
-             ORDER BY b.inserted_at ASC";
+             ORDER BY b.inserted_at ASC",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .bind(cutoff.timestamp_millis() as f64 / 1000.0)
-            .bind(Identifier(&self.db_name))
-            .fetch_all::<(u64, u64, u64)>()
+        let result = self
+            .run(&sql, |client| {
+                client
+                    .query(&sql)
+                    .bind(Identifier(&self.db_name))
+                    .bind(cutoff.timestamp_millis() as f64 / 1000.0)
+                    .bind(Identifier(&self.db_name))
+                    .fetch_all::<(u64, u64, u64)>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
         let rows = result.context("fetching unverified batches failed")?;
         Ok(rows
@@ -389,58 +870,320 @@ impl ClickhouseReader {
             .collect())
     }
 
+    /// Get the L1 transactions that proposed, proved and verified each of the given batches.
+    /// Batches that were never proposed are omitted.
+    pub async fn get_batch_tx_hashes(&self, batch_ids: &[u64]) -> Result<Vec<BatchTxHashesRow>> {
+        if batch_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = batch_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+        let query = format!(
+            "SELECT b.batch_id AS batch_id, \
+                    b.l1_tx_hash AS proposal_l1_tx_hash, \
+                    p.l1_tx_hash AS proof_l1_tx_hash, \
+                    v.l1_tx_hash AS verification_l1_tx_hash \
+             FROM ( \
+                SELECT batch_id, argMax(l1_tx_hash, inserted_at) AS l1_tx_hash \
+                FROM {db}.{prefix}batches \
+                WHERE batch_id IN ({ids}) \
+                GROUP BY batch_id \
+             ) b \
+             LEFT JOIN ( \
+                SELECT batch_id, argMin(l1_tx_hash, inserted_at) AS l1_tx_hash \
+                FROM {db}.{prefix}proved_batches \
+                WHERE batch_id IN ({ids}) \
+                GROUP BY batch_id \
+             ) p ON b.batch_id = p.batch_id \
+             LEFT JOIN ( \
+                SELECT batch_id, argMax(l1_tx_hash, inserted_at) AS l1_tx_hash \
+                FROM {db}.{prefix}verified_batches \
+                WHERE batch_id IN ({ids}) \
+                GROUP BY batch_id \
+             ) v ON b.batch_id = v.batch_id",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BatchTxHashesRow>(&query).await.context("fetching batch tx hashes failed")
+    }
+
+    /// Get the time each of the given batches was first proved. Unproved batches are omitted.
+    pub async fn get_batch_proved_times(
+        &self,
+        batch_ids: &[u64],
+    ) -> Result<Vec<(u64, DateTime<Utc>)>> {
+        #[derive(Row, Deserialize)]
+        struct ProvedAtRow {
+            batch_id: u64,
+            proved_at: u64,
+        }
+
+        if batch_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = batch_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", ");
+        let query = format!(
+            "SELECT batch_id, toUInt64(toUnixTimestamp64Milli(min(inserted_at))) AS proved_at \
+             FROM {db}.{prefix}proved_batches \
+             WHERE batch_id IN ({ids}) \
+             GROUP BY batch_id",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<ProvedAtRow>(&query).await?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|r| {
+                Utc.timestamp_millis_opt(r.proved_at as i64).single().map(|dt| (r.batch_id, dt))
+            })
+            .collect())
+    }
+
     /// Get all verified batch IDs from the `verified_batches` table
     pub async fn get_verified_batch_ids(&self) -> Result<Vec<u64>> {
         #[derive(Row, Deserialize)]
         struct VerifiedBatchIdRow {
             batch_id: u64,
         }
-        let client = self.base.clone();
-        let sql = "SELECT batch_id FROM ?.verified_batches";
+        let sql =
+            format!("SELECT batch_id FROM ?.{prefix}verified_batches", prefix = self.table_prefix);
 
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .fetch_all::<VerifiedBatchIdRow>()
+        let result = self
+            .run(&sql, |client| {
+                client.query(&sql).bind(Identifier(&self.db_name)).fetch_all::<VerifiedBatchIdRow>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result?;
         Ok(rows.into_iter().map(|r| r.batch_id).collect())
     }
 
+    /// Get verified batches that have no matching record in `proved_batches`.
+    ///
+    /// Batches proposed before indexing started are ignored.
+    pub async fn get_verified_batches_without_proof(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<BatchAnomalyRow>> {
+        let query = format!(
+            "SELECT batch_id, min(l1_block_number) AS l1_block_number \
+             FROM {db}.{prefix}verified_batches \
+             WHERE batch_id NOT IN (SELECT batch_id FROM {db}.{prefix}proved_batches) \
+               AND batch_id IN (SELECT batch_id FROM {db}.{prefix}batches) \
+             GROUP BY batch_id \
+             ORDER BY batch_id ASC \
+             LIMIT {limit}",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BatchAnomalyRow>(&query).await
+    }
+
+    /// Get proved batches that have no matching record in `batches`.
+    ///
+    /// Batches older than the earliest indexed proposal are ignored.
+    pub async fn get_proved_batches_without_proposal(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<BatchAnomalyRow>> {
+        let query = format!(
+            "SELECT batch_id, min(l1_block_number) AS l1_block_number \
+             FROM {db}.{prefix}proved_batches \
+             WHERE batch_id NOT IN (SELECT batch_id FROM {db}.{prefix}batches) \
+               AND batch_id >= (SELECT min(batch_id) FROM {db}.{prefix}batches) \
+             GROUP BY batch_id \
+             ORDER BY batch_id ASC \
+             LIMIT {limit}",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BatchAnomalyRow>(&query).await
+    }
+
+    /// Count verified batches that have no matching record in `proved_batches`, uncapped unlike
+    /// [`Self::get_verified_batches_without_proof`].
+    pub async fn count_verified_batches_without_proof(&self) -> Result<u64> {
+        let query = format!(
+            "SELECT uniqExact(batch_id) AS count \
+             FROM {db}.{prefix}verified_batches \
+             WHERE batch_id NOT IN (SELECT batch_id FROM {db}.{prefix}proved_batches) \
+               AND batch_id IN (SELECT batch_id FROM {db}.{prefix}batches)",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute_count(&query).await
+    }
+
+    /// Count proved batches that have no matching record in `batches`, uncapped unlike
+    /// [`Self::get_proved_batches_without_proposal`].
+    pub async fn count_proved_batches_without_proposal(&self) -> Result<u64> {
+        let query = format!(
+            "SELECT uniqExact(batch_id) AS count \
+             FROM {db}.{prefix}proved_batches \
+             WHERE batch_id NOT IN (SELECT batch_id FROM {db}.{prefix}batches) \
+               AND batch_id >= (SELECT min(batch_id) FROM {db}.{prefix}batches)",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute_count(&query).await
+    }
+
+    async fn execute_count(&self, query: &str) -> Result<u64> {
+        #[derive(Row, Deserialize)]
+        struct CountRow {
+            count: u64,
+        }
+
+        let rows = self.execute::<CountRow>(query).await?;
+        Ok(rows.into_iter().next().map_or(0, |r| r.count))
+    }
+
+    /// Find runs of batch IDs missing between proposals indexed at or after `min_l1_block`, most
+    /// recent first
+    pub async fn find_missing_batch_ids(
+        &self,
+        min_l1_block: u64,
+        limit: u64,
+    ) -> Result<Vec<BatchIdGapRow>> {
+        let query = format!(
+            "SELECT prev_batch_id + 1 AS first_missing, batch_id - 1 AS last_missing, \
+                    prev_l1_block_number AS from_l1_block, l1_block_number AS to_l1_block \
+             FROM ( \
+                 SELECT batch_id, l1_block_number, \
+                        lagInFrame(batch_id) OVER w AS prev_batch_id, \
+                        lagInFrame(l1_block_number) OVER w AS prev_l1_block_number \
+                 FROM ( \
+                     SELECT batch_id, min(l1_block_number) AS l1_block_number \
+                     FROM {db}.{prefix}batches \
+                     WHERE l1_block_number >= {min_l1_block} \
+                     GROUP BY batch_id \
+                 ) \
+                 WINDOW w AS (ORDER BY batch_id ASC ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) \
+             ) \
+             WHERE prev_batch_id > 0 AND batch_id > prev_batch_id + 1 \
+             ORDER BY first_missing DESC \
+             LIMIT {limit}",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BatchIdGapRow>(&query).await
+    }
+
+    /// Get batches proposed at or after `min_l1_block` and indexed before `cutoff` that have no
+    /// matching record in `proved_batches`
+    pub async fn get_batches_without_proof(
+        &self,
+        cutoff: DateTime<Utc>,
+        min_l1_block: u64,
+        limit: u64,
+    ) -> Result<Vec<BatchAnomalyRow>> {
+        let query = format!(
+            "SELECT batch_id, min(l1_block_number) AS l1_block_number \
+             FROM {db}.{prefix}batches \
+             WHERE batch_id NOT IN (SELECT batch_id FROM {db}.{prefix}proved_batches) \
+               AND l1_block_number >= {min_l1_block} \
+               AND inserted_at < toDateTime64({cutoff}, 3) \
+             GROUP BY batch_id \
+             ORDER BY batch_id ASC \
+             LIMIT {limit}",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            cutoff = cutoff.timestamp_millis() as f64 / 1000.0,
+        );
+
+        self.execute::<BatchAnomalyRow>(&query).await
+    }
+
+    /// Get batches proved at or after `min_l1_block` and indexed before `cutoff` that are not
+    /// covered by any record in `verified_batches`.
+    ///
+    /// A verification covers every batch up to its batch ID.
+    pub async fn get_proved_batches_without_verification(
+        &self,
+        cutoff: DateTime<Utc>,
+        min_l1_block: u64,
+        limit: u64,
+    ) -> Result<Vec<BatchAnomalyRow>> {
+        let query = format!(
+            "SELECT batch_id, min(l1_block_number) AS l1_block_number \
+             FROM {db}.{prefix}proved_batches \
+             WHERE batch_id > (SELECT max(batch_id) FROM {db}.{prefix}verified_batches) \
+               AND l1_block_number >= {min_l1_block} \
+               AND inserted_at < toDateTime64({cutoff}, 3) \
+             GROUP BY batch_id \
+             ORDER BY batch_id ASC \
+             LIMIT {limit}",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            cutoff = cutoff.timestamp_millis() as f64 / 1000.0,
+        );
+
+        self.execute::<BatchAnomalyRow>(&query).await
+    }
+
+    /// Get proved batches that have no matching record in `prove_costs`, newest first
+    pub async fn get_proved_batches_without_prove_cost(
+        &self,
+        limit: u64,
+    ) -> Result<Vec<BatchAnomalyRow>> {
+        let query = format!(
+            "SELECT DISTINCT batch_id, l1_block_number \
+             FROM {db}.{prefix}proved_batches \
+             WHERE (l1_block_number, batch_id) NOT IN \
+                   (SELECT l1_block_number, batch_id FROM {db}.{prefix}prove_costs) \
+             ORDER BY l1_block_number DESC, batch_id ASC \
+             LIMIT {limit}",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BatchAnomalyRow>(&query).await
+    }
+
     /// Get all slashing events that occurred after the given cutoff time
     pub async fn get_slashing_events_since(
         &self,
         since: DateTime<Utc>,
     ) -> Result<Vec<SlashingEventRow>> {
-        let client = self.base.clone();
-        let sql = "SELECT l1_block_number, validator_addr FROM ?.slashing_events \
+        let sql = format!(
+            "SELECT l1_block_number, validator_addr, reason FROM ?.{prefix}slashing_events \
              WHERE inserted_at > toDateTime64(?, 3) \
-             ORDER BY inserted_at ASC";
+             ORDER BY inserted_at ASC",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .bind(since.timestamp_millis() as f64 / 1000.0)
-            .fetch_all::<SlashingEventRow>()
+        let result = self
+            .run(&sql, |client| {
+                client
+                    .query(&sql)
+                    .bind(Identifier(&self.db_name))
+                    .bind(since.timestamp_millis() as f64 / 1000.0)
+                    .fetch_all::<SlashingEventRow>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
         let rows = result.context("fetching slashing events failed")?;
         Ok(rows)
@@ -452,56 +1195,132 @@ impl ClickhouseReader {
         since: DateTime<Utc>,
         until: DateTime<Utc>,
     ) -> Result<Vec<SlashingEventRow>> {
-        let client = self.base.clone();
-        let sql = "SELECT l1_block_number, validator_addr FROM ?.slashing_events \
+        let sql = format!(
+            "SELECT l1_block_number, validator_addr, reason FROM ?.{prefix}slashing_events \
              WHERE inserted_at > toDateTime64(?, 3) \
                AND inserted_at <= toDateTime64(?, 3) \
-             ORDER BY inserted_at ASC";
+             ORDER BY inserted_at ASC",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .bind(since.timestamp_millis() as f64 / 1000.0)
-            .bind(until.timestamp_millis() as f64 / 1000.0)
-            .fetch_all::<SlashingEventRow>()
+        let result = self
+            .run(&sql, |client| {
+                client
+                    .query(&sql)
+                    .bind(Identifier(&self.db_name))
+                    .bind(since.timestamp_millis() as f64 / 1000.0)
+                    .bind(until.timestamp_millis() as f64 / 1000.0)
+                    .fetch_all::<SlashingEventRow>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
         let rows = result.context("fetching slashing events failed")?;
         Ok(rows)
     }
 
-    /// Get all forced inclusion events that occurred after the given cutoff time
-    pub async fn get_forced_inclusions_since(
+    /// Get soft blocks that differed from their canonical block, detected within the given
+    /// time range
+    pub async fn get_preconf_mismatches_range(
         &self,
         since: DateTime<Utc>,
-    ) -> Result<Vec<ForcedInclusionProcessedRow>> {
-        let client = self.base.clone();
-        let sql = "SELECT blob_hash FROM ?.forced_inclusion_processed \
-             WHERE inserted_at > toDateTime64(?, 3) \
-             ORDER BY inserted_at ASC";
-
+        until: DateTime<Utc>,
+    ) -> Result<Vec<PreconfMismatchRow>> {
+        let query = format!(
+            "SELECT l2_block_number, soft_block_hash, canonical_block_hash, soft_tx_count, \
+                    canonical_tx_count, missing_tx_hashes, extra_tx_hashes, reordered \
+             FROM {db}.{prefix}preconf_mismatches \
+             WHERE inserted_at > toDateTime64({since}, 3) \
+               AND inserted_at <= toDateTime64({until}, 3) \
+             ORDER BY inserted_at ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp_millis() as f64 / 1000.0,
+            until = until.timestamp_millis() as f64 / 1000.0,
+        );
+        self.execute::<PreconfMismatchRow>(&query)
+            .await
+            .context("fetching preconf mismatches failed")
+    }
+
+    /// Get the hash of the indexed L1 block at `l1_block_number`. When the block was reorged the
+    /// most recently indexed hash is returned.
+    pub async fn get_l1_block_hash(&self, l1_block_number: u64) -> Result<Option<HashBytes>> {
+        #[derive(Row, Deserialize)]
+        struct BlockHashRow {
+            block_hash: HashBytes,
+        }
+
+        let query = format!(
+            "SELECT argMax(block_hash, inserted_at) AS block_hash \
+             FROM {db}.{prefix}l1_head_events \
+             WHERE l1_block_number = {l1_block_number} \
+             HAVING count() > 0",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        let rows =
+            self.execute::<BlockHashRow>(&query).await.context("fetching L1 block hash failed")?;
+        Ok(rows.into_iter().next().map(|r| r.block_hash))
+    }
+
+    /// Get batches whose anchor block hash differed from the indexed L1 block, detected within
+    /// the given time range
+    pub async fn get_anchor_mismatches_range(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<AnchorMismatchRow>> {
+        let query = format!(
+            "SELECT batch_id, l1_block_number, anchor_block_id, anchor_block_hash, l1_block_hash \
+             FROM {db}.{prefix}anchor_mismatches \
+             WHERE inserted_at > toDateTime64({since}, 3) \
+               AND inserted_at <= toDateTime64({until}, 3) \
+             ORDER BY inserted_at ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp_millis() as f64 / 1000.0,
+            until = until.timestamp_millis() as f64 / 1000.0,
+        );
+        self.execute::<AnchorMismatchRow>(&query).await.context("fetching anchor mismatches failed")
+    }
+
+    /// Get all forced inclusion events that occurred after the given cutoff time
+    pub async fn get_forced_inclusions_since(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ForcedInclusionProcessedRow>> {
+        let sql = format!(
+            "SELECT blob_hash FROM ?.{prefix}forced_inclusion_processed \
+             WHERE inserted_at > toDateTime64(?, 3) \
+             ORDER BY inserted_at ASC",
+            prefix = self.table_prefix
+        );
+
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .bind(since.timestamp_millis() as f64 / 1000.0)
-            .fetch_all::<ForcedInclusionProcessedRow>()
+        let result = self
+            .run(&sql, |client| {
+                client
+                    .query(&sql)
+                    .bind(Identifier(&self.db_name))
+                    .bind(since.timestamp_millis() as f64 / 1000.0)
+                    .fetch_all::<ForcedInclusionProcessedRow>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
         let rows = result.context("fetching forced inclusion events failed")?;
         Ok(rows)
@@ -513,27 +1332,32 @@ impl ClickhouseReader {
         since: DateTime<Utc>,
         until: DateTime<Utc>,
     ) -> Result<Vec<ForcedInclusionProcessedRow>> {
-        let client = self.base.clone();
-        let sql = "SELECT blob_hash FROM ?.forced_inclusion_processed \
+        let sql = format!(
+            "SELECT blob_hash FROM ?.{prefix}forced_inclusion_processed \
              WHERE inserted_at > toDateTime64(?, 3) \
                AND inserted_at <= toDateTime64(?, 3) \
-             ORDER BY inserted_at ASC";
+             ORDER BY inserted_at ASC",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .bind(since.timestamp_millis() as f64 / 1000.0)
-            .bind(until.timestamp_millis() as f64 / 1000.0)
-            .fetch_all::<ForcedInclusionProcessedRow>()
+        let result = self
+            .run(&sql, |client| {
+                client
+                    .query(&sql)
+                    .bind(Identifier(&self.db_name))
+                    .bind(since.timestamp_millis() as f64 / 1000.0)
+                    .bind(until.timestamp_millis() as f64 / 1000.0)
+                    .fetch_all::<ForcedInclusionProcessedRow>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
         let rows = result.context("fetching forced inclusion events failed")?;
         Ok(rows)
@@ -559,8 +1383,8 @@ impl ClickhouseReader {
             "SELECT b.batch_id, h.sequencer AS original_sequencer, \
                     b.proposer_addr AS proposer, b.l1_block_number, \
                     toUInt64(toUnixTimestamp64Milli(b.inserted_at)) AS ts \
-             FROM {db}.batches b \
-             INNER JOIN {db}.l2_head_events h \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l2_head_events h \
                ON h.l2_block_number = b.last_l2_block_number \
              WHERE b.inserted_at > toDateTime64({since}, 3) \
                AND {rf} \
@@ -568,6 +1392,7 @@ impl ClickhouseReader {
                    != transform(lower(concat('0x', hex(b.proposer_addr))), {addr_arr}, {name_arr}, lower(concat('0x', hex(b.proposer_addr)))) \
              ORDER BY b.inserted_at ASC",
             db = self.db_name,
+            prefix = self.table_prefix,
             since = since.timestamp_millis() as f64 / 1000.0,
             rf = rf,
             addr_arr = addr_arr,
@@ -611,8 +1436,8 @@ impl ClickhouseReader {
             "SELECT b.batch_id, h.sequencer AS original_sequencer, \
                     b.proposer_addr AS proposer, b.l1_block_number, \
                     toUInt64(toUnixTimestamp64Milli(b.inserted_at)) AS ts \
-             FROM {db}.batches b \
-             INNER JOIN {db}.l2_head_events h \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l2_head_events h \
                ON h.l2_block_number = b.last_l2_block_number \
              WHERE b.inserted_at > toDateTime64({since}, 3) \
                AND b.inserted_at <= toDateTime64({until}, 3) \
@@ -621,6 +1446,7 @@ impl ClickhouseReader {
                    != transform(lower(concat('0x', hex(b.proposer_addr))), {addr_arr}, {name_arr}, lower(concat('0x', hex(b.proposer_addr)))) \
              ORDER BY b.inserted_at ASC",
             db = self.db_name,
+            prefix = self.table_prefix,
             since = since.timestamp_millis() as f64 / 1000.0,
             until = until.timestamp_millis() as f64 / 1000.0,
             rf = rf,
@@ -669,13 +1495,14 @@ impl ClickhouseReader {
             "SELECT b.batch_id, h.sequencer AS original_sequencer, \
                     b.proposer_addr AS proposer, b.l1_block_number, \
                     toUInt64(toUnixTimestamp64Milli(b.inserted_at)) AS ts \
-             FROM {db}.batches b \
-             INNER JOIN {db}.l2_head_events h \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l2_head_events h \
                ON h.l2_block_number = b.last_l2_block_number \
              WHERE {rf} \
                AND transform(lower(concat('0x', hex(h.sequencer))), {addr_arr}, {name_arr}, lower(concat('0x', hex(h.sequencer)))) \
                    != transform(lower(concat('0x', hex(b.proposer_addr))), {addr_arr}, {name_arr}, lower(concat('0x', hex(b.proposer_addr))))",
             db = self.db_name,
+            prefix = self.table_prefix,
             rf = rf,
             addr_arr = addr_arr,
             name_arr = name_arr,
@@ -746,16 +1573,18 @@ impl ClickhouseReader {
             depth: u16,
             old_sequencer: AddressBytes,
             new_sequencer: AddressBytes,
+            probable_cause: u8,
             ts: u64,
         }
 
         let query = format!(
-            "SELECT l2_block_number, depth, old_sequencer, new_sequencer, \
+            "SELECT l2_block_number, depth, old_sequencer, new_sequencer, probable_cause, \
                     toUInt64(toUnixTimestamp64Milli(inserted_at)) AS ts \
-             FROM {}.l2_reorgs \
+             FROM {}.{}l2_reorgs \
              WHERE inserted_at > toDateTime64({}, 3) \
              ORDER BY inserted_at ASC",
             self.db_name,
+            self.table_prefix,
             since.timestamp_millis() as f64 / 1000.0,
         );
         let rows = self.execute::<RawRow>(&query).await.context("fetching reorg events failed")?;
@@ -768,12 +1597,73 @@ impl ClickhouseReader {
                     depth: r.depth,
                     old_sequencer: r.old_sequencer,
                     new_sequencer: r.new_sequencer,
+                    probable_cause: r.probable_cause,
                     inserted_at: ts,
                 })
             })
             .collect())
     }
 
+    /// Get the number of L2 reorgs per depth recorded within the given bounds
+    pub async fn get_reorg_depth_histogram(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<ReorgDepthCountRow>> {
+        let query = format!(
+            "SELECT depth, count() AS count \
+             FROM {db}.{prefix}l2_reorgs \
+             WHERE inserted_at > toDateTime64({since}, 3) \
+               AND inserted_at <= toDateTime64({until}, 3) \
+             GROUP BY depth \
+             ORDER BY depth ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp_millis() as f64 / 1000.0,
+            until = until.timestamp_millis() as f64 / 1000.0,
+        );
+
+        self.execute::<ReorgDepthCountRow>(&query).await
+    }
+
+    /// Get reorg, orphaned block and observed L2 block totals within the given bounds
+    pub async fn get_reorg_totals(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<ReorgTotalsRow> {
+        let range = format!(
+            "inserted_at > toDateTime64({since}, 3) AND inserted_at <= toDateTime64({until}, 3)",
+            since = since.timestamp_millis() as f64 / 1000.0,
+            until = until.timestamp_millis() as f64 / 1000.0,
+        );
+        let query = format!(
+            "SELECT r.reorgs AS reorgs, \
+                    r.first_reorg_ms AS first_reorg_ms, \
+                    r.last_reorg_ms AS last_reorg_ms, \
+                    (SELECT count() FROM {db}.{prefix}orphaned_l2_hashes \
+                     WHERE {range}) AS orphaned_blocks, \
+                    (SELECT uniqExact(l2_block_number) FROM {db}.{prefix}l2_head_events \
+                     WHERE {range}) AS l2_blocks \
+             FROM (SELECT count() AS reorgs, \
+                          toUInt64(toUnixTimestamp64Milli(min(inserted_at))) AS first_reorg_ms, \
+                          toUInt64(toUnixTimestamp64Milli(max(inserted_at))) AS last_reorg_ms \
+                   FROM {db}.{prefix}l2_reorgs \
+                   WHERE {range}) r",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<ReorgTotalsRow>(&query).await?;
+        Ok(rows.into_iter().next().unwrap_or(ReorgTotalsRow {
+            reorgs: 0,
+            first_reorg_ms: 0,
+            last_reorg_ms: 0,
+            orphaned_blocks: 0,
+            l2_blocks: 0,
+        }))
+    }
+
     /// Get L2 reorg events since the given cutoff with cursor-based pagination.
     /// Results are returned in descending order by time recorded.
     pub async fn get_l2_reorgs_paginated(
@@ -784,22 +1674,41 @@ impl ClickhouseReader {
         starting_after: Option<u64>,
         ending_before: Option<u64>,
     ) -> Result<Vec<L2ReorgRow>> {
-        #[derive(Row, Deserialize)]
-        struct RawRow {
-            l2_block_number: u64,
-            depth: u16,
-            old_sequencer: AddressBytes,
-            new_sequencer: AddressBytes,
-            ts: u64,
-        }
+        let query = self.l2_reorgs_page_query(since, until, limit, starting_after, ending_before);
+        let rows =
+            self.execute::<RawReorgRow>(&query).await.context("fetching reorg events failed")?;
+        Ok(rows.into_iter().filter_map(RawReorgRow::into_row).collect())
+    }
 
+    /// [`Self::get_l2_reorgs_paginated`], streaming the rows as they are read
+    pub async fn stream_l2_reorgs_paginated(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: u64,
+        starting_after: Option<u64>,
+        ending_before: Option<u64>,
+    ) -> Result<RowStream<L2ReorgRow>> {
+        let query = self.l2_reorgs_page_query(since, until, limit, starting_after, ending_before);
+        self.stream(query, RawReorgRow::into_row).await
+    }
+
+    fn l2_reorgs_page_query(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: u64,
+        starting_after: Option<u64>,
+        ending_before: Option<u64>,
+    ) -> String {
         let mut query = format!(
-            "SELECT l2_block_number, depth, old_sequencer, new_sequencer, \
+            "SELECT l2_block_number, depth, old_sequencer, new_sequencer, probable_cause, \
                     toUInt64(toUnixTimestamp64Milli(inserted_at)) AS ts \
-             FROM {db}.l2_reorgs \
+             FROM {db}.{prefix}l2_reorgs \
              WHERE inserted_at > toDateTime64({since}, 3) \
                AND inserted_at <= toDateTime64({until}, 3)",
             db = self.db_name,
+            prefix = self.table_prefix,
             since = since.timestamp_millis() as f64 / 1000.0,
             until = until.timestamp_millis() as f64 / 1000.0,
         );
@@ -813,21 +1722,7 @@ impl ClickhouseReader {
 
         query.push_str(" ORDER BY inserted_at DESC");
         query.push_str(&format!(" LIMIT {}", limit));
-
-        let rows = self.execute::<RawRow>(&query).await.context("fetching reorg events failed")?;
-        Ok(rows
-            .into_iter()
-            .filter_map(|r| {
-                let ts = Utc.timestamp_millis_opt(r.ts as i64).single()?;
-                Some(L2ReorgRow {
-                    l2_block_number: r.l2_block_number,
-                    depth: r.depth,
-                    old_sequencer: r.old_sequencer,
-                    new_sequencer: r.new_sequencer,
-                    inserted_at: ts,
-                })
-            })
-            .collect())
+        query
     }
 
     /// Get all active gateway addresses observed since the given cutoff time
@@ -842,24 +1737,29 @@ impl ClickhouseReader {
             next_operator: Option<AddressBytes>,
         }
 
-        let client = self.base.clone();
-        let sql = "SELECT candidates, current_operator, next_operator FROM ?.preconf_data \
-             WHERE inserted_at > toDateTime64(?, 3)";
+        let sql = format!(
+            "SELECT candidates, current_operator, next_operator FROM ?.{prefix}preconf_data \
+             WHERE inserted_at > toDateTime64(?, 3)",
+            prefix = self.table_prefix
+        );
 
         let start = Instant::now();
-        let result = client
-            .query(sql)
-            .bind(Identifier(&self.db_name))
-            .bind(since.timestamp_millis() as f64 / 1000.0)
-            .fetch_all::<GatewayRow>()
+        let result = self
+            .run(&sql, |client| {
+                client
+                    .query(&sql)
+                    .bind(Identifier(&self.db_name))
+                    .bind(since.timestamp_millis() as f64 / 1000.0)
+                    .fetch_all::<GatewayRow>()
+            })
             .await;
 
         let duration_ms = start.elapsed().as_millis();
         match &result {
             Ok(rows) => {
-                debug!(query = sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
+                debug!(query = %sql, duration_ms, rows = rows.len(), "ClickHouse query executed")
             }
-            Err(e) => error!(query = sql, duration_ms, error = %e, "ClickHouse query failed"),
+            Err(e) => error!(query = %sql, duration_ms, error = %e, "ClickHouse query failed"),
         }
 
         let rows = result?;
@@ -889,7 +1789,7 @@ impl ClickhouseReader {
                    toUInt64(min(h.block_ts)) AS min_ts,\n\
                    toUInt64(max(h.block_ts)) AS max_ts,\n\
                    sum(sum_tx) AS tx_sum\n\
-             FROM {db}.l2_head_events h\n\
+             FROM {db}.{prefix}l2_head_events h\n\
              WHERE h.block_ts > {since}\n\
                AND {filter}\n\
              GROUP BY sequencer\n\
@@ -897,6 +1797,7 @@ impl ClickhouseReader {
             since = since.timestamp(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<SequencerDistributionRow>(&query).await?;
@@ -910,13 +1811,14 @@ impl ClickhouseReader {
     ) -> Result<Vec<SequencerBlockRow>> {
         let query = format!(
             "SELECT sequencer, h.l2_block_number \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts > {} \
                AND {filter} \
              ORDER BY sequencer, h.l2_block_number ASC",
             since.timestamp(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<SequencerBlockRow>(&query).await?;
@@ -931,7 +1833,7 @@ impl ClickhouseReader {
     ) -> Result<Vec<SequencerBlocksGrouped>> {
         let query = format!(
             "SELECT sequencer, groupArray(h.l2_block_number) as blocks \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts > {} \
                AND {filter} \
              GROUP BY sequencer \
@@ -939,6 +1841,7 @@ impl ClickhouseReader {
             since.timestamp(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<SequencerBlocksGrouped>(&query).await?;
@@ -960,48 +1863,15 @@ impl ClickhouseReader {
 
         if bucket <= 1 {
             // Non-bucketed implementation
-            #[derive(Row, Deserialize)]
-            struct RawRow {
-                sequencer: AddressBytes,
-                l2_block_number: u64,
-                block_time: u64,
-                sum_tx: u32,
-            }
-
-            let mut query = format!(
-                "SELECT sequencer, h.l2_block_number, h.block_ts AS block_time, sum_tx \
-                 FROM {db}.l2_head_events h \
-                 WHERE h.block_ts >= {} \
-                   AND {filter}",
-                since.timestamp(),
-                filter = self.reorg_filter("h"),
-                db = self.db_name,
+            let query = self.block_transactions_page_query(
+                since,
+                limit,
+                starting_after,
+                ending_before,
+                sequencer,
             );
-            if let Some(addr) = sequencer {
-                query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
-            }
-
-            if let Some(start) = starting_after {
-                query.push_str(&format!(" AND l2_block_number < {}", start));
-            }
-
-            if let Some(end) = ending_before {
-                query.push_str(&format!(" AND l2_block_number > {}", end));
-            }
-
-            query.push_str(" ORDER BY l2_block_number DESC");
-            query.push_str(&format!(" LIMIT {}", limit));
-
-            let rows = self.execute::<RawRow>(&query).await?;
-            return Ok(rows
-                .into_iter()
-                .map(|r| BlockTransactionRow {
-                    sequencer: r.sequencer,
-                    l2_block_number: r.l2_block_number,
-                    block_time: Utc.timestamp_opt(r.block_time as i64, 0).unwrap(),
-                    sum_tx: r.sum_tx,
-                })
-                .collect());
+            let rows = self.execute::<RawBlockTransactionRow>(&query).await?;
+            return Ok(rows.into_iter().filter_map(RawBlockTransactionRow::into_row).collect());
         }
 
         // Bucketed implementation using SQL aggregation
@@ -1015,12 +1885,13 @@ impl ClickhouseReader {
 
         let mut inner = format!(
             "SELECT sequencer, h.l2_block_number, h.block_ts AS block_time, sum_tx \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts >= {} \
                AND {filter}",
             since.timestamp(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             inner.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -1087,10 +1958,11 @@ impl ClickhouseReader {
 
         let mut query = format!(
             "SELECT sequencer, h.l2_block_number, h.block_ts AS block_time, sum_tx \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE {filter}",
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         if let Some(start) = start_block {
@@ -1128,6 +2000,61 @@ impl ClickhouseReader {
             .collect())
     }
 
+    /// [`Self::get_block_transactions_paginated`] without bucketing, streaming the rows as they
+    /// are read
+    pub async fn stream_block_transactions_paginated(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+        starting_after: Option<u64>,
+        ending_before: Option<u64>,
+        sequencer: Option<AddressBytes>,
+    ) -> Result<RowStream<BlockTransactionRow>> {
+        let query = self.block_transactions_page_query(
+            since,
+            limit,
+            starting_after,
+            ending_before,
+            sequencer,
+        );
+        self.stream(query, RawBlockTransactionRow::into_row).await
+    }
+
+    fn block_transactions_page_query(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+        starting_after: Option<u64>,
+        ending_before: Option<u64>,
+        sequencer: Option<AddressBytes>,
+    ) -> String {
+        let mut query = format!(
+            "SELECT sequencer, h.l2_block_number, h.block_ts AS block_time, sum_tx \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.block_ts >= {} \
+               AND {filter}",
+            since.timestamp(),
+            filter = self.reorg_filter("h"),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        if let Some(addr) = sequencer {
+            query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
+        }
+
+        if let Some(start) = starting_after {
+            query.push_str(&format!(" AND l2_block_number < {}", start));
+        }
+
+        if let Some(end) = ending_before {
+            query.push_str(&format!(" AND l2_block_number > {}", end));
+        }
+
+        query.push_str(" ORDER BY l2_block_number DESC");
+        query.push_str(&format!(" LIMIT {}", limit));
+        query
+    }
+
     /// Get L2 block times since the given cutoff with cursor-based pagination.
     /// Results are returned in descending order by block number.
     pub async fn get_l2_block_times_paginated(
@@ -1138,13 +2065,34 @@ impl ClickhouseReader {
         ending_before: Option<u64>,
         sequencer: Option<AddressBytes>,
     ) -> Result<Vec<L2BlockTimeRow>> {
-        #[derive(Row, Deserialize)]
-        struct RawRow {
-            l2_block_number: u64,
-            block_time: u64,
-            s_since_prev_block: Option<u64>,
-        }
+        let query =
+            self.l2_block_times_page_query(since, limit, starting_after, ending_before, sequencer);
+        let rows = self.execute::<RawBlockTimeRow>(&query).await?;
+        Ok(rows.into_iter().filter_map(RawBlockTimeRow::into_row).collect())
+    }
+
+    /// [`Self::get_l2_block_times_paginated`], streaming the rows as they are read
+    pub async fn stream_l2_block_times_paginated(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+        starting_after: Option<u64>,
+        ending_before: Option<u64>,
+        sequencer: Option<AddressBytes>,
+    ) -> Result<RowStream<L2BlockTimeRow>> {
+        let query =
+            self.l2_block_times_page_query(since, limit, starting_after, ending_before, sequencer);
+        self.stream(query, RawBlockTimeRow::into_row).await
+    }
 
+    fn l2_block_times_page_query(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+        starting_after: Option<u64>,
+        ending_before: Option<u64>,
+        sequencer: Option<AddressBytes>,
+    ) -> String {
         let mut query = format!(
             "WITH time_diffs AS ( \
                 SELECT h.l2_block_number, \
@@ -1152,7 +2100,7 @@ impl ClickhouseReader {
                        h.sequencer, \
                        toUInt64OrNull(toString(if(isNull(lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)), NULL, h.block_ts - lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)))) \
                            AS s_since_prev_block \
-                FROM {db}.l2_head_events h \
+                FROM {db}.{prefix}l2_head_events h \
                 WHERE {filter} \
             ) \
             SELECT l2_block_number, block_time, s_since_prev_block \
@@ -1161,6 +2109,7 @@ impl ClickhouseReader {
             since = since.timestamp(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -1173,19 +2122,7 @@ impl ClickhouseReader {
         }
         query.push_str(" ORDER BY l2_block_number DESC");
         query.push_str(&format!(" LIMIT {}", limit));
-
-        let rows = self.execute::<RawRow>(&query).await?;
-        Ok(rows
-            .into_iter()
-            .filter_map(|r| {
-                let dt = Utc.timestamp_opt(r.block_time as i64, 0).single()?;
-                r.s_since_prev_block.map(|s| L2BlockTimeRow {
-                    l2_block_number: r.l2_block_number,
-                    block_time: dt,
-                    s_since_prev_block: s,
-                })
-            })
-            .collect())
+        query
     }
 
     /// Get L2 gas usage since the given cutoff with cursor-based pagination.
@@ -1207,12 +2144,13 @@ impl ClickhouseReader {
 
         let mut query = format!(
             "SELECT h.l2_block_number, h.block_ts AS block_time, toUInt64(sum_gas_used) AS gas_used \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts >= {since} \
                AND {filter}",
             since = since.timestamp(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -1260,12 +2198,13 @@ impl ClickhouseReader {
         let mut query = format!(
             "SELECT h.l2_block_number, sum_tx, \
                     toUInt64OrNull(toString(if(isNull(lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)), NULL, h.block_ts - lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)))) AS s_since_prev_block \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts >= {since} \
                AND {filter}",
             since = since.timestamp(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -1290,6 +2229,7 @@ impl ClickhouseReader {
                     Some(L2TpsRow {
                         l2_block_number: r.l2_block_number,
                         tps: r.sum_tx as f64 / s as f64,
+                        block_time: None,
                     })
                 }
             })
@@ -1307,11 +2247,12 @@ impl ClickhouseReader {
         // First try the materialized view
         let mv_query = format!(
             "SELECT avg(prove_time_ms) AS avg_ms \
-             FROM {db}.batch_prove_times_mv \
+             FROM {db}.{prefix}batch_prove_times_mv \
              WHERE proved_at >= now64() - INTERVAL {interval} \
                AND batch_id != 0",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<AvgRow>(&mv_query).await?;
@@ -1324,16 +2265,17 @@ impl ClickhouseReader {
         // Fallback to raw data if materialized view is empty
         let fallback_query = format!(
             "SELECT avg((l1_proved.block_ts - l1_proposed.block_ts) * 1000) AS avg_ms \
-             FROM {db}.batches b \
-             JOIN {db}.proved_batches pb ON b.batch_id = pb.batch_id \
-             JOIN {db}.l1_head_events l1_proposed \
+             FROM {db}.{prefix}batches b \
+             JOIN {db}.{prefix}proved_batches pb ON b.batch_id = pb.batch_id \
+             JOIN {db}.{prefix}l1_head_events l1_proposed \
                ON b.l1_block_number = l1_proposed.l1_block_number \
-             JOIN {db}.l1_head_events l1_proved \
+             JOIN {db}.{prefix}l1_head_events l1_proved \
                ON pb.l1_block_number = l1_proved.l1_block_number \
              WHERE l1_proved.block_ts >= (toUInt64(now()) - {secs}) \
                AND b.batch_id != 0",
             secs = range.seconds(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<AvgRow>(&fallback_query).await?;
@@ -1356,11 +2298,12 @@ impl ClickhouseReader {
         // First try the materialized view
         let mv_query = format!(
             "SELECT avg(verify_time_ms) AS avg_ms \
-             FROM {db}.batch_verify_times_mv \
+             FROM {db}.{prefix}batch_verify_times_mv \
              WHERE verified_at >= now64() - INTERVAL {interval} \
                AND batch_id != 0",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<AvgRow>(&mv_query).await?;
@@ -1373,19 +2316,20 @@ impl ClickhouseReader {
         // Fallback to raw data if materialized view is empty
         let fallback_query = format!(
             "SELECT avg((l1_verified.block_ts - l1_proved.block_ts) * 1000) AS avg_ms \
-             FROM {db}.proved_batches pb \
-             INNER JOIN {db}.verified_batches vb \
+             FROM {db}.{prefix}proved_batches pb \
+             INNER JOIN {db}.{prefix}verified_batches vb \
                 ON pb.batch_id = vb.batch_id AND pb.block_hash = vb.block_hash \
-             INNER JOIN {db}.l1_head_events l1_proved \
+             INNER JOIN {db}.{prefix}l1_head_events l1_proved \
                 ON pb.l1_block_number = l1_proved.l1_block_number \
-             INNER JOIN {db}.l1_head_events l1_verified \
+             INNER JOIN {db}.{prefix}l1_head_events l1_verified \
                 ON vb.l1_block_number = l1_verified.l1_block_number \
              WHERE l1_verified.block_ts >= (toUInt64(now()) - {}) \
                AND l1_verified.block_ts > l1_proved.block_ts \
                AND (l1_verified.block_ts - l1_proved.block_ts) > 60 \
                AND pb.batch_id != 0",
             range.seconds(),
-            db = self.db_name
+            db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<AvgRow>(&fallback_query).await?;
@@ -1397,6 +2341,98 @@ impl ClickhouseReader {
         if row.avg_ms.is_nan() { Ok(None) } else { Ok(Some(row.avg_ms.round() as u64)) }
     }
 
+    /// Get the daily p50/p90/p99 and maximum time in milliseconds it takes for a batch to be
+    /// proven, for proofs submitted within the given time range
+    pub async fn get_prove_time_percentiles(
+        &self,
+        range: TimeRange,
+    ) -> Result<Vec<DailyTimePercentilesRow>> {
+        self.get_daily_time_percentiles("batch_prove_times_mv", "prove_time_ms", "proved_at", range)
+            .await
+    }
+
+    /// Get the distribution of the time in milliseconds it takes for a batch to be proven per
+    /// proof type, for proofs submitted within the given time range. Batches proved several
+    /// times count once per proof.
+    pub async fn get_prove_times_by_proof_type(
+        &self,
+        range: TimeRange,
+    ) -> Result<Vec<ProofTypeProveTimeRow>> {
+        let query = format!(
+            "SELECT proof_type, \
+                    count() AS batches, \
+                    toUInt64(avg(prove_time_ms)) AS avg_ms, \
+                    toUInt64(quantileExact(0.5)(prove_time_ms)) AS p50_ms, \
+                    toUInt64(quantileExact(0.9)(prove_time_ms)) AS p90_ms, \
+                    toUInt64(quantileExact(0.99)(prove_time_ms)) AS p99_ms, \
+                    max(prove_time_ms) AS max_ms \
+             FROM ( \
+                SELECT pb.proof_type AS proof_type, \
+                       (l1_proved.block_ts - l1_proposed.block_ts) * 1000 AS prove_time_ms \
+                FROM {db}.{prefix}proved_batches pb FINAL \
+                INNER JOIN {db}.{prefix}batches b ON pb.batch_id = b.batch_id \
+                INNER JOIN {db}.{prefix}l1_head_events l1_proposed \
+                   ON b.l1_block_number = l1_proposed.l1_block_number \
+                INNER JOIN {db}.{prefix}l1_head_events l1_proved \
+                   ON pb.l1_block_number = l1_proved.l1_block_number \
+                WHERE l1_proved.block_ts >= (toUInt64(now()) - {secs}) \
+                  AND pb.batch_id != 0 \
+                  AND l1_proved.block_ts > l1_proposed.block_ts \
+             ) \
+             GROUP BY proof_type \
+             ORDER BY proof_type ASC",
+            secs = range.seconds(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<ProofTypeProveTimeRow>(&query).await
+    }
+
+    /// Get the daily p50/p90/p99 and maximum time in milliseconds it takes for a batch to be
+    /// verified, for verifications submitted within the given time range
+    pub async fn get_verify_time_percentiles(
+        &self,
+        range: TimeRange,
+    ) -> Result<Vec<DailyTimePercentilesRow>> {
+        self.get_daily_time_percentiles(
+            "batch_verify_times_mv",
+            "verify_time_ms",
+            "verified_at",
+            range,
+        )
+        .await
+    }
+
+    /// Daily percentiles of the `value` column of a batch timing view, bucketed by the `ts`
+    /// column
+    async fn get_daily_time_percentiles(
+        &self,
+        view: &str,
+        value: &str,
+        ts: &str,
+        range: TimeRange,
+    ) -> Result<Vec<DailyTimePercentilesRow>> {
+        let query = format!(
+            "SELECT toUInt64(toUnixTimestamp(toStartOfDay({ts}))) AS day_ts, \
+                    count() AS batches, \
+                    toUInt64(quantileExact(0.5)({value})) AS p50_ms, \
+                    toUInt64(quantileExact(0.9)({value})) AS p90_ms, \
+                    toUInt64(quantileExact(0.99)({value})) AS p99_ms, \
+                    max({value}) AS max_ms \
+             FROM {db}.{prefix}{view} \
+             WHERE {ts} >= now64() - INTERVAL {interval} \
+               AND batch_id != 0 \
+             GROUP BY day_ts \
+             ORDER BY day_ts ASC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<DailyTimePercentilesRow>(&query).await
+    }
+
     /// Get the average interval in milliseconds between consecutive L2 blocks
     /// observed within the given range.
     pub async fn get_l2_block_cadence(
@@ -1415,12 +2451,13 @@ impl ClickhouseReader {
             "SELECT toUInt64(min(h.block_ts) * 1000) AS min_ts, \
                     toUInt64(max(h.block_ts) * 1000) AS max_ts, \
                     count() as cnt \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                AND {filter}",
             interval = range.interval(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -1453,12 +2490,13 @@ impl ClickhouseReader {
             "SELECT toUInt64(min(l1_events.block_ts) * 1000) AS min_ts, \
                     toUInt64(max(l1_events.block_ts) * 1000) AS max_ts, \
                     count() as cnt \
-             FROM {db}.batches b \
-             INNER JOIN {db}.l1_head_events l1_events \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l1_head_events l1_events \
                ON b.l1_block_number = l1_events.l1_block_number \
              WHERE l1_events.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval})",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<CadenceRow>(&query).await?;
@@ -1474,6 +2512,54 @@ impl ClickhouseReader {
         }
     }
 
+    /// Get the proposal and verification progress of batches within the given range, used to
+    /// forecast when the proposer hits the inbox limits.
+    pub async fn get_batch_cadence(&self, range: TimeRange) -> Result<BatchCadenceRow> {
+        let since = format!("toUnixTimestamp(now64() - INTERVAL {})", range.interval());
+        let query = format!(
+            "SELECT p.last_id AS last_proposed_batch_id, \
+                    p.in_window AS proposed_batches, \
+                    v.last_id AS last_verified_batch_id, \
+                    if(v.before_id > 0 OR v.first_id = 0, v.last_id - v.before_id, \
+                       v.last_id - v.first_id) AS verified_batches, \
+                    a.max_age AS max_anchor_age \
+             FROM ( \
+                 SELECT max(b.batch_id) AS last_id, \
+                        uniqExactIf(b.batch_id, l1.block_ts >= {since}) AS in_window \
+                 FROM {db}.{prefix}batches b \
+                 INNER JOIN {db}.{prefix}l1_head_events l1 \
+                   ON b.l1_block_number = l1.l1_block_number \
+             ) p \
+             CROSS JOIN ( \
+                 SELECT max(vb.batch_id) AS last_id, \
+                        maxIf(vb.batch_id, l1.block_ts < {since}) AS before_id, \
+                        minIf(vb.batch_id, l1.block_ts >= {since}) AS first_id \
+                 FROM {db}.{prefix}verified_batches vb \
+                 INNER JOIN {db}.{prefix}l1_head_events l1 \
+                   ON vb.l1_block_number = l1.l1_block_number \
+             ) v \
+             CROSS JOIN ( \
+                 SELECT maxOrNull(age) AS max_age FROM ( \
+                     SELECT b.batch_id, \
+                            toUInt64(any(b.l1_block_number) - min(a.anchor_block_id)) AS age \
+                     FROM {db}.{prefix}batches b \
+                     INNER JOIN {db}.{prefix}l1_head_events l1 \
+                       ON b.l1_block_number = l1.l1_block_number \
+                     INNER JOIN {db}.{prefix}batch_blocks bb ON bb.batch_id = b.batch_id \
+                     INNER JOIN {db}.{prefix}l2_anchor_blocks a \
+                       ON a.l2_block_number = bb.l2_block_number \
+                     WHERE l1.block_ts >= {since} AND a.anchor_block_id <= b.l1_block_number \
+                     GROUP BY b.batch_id \
+                 ) \
+             ) a",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<BatchCadenceRow>(&query).await?;
+        Ok(rows.into_iter().next().unwrap_or_default())
+    }
+
     /// Get the interval in milliseconds between consecutive batch proposals
     /// observed within the given range.
     pub async fn get_batch_posting_times(
@@ -1495,8 +2581,8 @@ impl ClickhouseReader {
                         toUInt64(l1_events.block_ts * 1000) AS ts, \
                         lagInFrame(toNullable(toUInt64(l1_events.block_ts * 1000))) \
                             OVER (ORDER BY l1_events.block_ts, b.batch_id) AS prev_ts \
-                   FROM {db}.batches b \
-                   INNER JOIN {db}.l1_head_events l1_events \
+                   FROM {db}.{prefix}batches b \
+                   INNER JOIN {db}.{prefix}l1_head_events l1_events \
                      ON b.l1_block_number = l1_events.l1_block_number \
                   WHERE l1_events.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                   ORDER BY l1_events.block_ts, b.batch_id \
@@ -1505,6 +2591,7 @@ impl ClickhouseReader {
              ORDER BY ts",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<RawRow>(&query).await?;
@@ -1546,8 +2633,8 @@ impl ClickhouseReader {
                         toUInt64(l1_events.block_ts * 1000) AS ts, \
                         lagInFrame(toNullable(toUInt64(l1_events.block_ts * 1000))) \
                             OVER (ORDER BY l1_events.block_ts, b.batch_id) AS prev_ts \
-                   FROM {db}.batches b \
-                   INNER JOIN {db}.l1_head_events l1_events \
+                   FROM {db}.{prefix}batches b \
+                   INNER JOIN {db}.{prefix}l1_head_events l1_events \
                      ON b.l1_block_number = l1_events.l1_block_number \
                   WHERE l1_events.block_ts >= {since} \
                   ORDER BY l1_events.block_ts, b.batch_id \
@@ -1555,6 +2642,7 @@ impl ClickhouseReader {
              WHERE prev_ts IS NOT NULL",
             since = since.timestamp(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(start) = starting_after {
             // For descending order we fetch records with id less than the
@@ -1584,47 +2672,60 @@ impl ClickhouseReader {
     }
 
     /// Get prove times in seconds for batches proved within the given range
+    ///
+    /// With `as_of`, the range ends at that moment and only rows inserted by then are used. The
+    /// prove time view does not record insertion times, so such queries read the base tables.
     pub async fn get_prove_times(
         &self,
         range: TimeRange,
         bucket: Option<u64>,
+        as_of: Option<DateTime<Utc>>,
     ) -> Result<Vec<BatchProveTimeRow>> {
         let bucket = bucket.unwrap_or(1);
+        let joins = format!(
+            "FROM {batches} b \
+             JOIN {proved_batches} pb ON b.batch_id = pb.batch_id \
+             JOIN {l1_head_events} l1_proposed \
+               ON b.l1_block_number = l1_proposed.l1_block_number \
+             JOIN {l1_head_events} l1_proved \
+               ON pb.l1_block_number = l1_proved.l1_block_number \
+             WHERE l1_proved.block_ts >= (toUnixTimestamp({now}) - {secs}) \
+               AND b.batch_id != 0",
+            batches = self.table_as_of("batches", as_of),
+            proved_batches = self.table_as_of("proved_batches", as_of),
+            l1_head_events = self.table_as_of("l1_head_events", as_of),
+            now = now_as_of(as_of),
+            secs = range.seconds(),
+        );
 
         if bucket <= 1 {
             // Non-bucketed implementation
             let mv_query = format!(
                 "SELECT batch_id, toUInt64(prove_time_ms / 1000) AS seconds_to_prove \
-                 FROM {db}.batch_prove_times_mv \
+                 FROM {db}.{prefix}batch_prove_times_mv \
                  WHERE proved_at >= now64() - INTERVAL {interval} \
                    AND batch_id != 0 \
                  ORDER BY batch_id ASC",
                 interval = range.interval(),
                 db = self.db_name,
+                prefix = self.table_prefix,
             );
 
-            let rows = self.execute::<BatchProveTimeRow>(&mv_query).await?;
-            if !rows.is_empty() {
-                return Ok(rows);
+            if as_of.is_none() {
+                let rows = self.execute::<BatchProveTimeRow>(&mv_query).await?;
+                if !rows.is_empty() {
+                    return Ok(rows);
+                }
             }
 
             let fallback_query = format!(
                 "SELECT toUInt64(b.batch_id) AS batch_id, \
                         (l1_proved.block_ts - l1_proposed.block_ts) AS seconds_to_prove \
-                 FROM {db}.batches b \
-                 JOIN {db}.proved_batches pb ON b.batch_id = pb.batch_id \
-                 JOIN {db}.l1_head_events l1_proposed \
-                   ON b.l1_block_number = l1_proposed.l1_block_number \
-                 JOIN {db}.l1_head_events l1_proved \
-                   ON pb.l1_block_number = l1_proved.l1_block_number \
-                 WHERE l1_proved.block_ts >= (toUInt64(now()) - {secs}) \
-                   AND b.batch_id != 0 \
-                 ORDER BY b.batch_id ASC",
-                secs = range.seconds(),
-                db = self.db_name,
+                 {joins} \
+                 ORDER BY b.batch_id ASC"
             );
 
-            let rows = self.execute::<BatchProveTimeRow>(&fallback_query).await?;
+            let rows = self.execute_as_of::<BatchProveTimeRow>(&fallback_query, as_of).await?;
             return Ok(rows);
         }
 
@@ -1635,7 +2736,7 @@ impl ClickhouseReader {
              FROM ( \
                 SELECT intDiv(batch_id, {bucket}) * {bucket} AS batch_bucket, \
                        toUInt64(prove_time_ms / 1000) AS seconds_to_prove \
-                FROM {db}.batch_prove_times_mv \
+                FROM {db}.{prefix}batch_prove_times_mv \
                 WHERE proved_at >= now64() - INTERVAL {interval} \
                   AND batch_id != 0 \
              ) AS sub \
@@ -1644,11 +2745,14 @@ impl ClickhouseReader {
             bucket = bucket,
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
-        let rows = self.execute::<BatchProveTimeRow>(&mv_query).await?;
-        if !rows.is_empty() {
-            return Ok(rows);
+        if as_of.is_none() {
+            let rows = self.execute::<BatchProveTimeRow>(&mv_query).await?;
+            if !rows.is_empty() {
+                return Ok(rows);
+            }
         }
 
         // Fallback with bucketing
@@ -1658,23 +2762,13 @@ impl ClickhouseReader {
              FROM ( \
                 SELECT intDiv(b.batch_id, {bucket}) * {bucket} AS batch_bucket, \
                        (l1_proved.block_ts - l1_proposed.block_ts) AS seconds_to_prove \
-                FROM {db}.batches b \
-                JOIN {db}.proved_batches pb ON b.batch_id = pb.batch_id \
-                JOIN {db}.l1_head_events l1_proposed \
-                  ON b.l1_block_number = l1_proposed.l1_block_number \
-                JOIN {db}.l1_head_events l1_proved \
-                  ON pb.l1_block_number = l1_proved.l1_block_number \
-                WHERE l1_proved.block_ts >= (toUInt64(now()) - {secs}) \
-                  AND b.batch_id != 0 \
+                {joins} \
              ) AS sub \
              GROUP BY batch_bucket \
-             ORDER BY batch_bucket ASC",
-            bucket = bucket,
-            secs = range.seconds(),
-            db = self.db_name,
+             ORDER BY batch_bucket ASC"
         );
 
-        let rows = self.execute::<BatchProveTimeRow>(&fallback_query).await?;
+        let rows = self.execute_as_of::<BatchProveTimeRow>(&fallback_query, as_of).await?;
         Ok(rows)
     }
 
@@ -1690,13 +2784,14 @@ impl ClickhouseReader {
             // Non-bucketed implementation
             let mv_query = format!(
                 "SELECT batch_id, toUInt64(verify_time_ms / 1000) AS seconds_to_verify \
-                 FROM {db}.batch_verify_times_mv \
+                 FROM {db}.{prefix}batch_verify_times_mv \
                  WHERE verified_at >= now64() - INTERVAL {interval} \
                    AND verify_time_ms > 60000 \
                    AND batch_id != 0 \
                  ORDER BY batch_id ASC",
                 interval = range.interval(),
                 db = self.db_name,
+                prefix = self.table_prefix,
             );
 
             let rows = self.execute::<BatchVerifyTimeRow>(&mv_query).await?;
@@ -1707,19 +2802,20 @@ impl ClickhouseReader {
             let fallback_query = format!(
                 "SELECT toUInt64(pb.batch_id) AS batch_id, \
                         (l1_verified.block_ts - l1_proved.block_ts) AS seconds_to_verify \
-                 FROM {db}.proved_batches pb \
-                 INNER JOIN {db}.verified_batches vb \
+                 FROM {db}.{prefix}proved_batches pb \
+                 INNER JOIN {db}.{prefix}verified_batches vb \
                     ON pb.batch_id = vb.batch_id AND pb.block_hash = vb.block_hash \
-                 INNER JOIN {db}.l1_head_events l1_proved \
+                 INNER JOIN {db}.{prefix}l1_head_events l1_proved \
                     ON pb.l1_block_number = l1_proved.l1_block_number \
-                 INNER JOIN {db}.l1_head_events l1_verified \
+                 INNER JOIN {db}.{prefix}l1_head_events l1_verified \
                     ON vb.l1_block_number = l1_verified.l1_block_number \
                  WHERE l1_verified.block_ts >= (toUInt64(now()) - {}) \
                    AND l1_verified.block_ts > l1_proved.block_ts \
                    AND (l1_verified.block_ts - l1_proved.block_ts) > 60 \
                    AND pb.batch_id != 0",
                 range.seconds(),
-                db = self.db_name
+                db = self.db_name,
+                prefix = self.table_prefix,
             );
 
             let rows = self.execute::<BatchVerifyTimeRow>(&fallback_query).await?;
@@ -1733,7 +2829,7 @@ impl ClickhouseReader {
              FROM ( \
                 SELECT intDiv(batch_id, {bucket}) * {bucket} AS batch_bucket, \
                        toUInt64(verify_time_ms / 1000) AS seconds_to_verify \
-                FROM {db}.batch_verify_times_mv \
+                FROM {db}.{prefix}batch_verify_times_mv \
                 WHERE verified_at >= now64() - INTERVAL {interval} \
                   AND verify_time_ms > 60000 \
                   AND batch_id != 0 \
@@ -1743,6 +2839,7 @@ impl ClickhouseReader {
             bucket = bucket,
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<BatchVerifyTimeRow>(&mv_query).await?;
@@ -1757,12 +2854,12 @@ impl ClickhouseReader {
              FROM ( \
                 SELECT intDiv(pb.batch_id, {bucket}) * {bucket} AS batch_bucket, \
                        (l1_verified.block_ts - l1_proved.block_ts) AS seconds_to_verify \
-                FROM {db}.proved_batches pb \
-                INNER JOIN {db}.verified_batches vb \
+                FROM {db}.{prefix}proved_batches pb \
+                INNER JOIN {db}.{prefix}verified_batches vb \
                    ON pb.batch_id = vb.batch_id AND pb.block_hash = vb.block_hash \
-                INNER JOIN {db}.l1_head_events l1_proved \
+                INNER JOIN {db}.{prefix}l1_head_events l1_proved \
                    ON pb.l1_block_number = l1_proved.l1_block_number \
-                INNER JOIN {db}.l1_head_events l1_verified \
+                INNER JOIN {db}.{prefix}l1_head_events l1_verified \
                    ON vb.l1_block_number = l1_verified.l1_block_number \
                 WHERE l1_verified.block_ts >= (toUInt64(now()) - {secs}) \
                   AND l1_verified.block_ts > l1_proved.block_ts \
@@ -1773,7 +2870,8 @@ impl ClickhouseReader {
              ORDER BY batch_bucket ASC",
             bucket = bucket,
             secs = range.seconds(),
-            db = self.db_name
+            db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<BatchVerifyTimeRow>(&fallback_query).await?;
@@ -1792,12 +2890,13 @@ impl ClickhouseReader {
         // First try the materialized view
         let mut mv_query = format!(
             "SELECT batch_id, toUInt64(verify_time_ms / 1000) AS seconds_to_verify \
-             FROM {db}.batch_verify_times_mv \
+             FROM {db}.{prefix}batch_verify_times_mv \
              WHERE verified_at >= toDateTime64({since}, 3) \
                AND verify_time_ms > 60000 \
                AND batch_id != 0",
             since = since.timestamp(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(start) = starting_after {
             mv_query.push_str(&format!(" AND batch_id < {}", start));
@@ -1817,12 +2916,12 @@ impl ClickhouseReader {
         let mut fallback_query = format!(
             "SELECT toUInt64(pb.batch_id) AS batch_id, \
                     (l1_verified.block_ts - l1_proved.block_ts) AS seconds_to_verify \
-             FROM {db}.proved_batches pb \
-             INNER JOIN {db}.verified_batches vb \
+             FROM {db}.{prefix}proved_batches pb \
+             INNER JOIN {db}.{prefix}verified_batches vb \
                 ON pb.batch_id = vb.batch_id AND pb.block_hash = vb.block_hash \
-             INNER JOIN {db}.l1_head_events l1_proved \
+             INNER JOIN {db}.{prefix}l1_head_events l1_proved \
                 ON pb.l1_block_number = l1_proved.l1_block_number \
-             INNER JOIN {db}.l1_head_events l1_verified \
+             INNER JOIN {db}.{prefix}l1_head_events l1_verified \
                 ON vb.l1_block_number = l1_verified.l1_block_number \
              WHERE l1_verified.block_ts >= {since} \
                AND l1_verified.block_ts > l1_proved.block_ts \
@@ -1830,6 +2929,7 @@ impl ClickhouseReader {
                AND pb.batch_id != 0",
             since = since.timestamp(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(start) = starting_after {
             fallback_query.push_str(&format!(" AND pb.batch_id < {}", start));
@@ -1856,11 +2956,12 @@ impl ClickhouseReader {
         // First try the materialized view
         let mut mv_query = format!(
             "SELECT batch_id, toUInt64(prove_time_ms / 1000) AS seconds_to_prove \
-             FROM {db}.batch_prove_times_mv \
+             FROM {db}.{prefix}batch_prove_times_mv \
              WHERE proved_at >= toDateTime64({since}, 3) \
                AND batch_id != 0",
             since = since.timestamp(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(start) = starting_after {
             mv_query.push_str(&format!(" AND batch_id < {}", start));
@@ -1880,16 +2981,17 @@ impl ClickhouseReader {
         let mut fallback_query = format!(
             "SELECT toUInt64(b.batch_id) AS batch_id, \
                     (l1_proved.block_ts - l1_proposed.block_ts) AS seconds_to_prove \
-             FROM {db}.batches b \
-             JOIN {db}.proved_batches pb ON b.batch_id = pb.batch_id \
-             JOIN {db}.l1_head_events l1_proposed \
+             FROM {db}.{prefix}batches b \
+             JOIN {db}.{prefix}proved_batches pb ON b.batch_id = pb.batch_id \
+             JOIN {db}.{prefix}l1_head_events l1_proposed \
                ON b.l1_block_number = l1_proposed.l1_block_number \
-             JOIN {db}.l1_head_events l1_proved \
+             JOIN {db}.{prefix}l1_head_events l1_proved \
                ON pb.l1_block_number = l1_proved.l1_block_number \
              WHERE l1_proved.block_ts >= {since} \
                AND b.batch_id != 0",
             since = since.timestamp(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(start) = starting_after {
             fallback_query.push_str(&format!(" AND b.batch_id < {}", start));
@@ -1909,12 +3011,13 @@ impl ClickhouseReader {
         let query = format!(
             "SELECT toUInt64(toStartOfMinute(fromUnixTimestamp64Milli(block_ts * 1000))) AS minute, \
                     max(l1_block_number) AS l1_block_number \
-             FROM {db}.l1_head_events \
+             FROM {db}.{prefix}l1_head_events \
               WHERE block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
               GROUP BY minute \
               ORDER BY minute",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<L1BlockTimeRow>(&query).await?;
@@ -1953,7 +3056,7 @@ impl ClickhouseReader {
                                 h.l2_block_number)), NULL, h.block_ts - lagInFrame(h.block_ts) OVER (ORDER BY \
                                 h.l2_block_number)) \
                            )) AS s_since_prev_block \
-                    FROM {db}.l2_head_events h \
+                    FROM {db}.{prefix}l2_head_events h \
                     WHERE {filter} \
                 ) \
                 SELECT l2_block_number, block_time, s_since_prev_block \
@@ -1962,6 +3065,7 @@ impl ClickhouseReader {
                 interval = range.interval(),
                 filter = self.reorg_filter("h"),
                 db = self.db_name,
+                prefix = self.table_prefix,
             );
             if let Some(addr) = sequencer {
                 query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -1992,7 +3096,7 @@ impl ClickhouseReader {
                             h.l2_block_number)), NULL, h.block_ts - lagInFrame(h.block_ts) OVER (ORDER BY \
                             h.l2_block_number)) \
                        )) AS s_since_prev_block \
-                FROM {db}.l2_head_events h \
+                FROM {db}.{prefix}l2_head_events h \
                 WHERE {filter} \
             ) \
             SELECT l2_block_number, block_time, s_since_prev_block \
@@ -2001,6 +3105,7 @@ impl ClickhouseReader {
             interval = range.interval(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             inner.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -2054,7 +3159,7 @@ impl ClickhouseReader {
                             h.l2_block_number)), NULL, h.block_ts - lagInFrame(h.block_ts) OVER (ORDER BY \
                             h.l2_block_number))\
                        )) AS s_since_prev_block \
-                FROM {db}.l2_head_events h \
+                FROM {db}.{prefix}l2_head_events h \
                 WHERE {filter} \
             ) \
             SELECT l2_block_number, block_time, s_since_prev_block \
@@ -2062,6 +3167,7 @@ impl ClickhouseReader {
             WHERE 1=1",
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         if let Some(start) = start_block {
@@ -2118,12 +3224,13 @@ impl ClickhouseReader {
             "SELECT toUInt64(min(h.block_ts)) AS min_ts, \
                     toUInt64(max(h.block_ts)) AS max_ts, \
                     sum(sum_tx) AS tx_sum \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                AND {filter}",
             interval = range.interval(),
             filter = self.reorg_filter("h"),
-            db = self.db_name
+            db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -2163,12 +3270,13 @@ impl ClickhouseReader {
 
             let mut query = format!(
                 "SELECT h.l2_block_number, h.block_ts AS block_time, toUInt64(sum_gas_used) AS gas_used \
-                 FROM {db}.l2_head_events h \
+                 FROM {db}.{prefix}l2_head_events h \
                  WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                    AND {filter}",
                 interval = range.interval(),
                 filter = self.reorg_filter("h"),
                 db = self.db_name,
+                prefix = self.table_prefix,
             );
             if let Some(addr) = sequencer {
                 query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -2199,12 +3307,13 @@ impl ClickhouseReader {
 
         let mut inner = format!(
             "SELECT h.l2_block_number, h.block_ts AS block_time, toUInt64(sum_gas_used) AS gas_used \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                AND {filter}",
             interval = range.interval(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             inner.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -2240,6 +3349,89 @@ impl ClickhouseReader {
             .collect())
     }
 
+    /// Aggregate the L2 blocks of the given range over wallclock-aligned buckets of
+    /// `bucket_secs` seconds, oldest first. Buckets without blocks are omitted.
+    ///
+    /// With `as_of`, the range ends at that moment and only rows inserted by then are used.
+    pub async fn get_l2_time_buckets(
+        &self,
+        sequencer: Option<AddressBytes>,
+        range: TimeRange,
+        bucket_secs: u64,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<Vec<L2TimeBucketRow>> {
+        let mut query = format!(
+            "SELECT toUInt64(toUnixTimestamp(toStartOfInterval(toDateTime(h.block_ts), \
+                        INTERVAL {bucket_secs} SECOND))) AS bucket_ts, \
+                    min(h.l2_block_number) AS first_block, \
+                    max(h.l2_block_number) AS last_block, \
+                    min(h.block_ts) AS first_block_ts, \
+                    max(h.block_ts) AS last_block_ts, \
+                    count() AS blocks, \
+                    toUInt64(sum(h.sum_tx)) AS tx_count, \
+                    toUInt64(sum(h.sum_gas_used)) AS gas_used, \
+                    sum(h.sum_priority_fee) AS priority_fee, \
+                    sum(h.sum_base_fee) AS base_fee \
+             FROM {l2_head_events} h \
+             WHERE h.block_ts >= (toUnixTimestamp({now}) - {secs}) \
+               AND h.block_ts <= toUnixTimestamp({now}) \
+               AND {filter}",
+            bucket_secs = bucket_secs.max(1),
+            l2_head_events = self.table_as_of("l2_head_events", as_of),
+            now = now_as_of(as_of),
+            secs = range.seconds(),
+            filter = self.reorg_filter_as_of("h", as_of),
+        );
+        if let Some(addr) = sequencer {
+            query.push_str(&format!(" AND h.sequencer = unhex('{}')", encode(addr)));
+        }
+        query.push_str(" GROUP BY bucket_ts ORDER BY bucket_ts ASC");
+
+        self.execute_as_of::<L2TimeBucketRow>(&query, as_of)
+            .await
+            .context("fetching L2 time buckets failed")
+    }
+
+    /// Get the transactions and operating costs of the L2 blocks of each sequencer, grouped
+    /// into wallclock aligned buckets of `bucket_secs` seconds. Blocks of batches without a
+    /// recorded cost count as free.
+    pub async fn get_sequencer_cost_buckets(
+        &self,
+        range: TimeRange,
+        bucket_secs: u64,
+    ) -> Result<Vec<SequencerCostBucketRow>> {
+        let query = format!(
+            "SELECT toUInt64(toUnixTimestamp(toStartOfInterval(toDateTime(h.block_ts), \
+                        INTERVAL {bucket_secs} SECOND))) AS bucket_ts, \
+                    h.sequencer AS sequencer, \
+                    toUInt64(sum(h.sum_tx)) AS tx_count, \
+                    coalesce(sum(if(b.batch_size > 0, intDiv(dc.cost, b.batch_size), NULL)), toUInt128(0)) AS l1_data_cost, \
+                    coalesce(sum(if(b.batch_size > 0, intDiv(pc.cost, b.batch_size), NULL)), toUInt128(0)) AS prove_cost \
+             FROM {db}.{prefix}l2_head_events h \
+             LEFT JOIN (SELECT DISTINCT batch_id, l2_block_number FROM {db}.{prefix}batch_blocks) bb \
+               ON h.l2_block_number = bb.l2_block_number \
+             LEFT JOIN {db}.{prefix}batches b \
+               ON bb.batch_id = b.batch_id \
+             LEFT JOIN {db}.{prefix}l1_data_costs dc \
+               ON b.batch_id = dc.batch_id \
+             LEFT JOIN {db}.{prefix}prove_costs pc \
+               ON b.batch_id = pc.batch_id \
+             WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+               AND {filter} \
+             GROUP BY bucket_ts, sequencer \
+             ORDER BY bucket_ts ASC, sequencer ASC",
+            bucket_secs = bucket_secs.max(1),
+            interval = range.interval(),
+            filter = self.reorg_filter("h"),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<SequencerCostBucketRow>(&query)
+            .await
+            .context("fetching sequencer cost buckets failed")
+    }
+
     /// Get the gas used for each L2 block within the specified block range
     pub async fn get_l2_gas_used_block_range(
         &self,
@@ -2259,116 +3451,1149 @@ impl ClickhouseReader {
 
         let mut query = format!(
             "SELECT h.l2_block_number, h.block_ts AS block_time, toUInt64(sum_gas_used) AS gas_used \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE {filter}",
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        if let Some(start) = start_block {
+            query.push_str(&format!(" AND h.l2_block_number >= {}", start));
+        }
+
+        if let Some(end) = end_block {
+            query.push_str(&format!(" AND h.l2_block_number <= {}", end));
+        }
+
+        if let Some(addr) = sequencer {
+            query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
+        }
+
+        if let Some(start) = starting_after {
+            query.push_str(&format!(" AND l2_block_number < {}", start));
+        }
+
+        if let Some(end) = ending_before {
+            query.push_str(&format!(" AND l2_block_number > {}", end));
+        }
+
+        query.push_str(" ORDER BY l2_block_number DESC");
+        query.push_str(&format!(" LIMIT {}", limit));
+
+        let rows = self.execute::<RawRow>(&query).await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| {
+                let dt = Utc.timestamp_opt(r.block_time as i64, 0).unwrap();
+                L2GasUsedRow {
+                    l2_block_number: r.l2_block_number,
+                    block_time: dt,
+                    gas_used: r.gas_used,
+                }
+            })
+            .collect())
+    }
+    /// Get the L1 data posting cost for each block within the given range
+    pub async fn get_l1_data_costs(&self, range: TimeRange) -> Result<Vec<L1DataCostRow>> {
+        #[derive(Row, Deserialize)]
+        struct RawRow {
+            l1_block_number: u64,
+            cost: u128,
+            blob_cost: u128,
+            calldata_cost: u128,
+        }
+
+        // Group by l1_block_number and sum the total and per-component costs
+        let query = format!(
+            "SELECT c.l1_block_number, sum(c.cost) as cost, \
+                sum(c.blob_cost) as blob_cost, sum(c.calldata_cost) as calldata_cost \
+         FROM {db}.{prefix}l1_data_costs c \
+         INNER JOIN {db}.{prefix}l1_head_events h \
+           ON c.l1_block_number = h.l1_block_number \
+         WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+         GROUP BY c.l1_block_number \
+         ORDER BY c.l1_block_number ASC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<RawRow>(&query).await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| L1DataCostRow {
+                l1_block_number: r.l1_block_number,
+                cost: r.cost,
+                blob_cost: r.blob_cost,
+                calldata_cost: r.calldata_cost,
+            })
+            .collect())
+    }
+
+    /// Get the L1 data posting cost since the given cutoff time with cursor-based pagination.
+    /// Results are returned in descending order by block number.
+    pub async fn get_l1_data_costs_paginated(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+        starting_after: Option<u64>,
+        ending_before: Option<u64>,
+    ) -> Result<Vec<L1DataCostRow>> {
+        #[derive(Row, Deserialize)]
+        struct RawRow {
+            l1_block_number: u64,
+            cost: u128,
+            blob_cost: u128,
+            calldata_cost: u128,
+        }
+
+        // First join with l1_head_events to filter by time, then group by l1_block_number
+        let mut query = format!(
+            "SELECT c.l1_block_number, sum(c.cost) as cost, \
+                sum(c.blob_cost) as blob_cost, sum(c.calldata_cost) as calldata_cost \
+         FROM {db}.{prefix}l1_data_costs c \
+         INNER JOIN {db}.{prefix}l1_head_events h \
+           ON c.l1_block_number = h.l1_block_number \
+         WHERE h.block_ts >= {since}",
+            since = since.timestamp(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        if let Some(start) = starting_after {
+            query.push_str(&format!(" AND c.l1_block_number < {}", start));
+        }
+        if let Some(end) = ending_before {
+            query.push_str(&format!(" AND c.l1_block_number > {}", end));
+        }
+        query.push_str(" GROUP BY c.l1_block_number");
+        query.push_str(" ORDER BY c.l1_block_number DESC");
+        query.push_str(&format!(" LIMIT {}", limit));
+
+        let rows = self.execute::<RawRow>(&query).await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| L1DataCostRow {
+                l1_block_number: r.l1_block_number,
+                cost: r.cost,
+                blob_cost: r.blob_cost,
+                calldata_cost: r.calldata_cost,
+            })
+            .collect())
+    }
+
+    /// Get the L1 blob fee market state of each batch proposed since the given time, paginated
+    /// by batch ID in descending order
+    pub async fn get_blob_fee_history_paginated(
+        &self,
+        since: DateTime<Utc>,
+        limit: u64,
+        starting_after: Option<u64>,
+        ending_before: Option<u64>,
+    ) -> Result<Vec<BlobFeeHistoryRow>> {
+        let mut query = format!(
+            "SELECT f.batch_id, f.l1_block_number, h.block_ts, \
+                    max(f.excess_blob_gas) AS excess_blob_gas, \
+                    max(f.blob_base_fee) AS blob_base_fee \
+             FROM {db}.{prefix}batch_blob_fees f \
+             INNER JOIN {db}.{prefix}l1_head_events h \
+               ON f.l1_block_number = h.l1_block_number \
+             WHERE h.block_ts >= {since}",
+            since = since.timestamp(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        if let Some(start) = starting_after {
+            query.push_str(&format!(" AND f.batch_id < {}", start));
+        }
+        if let Some(end) = ending_before {
+            query.push_str(&format!(" AND f.batch_id > {}", end));
+        }
+        query.push_str(" GROUP BY f.batch_id, f.l1_block_number, h.block_ts");
+        query.push_str(" ORDER BY f.batch_id DESC");
+        query.push_str(&format!(" LIMIT {}", limit));
+
+        self.execute::<BlobFeeHistoryRow>(&query).await
+    }
+
+    /// Get the distribution of the L1 -> L2 anchor lag (L1 head minus anchor block id) over the
+    /// given range, in about 60 time buckets of at least one minute each
+    pub async fn get_anchor_lag(&self, range: TimeRange) -> Result<Vec<AnchorLagRow>> {
+        let bucket = (range.seconds() / 60).max(60);
+        let query = format!(
+            "SELECT toUInt64(intDiv(block_ts, {bucket}) * {bucket}) AS bucket_ts, \
+                    count() AS blocks, \
+                    avg(lag) AS avg_lag, \
+                    toUInt64(quantile(0.5)(lag)) AS p50_lag, \
+                    toUInt64(quantile(0.95)(lag)) AS p95_lag, \
+                    max(lag) AS max_lag \
+             FROM ( \
+                SELECT l2_block_number, \
+                       argMax(block_ts, inserted_at) AS block_ts, \
+                       argMax(toUInt64(if(l1_head > anchor_block_id, l1_head - anchor_block_id, 0)), \
+                              inserted_at) AS lag \
+                FROM {db}.{prefix}l2_anchor_blocks \
+                WHERE block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+                GROUP BY l2_block_number \
+             ) \
+             GROUP BY bucket_ts \
+             ORDER BY bucket_ts ASC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<AnchorLagRow>(&query).await
+    }
+
+    /// Get the distribution of L2 block gas saturation (gas used over gas limit) over the given
+    /// range, in about 60 time buckets of at least one minute each. Blocks without a recorded
+    /// gas limit are skipped.
+    pub async fn get_gas_saturation(&self, range: TimeRange) -> Result<Vec<GasSaturationRow>> {
+        let bucket = (range.seconds() / 60).max(60);
+        let query = format!(
+            "SELECT toUInt64(intDiv(h.block_ts, {bucket}) * {bucket}) AS bucket_ts, \
+                    count() AS blocks, \
+                    avg(toFloat64(h.sum_gas_used) / h.gas_limit) AS avg_saturation, \
+                    quantile(0.95)(toFloat64(h.sum_gas_used) / h.gas_limit) AS p95_saturation, \
+                    max(toFloat64(h.sum_gas_used) / h.gas_limit) AS max_saturation \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+               AND h.gas_limit > 0 \
+               AND {filter} \
+             GROUP BY bucket_ts \
+             ORDER BY bucket_ts ASC",
+            interval = range.interval(),
+            filter = self.reorg_filter("h"),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<GasSaturationRow>(&query).await
+    }
+
+    /// Get the average L2 block gas saturation over the given range
+    pub async fn get_gas_saturation_summary(
+        &self,
+        range: TimeRange,
+    ) -> Result<GasSaturationSummaryRow> {
+        let query = format!(
+            "SELECT count() AS blocks, \
+                    ifNotFinite(avg(toFloat64(h.sum_gas_used) / h.gas_limit), 0) AS avg_saturation \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+               AND h.gas_limit > 0 \
+               AND {filter}",
+            interval = range.interval(),
+            filter = self.reorg_filter("h"),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<GasSaturationSummaryRow>(&query).await?;
+        Ok(rows.into_iter().next().unwrap_or_default())
+    }
+
+    /// Get the average gas used per L2 block over the given range
+    pub async fn get_avg_l2_gas_used(&self, range: TimeRange) -> Result<Option<f64>> {
+        #[derive(Row, Deserialize)]
+        struct AvgRow {
+            avg_gas_used: Option<f64>,
+        }
+
+        let query = format!(
+            "SELECT avgOrNull(toFloat64(h.sum_gas_used)) AS avg_gas_used \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+               AND {filter}",
+            interval = range.interval(),
+            filter = self.reorg_filter("h"),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<AvgRow>(&query).await?;
+        Ok(rows.into_iter().next().and_then(|r| r.avg_gas_used))
+    }
+
+    /// Get the SLA summary of every probed public RPC endpoint over the given range
+    pub async fn get_rpc_status(&self, range: TimeRange) -> Result<Vec<RpcStatusRow>> {
+        let query = format!(
+            "SELECT url, \
+                    count() AS probes, \
+                    countIf(healthy = 0) AS failures, \
+                    avgOrNull(latency_ms) AS avg_latency_ms, \
+                    quantileOrNull(0.95)(latency_ms) AS p95_latency_ms, \
+                    argMax(healthy, inserted_at) AS last_healthy, \
+                    argMax(tuple(block_lag), inserted_at).1 AS last_block_lag, \
+                    toUInt64(toUnixTimestamp(max(inserted_at))) AS last_probed_at \
+             FROM {db}.{prefix}rpc_health \
+             WHERE inserted_at >= now64() - INTERVAL {interval} \
+             GROUP BY url \
+             ORDER BY url ASC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<RpcStatusRow>(&query).await
+    }
+
+    /// Get ingestion latency percentiles per event type over the given range. The row with an
+    /// empty `kind` covers all event types.
+    pub async fn get_pipeline_latency(&self, range: TimeRange) -> Result<Vec<PipelineLatencyRow>> {
+        let query = format!(
+            "SELECT kind, \
+                    count() AS events, \
+                    quantile(0.5)(queue_us) AS queue_p50_us, \
+                    quantile(0.99)(queue_us) AS queue_p99_us, \
+                    quantile(0.5)(process_us) AS process_p50_us, \
+                    quantile(0.99)(process_us) AS process_p99_us, \
+                    quantile(0.5)(total_us) AS total_p50_us, \
+                    quantile(0.99)(total_us) AS total_p99_us, \
+                    max(total_us) AS total_max_us \
+             FROM {db}.{prefix}pipeline_latency \
+             WHERE inserted_at >= now64() - INTERVAL {interval} \
+             GROUP BY ROLLUP(kind) \
+             ORDER BY kind ASC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<PipelineLatencyRow>(&query).await
+    }
+
+    /// Get the distribution of header propagation delays per chain over the given range
+    pub async fn get_header_propagation(
+        &self,
+        range: TimeRange,
+    ) -> Result<Vec<HeaderPropagationRow>> {
+        let query = format!(
+            "SELECT chain, \
+                    count() AS blocks, \
+                    countIf(delay_ms < 0) AS early_blocks, \
+                    min(delay_ms) AS min_ms, \
+                    avg(delay_ms) AS avg_ms, \
+                    quantile(0.5)(delay_ms) AS p50_ms, \
+                    quantile(0.9)(delay_ms) AS p90_ms, \
+                    quantile(0.99)(delay_ms) AS p99_ms, \
+                    max(delay_ms) AS max_ms \
+             FROM {db}.{prefix}header_propagation \
+             WHERE observed_at >= now64() - INTERVAL {interval} \
+             GROUP BY chain \
+             ORDER BY chain ASC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<HeaderPropagationRow>(&query).await
+    }
+
+    /// Get the latest backfill progress snapshot of each chain
+    pub async fn get_backfill_status(&self) -> Result<Vec<BackfillStatusRow>> {
+        let query = format!(
+            "SELECT chain, scheduled_blocks, filled_blocks, failed_blocks, newest_block, \
+                    oldest_block, current_block, max_requests_per_sec, \
+                    toUInt64(toUnixTimestamp(inserted_at)) AS updated_at \
+             FROM {db}.{prefix}backfill_progress \
+             ORDER BY chain ASC, inserted_at DESC \
+             LIMIT 1 BY chain",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BackfillStatusRow>(&query).await
+    }
+
+    /// Get the driver leadership changes within the given range, most recent first
+    pub async fn get_leader_changes(&self, range: TimeRange) -> Result<Vec<LeaderChangeRow>> {
+        let query = format!(
+            "SELECT lease, holder, previous_holder, \
+                    toUInt64(toUnixTimestamp(inserted_at)) AS changed_at \
+             FROM {db}.{prefix}leader_changes \
+             WHERE inserted_at >= now64() - INTERVAL {interval} \
+             ORDER BY inserted_at DESC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<LeaderChangeRow>(&query).await
+    }
+
+    /// Get the last L1 block recorded in the bond ledger
+    pub async fn get_bond_ledger_last_l1_block(&self) -> Result<Option<u64>> {
+        #[derive(Row, Deserialize)]
+        struct MaxBlock {
+            block: Option<u64>,
+        }
+
+        let query = format!(
+            "SELECT maxOrNull(l1_block_number) AS block FROM {db}.{prefix}bond_ledger",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<MaxBlock>(&query).await?;
+        Ok(rows.into_iter().next().and_then(|r| r.block))
+    }
+
+    /// Get the latest bond balance of every account in the bond ledger, lowest balance first
+    pub async fn get_bond_balances(&self) -> Result<Vec<BondBalanceRow>> {
+        let query = format!(
+            "SELECT address, \
+                    argMax(balance, (l1_block_number, log_index)) AS balance, \
+                    max(l1_block_number) AS l1_block_number, \
+                    sumIf(amount, kind = 'debited') AS total_debited, \
+                    sumIf(amount, kind = 'credited') AS total_credited \
+             FROM {db}.{prefix}bond_ledger \
+             GROUP BY address \
+             ORDER BY balance ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BondBalanceRow>(&query).await
+    }
+
+    /// Get the latest `limit` bond ledger entries of `address`, most recent first
+    pub async fn get_bond_history(
+        &self,
+        address: AddressBytes,
+        limit: u64,
+    ) -> Result<Vec<BondLedgerInsertRow>> {
+        let query = format!(
+            "SELECT address, l1_block_number, tx_hash, log_index, kind, amount, balance \
+             FROM {db}.{prefix}bond_ledger \
+             WHERE address = unhex('{addr}') \
+             ORDER BY l1_block_number DESC, log_index DESC \
+             LIMIT {limit}",
+            addr = encode(address),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BondLedgerInsertRow>(&query).await
+    }
+
+    /// Get the distinct proposers of the batches posted in the given range
+    pub async fn get_recent_proposers(&self, range: TimeRange) -> Result<Vec<AddressBytes>> {
+        #[derive(Row, Deserialize)]
+        struct ProposerRow {
+            proposer_addr: AddressBytes,
+        }
+
+        let query = format!(
+            "SELECT DISTINCT b.proposer_addr AS proposer_addr \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l1_head_events l1 \
+               ON b.l1_block_number = l1.l1_block_number \
+             WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval})",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<ProposerRow>(&query).await?;
+        Ok(rows.into_iter().map(|r| r.proposer_addr).collect())
+    }
+
+    /// Get the operator balances of the latest snapshot round, lowest ETH balance first
+    pub async fn get_operator_balances(&self) -> Result<Vec<OperatorBalanceRow>> {
+        let query = format!(
+            "SELECT snapshot_ts, address, l1_block_number, eth_balance, taiko_balance, \
+                    low_balance \
+             FROM {db}.{prefix}operator_balances \
+             WHERE snapshot_ts = (SELECT max(snapshot_ts) FROM {db}.{prefix}operator_balances) \
+             ORDER BY eth_balance ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<OperatorBalanceRow>(&query).await
+    }
+
+    /// Get the most recent snapshot of the whitelist operator candidates
+    pub async fn get_latest_operator_history(&self) -> Result<Option<OperatorHistoryRow>> {
+        let query = format!(
+            "SELECT observed_ts, l1_block_number, candidates, added, removed \
+             FROM {db}.{prefix}operator_history \
+             ORDER BY observed_ts DESC, l1_block_number DESC \
+             LIMIT 1",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self
+            .execute::<OperatorHistoryRow>(&query)
+            .await
+            .context("fetching latest operator history failed")?;
+        Ok(rows.into_iter().next())
+    }
+
+    /// Get the changes to the whitelist operator candidates observed within the given time
+    /// range, oldest first
+    pub async fn get_operator_history_range(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<OperatorHistoryRow>> {
+        let query = format!(
+            "SELECT observed_ts, l1_block_number, candidates, added, removed \
+             FROM {db}.{prefix}operator_history FINAL \
+             WHERE observed_ts > {since} AND observed_ts <= {until} \
+             ORDER BY observed_ts ASC, l1_block_number ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp(),
+            until = until.timestamp(),
+        );
+        self.execute::<OperatorHistoryRow>(&query).await.context("fetching operator history failed")
+    }
+
+    /// Get the anomalies of core metrics detected within the given time range, oldest first
+    pub async fn get_anomalies_range(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<AnomalyRow>> {
+        let query = format!(
+            "SELECT detected_ts, metric, value, expected, std_dev, z_score \
+             FROM {db}.{prefix}anomalies FINAL \
+             WHERE detected_ts > {since} AND detected_ts <= {until} \
+             ORDER BY detected_ts ASC, metric ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp(),
+            until = until.timestamp(),
+        );
+        self.execute::<AnomalyRow>(&query).await.context("fetching anomalies failed")
+    }
+
+    /// Get the incident operations logged within the given time range, oldest first
+    pub async fn get_incident_log_range(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<IncidentLogRow>> {
+        let query = format!(
+            "SELECT logged_at_ms, action, incident_id, component_ids, incident_name, status, \
+                    payload, response_code, error, dry_run \
+             FROM {db}.{prefix}incident_log FINAL \
+             WHERE logged_at_ms > {since} AND logged_at_ms <= {until} \
+             ORDER BY logged_at_ms ASC, action ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp_millis(),
+            until = until.timestamp_millis(),
+        );
+        self.execute::<IncidentLogRow>(&query).await.context("fetching incident log failed")
+    }
+
+    /// Get the current label of every labelled address
+    pub async fn get_address_labels(&self) -> Result<Vec<AddressLabelRow>> {
+        let query = format!(
+            "SELECT address, label, source, updated_ts \
+             FROM {db}.{prefix}address_labels FINAL \
+             WHERE label != ''",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        self.execute::<AddressLabelRow>(&query).await.context("fetching address labels failed")
+    }
+
+    /// Get the sequencers and proposers active since the given cutoff time
+    pub async fn get_active_operator_addresses(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<AddressBytes>> {
+        #[derive(Row, Deserialize)]
+        struct AddressRow {
+            address: AddressBytes,
+        }
+
+        let query = format!(
+            "SELECT DISTINCT h.sequencer AS address \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.block_ts > {since} AND {filter} \
+             UNION DISTINCT \
+             SELECT DISTINCT proposer_addr AS address \
+             FROM {db}.{prefix}batches \
+             WHERE inserted_at > fromUnixTimestamp64Milli({since_ms})",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp(),
+            since_ms = since.timestamp_millis(),
+            filter = self.reorg_filter("h"),
+        );
+        let rows = self
+            .execute::<AddressRow>(&query)
+            .await
+            .context("fetching active operator addresses failed")?;
+        Ok(rows.into_iter().map(|r| r.address).collect())
+    }
+
+    /// Get the most recent snapshot of the forced inclusion queue
+    pub async fn get_latest_forced_inclusion_queue(
+        &self,
+    ) -> Result<Option<ForcedInclusionQueueRow>> {
+        let query = format!(
+            "SELECT snapshot_ts, l1_block_number, pending, oldest_created_ts, oldest_age_secs, \
+                    deadline_batch_id, next_batch_id, near_deadline \
+             FROM {db}.{prefix}forced_inclusion_queue FINAL \
+             ORDER BY snapshot_ts DESC \
+             LIMIT 1",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        let rows = self
+            .execute::<ForcedInclusionQueueRow>(&query)
+            .await
+            .context("fetching latest forced inclusion queue failed")?;
+        Ok(rows.into_iter().next())
+    }
+
+    /// Get the snapshots of the forced inclusion queue taken within the given time range, oldest
+    /// first
+    pub async fn get_forced_inclusion_queue_range(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<ForcedInclusionQueueRow>> {
+        let query = format!(
+            "SELECT snapshot_ts, l1_block_number, pending, oldest_created_ts, oldest_age_secs, \
+                    deadline_batch_id, next_batch_id, near_deadline \
+             FROM {db}.{prefix}forced_inclusion_queue FINAL \
+             WHERE snapshot_ts > {since} AND snapshot_ts <= {until} \
+             ORDER BY snapshot_ts ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp(),
+            until = until.timestamp(),
+        );
+        self.execute::<ForcedInclusionQueueRow>(&query)
+            .await
+            .context("fetching forced inclusion queue failed")
+    }
+
+    /// Get the most recent snapshot of the verification backlog
+    pub async fn get_latest_verification_backlog(&self) -> Result<Option<VerificationBacklogRow>> {
+        let query = format!(
+            "SELECT snapshot_ts, l1_block_number, last_proposed_batch_id, \
+                    last_verified_batch_id, backlog, over_threshold \
+             FROM {db}.{prefix}verification_backlog FINAL \
+             ORDER BY snapshot_ts DESC \
+             LIMIT 1",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        let rows = self
+            .execute::<VerificationBacklogRow>(&query)
+            .await
+            .context("fetching latest verification backlog failed")?;
+        Ok(rows.into_iter().next())
+    }
+
+    /// Get the snapshots of the verification backlog taken within the given time range, oldest
+    /// first
+    pub async fn get_verification_backlog_range(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<VerificationBacklogRow>> {
+        let query = format!(
+            "SELECT snapshot_ts, l1_block_number, last_proposed_batch_id, \
+                    last_verified_batch_id, backlog, over_threshold \
+             FROM {db}.{prefix}verification_backlog FINAL \
+             WHERE snapshot_ts > {since} AND snapshot_ts <= {until} \
+             ORDER BY snapshot_ts ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp(),
+            until = until.timestamp(),
+        );
+        self.execute::<VerificationBacklogRow>(&query)
+            .await
+            .context("fetching verification backlog failed")
+    }
+
+    /// Get the indexed roles of the L1 transaction `tx_hash`, with the timestamp of the block it
+    /// was included in. Empty when the transaction is not indexed.
+    pub async fn get_l1_tx_roles(&self, tx_hash: HashBytes) -> Result<Vec<L1TxLookupRow>> {
+        let query = format!(
+            "SELECT i.role AS role, i.batch_id AS batch_id, \
+                    i.l1_block_number AS l1_block_number, h.block_ts AS block_ts, \
+                    toUInt64(toUnixTimestamp64Milli(i.inserted_at)) AS indexed_at_ms \
+             FROM {db}.{prefix}l1_tx_index i FINAL \
+             LEFT JOIN ( \
+                SELECT l1_block_number, max(block_ts) AS block_ts \
+                FROM {db}.{prefix}l1_head_events \
+                GROUP BY l1_block_number \
+             ) h ON i.l1_block_number = h.l1_block_number \
+             WHERE i.tx_hash = unhex('{hash}') \
+             ORDER BY i.role ASC, i.batch_id ASC",
+            hash = encode(tx_hash),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        self.execute::<L1TxLookupRow>(&query).await.context("fetching L1 transaction roles failed")
+    }
+
+    /// Get the batch proposals first seen in the L1 mempool within the given time range, oldest
+    /// first
+    pub async fn get_proposal_inclusions(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<ProposalInclusionRow>> {
+        let query = format!(
+            "SELECT proposer, nonce, tx_hash, first_seen_ms, max_fee_per_gas, \
+                    max_priority_fee_per_gas, replacements, batch_id, l1_block_number, \
+                    included_at_ms, inclusion_delay_ms, dropped \
+             FROM {db}.{prefix}proposal_inclusions FINAL \
+             WHERE first_seen_ms > {since} AND first_seen_ms <= {until} \
+             ORDER BY first_seen_ms ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp_millis(),
+            until = until.timestamp_millis(),
+        );
+        self.execute::<ProposalInclusionRow>(&query)
+            .await
+            .context("fetching proposal inclusions failed")
+    }
+
+    /// Get the batch proposals first seen in the L1 mempool at or after `seen_since_ms` that have
+    /// not been included yet, oldest first
+    pub async fn get_pending_proposals(
+        &self,
+        seen_since_ms: u64,
+    ) -> Result<Vec<ProposalInclusionRow>> {
+        let query = format!(
+            "SELECT proposer, nonce, tx_hash, first_seen_ms, max_fee_per_gas, \
+                    max_priority_fee_per_gas, replacements, batch_id, l1_block_number, \
+                    included_at_ms, inclusion_delay_ms, dropped \
+             FROM {db}.{prefix}proposal_inclusions FINAL \
+             WHERE first_seen_ms >= {seen_since_ms} AND included_at_ms IS NULL AND dropped = 0 \
+             ORDER BY first_seen_ms ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        self.execute::<ProposalInclusionRow>(&query)
+            .await
+            .context("fetching pending proposals failed")
+    }
+
+    /// Get the operator scheduled at every L1 slot with a block in `[since, until)`, given as
+    /// UNIX timestamps in seconds, oldest first
+    pub async fn get_operator_schedule(
+        &self,
+        since: u64,
+        until: u64,
+    ) -> Result<Vec<OperatorScheduleRow>> {
+        let query = format!(
+            "SELECT h.block_ts AS block_ts, p.operator AS operator \
+             FROM ( \
+                SELECT slot, min(block_ts) AS block_ts \
+                FROM {db}.{prefix}l1_head_events \
+                WHERE block_ts >= {since} AND block_ts < {until} \
+                GROUP BY slot \
+             ) h \
+             INNER JOIN ( \
+                SELECT slot, assumeNotNull(argMax(current_operator, inserted_at)) AS operator \
+                FROM {db}.{prefix}preconf_data \
+                WHERE current_operator IS NOT NULL \
+                  AND slot IN ( \
+                    SELECT slot FROM {db}.{prefix}l1_head_events \
+                    WHERE block_ts >= {since} AND block_ts < {until} \
+                  ) \
+                GROUP BY slot \
+             ) p ON h.slot = p.slot \
+             ORDER BY block_ts ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<OperatorScheduleRow>(&query).await
+    }
+
+    /// Get the operator of every L1 epoch with a block in `[since, until)`, given as UNIX
+    /// timestamps in seconds, most recent epoch first
+    pub async fn get_operator_epochs(
+        &self,
+        since: u64,
+        until: u64,
+    ) -> Result<Vec<OperatorEpochRow>> {
+        let query = format!(
+            "SELECT intDiv(h.slot, {slots_per_epoch}) AS epoch, \
+                    argMin(p.operator, h.slot) AS operator, \
+                    min(h.l1_block_number) AS first_l1_block, \
+                    max(h.l1_block_number) AS last_l1_block \
+             FROM ( \
+                SELECT slot, min(l1_block_number) AS l1_block_number \
+                FROM {db}.{prefix}l1_head_events \
+                WHERE block_ts >= {since} AND block_ts < {until} \
+                GROUP BY slot \
+             ) h \
+             INNER JOIN ( \
+                SELECT slot, assumeNotNull(argMax(current_operator, inserted_at)) AS operator \
+                FROM {db}.{prefix}preconf_data \
+                WHERE current_operator IS NOT NULL \
+                  AND slot IN ( \
+                    SELECT slot FROM {db}.{prefix}l1_head_events \
+                    WHERE block_ts >= {since} AND block_ts < {until} \
+                  ) \
+                GROUP BY slot \
+             ) p ON h.slot = p.slot \
+             GROUP BY epoch \
+             ORDER BY epoch DESC",
+            slots_per_epoch = SLOTS_PER_EPOCH,
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<OperatorEpochRow>(&query).await
+    }
+
+    /// Get the number, timestamp and sequencer of every canonical L2 block in `[since, until)`,
+    /// given as UNIX timestamps in seconds, oldest first
+    pub async fn get_l2_block_producers(
+        &self,
+        since: u64,
+        until: u64,
+    ) -> Result<Vec<L2BlockProducerRow>> {
+        let query = format!(
+            "SELECT h.l2_block_number AS l2_block_number, h.block_ts AS block_ts, \
+                    h.sequencer AS sequencer \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.block_ts >= {since} AND h.block_ts < {until} \
+               AND {filter} \
+             ORDER BY h.block_ts ASC, h.l2_block_number ASC",
+            filter = self.reorg_filter("h"),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<L2BlockProducerRow>(&query).await
+    }
+
+    /// Get the sequencer handovers whose incoming sequencer produced its first block within the
+    /// given time range, oldest first
+    pub async fn get_sequencer_handovers(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<SequencerHandoverRow>> {
+        let query = format!(
+            "SELECT outgoing, incoming, last_block_number, last_block_ts, first_block_number, \
+                    first_block_ts, latency_secs \
+             FROM {db}.{prefix}sequencer_handovers FINAL \
+             WHERE first_block_ts > {since} AND first_block_ts <= {until} \
+             ORDER BY first_block_number ASC",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp(),
+            until = until.timestamp(),
+        );
+        self.execute::<SequencerHandoverRow>(&query)
+            .await
+            .context("fetching sequencer handovers failed")
+    }
+
+    /// Get the last L1 and L2 blocks before `since` and the last ones before `until`, bounding
+    /// the blocks of the period `[since, until)`. `None` until both chains have blocks on both
+    /// sides of `since`.
+    pub async fn get_reconciliation_blocks(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Option<ReconciliationBlocksRow>> {
+        let query = format!(
+            "SELECT \
+                (SELECT max(l1_block_number) FROM {db}.{prefix}l1_head_events \
+                 WHERE block_ts < {since}) AS l1_from, \
+                (SELECT max(l1_block_number) FROM {db}.{prefix}l1_head_events \
+                 WHERE block_ts < {until}) AS l1_to, \
+                (SELECT max(l2_block_number) FROM {db}.{prefix}l2_head_events \
+                 WHERE block_ts < {since}) AS l2_from, \
+                (SELECT max(l2_block_number) FROM {db}.{prefix}l2_head_events \
+                 WHERE block_ts < {until}) AS l2_to",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            since = since.timestamp(),
+            until = until.timestamp(),
+        );
+        let rows = self
+            .execute::<ReconciliationBlocksRow>(&query)
+            .await
+            .context("fetching reconciliation blocks failed")?;
+        Ok(rows.into_iter().next().filter(|r| {
+            r.l1_from > 0 && r.l1_to > r.l1_from && r.l2_from > 0 && r.l2_to > r.l2_from
+        }))
+    }
+
+    /// Get the fees attributed to every sequencer over L2 blocks `(l2_from, l2_to]` and L1
+    /// blocks `(l1_from, l1_to]`: the priority and base fees of the canonical L2 blocks it
+    /// produced and the L1 cost of the batches it proposed.
+    pub async fn get_sequencer_fee_totals(
+        &self,
+        l1_from: u64,
+        l1_to: u64,
+        l2_from: u64,
+        l2_to: u64,
+    ) -> Result<Vec<SequencerFeeTotalsRow>> {
+        let query = format!(
+            "SELECT sequencer, \
+                    sum(priority_fee) AS priority_fee, \
+                    sum(base_fee) AS base_fee, \
+                    sum(l1_data_cost) AS l1_data_cost \
+             FROM ( \
+                SELECT h.sequencer AS sequencer, \
+                       h.sum_priority_fee AS priority_fee, \
+                       h.sum_base_fee AS base_fee, \
+                       toUInt128(0) AS l1_data_cost \
+                FROM {db}.{prefix}l2_head_events h FINAL \
+                WHERE h.l2_block_number > {l2_from} AND h.l2_block_number <= {l2_to} \
+                  AND {filter} \
+                UNION ALL \
+                SELECT b.proposer_addr AS sequencer, \
+                       toUInt128(0) AS priority_fee, \
+                       toUInt128(0) AS base_fee, \
+                       dc.cost AS l1_data_cost \
+                FROM {db}.{prefix}batches b FINAL \
+                INNER JOIN {db}.{prefix}l1_data_costs dc FINAL \
+                    ON b.batch_id = dc.batch_id AND b.l1_block_number = dc.l1_block_number \
+                WHERE b.l1_block_number > {l1_from} AND b.l1_block_number <= {l1_to} \
+             ) \
+             GROUP BY sequencer \
+             ORDER BY sequencer",
+            db = self.db_name,
+            prefix = self.table_prefix,
+            filter = self.reorg_filter("h"),
+        );
+        self.execute::<SequencerFeeTotalsRow>(&query)
+            .await
+            .context("fetching sequencer fee totals failed")
+    }
+
+    /// Get the daily scheduled time and downtime of every sequencer within the given range,
+    /// most recent day first. Only the rows of the latest computation version of every hour are
+    /// used.
+    pub async fn get_sequencer_uptime(&self, range: TimeRange) -> Result<Vec<SequencerUptimeRow>> {
+        let versions = format!(
+            "SELECT hour_ts, max(computation_version) \
+             FROM {db}.{prefix}sequencer_schedule \
+             WHERE hour_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) - 3600 \
+             GROUP BY hour_ts",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        let query = format!(
+            "SELECT s.day_ts AS day_ts, s.sequencer AS sequencer, \
+                    s.scheduled_secs AS scheduled_secs, \
+                    d.downtime_secs AS downtime_secs, \
+                    d.downtime_intervals AS downtime_intervals \
+             FROM ( \
+                SELECT toUInt64(toUnixTimestamp(toStartOfDay(fromUnixTimestamp(hour_ts)))) \
+                           AS day_ts, \
+                       sequencer, \
+                       sum(scheduled_secs) AS scheduled_secs \
+                FROM {db}.{prefix}sequencer_schedule FINAL \
+                WHERE hour_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+                  AND (hour_ts, computation_version) IN ({versions}) \
+                GROUP BY day_ts, sequencer \
+             ) s \
+             LEFT JOIN ( \
+                SELECT toUInt64(toUnixTimestamp(toStartOfDay(fromUnixTimestamp(start_ts)))) \
+                           AS day_ts, \
+                       sequencer, \
+                       sum(end_ts - start_ts) AS downtime_secs, \
+                       count() AS downtime_intervals \
+                FROM {db}.{prefix}sequencer_downtime FINAL \
+                WHERE start_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+                  AND (intDiv(start_ts, 3600) * 3600, computation_version) IN ({versions}) \
+                GROUP BY day_ts, sequencer \
+             ) d ON s.day_ts = d.day_ts AND s.sequencer = d.sequencer \
+             ORDER BY day_ts DESC, scheduled_secs DESC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
         );
 
-        if let Some(start) = start_block {
-            query.push_str(&format!(" AND h.l2_block_number >= {}", start));
-        }
-
-        if let Some(end) = end_block {
-            query.push_str(&format!(" AND h.l2_block_number <= {}", end));
-        }
-
-        if let Some(addr) = sequencer {
-            query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
-        }
+        self.execute::<SequencerUptimeRow>(&query).await
+    }
 
-        if let Some(start) = starting_after {
-            query.push_str(&format!(" AND l2_block_number < {}", start));
+    /// Get up to `limit` hours whose sequencer uptime rows were derived by a computation version
+    /// older than `version`, oldest first
+    pub async fn get_outdated_sequencer_hours(&self, version: u16, limit: u64) -> Result<Vec<u64>> {
+        #[derive(Row, Deserialize)]
+        struct HourRow {
+            hour_ts: u64,
         }
 
-        if let Some(end) = ending_before {
-            query.push_str(&format!(" AND l2_block_number > {}", end));
-        }
+        let query = format!(
+            "SELECT hour_ts \
+             FROM {db}.{prefix}sequencer_schedule \
+             GROUP BY hour_ts \
+             HAVING max(computation_version) < {version} \
+             ORDER BY hour_ts ASC \
+             LIMIT {limit}",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+        let rows = self
+            .execute::<HourRow>(&query)
+            .await
+            .context("fetching outdated sequencer hours failed")?;
+        Ok(rows.into_iter().map(|r| r.hour_ts).collect())
+    }
 
-        query.push_str(" ORDER BY l2_block_number DESC");
-        query.push_str(&format!(" LIMIT {}", limit));
+    /// Get the per-minute L2 mempool activity within the given range, oldest minute first
+    pub async fn get_mempool_stats(&self, range: TimeRange) -> Result<Vec<MempoolStatsRow>> {
+        let query = format!(
+            "SELECT minute_ts, seen_txs, included_txs, max_depth, end_depth, \
+                    avg_inclusion_ms, p50_inclusion_ms, p95_inclusion_ms, max_inclusion_ms \
+             FROM {db}.{prefix}mempool_stats FINAL \
+             WHERE minute_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+             ORDER BY minute_ts ASC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
 
-        let rows = self.execute::<RawRow>(&query).await?;
-        Ok(rows
-            .into_iter()
-            .map(|r| {
-                let dt = Utc.timestamp_opt(r.block_time as i64, 0).unwrap();
-                L2GasUsedRow {
-                    l2_block_number: r.l2_block_number,
-                    block_time: dt,
-                    gas_used: r.gas_used,
-                }
-            })
-            .collect())
+        self.execute::<MempoolStatsRow>(&query).await.context("fetching mempool stats failed")
     }
-    /// Get the L1 data posting cost for each block within the given range
-    pub async fn get_l1_data_costs(&self, range: TimeRange) -> Result<Vec<L1DataCostRow>> {
-        #[derive(Row, Deserialize)]
-        struct RawRow {
-            l1_block_number: u64,
-            cost: u128,
-        }
 
-        // Group by l1_block_number and sum costs since L1DataCostRow only has these 2 fields
+    /// Get the L1 gas spent on protocol transactions within the given range, per operation and
+    /// sending address, most expensive first. Transactions recorded more than once (e.g. by a
+    /// backfill) are counted once.
+    pub async fn get_protocol_gas_spend(
+        &self,
+        range: TimeRange,
+    ) -> Result<Vec<ProtocolGasSpendRow>> {
         let query = format!(
-            "SELECT c.l1_block_number, sum(c.cost) as cost \
-         FROM {db}.l1_data_costs c \
-         INNER JOIN {db}.l1_head_events h \
-           ON c.l1_block_number = h.l1_block_number \
-         WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
-         GROUP BY c.l1_block_number \
-         ORDER BY c.l1_block_number ASC",
+            "SELECT operation, address, count() AS txs, \
+                    toUInt64(sum(gas_used)) AS gas_used, sum(cost) AS cost \
+             FROM ( \
+                SELECT g.operation AS operation, g.tx_hash AS tx_hash, \
+                       any(g.address) AS address, any(g.gas_used) AS gas_used, \
+                       any(g.cost) AS cost \
+                FROM {db}.{prefix}protocol_gas_spend g \
+                INNER JOIN {db}.{prefix}l1_head_events l1_events \
+                  ON g.l1_block_number = l1_events.l1_block_number \
+                WHERE l1_events.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+                GROUP BY operation, tx_hash \
+             ) \
+             GROUP BY operation, address \
+             ORDER BY cost DESC",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
-        let rows = self.execute::<RawRow>(&query).await?;
-        Ok(rows
-            .into_iter()
-            .map(|r| L1DataCostRow { l1_block_number: r.l1_block_number, cost: r.cost })
-            .collect())
+        self.execute::<ProtocolGasSpendRow>(&query).await
     }
 
-    /// Get the L1 data posting cost since the given cutoff time with cursor-based pagination.
-    /// Results are returned in descending order by block number.
-    pub async fn get_l1_data_costs_paginated(
+    /// Get the canonical L2 blocks of batch `batch_id` with their gas and transaction count,
+    /// ordered by block number descending
+    pub async fn get_batch_blocks(
         &self,
-        since: DateTime<Utc>,
+        batch_id: u64,
         limit: u64,
         starting_after: Option<u64>,
         ending_before: Option<u64>,
-    ) -> Result<Vec<L1DataCostRow>> {
-        #[derive(Row, Deserialize)]
-        struct RawRow {
-            l1_block_number: u64,
-            cost: u128,
-        }
-
-        // First join with l1_head_events to filter by time, then group by l1_block_number
+    ) -> Result<Vec<BatchL2BlockRow>> {
         let mut query = format!(
-            "SELECT c.l1_block_number, sum(c.cost) as cost \
-         FROM {db}.l1_data_costs c \
-         INNER JOIN {db}.l1_head_events h \
-           ON c.l1_block_number = h.l1_block_number \
-         WHERE h.block_ts >= {since}",
-            since = since.timestamp(),
+            "SELECT h.l2_block_number AS l2_block_number, h.block_ts AS block_ts, \
+                    h.sum_gas_used AS sum_gas_used, h.gas_limit AS gas_limit, \
+                    h.sum_tx AS sum_tx, h.sequencer AS sequencer \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.l2_block_number IN ( \
+                SELECT l2_block_number FROM {db}.{prefix}batch_blocks WHERE batch_id = {batch_id} \
+             ) \
+               AND {filter}",
+            filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(start) = starting_after {
-            query.push_str(&format!(" AND c.l1_block_number < {}", start));
+            query.push_str(&format!(" AND h.l2_block_number < {}", start));
         }
         if let Some(end) = ending_before {
-            query.push_str(&format!(" AND c.l1_block_number > {}", end));
+            query.push_str(&format!(" AND h.l2_block_number > {}", end));
         }
-        query.push_str(" GROUP BY c.l1_block_number");
-        query.push_str(" ORDER BY c.l1_block_number DESC");
+        query.push_str(" ORDER BY l2_block_number DESC LIMIT 1 BY l2_block_number");
         query.push_str(&format!(" LIMIT {}", limit));
 
-        let rows = self.execute::<RawRow>(&query).await?;
-        Ok(rows
-            .into_iter()
-            .map(|r| L1DataCostRow { l1_block_number: r.l1_block_number, cost: r.cost })
-            .collect())
+        self.execute::<BatchL2BlockRow>(&query).await.context("fetching batch blocks failed")
+    }
+
+    /// Get the finality stage transitions of an L2 block. Returns `None` if the block was
+    /// neither observed nor proposed.
+    pub async fn get_l2_block_status(&self, block_number: u64) -> Result<Option<L2BlockStatusRow>> {
+        // Verification is recorded against the last batch of a `BatchesVerified` event, so a
+        // batch is verified by the first event whose batch id is at least its own.
+        let query = format!(
+            "WITH (SELECT maxOrNull(batch_id) FROM {db}.{prefix}batch_blocks \
+                   WHERE l2_block_number = {block_number}) AS block_batch \
+             SELECT toUInt64({block_number}) AS l2_block_number, \
+                    (SELECT maxOrNull(block_ts) FROM {db}.{prefix}l2_head_events \
+                     WHERE l2_block_number = {block_number}) AS block_ts, \
+                    block_batch AS batch_id, \
+                    (SELECT minOrNull(l1.block_ts) FROM {db}.{prefix}batches b \
+                     INNER JOIN {db}.{prefix}l1_head_events l1 \
+                       ON b.l1_block_number = l1.l1_block_number \
+                     WHERE b.batch_id = block_batch) AS proposed_at, \
+                    (SELECT minOrNull(l1.block_ts) FROM {db}.{prefix}proved_batches pb \
+                     INNER JOIN {db}.{prefix}l1_head_events l1 \
+                       ON pb.l1_block_number = l1.l1_block_number \
+                     WHERE pb.batch_id = block_batch) AS proved_at, \
+                    (SELECT minOrNull(l1.block_ts) FROM {db}.{prefix}verified_batches vb \
+                     INNER JOIN {db}.{prefix}l1_head_events l1 \
+                       ON vb.l1_block_number = l1.l1_block_number \
+                     WHERE vb.batch_id >= block_batch) AS verified_at",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<L2BlockStatusRow>(&query).await?;
+        Ok(rows.into_iter().next().filter(|r| r.block_ts.is_some() || r.batch_id.is_some()))
+    }
+
+    /// Count the blocks in each finality stage among the latest `window` observed L2 blocks
+    pub async fn get_block_status_summary(
+        &self,
+        window: u64,
+    ) -> Result<Option<BlockStatusSummaryRow>> {
+        let query = format!(
+            "WITH (SELECT max(l2_block_number) FROM {db}.{prefix}l2_head_events) AS head, \
+                  (SELECT max(batch_id) FROM {db}.{prefix}verified_batches) AS last_verified \
+             SELECT head AS head_block, \
+                    countIf(b.batch_id = 0) AS preconfirmed, \
+                    countIf(b.batch_id != 0 AND b.batch_id > last_verified \
+                            AND pb.batch_id = 0) AS proposed, \
+                    countIf(b.batch_id != 0 AND b.batch_id > last_verified \
+                            AND pb.batch_id != 0) AS proved, \
+                    countIf(b.batch_id != 0 AND b.batch_id <= last_verified) AS verified \
+             FROM (SELECT DISTINCT l2_block_number FROM {db}.{prefix}l2_head_events \
+                   WHERE l2_block_number + {window} > head) h \
+             LEFT JOIN (SELECT l2_block_number, max(batch_id) AS batch_id \
+                        FROM {db}.{prefix}batch_blocks \
+                        WHERE l2_block_number + {window} > head \
+                        GROUP BY l2_block_number) b \
+               ON h.l2_block_number = b.l2_block_number \
+             LEFT JOIN (SELECT DISTINCT batch_id FROM {db}.{prefix}proved_batches \
+                        WHERE batch_id != 0) pb \
+               ON b.batch_id = pb.batch_id",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<BlockStatusSummaryRow>(&query).await?;
+        Ok(rows.into_iter().next().filter(|r| r.head_block != 0))
     }
 
     /// Get the total L1 data posting cost for the given range
@@ -2384,14 +4609,15 @@ impl ClickhouseReader {
 
         let mut query = format!(
             "SELECT sum(c.cost) AS total \
-             FROM {db}.l1_data_costs c \
-             INNER JOIN {db}.batches b \
+             FROM {db}.{prefix}l1_data_costs c \
+             INNER JOIN {db}.{prefix}batches b \
                ON c.batch_id = b.batch_id AND c.l1_block_number = b.l1_block_number \
-             INNER JOIN {db}.l1_head_events l1 \
+             INNER JOIN {db}.{prefix}l1_head_events l1 \
                ON b.l1_block_number = l1.l1_block_number \
              WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval})",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             query.push_str(&format!(" AND b.proposer_addr = unhex('{}')", encode(addr)));
@@ -2422,25 +4648,28 @@ impl ClickhouseReader {
                 priority_fee: u128,
                 base_fee: u128,
                 l1_data_cost: Option<u128>,
+                avg_gas_price: u128,
             }
 
             let mut query = format!(
                 "SELECT h.l2_block_number, \
                         sum_priority_fee AS priority_fee, \
                         sum_base_fee AS base_fee, \
-                        toNullable(if(b.batch_size > 0, intDiv(dc.cost, b.batch_size), NULL)) AS l1_data_cost \
-                 FROM {db}.l2_head_events h \
-                 LEFT JOIN (SELECT DISTINCT batch_id, l2_block_number FROM {db}.batch_blocks) bb \
+                        toNullable(if(b.batch_size > 0, intDiv(dc.cost, b.batch_size), NULL)) AS l1_data_cost, \
+                        h.avg_gas_price AS avg_gas_price \
+                 FROM {db}.{prefix}l2_head_events h \
+                 LEFT JOIN (SELECT DISTINCT batch_id, l2_block_number FROM {db}.{prefix}batch_blocks) bb \
                    ON h.l2_block_number = bb.l2_block_number \
-                 LEFT JOIN {db}.batches b \
+                 LEFT JOIN {db}.{prefix}batches b \
                    ON bb.batch_id = b.batch_id \
-                 LEFT JOIN {db}.l1_data_costs dc \
+                 LEFT JOIN {db}.{prefix}l1_data_costs dc \
                    ON b.batch_id = dc.batch_id AND b.l1_block_number = dc.l1_block_number \
                  WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                    AND {filter}",
                 interval = range.interval(),
                 filter = self.reorg_filter("h"),
                 db = self.db_name,
+                prefix = self.table_prefix,
             );
             if let Some(addr) = sequencer {
                 query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -2455,6 +4684,7 @@ impl ClickhouseReader {
                     priority_fee: r.priority_fee,
                     base_fee: r.base_fee,
                     l1_data_cost: r.l1_data_cost,
+                    avg_gas_price: r.avg_gas_price,
                 })
                 .collect());
         }
@@ -2466,25 +4696,29 @@ impl ClickhouseReader {
             priority_fee: u128,
             base_fee: u128,
             l1_data_cost: Option<u128>,
+            avg_gas_price: u128,
         }
 
         let mut inner = format!(
             "SELECT h.l2_block_number, \
                     sum_priority_fee AS priority_fee, \
                     sum_base_fee AS base_fee, \
-                    toNullable(if(b.batch_size > 0, intDiv(dc.cost, b.batch_size), NULL)) AS l1_data_cost \
-             FROM {db}.l2_head_events h \
-             LEFT JOIN (SELECT DISTINCT batch_id, l2_block_number FROM {db}.batch_blocks) bb \
+                    toNullable(if(b.batch_size > 0, intDiv(dc.cost, b.batch_size), NULL)) AS l1_data_cost, \
+                    h.avg_gas_price AS avg_gas_price, \
+                    h.sum_gas_used AS gas_used \
+             FROM {db}.{prefix}l2_head_events h \
+             LEFT JOIN (SELECT DISTINCT batch_id, l2_block_number FROM {db}.{prefix}batch_blocks) bb \
                ON h.l2_block_number = bb.l2_block_number \
-             LEFT JOIN {db}.batches b \
+             LEFT JOIN {db}.{prefix}batches b \
                ON bb.batch_id = b.batch_id \
-             LEFT JOIN {db}.l1_data_costs dc \
+             LEFT JOIN {db}.{prefix}l1_data_costs dc \
                ON b.batch_id = dc.batch_id AND b.l1_block_number = dc.l1_block_number \
              WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                AND {filter}",
             interval = range.interval(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             inner.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -2494,12 +4728,17 @@ impl ClickhouseReader {
             "SELECT l2_bucket AS l2_block_number, \
                     sum(priority_fee) AS priority_fee, \
                     sum(base_fee) AS base_fee, \
-                    if(sum(if(l1_data_cost IS NOT NULL, 1, 0)) > 0, sum(l1_data_cost), NULL) AS l1_data_cost \
+                    if(sum(if(l1_data_cost IS NOT NULL, 1, 0)) > 0, sum(l1_data_cost), NULL) AS l1_data_cost, \
+                    if(sumIf(gas_used, avg_gas_price > 0) > 0, \
+                       intDiv(sumIf(avg_gas_price * gas_used, avg_gas_price > 0), sumIf(gas_used, avg_gas_price > 0)), \
+                       toUInt128(0)) AS avg_gas_price \
              FROM ( \
                 SELECT intDiv(l2_block_number, {bucket}) * {bucket} AS l2_bucket, \
                        priority_fee, \
                        base_fee, \
-                       l1_data_cost \
+                       l1_data_cost, \
+                       avg_gas_price, \
+                       gas_used \
                 FROM ({inner}) AS base \
              ) AS sub \
              GROUP BY l2_bucket \
@@ -2516,6 +4755,7 @@ impl ClickhouseReader {
                 priority_fee: r.priority_fee,
                 base_fee: r.base_fee,
                 l1_data_cost: r.l1_data_cost,
+                avg_gas_price: r.avg_gas_price,
             })
             .collect())
     }
@@ -2525,6 +4765,7 @@ impl ClickhouseReader {
         &self,
         proposer: Option<AddressBytes>,
         range: TimeRange,
+        as_of: Option<DateTime<Utc>>,
     ) -> Result<Vec<BatchFeeComponentRow>> {
         #[derive(Row, Deserialize)]
         struct RawRow {
@@ -2536,6 +4777,7 @@ impl ClickhouseReader {
             base_fee: u128,
             l1_data_cost: Option<u128>,
             prove_cost: Option<u128>,
+            avg_gas_price: u128,
         }
 
         let query = format!(
@@ -2546,14 +4788,14 @@ WITH recent_batches AS (
         b.l1_block_number,
         b.l1_tx_hash,
         b.proposer_addr
-    FROM {db}.batches b
-    INNER JOIN {db}.l1_head_events l1 ON b.l1_block_number = l1.l1_block_number
-    WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval})
+    FROM {batches} b
+    INNER JOIN {l1_head_events} l1 ON b.l1_block_number = l1.l1_block_number
+    WHERE l1.block_ts >= toUnixTimestamp({now} - INTERVAL {interval})
     {proposer_clause}
 ),
 recent_batch_blocks AS (
     SELECT DISTINCT bb.batch_id, bb.l2_block_number
-    FROM {db}.batch_blocks bb
+    FROM {batch_blocks} bb
     INNER JOIN recent_batches rb USING (batch_id)
 )
 SELECT
@@ -2564,28 +4806,38 @@ SELECT
     coalesce(sum(h.sum_priority_fee), toUInt128(0)) AS priority_fee,
     coalesce(sum(h.sum_base_fee),   toUInt128(0)) AS base_fee,
     toNullable(max(dc.cost)) AS l1_data_cost,
-    toNullable(max(pc.cost)) AS prove_cost
+    toNullable(max(pc.cost)) AS prove_cost,
+    if(sumIf(h.sum_gas_used, h.avg_gas_price > 0) > 0,
+       intDiv(sumIf(h.avg_gas_price * h.sum_gas_used, h.avg_gas_price > 0),
+              sumIf(h.sum_gas_used, h.avg_gas_price > 0)),
+       toUInt128(0)) AS avg_gas_price
 FROM recent_batches rb
 INNER JOIN recent_batch_blocks bb USING (batch_id)
-LEFT JOIN {db}.l2_head_events h
+LEFT JOIN {l2_head_events} h
        ON bb.l2_block_number = h.l2_block_number
       AND {filter}                         -- keep reorg filter
-LEFT JOIN {db}.l1_data_costs dc
+LEFT JOIN {l1_data_costs} dc
        ON rb.batch_id = dc.batch_id AND rb.l1_block_number = dc.l1_block_number
-LEFT JOIN {db}.prove_costs pc
+LEFT JOIN {prove_costs} pc
        ON rb.batch_id = pc.batch_id
 GROUP BY rb.batch_id, rb.l1_block_number, rb.l1_tx_hash, rb.proposer_addr
 ORDER BY rb.batch_id ASC
 "#,
-            db = self.db_name,
+            batches = self.table_as_of("batches", as_of),
+            l1_head_events = self.table_as_of("l1_head_events", as_of),
+            batch_blocks = self.table_as_of("batch_blocks", as_of),
+            l2_head_events = self.table_as_of("l2_head_events", as_of),
+            l1_data_costs = self.table_as_of("l1_data_costs", as_of),
+            prove_costs = self.table_as_of("prove_costs", as_of),
+            now = now_as_of(as_of),
             interval = range.interval(),
-            filter = self.reorg_filter("h"),
+            filter = self.reorg_filter_as_of("h", as_of),
             proposer_clause = proposer
                 .map(|addr| format!("AND b.proposer_addr = unhex('{}')", encode(addr)))
                 .unwrap_or_default(),
         );
 
-        let rows = self.execute::<RawRow>(&query).await?;
+        let rows = self.execute_as_of::<RawRow>(&query, as_of).await?;
         Ok(rows
             .into_iter()
             .map(|r| BatchFeeComponentRow {
@@ -2597,6 +4849,7 @@ ORDER BY rb.batch_id ASC
                 base_fee: r.base_fee,
                 l1_data_cost: r.l1_data_cost,
                 prove_cost: r.prove_cost,
+                avg_gas_price: r.avg_gas_price,
             })
             .collect())
     }
@@ -2607,7 +4860,7 @@ ORDER BY rb.batch_id ASC
         proposer: Option<AddressBytes>,
         range: TimeRange,
     ) -> Result<Option<u128>> {
-        let rows = self.get_batch_fee_components(proposer, range).await?;
+        let rows = self.get_batch_fee_components(proposer, range, None).await?;
         let total: u128 = rows.iter().map(|r| r.priority_fee).sum();
         Ok((total > 0).then_some(total))
     }
@@ -2618,7 +4871,7 @@ ORDER BY rb.batch_id ASC
         proposer: Option<AddressBytes>,
         range: TimeRange,
     ) -> Result<Option<u128>> {
-        let rows = self.get_batch_fee_components(proposer, range).await?;
+        let rows = self.get_batch_fee_components(proposer, range, None).await?;
         let total: u128 = rows.iter().map(|r| r.base_fee).sum();
         Ok((total > 0).then_some(total))
     }
@@ -2629,7 +4882,7 @@ ORDER BY rb.batch_id ASC
         proposer: Option<AddressBytes>,
         range: TimeRange,
     ) -> Result<Option<u128>> {
-        let rows = self.get_batch_fee_components(proposer, range).await?;
+        let rows = self.get_batch_fee_components(proposer, range, None).await?;
         let total: u128 = rows.iter().map(|r| r.l1_data_cost.unwrap_or(0)).sum();
         Ok((total > 0).then_some(total))
     }
@@ -2648,20 +4901,122 @@ ORDER BY rb.batch_id ASC
         let query = format!(
             "SELECT b.proposer_addr AS proposer, \
                     sum(pc.cost) AS total_cost \
-             FROM {db}.prove_costs pc \
-             INNER JOIN {db}.batches b ON pc.batch_id = b.batch_id \
-             INNER JOIN {db}.l1_head_events l1 ON pc.l1_block_number = l1.l1_block_number \
+             FROM {db}.{prefix}prove_costs pc \
+             INNER JOIN {db}.{prefix}batches b ON pc.batch_id = b.batch_id \
+             INNER JOIN {db}.{prefix}l1_head_events l1 ON pc.l1_block_number = l1.l1_block_number \
              WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
              GROUP BY b.proposer_addr \
              ORDER BY total_cost DESC",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<RawRow>(&query).await?;
         Ok(rows.into_iter().map(|r| (r.proposer, r.total_cost)).collect())
     }
 
+    /// Get aggregated prover costs grouped by proposer for the given range, together with their
+    /// value in USD at the time each cost was incurred.
+    ///
+    /// Each cost is converted with the latest `eth_prices` snapshot taken at or before its L1
+    /// block. Costs older than the first snapshot count as zero in the USD total.
+    pub async fn get_prove_costs_by_proposer_usd(
+        &self,
+        range: TimeRange,
+    ) -> Result<Vec<(AddressBytes, u128, f64)>> {
+        #[derive(Row, Deserialize)]
+        struct RawRow {
+            proposer: AddressBytes,
+            total_cost: u128,
+            total_cost_usd: f64,
+        }
+
+        let query = format!(
+            "SELECT c.proposer AS proposer, \
+                    sum(c.cost) AS total_cost, \
+                    sum(toFloat64(c.cost) / 1e18 * p.usd) AS total_cost_usd \
+             FROM ( \
+                 SELECT b.proposer_addr AS proposer, pc.cost AS cost, l1.block_ts AS block_ts, \
+                        0 AS k \
+                 FROM {db}.{prefix}prove_costs pc \
+                 INNER JOIN {db}.{prefix}batches b ON pc.batch_id = b.batch_id \
+                 INNER JOIN {db}.{prefix}l1_head_events l1 \
+                   ON pc.l1_block_number = l1.l1_block_number \
+                 WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+             ) c \
+             ASOF LEFT JOIN ( \
+                 SELECT ts, usd, 0 AS k FROM {db}.{prefix}eth_prices \
+             ) p ON c.k = p.k AND c.block_ts >= p.ts \
+             GROUP BY c.proposer \
+             ORDER BY total_cost DESC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<RawRow>(&query).await?;
+        Ok(rows.into_iter().map(|r| (r.proposer, r.total_cost, r.total_cost_usd)).collect())
+    }
+
+    /// Get aggregated prover costs grouped by proposer for the given range, together with their
+    /// value in TAIKO at the time each cost was incurred.
+    ///
+    /// Each cost is converted with the latest `taiko_prices` snapshot taken at or before its L1
+    /// block. Costs older than the first snapshot count as zero in the TAIKO total.
+    pub async fn get_prove_costs_by_proposer_taiko(
+        &self,
+        range: TimeRange,
+    ) -> Result<Vec<(AddressBytes, u128, f64)>> {
+        #[derive(Row, Deserialize)]
+        struct RawRow {
+            proposer: AddressBytes,
+            total_cost: u128,
+            total_cost_taiko: f64,
+        }
+
+        let query = format!(
+            "SELECT c.proposer AS proposer, \
+                    sum(c.cost) AS total_cost, \
+                    sum(if(p.eth > 0, toFloat64(c.cost) / 1e18 / p.eth, 0)) AS total_cost_taiko \
+             FROM ( \
+                 SELECT b.proposer_addr AS proposer, pc.cost AS cost, l1.block_ts AS block_ts, \
+                        0 AS k \
+                 FROM {db}.{prefix}prove_costs pc \
+                 INNER JOIN {db}.{prefix}batches b ON pc.batch_id = b.batch_id \
+                 INNER JOIN {db}.{prefix}l1_head_events l1 \
+                   ON pc.l1_block_number = l1.l1_block_number \
+                 WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+             ) c \
+             ASOF LEFT JOIN ( \
+                 SELECT ts, eth, 0 AS k FROM {db}.{prefix}taiko_prices \
+             ) p ON c.k = p.k AND c.block_ts >= p.ts \
+             GROUP BY c.proposer \
+             ORDER BY total_cost DESC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<RawRow>(&query).await?;
+        Ok(rows.into_iter().map(|r| (r.proposer, r.total_cost, r.total_cost_taiko)).collect())
+    }
+
+    /// Get the most recent TAIKO price snapshot
+    pub async fn get_latest_taiko_price(&self) -> Result<Option<TaikoPriceInsertRow>> {
+        let query = format!(
+            "SELECT ts, eth, usd \
+             FROM {db}.{prefix}taiko_prices \
+             ORDER BY ts DESC \
+             LIMIT 1",
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        let rows = self.execute::<TaikoPriceInsertRow>(&query).await?;
+        Ok(rows.into_iter().next())
+    }
+
     /// Get aggregated batch fees grouped by proposer for the given range
     pub async fn get_batch_fees_by_proposer(
         &self,
@@ -2673,16 +5028,16 @@ ORDER BY rb.batch_id ASC
                     sum(h.sum_base_fee) AS base_fee, \
                     coalesce(sum(if(b.batch_size > 0, intDiv(dc.cost, b.batch_size), NULL)), toUInt128(0)) AS l1_data_cost, \
                     coalesce(sum(if(b.batch_size > 0, intDiv(pc.cost, b.batch_size), NULL)), toUInt128(0)) AS prove_cost \
-             FROM (SELECT DISTINCT batch_id, l2_block_number FROM {db}.batch_blocks) bb \
-             INNER JOIN {db}.batches b \
+             FROM (SELECT DISTINCT batch_id, l2_block_number FROM {db}.{prefix}batch_blocks) bb \
+             INNER JOIN {db}.{prefix}batches b \
                ON bb.batch_id = b.batch_id \
-             INNER JOIN {db}.l1_head_events l1 \
+             INNER JOIN {db}.{prefix}l1_head_events l1 \
                ON b.l1_block_number = l1.l1_block_number \
-             LEFT JOIN {db}.l2_head_events h \
+             LEFT JOIN {db}.{prefix}l2_head_events h \
                ON bb.l2_block_number = h.l2_block_number \
-             LEFT JOIN {db}.l1_data_costs dc \
+             LEFT JOIN {db}.{prefix}l1_data_costs dc \
                ON b.batch_id = dc.batch_id \
-             LEFT JOIN {db}.prove_costs pc \
+             LEFT JOIN {db}.{prefix}prove_costs pc \
                ON b.batch_id = pc.batch_id \
              WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                AND {filter} \
@@ -2691,6 +5046,7 @@ ORDER BY rb.batch_id ASC
             interval = range.interval(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<SequencerFeeRow>(&query).await?;
@@ -2708,13 +5064,14 @@ ORDER BY rb.batch_id ASC
     ) -> Result<Vec<ProveCostRow>> {
         let mut query = format!(
             "SELECT pc.l1_block_number, pc.batch_id, pc.cost \
-         FROM {db}.prove_costs pc \
-         INNER JOIN {db}.l1_head_events h \
+         FROM {db}.{prefix}prove_costs pc \
+         INNER JOIN {db}.{prefix}l1_head_events h \
            ON pc.l1_block_number = h.l1_block_number \
          WHERE h.block_ts >= toUnixTimestamp(fromUnixTimestamp({since})) \
            AND pc.cost > 0", // Only return non-zero costs
             since = since.timestamp(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(start) = starting_after {
             query.push_str(&format!(" AND pc.batch_id < {}", start));
@@ -2742,12 +5099,13 @@ ORDER BY rb.batch_id ASC
 
         let mut query = format!(
             "SELECT sum(pc.cost) AS total \
-             FROM {db}.prove_costs pc \
-             INNER JOIN {db}.batches b ON pc.batch_id = b.batch_id \
-             INNER JOIN {db}.l1_head_events l1 ON pc.l1_block_number = l1.l1_block_number \
+             FROM {db}.{prefix}prove_costs pc \
+             INNER JOIN {db}.{prefix}batches b ON pc.batch_id = b.batch_id \
+             INNER JOIN {db}.{prefix}l1_head_events l1 ON pc.l1_block_number = l1.l1_block_number \
              WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval})",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             query.push_str(&format!(" AND b.proposer_addr = unhex('{}')", encode(addr)));
@@ -2777,7 +5135,7 @@ ORDER BY rb.batch_id ASC
              FROM numbers({}, {})
              WHERE number NOT IN (
                  SELECT l1_block_number
-                 FROM {db}.l1_head_events
+                 FROM {db}.{prefix}l1_head_events
                  WHERE l1_block_number >= {} AND l1_block_number <= {}
              )
              ORDER BY number",
@@ -2786,10 +5144,11 @@ ORDER BY rb.batch_id ASC
             start_block,
             end_block,
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows =
-            self.base.query(&query).fetch_all::<BlockNumber>().await.map_err(eyre::Error::from)?;
+            self.run(&query, |client| client.query(&query).fetch_all::<BlockNumber>()).await?;
 
         Ok(rows.into_iter().map(|row| row.number).collect())
     }
@@ -2810,7 +5169,7 @@ ORDER BY rb.batch_id ASC
              FROM numbers({}, {})
              WHERE number NOT IN (
                  SELECT l2_block_number
-                 FROM {db}.l2_head_events
+                 FROM {db}.{prefix}l2_head_events
                  WHERE l2_block_number >= {} AND l2_block_number <= {}
              )
              ORDER BY number",
@@ -2819,10 +5178,11 @@ ORDER BY rb.batch_id ASC
             start_block,
             end_block,
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows =
-            self.base.query(&query).fetch_all::<BlockNumber>().await.map_err(eyre::Error::from)?;
+            self.run(&query, |client| client.query(&query).fetch_all::<BlockNumber>()).await?;
 
         Ok(rows.into_iter().map(|row| row.number).collect())
     }
@@ -2835,8 +5195,9 @@ ORDER BY rb.batch_id ASC
         }
 
         let query = format!(
-            "SELECT if(count() = 0, NULL, max(l1_block_number)) as max_block FROM {db}.l1_head_events",
-            db = self.db_name
+            "SELECT if(count() = 0, NULL, max(l1_block_number)) as max_block FROM {db}.{prefix}l1_head_events",
+            db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         // Use the execute method which handles errors more gracefully
@@ -2878,8 +5239,9 @@ ORDER BY rb.batch_id ASC
         }
 
         let query = format!(
-            "SELECT if(count() = 0, NULL, max(l2_block_number)) as max_block FROM {db}.l2_head_events",
-            db = self.db_name
+            "SELECT if(count() = 0, NULL, max(l2_block_number)) as max_block FROM {db}.{prefix}l2_head_events",
+            db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         // Use the execute method which handles errors more gracefully
@@ -2921,10 +5283,12 @@ ORDER BY rb.batch_id ASC
             min_block: Option<u64>,
         }
 
-        let query = format!("SELECT min(l1_block_number) FROM {}.l1_head_events", self.db_name);
+        let query = format!(
+            "SELECT min(l1_block_number) FROM {}.{}l1_head_events",
+            self.db_name, self.table_prefix
+        );
 
-        let row =
-            self.base.query(&query).fetch_one::<MinBlock>().await.map_err(eyre::Error::from)?;
+        let row = self.run(&query, |client| client.query(&query).fetch_one::<MinBlock>()).await?;
 
         Ok(row.min_block)
     }
@@ -2937,10 +5301,12 @@ ORDER BY rb.batch_id ASC
             min_block: Option<u64>,
         }
 
-        let query = format!("SELECT min(l2_block_number) FROM {}.l2_head_events", self.db_name);
+        let query = format!(
+            "SELECT min(l2_block_number) FROM {}.{}l2_head_events",
+            self.db_name, self.table_prefix
+        );
 
-        let row =
-            self.base.query(&query).fetch_one::<MinBlock>().await.map_err(eyre::Error::from)?;
+        let row = self.run(&query, |client| client.query(&query).fetch_one::<MinBlock>()).await?;
 
         Ok(row.min_block)
     }
@@ -2971,12 +5337,13 @@ ORDER BY rb.batch_id ASC
                         toUInt64OrNull(toString((h.block_ts - \
                             lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)))) \
                             AS s_since_prev_block \
-                 FROM {db}.l2_head_events h \
+                 FROM {db}.{prefix}l2_head_events h \
                  WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                    AND {filter}",
                 interval = range.interval(),
                 filter = self.reorg_filter("h"),
                 db = self.db_name,
+                prefix = self.table_prefix,
             );
             if let Some(addr) = sequencer {
                 query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -2994,6 +5361,7 @@ ORDER BY rb.batch_id ASC
                     Some(L2TpsRow {
                         l2_block_number: r.l2_block_number,
                         tps: r.sum_tx as f64 / s as f64,
+                        block_time: None,
                     })
                 })
                 .collect());
@@ -3003,12 +5371,13 @@ ORDER BY rb.batch_id ASC
             "SELECT h.l2_block_number, \
                     sum_tx, \
                     toUInt64OrNull(toString(if(isNull(lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)), NULL, h.block_ts - lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)))) AS s_since_prev_block \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                AND {filter}",
             interval = range.interval(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             inner.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -3031,7 +5400,7 @@ ORDER BY rb.batch_id ASC
         let rows = self.execute::<AggRow>(&query).await?;
         Ok(rows
             .into_iter()
-            .map(|r| L2TpsRow { l2_block_number: r.l2_block_number, tps: r.tps })
+            .map(|r| L2TpsRow { l2_block_number: r.l2_block_number, tps: r.tps, block_time: None })
             .collect())
     }
 
@@ -3056,10 +5425,11 @@ ORDER BY rb.batch_id ASC
             "SELECT h.l2_block_number, sum_tx, \
                     toUInt64OrNull(toString(if(isNull(lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)), NULL, h.block_ts - lagInFrame(h.block_ts) OVER (ORDER BY h.l2_block_number)))) \
                         AS s_since_prev_block \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE {filter}",
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         if let Some(start) = start_block {
@@ -3096,6 +5466,7 @@ ORDER BY rb.batch_id ASC
                     Some(L2TpsRow {
                         l2_block_number: r.l2_block_number,
                         tps: r.sum_tx as f64 / s as f64,
+                        block_time: None,
                     })
                 }
             })
@@ -3103,7 +5474,11 @@ ORDER BY rb.batch_id ASC
     }
 
     /// Get aggregated L2 fees grouped by sequencer for the given range
-    pub async fn get_l2_fees_by_sequencer(&self, range: TimeRange) -> Result<Vec<SequencerFeeRow>> {
+    pub async fn get_l2_fees_by_sequencer(
+        &self,
+        range: TimeRange,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<Vec<SequencerFeeRow>> {
         let query = format!(
             r#"
     WITH valid_batches AS (
@@ -3111,19 +5486,19 @@ ORDER BY rb.batch_id ASC
             b.batch_id,
             b.proposer_addr AS seq_addr,
             b.l1_block_number
-        FROM {db}.batches b
-        INNER JOIN {db}.l1_head_events l1 ON b.l1_block_number = l1.l1_block_number
-        WHERE l1.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval})
+        FROM {batches} b
+        INNER JOIN {l1_head_events} l1 ON b.l1_block_number = l1.l1_block_number
+        WHERE l1.block_ts >= toUnixTimestamp({now} - INTERVAL {interval})
     ),
     revenues AS (
     SELECT
         h.sequencer AS seq_addr,
         sum(h.sum_priority_fee) AS priority_fee,
         sum(h.sum_base_fee)   AS base_fee
-    FROM {db}.l2_head_events h
+    FROM {l2_head_events} h
     INNER JOIN (
         SELECT DISTINCT batch_id, l2_block_number
-        FROM {db}.batch_blocks
+        FROM {batch_blocks}
     ) bb ON bb.l2_block_number = h.l2_block_number
     INNER JOIN valid_batches vb ON vb.batch_id = bb.batch_id
     WHERE {filter}
@@ -3135,8 +5510,8 @@ ORDER BY rb.batch_id ASC
             sum(dc.cost) AS l1_data_cost,
             sum(pc.cost) AS prove_cost
         FROM valid_batches vb
-        LEFT JOIN {db}.l1_data_costs dc ON vb.batch_id = dc.batch_id AND vb.l1_block_number = dc.l1_block_number
-        LEFT JOIN {db}.prove_costs  pc ON vb.batch_id = pc.batch_id
+        LEFT JOIN {l1_data_costs} dc ON vb.batch_id = dc.batch_id AND vb.l1_block_number = dc.l1_block_number
+        LEFT JOIN {prove_costs}  pc ON vb.batch_id = pc.batch_id
         GROUP BY vb.seq_addr
     )
     SELECT
@@ -3154,31 +5529,199 @@ ORDER BY rb.batch_id ASC
     FULL OUTER JOIN costs c ON r.seq_addr = c.seq_addr
     ORDER BY priority_fee DESC
     "#,
-            db = self.db_name,
+            batches = self.table_as_of("batches", as_of),
+            l1_head_events = self.table_as_of("l1_head_events", as_of),
+            l2_head_events = self.table_as_of("l2_head_events", as_of),
+            batch_blocks = self.table_as_of("batch_blocks", as_of),
+            l1_data_costs = self.table_as_of("l1_data_costs", as_of),
+            prove_costs = self.table_as_of("prove_costs", as_of),
+            now = now_as_of(as_of),
             interval = range.interval(),
+            filter = self.reorg_filter_as_of("h", as_of),
+        );
+
+        self.execute_as_of(&query, as_of).await
+    }
+
+    /// Get the L2 blocks with the highest gas usage in the given time window
+    pub async fn get_top_l2_blocks_by_gas(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<L2BlockLeaderboardRow>> {
+        self.get_top_l2_blocks("sum_gas_used", since, until, limit).await
+    }
+
+    /// Get the L2 blocks with the most transactions in the given time window
+    pub async fn get_top_l2_blocks_by_tx_count(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<L2BlockLeaderboardRow>> {
+        self.get_top_l2_blocks("sum_tx", since, until, limit).await
+    }
+
+    /// Get the top `limit` non-reorged L2 blocks in the window, ordered by `order_column`
+    async fn get_top_l2_blocks(
+        &self,
+        order_column: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<L2BlockLeaderboardRow>> {
+        let query = format!(
+            "SELECT h.l2_block_number, h.block_ts, h.sequencer, \
+                    toUInt64(h.sum_gas_used) AS gas_used, h.sum_tx AS tx_count \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.block_ts > {since} \
+               AND h.block_ts <= {until} \
+               AND {filter} \
+             ORDER BY h.{order_column} DESC, h.l2_block_number DESC \
+             LIMIT {limit}",
+            since = since.timestamp(),
+            until = until.timestamp(),
+            filter = self.reorg_filter("h"),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<L2BlockLeaderboardRow>(&query).await
+    }
+
+    /// Get the batches that took longest to prove among those proved in the given time window
+    pub async fn get_slowest_prove_times(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<BatchProveTimeRow>> {
+        let query = format!(
+            "SELECT batch_id, toUInt64(prove_time_ms / 1000) AS seconds_to_prove \
+             FROM {db}.{prefix}batch_prove_times_mv \
+             WHERE proved_at > fromUnixTimestamp64Milli({since}) \
+               AND proved_at <= fromUnixTimestamp64Milli({until}) \
+               AND batch_id != 0 \
+             ORDER BY prove_time_ms DESC, batch_id DESC \
+             LIMIT {limit}",
+            since = since.timestamp_millis(),
+            until = until.timestamp_millis(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BatchProveTimeRow>(&query).await
+    }
+
+    /// Get block counts and fees per sequencer in the given time window, ordered by blocks
+    pub async fn get_sequencer_leaderboard(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<SequencerLeaderboardRow>> {
+        let query = format!(
+            "SELECT h.sequencer AS sequencer, \
+                    count() AS blocks, \
+                    sum(h.sum_priority_fee) AS priority_fee, \
+                    sum(h.sum_base_fee) AS base_fee \
+             FROM {db}.{prefix}l2_head_events h \
+             WHERE h.block_ts > {since} \
+               AND h.block_ts <= {until} \
+               AND {filter} \
+             GROUP BY h.sequencer \
+             ORDER BY blocks DESC",
+            since = since.timestamp(),
+            until = until.timestamp(),
             filter = self.reorg_filter("h"),
+            db = self.db_name,
+            prefix = self.table_prefix,
         );
 
-        self.execute(&query).await
+        self.execute::<SequencerLeaderboardRow>(&query).await
     }
 
     /// Get the blob count for each batch within the given range
     pub async fn get_blobs_per_batch(&self, range: TimeRange) -> Result<Vec<BatchBlobCountRow>> {
         let query = format!(
             "SELECT b.l1_block_number, b.batch_id, b.blob_count \
-             FROM {db}.batches b \
-             INNER JOIN {db}.l1_head_events l1_events \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l1_head_events l1_events \
                ON b.l1_block_number = l1_events.l1_block_number \
              WHERE l1_events.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
              ORDER BY b.l1_block_number ASC",
             interval = range.interval(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<BatchBlobCountRow>(&query).await?;
         Ok(rows)
     }
 
+    /// Get the blob usage, block count and transaction count of every batch proposed within the
+    /// given range, optionally only those posted with the given data availability mode, ordered
+    /// by batch id
+    pub async fn get_batch_efficiency(
+        &self,
+        range: TimeRange,
+        da_mode: Option<DaMode>,
+    ) -> Result<Vec<BatchEfficiencyRow>> {
+        let rf = self.reorg_filter("h");
+        let da_filter =
+            da_mode.map(|mode| format!("AND b.da_mode = {}", mode as u8)).unwrap_or_default();
+        let query = format!(
+            "SELECT b.batch_id, b.l1_block_number, b.blob_count, b.blob_total_bytes, b.da_mode, \
+                    b.calldata_bytes, b.batch_size AS blocks, toUInt64(t.txs) AS txs \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l1_head_events l1_events \
+               ON b.l1_block_number = l1_events.l1_block_number \
+             LEFT JOIN ( \
+                SELECT bb.batch_id, sum(h.sum_tx) AS txs \
+                FROM {db}.{prefix}batch_blocks bb \
+                INNER JOIN {db}.{prefix}l2_head_events h \
+                  ON h.l2_block_number = bb.l2_block_number \
+                WHERE bb.inserted_at >= now64() - INTERVAL {interval} \
+                  AND {rf} \
+                GROUP BY bb.batch_id \
+             ) t ON t.batch_id = b.batch_id \
+             WHERE l1_events.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+               {da_filter} \
+             ORDER BY b.batch_id ASC",
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<BatchEfficiencyRow>(&query).await
+    }
+
+    /// Get the number of batches and bytes posted per data availability mode over the given
+    /// range, in 24 buckets of at least five minutes
+    pub async fn get_da_mode_share(&self, range: TimeRange) -> Result<Vec<DaModeShareRow>> {
+        let bucket = (range.seconds() / 24).max(300);
+        let query = format!(
+            "SELECT toUInt64(intDiv(l1_events.block_ts, {bucket}) * {bucket}) AS bucket_ts, \
+                    countIf(b.da_mode = {blob}) AS blob_batches, \
+                    countIf(b.da_mode = {calldata}) AS calldata_batches, \
+                    sumIf(toUInt64(b.blob_total_bytes), b.da_mode = {blob}) AS blob_bytes, \
+                    sumIf(toUInt64(b.calldata_bytes), b.da_mode = {calldata}) AS calldata_bytes \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l1_head_events l1_events \
+               ON b.l1_block_number = l1_events.l1_block_number \
+             WHERE l1_events.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
+             GROUP BY bucket_ts \
+             ORDER BY bucket_ts ASC",
+            blob = DaMode::Blob as u8,
+            calldata = DaMode::Calldata as u8,
+            interval = range.interval(),
+            db = self.db_name,
+            prefix = self.table_prefix,
+        );
+
+        self.execute::<DaModeShareRow>(&query).await
+    }
+
     /// Get the blob count per batch since the given cutoff time with cursor-based pagination.
     /// Results are returned in descending order by batch id.
     pub async fn get_blobs_per_batch_paginated(
@@ -3190,12 +5733,13 @@ ORDER BY rb.batch_id ASC
     ) -> Result<Vec<BatchBlobCountRow>> {
         let mut query = format!(
             "SELECT b.l1_block_number, b.batch_id, b.blob_count \
-             FROM {db}.batches b \
-             INNER JOIN {db}.l1_head_events l1_events \
+             FROM {db}.{prefix}batches b \
+             INNER JOIN {db}.{prefix}l1_head_events l1_events \
                ON b.l1_block_number = l1_events.l1_block_number \
              WHERE l1_events.block_ts >= {since}",
             since = since.timestamp(),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(start) = starting_after {
             query.push_str(&format!(" AND b.batch_id < {}", start));
@@ -3216,6 +5760,7 @@ ORDER BY rb.batch_id ASC
         &self,
         since: DateTime<Utc>,
         until: DateTime<Utc>,
+        as_of: Option<DateTime<Utc>>,
     ) -> Result<Vec<SequencerDistributionRow>> {
         let query = format!(
             r#"
@@ -3226,29 +5771,60 @@ SELECT
   toUInt64(min(h.block_ts))        AS min_ts,
   toUInt64(max(h.block_ts))        AS max_ts,
   sum(h.sum_tx)                    AS tx_sum
-FROM {db}.l2_head_events h
-INNER JOIN (SELECT DISTINCT batch_id, l2_block_number FROM {db}.batch_blocks) bb ON bb.l2_block_number = h.l2_block_number
-INNER JOIN {db}.batches b       ON b.batch_id = bb.batch_id
-INNER JOIN {db}.l1_head_events l1 ON l1.l1_block_number = b.l1_block_number
+FROM {l2_head_events} h
+INNER JOIN (SELECT DISTINCT batch_id, l2_block_number FROM {batch_blocks}) bb ON bb.l2_block_number = h.l2_block_number
+INNER JOIN {batches} b       ON b.batch_id = bb.batch_id
+INNER JOIN {l1_head_events} l1 ON l1.l1_block_number = b.l1_block_number
 WHERE l1.block_ts > {since}
   AND l1.block_ts <= {until}
   AND {filter}
 GROUP BY b.proposer_addr
 ORDER BY blocks DESC
 "#,
-            db = self.db_name,
+            l2_head_events = self.table_as_of("l2_head_events", as_of),
+            batch_blocks = self.table_as_of("batch_blocks", as_of),
+            batches = self.table_as_of("batches", as_of),
+            l1_head_events = self.table_as_of("l1_head_events", as_of),
             since = since.timestamp(),
             until = until.timestamp(),
-            filter = self.reorg_filter("h"),
+            filter = self.reorg_filter_as_of("h", as_of),
         );
 
         let rows = self
-            .execute::<SequencerDistributionRow>(&query)
+            .execute_as_of::<SequencerDistributionRow>(&query, as_of)
             .await
             .context("fetching sequencer distribution failed")?;
         Ok(rows)
     }
 
+    /// Get the number of L2 blocks per distinct extra data with a block timestamp in
+    /// `(since, until]`. Blocks ingested before the extra data was recorded are left out.
+    pub async fn get_l2_extra_data_counts(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        as_of: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ExtraDataCountRow>> {
+        let query = format!(
+            "SELECT h.extra_data AS extra_data, count() AS blocks \
+             FROM {l2_head_events} h \
+             WHERE h.block_ts > {since} \
+               AND h.block_ts <= {until} \
+               AND h.extra_data != '' \
+               AND {filter} \
+             GROUP BY h.extra_data \
+             ORDER BY blocks DESC",
+            l2_head_events = self.table_as_of("l2_head_events", as_of),
+            since = since.timestamp(),
+            until = until.timestamp(),
+            filter = self.reorg_filter_as_of("h", as_of),
+        );
+
+        self.execute_as_of::<ExtraDataCountRow>(&query, as_of)
+            .await
+            .context("fetching L2 extra data counts failed")
+    }
+
     /// Get aggregated block transactions with automatic bucketing based on time range
     pub async fn get_block_transactions(
         &self,
@@ -3274,12 +5850,13 @@ ORDER BY blocks DESC
         if bucket <= 1 {
             let mut query = format!(
                 "SELECT sequencer, h.l2_block_number, h.block_ts AS block_time, sum_tx \
-             FROM {db}.l2_head_events h \
+             FROM {db}.{prefix}l2_head_events h \
              WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
                AND {filter}",
                 interval = range.interval(),
                 filter = self.reorg_filter("h"),
                 db = self.db_name,
+                prefix = self.table_prefix,
             );
             if let Some(addr) = sequencer {
                 query.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -3300,12 +5877,13 @@ ORDER BY blocks DESC
 
         let mut inner = format!(
             "SELECT sequencer, h.l2_block_number, h.block_ts AS block_time, sum_tx \
-         FROM {db}.l2_head_events h \
+         FROM {db}.{prefix}l2_head_events h \
          WHERE h.block_ts >= toUnixTimestamp(now64() - INTERVAL {interval}) \
            AND {filter}",
             interval = range.interval(),
             filter = self.reorg_filter("h"),
             db = self.db_name,
+            prefix = self.table_prefix,
         );
         if let Some(addr) = sequencer {
             inner.push_str(&format!(" AND sequencer = unhex('{}')", encode(addr)));
@@ -3362,12 +5940,13 @@ ORDER BY blocks DESC
              FROM (\
                  SELECT block_hash, l2_block_number, \
                         ROW_NUMBER() OVER (PARTITION BY l2_block_number ORDER BY inserted_at DESC) as rn \
-                 FROM {db}.l2_head_events \
+                 FROM {db}.{prefix}l2_head_events \
                  WHERE l2_block_number IN ({block_list})\
              ) ranked \
              WHERE rn = 1 \
              ORDER BY l2_block_number",
             db = self.db_name,
+            prefix = self.table_prefix,
         );
 
         let rows = self.execute::<HashRow>(&query).await?;
@@ -3379,12 +5958,52 @@ ORDER BY blocks DESC
         &self,
         proposer: Option<AddressBytes>,
         range: TimeRange,
+        as_of: Option<DateTime<Utc>>,
     ) -> Result<(Vec<SequencerFeeRow>, Vec<BatchFeeComponentRow>)> {
         // Fetch both concurrently
         let (sequencer_fees, batch_components) = try_join!(
-            self.get_l2_fees_by_sequencer(range),
-            self.get_batch_fee_components(proposer, range)
+            self.get_l2_fees_by_sequencer(range, as_of),
+            self.get_batch_fee_components(proposer, range, as_of)
         )?;
         Ok((sequencer_fees, batch_components))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn as_of_restricts_tables_to_rows_inserted_by_then() {
+        let url = Url::parse("http://127.0.0.1:1").unwrap();
+        let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .unwrap()
+            .with_table_prefix("p_");
+        let as_of = DateTime::from_timestamp_millis(1_700_000_000_250).unwrap();
+
+        assert_eq!(reader.table_as_of("batch_blocks", None), "db.p_batch_blocks");
+        assert_eq!(
+            reader.table_as_of("batch_blocks", Some(as_of)),
+            "(SELECT * FROM db.p_batch_blocks WHERE inserted_at <= toDateTime64(1700000000.250, 3))"
+        );
+        assert_eq!(now_as_of(None), "now64()");
+    }
+
+    #[test]
+    fn as_of_deduplicates_after_filtering() {
+        let url = Url::parse("http://127.0.0.1:1").unwrap();
+        let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .unwrap()
+            .with_table_prefix("p_");
+        let as_of = DateTime::from_timestamp_millis(1_700_000_000_250).unwrap();
+
+        // A batch re-inserted after `as_of` must still read as its earlier copy, so the filter
+        // runs before the latest copy of each key is picked
+        assert_eq!(reader.table_as_of("batches", None), "db.p_batches");
+        assert_eq!(
+            reader.table_as_of("batches", Some(as_of)),
+            "(SELECT * FROM db.p_batches WHERE inserted_at <= toDateTime64(1700000000.250, 3) \
+             ORDER BY inserted_at DESC LIMIT 1 BY batch_id, l1_block_number)"
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! Query timeouts and concurrency limits
+//!
+//! API queries are bounded both in duration and in how many may run at once, so that a slow
+//! query or a burst of dashboard requests cannot pile up behind an overloaded `ClickHouse`.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// How long a query waits for a free slot before the reader reports it is saturated
+pub(super) const QUEUE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A query that was rejected or abandoned by the reader's limits.
+///
+/// Carried inside the [`eyre::Report`] returned by reader methods; callers can tell overload
+/// apart from other failures with `report.downcast_ref::<QueryError>()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryError {
+    /// Every query slot stayed busy for longer than [`QUEUE_TIMEOUT`]
+    Saturated,
+    /// The query did not finish within the configured timeout
+    TimedOut(Duration),
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Saturated => write!(f, "too many concurrent queries"),
+            Self::TimedOut(timeout) => write!(f, "query timed out after {timeout:?}"),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Limits applied to every query of a reader.
+#[derive(Debug, Clone, Default)]
+pub(super) struct QueryLimits {
+    /// Maximum duration of a single attempt
+    pub(super) timeout: Option<Duration>,
+    /// Slots shared by all clones of the reader
+    pub(super) slots: Option<Arc<Semaphore>>,
+}
+
+impl QueryLimits {
+    /// Take a query slot, waiting at most [`QUEUE_TIMEOUT`] for one to free up.
+    pub(super) async fn acquire(&self) -> Result<Option<OwnedSemaphorePermit>, QueryError> {
+        let Some(slots) = &self.slots else {
+            return Ok(None);
+        };
+        match tokio::time::timeout(QUEUE_TIMEOUT, Arc::clone(slots).acquire_owned()).await {
+            Ok(Ok(permit)) => Ok(Some(permit)),
+            // The semaphore is never closed, so this only happens on timeout
+            _ => Err(QueryError::Saturated),
+        }
+    }
+}
@@ -1,7 +1,13 @@
 mod client;
+mod limits;
+mod replicas;
+mod request_id;
 mod time_range;
 
-pub use client::ClickhouseReader;
+pub use client::{ClickhouseReader, RowStream};
+pub use limits::QueryError;
+pub use replicas::ReplicaStats;
+pub use request_id::{current_request_id, with_request_id};
 pub use time_range::TimeRange;
 
 #[cfg(test)]
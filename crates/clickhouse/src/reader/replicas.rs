@@ -0,0 +1,211 @@
+//! Read replica routing
+//!
+//! Queries are spread round-robin over the primary and its read replicas. A replica that fails
+//! [`FAILURE_THRESHOLD`] queries in a row is only used as a last resort for
+//! [`UNHEALTHY_COOLDOWN`], after which it is tried again.
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{
+        Mutex,
+        atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use clickhouse::Client;
+use derive_more::Debug;
+use url::Url;
+
+/// Consecutive failed queries before a replica is marked unhealthy
+const FAILURE_THRESHOLD: u32 = 3;
+/// How long an unhealthy replica is skipped
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Query counters and health of a single `ClickHouse` endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplicaStats {
+    /// Endpoint URL
+    pub url: Url,
+    /// Whether the replica is currently used for routing
+    pub healthy: bool,
+    /// Queries sent to the replica
+    pub queries: u64,
+    /// Queries that failed
+    pub errors: u64,
+    /// Average query latency in milliseconds
+    pub avg_latency_ms: f64,
+}
+
+#[derive(Debug)]
+struct Replica {
+    url: Url,
+    #[debug(skip)]
+    client: Client,
+    consecutive_failures: AtomicU32,
+    unhealthy_until: Mutex<Option<Instant>>,
+    queries: AtomicU64,
+    errors: AtomicU64,
+    total_latency_us: AtomicU64,
+}
+
+impl Replica {
+    const fn new(url: Url, client: Client) -> Self {
+        Self {
+            url,
+            client,
+            consecutive_failures: AtomicU32::new(0),
+            unhealthy_until: Mutex::new(None),
+            queries: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            total_latency_us: AtomicU64::new(0),
+        }
+    }
+
+    fn is_healthy(&self) -> bool {
+        let until = *self.unhealthy_until.lock().unwrap_or_else(|e| e.into_inner());
+        until.is_none_or(|until| Instant::now() >= until)
+    }
+}
+
+/// The primary `ClickHouse` endpoint followed by its read replicas.
+#[derive(Debug)]
+pub(super) struct ReplicaPool {
+    replicas: Vec<Replica>,
+    next: AtomicUsize,
+}
+
+impl ReplicaPool {
+    /// Create a pool with only the primary endpoint.
+    pub(super) fn new(url: Url, client: Client) -> Self {
+        Self { replicas: vec![Replica::new(url, client)], next: AtomicUsize::new(0) }
+    }
+
+    /// Create a pool from the primary and `replica_urls`. Replicas share the primary's
+    /// credentials and settings.
+    pub(super) fn with_replicas(&self, replica_urls: Vec<Url>) -> Self {
+        let primary = &self.replicas[0];
+        let mut replicas = vec![Replica::new(primary.url.clone(), primary.client.clone())];
+        replicas.extend(replica_urls.into_iter().map(|url| {
+            let client = primary.client.clone().with_url(url.clone());
+            Replica::new(url, client)
+        }));
+        Self { replicas, next: AtomicUsize::new(0) }
+    }
+
+    /// Indices of the replicas to try, in order.
+    ///
+    /// Routing starts at the next replica in round-robin order, or at a replica derived from
+    /// `sticky_key` so that repeated queries land on the same replica. Unhealthy replicas are
+    /// moved to the end of the list.
+    pub(super) fn candidates(&self, sticky_key: Option<&str>) -> Vec<usize> {
+        let len = self.replicas.len();
+        let start = match sticky_key {
+            Some(key) => {
+                let mut hasher = DefaultHasher::new();
+                key.hash(&mut hasher);
+                (hasher.finish() % len as u64) as usize
+            }
+            None => self.next.fetch_add(1, Ordering::Relaxed) % len,
+        };
+        let (mut healthy, unhealthy): (Vec<_>, Vec<_>) =
+            (0..len).map(|i| (start + i) % len).partition(|&i| self.replicas[i].is_healthy());
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    /// Client of the replica at `index`.
+    pub(super) fn client(&self, index: usize) -> &Client {
+        &self.replicas[index].client
+    }
+
+    /// URL of the replica at `index`.
+    pub(super) fn url(&self, index: usize) -> &Url {
+        &self.replicas[index].url
+    }
+
+    /// Record a query against the replica at `index`. Failed queries count towards marking the
+    /// replica unhealthy; a successful one marks it healthy again.
+    pub(super) fn record(&self, index: usize, latency: Duration, ok: bool) {
+        let replica = &self.replicas[index];
+        replica.queries.fetch_add(1, Ordering::Relaxed);
+        replica.total_latency_us.fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+
+        let mut until = replica.unhealthy_until.lock().unwrap_or_else(|e| e.into_inner());
+        if ok {
+            replica.consecutive_failures.store(0, Ordering::Relaxed);
+            *until = None;
+            return;
+        }
+        replica.errors.fetch_add(1, Ordering::Relaxed);
+        let failures = replica.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= FAILURE_THRESHOLD {
+            *until = Some(Instant::now() + UNHEALTHY_COOLDOWN);
+        }
+    }
+
+    /// Counters of every replica, primary first.
+    pub(super) fn stats(&self) -> Vec<ReplicaStats> {
+        self.replicas
+            .iter()
+            .map(|replica| {
+                let queries = replica.queries.load(Ordering::Relaxed);
+                let total_us = replica.total_latency_us.load(Ordering::Relaxed);
+                ReplicaStats {
+                    url: replica.url.clone(),
+                    healthy: replica.is_healthy(),
+                    queries,
+                    errors: replica.errors.load(Ordering::Relaxed),
+                    avg_latency_ms: if queries == 0 {
+                        0.0
+                    } else {
+                        total_us as f64 / queries as f64 / 1000.0
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(replicas: usize) -> ReplicaPool {
+        let url = |i| Url::parse(&format!("http://replica-{i}:8123")).unwrap();
+        ReplicaPool::new(url(0), Client::default()).with_replicas((1..=replicas).map(url).collect())
+    }
+
+    #[test]
+    fn rotates_round_robin() {
+        let pool = pool(2);
+        assert_eq!(pool.candidates(None), vec![0, 1, 2]);
+        assert_eq!(pool.candidates(None), vec![1, 2, 0]);
+        assert_eq!(pool.candidates(None), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn sticky_key_routes_to_same_replica() {
+        let pool = pool(3);
+        let first = pool.candidates(Some("SELECT 1 GROUP BY 1"));
+        for _ in 0..5 {
+            assert_eq!(pool.candidates(Some("SELECT 1 GROUP BY 1")), first);
+        }
+    }
+
+    #[test]
+    fn failing_replica_moves_to_end() {
+        let pool = pool(2);
+        for _ in 0..FAILURE_THRESHOLD {
+            pool.record(0, Duration::from_millis(10), false);
+        }
+        assert_eq!(pool.candidates(None), vec![1, 2, 0]);
+        assert!(!pool.stats()[0].healthy);
+
+        pool.record(0, Duration::from_millis(30), true);
+        let stats = &pool.stats()[0];
+        assert!(stats.healthy);
+        assert_eq!((stats.queries, stats.errors), (4, 3));
+        assert!((stats.avg_latency_ms - 15.0).abs() < f64::EPSILON);
+    }
+}
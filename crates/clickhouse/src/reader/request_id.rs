@@ -0,0 +1,34 @@
+//! Request id propagation
+//!
+//! The API server runs every request inside [`with_request_id`]. Queries issued while handling
+//! the request carry the id as the `ClickHouse` `log_comment` setting, so slow or failing queries
+//! in `system.query_log` can be matched to the API request that caused them.
+
+use std::future::Future;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Run `fut` with `request_id` attached to every query it issues.
+pub async fn with_request_id<F: Future>(request_id: String, fut: F) -> F::Output {
+    REQUEST_ID.scope(request_id, fut).await
+}
+
+/// The request id of the API request currently being handled, if any.
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{current_request_id, with_request_id};
+
+    #[tokio::test]
+    async fn request_id_is_scoped() {
+        assert_eq!(current_request_id(), None);
+        let id = with_request_id("abc".to_owned(), async { current_request_id() }).await;
+        assert_eq!(id.as_deref(), Some("abc"));
+        assert_eq!(current_request_id(), None);
+    }
+}
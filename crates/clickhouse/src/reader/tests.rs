@@ -4,6 +4,7 @@ use clickhouse::{
     Row,
     test::{Mock, handlers},
 };
+use primitives::da::DaMode;
 
 #[derive(Row, serde::Serialize)]
 struct FeeRow {
@@ -11,6 +12,7 @@ struct FeeRow {
     priority_fee: u128,
     base_fee: u128,
     l1_data_cost: Option<u128>,
+    avg_gas_price: u128,
 }
 
 #[tokio::test]
@@ -21,6 +23,7 @@ async fn fee_components_returns_expected_rows() {
         priority_fee: 10,
         base_fee: 20,
         l1_data_cost: Some(5),
+        avg_gas_price: 7,
     }]));
 
     let url = url::Url::parse(mock.url()).unwrap();
@@ -35,6 +38,7 @@ async fn fee_components_returns_expected_rows() {
             priority_fee: 10,
             base_fee: 20,
             l1_data_cost: Some(5),
+            avg_gas_price: 7,
         }]
     );
 }
@@ -65,7 +69,7 @@ async fn fees_by_sequencer_returns_expected_rows() {
     let url = url::Url::parse(mock.url()).unwrap();
     let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
 
-    let rows = reader.get_l2_fees_by_sequencer(TimeRange::LastHour).await.unwrap();
+    let rows = reader.get_l2_fees_by_sequencer(TimeRange::LastHour, None).await.unwrap();
 
     assert_eq!(
         rows,
@@ -141,6 +145,7 @@ struct BatchFeeRow {
     base_fee: u128,
     l1_data_cost: Option<u128>,
     prove_cost: Option<u128>,
+    avg_gas_price: u128,
 }
 
 #[tokio::test]
@@ -155,12 +160,13 @@ async fn batch_fee_components_returns_expected_rows() {
         base_fee: 20,
         l1_data_cost: Some(5),
         prove_cost: Some(3),
+        avg_gas_price: 7,
     }]));
 
     let url = url::Url::parse(mock.url()).unwrap();
     let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
 
-    let rows = reader.get_batch_fee_components(None, TimeRange::LastHour).await.unwrap();
+    let rows = reader.get_batch_fee_components(None, TimeRange::LastHour, None).await.unwrap();
 
     assert_eq!(
         rows,
@@ -173,6 +179,7 @@ async fn batch_fee_components_returns_expected_rows() {
             base_fee: 20,
             l1_data_cost: Some(5),
             prove_cost: Some(3),
+            avg_gas_price: 7,
         }]
     );
 }
@@ -190,6 +197,7 @@ async fn batch_total_fee_helpers_return_expected_values() {
             base_fee: 20,
             l1_data_cost: Some(5),
             prove_cost: Some(3),
+            avg_gas_price: 7,
         }]));
     }
 
@@ -231,3 +239,1330 @@ async fn batch_fees_by_proposer_returns_expected_rows() {
         }]
     );
 }
+
+#[tokio::test]
+async fn verified_batches_without_proof_returns_expected_rows() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![BatchAnomalyRow { batch_id: 7, l1_block_number: 100 }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_verified_batches_without_proof(10).await.unwrap();
+
+    assert_eq!(rows, vec![BatchAnomalyRow { batch_id: 7, l1_block_number: 100 }]);
+}
+
+#[tokio::test]
+async fn count_proved_batches_without_proposal_returns_count() {
+    #[derive(Row, serde::Serialize)]
+    struct CountRow {
+        count: u64,
+    }
+
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![CountRow { count: 250 }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.count_proved_batches_without_proposal().await.unwrap(), 250);
+}
+
+#[tokio::test]
+async fn missing_batch_ids_returns_expected_rows() {
+    let mock = Mock::new();
+    let gap =
+        BatchIdGapRow { first_missing: 5, last_missing: 6, from_l1_block: 90, to_l1_block: 95 };
+    mock.add(handlers::provide(vec![gap]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.find_missing_batch_ids(0, 10).await.unwrap();
+
+    assert_eq!(
+        rows,
+        vec![BatchIdGapRow {
+            first_missing: 5,
+            last_missing: 6,
+            from_l1_block: 90,
+            to_l1_block: 95
+        }]
+    );
+}
+
+#[tokio::test]
+async fn proved_batches_without_prove_cost_returns_expected_rows() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![
+        BatchAnomalyRow { batch_id: 3, l1_block_number: 20 },
+        BatchAnomalyRow { batch_id: 2, l1_block_number: 10 },
+    ]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_proved_batches_without_prove_cost(10).await.unwrap();
+
+    assert_eq!(
+        rows,
+        vec![
+            BatchAnomalyRow { batch_id: 3, l1_block_number: 20 },
+            BatchAnomalyRow { batch_id: 2, l1_block_number: 10 },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn sequencer_leaderboard_returns_expected_rows() {
+    let mock = Mock::new();
+    let addr = AddressBytes([2u8; 20]);
+    mock.add(handlers::provide(vec![SequencerLeaderboardRow {
+        sequencer: addr,
+        blocks: 42,
+        priority_fee: 100,
+        base_fee: 200,
+    }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let since = until - chrono::Duration::hours(1);
+    let rows = reader.get_sequencer_leaderboard(since, until).await.unwrap();
+
+    assert_eq!(
+        rows,
+        vec![SequencerLeaderboardRow {
+            sequencer: addr,
+            blocks: 42,
+            priority_fee: 100,
+            base_fee: 200
+        }]
+    );
+}
+
+#[derive(Row, serde::Serialize)]
+struct L1DataCostComponentsRow {
+    l1_block_number: u64,
+    cost: u128,
+    blob_cost: u128,
+    calldata_cost: u128,
+}
+
+#[tokio::test]
+async fn l1_data_costs_paginated_returns_cost_components() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![L1DataCostComponentsRow {
+        l1_block_number: 5,
+        cost: 42,
+        blob_cost: 12,
+        calldata_cost: 30,
+    }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows =
+        reader.get_l1_data_costs_paginated(chrono::Utc::now(), 10, None, None).await.unwrap();
+
+    assert_eq!(
+        rows,
+        vec![L1DataCostRow { l1_block_number: 5, cost: 42, blob_cost: 12, calldata_cost: 30 }]
+    );
+}
+
+#[tokio::test]
+async fn protocol_config_returns_latest_row() {
+    let row = ProtocolConfigRow {
+        inbox_address: AddressBytes([2u8; 20]),
+        chain_id: 167000,
+        max_unverified_batches: 324_000,
+        batch_ring_buffer_size: 324_512,
+        max_batches_to_verify: 16,
+        block_max_gas_limit: 240_000_000,
+        liveness_bond_base: 125,
+        liveness_bond_per_block: 0,
+        state_root_sync_internal: 4,
+        max_anchor_height_offset: 64,
+        base_fee_sharing_pctg: 75,
+        proving_window: 7200,
+        cooldown_window: 14_400,
+        max_signals_to_receive: 16,
+        max_blocks_per_batch: 768,
+        ontake_fork_height: 538_304,
+        pacaya_fork_height: 1_166_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row.clone()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_protocol_config().await.unwrap(), Some(row));
+}
+
+#[tokio::test]
+async fn blob_fee_history_paginated_returns_rows() {
+    let row = || BlobFeeHistoryRow {
+        batch_id: 12,
+        l1_block_number: 100,
+        block_ts: 1_700_000_000,
+        excess_blob_gas: 393_216,
+        blob_base_fee: 1_000_000_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows =
+        reader.get_blob_fee_history_paginated(chrono::Utc::now(), 10, None, None).await.unwrap();
+
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn anchor_lag_returns_buckets() {
+    let row = || AnchorLagRow {
+        bucket_ts: 1_700_000_040,
+        blocks: 30,
+        avg_lag: 4.5,
+        p50_lag: 4,
+        p95_lag: 8,
+        max_lag: 9,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_anchor_lag(TimeRange::LastHour).await.unwrap();
+
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn gas_saturation_returns_buckets() {
+    let row = || GasSaturationRow {
+        bucket_ts: 1_700_000_040,
+        blocks: 30,
+        avg_saturation: 0.42,
+        p95_saturation: 0.9,
+        max_saturation: 0.97,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_gas_saturation(TimeRange::LastHour).await.unwrap();
+
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn l2_time_buckets_returns_buckets() {
+    let row = || L2TimeBucketRow {
+        bucket_ts: 1_700_000_040,
+        first_block: 100,
+        last_block: 129,
+        first_block_ts: 1_700_000_041,
+        last_block_ts: 1_700_000_099,
+        blocks: 30,
+        tx_count: 120,
+        gas_used: 3_000_000,
+        priority_fee: 42,
+        base_fee: 84,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_l2_time_buckets(None, TimeRange::LastHour, 60, None).await.unwrap();
+
+    assert_eq!(rows, vec![row()]);
+    assert_eq!(rows[0].tps(60), 2.0);
+    assert_eq!(rows[0].avg_gas_used(), 100_000);
+    assert_eq!(rows[0].avg_block_time_secs(), Some(2));
+}
+
+#[tokio::test]
+async fn gas_saturation_summary_defaults_without_blocks() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(Vec::<GasSaturationSummaryRow>::new()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let summary = reader.get_gas_saturation_summary(TimeRange::Last15Min).await.unwrap();
+
+    assert_eq!(summary, GasSaturationSummaryRow::default());
+}
+
+#[tokio::test]
+async fn rpc_status_returns_rows() {
+    let row = || RpcStatusRow {
+        url: "https://rpc.example".to_owned(),
+        probes: 60,
+        failures: 3,
+        avg_latency_ms: Some(110.5),
+        p95_latency_ms: Some(240.0),
+        last_healthy: 1,
+        last_block_lag: Some(1),
+        last_probed_at: 1_700_000_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_rpc_status(TimeRange::Last24Hours).await.unwrap();
+
+    assert_eq!(rows, vec![row()]);
+}
+
+#[derive(Row, serde::Serialize)]
+struct ProvedAtRow {
+    batch_id: u64,
+    proved_at: u64,
+}
+
+#[tokio::test]
+async fn batch_proved_times_returns_expected_rows() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![ProvedAtRow { batch_id: 7, proved_at: 1_700_000_000_000 }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_batch_proved_times(&[7, 8]).await.unwrap();
+    let expected = chrono::DateTime::from_timestamp_millis(1_700_000_000_000).unwrap();
+    assert_eq!(rows, vec![(7, expected)]);
+
+    // No query is sent without batch ids
+    assert!(reader.get_batch_proved_times(&[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn fails_over_to_replica_when_primary_is_down() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![ProvedAtRow { batch_id: 7, proved_at: 1_700_000_000_000 }]));
+
+    let primary = url::Url::parse("http://127.0.0.1:1").unwrap();
+    let replica = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(primary, "db".to_owned(), "user".into(), "pass".into())
+        .unwrap()
+        .with_replicas(vec![replica]);
+
+    let rows = reader.get_batch_proved_times(&[7]).await.unwrap();
+    assert_eq!(rows.len(), 1);
+
+    let stats = reader.replica_stats();
+    assert_eq!((stats[0].queries, stats[0].errors), (1, 1));
+    assert_eq!((stats[1].queries, stats[1].errors), (1, 0));
+}
+
+#[tokio::test]
+async fn abandons_slow_queries_and_rejects_when_saturated() {
+    // Accepts connections but never answers
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = url::Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+    let timeout = std::time::Duration::from_secs(2);
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into())
+        .unwrap()
+        .with_query_timeout(Some(timeout))
+        .with_max_concurrent_queries(1);
+
+    let (slow, rejected) = tokio::join!(reader.get_batch_proved_times(&[7]), async {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        reader.get_batch_proved_times(&[7]).await
+    });
+
+    let slow = slow.unwrap_err();
+    assert_eq!(slow.downcast_ref::<QueryError>(), Some(&QueryError::TimedOut(timeout)));
+    let rejected = rejected.unwrap_err();
+    assert_eq!(rejected.downcast_ref::<QueryError>(), Some(&QueryError::Saturated));
+}
+
+#[derive(Row, serde::Serialize)]
+struct ProveCostUsdRow {
+    proposer: AddressBytes,
+    total_cost: u128,
+    total_cost_usd: f64,
+}
+
+#[tokio::test]
+async fn prove_costs_by_proposer_usd_returns_expected_rows() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![ProveCostUsdRow {
+        proposer: AddressBytes([1u8; 20]),
+        total_cost: 2_000_000_000_000_000,
+        total_cost_usd: 5.0,
+    }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_prove_costs_by_proposer_usd(TimeRange::LastHour).await.unwrap();
+    assert_eq!(rows, vec![(AddressBytes([1u8; 20]), 2_000_000_000_000_000, 5.0)]);
+}
+
+#[derive(Row, serde::Serialize)]
+struct ProveCostTaikoRow {
+    proposer: AddressBytes,
+    total_cost: u128,
+    total_cost_taiko: f64,
+}
+
+#[tokio::test]
+async fn prove_costs_by_proposer_taiko_returns_expected_rows() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![ProveCostTaikoRow {
+        proposer: AddressBytes([1u8; 20]),
+        total_cost: 2_000_000_000_000_000,
+        total_cost_taiko: 16.5,
+    }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_prove_costs_by_proposer_taiko(TimeRange::LastHour).await.unwrap();
+    assert_eq!(rows, vec![(AddressBytes([1u8; 20]), 2_000_000_000_000_000, 16.5)]);
+}
+
+#[tokio::test]
+async fn latest_taiko_price_returns_newest_snapshot() {
+    let row = TaikoPriceInsertRow { ts: 1_700_000_000, eth: 0.000_12, usd: 0.42 };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row.clone()]));
+    mock.add(handlers::provide(Vec::<TaikoPriceInsertRow>::new()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_latest_taiko_price().await.unwrap(), Some(row));
+    assert_eq!(reader.get_latest_taiko_price().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn bond_balances_returns_expected_rows() {
+    let row = || BondBalanceRow {
+        address: AddressBytes([1u8; 20]),
+        balance: 500,
+        l1_block_number: 100,
+        total_debited: 250,
+        total_credited: 125,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_bond_balances().await.unwrap();
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn sequencer_cost_buckets_returns_expected_rows() {
+    let row = |sequencer: u8| SequencerCostBucketRow {
+        bucket_ts: 1_700_000_000,
+        sequencer: AddressBytes([sequencer; 20]),
+        tx_count: 120,
+        l1_data_cost: 5_000,
+        prove_cost: 1_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row(1), row(2)]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_sequencer_cost_buckets(TimeRange::LastHour, 60).await.unwrap();
+    assert_eq!(rows, vec![row(1), row(2)]);
+}
+
+#[tokio::test]
+async fn operator_epochs_returns_expected_rows() {
+    let row = |epoch: u64, operator: u8| OperatorEpochRow {
+        epoch,
+        operator: AddressBytes([operator; 20]),
+        first_l1_block: epoch * 32,
+        last_l1_block: epoch * 32 + 31,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row(401, 2), row(400, 1)]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_operator_epochs(1_700_000_000, 1_700_003_600).await.unwrap();
+    assert_eq!(rows, vec![row(401, 2), row(400, 1)]);
+}
+
+#[tokio::test]
+async fn bond_history_returns_expected_rows() {
+    let row = |log_index: u64, kind: &str, balance: u128| BondLedgerInsertRow {
+        address: AddressBytes([1u8; 20]),
+        l1_block_number: 100,
+        tx_hash: HashBytes([2u8; 32]),
+        log_index,
+        kind: kind.to_owned(),
+        amount: 250,
+        balance,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row(1, "debited", 250), row(0, "deposited", 500)]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_bond_history(AddressBytes([1u8; 20]), 10).await.unwrap();
+    assert_eq!(rows, vec![row(1, "debited", 250), row(0, "deposited", 500)]);
+}
+
+#[tokio::test]
+async fn pipeline_latency_returns_expected_rows() {
+    let row = |kind: &str| PipelineLatencyRow {
+        kind: kind.to_owned(),
+        events: 10,
+        queue_p50_us: 100.0,
+        queue_p99_us: 900.0,
+        process_p50_us: 2_000.0,
+        process_p99_us: 8_000.0,
+        total_p50_us: 2_100.0,
+        total_p99_us: 8_900.0,
+        total_max_us: 12_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row(""), row("l2_header")]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_pipeline_latency(TimeRange::LastHour).await.unwrap();
+    assert_eq!(rows, vec![row(""), row("l2_header")]);
+}
+
+#[tokio::test]
+async fn header_propagation_returns_expected_rows() {
+    let row = |chain: &str| HeaderPropagationRow {
+        chain: chain.to_owned(),
+        blocks: 300,
+        early_blocks: 2,
+        min_ms: -40,
+        avg_ms: 420.5,
+        p50_ms: 380.0,
+        p90_ms: 700.0,
+        p99_ms: 1_250.0,
+        max_ms: 3_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row("l1"), row("l2")]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_header_propagation(TimeRange::LastHour).await.unwrap();
+    assert_eq!(rows, vec![row("l1"), row("l2")]);
+}
+
+#[tokio::test]
+async fn backfill_status_returns_expected_rows() {
+    let row = |chain: &str| BackfillStatusRow {
+        chain: chain.to_owned(),
+        scheduled_blocks: 10,
+        filled_blocks: 4,
+        failed_blocks: 0,
+        newest_block: Some(110),
+        oldest_block: Some(101),
+        current_block: Some(107),
+        max_requests_per_sec: 20,
+        updated_at: 1_700_000_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row("l1"), row("l2")]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_backfill_status().await.unwrap();
+    assert_eq!(rows, vec![row("l1"), row("l2")]);
+}
+
+#[tokio::test]
+async fn batch_cadence_returns_expected_row() {
+    let row = BatchCadenceRow {
+        last_proposed_batch_id: 1_200,
+        proposed_batches: 60,
+        last_verified_batch_id: 1_000,
+        verified_batches: 40,
+        max_anchor_age: Some(32),
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row.clone()]));
+    mock.add(handlers::provide(Vec::<BatchCadenceRow>::new()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_batch_cadence(TimeRange::LastHour).await.unwrap(), row);
+    assert_eq!(reader.get_batch_cadence(TimeRange::LastHour).await.unwrap(), Default::default());
+}
+
+#[tokio::test]
+async fn leader_changes_returns_expected_rows() {
+    let row = |holder: &str, previous_holder: Option<&str>| LeaderChangeRow {
+        lease: "ingest".to_owned(),
+        holder: holder.to_owned(),
+        previous_holder: previous_holder.map(str::to_owned),
+        changed_at: 1_700_000_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row("driver-b", Some("driver-a")), row("driver-a", None)]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_leader_changes(TimeRange::Last24Hours).await.unwrap();
+    assert_eq!(rows, vec![row("driver-b", Some("driver-a")), row("driver-a", None)]);
+}
+
+#[tokio::test]
+async fn operator_balances_returns_expected_rows() {
+    let row = || OperatorBalanceRow {
+        snapshot_ts: 1_700_000_000,
+        address: AddressBytes([1u8; 20]),
+        l1_block_number: 100,
+        eth_balance: 5_000,
+        taiko_balance: None,
+        low_balance: 0,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_operator_balances().await.unwrap();
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn sequencer_uptime_returns_days() {
+    let row = || SequencerUptimeRow {
+        day_ts: 1_700_006_400,
+        sequencer: AddressBytes([1u8; 20]),
+        scheduled_secs: 28_800,
+        downtime_secs: 240,
+        downtime_intervals: 3,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_sequencer_uptime(TimeRange::Last7Days).await.unwrap();
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn mempool_stats_returns_minutes() {
+    let row = || MempoolStatsRow {
+        minute_ts: 1_700_000_040,
+        seen_txs: 12,
+        included_txs: 10,
+        max_depth: 5,
+        end_depth: 2,
+        avg_inclusion_ms: 1_500,
+        p50_inclusion_ms: 1_200,
+        p95_inclusion_ms: 3_000,
+        max_inclusion_ms: 4_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_mempool_stats(TimeRange::LastHour).await.unwrap();
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn latest_operator_history_returns_row() {
+    let row = || OperatorHistoryRow {
+        observed_ts: 1_700_000_000,
+        l1_block_number: 120,
+        candidates: vec![AddressBytes([1u8; 20])],
+        added: Vec::new(),
+        removed: vec![AddressBytes([2u8; 20])],
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_latest_operator_history().await.unwrap(), Some(row()));
+}
+
+#[tokio::test]
+async fn avg_l2_gas_used_is_none_without_blocks() {
+    #[derive(Row, serde::Serialize)]
+    struct AvgRow {
+        avg_gas_used: Option<f64>,
+    }
+
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![AvgRow { avg_gas_used: Some(1_500_000.0) }]));
+    mock.add(handlers::provide(vec![AvgRow { avg_gas_used: None }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_avg_l2_gas_used(TimeRange::Last15Min).await.unwrap(), Some(1_500_000.0));
+    assert_eq!(reader.get_avg_l2_gas_used(TimeRange::Last15Min).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn anomalies_range_returns_rows() {
+    let row = || AnomalyRow {
+        detected_ts: 1_700_000_000,
+        metric: 0,
+        value: 42.0,
+        expected: 10.0,
+        std_dev: 2.0,
+        z_score: 16.0,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let rows = reader.get_anomalies_range(until - chrono::Duration::hours(1), until).await.unwrap();
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn incident_log_range_returns_rows() {
+    let row = || IncidentLogRow {
+        logged_at_ms: 1_700_000_000_000,
+        action: 2,
+        incident_id: "inc1".to_owned(),
+        component_ids: vec!["comp1".to_owned()],
+        incident_name: String::new(),
+        status: "RESOLVED".to_owned(),
+        payload: "{}".to_owned(),
+        response_code: 500,
+        error: Some("HTTP error 500".to_owned()),
+        dry_run: 0,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let rows =
+        reader.get_incident_log_range(until - chrono::Duration::hours(1), until).await.unwrap();
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn address_labels_returns_rows() {
+    let row = || AddressLabelRow {
+        address: AddressBytes([7u8; 20]),
+        label: "prover.eth".to_owned(),
+        source: 1,
+        updated_ts: 1_700_000_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_address_labels().await.unwrap(), vec![row()]);
+}
+
+#[tokio::test]
+async fn latest_forced_inclusion_queue_returns_row() {
+    let row = || ForcedInclusionQueueRow {
+        snapshot_ts: 1_700_000_600,
+        l1_block_number: 21_000_000,
+        pending: 1,
+        oldest_created_ts: Some(1_700_000_000),
+        oldest_age_secs: Some(600),
+        deadline_batch_id: Some(1_012),
+        next_batch_id: 1_005,
+        near_deadline: 0,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+    mock.add(handlers::provide(Vec::<ForcedInclusionQueueRow>::new()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_latest_forced_inclusion_queue().await.unwrap(), Some(row()));
+    assert_eq!(reader.get_latest_forced_inclusion_queue().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn verification_backlog_range_returns_rows() {
+    let rows = vec![
+        VerificationBacklogRow {
+            snapshot_ts: 1_700_000_000,
+            l1_block_number: 21_000_000,
+            last_proposed_batch_id: 1_210,
+            last_verified_batch_id: 1_200,
+            backlog: 10,
+            over_threshold: 0,
+        },
+        VerificationBacklogRow {
+            snapshot_ts: 1_700_000_060,
+            l1_block_number: 21_000_005,
+            last_proposed_batch_id: 1_212,
+            last_verified_batch_id: 1_200,
+            backlog: 12,
+            over_threshold: 1,
+        },
+    ];
+    let mock = Mock::new();
+    mock.add(handlers::provide(rows.clone()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let since = until - chrono::Duration::hours(1);
+    assert_eq!(reader.get_verification_backlog_range(since, until).await.unwrap(), rows);
+}
+
+#[tokio::test]
+async fn sequencer_handovers_returns_rows() {
+    let rows = vec![SequencerHandoverRow {
+        outgoing: AddressBytes([1u8; 20]),
+        incoming: AddressBytes([2u8; 20]),
+        last_block_number: 1_000,
+        last_block_ts: 1_700_000_000,
+        first_block_number: 1_001,
+        first_block_ts: 1_700_000_004,
+        latency_secs: 4,
+    }];
+    let mock = Mock::new();
+    mock.add(handlers::provide(rows.clone()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let since = until - chrono::Duration::hours(1);
+    assert_eq!(reader.get_sequencer_handovers(since, until).await.unwrap(), rows);
+}
+
+#[tokio::test]
+async fn l1_tx_roles_returns_rows() {
+    let rows = vec![
+        L1TxLookupRow {
+            role: 1,
+            batch_id: 1_300,
+            l1_block_number: 21_000_000,
+            block_ts: 1_700_000_000,
+            indexed_at_ms: 1_700_000_002_000,
+        },
+        L1TxLookupRow {
+            role: 4,
+            batch_id: 0,
+            l1_block_number: 21_000_000,
+            block_ts: 1_700_000_000,
+            indexed_at_ms: 1_700_000_002_100,
+        },
+    ];
+    let mock = Mock::new();
+    mock.add(handlers::provide(rows.clone()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let tx_hash = HashBytes::from([7u8; 32]);
+    assert_eq!(reader.get_l1_tx_roles(tx_hash).await.unwrap(), rows);
+}
+
+#[tokio::test]
+async fn pending_proposals_returns_rows() {
+    let row = ProposalInclusionRow {
+        proposer: AddressBytes([1u8; 20]),
+        nonce: 7,
+        tx_hash: HashBytes([2u8; 32]),
+        first_seen_ms: 1_700_000_000_000,
+        max_fee_per_gas: 30_000_000_000,
+        max_priority_fee_per_gas: None,
+        replacements: 0,
+        batch_id: None,
+        l1_block_number: None,
+        included_at_ms: None,
+        inclusion_delay_ms: None,
+        dropped: 0,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row.clone()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_pending_proposals(1_699_999_000_000).await.unwrap(), vec![row]);
+}
+
+#[tokio::test]
+async fn outdated_sequencer_hours_returns_hours() {
+    #[derive(Row, serde::Serialize)]
+    struct HourRow {
+        hour_ts: u64,
+    }
+
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![
+        HourRow { hour_ts: 1_699_999_200 },
+        HourRow { hour_ts: 1_700_002_800 },
+    ]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let hours = reader.get_outdated_sequencer_hours(2, 100).await.unwrap();
+    assert_eq!(hours, vec![1_699_999_200, 1_700_002_800]);
+}
+
+#[tokio::test]
+async fn prove_time_percentiles_returns_days() {
+    let row = || DailyTimePercentilesRow {
+        day_ts: 1_700_006_400,
+        batches: 120,
+        p50_ms: 1_800_000,
+        p90_ms: 3_600_000,
+        p99_ms: 7_200_000,
+        max_ms: 9_000_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_prove_time_percentiles(TimeRange::Last7Days).await.unwrap();
+
+    assert_eq!(rows, vec![row()]);
+}
+
+#[tokio::test]
+async fn prove_times_by_proof_type_returns_rows() {
+    let row = |proof_type: u8| ProofTypeProveTimeRow {
+        proof_type,
+        batches: 40,
+        avg_ms: 2_000_000,
+        p50_ms: 1_800_000,
+        p90_ms: 3_600_000,
+        p99_ms: 7_200_000,
+        max_ms: 9_000_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row(1), row(2)]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_prove_times_by_proof_type(TimeRange::Last7Days).await.unwrap();
+
+    assert_eq!(rows, vec![row(1), row(2)]);
+}
+
+#[tokio::test]
+async fn l2_block_status_returns_transitions() {
+    let row = L2BlockStatusRow {
+        l2_block_number: 42,
+        block_ts: Some(1_700_000_000),
+        batch_id: Some(7),
+        proposed_at: Some(1_700_000_024),
+        proved_at: Some(1_700_001_200),
+        verified_at: None,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let status = reader.get_l2_block_status(42).await.unwrap().unwrap();
+
+    assert_eq!(status.batch_id, Some(7));
+    assert_eq!(status.proved_at, Some(1_700_001_200));
+    assert_eq!(status.verified_at, None);
+}
+
+#[tokio::test]
+async fn l2_block_status_unknown_block_is_none() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![L2BlockStatusRow {
+        l2_block_number: 42,
+        block_ts: None,
+        batch_id: None,
+        proposed_at: None,
+        proved_at: None,
+        verified_at: None,
+    }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert!(reader.get_l2_block_status(42).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn batch_blocks_returns_rows() {
+    let row = |l2_block_number: u64| BatchL2BlockRow {
+        l2_block_number,
+        block_ts: 1_700_000_000 + l2_block_number,
+        sum_gas_used: 1_500_000,
+        gas_limit: 30_000_000,
+        sum_tx: 12,
+        sequencer: AddressBytes([1u8; 20]),
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row(101), row(100)]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_batch_blocks(7, 2, None, None).await.unwrap();
+
+    assert_eq!(rows, vec![row(101), row(100)]);
+}
+
+#[tokio::test]
+async fn reorg_depth_histogram_returns_buckets() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![
+        ReorgDepthCountRow { depth: 1, count: 12 },
+        ReorgDepthCountRow { depth: 3, count: 2 },
+    ]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let rows =
+        reader.get_reorg_depth_histogram(until - chrono::Duration::days(1), until).await.unwrap();
+
+    assert_eq!(
+        rows,
+        vec![ReorgDepthCountRow { depth: 1, count: 12 }, ReorgDepthCountRow { depth: 3, count: 2 }]
+    );
+}
+
+#[tokio::test]
+async fn preconf_mismatches_range_returns_expected_rows() {
+    let row = PreconfMismatchRow {
+        l2_block_number: 42,
+        soft_block_hash: None,
+        canonical_block_hash: None,
+        soft_tx_count: 1,
+        canonical_tx_count: 0,
+        missing_tx_hashes: vec![HashBytes([7u8; 32])],
+        extra_tx_hashes: vec![],
+        reordered: 0,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row.clone()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let rows = reader
+        .get_preconf_mismatches_range(until - chrono::Duration::hours(1), until)
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![row]);
+}
+
+#[derive(Row, serde::Serialize)]
+struct BlockHashRow {
+    block_hash: HashBytes,
+}
+
+#[tokio::test]
+async fn l1_block_hash_is_none_for_unindexed_blocks() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![BlockHashRow { block_hash: HashBytes([5u8; 32]) }]));
+    mock.add(handlers::provide(Vec::<BlockHashRow>::new()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert_eq!(reader.get_l1_block_hash(100).await.unwrap(), Some(HashBytes([5u8; 32])));
+    assert_eq!(reader.get_l1_block_hash(101).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn batch_tx_hashes_returns_expected_rows() {
+    let row = BatchTxHashesRow {
+        batch_id: 7,
+        proposal_l1_tx_hash: HashBytes([1u8; 32]),
+        proof_l1_tx_hash: HashBytes([2u8; 32]),
+        verification_l1_tx_hash: HashBytes::default(),
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_batch_tx_hashes(&[7]).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].proof_l1_tx_hash, HashBytes([2u8; 32]));
+    assert!(reader.get_batch_tx_hashes(&[]).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn batch_efficiency_returns_expected_rows() {
+    let row = BatchEfficiencyRow {
+        batch_id: 7,
+        l1_block_number: 100,
+        blob_count: 2,
+        blob_total_bytes: 200_000,
+        da_mode: 0,
+        calldata_bytes: 0,
+        blocks: 12,
+        txs: 340,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row.clone()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_batch_efficiency(TimeRange::LastHour, Some(DaMode::Blob)).await.unwrap();
+    assert_eq!(rows, vec![row]);
+}
+
+#[tokio::test]
+async fn da_mode_share_returns_expected_rows() {
+    let row = DaModeShareRow {
+        bucket_ts: 1_700_000_000,
+        blob_batches: 9,
+        calldata_batches: 1,
+        blob_bytes: 1_500_000,
+        calldata_bytes: 80_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row.clone()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_da_mode_share(TimeRange::Last24Hours).await.unwrap();
+    assert_eq!(rows, vec![row]);
+}
+
+#[tokio::test]
+async fn protocol_gas_spend_returns_expected_rows() {
+    let row = ProtocolGasSpendRow {
+        operation: "prove".to_owned(),
+        address: AddressBytes([1u8; 20]),
+        txs: 3,
+        gas_used: 750_000,
+        cost: 15_000_000_000_000_000,
+    };
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![row.clone()]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let rows = reader.get_protocol_gas_spend(TimeRange::LastHour).await.unwrap();
+    assert_eq!(rows, vec![row]);
+}
+
+#[derive(Row, serde::Serialize)]
+struct ReorgRawRow {
+    l2_block_number: u64,
+    depth: u16,
+    old_sequencer: AddressBytes,
+    new_sequencer: AddressBytes,
+    probable_cause: u8,
+    ts: u64,
+}
+
+#[tokio::test]
+async fn l2_reorgs_paginated_returns_probable_cause() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![ReorgRawRow {
+        l2_block_number: 100,
+        depth: 2,
+        old_sequencer: AddressBytes([1u8; 20]),
+        new_sequencer: AddressBytes([2u8; 20]),
+        probable_cause: 1,
+        ts: 1_700_000_000_000,
+    }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let rows = reader
+        .get_l2_reorgs_paginated(until - chrono::Duration::days(1), until, 50, None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].l2_block_number, 100);
+    assert_eq!(rows[0].probable_cause, 1);
+    assert_eq!(rows[0].inserted_at.timestamp_millis(), 1_700_000_000_000);
+}
+
+#[tokio::test]
+async fn l2_block_times_stream_skips_first_block() {
+    use futures::TryStreamExt;
+
+    #[derive(Row, serde::Serialize)]
+    struct BlockTimeRow {
+        l2_block_number: u64,
+        block_time: u64,
+        s_since_prev_block: Option<u64>,
+    }
+
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![
+        BlockTimeRow {
+            l2_block_number: 12,
+            block_time: 1_700_000_004,
+            s_since_prev_block: Some(2),
+        },
+        BlockTimeRow {
+            l2_block_number: 11,
+            block_time: 1_700_000_002,
+            s_since_prev_block: Some(2),
+        },
+        BlockTimeRow { l2_block_number: 10, block_time: 1_700_000_000, s_since_prev_block: None },
+    ]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let since = chrono::Utc::now() - chrono::Duration::hours(1);
+    let stream = reader.stream_l2_block_times_paginated(since, 50, None, None, None).await.unwrap();
+    let rows: Vec<L2BlockTimeRow> = stream.try_collect().await.unwrap();
+
+    let blocks: Vec<_> = rows.iter().map(|r| (r.l2_block_number, r.s_since_prev_block)).collect();
+    assert_eq!(blocks, vec![(12, 2), (11, 2)]);
+}
+
+#[tokio::test]
+async fn estimate_row_count_returns_count() {
+    #[derive(Row, serde::Serialize)]
+    struct CountRow {
+        rows: u64,
+    }
+
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![CountRow { rows: 1_234 }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let since = until - chrono::Duration::hours(1);
+    let rows = reader.estimate_row_count("batches", since, until).await.unwrap();
+
+    assert_eq!(rows, 1_234);
+}
+
+#[tokio::test]
+async fn is_read_only_reads_the_readonly_setting() {
+    let mock = Mock::new();
+    mock.add(handlers::provide(vec![2u8]));
+    mock.add(handlers::provide(vec![0u8]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    assert!(reader.is_read_only().await.unwrap());
+    assert!(!reader.is_read_only().await.unwrap());
+}
+
+#[tokio::test]
+async fn l2_extra_data_counts_return_expected_rows() {
+    let mock = Mock::new();
+    let rows = vec![
+        ExtraDataCountRow { extra_data: "0x7461696b6f".to_owned(), blocks: 20 },
+        ExtraDataCountRow { extra_data: "0x".to_owned(), blocks: 3 },
+    ];
+    mock.add(handlers::provide(rows));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let rows = reader
+        .get_l2_extra_data_counts(until - chrono::Duration::hours(1), until, None)
+        .await
+        .unwrap();
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].extra_data, "0x7461696b6f");
+    assert_eq!(rows[1].blocks, 3);
+}
+
+#[tokio::test]
+async fn reconciliation_blocks_require_blocks_before_the_period() {
+    let mock = Mock::new();
+    let bounds = ReconciliationBlocksRow {
+        l1_from: 21_000_000,
+        l1_to: 21_000_300,
+        l2_from: 1_000_000,
+        l2_to: 1_001_800,
+    };
+    mock.add(handlers::provide(vec![bounds.clone()]));
+    mock.add(handlers::provide(vec![ReconciliationBlocksRow { l1_from: 0, ..bounds.clone() }]));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let until = chrono::Utc::now();
+    let since = until - chrono::Duration::hours(1);
+    assert_eq!(reader.get_reconciliation_blocks(since, until).await.unwrap(), Some(bounds));
+    assert_eq!(reader.get_reconciliation_blocks(since, until).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn sequencer_fee_totals_returns_rows() {
+    let rows = vec![SequencerFeeTotalsRow {
+        sequencer: AddressBytes([1u8; 20]),
+        priority_fee: 3_000,
+        base_fee: 4_000,
+        l1_data_cost: 2_500,
+    }];
+    let mock = Mock::new();
+    mock.add(handlers::provide(rows.clone()));
+
+    let url = url::Url::parse(mock.url()).unwrap();
+    let reader = ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+    let totals =
+        reader.get_sequencer_fee_totals(21_000_000, 21_000_300, 1_000_000, 1_001_800).await;
+    assert_eq!(totals.unwrap(), rows);
+}
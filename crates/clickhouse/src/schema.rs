@@ -28,6 +28,36 @@ pub const TABLES: &[&str] = &[
     "verify_costs",
     "orphaned_l2_hashes",
     "schema_migrations",
+    "protocol_config",
+    "batch_blob_fees",
+    "l2_anchor_blocks",
+    "rpc_health",
+    "eth_prices",
+    "taiko_prices",
+    "bond_ledger",
+    "operator_balances",
+    "pipeline_latency",
+    "preconf_mismatches",
+    "protocol_gas_spend",
+    "header_propagation",
+    "backfill_progress",
+    "driver_leases",
+    "leader_changes",
+    "sequencer_downtime",
+    "sequencer_schedule",
+    "anchor_mismatches",
+    "mempool_stats",
+    "operator_history",
+    "orphaned_block_details",
+    "anomalies",
+    "forced_inclusion_queue",
+    "verification_backlog",
+    "incident_log",
+    "address_labels",
+    "proposal_inclusions",
+    "l1_tx_index",
+    "sequencer_handovers",
+    "fee_reconciliations",
 ];
 
 /// Names of all materialized views
@@ -44,6 +74,18 @@ pub const VIEWS: &[&str] = &[
     "daily_batch_metrics_mv",
 ];
 
+/// Tables using `ReplacingMergeTree`, with their sorting key. Rows with equal keys are copies of
+/// the same event, e.g. replayed after a reconnect, and are collapsed when parts merge.
+pub const DEDUPLICATED_TABLES: &[(&str, &str)] = &[
+    ("l1_head_events", "block_ts, l1_block_number, block_hash"),
+    ("l2_head_events", "block_ts, l2_block_number, block_hash"),
+    ("batches", "batch_id, l1_block_number"),
+    ("proved_batches", "l1_block_number, batch_id"),
+    ("verified_batches", "l1_block_number, batch_id"),
+    ("l1_data_costs", "l1_block_number, batch_id"),
+    ("prove_costs", "l1_block_number, batch_id"),
+];
+
 /// Schema definitions for tables
 pub const TABLE_SCHEMAS: &[TableSchema] = &[
     TableSchema {
@@ -53,7 +95,7 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
                  slot UInt64,
                  block_ts UInt64,
                  inserted_at DateTime64(3) DEFAULT now64()",
-        order_by: "l1_block_number",
+        order_by: "block_ts, l1_block_number, block_hash",
     },
     TableSchema {
         name: "preconf_data",
@@ -70,12 +112,15 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
                  block_hash FixedString(32),
                  block_ts UInt64,
                  sum_gas_used UInt128,
+                 gas_limit UInt64 DEFAULT 0,
                  sum_tx UInt32,
                  sum_priority_fee UInt128,
                  sum_base_fee UInt128,
+                 avg_gas_price UInt128 DEFAULT 0,
                  sequencer FixedString(20),
+                 extra_data String DEFAULT '',
                  inserted_at DateTime64(3) DEFAULT now64()",
-        order_by: "l2_block_number",
+        order_by: "block_ts, l2_block_number, block_hash",
     },
     TableSchema {
         name: "batches",
@@ -87,8 +132,11 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
                  proposer_addr FixedString(20),
                  blob_count UInt8,
                  blob_total_bytes UInt32,
+                 da_mode UInt8 DEFAULT 0,
+                 calldata_bytes UInt32 DEFAULT 0,
+                 fork LowCardinality(String) DEFAULT '',
                  inserted_at DateTime64(3) DEFAULT now64()",
-        order_by: "toStartOfDay(inserted_at), l1_block_number, batch_id",
+        order_by: "batch_id, l1_block_number",
     },
     TableSchema {
         name: "batch_blocks",
@@ -105,6 +153,7 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
                  parent_hash FixedString(32),
                  block_hash FixedString(32),
                  state_root FixedString(32),
+                 l1_tx_hash FixedString(32),
                  inserted_at DateTime64(3) DEFAULT now64()",
         order_by: "l1_block_number, batch_id",
     },
@@ -112,6 +161,7 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
         name: "l2_reorgs",
         columns: "l2_block_number UInt64,
                  depth UInt16,
+                 probable_cause UInt8 DEFAULT 0,
                  inserted_at DateTime64(3) DEFAULT now64()",
         order_by: "inserted_at",
     },
@@ -126,6 +176,7 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
         columns: "l1_block_number UInt64,
                  batch_id UInt64,
                  block_hash FixedString(32),
+                 l1_tx_hash FixedString(32),
                  inserted_at DateTime64(3) DEFAULT now64()",
         order_by: "l1_block_number, batch_id",
     },
@@ -133,6 +184,7 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
         name: "slashing_events",
         columns: "l1_block_number UInt64,
                  validator_addr FixedString(20),
+                 reason UInt8 DEFAULT 0,
                  inserted_at DateTime64(3) DEFAULT now64()",
         order_by: "l1_block_number, validator_addr",
     },
@@ -141,6 +193,8 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
         columns: "l1_block_number UInt64,
                  batch_id UInt64,
                  cost UInt128,
+                 blob_cost UInt128 DEFAULT 0,
+                 calldata_cost UInt128 DEFAULT 0,
                  inserted_at DateTime64(3) DEFAULT now64()",
         order_by: "l1_block_number, batch_id",
     },
@@ -192,6 +246,9 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
                  proposer_addr FixedString(20),
                  blob_count UInt8,
                  blob_total_bytes UInt32,
+                 da_mode UInt8 DEFAULT 0,
+                 calldata_bytes UInt32 DEFAULT 0,
+                 fork LowCardinality(String) DEFAULT '',
                  inserted_at DateTime64(3) DEFAULT now64()",
         order_by: "toStartOfDay(inserted_at), l1_block_number, batch_id",
     },
@@ -210,4 +267,345 @@ pub const TABLE_SCHEMAS: &[TableSchema] = &[
                  checksum String",
         order_by: "version",
     },
+    TableSchema {
+        name: "protocol_config",
+        columns: "inbox_address FixedString(20),
+                 chain_id UInt64,
+                 max_unverified_batches UInt64,
+                 batch_ring_buffer_size UInt64,
+                 max_batches_to_verify UInt64,
+                 block_max_gas_limit UInt32,
+                 liveness_bond_base UInt128,
+                 liveness_bond_per_block UInt128,
+                 state_root_sync_internal UInt8,
+                 max_anchor_height_offset UInt64,
+                 base_fee_sharing_pctg UInt8,
+                 proving_window UInt16,
+                 cooldown_window UInt32,
+                 max_signals_to_receive UInt8,
+                 max_blocks_per_batch UInt16,
+                 ontake_fork_height UInt64,
+                 pacaya_fork_height UInt64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "inbox_address, inserted_at",
+    },
+    TableSchema {
+        name: "batch_blob_fees",
+        columns: "l1_block_number UInt64,
+                 batch_id UInt64,
+                 excess_blob_gas UInt64,
+                 blob_base_fee UInt128,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "l1_block_number, batch_id",
+    },
+    TableSchema {
+        name: "l2_anchor_blocks",
+        columns: "l2_block_number UInt64,
+                 block_ts UInt64,
+                 anchor_block_id UInt64,
+                 l1_head UInt64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "l2_block_number",
+    },
+    TableSchema {
+        name: "rpc_health",
+        columns: "url String,
+                 healthy UInt8,
+                 latency_ms Nullable(UInt64),
+                 block_number Nullable(UInt64),
+                 block_lag Nullable(UInt64),
+                 error Nullable(String),
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "url, inserted_at",
+    },
+    TableSchema {
+        name: "eth_prices",
+        columns: "ts UInt64,
+                 usd Float64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "ts",
+    },
+    TableSchema {
+        name: "taiko_prices",
+        columns: "ts UInt64,
+                 eth Float64,
+                 usd Float64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "ts",
+    },
+    TableSchema {
+        name: "bond_ledger",
+        columns: "address FixedString(20),
+                 l1_block_number UInt64,
+                 tx_hash FixedString(32),
+                 log_index UInt64,
+                 kind LowCardinality(String),
+                 amount UInt128,
+                 balance UInt128,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "address, l1_block_number, log_index",
+    },
+    TableSchema {
+        name: "operator_balances",
+        columns: "snapshot_ts UInt64,
+                 address FixedString(20),
+                 l1_block_number UInt64,
+                 eth_balance UInt128,
+                 taiko_balance Nullable(UInt128),
+                 low_balance UInt8,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "snapshot_ts, address",
+    },
+    TableSchema {
+        name: "pipeline_latency",
+        columns: "kind LowCardinality(String),
+                 queue_us UInt64,
+                 process_us UInt64,
+                 total_us UInt64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "kind, inserted_at",
+    },
+    TableSchema {
+        name: "preconf_mismatches",
+        columns: "l2_block_number UInt64,
+                 soft_block_hash Nullable(FixedString(32)),
+                 canonical_block_hash Nullable(FixedString(32)),
+                 soft_tx_count UInt32,
+                 canonical_tx_count UInt32,
+                 missing_tx_hashes Array(FixedString(32)),
+                 extra_tx_hashes Array(FixedString(32)),
+                 reordered UInt8,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "l2_block_number, inserted_at",
+    },
+    TableSchema {
+        name: "protocol_gas_spend",
+        columns: "l1_block_number UInt64,
+                 tx_hash FixedString(32),
+                 address FixedString(20),
+                 operation LowCardinality(String),
+                 gas_used UInt64,
+                 cost UInt128,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "operation, l1_block_number, tx_hash",
+    },
+    TableSchema {
+        name: "header_propagation",
+        columns: "chain LowCardinality(String),
+                 block_number UInt64,
+                 block_ts UInt64,
+                 observed_at DateTime64(3),
+                 delay_ms Int64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "chain, observed_at",
+    },
+    TableSchema {
+        name: "backfill_progress",
+        columns: "chain LowCardinality(String),
+                 scheduled_blocks UInt64,
+                 filled_blocks UInt64,
+                 failed_blocks UInt64,
+                 newest_block Nullable(UInt64),
+                 oldest_block Nullable(UInt64),
+                 current_block Nullable(UInt64),
+                 max_requests_per_sec UInt32,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "chain, inserted_at",
+    },
+    TableSchema {
+        name: "driver_leases",
+        columns: "lease LowCardinality(String),
+                 holder String,
+                 started_at UInt64,
+                 released UInt8 DEFAULT 0,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "lease, inserted_at",
+    },
+    TableSchema {
+        name: "leader_changes",
+        columns: "lease LowCardinality(String),
+                 holder String,
+                 previous_holder Nullable(String),
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "lease, inserted_at",
+    },
+    TableSchema {
+        name: "sequencer_downtime",
+        columns: "sequencer FixedString(20),
+                 start_ts UInt64,
+                 end_ts UInt64,
+                 computation_version UInt16 DEFAULT 1,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "sequencer, start_ts",
+    },
+    TableSchema {
+        name: "sequencer_schedule",
+        columns: "sequencer FixedString(20),
+                 hour_ts UInt64,
+                 scheduled_secs UInt64,
+                 computation_version UInt16 DEFAULT 1,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "sequencer, hour_ts",
+    },
+    TableSchema {
+        name: "anchor_mismatches",
+        columns: "batch_id UInt64,
+                 l1_block_number UInt64,
+                 anchor_block_id UInt64,
+                 anchor_block_hash FixedString(32),
+                 l1_block_hash FixedString(32),
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "batch_id, l1_block_number",
+    },
+    TableSchema {
+        name: "mempool_stats",
+        columns: "minute_ts UInt64,
+                 seen_txs UInt32,
+                 included_txs UInt32,
+                 max_depth UInt32,
+                 end_depth UInt32,
+                 avg_inclusion_ms UInt64,
+                 p50_inclusion_ms UInt64,
+                 p95_inclusion_ms UInt64,
+                 max_inclusion_ms UInt64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "minute_ts",
+    },
+    TableSchema {
+        name: "operator_history",
+        columns: "observed_ts UInt64,
+                 l1_block_number UInt64,
+                 candidates Array(FixedString(20)),
+                 added Array(FixedString(20)),
+                 removed Array(FixedString(20)),
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "observed_ts, l1_block_number",
+    },
+    TableSchema {
+        name: "orphaned_block_details",
+        columns: "block_hash FixedString(32),
+                 l2_block_number UInt64,
+                 parent_hash FixedString(32),
+                 block_ts UInt64,
+                 sequencer FixedString(20),
+                 gas_used UInt64,
+                 tx_count UInt32,
+                 tx_hashes Array(FixedString(32)),
+                 reinclusion_checked UInt8,
+                 reincluded_txs UInt32,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "l2_block_number, block_hash",
+    },
+    TableSchema {
+        name: "anomalies",
+        columns: "detected_ts UInt64,
+                 metric UInt8,
+                 value Float64,
+                 expected Float64,
+                 std_dev Float64,
+                 z_score Float64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "metric, detected_ts",
+    },
+    TableSchema {
+        name: "forced_inclusion_queue",
+        columns: "snapshot_ts UInt64,
+                 l1_block_number UInt64,
+                 pending UInt64,
+                 oldest_created_ts Nullable(UInt64),
+                 oldest_age_secs Nullable(UInt64),
+                 deadline_batch_id Nullable(UInt64),
+                 next_batch_id UInt64,
+                 near_deadline UInt8,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "snapshot_ts",
+    },
+    TableSchema {
+        name: "verification_backlog",
+        columns: "snapshot_ts UInt64,
+                 l1_block_number UInt64,
+                 last_proposed_batch_id UInt64,
+                 last_verified_batch_id UInt64,
+                 backlog UInt64,
+                 over_threshold UInt8,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "snapshot_ts",
+    },
+    TableSchema {
+        name: "incident_log",
+        columns: "logged_at_ms UInt64,
+                 action UInt8,
+                 incident_id String,
+                 component_ids Array(String),
+                 incident_name String,
+                 status String,
+                 payload String,
+                 response_code UInt16,
+                 error Nullable(String),
+                 dry_run UInt8,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "logged_at_ms, action, incident_id",
+    },
+    TableSchema {
+        name: "address_labels",
+        columns: "address FixedString(20),
+                 label String,
+                 source UInt8,
+                 updated_ts UInt64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "address",
+    },
+    TableSchema {
+        name: "proposal_inclusions",
+        columns: "proposer FixedString(20),
+                 nonce UInt64,
+                 tx_hash FixedString(32),
+                 first_seen_ms UInt64,
+                 max_fee_per_gas UInt128,
+                 max_priority_fee_per_gas Nullable(UInt128),
+                 replacements UInt32,
+                 batch_id Nullable(UInt64),
+                 l1_block_number Nullable(UInt64),
+                 included_at_ms Nullable(UInt64),
+                 inclusion_delay_ms Nullable(UInt64),
+                 dropped UInt8,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "proposer, nonce",
+    },
+    TableSchema {
+        name: "l1_tx_index",
+        columns: "tx_hash FixedString(32),
+                 role UInt8,
+                 batch_id UInt64,
+                 l1_block_number UInt64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "tx_hash, role, batch_id",
+    },
+    TableSchema {
+        name: "sequencer_handovers",
+        columns: "outgoing FixedString(20),
+                 incoming FixedString(20),
+                 last_block_number UInt64,
+                 last_block_ts UInt64,
+                 first_block_number UInt64,
+                 first_block_ts UInt64,
+                 latency_secs UInt64,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "first_block_number",
+    },
+    TableSchema {
+        name: "fee_reconciliations",
+        columns: "period_start UInt64,
+                 period_end UInt64,
+                 sequencer FixedString(20),
+                 chain LowCardinality(String),
+                 from_block UInt64,
+                 to_block UInt64,
+                 expected_delta Int128,
+                 actual_delta Int128,
+                 discrepancy Int128,
+                 flagged UInt8,
+                 inserted_at DateTime64(3) DEFAULT now64()",
+        order_by: "period_end, sequencer, chain",
+    },
 ];
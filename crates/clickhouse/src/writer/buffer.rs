@@ -0,0 +1,157 @@
+//! Row buffering for high-volume tables
+//!
+//! Head events arrive once per block, so writing each of them with its own `INSERT` creates many
+//! small parts in `ClickHouse`. Buffered rows are written in a single `INSERT` once a table holds
+//! `max_rows` rows or its oldest row is older than `flush_interval`.
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Mutex;
+
+use crate::models::{
+    HeaderPropagationInsertRow, L1HeadEvent, L2HeadEvent, PipelineLatencyInsertRow, PreconfData,
+};
+
+/// Thresholds at which buffered rows are flushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteBufferConfig {
+    /// Flush a table once it holds this many rows
+    pub max_rows: usize,
+    /// Flush a table once its oldest row has been buffered for this long
+    pub flush_interval: Duration,
+}
+
+#[derive(Debug)]
+struct Pending<T> {
+    rows: Vec<T>,
+    oldest: Option<Instant>,
+}
+
+/// Rows waiting to be written to a single table.
+#[derive(Debug)]
+pub(super) struct TableBuffer<T> {
+    pub(super) table: &'static str,
+    config: WriteBufferConfig,
+    pending: Mutex<Pending<T>>,
+    depth: AtomicUsize,
+}
+
+impl<T> TableBuffer<T> {
+    fn new(table: &'static str, config: WriteBufferConfig) -> Self {
+        Self {
+            table,
+            config,
+            pending: Mutex::new(Pending { rows: Vec::new(), oldest: None }),
+            depth: AtomicUsize::new(0),
+        }
+    }
+
+    /// Buffer `row`, returning the buffered rows if a flush threshold was reached.
+    pub(super) async fn push(&self, row: T) -> Option<Vec<T>> {
+        let mut pending = self.pending.lock().await;
+        let oldest = *pending.oldest.get_or_insert_with(Instant::now);
+        pending.rows.push(row);
+
+        let full = pending.rows.len() >= self.config.max_rows;
+        let due = oldest.elapsed() >= self.config.flush_interval;
+        let rows = (full || due).then(|| Self::drain(&mut pending));
+        self.depth.store(pending.rows.len(), Ordering::Relaxed);
+        rows
+    }
+
+    /// Take the buffered rows if the oldest one is older than the flush interval.
+    pub(super) async fn take_due(&self) -> Vec<T> {
+        let mut pending = self.pending.lock().await;
+        let due =
+            pending.oldest.is_some_and(|oldest| oldest.elapsed() >= self.config.flush_interval);
+        if !due {
+            return Vec::new();
+        }
+        self.depth.store(0, Ordering::Relaxed);
+        Self::drain(&mut pending)
+    }
+
+    /// Take all buffered rows.
+    pub(super) async fn take_all(&self) -> Vec<T> {
+        let mut pending = self.pending.lock().await;
+        self.depth.store(0, Ordering::Relaxed);
+        Self::drain(&mut pending)
+    }
+
+    fn drain(pending: &mut Pending<T>) -> Vec<T> {
+        pending.oldest = None;
+        std::mem::take(&mut pending.rows)
+    }
+
+    /// Number of rows currently buffered.
+    pub(super) fn depth(&self) -> usize {
+        self.depth.load(Ordering::Relaxed)
+    }
+}
+
+/// Buffers of the tables written once per block or event.
+#[derive(Debug)]
+pub(super) struct WriteBuffers {
+    pub(super) config: WriteBufferConfig,
+    pub(super) l1_head_events: TableBuffer<L1HeadEvent>,
+    pub(super) l2_head_events: TableBuffer<L2HeadEvent>,
+    pub(super) preconf_data: TableBuffer<PreconfData>,
+    pub(super) pipeline_latency: TableBuffer<PipelineLatencyInsertRow>,
+    pub(super) header_propagation: TableBuffer<HeaderPropagationInsertRow>,
+}
+
+impl WriteBuffers {
+    pub(super) fn new(config: WriteBufferConfig) -> Self {
+        Self {
+            config,
+            l1_head_events: TableBuffer::new("l1_head_events", config),
+            l2_head_events: TableBuffer::new("l2_head_events", config),
+            preconf_data: TableBuffer::new("preconf_data", config),
+            pipeline_latency: TableBuffer::new("pipeline_latency", config),
+            header_propagation: TableBuffer::new("header_propagation", config),
+        }
+    }
+
+    /// Number of buffered rows per table.
+    pub(super) fn depths(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            (self.l1_head_events.table, self.l1_head_events.depth()),
+            (self.l2_head_events.table, self.l2_head_events.depth()),
+            (self.preconf_data.table, self.preconf_data.depth()),
+            (self.pipeline_latency.table, self.pipeline_latency.depth()),
+            (self.header_propagation.table, self.header_propagation.depth()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn flushes_when_max_rows_reached() {
+        let config = WriteBufferConfig { max_rows: 3, flush_interval: Duration::from_secs(60) };
+        let buffer = TableBuffer::new("t", config);
+
+        assert_eq!(buffer.push(1).await, None);
+        assert_eq!(buffer.push(2).await, None);
+        assert_eq!(buffer.depth(), 2);
+        assert_eq!(buffer.take_due().await, Vec::<i32>::new());
+
+        assert_eq!(buffer.push(3).await, Some(vec![1, 2, 3]));
+        assert_eq!(buffer.depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn flushes_when_interval_elapsed() {
+        let config = WriteBufferConfig { max_rows: 100, flush_interval: Duration::ZERO };
+        let buffer = TableBuffer::new("t", config);
+
+        assert_eq!(buffer.push(1).await, Some(vec![1]));
+        assert_eq!(buffer.take_due().await, Vec::<i32>::new());
+        assert_eq!(buffer.take_all().await, Vec::<i32>::new());
+    }
+}
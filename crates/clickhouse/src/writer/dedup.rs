@@ -0,0 +1,105 @@
+//! Duplicate row verification
+//!
+//! The tables in [`DEDUPLICATED_TABLES`] collapse rows with the same sorting key when parts
+//! merge. [`ClickhouseWriter::verify_dedup`] reports how many keys currently have duplicate rows
+//! and how many still have them when read with `FINAL`. Duplicates surviving `FINAL` mean the
+//! sorting key does not identify a row and readers over-count.
+
+use chrono::{DateTime, Utc};
+use clickhouse::Row;
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use super::ClickhouseWriter;
+use crate::schema::DEDUPLICATED_TABLES;
+
+/// Duplicate keys of a deduplicated table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateReport {
+    /// Table the keys belong to
+    pub table: &'static str,
+    /// Keys with more than one row, including rows awaiting a merge
+    pub duplicate_keys: u64,
+    /// Keys with more than one row when read with `FINAL`
+    pub surviving_keys: u64,
+}
+
+#[derive(Debug, Row, Serialize, Deserialize)]
+struct DuplicateCountRow {
+    duplicate_keys: u64,
+    surviving_keys: u64,
+}
+
+impl ClickhouseWriter {
+    /// Count duplicate keys among the rows of every deduplicated table inserted since `since`.
+    pub async fn verify_dedup(&self, since: DateTime<Utc>) -> Result<Vec<DuplicateReport>> {
+        let mut reports = Vec::with_capacity(DEDUPLICATED_TABLES.len());
+        for &(table, key) in DEDUPLICATED_TABLES {
+            let row = self
+                .base
+                .query(&duplicate_count_query(&self.table(table), key, since))
+                .fetch_one::<DuplicateCountRow>()
+                .await?;
+            reports.push(DuplicateReport {
+                table,
+                duplicate_keys: row.duplicate_keys,
+                surviving_keys: row.surviving_keys,
+            });
+        }
+        Ok(reports)
+    }
+}
+
+/// Query counting keys of `table` with more than one row, with and without `FINAL`.
+fn duplicate_count_query(table: &str, key: &str, since: DateTime<Utc>) -> String {
+    let since = since.timestamp_millis() as f64 / 1000.0;
+    let duplicates = |modifier: &str| {
+        format!(
+            "SELECT count() FROM (
+                SELECT 1 FROM {table} {modifier}
+                WHERE inserted_at >= toDateTime64({since:.3}, 3)
+                GROUP BY {key}
+                HAVING count() > 1
+             )"
+        )
+    };
+    format!(
+        "SELECT ({}) AS duplicate_keys, ({}) AS surviving_keys",
+        duplicates(""),
+        duplicates("FINAL")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clickhouse::test::{Mock, handlers};
+    use url::Url;
+
+    #[test]
+    fn query_groups_by_sorting_key() {
+        let since = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let query = duplicate_count_query("db.batches", "batch_id, l1_block_number", since);
+        assert!(query.contains("FROM db.batches FINAL"));
+        assert!(query.contains("GROUP BY batch_id, l1_block_number"));
+        assert!(query.contains("toDateTime64(1700000000.000, 3)"));
+    }
+
+    #[tokio::test]
+    async fn reports_every_deduplicated_table() {
+        let mock = Mock::new();
+        for _ in DEDUPLICATED_TABLES {
+            mock.add(handlers::provide(vec![DuplicateCountRow {
+                duplicate_keys: 3,
+                surviving_keys: 0,
+            }]));
+        }
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let reports = writer.verify_dedup(Utc::now()).await.unwrap();
+        assert_eq!(reports.len(), DEDUPLICATED_TABLES.len());
+        assert_eq!(reports[0].table, "l1_head_events");
+        assert!(reports.iter().all(|r| r.duplicate_keys == 3 && r.surviving_keys == 0));
+    }
+}
@@ -0,0 +1,276 @@
+//! Dual writes to a secondary `ClickHouse` cluster
+//!
+//! While moving to a new cluster, every row written to the primary is written to the secondary
+//! as well. Writes to the secondary never fail the primary write, and give up after
+//! [`MIRROR_TIMEOUT`] so a slow secondary does not hold up ingestion; rows that could not be
+//! mirrored are counted instead. [`ClickhouseWriter::verify_dual`] samples keys of the core
+//! tables and compares their row counts within the sampled window on both clusters.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use clickhouse::Row;
+use eyre::{Result, eyre};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::ClickhouseWriter;
+
+/// Tables compared by [`ClickhouseWriter::verify_dual`], with the column identifying a row
+const VERIFIED_TABLES: &[(&str, &str)] = &[
+    ("l1_head_events", "l1_block_number"),
+    ("l2_head_events", "l2_block_number"),
+    ("batches", "batch_id"),
+    ("proved_batches", "batch_id"),
+    ("verified_batches", "batch_id"),
+    ("l1_data_costs", "batch_id"),
+    ("prove_costs", "batch_id"),
+];
+
+/// Seconds a row must have been written before it is verified, so in-flight writes to the
+/// secondary are not reported as mismatches.
+const VERIFY_SETTLE_SECS: u64 = 60;
+
+/// Time a write to the secondary may take before it is abandoned and counted as failed
+const MIRROR_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Counters of the writes mirrored to the secondary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DualWriteStats {
+    /// Rows written to both clusters
+    pub mirrored_rows: u64,
+    /// Rows written to the primary but not the secondary
+    pub diverged_rows: u64,
+    /// Failed writes to the secondary
+    pub failed_writes: u64,
+}
+
+/// A key whose rows differ between the primary and the secondary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DualWriteMismatch {
+    /// Table the key belongs to
+    pub table: &'static str,
+    /// Value of the key column
+    pub key: u64,
+    /// Rows with the key on the primary
+    pub primary_rows: u64,
+    /// Rows with the key on the secondary
+    pub secondary_rows: u64,
+}
+
+/// Secondary write target and its divergence counters.
+#[derive(Debug)]
+pub(super) struct DualWrite {
+    pub(super) target: ClickhouseWriter,
+    mirrored_rows: AtomicU64,
+    diverged_rows: AtomicU64,
+    failed_writes: AtomicU64,
+}
+
+impl DualWrite {
+    pub(super) const fn new(target: ClickhouseWriter) -> Self {
+        Self {
+            target,
+            mirrored_rows: AtomicU64::new(0),
+            diverged_rows: AtomicU64::new(0),
+            failed_writes: AtomicU64::new(0),
+        }
+    }
+
+    /// Write `rows` to the secondary within [`MIRROR_TIMEOUT`], recording instead of returning
+    /// a failure.
+    pub(super) async fn mirror<T>(&self, table: &str, rows: &[T])
+    where
+        T: Row + Serialize,
+    {
+        let count = rows.len() as u64;
+        let result = tokio::time::timeout(MIRROR_TIMEOUT, self.target.write_target(table, rows))
+            .await
+            .unwrap_or_else(|_| Err(eyre!("timed out after {MIRROR_TIMEOUT:?}")));
+        match result {
+            Ok(()) => {
+                self.mirrored_rows.fetch_add(count, Ordering::Relaxed);
+            }
+            Err(e) => {
+                self.failed_writes.fetch_add(1, Ordering::Relaxed);
+                self.diverged_rows.fetch_add(count, Ordering::Relaxed);
+                warn!(table, rows = count, err = %e, "Failed to mirror rows to secondary ClickHouse");
+            }
+        }
+    }
+
+    pub(super) fn stats(&self) -> DualWriteStats {
+        DualWriteStats {
+            mirrored_rows: self.mirrored_rows.load(Ordering::Relaxed),
+            diverged_rows: self.diverged_rows.load(Ordering::Relaxed),
+            failed_writes: self.failed_writes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Row, Deserialize)]
+struct KeyCountRow {
+    key: u64,
+    rows: u64,
+}
+
+/// Keys of `sample` whose row counts differ between `primary` and `secondary`.
+fn compare_key_counts(
+    table: &'static str,
+    sample: &[u64],
+    primary: &[(u64, u64)],
+    secondary: &[(u64, u64)],
+) -> Vec<DualWriteMismatch> {
+    let count = |rows: &[(u64, u64)], key: u64| {
+        rows.iter().find(|(k, _)| *k == key).map_or(0, |(_, rows)| *rows)
+    };
+    sample
+        .iter()
+        .map(|&key| DualWriteMismatch {
+            table,
+            key,
+            primary_rows: count(primary, key),
+            secondary_rows: count(secondary, key),
+        })
+        .filter(|m| m.primary_rows != m.secondary_rows)
+        .collect()
+}
+
+impl ClickhouseWriter {
+    /// Compare up to `sample_size` keys per verified table, written to the primary since
+    /// `since`, against the secondary. Only rows written within the sampled window are counted
+    /// on either cluster. Returns `None` without a secondary.
+    pub async fn verify_dual(
+        &self,
+        since: DateTime<Utc>,
+        sample_size: u64,
+    ) -> Option<Result<Vec<DualWriteMismatch>>> {
+        let secondary = &self.secondary.as_ref()?.target;
+        let until = match self.server_time_ms().await {
+            Ok(now_ms) => settled_until(now_ms),
+            Err(e) => return Some(Err(e)),
+        };
+        // Mirrored rows reach the secondary up to `MIRROR_TIMEOUT` after the primary
+        let secondary_until = until + TimeDelta::from_std(MIRROR_TIMEOUT).unwrap_or_default();
+        let mut mismatches = Vec::new();
+        for &(table, key_column) in VERIFIED_TABLES {
+            let sample = match self.sample_keys(table, key_column, since, until, sample_size).await
+            {
+                Ok(sample) => sample,
+                Err(e) => return Some(Err(e)),
+            };
+            if sample.is_empty() {
+                continue;
+            }
+            let counts = tokio::try_join!(
+                self.key_counts(table, key_column, &sample, since, until),
+                secondary.key_counts(table, key_column, &sample, since, secondary_until),
+            );
+            match counts {
+                Ok((primary, secondary)) => {
+                    mismatches.extend(compare_key_counts(table, &sample, &primary, &secondary))
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        Some(Ok(mismatches))
+    }
+
+    /// Random keys of `table` written between `since` and `until`.
+    async fn sample_keys(
+        &self,
+        table: &str,
+        key_column: &str,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        sample_size: u64,
+    ) -> Result<Vec<u64>> {
+        let query = format!(
+            "SELECT key FROM (
+                SELECT DISTINCT toUInt64({key_column}) AS key
+                FROM {table}
+                WHERE {window}
+             )
+             ORDER BY rand()
+             LIMIT {sample_size}",
+            table = self.table(table),
+            window = window_filter(since, until),
+        );
+        Ok(self.base.query(&query).fetch_all::<u64>().await?)
+    }
+
+    /// Number of rows per key of `keys` in `table` written between `since` and `until`, not
+    /// counting duplicates awaiting a merge.
+    async fn key_counts(
+        &self,
+        table: &str,
+        key_column: &str,
+        keys: &[u64],
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<(u64, u64)>> {
+        let keys = keys.iter().map(u64::to_string).collect::<Vec<_>>().join(", ");
+        let query = format!(
+            "SELECT toUInt64({key_column}) AS key, count() AS rows
+             FROM {table} FINAL
+             WHERE {key_column} IN ({keys}) AND {window}
+             GROUP BY key",
+            table = self.table(table),
+            window = window_filter(since, until),
+        );
+        let rows = self.base.query(&query).fetch_all::<KeyCountRow>().await?;
+        Ok(rows.into_iter().map(|row| (row.key, row.rows)).collect())
+    }
+}
+
+/// End of the verified window: [`VERIFY_SETTLE_SECS`] before `now_ms`, the server time in
+/// milliseconds, so in-flight writes to the secondary are not reported as mismatches.
+fn settled_until(now_ms: u64) -> DateTime<Utc> {
+    let until_ms = now_ms.saturating_sub(VERIFY_SETTLE_SECS * 1000);
+    DateTime::from_timestamp_millis(until_ms as i64).unwrap_or_default()
+}
+
+/// Condition keeping the rows inserted from `since` up to, but excluding, `until`
+fn window_filter(since: DateTime<Utc>, until: DateTime<Utc>) -> String {
+    let secs = |ts: DateTime<Utc>| ts.timestamp_millis() as f64 / 1000.0;
+    format!(
+        "inserted_at >= toDateTime64({:.3}, 3) AND inserted_at < toDateTime64({:.3}, 3)",
+        secs(since),
+        secs(until)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_keys_with_differing_row_counts() {
+        let primary = [(1, 1), (2, 1), (3, 2)];
+        let secondary = [(1, 1), (3, 1)];
+
+        let mismatches = compare_key_counts("batches", &[1, 2, 3], &primary, &secondary);
+        assert_eq!(
+            mismatches,
+            vec![
+                DualWriteMismatch { table: "batches", key: 2, primary_rows: 1, secondary_rows: 0 },
+                DualWriteMismatch { table: "batches", key: 3, primary_rows: 2, secondary_rows: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn window_ends_before_in_flight_writes() {
+        let since = DateTime::from_timestamp_millis(1_700_000_000_250).unwrap();
+        let until = settled_until(1_700_000_100_500);
+        assert_eq!(until.timestamp_millis(), 1_700_000_040_500);
+        assert_eq!(
+            window_filter(since, until),
+            "inserted_at >= toDateTime64(1700000000.250, 3) \
+             AND inserted_at < toDateTime64(1700000040.500, 3)"
+        );
+    }
+}
@@ -0,0 +1,448 @@
+//! Sample data for local development
+//!
+//! Generates a deterministic, internally consistent chain history ending now: L1 and L2 heads,
+//! batches with their blocks, proofs, verifications and costs, a couple of L2 reorgs, a forced
+//! inclusion and an operator ejection. Rows carry explicit `inserted_at` times spread over the
+//! history, so time-range queries behave as they would against an indexed chain. Incidents
+//! themselves live on the status page, so the events raising them are seeded instead.
+
+use chrono::Utc;
+use clickhouse::Row;
+use eyre::{Result, bail};
+use primitives::{da::DaMode, reorg::ReorgCause};
+use serde::Serialize;
+use tracing::info;
+
+use super::ClickhouseWriter;
+use crate::types::{AddressBytes, HashBytes};
+
+/// Seconds between L2 blocks
+const L2_BLOCK_TIME_SECS: u64 = 2;
+/// Seconds between L1 blocks
+const L1_BLOCK_TIME_SECS: u64 = 12;
+/// Slots in an L1 epoch, after which the sequencer rotates
+const SLOTS_PER_EPOCH: u64 = 32;
+/// Gas limit of every L2 block
+const L2_GAS_LIMIT: u64 = 12_000_000;
+/// Extra data of the L2 blocks, "taiko-geth" for two in three blocks and "nethermind" for the rest
+const L2_EXTRA_DATA: [&str; 3] =
+    ["0x7461696b6f2d67657468", "0x7461696b6f2d67657468", "0x6e65746865726d696e64"];
+/// L2 blocks proposed per batch
+const BATCH_SIZE: u64 = 8;
+/// First L2 block number of the generated history
+const FIRST_L2_BLOCK: u64 = 1_000_000;
+/// First L1 block number of the generated history
+const FIRST_L1_BLOCK: u64 = 20_000_000;
+/// Slot of the first L1 block
+const FIRST_SLOT: u64 = 9_000_000;
+/// Sequencers rotating every epoch; the last one is ejected during the history
+const SEQUENCERS: [[u8; 20]; 3] = [[0x11; 20], [0x22; 20], [0x33; 20]];
+/// Verifier address of every proof
+const VERIFIER: [u8; 20] = [0xaa; 20];
+/// Seed of the generated history, fixed so every developer sees the same data
+const SEED: u64 = 0x7a1c_05c0;
+
+#[derive(Debug, Row, Serialize)]
+struct L1HeadFixture {
+    l1_block_number: u64,
+    block_hash: HashBytes,
+    slot: u64,
+    block_ts: u64,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct L2HeadFixture {
+    l2_block_number: u64,
+    block_hash: HashBytes,
+    block_ts: u64,
+    sum_gas_used: u128,
+    gas_limit: u64,
+    sum_tx: u32,
+    sum_priority_fee: u128,
+    sum_base_fee: u128,
+    avg_gas_price: u128,
+    sequencer: AddressBytes,
+    extra_data: String,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct BatchFixture {
+    l1_block_number: u64,
+    l1_tx_hash: HashBytes,
+    batch_id: u64,
+    batch_size: u16,
+    last_l2_block_number: u64,
+    proposer_addr: AddressBytes,
+    blob_count: u8,
+    blob_total_bytes: u32,
+    da_mode: u8,
+    calldata_bytes: u32,
+    fork: String,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct BatchBlockFixture {
+    batch_id: u64,
+    l2_block_number: u64,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct ProvedBatchFixture {
+    l1_block_number: u64,
+    batch_id: u64,
+    verifier_addr: AddressBytes,
+    parent_hash: HashBytes,
+    block_hash: HashBytes,
+    state_root: HashBytes,
+    l1_tx_hash: HashBytes,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct VerifiedBatchFixture {
+    l1_block_number: u64,
+    batch_id: u64,
+    block_hash: HashBytes,
+    l1_tx_hash: HashBytes,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct L1DataCostFixture {
+    l1_block_number: u64,
+    batch_id: u64,
+    cost: u128,
+    blob_cost: u128,
+    calldata_cost: u128,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct ProveCostFixture {
+    l1_block_number: u64,
+    batch_id: u64,
+    cost: u128,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct L2ReorgFixture {
+    l2_block_number: u64,
+    depth: u16,
+    old_sequencer: AddressBytes,
+    new_sequencer: AddressBytes,
+    probable_cause: u8,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct SlashingEventFixture {
+    l1_block_number: u64,
+    validator_addr: AddressBytes,
+    reason: u8,
+    inserted_at: u64,
+}
+
+#[derive(Debug, Row, Serialize)]
+struct ForcedInclusionFixture {
+    blob_hash: HashBytes,
+    inserted_at: u64,
+}
+
+/// Rows of every seeded table.
+#[derive(Debug, Default)]
+struct Fixtures {
+    l1_heads: Vec<L1HeadFixture>,
+    l2_heads: Vec<L2HeadFixture>,
+    batches: Vec<BatchFixture>,
+    batch_blocks: Vec<BatchBlockFixture>,
+    proved_batches: Vec<ProvedBatchFixture>,
+    verified_batches: Vec<VerifiedBatchFixture>,
+    l1_data_costs: Vec<L1DataCostFixture>,
+    prove_costs: Vec<ProveCostFixture>,
+    reorgs: Vec<L2ReorgFixture>,
+    slashings: Vec<SlashingEventFixture>,
+    forced_inclusions: Vec<ForcedInclusionFixture>,
+}
+
+/// splitmix64 generator, so the same seed always produces the same history.
+#[derive(Debug)]
+struct Rng(u64);
+
+impl Rng {
+    const fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `[low, high]`.
+    const fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.next() % (high - low + 1)
+    }
+
+    fn hash(&mut self) -> HashBytes {
+        let mut bytes = [0u8; 32];
+        for chunk in bytes.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next().to_be_bytes());
+        }
+        HashBytes(bytes)
+    }
+}
+
+impl Fixtures {
+    /// Generate `l2_blocks` L2 blocks and the L1 activity around them, ending at `now_secs`.
+    fn generate(l2_blocks: u64, seed: u64, now_secs: u64) -> Self {
+        let mut rng = Rng(seed);
+        let mut fixtures = Self::default();
+        let start = now_secs.saturating_sub(l2_blocks * L2_BLOCK_TIME_SECS);
+        let ms = |secs: u64| secs * 1000;
+
+        let l1_blocks = l2_blocks * L2_BLOCK_TIME_SECS / L1_BLOCK_TIME_SECS + 1;
+        for i in 0..l1_blocks {
+            let block_ts = start + i * L1_BLOCK_TIME_SECS;
+            fixtures.l1_heads.push(L1HeadFixture {
+                l1_block_number: FIRST_L1_BLOCK + i,
+                block_hash: rng.hash(),
+                slot: FIRST_SLOT + i,
+                block_ts,
+                inserted_at: ms(block_ts) + rng.range(200, 1500),
+            });
+        }
+        // L1 block of the slot `ts` falls in
+        let l1_at = |ts: u64| FIRST_L1_BLOCK + (ts - start) / L1_BLOCK_TIME_SECS;
+        let sequencer_at = |ts: u64| {
+            let epoch = (FIRST_SLOT + (ts - start) / L1_BLOCK_TIME_SECS) / SLOTS_PER_EPOCH;
+            SEQUENCERS[(epoch % SEQUENCERS.len() as u64) as usize]
+        };
+
+        for i in 0..l2_blocks {
+            let block_ts = start + i * L2_BLOCK_TIME_SECS;
+            let sum_tx = rng.range(0, 120) as u32;
+            let sum_gas_used = u128::from(sum_tx) * u128::from(rng.range(21_000, 90_000));
+            let base_fee = u128::from(rng.range(10_000_000, 25_000_000));
+            let priority_fee = u128::from(rng.range(0, 2_000_000));
+            fixtures.l2_heads.push(L2HeadFixture {
+                l2_block_number: FIRST_L2_BLOCK + i,
+                block_hash: rng.hash(),
+                block_ts,
+                sum_gas_used,
+                gas_limit: L2_GAS_LIMIT,
+                sum_tx,
+                sum_priority_fee: sum_gas_used * priority_fee,
+                sum_base_fee: sum_gas_used * base_fee,
+                avg_gas_price: if sum_gas_used > 0 { base_fee + priority_fee } else { 0 },
+                sequencer: AddressBytes(sequencer_at(block_ts)),
+                extra_data: L2_EXTRA_DATA[(i % L2_EXTRA_DATA.len() as u64) as usize].to_owned(),
+                inserted_at: ms(block_ts) + rng.range(50, 800),
+            });
+        }
+
+        let batch_count = l2_blocks / BATCH_SIZE;
+        for batch in 0..batch_count {
+            let batch_id = batch + 1;
+            let last_block = FIRST_L2_BLOCK + (batch + 1) * BATCH_SIZE - 1;
+            let last_ts = start + (last_block - FIRST_L2_BLOCK) * L2_BLOCK_TIME_SECS;
+            // Proposed a slot after the last block, or in the last L1 block near the head
+            let proposed_ts = (last_ts + L1_BLOCK_TIME_SECS).min(now_secs);
+            let proposed_l1 = l1_at(proposed_ts);
+            let proposed_at = ms(proposed_ts) + rng.range(500, 3000);
+            // One batch in ten posts its transactions as calldata
+            let da_mode = if rng.range(0, 9) == 0 { DaMode::Calldata } else { DaMode::Blob };
+            let (blob_count, calldata_bytes) = match da_mode {
+                DaMode::Blob => (rng.range(1, 3) as u8, 0),
+                DaMode::Calldata => (0, rng.range(20_000, 120_000) as u32),
+            };
+            fixtures.batches.push(BatchFixture {
+                l1_block_number: proposed_l1,
+                l1_tx_hash: rng.hash(),
+                batch_id,
+                batch_size: BATCH_SIZE as u16,
+                last_l2_block_number: last_block,
+                proposer_addr: AddressBytes(sequencer_at(last_ts)),
+                blob_count,
+                blob_total_bytes: u32::from(blob_count) * rng.range(40_000, 131_072) as u32,
+                da_mode: da_mode as u8,
+                calldata_bytes,
+                fork: "pacaya".to_owned(),
+                inserted_at: proposed_at,
+            });
+            fixtures.batch_blocks.extend((last_block + 1 - BATCH_SIZE..=last_block).map(
+                |l2_block_number| BatchBlockFixture {
+                    batch_id,
+                    l2_block_number,
+                    inserted_at: proposed_at,
+                },
+            ));
+            let blob_cost = u128::from(blob_count > 0) * u128::from(rng.range(50_000, 400_000));
+            let calldata_cost = u128::from(rng.range(100_000, 300_000));
+            fixtures.l1_data_costs.push(L1DataCostFixture {
+                l1_block_number: proposed_l1,
+                batch_id,
+                cost: blob_cost + calldata_cost,
+                blob_cost,
+                calldata_cost,
+                inserted_at: proposed_at,
+            });
+
+            // Proofs land 10 to 40 minutes after the proposal; recent batches are unproven
+            let proved_ts = proposed_ts + rng.range(600, 2400);
+            if proved_ts > now_secs {
+                continue;
+            }
+            let proved_l1 = l1_at(proved_ts);
+            let block_hash = rng.hash();
+            fixtures.proved_batches.push(ProvedBatchFixture {
+                l1_block_number: proved_l1,
+                batch_id,
+                verifier_addr: AddressBytes(VERIFIER),
+                parent_hash: rng.hash(),
+                block_hash,
+                state_root: rng.hash(),
+                l1_tx_hash: rng.hash(),
+                inserted_at: ms(proved_ts),
+            });
+            fixtures.prove_costs.push(ProveCostFixture {
+                l1_block_number: proved_l1,
+                batch_id,
+                cost: u128::from(rng.range(200_000, 1_500_000)),
+                inserted_at: ms(proved_ts),
+            });
+
+            let verified_ts = proved_ts + rng.range(120, 600);
+            if verified_ts > now_secs {
+                continue;
+            }
+            fixtures.verified_batches.push(VerifiedBatchFixture {
+                l1_block_number: l1_at(verified_ts),
+                batch_id,
+                block_hash,
+                l1_tx_hash: rng.hash(),
+                inserted_at: ms(verified_ts),
+            });
+        }
+
+        // A shallow reorg at an operator handover and a deeper one after an L1 reorg
+        for (fraction, depth, cause) in
+            [(3, 1, ReorgCause::OperatorChange), (2, 3, ReorgCause::L1Reorg)]
+        {
+            let Some(head) = fixtures.l2_heads.get((l2_blocks / fraction) as usize) else {
+                continue;
+            };
+            fixtures.reorgs.push(L2ReorgFixture {
+                l2_block_number: head.l2_block_number,
+                depth,
+                old_sequencer: AddressBytes(sequencer_at(head.block_ts.saturating_sub(24))),
+                new_sequencer: head.sequencer,
+                probable_cause: cause as u8,
+                inserted_at: head.inserted_at,
+            });
+        }
+
+        if let Some(head) = fixtures.l1_heads.get(fixtures.l1_heads.len() * 3 / 4) {
+            fixtures.slashings.push(SlashingEventFixture {
+                l1_block_number: head.l1_block_number,
+                validator_addr: AddressBytes(SEQUENCERS[SEQUENCERS.len() - 1]),
+                reason: 2,
+                inserted_at: head.inserted_at,
+            });
+        }
+        if let Some(head) = fixtures.l1_heads.get(fixtures.l1_heads.len() / 4) {
+            fixtures.forced_inclusions.push(ForcedInclusionFixture {
+                blob_hash: rng.hash(),
+                inserted_at: head.inserted_at,
+            });
+        }
+
+        fixtures
+    }
+}
+
+impl ClickhouseWriter {
+    /// Populate an empty database with `l2_blocks` L2 blocks of sample data ending now. Fails if
+    /// `l2_head_events` already has rows.
+    pub async fn seed_fixtures(&self, l2_blocks: u64) -> Result<()> {
+        let query = format!("SELECT count() FROM {}", self.table("l2_head_events"));
+        let existing = self.base.query(&query).fetch_one::<u64>().await?;
+        if existing > 0 {
+            bail!(
+                "l2_head_events already has {existing} rows; seed fixtures into a fresh database"
+            );
+        }
+
+        let fixtures = Fixtures::generate(l2_blocks, SEED, Utc::now().timestamp() as u64);
+        self.write_rows("l1_head_events", &fixtures.l1_heads).await?;
+        self.write_rows("l2_head_events", &fixtures.l2_heads).await?;
+        self.write_rows("batches", &fixtures.batches).await?;
+        self.write_rows("batch_blocks", &fixtures.batch_blocks).await?;
+        self.write_rows("proved_batches", &fixtures.proved_batches).await?;
+        self.write_rows("verified_batches", &fixtures.verified_batches).await?;
+        self.write_rows("l1_data_costs", &fixtures.l1_data_costs).await?;
+        self.write_rows("prove_costs", &fixtures.prove_costs).await?;
+        self.write_rows("l2_reorgs", &fixtures.reorgs).await?;
+        self.write_rows("slashing_events", &fixtures.slashings).await?;
+        self.write_rows("forced_inclusion_processed", &fixtures.forced_inclusions).await?;
+
+        info!(
+            l1_blocks = fixtures.l1_heads.len(),
+            l2_blocks = fixtures.l2_heads.len(),
+            batches = fixtures.batches.len(),
+            proved = fixtures.proved_batches.len(),
+            verified = fixtures.verified_batches.len(),
+            reorgs = fixtures.reorgs.len(),
+            "Seeded fixtures"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_750_000_000;
+
+    #[test]
+    fn same_seed_same_history() {
+        let a = Fixtures::generate(400, 7, NOW);
+        let b = Fixtures::generate(400, 7, NOW);
+        assert_eq!(a.l2_heads.len(), 400);
+        assert!(
+            a.l2_heads
+                .iter()
+                .zip(&b.l2_heads)
+                .all(|(a, b)| a.block_hash == b.block_hash && a.sum_tx == b.sum_tx)
+        );
+    }
+
+    #[test]
+    fn history_is_consistent() {
+        let fixtures = Fixtures::generate(3000, 1, NOW);
+
+        assert_eq!(fixtures.batches.len(), 3000 / BATCH_SIZE as usize);
+        assert_eq!(fixtures.batch_blocks.len(), fixtures.batches.len() * BATCH_SIZE as usize);
+        assert!(!fixtures.proved_batches.is_empty());
+        assert!(fixtures.proved_batches.len() < fixtures.batches.len());
+        assert!(fixtures.verified_batches.len() <= fixtures.proved_batches.len());
+        assert_eq!(fixtures.prove_costs.len(), fixtures.proved_batches.len());
+        assert_eq!(fixtures.reorgs.len(), 2);
+        assert_eq!(fixtures.slashings.len(), 1);
+        assert_eq!(fixtures.forced_inclusions.len(), 1);
+
+        let last_l1 = fixtures.l1_heads.last().unwrap();
+        assert!(last_l1.block_ts <= NOW && fixtures.l2_heads.iter().all(|h| h.block_ts <= NOW));
+        assert!(fixtures.batches.iter().all(|b| b.l1_block_number <= last_l1.l1_block_number));
+        for verified in &fixtures.verified_batches {
+            let proved =
+                fixtures.proved_batches.iter().find(|p| p.batch_id == verified.batch_id).unwrap();
+            assert!(proved.inserted_at <= verified.inserted_at);
+            assert_eq!(proved.block_hash, verified.block_hash);
+        }
+    }
+}
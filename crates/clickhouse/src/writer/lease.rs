@@ -0,0 +1,97 @@
+//! Driver leases
+//!
+//! Driver instances renew a row in `driver_leases` at a fixed interval. A holder is live while
+//! its latest row is younger than the lease TTL and not released. Row timestamps come from the
+//! server, so holders do not need synchronized clocks.
+
+use eyre::Result;
+
+use super::ClickhouseWriter;
+use crate::models::{DriverLeaseInsertRow, LeaderChangeInsertRow, LeaseHolderRow};
+
+impl ClickhouseWriter {
+    /// Renew or release a lease.
+    pub async fn insert_driver_lease(&self, row: &DriverLeaseInsertRow) -> Result<()> {
+        self.write_rows("driver_leases", std::slice::from_ref(row)).await
+    }
+
+    /// Record that a holder became leader.
+    pub async fn insert_leader_change(&self, row: &LeaderChangeInsertRow) -> Result<()> {
+        self.write_rows("leader_changes", std::slice::from_ref(row)).await
+    }
+
+    /// Current time of the `ClickHouse` server as a UNIX timestamp in milliseconds.
+    pub async fn server_time_ms(&self) -> Result<u64> {
+        let query = "SELECT toUInt64(toUnixTimestamp64Milli(now64(3)))";
+        Ok(self.base.query(query).fetch_one::<u64>().await?)
+    }
+
+    /// Holders of `lease` that renewed it within the last `ttl_secs` seconds without releasing
+    /// it, earliest contender first.
+    pub async fn get_live_lease_holders(
+        &self,
+        lease: &str,
+        ttl_secs: u64,
+    ) -> Result<Vec<LeaseHolderRow>> {
+        let query = format!(
+            "SELECT holder, argMax(started_at, inserted_at) AS started_at \
+             FROM {table} \
+             WHERE lease = ? AND inserted_at >= now64() - INTERVAL {ttl_secs} SECOND \
+             GROUP BY holder \
+             HAVING argMax(released, inserted_at) = 0 \
+             ORDER BY started_at ASC, holder ASC",
+            table = self.table("driver_leases"),
+        );
+        Ok(self.base.query(&query).bind(lease).fetch_all::<LeaseHolderRow>().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clickhouse::test::{Mock, handlers};
+    use url::Url;
+
+    fn writer(mock: &Mock) -> ClickhouseWriter {
+        let url = Url::parse(mock.url()).unwrap();
+        ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+    }
+
+    #[tokio::test]
+    async fn insert_driver_lease_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<DriverLeaseInsertRow>());
+
+        let row = DriverLeaseInsertRow {
+            lease: "ingest".to_owned(),
+            holder: "driver-a".to_owned(),
+            started_at: 1_700_000_000_000,
+            released: 0,
+        };
+        writer(&mock).insert_driver_lease(&row).await.unwrap();
+
+        let rows: Vec<DriverLeaseInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn live_lease_holders_returns_rows() {
+        let holders = vec![
+            LeaseHolderRow { holder: "driver-a".to_owned(), started_at: 1 },
+            LeaseHolderRow { holder: "driver-b".to_owned(), started_at: 2 },
+        ];
+        let mock = Mock::new();
+        mock.add(handlers::provide(holders.clone()));
+
+        let rows = writer(&mock).get_live_lease_holders("ingest", 30).await.unwrap();
+        assert_eq!(rows, holders);
+    }
+
+    #[tokio::test]
+    async fn server_time_reads_clickhouse_clock() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![1_700_000_000_123u64]));
+
+        assert_eq!(writer(&mock).server_time_ms().await.unwrap(), 1_700_000_000_123);
+    }
+}
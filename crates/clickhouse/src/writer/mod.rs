@@ -0,0 +1,2385 @@
+//! `ClickHouse` writer functionality for taikoscope
+//! Handles database initialization, migrations, and data insertion
+
+use alloy::primitives::{Address, B256, BlockNumber};
+use clickhouse::{Client, Row, insert::Insert};
+use derive_more::Debug;
+use eyre::{Context, Result};
+use include_dir::{Dir, include_dir};
+#[cfg(feature = "fault-injection")]
+use primitives::faults::{Fault, FaultSchedule};
+use primitives::{l1_data_cost::L1DataCostBreakdown, proof::ProofType, reorg::ReorgCause};
+use regex::Regex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlparser::{dialect::GenericDialect, parser::Parser};
+use std::{collections::HashMap, sync::Arc};
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+use crate::{
+    L1Header,
+    models::{
+        AddressLabelRow, AnchorMismatchRow, AnomalyRow, BackfillProgressInsertRow,
+        BatchBlobFeeInsertRow, BatchBlockRow, BatchRow, BondLedgerInsertRow, EthPriceInsertRow,
+        FeeReconciliationRow, ForcedInclusionProcessedRow, ForcedInclusionQueueRow,
+        HeaderPropagationInsertRow, IncidentLogRow, L1DataCostInsertRow, L1HeadEvent, L1TxIndexRow,
+        L2AnchorBlockInsertRow, L2HeadEvent, L2ReorgInsertRow, MempoolStatsRow, OperatorBalanceRow,
+        OperatorHistoryRow, OrphanedBlockDetailsRow, OrphanedL2HashRow, PipelineLatencyInsertRow,
+        PreconfData, PreconfMismatchRow, ProposalInclusionRow, ProtocolConfigRow,
+        ProtocolGasSpendInsertRow, ProveCostInsertRow, ProvedBatchRow, RpcHealthInsertRow,
+        SchemaVersionInsert, SequencerDowntimeRow, SequencerHandoverRow, SequencerScheduleRow,
+        SlashingEventRow, TaikoPriceInsertRow, VerificationBacklogRow, VerifiedBatchRow,
+        VerifyCostInsertRow,
+    },
+    schema::{TABLE_SCHEMAS, TABLES, TableSchema, VIEWS},
+    types::{AddressBytes, HashBytes},
+};
+
+mod buffer;
+mod dedup;
+mod dual;
+mod fixtures;
+mod lease;
+mod snapshot;
+mod spill;
+
+pub use buffer::WriteBufferConfig;
+use buffer::{TableBuffer, WriteBuffers};
+pub use dedup::DuplicateReport;
+use dual::DualWrite;
+pub use dual::{DualWriteMismatch, DualWriteStats};
+pub use snapshot::{SnapshotFormat, SnapshotLocation, SnapshotTableRow, snapshot_tables};
+use spill::SpillQueue;
+pub use spill::{SpillConfig, SpillStats};
+
+/// Embedded migrations directory
+static MIGRATIONS_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/migrations");
+
+/// Split a SQL script into individual statements using sqlparser-rs.
+///
+/// This parser properly handles comments, quoted strings, and complex SQL syntax.
+pub(crate) fn parse_sql_statements(sql: &str) -> Vec<String> {
+    let dialect = GenericDialect {};
+
+    // Use sqlparser to split statements properly, handling quotes and comments
+    match Parser::parse_sql(&dialect, sql) {
+        Ok(parsed_statements) => {
+            // Successfully parsed - extract statement strings by re-parsing each individually
+            let mut statements = Vec::new();
+            let mut remaining = sql;
+
+            for _ in parsed_statements {
+                // Find the next complete statement in the remaining text
+                if let Some((stmt_text, rest)) = extract_next_statement(remaining) {
+                    let trimmed = stmt_text.trim();
+                    if !trimmed.is_empty() {
+                        statements.push(trimmed.to_owned());
+                    }
+                    remaining = rest;
+                }
+            }
+            statements
+        }
+        Err(_) => {
+            // Fallback to manual parsing when sqlparser fails (e.g., ClickHouse-specific syntax)
+            split_statements_manually(sql)
+        }
+    }
+}
+
+/// Extract the next complete SQL statement from text, handling quotes and comments
+fn extract_next_statement(sql: &str) -> Option<(String, &str)> {
+    let mut statement = String::new();
+    let mut chars = sql.chars().peekable();
+    let mut in_single_quote = false;
+    let mut in_double_quote = false;
+    let mut in_line_comment = false;
+    let mut in_block_comment = false;
+    let mut prev_char = '\0';
+    let mut char_count = 0;
+
+    while let Some(c) = chars.next() {
+        char_count += c.len_utf8();
+
+        if in_line_comment {
+            statement.push(c);
+            if c == '\n' {
+                in_line_comment = false;
+            }
+            prev_char = c;
+            continue;
+        }
+
+        if in_block_comment {
+            statement.push(c);
+            if prev_char == '*' && c == '/' {
+                in_block_comment = false;
+            }
+            prev_char = c;
+            continue;
+        }
+
+        if !in_single_quote && !in_double_quote {
+            if c == '-' && chars.peek() == Some(&'-') {
+                in_line_comment = true;
+                statement.push(c);
+                if let Some(next) = chars.next() {
+                    char_count += next.len_utf8();
+                    statement.push(next);
+                    prev_char = next;
+                }
+                continue;
+            }
+            if c == '/' && chars.peek() == Some(&'*') {
+                in_block_comment = true;
+                statement.push(c);
+                if let Some(next) = chars.next() {
+                    char_count += next.len_utf8();
+                    statement.push(next);
+                    prev_char = next;
+                }
+                continue;
+            }
+        }
+
+        if c == '\'' && !in_double_quote {
+            in_single_quote = !in_single_quote;
+        } else if c == '"' && !in_single_quote {
+            in_double_quote = !in_double_quote;
+        }
+
+        statement.push(c);
+
+        if c == ';' && !in_single_quote && !in_double_quote && !in_line_comment && !in_block_comment
+        {
+            // Found end of statement
+            let remaining = &sql[char_count..];
+            return Some((statement, remaining));
+        }
+
+        prev_char = c;
+    }
+
+    // If we reach here, we have remaining text but no semicolon
+    (!statement.trim().is_empty()).then_some((statement, ""))
+}
+
+/// Fallback manual statement splitting for when sqlparser fails
+fn split_statements_manually(sql: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut remaining = sql;
+
+    while !remaining.trim().is_empty() {
+        if let Some((stmt, rest)) = extract_next_statement(remaining) {
+            let trimmed = stmt.trim();
+            if !trimmed.is_empty() {
+                statements.push(trimmed.to_owned());
+            }
+            remaining = rest;
+        } else {
+            break;
+        }
+    }
+
+    statements
+}
+
+/// Simple struct for version query results
+#[derive(Debug, clickhouse::Row, serde::Deserialize)]
+struct VersionRow {
+    version: u32,
+    checksum: String,
+}
+
+/// Compare the checksums recorded for applied migrations against the embedded files.
+///
+/// Migrations are up-only: once applied, a file must never change. A mismatch is an error
+/// unless `allow_dirty` is set, in which case it is only logged.
+fn verify_migration_checksums(
+    applied: &HashMap<u32, String>,
+    migrations: &[(u32, &str, String)],
+    allow_dirty: bool,
+) -> Result<()> {
+    let mut dirty = Vec::new();
+    for (version, name, checksum) in migrations {
+        if let Some(recorded) = applied.get(version) &&
+            recorded != checksum
+        {
+            warn!(migration = name, version, recorded = %recorded, checksum = %checksum, "Migration checksum mismatch");
+            dirty.push(*name);
+        }
+    }
+
+    if dirty.is_empty() || allow_dirty {
+        return Ok(());
+    }
+    eyre::bail!(
+        "Applied migrations were modified after being applied: {}. \
+         Restore the original files or start with --allow-dirty-schema",
+        dirty.join(", ")
+    )
+}
+
+/// Validate migration file name (e.g. `001_description.sql`)
+fn validate_migration_name(name: &str) -> bool {
+    Regex::new(r"^\d{3}_[a-z0-9_]+\.sql$").map(|re| re.is_match(name)).unwrap_or(false)
+}
+
+/// `ClickHouse` writer client for taikoscope (data insertion and migrations)
+#[derive(Clone, Debug)]
+pub struct ClickhouseWriter {
+    /// Base client
+    #[debug(skip)]
+    base: Client,
+    /// Database name
+    db_name: String,
+    /// Prefix prepended to every table and view name
+    table_prefix: String,
+    /// Start even if applied migrations no longer match their recorded checksums
+    allow_dirty_schema: bool,
+    /// Buffers of the per-block tables, shared between clones (unbuffered when unset)
+    buffers: Option<Arc<WriteBuffers>>,
+    /// Fork names by activation L1 block, sorted ascending
+    fork_schedule: Vec<(u64, String)>,
+    /// Secondary cluster every write is mirrored to, shared between clones
+    secondary: Option<Arc<DualWrite>>,
+    /// Local queue of the rows of failed writes, shared between clones (dropped when unset)
+    spill: Option<Arc<SpillQueue>>,
+    /// Faults injected into inserts
+    #[cfg(feature = "fault-injection")]
+    faults: Option<FaultSchedule>,
+}
+
+impl ClickhouseWriter {
+    /// Create a new `ClickHouse` writer client.
+    ///
+    /// This constructor is infallible and directly returns the writer instance.
+    pub fn new(url: Url, db_name: String, username: String, password: String) -> Self {
+        let client = Client::default().with_url(url).with_user(username).with_password(password);
+
+        Self {
+            base: client,
+            db_name,
+            table_prefix: String::new(),
+            allow_dirty_schema: false,
+            buffers: None,
+            fork_schedule: Vec::new(),
+            secondary: None,
+            spill: None,
+            #[cfg(feature = "fault-injection")]
+            faults: None,
+        }
+    }
+
+    /// Prefix every table and view with `table_prefix`, e.g. `staging_`.
+    ///
+    /// Allows several deployments to share a single `ClickHouse` database without clashing.
+    pub fn with_table_prefix(mut self, table_prefix: impl Into<String>) -> Self {
+        self.table_prefix = table_prefix.into();
+        self
+    }
+
+    /// Tolerate applied migrations whose checksums no longer match the embedded files.
+    pub const fn with_allow_dirty_schema(mut self, allow_dirty_schema: bool) -> Self {
+        self.allow_dirty_schema = allow_dirty_schema;
+        self
+    }
+
+    /// Buffer rows of the per-block tables (L1/L2 head events and preconf data) and write them
+    /// in bulk according to `config`.
+    ///
+    /// Call [`Self::flush`] before shutting down so buffered rows are not lost.
+    pub fn with_write_buffer(mut self, config: WriteBufferConfig) -> Self {
+        self.buffers = Some(Arc::new(WriteBuffers::new(config)));
+        self
+    }
+
+    /// Tag batches with the name of the fork active at their L1 block. `schedule` holds
+    /// `(activation_l1_block, fork_name)` pairs.
+    pub fn with_fork_schedule(mut self, mut schedule: Vec<(u64, String)>) -> Self {
+        schedule.sort_by_key(|(activation_block, _)| *activation_block);
+        self.fork_schedule = schedule;
+        self
+    }
+
+    /// Mirror every write to `secondary`, e.g. while migrating to a new cluster.
+    ///
+    /// Failed writes to the secondary are counted in [`Self::dual_write_stats`] but never fail
+    /// the write to this writer.
+    pub fn with_secondary(mut self, secondary: Self) -> Self {
+        self.secondary = Some(Arc::new(DualWrite::new(secondary)));
+        self
+    }
+
+    /// Writer of the secondary cluster, if dual writes are enabled.
+    pub fn secondary(&self) -> Option<&Self> {
+        self.secondary.as_deref().map(|dual| &dual.target)
+    }
+
+    /// Counters of the writes mirrored to the secondary. `None` when dual writes are disabled.
+    pub fn dual_write_stats(&self) -> Option<DualWriteStats> {
+        self.secondary.as_deref().map(DualWrite::stats)
+    }
+
+    /// Name of the fork active at `l1_block_number`, empty without a fork schedule.
+    fn fork_at(&self, l1_block_number: u64) -> String {
+        self.fork_schedule
+            .iter()
+            .rev()
+            .find(|(activation_block, _)| *activation_block <= l1_block_number)
+            .map(|(_, name)| name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Delay or fail inserts according to `faults`.
+    #[cfg(feature = "fault-injection")]
+    pub fn with_faults(mut self, faults: FaultSchedule) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    /// Number of rows currently buffered per table. Empty when buffering is disabled.
+    pub fn buffer_depths(&self) -> Vec<(&'static str, usize)> {
+        self.buffers.as_ref().map(|b| b.depths()).unwrap_or_default()
+    }
+
+    /// Write all buffered rows.
+    pub async fn flush(&self) -> Result<()> {
+        match &self.buffers {
+            Some(buffers) => self.flush_buffers(buffers, true).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Spawn a task that writes buffered rows once they exceed the flush interval, so rows are
+    /// not held back while no new events arrive. Returns `None` when buffering is disabled.
+    pub fn spawn_flush_task(&self) -> Option<JoinHandle<()>> {
+        let buffers = self.buffers.clone()?;
+        let writer = self.clone();
+
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(buffers.config.flush_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = writer.flush_buffers(&buffers, false).await {
+                    error!(err = %e, "Failed to flush buffered rows");
+                }
+                debug!(depths = ?buffers.depths(), "Write buffer depths");
+            }
+        }))
+    }
+
+    /// Write the buffered rows of every table, or only those past the flush interval.
+    async fn flush_buffers(&self, buffers: &WriteBuffers, all: bool) -> Result<()> {
+        let results = [
+            self.flush_table(&buffers.l1_head_events, all).await,
+            self.flush_table(&buffers.l2_head_events, all).await,
+            self.flush_table(&buffers.preconf_data, all).await,
+            self.flush_table(&buffers.pipeline_latency, all).await,
+            self.flush_table(&buffers.header_propagation, all).await,
+        ];
+        results.into_iter().collect()
+    }
+
+    async fn flush_table<T>(&self, buffer: &TableBuffer<T>, all: bool) -> Result<()>
+    where
+        T: Row + Serialize,
+    {
+        let rows = if all { buffer.take_all().await } else { buffer.take_due().await };
+        self.write_rows(buffer.table, &rows).await
+    }
+
+    /// Buffer `row` if buffering is enabled, writing the buffer once a threshold is reached;
+    /// otherwise write it immediately.
+    async fn insert_or_buffer<T>(
+        &self,
+        buffer: Option<&TableBuffer<T>>,
+        table: &str,
+        row: T,
+    ) -> Result<()>
+    where
+        T: Row + Serialize,
+    {
+        let rows = match buffer {
+            Some(buffer) => match buffer.push(row).await {
+                Some(rows) => rows,
+                None => return Ok(()),
+            },
+            None => vec![row],
+        };
+        self.write_rows(table, &rows).await
+    }
+
+    /// Write `rows` to `table` in a single `INSERT`, mirroring them to the secondary if set.
+    ///
+    /// Rows of a failed write are spilled for a later replay when spilling is enabled, and
+    /// dropped otherwise; gap detection backfills the missing blocks.
+    async fn write_rows<T>(&self, table: &str, rows: &[T]) -> Result<()>
+    where
+        T: Row + Serialize,
+    {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        if let Err(e) = self.write_target(table, rows).await {
+            return self.spill_or_fail(table, rows, e).await;
+        }
+        if let Some(secondary) = &self.secondary {
+            secondary.mirror(table, rows).await;
+        }
+        Ok(())
+    }
+
+    /// Write `rows` to `table` of this writer's cluster only.
+    async fn write_target<T>(&self, table: &str, rows: &[T]) -> Result<()>
+    where
+        T: Row + Serialize,
+    {
+        let mut insert = self.insert(table).await?;
+        for row in rows {
+            insert.write(row).await?;
+        }
+        insert
+            .end()
+            .await
+            .wrap_err_with(|| format!("Failed to write {} rows to {table}", rows.len()))
+    }
+
+    /// Start an `INSERT` into `table`.
+    async fn insert<T: Row>(&self, table: &str) -> Result<Insert<T>> {
+        #[cfg(feature = "fault-injection")]
+        {
+            self.inject_fault(table).await?;
+        }
+        Ok(self.base.insert(&self.table(table))?)
+    }
+
+    /// Apply the next scheduled fault to an insert into `table`.
+    #[cfg(feature = "fault-injection")]
+    async fn inject_fault(&self, table: &str) -> Result<()> {
+        let Some(faults) = &self.faults else { return Ok(()) };
+        match faults.next_fault() {
+            Fault::None => Ok(()),
+            Fault::Delay(delay) => {
+                tokio::time::sleep(delay).await;
+                Ok(())
+            }
+            Fault::Fail => Err(eyre::eyre!("Injected fault: insert into {table} failed")),
+        }
+    }
+
+    /// Fully qualified name of `table`, including database and table prefix.
+    fn table(&self, table: &str) -> String {
+        format!("{}.{}{}", self.db_name, self.table_prefix, table)
+    }
+
+    /// Create a table with the given schema
+    async fn create_table(&self, schema: &TableSchema) -> Result<()> {
+        let query = format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                {}
+            ) ENGINE = MergeTree()
+            ORDER BY ({})",
+            self.table(schema.name),
+            schema.columns,
+            schema.order_by
+        );
+
+        self.base
+            .query(&query)
+            .execute()
+            .await
+            .wrap_err_with(|| format!("Failed to create {} table", schema.name))
+    }
+
+    /// Drop a table if it exists
+    async fn drop_table(&self, table_name: &str) -> Result<()> {
+        self.base
+            .query(&format!("DROP TABLE IF EXISTS {}", self.table(table_name)))
+            .execute()
+            .await
+            .wrap_err_with(|| format!("Failed to drop {} table", table_name))
+    }
+
+    /// Drop a view if it exists
+    async fn drop_view(&self, view_name: &str) -> Result<()> {
+        self.base
+            .query(&format!("DROP TABLE IF EXISTS {}", self.table(view_name)))
+            .execute()
+            .await
+            .wrap_err_with(|| format!("Failed to drop {} view", view_name))
+    }
+
+    /// Run a trivial query to check that the server is reachable and accepts the credentials
+    pub async fn ping(&self) -> Result<()> {
+        self.base.query("SELECT 1").execute().await.wrap_err("Failed to query ClickHouse")
+    }
+
+    /// Check that the user may write, i.e. its settings profile does not set `readonly`
+    pub async fn ensure_writable(&self) -> Result<()> {
+        let readonly = self
+            .base
+            .query("SELECT toUInt8(getSetting('readonly'))")
+            .fetch_one::<u8>()
+            .await
+            .wrap_err("Failed to query the readonly setting")?;
+        if readonly > 0 {
+            return Err(eyre::eyre!(
+                "ClickHouse user is read-only (readonly = {readonly}); use write credentials"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Initialize database and optionally reset
+    pub async fn init_db(&self, reset: bool) -> Result<()> {
+        self.init_db_with_migrations(reset, true).await
+    }
+
+    /// Initialize database with option to skip migrations (useful for tests)
+    pub async fn init_db_with_migrations(&self, reset: bool, run_migrations: bool) -> Result<()> {
+        self.init_db_internal(reset, run_migrations, true).await
+    }
+
+    /// Initialize database with full options
+    #[cfg(test)]
+    pub async fn init_db_with_options(
+        &self,
+        reset: bool,
+        run_migrations: bool,
+        enable_tracking: bool,
+    ) -> Result<()> {
+        self.init_db_internal(reset, run_migrations, enable_tracking).await
+    }
+
+    /// Initialize database with full options (internal implementation)
+    async fn init_db_internal(
+        &self,
+        reset: bool,
+        run_migrations: bool,
+        enable_tracking: bool,
+    ) -> Result<()> {
+        // Create database
+        self.base
+            .query(&format!("CREATE DATABASE IF NOT EXISTS {}", self.db_name))
+            .execute()
+            .await
+            .wrap_err_with(|| format!("Failed to init database {}", self.db_name))?;
+
+        if reset {
+            for view in VIEWS {
+                self.drop_view(view).await?;
+            }
+            for table in TABLES {
+                self.drop_table(table).await?;
+            }
+            info!(db_name = %self.db_name, "Database reset complete");
+        }
+
+        if run_migrations {
+            self.init_schema_with_tracking(enable_tracking).await?;
+        }
+        Ok(())
+    }
+
+    /// Initialize schema (with migration tracking enabled)
+    pub async fn init_schema(&self) -> Result<()> {
+        self.init_schema_with_tracking(true).await
+    }
+
+    /// Initialize schema with option to enable/disable migration tracking
+    #[allow(clippy::cognitive_complexity)]
+    pub async fn init_schema_with_tracking(&self, enable_tracking: bool) -> Result<()> {
+        let applied_migrations = if enable_tracking {
+            // Ensure migrations table exists before checking applied migrations
+            self.ensure_migrations_table().await?;
+
+            // Get already applied migrations and their checksums
+            (self.get_applied_migrations().await).unwrap_or_default()
+        } else {
+            // For tests or when tracking is disabled, apply all migrations
+            HashMap::new()
+        };
+
+        let migrations = self.embedded_migrations()?;
+        let checksums: Vec<_> = migrations
+            .iter()
+            .map(|(version, name, sql)| (*version, *name, self.calculate_migration_checksum(sql)))
+            .collect();
+        verify_migration_checksums(&applied_migrations, &checksums, self.allow_dirty_schema)?;
+
+        for ((version, name, sql), (_, _, checksum)) in migrations.into_iter().zip(checksums) {
+            // Skip if migration already applied
+            if applied_migrations.contains_key(&version) {
+                info!(migration = name, version = version, "Skipping already applied migration");
+                continue;
+            }
+
+            self.apply_migration(version, name, sql).await?;
+
+            // Record migration as applied (only if tracking is enabled)
+            if enable_tracking {
+                self.record_migration(version, name, &checksum)
+                    .await
+                    .wrap_err_with(|| format!("Failed to record migration {name} as applied"))?;
+            }
+
+            info!(migration = name, version = version, "Migration applied successfully");
+        }
+
+        Ok(())
+    }
+
+    /// Re-apply the statements of the embedded migration `version`, e.g. to recreate a view that
+    /// was dropped by hand. The migration is recorded as applied if it was not already.
+    /// Returns the migration's file name.
+    pub async fn rerun_migration(&self, version: u32) -> Result<&'static str> {
+        let (_, name, sql) = self
+            .embedded_migrations()?
+            .into_iter()
+            .find(|(v, _, _)| *v == version)
+            .ok_or_else(|| eyre::eyre!("Unknown migration version {version}"))?;
+
+        self.ensure_migrations_table().await?;
+        self.apply_migration(version, name, sql).await?;
+        if !self.get_applied_migrations().await?.contains_key(&version) {
+            let checksum = self.calculate_migration_checksum(sql);
+            self.record_migration(version, name, &checksum).await?;
+        }
+        info!(migration = name, version, "Migration re-applied");
+        Ok(name)
+    }
+
+    /// Embedded migrations as `(version, file name, SQL)`, ordered by file name
+    fn embedded_migrations(&self) -> Result<Vec<(u32, &'static str, &'static str)>> {
+        let mut files: Vec<_> = MIGRATIONS_DIR
+            .files()
+            .filter(|f| f.path().extension().and_then(|s| s.to_str()) == Some("sql"))
+            .collect();
+        files.sort_by_key(|f| f.path().file_name().map(|n| n.to_owned()));
+
+        let mut migrations = Vec::with_capacity(files.len());
+        for file in files {
+            let name = file.path().file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            if !validate_migration_name(name) {
+                eyre::bail!("Invalid migration name: {name}");
+            }
+            let version = self.extract_migration_version(name)?;
+            let sql = file
+                .contents_utf8()
+                .ok_or_else(|| eyre::eyre!("Invalid UTF-8 in migration {name}"))?;
+            migrations.push((version, name, sql));
+        }
+        Ok(migrations)
+    }
+
+    /// Execute every statement of a migration
+    async fn apply_migration(&self, version: u32, name: &str, sql: &str) -> Result<()> {
+        let statements = parse_sql_statements(sql);
+        info!(
+            migration = name,
+            version = version,
+            statement_count = statements.len(),
+            "Applying migration"
+        );
+
+        for (i, stmt) in statements.iter().enumerate() {
+            let stmt = self.expand_placeholders(stmt);
+            info!(
+                statement_index = i,
+                "Executing migration statement: {}",
+                stmt.chars().take(100).collect::<String>()
+            );
+            self.base.query(&stmt).execute().await.wrap_err_with(|| {
+                format!("Failed to execute migration {name} statement {i}: {stmt}")
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Substitute the `${DB}` placeholder used in migration files.
+    ///
+    /// Every table reference in a migration is written as `${DB}.table`, so the table prefix is
+    /// applied together with the database name.
+    fn expand_placeholders(&self, stmt: &str) -> String {
+        stmt.replace("${DB}.", &self.table("")).replace("${DB}", &self.db_name)
+    }
+
+    /// Ensure the schema migrations table exists
+    async fn ensure_migrations_table(&self) -> Result<()> {
+        let schema = TABLE_SCHEMAS
+            .iter()
+            .find(|s| s.name == "schema_migrations")
+            .ok_or_else(|| eyre::eyre!("schema_migrations table schema not found"))?;
+
+        self.create_table(schema).await
+    }
+
+    /// Get applied migrations and their recorded checksums from database
+    async fn get_applied_migrations(&self) -> Result<HashMap<u32, String>> {
+        let query = format!("SELECT version, checksum FROM {}", self.table("schema_migrations"));
+        let rows = self.base.query(&query).fetch_all::<VersionRow>().await?;
+
+        let applied = rows.into_iter().map(|row| (row.version, row.checksum)).collect();
+        Ok(applied)
+    }
+
+    /// Record a migration as applied
+    async fn record_migration(&self, version: u32, name: &str, checksum: &str) -> Result<()> {
+        let migration =
+            SchemaVersionInsert { version, name: name.to_owned(), checksum: checksum.to_owned() };
+
+        let mut insert = self.insert("schema_migrations").await?;
+        insert.write(&migration).await?;
+        insert.end().await?;
+
+        Ok(())
+    }
+
+    /// Calculate checksum of migration content
+    fn calculate_migration_checksum(&self, content: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Extract version number from migration filename
+    fn extract_migration_version(&self, name: &str) -> Result<u32> {
+        if let Some(captures) = Regex::new(r"^(\d{3})_").unwrap().captures(name) {
+            captures[1]
+                .parse::<u32>()
+                .map_err(|e| eyre::eyre!("Invalid version number in {}: {}", name, e))
+        } else {
+            eyre::bail!("Invalid migration filename format: {}", name)
+        }
+    }
+
+    /// Insert L1 header
+    pub async fn insert_l1_header(&self, header: &L1Header) -> Result<()> {
+        let hash_bytes = HashBytes::from(header.hash);
+        let event = L1HeadEvent {
+            l1_block_number: header.number,
+            block_hash: hash_bytes,
+            slot: header.slot,
+            block_ts: header.timestamp,
+        };
+        let buffer = self.buffers.as_deref().map(|b| &b.l1_head_events);
+        self.insert_or_buffer(buffer, "l1_head_events", event).await
+    }
+
+    /// Insert preconfiguration data
+    pub async fn insert_preconf_data(
+        &self,
+        slot: u64,
+        candidates: Vec<Address>,
+        current_operator: Option<Address>,
+        next_operator: Option<Address>,
+    ) -> Result<()> {
+        let candidate_array = candidates.into_iter().map(AddressBytes::from).collect();
+        let data = PreconfData {
+            slot,
+            candidates: candidate_array,
+            current_operator: current_operator.map(AddressBytes::from),
+            next_operator: next_operator.map(AddressBytes::from),
+        };
+        let buffer = self.buffers.as_deref().map(|b| &b.preconf_data);
+        self.insert_or_buffer(buffer, "preconf_data", data).await
+    }
+
+    /// Insert L2 header event
+    pub async fn insert_l2_header(&self, event: &L2HeadEvent) -> Result<()> {
+        let buffer = self.buffers.as_deref().map(|b| &b.l2_head_events);
+        self.insert_or_buffer(buffer, "l2_head_events", event.clone()).await
+    }
+
+    /// Insert the ingestion stage timings of an event
+    pub async fn insert_pipeline_latency(&self, row: PipelineLatencyInsertRow) -> Result<()> {
+        let buffer = self.buffers.as_deref().map(|b| &b.pipeline_latency);
+        self.insert_or_buffer(buffer, "pipeline_latency", row).await
+    }
+
+    /// Insert the receive time of a live header
+    pub async fn insert_header_propagation(&self, row: HeaderPropagationInsertRow) -> Result<()> {
+        let buffer = self.buffers.as_deref().map(|b| &b.header_propagation);
+        self.insert_or_buffer(buffer, "header_propagation", row).await
+    }
+
+    /// Insert L1 data posting cost with its blob and calldata components; the execution gas
+    /// makes up the rest of the total
+    pub async fn insert_l1_data_cost(
+        &self,
+        l1_block_number: u64,
+        batch_id: u64,
+        cost: L1DataCostBreakdown,
+    ) -> Result<()> {
+        let row = L1DataCostInsertRow {
+            l1_block_number,
+            batch_id,
+            cost: cost.total(),
+            blob_cost: cost.blob_cost,
+            calldata_cost: cost.calldata_cost,
+        };
+        self.write_rows("l1_data_costs", std::slice::from_ref(&row)).await
+    }
+
+    /// Insert prover cost for a batch
+    pub async fn insert_prove_cost(
+        &self,
+        l1_block_number: u64,
+        batch_id: u64,
+        cost: u128,
+    ) -> Result<()> {
+        let row = ProveCostInsertRow { l1_block_number, batch_id, cost };
+        self.write_rows("prove_costs", std::slice::from_ref(&row)).await
+    }
+
+    /// Insert verifier cost for a batch
+    pub async fn insert_verify_cost(
+        &self,
+        l1_block_number: u64,
+        batch_id: u64,
+        cost: u128,
+    ) -> Result<()> {
+        let row = VerifyCostInsertRow { l1_block_number, batch_id, cost };
+        self.write_rows("verify_costs", std::slice::from_ref(&row)).await
+    }
+
+    /// Insert the L1 blob fee market state for a batch proposal
+    pub async fn insert_batch_blob_fee(
+        &self,
+        l1_block_number: u64,
+        batch_id: u64,
+        excess_blob_gas: u64,
+        blob_base_fee: u128,
+    ) -> Result<()> {
+        let row =
+            BatchBlobFeeInsertRow { l1_block_number, batch_id, excess_blob_gas, blob_base_fee };
+        self.write_rows("batch_blob_fees", std::slice::from_ref(&row)).await
+    }
+
+    /// Insert the anchor block id of an L2 block
+    pub async fn insert_l2_anchor_block(&self, row: &L2AnchorBlockInsertRow) -> Result<()> {
+        self.write_rows("l2_anchor_blocks", std::slice::from_ref(row)).await
+    }
+
+    /// Insert the results of a round of public RPC probes
+    pub async fn insert_rpc_health(&self, rows: &[RpcHealthInsertRow]) -> Result<()> {
+        self.write_rows("rpc_health", rows).await
+    }
+
+    /// Insert a progress snapshot of a gap backfill run
+    pub async fn insert_backfill_progress(&self, row: &BackfillProgressInsertRow) -> Result<()> {
+        self.write_rows("backfill_progress", std::slice::from_ref(row)).await
+    }
+
+    /// Insert an ETH/USD price snapshot
+    pub async fn insert_eth_price(&self, row: &EthPriceInsertRow) -> Result<()> {
+        self.write_rows("eth_prices", std::slice::from_ref(row)).await
+    }
+
+    /// Insert a TAIKO/ETH and TAIKO/USD price snapshot
+    pub async fn insert_taiko_price(&self, row: &TaikoPriceInsertRow) -> Result<()> {
+        self.write_rows("taiko_prices", std::slice::from_ref(row)).await
+    }
+
+    /// Insert inbox bond balance changes
+    pub async fn insert_bond_ledger(&self, rows: &[BondLedgerInsertRow]) -> Result<()> {
+        self.write_rows("bond_ledger", rows).await
+    }
+
+    /// Insert a round of operator balance snapshots
+    pub async fn insert_operator_balances(&self, rows: &[OperatorBalanceRow]) -> Result<()> {
+        self.write_rows("operator_balances", rows).await
+    }
+
+    /// Insert sequencer downtime spans
+    pub async fn insert_sequencer_downtime(&self, rows: &[SequencerDowntimeRow]) -> Result<()> {
+        self.write_rows("sequencer_downtime", rows).await
+    }
+
+    /// Insert sequencer handovers
+    pub async fn insert_sequencer_handovers(&self, rows: &[SequencerHandoverRow]) -> Result<()> {
+        self.write_rows("sequencer_handovers", rows).await
+    }
+
+    /// Insert fee reconciliation results
+    pub async fn insert_fee_reconciliations(&self, rows: &[FeeReconciliationRow]) -> Result<()> {
+        self.write_rows("fee_reconciliations", rows).await
+    }
+
+    /// Insert the hourly scheduled time of sequencers
+    pub async fn insert_sequencer_schedule(&self, rows: &[SequencerScheduleRow]) -> Result<()> {
+        self.write_rows("sequencer_schedule", rows).await
+    }
+
+    /// Insert a soft block that differs from its canonical block
+    pub async fn insert_preconf_mismatch(&self, row: &PreconfMismatchRow) -> Result<()> {
+        self.write_rows("preconf_mismatches", std::slice::from_ref(row)).await
+    }
+
+    /// Insert a batch whose anchor block hash differs from the indexed L1 block
+    pub async fn insert_anchor_mismatch(&self, row: &AnchorMismatchRow) -> Result<()> {
+        self.write_rows("anchor_mismatches", std::slice::from_ref(row)).await
+    }
+
+    /// Insert the L2 mempool activity of a minute
+    pub async fn insert_mempool_stats(&self, row: &MempoolStatsRow) -> Result<()> {
+        self.write_rows("mempool_stats", std::slice::from_ref(row)).await
+    }
+
+    /// Insert or update the mempool timing of a batch proposal
+    pub async fn insert_proposal_inclusion(&self, row: &ProposalInclusionRow) -> Result<()> {
+        self.write_rows("proposal_inclusions", std::slice::from_ref(row)).await
+    }
+
+    /// Insert the content of an orphaned L2 block
+    pub async fn insert_orphaned_block_details(&self, row: &OrphanedBlockDetailsRow) -> Result<()> {
+        self.write_rows("orphaned_block_details", std::slice::from_ref(row)).await
+    }
+
+    /// Insert a sample of a core metric that deviated from its moving average
+    pub async fn insert_anomaly(&self, row: &AnomalyRow) -> Result<()> {
+        self.write_rows("anomalies", std::slice::from_ref(row)).await
+    }
+
+    /// Insert a snapshot of the forced inclusion queue
+    pub async fn insert_forced_inclusion_queue(&self, row: &ForcedInclusionQueueRow) -> Result<()> {
+        self.write_rows("forced_inclusion_queue", std::slice::from_ref(row)).await
+    }
+
+    /// Insert a snapshot of the verification backlog
+    pub async fn insert_verification_backlog(&self, row: &VerificationBacklogRow) -> Result<()> {
+        self.write_rows("verification_backlog", std::slice::from_ref(row)).await
+    }
+
+    /// Insert entries of the L1 transaction index
+    pub async fn insert_l1_tx_index(&self, rows: &[L1TxIndexRow]) -> Result<()> {
+        self.write_rows("l1_tx_index", rows).await
+    }
+
+    /// Insert or refresh the labels of addresses
+    pub async fn insert_address_labels(&self, rows: &[AddressLabelRow]) -> Result<()> {
+        self.write_rows("address_labels", rows).await
+    }
+
+    /// Insert an incident operation into the audit log
+    pub async fn insert_incident_log(&self, row: &IncidentLogRow) -> Result<()> {
+        self.write_rows("incident_log", std::slice::from_ref(row)).await
+    }
+
+    /// Insert a change to the whitelist operator candidates
+    pub async fn insert_operator_history(&self, row: &OperatorHistoryRow) -> Result<()> {
+        self.write_rows("operator_history", std::slice::from_ref(row)).await
+    }
+
+    /// Insert the L1 gas spent by a protocol transaction
+    pub async fn insert_protocol_gas_spend(&self, row: &ProtocolGasSpendInsertRow) -> Result<()> {
+        self.write_rows("protocol_gas_spend", std::slice::from_ref(row)).await
+    }
+
+    /// Insert the `TaikoInbox` protocol configuration
+    pub async fn insert_protocol_config(&self, config: &ProtocolConfigRow) -> Result<()> {
+        self.write_rows("protocol_config", std::slice::from_ref(config)).await
+    }
+
+    /// Insert batch block mappings for a batch
+    pub async fn insert_batch_blocks(
+        &self,
+        batch_id: u64,
+        l2_block_numbers: Vec<u64>,
+    ) -> Result<()> {
+        if l2_block_numbers.is_empty() {
+            return Ok(());
+        }
+
+        let rows: Vec<_> = l2_block_numbers
+            .into_iter()
+            .map(|l2_block_number| BatchBlockRow { batch_id, l2_block_number })
+            .collect();
+        self.write_rows("batch_blocks", &rows).await
+    }
+
+    /// Insert a batch and its block mappings
+    pub async fn insert_batch(
+        &self,
+        batch: &chainio::ITaikoInbox::BatchProposed,
+        l1_tx_hash: B256,
+    ) -> Result<()> {
+        let mut batch_row = BatchRow::try_from((batch, l1_tx_hash))?;
+        batch_row.fork = self.fork_at(batch_row.l1_block_number);
+
+        // Insert the batch
+        self.write_rows("batches", std::slice::from_ref(&batch_row)).await?;
+
+        // Insert batch-block mappings
+        let l2_block_numbers = batch.block_numbers_proposed();
+        self.insert_batch_blocks(batch_row.batch_id, l2_block_numbers).await?;
+
+        Ok(())
+    }
+
+    /// Insert proved batches
+    pub async fn insert_proved_batch(
+        &self,
+        proved: &chainio::ITaikoInbox::BatchesProved,
+        l1_block_number: u64,
+        l1_tx_hash: B256,
+        proof_type: ProofType,
+    ) -> Result<()> {
+        for (i, batch_id) in proved.batchIds.iter().enumerate() {
+            if i >= proved.transitions.len() {
+                continue;
+            }
+            let single_proved = chainio::ITaikoInbox::BatchesProved {
+                verifier: proved.verifier,
+                batchIds: vec![*batch_id],
+                transitions: vec![proved.transitions[i].clone()],
+            };
+            let mut proved_row =
+                ProvedBatchRow::try_from((&single_proved, l1_block_number, l1_tx_hash))?;
+            proved_row.proof_type = proof_type as u8;
+            self.write_rows("proved_batches", std::slice::from_ref(&proved_row)).await?;
+        }
+        Ok(())
+    }
+
+    /// Insert forced inclusion processed row
+    pub async fn insert_forced_inclusion(
+        &self,
+        event: &chainio::taiko::wrapper::ITaikoWrapper::ForcedInclusionProcessed,
+    ) -> Result<()> {
+        let row = ForcedInclusionProcessedRow::try_from(event)?;
+        self.write_rows("forced_inclusion_processed", std::slice::from_ref(&row)).await
+    }
+
+    /// Insert an operator removal from the preconf whitelist
+    pub async fn insert_slashing_event(&self, row: &SlashingEventRow) -> Result<()> {
+        self.write_rows("slashing_events", std::slice::from_ref(row)).await
+    }
+
+    /// Insert L2 reorg row
+    pub async fn insert_l2_reorg(
+        &self,
+        block_number: BlockNumber,
+        depth: u16,
+        old_sequencer: Address,
+        new_sequencer: Address,
+        probable_cause: ReorgCause,
+    ) -> Result<()> {
+        let row = L2ReorgInsertRow {
+            l2_block_number: block_number,
+            depth,
+            old_sequencer: AddressBytes(old_sequencer.into_array()),
+            new_sequencer: AddressBytes(new_sequencer.into_array()),
+            probable_cause: probable_cause as u8,
+        };
+        self.write_rows("l2_reorgs", std::slice::from_ref(&row)).await
+    }
+
+    /// Insert verified batch row
+    pub async fn insert_verified_batch(
+        &self,
+        verified: &chainio::BatchesVerified,
+        l1_block_number: u64,
+        l1_tx_hash: B256,
+    ) -> Result<()> {
+        let verified_row = VerifiedBatchRow::try_from((verified, l1_block_number, l1_tx_hash))?;
+        self.write_rows("verified_batches", std::slice::from_ref(&verified_row)).await
+    }
+
+    /// Insert orphaned L2 block hashes
+    pub async fn insert_orphaned_hashes(&self, hashes: &[(HashBytes, u64)]) -> Result<()> {
+        if hashes.is_empty() {
+            return Ok(());
+        }
+
+        let rows: Vec<_> = hashes
+            .iter()
+            .map(|(hash, block_number)| OrphanedL2HashRow {
+                block_hash: *hash,
+                l2_block_number: *block_number,
+            })
+            .collect();
+        self.write_rows("orphaned_l2_hashes", &rows).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::L2ReorgInsertRow;
+
+    use super::*;
+
+    use alloy::primitives::{Address, B256};
+    use chainio::{ITaikoInbox, taiko::wrapper::ITaikoWrapper};
+    use clickhouse::test::{self, Mock, handlers};
+
+    #[tokio::test]
+    async fn ensure_writable_rejects_read_only_user() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![0u8]));
+        mock.add(handlers::provide(vec![2u8]));
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        writer.ensure_writable().await.unwrap();
+        let err = writer.ensure_writable().await.unwrap_err();
+        assert!(err.to_string().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn create_table_generates_correct_query() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record_ddl());
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        writer.create_table(&TABLE_SCHEMAS[0]).await.unwrap();
+        let query = ctl.query().await;
+        assert!(query.contains("CREATE TABLE IF NOT EXISTS db.l1_head_events"));
+    }
+
+    #[tokio::test]
+    async fn create_table_applies_table_prefix() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record_ddl());
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .with_table_prefix("staging_");
+
+        writer.create_table(&TABLE_SCHEMAS[0]).await.unwrap();
+        let query = ctl.query().await;
+        assert!(query.contains("CREATE TABLE IF NOT EXISTS db.staging_l1_head_events"));
+    }
+
+    #[test]
+    fn expand_placeholders_applies_table_prefix() {
+        let url = Url::parse("http://localhost:8123").unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .with_table_prefix("staging_");
+
+        let stmt = "CREATE MATERIALIZED VIEW IF NOT EXISTS ${DB}.batch_prove_times_mv \
+                    TO ${DB}.batch_prove_times AS SELECT * FROM ${DB}.batches";
+        assert_eq!(
+            writer.expand_placeholders(stmt),
+            "CREATE MATERIALIZED VIEW IF NOT EXISTS db.staging_batch_prove_times_mv \
+                    TO db.staging_batch_prove_times AS SELECT * FROM db.staging_batches"
+        );
+    }
+
+    #[tokio::test]
+    async fn insert_l1_header_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L1HeadEvent>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let header = L1Header { number: 1, hash: B256::repeat_byte(1), slot: 2, timestamp: 42 };
+
+        writer.insert_l1_header(&header).await.unwrap();
+
+        let rows: Vec<L1HeadEvent> = ctl.collect().await;
+        let expected = L1HeadEvent {
+            l1_block_number: 1,
+            block_hash: HashBytes::from([1u8; 32]),
+            slot: 2,
+            block_ts: 42,
+        };
+        assert_eq!(rows, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn secondary_mirrors_writes() {
+        let primary = Mock::new();
+        let secondary = Mock::new();
+        let primary_ctl = primary.add(handlers::record::<L1HeadEvent>());
+        let secondary_ctl = secondary.add(handlers::record::<L1HeadEvent>());
+
+        let writer = |mock: &Mock| {
+            let url = Url::parse(mock.url()).unwrap();
+            ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+        };
+        let writer = writer(&primary).with_secondary(writer(&secondary));
+
+        let header = L1Header { number: 1, hash: B256::ZERO, slot: 1, timestamp: 1 };
+        writer.insert_l1_header(&header).await.unwrap();
+
+        let primary_rows: Vec<L1HeadEvent> = primary_ctl.collect().await;
+        let secondary_rows: Vec<L1HeadEvent> = secondary_ctl.collect().await;
+        assert_eq!(primary_rows, secondary_rows);
+        assert_eq!(
+            writer.dual_write_stats(),
+            Some(DualWriteStats { mirrored_rows: 1, diverged_rows: 0, failed_writes: 0 })
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_secondary_write_does_not_fail_primary() {
+        let primary = Mock::new();
+        let primary_ctl = primary.add(handlers::record::<L1HeadEvent>());
+        let secondary = Mock::new();
+        secondary.add(handlers::failure(test::status::INTERNAL_SERVER_ERROR));
+
+        let writer = |mock: &Mock| {
+            let url = Url::parse(mock.url()).unwrap();
+            ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+        };
+        let writer = writer(&primary).with_secondary(writer(&secondary));
+
+        let header = L1Header { number: 1, hash: B256::ZERO, slot: 1, timestamp: 1 };
+        writer.insert_l1_header(&header).await.unwrap();
+
+        let rows: Vec<L1HeadEvent> = primary_ctl.collect().await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            writer.dual_write_stats(),
+            Some(DualWriteStats { mirrored_rows: 0, diverged_rows: 1, failed_writes: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_write_is_spilled_and_replayed() {
+        let mock = Mock::new();
+        mock.add(handlers::failure(test::status::INTERNAL_SERVER_ERROR));
+        let dir =
+            std::env::temp_dir().join(format!("taikoscope-writer-spill-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = SpillConfig {
+            path: dir.join("spill.jsonl"),
+            max_bytes: 1 << 20,
+            replay_interval: std::time::Duration::from_secs(1),
+        };
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .with_spill(config)
+            .unwrap();
+
+        let row = EthPriceInsertRow { ts: 1_700_000_000, usd: 2_345.67 };
+        writer.insert_eth_price(&row).await.unwrap();
+        assert_eq!(writer.spill_stats().map(|s| (s.spilled_rows, s.pending_rows)), Some((1, 1)));
+
+        let ctl = mock.add(handlers::record::<EthPriceInsertRow>());
+        assert_eq!(writer.replay_spill().await.unwrap(), 1);
+        let rows: Vec<EthPriceInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+        assert_eq!(writer.spill_stats().map(|s| (s.replayed_rows, s.pending_rows)), Some((1, 0)));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[tokio::test]
+    async fn injected_fault_fails_insert() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L1HeadEvent>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let faults = FaultSchedule::new(0).with_script([Fault::Fail]);
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .with_faults(faults);
+
+        let header = L1Header { number: 1, hash: B256::ZERO, slot: 1, timestamp: 1 };
+        let err = writer.insert_l1_header(&header).await.unwrap_err();
+        assert!(err.to_string().contains("Injected fault"));
+
+        writer.insert_l1_header(&header).await.unwrap();
+        let rows: Vec<L1HeadEvent> = ctl.collect().await;
+        assert_eq!(rows.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn buffered_l1_headers_are_written_in_bulk() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L1HeadEvent>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let config =
+            WriteBufferConfig { max_rows: 2, flush_interval: std::time::Duration::from_secs(60) };
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .with_write_buffer(config);
+
+        for number in 1..=3 {
+            let header = L1Header { number, hash: B256::ZERO, slot: number, timestamp: number };
+            writer.insert_l1_header(&header).await.unwrap();
+        }
+        assert_eq!(writer.buffer_depths()[0], ("l1_head_events", 1));
+
+        let rows: Vec<L1HeadEvent> = ctl.collect().await;
+        assert_eq!(rows.iter().map(|r| r.l1_block_number).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn flush_writes_remaining_buffered_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L1HeadEvent>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let config =
+            WriteBufferConfig { max_rows: 10, flush_interval: std::time::Duration::from_secs(60) };
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .with_write_buffer(config);
+
+        let header = L1Header { number: 7, hash: B256::ZERO, slot: 7, timestamp: 7 };
+        writer.insert_l1_header(&header).await.unwrap();
+        writer.flush().await.unwrap();
+
+        let rows: Vec<L1HeadEvent> = ctl.collect().await;
+        assert_eq!(rows.iter().map(|r| r.l1_block_number).collect::<Vec<_>>(), vec![7]);
+        assert_eq!(writer.buffer_depths()[0], ("l1_head_events", 0));
+    }
+
+    #[tokio::test]
+    async fn insert_preconf_data_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<PreconfData>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let candidates = vec![Address::repeat_byte(1), Address::repeat_byte(2)];
+        writer
+            .insert_preconf_data(5, candidates.clone(), Some(Address::repeat_byte(3)), None)
+            .await
+            .unwrap();
+
+        let rows: Vec<PreconfData> = ctl.collect().await;
+        let expected = PreconfData {
+            slot: 5,
+            candidates: vec![
+                AddressBytes::from(Address::repeat_byte(1)),
+                AddressBytes::from(Address::repeat_byte(2)),
+            ],
+            current_operator: Some(AddressBytes::from(Address::repeat_byte(3))),
+            next_operator: None,
+        };
+        assert_eq!(rows, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn insert_l2_reorg_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L2ReorgInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        writer
+            .insert_l2_reorg(
+                10,
+                3,
+                Address::repeat_byte(1),
+                Address::repeat_byte(2),
+                ReorgCause::OperatorChange,
+            )
+            .await
+            .unwrap();
+
+        let rows: Vec<L2ReorgInsertRow> = ctl.collect().await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].l2_block_number, 10);
+        assert_eq!(rows[0].depth, 3);
+        assert_eq!(rows[0].old_sequencer, AddressBytes::from(Address::repeat_byte(1)));
+        assert_eq!(rows[0].new_sequencer, AddressBytes::from(Address::repeat_byte(2)));
+        assert_eq!(rows[0].probable_cause, ReorgCause::OperatorChange as u8);
+    }
+
+    #[tokio::test]
+    async fn insert_l2_header_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L2HeadEvent>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let event = L2HeadEvent {
+            l2_block_number: 1,
+            block_hash: HashBytes::from([1u8; 32]),
+            block_ts: 10,
+            sum_gas_used: 20,
+            gas_limit: 100,
+            sum_tx: 3,
+            sum_priority_fee: 30,
+            sum_base_fee: 40,
+            avg_gas_price: 23,
+            sequencer: AddressBytes::from([5u8; 20]),
+            extra_data: "0x7461696b6f".to_owned(),
+        };
+
+        writer.insert_l2_header(&event).await.unwrap();
+
+        let rows: Vec<L2HeadEvent> = ctl.collect().await;
+        assert_eq!(rows, vec![event]);
+    }
+
+    #[tokio::test]
+    async fn insert_batch_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<BatchRow>());
+        // Add a handler for the batch_blocks table insert
+        let _ctl_blocks = mock.add(handlers::record::<BatchBlockRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into())
+            .with_fork_schedule(vec![(10, "shasta".to_owned()), (0, "pacaya".to_owned())]);
+
+        let batch = ITaikoInbox::BatchProposed {
+            info: ITaikoInbox::BatchInfo {
+                proposedIn: 2,
+                blobByteSize: 50,
+                blocks: vec![ITaikoInbox::BlockParams::default(); 1],
+                blobHashes: vec![B256::repeat_byte(1)],
+                lastBlockId: 100, // Adding test value for last block ID
+                ..Default::default()
+            },
+            meta: ITaikoInbox::BatchMetadata {
+                proposer: Address::repeat_byte(2),
+                batchId: 7,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        writer.insert_batch(&batch, B256::ZERO).await.unwrap();
+
+        let rows: Vec<BatchRow> = ctl.collect().await;
+        let expected = BatchRow {
+            l1_block_number: 2,
+            l1_tx_hash: HashBytes::from([0u8; 32]),
+            batch_id: 7,
+            batch_size: 1,
+            last_l2_block_number: 100,
+            proposer_addr: AddressBytes::from(Address::repeat_byte(2)),
+            blob_count: 1,
+            blob_total_bytes: 50,
+            da_mode: 0,
+            calldata_bytes: 0,
+            fork: "pacaya".to_owned(),
+        };
+        assert_eq!(rows, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn insert_batch_blocks_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<BatchBlockRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        writer.insert_batch_blocks(7, vec![98, 99, 100]).await.unwrap();
+
+        let rows: Vec<BatchBlockRow> = ctl.collect().await;
+        let expected = vec![
+            BatchBlockRow { batch_id: 7, l2_block_number: 98 },
+            BatchBlockRow { batch_id: 7, l2_block_number: 99 },
+            BatchBlockRow { batch_id: 7, l2_block_number: 100 },
+        ];
+        assert_eq!(rows, expected);
+    }
+
+    #[tokio::test]
+    async fn insert_proved_batch_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<ProvedBatchRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let transition = ITaikoInbox::Transition {
+            parentHash: B256::repeat_byte(1),
+            blockHash: B256::repeat_byte(2),
+            stateRoot: B256::repeat_byte(3),
+        };
+        let proved = ITaikoInbox::BatchesProved {
+            verifier: Address::repeat_byte(4),
+            batchIds: vec![8],
+            transitions: vec![transition],
+        };
+
+        writer.insert_proved_batch(&proved, 10, B256::repeat_byte(5), ProofType::Zk).await.unwrap();
+
+        let rows: Vec<ProvedBatchRow> = ctl.collect().await;
+        let expected = ProvedBatchRow {
+            l1_block_number: 10,
+            batch_id: 8,
+            verifier_addr: AddressBytes::from(Address::repeat_byte(4)),
+            parent_hash: HashBytes::from([1u8; 32]),
+            block_hash: HashBytes::from([2u8; 32]),
+            state_root: HashBytes::from([3u8; 32]),
+            l1_tx_hash: HashBytes::from([5u8; 32]),
+            proof_type: ProofType::Zk as u8,
+        };
+        assert_eq!(rows, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn insert_verified_batch_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<VerifiedBatchRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let verified = chainio::BatchesVerified { batch_id: 3, block_hash: [9u8; 32] };
+
+        writer.insert_verified_batch(&verified, 12, B256::repeat_byte(5)).await.unwrap();
+
+        let rows: Vec<VerifiedBatchRow> = ctl.collect().await;
+        let expected = VerifiedBatchRow {
+            l1_block_number: 12,
+            batch_id: 3,
+            block_hash: HashBytes::from([9u8; 32]),
+            l1_tx_hash: HashBytes::from([5u8; 32]),
+        };
+        assert_eq!(rows, vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn insert_forced_inclusion_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<ForcedInclusionProcessedRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let event = ITaikoWrapper::ForcedInclusionProcessed {
+            forcedInclusion: ITaikoWrapper::ForcedInclusion {
+                blobHash: B256::repeat_byte(5),
+                feeInGwei: 1,
+                createdAtBatchId: 0,
+                blobByteOffset: 0,
+                blobByteSize: 0,
+                blobCreatedIn: 0,
+            },
+        };
+
+        writer.insert_forced_inclusion(&event).await.unwrap();
+
+        let rows: Vec<ForcedInclusionProcessedRow> = ctl.collect().await;
+        assert_eq!(
+            rows,
+            vec![ForcedInclusionProcessedRow { blob_hash: HashBytes::from([5u8; 32]) }]
+        );
+    }
+
+    #[tokio::test]
+    async fn insert_l1_data_cost_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L1DataCostInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let cost = L1DataCostBreakdown { blob_cost: 12, calldata_cost: 20, execution_cost: 10 };
+        writer.insert_l1_data_cost(10, 7, cost).await.unwrap();
+
+        let rows: Vec<L1DataCostInsertRow> = ctl.collect().await;
+        assert_eq!(
+            rows,
+            vec![L1DataCostInsertRow {
+                l1_block_number: 10,
+                batch_id: 7,
+                cost: 42,
+                blob_cost: 12,
+                calldata_cost: 20,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn insert_prove_cost_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<ProveCostInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        writer.insert_prove_cost(8, 9, 55).await.unwrap();
+
+        let rows: Vec<ProveCostInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![ProveCostInsertRow { l1_block_number: 8, batch_id: 9, cost: 55 }]);
+    }
+
+    #[tokio::test]
+    async fn insert_verify_cost_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<VerifyCostInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        writer.insert_verify_cost(8, 10, 66).await.unwrap();
+
+        let rows: Vec<VerifyCostInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![VerifyCostInsertRow { l1_block_number: 8, batch_id: 10, cost: 66 }]);
+    }
+
+    #[tokio::test]
+    async fn insert_batch_blob_fee_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<BatchBlobFeeInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        writer.insert_batch_blob_fee(8, 11, 393_216, 7).await.unwrap();
+
+        let rows: Vec<BatchBlobFeeInsertRow> = ctl.collect().await;
+        assert_eq!(
+            rows,
+            vec![BatchBlobFeeInsertRow {
+                l1_block_number: 8,
+                batch_id: 11,
+                excess_blob_gas: 393_216,
+                blob_base_fee: 7,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn insert_l2_anchor_block_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L2AnchorBlockInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = L2AnchorBlockInsertRow {
+            l2_block_number: 100,
+            block_ts: 1_700_000_000,
+            anchor_block_id: 20,
+            l1_head: 24,
+        };
+        writer.insert_l2_anchor_block(&row).await.unwrap();
+
+        let rows: Vec<L2AnchorBlockInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_rpc_health_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<RpcHealthInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let rows = vec![
+            RpcHealthInsertRow {
+                url: "https://rpc.example".to_owned(),
+                healthy: 1,
+                latency_ms: Some(120),
+                block_number: Some(1_000),
+                block_lag: Some(2),
+                error: None,
+            },
+            RpcHealthInsertRow {
+                url: "https://other.example".to_owned(),
+                healthy: 0,
+                latency_ms: None,
+                block_number: None,
+                block_lag: None,
+                error: Some("request timed out".to_owned()),
+            },
+        ];
+        writer.insert_rpc_health(&rows).await.unwrap();
+
+        let recorded: Vec<RpcHealthInsertRow> = ctl.collect().await;
+        assert_eq!(recorded, rows);
+    }
+
+    #[tokio::test]
+    async fn insert_backfill_progress_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<BackfillProgressInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = BackfillProgressInsertRow {
+            chain: "l2".to_owned(),
+            scheduled_blocks: 120,
+            filled_blocks: 50,
+            failed_blocks: 1,
+            newest_block: Some(1_200),
+            oldest_block: Some(1_081),
+            current_block: Some(1_150),
+            max_requests_per_sec: 20,
+        };
+        writer.insert_backfill_progress(&row).await.unwrap();
+
+        let rows: Vec<BackfillProgressInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_eth_price_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<EthPriceInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = EthPriceInsertRow { ts: 1_700_000_000, usd: 2_345.67 };
+        writer.insert_eth_price(&row).await.unwrap();
+
+        let rows: Vec<EthPriceInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_taiko_price_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<TaikoPriceInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = TaikoPriceInsertRow { ts: 1_700_000_000, eth: 0.000_12, usd: 0.42 };
+        writer.insert_taiko_price(&row).await.unwrap();
+
+        let rows: Vec<TaikoPriceInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_bond_ledger_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<BondLedgerInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let rows = vec![BondLedgerInsertRow {
+            address: AddressBytes([1u8; 20]),
+            l1_block_number: 100,
+            tx_hash: HashBytes([2u8; 32]),
+            log_index: 3,
+            kind: "debited".to_owned(),
+            amount: 125,
+            balance: 1_000,
+        }];
+        writer.insert_bond_ledger(&rows).await.unwrap();
+
+        let recorded: Vec<BondLedgerInsertRow> = ctl.collect().await;
+        assert_eq!(recorded, rows);
+    }
+
+    #[tokio::test]
+    async fn insert_operator_balances_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<OperatorBalanceRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let rows = vec![OperatorBalanceRow {
+            snapshot_ts: 1_700_000_000,
+            address: AddressBytes([1u8; 20]),
+            l1_block_number: 100,
+            eth_balance: 5_000,
+            taiko_balance: Some(7_000),
+            low_balance: 1,
+        }];
+        writer.insert_operator_balances(&rows).await.unwrap();
+
+        let recorded: Vec<OperatorBalanceRow> = ctl.collect().await;
+        assert_eq!(recorded, rows);
+    }
+
+    #[tokio::test]
+    async fn insert_sequencer_downtime_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<SequencerDowntimeRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let rows = vec![SequencerDowntimeRow {
+            sequencer: AddressBytes([1u8; 20]),
+            start_ts: 1_700_000_000,
+            end_ts: 1_700_000_090,
+            computation_version: 1,
+        }];
+        writer.insert_sequencer_downtime(&rows).await.unwrap();
+
+        let recorded: Vec<SequencerDowntimeRow> = ctl.collect().await;
+        assert_eq!(recorded, rows);
+    }
+
+    #[tokio::test]
+    async fn insert_sequencer_handovers_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<SequencerHandoverRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let rows = vec![SequencerHandoverRow {
+            outgoing: AddressBytes([1u8; 20]),
+            incoming: AddressBytes([2u8; 20]),
+            last_block_number: 1_000,
+            last_block_ts: 1_700_000_000,
+            first_block_number: 1_001,
+            first_block_ts: 1_700_000_006,
+            latency_secs: 6,
+        }];
+        writer.insert_sequencer_handovers(&rows).await.unwrap();
+
+        let recorded: Vec<SequencerHandoverRow> = ctl.collect().await;
+        assert_eq!(recorded, rows);
+    }
+
+    #[tokio::test]
+    async fn insert_fee_reconciliations_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<FeeReconciliationRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let rows = vec![FeeReconciliationRow {
+            period_start: 1_700_000_000,
+            period_end: 1_700_003_600,
+            sequencer: AddressBytes([1u8; 20]),
+            chain: "l1".to_owned(),
+            from_block: 21_000_000,
+            to_block: 21_000_300,
+            expected_delta: -5_000,
+            actual_delta: -5_100,
+            discrepancy: -100,
+            flagged: 0,
+        }];
+        writer.insert_fee_reconciliations(&rows).await.unwrap();
+
+        let recorded: Vec<FeeReconciliationRow> = ctl.collect().await;
+        assert_eq!(recorded, rows);
+    }
+
+    #[tokio::test]
+    async fn rerun_migration_rejects_unknown_version() {
+        let url = Url::parse("http://127.0.0.1:1").unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let err = writer.rerun_migration(999).await.unwrap_err();
+        assert!(err.to_string().contains("Unknown migration version 999"));
+    }
+
+    #[tokio::test]
+    async fn insert_preconf_mismatch_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<PreconfMismatchRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = PreconfMismatchRow {
+            l2_block_number: 42,
+            soft_block_hash: Some(HashBytes([1u8; 32])),
+            canonical_block_hash: Some(HashBytes([2u8; 32])),
+            soft_tx_count: 2,
+            canonical_tx_count: 2,
+            missing_tx_hashes: vec![HashBytes([3u8; 32])],
+            extra_tx_hashes: vec![HashBytes([4u8; 32])],
+            reordered: 0,
+        };
+        writer.insert_preconf_mismatch(&row).await.unwrap();
+
+        let recorded: Vec<PreconfMismatchRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_anchor_mismatch_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<AnchorMismatchRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = AnchorMismatchRow {
+            batch_id: 7,
+            l1_block_number: 120,
+            anchor_block_id: 115,
+            anchor_block_hash: HashBytes([1u8; 32]),
+            l1_block_hash: HashBytes([2u8; 32]),
+        };
+        writer.insert_anchor_mismatch(&row).await.unwrap();
+
+        let recorded: Vec<AnchorMismatchRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_mempool_stats_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<MempoolStatsRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = MempoolStatsRow {
+            minute_ts: 1_700_000_040,
+            seen_txs: 12,
+            included_txs: 10,
+            max_depth: 5,
+            end_depth: 2,
+            avg_inclusion_ms: 1_500,
+            p50_inclusion_ms: 1_200,
+            p95_inclusion_ms: 3_000,
+            max_inclusion_ms: 4_000,
+        };
+        writer.insert_mempool_stats(&row).await.unwrap();
+
+        let recorded: Vec<MempoolStatsRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_orphaned_block_details_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<OrphanedBlockDetailsRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = OrphanedBlockDetailsRow {
+            block_hash: HashBytes([1u8; 32]),
+            l2_block_number: 42,
+            parent_hash: HashBytes([2u8; 32]),
+            block_ts: 1_700_000_000,
+            sequencer: AddressBytes([3u8; 20]),
+            gas_used: 21_000,
+            tx_count: 1,
+            tx_hashes: vec![HashBytes([4u8; 32])],
+            reinclusion_checked: 1,
+            reincluded_txs: 1,
+        };
+        writer.insert_orphaned_block_details(&row).await.unwrap();
+
+        let recorded: Vec<OrphanedBlockDetailsRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_anomaly_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<AnomalyRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = AnomalyRow {
+            detected_ts: 1_700_000_000,
+            metric: 1,
+            value: 9_000_000.0,
+            expected: 3_000_000.0,
+            std_dev: 500_000.0,
+            z_score: 12.0,
+        };
+        writer.insert_anomaly(&row).await.unwrap();
+
+        let recorded: Vec<AnomalyRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_forced_inclusion_queue_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<ForcedInclusionQueueRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = ForcedInclusionQueueRow {
+            snapshot_ts: 1_700_000_600,
+            l1_block_number: 21_000_000,
+            pending: 2,
+            oldest_created_ts: Some(1_700_000_000),
+            oldest_age_secs: Some(600),
+            deadline_batch_id: Some(1_010),
+            next_batch_id: 1_009,
+            near_deadline: 1,
+        };
+        writer.insert_forced_inclusion_queue(&row).await.unwrap();
+
+        let recorded: Vec<ForcedInclusionQueueRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_verification_backlog_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<VerificationBacklogRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = VerificationBacklogRow {
+            snapshot_ts: 1_700_000_600,
+            l1_block_number: 21_000_000,
+            last_proposed_batch_id: 1_250,
+            last_verified_batch_id: 1_200,
+            backlog: 50,
+            over_threshold: 1,
+        };
+        writer.insert_verification_backlog(&row).await.unwrap();
+
+        let recorded: Vec<VerificationBacklogRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_l1_tx_index_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<L1TxIndexRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let rows = vec![
+            L1TxIndexRow {
+                tx_hash: HashBytes::from([3u8; 32]),
+                role: 2,
+                batch_id: 1_200,
+                l1_block_number: 21_000_010,
+            },
+            L1TxIndexRow {
+                tx_hash: HashBytes::from([3u8; 32]),
+                role: 2,
+                batch_id: 1_201,
+                l1_block_number: 21_000_010,
+            },
+        ];
+        writer.insert_l1_tx_index(&rows).await.unwrap();
+
+        let recorded: Vec<L1TxIndexRow> = ctl.collect().await;
+        assert_eq!(recorded, rows);
+    }
+
+    #[tokio::test]
+    async fn insert_proposal_inclusion_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<ProposalInclusionRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = ProposalInclusionRow {
+            proposer: AddressBytes([1u8; 20]),
+            nonce: 7,
+            tx_hash: HashBytes([2u8; 32]),
+            first_seen_ms: 1_700_000_000_000,
+            max_fee_per_gas: 30_000_000_000,
+            max_priority_fee_per_gas: Some(2_000_000_000),
+            replacements: 1,
+            batch_id: Some(1_000),
+            l1_block_number: Some(21_000_000),
+            included_at_ms: Some(1_700_000_024_000),
+            inclusion_delay_ms: Some(24_000),
+            dropped: 0,
+        };
+        writer.insert_proposal_inclusion(&row).await.unwrap();
+
+        let recorded: Vec<ProposalInclusionRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_address_labels_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<AddressLabelRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let rows = vec![
+            AddressLabelRow {
+                address: AddressBytes([1u8; 20]),
+                label: "Gattaca".to_owned(),
+                source: 0,
+                updated_ts: 1_700_000_000,
+            },
+            AddressLabelRow {
+                address: AddressBytes([2u8; 20]),
+                label: "sequencer.eth".to_owned(),
+                source: 1,
+                updated_ts: 1_700_000_000,
+            },
+        ];
+        writer.insert_address_labels(&rows).await.unwrap();
+
+        let recorded: Vec<AddressLabelRow> = ctl.collect().await;
+        assert_eq!(recorded, rows);
+    }
+
+    #[tokio::test]
+    async fn insert_incident_log_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<IncidentLogRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = IncidentLogRow {
+            logged_at_ms: 1_700_000_000_123,
+            action: 0,
+            incident_id: "inc1".to_owned(),
+            component_ids: vec!["comp1".to_owned()],
+            incident_name: "Batch proof timeout".to_owned(),
+            status: "INVESTIGATING".to_owned(),
+            payload: r#"{"name":"Batch proof timeout"}"#.to_owned(),
+            response_code: 200,
+            error: None,
+            dry_run: 0,
+        };
+        writer.insert_incident_log(&row).await.unwrap();
+
+        let recorded: Vec<IncidentLogRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_operator_history_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<OperatorHistoryRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = OperatorHistoryRow {
+            observed_ts: 1_700_000_000,
+            l1_block_number: 120,
+            candidates: vec![AddressBytes([1u8; 20]), AddressBytes([3u8; 20])],
+            added: vec![AddressBytes([3u8; 20])],
+            removed: vec![AddressBytes([2u8; 20])],
+        };
+        writer.insert_operator_history(&row).await.unwrap();
+
+        let recorded: Vec<OperatorHistoryRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_slashing_event_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<SlashingEventRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = SlashingEventRow {
+            l1_block_number: 7,
+            validator_addr: AddressBytes([5u8; 20]),
+            reason: 2,
+        };
+        writer.insert_slashing_event(&row).await.unwrap();
+
+        let recorded: Vec<SlashingEventRow> = ctl.collect().await;
+        assert_eq!(recorded, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_protocol_gas_spend_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<ProtocolGasSpendInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = ProtocolGasSpendInsertRow {
+            l1_block_number: 10,
+            tx_hash: HashBytes([3u8; 32]),
+            address: AddressBytes([1u8; 20]),
+            operation: "prove".to_owned(),
+            gas_used: 250_000,
+            cost: 5_000_000_000_000_000,
+        };
+        writer.insert_protocol_gas_spend(&row).await.unwrap();
+
+        let rows: Vec<ProtocolGasSpendInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_pipeline_latency_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<PipelineLatencyInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = PipelineLatencyInsertRow {
+            kind: "l2_header".to_owned(),
+            queue_us: 150,
+            process_us: 2_500,
+            total_us: 2_650,
+        };
+        writer.insert_pipeline_latency(row.clone()).await.unwrap();
+
+        let rows: Vec<PipelineLatencyInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_header_propagation_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<HeaderPropagationInsertRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = HeaderPropagationInsertRow {
+            chain: "l2".to_owned(),
+            block_number: 42,
+            block_ts: 1_700_000_000,
+            observed_at: 1_700_000_000_350,
+            delay_ms: 350,
+        };
+        writer.insert_header_propagation(row.clone()).await.unwrap();
+
+        let rows: Vec<HeaderPropagationInsertRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_protocol_config_writes_expected_row() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<ProtocolConfigRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let row = ProtocolConfigRow {
+            inbox_address: AddressBytes([1u8; 20]),
+            chain_id: 167000,
+            max_unverified_batches: 324_000,
+            batch_ring_buffer_size: 324_512,
+            max_batches_to_verify: 16,
+            block_max_gas_limit: 240_000_000,
+            liveness_bond_base: 125,
+            liveness_bond_per_block: 0,
+            state_root_sync_internal: 4,
+            max_anchor_height_offset: 64,
+            base_fee_sharing_pctg: 75,
+            proving_window: 7200,
+            cooldown_window: 14_400,
+            max_signals_to_receive: 16,
+            max_blocks_per_batch: 768,
+            ontake_fork_height: 538_304,
+            pacaya_fork_height: 1_166_000,
+        };
+        writer.insert_protocol_config(&row).await.unwrap();
+
+        let rows: Vec<ProtocolConfigRow> = ctl.collect().await;
+        assert_eq!(rows, vec![row]);
+    }
+
+    #[tokio::test]
+    async fn insert_orphaned_hashes_writes_expected_rows() {
+        let mock = Mock::new();
+        let ctl = mock.add(handlers::record::<OrphanedL2HashRow>());
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let hashes =
+            vec![(HashBytes::from([1u8; 32]), 100u64), (HashBytes::from([2u8; 32]), 101u64)];
+        writer.insert_orphaned_hashes(&hashes).await.unwrap();
+
+        let rows: Vec<OrphanedL2HashRow> = ctl.collect().await;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].block_hash, HashBytes::from([1u8; 32]));
+        assert_eq!(rows[0].l2_block_number, 100);
+        assert_eq!(rows[1].block_hash, HashBytes::from([2u8; 32]));
+        assert_eq!(rows[1].l2_block_number, 101);
+    }
+
+    #[test]
+    fn verify_migration_checksums_rejects_modified_migrations() {
+        let applied = HashMap::from([(1, "aaa".to_owned()), (2, "bbb".to_owned())]);
+        let migrations = vec![
+            (1, "001_create_tables.sql", "aaa".to_owned()),
+            (2, "002_add_column.sql", "changed".to_owned()),
+            (3, "003_pending.sql", "ccc".to_owned()),
+        ];
+
+        let err = verify_migration_checksums(&applied, &migrations, false).unwrap_err();
+        assert!(err.to_string().contains("002_add_column.sql"));
+        assert!(verify_migration_checksums(&applied, &migrations, true).is_ok());
+        assert!(verify_migration_checksums(&applied, &migrations[..1], false).is_ok());
+    }
+
+    #[test]
+    fn parse_sql_handles_semicolons_in_strings() {
+        let sql = "CREATE TABLE t(a String DEFAULT ';');\nCREATE TABLE t2(b String);";
+        let statements = parse_sql_statements(sql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].contains("DEFAULT ';'"));
+    }
+
+    #[tokio::test]
+    async fn create_table_returns_error_on_failure() {
+        let mock = Mock::new();
+        mock.add(handlers::failure(test::status::INTERNAL_SERVER_ERROR));
+
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let result = writer.create_table(&TABLE_SCHEMAS[0]).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn insert_batch_fails_with_too_many_blobs() {
+        let mock = Mock::new();
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let batch = ITaikoInbox::BatchProposed {
+            info: ITaikoInbox::BatchInfo {
+                proposedIn: 1,
+                blobByteSize: 10,
+                blocks: vec![ITaikoInbox::BlockParams::default(); 1],
+                blobHashes: vec![B256::repeat_byte(1); 256],
+                ..Default::default()
+            },
+            meta: ITaikoInbox::BatchMetadata {
+                proposer: Address::repeat_byte(2),
+                batchId: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let result = writer.insert_batch(&batch, B256::ZERO).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn insert_proved_batch_returns_error_on_failure() {
+        let mock = Mock::new();
+        mock.add(handlers::failure(test::status::INTERNAL_SERVER_ERROR));
+        let url = Url::parse(mock.url()).unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+
+        let transition = ITaikoInbox::Transition {
+            parentHash: B256::repeat_byte(1),
+            blockHash: B256::repeat_byte(2),
+            stateRoot: B256::repeat_byte(3),
+        };
+        let proved = ITaikoInbox::BatchesProved {
+            verifier: Address::repeat_byte(4),
+            batchIds: vec![1],
+            transitions: vec![transition],
+        };
+
+        let result = writer.insert_proved_batch(&proved, 10, B256::ZERO, ProofType::Unknown).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,411 @@
+//! Dataset snapshots in object storage
+//!
+//! [`ClickhouseWriter::export_snapshot`] copies every table from [`snapshot_tables`] to an
+//! S3-compatible bucket through the `s3` table function, so the data flows from the server to the
+//! bucket without passing through the indexer. Each table is split into chunks of roughly the
+//! configured number of rows by hashing its rows, written as `<table>/<chunk>.<ext>`, and a
+//! `manifest.jsonl` listing the row and chunk count of every table is written last. Rows are
+//! bounded by the server time at which the export started, so rows ingested while it runs do
+//! not make the chunks of a table overlap, and the row counts are read back from the written
+//! chunks.
+//!
+//! [`ClickhouseWriter::import_snapshot`] loads a snapshot into an empty database at the same
+//! schema version and checks the row counts against the manifest. Materialized views are not
+//! exported: they are detached while the tables are loaded, so chunks inserted in any order do
+//! not feed them partial joins, and rebuilt from the imported tables afterwards.
+
+use clickhouse::Row;
+use eyre::{Result, bail};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::ClickhouseWriter;
+use crate::schema::{TABLES, VIEWS};
+
+/// Tables that are not part of a snapshot: the migration history, recreated by the migrations
+/// of the importing instance, and the leader lease, which is specific to a deployment
+const EXCLUDED_TABLES: &[&str] = &["schema_migrations", "driver_leases"];
+
+/// Name of the manifest object at the root of a snapshot
+const MANIFEST: &str = "manifest.jsonl";
+
+/// Column bounding the rows of a table to those inserted before the export started
+const INSERTED_AT: &str = "inserted_at";
+
+/// Tables copied by a snapshot. Materialized views hold aggregate states, which not every
+/// format can encode, and are rebuilt on import instead.
+pub fn snapshot_tables() -> impl Iterator<Item = &'static str> {
+    TABLES
+        .iter()
+        .copied()
+        .filter(|table| !EXCLUDED_TABLES.contains(table))
+        .filter(|table| !VIEWS.contains(table) && !table.ends_with("_mv"))
+}
+
+/// File format of the chunks of a snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnapshotFormat {
+    /// `ClickHouse` native format, lossless for every column type
+    #[default]
+    Native,
+    /// Apache Parquet, readable by other tools
+    Parquet,
+}
+
+impl SnapshotFormat {
+    /// Format name understood by `ClickHouse`
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Native => "Native",
+            Self::Parquet => "Parquet",
+        }
+    }
+
+    /// File extension of the chunks
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Native => "native",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+/// S3-compatible location of a snapshot
+#[derive(Debug, Clone)]
+pub struct SnapshotLocation {
+    /// URL of the snapshot prefix, e.g. `https://bucket.s3.amazonaws.com/taikoscope/2026-10-17`
+    pub url: String,
+    /// Access key of the bucket; the server's own credentials are used when unset
+    pub access_key_id: Option<String>,
+    /// Secret key of the bucket
+    pub secret_access_key: Option<String>,
+    /// File format of the chunks
+    pub format: SnapshotFormat,
+}
+
+impl SnapshotLocation {
+    /// `s3` table function reading or writing `path` below the snapshot prefix in `format`
+    fn s3(&self, path: &str, format: &str) -> String {
+        let url = quote(&format!("{}/{path}", self.url.trim_end_matches('/')));
+        match (&self.access_key_id, &self.secret_access_key) {
+            (Some(key), Some(secret)) => {
+                format!("s3({url}, {}, {}, '{format}')", quote(key), quote(secret))
+            }
+            _ => format!("s3({url}, '{format}')"),
+        }
+    }
+
+    /// Table function of chunk `chunk` of `table`
+    fn chunk(&self, table: &str, chunk: u64) -> String {
+        self.s3(&format!("{table}/{chunk:06}.{}", self.format.extension()), self.format.name())
+    }
+
+    /// Table function of all `chunks` chunks of `table`
+    fn chunks(&self, table: &str, chunks: u64) -> String {
+        let ext = self.format.extension();
+        let path = match chunks {
+            0 | 1 => format!("{table}/{:06}.{ext}", 0),
+            n => format!("{table}/{{{:06}..{:06}}}.{ext}", 0, n - 1),
+        };
+        self.s3(&path, self.format.name())
+    }
+}
+
+/// Quote `value` as a `ClickHouse` string literal
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Contents of one table in a snapshot, as listed in its manifest
+#[derive(Debug, Clone, Row, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SnapshotTableRow {
+    /// Table name, without prefix
+    pub table: String,
+    /// Rows exported, as read back from the chunks
+    pub rows: u64,
+    /// Chunks the rows were split into
+    pub chunks: u64,
+    /// Latest migration applied to the exporting database
+    pub schema_version: u32,
+}
+
+/// Number of chunks of at most about `chunk_rows` rows for `rows` rows
+fn chunk_count(rows: u64, chunk_rows: u64) -> u64 {
+    rows.div_ceil(chunk_rows.max(1))
+}
+
+impl ClickhouseWriter {
+    /// Export every snapshot table to `location` in chunks of about `chunk_rows` rows, then
+    /// write the manifest. Returns the manifest entries.
+    pub async fn export_snapshot(
+        &self,
+        location: &SnapshotLocation,
+        chunk_rows: u64,
+    ) -> Result<Vec<SnapshotTableRow>> {
+        let schema_version = self.schema_version().await?;
+        let snapshot_ms = self.server_time_ms().await?;
+        let mut manifest = Vec::new();
+        for table in snapshot_tables() {
+            let filter = if self.has_column(table, INSERTED_AT).await? {
+                snapshot_filter(snapshot_ms)
+            } else {
+                "1".to_owned()
+            };
+            let query = format!("SELECT count() FROM {} WHERE {filter}", self.table(table));
+            let chunks = chunk_count(self.base.query(&query).fetch_one::<u64>().await?, chunk_rows);
+            let mut rows = 0;
+            for chunk in 0..chunks {
+                let query = format!(
+                    "INSERT INTO FUNCTION {} \
+                     SELECT * FROM {} WHERE {filter} AND cityHash64(*) % {chunks} = {chunk} \
+                     SETTINGS s3_truncate_on_insert = 1",
+                    location.chunk(table, chunk),
+                    self.table(table),
+                );
+                self.base.query(&query).execute().await?;
+                let query = format!("SELECT count() FROM {}", location.chunk(table, chunk));
+                rows += self.base.query(&query).fetch_one::<u64>().await?;
+            }
+            info!(table, rows, chunks, snapshot_ms, "Exported table");
+            manifest.push(SnapshotTableRow {
+                table: table.to_owned(),
+                rows,
+                chunks,
+                schema_version,
+            });
+        }
+
+        self.base.query(&manifest_insert_query(location, &manifest)).execute().await?;
+        Ok(manifest)
+    }
+
+    /// Import the snapshot at `location` into this database, which must be empty and at the
+    /// schema version of the snapshot. Returns the manifest entries.
+    pub async fn import_snapshot(
+        &self,
+        location: &SnapshotLocation,
+    ) -> Result<Vec<SnapshotTableRow>> {
+        let manifest = self
+            .base
+            .query(&format!(
+                "SELECT table, rows, chunks, schema_version FROM {}",
+                location.s3(MANIFEST, "JSONEachRow")
+            ))
+            .fetch_all::<SnapshotTableRow>()
+            .await?;
+        let Some(first) = manifest.first() else {
+            bail!("snapshot manifest at {} is empty", location.url);
+        };
+
+        let schema_version = self.schema_version().await?;
+        if first.schema_version != schema_version {
+            bail!(
+                "snapshot was exported at schema version {}, but this database is at {schema_version}",
+                first.schema_version
+            );
+        }
+        for entry in &manifest {
+            let existing = self.row_count(&entry.table).await?;
+            if existing > 0 {
+                bail!(
+                    "{} already has {existing} rows; import snapshots into a fresh database",
+                    entry.table
+                );
+            }
+        }
+
+        let views = self.view_definitions().await?;
+        for (view, _) in &views {
+            self.base.query(&format!("DETACH TABLE {}", self.table(view))).execute().await?;
+        }
+        let imported = self.import_tables(location, &manifest).await;
+        for (view, _) in &views {
+            self.base.query(&format!("ATTACH TABLE {}", self.table(view))).execute().await?;
+        }
+        imported?;
+
+        // Views are rebuilt in `VIEWS` order, so views reading other views see them complete
+        for (view, select) in &views {
+            let view = self.table(view);
+            self.base.query(&format!("TRUNCATE TABLE {view}")).execute().await?;
+            self.base.query(&format!("INSERT INTO {view} {select}")).execute().await?;
+            info!(%view, "Rebuilt materialized view");
+        }
+        Ok(manifest)
+    }
+
+    /// Load the chunks of every table of `manifest` and check their row counts.
+    async fn import_tables(
+        &self,
+        location: &SnapshotLocation,
+        manifest: &[SnapshotTableRow],
+    ) -> Result<()> {
+        for entry in manifest {
+            if entry.chunks > 0 {
+                let query = format!(
+                    "INSERT INTO {} SELECT * FROM {}",
+                    self.table(&entry.table),
+                    location.chunks(&entry.table, entry.chunks),
+                );
+                self.base.query(&query).execute().await?;
+            }
+            let imported = self.row_count(&entry.table).await?;
+            if imported != entry.rows {
+                bail!("imported {imported} rows into {}, snapshot has {}", entry.table, entry.rows);
+            }
+            info!(table = %entry.table, rows = entry.rows, "Imported table");
+        }
+        Ok(())
+    }
+
+    /// Defining query of every materialized view in `VIEWS` order. The queries are read
+    /// before the views are detached, as detached views are not listed in `system.tables`.
+    async fn view_definitions(&self) -> Result<Vec<(&'static str, String)>> {
+        let mut definitions = Vec::with_capacity(VIEWS.len());
+        for view in VIEWS {
+            let select = self
+                .base
+                .query("SELECT as_select FROM system.tables WHERE database = ? AND name = ?")
+                .bind(&self.db_name)
+                .bind(format!("{}{view}", self.table_prefix))
+                .fetch_one::<String>()
+                .await?;
+            definitions.push((*view, select));
+        }
+        Ok(definitions)
+    }
+
+    /// Whether `table` has a column named `column`
+    async fn has_column(&self, table: &str, column: &str) -> Result<bool> {
+        let columns = self
+            .base
+            .query(
+                "SELECT count() FROM system.columns WHERE database = ? AND table = ? AND name = ?",
+            )
+            .bind(&self.db_name)
+            .bind(format!("{}{table}", self.table_prefix))
+            .bind(column)
+            .fetch_one::<u64>()
+            .await?;
+        Ok(columns > 0)
+    }
+
+    /// Number of rows of `table`, including rows awaiting deduplication
+    async fn row_count(&self, table: &str) -> Result<u64> {
+        let query = format!("SELECT count() FROM {}", self.table(table));
+        Ok(self.base.query(&query).fetch_one::<u64>().await?)
+    }
+
+    /// Latest migration applied to the database
+    async fn schema_version(&self) -> Result<u32> {
+        let query = format!("SELECT max(version) FROM {}", self.table("schema_migrations"));
+        Ok(self.base.query(&query).fetch_one::<u32>().await?)
+    }
+}
+
+/// Condition keeping the rows inserted up to `snapshot_ms`, in milliseconds since the epoch
+fn snapshot_filter(snapshot_ms: u64) -> String {
+    format!("{INSERTED_AT} <= fromUnixTimestamp64Milli(toInt64({snapshot_ms}))")
+}
+
+/// Query writing `manifest` to the manifest object of `location`
+fn manifest_insert_query(location: &SnapshotLocation, manifest: &[SnapshotTableRow]) -> String {
+    let values: Vec<String> = manifest
+        .iter()
+        .map(|entry| {
+            format!(
+                "({}, {}, {}, {})",
+                quote(&entry.table),
+                entry.rows,
+                entry.chunks,
+                entry.schema_version
+            )
+        })
+        .collect();
+    format!(
+        "INSERT INTO FUNCTION {} \
+         SELECT * FROM values('table String, rows UInt64, chunks UInt64, schema_version UInt32', {}) \
+         SETTINGS s3_truncate_on_insert = 1",
+        location.s3(MANIFEST, "JSONEachRow"),
+        values.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(credentials: bool) -> SnapshotLocation {
+        SnapshotLocation {
+            url: "https://bucket.s3.amazonaws.com/snapshots/".to_owned(),
+            access_key_id: credentials.then(|| "key".to_owned()),
+            secret_access_key: credentials.then(|| "se'cret".to_owned()),
+            format: SnapshotFormat::Native,
+        }
+    }
+
+    #[test]
+    fn snapshot_excludes_deployment_tables() {
+        let tables: Vec<&str> = snapshot_tables().collect();
+        assert!(tables.contains(&"l2_head_events"));
+        assert!(!tables.contains(&"schema_migrations"));
+        assert!(!tables.contains(&"driver_leases"));
+        assert!(tables.iter().all(|table| !table.ends_with("_mv")));
+        assert_eq!(tables.len(), TABLES.len() - EXCLUDED_TABLES.len());
+    }
+
+    #[test]
+    fn export_is_bounded_by_snapshot_time() {
+        assert_eq!(
+            snapshot_filter(1_700_000_000_123),
+            "inserted_at <= fromUnixTimestamp64Milli(toInt64(1700000000123))"
+        );
+    }
+
+    #[test]
+    fn chunks_are_named_below_the_prefix() {
+        assert_eq!(
+            location(true).chunk("batches", 3),
+            "s3('https://bucket.s3.amazonaws.com/snapshots/batches/000003.native', 'key', \
+             'se\\'cret', 'Native')"
+        );
+        assert_eq!(
+            location(false).chunks("batches", 12),
+            "s3('https://bucket.s3.amazonaws.com/snapshots/batches/{000000..000011}.native', \
+             'Native')"
+        );
+        assert_eq!(
+            location(false).chunks("batches", 1),
+            "s3('https://bucket.s3.amazonaws.com/snapshots/batches/000000.native', 'Native')"
+        );
+    }
+
+    #[test]
+    fn chunk_count_rounds_up() {
+        assert_eq!(chunk_count(0, 1_000), 0);
+        assert_eq!(chunk_count(1, 1_000), 1);
+        assert_eq!(chunk_count(2_001, 1_000), 3);
+        assert_eq!(chunk_count(5, 0), 5);
+    }
+
+    #[test]
+    fn manifest_lists_every_table() {
+        let manifest = vec![
+            SnapshotTableRow {
+                table: "batches".to_owned(),
+                rows: 10,
+                chunks: 1,
+                schema_version: 54,
+            },
+            SnapshotTableRow {
+                table: "anomalies".to_owned(),
+                rows: 0,
+                chunks: 0,
+                schema_version: 54,
+            },
+        ];
+        let query = manifest_insert_query(&location(false), &manifest);
+        assert!(query.contains("snapshots/manifest.jsonl', 'JSONEachRow')"));
+        assert!(query.contains("('batches', 10, 1, 54), ('anomalies', 0, 0, 54)"));
+    }
+}
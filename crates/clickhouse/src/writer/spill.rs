@@ -0,0 +1,596 @@
+//! Local spill queue for failed inserts
+//!
+//! While `ClickHouse` is briefly unavailable, the rows of every failed write are appended to a
+//! local file, one JSON record per write, instead of being dropped. The rows are encoded straight
+//! from their row type, so 128-bit integers keep their full range. The replay task started by
+//! [`ClickhouseWriter::spawn_spill_replay_task`] writes them back oldest first once the server
+//! accepts inserts again, and mirrors them to the secondary cluster if one is set. Replayed
+//! records are removed from the file once per pass. The file is bounded; the oldest records are
+//! evicted to make room for new ones. Records left by a previous run are loaded on startup.
+
+use std::{
+    collections::VecDeque,
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use clickhouse::Row;
+use eyre::{Context, Result, eyre};
+use serde::{
+    Deserialize, Serialize,
+    de::{DeserializeOwned, IgnoredAny},
+};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use super::ClickhouseWriter;
+use crate::models::{
+    AddressLabelRow, AnchorMismatchRow, AnomalyRow, BackfillProgressInsertRow,
+    BatchBlobFeeInsertRow, BatchBlockRow, BatchRow, BondLedgerInsertRow, EthPriceInsertRow,
+    FeeReconciliationRow, ForcedInclusionProcessedRow, ForcedInclusionQueueRow,
+    HeaderPropagationInsertRow, IncidentLogRow, L1DataCostInsertRow, L1HeadEvent, L1TxIndexRow,
+    L2AnchorBlockInsertRow, L2HeadEvent, L2ReorgInsertRow, LeaderChangeInsertRow, MempoolStatsRow,
+    OperatorBalanceRow, OperatorHistoryRow, OrphanedBlockDetailsRow, OrphanedL2HashRow,
+    PipelineLatencyInsertRow, PreconfData, PreconfMismatchRow, ProposalInclusionRow,
+    ProtocolConfigRow, ProtocolGasSpendInsertRow, ProveCostInsertRow, ProvedBatchRow,
+    RpcHealthInsertRow, SequencerDowntimeRow, SequencerHandoverRow, SequencerScheduleRow,
+    SlashingEventRow, TaikoPriceInsertRow, VerificationBacklogRow, VerifiedBatchRow,
+    VerifyCostInsertRow,
+};
+
+/// Tables whose failed writes are never spilled: a replayed lease renewal would claim
+/// leadership for a holder that may have stopped long ago
+const UNSPILLED_TABLES: &[&str] = &["driver_leases"];
+
+/// Failed replays of the oldest record while the server is reachable, after which the record is
+/// dropped as rejected
+const MAX_REPLAY_ATTEMPTS: u32 = 5;
+
+/// Location and limits of the spill file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpillConfig {
+    /// File the rows of failed writes are appended to
+    pub path: PathBuf,
+    /// Size in bytes the file may grow to before the oldest records are evicted
+    pub max_bytes: u64,
+    /// Interval at which spilled rows are replayed
+    pub replay_interval: Duration,
+}
+
+/// Counters of the spill queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpillStats {
+    /// Rows spilled after a failed write
+    pub spilled_rows: u64,
+    /// Spilled rows written back
+    pub replayed_rows: u64,
+    /// Spilled rows evicted to keep the file within its size limit
+    pub evicted_rows: u64,
+    /// Spilled rows dropped because the server kept rejecting them
+    pub rejected_rows: u64,
+    /// Rows waiting to be replayed
+    pub pending_rows: u64,
+    /// Size of the spill file in bytes
+    pub pending_bytes: u64,
+}
+
+/// Rows of one failed write, as stored on disk
+#[derive(Debug, Serialize)]
+struct SpillRecord<'a, T> {
+    table: &'a str,
+    rows: &'a [T],
+}
+
+/// Table and row count of a stored record, read without decoding the rows
+#[derive(Debug, Deserialize)]
+struct SpillHeader {
+    table: String,
+    rows: Vec<IgnoredAny>,
+}
+
+/// Rows of a stored record, decoded into the row type of its table
+#[derive(Debug, Deserialize)]
+struct SpilledRows<T> {
+    rows: Vec<T>,
+}
+
+/// A record waiting to be replayed, with its encoded line
+#[derive(Debug)]
+struct Spilled {
+    id: u64,
+    table: String,
+    rows: u64,
+    line: String,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    records: VecDeque<Spilled>,
+    bytes: u64,
+    next_id: u64,
+    /// Failed replays of the oldest record
+    attempts: u32,
+    /// Whether records were removed since the file was last rewritten
+    stale: bool,
+}
+
+impl Queue {
+    fn push(&mut self, table: String, rows: u64, line: String) {
+        self.bytes += line.len() as u64;
+        self.records.push_back(Spilled { id: self.next_id, table, rows, line });
+        self.next_id += 1;
+    }
+
+    fn pop_front(&mut self) -> Option<Spilled> {
+        let spilled = self.records.pop_front()?;
+        self.bytes -= spilled.line.len() as u64;
+        self.attempts = 0;
+        Some(spilled)
+    }
+}
+
+/// Records of failed writes and their counters, shared between clones of a writer.
+#[derive(Debug)]
+pub(super) struct SpillQueue {
+    config: SpillConfig,
+    queue: Mutex<Queue>,
+    spilled_rows: AtomicU64,
+    replayed_rows: AtomicU64,
+    evicted_rows: AtomicU64,
+    rejected_rows: AtomicU64,
+}
+
+impl SpillQueue {
+    /// Open the spill file of `config`, loading the records left by a previous run.
+    pub(super) fn open(config: SpillConfig) -> Result<Self> {
+        let mut queue = Queue::default();
+        match std::fs::read_to_string(&config.path) {
+            Ok(contents) => {
+                for line in contents.lines().filter(|line| !line.is_empty()) {
+                    match serde_json::from_str::<SpillHeader>(line) {
+                        Ok(record) => {
+                            queue.push(record.table, record.rows.len() as u64, format!("{line}\n"))
+                        }
+                        Err(e) => {
+                            warn!(path = %config.path.display(), err = %e, "Skipping corrupt spill record")
+                        }
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).wrap_err_with(|| format!("reading {}", config.path.display()));
+            }
+        }
+        if let Some(dir) = config.path.parent() {
+            std::fs::create_dir_all(dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+        }
+
+        let spill = Self {
+            config,
+            queue: Mutex::new(queue),
+            spilled_rows: AtomicU64::new(0),
+            replayed_rows: AtomicU64::new(0),
+            evicted_rows: AtomicU64::new(0),
+            rejected_rows: AtomicU64::new(0),
+        };
+        // Drop corrupt lines and apply a lowered size limit
+        let mut queue = spill.lock();
+        spill.evict(&mut queue, 0);
+        spill.rewrite(&mut queue)?;
+        drop(queue);
+        Ok(spill)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Encode the `rows` of a failed write to `table` as a record line.
+    fn encode<T: Serialize>(table: &str, rows: &[T]) -> Result<String> {
+        Ok(format!("{}\n", serde_json::to_string(&SpillRecord { table, rows })?))
+    }
+
+    /// Append the `rows` of a failed write to `table`, evicting the oldest records if the file
+    /// would exceed its limit. Blocks on file I/O.
+    #[cfg(test)]
+    fn push<T: Serialize>(&self, table: &str, rows: &[T]) -> Result<()> {
+        self.push_line(table, rows.len() as u64, Self::encode(table, rows)?)
+    }
+
+    /// Append the encoded `line` holding `count` rows of `table`. Blocks on file I/O.
+    fn push_line(&self, table: &str, count: u64, line: String) -> Result<()> {
+        if line.len() as u64 > self.config.max_bytes {
+            self.evicted_rows.fetch_add(count, Ordering::Relaxed);
+            warn!(
+                table,
+                rows = count,
+                "Rows of failed write exceed the spill limit, dropping them"
+            );
+            return Ok(());
+        }
+
+        let mut queue = self.lock();
+        if self.evict(&mut queue, line.len() as u64) || queue.stale {
+            self.rewrite(&mut queue)?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.config.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .wrap_err_with(|| format!("appending to {}", self.config.path.display()))?;
+        queue.push(table.to_owned(), count, line);
+        self.spilled_rows.fetch_add(count, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Evict the oldest records until `incoming` more bytes fit. Returns whether any were
+    /// evicted.
+    fn evict(&self, queue: &mut Queue, incoming: u64) -> bool {
+        let mut evicted = false;
+        while queue.bytes + incoming > self.config.max_bytes &&
+            let Some(oldest) = queue.pop_front()
+        {
+            self.evicted_rows.fetch_add(oldest.rows, Ordering::Relaxed);
+            warn!(table = %oldest.table, rows = oldest.rows, "Evicted spilled rows");
+            evicted = true;
+        }
+        evicted
+    }
+
+    /// Replace the file with the records of `queue`.
+    fn rewrite(&self, queue: &mut Queue) -> Result<()> {
+        let path = &self.config.path;
+        let tmp = path.with_extension("tmp");
+        let contents: String = queue.records.iter().map(|r| r.line.as_str()).collect();
+        std::fs::write(&tmp, contents).wrap_err_with(|| format!("writing {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .wrap_err_with(|| format!("renaming {} into place", tmp.display()))?;
+        queue.stale = false;
+        Ok(())
+    }
+
+    /// Rewrite the file without the records removed since it was last written.
+    fn compact(&self) -> Result<()> {
+        let mut queue = self.lock();
+        if queue.stale { self.rewrite(&mut queue) } else { Ok(()) }
+    }
+
+    /// Id, table and encoded line of the oldest record.
+    fn front(&self) -> Option<(u64, String, String)> {
+        self.lock().records.front().map(|r| (r.id, r.table.clone(), r.line.clone()))
+    }
+
+    /// Remove record `id` once it was replayed, or rejected when `replayed` is false. A record
+    /// evicted meanwhile is left alone. The record stays in the file until [`Self::compact`].
+    fn remove(&self, id: u64, replayed: bool) {
+        let mut queue = self.lock();
+        if queue.records.front().is_none_or(|r| r.id != id) {
+            return;
+        }
+        if let Some(record) = queue.pop_front() {
+            let counter = if replayed { &self.replayed_rows } else { &self.rejected_rows };
+            counter.fetch_add(record.rows, Ordering::Relaxed);
+            queue.stale = true;
+        }
+    }
+
+    /// Count a failed replay of record `id`. Returns the failed attempts so far.
+    fn fail(&self, id: u64) -> u32 {
+        let mut queue = self.lock();
+        if queue.records.front().is_none_or(|r| r.id != id) {
+            return 0;
+        }
+        queue.attempts += 1;
+        queue.attempts
+    }
+
+    pub(super) fn stats(&self) -> SpillStats {
+        let queue = self.lock();
+        SpillStats {
+            spilled_rows: self.spilled_rows.load(Ordering::Relaxed),
+            replayed_rows: self.replayed_rows.load(Ordering::Relaxed),
+            evicted_rows: self.evicted_rows.load(Ordering::Relaxed),
+            rejected_rows: self.rejected_rows.load(Ordering::Relaxed),
+            pending_rows: queue.records.iter().map(|r| r.rows).sum(),
+            pending_bytes: queue.bytes,
+        }
+    }
+}
+
+/// Replay the rows of `$record`, decoding them into the row type of their table
+macro_rules! replay_as {
+    ($writer:expr, $table:expr, $line:expr, { $($name:literal => $row:ty),* $(,)? }) => {
+        match $table {
+            $($name => $writer.replay_rows::<$row>($name, $line).await,)*
+            other => Err(eyre!("no row type for spilled rows of {other}")),
+        }
+    };
+}
+
+impl ClickhouseWriter {
+    /// Spill the rows of failed writes to the file of `config` instead of failing the write,
+    /// and replay them with [`Self::spawn_spill_replay_task`].
+    pub fn with_spill(mut self, config: SpillConfig) -> Result<Self> {
+        self.spill = Some(Arc::new(SpillQueue::open(config)?));
+        Ok(self)
+    }
+
+    /// Counters of the spill queue. `None` when spilling is disabled.
+    pub fn spill_stats(&self) -> Option<SpillStats> {
+        self.spill.as_deref().map(SpillQueue::stats)
+    }
+
+    /// Spill the `rows` of a write to `table` that failed with `err`, returning `err` when
+    /// spilling is disabled, the table is never spilled or the rows cannot be spilled.
+    pub(super) async fn spill_or_fail<T: Serialize>(
+        &self,
+        table: &str,
+        rows: &[T],
+        err: eyre::Report,
+    ) -> Result<()> {
+        let Some(spill) = self.spill.clone().filter(|_| !UNSPILLED_TABLES.contains(&table)) else {
+            return Err(err);
+        };
+        let pushed = match SpillQueue::encode(table, rows) {
+            Ok(line) => {
+                // The file is appended to off the async workers
+                let (owned_table, count) = (table.to_owned(), rows.len() as u64);
+                tokio::task::spawn_blocking(move || spill.push_line(&owned_table, count, line))
+                    .await
+                    .map_err(eyre::Report::from)
+                    .and_then(|pushed| pushed)
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = pushed {
+            error!(table, err = %e, "Failed to spill rows of failed write");
+            return Err(err);
+        }
+        warn!(table, rows = rows.len(), err = %err, "Spilled rows of failed write");
+        Ok(())
+    }
+
+    /// Write spilled rows back, oldest first, until none are left or a write fails. Returns
+    /// the number of rows written.
+    #[allow(clippy::cognitive_complexity)]
+    pub async fn replay_spill(&self) -> Result<u64> {
+        let Some(spill) = self.spill.clone() else { return Ok(0) };
+        let mut replayed = 0;
+        while let Some((id, table, line)) = spill.front() {
+            match self.replay_record(&table, &line).await {
+                Ok(rows) => {
+                    spill.remove(id, true);
+                    replayed += rows;
+                }
+                // Still unavailable, retry at the next interval
+                Err(e) if self.ping().await.is_err() => {
+                    warn!(table, err = %e, "ClickHouse unavailable, postponing replay");
+                    break;
+                }
+                Err(e) if spill.fail(id) >= MAX_REPLAY_ATTEMPTS => {
+                    error!(table, err = %e, "ClickHouse keeps rejecting spilled rows, dropping them");
+                    spill.remove(id, false);
+                }
+                Err(e) => {
+                    warn!(table, err = %e, "Failed to replay spilled rows");
+                    break;
+                }
+            }
+        }
+        // Drop the removed records from the file once per pass, off the async workers
+        tokio::task::spawn_blocking(move || spill.compact()).await??;
+        Ok(replayed)
+    }
+
+    #[allow(clippy::cognitive_complexity)]
+    async fn replay_record(&self, table: &str, line: &str) -> Result<u64> {
+        replay_as!(self, table, line, {
+            "l1_head_events" => L1HeadEvent,
+            "l2_head_events" => L2HeadEvent,
+            "preconf_data" => PreconfData,
+            "pipeline_latency" => PipelineLatencyInsertRow,
+            "header_propagation" => HeaderPropagationInsertRow,
+            "l1_data_costs" => L1DataCostInsertRow,
+            "prove_costs" => ProveCostInsertRow,
+            "verify_costs" => VerifyCostInsertRow,
+            "batch_blob_fees" => BatchBlobFeeInsertRow,
+            "l2_anchor_blocks" => L2AnchorBlockInsertRow,
+            "rpc_health" => RpcHealthInsertRow,
+            "backfill_progress" => BackfillProgressInsertRow,
+            "eth_prices" => EthPriceInsertRow,
+            "taiko_prices" => TaikoPriceInsertRow,
+            "bond_ledger" => BondLedgerInsertRow,
+            "operator_balances" => OperatorBalanceRow,
+            "sequencer_downtime" => SequencerDowntimeRow,
+            "sequencer_schedule" => SequencerScheduleRow,
+            "sequencer_handovers" => SequencerHandoverRow,
+            "fee_reconciliations" => FeeReconciliationRow,
+            "preconf_mismatches" => PreconfMismatchRow,
+            "anchor_mismatches" => AnchorMismatchRow,
+            "mempool_stats" => MempoolStatsRow,
+            "proposal_inclusions" => ProposalInclusionRow,
+            "l1_tx_index" => L1TxIndexRow,
+            "orphaned_block_details" => OrphanedBlockDetailsRow,
+            "anomalies" => AnomalyRow,
+            "forced_inclusion_queue" => ForcedInclusionQueueRow,
+            "verification_backlog" => VerificationBacklogRow,
+            "address_labels" => AddressLabelRow,
+            "incident_log" => IncidentLogRow,
+            "operator_history" => OperatorHistoryRow,
+            "protocol_gas_spend" => ProtocolGasSpendInsertRow,
+            "protocol_config" => ProtocolConfigRow,
+            "batch_blocks" => BatchBlockRow,
+            "batches" => BatchRow,
+            "proved_batches" => ProvedBatchRow,
+            "forced_inclusion_processed" => ForcedInclusionProcessedRow,
+            "slashing_events" => SlashingEventRow,
+            "l2_reorgs" => L2ReorgInsertRow,
+            "verified_batches" => VerifiedBatchRow,
+            "orphaned_l2_hashes" => OrphanedL2HashRow,
+            "leader_changes" => LeaderChangeInsertRow,
+        })
+    }
+
+    /// Decode the spilled rows of `table` from `line` and write them to this cluster and the
+    /// secondary.
+    async fn replay_rows<T>(&self, table: &str, line: &str) -> Result<u64>
+    where
+        T: Row + Serialize + DeserializeOwned,
+    {
+        let SpilledRows::<T> { rows } = serde_json::from_str(line)?;
+        self.write_target(table, &rows).await?;
+        if let Some(secondary) = &self.secondary {
+            secondary.mirror(table, &rows).await;
+        }
+        Ok(rows.len() as u64)
+    }
+
+    /// Spawn a task that replays spilled rows at the configured interval. Returns `None` when
+    /// spilling is disabled.
+    pub fn spawn_spill_replay_task(&self) -> Option<JoinHandle<()>> {
+        let spill = Arc::clone(self.spill.as_ref()?);
+        let writer = self.clone();
+
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(spill.config.replay_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut last = SpillStats::default();
+
+            loop {
+                interval.tick().await;
+                match writer.replay_spill().await {
+                    Ok(0) => {}
+                    Ok(rows) => info!(rows, "Replayed spilled rows"),
+                    Err(e) => error!(err = %e, "Failed to replay spilled rows"),
+                }
+                // The counters are cumulative, so only log while rows wait or after they moved
+                let stats = spill.stats();
+                if stats.pending_rows > 0 || stats != last {
+                    info!(
+                        spilled_rows = stats.spilled_rows,
+                        replayed_rows = stats.replayed_rows,
+                        evicted_rows = stats.evicted_rows,
+                        rejected_rows = stats.rejected_rows,
+                        pending_rows = stats.pending_rows,
+                        pending_bytes = stats.pending_bytes,
+                        "Spill queue counters"
+                    );
+                }
+                last = stats;
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config(name: &str, max_bytes: u64) -> SpillConfig {
+        let dir =
+            std::env::temp_dir().join(format!("taikoscope-spill-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        SpillConfig {
+            path: dir.join("spill.jsonl"),
+            max_bytes,
+            replay_interval: Duration::from_secs(1),
+        }
+    }
+
+    fn eth_price(ts: u64) -> EthPriceInsertRow {
+        EthPriceInsertRow { ts, usd: 2_000.0 }
+    }
+
+    #[test]
+    fn spilled_rows_survive_reopening() {
+        let config = temp_config("reopen", 1 << 20);
+        let spill = SpillQueue::open(config.clone()).unwrap();
+        spill.push("eth_prices", &[eth_price(1), eth_price(2)]).unwrap();
+        spill.push("eth_prices", &[eth_price(3)]).unwrap();
+        assert_eq!(spill.stats().spilled_rows, 3);
+
+        let reopened = SpillQueue::open(config.clone()).unwrap();
+        let stats = reopened.stats();
+        assert_eq!(stats.pending_rows, 3);
+        assert_eq!(stats.pending_bytes, std::fs::metadata(&config.path).unwrap().len());
+
+        let (id, table, _) = reopened.front().unwrap();
+        assert_eq!(table, "eth_prices");
+        reopened.remove(id, true);
+        assert_eq!(reopened.stats().pending_rows, 1);
+        // Removed records stay in the file until it is compacted
+        assert_eq!(SpillQueue::open(config.clone()).unwrap().stats().pending_rows, 3);
+        reopened.compact().unwrap();
+        assert_eq!(SpillQueue::open(config.clone()).unwrap().stats().pending_rows, 1);
+        let _ = std::fs::remove_dir_all(config.path.parent().unwrap());
+    }
+
+    #[test]
+    fn evicts_oldest_records_beyond_the_limit() {
+        let probe = temp_config("probe", 1 << 20);
+        let line_bytes = {
+            let spill = SpillQueue::open(probe.clone()).unwrap();
+            spill.push("eth_prices", &[eth_price(1)]).unwrap();
+            spill.stats().pending_bytes
+        };
+        let _ = std::fs::remove_dir_all(probe.path.parent().unwrap());
+
+        let config = temp_config("evict", line_bytes * 2);
+        let spill = SpillQueue::open(config.clone()).unwrap();
+        for ts in 1..=3 {
+            spill.push("eth_prices", &[eth_price(ts)]).unwrap();
+        }
+        let stats = spill.stats();
+        assert_eq!((stats.spilled_rows, stats.evicted_rows, stats.pending_rows), (3, 1, 2));
+
+        let (_, _, line) = spill.front().unwrap();
+        let record: SpilledRows<EthPriceInsertRow> = serde_json::from_str(&line).unwrap();
+        assert_eq!(record.rows[0], eth_price(2));
+
+        // A write larger than the whole file is dropped rather than evicting everything
+        let oversized: Vec<_> = (4..=8).map(eth_price).collect();
+        spill.push("eth_prices", &oversized).unwrap();
+        assert_eq!(spill.stats().evicted_rows, 6);
+        assert_eq!(spill.stats().pending_rows, 2);
+        let _ = std::fs::remove_dir_all(config.path.parent().unwrap());
+    }
+
+    #[test]
+    fn skips_corrupt_records() {
+        let config = temp_config("corrupt", 1 << 20);
+        std::fs::create_dir_all(config.path.parent().unwrap()).unwrap();
+        std::fs::write(&config.path, "not json\n{\"table\":\"eth_prices\",\"rows\":[]}\n").unwrap();
+
+        let spill = SpillQueue::open(config.clone()).unwrap();
+        assert_eq!(spill.lock().records.len(), 1);
+        assert!(!std::fs::read_to_string(&config.path).unwrap().contains("not json"));
+        let _ = std::fs::remove_dir_all(config.path.parent().unwrap());
+    }
+
+    #[test]
+    fn keeps_the_full_range_of_wide_integers() {
+        #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+        struct WideRow {
+            fee: u128,
+            delta: i128,
+        }
+
+        let config = temp_config("wide", 1 << 20);
+        let spill = SpillQueue::open(config.clone()).unwrap();
+        let row = WideRow { fee: u128::MAX, delta: i128::MIN };
+        spill.push("fee_reconciliations", &[row]).unwrap();
+
+        let (_, _, line) = SpillQueue::open(config.clone()).unwrap().front().unwrap();
+        let record: SpilledRows<WideRow> = serde_json::from_str(&line).unwrap();
+        assert_eq!(record.rows, vec![WideRow { fee: u128::MAX, delta: i128::MIN }]);
+        let _ = std::fs::remove_dir_all(config.path.parent().unwrap());
+    }
+}
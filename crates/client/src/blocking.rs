@@ -0,0 +1,61 @@
+//! Synchronous client
+//!
+//! Runs the requests of an asynchronous [`Client`] on a runtime owned by the client, for
+//! services without an async executor. Its methods must not be called from within an async
+//! context.
+
+use std::future::Future;
+
+use eyre::Result;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{BatchBlocksQuery, BatchBlocksResponse, BlockStatusResponse, Client, TxLookupResponse};
+
+/// Blocking Taikoscope API client
+#[derive(Debug)]
+pub struct BlockingClient {
+    client: Client,
+    runtime: Runtime,
+}
+
+impl BlockingClient {
+    /// Create a client for the API served at `base_url`, see [`Client::new`].
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::from_client(Client::new(base_url)?)
+    }
+
+    /// Run the requests of `client` on a new single-threaded runtime.
+    pub fn from_client(client: Client) -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { client, runtime })
+    }
+
+    /// The asynchronous client requests are sent with.
+    pub const fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Run `future` to completion on the client's runtime.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Finality stage of an L2 block.
+    pub fn block_status(&self, block_number: u64) -> Result<BlockStatusResponse> {
+        self.block_on(self.client.block_status(block_number))
+    }
+
+    /// L2 blocks of a batch, highest block number first.
+    pub fn batch_blocks(
+        &self,
+        batch_id: u64,
+        query: &BatchBlocksQuery,
+    ) -> Result<BatchBlocksResponse> {
+        self.block_on(self.client.batch_blocks(batch_id, query))
+    }
+
+    /// Protocol roles of an indexed L1 transaction.
+    pub fn tx_lookup(&self, tx_hash: &str) -> Result<TxLookupResponse> {
+        self.block_on(self.client.tx_lookup(tx_hash))
+    }
+}
@@ -0,0 +1,410 @@
+//! Typed client for the Taikoscope API.
+//!
+//! Every endpoint has a method returning the response type from `api-types`, so consumers
+//! do not need to copy the struct definitions. Requests failing with a connection error, a
+//! timeout, `429 Too Many Requests` or a server error are retried with exponential backoff.
+//! Other errors are returned as an [`ErrorResponse`] that can be recovered with
+//! [`eyre::Report::downcast_ref`].
+//!
+//! Enable the `blocking` feature for a synchronous [`blocking::BlockingClient`].
+
+pub use api_types::*;
+
+mod query;
+pub use query::{
+    AnomalyQuery, BatchBlocksQuery, BatchEfficiencyQuery, BlockStatusSummaryQuery,
+    BondHistoryQuery, CommonQuery, CostPerTxQuery, CostQuery, IncidentHistoryQuery,
+    LeaderboardQuery, PaginatedQuery, QueryParams, TimeRange, UnifiedQuery,
+};
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+use std::time::Duration;
+
+use eyre::{Report, Result};
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+/// Backoff between retries of a failed request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for every following one
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between retries
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries.
+    pub const fn none() -> Self {
+        Self { max_retries: 0, initial_backoff: Duration::ZERO, max_backoff: Duration::ZERO }
+    }
+
+    /// Delay before retry number `attempt`, starting at 0.
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff.saturating_mul(2u32.saturating_pow(attempt)).min(self.max_backoff)
+    }
+}
+
+/// Whether a failed request is worth retrying.
+fn is_retryable(err: &Report) -> bool {
+    err.downcast_ref::<reqwest::Error>().is_some_and(|err| {
+        err.is_timeout() ||
+            err.is_connect() ||
+            err.status().is_some_and(|status| {
+                status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+            })
+    })
+}
+
+/// Asynchronous Taikoscope API client
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: Url,
+    retry: RetryPolicy,
+}
+
+impl Client {
+    /// Create a client for the API served at `base_url`, including the version prefix, e.g.
+    /// `https://api.example.com/v1`.
+    pub fn new(base_url: &str) -> Result<Self> {
+        let mut base_url = Url::parse(base_url)?;
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+        Ok(Self { http: reqwest::Client::new(), base_url, retry: RetryPolicy::default() })
+    }
+
+    /// Send requests with `http`, e.g. to configure timeouts or default headers.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Retry failed requests according to `retry`.
+    pub const fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Finality stage of an L2 block.
+    pub async fn block_status(&self, block_number: u64) -> Result<BlockStatusResponse> {
+        self.get(&format!("block-status/{block_number}"), &()).await
+    }
+
+    /// L2 blocks of a batch, highest block number first.
+    pub async fn batch_blocks(
+        &self,
+        batch_id: u64,
+        query: &BatchBlocksQuery,
+    ) -> Result<BatchBlocksResponse> {
+        self.get(&format!("batch/{batch_id}/blocks"), query).await
+    }
+
+    /// Protocol roles of an indexed L1 transaction.
+    pub async fn tx_lookup(&self, tx_hash: &str) -> Result<TxLookupResponse> {
+        self.get(&format!("tx/{tx_hash}"), &()).await
+    }
+
+    /// GET `path` with `query`, retrying according to the retry policy.
+    async fn get<T: DeserializeOwned>(&self, path: &str, query: &dyn QueryParams) -> Result<T> {
+        let url = self.base_url.join(path)?;
+        let mut query = query.pairs();
+        // Responses decode into the endpoint specific types, which paginated endpoints only
+        // return outside of the pagination envelope
+        query.push(("envelope", "false".to_owned()));
+        let mut attempt = 0;
+        loop {
+            match self.fetch(&url, &query).await {
+                Err(err) if attempt < self.retry.max_retries && is_retryable(&err) => {
+                    tokio::time::sleep(self.retry.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Send a single request and decode the response.
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        url: &Url,
+        query: &[(&'static str, String)],
+    ) -> Result<T> {
+        let response = self.http.get(url.clone()).query(query).send().await?;
+        let status = response.status();
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            response.error_for_status_ref()?;
+        }
+        if !status.is_success() {
+            let error = response.json::<ErrorResponse>().await.unwrap_or_else(|_| ErrorResponse {
+                r#type: ErrorCode::Unknown,
+                title: status.canonical_reason().unwrap_or("Unexpected status").to_owned(),
+                status: status.as_u16(),
+                detail: String::new(),
+            });
+            return Err(error.into());
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// Generate the async method of every endpoint and its blocking counterpart. The `@query`
+/// arms come first so that they are not mistaken for an endpoint list.
+macro_rules! endpoints {
+    (@query) => { &() };
+    (@query $arg:ident) => { $arg };
+    ($(
+        $(#[$doc:meta])*
+        $name:ident($($arg:ident: $query:ty)?) -> $response:ty = $path:literal;
+    )*) => {
+        impl Client {
+            $(
+                $(#[$doc])*
+                pub async fn $name(&self $(, $arg: &$query)?) -> Result<$response> {
+                    self.get($path, endpoints!(@query $($arg)?)).await
+                }
+            )*
+        }
+
+        #[cfg(feature = "blocking")]
+        impl blocking::BlockingClient {
+            $(
+                $(#[$doc])*
+                pub fn $name(&self $(, $arg: &$query)?) -> Result<$response> {
+                    self.block_on(self.client().$name($($arg)?))
+                }
+            )*
+        }
+    };
+}
+
+endpoints! {
+    /// Number of the most recent L2 block.
+    l2_head_block() -> L2HeadBlockResponse = "l2-head-block";
+    /// Number of the most recent L1 block.
+    l1_head_block() -> L1HeadBlockResponse = "l1-head-block";
+    /// Latest preconfirmation operator data.
+    preconf_data() -> PreconfDataResponse = "preconf-data";
+    /// Latest `TaikoInbox` protocol configuration.
+    protocol_config() -> ProtocolConfigResponse = "protocol-config";
+    /// Current ETH price.
+    eth_price() -> EthPriceResponse = "eth-price";
+    /// Number of recent L2 blocks per finality stage.
+    block_status_summary(query: BlockStatusSummaryQuery) -> BlockStatusSummaryResponse =
+        "block-status-summary";
+    /// L2 reorg events.
+    reorgs(query: PaginatedQuery) -> ReorgEventsResponse = "reorgs";
+    /// Validator slashing events.
+    slashings(query: CommonQuery) -> SlashingEventsResponse = "slashings";
+    /// Soft blocks that differed from their canonical block.
+    preconf_mismatches(query: CommonQuery) -> PreconfMismatchesResponse = "preconf-mismatches";
+    /// Batches anchored to an L1 block hash that differs from the indexed block.
+    anchor_mismatches(query: CommonQuery) -> AnchorMismatchesResponse = "anchor-mismatches";
+    /// Processed forced inclusions.
+    forced_inclusions(query: CommonQuery) -> ForcedInclusionEventsResponse = "forced-inclusions";
+    /// Failed batch proposals.
+    failed_proposals(query: PaginatedQuery) -> FailedProposalEventsResponse = "failed-proposals";
+    /// Time between consecutive batch proposals.
+    batch_posting_times(query: PaginatedQuery) -> BatchPostingTimesResponse =
+        "batch-posting-times";
+    /// Blobs used per batch.
+    blobs_per_batch(query: UnifiedQuery) -> BatchBlobsResponse = "blobs-per-batch";
+    /// Time from proposal to proof per batch.
+    prove_times(query: UnifiedQuery) -> ProveTimesResponse = "prove-times";
+    /// Time from proof to verification per batch.
+    verify_times(query: UnifiedQuery) -> VerifyTimesResponse = "verify-times";
+    /// Time between L1 blocks.
+    l1_block_times(query: CommonQuery) -> L1BlockTimesResponse = "l1-block-times";
+    /// Time between L2 blocks.
+    l2_block_times(query: UnifiedQuery) -> L2BlockTimesResponse = "l2-block-times";
+    /// Gas used per L2 block.
+    l2_gas_used(query: UnifiedQuery) -> L2GasUsedResponse = "l2-gas-used";
+    /// L2 transactions per second.
+    l2_tps(query: UnifiedQuery) -> L2TpsResponse = "l2-tps";
+    /// Transactions per L2 block.
+    block_transactions(query: UnifiedQuery) -> BlockTransactionsResponse = "block-transactions";
+    /// L1 data posting cost per batch.
+    l1_data_cost(query: PaginatedQuery) -> L1DataCostResponse = "l1-data-cost";
+    /// Prover cost per batch.
+    prove_cost(query: PaginatedQuery) -> ProveCostResponse = "prove-cost";
+    /// L1 blob fee market state per batch.
+    blob_fee_history(query: PaginatedQuery) -> BlobFeeHistoryResponse = "blob-fee-history";
+    /// Blocks produced per sequencer.
+    sequencer_distribution(query: CommonQuery) -> SequencerDistributionResponse =
+        "sequencer-distribution";
+    /// Share of L2 blocks built by each builder.
+    builder_distribution(query: CommonQuery) -> BuilderDistributionResponse =
+        "builder-distribution";
+    /// Blocks produced by each sequencer.
+    sequencer_blocks(query: CommonQuery) -> SequencerBlocksResponse = "sequencer-blocks";
+    /// Combined L2 fees and batch cost components.
+    l2_fees_components(query: CommonQuery) -> L2FeesComponentsResponse = "l2-fees-components";
+    /// Aggregated dashboard data.
+    dashboard_data(query: CommonQuery) -> DashboardDataResponse = "dashboard-data";
+    /// Aggregated prover costs per proposer.
+    prove_costs(query: CostQuery) -> ProposerCostsResponse = "prove-costs";
+    /// Unresolved batch data-quality anomalies.
+    data_quality() -> DataQualityResponse = "data-quality";
+    /// Top blocks, slowest proofs and sequencer rankings.
+    leaderboards(query: LeaderboardQuery) -> LeaderboardsResponse = "leaderboards";
+    /// L1 to L2 anchor lag distribution over time.
+    anchor_lag(query: CommonQuery) -> AnchorLagResponse = "anchor-lag";
+    /// L2 block gas saturation distribution over time.
+    gas_saturation(query: CommonQuery) -> GasSaturationResponse = "gas-saturation";
+    /// Blob utilization, blocks per batch and transactions per batch.
+    batch_efficiency(query: BatchEfficiencyQuery) -> BatchEfficiencyResponse = "batch-efficiency";
+    /// Operating cost per L2 transaction over time and per sequencer.
+    cost_per_tx(query: CostPerTxQuery) -> CostPerTxResponse = "cost-per-tx";
+    /// L1 gas spent on protocol transactions per operation and address.
+    protocol_gas(query: CommonQuery) -> ProtocolGasResponse = "protocol-gas";
+    /// Daily batch prove time percentiles.
+    prove_time_percentiles(query: CommonQuery) -> TimePercentilesResponse =
+        "prove-time-percentiles";
+    /// Batch prove times per proof type.
+    prove_times_by_proof_type(query: CommonQuery) -> ProofTypeProveTimesResponse =
+        "prove-times-by-proof-type";
+    /// Daily batch verify time percentiles.
+    verify_time_percentiles(query: CommonQuery) -> TimePercentilesResponse =
+        "verify-time-percentiles";
+    /// Daily uptime of every scheduled sequencer.
+    sequencer_uptime(query: CommonQuery) -> SequencerUptimeResponse = "sequencer-uptime";
+    /// Per-minute L2 mempool depth and inclusion latency.
+    mempool_stats(query: CommonQuery) -> MempoolStatsResponse = "mempool-stats";
+    /// Sudden changes of TPS, gas used, batch cadence and prove times.
+    anomalies(query: AnomalyQuery) -> AnomaliesResponse = "anomalies";
+    /// Latency, block lag and error rate of the public RPC endpoints.
+    rpc_status(query: CommonQuery) -> RpcStatusResponse = "rpc-status";
+    /// End-to-end ingestion latency per event type.
+    pipeline_latency(query: CommonQuery) -> PipelineLatencyResponse = "pipeline-latency";
+    /// Delay between block timestamps and header receipt per chain.
+    propagation_delay(query: CommonQuery) -> PropagationDelayResponse = "propagation-delay";
+    /// Latest gap backfill progress per chain.
+    backfill_status() -> BackfillStatusResponse = "backfill-status";
+    /// Changes of the driver instance ingesting events.
+    leader_changes(query: CommonQuery) -> LeaderChangesResponse = "leader-changes";
+    /// Latest inbox bond balance per account.
+    bond_balances() -> BondBalancesResponse = "bond-balances";
+    /// Latest inbox bond balance changes of an account.
+    bond_history(query: BondHistoryQuery) -> BondHistoryResponse = "bond-history";
+    /// Latest L1 wallet balances of the whitelisted operators.
+    operator_balances() -> OperatorBalancesResponse = "operator-balances";
+    /// Preconfirmation operators of the current, next and past L1 epochs.
+    operator_schedule(query: CommonQuery) -> OperatorScheduleResponse = "operator-schedule";
+    /// Latest and historical depth of the forced inclusion queue.
+    forced_inclusion_queue(query: CommonQuery) -> ForcedInclusionQueueResponse = "forced-inclusion-queue";
+    /// Latest and historical number of proposed batches waiting for verification.
+    verification_backlog(query: CommonQuery) -> VerificationBacklogResponse = "verification-backlog";
+    /// Time batch proposals spent in the L1 mempool before inclusion.
+    proposal_inclusion_delay(query: CommonQuery) -> ProposalInclusionDelayResponse = "proposal-inclusion-delay";
+    /// Gaps between the outgoing and incoming sequencer at operator handovers.
+    handover_latency(query: CommonQuery) -> HandoverLatencyResponse = "handover-latency";
+    /// Incident operations performed by the monitors against Instatus.
+    incident_history(query: IncidentHistoryQuery) -> IncidentHistoryResponse = "incident-history";
+    /// Reorg depth histogram, orphan rate and reorg frequency.
+    reorg_stats(query: CommonQuery) -> ReorgStatsResponse = "reorg-stats";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+
+    fn client(server: &Server) -> Client {
+        let retry = RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        };
+        Client::new(&format!("{}/v1", server.url())).unwrap().with_retry_policy(retry)
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let retry = RetryPolicy::default();
+        assert_eq!(retry.backoff(0), Duration::from_millis(200));
+        assert_eq!(retry.backoff(2), Duration::from_millis(800));
+        assert_eq!(retry.backoff(10), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn decodes_response_with_query() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1/l2-tps")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("limit".into(), "10".into()),
+                Matcher::UrlEncoded("aggregated".into(), "true".into()),
+                Matcher::UrlEncoded("envelope".into(), "false".into()),
+            ]))
+            .with_body(serde_json::json!({ "blocks": [] }).to_string())
+            .create_async()
+            .await;
+
+        let query = UnifiedQuery {
+            page: PaginatedQuery { limit: Some(10), ..Default::default() },
+            aggregated: true,
+            ..Default::default()
+        };
+        let response = client(&server).l2_tps(&query).await.unwrap();
+
+        assert!(response.blocks.is_empty());
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1/l2-head-block")
+            .match_query(Matcher::Any)
+            .with_status(503)
+            .expect(3)
+            .create_async()
+            .await;
+
+        assert!(client(&server).l2_head_block().await.is_err());
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn returns_api_errors_without_retrying() {
+        let mut server = Server::new_async().await;
+        let body = serde_json::json!({
+            "type": "not_found",
+            "title": "Not found",
+            "status": 404,
+            "detail": "block 7 not found",
+        });
+        let mock = server
+            .mock("GET", "/v1/block-status/7")
+            .match_query(Matcher::Any)
+            .with_status(404)
+            .with_body(body.to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let err = client(&server).block_status(7).await.unwrap_err();
+        let error = err.downcast_ref::<ErrorResponse>().unwrap();
+        assert_eq!(error.r#type, ErrorCode::NotFound);
+        assert_eq!(error.status, 404);
+        assert_eq!(error.detail, "block 7 not found");
+        mock.assert_async().await;
+    }
+}
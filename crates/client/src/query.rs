@@ -0,0 +1,355 @@
+//! Query parameters accepted by the API endpoints
+
+use api_types::TimeRangePreset;
+
+/// Parameters encoded into the query string of a request
+pub trait QueryParams {
+    /// Key-value pairs of the parameters that are set.
+    fn pairs(&self) -> Vec<(&'static str, String)>;
+}
+
+/// Endpoints without parameters
+impl QueryParams for () {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+/// Push `value` under `key` if it is set.
+fn push<T: ToString>(pairs: &mut Vec<(&'static str, String)>, key: &'static str, value: Option<T>) {
+    if let Some(value) = value {
+        pairs.push((key, value.to_string()));
+    }
+}
+
+/// Time range filter, timestamps in milliseconds. A preset cannot be combined with timestamps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    /// Named range ending at the time of the request
+    pub range: Option<TimeRangePreset>,
+    /// Only include items created after this time (exclusive)
+    pub created_gt: Option<u64>,
+    /// Only include items created at or after this time
+    pub created_gte: Option<u64>,
+    /// Only include items created before this time (exclusive)
+    pub created_lt: Option<u64>,
+    /// Only include items created at or before this time
+    pub created_lte: Option<u64>,
+}
+
+impl TimeRange {
+    /// Items created within the last `millis` milliseconds before `now_millis`.
+    pub const fn last(now_millis: u64, millis: u64) -> Self {
+        Self {
+            range: None,
+            created_gt: None,
+            created_gte: Some(now_millis.saturating_sub(millis)),
+            created_lt: None,
+            created_lte: None,
+        }
+    }
+
+    /// Items created within the named range ending at the time of the request.
+    pub const fn preset(range: TimeRangePreset) -> Self {
+        Self {
+            range: Some(range),
+            created_gt: None,
+            created_gte: None,
+            created_lt: None,
+            created_lte: None,
+        }
+    }
+}
+
+impl QueryParams for TimeRange {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        push(&mut pairs, "range", self.range.map(TimeRangePreset::as_str));
+        push(&mut pairs, "created[gt]", self.created_gt);
+        push(&mut pairs, "created[gte]", self.created_gte);
+        push(&mut pairs, "created[lt]", self.created_lt);
+        push(&mut pairs, "created[lte]", self.created_lte);
+        pairs
+    }
+}
+
+/// Filters shared by most endpoints
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommonQuery {
+    /// Only include items of this address
+    pub address: Option<String>,
+    /// Time range filter
+    pub time_range: TimeRange,
+}
+
+impl QueryParams for CommonQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        push(&mut pairs, "address", self.address.as_deref());
+        pairs.extend(self.time_range.pairs());
+        pairs
+    }
+}
+
+/// Filters and pagination of table endpoints
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaginatedQuery {
+    /// Shared filters
+    pub common: CommonQuery,
+    /// Maximum number of items to return
+    pub limit: Option<u64>,
+    /// Return items after this cursor (exclusive)
+    pub starting_after: Option<u64>,
+    /// Return items before this cursor (exclusive)
+    pub ending_before: Option<u64>,
+    /// Cursor taken from `next_cursor` or `prev_cursor` of a previous response
+    pub cursor: Option<String>,
+}
+
+impl QueryParams for PaginatedQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = self.common.pairs();
+        push(&mut pairs, "limit", self.limit);
+        push(&mut pairs, "starting_after", self.starting_after);
+        push(&mut pairs, "ending_before", self.ending_before);
+        push(&mut pairs, "cursor", self.cursor.as_deref());
+        pairs
+    }
+}
+
+/// Pagination of the blocks of a batch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchBlocksQuery {
+    /// Maximum number of blocks to return
+    pub limit: Option<u64>,
+    /// Return blocks below this block number (exclusive)
+    pub starting_after: Option<u64>,
+    /// Return blocks above this block number (exclusive)
+    pub ending_before: Option<u64>,
+    /// Cursor taken from `next_cursor` or `prev_cursor` of a previous response
+    pub cursor: Option<String>,
+}
+
+impl QueryParams for BatchBlocksQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        push(&mut pairs, "limit", self.limit);
+        push(&mut pairs, "starting_after", self.starting_after);
+        push(&mut pairs, "ending_before", self.ending_before);
+        push(&mut pairs, "cursor", self.cursor.as_deref());
+        pairs
+    }
+}
+
+/// Query of endpoints returning either a page of items or aggregated buckets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnifiedQuery {
+    /// Filters and pagination, ignored in aggregated mode except for the filters
+    pub page: PaginatedQuery,
+    /// Return aggregated buckets instead of individual items
+    pub aggregated: bool,
+    /// Bucket aggregated items by `block` (default) or wallclock `time`
+    pub bucket_by: Option<String>,
+}
+
+impl QueryParams for UnifiedQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = self.page.pairs();
+        if self.aggregated {
+            pairs.push(("aggregated", "true".to_owned()));
+        }
+        push(&mut pairs, "bucket_by", self.bucket_by.as_deref());
+        pairs
+    }
+}
+
+/// Query of the aggregated cost endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CostQuery {
+    /// Shared filters
+    pub common: CommonQuery,
+    /// Unit costs are reported in, `gwei` (default) or `usd`
+    pub denomination: Option<String>,
+}
+
+impl QueryParams for CostQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = self.common.pairs();
+        push(&mut pairs, "denomination", self.denomination.as_deref());
+        pairs
+    }
+}
+
+/// Query of the batch efficiency endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchEfficiencyQuery {
+    /// Shared filters
+    pub common: CommonQuery,
+    /// Only include batches posted in this mode, `blob` or `calldata`
+    pub da_mode: Option<String>,
+}
+
+impl QueryParams for BatchEfficiencyQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = self.common.pairs();
+        push(&mut pairs, "da_mode", self.da_mode.as_deref());
+        pairs
+    }
+}
+
+/// Query of the anomalies endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnomalyQuery {
+    /// Shared filters
+    pub common: CommonQuery,
+    /// Only include anomalies of this metric, `tps`, `gas_used`, `batch_cadence` or `prove_time`
+    pub metric: Option<String>,
+}
+
+impl QueryParams for AnomalyQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = self.common.pairs();
+        push(&mut pairs, "metric", self.metric.as_deref());
+        pairs
+    }
+}
+
+/// Query of the incident history endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IncidentHistoryQuery {
+    /// Shared filters
+    pub common: CommonQuery,
+    /// Only include operations on incidents affecting this Instatus component
+    pub component_id: Option<String>,
+    /// Only include operations of this kind, `create`, `update` or `resolve`
+    pub action: Option<String>,
+}
+
+impl QueryParams for IncidentHistoryQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = self.common.pairs();
+        push(&mut pairs, "component_id", self.component_id.as_deref());
+        push(&mut pairs, "action", self.action.as_deref());
+        pairs
+    }
+}
+
+/// Query of the leaderboards endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LeaderboardQuery {
+    /// Time range filter
+    pub time_range: TimeRange,
+    /// Number of entries returned per leaderboard
+    pub limit: Option<u64>,
+}
+
+impl QueryParams for LeaderboardQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = self.time_range.pairs();
+        push(&mut pairs, "limit", self.limit);
+        pairs
+    }
+}
+
+/// Query of the cost per transaction endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostPerTxQuery {
+    /// Time range filter
+    pub time_range: TimeRange,
+    /// Monthly hardware cost of one sequencer in USD, the server's estimate when unset
+    pub hardware_cost_usd: Option<f64>,
+}
+
+impl QueryParams for CostPerTxQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = self.time_range.pairs();
+        push(&mut pairs, "hardware_cost_usd", self.hardware_cost_usd);
+        pairs
+    }
+}
+
+/// Query of the block status summary endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockStatusSummaryQuery {
+    /// Number of most recent L2 blocks to summarise
+    pub limit: Option<u64>,
+}
+
+impl QueryParams for BlockStatusSummaryQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        push(&mut pairs, "limit", self.limit);
+        pairs
+    }
+}
+
+/// Query of the bond history endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BondHistoryQuery {
+    /// Account whose bond ledger entries are returned
+    pub address: String,
+    /// Number of most recent ledger entries to return
+    pub limit: Option<u64>,
+}
+
+impl QueryParams for BondHistoryQuery {
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![("address", self.address.clone())];
+        push(&mut pairs, "limit", self.limit);
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preset_time_range_pairs() {
+        let range = TimeRange::preset(TimeRangePreset::Last24Hours);
+        assert_eq!(range.pairs(), vec![("range", "24h".to_owned())]);
+    }
+
+    #[test]
+    fn batch_blocks_query_pairs() {
+        let query = BatchBlocksQuery {
+            limit: Some(100),
+            cursor: Some("next".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(query.pairs(), vec![("limit", "100".to_owned()), ("cursor", "next".to_owned())]);
+    }
+
+    #[test]
+    fn unset_parameters_are_omitted() {
+        assert!(UnifiedQuery::default().pairs().is_empty());
+    }
+
+    #[test]
+    fn unified_query_pairs() {
+        let query = UnifiedQuery {
+            page: PaginatedQuery {
+                common: CommonQuery {
+                    address: Some("0xabc".to_owned()),
+                    time_range: TimeRange::last(10_000, 3_000),
+                },
+                limit: Some(50),
+                cursor: Some("next".to_owned()),
+                ..Default::default()
+            },
+            aggregated: true,
+            bucket_by: Some("time".to_owned()),
+        };
+        assert_eq!(
+            query.pairs(),
+            vec![
+                ("address", "0xabc".to_owned()),
+                ("created[gte]", "7000".to_owned()),
+                ("limit", "50".to_owned()),
+                ("cursor", "next".to_owned()),
+                ("aggregated", "true".to_owned()),
+                ("bucket_by", "time".to_owned()),
+            ]
+        );
+    }
+}
@@ -2,7 +2,8 @@
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
 use alloy_primitives::Address;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use std::path::PathBuf;
 use url::Url;
 
 /// Default origins allowed to access the API.
@@ -22,20 +23,227 @@ pub struct ClickhouseOpts {
     /// Clickhouse password
     #[clap(long, env = "CLICKHOUSE_PASSWORD")]
     pub password: String,
+    /// Username of the read-only user the API server and the indexer's readers query with
+    /// (default: `CLICKHOUSE_USERNAME`). Its settings profile should set `readonly = 2`.
+    #[clap(long = "clickhouse-read-username", env = "CLICKHOUSE_READ_USERNAME")]
+    pub read_username: Option<String>,
+    /// Password of the read-only user (default: `CLICKHOUSE_PASSWORD`)
+    #[clap(long = "clickhouse-read-password", env = "CLICKHOUSE_READ_PASSWORD")]
+    pub read_password: Option<String>,
+    /// Username of the user the indexer writes and migrates with (default:
+    /// `CLICKHOUSE_USERNAME`). The API server refuses to start when write credentials are set.
+    #[clap(long = "clickhouse-write-username", env = "CLICKHOUSE_WRITE_USERNAME")]
+    pub write_username: Option<String>,
+    /// Password of the write user (default: `CLICKHOUSE_PASSWORD`)
+    #[clap(long = "clickhouse-write-password", env = "CLICKHOUSE_WRITE_PASSWORD")]
+    pub write_password: Option<String>,
+    /// Refuse to start the API server when its user is not read-only, instead of warning
+    #[clap(
+        long = "clickhouse-enforce-read-only",
+        env = "CLICKHOUSE_ENFORCE_READ_ONLY",
+        default_value = "false"
+    )]
+    pub enforce_read_only: bool,
+    /// Prefix prepended to every table and view name, so that several deployments (e.g.
+    /// staging and production) can share one database
+    #[clap(
+        long = "clickhouse-table-prefix",
+        env = "CLICKHOUSE_TABLE_PREFIX",
+        default_value = "",
+        value_parser = parse_table_prefix
+    )]
+    pub table_prefix: String,
+    /// Read replica URLs the API server spreads queries over in addition to the primary,
+    /// comma-separated. The indexer always writes to and reads from the primary.
+    #[clap(
+        long = "clickhouse-replica-urls",
+        env = "CLICKHOUSE_REPLICA_URLS",
+        value_delimiter = ','
+    )]
+    pub replica_urls: Vec<Url>,
+    /// Always route identical aggregate queries to the same replica
+    #[clap(
+        long = "clickhouse-sticky-aggregates",
+        env = "CLICKHOUSE_STICKY_AGGREGATES",
+        default_value = "false"
+    )]
+    pub sticky_aggregates: bool,
+    /// Seconds after which an API query is abandoned and cancelled on the server (0 = no limit)
+    #[clap(
+        long = "clickhouse-query-timeout-secs",
+        env = "CLICKHOUSE_QUERY_TIMEOUT_SECS",
+        default_value = "30"
+    )]
+    pub query_timeout_secs: u64,
+    /// Maximum number of API queries in flight at once (0 = unlimited). Requests that cannot
+    /// get a slot are rejected with 503.
+    #[clap(
+        long = "clickhouse-max-concurrent-queries",
+        env = "CLICKHOUSE_MAX_CONCURRENT_QUERIES",
+        default_value = "64"
+    )]
+    pub max_concurrent_queries: usize,
+    /// Secondary cluster the indexer mirrors every write to, e.g. while migrating to a new
+    /// cluster. Migrations are applied to it as well.
+    #[clap(long = "clickhouse-secondary-url", env = "CLICKHOUSE_SECONDARY_URL")]
+    pub secondary_url: Option<Url>,
+    /// Database on the secondary cluster (default: same as the primary)
+    #[clap(long = "clickhouse-secondary-db", env = "CLICKHOUSE_SECONDARY_DB")]
+    pub secondary_db: Option<String>,
+    /// Username on the secondary cluster (default: same as the primary)
+    #[clap(long = "clickhouse-secondary-username", env = "CLICKHOUSE_SECONDARY_USERNAME")]
+    pub secondary_username: Option<String>,
+    /// Password on the secondary cluster (default: same as the primary)
+    #[clap(long = "clickhouse-secondary-password", env = "CLICKHOUSE_SECONDARY_PASSWORD")]
+    pub secondary_password: Option<String>,
+}
+
+impl ClickhouseOpts {
+    /// Username and password to read with
+    pub fn read_credentials(&self) -> (String, String) {
+        (
+            self.read_username.clone().unwrap_or_else(|| self.username.clone()),
+            self.read_password.clone().unwrap_or_else(|| self.password.clone()),
+        )
+    }
+
+    /// Username and password to write and apply migrations with
+    pub fn write_credentials(&self) -> (String, String) {
+        (
+            self.write_username.clone().unwrap_or_else(|| self.username.clone()),
+            self.write_password.clone().unwrap_or_else(|| self.password.clone()),
+        )
+    }
+
+    /// Whether dedicated write credentials are configured
+    pub const fn has_write_credentials(&self) -> bool {
+        self.write_username.is_some() || self.write_password.is_some()
+    }
+}
+
+/// Validate a table prefix: only lowercase ASCII letters, digits and underscores are allowed.
+fn parse_table_prefix(s: &str) -> Result<String, String> {
+    if s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+        Ok(s.to_owned())
+    } else {
+        Err(format!("invalid table prefix '{}': only [a-z0-9_] are allowed", s))
+    }
+}
+
+/// Parse an admin token, ignoring surrounding whitespace. Blank tokens are rejected.
+fn parse_admin_token(s: &str) -> Result<String, String> {
+    let token = s.trim();
+    if token.is_empty() {
+        return Err("admin token must not be blank".to_owned());
+    }
+    Ok(token.to_owned())
+}
+
+/// Parse a `path=weight` rate limit route weight.
+fn parse_route_weight(s: &str) -> Result<(String, u64), String> {
+    let (path, weight) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid route weight '{}': expected path=weight", s))?;
+    if !path.starts_with('/') {
+        return Err(format!("invalid route weight '{}': path must start with '/'", s));
+    }
+    let weight =
+        weight.parse::<u64>().map_err(|e| format!("invalid route weight '{}': {}", s, e))?;
+    Ok((path.to_owned(), weight))
+}
+
+/// HTTP methods cross-origin requests may use
+const CORS_METHODS: &[&str] = &["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE"];
+
+/// Parse an HTTP method allowed in cross-origin requests.
+fn parse_cors_method(s: &str) -> Result<String, String> {
+    let method = s.trim().to_ascii_uppercase();
+    if !CORS_METHODS.contains(&method.as_str()) {
+        return Err(format!("invalid CORS method '{}': expected one of {:?}", s, CORS_METHODS));
+    }
+    Ok(method)
+}
+
+/// Extractor event streams whose channel policy can be configured
+pub const EVENT_STREAMS: &[&str] = &[
+    "l1_headers",
+    "l2_headers",
+    "pending_txs",
+    "pending_proposals",
+    "batch_proposed",
+    "batches_proved",
+    "batches_verified",
+    "forced_inclusions",
+    "operator_removed",
+];
+
+/// What an event stream does when its channel is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OverflowPolicy {
+    /// Wait until the consumer makes room, pausing the subscription
+    #[default]
+    Block,
+    /// Drop the oldest queued event to make room for the new one
+    DropOldest,
+}
+
+/// Parse a `stream=policy` pair of an event stream channel.
+fn parse_channel_policy(s: &str) -> Result<(String, OverflowPolicy), String> {
+    let (stream, policy) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid channel policy '{}': expected stream=policy", s))?;
+    if !EVENT_STREAMS.contains(&stream) {
+        return Err(format!(
+            "invalid channel policy '{}': expected one of the streams {:?}",
+            s, EVENT_STREAMS
+        ));
+    }
+    let policy = OverflowPolicy::from_str(policy, true)
+        .map_err(|e| format!("invalid channel policy '{}': {}", s, e))?;
+    Ok((stream.to_owned(), policy))
 }
 
 /// RPC endpoint configuration options
 #[derive(Debug, Clone, Parser)]
 pub struct RpcOpts {
-    /// L1 WebSocket RPC URL (must use ws:// or wss:// scheme)
-    #[clap(long, env = "L1_RPC_URL")]
-    pub l1_url: Url,
-    /// L2 WebSocket RPC URL (must use ws:// or wss:// scheme)
-    #[clap(long, env = "L2_RPC_URL")]
-    pub l2_url: Url,
+    /// L1 WebSocket RPC URLs, comma-separated (must use ws:// or wss:// scheme). The first is
+    /// the primary; the rest are fallbacks used while the primary keeps failing
+    #[clap(long, env = "L1_RPC_URL", value_delimiter = ',', required = true)]
+    pub l1_url: Vec<Url>,
+    /// L2 WebSocket RPC URLs, comma-separated (must use ws:// or wss:// scheme). The first is
+    /// the primary; the rest are fallbacks used while the primary keeps failing
+    #[clap(long, env = "L2_RPC_URL", value_delimiter = ',', required = true)]
+    pub l2_url: Vec<Url>,
     /// Public RPC URL for health checks
     #[clap(long, env = "PUBLIC_RPC")]
     pub public_url: Option<Url>,
+    /// Additional public RPC URLs to probe, comma-separated
+    #[clap(long, env = "PUBLIC_RPC_URLS", value_delimiter = ',')]
+    pub public_urls: Vec<Url>,
+    /// Blocks a public RPC may lag behind the L2 head before it is considered unhealthy
+    #[clap(long, env = "PUBLIC_RPC_MAX_BLOCK_LAG", default_value = "10")]
+    pub public_max_block_lag: u64,
+    /// Seconds without a new L1 head before the L1 connection is considered stalled and
+    /// re-established (0 disables the watchdog)
+    #[clap(long, env = "L1_STALL_TIMEOUT_SECS", default_value = "60")]
+    pub l1_stall_timeout_secs: u64,
+    /// Seconds without a new L2 head before the L2 connection is considered stalled and
+    /// re-established (0 disables the watchdog)
+    #[clap(long, env = "L2_STALL_TIMEOUT_SECS", default_value = "30")]
+    pub l2_stall_timeout_secs: u64,
+    /// Events each extractor stream queues for the driver before its overflow policy applies
+    #[clap(long, env = "STREAM_CHANNEL_CAPACITY", default_value = "1024")]
+    pub stream_channel_capacity: usize,
+    /// Overflow policies of the extractor streams as comma separated `stream=policy` pairs,
+    /// with `block` or `drop-oldest` as policy; streams not listed block
+    #[clap(
+        long,
+        env = "STREAM_CHANNEL_POLICIES",
+        value_delimiter = ',',
+        value_parser = parse_channel_policy,
+        default_value = "pending_txs=drop-oldest,pending_proposals=drop-oldest"
+    )]
+    pub stream_channel_policies: Vec<(String, OverflowPolicy)>,
 }
 
 /// Taiko contract address configuration options
@@ -53,6 +261,80 @@ pub struct TaikoAddressOpts {
     /// Taiko anchor contract address
     #[clap(long, env = "TAIKO_ANCHOR_ADDRESS")]
     pub anchor_address: Address,
+    /// Fork name recorded for the addresses above
+    #[clap(long = "taiko-fork-name", env = "TAIKO_FORK_NAME", default_value = "pacaya")]
+    pub fork_name: String,
+    /// Contract addresses of later forks as comma separated
+    /// `name:activation_l1_block:inbox:wrapper:preconf_whitelist` entries. Each set replaces the
+    /// previous one from its activation block on
+    #[clap(
+        long = "taiko-address-forks",
+        env = "TAIKO_ADDRESS_FORKS",
+        value_delimiter = ',',
+        value_parser = parse_fork_addresses
+    )]
+    pub forks: Vec<ForkAddresses>,
+    /// Comma separated verifier contracts whose proofs are recorded as guardian proofs
+    #[clap(long, env = "GUARDIAN_VERIFIER_ADDRESSES", value_delimiter = ',')]
+    pub guardian_verifier_addresses: Vec<Address>,
+}
+
+impl TaikoAddressOpts {
+    /// All address sets ordered by activation block, starting with the addresses configured for
+    /// [`fork_name`](Self::fork_name) which are active from genesis.
+    pub fn address_schedule(&self) -> Vec<ForkAddresses> {
+        let mut schedule = vec![ForkAddresses {
+            name: self.fork_name.clone(),
+            activation_block: 0,
+            inbox_address: self.inbox_address,
+            taiko_wrapper_address: self.taiko_wrapper_address,
+            preconf_whitelist_address: self.preconf_whitelist_address,
+        }];
+        schedule.extend(self.forks.iter().cloned());
+        schedule.sort_by_key(|fork| fork.activation_block);
+        schedule
+    }
+}
+
+/// Taiko contract addresses of a fork, active from an L1 block height on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForkAddresses {
+    /// Fork name, recorded on the rows indexed from these contracts
+    pub name: String,
+    /// First L1 block the addresses are used for
+    pub activation_block: u64,
+    /// Taiko inbox contract address
+    pub inbox_address: Address,
+    /// Taiko wrapper contract address
+    pub taiko_wrapper_address: Address,
+    /// Taiko preconf whitelist contract address
+    pub preconf_whitelist_address: Address,
+}
+
+/// Parse a `name:activation_l1_block:inbox:wrapper:preconf_whitelist` fork address set.
+fn parse_fork_addresses(s: &str) -> Result<ForkAddresses, String> {
+    let parts: Vec<&str> = s.split(':').map(str::trim).collect();
+    let [name, activation_block, inbox, wrapper, preconf_whitelist] = parts.as_slice() else {
+        return Err(format!(
+            "invalid fork addresses '{}': expected name:activation_l1_block:inbox:wrapper:preconf_whitelist",
+            s
+        ));
+    };
+    if name.is_empty() {
+        return Err(format!("invalid fork addresses '{}': empty fork name", s));
+    }
+    let address = |value: &str| {
+        value.parse::<Address>().map_err(|e| format!("invalid fork addresses '{}': {}", s, e))
+    };
+    Ok(ForkAddresses {
+        name: (*name).to_owned(),
+        activation_block: activation_block
+            .parse()
+            .map_err(|e| format!("invalid fork addresses '{}': {}", s, e))?,
+        inbox_address: address(inbox)?,
+        taiko_wrapper_address: address(wrapper)?,
+        preconf_whitelist_address: address(preconf_whitelist)?,
+    })
 }
 
 /// Instatus monitoring configuration options
@@ -79,6 +361,43 @@ pub struct InstatusOpts {
     /// Instatus component ID for the public API monitor
     #[clap(long, env = "INSTATUS_PUBLIC_API_COMPONENT_ID", default_value = "")]
     pub public_api_component_id: String,
+    /// Instatus component IDs for the endpoints in `PUBLIC_RPC_URLS`, comma-separated and in the
+    /// same order (endpoints without one are only logged)
+    #[clap(long, env = "INSTATUS_PUBLIC_RPC_COMPONENT_IDS", value_delimiter = ',')]
+    pub public_rpc_component_ids: Vec<String>,
+    /// Instatus component ID for the ingestion pipeline monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_PIPELINE_COMPONENT_ID", default_value = "")]
+    pub pipeline_component_id: String,
+    /// Instatus component ID for the operator balance monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_OPERATOR_BALANCE_COMPONENT_ID", default_value = "")]
+    pub operator_balance_component_id: String,
+    /// Instatus component ID for the proposer bond balance monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_BOND_BALANCE_COMPONENT_ID", default_value = "")]
+    pub bond_balance_component_id: String,
+    /// Instatus component ID for the batch cadence monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_BATCH_CADENCE_COMPONENT_ID", default_value = "")]
+    pub batch_cadence_component_id: String,
+    /// Instatus component ID for the gas saturation monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_GAS_SATURATION_COMPONENT_ID", default_value = "")]
+    pub gas_saturation_component_id: String,
+    /// Instatus component ID for the anchor mismatch monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_ANCHOR_MISMATCH_COMPONENT_ID", default_value = "")]
+    pub anchor_mismatch_component_id: String,
+    /// Instatus component ID for the operator candidate churn monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_CANDIDATE_CHURN_COMPONENT_ID", default_value = "")]
+    pub candidate_churn_component_id: String,
+    /// Instatus component ID for the metric anomaly monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_ANOMALY_COMPONENT_ID", default_value = "")]
+    pub anomaly_component_id: String,
+    /// Instatus component ID for the forced inclusion queue monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_FORCED_INCLUSION_COMPONENT_ID", default_value = "")]
+    pub forced_inclusion_component_id: String,
+    /// Instatus component ID for the verification backlog monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_VERIFICATION_BACKLOG_COMPONENT_ID", default_value = "")]
+    pub verification_backlog_component_id: String,
+    /// Instatus component ID for the pending proposal monitor (dry-run when unset)
+    #[clap(long, env = "INSTATUS_PROPOSAL_INCLUSION_COMPONENT_ID", default_value = "")]
+    pub proposal_inclusion_component_id: String,
     /// Enable all Instatus monitors
     #[clap(long = "enable-monitors", env = "INSTATUS_MONITORS_ENABLED", default_value_t = true)]
     pub monitors_enabled: bool,
@@ -93,9 +412,84 @@ pub struct InstatusOpts {
     #[clap(long, env = "INSTATUS_L2_MONITOR_THRESHOLD_SECS", default_value = "600")]
     pub l2_monitor_threshold_secs: u64,
 
-    /// Batch proof timeout threshold in seconds (default 3 hours)
-    #[clap(long, env = "BATCH_PROOF_TIMEOUT_SECS", default_value = "10800")]
-    pub batch_proof_timeout_secs: u64,
+    /// Threshold in seconds without head event inserts before the pipeline is considered stalled
+    #[clap(long, env = "INSTATUS_PIPELINE_MONITOR_THRESHOLD_SECS", default_value = "300")]
+    pub pipeline_monitor_threshold_secs: u64,
+
+    /// Batch proof timeout threshold in seconds (defaults to the inbox proving window, or 3 hours
+    /// if it cannot be read)
+    #[clap(long, env = "BATCH_PROOF_TIMEOUT_SECS")]
+    pub batch_proof_timeout_secs: Option<u64>,
+
+    /// Margin in seconds added to the inbox cooldown window before a proved batch is considered
+    /// overdue for verification
+    #[clap(long, env = "BATCH_VERIFY_COOLDOWN_MARGIN_SECS", default_value = "600")]
+    pub batch_verify_cooldown_margin_secs: u64,
+
+    /// Seconds ahead of reaching the unverified batch limit at the current posting cadence at
+    /// which the batch cadence monitor opens an incident (default: 3600)
+    #[clap(long, env = "BATCH_CADENCE_WARNING_SECS", default_value = "3600")]
+    pub batch_cadence_warning_secs: u64,
+
+    /// Percentage of the inbox anchor height offset above which the anchor age of recent batches
+    /// opens an incident (default: 80)
+    #[clap(long, env = "BATCH_CADENCE_ANCHOR_AGE_PCT", default_value = "80")]
+    pub batch_cadence_anchor_age_pct: u64,
+
+    /// Average L2 block gas used over gas limit, in percent, at or above which the gas
+    /// saturation monitor opens an incident (default: 90)
+    #[clap(long, env = "GAS_SATURATION_THRESHOLD_PCT", default_value = "90")]
+    pub gas_saturation_threshold_pct: u64,
+
+    /// Window in seconds over which the average L2 block gas saturation is measured
+    /// (default: 900)
+    #[clap(long, env = "GAS_SATURATION_WINDOW_SECS", default_value = "900")]
+    pub gas_saturation_window_secs: u64,
+
+    /// Window in seconds without anchor mismatches after which the anchor mismatch incident is
+    /// resolved (default: 3600)
+    #[clap(long, env = "ANCHOR_MISMATCH_WINDOW_SECS", default_value = "3600")]
+    pub anchor_mismatch_window_secs: u64,
+
+    /// Window in seconds without changes to the whitelist operator candidates after which the
+    /// candidate churn incident is resolved (default: 3600)
+    #[clap(long, env = "CANDIDATE_CHURN_WINDOW_SECS", default_value = "3600")]
+    pub candidate_churn_window_secs: u64,
+
+    /// Window in seconds without metric anomalies after which the anomaly incident is resolved
+    /// (default: 3600)
+    #[clap(long, env = "ANOMALY_INCIDENT_WINDOW_SECS", default_value = "3600")]
+    pub anomaly_incident_window_secs: u64,
+
+    /// Seconds a batch proposal may stay pending in the L1 mempool before the pending proposal
+    /// monitor opens an incident (default: 120)
+    #[clap(long, env = "PROPOSAL_PENDING_THRESHOLD_SECS", default_value = "120")]
+    pub proposal_pending_threshold_secs: u64,
+
+    /// Number of full batches whose liveness bonds an active proposer's inbox bond balance must
+    /// cover before the bond balance monitor opens an incident (default: 3)
+    #[clap(long, env = "BOND_LOW_BATCHES", default_value = "3")]
+    pub bond_low_batches: u64,
+
+    /// Number of whitelist operator candidates below which the candidate churn monitor opens an
+    /// incident (default: 1)
+    #[clap(long, env = "MIN_OPERATOR_CANDIDATES", default_value = "1")]
+    pub min_operator_candidates: usize,
+
+    /// Create the required components that have no configured ID on the Instatus page, matched
+    /// by name, and record their IDs in the incident state directory (default: false)
+    #[clap(long, env = "INSTATUS_PROVISION_COMPONENTS", default_value = "false")]
+    pub provision_components: bool,
+
+    /// Directory where monitors persist open incidents across restarts (disabled when unset)
+    #[clap(long = "incident-state-dir", env = "INCIDENT_STATE_DIR")]
+    pub state_dir: Option<PathBuf>,
+
+    /// Maintenance windows during which monitors log instead of creating incidents,
+    /// comma-separated as `START/END[/COMPONENT+COMPONENT]` with RFC 3339 timestamps; a window
+    /// without components covers all of them
+    #[clap(long, env = "INSTATUS_MAINTENANCE_WINDOWS", value_delimiter = ',')]
+    pub maintenance_windows: Vec<String>,
 }
 
 impl InstatusOpts {
@@ -126,6 +520,9 @@ pub struct ApiOpts {
     /// API server port
     #[clap(long = "api-port", env = "API_PORT", default_value = "3000")]
     pub port: u16,
+    /// Port of the gRPC API for internal consumers; disabled when unset
+    #[clap(long = "grpc-port", env = "API_GRPC_PORT")]
+    pub grpc_port: Option<u16>,
     /// Allowed CORS origins (comma separated)
     #[clap(
         long = "allowed-origin",
@@ -135,6 +532,54 @@ pub struct ApiOpts {
     )]
     pub allowed_origins: Vec<String>,
 
+    /// HTTP methods allowed in cross-origin requests (comma separated); preflight `OPTIONS`
+    /// requests are answered for them
+    #[clap(
+        long = "cors-allowed-methods",
+        env = "CORS_ALLOWED_METHODS",
+        value_delimiter = ',',
+        value_parser = parse_cors_method,
+        default_value = "GET"
+    )]
+    pub cors_allowed_methods: Vec<String>,
+
+    /// Seconds browsers may cache preflight responses (`Access-Control-Max-Age`, not sent when
+    /// unset)
+    #[clap(long = "cors-max-age-secs", env = "CORS_MAX_AGE_SECS")]
+    pub cors_max_age_secs: Option<u64>,
+
+    /// `max-age` in seconds of the `Strict-Transport-Security` header (not sent when unset)
+    #[clap(long = "hsts-max-age-secs", env = "HSTS_MAX_AGE_SECS")]
+    pub hsts_max_age_secs: Option<u64>,
+
+    /// Apply the `Strict-Transport-Security` header to subdomains as well
+    #[clap(
+        long = "hsts-include-subdomains",
+        env = "HSTS_INCLUDE_SUBDOMAINS",
+        default_value = "false"
+    )]
+    pub hsts_include_subdomains: bool,
+
+    /// Send `X-Content-Type-Options: nosniff` with every response
+    #[clap(long = "content-type-nosniff", env = "CONTENT_TYPE_NOSNIFF", default_value = "true")]
+    pub content_type_nosniff: bool,
+
+    /// Value of the `Referrer-Policy` header (not sent when empty)
+    #[clap(
+        long = "referrer-policy",
+        env = "REFERRER_POLICY",
+        default_value = "strict-origin-when-cross-origin"
+    )]
+    pub referrer_policy: String,
+
+    /// Compress responses with gzip or brotli when the client accepts them
+    #[clap(long = "api-compression", env = "API_COMPRESSION", default_value = "true")]
+    pub compression: bool,
+
+    /// Tag `GET` responses with an `ETag` and answer matching `If-None-Match` with 304
+    #[clap(long = "api-etag", env = "API_ETAG", default_value = "true")]
+    pub etag: bool,
+
     /// Maximum number of requests allowed during the rate limiting period
     #[clap(
         long = "rate-limit-max-requests",
@@ -146,6 +591,154 @@ pub struct ApiOpts {
     /// Duration of the rate limiting window in seconds
     #[clap(long = "rate-limit-period-secs", env = "RATE_LIMIT_PERIOD_SECS", default_value = "60")]
     pub rate_limit_period_secs: u64,
+
+    /// Largest burst of requests allowed at once; defaults to `rate-limit-max-requests`
+    #[clap(long = "rate-limit-burst", env = "RATE_LIMIT_BURST")]
+    pub rate_limit_burst: Option<u64>,
+
+    /// Token cost of expensive routes as comma separated `path=weight` pairs; other routes
+    /// cost one token
+    #[clap(
+        long = "rate-limit-route-weights",
+        env = "RATE_LIMIT_ROUTE_WEIGHTS",
+        value_delimiter = ',',
+        value_parser = parse_route_weight,
+        default_value = "/dashboard-data=10"
+    )]
+    pub rate_limit_route_weights: Vec<(String, u64)>,
+
+    /// Known L2 block builders as comma separated `0xPREFIX=NAME` pairs, matched against a
+    /// prefix of the extra data of the blocks they build
+    #[clap(long = "builder-fingerprints", env = "BUILDER_FINGERPRINTS", value_delimiter = ',')]
+    pub builder_fingerprints: Vec<String>,
+
+    /// Response cache TTL in seconds for aggregate dashboard endpoints (0 disables caching)
+    #[clap(
+        long = "api-cache-ttl-dashboard-secs",
+        env = "API_CACHE_TTL_DASHBOARD_SECS",
+        default_value = "30"
+    )]
+    pub cache_ttl_dashboard_secs: u64,
+
+    /// Response cache TTL in seconds for paginated table endpoints (0 disables caching)
+    #[clap(
+        long = "api-cache-ttl-table-secs",
+        env = "API_CACHE_TTL_TABLE_SECS",
+        default_value = "10"
+    )]
+    pub cache_ttl_table_secs: u64,
+
+    /// Seconds between refreshes of the pre-warmed responses, shortened by up to a fifth at
+    /// random so the refreshes of several servers spread out (0 disables pre-warming)
+    #[clap(
+        long = "api-prewarm-interval-secs",
+        env = "API_PREWARM_INTERVAL_SECS",
+        default_value = "25"
+    )]
+    pub prewarm_interval_secs: u64,
+
+    /// Comma separated paths with query, relative to the API version prefix, whose responses
+    /// are kept in the response cache
+    #[clap(
+        long = "api-prewarm-paths",
+        env = "API_PREWARM_PATHS",
+        value_delimiter = ',',
+        default_value = "/dashboard-data?range=24h,/dashboard-data?range=7d,/l2-fees-components?range=24h,/l2-fees-components?range=7d"
+    )]
+    pub prewarm_paths: Vec<String>,
+
+    /// Maximum number of `ClickHouse` sub-queries per request of endpoints that fan out, such as
+    /// `/dashboard-data` (0 disables the limit)
+    #[clap(
+        long = "api-query-budget-max-queries",
+        env = "API_QUERY_BUDGET_MAX_QUERIES",
+        default_value = "16"
+    )]
+    pub query_budget_max_queries: usize,
+
+    /// Total time in milliseconds the sub-queries of such a request may take before the
+    /// remaining ones are aborted (0 disables the limit)
+    #[clap(long = "api-query-budget-ms", env = "API_QUERY_BUDGET_MS", default_value = "8000")]
+    pub query_budget_ms: u64,
+
+    /// Serve an HTML status page at `/status` summarizing head lag, open incidents (read from
+    /// `incident-state-dir`) and the proving backlog
+    #[clap(long = "status-page", env = "STATUS_PAGE_ENABLED", default_value = "false")]
+    pub status_page: bool,
+
+    /// Seconds in-flight requests are given to complete after SIGINT or SIGTERM before the
+    /// remaining connections are dropped
+    #[clap(
+        long = "api-shutdown-grace-secs",
+        env = "API_SHUTDOWN_GRACE_SECS",
+        default_value = "30"
+    )]
+    pub shutdown_grace_secs: u64,
+}
+
+/// Output format of log lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum LogFormat {
+    /// One JSON object per line, for log aggregation in production
+    Json,
+    /// Human-readable, multi-field lines
+    #[default]
+    Pretty,
+}
+
+/// Logging configuration options
+#[derive(Debug, Clone, Parser)]
+pub struct LogOpts {
+    /// Log output format
+    #[clap(long = "log-format", env = "LOG_FORMAT", value_enum, default_value = "pretty")]
+    pub format: LogFormat,
+
+    /// Window in seconds in which repeated noisy warnings (e.g. resubscribe attempts) are logged
+    /// at most once (0 disables sampling)
+    #[clap(long = "log-sample-window-secs", env = "LOG_SAMPLE_WINDOW_SECS", default_value = "60")]
+    pub sample_window_secs: u64,
+}
+
+/// File format of snapshot chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SnapshotFormat {
+    /// `ClickHouse` native format, lossless for every column type
+    #[default]
+    Native,
+    /// Apache Parquet, readable by other tools
+    Parquet,
+}
+
+/// Snapshot export and import configuration options
+#[derive(Debug, Clone, Parser)]
+pub struct SnapshotOpts {
+    /// S3-compatible URL of the snapshot prefix, e.g.
+    /// `https://bucket.s3.amazonaws.com/taikoscope/2026-10-17`
+    #[clap(id = "snapshot_url", long = "snapshot-url", env = "SNAPSHOT_URL")]
+    pub url: Option<String>,
+
+    /// Access key of the snapshot bucket; the `ClickHouse` server's own credentials are used when
+    /// unset
+    #[clap(long = "snapshot-access-key-id", env = "SNAPSHOT_ACCESS_KEY_ID")]
+    pub access_key_id: Option<String>,
+
+    /// Secret key of the snapshot bucket
+    #[clap(long = "snapshot-secret-access-key", env = "SNAPSHOT_SECRET_ACCESS_KEY")]
+    pub secret_access_key: Option<String>,
+
+    /// File format of exported chunks
+    #[clap(
+        id = "snapshot_format",
+        long = "snapshot-format",
+        env = "SNAPSHOT_FORMAT",
+        value_enum,
+        default_value = "native"
+    )]
+    pub format: SnapshotFormat,
+
+    /// Approximate number of rows per exported chunk (default: 1000000)
+    #[clap(long = "snapshot-chunk-rows", env = "SNAPSHOT_CHUNK_ROWS", default_value = "1000000")]
+    pub chunk_rows: u64,
 }
 
 /// CLI options for taikoscope
@@ -171,6 +764,14 @@ pub struct Opts {
     #[clap(flatten)]
     pub api: ApiOpts,
 
+    /// Logging configuration
+    #[clap(flatten)]
+    pub log: LogOpts,
+
+    /// Snapshot export and import configuration
+    #[clap(flatten)]
+    pub snapshot: SnapshotOpts,
+
     /// Enable database writes in processor (default: false, processor will log and drop events)
     #[clap(long, env = "ENABLE_DB_WRITES", default_value = "true")]
     pub enable_db_writes: bool,
@@ -183,6 +784,118 @@ pub struct Opts {
     #[clap(long, env = "SKIP_MIGRATIONS", default_value = "false")]
     pub skip_migrations: bool,
 
+    /// Apply pending database migrations and exit without starting the indexer
+    #[clap(
+        long,
+        env = "MIGRATE_ONLY",
+        default_value = "false",
+        conflicts_with = "skip_migrations"
+    )]
+    pub migrate_only: bool,
+
+    /// Apply pending migrations, populate the empty database with sample data and exit without
+    /// connecting to any RPC (local development only)
+    #[clap(
+        long,
+        env = "SEED_FIXTURES",
+        default_value = "false",
+        conflicts_with_all = ["skip_migrations", "migrate_only"]
+    )]
+    pub seed_fixtures: bool,
+
+    /// Check that the configured RPC endpoints, feeds, contracts, `ClickHouse` and Instatus are
+    /// reachable and accept the credentials, print a report and exit, with a non-zero status if
+    /// any check fails
+    #[clap(
+        long,
+        env = "CHECK_CONFIG",
+        default_value = "false",
+        conflicts_with_all = ["migrate_only", "seed_fixtures"]
+    )]
+    pub check_config: bool,
+
+    /// Apply pending migrations, recompute the rows of derived tables that were computed by an
+    /// older version of their logic from the raw tables, and exit
+    #[clap(
+        long,
+        env = "REPROCESS",
+        default_value = "false",
+        conflicts_with_all = ["skip_migrations", "migrate_only", "seed_fixtures", "check_config"]
+    )]
+    pub reprocess: bool,
+
+    /// Export every table to the S3-compatible bucket at `SNAPSHOT_URL` and exit
+    #[clap(
+        long,
+        env = "SNAPSHOT_EXPORT",
+        default_value = "false",
+        conflicts_with_all = ["migrate_only", "seed_fixtures", "check_config", "reprocess"]
+    )]
+    pub snapshot_export: bool,
+
+    /// Apply pending migrations, import the snapshot at `SNAPSHOT_URL` into the empty database
+    /// and exit
+    #[clap(
+        long,
+        env = "SNAPSHOT_IMPORT",
+        default_value = "false",
+        conflicts_with_all = [
+            "skip_migrations",
+            "migrate_only",
+            "seed_fixtures",
+            "check_config",
+            "reprocess",
+            "snapshot_export"
+        ]
+    )]
+    pub snapshot_import: bool,
+
+    /// Run the indexer and the API server in one process, sharing this configuration (local
+    /// development only, requires the `dev` feature of the taikoscope binary)
+    #[clap(
+        long,
+        env = "DEV_MODE",
+        default_value = "false",
+        conflicts_with_all = [
+            "migrate_only",
+            "seed_fixtures",
+            "check_config",
+            "reprocess",
+            "snapshot_export",
+            "snapshot_import"
+        ]
+    )]
+    pub dev: bool,
+
+    /// Units, e.g. hours, recomputed per batch by `--reprocess` (default: 168)
+    #[clap(long, env = "REPROCESS_BATCH_SIZE", default_value = "168")]
+    pub reprocess_batch_size: u64,
+
+    /// Number of L2 blocks generated by `--seed-fixtures`, two seconds apart and ending now
+    #[clap(long, env = "FIXTURE_L2_BLOCKS", default_value = "3600")]
+    pub fixture_l2_blocks: u64,
+
+    /// Start even if applied migrations were modified after being applied
+    #[clap(long, env = "ALLOW_DIRTY_SCHEMA", default_value = "false")]
+    pub allow_dirty_schema: bool,
+
+    /// Bearer token required by the admin endpoints; they are disabled when unset
+    #[clap(long, env = "ADMIN_TOKEN", value_parser = parse_admin_token)]
+    pub admin_token: Option<String>,
+
+    /// Host the indexer's admin server listens on (default: 127.0.0.1)
+    #[clap(long, env = "ADMIN_HOST", default_value = "127.0.0.1")]
+    pub admin_host: String,
+
+    /// Port the indexer's admin server listens on (default: 3001)
+    #[clap(long, env = "ADMIN_PORT", default_value = "3001")]
+    pub admin_port: u16,
+
+    /// Port the indexer serves `/health` on, reporting the active RPC endpoints; disabled when
+    /// unset. Listens on the admin host
+    #[clap(long, env = "HEALTH_PORT")]
+    pub health_port: Option<u16>,
+
     /// Enable gap detection and backfill (default: true)
     #[clap(long, env = "ENABLE_GAP_DETECTION", default_value = "true")]
     pub enable_gap_detection: bool,
@@ -199,6 +912,11 @@ pub struct Opts {
     #[clap(long, env = "GAP_CONTINUOUS_LOOKBACK_BLOCKS", default_value = "32")]
     pub gap_continuous_lookback_blocks: u64,
 
+    /// Number of L1 blocks searched for missing batch proposals, proofs and verifications
+    /// (default: 7200)
+    #[clap(long, env = "GAP_BATCH_LOOKBACK_BLOCKS", default_value = "7200")]
+    pub gap_batch_lookback_blocks: u64,
+
     /// Gap detection poll interval in seconds (default: 30)
     #[clap(long, env = "GAP_POLL_INTERVAL_SECS", default_value = "30")]
     pub gap_poll_interval_secs: u64,
@@ -218,6 +936,376 @@ pub struct Opts {
     /// Minimum L2 block number to backfill
     #[clap(long, env = "GAP_MIN_L2_BLOCK")]
     pub gap_min_l2_block: u64,
+
+    /// RPC requests per second shared by all backfills, 0 disables the limit (default: 20)
+    #[clap(long, env = "GAP_BACKFILL_MAX_RPS", default_value = "20")]
+    pub gap_backfill_max_rps: u32,
+
+    /// Enable the batch data-quality checker (default: true)
+    #[clap(long, env = "ENABLE_DATA_QUALITY_CHECKS", default_value = "true")]
+    pub enable_data_quality_checks: bool,
+
+    /// Data-quality check interval in seconds (default: 300)
+    #[clap(
+        long,
+        env = "DATA_QUALITY_INTERVAL_SECS",
+        default_value = "300",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub data_quality_interval_secs: u64,
+
+    /// Number of L1 blocks to search when backfilling a missing batch event (default: 7200)
+    #[clap(long, env = "DATA_QUALITY_LOOKBACK_BLOCKS", default_value = "7200")]
+    pub data_quality_lookback_blocks: u64,
+
+    /// Backfill prove costs for proved batches that have none (default: true)
+    #[clap(long, env = "ENABLE_PROVE_COST_BACKFILL", default_value = "true")]
+    pub enable_prove_cost_backfill: bool,
+
+    /// Prove cost backfill interval in seconds (default: 300)
+    #[clap(
+        long,
+        env = "PROVE_COST_BACKFILL_INTERVAL_SECS",
+        default_value = "300",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub prove_cost_backfill_interval_secs: u64,
+
+    /// Track inbox bond balance changes in the bond ledger (default: true)
+    #[clap(long, env = "ENABLE_BOND_LEDGER", default_value = "true")]
+    pub enable_bond_ledger: bool,
+
+    /// Bond ledger sync interval in seconds (default: 60)
+    #[clap(
+        long,
+        env = "BOND_LEDGER_INTERVAL_SECS",
+        default_value = "60",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub bond_ledger_interval_secs: u64,
+
+    /// Number of L1 blocks scanned for bond events when the ledger is empty (default: 7200)
+    #[clap(long, env = "BOND_LEDGER_LOOKBACK_BLOCKS", default_value = "7200")]
+    pub bond_ledger_lookback_blocks: u64,
+
+    /// Record spans in which the scheduled sequencer produced no blocks (default: true)
+    #[clap(long, env = "ENABLE_SEQUENCER_DOWNTIME", default_value = "true")]
+    pub enable_sequencer_downtime: bool,
+
+    /// Sequencer downtime sync interval in seconds (default: 300)
+    #[clap(
+        long,
+        env = "SEQUENCER_DOWNTIME_INTERVAL_SECS",
+        default_value = "300",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub sequencer_downtime_interval_secs: u64,
+
+    /// Seconds without a block from the scheduled sequencer counted as downtime (default: 60)
+    #[clap(long, env = "SEQUENCER_DOWNTIME_MAX_GAP_SECS", default_value = "60")]
+    pub sequencer_downtime_max_gap_secs: u64,
+
+    /// Number of complete hours recomputed every cycle (default: 6)
+    #[clap(long, env = "SEQUENCER_DOWNTIME_LOOKBACK_HOURS", default_value = "6")]
+    pub sequencer_downtime_lookback_hours: u64,
+
+    /// Snapshot the L1 ETH and TAIKO balances of the whitelisted operators (default: true)
+    #[clap(long, env = "ENABLE_OPERATOR_BALANCES", default_value = "true")]
+    pub enable_operator_balances: bool,
+
+    /// Operator balance snapshot interval in seconds (default: 300)
+    #[clap(
+        long,
+        env = "OPERATOR_BALANCE_INTERVAL_SECS",
+        default_value = "300",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub operator_balance_interval_secs: u64,
+
+    /// Record changes to the whitelist operator candidates (default: true)
+    #[clap(long, env = "ENABLE_OPERATOR_HISTORY", default_value = "true")]
+    pub enable_operator_history: bool,
+
+    /// Operator candidate check interval in seconds (default: 60)
+    #[clap(
+        long,
+        env = "OPERATOR_HISTORY_INTERVAL_SECS",
+        default_value = "60",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub operator_history_interval_secs: u64,
+
+    /// ETH balance in wei below which an operator is flagged (default: 1 ETH)
+    #[clap(long, env = "OPERATOR_MIN_ETH_BALANCE_WEI", default_value = "1000000000000000000")]
+    pub operator_min_eth_balance_wei: u128,
+
+    /// TAIKO token contract on L1; operator token balances are not tracked when unset
+    #[clap(long, env = "TAIKO_TOKEN_ADDRESS")]
+    pub taiko_token_address: Option<Address>,
+
+    /// TAIKO balance in base units below which an operator is flagged (default: 0, disabled)
+    #[clap(long, env = "OPERATOR_MIN_TAIKO_BALANCE_WEI", default_value = "0")]
+    pub operator_min_taiko_balance_wei: u128,
+
+    /// Store periodic ETH/USD price snapshots used to report costs in USD (default: true)
+    #[clap(long, env = "ENABLE_ETH_PRICE_SNAPSHOTS", default_value = "true")]
+    pub enable_eth_price_snapshots: bool,
+
+    /// ETH/USD price snapshot interval in seconds (default: 300)
+    #[clap(
+        long,
+        env = "ETH_PRICE_SNAPSHOT_INTERVAL_SECS",
+        default_value = "300",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub eth_price_snapshot_interval_secs: u64,
+
+    /// `CoinGecko`-compatible endpoint returning `{"ethereum":{"usd":<price>}}`
+    #[clap(
+        long,
+        env = "ETH_PRICE_URL",
+        default_value = "https://api.coingecko.com/api/v3/simple/price?ids=ethereum&vs_currencies=usd"
+    )]
+    pub eth_price_url: String,
+
+    /// Store periodic TAIKO/ETH and TAIKO/USD price snapshots, taken at the ETH price snapshot
+    /// interval, used to report costs and bonds in TAIKO (default: true)
+    #[clap(long, env = "ENABLE_TAIKO_PRICE_SNAPSHOTS", default_value = "true")]
+    pub enable_taiko_price_snapshots: bool,
+
+    /// `CoinGecko`-compatible endpoint returning `{"taiko":{"eth":<price>,"usd":<price>}}`
+    #[clap(
+        long,
+        env = "TAIKO_PRICE_URL",
+        default_value = "https://api.coingecko.com/api/v3/simple/price?ids=taiko&vs_currencies=eth,usd"
+    )]
+    pub taiko_price_url: String,
+
+    /// `CoinGecko` pro API key sent with price requests
+    #[clap(long, env = "COINGECKO_API_KEY")]
+    pub coingecko_api_key: Option<String>,
+
+    /// WebSocket feed of the sequencer's soft blocks; when set, each soft block is compared
+    /// against the canonical L2 block at its height and differences are stored
+    #[clap(long, env = "PRECONF_FEED_URL")]
+    pub preconf_feed_url: Option<Url>,
+
+    /// Seconds to wait for the canonical block of a soft block before recording it as missing
+    /// (default: 120)
+    #[clap(long, env = "PRECONF_COMPARE_TIMEOUT_SECS", default_value = "120")]
+    pub preconf_compare_timeout_secs: u64,
+
+    /// Follow the L2 node's pending transaction feed and store per-minute mempool depth and
+    /// inclusion latency (default: false)
+    #[clap(long, env = "ENABLE_MEMPOOL_MONITOR", default_value = "false")]
+    pub enable_mempool_monitor: bool,
+
+    /// Follow the batch proposals entering the L1 node's mempool and record how long each takes
+    /// to be included (default: false)
+    #[clap(long, env = "ENABLE_PROPOSAL_MEMPOOL_MONITOR", default_value = "false")]
+    pub enable_proposal_mempool_monitor: bool,
+
+    /// Contracts besides the inbox and wrapper whose pending transactions are batch proposals,
+    /// e.g. a preconfirmation router, comma-separated
+    #[clap(long, env = "PROPOSAL_WATCH_ADDRESSES", value_delimiter = ',')]
+    pub proposal_watch_addresses: Vec<Address>,
+
+    /// Record sudden changes of TPS, gas used, batch cadence and prove times (default: true)
+    #[clap(long, env = "ENABLE_ANOMALY_DETECTION", default_value = "true")]
+    pub enable_anomaly_detection: bool,
+
+    /// Anomaly detection sample interval in seconds (default: 300)
+    #[clap(
+        long,
+        env = "ANOMALY_INTERVAL_SECS",
+        default_value = "300",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub anomaly_interval_secs: u64,
+
+    /// Trailing window in seconds over which every metric is averaged per sample (default: 3600)
+    #[clap(long, env = "ANOMALY_WINDOW_SECS", default_value = "3600")]
+    pub anomaly_window_secs: u64,
+
+    /// Standard deviations from the moving average beyond which a sample is an anomaly
+    /// (default: 4)
+    #[clap(long, env = "ANOMALY_Z_THRESHOLD", default_value = "4.0")]
+    pub anomaly_z_threshold: f64,
+
+    /// Weight of every new sample in the exponentially weighted moving average (default: 0.1)
+    #[clap(long, env = "ANOMALY_EWMA_ALPHA", default_value = "0.1")]
+    pub anomaly_ewma_alpha: f64,
+
+    /// Snapshot the depth of the forced inclusion queue (default: true)
+    #[clap(long, env = "ENABLE_FORCED_INCLUSION_QUEUE", default_value = "true")]
+    pub enable_forced_inclusion_queue: bool,
+
+    /// Forced inclusion queue snapshot interval in seconds (default: 60)
+    #[clap(
+        long,
+        env = "FORCED_INCLUSION_QUEUE_INTERVAL_SECS",
+        default_value = "60",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub forced_inclusion_queue_interval_secs: u64,
+
+    /// Batches before its deadline at which the oldest queued forced inclusion is flagged
+    /// (default: 2)
+    #[clap(long, env = "FORCED_INCLUSION_DEADLINE_MARGIN_BATCHES", default_value = "2")]
+    pub forced_inclusion_deadline_margin_batches: u64,
+
+    /// Snapshot how many batches the latest verified batch lags behind the latest proposed
+    /// batch (default: true)
+    #[clap(long, env = "ENABLE_VERIFICATION_BACKLOG", default_value = "true")]
+    pub enable_verification_backlog: bool,
+
+    /// Verification backlog snapshot interval in seconds (default: 60)
+    #[clap(
+        long,
+        env = "VERIFICATION_BACKLOG_INTERVAL_SECS",
+        default_value = "60",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub verification_backlog_interval_secs: u64,
+
+    /// Unverified batches beyond which a verification backlog snapshot is flagged (default: 100)
+    #[clap(long, env = "VERIFICATION_BACKLOG_THRESHOLD_BATCHES", default_value = "100")]
+    pub verification_backlog_threshold_batches: u64,
+
+    /// Static address labels, comma-separated as `ADDRESS=LABEL`; they take precedence over ENS
+    /// names
+    #[clap(long, env = "ADDRESS_LABELS", value_delimiter = ',')]
+    pub address_labels: Vec<String>,
+
+    /// Label sequencer and proposer addresses with their verified ENS names on L1
+    /// (default: false)
+    #[clap(long, env = "ENABLE_ENS_LABELS", default_value = "false")]
+    pub enable_ens_labels: bool,
+
+    /// Address label refresh interval in seconds (default: 3600)
+    #[clap(
+        long,
+        env = "ADDRESS_LABELS_INTERVAL_SECS",
+        default_value = "3600",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub address_labels_interval_secs: u64,
+
+    /// Maximum rows buffered per table before head events are written in bulk (0 disables
+    /// buffering)
+    #[clap(long, env = "WRITE_BUFFER_MAX_ROWS", default_value = "100")]
+    pub write_buffer_max_rows: usize,
+
+    /// Maximum time in milliseconds a buffered row waits before being written
+    #[clap(long, env = "WRITE_BUFFER_FLUSH_INTERVAL_MS", default_value = "1000")]
+    pub write_buffer_flush_interval_ms: u64,
+
+    /// File the rows of failed `ClickHouse` writes are spilled to and replayed from once the
+    /// server recovers (default: unset, failed rows are dropped)
+    #[clap(long, env = "SPILL_PATH")]
+    pub spill_path: Option<PathBuf>,
+
+    /// Size in bytes the spill file may grow to before the oldest rows are evicted
+    /// (default: 268435456)
+    #[clap(long, env = "SPILL_MAX_BYTES", default_value = "268435456")]
+    pub spill_max_bytes: u64,
+
+    /// Interval in seconds at which spilled rows are replayed (default: 10)
+    #[clap(
+        long,
+        env = "SPILL_REPLAY_INTERVAL_SECS",
+        default_value = "10",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub spill_replay_interval_secs: u64,
+
+    /// Periodically sample rows written to both the primary and the secondary cluster and
+    /// report mismatches (default: false)
+    #[clap(
+        long = "verify-dual",
+        env = "VERIFY_DUAL",
+        default_value = "false",
+        requires = "secondary_url"
+    )]
+    pub verify_dual: bool,
+
+    /// Dual-write verification interval in seconds (default: 300)
+    #[clap(
+        long,
+        env = "VERIFY_DUAL_INTERVAL_SECS",
+        default_value = "300",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub verify_dual_interval_secs: u64,
+
+    /// Keys sampled per table in each dual-write verification round (default: 100)
+    #[clap(long, env = "VERIFY_DUAL_SAMPLE_SIZE", default_value = "100")]
+    pub verify_dual_sample_size: u64,
+
+    /// Periodically count duplicate rows in the deduplicated tables (default: true)
+    #[clap(long, env = "ENABLE_DEDUP_VERIFICATION", default_value = "true")]
+    pub enable_dedup_verification: bool,
+
+    /// Duplicate row verification interval in seconds (default: 3600)
+    #[clap(
+        long,
+        env = "DEDUP_VERIFY_INTERVAL_SECS",
+        default_value = "3600",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub dedup_verify_interval_secs: u64,
+
+    /// Hours of recently inserted rows checked for duplicates (default: 24)
+    #[clap(long, env = "DEDUP_VERIFY_LOOKBACK_HOURS", default_value = "24")]
+    pub dedup_verify_lookback_hours: u64,
+
+    /// Periodically reconcile the fees attributed to sequencers against the balance changes of
+    /// their addresses on L1 and L2 (default: false)
+    #[clap(long, env = "ENABLE_FEE_RECONCILIATION", default_value = "false")]
+    pub enable_fee_reconciliation: bool,
+
+    /// Length in seconds of the period reconciled in each run, ending at the last complete
+    /// period (default: 3600)
+    #[clap(
+        long,
+        env = "FEE_RECONCILE_PERIOD_SECS",
+        default_value = "3600",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub fee_reconcile_period_secs: u64,
+
+    /// Difference in wei between the expected and the actual balance change above which a
+    /// sequencer is flagged (default: 0.001 ETH)
+    #[clap(long, env = "FEE_RECONCILE_TOLERANCE_WEI", default_value = "1000000000000000")]
+    pub fee_reconcile_tolerance_wei: u128,
+
+    /// Elect a single ingesting instance through a lease in `ClickHouse`, so instances running
+    /// side by side during a redeploy do not both ingest (default: false)
+    #[clap(long, env = "ENABLE_LEADER_ELECTION", default_value = "false")]
+    pub enable_leader_election: bool,
+
+    /// Name this instance holds the lease under; defaults to the host name and process id
+    #[clap(long, env = "LEADER_INSTANCE_ID")]
+    pub leader_instance_id: Option<String>,
+
+    /// Seconds without a renewal after which a lease expires and another instance takes over
+    /// (default: 30)
+    #[clap(
+        long,
+        env = "LEADER_LEASE_TTL_SECS",
+        default_value = "30",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub leader_lease_ttl_secs: u64,
+
+    /// Lease renewal interval in seconds (default: 5)
+    #[clap(
+        long,
+        env = "LEADER_RENEW_INTERVAL_SECS",
+        default_value = "5",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub leader_renew_interval_secs: u64,
 }
 
 #[cfg(test)]
@@ -225,6 +1313,7 @@ mod tests {
     //! Tests that modify environment variables need to be run with --test-threads=1
     //! to avoid interference between parallel test execution.
     use super::Opts;
+    use alloy_primitives::Address;
     use clap::Parser;
     use serial_test::serial;
 
@@ -295,13 +1384,152 @@ mod tests {
             env::remove_var("BATCH_PROOF_TIMEOUT_SECS");
             env::remove_var("INSTATUS_MONITORS_ENABLED");
             env::remove_var("ALLOWED_ORIGINS");
+            env::remove_var("CORS_ALLOWED_METHODS");
+            env::remove_var("CORS_MAX_AGE_SECS");
+            env::remove_var("API_GRPC_PORT");
+            env::remove_var("HSTS_MAX_AGE_SECS");
+            env::remove_var("API_COMPRESSION");
+            env::remove_var("API_ETAG");
+            env::remove_var("HSTS_INCLUDE_SUBDOMAINS");
+            env::remove_var("CONTENT_TYPE_NOSNIFF");
+            env::remove_var("REFERRER_POLICY");
             env::remove_var("RATE_LIMIT_MAX_REQUESTS");
             env::remove_var("RATE_LIMIT_PERIOD_SECS");
             env::remove_var("GAP_FINALIZATION_BUFFER_BLOCKS");
             env::remove_var("GAP_STARTUP_LOOKBACK_BLOCKS");
             env::remove_var("GAP_CONTINUOUS_LOOKBACK_BLOCKS");
+            env::remove_var("GAP_BATCH_LOOKBACK_BLOCKS");
             env::remove_var("GAP_POLL_INTERVAL_SECS");
             env::remove_var("GAP_DRY_RUN");
+            env::remove_var("GAP_BACKFILL_MAX_RPS");
+            env::remove_var("INCIDENT_STATE_DIR");
+            env::remove_var("INSTATUS_PROVISION_COMPONENTS");
+            env::remove_var("ENABLE_DATA_QUALITY_CHECKS");
+            env::remove_var("DATA_QUALITY_INTERVAL_SECS");
+            env::remove_var("DATA_QUALITY_LOOKBACK_BLOCKS");
+            env::remove_var("ENABLE_PROVE_COST_BACKFILL");
+            env::remove_var("PROVE_COST_BACKFILL_INTERVAL_SECS");
+            env::remove_var("MIGRATE_ONLY");
+            env::remove_var("SEED_FIXTURES");
+            env::remove_var("CHECK_CONFIG");
+            env::remove_var("REPROCESS");
+            env::remove_var("SNAPSHOT_EXPORT");
+            env::remove_var("SNAPSHOT_IMPORT");
+            env::remove_var("SNAPSHOT_URL");
+            env::remove_var("SNAPSHOT_ACCESS_KEY_ID");
+            env::remove_var("SNAPSHOT_SECRET_ACCESS_KEY");
+            env::remove_var("SNAPSHOT_FORMAT");
+            env::remove_var("SNAPSHOT_CHUNK_ROWS");
+            env::remove_var("REPROCESS_BATCH_SIZE");
+            env::remove_var("DEV_MODE");
+            env::remove_var("FIXTURE_L2_BLOCKS");
+            env::remove_var("ALLOW_DIRTY_SCHEMA");
+            env::remove_var("API_CACHE_TTL_DASHBOARD_SECS");
+            env::remove_var("API_CACHE_TTL_TABLE_SECS");
+            env::remove_var("API_PREWARM_INTERVAL_SECS");
+            env::remove_var("API_PREWARM_PATHS");
+            env::remove_var("API_QUERY_BUDGET_MAX_QUERIES");
+            env::remove_var("API_QUERY_BUDGET_MS");
+            env::remove_var("INSTATUS_PIPELINE_COMPONENT_ID");
+            env::remove_var("INSTATUS_PIPELINE_MONITOR_THRESHOLD_SECS");
+            env::remove_var("CLICKHOUSE_REPLICA_URLS");
+            env::remove_var("CLICKHOUSE_STICKY_AGGREGATES");
+            env::remove_var("CLICKHOUSE_QUERY_TIMEOUT_SECS");
+            env::remove_var("CLICKHOUSE_MAX_CONCURRENT_QUERIES");
+            env::remove_var("CLICKHOUSE_READ_USERNAME");
+            env::remove_var("CLICKHOUSE_READ_PASSWORD");
+            env::remove_var("CLICKHOUSE_WRITE_USERNAME");
+            env::remove_var("CLICKHOUSE_WRITE_PASSWORD");
+            env::remove_var("CLICKHOUSE_ENFORCE_READ_ONLY");
+            env::remove_var("BUILDER_FINGERPRINTS");
+            env::remove_var("ENABLE_ETH_PRICE_SNAPSHOTS");
+            env::remove_var("ENABLE_BOND_LEDGER");
+            env::remove_var("BOND_LEDGER_INTERVAL_SECS");
+            env::remove_var("BOND_LEDGER_LOOKBACK_BLOCKS");
+            env::remove_var("ENABLE_SEQUENCER_DOWNTIME");
+            env::remove_var("SEQUENCER_DOWNTIME_INTERVAL_SECS");
+            env::remove_var("SEQUENCER_DOWNTIME_MAX_GAP_SECS");
+            env::remove_var("SEQUENCER_DOWNTIME_LOOKBACK_HOURS");
+            env::remove_var("ETH_PRICE_SNAPSHOT_INTERVAL_SECS");
+            env::remove_var("ENABLE_TAIKO_PRICE_SNAPSHOTS");
+            env::remove_var("TAIKO_PRICE_URL");
+            env::remove_var("ENABLE_OPERATOR_BALANCES");
+            env::remove_var("OPERATOR_BALANCE_INTERVAL_SECS");
+            env::remove_var("ENABLE_OPERATOR_HISTORY");
+            env::remove_var("OPERATOR_HISTORY_INTERVAL_SECS");
+            env::remove_var("OPERATOR_MIN_ETH_BALANCE_WEI");
+            env::remove_var("TAIKO_TOKEN_ADDRESS");
+            env::remove_var("OPERATOR_MIN_TAIKO_BALANCE_WEI");
+            env::remove_var("INSTATUS_OPERATOR_BALANCE_COMPONENT_ID");
+            env::remove_var("INSTATUS_BATCH_CADENCE_COMPONENT_ID");
+            env::remove_var("BATCH_CADENCE_WARNING_SECS");
+            env::remove_var("BATCH_CADENCE_ANCHOR_AGE_PCT");
+            env::remove_var("INSTATUS_GAS_SATURATION_COMPONENT_ID");
+            env::remove_var("GAS_SATURATION_THRESHOLD_PCT");
+            env::remove_var("GAS_SATURATION_WINDOW_SECS");
+            env::remove_var("INSTATUS_ANCHOR_MISMATCH_COMPONENT_ID");
+            env::remove_var("ANCHOR_MISMATCH_WINDOW_SECS");
+            env::remove_var("INSTATUS_CANDIDATE_CHURN_COMPONENT_ID");
+            env::remove_var("CANDIDATE_CHURN_WINDOW_SECS");
+            env::remove_var("MIN_OPERATOR_CANDIDATES");
+            env::remove_var("PRECONF_FEED_URL");
+            env::remove_var("ADMIN_TOKEN");
+            env::remove_var("ADMIN_HOST");
+            env::remove_var("ADMIN_PORT");
+            env::remove_var("HEALTH_PORT");
+            env::remove_var("PRECONF_COMPARE_TIMEOUT_SECS");
+            env::remove_var("ENABLE_MEMPOOL_MONITOR");
+            env::remove_var("ENABLE_PROPOSAL_MEMPOOL_MONITOR");
+            env::remove_var("PROPOSAL_WATCH_ADDRESSES");
+            env::remove_var("ENABLE_ANOMALY_DETECTION");
+            env::remove_var("ANOMALY_INTERVAL_SECS");
+            env::remove_var("ANOMALY_WINDOW_SECS");
+            env::remove_var("ANOMALY_Z_THRESHOLD");
+            env::remove_var("ANOMALY_EWMA_ALPHA");
+            env::remove_var("INSTATUS_ANOMALY_COMPONENT_ID");
+            env::remove_var("ANOMALY_INCIDENT_WINDOW_SECS");
+            env::remove_var("ENABLE_FORCED_INCLUSION_QUEUE");
+            env::remove_var("FORCED_INCLUSION_QUEUE_INTERVAL_SECS");
+            env::remove_var("FORCED_INCLUSION_DEADLINE_MARGIN_BATCHES");
+            env::remove_var("ENABLE_VERIFICATION_BACKLOG");
+            env::remove_var("VERIFICATION_BACKLOG_INTERVAL_SECS");
+            env::remove_var("VERIFICATION_BACKLOG_THRESHOLD_BATCHES");
+            env::remove_var("ADDRESS_LABELS");
+            env::remove_var("ENABLE_ENS_LABELS");
+            env::remove_var("ADDRESS_LABELS_INTERVAL_SECS");
+            env::remove_var("INSTATUS_FORCED_INCLUSION_COMPONENT_ID");
+            env::remove_var("INSTATUS_VERIFICATION_BACKLOG_COMPONENT_ID");
+            env::remove_var("WRITE_BUFFER_MAX_ROWS");
+            env::remove_var("WRITE_BUFFER_FLUSH_INTERVAL_MS");
+            env::remove_var("SPILL_PATH");
+            env::remove_var("SPILL_MAX_BYTES");
+            env::remove_var("SPILL_REPLAY_INTERVAL_SECS");
+            env::remove_var("ENABLE_DEDUP_VERIFICATION");
+            env::remove_var("DEDUP_VERIFY_INTERVAL_SECS");
+            env::remove_var("DEDUP_VERIFY_LOOKBACK_HOURS");
+            env::remove_var("ENABLE_FEE_RECONCILIATION");
+            env::remove_var("FEE_RECONCILE_PERIOD_SECS");
+            env::remove_var("FEE_RECONCILE_TOLERANCE_WEI");
+            env::remove_var("ENABLE_LEADER_ELECTION");
+            env::remove_var("LEADER_INSTANCE_ID");
+            env::remove_var("LEADER_LEASE_TTL_SECS");
+            env::remove_var("LEADER_RENEW_INTERVAL_SECS");
+            env::remove_var("PUBLIC_RPC_URLS");
+            env::remove_var("PUBLIC_RPC_MAX_BLOCK_LAG");
+            env::remove_var("INSTATUS_PUBLIC_RPC_COMPONENT_IDS");
+            env::remove_var("BATCH_VERIFY_COOLDOWN_MARGIN_SECS");
+            env::remove_var("LOG_FORMAT");
+            env::remove_var("LOG_SAMPLE_WINDOW_SECS");
+            env::remove_var("L1_STALL_TIMEOUT_SECS");
+            env::remove_var("L2_STALL_TIMEOUT_SECS");
+            env::remove_var("STREAM_CHANNEL_CAPACITY");
+            env::remove_var("STREAM_CHANNEL_POLICIES");
+            env::remove_var("RATE_LIMIT_BURST");
+            env::remove_var("RATE_LIMIT_ROUTE_WEIGHTS");
+            env::remove_var("TAIKO_FORK_NAME");
+            env::remove_var("STATUS_PAGE_ENABLED");
+            env::remove_var("TAIKO_ADDRESS_FORKS");
+            env::remove_var("GUARDIAN_VERIFIER_ADDRESSES");
         }
 
         let args = base_args();
@@ -310,15 +1538,459 @@ mod tests {
         assert_eq!(opts.instatus.monitor_poll_interval_secs, 30);
         assert_eq!(opts.instatus.l1_monitor_threshold_secs, 600);
         assert_eq!(opts.instatus.l2_monitor_threshold_secs, 600);
-        assert_eq!(opts.instatus.batch_proof_timeout_secs, 10800);
+        assert_eq!(opts.instatus.batch_proof_timeout_secs, None);
         assert_eq!(opts.gap_finalization_buffer_blocks, 12);
         assert_eq!(opts.gap_startup_lookback_blocks, 128);
         assert_eq!(opts.gap_continuous_lookback_blocks, 32);
+        assert_eq!(opts.gap_batch_lookback_blocks, 7200);
         assert_eq!(opts.gap_poll_interval_secs, 30);
         assert_eq!(opts.gap_initial_delay_secs, 30);
         assert!(!opts.gap_dry_run);
         assert_eq!(opts.gap_min_l1_block, 1);
         assert_eq!(opts.gap_min_l2_block, 1);
+        assert_eq!(opts.gap_backfill_max_rps, 20);
+        assert!(opts.instatus.state_dir.is_none());
+        assert!(!opts.instatus.provision_components);
+        assert!(opts.enable_data_quality_checks);
+        assert_eq!(opts.data_quality_interval_secs, 300);
+        assert_eq!(opts.data_quality_lookback_blocks, 7200);
+        assert!(opts.enable_prove_cost_backfill);
+        assert_eq!(opts.prove_cost_backfill_interval_secs, 300);
+        assert!(!opts.migrate_only);
+        assert!(!opts.seed_fixtures);
+        assert!(!opts.check_config);
+        assert!(!opts.reprocess);
+        assert!(!opts.snapshot_export);
+        assert!(!opts.snapshot_import);
+        assert!(!opts.dev);
+        assert!(opts.snapshot.url.is_none());
+        assert!(opts.snapshot.access_key_id.is_none());
+        assert!(opts.snapshot.secret_access_key.is_none());
+        assert_eq!(opts.snapshot.format, super::SnapshotFormat::Native);
+        assert_eq!(opts.snapshot.chunk_rows, 1_000_000);
+        assert_eq!(opts.reprocess_batch_size, 168);
+        assert_eq!(opts.fixture_l2_blocks, 3600);
+        assert!(!opts.allow_dirty_schema);
+        assert!(opts.admin_token.is_none());
+        assert_eq!(opts.admin_host, "127.0.0.1");
+        assert_eq!(opts.admin_port, 3001);
+        assert_eq!(opts.health_port, None);
+        assert_eq!(opts.api.cache_ttl_dashboard_secs, 30);
+        assert_eq!(opts.api.cache_ttl_table_secs, 10);
+        assert_eq!(opts.api.prewarm_interval_secs, 25);
+        assert_eq!(opts.api.prewarm_paths.len(), 4);
+        assert_eq!(opts.api.query_budget_max_queries, 16);
+        assert_eq!(opts.api.query_budget_ms, 8000);
+        assert!(opts.instatus.pipeline_component_id.is_empty());
+        assert_eq!(opts.instatus.pipeline_monitor_threshold_secs, 300);
+        assert_eq!(opts.write_buffer_max_rows, 100);
+        assert_eq!(opts.write_buffer_flush_interval_ms, 1000);
+        assert_eq!(opts.spill_path, None);
+        assert_eq!(opts.spill_max_bytes, 268_435_456);
+        assert_eq!(opts.spill_replay_interval_secs, 10);
+        assert!(opts.enable_dedup_verification);
+        assert_eq!(opts.dedup_verify_interval_secs, 3600);
+        assert_eq!(opts.dedup_verify_lookback_hours, 24);
+        assert!(!opts.enable_fee_reconciliation);
+        assert_eq!(opts.fee_reconcile_period_secs, 3600);
+        assert_eq!(opts.fee_reconcile_tolerance_wei, 1_000_000_000_000_000);
+        assert!(!opts.enable_leader_election);
+        assert!(opts.leader_instance_id.is_none());
+        assert_eq!(opts.leader_lease_ttl_secs, 30);
+        assert_eq!(opts.leader_renew_interval_secs, 5);
+        assert!(opts.rpc.public_urls.is_empty());
+        assert_eq!(opts.rpc.public_max_block_lag, 10);
+        assert!(opts.instatus.public_rpc_component_ids.is_empty());
+        assert_eq!(opts.instatus.batch_verify_cooldown_margin_secs, 600);
+        assert!(opts.clickhouse.replica_urls.is_empty());
+        assert!(!opts.clickhouse.sticky_aggregates);
+        assert_eq!(opts.clickhouse.query_timeout_secs, 30);
+        assert_eq!(opts.clickhouse.max_concurrent_queries, 64);
+        assert!(!opts.clickhouse.has_write_credentials());
+        assert!(!opts.clickhouse.enforce_read_only);
+        assert!(opts.enable_eth_price_snapshots);
+        assert_eq!(opts.eth_price_snapshot_interval_secs, 300);
+        assert!(opts.enable_taiko_price_snapshots);
+        assert_eq!(
+            opts.taiko_price_url,
+            "https://api.coingecko.com/api/v3/simple/price?ids=taiko&vs_currencies=eth,usd"
+        );
+        assert!(opts.enable_bond_ledger);
+        assert_eq!(opts.bond_ledger_interval_secs, 60);
+        assert_eq!(opts.bond_ledger_lookback_blocks, 7200);
+        assert!(opts.enable_sequencer_downtime);
+        assert_eq!(opts.sequencer_downtime_interval_secs, 300);
+        assert_eq!(opts.sequencer_downtime_max_gap_secs, 60);
+        assert_eq!(opts.sequencer_downtime_lookback_hours, 6);
+        assert!(opts.enable_operator_balances);
+        assert_eq!(opts.operator_balance_interval_secs, 300);
+        assert!(opts.enable_operator_history);
+        assert_eq!(opts.operator_history_interval_secs, 60);
+        assert_eq!(opts.operator_min_eth_balance_wei, 1_000_000_000_000_000_000);
+        assert!(opts.taiko_token_address.is_none());
+        assert_eq!(opts.operator_min_taiko_balance_wei, 0);
+        assert!(opts.instatus.operator_balance_component_id.is_empty());
+        assert!(opts.instatus.bond_balance_component_id.is_empty());
+        assert_eq!(opts.instatus.bond_low_batches, 3);
+        assert!(opts.instatus.batch_cadence_component_id.is_empty());
+        assert_eq!(opts.instatus.batch_cadence_warning_secs, 3600);
+        assert_eq!(opts.instatus.batch_cadence_anchor_age_pct, 80);
+        assert!(opts.instatus.gas_saturation_component_id.is_empty());
+        assert_eq!(opts.instatus.gas_saturation_threshold_pct, 90);
+        assert_eq!(opts.instatus.gas_saturation_window_secs, 900);
+        assert!(opts.instatus.anchor_mismatch_component_id.is_empty());
+        assert_eq!(opts.instatus.anchor_mismatch_window_secs, 3600);
+        assert!(opts.instatus.candidate_churn_component_id.is_empty());
+        assert_eq!(opts.instatus.candidate_churn_window_secs, 3600);
+        assert_eq!(opts.instatus.min_operator_candidates, 1);
+        assert!(opts.preconf_feed_url.is_none());
+        assert_eq!(opts.preconf_compare_timeout_secs, 120);
+        assert!(!opts.enable_mempool_monitor);
+        assert!(!opts.enable_proposal_mempool_monitor);
+        assert!(opts.proposal_watch_addresses.is_empty());
+        assert!(opts.instatus.proposal_inclusion_component_id.is_empty());
+        assert_eq!(opts.instatus.proposal_pending_threshold_secs, 120);
+        assert!(opts.enable_anomaly_detection);
+        assert_eq!(opts.anomaly_interval_secs, 300);
+        assert_eq!(opts.anomaly_window_secs, 3600);
+        assert_eq!(opts.anomaly_z_threshold, 4.0);
+        assert_eq!(opts.anomaly_ewma_alpha, 0.1);
+        assert!(opts.instatus.anomaly_component_id.is_empty());
+        assert_eq!(opts.instatus.anomaly_incident_window_secs, 3600);
+        assert!(opts.enable_forced_inclusion_queue);
+        assert_eq!(opts.forced_inclusion_queue_interval_secs, 60);
+        assert_eq!(opts.forced_inclusion_deadline_margin_batches, 2);
+        assert!(opts.enable_verification_backlog);
+        assert_eq!(opts.verification_backlog_interval_secs, 60);
+        assert_eq!(opts.verification_backlog_threshold_batches, 100);
+        assert!(opts.instatus.verification_backlog_component_id.is_empty());
+        assert!(opts.address_labels.is_empty());
+        assert!(!opts.enable_ens_labels);
+        assert_eq!(opts.address_labels_interval_secs, 3600);
+        assert!(opts.instatus.forced_inclusion_component_id.is_empty());
+        assert_eq!(opts.log.format, super::LogFormat::Pretty);
+        assert_eq!(opts.log.sample_window_secs, 60);
+        assert_eq!(opts.rpc.l1_stall_timeout_secs, 60);
+        assert_eq!(opts.rpc.l2_stall_timeout_secs, 30);
+        assert_eq!(opts.rpc.stream_channel_capacity, 1024);
+        assert_eq!(
+            opts.rpc.stream_channel_policies,
+            vec![
+                ("pending_txs".to_owned(), super::OverflowPolicy::DropOldest),
+                ("pending_proposals".to_owned(), super::OverflowPolicy::DropOldest),
+            ]
+        );
+        assert_eq!(opts.api.rate_limit_burst, None);
+        assert_eq!(opts.api.rate_limit_route_weights, vec![("/dashboard-data".to_owned(), 10)]);
+        assert!(opts.api.builder_fingerprints.is_empty());
+        assert!(!opts.api.status_page);
+        assert_eq!(opts.api.cors_allowed_methods, vec!["GET"]);
+        assert_eq!(opts.api.cors_max_age_secs, None);
+        assert_eq!(opts.api.grpc_port, None);
+        assert_eq!(opts.api.hsts_max_age_secs, None);
+        assert!(!opts.api.hsts_include_subdomains);
+        assert!(opts.api.compression);
+        assert!(opts.api.etag);
+        assert!(opts.api.content_type_nosniff);
+        assert_eq!(opts.api.shutdown_grace_secs, 30);
+        assert_eq!(opts.api.referrer_policy, "strict-origin-when-cross-origin");
+        assert_eq!(opts.taiko_addresses.fork_name, "pacaya");
+        assert!(opts.taiko_addresses.forks.is_empty());
+        assert!(opts.taiko_addresses.guardian_verifier_addresses.is_empty());
+        assert_eq!(opts.taiko_addresses.address_schedule().len(), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_taiko_address_forks() {
+        let mut args = base_args();
+        args.extend([
+            "--taiko-address-forks",
+            "shasta:2000:0x00000000000000000000000000000000000000a1:\
+             0x00000000000000000000000000000000000000a2:0x00000000000000000000000000000000000000a3",
+        ]);
+        let opts = Opts::try_parse_from(&args).unwrap();
+
+        let schedule = opts.taiko_addresses.address_schedule();
+        assert_eq!(schedule.len(), 2);
+        assert_eq!(schedule[0].name, "pacaya");
+        assert_eq!(schedule[0].activation_block, 0);
+        assert_eq!(schedule[0].inbox_address, opts.taiko_addresses.inbox_address);
+        assert_eq!(schedule[1].name, "shasta");
+        assert_eq!(schedule[1].activation_block, 2000);
+        assert_eq!(schedule[1].inbox_address, Address::with_last_byte(0xa1));
+
+        let mut args = base_args();
+        args.extend(["--taiko-address-forks", "shasta:2000:0x01"]);
+        assert!(Opts::try_parse_from(&args).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_dev_mode_conflicts_with_one_shot_modes() {
+        let mut args = base_args();
+        args.push("--dev");
+        assert!(Opts::try_parse_from(&args).unwrap().dev);
+
+        args.push("--seed-fixtures");
+        assert!(Opts::try_parse_from(&args).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_rate_limit_route_weights() {
+        let mut args = base_args();
+        args.extend([
+            "--rate-limit-burst",
+            "50",
+            "--rate-limit-route-weights",
+            "/dashboard-data=20,/leaderboards=5",
+        ]);
+        let opts = Opts::try_parse_from(&args).unwrap();
+        assert_eq!(opts.api.rate_limit_burst, Some(50));
+        assert_eq!(
+            opts.api.rate_limit_route_weights,
+            vec![("/dashboard-data".to_owned(), 20), ("/leaderboards".to_owned(), 5)]
+        );
+
+        let mut args = base_args();
+        args.extend(["--rate-limit-route-weights", "dashboard-data"]);
+        assert!(Opts::try_parse_from(&args).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_admin_token_is_trimmed() {
+        let mut args = base_args();
+        args.extend(["--admin-token", " secret\n"]);
+        let opts = Opts::try_parse_from(&args).unwrap();
+        assert_eq!(opts.admin_token.as_deref(), Some("secret"));
+
+        let mut args = base_args();
+        args.extend(["--admin-token", "  "]);
+        assert!(Opts::try_parse_from(&args).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_stream_channel_policies() {
+        let mut args = base_args();
+        args.extend(["--stream-channel-policies", "l2_headers=drop-oldest,l1_headers=block"]);
+        let opts = Opts::try_parse_from(&args).unwrap();
+        assert_eq!(
+            opts.rpc.stream_channel_policies,
+            vec![
+                ("l2_headers".to_owned(), super::OverflowPolicy::DropOldest),
+                ("l1_headers".to_owned(), super::OverflowPolicy::Block),
+            ]
+        );
+
+        for policy in ["l2_headers", "l3_headers=block", "l2_headers=drop-newest"] {
+            let mut args = base_args();
+            args.extend(["--stream-channel-policies", policy]);
+            assert!(Opts::try_parse_from(&args).is_err());
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_cors_and_security_headers() {
+        let mut args = base_args();
+        args.extend([
+            "--cors-allowed-methods",
+            "get,post",
+            "--cors-max-age-secs",
+            "600",
+            "--hsts-max-age-secs",
+            "31536000",
+            "--referrer-policy",
+            "",
+        ]);
+        let opts = Opts::try_parse_from(&args).unwrap();
+        assert_eq!(opts.api.cors_allowed_methods, vec!["GET", "POST"]);
+        assert_eq!(opts.api.cors_max_age_secs, Some(600));
+        assert_eq!(opts.api.hsts_max_age_secs, Some(31_536_000));
+        assert!(opts.api.referrer_policy.is_empty());
+
+        let mut args = base_args();
+        args.extend(["--cors-allowed-methods", "CONNECT"]);
+        assert!(Opts::try_parse_from(&args).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_task_intervals_reject_zero() {
+        for flag in [
+            "--data-quality-interval-secs",
+            "--prove-cost-backfill-interval-secs",
+            "--bond-ledger-interval-secs",
+            "--sequencer-downtime-interval-secs",
+            "--operator-balance-interval-secs",
+            "--operator-history-interval-secs",
+            "--eth-price-snapshot-interval-secs",
+            "--anomaly-interval-secs",
+            "--forced-inclusion-queue-interval-secs",
+            "--verification-backlog-interval-secs",
+            "--address-labels-interval-secs",
+            "--verify-dual-interval-secs",
+            "--dedup-verify-interval-secs",
+            "--fee-reconcile-period-secs",
+        ] {
+            let mut args = base_args();
+            args.extend([flag, "0"]);
+            assert!(Opts::try_parse_from(&args).is_err(), "{flag} accepted 0");
+
+            let mut args = base_args();
+            args.extend([flag, "1"]);
+            assert!(Opts::try_parse_from(&args).is_ok(), "{flag} rejected 1");
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_log_format() {
+        let mut args = base_args();
+        args.extend(["--log-format", "json", "--log-sample-window-secs", "0"]);
+        let opts = Opts::try_parse_from(&args).unwrap();
+        assert_eq!(opts.log.format, super::LogFormat::Json);
+        assert_eq!(opts.log.sample_window_secs, 0);
+
+        let mut args = base_args();
+        args.extend(["--log-format", "xml"]);
+        assert!(Opts::try_parse_from(&args).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_clickhouse_replica_urls_are_comma_separated() {
+        let mut args = base_args();
+        args.extend([
+            "--clickhouse-replica-urls",
+            "http://replica-a:8123,http://replica-b:8123",
+            "--clickhouse-sticky-aggregates",
+            "--clickhouse-query-timeout-secs",
+            "5",
+            "--clickhouse-max-concurrent-queries",
+            "0",
+        ]);
+
+        let opts = Opts::try_parse_from(&args).expect("failed to parse opts");
+
+        assert_eq!(
+            opts.clickhouse.replica_urls,
+            vec![
+                url::Url::parse("http://replica-a:8123").unwrap(),
+                url::Url::parse("http://replica-b:8123").unwrap()
+            ]
+        );
+        assert!(opts.clickhouse.sticky_aggregates);
+        assert_eq!(opts.clickhouse.query_timeout_secs, 5);
+        assert_eq!(opts.clickhouse.max_concurrent_queries, 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_clickhouse_read_and_write_credentials() {
+        let opts = Opts::try_parse_from(base_args()).expect("failed to parse opts");
+        let shared = (opts.clickhouse.username.clone(), opts.clickhouse.password.clone());
+        assert_eq!(opts.clickhouse.read_credentials(), shared);
+        assert_eq!(opts.clickhouse.write_credentials(), shared);
+
+        let mut args = base_args();
+        args.extend([
+            "--clickhouse-read-username",
+            "reader",
+            "--clickhouse-read-password",
+            "read-pass",
+            "--clickhouse-write-username",
+            "writer",
+        ]);
+        let opts = Opts::try_parse_from(&args).expect("failed to parse opts");
+        assert_eq!(
+            opts.clickhouse.read_credentials(),
+            ("reader".to_owned(), "read-pass".to_owned())
+        );
+        assert_eq!(opts.clickhouse.write_credentials(), ("writer".to_owned(), shared.1));
+        assert!(opts.clickhouse.has_write_credentials());
+    }
+
+    #[test]
+    #[serial]
+    fn test_verify_dual_requires_secondary() {
+        let mut args = base_args();
+        args.push("--verify-dual");
+        assert!(Opts::try_parse_from(&args).is_err());
+
+        args.extend(["--clickhouse-secondary-url", "http://new-cluster:8123"]);
+        let opts = Opts::try_parse_from(&args).expect("failed to parse opts");
+        assert!(opts.verify_dual);
+        assert_eq!(
+            opts.clickhouse.secondary_url,
+            Some(url::Url::parse("http://new-cluster:8123").unwrap())
+        );
+        assert_eq!(opts.clickhouse.secondary_db, None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_rpc_urls_list_primary_first() {
+        let mut args = base_args();
+        let l1 = args.iter().position(|arg| *arg == "--l1-url").unwrap();
+        args[l1 + 1] = "ws://primary,wss://fallback";
+
+        let opts = Opts::try_parse_from(&args).expect("failed to parse opts");
+
+        assert_eq!(
+            opts.rpc.l1_url,
+            vec![
+                url::Url::parse("ws://primary").unwrap(),
+                url::Url::parse("wss://fallback").unwrap()
+            ]
+        );
+        assert_eq!(opts.rpc.l2_url, vec![url::Url::parse("http://l2").unwrap()]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_public_rpc_lists_are_comma_separated() {
+        let mut args = base_args();
+        args.extend([
+            "--public-urls",
+            "https://rpc.a.example,https://rpc.b.example",
+            "--public-rpc-component-ids",
+            "comp-a,comp-b",
+        ]);
+
+        let opts = Opts::try_parse_from(&args).expect("failed to parse opts");
+
+        assert_eq!(
+            opts.rpc.public_urls,
+            vec![
+                url::Url::parse("https://rpc.a.example").unwrap(),
+                url::Url::parse("https://rpc.b.example").unwrap()
+            ]
+        );
+        assert_eq!(opts.instatus.public_rpc_component_ids, vec!["comp-a", "comp-b"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_maintenance_windows_are_comma_separated() {
+        let mut args = base_args();
+        args.extend([
+            "--maintenance-windows",
+            "2025-01-01T10:00:00Z/2025-01-01T12:00:00Z/comp-a+comp-b,2025-02-01T00:00:00Z/2025-02-01T01:00:00Z",
+        ]);
+
+        let opts = Opts::try_parse_from(&args).expect("failed to parse opts");
+
+        assert_eq!(
+            opts.instatus.maintenance_windows,
+            vec![
+                "2025-01-01T10:00:00Z/2025-01-01T12:00:00Z/comp-a+comp-b",
+                "2025-02-01T00:00:00Z/2025-02-01T01:00:00Z"
+            ]
+        );
     }
 
     #[test]
@@ -367,7 +2039,7 @@ mod tests {
         assert_eq!(opts.instatus.monitor_poll_interval_secs, 42);
         assert_eq!(opts.instatus.l1_monitor_threshold_secs, 33);
         assert_eq!(opts.instatus.l2_monitor_threshold_secs, 44);
-        assert_eq!(opts.instatus.batch_proof_timeout_secs, 99);
+        assert_eq!(opts.instatus.batch_proof_timeout_secs, Some(99));
         assert_eq!(opts.api.host, "127.0.0.1");
         assert_eq!(opts.api.port, 3000);
         assert_eq!(
@@ -404,6 +2076,27 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial]
+    fn test_table_prefix() {
+        use std::env;
+        unsafe {
+            env::remove_var("CLICKHOUSE_TABLE_PREFIX");
+        }
+
+        let opts = Opts::try_parse_from(base_args()).unwrap();
+        assert_eq!(opts.clickhouse.table_prefix, "");
+
+        let mut args = base_args();
+        args.extend(["--clickhouse-table-prefix", "staging_"]);
+        let opts = Opts::try_parse_from(&args).unwrap();
+        assert_eq!(opts.clickhouse.table_prefix, "staging_");
+
+        let mut args = base_args();
+        args.extend(["--clickhouse-table-prefix", "staging; DROP"]);
+        assert!(Opts::try_parse_from(&args).is_err());
+    }
+
     #[test]
     #[serial]
     fn test_all_origins_included() {
@@ -0,0 +1,161 @@
+//! Address labels
+//!
+//! Sequencer and proposer addresses are hard to tell apart on the dashboard. This task
+//! periodically writes the configured static labels to the `address_labels` table and, when ENS
+//! labels are enabled, looks up the verified ENS name of every sequencer and proposer active
+//! within [`ACTIVE_WINDOW`] that has no static label. The API joins the labels into the
+//! responses listing these addresses.
+
+use std::time::Duration;
+
+use alloy_primitives::Address;
+use chrono::Utc;
+use clickhouse::{AddressBytes, AddressLabelRow, ClickhouseReader, ClickhouseWriter};
+use extractor::Extractor;
+use eyre::{Result, eyre};
+use primitives::label::LabelSource;
+use tracing::{debug, error, info};
+
+/// Addresses active within this window are looked up on ENS
+pub const ACTIVE_WINDOW: chrono::Duration = chrono::Duration::days(7);
+
+/// Parse static labels given as `ADDRESS=LABEL`
+pub fn parse_static_labels(entries: &[String]) -> Result<Vec<(Address, String)>> {
+    entries
+        .iter()
+        .map(|entry| {
+            let (address, label) = entry
+                .split_once('=')
+                .ok_or_else(|| eyre!("expected ADDRESS=LABEL, got '{entry}'"))?;
+            let address = address
+                .trim()
+                .parse::<Address>()
+                .map_err(|e| eyre!("invalid address in '{entry}': {e}"))?;
+            let label = label.trim();
+            if label.is_empty() {
+                return Err(eyre!("empty label in '{entry}'"));
+            }
+            Ok((address, label.to_owned()))
+        })
+        .collect()
+}
+
+/// Address label methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic address label refresh task
+    pub fn start_address_labels_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let ens = if self.enable_ens_labels {
+            Some((self.clickhouse_reader.as_ref()?.clone(), self.extractor.clone()))
+        } else {
+            None
+        };
+        let static_labels = self.address_labels.clone();
+        let interval_secs = self.address_labels_interval_secs;
+
+        info!(
+            interval_secs,
+            static_labels = static_labels.len(),
+            ens = ens.is_some(),
+            "Starting address labels task"
+        );
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let ens = ens.as_ref().map(|(reader, extractor)| (reader, extractor));
+                match refresh_address_labels(&writer, ens, &static_labels).await {
+                    Ok(count) => debug!(count, "Refreshed address labels"),
+                    Err(e) => error!(err = %e, "Address label refresh failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Write the static labels and the ENS names of the active sequencers and proposers without a
+/// static label, returning the number of labels written
+pub async fn refresh_address_labels(
+    writer: &ClickhouseWriter,
+    ens: Option<(&ClickhouseReader, &Extractor)>,
+    static_labels: &[(Address, String)],
+) -> Result<usize> {
+    let now = Utc::now();
+    let mut rows = static_label_rows(now.timestamp() as u64, static_labels);
+
+    if let Some((reader, extractor)) = ens {
+        for address in reader.get_active_operator_addresses(now - ACTIVE_WINDOW).await? {
+            if rows.iter().any(|row| row.address == address) {
+                continue;
+            }
+            match extractor.lookup_ens_name(Address::from(address)).await {
+                Ok(Some(name)) => rows.push(AddressLabelRow {
+                    address,
+                    label: name,
+                    source: LabelSource::Ens as u8,
+                    updated_ts: now.timestamp() as u64,
+                }),
+                Ok(None) => {}
+                Err(e) => debug!(?address, err = %e, "ENS reverse lookup failed"),
+            }
+        }
+    }
+
+    writer.insert_address_labels(&rows).await?;
+    Ok(rows.len())
+}
+
+/// Rows of the static labels refreshed at `updated_ts`
+pub fn static_label_rows(updated_ts: u64, labels: &[(Address, String)]) -> Vec<AddressLabelRow> {
+    labels
+        .iter()
+        .map(|(address, label)| AddressLabelRow {
+            address: AddressBytes::from(*address),
+            label: label.clone(),
+            source: LabelSource::Static as u8,
+            updated_ts,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_static_labels() {
+        let entries = vec![
+            "0x0101010101010101010101010101010101010101=Gattaca".to_owned(),
+            " 0x0202020202020202020202020202020202020202 = Chainbound ".to_owned(),
+        ];
+        let labels = parse_static_labels(&entries).unwrap();
+        assert_eq!(
+            labels,
+            vec![
+                (Address::repeat_byte(1), "Gattaca".to_owned()),
+                (Address::repeat_byte(2), "Chainbound".to_owned()),
+            ]
+        );
+
+        let rows = static_label_rows(1_700_000_000, &labels);
+        assert_eq!(rows[1].address, AddressBytes([2u8; 20]));
+        assert_eq!(rows[1].source, LabelSource::Static as u8);
+        assert_eq!(rows[1].updated_ts, 1_700_000_000);
+    }
+
+    #[test]
+    fn rejects_malformed_static_labels() {
+        assert!(parse_static_labels(&["Gattaca".to_owned()]).is_err());
+        assert!(parse_static_labels(&["0x01=Gattaca".to_owned()]).is_err());
+        assert!(
+            parse_static_labels(&["0x0101010101010101010101010101010101010101=".to_owned()])
+                .is_err()
+        );
+    }
+}
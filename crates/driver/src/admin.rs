@@ -0,0 +1,335 @@
+//! Admin endpoints of the indexer
+//!
+//! Operational actions that otherwise need a redeploy or direct database access: backfilling a
+//! block range, flushing the write buffers, re-running a migration, force-resolving an incident
+//! that a monitor failed to close and scheduling incident maintenance windows. The server only
+//! starts when an admin token is configured, and every route requires it as a bearer token.
+
+use std::net::SocketAddr;
+
+use api_types::{ErrorCode, ErrorResponse};
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use clickhouse::ClickhouseWriter;
+use extractor::Extractor;
+use incident::{
+    MaintenanceWindow, client::Client as IncidentClient, helpers::build_resolve_payload,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::{
+    backfill::BackfillBudget,
+    gap_detection::{backfill_l1_blocks, backfill_l2_blocks},
+};
+
+/// Largest block range a single backfill request may cover
+pub const MAX_BACKFILL_BLOCKS: u64 = 10_000;
+
+/// Chain a backfill targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Chain {
+    /// Ethereum L1
+    L1,
+    /// Taiko L2
+    L2,
+}
+
+/// Body of `POST /admin/backfill`
+#[derive(Debug, Deserialize)]
+pub struct BackfillRequest {
+    /// Chain to backfill
+    pub chain: Chain,
+    /// First block of the range (inclusive)
+    pub from_block: u64,
+    /// Last block of the range (inclusive)
+    pub to_block: u64,
+}
+
+/// Body of `POST /admin/incidents/resolve`
+#[derive(Debug, Deserialize)]
+pub struct ResolveIncidentRequest {
+    /// Component whose incident should be resolved
+    pub component_id: String,
+    /// Incident to resolve; the open incident of the component is looked up when omitted
+    #[serde(default)]
+    pub incident_id: Option<String>,
+}
+
+/// Outcome of an admin action
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct AdminResponse {
+    /// Human readable description of what was done
+    pub message: String,
+}
+
+impl AdminResponse {
+    fn new(message: impl Into<String>) -> Json<Self> {
+        Json(Self { message: message.into() })
+    }
+}
+
+/// State shared by the admin handlers
+#[derive(Debug, Clone)]
+struct AdminState {
+    writer: Option<ClickhouseWriter>,
+    extractor: Extractor,
+    incident_client: IncidentClient,
+    enable_db_writes: bool,
+    budget: BackfillBudget,
+}
+
+impl AdminState {
+    fn writer(&self) -> Result<&ClickhouseWriter, ErrorResponse> {
+        self.writer.as_ref().ok_or_else(|| {
+            ErrorResponse::new(
+                ErrorCode::Conflict,
+                "Writer disabled",
+                StatusCode::CONFLICT,
+                "database writes are disabled",
+            )
+        })
+    }
+}
+
+/// Validate a backfill range and expand it into block numbers
+pub fn backfill_blocks(from_block: u64, to_block: u64) -> Result<Vec<u64>, String> {
+    if from_block > to_block {
+        return Err(format!("from_block {from_block} is after to_block {to_block}"));
+    }
+    if to_block - from_block >= MAX_BACKFILL_BLOCKS {
+        return Err(format!("range exceeds {MAX_BACKFILL_BLOCKS} blocks"));
+    }
+    Ok((from_block..=to_block).collect())
+}
+
+fn internal_error(action: &str, e: &eyre::Report) -> ErrorResponse {
+    error!(action, err = %e, "Admin action failed");
+    ErrorResponse::new(
+        ErrorCode::UpstreamError,
+        "Admin action failed",
+        StatusCode::BAD_GATEWAY,
+        e.to_string(),
+    )
+}
+
+/// List the incident maintenance windows
+async fn maintenance_windows(State(state): State<AdminState>) -> Json<Vec<MaintenanceWindow>> {
+    Json(state.incident_client.maintenance().windows())
+}
+
+/// Schedule a maintenance window during which monitors do not create incidents
+async fn add_maintenance_window(
+    State(state): State<AdminState>,
+    Json(window): Json<MaintenanceWindow>,
+) -> Result<(StatusCode, Json<AdminResponse>), ErrorResponse> {
+    let message = format!(
+        "maintenance window from {} to {} scheduled",
+        window.start.to_rfc3339(),
+        window.end.to_rfc3339()
+    );
+    info!(start = %window.start, end = %window.end, components = ?window.components, "Scheduled maintenance window");
+    state
+        .incident_client
+        .maintenance()
+        .add(window)
+        .map_err(|e| ErrorResponse::bad_request(ErrorCode::InvalidParams, e.to_string()))?;
+    Ok((StatusCode::CREATED, AdminResponse::new(message)))
+}
+
+/// Remove every maintenance window, resuming incident creation immediately
+async fn clear_maintenance_windows(State(state): State<AdminState>) -> Json<AdminResponse> {
+    let removed = state.incident_client.maintenance().clear();
+    info!(removed, "Cleared maintenance windows");
+    AdminResponse::new(format!("{removed} maintenance windows removed"))
+}
+
+/// Backfill a block range in the background, newest block first and within the shared RPC budget
+async fn backfill(
+    State(state): State<AdminState>,
+    Json(request): Json<BackfillRequest>,
+) -> Result<(StatusCode, Json<AdminResponse>), ErrorResponse> {
+    let blocks = backfill_blocks(request.from_block, request.to_block)
+        .map_err(|detail| ErrorResponse::bad_request(ErrorCode::InvalidRange, detail))?;
+    let writer = state.writer()?.clone();
+    let count = blocks.len();
+
+    info!(chain = ?request.chain, request.from_block, request.to_block, "Admin backfill requested");
+    tokio::spawn(async move {
+        let result = match request.chain {
+            Chain::L1 => {
+                backfill_l1_blocks(
+                    Some(&writer),
+                    &state.extractor,
+                    blocks,
+                    state.enable_db_writes,
+                    0,
+                    &state.budget,
+                )
+                .await
+            }
+            Chain::L2 => {
+                backfill_l2_blocks(
+                    Some(&writer),
+                    &state.extractor,
+                    blocks,
+                    state.enable_db_writes,
+                    0,
+                    &state.budget,
+                )
+                .await
+            }
+        };
+        match result {
+            Ok(()) => info!(chain = ?request.chain, count, "Admin backfill finished"),
+            Err(e) => error!(chain = ?request.chain, err = %e, "Admin backfill failed"),
+        }
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        AdminResponse::new(format!(
+            "backfilling {count} {:?} blocks from {} to {}",
+            request.chain, request.from_block, request.to_block
+        )),
+    ))
+}
+
+/// Write all buffered rows to `ClickHouse`
+async fn flush(State(state): State<AdminState>) -> Result<Json<AdminResponse>, ErrorResponse> {
+    state.writer()?.flush().await.map_err(|e| internal_error("flush", &e))?;
+    info!("Flushed write buffers via admin endpoint");
+    Ok(AdminResponse::new("write buffers flushed"))
+}
+
+/// Apply a migration again, e.g. after a table was dropped by hand
+async fn rerun_migration(
+    State(state): State<AdminState>,
+    Path(version): Path<u32>,
+) -> Result<Json<AdminResponse>, ErrorResponse> {
+    let name = state
+        .writer()?
+        .rerun_migration(version)
+        .await
+        .map_err(|e| internal_error("rerun-migration", &e))?;
+    info!(version, name, "Re-ran migration via admin endpoint");
+    Ok(AdminResponse::new(format!("migration {version:03} ({name}) applied")))
+}
+
+/// Resolve an incident regardless of what the monitors think
+///
+/// Monitors that still consider the incident open will resolve it again once they recover.
+async fn resolve_incident(
+    State(state): State<AdminState>,
+    Json(request): Json<ResolveIncidentRequest>,
+) -> Result<Json<AdminResponse>, ErrorResponse> {
+    let incident_id = match request.incident_id {
+        Some(id) => id,
+        None => state
+            .incident_client
+            .open_incident(&request.component_id)
+            .await
+            .map_err(|e| internal_error("resolve-incident", &e))?
+            .ok_or_else(|| {
+                ErrorResponse::not_found(format!(
+                    "no open incident for component {}",
+                    request.component_id
+                ))
+            })?,
+    };
+
+    state
+        .incident_client
+        .resolve_incident(&incident_id, &build_resolve_payload(&request.component_id))
+        .await
+        .map_err(|e| internal_error("resolve-incident", &e))?;
+    info!(%incident_id, component_id = %request.component_id, "Force-resolved incident");
+    Ok(AdminResponse::new(format!("incident {incident_id} resolved")))
+}
+
+/// Admin server methods for the Driver
+impl crate::driver::Driver {
+    /// Build the admin router, requiring `token` on every route
+    pub fn admin_router(&self, token: &str) -> Router {
+        let state = AdminState {
+            writer: self.clickhouse_writer.clone(),
+            extractor: self.extractor.clone(),
+            incident_client: self.incident_client.clone(),
+            enable_db_writes: self.enable_db_writes,
+            budget: self.backfill_budget.clone(),
+        };
+        let router = Router::new()
+            .route("/admin/backfill", post(backfill))
+            .route("/admin/flush", post(flush))
+            .route("/admin/migrations/:version/rerun", post(rerun_migration))
+            .route("/admin/incidents/resolve", post(resolve_incident))
+            .route(
+                "/admin/maintenance",
+                get(maintenance_windows)
+                    .post(add_maintenance_window)
+                    .delete(clear_maintenance_windows),
+            )
+            .with_state(state);
+        runtime::admin::protect(router, token)
+    }
+
+    /// Serve the admin endpoints on the configured address
+    pub fn start_admin_server(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let token = self.admin_token.as_deref()?;
+        let addr: SocketAddr = match format!("{}:{}", self.admin_host, self.admin_port).parse() {
+            Ok(addr) => addr,
+            Err(e) => {
+                error!(err = %e, "Invalid admin server address");
+                return None;
+            }
+        };
+        let app = self.admin_router(token);
+
+        let handle = tokio::spawn(async move {
+            info!("Starting admin server on {}", addr);
+            let listener = match tokio::net::TcpListener::bind(addr).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    error!(%addr, err = %e, "Failed to bind admin server");
+                    return;
+                }
+            };
+            if let Err(e) = axum::serve(listener, app).await {
+                error!(err = %e, "Admin server failed");
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_inclusive_range() {
+        assert_eq!(backfill_blocks(5, 7).unwrap(), vec![5, 6, 7]);
+        assert_eq!(backfill_blocks(5, 5).unwrap(), vec![5]);
+    }
+
+    #[test]
+    fn rejects_inverted_or_oversized_ranges() {
+        assert!(backfill_blocks(7, 5).is_err());
+        assert!(backfill_blocks(0, MAX_BACKFILL_BLOCKS).is_err());
+        assert_eq!(backfill_blocks(0, MAX_BACKFILL_BLOCKS - 1).unwrap().len(), 10_000);
+    }
+
+    #[test]
+    fn parses_backfill_request() {
+        let request: BackfillRequest =
+            serde_json::from_str(r#"{"chain": "l2", "from_block": 1, "to_block": 2}"#).unwrap();
+        assert_eq!(request.chain, Chain::L2);
+        assert!(serde_json::from_str::<BackfillRequest>(r#"{"chain": "l3"}"#).is_err());
+    }
+}
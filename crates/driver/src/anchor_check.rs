@@ -0,0 +1,115 @@
+//! Anchor block hash verification
+//!
+//! Every proposed batch commits to the L1 block it is anchored to, by number and by hash. When a
+//! batch is processed, the committed hash is compared with the hash of the indexed L1 block at
+//! that number. A mismatch means either the batch or the indexed L1 chain is wrong, and is
+//! recorded in the `anchor_mismatches` table. Batches anchored to a block that is not indexed
+//! are skipped.
+
+use chainio::ITaikoInbox::BatchProposed;
+use clickhouse::{AnchorMismatchRow, HashBytes};
+use tracing::{debug, error, info, warn};
+
+/// Anchor block verification methods for the Driver
+impl crate::driver::Driver {
+    /// Compare the anchor block hash of `batch` with the indexed L1 block and record a mismatch
+    pub async fn check_batch_anchor(&self, batch: &BatchProposed) {
+        let Some(reader) = &self.clickhouse_reader else {
+            return;
+        };
+
+        let anchor_block_id = batch.info.anchorBlockId;
+        let l1_block_hash = match reader.get_l1_block_hash(anchor_block_id).await {
+            Ok(Some(hash)) => hash,
+            Ok(None) => {
+                debug!(
+                    batch_id = batch.meta.batchId,
+                    anchor_block_id, "Anchor block not indexed, skipping anchor check"
+                );
+                return;
+            }
+            Err(e) => {
+                warn!(err = %e, anchor_block_id, "Failed to fetch anchor block hash");
+                return;
+            }
+        };
+
+        let Some(row) = anchor_mismatch(batch, l1_block_hash) else {
+            return;
+        };
+        error!(
+            batch_id = row.batch_id,
+            anchor_block_id,
+            anchor_block_hash = ?row.anchor_block_hash,
+            l1_block_hash = ?row.l1_block_hash,
+            "Batch anchor block hash does not match the indexed L1 block"
+        );
+
+        if !self.enable_db_writes {
+            info!(batch_id = row.batch_id, "🧪 DRY-RUN: Would insert anchor mismatch");
+            return;
+        }
+        if let Some(writer) = &self.clickhouse_writer &&
+            let Err(e) = writer.insert_anchor_mismatch(&row).await
+        {
+            error!(err = %e, batch_id = row.batch_id, "Failed to insert anchor mismatch");
+        }
+    }
+}
+
+/// Mismatch between the anchor block hash of `batch` and `l1_block_hash`, the hash of the indexed
+/// L1 block at its anchor block id, if they differ
+pub fn anchor_mismatch(
+    batch: &BatchProposed,
+    l1_block_hash: HashBytes,
+) -> Option<AnchorMismatchRow> {
+    let anchor_block_hash = HashBytes::from(batch.info.anchorBlockHash);
+    (anchor_block_hash != l1_block_hash).then_some(AnchorMismatchRow {
+        batch_id: batch.meta.batchId,
+        l1_block_number: batch.info.proposedIn,
+        anchor_block_id: batch.info.anchorBlockId,
+        anchor_block_hash,
+        l1_block_hash,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use chainio::ITaikoInbox::{BatchInfo, BatchMetadata};
+
+    fn batch(anchor_block_hash: B256) -> BatchProposed {
+        BatchProposed {
+            info: BatchInfo {
+                proposedIn: 120,
+                anchorBlockId: 115,
+                anchorBlockHash: anchor_block_hash,
+                ..Default::default()
+            },
+            meta: BatchMetadata { batchId: 7, ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matching_anchor_hash_is_not_a_mismatch() {
+        let batch = batch(B256::repeat_byte(1));
+        assert_eq!(anchor_mismatch(&batch, HashBytes([1u8; 32])), None);
+    }
+
+    #[test]
+    fn differing_anchor_hash_is_a_mismatch() {
+        let batch = batch(B256::repeat_byte(1));
+        assert_eq!(
+            anchor_mismatch(&batch, HashBytes([2u8; 32])),
+            Some(AnchorMismatchRow {
+                batch_id: 7,
+                l1_block_number: 120,
+                anchor_block_id: 115,
+                anchor_block_hash: HashBytes([1u8; 32]),
+                l1_block_hash: HashBytes([2u8; 32]),
+            })
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Anomaly detection on core metrics
+//!
+//! Sudden changes of throughput or pipeline latency often precede an outage, but the absolute
+//! thresholds of the incident monitors only fire once a metric is already out of bounds. This
+//! task periodically samples the average TPS, gas used per block, batch posting cadence and
+//! prove time over a trailing window and tracks an exponentially weighted moving average and
+//! variance of every metric. A sample more than the configured number of standard deviations
+//! away from the average is recorded in the `anomalies` table, where the API serves it to
+//! annotate the dashboard charts and the anomaly monitor picks it up to open an incident.
+//!
+//! The moving statistics live in memory, so after a restart every metric goes through
+//! [`WARMUP_SAMPLES`] samples again before anomalies are reported.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use clickhouse::{AnomalyRow, ClickhouseReader, ClickhouseWriter, TimeRange};
+use eyre::Result;
+use primitives::anomaly::AnomalyMetric;
+use tracing::{debug, error, info, warn};
+
+/// Samples a metric needs before its deviations are reported
+pub const WARMUP_SAMPLES: u32 = 10;
+
+/// Deviation of a sample from the moving statistics of the previous samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deviation {
+    /// Moving average of the previous samples
+    pub expected: f64,
+    /// Moving standard deviation of the previous samples
+    pub std_dev: f64,
+    /// Number of standard deviations between the sample and the moving average
+    pub z_score: f64,
+}
+
+/// Exponentially weighted moving average and variance of a metric
+#[derive(Debug, Clone, Copy)]
+pub struct Ewma {
+    alpha: f64,
+    mean: f64,
+    variance: f64,
+    samples: u32,
+}
+
+impl Ewma {
+    /// Create an empty average weighting every new sample by `alpha`
+    pub const fn new(alpha: f64) -> Self {
+        Self { alpha, mean: 0.0, variance: 0.0, samples: 0 }
+    }
+
+    /// Add `value` to the average. Returns its deviation from the previous samples once the
+    /// average is warmed up and the samples varied at all.
+    pub fn observe(&mut self, value: f64) -> Option<Deviation> {
+        if self.samples == 0 {
+            self.mean = value;
+            self.samples = 1;
+            return None;
+        }
+
+        let std_dev = self.variance.sqrt();
+        let deviation = (self.samples >= WARMUP_SAMPLES && std_dev > 0.0).then(|| Deviation {
+            expected: self.mean,
+            std_dev,
+            z_score: (value - self.mean) / std_dev,
+        });
+
+        let diff = value - self.mean;
+        let increment = self.alpha * diff;
+        self.mean += increment;
+        self.variance = (1.0 - self.alpha) * diff.mul_add(increment, self.variance);
+        self.samples = self.samples.saturating_add(1);
+        deviation
+    }
+}
+
+/// Moving statistics of every [`AnomalyMetric`]
+#[derive(Debug, Clone)]
+pub struct AnomalyDetector {
+    z_threshold: f64,
+    averages: [Ewma; AnomalyMetric::ALL.len()],
+}
+
+impl AnomalyDetector {
+    /// Create a detector reporting samples more than `z_threshold` standard deviations away from
+    /// the average, weighting every new sample by `alpha`
+    pub const fn new(z_threshold: f64, alpha: f64) -> Self {
+        Self { z_threshold, averages: [Ewma::new(alpha); AnomalyMetric::ALL.len()] }
+    }
+
+    /// Add the sample of `metric` taken at `ts`. Returns the anomaly to record if it deviates
+    /// beyond the threshold.
+    pub fn observe(&mut self, metric: AnomalyMetric, ts: u64, value: f64) -> Option<AnomalyRow> {
+        let deviation = self.averages[metric as usize].observe(value)?;
+        (deviation.z_score.abs() > self.z_threshold).then_some(AnomalyRow {
+            detected_ts: ts,
+            metric: metric as u8,
+            value,
+            expected: deviation.expected,
+            std_dev: deviation.std_dev,
+            z_score: deviation.z_score,
+        })
+    }
+}
+
+/// Anomaly detection methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic anomaly detection task
+    pub fn start_anomaly_detection_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let reader = self.clickhouse_reader.as_ref()?.clone();
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let interval_secs = self.anomaly_interval_secs;
+        let range =
+            TimeRange::from_duration(chrono::Duration::seconds(self.anomaly_window_secs as i64));
+        let mut detector = AnomalyDetector::new(self.anomaly_z_threshold, self.anomaly_ewma_alpha);
+
+        info!(interval_secs, window_secs = range.seconds(), "Starting anomaly detection task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                if let Err(e) = detect_anomalies(&reader, &writer, &mut detector, range).await {
+                    error!(err = %e, "Anomaly detection failed");
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Sample every metric over `range` and record the samples that deviate from their average
+pub async fn detect_anomalies(
+    reader: &ClickhouseReader,
+    writer: &ClickhouseWriter,
+    detector: &mut AnomalyDetector,
+    range: TimeRange,
+) -> Result<()> {
+    let ts = Utc::now().timestamp() as u64;
+    for metric in AnomalyMetric::ALL {
+        let Some(value) = sample_metric(reader, metric, range).await? else {
+            debug!(metric = metric.as_str(), "No samples for metric in window");
+            continue;
+        };
+        if let Some(row) = detector.observe(metric, ts, value) {
+            warn!(
+                metric = metric.as_str(),
+                value,
+                expected = row.expected,
+                z_score = row.z_score,
+                "Metric anomaly detected"
+            );
+            writer.insert_anomaly(&row).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Average of `metric` over `range`, `None` without data
+async fn sample_metric(
+    reader: &ClickhouseReader,
+    metric: AnomalyMetric,
+    range: TimeRange,
+) -> Result<Option<f64>> {
+    Ok(match metric {
+        AnomalyMetric::Tps => reader.get_avg_l2_tps(None, range).await?,
+        AnomalyMetric::GasUsed => reader.get_avg_l2_gas_used(range).await?,
+        AnomalyMetric::BatchCadence => {
+            reader.get_batch_posting_cadence(range).await?.map(|ms| ms as f64)
+        }
+        AnomalyMetric::ProveTime => reader.get_avg_prove_time(range).await?.map(|ms| ms as f64),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ewma_reports_deviations_after_warmup() {
+        let mut ewma = Ewma::new(0.5);
+        for i in 0..WARMUP_SAMPLES {
+            let value = if i % 2 == 0 { 9.0 } else { 11.0 };
+            assert_eq!(ewma.observe(value), None);
+        }
+        let deviation = ewma.observe(10.0).unwrap();
+        assert!((deviation.expected - 10.0).abs() < 1.0);
+        assert!(deviation.std_dev > 0.0);
+        assert!(deviation.z_score.abs() < 1.0);
+    }
+
+    #[test]
+    fn ewma_ignores_constant_samples() {
+        let mut ewma = Ewma::new(0.1);
+        for _ in 0..2 * WARMUP_SAMPLES {
+            assert_eq!(ewma.observe(5.0), None);
+        }
+    }
+
+    #[test]
+    fn detector_flags_only_large_deviations() {
+        let mut detector = AnomalyDetector::new(4.0, 0.2);
+        for i in 0..=WARMUP_SAMPLES {
+            let value = if i % 2 == 0 { 99.0 } else { 101.0 };
+            assert!(detector.observe(AnomalyMetric::Tps, u64::from(i), value).is_none());
+        }
+        assert!(detector.observe(AnomalyMetric::Tps, 100, 101.0).is_none());
+
+        let row = detector.observe(AnomalyMetric::Tps, 101, 500.0).unwrap();
+        assert_eq!(row.detected_ts, 101);
+        assert_eq!(row.metric, AnomalyMetric::Tps as u8);
+        assert_eq!(row.value, 500.0);
+        assert!(row.z_score > 4.0);
+
+        // Metrics are tracked independently
+        assert!(detector.observe(AnomalyMetric::ProveTime, 101, 500.0).is_none());
+    }
+}
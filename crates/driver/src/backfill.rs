@@ -0,0 +1,173 @@
+//! Gap backfill scheduling
+//!
+//! Missing blocks closest to the head are filled first, as the dashboards mostly show recent
+//! data. RPC requests made while backfilling draw from a per-second budget shared by every
+//! backfill, so a long catch-up does not starve live ingestion. Each run stores progress
+//! snapshots in `backfill_progress`, served by the `/backfill-status` endpoint.
+
+use std::time::Duration;
+
+use clickhouse::{BackfillProgressInsertRow, ClickhouseWriter};
+use runtime::rate_limiter::RateLimiter;
+use tracing::{error, info};
+
+/// Blocks processed between two progress snapshots of a run
+pub const PROGRESS_REPORT_BLOCKS: u64 = 50;
+
+/// Order missing blocks so that the block closest to the head is filled first
+pub fn prioritize_gaps(mut blocks: Vec<u64>) -> Vec<u64> {
+    blocks.sort_unstable_by(|a, b| b.cmp(a));
+    blocks.dedup();
+    blocks
+}
+
+/// RPC request budget shared by all backfills
+#[derive(Debug, Clone)]
+pub struct BackfillBudget {
+    limiter: Option<RateLimiter>,
+    max_requests_per_sec: u32,
+}
+
+impl BackfillBudget {
+    /// Allow `max_requests_per_sec` requests per second; 0 disables the limit
+    pub fn new(max_requests_per_sec: u32) -> Self {
+        let limiter = (max_requests_per_sec > 0)
+            .then(|| RateLimiter::new(max_requests_per_sec.into(), Duration::from_secs(1)));
+        Self { limiter, max_requests_per_sec }
+    }
+
+    /// Budget without a limit
+    pub fn unlimited() -> Self {
+        Self::new(0)
+    }
+
+    /// Requests allowed per second, 0 when unlimited
+    pub const fn max_requests_per_sec(&self) -> u32 {
+        self.max_requests_per_sec
+    }
+
+    /// Wait until the budget allows another request
+    pub async fn acquire(&self) {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire().await;
+        }
+    }
+}
+
+/// Progress of a single backfill run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackfillProgress {
+    row: BackfillProgressInsertRow,
+}
+
+impl BackfillProgress {
+    /// Start tracking a run over `blocks`, given in the order they are filled
+    pub fn new(chain: &str, blocks: &[u64], budget: &BackfillBudget) -> Self {
+        Self {
+            row: BackfillProgressInsertRow {
+                chain: chain.to_owned(),
+                scheduled_blocks: blocks.len() as u64,
+                filled_blocks: 0,
+                failed_blocks: 0,
+                newest_block: blocks.iter().max().copied(),
+                oldest_block: blocks.iter().min().copied(),
+                current_block: None,
+                max_requests_per_sec: budget.max_requests_per_sec(),
+            },
+        }
+    }
+
+    /// Record the outcome of a block
+    pub const fn record(&mut self, block_number: u64, filled: bool) {
+        if filled {
+            self.row.filled_blocks += 1;
+        } else {
+            self.row.failed_blocks += 1;
+        }
+        self.row.current_block = Some(block_number);
+    }
+
+    /// Blocks processed so far, filled or failed
+    pub const fn processed(&self) -> u64 {
+        self.row.filled_blocks + self.row.failed_blocks
+    }
+
+    /// Whether a progress snapshot is due after the last recorded block
+    pub const fn report_due(&self) -> bool {
+        self.processed().is_multiple_of(PROGRESS_REPORT_BLOCKS)
+    }
+
+    /// Snapshot of the run
+    pub const fn row(&self) -> &BackfillProgressInsertRow {
+        &self.row
+    }
+
+    /// Log the progress and store it when a writer is given
+    pub async fn report(&self, writer: Option<&ClickhouseWriter>) {
+        if self.row.scheduled_blocks > 0 {
+            info!(
+                chain = %self.row.chain,
+                scheduled = self.row.scheduled_blocks,
+                filled = self.row.filled_blocks,
+                failed = self.row.failed_blocks,
+                current_block = ?self.row.current_block,
+                "Backfill progress"
+            );
+        }
+        if let Some(writer) = writer &&
+            let Err(e) = writer.insert_backfill_progress(&self.row).await
+        {
+            error!(chain = %self.row.chain, err = %e, "Failed to store backfill progress");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gaps_closest_to_head_come_first() {
+        assert_eq!(prioritize_gaps(vec![3, 10, 7, 10, 1]), vec![10, 7, 3, 1]);
+        assert!(prioritize_gaps(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn progress_counts_outcomes() {
+        let budget = BackfillBudget::new(20);
+        let mut progress = BackfillProgress::new("l2", &[12, 11, 10], &budget);
+        assert_eq!(progress.row().newest_block, Some(12));
+        assert_eq!(progress.row().oldest_block, Some(10));
+        assert_eq!(progress.row().max_requests_per_sec, 20);
+
+        progress.record(12, true);
+        progress.record(11, false);
+        assert_eq!(progress.processed(), 2);
+        assert_eq!(progress.row().filled_blocks, 1);
+        assert_eq!(progress.row().failed_blocks, 1);
+        assert_eq!(progress.row().current_block, Some(11));
+    }
+
+    #[test]
+    fn progress_is_reported_periodically() {
+        let blocks: Vec<u64> = (0..PROGRESS_REPORT_BLOCKS * 2).rev().collect();
+        let mut progress = BackfillProgress::new("l1", &blocks, &BackfillBudget::unlimited());
+        let due = blocks
+            .iter()
+            .filter(|&&block| {
+                progress.record(block, true);
+                progress.report_due()
+            })
+            .count();
+        assert_eq!(due, 2);
+    }
+
+    #[tokio::test]
+    async fn unlimited_budget_does_not_wait() {
+        let budget = BackfillBudget::unlimited();
+        assert_eq!(budget.max_requests_per_sec(), 0);
+        for _ in 0..1_000 {
+            budget.acquire().await;
+        }
+    }
+}
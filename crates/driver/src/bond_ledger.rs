@@ -0,0 +1,136 @@
+//! Bond ledger
+//!
+//! The inbox takes a liveness bond from the proposer of every batch and returns it once the
+//! batch is proved. This task follows the `BondDeposited`, `BondWithdrawn`, `BondDebited` and
+//! `BondCredited` events of the inbox and records each of them in the `bond_ledger` table, along
+//! with the account's bond balance at the end of the block, so that proposers running low on
+//! bond can be spotted before their proposals revert.
+
+use std::{collections::HashMap, time::Duration};
+
+use alloy_primitives::Address;
+use clickhouse::{
+    AddressBytes, BondLedgerInsertRow, ClickhouseReader, ClickhouseWriter, HashBytes,
+};
+use extractor::Extractor;
+use eyre::Result;
+use tracing::{error, info};
+
+/// Blocks behind the L1 head that are left for the next cycle, so that reorged logs are not
+/// recorded
+const CONFIRMATION_BLOCKS: u64 = 12;
+/// Maximum number of L1 blocks scanned per cycle
+const MAX_BLOCKS_PER_CYCLE: u64 = 1_000;
+
+/// Bond ledger methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic bond ledger task
+    pub fn start_bond_ledger_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let reader = self.clickhouse_reader.as_ref()?.clone();
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let extractor = self.extractor.clone();
+        let interval_secs = self.bond_ledger_interval_secs;
+        let lookback_blocks = self.bond_ledger_lookback_blocks;
+
+        info!(interval_secs, lookback_blocks, "Starting bond ledger task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                match sync_bond_ledger(&reader, &writer, &extractor, lookback_blocks).await {
+                    Ok(0) => {}
+                    Ok(inserted) => info!(inserted, "Bond ledger cycle completed"),
+                    Err(e) => error!(err = %e, "Bond ledger sync failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Record the bond events of the next range of confirmed L1 blocks.
+/// Returns the number of ledger rows inserted.
+pub async fn sync_bond_ledger(
+    reader: &ClickhouseReader,
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    lookback_blocks: u64,
+) -> Result<usize> {
+    let last_recorded = reader.get_bond_ledger_last_l1_block().await?;
+    let head = extractor.get_l1_latest_block_number().await?;
+    let Some((from_block, to_block)) = next_range(last_recorded, head, lookback_blocks) else {
+        return Ok(0);
+    };
+
+    let events = extractor.get_bond_events(from_block, to_block).await?;
+
+    // The balance is read once per account and block, at the end of the block
+    let mut balances: HashMap<(Address, u64), u128> = HashMap::new();
+    let mut rows = Vec::with_capacity(events.len());
+    for (event, l1_block_number, tx_hash, log_index) in events {
+        let balance = match balances.get(&(event.user, l1_block_number)) {
+            Some(balance) => *balance,
+            None => {
+                let balance = extractor
+                    .get_bond_balance(event.user, l1_block_number)
+                    .await?
+                    .saturating_to::<u128>();
+                balances.insert((event.user, l1_block_number), balance);
+                balance
+            }
+        };
+        rows.push(BondLedgerInsertRow {
+            address: AddressBytes::from(event.user),
+            l1_block_number,
+            tx_hash: HashBytes::from(tx_hash),
+            log_index,
+            kind: event.kind.as_str().to_owned(),
+            amount: event.amount.saturating_to::<u128>(),
+            balance,
+        });
+    }
+
+    writer.insert_bond_ledger(&rows).await?;
+    Ok(rows.len())
+}
+
+/// Next inclusive L1 block range to scan, or `None` if there are no new confirmed blocks.
+///
+/// Scanning resumes after the last recorded block, or starts `lookback_blocks` behind the head
+/// when the ledger is empty.
+fn next_range(last_recorded: Option<u64>, head: u64, lookback_blocks: u64) -> Option<(u64, u64)> {
+    let confirmed = head.checked_sub(CONFIRMATION_BLOCKS)?;
+    let from_block = match last_recorded {
+        Some(block) => block + 1,
+        None => confirmed.saturating_sub(lookback_blocks),
+    };
+    let to_block = confirmed.min(from_block + MAX_BLOCKS_PER_CYCLE - 1);
+    (from_block <= to_block).then_some((from_block, to_block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_range_starts_from_lookback_when_empty() {
+        assert_eq!(next_range(None, 10_012, 500), Some((9_500, 10_000)));
+    }
+
+    #[test]
+    fn next_range_resumes_after_last_recorded_block() {
+        assert_eq!(next_range(Some(9_000), 20_000, 500), Some((9_001, 10_000)));
+        assert_eq!(next_range(Some(9_990), 10_012, 500), Some((9_991, 10_000)));
+    }
+
+    #[test]
+    fn next_range_waits_for_confirmations() {
+        assert_eq!(next_range(Some(10_000), 10_012, 500), None);
+        assert_eq!(next_range(None, 5, 500), None);
+    }
+}
@@ -0,0 +1,334 @@
+//! Batch data-quality checks
+//!
+//! The proposal, proof and verification streams are consumed independently, so a stream race
+//! can leave a batch verified without a proof record or proved without a proposal record. This
+//! task periodically looks for such anomalies, replays the missing events from L1 logs and
+//! reports whatever remains unresolved. The counters of the last cycle are exposed on `/health`.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use api_types::DataQualityHealth;
+
+use clickhouse::{BatchAnomalyRow, ClickhouseReader, ClickhouseWriter};
+use extractor::Extractor;
+use eyre::Result;
+use messages::{BatchProposedWrapper, BatchesProvedWrapper};
+use tracing::{error, info, warn};
+
+use crate::gap_detection::{
+    handle_batch_proposed_event_during_backfill, handle_batches_proved_event_during_backfill,
+};
+
+/// Maximum number of anomalies of each kind handled per cycle
+const MAX_ANOMALIES_PER_CYCLE: u64 = 100;
+
+/// Number of L1 blocks requested per `eth_getLogs` call
+const LOG_SEARCH_CHUNK_BLOCKS: u64 = 1_000;
+
+/// Outcome of a single data-quality cycle
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DataQualityReport {
+    /// Proved batches still missing a proposal record
+    pub proved_without_proposal: u64,
+    /// Verified batches still missing a proof record
+    pub verified_without_proof: u64,
+    /// Missing events recovered from L1 during this cycle
+    pub backfilled: u64,
+}
+
+/// Counters of the data-quality task, shared with the health endpoint
+#[derive(Debug, Clone, Default)]
+pub struct DataQualityStats {
+    inner: Arc<DataQualityCounters>,
+}
+
+#[derive(Debug, Default)]
+struct DataQualityCounters {
+    checks: AtomicU64,
+    failures: AtomicU64,
+    backfilled: AtomicU64,
+    proved_without_proposal: AtomicU64,
+    verified_without_proof: AtomicU64,
+}
+
+impl DataQualityStats {
+    /// Record a completed cycle
+    pub fn record(&self, report: &DataQualityReport) {
+        let counters = &self.inner;
+        counters.checks.fetch_add(1, Ordering::Relaxed);
+        counters.backfilled.fetch_add(report.backfilled, Ordering::Relaxed);
+        counters.proved_without_proposal.store(report.proved_without_proposal, Ordering::Relaxed);
+        counters.verified_without_proof.store(report.verified_without_proof, Ordering::Relaxed);
+    }
+
+    /// Record a cycle that failed before producing a report
+    pub fn record_failure(&self) {
+        self.inner.failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Current counters as reported by `/health`
+    pub fn snapshot(&self) -> DataQualityHealth {
+        let counters = &self.inner;
+        DataQualityHealth {
+            checks: counters.checks.load(Ordering::Relaxed),
+            failures: counters.failures.load(Ordering::Relaxed),
+            backfilled: counters.backfilled.load(Ordering::Relaxed),
+            proved_without_proposal: counters.proved_without_proposal.load(Ordering::Relaxed),
+            verified_without_proof: counters.verified_without_proof.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Data-quality methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic batch data-quality checker
+    pub fn start_data_quality_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let reader = self.clickhouse_reader.as_ref()?.clone();
+        let writer = self.clickhouse_writer.clone();
+        let extractor = self.extractor.clone();
+        let enable_db_writes = self.enable_db_writes;
+        let interval_secs = self.data_quality_interval_secs;
+        let lookback_blocks = self.data_quality_lookback_blocks;
+        let stats = self.data_quality_stats.clone();
+
+        info!(interval_secs, lookback_blocks, "Starting data-quality task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                match run_data_quality_check(
+                    &reader,
+                    writer.as_ref(),
+                    &extractor,
+                    enable_db_writes,
+                    lookback_blocks,
+                )
+                .await
+                {
+                    Ok(report) => {
+                        stats.record(&report);
+                        info!(
+                            proved_without_proposal = report.proved_without_proposal,
+                            verified_without_proof = report.verified_without_proof,
+                            backfilled = report.backfilled,
+                            "Data-quality check completed"
+                        );
+                    }
+                    Err(e) => {
+                        stats.record_failure();
+                        error!(err = %e, "Data-quality check failed");
+                    }
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Run a single data-quality cycle: backfill what can be recovered and report the rest
+pub async fn run_data_quality_check(
+    reader: &ClickhouseReader,
+    writer: Option<&ClickhouseWriter>,
+    extractor: &Extractor,
+    enable_db_writes: bool,
+    lookback_blocks: u64,
+) -> Result<DataQualityReport> {
+    let mut backfilled = 0;
+
+    // Proposals first: verified batches are only flagged once their proposal is indexed.
+    for anomaly in reader.get_proved_batches_without_proposal(MAX_ANOMALIES_PER_CYCLE).await? {
+        match backfill_batch_proposal(
+            writer,
+            extractor,
+            &anomaly,
+            enable_db_writes,
+            lookback_blocks,
+        )
+        .await
+        {
+            Ok(true) => backfilled += 1,
+            Ok(false) => warn!(
+                batch_id = anomaly.batch_id,
+                l1_block_number = anomaly.l1_block_number,
+                "No BatchProposed log found for proved batch"
+            ),
+            Err(e) => warn!(
+                batch_id = anomaly.batch_id,
+                err = %e,
+                "Failed to backfill proposal for proved batch"
+            ),
+        }
+    }
+
+    for anomaly in reader.get_verified_batches_without_proof(MAX_ANOMALIES_PER_CYCLE).await? {
+        match backfill_batch_proof(writer, extractor, &anomaly, enable_db_writes, lookback_blocks)
+            .await
+        {
+            Ok(true) => backfilled += 1,
+            Ok(false) => warn!(
+                batch_id = anomaly.batch_id,
+                l1_block_number = anomaly.l1_block_number,
+                "No BatchesProved log found for verified batch"
+            ),
+            Err(e) => warn!(
+                batch_id = anomaly.batch_id,
+                err = %e,
+                "Failed to backfill proof for verified batch"
+            ),
+        }
+    }
+
+    // Counted rather than listed, since more anomalies may remain than a cycle handles.
+    let proved_without_proposal = reader.count_proved_batches_without_proposal().await?;
+    let verified_without_proof = reader.count_verified_batches_without_proof().await?;
+
+    Ok(DataQualityReport { proved_without_proposal, verified_without_proof, backfilled })
+}
+
+/// Search L1 for the `BatchProposed` log of a proved batch and replay it.
+/// Returns `false` when no matching log exists within the lookback window.
+async fn backfill_batch_proposal(
+    writer: Option<&ClickhouseWriter>,
+    extractor: &Extractor,
+    anomaly: &BatchAnomalyRow,
+    enable_db_writes: bool,
+    lookback_blocks: u64,
+) -> Result<bool> {
+    for (from, to) in
+        log_search_windows(anomaly.l1_block_number, lookback_blocks, LOG_SEARCH_CHUNK_BLOCKS)
+    {
+        let logs = extractor.get_batch_proposed_logs(from, to).await?;
+        if let Some((batch, block_number, tx_hash)) =
+            logs.into_iter().find(|(batch, _, _)| batch.meta.batchId == anomaly.batch_id)
+        {
+            info!(
+                batch_id = anomaly.batch_id,
+                block_number,
+                tx_hash = %tx_hash,
+                "Backfilling missing BatchProposed event"
+            );
+            let wrapper = BatchProposedWrapper::from((batch, tx_hash, false));
+            handle_batch_proposed_event_during_backfill(
+                writer,
+                extractor,
+                wrapper,
+                enable_db_writes,
+            )
+            .await?;
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Search L1 for the `BatchesProved` log covering a verified batch and replay it.
+/// Returns `false` when no matching log exists within the lookback window.
+async fn backfill_batch_proof(
+    writer: Option<&ClickhouseWriter>,
+    extractor: &Extractor,
+    anomaly: &BatchAnomalyRow,
+    enable_db_writes: bool,
+    lookback_blocks: u64,
+) -> Result<bool> {
+    for (from, to) in
+        log_search_windows(anomaly.l1_block_number, lookback_blocks, LOG_SEARCH_CHUNK_BLOCKS)
+    {
+        let logs = extractor.get_batches_proved_logs(from, to).await?;
+        if let Some((proved, block_number, tx_hash)) = logs
+            .into_iter()
+            .find(|(proved, _, _)| proved.batch_ids_proved().contains(&anomaly.batch_id))
+        {
+            info!(
+                batch_id = anomaly.batch_id,
+                block_number,
+                tx_hash = %tx_hash,
+                "Backfilling missing BatchesProved event"
+            );
+            let wrapper = BatchesProvedWrapper::from((proved, block_number, tx_hash, false));
+            handle_batches_proved_event_during_backfill(
+                writer,
+                extractor,
+                wrapper,
+                enable_db_writes,
+            )
+            .await?;
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Split `[end - lookback, end]` into inclusive block windows of at most `chunk` blocks,
+/// newest first, since the missing event is most likely close to the referencing one.
+pub fn log_search_windows(end: u64, lookback: u64, chunk: u64) -> Vec<(u64, u64)> {
+    let floor = end.saturating_sub(lookback);
+    let chunk = chunk.max(1);
+    let mut windows = Vec::new();
+    let mut to = end;
+    loop {
+        let from = to.saturating_sub(chunk - 1).max(floor);
+        windows.push((from, to));
+        if from == floor {
+            break;
+        }
+        to = from - 1;
+    }
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_accumulate_backfills_and_keep_latest_anomalies() {
+        let stats = DataQualityStats::default();
+        stats.record(&DataQualityReport {
+            proved_without_proposal: 3,
+            verified_without_proof: 2,
+            backfilled: 4,
+        });
+        stats.record_failure();
+        stats.record(&DataQualityReport {
+            proved_without_proposal: 1,
+            verified_without_proof: 0,
+            backfilled: 2,
+        });
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.checks, 2);
+        assert_eq!(snapshot.failures, 1);
+        assert_eq!(snapshot.backfilled, 6);
+        assert_eq!(snapshot.proved_without_proposal, 1);
+        assert_eq!(snapshot.verified_without_proof, 0);
+    }
+
+    #[test]
+    fn log_search_windows_splits_newest_first() {
+        assert_eq!(
+            log_search_windows(2_500, 2_000, 1_000),
+            vec![(1_501, 2_500), (501, 1_500), (500, 500)]
+        );
+    }
+
+    #[test]
+    fn log_search_windows_clamps_at_genesis() {
+        assert_eq!(log_search_windows(10, 7_200, 1_000), vec![(0, 10)]);
+    }
+
+    #[test]
+    fn log_search_windows_without_lookback() {
+        assert_eq!(log_search_windows(42, 0, 1_000), vec![(42, 42)]);
+    }
+}
@@ -0,0 +1,56 @@
+//! Duplicate row verification
+//!
+//! The hot tables are deduplicated on their sorting key by `ClickHouse` merges. This task
+//! periodically counts keys of recently inserted rows that still have more than one row, and
+//! warns when duplicates survive `FINAL`, as readers would then over-count.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+/// Duplicate row verification methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic duplicate row verification task
+    pub fn start_dedup_verify_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let interval_secs = self.dedup_verify_interval_secs;
+        let lookback_hours = self.dedup_verify_lookback_hours;
+
+        info!(interval_secs, lookback_hours, "Starting dedup verification task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let since = Utc::now() - chrono::Duration::hours(lookback_hours as i64);
+                match writer.verify_dedup(since).await {
+                    Ok(reports) => {
+                        for r in &reports {
+                            if r.surviving_keys > 0 {
+                                warn!(
+                                    table = r.table,
+                                    duplicate_keys = r.duplicate_keys,
+                                    surviving_keys = r.surviving_keys,
+                                    "Duplicate rows survive FINAL"
+                                );
+                            } else if r.duplicate_keys > 0 {
+                                info!(
+                                    table = r.table,
+                                    duplicate_keys = r.duplicate_keys,
+                                    "Duplicate rows awaiting merge"
+                                );
+                            }
+                        }
+                    }
+                    Err(e) => error!(err = %e, "Dedup verification failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
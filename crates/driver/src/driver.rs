@@ -1,14 +1,20 @@
 //! Taikoscope Driver - combines ingestor and processor
 
+use std::path::PathBuf;
+
 use alloy_primitives::Address;
-use clickhouse::{ClickhouseReader, ClickhouseWriter};
+use clickhouse::{
+    ClickhouseReader, ClickhouseWriter, ProtocolConfigRow, SpillConfig, WriteBufferConfig,
+};
 use config::Opts;
 use extractor::{
-    BatchProposedStream, BatchesProvedStream, BatchesVerifiedStream, Extractor,
-    ForcedInclusionStream, ReorgDetector,
+    BatchProposedStream, BatchesProvedStream, BatchesVerifiedStream, ChannelSettings, Extractor,
+    ForcedInclusionStream, OperatorRemovedStream, ReorgDetector,
 };
 use eyre::{Context, Result};
-use incident::client::Client as IncidentClient;
+use incident::{
+    MaintenanceSchedule, MaintenanceWindow, RpcEndpoint, client::Client as IncidentClient,
+};
 use messages::TaikoEvent;
 use primitives::headers::{L1HeaderStream, L2HeaderStream};
 use tokio::sync::broadcast;
@@ -16,7 +22,16 @@ use tokio_stream::StreamExt;
 use tracing::{error, info, warn};
 use url::Url;
 
-use crate::{gap_detection::run_initial_gap_catchup, subscription::subscribe_with_retry};
+use crate::{
+    address_labels::parse_static_labels,
+    backfill::BackfillBudget,
+    data_quality::DataQualityStats,
+    gap_detection::run_initial_gap_catchup,
+    leader::{Leadership, default_instance_id},
+    protocol_config::{batch_proof_timeout_secs, discover_protocol_config},
+    reorg_detection::ReorgCorrelator,
+    subscription::subscribe_with_retry,
+};
 
 /// Driver that combines ingestor and processor functionality
 #[derive(Debug)]
@@ -24,41 +39,139 @@ use crate::{gap_detection::run_initial_gap_catchup, subscription::subscribe_with
 #[allow(missing_docs)]
 pub struct Driver {
     pub extractor: Extractor,
+    pub protocol_config: Option<ProtocolConfigRow>,
     pub clickhouse_writer: Option<ClickhouseWriter>,
     pub clickhouse_reader: Option<ClickhouseReader>,
     pub reorg_detector: ReorgDetector,
     pub last_l2_header: Option<(u64, Address)>,
+    pub last_l1_head: Option<u64>,
+    pub reorg_correlator: ReorgCorrelator,
     pub enable_db_writes: bool,
     pub enable_gap_detection: bool,
     pub gap_finalization_buffer_blocks: u64,
     pub gap_startup_lookback_blocks: u64,
     pub gap_continuous_lookback_blocks: u64,
+    pub gap_batch_lookback_blocks: u64,
     pub gap_poll_interval_secs: u64,
     pub gap_initial_delay_secs: u64,
     pub gap_dry_run: bool,
     pub gap_min_l1_block: u64,
     pub gap_min_l2_block: u64,
+    pub backfill_budget: BackfillBudget,
+    pub enable_data_quality_checks: bool,
+    pub data_quality_interval_secs: u64,
+    pub data_quality_lookback_blocks: u64,
+    pub data_quality_stats: DataQualityStats,
+    pub enable_prove_cost_backfill: bool,
+    pub prove_cost_backfill_interval_secs: u64,
+    pub enable_bond_ledger: bool,
+    pub bond_ledger_interval_secs: u64,
+    pub bond_ledger_lookback_blocks: u64,
+    pub enable_sequencer_downtime: bool,
+    pub sequencer_downtime_interval_secs: u64,
+    pub sequencer_downtime_max_gap_secs: u64,
+    pub sequencer_downtime_lookback_hours: u64,
+    pub enable_operator_balances: bool,
+    pub operator_balance_interval_secs: u64,
+    pub enable_operator_history: bool,
+    pub operator_history_interval_secs: u64,
+    pub operator_min_eth_balance_wei: u128,
+    pub taiko_token_address: Option<Address>,
+    pub operator_min_taiko_balance_wei: u128,
+    pub enable_eth_price_snapshots: bool,
+    pub eth_price_snapshot_interval_secs: u64,
+    pub eth_price_url: String,
+    pub enable_taiko_price_snapshots: bool,
+    pub taiko_price_url: String,
+    pub coingecko_api_key: Option<String>,
+    pub preconf_feed_url: Option<Url>,
+    pub preconf_compare_timeout_secs: u64,
+    pub enable_mempool_monitor: bool,
+    pub enable_proposal_mempool_monitor: bool,
+    pub proposal_watch_addresses: Vec<Address>,
+    pub enable_anomaly_detection: bool,
+    pub anomaly_interval_secs: u64,
+    pub anomaly_window_secs: u64,
+    pub anomaly_z_threshold: f64,
+    pub anomaly_ewma_alpha: f64,
+    pub enable_forced_inclusion_queue: bool,
+    pub forced_inclusion_queue_interval_secs: u64,
+    pub forced_inclusion_deadline_margin_batches: u64,
+    pub enable_verification_backlog: bool,
+    pub verification_backlog_interval_secs: u64,
+    pub verification_backlog_threshold_batches: u64,
+    pub address_labels: Vec<(Address, String)>,
+    pub enable_ens_labels: bool,
+    pub address_labels_interval_secs: u64,
+    pub admin_token: Option<String>,
+    pub admin_host: String,
+    pub admin_port: u16,
+    pub health_port: Option<u16>,
+    pub verify_dual: bool,
+    pub verify_dual_interval_secs: u64,
+    pub verify_dual_sample_size: u64,
+    pub enable_dedup_verification: bool,
+    pub dedup_verify_interval_secs: u64,
+    pub dedup_verify_lookback_hours: u64,
+    pub enable_fee_reconciliation: bool,
+    pub fee_reconcile_period_secs: u64,
+    pub fee_reconcile_tolerance_wei: u128,
+    pub enable_leader_election: bool,
+    pub leader_instance_id: String,
+    pub leader_lease_ttl_secs: u64,
+    pub leader_renew_interval_secs: u64,
+    pub leadership: Leadership,
     pub incident_client: IncidentClient,
     pub instatus_batch_submission_component_id: String,
     pub instatus_proof_submission_component_id: String,
     pub instatus_proof_verification_component_id: String,
     pub instatus_transaction_sequencing_component_id: String,
     pub instatus_public_api_component_id: String,
+    pub instatus_pipeline_component_id: String,
+    pub instatus_operator_balance_component_id: String,
+    pub instatus_bond_balance_component_id: String,
+    pub instatus_batch_cadence_component_id: String,
+    pub instatus_gas_saturation_component_id: String,
+    pub instatus_anchor_mismatch_component_id: String,
+    pub instatus_candidate_churn_component_id: String,
+    pub instatus_anomaly_component_id: String,
+    pub instatus_forced_inclusion_component_id: String,
+    pub instatus_verification_backlog_component_id: String,
+    pub instatus_proposal_inclusion_component_id: String,
     pub instatus_monitors_enabled: bool,
     pub instatus_monitor_poll_interval_secs: u64,
     pub instatus_l1_monitor_threshold_secs: u64,
     pub instatus_l2_monitor_threshold_secs: u64,
+    pub instatus_pipeline_monitor_threshold_secs: u64,
     pub batch_proof_timeout_secs: u64,
-    pub public_rpc_url: Option<Url>,
-    pub inbox_address: Address,
-    pub taiko_wrapper_address: Address,
+    pub batch_verify_cooldown_secs: u64,
+    pub batch_verify_cooldown_margin_secs: u64,
+    pub batch_cadence_warning_secs: u64,
+    pub batch_cadence_anchor_age_pct: u64,
+    pub gas_saturation_threshold_pct: u64,
+    pub gas_saturation_window_secs: u64,
+    pub anchor_mismatch_window_secs: u64,
+    pub candidate_churn_window_secs: u64,
+    pub anomaly_incident_window_secs: u64,
+    pub proposal_pending_threshold_secs: u64,
+    pub bond_low_batches: u64,
+    pub min_operator_candidates: usize,
+    pub incident_state_dir: Option<PathBuf>,
+    pub public_rpc_endpoints: Vec<RpcEndpoint>,
+    pub public_rpc_max_block_lag: u64,
 }
 
 impl Driver {
     /// Create a new driver with the given configuration
-    pub async fn new(opts: Opts) -> Result<Self> {
+    pub async fn new(mut opts: Opts) -> Result<Self> {
         info!("Initializing driver");
 
+        if opts.instatus.monitors_enabled && opts.instatus.provision_components {
+            crate::monitoring::provision_required_components(&mut opts.instatus)
+                .await
+                .wrap_err("Failed to provision Instatus components")?;
+        }
+
         // verify monitoring configuration before doing any heavy work
         if opts.instatus.monitors_enabled && !opts.instatus.enabled() {
             return Err(eyre::eyre!(
@@ -78,7 +191,7 @@ impl Driver {
                     "ClickHouse database name is required when database writes are enabled"
                 ));
             }
-            if opts.clickhouse.username.is_empty() {
+            if opts.clickhouse.write_credentials().0.is_empty() {
                 return Err(eyre::eyre!(
                     "ClickHouse username is required when database writes are enabled"
                 ));
@@ -99,15 +212,22 @@ impl Driver {
             opts.taiko_addresses.anchor_address,
         )
         .await
-        .wrap_err("Failed to initialize blockchain extractor. Ensure RPC URLs are WebSocket endpoints (ws:// or wss://)")?;
-
-        // Always create a ClickhouseWriter for migrations, regardless of enable_db_writes
-        let migration_writer = ClickhouseWriter::new(
-            opts.clickhouse.url.clone(),
-            opts.clickhouse.db.clone(),
-            opts.clickhouse.username.clone(),
-            opts.clickhouse.password.clone(),
-        );
+        .wrap_err("Failed to initialize blockchain extractor. Ensure RPC URLs are WebSocket endpoints (ws:// or wss://)")?
+        .with_stall_timeouts(
+            (opts.rpc.l1_stall_timeout_secs > 0)
+                .then_some(std::time::Duration::from_secs(opts.rpc.l1_stall_timeout_secs)),
+            (opts.rpc.l2_stall_timeout_secs > 0)
+                .then_some(std::time::Duration::from_secs(opts.rpc.l2_stall_timeout_secs)),
+        )
+        .with_channel_settings(ChannelSettings {
+            capacity: opts.rpc.stream_channel_capacity,
+            policies: opts.rpc.stream_channel_policies.iter().cloned().collect(),
+        })
+        .with_forks(&opts.taiko_addresses.address_schedule())
+        .with_guardian_verifiers(opts.taiko_addresses.guardian_verifier_addresses.clone());
+        for (activation_block, name) in extractor.fork_schedule() {
+            info!(fork = %name, activation_block, "Indexing Taiko fork");
+        }
 
         // Handle dry-run mode (when database writes are disabled)
         if !opts.enable_db_writes {
@@ -119,32 +239,90 @@ impl Driver {
         } else if opts.skip_migrations {
             info!("⚠️  Skipping database migrations");
         } else {
-            info!("🚀 Running database migrations...");
-            migration_writer.init_db(opts.reset_db).await?;
-            info!("✅ Database migrations completed");
+            run_migrations(&opts).await?;
         }
 
         // Only keep the writer for event processing if database writes are enabled
         let clickhouse_writer = opts.enable_db_writes.then(|| {
+            let (username, password) = opts.clickhouse.write_credentials();
             ClickhouseWriter::new(
                 opts.clickhouse.url.clone(),
                 opts.clickhouse.db.clone(),
-                opts.clickhouse.username.clone(),
-                opts.clickhouse.password.clone(),
+                username,
+                password,
             )
+            .with_table_prefix(opts.clickhouse.table_prefix.clone())
+            .with_fork_schedule(extractor.fork_schedule())
         });
+        let clickhouse_writer = match (clickhouse_writer, secondary_writer(&opts)) {
+            (Some(writer), Some(secondary)) => {
+                info!("Mirroring writes to secondary ClickHouse");
+                Some(writer.with_secondary(secondary))
+            }
+            (writer, _) => writer,
+        };
+        let clickhouse_writer = match clickhouse_writer {
+            Some(writer) if opts.write_buffer_max_rows > 0 => {
+                info!(
+                    max_rows = opts.write_buffer_max_rows,
+                    flush_interval_ms = opts.write_buffer_flush_interval_ms,
+                    "Buffering head event inserts"
+                );
+                Some(writer.with_write_buffer(WriteBufferConfig {
+                    max_rows: opts.write_buffer_max_rows,
+                    flush_interval: std::time::Duration::from_millis(
+                        opts.write_buffer_flush_interval_ms,
+                    ),
+                }))
+            }
+            writer => writer,
+        };
+        let clickhouse_writer = match (clickhouse_writer, &opts.spill_path) {
+            (Some(writer), Some(path)) => {
+                info!(
+                    path = %path.display(),
+                    max_bytes = opts.spill_max_bytes,
+                    "Spilling rows of failed inserts"
+                );
+                Some(writer.with_spill(SpillConfig {
+                    path: path.clone(),
+                    max_bytes: opts.spill_max_bytes,
+                    replay_interval: std::time::Duration::from_secs(
+                        opts.spill_replay_interval_secs,
+                    ),
+                })?)
+            }
+            (writer, _) => writer,
+        };
+
+        if let Some(writer) = &clickhouse_writer {
+            writer.ensure_writable().await.wrap_err("ClickHouse write user cannot write")?;
+        }
+
+        let protocol_config =
+            discover_protocol_config(&extractor, clickhouse_writer.as_ref()).await;
+        let batch_proof_timeout_secs = batch_proof_timeout_secs(
+            opts.instatus.batch_proof_timeout_secs,
+            protocol_config.as_ref(),
+        );
+        info!(batch_proof_timeout_secs, "Using batch proof timeout");
+        let batch_verify_cooldown_secs =
+            protocol_config.as_ref().map_or(0, |config| u64::from(config.cooldown_window));
 
-        // Create ClickhouseReader for gap detection and reorg detection (always create if gap
-        // detection is enabled)
-        let clickhouse_reader = opts
-            .enable_gap_detection
+        // Create ClickhouseReader for gap detection, data-quality checks, prove cost backfill,
+        // reorg detection and anchor block checks
+        let clickhouse_reader = (opts.enable_gap_detection ||
+            opts.enable_data_quality_checks ||
+            opts.enable_prove_cost_backfill)
             .then(|| {
+                let (username, password) = opts.clickhouse.read_credentials();
                 ClickhouseReader::new(
                     opts.clickhouse.url.clone(),
                     opts.clickhouse.db.clone(),
-                    opts.clickhouse.username.clone(),
-                    opts.clickhouse.password.clone(),
+                    username,
+                    password,
                 )
+                .map(|reader| reader.with_table_prefix(opts.clickhouse.table_prefix.clone()))
             })
             .transpose()?;
 
@@ -158,6 +336,17 @@ impl Driver {
             instatus_proof_verification_component_id,
             instatus_transaction_sequencing_component_id,
             instatus_public_api_component_id,
+            instatus_pipeline_component_id,
+            instatus_operator_balance_component_id,
+            instatus_bond_balance_component_id,
+            instatus_batch_cadence_component_id,
+            instatus_gas_saturation_component_id,
+            instatus_anchor_mismatch_component_id,
+            instatus_candidate_churn_component_id,
+            instatus_anomaly_component_id,
+            instatus_forced_inclusion_component_id,
+            instatus_verification_backlog_component_id,
+            instatus_proposal_inclusion_component_id,
             incident_client,
         ) = if opts.instatus.monitors_enabled {
             (
@@ -166,10 +355,32 @@ impl Driver {
                 opts.instatus.proof_verification_component_id.clone(),
                 opts.instatus.transaction_sequencing_component_id.clone(),
                 opts.instatus.public_api_component_id.clone(),
+                opts.instatus.pipeline_component_id.clone(),
+                opts.instatus.operator_balance_component_id.clone(),
+                opts.instatus.bond_balance_component_id.clone(),
+                opts.instatus.batch_cadence_component_id.clone(),
+                opts.instatus.gas_saturation_component_id.clone(),
+                opts.instatus.anchor_mismatch_component_id.clone(),
+                opts.instatus.candidate_churn_component_id.clone(),
+                opts.instatus.anomaly_component_id.clone(),
+                opts.instatus.forced_inclusion_component_id.clone(),
+                opts.instatus.verification_backlog_component_id.clone(),
+                opts.instatus.proposal_inclusion_component_id.clone(),
                 IncidentClient::new(opts.instatus.api_key.clone(), opts.instatus.page_id.clone()),
             )
         } else {
             (
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
                 String::new(),
                 String::new(),
                 String::new(),
@@ -179,36 +390,148 @@ impl Driver {
             )
         };
 
+        let maintenance_windows = opts
+            .instatus
+            .maintenance_windows
+            .iter()
+            .map(|window| window.parse::<MaintenanceWindow>())
+            .collect::<Result<Vec<_>>>()
+            .wrap_err("Invalid INSTATUS_MAINTENANCE_WINDOWS")?;
+        if !maintenance_windows.is_empty() {
+            info!(windows = maintenance_windows.len(), "Loaded incident maintenance windows");
+        }
+        let incident_client = incident_client
+            .with_maintenance(MaintenanceSchedule::new(maintenance_windows))
+            .with_audit_log(clickhouse_writer.clone());
+
+        let address_labels =
+            parse_static_labels(&opts.address_labels).wrap_err("Invalid ADDRESS_LABELS")?;
+
+        let public_rpc_endpoints = public_rpc_endpoints(&opts);
+
         Ok(Self {
             extractor,
+            protocol_config,
             clickhouse_writer,
             clickhouse_reader,
             reorg_detector,
             last_l2_header: None,
+            last_l1_head: None,
+            reorg_correlator: ReorgCorrelator::default(),
             enable_db_writes: opts.enable_db_writes,
             enable_gap_detection: opts.enable_gap_detection,
             gap_finalization_buffer_blocks: opts.gap_finalization_buffer_blocks,
             gap_startup_lookback_blocks: opts.gap_startup_lookback_blocks,
             gap_continuous_lookback_blocks: opts.gap_continuous_lookback_blocks,
+            gap_batch_lookback_blocks: opts.gap_batch_lookback_blocks,
             gap_poll_interval_secs: opts.gap_poll_interval_secs,
             gap_initial_delay_secs: opts.gap_initial_delay_secs,
             gap_dry_run: opts.gap_dry_run,
             gap_min_l1_block: opts.gap_min_l1_block,
             gap_min_l2_block: opts.gap_min_l2_block,
+            backfill_budget: BackfillBudget::new(opts.gap_backfill_max_rps),
+            enable_data_quality_checks: opts.enable_data_quality_checks,
+            data_quality_interval_secs: opts.data_quality_interval_secs,
+            data_quality_lookback_blocks: opts.data_quality_lookback_blocks,
+            data_quality_stats: DataQualityStats::default(),
+            enable_prove_cost_backfill: opts.enable_prove_cost_backfill,
+            prove_cost_backfill_interval_secs: opts.prove_cost_backfill_interval_secs,
+            enable_bond_ledger: opts.enable_bond_ledger,
+            bond_ledger_interval_secs: opts.bond_ledger_interval_secs,
+            bond_ledger_lookback_blocks: opts.bond_ledger_lookback_blocks,
+            enable_sequencer_downtime: opts.enable_sequencer_downtime,
+            sequencer_downtime_interval_secs: opts.sequencer_downtime_interval_secs,
+            sequencer_downtime_max_gap_secs: opts.sequencer_downtime_max_gap_secs,
+            sequencer_downtime_lookback_hours: opts.sequencer_downtime_lookback_hours,
+            enable_operator_balances: opts.enable_operator_balances,
+            operator_balance_interval_secs: opts.operator_balance_interval_secs,
+            enable_operator_history: opts.enable_operator_history,
+            operator_history_interval_secs: opts.operator_history_interval_secs,
+            operator_min_eth_balance_wei: opts.operator_min_eth_balance_wei,
+            taiko_token_address: opts.taiko_token_address,
+            operator_min_taiko_balance_wei: opts.operator_min_taiko_balance_wei,
+            enable_eth_price_snapshots: opts.enable_eth_price_snapshots,
+            eth_price_snapshot_interval_secs: opts.eth_price_snapshot_interval_secs,
+            eth_price_url: opts.eth_price_url,
+            enable_taiko_price_snapshots: opts.enable_taiko_price_snapshots,
+            taiko_price_url: opts.taiko_price_url,
+            coingecko_api_key: opts.coingecko_api_key,
+            preconf_feed_url: opts.preconf_feed_url,
+            preconf_compare_timeout_secs: opts.preconf_compare_timeout_secs,
+            enable_mempool_monitor: opts.enable_mempool_monitor,
+            enable_proposal_mempool_monitor: opts.enable_proposal_mempool_monitor,
+            proposal_watch_addresses: opts.proposal_watch_addresses,
+            enable_anomaly_detection: opts.enable_anomaly_detection,
+            anomaly_interval_secs: opts.anomaly_interval_secs,
+            anomaly_window_secs: opts.anomaly_window_secs,
+            anomaly_z_threshold: opts.anomaly_z_threshold,
+            anomaly_ewma_alpha: opts.anomaly_ewma_alpha,
+            enable_forced_inclusion_queue: opts.enable_forced_inclusion_queue,
+            forced_inclusion_queue_interval_secs: opts.forced_inclusion_queue_interval_secs,
+            forced_inclusion_deadline_margin_batches: opts.forced_inclusion_deadline_margin_batches,
+            enable_verification_backlog: opts.enable_verification_backlog,
+            verification_backlog_interval_secs: opts.verification_backlog_interval_secs,
+            verification_backlog_threshold_batches: opts.verification_backlog_threshold_batches,
+            address_labels,
+            enable_ens_labels: opts.enable_ens_labels,
+            address_labels_interval_secs: opts.address_labels_interval_secs,
+            admin_token: opts.admin_token,
+            admin_host: opts.admin_host,
+            admin_port: opts.admin_port,
+            health_port: opts.health_port,
+            verify_dual: opts.verify_dual,
+            verify_dual_interval_secs: opts.verify_dual_interval_secs,
+            verify_dual_sample_size: opts.verify_dual_sample_size,
+            enable_dedup_verification: opts.enable_dedup_verification,
+            dedup_verify_interval_secs: opts.dedup_verify_interval_secs,
+            dedup_verify_lookback_hours: opts.dedup_verify_lookback_hours,
+            enable_fee_reconciliation: opts.enable_fee_reconciliation,
+            fee_reconcile_period_secs: opts.fee_reconcile_period_secs,
+            fee_reconcile_tolerance_wei: opts.fee_reconcile_tolerance_wei,
+            enable_leader_election: opts.enable_leader_election,
+            leader_instance_id: opts.leader_instance_id.unwrap_or_else(default_instance_id),
+            leader_lease_ttl_secs: opts.leader_lease_ttl_secs,
+            leader_renew_interval_secs: opts.leader_renew_interval_secs,
+            // Without an election every instance ingests; otherwise wait for the lease
+            leadership: Leadership::new(!opts.enable_leader_election),
             incident_client,
             instatus_batch_submission_component_id,
             instatus_proof_submission_component_id,
             instatus_proof_verification_component_id,
             instatus_transaction_sequencing_component_id,
             instatus_public_api_component_id,
+            instatus_pipeline_component_id,
+            instatus_operator_balance_component_id,
+            instatus_bond_balance_component_id,
+            instatus_batch_cadence_component_id,
+            instatus_gas_saturation_component_id,
+            instatus_anchor_mismatch_component_id,
+            instatus_candidate_churn_component_id,
+            instatus_anomaly_component_id,
+            instatus_forced_inclusion_component_id,
+            instatus_verification_backlog_component_id,
+            instatus_proposal_inclusion_component_id,
             instatus_monitors_enabled: opts.instatus.monitors_enabled,
             instatus_monitor_poll_interval_secs: opts.instatus.monitor_poll_interval_secs,
             instatus_l1_monitor_threshold_secs: opts.instatus.l1_monitor_threshold_secs,
             instatus_l2_monitor_threshold_secs: opts.instatus.l2_monitor_threshold_secs,
-            batch_proof_timeout_secs: opts.instatus.batch_proof_timeout_secs,
-            public_rpc_url: opts.rpc.public_url,
-            inbox_address: opts.taiko_addresses.inbox_address,
-            taiko_wrapper_address: opts.taiko_addresses.taiko_wrapper_address,
+            instatus_pipeline_monitor_threshold_secs: opts.instatus.pipeline_monitor_threshold_secs,
+            batch_proof_timeout_secs,
+            batch_verify_cooldown_secs,
+            batch_verify_cooldown_margin_secs: opts.instatus.batch_verify_cooldown_margin_secs,
+            batch_cadence_warning_secs: opts.instatus.batch_cadence_warning_secs,
+            batch_cadence_anchor_age_pct: opts.instatus.batch_cadence_anchor_age_pct,
+            gas_saturation_threshold_pct: opts.instatus.gas_saturation_threshold_pct,
+            gas_saturation_window_secs: opts.instatus.gas_saturation_window_secs,
+            anchor_mismatch_window_secs: opts.instatus.anchor_mismatch_window_secs,
+            candidate_churn_window_secs: opts.instatus.candidate_churn_window_secs,
+            anomaly_incident_window_secs: opts.instatus.anomaly_incident_window_secs,
+            proposal_pending_threshold_secs: opts.instatus.proposal_pending_threshold_secs,
+            bond_low_batches: opts.instatus.bond_low_batches,
+            min_operator_candidates: opts.instatus.min_operator_candidates,
+            incident_state_dir: opts.instatus.state_dir,
+            public_rpc_endpoints,
+            public_rpc_max_block_lag: opts.rpc.public_max_block_lag,
         })
     }
 
@@ -229,6 +552,11 @@ impl Driver {
             .await
     }
 
+    async fn get_operator_removed(&self) -> OperatorRemovedStream {
+        subscribe_with_retry(|| self.extractor.get_operator_removed_stream(), "operator removed")
+            .await
+    }
+
     async fn get_batches_proved(&self) -> BatchesProvedStream {
         subscribe_with_retry(|| self.extractor.get_batches_proved_stream(), "batches proved").await
     }
@@ -250,6 +578,100 @@ impl Driver {
     ) -> Result<()> {
         info!("Starting driver event loop");
 
+        // Serve the admin endpoints if a token is configured
+        let admin_handle = if self.admin_token.is_some() {
+            self.start_admin_server()
+        } else {
+            info!("Admin endpoints disabled via configuration");
+            None
+        };
+
+        // Serve the indexer health endpoint if a port is configured
+        let health_handle = if self.health_port.is_some() {
+            self.start_health_server()
+        } else {
+            info!("Health endpoint disabled via configuration");
+            None
+        };
+
+        // Contend for the ingest lease if leader election is enabled
+        let leader_election_handle = if self.enable_leader_election {
+            self.start_leader_election_task()
+        } else {
+            info!("Leader election disabled via configuration");
+            None
+        };
+
+        // Writers and monitors run on the leader only; the event loop starts and stops them on
+        // leadership changes
+        let mut leader_tasks =
+            if self.leadership.is_leader() { self.start_leader_tasks().await } else { Vec::new() };
+
+        // Periodically flush buffered rows even when no new events arrive
+        let write_buffer_flush_handle =
+            self.clickhouse_writer.as_ref().and_then(ClickhouseWriter::spawn_flush_task);
+
+        // Write back rows spilled while ClickHouse was unavailable
+        let spill_replay_handle =
+            self.clickhouse_writer.as_ref().and_then(ClickhouseWriter::spawn_spill_replay_task);
+
+        let l1_stream = self.get_l1_headers().await;
+        let l2_stream = self.get_l2_headers().await;
+        let batch_stream = self.get_batch_proposed().await;
+        let forced_stream = self.get_forced_inclusion().await;
+        let proved_stream = self.get_batches_proved().await;
+        let verified_stream = self.get_batches_verified().await;
+        let operator_removed_stream = self.get_operator_removed().await;
+
+        let result = self
+            .event_loop(
+                l1_stream,
+                l2_stream,
+                batch_stream,
+                forced_stream,
+                proved_stream,
+                verified_stream,
+                operator_removed_stream,
+                shutdown_rx,
+                &mut leader_tasks,
+            )
+            .await;
+
+        // Clean up monitors and background tasks
+        for handle in leader_tasks {
+            handle.abort();
+        }
+        if let Some(handle) = admin_handle {
+            handle.abort();
+        }
+        if let Some(handle) = health_handle {
+            handle.abort();
+        }
+        if let Some(handle) = write_buffer_flush_handle {
+            handle.abort();
+        }
+        if let Some(handle) = spill_replay_handle {
+            handle.abort();
+        }
+        if let Some(writer) = &self.clickhouse_writer &&
+            let Err(e) = writer.flush().await
+        {
+            error!(err = %e, "Failed to flush buffered rows on shutdown");
+        }
+        // Hand the lease over only once buffered rows are written
+        if let Some(handle) = leader_election_handle {
+            handle.abort();
+            self.release_leadership().await;
+        }
+
+        result
+    }
+
+    /// Start the background writers and monitors that only the leader runs: gap catch-up and
+    /// detection, snapshots, ledgers, verifications and the incident monitors.
+    async fn start_leader_tasks(&self) -> Vec<tokio::task::JoinHandle<()>> {
+        let mut handles = Vec::new();
+
         // Start initial gap catch-up in background with delay
         #[allow(clippy::if_then_some_else_none)]
         let initial_catchup_handle = if self.enable_gap_detection {
@@ -262,9 +684,8 @@ impl Driver {
             let gap_startup_lookback_blocks = self.gap_startup_lookback_blocks;
             let gap_min_l1_block = self.gap_min_l1_block;
             let gap_min_l2_block = self.gap_min_l2_block;
+            let budget = self.backfill_budget.clone();
             let gap_initial_delay_secs = self.gap_initial_delay_secs;
-            let inbox_address = self.inbox_address;
-            let taiko_wrapper_address = self.taiko_wrapper_address;
 
             info!(
                 "Will start initial gap catch-up after {} second delay...",
@@ -284,13 +705,12 @@ impl Driver {
                         &reader,
                         writer.as_ref(),
                         &extractor,
-                        inbox_address,
-                        taiko_wrapper_address,
                         enable_db_writes && !gap_dry_run,
                         gap_finalization_buffer_blocks,
                         gap_startup_lookback_blocks,
                         gap_min_l1_block,
                         gap_min_l2_block,
+                        &budget,
                     )
                     .await;
 
@@ -318,37 +738,172 @@ impl Driver {
             None
         };
 
-        let l1_stream = self.get_l1_headers().await;
-        let l2_stream = self.get_l2_headers().await;
-        let batch_stream = self.get_batch_proposed().await;
-        let forced_stream = self.get_forced_inclusion().await;
-        let proved_stream = self.get_batches_proved().await;
-        let verified_stream = self.get_batches_verified().await;
+        // Start data-quality checker if enabled
+        let data_quality_handle = if self.enable_data_quality_checks {
+            self.start_data_quality_task()
+        } else {
+            info!("Data-quality checks disabled via configuration");
+            None
+        };
 
-        let result = self
-            .event_loop(
-                l1_stream,
-                l2_stream,
-                batch_stream,
-                forced_stream,
-                proved_stream,
-                verified_stream,
-                shutdown_rx,
-            )
-            .await;
+        // Start prove cost backfill if enabled
+        let prove_cost_backfill_handle = if self.enable_prove_cost_backfill {
+            self.start_prove_cost_backfill_task()
+        } else {
+            info!("Prove cost backfill disabled via configuration");
+            None
+        };
 
-        // Clean up monitors, gap detection, and initial catchup
-        for handle in monitor_handles {
-            handle.abort();
-        }
-        if let Some(handle) = gap_detection_handle {
-            handle.abort();
-        }
-        if let Some(handle) = initial_catchup_handle {
-            handle.abort();
-        }
+        // Start bond ledger sync if enabled
+        let bond_ledger_handle = if self.enable_bond_ledger {
+            self.start_bond_ledger_task()
+        } else {
+            info!("Bond ledger disabled via configuration");
+            None
+        };
 
-        result
+        // Start sequencer downtime ledger if enabled
+        let sequencer_downtime_handle = if self.enable_sequencer_downtime {
+            self.start_sequencer_downtime_task()
+        } else {
+            info!("Sequencer downtime ledger disabled via configuration");
+            None
+        };
+
+        // Start operator balance snapshots if enabled
+        let operator_balance_handle = if self.enable_operator_balances {
+            self.start_operator_balance_task()
+        } else {
+            info!("Operator balance snapshots disabled via configuration");
+            None
+        };
+
+        // Start recording whitelist operator candidate changes if enabled
+        let operator_history_handle = if self.enable_operator_history {
+            self.start_operator_history_task()
+        } else {
+            info!("Operator candidate history disabled via configuration");
+            None
+        };
+
+        // Start ETH/USD price snapshots if enabled
+        let eth_price_snapshot_handle = if self.enable_eth_price_snapshots {
+            self.start_eth_price_snapshot_task()
+        } else {
+            info!("ETH price snapshots disabled via configuration");
+            None
+        };
+
+        // Start TAIKO price snapshots if enabled
+        let taiko_price_snapshot_handle = if self.enable_taiko_price_snapshots {
+            self.start_taiko_price_snapshot_task()
+        } else {
+            info!("TAIKO price snapshots disabled via configuration");
+            None
+        };
+
+        // Compare advertised soft blocks against canonical blocks if a feed is configured
+        let preconf_feed_handle = if self.preconf_feed_url.is_some() {
+            self.start_preconf_feed_task()
+        } else {
+            info!("Preconf feed comparison disabled via configuration");
+            None
+        };
+
+        // Track L2 mempool depth and inclusion latency if enabled
+        let mempool_handle = if self.enable_mempool_monitor {
+            self.start_mempool_task()
+        } else {
+            info!("Mempool monitoring disabled via configuration");
+            None
+        };
+
+        // Time batch proposals from the L1 mempool to inclusion if enabled
+        let proposal_mempool_handle = if self.enable_proposal_mempool_monitor {
+            self.start_proposal_mempool_task()
+        } else {
+            info!("Proposal mempool monitoring disabled via configuration");
+            None
+        };
+
+        // Record sudden changes of the core metrics if enabled
+        let anomaly_detection_handle = if self.enable_anomaly_detection {
+            self.start_anomaly_detection_task()
+        } else {
+            info!("Anomaly detection disabled via configuration");
+            None
+        };
+
+        // Snapshot the forced inclusion queue if enabled
+        let forced_inclusion_queue_handle = if self.enable_forced_inclusion_queue {
+            self.start_forced_inclusion_queue_task()
+        } else {
+            info!("Forced inclusion queue snapshots disabled via configuration");
+            None
+        };
+
+        // Snapshot the verification backlog if enabled
+        let verification_backlog_handle = if self.enable_verification_backlog {
+            self.start_verification_backlog_task()
+        } else {
+            info!("Verification backlog snapshots disabled via configuration");
+            None
+        };
+
+        // Refresh the address labels if any source is configured
+        let address_labels_handle = if !self.address_labels.is_empty() || self.enable_ens_labels {
+            self.start_address_labels_task()
+        } else {
+            info!("Address labels disabled via configuration");
+            None
+        };
+
+        // Compare the primary and secondary cluster if dual writes are verified
+        let dual_verify_handle = if self.verify_dual {
+            self.start_dual_verify_task()
+        } else {
+            info!("Dual-write verification disabled via configuration");
+            None
+        };
+
+        // Count rows of the deduplicated tables that are still duplicated
+        let dedup_verify_handle = if self.enable_dedup_verification {
+            self.start_dedup_verify_task()
+        } else {
+            info!("Dedup verification disabled via configuration");
+            None
+        };
+
+        // Check the attributed sequencer fees against on-chain balance changes
+        let fee_reconcile_handle = if self.enable_fee_reconciliation {
+            self.start_fee_reconciliation_task()
+        } else {
+            info!("Fee reconciliation disabled via configuration");
+            None
+        };
+
+        handles.extend(initial_catchup_handle);
+        handles.extend(monitor_handles);
+        handles.extend(gap_detection_handle);
+        handles.extend(data_quality_handle);
+        handles.extend(prove_cost_backfill_handle);
+        handles.extend(bond_ledger_handle);
+        handles.extend(sequencer_downtime_handle);
+        handles.extend(operator_balance_handle);
+        handles.extend(operator_history_handle);
+        handles.extend(eth_price_snapshot_handle);
+        handles.extend(taiko_price_snapshot_handle);
+        handles.extend(preconf_feed_handle);
+        handles.extend(mempool_handle);
+        handles.extend(proposal_mempool_handle);
+        handles.extend(anomaly_detection_handle);
+        handles.extend(forced_inclusion_queue_handle);
+        handles.extend(verification_backlog_handle);
+        handles.extend(address_labels_handle);
+        handles.extend(dual_verify_handle);
+        handles.extend(dedup_verify_handle);
+        handles.extend(fee_reconcile_handle);
+        handles
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -360,10 +915,16 @@ impl Driver {
         mut forced_stream: ForcedInclusionStream,
         mut proved_stream: BatchesProvedStream,
         mut verified_stream: BatchesVerifiedStream,
+        mut operator_removed_stream: OperatorRemovedStream,
         mut shutdown_rx: Option<broadcast::Receiver<()>>,
+        leader_tasks: &mut Vec<tokio::task::JoinHandle<()>>,
     ) -> Result<()> {
         info!("Starting event loop - processing events directly to database");
 
+        let mut leadership_rx = self.leadership.subscribe();
+        // Reconcile once with changes made before subscribing
+        leadership_rx.mark_changed();
+
         loop {
             tokio::select! {
                 // Check for shutdown signal
@@ -378,11 +939,26 @@ impl Driver {
                     break;
                 }
 
+                // Start or stop the writers and monitors when leadership changes
+                Ok(()) = leadership_rx.changed() => {
+                    let is_leader = *leadership_rx.borrow_and_update();
+                    if is_leader && leader_tasks.is_empty() {
+                        *leader_tasks = self.start_leader_tasks().await;
+                        info!(tasks = leader_tasks.len(), "Became leader, started writers and monitors");
+                    } else if !is_leader && !leader_tasks.is_empty() {
+                        for handle in leader_tasks.drain(..) {
+                            handle.abort();
+                        }
+                        info!("Lost leadership, stopped writers and monitors");
+                    }
+                }
+
                 maybe_l1 = l1_stream.next() => {
                     match maybe_l1 {
-                        Some(header) => {
+                        Some(received) => {
+                            let header = &received.value;
                             info!(block_number = header.number, hash = %header.hash, "Processing L1 header");
-                            let event = TaikoEvent::L1Header(header);
+                            let event = received.map(TaikoEvent::L1Header);
                             if let Err(e) = self.process_event(event).await {
                                 error!(err = %e, "Failed to process L1Header");
                             }
@@ -395,9 +971,10 @@ impl Driver {
                 }
                 maybe_l2 = l2_stream.next() => {
                     match maybe_l2 {
-                        Some(header) => {
+                        Some(received) => {
+                            let header = &received.value;
                             info!(block_number = header.number, hash = %header.hash, "Processing L2 header");
-                            let event = TaikoEvent::L2Header(header);
+                            let event = received.map(TaikoEvent::L2Header);
                             if let Err(e) = self.process_event(event).await {
                                 error!(err = %e, "Failed to process L2Header");
                             }
@@ -410,10 +987,11 @@ impl Driver {
                 }
                 maybe_batch = batch_stream.next() => {
                     match maybe_batch {
-                        Some((batch, l1_tx_hash)) => {
-                            info!(block_number = batch.last_block_number(), "Processing BatchProposed");
-                            let wrapper = messages::BatchProposedWrapper::from((batch, l1_tx_hash, false));
-                            let event = TaikoEvent::BatchProposed(wrapper);
+                        Some(received) => {
+                            info!(block_number = received.value.0.last_block_number(), "Processing BatchProposed");
+                            let event = received.map(|(batch, l1_tx_hash)| {
+                                TaikoEvent::BatchProposed(messages::BatchProposedWrapper::from((batch, l1_tx_hash, false)))
+                            });
                             if let Err(e) = self.process_event(event).await {
                                 error!(err = %e, "Failed to process BatchProposed");
                             }
@@ -426,10 +1004,11 @@ impl Driver {
                 }
                 maybe_fi = forced_stream.next() => {
                     match maybe_fi {
-                        Some(fi) => {
-                            info!(blob_hash = ?fi.forcedInclusion.blobHash, "Processing forced inclusion processed");
-                            let wrapper = messages::ForcedInclusionProcessedWrapper::from((fi, false));
-                            let event = TaikoEvent::ForcedInclusionProcessed(wrapper);
+                        Some(received) => {
+                            info!(blob_hash = ?received.value.0.forcedInclusion.blobHash, "Processing forced inclusion processed");
+                            let event = received.map(|(fi, l1_block_number, l1_tx_hash)| {
+                                TaikoEvent::ForcedInclusionProcessed(messages::ForcedInclusionProcessedWrapper::from((fi, l1_block_number, l1_tx_hash, false)))
+                            });
                             if let Err(e) = self.process_event(event).await {
                                 error!(err = %e, "Failed to process ForcedInclusionProcessed");
                             }
@@ -442,10 +1021,11 @@ impl Driver {
                 }
                 maybe_proved = proved_stream.next() => {
                     match maybe_proved {
-                        Some((proved, l1_block_number, l1_tx_hash)) => {
-                            info!(batch_ids = ?proved.batch_ids_proved(), "Processing batches proved");
-                            let wrapper = messages::BatchesProvedWrapper::from((proved, l1_block_number, l1_tx_hash, false));
-                            let event = TaikoEvent::BatchesProved(wrapper);
+                        Some(received) => {
+                            info!(batch_ids = ?received.value.0.batch_ids_proved(), "Processing batches proved");
+                            let event = received.map(|(proved, l1_block_number, l1_tx_hash)| {
+                                TaikoEvent::BatchesProved(messages::BatchesProvedWrapper::from((proved, l1_block_number, l1_tx_hash, false)))
+                            });
                             if let Err(e) = self.process_event(event).await {
                                 error!(err = %e, "Failed to process BatchesProved");
                             }
@@ -458,10 +1038,11 @@ impl Driver {
                 }
                 maybe_verified = verified_stream.next() => {
                     match maybe_verified {
-                        Some((verified, l1_block_number, l1_tx_hash)) => {
-                            info!(batch_ids = ?verified.batch_id(), "Processing batches verified");
-                            let wrapper = messages::BatchesVerifiedWrapper::from((verified, l1_block_number, l1_tx_hash, false));
-                            let event = TaikoEvent::BatchesVerified(wrapper);
+                        Some(received) => {
+                            info!(batch_ids = ?received.value.0.batch_id(), "Processing batches verified");
+                            let event = received.map(|(verified, l1_block_number, l1_tx_hash)| {
+                                TaikoEvent::BatchesVerified(messages::BatchesVerifiedWrapper::from((verified, l1_block_number, l1_tx_hash, false)))
+                            });
                             if let Err(e) = self.process_event(event).await {
                                 error!(err = %e, "Failed to process BatchesVerified");
                             }
@@ -472,6 +1053,23 @@ impl Driver {
                         }
                     }
                 }
+                maybe_removed = operator_removed_stream.next() => {
+                    match maybe_removed {
+                        Some(received) => {
+                            info!(proposer = %received.value.0.proposer, "Processing operator removed");
+                            let event = received.map(|(removed, l1_block_number, l1_tx_hash)| {
+                                TaikoEvent::OperatorRemoved(messages::OperatorRemovedWrapper::from((removed, l1_block_number, l1_tx_hash, false)))
+                            });
+                            if let Err(e) = self.process_event(event).await {
+                                error!(err = %e, "Failed to process OperatorRemoved");
+                            }
+                        }
+                        None => {
+                            warn!("Operator removed stream ended; re-subscribing…");
+                            operator_removed_stream = self.get_operator_removed().await;
+                        }
+                    }
+                }
                 else => {
                     error!("All event streams ended and failed to re-subscribe. Shutting down driver loop");
                     break;
@@ -481,3 +1079,78 @@ impl Driver {
         Ok(())
     }
 }
+
+/// Pair the public RPC URLs with their Instatus components. The `PUBLIC_RPC` endpoint reports on
+/// the public API component, the `PUBLIC_RPC_URLS` ones on the component at the same position.
+fn public_rpc_endpoints(opts: &Opts) -> Vec<RpcEndpoint> {
+    let component = |id: &String| Some(id.clone()).filter(|id| !id.is_empty());
+    let legacy = opts.rpc.public_url.iter().map(|url| RpcEndpoint {
+        url: url.clone(),
+        component_id: component(&opts.instatus.public_api_component_id),
+    });
+    let listed = opts.rpc.public_urls.iter().enumerate().map(|(i, url)| RpcEndpoint {
+        url: url.clone(),
+        component_id: opts.instatus.public_rpc_component_ids.get(i).and_then(component),
+    });
+    legacy.chain(listed).collect()
+}
+
+/// Apply pending database migrations.
+///
+/// Refuses to proceed if an applied migration was modified after being applied, unless
+/// `--allow-dirty-schema` is set.
+pub async fn run_migrations(opts: &Opts) -> Result<()> {
+    let (username, password) = opts.clickhouse.write_credentials();
+    let migration_writer = ClickhouseWriter::new(
+        opts.clickhouse.url.clone(),
+        opts.clickhouse.db.clone(),
+        username,
+        password,
+    )
+    .with_table_prefix(opts.clickhouse.table_prefix.clone())
+    .with_allow_dirty_schema(opts.allow_dirty_schema);
+
+    info!("🚀 Running database migrations...");
+    migration_writer.init_db(opts.reset_db).await?;
+    if let Some(secondary) = secondary_writer(opts) {
+        info!("🚀 Running database migrations on secondary cluster...");
+        secondary.with_allow_dirty_schema(opts.allow_dirty_schema).init_db(opts.reset_db).await?;
+    }
+    info!("✅ Database migrations completed");
+    Ok(())
+}
+
+/// Apply pending migrations and populate the empty database with sample data.
+pub async fn seed_fixtures(opts: &Opts) -> Result<()> {
+    run_migrations(opts).await?;
+
+    let (username, password) = opts.clickhouse.write_credentials();
+    let writer = ClickhouseWriter::new(
+        opts.clickhouse.url.clone(),
+        opts.clickhouse.db.clone(),
+        username,
+        password,
+    )
+    .with_table_prefix(opts.clickhouse.table_prefix.clone());
+
+    info!(l2_blocks = opts.fixture_l2_blocks, "🌱 Seeding fixtures...");
+    writer.seed_fixtures(opts.fixture_l2_blocks).await?;
+    info!("✅ Fixtures seeded");
+    Ok(())
+}
+
+/// Writer of the secondary cluster writes are mirrored to, if one is configured.
+///
+/// Database and credentials default to the primary's write credentials.
+pub(crate) fn secondary_writer(opts: &Opts) -> Option<ClickhouseWriter> {
+    let clickhouse = &opts.clickhouse;
+    let url = clickhouse.secondary_url.clone()?;
+    let (username, password) = clickhouse.write_credentials();
+    let writer = ClickhouseWriter::new(
+        url,
+        clickhouse.secondary_db.clone().unwrap_or_else(|| clickhouse.db.clone()),
+        clickhouse.secondary_username.clone().unwrap_or(username),
+        clickhouse.secondary_password.clone().unwrap_or(password),
+    );
+    Some(writer.with_table_prefix(clickhouse.table_prefix.clone()))
+}
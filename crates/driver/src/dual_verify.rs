@@ -0,0 +1,63 @@
+//! Dual-write verification
+//!
+//! While writes are mirrored to a secondary cluster, this task periodically samples keys written
+//! since startup from the primary, compares their row counts against the secondary and logs the
+//! divergence counters of the writer.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+/// Dual-write verification methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic dual-write verification task
+    pub fn start_dual_verify_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        writer.secondary()?;
+        let interval_secs = self.verify_dual_interval_secs;
+        let sample_size = self.verify_dual_sample_size;
+        let since = Utc::now();
+
+        info!(interval_secs, sample_size, "Starting dual-write verification task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let stats = writer.dual_write_stats().unwrap_or_default();
+                info!(
+                    mirrored_rows = stats.mirrored_rows,
+                    diverged_rows = stats.diverged_rows,
+                    failed_writes = stats.failed_writes,
+                    "Dual-write counters"
+                );
+
+                match writer.verify_dual(since, sample_size).await {
+                    Some(Ok(mismatches)) if mismatches.is_empty() => {
+                        info!("Dual-write verification found no mismatches");
+                    }
+                    Some(Ok(mismatches)) => {
+                        for m in &mismatches {
+                            warn!(
+                                table = m.table,
+                                key = m.key,
+                                primary_rows = m.primary_rows,
+                                secondary_rows = m.secondary_rows,
+                                "Dual-write mismatch"
+                            );
+                        }
+                        warn!(count = mismatches.len(), "Dual-write verification found mismatches");
+                    }
+                    Some(Err(e)) => error!(err = %e, "Dual-write verification failed"),
+                    None => return,
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
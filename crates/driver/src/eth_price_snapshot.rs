@@ -0,0 +1,49 @@
+//! ETH/USD price snapshots
+//!
+//! Costs are stored in wei. To report them in USD at the time they were incurred, the current
+//! ETH price is periodically written to the `eth_prices` table, which the API joins against.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use clickhouse::EthPriceInsertRow;
+use network::eth_price::fetch_eth_usd;
+use tracing::{debug, error, info};
+
+/// ETH price snapshot methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic ETH/USD price snapshot task
+    pub fn start_eth_price_snapshot_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let url = self.eth_price_url.clone();
+        let api_key = self.coingecko_api_key.clone();
+        let interval_secs = self.eth_price_snapshot_interval_secs;
+
+        info!(interval_secs, "Starting ETH price snapshot task");
+
+        let handle = tokio::spawn(async move {
+            let http = reqwest::Client::new();
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let usd = match fetch_eth_usd(&http, &url, api_key.as_deref()).await {
+                    Ok(usd) => usd,
+                    Err(e) => {
+                        error!(err = %e, "Failed to fetch ETH price");
+                        continue;
+                    }
+                };
+                let row = EthPriceInsertRow { ts: Utc::now().timestamp() as u64, usd };
+                match writer.insert_eth_price(&row).await {
+                    Ok(()) => debug!(usd, "Stored ETH price snapshot"),
+                    Err(e) => error!(err = %e, "Failed to store ETH price snapshot"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
@@ -1,13 +1,24 @@
 //! Event handler for processing Taiko events
 
-use clickhouse::ClickhouseWriter;
+use alloy_primitives::B256;
+use alloy_rpc_types_eth::TransactionReceipt;
+use chainio::taiko::preconf_whitelist::RemovalReason;
+use clickhouse::{
+    AddressBytes, ClickhouseWriter, HashBytes, L1TxIndexRow, ProtocolGasSpendInsertRow,
+    SlashingEventRow,
+};
 use extractor::Extractor;
 use eyre::Result;
 use messages::{
     BatchProposedWrapper, BatchesProvedWrapper, BatchesVerifiedWrapper,
-    ForcedInclusionProcessedWrapper,
+    ForcedInclusionProcessedWrapper, OperatorRemovedWrapper,
+};
+use primitives::{
+    l1_data_cost::{cost_breakdown_from_receipt, cost_from_receipt},
+    proof::ProofType,
+    tx_role::L1TxRole,
 };
-use tracing::info;
+use tracing::{info, warn};
 
 /// State for gap detection operations
 #[derive(Debug)]
@@ -65,11 +76,19 @@ impl<'a> EventHandler<'a> {
                 "🧪 DRY-RUN: Would insert batch"
             );
         }
+        self.index_tx(l1_tx_hash, L1TxRole::Proposal, &[batch.meta.batchId], batch.info.proposedIn)
+            .await?;
+
+        let receipt = crate::event_processing::fetch_receipt(self.extractor, l1_tx_hash).await;
 
         // Calculate and insert L1 data cost
-        if let Some(cost) =
-            crate::event_processing::fetch_transaction_cost(self.extractor, l1_tx_hash).await
-        {
+        let input = if receipt.is_some() {
+            crate::event_processing::fetch_transaction_input(self.extractor, l1_tx_hash).await
+        } else {
+            None
+        };
+        if let Some((receipt, input)) = receipt.as_ref().zip(input) {
+            let cost = cost_breakdown_from_receipt(receipt, &input);
             if self.enable_db_writes {
                 crate::event_processing::with_db_error_context(
                     self.writer.insert_l1_data_cost(
@@ -85,11 +104,44 @@ impl<'a> EventHandler<'a> {
                 info!(
                     l1_block_number = batch.info.proposedIn,
                     batch_id = batch.meta.batchId,
-                    cost = cost,
+                    cost = cost.total(),
+                    blob_cost = cost.blob_cost,
+                    calldata_cost = cost.calldata_cost,
                     "🧪 DRY-RUN: Would insert L1 data cost"
                 );
             }
         }
+
+        // Record the blob fee market state of the proposal block
+        if let Some((excess_blob_gas, blob_base_fee)) =
+            crate::event_processing::fetch_l1_blob_fee(self.extractor, batch.info.proposedIn).await
+        {
+            if self.enable_db_writes {
+                crate::event_processing::with_db_error_context(
+                    self.writer.insert_batch_blob_fee(
+                        batch.info.proposedIn,
+                        batch.meta.batchId,
+                        excess_blob_gas,
+                        blob_base_fee,
+                    ),
+                    "insert batch blob fee",
+                    format!("l1_block_number={}", batch.info.proposedIn),
+                )
+                .await?;
+            } else {
+                info!(
+                    l1_block_number = batch.info.proposedIn,
+                    batch_id = batch.meta.batchId,
+                    excess_blob_gas,
+                    blob_base_fee,
+                    "🧪 DRY-RUN: Would insert batch blob fee"
+                );
+            }
+        }
+
+        if let Some(receipt) = &receipt {
+            self.record_gas_spend("propose", batch.info.proposedIn, receipt).await?;
+        }
         Ok(())
     }
 
@@ -99,10 +151,16 @@ impl<'a> EventHandler<'a> {
         let l1_block_number = wrapper.l1_block_number;
         let l1_tx_hash = wrapper.l1_tx_hash;
 
+        let proof_type =
+            self.extractor.get_proof_type(l1_tx_hash, proved.verifier).await.unwrap_or_else(|e| {
+                warn!(tx_hash = %l1_tx_hash, error = %e, "Failed to determine proof type");
+                ProofType::Unknown
+            });
+
         // Insert proved batch
         if self.enable_db_writes {
             crate::event_processing::with_db_error_context(
-                self.writer.insert_proved_batch(proved, l1_block_number),
+                self.writer.insert_proved_batch(proved, l1_block_number, l1_tx_hash, proof_type),
                 "insert proved batch",
                 format!("batch_ids={:?}", proved.batch_ids_proved()),
             )
@@ -111,14 +169,17 @@ impl<'a> EventHandler<'a> {
             info!(
                 batch_ids = ?proved.batch_ids_proved(),
                 l1_block_number = l1_block_number,
+                proof_type = proof_type.as_str(),
                 "🧪 DRY-RUN: Would insert proved batch"
             );
         }
+        self.index_tx(l1_tx_hash, L1TxRole::Prove, proved.batch_ids_proved(), l1_block_number)
+            .await?;
+
+        let receipt = crate::event_processing::fetch_receipt(self.extractor, l1_tx_hash).await;
 
         // Calculate and insert prove costs for each batch
-        if let Some(cost) =
-            crate::event_processing::fetch_transaction_cost(self.extractor, l1_tx_hash).await
-        {
+        if let Some(cost) = receipt.as_ref().map(cost_from_receipt) {
             let cost_per_batch = crate::event_processing::average_cost_per_batch(
                 cost,
                 proved.batch_ids_proved().len(),
@@ -146,6 +207,10 @@ impl<'a> EventHandler<'a> {
                 );
             }
         }
+
+        if let Some(receipt) = &receipt {
+            self.record_gas_spend("prove", l1_block_number, receipt).await?;
+        }
         Ok(())
     }
 
@@ -158,7 +223,7 @@ impl<'a> EventHandler<'a> {
         // Insert verified batch
         if self.enable_db_writes {
             crate::event_processing::with_db_error_context(
-                self.writer.insert_verified_batch(verified, l1_block_number),
+                self.writer.insert_verified_batch(verified, l1_block_number, l1_tx_hash),
                 "insert verified batch",
                 format!("batch_id={}", verified.batch_id),
             )
@@ -170,11 +235,12 @@ impl<'a> EventHandler<'a> {
                 "🧪 DRY-RUN: Would insert verified batch"
             );
         }
+        self.index_tx(l1_tx_hash, L1TxRole::Verify, &[verified.batch_id], l1_block_number).await?;
+
+        let receipt = crate::event_processing::fetch_receipt(self.extractor, l1_tx_hash).await;
 
         // Calculate and insert verify cost
-        if let Some(cost) =
-            crate::event_processing::fetch_transaction_cost(self.extractor, l1_tx_hash).await
-        {
+        if let Some(cost) = receipt.as_ref().map(cost_from_receipt) {
             if self.enable_db_writes {
                 crate::event_processing::with_db_error_context(
                     self.writer.insert_verify_cost(l1_block_number, verified.batch_id, cost),
@@ -194,6 +260,75 @@ impl<'a> EventHandler<'a> {
                 );
             }
         }
+
+        if let Some(receipt) = &receipt {
+            self.record_gas_spend("verify", l1_block_number, receipt).await?;
+        }
+        Ok(())
+    }
+
+    /// Records the role a protocol transaction played for each related batch in the L1
+    /// transaction index
+    async fn index_tx(
+        &self,
+        tx_hash: B256,
+        role: L1TxRole,
+        batch_ids: &[u64],
+        l1_block_number: u64,
+    ) -> Result<()> {
+        let rows = tx_index_rows(tx_hash, role, batch_ids, l1_block_number);
+
+        if self.enable_db_writes {
+            crate::event_processing::with_db_error_context(
+                self.writer.insert_l1_tx_index(&rows),
+                "insert L1 tx index",
+                format!("role={}, tx_hash={tx_hash:?}", role.as_str()),
+            )
+            .await?;
+        } else {
+            info!(
+                role = role.as_str(),
+                tx_hash = %tx_hash,
+                entries = rows.len(),
+                "🧪 DRY-RUN: Would index L1 transaction"
+            );
+        }
+        Ok(())
+    }
+
+    /// Records the L1 gas a protocol transaction spent, attributed to its sender
+    async fn record_gas_spend(
+        &self,
+        operation: &str,
+        l1_block_number: u64,
+        receipt: &TransactionReceipt,
+    ) -> Result<()> {
+        let row = ProtocolGasSpendInsertRow {
+            l1_block_number,
+            tx_hash: HashBytes::from(receipt.transaction_hash),
+            address: AddressBytes::from(receipt.from),
+            operation: operation.to_owned(),
+            gas_used: receipt.gas_used,
+            cost: cost_from_receipt(receipt),
+        };
+
+        if self.enable_db_writes {
+            crate::event_processing::with_db_error_context(
+                self.writer.insert_protocol_gas_spend(&row),
+                "insert protocol gas spend",
+                format!("operation={operation}, tx_hash={:?}", receipt.transaction_hash),
+            )
+            .await?;
+        } else {
+            info!(
+                operation,
+                tx_hash = %receipt.transaction_hash,
+                from = %receipt.from,
+                gas_used = row.gas_used,
+                cost = row.cost,
+                "🧪 DRY-RUN: Would insert protocol gas spend"
+            );
+        }
         Ok(())
     }
 
@@ -217,7 +352,82 @@ impl<'a> EventHandler<'a> {
                 "🧪 DRY-RUN: Would insert forced inclusion"
             );
         }
+        // Forced inclusions carry no batch id, so they are indexed under batch 0
+        self.index_tx(wrapper.l1_tx_hash, L1TxRole::ForcedInclusion, &[0], wrapper.l1_block_number)
+            .await
+    }
+
+    /// Handles an operator removed from the preconf whitelist, recording it as a slashing event
+    /// with the reason derived from the removing transaction
+    pub async fn handle_operator_removed(&self, wrapper: OperatorRemovedWrapper) -> Result<()> {
+        let proposer = wrapper.event.proposer;
+        let reason = match self
+            .extractor
+            .get_operator_removal_reason(wrapper.l1_tx_hash, wrapper.l1_block_number)
+            .await
+        {
+            Ok(reason) => reason,
+            Err(e) => {
+                warn!(tx_hash = %wrapper.l1_tx_hash, err = %e, "Failed to determine operator removal reason");
+                RemovalReason::Unknown
+            }
+        };
+
+        if self.enable_db_writes {
+            let row = SlashingEventRow {
+                l1_block_number: wrapper.l1_block_number,
+                validator_addr: AddressBytes::from(proposer),
+                reason: reason as u8,
+            };
+            crate::event_processing::with_db_error_context(
+                self.writer.insert_slashing_event(&row),
+                "insert slashing event",
+                format!("proposer={proposer}"),
+            )
+            .await?;
+            info!(%proposer, reason = reason.as_str(), "Recorded operator removal");
+        } else {
+            info!(
+                %proposer,
+                reason = reason.as_str(),
+                "🧪 DRY-RUN: Would insert slashing event"
+            );
+        }
 
         Ok(())
     }
 }
+
+/// Entries of the L1 transaction index for a transaction that played `role` for `batch_ids`
+fn tx_index_rows(
+    tx_hash: B256,
+    role: L1TxRole,
+    batch_ids: &[u64],
+    l1_block_number: u64,
+) -> Vec<L1TxIndexRow> {
+    batch_ids
+        .iter()
+        .map(|&batch_id| L1TxIndexRow {
+            tx_hash: HashBytes::from(tx_hash),
+            role: role as u8,
+            batch_id,
+            l1_block_number,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tx_index_rows_cover_every_batch() {
+        let tx_hash = B256::repeat_byte(0x11);
+        let rows = tx_index_rows(tx_hash, L1TxRole::Prove, &[7, 8], 100);
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|r| r.tx_hash == HashBytes::from(tx_hash)));
+        assert!(rows.iter().all(|r| r.role == L1TxRole::Prove as u8 && r.l1_block_number == 100));
+        assert_eq!(rows.iter().map(|r| r.batch_id).collect::<Vec<_>>(), vec![7, 8]);
+    }
+}
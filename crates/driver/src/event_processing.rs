@@ -1,21 +1,130 @@
 //! Event processing methods for the Driver
 #![allow(missing_docs)]
 
-use clickhouse::{AddressBytes, HashBytes, L2HeadEvent};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use clickhouse::{
+    AddressBytes, HashBytes, HeaderPropagationInsertRow, L2AnchorBlockInsertRow, L2HeadEvent,
+    PipelineLatencyInsertRow,
+};
 use extractor::Extractor;
 use eyre::Result;
 use messages::{
     BatchProposedWrapper, BatchesProvedWrapper, BatchesVerifiedWrapper,
-    ForcedInclusionProcessedWrapper, TaikoEvent,
+    ForcedInclusionProcessedWrapper, OperatorRemovedWrapper, TaikoEvent,
 };
-use tracing::{error, info, warn};
+use primitives::{block_stats::BlockStats, ingest::Received};
+use tracing::{debug, error, info, warn};
 
 use crate::event_handler::EventHandler;
 
 /// Event processing methods for the Driver
 impl crate::driver::Driver {
-    /// Process an event and insert it into the database
-    pub async fn process_event(&mut self, event: TaikoEvent) -> Result<()> {
+    /// Process an event and insert it into the database, recording how long it spent in each
+    /// pipeline stage since the extractor received it. Standby instances skip events, as the
+    /// leader ingests them.
+    pub async fn process_event(&mut self, event: Received<TaikoEvent>) -> Result<()> {
+        let queued = event.elapsed();
+        let Received { value: event, at } = event;
+        let kind = event.kind();
+
+        if !self.leadership.is_leader() {
+            debug!(kind, "Standing by, skipping event");
+            return Ok(());
+        }
+
+        let header = match &event {
+            TaikoEvent::L1Header(header) => Some(("l1", header.number, header.timestamp)),
+            TaikoEvent::L2Header(header) => Some(("l2", header.number, header.timestamp)),
+            _ => None,
+        };
+        if let Some((chain, block_number, block_ts)) = header {
+            let observed_at = SystemTime::now().checked_sub(queued).unwrap_or_else(SystemTime::now);
+            self.record_header_propagation(chain, block_number, block_ts, observed_at).await;
+        }
+
+        self.dispatch_event(event).await?;
+
+        let total = at.elapsed();
+        self.record_pipeline_latency(kind, queued, total).await;
+        Ok(())
+    }
+
+    /// Store the stage timings of a processed event. Failures are logged, not propagated, as
+    /// they do not affect the event's own rows.
+    async fn record_pipeline_latency(&self, kind: &str, queued: Duration, total: Duration) {
+        let processed = total.saturating_sub(queued);
+        debug!(
+            kind,
+            queue_us = queued.as_micros() as u64,
+            process_us = processed.as_micros() as u64,
+            total_us = total.as_micros() as u64,
+            "Event ingested"
+        );
+
+        let Some(writer) = &self.clickhouse_writer else {
+            return;
+        };
+        let row = PipelineLatencyInsertRow {
+            kind: kind.to_owned(),
+            queue_us: queued.as_micros() as u64,
+            process_us: processed.as_micros() as u64,
+            total_us: total.as_micros() as u64,
+        };
+        if let Err(e) = writer.insert_pipeline_latency(row).await {
+            warn!(err = %e, kind, "Failed to store pipeline latency");
+        }
+    }
+
+    /// Store when a live header was received next to its chain timestamp. Failures are logged,
+    /// not propagated, as they do not affect the header's own rows.
+    async fn record_header_propagation(
+        &self,
+        chain: &str,
+        block_number: u64,
+        block_ts: u64,
+        observed_at: SystemTime,
+    ) {
+        let observed_ms =
+            observed_at.duration_since(UNIX_EPOCH).map_or(0, |since| since.as_millis() as u64);
+        let delay_ms = propagation_delay_ms(block_ts, observed_ms);
+        debug!(chain, block_number, delay_ms, "Header received");
+
+        let Some(writer) = &self.clickhouse_writer else {
+            return;
+        };
+        let row = HeaderPropagationInsertRow {
+            chain: chain.to_owned(),
+            block_number,
+            block_ts,
+            observed_at: observed_ms,
+            delay_ms,
+        };
+        if let Err(e) = writer.insert_header_propagation(row).await {
+            warn!(err = %e, chain, block_number, "Failed to store header propagation");
+        }
+    }
+
+    /// Route an event to its handler
+    async fn dispatch_event(&mut self, event: TaikoEvent) -> Result<()> {
+        // Track the L1 head to measure how far L2 anchors lag behind it, and L1 reorgs and
+        // forced inclusions to attribute L2 reorgs to them
+        match &event {
+            TaikoEvent::L1Header(header) => {
+                self.last_l1_head = Some(header.number);
+                self.reorg_correlator.on_l1_header(
+                    header.number,
+                    header.hash,
+                    header.slot,
+                    Instant::now(),
+                );
+            }
+            TaikoEvent::ForcedInclusionProcessed(wrapper) if !wrapper.removed => {
+                self.reorg_correlator.on_forced_inclusion(Instant::now());
+            }
+            _ => {}
+        }
+
         // Handle dry-run mode with detailed logging
         if !self.enable_db_writes {
             return self.process_event_dry_run(event).await;
@@ -54,6 +163,10 @@ impl crate::driver::Driver {
                 info!(batch_id = wrapper.verified.batch_id, "Processing batches verified");
                 self.handle_batches_verified_event(wrapper).await
             }
+            TaikoEvent::OperatorRemoved(wrapper) => {
+                info!(proposer = %wrapper.event.proposer, "Processing operator removed");
+                self.handle_operator_removed_event(wrapper).await
+            }
         }
     }
 
@@ -100,33 +213,45 @@ impl crate::driver::Driver {
                 // Still run reorg detection for validation (but won't write to DB)
                 crate::reorg_detection::process_reorg_detection(
                     &mut self.reorg_detector,
+                    &self.extractor,
                     &mut self.last_l2_header,
                     &self.clickhouse_writer,
                     &self.clickhouse_reader,
+                    &self.reorg_correlator,
                     &header,
                 )
                 .await;
 
                 // Simulate stats calculation
-                let (sum_gas_used, sum_tx, sum_priority_fee) = self.extractor
+                let stats = self.extractor
                     .get_l2_block_stats(alloy_primitives::B256::from(*header.hash), header.base_fee_per_gas)
                     .await
                     .unwrap_or_else(|e| {
                         warn!(header_number = header.number, err = %e, "🧪 DRY-RUN: Failed to get L2 block stats");
-                        (0, 0, 0)
+                        BlockStats::default()
                     });
 
-                let sum_base_fee = sum_gas_used.saturating_mul(header.base_fee_per_gas as u128);
-
                 info!(
                     block_number = header.number,
-                    sum_gas_used = sum_gas_used,
-                    sum_tx = sum_tx,
-                    sum_priority_fee = sum_priority_fee,
-                    sum_base_fee = sum_base_fee,
+                    sum_gas_used = stats.sum_gas_used,
+                    sum_tx = stats.tx_count,
+                    sum_priority_fee = stats.sum_priority_fee,
+                    sum_base_fee = stats.sum_burned_fee,
+                    avg_gas_price = stats.avg_gas_price,
                     "🧪 DRY-RUN: Would insert L2 header with calculated stats"
                 );
 
+                if let Some(anchor_block_id) =
+                    fetch_l2_anchor_block_id(&self.extractor, &header).await
+                {
+                    info!(
+                        block_number = header.number,
+                        anchor_block_id,
+                        l1_head = self.last_l1_head,
+                        "🧪 DRY-RUN: Would insert L2 anchor block"
+                    );
+                }
+
                 Ok(())
             }
             TaikoEvent::BatchProposed(wrapper) => {
@@ -141,15 +266,26 @@ impl crate::driver::Driver {
 
                 // Simulate cost calculation
                 if let Some(cost) =
-                    fetch_transaction_cost(&self.extractor, wrapper.l1_tx_hash).await
+                    fetch_transaction_cost_breakdown(&self.extractor, wrapper.l1_tx_hash).await
                 {
                     info!(
                         batch_id = batch.meta.batchId,
-                        l1_data_cost = cost,
+                        l1_data_cost = cost.total(),
+                        blob_cost = cost.blob_cost,
+                        calldata_cost = cost.calldata_cost,
                         "🧪 DRY-RUN: Would insert L1 data cost"
                     );
                 }
 
+                if let Some((excess_blob_gas, blob_base_fee)) =
+                    fetch_l1_blob_fee(&self.extractor, batch.info.proposedIn).await
+                {
+                    info!(
+                        batch_id = batch.meta.batchId,
+                        excess_blob_gas, blob_base_fee, "🧪 DRY-RUN: Would insert batch blob fee"
+                    );
+                }
+
                 info!(batch_id = batch.meta.batchId, "🧪 DRY-RUN: Would insert batch row");
 
                 Ok(())
@@ -222,6 +358,17 @@ impl crate::driver::Driver {
                     "🧪 DRY-RUN: Would insert verified batch record"
                 );
 
+                Ok(())
+            }
+            TaikoEvent::OperatorRemoved(wrapper) => {
+                info!(
+                    proposer = %wrapper.event.proposer,
+                    sequencer = %wrapper.event.sequencer,
+                    l1_block_number = wrapper.l1_block_number,
+                    l1_tx_hash = %wrapper.l1_tx_hash,
+                    "🧪 DRY-RUN: Would insert slashing event"
+                );
+
                 Ok(())
             }
         }
@@ -263,9 +410,11 @@ impl crate::driver::Driver {
         // Process reorg detection
         crate::reorg_detection::process_reorg_detection(
             &mut self.reorg_detector,
+            &self.extractor,
             &mut self.last_l2_header,
             &self.clickhouse_writer,
             &self.clickhouse_reader,
+            &self.reorg_correlator,
             &header,
         )
         .await;
@@ -273,6 +422,9 @@ impl crate::driver::Driver {
         // Insert L2 header with block statistics
         self.insert_l2_header_with_stats(&header).await;
 
+        // Insert the anchor block id used for the L1 -> L2 sync lag
+        self.insert_l2_anchor_block(&header).await;
+
         Ok(())
     }
 
@@ -281,6 +433,8 @@ impl crate::driver::Driver {
             eyre::eyre!("ClickHouse writer not available for batch proposed processing")
         })?;
 
+        self.check_batch_anchor(&wrapper.batch).await;
+
         let handler = EventHandler::new(writer, &self.extractor, self.enable_db_writes);
         handler.handle_batch_proposed(wrapper).await
     }
@@ -297,6 +451,18 @@ impl crate::driver::Driver {
         handler.handle_forced_inclusion(wrapper).await
     }
 
+    pub async fn handle_operator_removed_event(
+        &self,
+        wrapper: OperatorRemovedWrapper,
+    ) -> Result<()> {
+        let writer = self.clickhouse_writer.as_ref().ok_or_else(|| {
+            eyre::eyre!("ClickHouse writer not available for operator removed processing")
+        })?;
+
+        let handler = EventHandler::new(writer, &self.extractor, self.enable_db_writes);
+        handler.handle_operator_removed(wrapper).await
+    }
+
     pub async fn handle_batches_proved_event(&self, wrapper: BatchesProvedWrapper) -> Result<()> {
         let writer = self.clickhouse_writer.as_ref().ok_or_else(|| {
             eyre::eyre!("ClickHouse writer not available for batches proved processing")
@@ -324,25 +490,26 @@ impl crate::driver::Driver {
             None => return,
         };
 
-        let (sum_gas_used, sum_tx, sum_priority_fee) = self.extractor
+        let stats = self.extractor
             .get_l2_block_stats(alloy_primitives::B256::from(*header.hash), header.base_fee_per_gas)
             .await
             .unwrap_or_else(|e| {
                 error!(header_number = header.number, err = %e, "Failed to get L2 block stats, using defaults");
-                (0, 0, 0)
+                BlockStats::default()
             });
 
-        let sum_base_fee = sum_gas_used.saturating_mul(header.base_fee_per_gas as u128);
-
         let event = L2HeadEvent {
             l2_block_number: header.number,
             block_hash: HashBytes(*header.hash),
             block_ts: header.timestamp,
-            sum_gas_used,
-            sum_tx,
-            sum_priority_fee,
-            sum_base_fee,
+            sum_gas_used: stats.sum_gas_used,
+            gas_limit: header.gas_limit,
+            sum_tx: stats.tx_count,
+            sum_priority_fee: stats.sum_priority_fee,
+            sum_base_fee: stats.sum_burned_fee,
+            avg_gas_price: stats.avg_gas_price,
             sequencer: AddressBytes(header.beneficiary.into_array()),
+            extra_data: header.extra_data.to_string(),
         };
 
         if let Err(e) = writer.insert_l2_header(&event).await {
@@ -351,6 +518,25 @@ impl crate::driver::Driver {
             info!(header_number = header.number, "Inserted L2 header with stats");
         }
     }
+
+    pub async fn insert_l2_anchor_block(&self, header: &primitives::headers::L2Header) {
+        let (Some(writer), Some(l1_head)) = (&self.clickhouse_writer, self.last_l1_head) else {
+            return;
+        };
+        let Some(anchor_block_id) = fetch_l2_anchor_block_id(&self.extractor, header).await else {
+            return;
+        };
+
+        let row = L2AnchorBlockInsertRow {
+            l2_block_number: header.number,
+            block_ts: header.timestamp,
+            anchor_block_id,
+            l1_head,
+        };
+        if let Err(e) = writer.insert_l2_anchor_block(&row).await {
+            error!(header_number = header.number, err = %e, "Failed to insert L2 anchor block");
+        }
+    }
 }
 
 // Helper functions
@@ -373,12 +559,47 @@ pub async fn fetch_transaction_cost(
     extractor: &Extractor,
     tx_hash: alloy_primitives::B256,
 ) -> Option<u128> {
+    fetch_receipt(extractor, tx_hash)
+        .await
+        .map(|receipt| primitives::l1_data_cost::cost_from_receipt(&receipt))
+}
+
+/// Fetch the receipt of `tx_hash` and split its cost into blob, calldata and execution
+/// components
+pub async fn fetch_transaction_cost_breakdown(
+    extractor: &Extractor,
+    tx_hash: alloy_primitives::B256,
+) -> Option<primitives::l1_data_cost::L1DataCostBreakdown> {
+    let receipt = fetch_receipt(extractor, tx_hash).await?;
+    let input = fetch_transaction_input(extractor, tx_hash).await?;
+    Some(primitives::l1_data_cost::cost_breakdown_from_receipt(&receipt, &input))
+}
+
+/// Fetch the calldata of `tx_hash`, logging failures
+pub async fn fetch_transaction_input(
+    extractor: &Extractor,
+    tx_hash: alloy_primitives::B256,
+) -> Option<alloy_primitives::Bytes> {
+    match extractor.get_transaction_input(tx_hash).await {
+        Ok(input) => Some(input),
+        Err(e) => {
+            warn!(err = %e, tx_hash = %tx_hash, "Failed to fetch transaction input");
+            None
+        }
+    }
+}
+
+/// Fetch the receipt of `tx_hash`, logging failures
+pub async fn fetch_receipt(
+    extractor: &Extractor,
+    tx_hash: alloy_primitives::B256,
+) -> Option<alloy_rpc_types_eth::TransactionReceipt> {
     if tx_hash == alloy_primitives::B256::ZERO {
         return None;
     }
 
     match extractor.get_receipt(tx_hash).await {
-        Ok(receipt) => Some(primitives::l1_data_cost::cost_from_receipt(&receipt)),
+        Ok(receipt) => Some(receipt),
         Err(e) => {
             warn!(err = %e, tx_hash = %tx_hash, "Failed to fetch transaction receipt");
             None
@@ -386,6 +607,52 @@ pub async fn fetch_transaction_cost(
     }
 }
 
+/// Fetch the anchor block id of an L2 block, logging blocks without a decodable anchor transaction
+pub async fn fetch_l2_anchor_block_id(
+    extractor: &Extractor,
+    header: &primitives::headers::L2Header,
+) -> Option<u64> {
+    match extractor.get_l2_anchor_block_id(alloy_primitives::B256::from(*header.hash)).await {
+        Ok(Some(anchor_block_id)) => Some(anchor_block_id),
+        Ok(None) => {
+            warn!(header_number = header.number, "L2 block has no anchor transaction");
+            None
+        }
+        Err(e) => {
+            warn!(header_number = header.number, err = %e, "Failed to fetch L2 anchor block id");
+            None
+        }
+    }
+}
+
+/// Fetch the excess blob gas and blob base fee of an L1 block
+pub async fn fetch_l1_blob_fee(extractor: &Extractor, l1_block_number: u64) -> Option<(u64, u128)> {
+    match extractor.get_l1_blob_fee(l1_block_number).await {
+        Ok(fee) => Some(fee),
+        Err(e) => {
+            warn!(err = %e, l1_block_number, "Failed to fetch L1 blob fee");
+            None
+        }
+    }
+}
+
 pub const fn average_cost_per_batch(total_cost: u128, num_batches: usize) -> u128 {
     if num_batches == 0 { 0 } else { total_cost / num_batches as u128 }
 }
+
+/// Milliseconds from a block timestamp (in seconds) until the header was observed, negative when
+/// it was observed before its own timestamp
+pub const fn propagation_delay_ms(block_ts: u64, observed_ms: u64) -> i64 {
+    observed_ms as i64 - (block_ts as i64).saturating_mul(1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn propagation_delay_is_signed() {
+        assert_eq!(propagation_delay_ms(1_700_000_000, 1_700_000_000_350), 350);
+        assert_eq!(propagation_delay_ms(1_700_000_001, 1_700_000_000_900), -100);
+    }
+}
@@ -0,0 +1,228 @@
+//! Fee attribution reconciliation
+//!
+//! The fee components served by the API attribute L2 revenues and L1 costs to sequencers from
+//! indexed blocks and batches. This task checks that attribution against the chain once per
+//! period: over the last complete period, the L2 balance of every sequencer should grow by the
+//! priority fees and its share of the base fees of the blocks it produced, and its L1 balance
+//! should shrink by the cost of the batches it proposed. Both comparisons are stored in
+//! `fee_reconciliations`, and differences above the tolerance are flagged and logged. Transfers
+//! to or from a sequencer address also show up as differences, so a flag points at either a
+//! bug in the attribution or activity outside of sequencing.
+
+use std::time::Duration;
+
+use alloy_primitives::{Address, U256};
+use chrono::{DateTime, Utc};
+use clickhouse::{
+    AddressBytes, ClickhouseReader, ClickhouseWriter, FeeReconciliationRow, SequencerFeeTotalsRow,
+};
+use extractor::Extractor;
+use eyre::{Result, eyre};
+use tracing::{error, info, warn};
+
+/// Base fee share of the sequencer used when the protocol config is unknown, in percent
+pub const DEFAULT_BASE_FEE_SHARING_PCTG: u8 = 75;
+
+/// Block range and time span a reconciliation covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    /// Start of the period in seconds
+    pub start: u64,
+    /// End of the period in seconds
+    pub end: u64,
+}
+
+/// Fee reconciliation methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic fee reconciliation task
+    pub fn start_fee_reconciliation_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let reader = self.clickhouse_reader.as_ref()?.clone();
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let extractor = self.extractor.clone();
+        let period_secs = self.fee_reconcile_period_secs;
+        let tolerance = self.fee_reconcile_tolerance_wei;
+        let sharing_pctg = self
+            .protocol_config
+            .as_ref()
+            .map_or(DEFAULT_BASE_FEE_SHARING_PCTG, |config| config.base_fee_sharing_pctg);
+
+        info!(period_secs, tolerance, sharing_pctg, "Starting fee reconciliation task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(period_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let now = Utc::now().timestamp() as u64;
+                let end = now / period_secs * period_secs;
+                let period = Period { start: end.saturating_sub(period_secs), end };
+                match reconcile_fees(&reader, &writer, &extractor, period, sharing_pctg, tolerance)
+                    .await
+                {
+                    Ok(0) => info!(period_end = period.end, "Sequencer fees reconciled"),
+                    Ok(flagged) => warn!(
+                        period_end = period.end,
+                        flagged, "Sequencer fees do not match balance changes"
+                    ),
+                    Err(e) => error!(err = %e, "Fee reconciliation failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Reconcile the fees attributed to every sequencer during `period` against the balance
+/// changes of its address on both chains. Returns the number of flagged comparisons.
+pub async fn reconcile_fees(
+    reader: &ClickhouseReader,
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    period: Period,
+    sharing_pctg: u8,
+    tolerance: u128,
+) -> Result<usize> {
+    let since = timestamp(period.start)?;
+    let until = timestamp(period.end)?;
+    let Some(blocks) = reader.get_reconciliation_blocks(since, until).await? else {
+        info!(period_end = period.end, "Not enough indexed blocks to reconcile fees");
+        return Ok(0);
+    };
+    let totals = reader
+        .get_sequencer_fee_totals(blocks.l1_from, blocks.l1_to, blocks.l2_from, blocks.l2_to)
+        .await?;
+
+    let mut rows = Vec::with_capacity(totals.len() * 2);
+    for fees in totals.iter().filter(|fees| fees.sequencer != AddressBytes::default()) {
+        let address = Address::from(fees.sequencer);
+        let (l2_expected, l1_expected) = expected_deltas(fees, sharing_pctg);
+
+        let l2_actual = balance_delta(
+            extractor.get_l2_eth_balance(address, blocks.l2_from).await?,
+            extractor.get_l2_eth_balance(address, blocks.l2_to).await?,
+        );
+        rows.push(reconciliation_row(
+            period,
+            fees.sequencer,
+            "l2",
+            (blocks.l2_from, blocks.l2_to),
+            l2_expected,
+            l2_actual,
+            tolerance,
+        ));
+
+        let l1_actual = balance_delta(
+            extractor.get_eth_balance(address, blocks.l1_from).await?,
+            extractor.get_eth_balance(address, blocks.l1_to).await?,
+        );
+        rows.push(reconciliation_row(
+            period,
+            fees.sequencer,
+            "l1",
+            (blocks.l1_from, blocks.l1_to),
+            l1_expected,
+            l1_actual,
+            tolerance,
+        ));
+    }
+
+    for row in rows.iter().filter(|row| row.flagged == 1) {
+        warn!(
+            sequencer = %Address::from(row.sequencer),
+            chain = row.chain,
+            expected_delta = %row.expected_delta,
+            actual_delta = %row.actual_delta,
+            discrepancy = %row.discrepancy,
+            "Attributed fees do not match the balance change"
+        );
+    }
+    writer.insert_fee_reconciliations(&rows).await?;
+    Ok(rows.iter().filter(|row| row.flagged == 1).count())
+}
+
+/// Balance changes of a sequencer on L2 and L1 implied by its attributed fees: the L2 balance
+/// receives the priority fees and `sharing_pctg` percent of the base fees, and the L1 balance
+/// pays for the proposed batches.
+pub fn expected_deltas(fees: &SequencerFeeTotalsRow, sharing_pctg: u8) -> (i128, i128) {
+    let shared_base_fee = fees.base_fee.saturating_mul(u128::from(sharing_pctg)) / 100;
+    let revenue = fees.priority_fee.saturating_add(shared_base_fee);
+    (to_i128(revenue), -to_i128(fees.l1_data_cost))
+}
+
+/// Comparison of `expected` with `actual`, flagged when they differ by more than `tolerance`.
+pub fn reconciliation_row(
+    period: Period,
+    sequencer: AddressBytes,
+    chain: &str,
+    (from_block, to_block): (u64, u64),
+    expected: i128,
+    actual: i128,
+    tolerance: u128,
+) -> FeeReconciliationRow {
+    let discrepancy = actual.saturating_sub(expected);
+    FeeReconciliationRow {
+        period_start: period.start,
+        period_end: period.end,
+        sequencer,
+        chain: chain.to_owned(),
+        from_block,
+        to_block,
+        expected_delta: expected,
+        actual_delta: actual,
+        discrepancy,
+        flagged: u8::from(discrepancy.unsigned_abs() > tolerance),
+    }
+}
+
+fn balance_delta(before: U256, after: U256) -> i128 {
+    to_i128(after.saturating_to::<u128>()) - to_i128(before.saturating_to::<u128>())
+}
+
+fn to_i128(value: u128) -> i128 {
+    i128::try_from(value).unwrap_or(i128::MAX)
+}
+
+fn timestamp(secs: u64) -> Result<DateTime<Utc>> {
+    DateTime::from_timestamp(secs as i64, 0).ok_or_else(|| eyre!("invalid timestamp {secs}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PERIOD: Period = Period { start: 1_700_000_000, end: 1_700_003_600 };
+
+    #[test]
+    fn expected_deltas_share_base_fee_and_charge_batch_costs() {
+        let fees = SequencerFeeTotalsRow {
+            sequencer: AddressBytes([1u8; 20]),
+            priority_fee: 1_000,
+            base_fee: 4_000,
+            l1_data_cost: 2_500,
+        };
+        assert_eq!(expected_deltas(&fees, 75), (4_000, -2_500));
+        assert_eq!(expected_deltas(&fees, 0), (1_000, -2_500));
+    }
+
+    #[test]
+    fn flags_discrepancies_above_tolerance() {
+        let sequencer = AddressBytes([1u8; 20]);
+        let row = reconciliation_row(PERIOD, sequencer, "l2", (10, 20), 4_000, 3_900, 100);
+        assert_eq!(row.discrepancy, -100);
+        assert_eq!(row.flagged, 0);
+
+        let row = reconciliation_row(PERIOD, sequencer, "l1", (10, 20), -2_500, -2_601, 100);
+        assert_eq!(row.discrepancy, -101);
+        assert_eq!(row.flagged, 1);
+        assert_eq!((row.from_block, row.to_block), (10, 20));
+    }
+
+    #[test]
+    fn balance_delta_is_signed() {
+        assert_eq!(balance_delta(U256::from(500), U256::from(200)), -300);
+        assert_eq!(balance_delta(U256::from(200), U256::from(500)), 300);
+    }
+}
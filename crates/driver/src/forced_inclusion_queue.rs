@@ -0,0 +1,137 @@
+//! Forced inclusion queue snapshots
+//!
+//! Users who are censored by the preconfirmation operators can queue a forced inclusion in the
+//! `ForcedInclusionStore`. Once the oldest queued forced inclusion reaches its deadline, given as
+//! a batch id, proposals without it revert. This task periodically records the number of queued
+//! forced inclusions and the age of the oldest one in the `forced_inclusion_queue` table,
+//! flagging snapshots whose oldest item is within the configured number of batches of its
+//! deadline. The forced inclusion monitor opens an incident while the latest snapshot is flagged.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use clickhouse::{ClickhouseWriter, ForcedInclusionQueueRow};
+use extractor::{Extractor, ForcedInclusionQueue};
+use eyre::Result;
+use tracing::{error, info, warn};
+
+/// Forced inclusion queue methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic forced inclusion queue snapshot task
+    pub fn start_forced_inclusion_queue_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let extractor = self.extractor.clone();
+        let interval_secs = self.forced_inclusion_queue_interval_secs;
+        let margin_batches = self.forced_inclusion_deadline_margin_batches;
+
+        info!(interval_secs, margin_batches, "Starting forced inclusion queue task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                match snapshot_forced_inclusion_queue(&writer, &extractor, margin_batches).await {
+                    Ok(row) if row.near_deadline == 1 => warn!(
+                        pending = row.pending,
+                        deadline_batch_id = ?row.deadline_batch_id,
+                        next_batch_id = row.next_batch_id,
+                        "Oldest forced inclusion approaching its deadline"
+                    ),
+                    Ok(_) => {}
+                    Err(e) => error!(err = %e, "Forced inclusion queue snapshot failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Record the forced inclusion queue at the latest L1 block
+pub async fn snapshot_forced_inclusion_queue(
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    margin_batches: u64,
+) -> Result<ForcedInclusionQueueRow> {
+    let l1_block_number = extractor.get_l1_latest_block_number().await?;
+    let queue = extractor.get_forced_inclusion_queue(l1_block_number).await?;
+    let oldest_created_ts = match queue.oldest_created_in {
+        Some(block) => Some(extractor.get_l1_block_by_number(block).await?.header.timestamp),
+        None => None,
+    };
+
+    let row = queue_snapshot(
+        Utc::now().timestamp() as u64,
+        l1_block_number,
+        &queue,
+        oldest_created_ts,
+        margin_batches,
+    );
+    writer.insert_forced_inclusion_queue(&row).await?;
+    Ok(row)
+}
+
+/// Snapshot row of `queue`, flagged when the oldest queued forced inclusion is at most
+/// `margin_batches` batches before its deadline
+pub fn queue_snapshot(
+    snapshot_ts: u64,
+    l1_block_number: u64,
+    queue: &ForcedInclusionQueue,
+    oldest_created_ts: Option<u64>,
+    margin_batches: u64,
+) -> ForcedInclusionQueueRow {
+    let near_deadline = queue.pending > 0 &&
+        queue.deadline_batch_id.is_some_and(|deadline| {
+            deadline.saturating_sub(queue.next_batch_id) <= margin_batches
+        });
+    ForcedInclusionQueueRow {
+        snapshot_ts,
+        l1_block_number,
+        pending: queue.pending,
+        oldest_created_ts,
+        oldest_age_secs: oldest_created_ts.map(|ts| snapshot_ts.saturating_sub(ts)),
+        deadline_batch_id: queue.deadline_batch_id,
+        next_batch_id: queue.next_batch_id,
+        near_deadline: u8::from(near_deadline),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(pending: u64, deadline_batch_id: Option<u64>) -> ForcedInclusionQueue {
+        ForcedInclusionQueue {
+            pending,
+            oldest_created_in: deadline_batch_id.map(|_| 100),
+            deadline_batch_id,
+            next_batch_id: 1_000,
+        }
+    }
+
+    #[test]
+    fn empty_queue_is_not_flagged() {
+        let row = queue_snapshot(1_700_000_600, 200, &queue(0, None), None, 2);
+        assert_eq!(row.pending, 0);
+        assert_eq!(row.oldest_age_secs, None);
+        assert_eq!(row.near_deadline, 0);
+    }
+
+    #[test]
+    fn flags_oldest_item_within_margin() {
+        let row =
+            queue_snapshot(1_700_000_600, 200, &queue(3, Some(1_005)), Some(1_700_000_000), 2);
+        assert_eq!(row.oldest_age_secs, Some(600));
+        assert_eq!(row.near_deadline, 0);
+
+        let row = queue_snapshot(1_700_000_600, 200, &queue(3, Some(1_002)), None, 2);
+        assert_eq!(row.near_deadline, 1);
+
+        // Overdue items stay flagged
+        let row = queue_snapshot(1_700_000_600, 200, &queue(3, Some(990)), None, 2);
+        assert_eq!(row.near_deadline, 1);
+    }
+}
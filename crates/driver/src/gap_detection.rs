@@ -1,9 +1,12 @@
 //! Gap detection and backfill functionality
 #![allow(missing_docs)]
 
-use std::{collections::HashSet, time::Duration};
+use std::{
+    collections::{BTreeSet, HashSet},
+    time::Duration,
+};
 
-use alloy_primitives::Address;
+use chrono::Utc;
 use clickhouse::{AddressBytes, ClickhouseReader, ClickhouseWriter, HashBytes, L2HeadEvent};
 use extractor::Extractor;
 use eyre::Result;
@@ -11,9 +14,46 @@ use messages::{
     BatchProposedWrapper, BatchesProvedWrapper, BatchesVerifiedWrapper,
     ForcedInclusionProcessedWrapper,
 };
+use primitives::block_stats::BlockStats;
 use tracing::{error, info, warn};
 
-use crate::event_handler::{EventHandler, GapDetectionState};
+use crate::{
+    backfill::{BackfillBudget, BackfillProgress, prioritize_gaps},
+    data_quality::log_search_windows,
+    event_handler::{EventHandler, GapDetectionState},
+};
+
+/// Maximum number of batch gaps of each kind handled per cycle
+const MAX_BATCH_GAPS_PER_CYCLE: u64 = 100;
+
+/// Number of L1 blocks requested per `eth_getLogs` call when backfilling batch events
+const BATCH_LOG_CHUNK_BLOCKS: u64 = 1_000;
+
+/// Parameters of the batch lifecycle gap checks
+#[derive(Debug, Clone, Copy)]
+pub struct BatchGapConfig {
+    /// Blocks to wait for finalization before backfilling
+    pub finalization_buffer: u64,
+    /// L1 blocks searched for missing proposals, proofs and verifications
+    pub lookback_blocks: u64,
+    /// Seconds after which a proposal without a proof is considered a gap
+    pub proof_window_secs: u64,
+    /// Seconds after which a proof without a verification is considered a gap
+    pub verify_window_secs: u64,
+}
+
+/// Outcome of a single batch gap detection cycle
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BatchGapReport {
+    /// Batch IDs missing between indexed proposals
+    pub missing_batch_ids: u64,
+    /// Proposals without a proof past the proof window
+    pub unproved: usize,
+    /// Proofs without a verification past the verification window
+    pub unverified: usize,
+    /// Events replayed from L1 logs during this cycle
+    pub backfilled: usize,
+}
 
 /// Retry an async operation with exponential backoff
 async fn retry_with_backoff<T, E, F, Fut>(operation: F, operation_name: &str) -> Result<T, E>
@@ -78,8 +118,6 @@ impl crate::driver::Driver {
         let reader = self.clickhouse_reader.as_ref()?.clone();
         let writer = self.clickhouse_writer.as_ref()?.clone();
         let extractor = self.extractor.clone();
-        let inbox_address = self.inbox_address;
-        let taiko_wrapper_address = self.taiko_wrapper_address;
         let enable_db_writes = self.enable_db_writes;
         let gap_dry_run = self.gap_dry_run;
         let finalization_buffer = self.gap_finalization_buffer_blocks;
@@ -87,6 +125,14 @@ impl crate::driver::Driver {
         let poll_interval = self.gap_poll_interval_secs;
         let min_l1_block = self.gap_min_l1_block;
         let min_l2_block = self.gap_min_l2_block;
+        let budget = self.backfill_budget.clone();
+        let batch_gap_config = BatchGapConfig {
+            finalization_buffer,
+            lookback_blocks: self.gap_batch_lookback_blocks,
+            proof_window_secs: self.batch_proof_timeout_secs,
+            verify_window_secs: self.batch_verify_cooldown_secs +
+                self.batch_verify_cooldown_margin_secs,
+        };
 
         info!("Starting gap detection task");
 
@@ -101,13 +147,12 @@ impl crate::driver::Driver {
                     &reader,
                     Some(&writer),
                     &extractor,
-                    inbox_address,
-                    taiko_wrapper_address,
                     enable_db_writes && !gap_dry_run,
                     finalization_buffer,
                     continuous_lookback,
                     min_l1_block,
                     min_l2_block,
+                    &budget,
                 )
                 .await
                 {
@@ -121,6 +166,26 @@ impl crate::driver::Driver {
                         error!(err = %e, "Gap detection failed");
                     }
                 }
+
+                match run_batch_gap_detection(
+                    &reader,
+                    Some(&writer),
+                    &extractor,
+                    enable_db_writes && !gap_dry_run,
+                    batch_gap_config,
+                    &budget,
+                )
+                .await
+                {
+                    Ok(report) => info!(
+                        missing_batch_ids = report.missing_batch_ids,
+                        unproved = report.unproved,
+                        unverified = report.unverified,
+                        backfilled = report.backfilled,
+                        "Batch gap detection cycle completed"
+                    ),
+                    Err(e) => error!(err = %e, "Batch gap detection failed"),
+                }
             }
         });
 
@@ -176,13 +241,12 @@ impl crate::driver::Driver {
             reader,
             writer,
             &self.extractor,
-            self.inbox_address,
-            self.taiko_wrapper_address,
             self.enable_db_writes && !self.gap_dry_run,
             self.gap_finalization_buffer_blocks,
             self.gap_startup_lookback_blocks,
             self.gap_min_l1_block,
             self.gap_min_l2_block,
+            &self.backfill_budget,
         )
         .await
         {
@@ -202,13 +266,12 @@ pub async fn run_initial_gap_catchup(
     reader: &ClickhouseReader,
     writer: Option<&ClickhouseWriter>,
     extractor: &Extractor,
-    inbox_address: Address,
-    taiko_wrapper_address: Address,
     enable_db_writes: bool,
     gap_finalization_buffer_blocks: u64,
     gap_startup_lookback_blocks: u64,
     gap_min_l1_block: u64,
     gap_min_l2_block: u64,
+    budget: &BackfillBudget,
 ) -> Result<()> {
     info!("Starting initial gap catch-up with startup lookback");
 
@@ -244,13 +307,12 @@ pub async fn run_initial_gap_catchup(
         reader,
         writer,
         extractor,
-        inbox_address,
-        taiko_wrapper_address,
         enable_db_writes,
         gap_finalization_buffer_blocks,
         gap_startup_lookback_blocks,
         gap_min_l1_block,
         gap_min_l2_block,
+        budget,
     )
     .await
     {
@@ -269,13 +331,12 @@ pub async fn run_gap_detection(
     reader: &ClickhouseReader,
     writer: Option<&ClickhouseWriter>,
     extractor: &Extractor,
-    inbox_address: Address,
-    taiko_wrapper_address: Address,
     enable_db_writes: bool,
     finalization_buffer: u64,
     lookback_blocks: u64,
     min_l1_block: u64,
     min_l2_block: u64,
+    budget: &BackfillBudget,
 ) -> Result<()> {
     // Verify RPC health before starting gap detection
     if !verify_rpc_health(extractor).await {
@@ -296,11 +357,10 @@ pub async fn run_gap_detection(
         writer,
         extractor,
         &gap_state,
-        inbox_address,
-        taiko_wrapper_address,
         enable_db_writes,
         l1_start_override,
         min_l1_block,
+        budget,
     )
     .await?;
 
@@ -312,6 +372,7 @@ pub async fn run_gap_detection(
         enable_db_writes,
         l2_start_override,
         min_l2_block,
+        budget,
     )
     .await?;
 
@@ -387,6 +448,139 @@ pub async fn get_gap_detection_state(
     Ok(state)
 }
 
+/// Run a single cycle of batch gap detection: find holes in batch IDs, proposals without proofs
+/// and proofs without verifications, and replay the missing events from targeted L1 log queries
+pub async fn run_batch_gap_detection(
+    reader: &ClickhouseReader,
+    writer: Option<&ClickhouseWriter>,
+    extractor: &Extractor,
+    enable_db_writes: bool,
+    config: BatchGapConfig,
+    budget: &BackfillBudget,
+) -> Result<BatchGapReport> {
+    let l1_end =
+        extractor.get_l1_latest_block_number().await?.saturating_sub(config.finalization_buffer);
+    let floor = l1_end.saturating_sub(config.lookback_blocks);
+    let mut report = BatchGapReport::default();
+
+    // Holes in batch IDs, most recent first: the proposals must lie between their indexed
+    // neighbours
+    for gap in reader.find_missing_batch_ids(floor, MAX_BATCH_GAPS_PER_CYCLE).await? {
+        report.missing_batch_ids += gap.last_missing - gap.first_missing + 1;
+        let mut missing: BTreeSet<u64> = (gap.first_missing..=gap.last_missing).collect();
+        info!(
+            first_missing = gap.first_missing,
+            last_missing = gap.last_missing,
+            l1_range = format!("{}..{}", gap.from_l1_block, gap.to_l1_block),
+            "Found missing batch IDs"
+        );
+        let lookback = gap.to_l1_block.saturating_sub(gap.from_l1_block);
+        for (from, to) in log_search_windows(gap.to_l1_block, lookback, BATCH_LOG_CHUNK_BLOCKS) {
+            if missing.is_empty() {
+                break;
+            }
+            budget.acquire().await;
+            for (batch, _, tx_hash) in extractor.get_batch_proposed_logs(from, to).await? {
+                if missing.remove(&batch.meta.batchId) {
+                    let wrapper = BatchProposedWrapper::from((batch, tx_hash, false));
+                    handle_batch_proposed_event_during_backfill(
+                        writer,
+                        extractor,
+                        wrapper,
+                        enable_db_writes,
+                    )
+                    .await?;
+                    report.backfilled += 1;
+                }
+            }
+        }
+    }
+
+    // Proposals without proofs past the proof window: proofs follow their proposal
+    let proof_cutoff = Utc::now() - chrono::Duration::seconds(config.proof_window_secs as i64);
+    let unproved =
+        reader.get_batches_without_proof(proof_cutoff, floor, MAX_BATCH_GAPS_PER_CYCLE).await?;
+    report.unproved = unproved.len();
+    if let Some(first_block) = unproved.iter().map(|b| b.l1_block_number).min() {
+        let mut missing: BTreeSet<u64> = unproved.iter().map(|b| b.batch_id).collect();
+        let lookback = l1_end.saturating_sub(first_block);
+        for (from, to) in log_search_windows(l1_end, lookback, BATCH_LOG_CHUNK_BLOCKS) {
+            if missing.is_empty() {
+                break;
+            }
+            budget.acquire().await;
+            for (proved, block_number, tx_hash) in
+                extractor.get_batches_proved_logs(from, to).await?
+            {
+                if take_proved(&mut missing, proved.batch_ids_proved()) {
+                    let wrapper =
+                        BatchesProvedWrapper::from((proved, block_number, tx_hash, false));
+                    handle_batches_proved_event_during_backfill(
+                        writer,
+                        extractor,
+                        wrapper,
+                        enable_db_writes,
+                    )
+                    .await?;
+                    report.backfilled += 1;
+                }
+            }
+        }
+    }
+
+    // Proofs without verifications past the verification window
+    let verify_cutoff = Utc::now() - chrono::Duration::seconds(config.verify_window_secs as i64);
+    let unverified = reader
+        .get_proved_batches_without_verification(verify_cutoff, floor, MAX_BATCH_GAPS_PER_CYCLE)
+        .await?;
+    report.unverified = unverified.len();
+    if let Some(first_block) = unverified.iter().map(|b| b.l1_block_number).min() {
+        let mut missing: BTreeSet<u64> = unverified.iter().map(|b| b.batch_id).collect();
+        let lookback = l1_end.saturating_sub(first_block);
+        for (from, to) in log_search_windows(l1_end, lookback, BATCH_LOG_CHUNK_BLOCKS) {
+            if missing.is_empty() {
+                break;
+            }
+            budget.acquire().await;
+            for (verified, block_number, tx_hash) in
+                extractor.get_batches_verified_logs(from, to).await?
+            {
+                if take_verified(&mut missing, verified.batch_id) {
+                    let wrapper =
+                        BatchesVerifiedWrapper::from((verified, block_number, tx_hash, false));
+                    handle_batches_verified_event_during_backfill(
+                        writer,
+                        extractor,
+                        wrapper,
+                        enable_db_writes,
+                    )
+                    .await?;
+                    report.backfilled += 1;
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Remove the batches proved by an event from `missing`, returning whether any was missing
+pub fn take_proved(missing: &mut BTreeSet<u64>, batch_ids: &[u64]) -> bool {
+    let before = missing.len();
+    for batch_id in batch_ids {
+        missing.remove(batch_id);
+    }
+    missing.len() != before
+}
+
+/// Remove the batches covered by a verification of `batch_id` from `missing`, returning whether
+/// any was missing. A verification covers every batch up to its batch ID.
+pub fn take_verified(missing: &mut BTreeSet<u64>, batch_id: u64) -> bool {
+    let before = missing.len();
+    missing.retain(|&id| id > batch_id);
+    missing.len() != before
+}
+
 /// Process L1 gaps and perform backfill if needed
 #[allow(clippy::too_many_arguments)]
 pub async fn process_l1_gaps(
@@ -394,19 +588,21 @@ pub async fn process_l1_gaps(
     writer: Option<&ClickhouseWriter>,
     extractor: &Extractor,
     state: &GapDetectionState,
-    inbox_address: Address,
-    taiko_wrapper_address: Address,
     enable_db_writes: bool,
     start_block_override: Option<u64>,
     min_l1_block: u64,
+    budget: &BackfillBudget,
 ) -> Result<()> {
+    let progress_writer = writer.filter(|_| enable_db_writes);
     let start_block = start_block_override.unwrap_or(state.latest_l1_db + 1);
     if start_block > state.l1_backfill_end {
+        BackfillProgress::new("l1", &[], budget).report(progress_writer).await;
         return Ok(());
     }
 
     let l1_gaps = reader.find_missing_l1_blocks(start_block, state.l1_backfill_end).await?;
     if l1_gaps.is_empty() {
+        BackfillProgress::new("l1", &[], budget).report(progress_writer).await;
         return Ok(());
     }
 
@@ -423,6 +619,7 @@ pub async fn process_l1_gaps(
 
         if still_missing.is_empty() {
             info!("All L1 gaps were filled by live processing, skipping backfill");
+            BackfillProgress::new("l1", &[], budget).report(progress_writer).await;
         } else {
             info!(
                 gaps = still_missing.len(),
@@ -432,10 +629,9 @@ pub async fn process_l1_gaps(
                 writer,
                 extractor,
                 still_missing,
-                inbox_address,
-                taiko_wrapper_address,
                 enable_db_writes,
                 min_l1_block,
+                budget,
             )
             .await?;
         }
@@ -447,6 +643,7 @@ pub async fn process_l1_gaps(
 }
 
 /// Process L2 gaps and perform backfill if needed
+#[allow(clippy::too_many_arguments)]
 pub async fn process_l2_gaps(
     reader: &ClickhouseReader,
     writer: Option<&ClickhouseWriter>,
@@ -455,14 +652,18 @@ pub async fn process_l2_gaps(
     enable_db_writes: bool,
     start_block_override: Option<u64>,
     min_l2_block: u64,
+    budget: &BackfillBudget,
 ) -> Result<()> {
+    let progress_writer = writer.filter(|_| enable_db_writes);
     let start_block = start_block_override.unwrap_or(state.latest_l2_db + 1);
     if start_block > state.l2_backfill_end {
+        BackfillProgress::new("l2", &[], budget).report(progress_writer).await;
         return Ok(());
     }
 
     let l2_gaps = reader.find_missing_l2_blocks(start_block, state.l2_backfill_end).await?;
     if l2_gaps.is_empty() {
+        BackfillProgress::new("l2", &[], budget).report(progress_writer).await;
         return Ok(());
     }
 
@@ -479,13 +680,21 @@ pub async fn process_l2_gaps(
 
         if still_missing.is_empty() {
             info!("All L2 gaps were filled by live processing, skipping backfill");
+            BackfillProgress::new("l2", &[], budget).report(progress_writer).await;
         } else {
             info!(
                 gaps = still_missing.len(),
                 "Confirmed L2 gaps still missing after double-check: {:?}", still_missing
             );
-            backfill_l2_blocks(writer, extractor, still_missing, enable_db_writes, min_l2_block)
-                .await?;
+            backfill_l2_blocks(
+                writer,
+                extractor,
+                still_missing,
+                enable_db_writes,
+                min_l2_block,
+                budget,
+            )
+            .await?;
         }
     } else {
         info!(gaps = l2_gaps.len(), "🧪 DRY-RUN: Would backfill L2 gaps: {:?}", l2_gaps);
@@ -520,10 +729,9 @@ pub async fn backfill_l1_blocks(
     writer: Option<&ClickhouseWriter>,
     extractor: &Extractor,
     block_numbers: Vec<u64>,
-    inbox_address: Address,
-    taiko_wrapper_address: Address,
     enable_db_writes: bool,
     min_l1_block: u64,
+    budget: &BackfillBudget,
 ) -> Result<()> {
     // Filter out blocks before the minimum L1 block number
     let original_count = block_numbers.len();
@@ -541,13 +749,26 @@ pub async fn backfill_l1_blocks(
         );
     }
 
+    // Fill the blocks closest to the head first
+    let filtered_blocks = prioritize_gaps(filtered_blocks);
+    let progress_writer = writer.filter(|_| enable_db_writes);
+    let mut progress = BackfillProgress::new("l1", &filtered_blocks, budget);
+    progress.report(progress_writer).await;
+
     let mut consecutive_failures = 0;
     const MAX_CONSECUTIVE_FAILURES: u32 = 5;
 
     for block_number in filtered_blocks {
+        if progress.processed() > 0 && progress.report_due() {
+            progress.report(progress_writer).await;
+        }
+
         // Use retry logic for block fetching
         let block_result = retry_with_backoff(
-            || extractor.get_l1_block_by_number(block_number),
+            || async move {
+                budget.acquire().await;
+                extractor.get_l1_block_by_number(block_number).await
+            },
             &format!("fetch L1 block {}", block_number),
         )
         .await;
@@ -570,6 +791,7 @@ pub async fn backfill_l1_blocks(
                     let Err(e) = w.insert_l1_header(&header).await
                 {
                     error!(block_number = block_number, err = %e, "Failed to backfill L1 header");
+                    progress.record(block_number, false);
                     continue;
                 }
                 if !enable_db_writes {
@@ -582,6 +804,7 @@ pub async fn backfill_l1_blocks(
 
                 // Process preconf data for backfill
                 if enable_db_writes {
+                    budget.acquire().await;
                     process_preconf_data_for_backfill(writer, extractor, &header).await;
                 } else {
                     info!(
@@ -591,15 +814,9 @@ pub async fn backfill_l1_blocks(
                 }
 
                 // Process all Taiko events from this L1 block
-                process_l1_block_taiko_events(
-                    writer,
-                    extractor,
-                    &block,
-                    inbox_address,
-                    taiko_wrapper_address,
-                    enable_db_writes,
-                )
-                .await?;
+                process_l1_block_taiko_events(writer, extractor, &block, enable_db_writes, budget)
+                    .await?;
+                progress.record(block_number, true);
 
                 if enable_db_writes {
                     info!(
@@ -621,6 +838,7 @@ pub async fn backfill_l1_blocks(
                     err = %e,
                     "Could not fetch L1 block for backfill after retries"
                 );
+                progress.record(block_number, false);
 
                 // Circuit breaker: stop processing if too many consecutive failures
                 if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
@@ -634,6 +852,7 @@ pub async fn backfill_l1_blocks(
             }
         }
     }
+    progress.report(progress_writer).await;
     Ok(())
 }
 
@@ -642,9 +861,8 @@ pub async fn process_l1_block_taiko_events(
     writer: Option<&ClickhouseWriter>,
     extractor: &Extractor,
     block: &alloy_rpc_types_eth::Block,
-    inbox_address: Address,
-    taiko_wrapper_address: Address,
     enable_db_writes: bool,
+    budget: &BackfillBudget,
 ) -> Result<()> {
     use chainio::{
         BatchesVerified,
@@ -657,6 +875,8 @@ pub async fn process_l1_block_taiko_events(
     };
 
     let block_number = block.header.number;
+    let inbox_address = extractor.inbox_address_at(block_number);
+    let taiko_wrapper_address = extractor.taiko_wrapper_address_at(block_number);
     let mut events_found = 0;
 
     info!(
@@ -668,6 +888,7 @@ pub async fn process_l1_block_taiko_events(
     // Process all transactions in the block to find Taiko events
     for tx_hash in block.transactions.hashes() {
         // Get transaction receipt to access logs
+        budget.acquire().await;
         match extractor.get_receipt(tx_hash).await {
             Ok(receipt) => {
                 for log in receipt.logs() {
@@ -761,6 +982,8 @@ pub async fn process_l1_block_taiko_events(
                             );
                             let wrapper = ForcedInclusionProcessedWrapper::from((
                                 decoded.data().clone(),
+                                block_number,
+                                tx_hash,
                                 false, // not reorged
                             ));
                             handle_forced_inclusion_event_during_backfill(
@@ -862,6 +1085,7 @@ pub async fn backfill_l2_blocks(
     block_numbers: Vec<u64>,
     enable_db_writes: bool,
     min_l2_block: u64,
+    budget: &BackfillBudget,
 ) -> Result<()> {
     // Filter out blocks before the minimum L2 block number
     let original_count = block_numbers.len();
@@ -879,13 +1103,26 @@ pub async fn backfill_l2_blocks(
         );
     }
 
+    // Fill the blocks closest to the head first
+    let filtered_blocks = prioritize_gaps(filtered_blocks);
+    let progress_writer = writer.filter(|_| enable_db_writes);
+    let mut progress = BackfillProgress::new("l2", &filtered_blocks, budget);
+    progress.report(progress_writer).await;
+
     let mut consecutive_failures = 0;
     const MAX_CONSECUTIVE_FAILURES: u32 = 5;
 
     for block_number in filtered_blocks {
+        if progress.processed() > 0 && progress.report_due() {
+            progress.report(progress_writer).await;
+        }
+
         // Use retry logic for block fetching
         let block_result = retry_with_backoff(
-            || extractor.get_l2_block_by_number(block_number),
+            || async move {
+                budget.acquire().await;
+                extractor.get_l2_block_by_number(block_number).await
+            },
             &format!("fetch L2 block {}", block_number),
         )
         .await;
@@ -899,30 +1136,34 @@ pub async fn backfill_l2_blocks(
                     parent_hash: block.header.parent_hash,
                     timestamp: block.header.timestamp,
                     gas_used: block.header.gas_used,
+                    gas_limit: block.header.gas_limit,
                     beneficiary: block.header.beneficiary,
                     base_fee_per_gas: block.header.base_fee_per_gas.unwrap_or(0),
+                    extra_data: block.header.extra_data.clone(),
                 };
 
                 // Use same stats calculation as processor
-                let (sum_gas_used, sum_tx, sum_priority_fee) = extractor
+                budget.acquire().await;
+                let stats = extractor
                     .get_l2_block_stats(alloy_primitives::B256::from(*header.hash), header.base_fee_per_gas)
                     .await
                     .unwrap_or_else(|e| {
                         error!(header_number = header.number, err = %e, "Failed to get L2 block stats for backfill, using defaults");
-                        (0, 0, 0)
+                        BlockStats::default()
                     });
 
-                let sum_base_fee = sum_gas_used.saturating_mul(header.base_fee_per_gas as u128);
-
                 let event = L2HeadEvent {
                     l2_block_number: header.number,
                     block_hash: HashBytes(*header.hash),
                     block_ts: header.timestamp,
-                    sum_gas_used,
-                    sum_tx,
-                    sum_priority_fee,
-                    sum_base_fee,
+                    sum_gas_used: stats.sum_gas_used,
+                    gas_limit: header.gas_limit,
+                    sum_tx: stats.tx_count,
+                    sum_priority_fee: stats.sum_priority_fee,
+                    sum_base_fee: stats.sum_burned_fee,
+                    avg_gas_price: stats.avg_gas_price,
                     sequencer: AddressBytes(header.beneficiary.into_array()),
+                    extra_data: header.extra_data.to_string(),
                 };
 
                 if enable_db_writes &&
@@ -930,7 +1171,11 @@ pub async fn backfill_l2_blocks(
                     let Err(e) = w.insert_l2_header(&event).await
                 {
                     error!(block_number = block_number, err = %e, "Failed to backfill L2 block");
-                } else if enable_db_writes && let Some(_w) = writer {
+                    progress.record(block_number, false);
+                    continue;
+                }
+                progress.record(block_number, true);
+                if enable_db_writes && writer.is_some() {
                     info!(block_number = block_number, "Successfully backfilled L2 block");
                 } else {
                     info!(
@@ -949,6 +1194,7 @@ pub async fn backfill_l2_blocks(
                     err = %e,
                     "Could not fetch L2 block for backfill after retries"
                 );
+                progress.record(block_number, false);
 
                 // Circuit breaker: stop processing if too many consecutive failures
                 if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
@@ -962,6 +1208,7 @@ pub async fn backfill_l2_blocks(
             }
         }
     }
+    progress.report(progress_writer).await;
     Ok(())
 }
 
@@ -971,69 +1218,32 @@ pub async fn process_preconf_data_for_backfill(
     extractor: &Extractor,
     header: &primitives::headers::L1Header,
 ) {
-    // Get operator candidates for current epoch
-    let opt_candidates = match extractor.get_operator_candidates_for_current_epoch().await {
-        Ok(c) => {
-            info!(
-                slot = header.slot,
-                block = header.number,
-                candidates = ?c,
-                candidates_count = c.len(),
-                "Successfully retrieved operator candidates for backfill"
-            );
-            Some(c)
-        }
-        Err(e) => {
-            error!(
-                slot = header.slot,
-                block = header.number,
-                err = %e,
-                "Failed picking operator candidates during backfill"
-            );
-            None
-        }
-    };
-    let candidates = opt_candidates.unwrap_or_else(Vec::new);
-
-    // Get current operator for epoch
-    let opt_current_operator = match extractor.get_operator_for_current_epoch().await {
-        Ok(op) => {
-            info!(
-                block = header.number,
-                operator = ?op,
-                "Current operator for epoch during backfill"
-            );
-            Some(op)
-        }
-        Err(e) => {
-            error!(
-                block = header.number,
-                err = %e,
-                "get_operator_for_current_epoch failed during backfill"
-            );
-            None
-        }
-    };
-
-    // Get next operator for epoch
-    let opt_next_operator = match extractor.get_operator_for_next_epoch().await {
-        Ok(op) => {
-            info!(
-                block = header.number,
-                operator = ?op,
-                "Next operator for epoch during backfill"
-            );
-            Some(op)
-        }
-        Err(e) => {
-            error!(
-                block = header.number,
-                err = %e,
-                "get_operator_for_next_epoch failed during backfill"
-            );
-            None
-        }
-    };
+    // Read candidates, operators and inbox stats in one round trip
+    let snapshot = extractor.get_preconf_snapshot(header.number).await;
+    info!(
+        slot = header.slot,
+        block = header.number,
+        candidates = ?snapshot.candidates,
+        current_operator = ?snapshot.current_operator,
+        next_operator = ?snapshot.next_operator,
+        "Retrieved preconf snapshot for backfill"
+    );
+    if snapshot.candidates.is_none() {
+        error!(
+            slot = header.slot,
+            block = header.number,
+            "Failed picking operator candidates during backfill"
+        );
+    }
+    if snapshot.current_operator.is_none() {
+        error!(block = header.number, "get_operator_for_current_epoch failed during backfill");
+    }
+    if snapshot.next_operator.is_none() {
+        error!(block = header.number, "get_operator_for_next_epoch failed during backfill");
+    }
+    let candidates = snapshot.candidates.unwrap_or_default();
+    let opt_current_operator = snapshot.current_operator;
+    let opt_next_operator = snapshot.next_operator;
 
     // Insert preconf data if we have at least one operator and a writer
     if let Some(writer) = writer {
@@ -1146,6 +1356,8 @@ pub fn decode_taiko_event_from_log(
         if let Ok(decoded) = log.log_decode::<ForcedInclusionProcessed>() {
             let wrapper = messages::ForcedInclusionProcessedWrapper::from((
                 decoded.data().clone(),
+                l1_block_number,
+                l1_tx_hash,
                 false, // not reorged
             ));
             return Some(DecodedEvent::ForcedInclusionProcessed(wrapper));
@@ -1192,6 +1404,23 @@ mod tests {
         assert_eq!(calculate_lookback_start(0, 50), 1);
     }
 
+    #[test]
+    fn test_take_proved() {
+        let mut missing = BTreeSet::from([3, 5, 7]);
+        assert!(take_proved(&mut missing, &[1, 3, 5]));
+        assert_eq!(missing, BTreeSet::from([7]));
+        assert!(!take_proved(&mut missing, &[8]));
+        assert_eq!(missing, BTreeSet::from([7]));
+    }
+
+    #[test]
+    fn test_take_verified_covers_earlier_batches() {
+        let mut missing = BTreeSet::from([3, 5, 7]);
+        assert!(!take_verified(&mut missing, 2));
+        assert!(take_verified(&mut missing, 5));
+        assert_eq!(missing, BTreeSet::from([7]));
+    }
+
     #[test]
     fn test_decode_taiko_event_from_log_basic() {
         // This test verifies the function structure without complex event encoding
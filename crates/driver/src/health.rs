@@ -0,0 +1,180 @@
+//! Health endpoint of the indexer
+//!
+//! Reports which of the configured L1 and L2 RPC endpoints the extractor is connected to. The
+//! status is `degraded` while either chain runs on a fallback endpoint. Every endpoint carries
+//! the score of its recent RPC calls and whether it is quarantined for failing too many. The
+//! depth of the event stream channels is reported as well, and the status is also `degraded`
+//! while any of them is under backpressure. When the data-quality checker runs, its counters
+//! are included too.
+
+use std::net::SocketAddr;
+
+use api_types::{HealthResponse, RpcEndpointHealth, StreamChannelHealth};
+use axum::{Json, Router, extract::State, routing::get};
+use extractor::{ChannelStats, Extractor, OverflowPolicy, RpcEndpoints};
+use network::endpoints::EndpointStatus;
+use tracing::{error, info};
+
+use crate::data_quality::DataQualityStats;
+
+/// Summarise the endpoint health of both chains and the depth of the event stream channels
+pub fn health_response(endpoints: &RpcEndpoints, channels: &[ChannelStats]) -> HealthResponse {
+    let on_fallback = [&endpoints.l1, &endpoints.l2]
+        .iter()
+        .any(|chain| chain.iter().skip(1).any(|endpoint| endpoint.active));
+    let backpressured = channels.iter().any(ChannelStats::backpressured);
+    let rpc_endpoints =
+        endpoint_health("l1", &endpoints.l1).chain(endpoint_health("l2", &endpoints.l2)).collect();
+    HealthResponse {
+        status: if on_fallback || backpressured { "degraded" } else { "ok" }.to_owned(),
+        rpc_endpoints,
+        stream_channels: channels.iter().map(channel_health).collect(),
+        data_quality: None,
+    }
+}
+
+fn channel_health(channel: &ChannelStats) -> StreamChannelHealth {
+    StreamChannelHealth {
+        stream: channel.stream.to_owned(),
+        policy: match channel.policy {
+            OverflowPolicy::Block => "block",
+            OverflowPolicy::DropOldest => "drop-oldest",
+        }
+        .to_owned(),
+        capacity: channel.capacity as u64,
+        depth: channel.depth as u64,
+        dropped: channel.dropped,
+        blocked: channel.blocked,
+        backpressured: channel.backpressured(),
+    }
+}
+
+fn endpoint_health<'a>(
+    chain: &'static str,
+    endpoints: &'a [EndpointStatus],
+) -> impl Iterator<Item = RpcEndpointHealth> + 'a {
+    endpoints.iter().enumerate().map(move |(i, endpoint)| RpcEndpointHealth {
+        chain: chain.to_owned(),
+        host: endpoint.url.host_str().unwrap_or_default().to_owned(),
+        primary: i == 0,
+        active: endpoint.active,
+        healthy: endpoint.healthy,
+        failures: endpoint.failures,
+        score: endpoint.score,
+        quarantined: endpoint.quarantined,
+    })
+}
+
+/// State of the health handler
+#[derive(Debug, Clone)]
+struct HealthState {
+    extractor: Extractor,
+    data_quality: Option<DataQualityStats>,
+}
+
+async fn health(State(state): State<HealthState>) -> Json<HealthResponse> {
+    let mut response =
+        health_response(&state.extractor.rpc_endpoints(), &state.extractor.stream_channels());
+    response.data_quality = state.data_quality.as_ref().map(DataQualityStats::snapshot);
+    Json(response)
+}
+
+/// Health server methods for the Driver
+impl crate::driver::Driver {
+    /// Serve `/health` on the admin host and the configured health port
+    pub fn start_health_server(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let port = self.health_port?;
+        let addr: SocketAddr = match format!("{}:{}", self.admin_host, port).parse() {
+            Ok(addr) => addr,
+            Err(e) => {
+                error!(err = %e, "Invalid health server address");
+                return None;
+            }
+        };
+        let state = HealthState {
+            extractor: self.extractor.clone(),
+            data_quality: self.enable_data_quality_checks.then(|| self.data_quality_stats.clone()),
+        };
+        let app = Router::new().route("/health", get(health)).with_state(state);
+
+        let handle = tokio::spawn(async move {
+            info!("Starting health server on {}", addr);
+            let listener = match tokio::net::TcpListener::bind(addr).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    error!(%addr, err = %e, "Failed to bind health server");
+                    return;
+                }
+            };
+            if let Err(e) = axum::serve(listener, app).await {
+                error!(err = %e, "Health server failed");
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(url: &str, active: bool) -> EndpointStatus {
+        EndpointStatus {
+            url: url.parse().unwrap(),
+            active,
+            healthy: active,
+            consecutive_failures: 0,
+            failures: u64::from(!active),
+            connects: 1,
+            score: 100,
+            quarantined: false,
+        }
+    }
+
+    #[test]
+    fn degraded_while_on_fallback() {
+        let endpoints = RpcEndpoints {
+            l1: vec![status("wss://l1.example/key", true)],
+            l2: vec![status("wss://l2.example", false), status("ws://fallback:8546", true)],
+        };
+
+        let response = health_response(&endpoints, &[]);
+        assert_eq!(response.status, "degraded");
+        assert_eq!(response.rpc_endpoints.len(), 3);
+        assert_eq!(response.rpc_endpoints[0].host, "l1.example");
+        assert!(response.rpc_endpoints[1].primary && !response.rpc_endpoints[1].active);
+        assert_eq!(response.rpc_endpoints[2].chain, "l2");
+        assert!(response.rpc_endpoints[2].active && !response.rpc_endpoints[2].primary);
+
+        let endpoints =
+            RpcEndpoints { l1: endpoints.l1, l2: vec![status("wss://l2.example", true)] };
+        assert_eq!(health_response(&endpoints, &[]).status, "ok");
+    }
+
+    #[test]
+    fn degraded_under_backpressure() {
+        let endpoints = RpcEndpoints {
+            l1: vec![status("wss://l1.example", true)],
+            l2: vec![status("wss://l2.example", true)],
+        };
+        let mut channel = ChannelStats {
+            stream: "l2_headers",
+            policy: OverflowPolicy::Block,
+            capacity: 100,
+            depth: 10,
+            dropped: 0,
+            blocked: 3,
+        };
+
+        let response = health_response(&endpoints, std::slice::from_ref(&channel));
+        assert_eq!(response.status, "ok");
+        assert_eq!(response.stream_channels[0].policy, "block");
+        assert!(!response.stream_channels[0].backpressured);
+
+        channel.depth = 85;
+        let response = health_response(&endpoints, &[channel]);
+        assert_eq!(response.status, "degraded");
+        assert!(response.stream_channels[0].backpressured);
+    }
+}
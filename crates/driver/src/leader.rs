@@ -0,0 +1,270 @@
+//! Leader election between driver instances
+//!
+//! During a rolling deploy the old and the new driver run side by side for a while. With leader
+//! election enabled both keep their subscriptions open, but only the leader ingests events and
+//! runs the background writers and monitors, which are started on promotion and aborted when
+//! leadership is lost. Every instance renews a lease in `driver_leases`; the live instance that
+//! started contending first leads, so a new instance waits until the old one releases the lease
+//! on shutdown or stops renewing it for the lease TTL. Contention start times are taken from the
+//! `ClickHouse` server clock, so the instance clocks need not be synchronized. The standby takes
+//! over without resubscribing, and gap detection fills blocks missed during the handoff. Each
+//! change is recorded in `leader_changes`.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use clickhouse::{ClickhouseWriter, DriverLeaseInsertRow, LeaderChangeInsertRow, LeaseHolderRow};
+use eyre::Result;
+use tokio::sync::watch;
+use tracing::{error, info, warn};
+
+/// Lease held by the instance ingesting events
+pub const INGEST_LEASE: &str = "ingest";
+
+/// Lease holder name of this process: the host name followed by the process id
+pub fn default_instance_id() -> String {
+    let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "taikoscope".to_owned());
+    format!("{host}-{}", std::process::id())
+}
+
+/// Whether this instance currently ingests events, shared with the election task
+#[derive(Debug, Clone)]
+pub struct Leadership(Arc<watch::Sender<bool>>);
+
+impl Leadership {
+    /// Start as leader or as standby
+    pub fn new(is_leader: bool) -> Self {
+        Self(Arc::new(watch::Sender::new(is_leader)))
+    }
+
+    /// Whether this instance is the leader
+    pub fn is_leader(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Update the leadership, returning whether it changed
+    pub fn set(&self, is_leader: bool) -> bool {
+        self.0.send_replace(is_leader) != is_leader
+    }
+
+    /// Receiver notified of every leadership change
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.0.subscribe()
+    }
+}
+
+/// Leader among the live holders of a lease: the earliest contender, ties broken by name
+pub fn elect(holders: &[LeaseHolderRow]) -> Option<&str> {
+    holders
+        .iter()
+        .min_by(|a, b| (a.started_at, &a.holder).cmp(&(b.started_at, &b.holder)))
+        .map(|h| h.holder.as_str())
+}
+
+/// Lease row this instance renews. It is dropped when leadership is lost, so a recovered
+/// instance contends again from a fresh server time instead of reclaiming the lease from the
+/// standby that took over.
+#[derive(Debug)]
+struct Contention {
+    holder: String,
+    row: Option<DriverLeaseInsertRow>,
+}
+
+impl Contention {
+    const fn new(holder: String) -> Self {
+        Self { holder, row: None }
+    }
+
+    /// Current lease row, if contention has started
+    const fn row(&self) -> Option<&DriverLeaseInsertRow> {
+        self.row.as_ref()
+    }
+
+    /// Start contending at `started_at`, keeping the row of an ongoing contention
+    fn start(&mut self, started_at: u64) -> &DriverLeaseInsertRow {
+        self.row.get_or_insert_with(|| DriverLeaseInsertRow {
+            lease: INGEST_LEASE.to_owned(),
+            holder: self.holder.clone(),
+            started_at,
+            released: 0,
+        })
+    }
+
+    /// Stop contending; the next renewal starts over from the server clock
+    fn reset(&mut self) {
+        self.row = None;
+    }
+}
+
+/// Renew the lease of `row.holder` and return the current leader
+async fn renew(
+    writer: &ClickhouseWriter,
+    row: &DriverLeaseInsertRow,
+    ttl_secs: u64,
+) -> Result<Option<String>> {
+    writer.insert_driver_lease(row).await?;
+    let holders = writer.get_live_lease_holders(&row.lease, ttl_secs).await?;
+    Ok(elect(&holders).map(str::to_owned))
+}
+
+/// Leader election methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic lease renewal task. Without a writer there is no lease to contend
+    /// for, so the instance leads on its own.
+    pub fn start_leader_election_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let Some(writer) = self.clickhouse_writer.clone() else {
+            warn!("Leader election needs database writes; leading without a lease");
+            self.leadership.set(true);
+            return None;
+        };
+        let leadership = self.leadership.clone();
+        let holder = self.leader_instance_id.clone();
+        let ttl_secs = self.leader_lease_ttl_secs;
+        let interval_secs = self.leader_renew_interval_secs;
+
+        info!(holder, ttl_secs, interval_secs, "Starting leader election task");
+
+        let handle = tokio::spawn(async move {
+            let mut contention = Contention::new(holder.clone());
+            let ttl = Duration::from_secs(ttl_secs);
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut last_renewed = Instant::now();
+            let mut current_leader: Option<String> = None;
+
+            loop {
+                interval.tick().await;
+
+                // Contention starts at the first successful read of the server clock
+                let row = match contention.row() {
+                    Some(row) => row.clone(),
+                    None => match writer.server_time_ms().await {
+                        Ok(started_at) => contention.start(started_at).clone(),
+                        Err(e) => {
+                            error!(err = %e, "Failed to read the ClickHouse server time");
+                            continue;
+                        }
+                    },
+                };
+
+                let leader = match renew(&writer, &row, ttl_secs).await {
+                    Ok(leader) => {
+                        last_renewed = Instant::now();
+                        leader
+                    }
+                    Err(e) => {
+                        error!(err = %e, "Failed to renew leader lease");
+                        // Other instances consider the lease expired after the TTL
+                        if last_renewed.elapsed() >= ttl {
+                            contention.reset();
+                            if leadership.set(false) {
+                                warn!(holder, "Lease expired, standing by");
+                            }
+                        }
+                        continue;
+                    }
+                };
+
+                let is_leader = leader.as_deref() == Some(holder.as_str());
+                if leadership.set(is_leader) {
+                    if is_leader {
+                        info!(holder, previous = ?current_leader, "Became leader, ingesting events");
+                        let change = LeaderChangeInsertRow {
+                            lease: INGEST_LEASE.to_owned(),
+                            holder: holder.clone(),
+                            previous_holder: current_leader.clone(),
+                        };
+                        if let Err(e) = writer.insert_leader_change(&change).await {
+                            error!(err = %e, "Failed to record leadership change");
+                        }
+                    } else {
+                        contention.reset();
+                        warn!(holder, leader = ?leader, "Lost leadership, standing by");
+                    }
+                }
+                if leader != current_leader {
+                    info!(leader = ?leader, "Ingest leader changed");
+                    current_leader = leader;
+                }
+            }
+        });
+
+        Some(handle)
+    }
+
+    /// Release the lease so a standby instance takes over without waiting for it to expire
+    pub async fn release_leadership(&self) {
+        self.leadership.set(false);
+        let Some(writer) = &self.clickhouse_writer else {
+            return;
+        };
+        let row = DriverLeaseInsertRow {
+            lease: INGEST_LEASE.to_owned(),
+            holder: self.leader_instance_id.clone(),
+            started_at: 0,
+            released: 1,
+        };
+        match writer.insert_driver_lease(&row).await {
+            Ok(()) => info!(holder = %row.holder, "Released leader lease"),
+            Err(e) => error!(err = %e, "Failed to release leader lease"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holder(name: &str, started_at: u64) -> LeaseHolderRow {
+        LeaseHolderRow { holder: name.to_owned(), started_at }
+    }
+
+    #[test]
+    fn earliest_contender_leads() {
+        let holders = [holder("new", 2_000), holder("old", 1_000)];
+        assert_eq!(elect(&holders), Some("old"));
+        assert_eq!(elect(&[holder("b", 1), holder("a", 1)]), Some("a"));
+        assert_eq!(elect(&[]), None);
+    }
+
+    #[test]
+    fn recovered_instance_does_not_reclaim_the_lease() {
+        let mut old = Contention::new("old".to_owned());
+        assert_eq!(old.start(1_000).started_at, 1_000);
+        assert_eq!(old.start(3_000).started_at, 1_000);
+
+        // The old leader stops renewing and the standby takes over
+        let standby = holder("standby", 2_000);
+        assert_eq!(elect(std::slice::from_ref(&standby)), Some("standby"));
+        old.reset();
+        assert!(old.row().is_none());
+
+        // After recovering, the old instance contends again behind the standby
+        let row = old.start(5_000);
+        let holders = [holder(&row.holder, row.started_at), standby];
+        assert_eq!(elect(&holders), Some("standby"));
+    }
+
+    #[test]
+    fn leadership_reports_changes() {
+        let leadership = Leadership::new(false);
+        let shared = leadership.clone();
+        assert!(!leadership.is_leader());
+        assert!(leadership.set(true));
+        assert!(!leadership.set(true));
+        assert!(shared.is_leader());
+        assert!(leadership.set(false));
+        assert!(!shared.is_leader());
+    }
+
+    #[tokio::test]
+    async fn subscribers_see_changes() {
+        let leadership = Leadership::new(false);
+        let mut changes = leadership.subscribe();
+        leadership.set(true);
+        changes.changed().await.unwrap();
+        assert!(*changes.borrow_and_update());
+    }
+}
@@ -2,11 +2,39 @@
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
 
+pub mod address_labels;
+pub mod admin;
+pub mod anchor_check;
+pub mod anomaly;
+pub mod backfill;
+pub mod bond_ledger;
+pub mod data_quality;
+pub mod dedup_verify;
 pub mod driver;
+pub mod dual_verify;
+pub mod eth_price_snapshot;
 pub mod event_handler;
 pub mod event_processing;
+pub mod fee_reconciliation;
+pub mod forced_inclusion_queue;
 pub mod gap_detection;
+pub mod health;
+pub mod leader;
+pub mod mempool;
 pub mod monitoring;
+pub mod operator_balances;
+pub mod operator_history;
+pub mod orphaned_blocks;
 pub mod preconf;
+pub mod preconf_feed;
+pub mod preflight;
+pub mod proposal_mempool;
+pub mod protocol_config;
+pub mod prove_cost_backfill;
 pub mod reorg_detection;
+pub mod reprocess;
+pub mod sequencer_downtime;
+pub mod snapshot;
 mod subscription;
+pub mod taiko_price_snapshot;
+pub mod verification_backlog;
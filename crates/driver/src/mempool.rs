@@ -0,0 +1,256 @@
+//! L2 mempool monitoring
+//!
+//! This task follows the L2 node's pending transaction feed and the canonical L2 blocks. Every
+//! transaction is timed from the moment it is first seen in the mempool until it is included in
+//! a block. Once a minute the activity of the past minute is stored in the `mempool_stats` table:
+//! how many transactions were seen and included, how deep the mempool grew and the distribution
+//! of inclusion latencies.
+//!
+//! Only transactions seen by this instance are tracked, so the depth right after a restart
+//! starts from zero. Transactions that stay pending for longer than [`PENDING_TTL_MS`], e.g.
+//! because they were dropped or replaced, are forgotten.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_primitives::B256;
+use clickhouse::MempoolStatsRow;
+use eyre::{Result, eyre};
+use primitives::ingest::Received;
+use tokio_stream::StreamExt;
+use tracing::{debug, error, info, warn};
+
+/// Length of an aggregation window in seconds
+const MINUTE_SECS: u64 = 60;
+/// Time after which a transaction that was never included is forgotten
+pub const PENDING_TTL_MS: u64 = 60 * 60 * 1000;
+/// Delay before restarting the task after a stream ended
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+/// Pending transactions and the inclusion latencies of the current minute
+#[derive(Debug, Default)]
+pub struct MempoolTracker {
+    /// First-seen time in milliseconds of every pending transaction
+    pending: HashMap<B256, u64>,
+    /// Transactions first seen during the current minute
+    seen: u32,
+    /// Largest number of pending transactions during the current minute
+    max_depth: u32,
+    /// Inclusion latencies in milliseconds of the transactions included during the current minute
+    latencies: Vec<u64>,
+}
+
+impl MempoolTracker {
+    /// Number of pending transactions
+    pub fn depth(&self) -> u32 {
+        self.pending.len() as u32
+    }
+
+    /// Record a transaction seen in the mempool at `at_ms`. Transactions already pending keep
+    /// their first-seen time.
+    pub fn seen(&mut self, hash: B256, at_ms: u64) {
+        if self.pending.contains_key(&hash) {
+            return;
+        }
+        self.pending.insert(hash, at_ms);
+        self.seen += 1;
+        self.max_depth = self.max_depth.max(self.depth());
+    }
+
+    /// Record the transactions of a block included at `at_ms`. Transactions that were not seen
+    /// in the mempool are ignored.
+    pub fn included(&mut self, hashes: impl IntoIterator<Item = B256>, at_ms: u64) {
+        for hash in hashes {
+            if let Some(seen_at) = self.pending.remove(&hash) {
+                self.latencies.push(at_ms.saturating_sub(seen_at));
+            }
+        }
+    }
+
+    /// Aggregate the activity since the previous flush into the row of the minute starting at
+    /// `minute_ts`, forget transactions pending for longer than [`PENDING_TTL_MS`] at `now_ms`,
+    /// and start a new minute
+    pub fn flush(&mut self, minute_ts: u64, now_ms: u64) -> MempoolStatsRow {
+        self.pending.retain(|_, seen_at| now_ms.saturating_sub(*seen_at) <= PENDING_TTL_MS);
+
+        let mut latencies = std::mem::take(&mut self.latencies);
+        latencies.sort_unstable();
+        let avg_inclusion_ms = if latencies.is_empty() {
+            0
+        } else {
+            latencies.iter().sum::<u64>() / latencies.len() as u64
+        };
+        let row = MempoolStatsRow {
+            minute_ts,
+            seen_txs: std::mem::take(&mut self.seen),
+            included_txs: latencies.len() as u32,
+            max_depth: self.max_depth,
+            end_depth: self.depth(),
+            avg_inclusion_ms,
+            p50_inclusion_ms: percentile(&latencies, 50),
+            p95_inclusion_ms: percentile(&latencies, 95),
+            max_inclusion_ms: latencies.last().copied().unwrap_or(0),
+        };
+        self.max_depth = self.depth();
+        row
+    }
+}
+
+/// Nearest-rank percentile of sorted `values`, or 0 when there are none
+fn percentile(values: &[u64], pct: usize) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    let rank = (values.len() * pct).div_ceil(100).max(1);
+    values[rank - 1]
+}
+
+/// Time elapsed since the Unix epoch
+pub(crate) fn since_epoch() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+/// Milliseconds since the Unix epoch at which `value` was received
+pub(crate) fn received_at_ms<T>(value: &Received<T>) -> u64 {
+    since_epoch().saturating_sub(value.elapsed()).as_millis() as u64
+}
+
+/// Mempool monitoring methods for the Driver
+impl crate::driver::Driver {
+    /// Start following the L2 mempool
+    pub fn start_mempool_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.clone();
+        let extractor = self.extractor.clone();
+        let enable_db_writes = self.enable_db_writes;
+
+        info!("Starting mempool monitoring task");
+
+        let handle = tokio::spawn(async move {
+            loop {
+                if let Err(e) = track_mempool(&extractor, writer.as_ref(), enable_db_writes).await {
+                    error!(err = %e, "Mempool monitoring failed");
+                }
+                warn!("Mempool monitoring stopped, restarting in {:?}", RESTART_DELAY);
+                tokio::time::sleep(RESTART_DELAY).await;
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Track pending transactions until one of the streams ends, storing the stats of every minute
+async fn track_mempool(
+    extractor: &extractor::Extractor,
+    writer: Option<&clickhouse::ClickhouseWriter>,
+    enable_db_writes: bool,
+) -> Result<()> {
+    let mut pending = extractor.get_pending_tx_stream().await?;
+    let mut headers = extractor.get_l2_header_stream().await?;
+    let mut tracker = MempoolTracker::default();
+
+    let now_secs = since_epoch().as_secs();
+    let mut minute_ts = now_secs / MINUTE_SECS * MINUTE_SECS;
+    let next_minute = Duration::from_secs(minute_ts + MINUTE_SECS - now_secs);
+    let mut flush = tokio::time::interval_at(
+        tokio::time::Instant::now() + next_minute,
+        Duration::from_secs(MINUTE_SECS),
+    );
+
+    loop {
+        tokio::select! {
+            hash = pending.next() => {
+                let hash = hash.ok_or_else(|| eyre!("pending transaction stream ended"))?;
+                tracker.seen(hash.value, received_at_ms(&hash));
+            }
+            header = headers.next() => {
+                let header = header.ok_or_else(|| eyre!("L2 header stream ended"))?;
+                let at_ms = received_at_ms(&header);
+                match extractor.get_l2_block_by_number(header.value.number).await {
+                    Ok(block) => tracker.included(block.transactions.hashes(), at_ms),
+                    Err(e) => {
+                        warn!(err = %e, block_number = header.value.number, "Failed to fetch L2 block transactions");
+                    }
+                }
+            }
+            _ = flush.tick() => {
+                let now_ms = since_epoch().as_millis() as u64;
+                let row = tracker.flush(minute_ts, now_ms);
+                minute_ts += MINUTE_SECS;
+                debug!(
+                    minute_ts = row.minute_ts,
+                    seen = row.seen_txs,
+                    included = row.included_txs,
+                    depth = row.end_depth,
+                    "Mempool minute completed"
+                );
+
+                if !enable_db_writes {
+                    info!(minute_ts = row.minute_ts, "🧪 DRY-RUN: Would insert mempool stats");
+                    continue;
+                }
+                if let Some(writer) = writer &&
+                    let Err(e) = writer.insert_mempool_stats(&row).await
+                {
+                    error!(err = %e, minute_ts = row.minute_ts, "Failed to insert mempool stats");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inclusion_latency_is_measured_from_first_seen() {
+        let mut tracker = MempoolTracker::default();
+        tracker.seen(B256::repeat_byte(1), 1_000);
+        tracker.seen(B256::repeat_byte(1), 1_500);
+        tracker.seen(B256::repeat_byte(2), 2_000);
+        tracker.seen(B256::repeat_byte(3), 3_000);
+        tracker.included([B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(9)], 5_000);
+
+        assert_eq!(
+            tracker.flush(60, 6_000),
+            MempoolStatsRow {
+                minute_ts: 60,
+                seen_txs: 3,
+                included_txs: 2,
+                max_depth: 3,
+                end_depth: 1,
+                avg_inclusion_ms: 3_500,
+                p50_inclusion_ms: 3_000,
+                p95_inclusion_ms: 4_000,
+                max_inclusion_ms: 4_000,
+            }
+        );
+    }
+
+    #[test]
+    fn flush_starts_a_new_minute_and_forgets_stale_transactions() {
+        let mut tracker = MempoolTracker::default();
+        tracker.seen(B256::repeat_byte(1), 0);
+        tracker.seen(B256::repeat_byte(2), 30_000);
+        tracker.flush(0, 60_000);
+
+        let row = tracker.flush(60, PENDING_TTL_MS + 10_000);
+        assert_eq!(row.seen_txs, 0);
+        assert_eq!(row.included_txs, 0);
+        assert_eq!(row.max_depth, 2);
+        assert_eq!(row.end_depth, 1);
+        assert_eq!(tracker.flush(120, PENDING_TTL_MS + 20_000).max_depth, 1);
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        assert_eq!(percentile(&[], 50), 0);
+        assert_eq!(percentile(&[7], 95), 7);
+        let values: Vec<u64> = (1..=20).collect();
+        assert_eq!(percentile(&values, 50), 10);
+        assert_eq!(percentile(&values, 95), 19);
+    }
+}
@@ -2,9 +2,15 @@
 
 use std::time::Duration;
 
+use config::InstatusOpts;
+use extractor::Extractor;
 use incident::{
-    BatchProofTimeoutMonitor, InstatusL1Monitor, InstatusMonitor, Monitor,
-    monitor::{BatchVerifyTimeoutMonitor, spawn_public_rpc_monitor},
+    AnchorMismatchMonitor, AnomalyMonitor, BatchCadenceMonitor, BatchProofTimeoutMonitor,
+    BondBalanceMonitor, CandidateChurnMonitor, ChainHeads, ComponentStore, ForcedInclusionMonitor,
+    GasSaturationMonitor, InstatusL1Monitor, InstatusMonitor, Monitor, OperatorBalanceMonitor,
+    PendingProposalMonitor, PipelineMonitor, PublicRpcMonitor, StateStore,
+    VerificationBacklogMonitor, client::Client as IncidentClient,
+    monitor::BatchVerifyTimeoutMonitor, provision_components,
 };
 use tracing::{info, warn};
 
@@ -21,15 +27,10 @@ impl crate::driver::Driver {
 
         let mut handles = Vec::new();
 
-        if let Some(url) = &self.public_rpc_url {
-            info!(url = url.as_str(), "public rpc monitor enabled");
-            let incident = self.instatus_monitors_enabled.then(|| {
-                (self.incident_client.clone(), self.instatus_public_api_component_id.clone())
-            });
-            // When disabled, incident will be None; monitor will still log.
-            let handle = spawn_public_rpc_monitor(url.clone(), incident);
-            handles.push(handle);
-        }
+        // Each monitor persists its open incidents under its own file in the state directory
+        let state_store = |name: &str| {
+            self.incident_state_dir.as_deref().map(|dir| StateStore::in_dir(dir, name))
+        };
 
         // Only spawn monitors if we have a clickhouse reader (database writes enabled)
         if let Some(reader) = &self.clickhouse_reader {
@@ -40,6 +41,7 @@ impl crate::driver::Driver {
                 Duration::from_secs(self.instatus_l1_monitor_threshold_secs),
                 Duration::from_secs(self.instatus_monitor_poll_interval_secs),
             )
+            .with_state_store(state_store("batch_submission"))
             .spawn();
             handles.push(handle);
 
@@ -50,6 +52,7 @@ impl crate::driver::Driver {
                 Duration::from_secs(self.instatus_l2_monitor_threshold_secs),
                 Duration::from_secs(self.instatus_monitor_poll_interval_secs),
             )
+            .with_state_store(state_store("transaction_sequencing"))
             .spawn();
             handles.push(handle);
 
@@ -60,6 +63,7 @@ impl crate::driver::Driver {
                 Duration::from_secs(self.batch_proof_timeout_secs),
                 Duration::from_secs(60),
             )
+            .with_state_store(state_store("proof_submission"))
             .spawn();
             handles.push(handle);
 
@@ -70,8 +74,167 @@ impl crate::driver::Driver {
                 Duration::from_secs(self.batch_proof_timeout_secs),
                 Duration::from_secs(60),
             )
+            .with_cooldown(
+                Duration::from_secs(self.batch_verify_cooldown_secs),
+                Duration::from_secs(self.batch_verify_cooldown_margin_secs),
+            )
+            .with_state_store(state_store("proof_verification"))
+            .spawn();
+            handles.push(handle);
+
+            let handle = PipelineMonitor::new(
+                reader.clone(),
+                self.incident_client.clone(),
+                self.instatus_pipeline_component_id.clone(),
+                ExtractorHeads(self.extractor.clone()),
+                Duration::from_secs(self.instatus_pipeline_monitor_threshold_secs),
+                Duration::from_secs(self.instatus_monitor_poll_interval_secs),
+            )
+            .with_state_store(state_store("pipeline"))
+            .spawn();
+            handles.push(handle);
+
+            let handle = BatchCadenceMonitor::new(
+                reader.clone(),
+                self.incident_client.clone(),
+                self.instatus_batch_cadence_component_id.clone(),
+                Duration::from_secs(self.batch_cadence_warning_secs),
+                self.batch_cadence_anchor_age_pct,
+                Duration::from_secs(self.instatus_monitor_poll_interval_secs),
+            )
+            .with_state_store(state_store("batch_cadence"))
+            .spawn();
+            handles.push(handle);
+
+            let handle = GasSaturationMonitor::new(
+                reader.clone(),
+                self.incident_client.clone(),
+                self.instatus_gas_saturation_component_id.clone(),
+                self.gas_saturation_threshold_pct,
+                Duration::from_secs(self.gas_saturation_window_secs),
+                Duration::from_secs(self.instatus_monitor_poll_interval_secs),
+            )
+            .with_state_store(state_store("gas_saturation"))
+            .spawn();
+            handles.push(handle);
+
+            let handle = AnchorMismatchMonitor::new(
+                reader.clone(),
+                self.incident_client.clone(),
+                self.instatus_anchor_mismatch_component_id.clone(),
+                Duration::from_secs(self.anchor_mismatch_window_secs),
+                Duration::from_secs(self.instatus_monitor_poll_interval_secs),
+            )
+            .with_state_store(state_store("anchor_mismatch"))
             .spawn();
             handles.push(handle);
+
+            if self.enable_anomaly_detection {
+                let handle = AnomalyMonitor::new(
+                    reader.clone(),
+                    self.incident_client.clone(),
+                    self.instatus_anomaly_component_id.clone(),
+                    Duration::from_secs(self.anomaly_incident_window_secs),
+                    Duration::from_secs(self.anomaly_interval_secs),
+                )
+                .with_state_store(state_store("anomaly"))
+                .spawn();
+                handles.push(handle);
+            }
+
+            if self.enable_operator_history {
+                let handle = CandidateChurnMonitor::new(
+                    reader.clone(),
+                    self.incident_client.clone(),
+                    self.instatus_candidate_churn_component_id.clone(),
+                    Duration::from_secs(self.candidate_churn_window_secs),
+                    self.min_operator_candidates,
+                    Duration::from_secs(self.operator_history_interval_secs),
+                )
+                .with_state_store(state_store("candidate_churn"))
+                .spawn();
+                handles.push(handle);
+            }
+
+            if self.enable_operator_balances {
+                let handle = OperatorBalanceMonitor::new(
+                    reader.clone(),
+                    self.incident_client.clone(),
+                    self.instatus_operator_balance_component_id.clone(),
+                    Duration::from_secs(self.operator_balance_interval_secs),
+                )
+                .with_state_store(state_store("operator_balance"))
+                .spawn();
+                handles.push(handle);
+            }
+
+            if self.enable_bond_ledger {
+                let handle = BondBalanceMonitor::new(
+                    reader.clone(),
+                    self.incident_client.clone(),
+                    self.instatus_bond_balance_component_id.clone(),
+                    self.bond_low_batches,
+                    Duration::from_secs(self.bond_ledger_interval_secs),
+                )
+                .with_state_store(state_store("bond_balance"))
+                .spawn();
+                handles.push(handle);
+            }
+
+            if self.enable_forced_inclusion_queue {
+                let handle = ForcedInclusionMonitor::new(
+                    reader.clone(),
+                    self.incident_client.clone(),
+                    self.instatus_forced_inclusion_component_id.clone(),
+                    Duration::from_secs(self.forced_inclusion_queue_interval_secs),
+                )
+                .with_state_store(state_store("forced_inclusion"))
+                .spawn();
+                handles.push(handle);
+            }
+
+            if self.enable_verification_backlog {
+                let handle = VerificationBacklogMonitor::new(
+                    reader.clone(),
+                    self.incident_client.clone(),
+                    self.instatus_verification_backlog_component_id.clone(),
+                    Duration::from_secs(self.verification_backlog_interval_secs),
+                )
+                .with_state_store(state_store("verification_backlog"))
+                .spawn();
+                handles.push(handle);
+            }
+
+            if self.enable_proposal_mempool_monitor {
+                let handle = PendingProposalMonitor::new(
+                    reader.clone(),
+                    self.incident_client.clone(),
+                    self.instatus_proposal_inclusion_component_id.clone(),
+                    Duration::from_secs(self.proposal_pending_threshold_secs),
+                    Duration::from_secs(self.instatus_monitor_poll_interval_secs),
+                )
+                .with_state_store(state_store("pending_proposal"))
+                .spawn();
+                handles.push(handle);
+            }
+
+            if !self.public_rpc_endpoints.is_empty() {
+                info!(endpoints = self.public_rpc_endpoints.len(), "public rpc monitor enabled");
+                // When monitors are disabled, incidents are only logged.
+                let handle = PublicRpcMonitor::new(
+                    reader.clone(),
+                    self.public_rpc_endpoints.clone(),
+                    self.incident_client.clone(),
+                    self.instatus_monitors_enabled,
+                    ExtractorHeads(self.extractor.clone()),
+                    self.public_rpc_max_block_lag,
+                    Duration::from_secs(60),
+                )
+                .with_writer(self.clickhouse_writer.clone())
+                .with_state_store(|index| state_store(&format!("public_rpc_{index}")))
+                .spawn();
+                handles.push(handle);
+            }
         } else if self.instatus_monitors_enabled {
             warn!(
                 "Instatus monitors enabled but no ClickHouse reader available (database writes disabled)"
@@ -85,3 +248,44 @@ impl crate::driver::Driver {
         handles
     }
 }
+
+/// Fill the required component IDs missing from `opts` with components of the same name on the
+/// Instatus page, creating the ones that do not exist yet
+pub async fn provision_required_components(opts: &mut InstatusOpts) -> eyre::Result<()> {
+    let required = [
+        ("Batch Submission", &mut opts.batch_submission_component_id),
+        ("Proof Submission", &mut opts.proof_submission_component_id),
+        ("Proof Verification", &mut opts.proof_verification_component_id),
+        ("Transaction Sequencing", &mut opts.transaction_sequencing_component_id),
+        ("Public API", &mut opts.public_api_component_id),
+    ];
+    let (names, targets): (Vec<&str>, Vec<&mut String>) =
+        required.into_iter().filter(|(_, id)| id.is_empty()).unzip();
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    let client = IncidentClient::new(opts.api_key.clone(), opts.page_id.clone());
+    let store = opts.state_dir.as_deref().map(ComponentStore::in_dir);
+    let ids = provision_components(&client, &names, store.as_ref()).await?;
+    for (name, target) in names.into_iter().zip(targets) {
+        info!(component_name = name, component_id = %ids[name], "Using provisioned component");
+        target.clone_from(&ids[name]);
+    }
+    Ok(())
+}
+
+/// Chain heads reported by the extractor's RPC providers
+#[derive(Debug)]
+struct ExtractorHeads(Extractor);
+
+#[async_trait::async_trait]
+impl ChainHeads for ExtractorHeads {
+    async fn l1_head(&self) -> eyre::Result<u64> {
+        self.0.get_l1_latest_block_number().await
+    }
+
+    async fn l2_head(&self) -> eyre::Result<u64> {
+        self.0.get_l2_latest_block_number().await
+    }
+}
@@ -0,0 +1,129 @@
+//! Operator balance snapshots
+//!
+//! Whitelisted operators pay for proposals from their L1 wallets, so a wallet running dry makes
+//! their proposals fail. This task periodically records the ETH and, if a token address is
+//! configured, TAIKO balances of the current operator candidates in the `operator_balances`
+//! table, flagging those below the configured thresholds. The operator balance monitor opens an
+//! incident while any operator of the latest snapshot is flagged.
+
+use std::time::Duration;
+
+use alloy_primitives::Address;
+use chrono::Utc;
+use clickhouse::{AddressBytes, ClickhouseWriter, OperatorBalanceRow};
+use extractor::Extractor;
+use eyre::Result;
+use tracing::{error, info, warn};
+
+/// Balance thresholds below which an operator is flagged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceThresholds {
+    /// Minimum ETH balance in wei
+    pub min_eth: u128,
+    /// Minimum TAIKO balance in base units, `0` disables the check
+    pub min_taiko: u128,
+}
+
+impl BalanceThresholds {
+    /// Whether either balance is below its threshold
+    pub fn is_low(&self, eth_balance: u128, taiko_balance: Option<u128>) -> bool {
+        eth_balance < self.min_eth ||
+            taiko_balance.is_some_and(|balance| self.min_taiko > 0 && balance < self.min_taiko)
+    }
+}
+
+/// Operator balance methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic operator balance snapshot task
+    pub fn start_operator_balance_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let extractor = self.extractor.clone();
+        let interval_secs = self.operator_balance_interval_secs;
+        let token = self.taiko_token_address;
+        let thresholds = BalanceThresholds {
+            min_eth: self.operator_min_eth_balance_wei,
+            min_taiko: self.operator_min_taiko_balance_wei,
+        };
+
+        info!(interval_secs, ?token, ?thresholds, "Starting operator balance task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                match snapshot_operator_balances(&writer, &extractor, token, thresholds).await {
+                    Ok(low) if low > 0 => warn!(low, "Operators below balance threshold"),
+                    Ok(_) => {}
+                    Err(e) => error!(err = %e, "Operator balance snapshot failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Record the balances of the current operator candidates at the latest L1 block.
+/// Returns the number of operators flagged as low.
+pub async fn snapshot_operator_balances(
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    token: Option<Address>,
+    thresholds: BalanceThresholds,
+) -> Result<usize> {
+    let operators = extractor.get_operator_candidates_for_current_epoch().await?;
+    let l1_block_number = extractor.get_l1_latest_block_number().await?;
+    let snapshot_ts = Utc::now().timestamp() as u64;
+
+    let mut rows = Vec::with_capacity(operators.len());
+    for operator in operators {
+        let eth_balance =
+            extractor.get_eth_balance(operator, l1_block_number).await?.saturating_to::<u128>();
+        let taiko_balance = match token {
+            Some(token) => Some(
+                extractor
+                    .get_erc20_balance(token, operator, l1_block_number)
+                    .await?
+                    .saturating_to::<u128>(),
+            ),
+            None => None,
+        };
+        rows.push(OperatorBalanceRow {
+            snapshot_ts,
+            address: AddressBytes::from(operator),
+            l1_block_number,
+            eth_balance,
+            taiko_balance,
+            low_balance: u8::from(thresholds.is_low(eth_balance, taiko_balance)),
+        });
+    }
+
+    writer.insert_operator_balances(&rows).await?;
+    Ok(rows.iter().filter(|row| row.low_balance == 1).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_low_eth_balance() {
+        let thresholds = BalanceThresholds { min_eth: 100, min_taiko: 0 };
+        assert!(thresholds.is_low(99, None));
+        assert!(!thresholds.is_low(100, None));
+    }
+
+    #[test]
+    fn flags_low_taiko_balance_only_when_enabled() {
+        let disabled = BalanceThresholds { min_eth: 100, min_taiko: 0 };
+        assert!(!disabled.is_low(100, Some(0)));
+
+        let enabled = BalanceThresholds { min_eth: 100, min_taiko: 50 };
+        assert!(enabled.is_low(100, Some(49)));
+        assert!(!enabled.is_low(100, Some(50)));
+        assert!(!enabled.is_low(100, None));
+    }
+}
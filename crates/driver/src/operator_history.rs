@@ -0,0 +1,159 @@
+//! Whitelist candidate history
+//!
+//! The preconfirmation whitelist elects the operator of every epoch among its candidates, so a
+//! candidate leaving the whitelist, or too few candidates remaining, directly affects who can
+//! sequence L2 blocks. This task periodically reads the candidates of the current epoch and
+//! records every change to the set in the `operator_history` table, together with the candidates
+//! added and removed. The candidate churn monitor opens an incident on recent changes and while
+//! fewer candidates than the configured minimum remain.
+//!
+//! The latest recorded snapshot is the baseline after a restart, so changes that happened while
+//! the driver was down are still recorded once.
+
+use std::{collections::BTreeSet, time::Duration};
+
+use alloy_primitives::Address;
+use chrono::Utc;
+use clickhouse::{AddressBytes, ClickhouseReader, ClickhouseWriter, OperatorHistoryRow};
+use extractor::Extractor;
+use eyre::Result;
+use tracing::{error, info, warn};
+
+/// Operator history methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic operator candidate history task
+    pub fn start_operator_history_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let reader = self.clickhouse_reader.as_ref()?.clone();
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let extractor = self.extractor.clone();
+        let interval_secs = self.operator_history_interval_secs;
+
+        info!(interval_secs, "Starting operator history task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            let mut previous = None;
+
+            loop {
+                interval.tick().await;
+
+                match record_operator_history(&reader, &writer, &extractor, &mut previous).await {
+                    Ok(Some(row)) if row.is_churn() => warn!(
+                        candidates = row.candidates.len(),
+                        added = ?row.added,
+                        removed = ?row.removed,
+                        "Whitelist operator candidates changed"
+                    ),
+                    Ok(Some(row)) => {
+                        info!(candidates = row.candidates.len(), "Recorded operator candidates")
+                    }
+                    Ok(None) => {}
+                    Err(e) => error!(err = %e, "Operator history update failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Compare the candidates of the current epoch with `previous`, loading the latest recorded
+/// snapshot if it is not known yet, and record them if they changed. Returns the inserted row.
+pub async fn record_operator_history(
+    reader: &ClickhouseReader,
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    previous: &mut Option<Vec<AddressBytes>>,
+) -> Result<Option<OperatorHistoryRow>> {
+    if previous.is_none() {
+        *previous = reader.get_latest_operator_history().await?.map(|row| row.candidates);
+    }
+
+    let candidates = extractor.get_operator_candidates_for_current_epoch().await?;
+    let l1_block_number = extractor.get_l1_latest_block_number().await?;
+    let observed_ts = Utc::now().timestamp() as u64;
+
+    let Some(row) =
+        candidate_change(previous.as_deref(), &candidates, observed_ts, l1_block_number)
+    else {
+        return Ok(None);
+    };
+    writer.insert_operator_history(&row).await?;
+    *previous = Some(row.candidates.clone());
+    Ok(Some(row))
+}
+
+/// Snapshot of `candidates` if they differ from `previous`, ignoring order. Without a previous
+/// snapshot the candidates are recorded as a baseline with no changes.
+pub fn candidate_change(
+    previous: Option<&[AddressBytes]>,
+    candidates: &[Address],
+    observed_ts: u64,
+    l1_block_number: u64,
+) -> Option<OperatorHistoryRow> {
+    let current: BTreeSet<AddressBytes> =
+        candidates.iter().copied().map(AddressBytes::from).collect();
+    let (added, removed) = match previous {
+        Some(previous) => {
+            let previous: BTreeSet<AddressBytes> = previous.iter().copied().collect();
+            if previous == current {
+                return None;
+            }
+            (
+                current.difference(&previous).copied().collect(),
+                previous.difference(&current).copied().collect(),
+            )
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Some(OperatorHistoryRow {
+        observed_ts,
+        l1_block_number,
+        candidates: candidates.iter().copied().map(AddressBytes::from).collect(),
+        added,
+        removed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses(bytes: &[u8]) -> Vec<Address> {
+        bytes.iter().map(|b| Address::repeat_byte(*b)).collect()
+    }
+
+    fn address_bytes(bytes: &[u8]) -> Vec<AddressBytes> {
+        bytes.iter().map(|b| AddressBytes([*b; 20])).collect()
+    }
+
+    #[test]
+    fn first_snapshot_is_a_baseline() {
+        let row = candidate_change(None, &addresses(&[1, 2]), 100, 10).unwrap();
+        assert_eq!(row.candidates, address_bytes(&[1, 2]));
+        assert!(!row.is_churn());
+    }
+
+    #[test]
+    fn unchanged_candidates_are_not_recorded() {
+        let previous = address_bytes(&[1, 2]);
+        assert_eq!(candidate_change(Some(&previous), &addresses(&[2, 1]), 100, 10), None);
+    }
+
+    #[test]
+    fn changes_list_added_and_removed_candidates() {
+        let previous = address_bytes(&[1, 2]);
+        assert_eq!(
+            candidate_change(Some(&previous), &addresses(&[3, 1]), 100, 10),
+            Some(OperatorHistoryRow {
+                observed_ts: 100,
+                l1_block_number: 10,
+                candidates: address_bytes(&[3, 1]),
+                added: address_bytes(&[3]),
+                removed: address_bytes(&[2]),
+            })
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Orphaned L2 block capture
+//!
+//! A reorg only leaves the hashes of the orphaned L2 blocks in `orphaned_l2_hashes`. To analyze
+//! what was dropped, the content of every orphaned block is fetched by hash right after the reorg
+//! is detected, before the node prunes the side chain, and stored in `orphaned_block_details`.
+//! After [`REINCLUSION_DELAY`] the transactions of the blocks are looked up again, and the rows
+//! are written once more with the number of transactions that made it into the canonical chain.
+
+use std::time::Duration;
+
+use alloy_primitives::B256;
+use alloy_rpc_types_eth::Block;
+use clickhouse::{AddressBytes, ClickhouseWriter, HashBytes, OrphanedBlockDetailsRow};
+use extractor::Extractor;
+use eyre::Result;
+use tracing::{error, info, warn};
+
+/// Time given to the canonical chain to re-include the transactions of an orphaned block
+pub const REINCLUSION_DELAY: Duration = Duration::from_secs(120);
+
+/// Capture the content of the orphaned blocks, given by hash and number, in the background
+pub fn spawn_orphaned_block_capture(
+    extractor: Extractor,
+    writer: ClickhouseWriter,
+    orphaned: Vec<(HashBytes, u64)>,
+) {
+    tokio::spawn(async move {
+        if let Err(e) = capture_orphaned_blocks(&extractor, &writer, &orphaned).await {
+            error!(count = orphaned.len(), err = %e, "Failed to capture orphaned blocks");
+        }
+    });
+}
+
+/// Store the content of the orphaned blocks, then count their re-included transactions after
+/// [`REINCLUSION_DELAY`]. Blocks the node no longer has are skipped.
+async fn capture_orphaned_blocks(
+    extractor: &Extractor,
+    writer: &ClickhouseWriter,
+    orphaned: &[(HashBytes, u64)],
+) -> Result<()> {
+    let mut rows = Vec::with_capacity(orphaned.len());
+    for &(hash, block_number) in orphaned {
+        let Some(block) = extractor.get_l2_block_by_hash(B256::from(hash)).await? else {
+            warn!(block_number, orphaned_hash = ?hash, "Orphaned block no longer available");
+            continue;
+        };
+        let row = orphaned_block_details(&block);
+        writer.insert_orphaned_block_details(&row).await?;
+        info!(block_number, tx_count = row.tx_count, "Captured orphaned block");
+        rows.push(row);
+    }
+
+    if rows.iter().all(|row| row.tx_hashes.is_empty()) {
+        return Ok(());
+    }
+    tokio::time::sleep(REINCLUSION_DELAY).await;
+
+    for mut row in rows {
+        for tx_hash in &row.tx_hashes {
+            let included_in = extractor.get_l2_transaction_block_hash(B256::from(*tx_hash)).await?;
+            if included_in.is_some_and(|block_hash| HashBytes::from(block_hash) != row.block_hash) {
+                row.reincluded_txs += 1;
+            }
+        }
+        row.reinclusion_checked = 1;
+        writer.insert_orphaned_block_details(&row).await?;
+        info!(
+            block_number = row.l2_block_number,
+            tx_count = row.tx_count,
+            reincluded_txs = row.reincluded_txs,
+            "Checked orphaned block transactions for re-inclusion"
+        );
+    }
+    Ok(())
+}
+
+/// Row describing the content of an orphaned `block`, not yet checked for re-inclusion
+pub fn orphaned_block_details(block: &Block) -> OrphanedBlockDetailsRow {
+    let tx_hashes: Vec<HashBytes> = block.transactions.hashes().map(HashBytes::from).collect();
+    OrphanedBlockDetailsRow {
+        block_hash: HashBytes::from(block.header.hash),
+        l2_block_number: block.header.number,
+        parent_hash: HashBytes::from(block.header.parent_hash),
+        block_ts: block.header.timestamp,
+        sequencer: AddressBytes::from(block.header.beneficiary),
+        gas_used: block.header.gas_used,
+        tx_count: tx_hashes.len() as u32,
+        tx_hashes,
+        reinclusion_checked: 0,
+        reincluded_txs: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use alloy_rpc_types_eth::BlockTransactions;
+
+    #[test]
+    fn details_describe_the_orphaned_block() {
+        let mut block: Block = Block::default();
+        block.header.hash = B256::repeat_byte(1);
+        block.header.inner.number = 42;
+        block.header.inner.parent_hash = B256::repeat_byte(2);
+        block.header.inner.timestamp = 1_700_000_000;
+        block.header.inner.beneficiary = Address::repeat_byte(3);
+        block.header.inner.gas_used = 42_000;
+        block.transactions =
+            BlockTransactions::Hashes(vec![B256::repeat_byte(4), B256::repeat_byte(5)]);
+
+        assert_eq!(
+            orphaned_block_details(&block),
+            OrphanedBlockDetailsRow {
+                block_hash: HashBytes([1u8; 32]),
+                l2_block_number: 42,
+                parent_hash: HashBytes([2u8; 32]),
+                block_ts: 1_700_000_000,
+                sequencer: AddressBytes([3u8; 20]),
+                gas_used: 42_000,
+                tx_count: 2,
+                tx_hashes: vec![HashBytes([4u8; 32]), HashBytes([5u8; 32])],
+                reinclusion_checked: 0,
+                reincluded_txs: 0,
+            }
+        );
+    }
+}
@@ -27,66 +27,37 @@ pub async fn process_preconf_data(
         }
     };
 
-    // Get operator candidates for current epoch
-    let opt_candidates = match extractor.get_operator_candidates_for_current_epoch().await {
-        Ok(c) => {
-            info!(
-                slot = header.slot,
-                block = header.number,
-                candidates = ?c,
-                candidates_count = c.len(),
-                "Successfully retrieved operator candidates"
-            );
-            Some(c)
-        }
-        Err(e) => {
-            error!(
-                slot = header.slot,
-                block = header.number,
-                err = %e,
-                "Failed picking operator candidates"
-            );
-            None
-        }
-    };
-    let candidates = opt_candidates.unwrap_or_else(Vec::new);
-
-    // Get current operator for epoch
-    let opt_current_operator = match extractor.get_operator_for_current_epoch().await {
-        Ok(op) => {
-            info!(
-                block = header.number,
-                operator = ?op,
-                "Current operator for epoch"
-            );
-            Some(op)
-        }
-        Err(e) => {
-            error!(block = header.number, err = %e, "get_operator_for_current_epoch failed");
-            None
-        }
-    };
-
-    // Get next operator for epoch
-    let opt_next_operator = match extractor.get_operator_for_next_epoch().await {
-        Ok(op) => {
-            info!(
-                block = header.number,
-                operator = ?op,
-                "Next operator for epoch"
-            );
-            Some(op)
-        }
-        Err(e) => {
-            error!(block = header.number, err = %e, "get_operator_for_next_epoch failed");
-            None
-        }
-    };
+    // Read candidates, operators and inbox stats in one round trip
+    let snapshot = extractor.get_preconf_snapshot(header.number).await;
+    info!(
+        slot = header.slot,
+        block = header.number,
+        candidates = ?snapshot.candidates,
+        current_operator = ?snapshot.current_operator,
+        next_operator = ?snapshot.next_operator,
+        num_batches = ?snapshot.num_batches,
+        "Retrieved preconf snapshot"
+    );
+    if snapshot.candidates.is_none() {
+        error!(slot = header.slot, block = header.number, "Failed picking operator candidates");
+    }
+    if snapshot.current_operator.is_none() {
+        error!(block = header.number, "get_operator_for_current_epoch failed");
+    }
+    if snapshot.next_operator.is_none() {
+        error!(block = header.number, "get_operator_for_next_epoch failed");
+    }
+    let candidates = snapshot.candidates.unwrap_or_default();
 
     // Insert preconf data if we have at least one operator
-    if opt_current_operator.is_some() || opt_next_operator.is_some() {
+    if snapshot.current_operator.is_some() || snapshot.next_operator.is_some() {
         if let Err(e) = writer
-            .insert_preconf_data(header.slot, candidates, opt_current_operator, opt_next_operator)
+            .insert_preconf_data(
+                header.slot,
+                candidates,
+                snapshot.current_operator,
+                snapshot.next_operator,
+            )
             .await
         {
             error!(slot = header.slot, err = %e, "Failed to insert preconf data");
@@ -106,69 +77,38 @@ pub async fn process_preconf_data_dry_run(
     extractor: &Extractor,
     header: &primitives::headers::L1Header,
 ) {
-    // Get operator candidates for current epoch (for validation)
-    let opt_candidates = match extractor.get_operator_candidates_for_current_epoch().await {
-        Ok(c) => {
-            info!(
-                slot = header.slot,
-                block = header.number,
-                candidates = ?c,
-                candidates_count = c.len(),
-                "🧪 DRY-RUN: Retrieved operator candidates"
-            );
-            Some(c)
-        }
-        Err(e) => {
-            warn!(
-                slot = header.slot,
-                block = header.number,
-                err = %e,
-                "🧪 DRY-RUN: Failed picking operator candidates"
-            );
-            None
-        }
-    };
-    let candidates = opt_candidates.unwrap_or_else(Vec::new);
-
-    // Get current operator for epoch (for validation)
-    let opt_current_operator = match extractor.get_operator_for_current_epoch().await {
-        Ok(op) => {
-            info!(
-                block = header.number,
-                operator = ?op,
-                "🧪 DRY-RUN: Current operator for epoch"
-            );
-            Some(op)
-        }
-        Err(e) => {
-            warn!(block = header.number, err = %e, "🧪 DRY-RUN: get_operator_for_current_epoch failed");
-            None
-        }
-    };
-
-    // Get next operator for epoch (for validation)
-    let opt_next_operator = match extractor.get_operator_for_next_epoch().await {
-        Ok(op) => {
-            info!(
-                block = header.number,
-                operator = ?op,
-                "🧪 DRY-RUN: Next operator for epoch"
-            );
-            Some(op)
-        }
-        Err(e) => {
-            warn!(block = header.number, err = %e, "🧪 DRY-RUN: get_operator_for_next_epoch failed");
-            None
-        }
-    };
+    // Read candidates, operators and inbox stats in one round trip (for validation)
+    let snapshot = extractor.get_preconf_snapshot(header.number).await;
+    info!(
+        slot = header.slot,
+        block = header.number,
+        candidates = ?snapshot.candidates,
+        current_operator = ?snapshot.current_operator,
+        next_operator = ?snapshot.next_operator,
+        num_batches = ?snapshot.num_batches,
+        "🧪 DRY-RUN: Retrieved preconf snapshot"
+    );
+    if snapshot.candidates.is_none() {
+        warn!(
+            slot = header.slot,
+            block = header.number,
+            "🧪 DRY-RUN: Failed picking operator candidates"
+        );
+    }
+    if snapshot.current_operator.is_none() {
+        warn!(block = header.number, "🧪 DRY-RUN: get_operator_for_current_epoch failed");
+    }
+    if snapshot.next_operator.is_none() {
+        warn!(block = header.number, "🧪 DRY-RUN: get_operator_for_next_epoch failed");
+    }
 
     // Simulate database insertion
-    if opt_current_operator.is_some() || opt_next_operator.is_some() {
+    if snapshot.current_operator.is_some() || snapshot.next_operator.is_some() {
         info!(
             slot = header.slot,
-            candidate_count = candidates.len(),
-            has_current_op = opt_current_operator.is_some(),
-            has_next_op = opt_next_operator.is_some(),
+            candidate_count = snapshot.candidates.as_ref().map_or(0, Vec::len),
+            has_current_op = snapshot.current_operator.is_some(),
+            has_next_op = snapshot.next_operator.is_some(),
             "🧪 DRY-RUN: Would insert preconf data"
         );
     } else {
@@ -0,0 +1,235 @@
+//! Soft block comparison
+//!
+//! The sequencer advertises preconfirmed "soft" blocks before they are proposed on L1. This task
+//! follows a WebSocket feed of those soft blocks and, once the canonical L2 block at the same
+//! height is known, compares the two transaction lists. Soft blocks whose transactions were
+//! dropped, joined by others or reordered are stored in the `preconf_mismatches` table, as are
+//! soft blocks whose height no canonical block reached in time.
+//!
+//! Feed messages are JSON text frames of the form
+//! `{"blockNumber": 42, "blockHash": "0x…", "transactions": ["0x…", …]}`, where `transactions`
+//! lists the transaction hashes in order and `blockHash` is optional.
+
+use std::{collections::HashSet, time::Duration};
+
+use alloy_primitives::B256;
+use clickhouse::{ClickhouseWriter, HashBytes, PreconfMismatchRow};
+use extractor::Extractor;
+use eyre::Result;
+use serde::Deserialize;
+use tokio::{task::JoinSet, time::Instant};
+use tokio_stream::StreamExt;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+/// Delay before reconnecting to the feed after it closed or failed
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// How often to check whether the canonical block of a soft block is available
+const CANONICAL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Soft block as advertised by the sequencer
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoftBlock {
+    /// L2 block number
+    pub block_number: u64,
+    /// Block hash, if the feed provides one
+    #[serde(default)]
+    pub block_hash: Option<B256>,
+    /// Transaction hashes in block order
+    pub transactions: Vec<B256>,
+}
+
+/// Differences between the transactions of a soft block and its canonical block
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TxDiff {
+    /// Preconfirmed transactions missing from the canonical block
+    pub missing: Vec<B256>,
+    /// Canonical transactions that were not preconfirmed
+    pub extra: Vec<B256>,
+    /// Whether the transactions in both blocks appear in a different order
+    pub reordered: bool,
+}
+
+impl TxDiff {
+    /// Whether the two blocks contain the same transactions in the same order
+    pub const fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty() && !self.reordered
+    }
+}
+
+/// Compare the transactions of a soft block against those of the canonical block
+pub fn diff_transactions(soft: &[B256], canonical: &[B256]) -> TxDiff {
+    let soft_set: HashSet<&B256> = soft.iter().collect();
+    let canonical_set: HashSet<&B256> = canonical.iter().collect();
+
+    let missing = soft.iter().filter(|hash| !canonical_set.contains(hash)).copied().collect();
+    let extra = canonical.iter().filter(|hash| !soft_set.contains(hash)).copied().collect();
+    let reordered = !soft
+        .iter()
+        .filter(|hash| canonical_set.contains(hash))
+        .eq(canonical.iter().filter(|hash| soft_set.contains(hash)));
+
+    TxDiff { missing, extra, reordered }
+}
+
+/// Soft block comparison methods for the Driver
+impl crate::driver::Driver {
+    /// Start following the soft block feed
+    pub fn start_preconf_feed_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let url = self.preconf_feed_url.clone()?;
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let extractor = self.extractor.clone();
+        let timeout = Duration::from_secs(self.preconf_compare_timeout_secs);
+
+        info!(%url, timeout_secs = timeout.as_secs(), "Starting preconf feed comparison task");
+
+        let handle = tokio::spawn(async move {
+            // Comparisons still waiting for their canonical block are aborted with the task
+            let mut comparisons = JoinSet::new();
+            loop {
+                if let Err(e) =
+                    follow_feed(&url, &writer, &extractor, timeout, &mut comparisons).await
+                {
+                    error!(err = %e, "Preconf feed failed");
+                }
+                warn!("Preconf feed closed, reconnecting in {:?}", RECONNECT_DELAY);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Read soft blocks from the feed until it closes, comparing each in the background
+async fn follow_feed(
+    url: &Url,
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    timeout: Duration,
+    comparisons: &mut JoinSet<()>,
+) -> Result<()> {
+    let (mut feed, _) = tokio_tungstenite::connect_async(url.as_str()).await?;
+    info!(%url, "Connected to preconf feed");
+
+    while let Some(message) = feed.next().await {
+        let text = match message? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let soft = match serde_json::from_str::<SoftBlock>(&text) {
+            Ok(soft) => soft,
+            Err(e) => {
+                warn!(err = %e, "Ignoring malformed soft block");
+                continue;
+            }
+        };
+
+        debug!(block_number = soft.block_number, txs = soft.transactions.len(), "Soft block");
+        while comparisons.try_join_next().is_some() {}
+        let (writer, extractor) = (writer.clone(), extractor.clone());
+        comparisons.spawn(async move {
+            let block_number = soft.block_number;
+            if let Err(e) = compare_soft_block(&writer, &extractor, soft, timeout).await {
+                error!(block_number, err = %e, "Soft block comparison failed");
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Wait for the canonical block at the soft block's height and store any differences
+async fn compare_soft_block(
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    soft: SoftBlock,
+    timeout: Duration,
+) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+    let canonical = loop {
+        match extractor.get_l2_block_by_number(soft.block_number).await {
+            Ok(block) => break Some(block),
+            Err(_) if Instant::now() < deadline => {
+                tokio::time::sleep(CANONICAL_POLL_INTERVAL).await;
+            }
+            Err(_) => break None,
+        }
+    };
+
+    let (canonical_block_hash, canonical_txs) = match canonical {
+        Some(block) => (
+            Some(HashBytes::from(block.header.hash)),
+            block.transactions.hashes().collect::<Vec<_>>(),
+        ),
+        None => (None, Vec::new()),
+    };
+    let diff = diff_transactions(&soft.transactions, &canonical_txs);
+    if canonical_block_hash.is_some() && diff.is_empty() {
+        return Ok(());
+    }
+
+    warn!(
+        block_number = soft.block_number,
+        canonical = canonical_block_hash.is_some(),
+        missing = diff.missing.len(),
+        extra = diff.extra.len(),
+        reordered = diff.reordered,
+        "Soft block differs from canonical block"
+    );
+    writer
+        .insert_preconf_mismatch(&PreconfMismatchRow {
+            l2_block_number: soft.block_number,
+            soft_block_hash: soft.block_hash.map(HashBytes::from),
+            canonical_block_hash,
+            soft_tx_count: soft.transactions.len() as u32,
+            canonical_tx_count: canonical_txs.len() as u32,
+            missing_tx_hashes: diff.missing.into_iter().map(HashBytes::from).collect(),
+            extra_tx_hashes: diff.extra.into_iter().map(HashBytes::from).collect(),
+            reordered: u8::from(diff.reordered),
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: u8) -> B256 {
+        B256::repeat_byte(byte)
+    }
+
+    #[test]
+    fn identical_blocks_have_no_diff() {
+        let txs = [hash(1), hash(2)];
+        assert!(diff_transactions(&txs, &txs).is_empty());
+    }
+
+    #[test]
+    fn detects_missing_and_extra_transactions() {
+        let diff = diff_transactions(&[hash(1), hash(2)], &[hash(1), hash(3)]);
+        assert_eq!(diff.missing, vec![hash(2)]);
+        assert_eq!(diff.extra, vec![hash(3)]);
+        assert!(!diff.reordered);
+    }
+
+    #[test]
+    fn detects_reordered_transactions() {
+        let diff = diff_transactions(&[hash(1), hash(2), hash(3)], &[hash(2), hash(4), hash(1)]);
+        assert_eq!(diff.missing, vec![hash(3)]);
+        assert_eq!(diff.extra, vec![hash(4)]);
+        assert!(diff.reordered);
+    }
+
+    #[test]
+    fn parses_soft_block_message() {
+        let json = format!(r#"{{"blockNumber": 42, "transactions": ["{}"]}}"#, hash(1));
+        let soft: SoftBlock = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            soft,
+            SoftBlock { block_number: 42, block_hash: None, transactions: vec![hash(1)] }
+        );
+    }
+}
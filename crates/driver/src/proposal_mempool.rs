@@ -0,0 +1,337 @@
+//! L1 batch proposal mempool monitoring
+//!
+//! An underpriced proposal sits in the L1 mempool while the preconfirmed L2 blocks it carries
+//! wait for their batch. This task follows the proposal transactions entering the L1 node's
+//! mempool and the `BatchProposed` events, and records in the `proposal_inclusions` table when
+//! the proposal of every proposer nonce was first seen, how often it was replaced and how long it
+//! took to land. The pending proposal monitor opens an incident while a proposal stays pending
+//! for longer than the configured threshold.
+//!
+//! Proposals are tracked by proposer nonce, so a fee bump keeps the first-seen time of the
+//! original transaction. A nonce found used at two consecutive checks without a `BatchProposed`
+//! event, e.g. by a reverted or cancelled transaction, is recorded as dropped. Only proposals
+//! seen by this instance are tracked, and proposals pending for longer than [`PENDING_TTL_MS`]
+//! are forgotten.
+
+use std::{collections::HashMap, time::Duration};
+
+use alloy_primitives::{Address, B256};
+use clickhouse::{AddressBytes, ClickhouseWriter, HashBytes, ProposalInclusionRow};
+use extractor::{Extractor, PendingProposal};
+use eyre::{Result, eyre};
+use tokio_stream::StreamExt;
+use tracing::{debug, error, info, warn};
+
+use crate::mempool::{received_at_ms, since_epoch};
+
+/// Interval at which the nonces of the pending proposers are checked, one L1 slot
+const CHECK_INTERVAL: Duration = Duration::from_secs(12);
+/// Time after which a proposal that was never included is forgotten
+pub const PENDING_TTL_MS: u64 = 60 * 60 * 1000;
+/// Delay before restarting the task after a stream ended
+const RESTART_DELAY: Duration = Duration::from_secs(5);
+
+/// Pending proposal of a proposer nonce
+#[derive(Debug)]
+struct PendingEntry {
+    /// Row recorded for the nonce so far
+    row: ProposalInclusionRow,
+    /// Hashes of every transaction seen for the nonce
+    hashes: Vec<B256>,
+    /// Whether the nonce was found used at the previous check
+    nonce_used: bool,
+}
+
+/// Batch proposals pending in the L1 mempool, by proposer and nonce
+#[derive(Debug, Default)]
+pub struct ProposalTracker {
+    pending: HashMap<(Address, u64), PendingEntry>,
+}
+
+impl ProposalTracker {
+    /// Number of pending proposals
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether no proposal is pending
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Record a proposal seen in the mempool at `at_ms`. Returns the row to write if the nonce
+    /// was not pending yet or the transaction replaces the pending one.
+    pub fn seen(&mut self, proposal: &PendingProposal, at_ms: u64) -> Option<ProposalInclusionRow> {
+        let key = (proposal.proposer, proposal.nonce);
+        if let Some(entry) = self.pending.get_mut(&key) {
+            if entry.hashes.contains(&proposal.tx_hash) {
+                return None;
+            }
+            entry.hashes.push(proposal.tx_hash);
+            entry.row.tx_hash = HashBytes::from(proposal.tx_hash);
+            entry.row.max_fee_per_gas = proposal.max_fee_per_gas;
+            entry.row.max_priority_fee_per_gas = proposal.max_priority_fee_per_gas;
+            entry.row.replacements += 1;
+            return Some(entry.row.clone());
+        }
+
+        let row = ProposalInclusionRow {
+            proposer: AddressBytes::from(proposal.proposer),
+            nonce: proposal.nonce,
+            tx_hash: HashBytes::from(proposal.tx_hash),
+            first_seen_ms: at_ms,
+            max_fee_per_gas: proposal.max_fee_per_gas,
+            max_priority_fee_per_gas: proposal.max_priority_fee_per_gas,
+            replacements: 0,
+            batch_id: None,
+            l1_block_number: None,
+            included_at_ms: None,
+            inclusion_delay_ms: None,
+            dropped: 0,
+        };
+        self.pending.insert(
+            key,
+            PendingEntry { row: row.clone(), hashes: vec![proposal.tx_hash], nonce_used: false },
+        );
+        Some(row)
+    }
+
+    /// Record the inclusion of transaction `tx_hash` proposing `batch_id` in `l1_block_number`,
+    /// received at `at_ms`. Returns the completed row if the transaction was seen pending.
+    pub fn included(
+        &mut self,
+        tx_hash: B256,
+        batch_id: u64,
+        l1_block_number: u64,
+        at_ms: u64,
+    ) -> Option<ProposalInclusionRow> {
+        let key = *self
+            .pending
+            .iter()
+            .find(|(_, entry)| entry.hashes.contains(&tx_hash))
+            .map(|(key, _)| key)?;
+        let mut row = self.pending.remove(&key)?.row;
+        row.tx_hash = HashBytes::from(tx_hash);
+        row.batch_id = Some(batch_id);
+        row.l1_block_number = Some(l1_block_number);
+        row.included_at_ms = Some(at_ms);
+        row.inclusion_delay_ms = Some(at_ms.saturating_sub(row.first_seen_ms));
+        Some(row)
+    }
+
+    /// Proposers with pending proposals
+    pub fn proposers(&self) -> Vec<Address> {
+        let mut proposers: Vec<Address> =
+            self.pending.keys().map(|(proposer, _)| *proposer).collect();
+        proposers.sort_unstable();
+        proposers.dedup();
+        proposers
+    }
+
+    /// Record that the next nonce of `proposer` on L1 is `next_nonce`. Pending proposals of a
+    /// lower nonce that was already used at the previous check are removed and returned as
+    /// dropped.
+    pub fn nonce_checked(
+        &mut self,
+        proposer: Address,
+        next_nonce: u64,
+    ) -> Vec<ProposalInclusionRow> {
+        let mut dropped = Vec::new();
+        self.pending.retain(|(pending_proposer, nonce), entry| {
+            if *pending_proposer != proposer || *nonce >= next_nonce {
+                return true;
+            }
+            if !entry.nonce_used {
+                entry.nonce_used = true;
+                return true;
+            }
+            let mut row = entry.row.clone();
+            row.dropped = 1;
+            dropped.push(row);
+            false
+        });
+        dropped
+    }
+
+    /// Forget proposals pending for longer than [`PENDING_TTL_MS`] at `now_ms`
+    pub fn expire(&mut self, now_ms: u64) {
+        self.pending
+            .retain(|_, entry| now_ms.saturating_sub(entry.row.first_seen_ms) <= PENDING_TTL_MS);
+    }
+}
+
+/// Proposal mempool monitoring methods for the Driver
+impl crate::driver::Driver {
+    /// Start following the batch proposals in the L1 mempool
+    pub fn start_proposal_mempool_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.clone();
+        let extractor = self.extractor.clone();
+        let enable_db_writes = self.enable_db_writes;
+        let extra_targets = self.proposal_watch_addresses.clone();
+
+        info!(extra_targets = extra_targets.len(), "Starting proposal mempool monitoring task");
+
+        let handle = tokio::spawn(async move {
+            loop {
+                if let Err(e) =
+                    track_proposals(&extractor, writer.as_ref(), enable_db_writes, &extra_targets)
+                        .await
+                {
+                    error!(err = %e, "Proposal mempool monitoring failed");
+                }
+                warn!("Proposal mempool monitoring stopped, restarting in {:?}", RESTART_DELAY);
+                tokio::time::sleep(RESTART_DELAY).await;
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Track pending proposals until one of the streams ends, writing every change of a proposal
+async fn track_proposals(
+    extractor: &Extractor,
+    writer: Option<&ClickhouseWriter>,
+    enable_db_writes: bool,
+    extra_targets: &[Address],
+) -> Result<()> {
+    let mut pending = extractor.get_pending_proposal_stream(extra_targets).await?;
+    let mut proposed = extractor.get_batch_proposed_stream().await?;
+    let mut tracker = ProposalTracker::default();
+    let mut check = tokio::time::interval(CHECK_INTERVAL);
+    check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        let rows: Vec<ProposalInclusionRow> = tokio::select! {
+            proposal = pending.next() => {
+                let proposal = proposal.ok_or_else(|| eyre!("pending proposal stream ended"))?;
+                let row = tracker.seen(&proposal.value, received_at_ms(&proposal));
+                if let Some(row) = &row {
+                    debug!(
+                        proposer = ?row.proposer,
+                        nonce = row.nonce,
+                        replacements = row.replacements,
+                        max_fee_per_gas = row.max_fee_per_gas,
+                        "Proposal seen in L1 mempool"
+                    );
+                }
+                row.into_iter().collect()
+            }
+            event = proposed.next() => {
+                let event = event.ok_or_else(|| eyre!("BatchProposed stream ended"))?;
+                let at_ms = received_at_ms(&event);
+                let (batch, tx_hash) = &event.value;
+                let row =
+                    tracker.included(*tx_hash, batch.meta.batchId, batch.info.proposedIn, at_ms);
+                if let Some(row) = &row {
+                    info!(
+                        batch_id = batch.meta.batchId,
+                        delay_ms = ?row.inclusion_delay_ms,
+                        replacements = row.replacements,
+                        "Proposal included"
+                    );
+                }
+                row.into_iter().collect()
+            }
+            _ = check.tick() => {
+                tracker.expire(since_epoch().as_millis() as u64);
+                let mut rows = Vec::new();
+                for proposer in tracker.proposers() {
+                    match extractor.get_l1_nonce(proposer).await {
+                        Ok(next_nonce) => rows.extend(tracker.nonce_checked(proposer, next_nonce)),
+                        Err(e) => warn!(err = %e, %proposer, "Failed to fetch proposer nonce"),
+                    }
+                }
+                for row in &rows {
+                    warn!(proposer = ?row.proposer, nonce = row.nonce, "Proposal nonce used without a batch");
+                }
+                rows
+            }
+        };
+
+        for row in rows {
+            if !enable_db_writes {
+                info!(nonce = row.nonce, "🧪 DRY-RUN: Would insert proposal inclusion");
+                continue;
+            }
+            if let Some(writer) = writer &&
+                let Err(e) = writer.insert_proposal_inclusion(&row).await
+            {
+                error!(err = %e, nonce = row.nonce, "Failed to insert proposal inclusion");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(tx: u8, nonce: u64, max_fee_per_gas: u128) -> PendingProposal {
+        PendingProposal {
+            tx_hash: B256::repeat_byte(tx),
+            proposer: Address::repeat_byte(1),
+            nonce,
+            to: Address::repeat_byte(2),
+            max_fee_per_gas,
+            max_priority_fee_per_gas: Some(1),
+        }
+    }
+
+    #[test]
+    fn replacement_keeps_first_seen_time() {
+        let mut tracker = ProposalTracker::default();
+        let first = tracker.seen(&proposal(1, 7, 10), 1_000).unwrap();
+        assert_eq!(first.first_seen_ms, 1_000);
+        assert_eq!(first.replacements, 0);
+        assert_eq!(tracker.seen(&proposal(1, 7, 10), 2_000), None);
+
+        let bumped = tracker.seen(&proposal(2, 7, 20), 3_000).unwrap();
+        assert_eq!(bumped.first_seen_ms, 1_000);
+        assert_eq!(bumped.tx_hash, HashBytes([2u8; 32]));
+        assert_eq!(bumped.max_fee_per_gas, 20);
+        assert_eq!(bumped.replacements, 1);
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[test]
+    fn inclusion_of_any_replacement_completes_the_proposal() {
+        let mut tracker = ProposalTracker::default();
+        tracker.seen(&proposal(1, 7, 10), 1_000);
+        tracker.seen(&proposal(2, 7, 20), 3_000);
+
+        assert_eq!(tracker.included(B256::repeat_byte(9), 100, 21_000_000, 5_000), None);
+        let row = tracker.included(B256::repeat_byte(1), 100, 21_000_000, 25_000).unwrap();
+        assert_eq!(row.tx_hash, HashBytes([1u8; 32]));
+        assert_eq!(row.batch_id, Some(100));
+        assert_eq!(row.l1_block_number, Some(21_000_000));
+        assert_eq!(row.inclusion_delay_ms, Some(24_000));
+        assert_eq!(row.dropped, 0);
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn used_nonce_is_dropped_at_the_second_check() {
+        let mut tracker = ProposalTracker::default();
+        tracker.seen(&proposal(1, 7, 10), 1_000);
+        tracker.seen(&proposal(2, 8, 10), 1_000);
+        assert_eq!(tracker.proposers(), vec![Address::repeat_byte(1)]);
+
+        assert!(tracker.nonce_checked(Address::repeat_byte(1), 7).is_empty());
+        assert!(tracker.nonce_checked(Address::repeat_byte(1), 8).is_empty());
+        let dropped = tracker.nonce_checked(Address::repeat_byte(1), 8);
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].nonce, 7);
+        assert_eq!(dropped[0].dropped, 1);
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[test]
+    fn stale_proposals_are_forgotten() {
+        let mut tracker = ProposalTracker::default();
+        tracker.seen(&proposal(1, 7, 10), 0);
+        tracker.seen(&proposal(2, 8, 10), 30_000);
+        tracker.expire(PENDING_TTL_MS + 10_000);
+        assert_eq!(tracker.len(), 1);
+    }
+}
@@ -0,0 +1,100 @@
+//! Protocol configuration discovery
+//!
+//! At startup the driver reads the protocol configuration from the `TaikoInbox` contract,
+//! stores it in `ClickHouse` so the API can serve it, and uses it to fill in monitor thresholds
+//! that were not set explicitly.
+
+use clickhouse::{ClickhouseWriter, ProtocolConfigRow};
+use extractor::Extractor;
+use tracing::{info, warn};
+
+/// Batch proof timeout used when neither the CLI flag nor the protocol config provide one
+pub const DEFAULT_BATCH_PROOF_TIMEOUT_SECS: u64 = 3 * 60 * 60;
+
+/// Read the protocol configuration from the inbox active at the L1 head and store it when a
+/// writer is available.
+///
+/// Failures are logged and reported as `None` so the driver can start with its defaults.
+pub async fn discover_protocol_config(
+    extractor: &Extractor,
+    writer: Option<&ClickhouseWriter>,
+) -> Option<ProtocolConfigRow> {
+    let config = match extractor.get_protocol_config().await {
+        Ok(config) => ProtocolConfigRow::from((extractor.current_inbox_address(), &config)),
+        Err(e) => {
+            warn!(err = %e, "Failed to read protocol config from TaikoInbox");
+            return None;
+        }
+    };
+
+    info!(
+        chain_id = config.chain_id,
+        proving_window = config.proving_window,
+        cooldown_window = config.cooldown_window,
+        max_blocks_per_batch = config.max_blocks_per_batch,
+        "Discovered TaikoInbox protocol config"
+    );
+
+    if let Some(writer) = writer &&
+        let Err(e) = writer.insert_protocol_config(&config).await
+    {
+        warn!(err = %e, "Failed to store protocol config");
+    }
+
+    Some(config)
+}
+
+/// Resolve the batch proof timeout, preferring the CLI flag over the protocol proving window.
+pub fn batch_proof_timeout_secs(flag: Option<u64>, config: Option<&ProtocolConfigRow>) -> u64 {
+    flag.or_else(|| {
+        config.map(|c| u64::from(c.proving_window)).filter(|&proving_window| proving_window > 0)
+    })
+    .unwrap_or(DEFAULT_BATCH_PROOF_TIMEOUT_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clickhouse::AddressBytes;
+
+    fn config_with_proving_window(proving_window: u16) -> ProtocolConfigRow {
+        ProtocolConfigRow {
+            inbox_address: AddressBytes([0u8; 20]),
+            chain_id: 167000,
+            max_unverified_batches: 0,
+            batch_ring_buffer_size: 0,
+            max_batches_to_verify: 0,
+            block_max_gas_limit: 0,
+            liveness_bond_base: 0,
+            liveness_bond_per_block: 0,
+            state_root_sync_internal: 0,
+            max_anchor_height_offset: 0,
+            base_fee_sharing_pctg: 0,
+            proving_window,
+            cooldown_window: 0,
+            max_signals_to_receive: 0,
+            max_blocks_per_batch: 0,
+            ontake_fork_height: 0,
+            pacaya_fork_height: 0,
+        }
+    }
+
+    #[test]
+    fn batch_proof_timeout_prefers_flag() {
+        let config = config_with_proving_window(7200);
+        assert_eq!(batch_proof_timeout_secs(Some(99), Some(&config)), 99);
+    }
+
+    #[test]
+    fn batch_proof_timeout_uses_proving_window() {
+        let config = config_with_proving_window(7200);
+        assert_eq!(batch_proof_timeout_secs(None, Some(&config)), 7200);
+    }
+
+    #[test]
+    fn batch_proof_timeout_falls_back_to_default() {
+        let config = config_with_proving_window(0);
+        assert_eq!(batch_proof_timeout_secs(None, Some(&config)), DEFAULT_BATCH_PROOF_TIMEOUT_SECS);
+        assert_eq!(batch_proof_timeout_secs(None, None), DEFAULT_BATCH_PROOF_TIMEOUT_SECS);
+    }
+}
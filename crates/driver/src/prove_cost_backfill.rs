@@ -0,0 +1,113 @@
+//! Prove cost backfill
+//!
+//! Prove costs are normally written when a `BatchesProved` event is processed, but batches
+//! ingested before cost tracking existed, or whose receipt could not be fetched at the time,
+//! have no `prove_costs` row. This task finds those batches, looks up the proving transaction
+//! from the inbox logs of the L1 block it was recorded in and derives the cost from its receipt.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use clickhouse::{ClickhouseReader, ClickhouseWriter};
+use extractor::Extractor;
+use eyre::Result;
+use tracing::{error, info, warn};
+
+use crate::event_processing::{
+    average_cost_per_batch, fetch_transaction_cost, with_db_error_context,
+};
+
+/// Maximum number of proved batches handled per cycle
+const MAX_BATCHES_PER_CYCLE: u64 = 500;
+
+/// Prove cost backfill methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic prove cost backfill task
+    pub fn start_prove_cost_backfill_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let reader = self.clickhouse_reader.as_ref()?.clone();
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let extractor = self.extractor.clone();
+        let interval_secs = self.prove_cost_backfill_interval_secs;
+
+        info!(interval_secs, "Starting prove cost backfill task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                match backfill_prove_costs(&reader, &writer, &extractor, MAX_BATCHES_PER_CYCLE)
+                    .await
+                {
+                    Ok(0) => {}
+                    Ok(inserted) => info!(inserted, "Prove cost backfill cycle completed"),
+                    Err(e) => error!(err = %e, "Prove cost backfill failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Backfill prove costs for up to `limit` proved batches that have none.
+/// Returns the number of prove cost rows inserted.
+pub async fn backfill_prove_costs(
+    reader: &ClickhouseReader,
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    limit: u64,
+) -> Result<usize> {
+    let missing = reader.get_proved_batches_without_prove_cost(limit).await?;
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    info!(batches = missing.len(), "Found proved batches without prove cost");
+
+    let mut by_block: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+    for row in missing {
+        by_block.entry(row.l1_block_number).or_default().push(row.batch_id);
+    }
+
+    let mut inserted = 0;
+    for (l1_block_number, batch_ids) in by_block.into_iter().rev() {
+        let logs = match extractor.get_batches_proved_logs(l1_block_number, l1_block_number).await {
+            Ok(logs) => logs,
+            Err(e) => {
+                warn!(l1_block_number, err = %e, "Failed to fetch BatchesProved logs");
+                continue;
+            }
+        };
+
+        for (proved, _, tx_hash) in logs {
+            let proved_ids = proved.batch_ids_proved();
+            let pending: Vec<u64> =
+                batch_ids.iter().copied().filter(|id| proved_ids.contains(id)).collect();
+            if pending.is_empty() {
+                continue;
+            }
+
+            let Some(cost) = fetch_transaction_cost(extractor, tx_hash).await else {
+                continue;
+            };
+            let cost_per_batch = average_cost_per_batch(cost, proved_ids.len());
+
+            for batch_id in pending {
+                with_db_error_context(
+                    writer.insert_prove_cost(l1_block_number, batch_id, cost_per_batch),
+                    "insert backfilled prove cost",
+                    format!(
+                        "l1_block_number={}, batch_id={}, tx_hash={:?}",
+                        l1_block_number, batch_id, tx_hash
+                    ),
+                )
+                .await?;
+                inserted += 1;
+            }
+        }
+    }
+
+    Ok(inserted)
+}
@@ -1,16 +1,96 @@
 //! Reorg detection and handling functionality
 
-use alloy_primitives::Address;
+use std::time::{Duration, Instant};
+
+use alloy_primitives::{Address, BlockHash};
 use clickhouse::{ClickhouseReader, ClickhouseWriter, HashBytes};
-use extractor::ReorgDetector;
+use extractor::{Extractor, ReorgDetector};
+use primitives::reorg::ReorgCause;
 use tracing::{error, info, warn};
 
-/// Process reorg detection for L2 headers
+use crate::orphaned_blocks::spawn_orphaned_block_capture;
+
+/// How long before an L2 reorg an L1 reorg or forced inclusion is considered related (two L1
+/// slots)
+const CORRELATION_WINDOW: Duration = Duration::from_secs(24);
+/// Number of slots in an L1 epoch
+const SLOTS_PER_EPOCH: u64 = 32;
+/// Distance in slots from an epoch boundary within which a sequencer change counts as an
+/// operator rotation
+const EPOCH_BOUNDARY_SLOTS: u64 = 2;
+
+/// Tracks L1 reorgs and forced inclusions to attribute a probable cause to L2 reorgs.
+#[derive(Debug, Default)]
+pub struct ReorgCorrelator {
+    /// Number, hash and slot of the last L1 header
+    last_l1: Option<(u64, BlockHash, u64)>,
+    /// When the last L1 reorg was observed
+    last_l1_reorg: Option<Instant>,
+    /// When the last forced inclusion was processed
+    last_forced_inclusion: Option<Instant>,
+}
+
+impl ReorgCorrelator {
+    /// Record an L1 header, detecting an L1 reorg when it replaces the previous head.
+    pub fn on_l1_header(&mut self, number: u64, hash: BlockHash, slot: u64, now: Instant) {
+        let replaced = self
+            .last_l1
+            .filter(|&(last_number, last_hash, _)| number <= last_number && hash != last_hash);
+        if let Some((last_number, _, _)) = replaced {
+            info!(block_number = number, previous_head = last_number, "L1 reorg observed");
+            self.last_l1_reorg = Some(now);
+        }
+        self.last_l1 = Some((number, hash, slot));
+    }
+
+    /// Record a processed forced inclusion.
+    pub const fn on_forced_inclusion(&mut self, now: Instant) {
+        self.last_forced_inclusion = Some(now);
+    }
+
+    /// Most likely cause of an L2 reorg at `now` replacing a block of `old_sequencer` with one
+    /// of `new_sequencer`. An L1 reorg takes precedence over an operator change, which takes
+    /// precedence over a forced inclusion.
+    pub fn probable_cause(
+        &self,
+        old_sequencer: Address,
+        new_sequencer: Address,
+        now: Instant,
+    ) -> ReorgCause {
+        let recent = |at: Option<Instant>| {
+            at.is_some_and(|at| now.saturating_duration_since(at) <= CORRELATION_WINDOW)
+        };
+
+        if recent(self.last_l1_reorg) {
+            ReorgCause::L1Reorg
+        } else if old_sequencer != new_sequencer && self.near_epoch_boundary() {
+            ReorgCause::OperatorChange
+        } else if recent(self.last_forced_inclusion) {
+            ReorgCause::ForcedInclusion
+        } else {
+            ReorgCause::Unknown
+        }
+    }
+
+    /// Whether the slot of the last L1 header is close to an epoch boundary, where operators
+    /// rotate.
+    fn near_epoch_boundary(&self) -> bool {
+        self.last_l1.is_some_and(|(_, _, slot)| {
+            let offset = slot % SLOTS_PER_EPOCH;
+            offset < EPOCH_BOUNDARY_SLOTS || SLOTS_PER_EPOCH - offset <= EPOCH_BOUNDARY_SLOTS
+        })
+    }
+}
+
+/// Process reorg detection for L2 headers. The content of orphaned blocks is captured in the
+/// background.
 pub async fn process_reorg_detection(
     reorg_detector: &mut ReorgDetector,
+    extractor: &Extractor,
     last_l2_header: &mut Option<(u64, Address)>,
     clickhouse_writer: &Option<ClickhouseWriter>,
     clickhouse_reader: &Option<ClickhouseReader>,
+    correlator: &ReorgCorrelator,
     header: &primitives::headers::L2Header,
 ) {
     let writer = match clickhouse_writer {
@@ -21,19 +101,20 @@ pub async fn process_reorg_detection(
     let old_head = reorg_detector.head_number();
     let reorg_result = reorg_detector.on_new_block_with_hash(header.number, header.hash);
 
-    // Update last L2 header tracking
-    *last_l2_header = Some((header.number, header.beneficiary));
+    // Update last L2 header tracking, keeping the previous one to identify the old sequencer
+    let previous_l2_header = last_l2_header.replace((header.number, header.beneficiary));
 
     if let Some((depth, orphaned_hash)) = reorg_result {
         // Handle orphaned hash from one-block reorg
         if let Some(hash) = orphaned_hash {
-            insert_orphaned_hash(writer, hash, header.number).await;
+            insert_orphaned_hash(writer, extractor, hash, header.number).await;
         }
 
         // Handle orphaned blocks from traditional reorg
         if depth > 0 {
             handle_traditional_reorg_orphans(
                 writer,
+                extractor,
                 clickhouse_reader,
                 old_head,
                 header.number,
@@ -43,7 +124,9 @@ pub async fn process_reorg_detection(
         }
 
         // Process L2 reorg
-        if let Some((prev_block_number, prev_sequencer)) = *last_l2_header {
+        if let Some((prev_block_number, prev_sequencer)) = previous_l2_header {
+            let cause =
+                correlator.probable_cause(prev_sequencer, header.beneficiary, Instant::now());
             info!(
                 prev_block = prev_block_number,
                 new_block = header.number,
@@ -51,12 +134,13 @@ pub async fn process_reorg_detection(
                 new_sequencer = ?header.beneficiary,
                 depth = depth,
                 orphaned_hash = ?orphaned_hash,
+                probable_cause = cause.as_str(),
                 "L2 reorg detected"
             );
 
             // Insert L2 reorg record
             if let Err(e) = writer
-                .insert_l2_reorg(header.number, depth, prev_sequencer, header.beneficiary)
+                .insert_l2_reorg(header.number, depth, prev_sequencer, header.beneficiary, cause)
                 .await
             {
                 error!(
@@ -71,22 +155,26 @@ pub async fn process_reorg_detection(
     }
 }
 
-/// Insert an orphaned hash from a one-block reorg
+/// Insert an orphaned hash from a one-block reorg and capture the block's content
 pub async fn insert_orphaned_hash(
     writer: &ClickhouseWriter,
+    extractor: &Extractor,
     hash: alloy_primitives::B256,
     block_number: u64,
 ) {
-    if let Err(e) = writer.insert_orphaned_hashes(&[(HashBytes::from(hash), block_number)]).await {
+    let orphaned = vec![(HashBytes::from(hash), block_number)];
+    if let Err(e) = writer.insert_orphaned_hashes(&orphaned).await {
         error!(block_number, orphaned_hash = ?hash, err = %e, "Failed to insert orphaned hash");
     } else {
         info!(block_number, orphaned_hash = ?hash, "Inserted orphaned hash");
     }
+    spawn_orphaned_block_capture(extractor.clone(), writer.clone(), orphaned);
 }
 
 /// Handle orphaned blocks from traditional reorgs
 pub async fn handle_traditional_reorg_orphans(
     writer: &ClickhouseWriter,
+    extractor: &Extractor,
     clickhouse_reader: &Option<ClickhouseReader>,
     old_head: u64,
     new_head: u64,
@@ -108,6 +196,7 @@ pub async fn handle_traditional_reorg_orphans(
             } else {
                 info!(count = orphaned_hashes.len(), "Inserted orphaned hashes for reorg");
             }
+            spawn_orphaned_block_capture(extractor.clone(), writer.clone(), orphaned_hashes);
         }
         Ok(_) => {} // No orphaned hashes found
         Err(e) => error!(err = %e, "Failed to fetch orphaned hashes"),
@@ -158,6 +247,47 @@ pub fn calculate_orphaned_blocks(old_head: u64, new_head: u64, depth: u32) -> Ve
 mod tests {
     use super::*;
 
+    const OLD: Address = Address::repeat_byte(1);
+    const NEW: Address = Address::repeat_byte(2);
+
+    #[test]
+    fn test_probable_cause_l1_reorg() {
+        let now = Instant::now();
+        let mut correlator = ReorgCorrelator::default();
+        correlator.on_l1_header(100, BlockHash::repeat_byte(1), 40, now);
+        correlator.on_l1_header(100, BlockHash::repeat_byte(2), 40, now);
+        correlator.on_forced_inclusion(now);
+
+        assert_eq!(correlator.probable_cause(OLD, NEW, now), ReorgCause::L1Reorg);
+        assert_eq!(
+            correlator.probable_cause(OLD, OLD, now + CORRELATION_WINDOW + Duration::from_secs(1)),
+            ReorgCause::Unknown
+        );
+    }
+
+    #[test]
+    fn test_probable_cause_operator_change_at_epoch_boundary() {
+        let now = Instant::now();
+        let mut correlator = ReorgCorrelator::default();
+        correlator.on_l1_header(100, BlockHash::repeat_byte(1), 63, now);
+        assert_eq!(correlator.probable_cause(OLD, NEW, now), ReorgCause::OperatorChange);
+        assert_eq!(correlator.probable_cause(OLD, OLD, now), ReorgCause::Unknown);
+
+        correlator.on_l1_header(101, BlockHash::repeat_byte(2), 80, now);
+        assert_eq!(correlator.probable_cause(OLD, NEW, now), ReorgCause::Unknown);
+    }
+
+    #[test]
+    fn test_probable_cause_forced_inclusion() {
+        let now = Instant::now();
+        let mut correlator = ReorgCorrelator::default();
+        correlator.on_l1_header(100, BlockHash::repeat_byte(1), 80, now);
+        correlator.on_l1_header(101, BlockHash::repeat_byte(2), 81, now);
+        correlator.on_forced_inclusion(now);
+
+        assert_eq!(correlator.probable_cause(OLD, NEW, now), ReorgCause::ForcedInclusion);
+    }
+
     #[test]
     fn test_calculate_orphaned_blocks_no_reorg() {
         let result = calculate_orphaned_blocks(100, 100, 0);
@@ -0,0 +1,220 @@
+//! Historical reprocessing of derived tables
+//!
+//! Derived tables hold rows computed from the raw tables, e.g. sequencer downtime from the
+//! operator schedule and the L2 block producers. Every derived row carries the
+//! `computation_version` of the logic that computed it. When a bug in that logic is fixed, its
+//! version is bumped and `--reprocess` recomputes the rows derived by older versions in batches.
+//! Recomputed rows are inserted next to the old ones, and readers only use the rows of the
+//! latest version, so results stay consistent while a run is in progress and a run can be
+//! interrupted and resumed at any time.
+
+use async_trait::async_trait;
+use clickhouse::{ClickhouseReader, ClickhouseWriter};
+use config::Opts;
+use eyre::{Result, bail};
+use tracing::info;
+
+use crate::sequencer_downtime::{HOUR_SECS, SEQUENCER_DOWNTIME_VERSION, recompute_sequencer_hours};
+
+/// Computation of derived tables that can be recomputed from the raw tables
+#[async_trait]
+pub trait Derivation: Send + Sync {
+    /// Name used in logs
+    fn name(&self) -> &'static str;
+
+    /// Current version of the computation
+    fn version(&self) -> u16;
+
+    /// Up to `limit` units, e.g. hours, whose rows were derived by an older version, oldest
+    /// first
+    async fn outdated(&self, reader: &ClickhouseReader, limit: u64) -> Result<Vec<u64>>;
+
+    /// Recompute the rows of `units` with the current version
+    async fn recompute(
+        &self,
+        reader: &ClickhouseReader,
+        writer: &ClickhouseWriter,
+        units: &[u64],
+    ) -> Result<()>;
+}
+
+/// Sequencer downtime and scheduled time, derived per hour
+#[derive(Debug, Clone, Copy)]
+pub struct SequencerDowntime {
+    /// Longest gap between blocks of the scheduled sequencer that is not downtime
+    pub max_gap_secs: u64,
+}
+
+#[async_trait]
+impl Derivation for SequencerDowntime {
+    fn name(&self) -> &'static str {
+        "sequencer_downtime"
+    }
+
+    fn version(&self) -> u16 {
+        SEQUENCER_DOWNTIME_VERSION
+    }
+
+    async fn outdated(&self, reader: &ClickhouseReader, limit: u64) -> Result<Vec<u64>> {
+        reader.get_outdated_sequencer_hours(self.version(), limit).await
+    }
+
+    async fn recompute(
+        &self,
+        reader: &ClickhouseReader,
+        writer: &ClickhouseWriter,
+        hours: &[u64],
+    ) -> Result<()> {
+        for (from, until) in hour_ranges(hours) {
+            recompute_sequencer_hours(reader, writer, from, until, self.max_gap_secs).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Recompute every outdated unit of `derivation`, `batch_size` units at a time. Returns the
+/// number of units recomputed.
+pub async fn reprocess(
+    derivation: &dyn Derivation,
+    reader: &ClickhouseReader,
+    writer: &ClickhouseWriter,
+    batch_size: u64,
+) -> Result<u64> {
+    let name = derivation.name();
+    let version = derivation.version();
+    let mut total = 0;
+    let mut last_batch: Vec<u64> = Vec::new();
+
+    loop {
+        let units = derivation.outdated(reader, batch_size).await?;
+        if units.is_empty() {
+            break;
+        }
+        // A batch that is still outdated after being recomputed would be retried forever
+        if units == last_batch {
+            bail!("{name}: units starting at {} are still outdated after recomputing", units[0]);
+        }
+
+        derivation.recompute(reader, writer, &units).await?;
+        total += units.len() as u64;
+        info!(
+            derivation = name,
+            version,
+            recomputed = total,
+            first = units[0],
+            last = units[units.len() - 1],
+            "Reprocessed batch"
+        );
+        last_batch = units;
+    }
+
+    Ok(total)
+}
+
+/// Recompute the outdated rows of every derived table and exit
+pub async fn run_reprocess(opts: &Opts) -> Result<()> {
+    let (read_username, read_password) = opts.clickhouse.read_credentials();
+    let reader = ClickhouseReader::new(
+        opts.clickhouse.url.clone(),
+        opts.clickhouse.db.clone(),
+        read_username,
+        read_password,
+    )?
+    .with_table_prefix(opts.clickhouse.table_prefix.clone());
+    let (write_username, write_password) = opts.clickhouse.write_credentials();
+    let writer = ClickhouseWriter::new(
+        opts.clickhouse.url.clone(),
+        opts.clickhouse.db.clone(),
+        write_username,
+        write_password,
+    )
+    .with_table_prefix(opts.clickhouse.table_prefix.clone());
+
+    let derivations: [&dyn Derivation; 1] =
+        [&SequencerDowntime { max_gap_secs: opts.sequencer_downtime_max_gap_secs }];
+    for derivation in derivations {
+        info!(derivation = derivation.name(), version = derivation.version(), "🔁 Reprocessing");
+        let recomputed = reprocess(derivation, &reader, &writer, opts.reprocess_batch_size).await?;
+        info!(derivation = derivation.name(), recomputed, "✅ Reprocessing completed");
+    }
+    Ok(())
+}
+
+/// Contiguous hour-aligned ranges `[from, until)` covering the sorted `hours`
+fn hour_ranges(hours: &[u64]) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(u64, u64)> = Vec::new();
+    for &hour in hours {
+        match ranges.last_mut() {
+            Some((_, until)) if *until == hour => *until = hour + HOUR_SECS,
+            _ => ranges.push((hour, hour + HOUR_SECS)),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Derivation returning the given batches of outdated units in turn
+    struct Batches(Mutex<Vec<Vec<u64>>>);
+
+    #[async_trait]
+    impl Derivation for Batches {
+        fn name(&self) -> &'static str {
+            "test"
+        }
+
+        fn version(&self) -> u16 {
+            2
+        }
+
+        async fn outdated(&self, _reader: &ClickhouseReader, _limit: u64) -> Result<Vec<u64>> {
+            let mut batches = self.0.lock().unwrap();
+            Ok(if batches.is_empty() { Vec::new() } else { batches.remove(0) })
+        }
+
+        async fn recompute(
+            &self,
+            _reader: &ClickhouseReader,
+            _writer: &ClickhouseWriter,
+            _units: &[u64],
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn clients() -> (ClickhouseReader, ClickhouseWriter) {
+        let url = url::Url::parse("http://127.0.0.1:1").unwrap();
+        let reader =
+            ClickhouseReader::new(url.clone(), "db".to_owned(), "user".into(), "pass".into())
+                .unwrap();
+        let writer = ClickhouseWriter::new(url, "db".to_owned(), "user".into(), "pass".into());
+        (reader, writer)
+    }
+
+    #[tokio::test]
+    async fn reprocess_recomputes_batches_until_none_are_outdated() {
+        let (reader, writer) = clients();
+        let derivation = Batches(Mutex::new(vec![vec![1, 2], vec![3]]));
+        assert_eq!(reprocess(&derivation, &reader, &writer, 2).await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn reprocess_fails_when_a_batch_stays_outdated() {
+        let (reader, writer) = clients();
+        let derivation = Batches(Mutex::new(vec![vec![1, 2], vec![1, 2]]));
+        assert!(reprocess(&derivation, &reader, &writer, 2).await.is_err());
+    }
+
+    #[test]
+    fn consecutive_hours_form_one_range() {
+        let hour = 1_699_999_200;
+        assert_eq!(
+            hour_ranges(&[hour, hour + HOUR_SECS, hour + 3 * HOUR_SECS]),
+            vec![(hour, hour + 2 * HOUR_SECS), (hour + 3 * HOUR_SECS, hour + 4 * HOUR_SECS)]
+        );
+        assert!(hour_ranges(&[]).is_empty());
+    }
+}
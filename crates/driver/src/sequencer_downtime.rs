@@ -0,0 +1,344 @@
+//! Sequencer downtime ledger
+//!
+//! The preconfirmation whitelist schedules one operator per epoch to sequence L2 blocks. This
+//! task compares the schedule recorded in `preconf_data` with the sequencers of the canonical L2
+//! blocks and records every span in which the scheduled operator produced no block for longer
+//! than the configured gap in the `sequencer_downtime` table. The seconds each operator was
+//! scheduled are recorded per hour in `sequencer_schedule`, so that uptime can be reported per
+//! operator and day. Every change of the block sequencer is recorded in `sequencer_handovers`
+//! with the seconds between the last block of the outgoing sequencer and the first block of the
+//! incoming one.
+//!
+//! Only complete hours are processed, and both tables are split at hour boundaries. Every cycle
+//! recomputes the last `lookback_hours` so that late blocks are taken into account; recomputed
+//! rows replace the earlier ones. The hour before the window is read as well, so that spans
+//! starting before the window are not cut short.
+//!
+//! Rows carry the [`SEQUENCER_DOWNTIME_VERSION`] that derived them. Bump it whenever the logic
+//! below changes; `--reprocess` then recomputes the hours derived by an older version from the
+//! raw tables.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::Utc;
+use clickhouse::{
+    AddressBytes, ClickhouseReader, ClickhouseWriter, L2BlockProducerRow, OperatorScheduleRow,
+    SequencerDowntimeRow, SequencerHandoverRow, SequencerScheduleRow,
+};
+use eyre::Result;
+use tracing::{error, info};
+
+/// Seconds in an hour
+pub const HOUR_SECS: u64 = 3_600;
+/// Version of the downtime and schedule computation, stored with every row
+pub const SEQUENCER_DOWNTIME_VERSION: u16 = 1;
+
+/// Span in which an operator was scheduled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Term {
+    /// Scheduled operator
+    pub operator: AddressBytes,
+    /// Start of the span in seconds
+    pub start_ts: u64,
+    /// End of the span in seconds, exclusive
+    pub end_ts: u64,
+}
+
+/// Sequencer downtime methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic sequencer downtime task
+    pub fn start_sequencer_downtime_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let reader = self.clickhouse_reader.as_ref()?.clone();
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let interval_secs = self.sequencer_downtime_interval_secs;
+        let lookback_hours = self.sequencer_downtime_lookback_hours;
+        let max_gap_secs = self.sequencer_downtime_max_gap_secs;
+
+        info!(interval_secs, lookback_hours, max_gap_secs, "Starting sequencer downtime task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let now = Utc::now().timestamp() as u64;
+                match sync_sequencer_downtime(&reader, &writer, now, lookback_hours, max_gap_secs)
+                    .await
+                {
+                    Ok(0) => {}
+                    Ok(spans) => info!(spans, "Sequencer downtime cycle completed"),
+                    Err(e) => error!(err = %e, "Sequencer downtime sync failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Recompute the downtime spans and scheduled time of the complete hours within the lookback.
+/// Returns the number of downtime spans inserted.
+pub async fn sync_sequencer_downtime(
+    reader: &ClickhouseReader,
+    writer: &ClickhouseWriter,
+    now: u64,
+    lookback_hours: u64,
+    max_gap_secs: u64,
+) -> Result<usize> {
+    let until = now / HOUR_SECS * HOUR_SECS;
+    let from = until.saturating_sub(lookback_hours * HOUR_SECS);
+    recompute_sequencer_hours(reader, writer, from, until, max_gap_secs).await
+}
+
+/// Recompute the downtime spans, scheduled time and handovers of the hours in `[from, until)`,
+/// which must be hour-aligned. Returns the number of downtime spans inserted.
+pub async fn recompute_sequencer_hours(
+    reader: &ClickhouseReader,
+    writer: &ClickhouseWriter,
+    from: u64,
+    until: u64,
+    max_gap_secs: u64,
+) -> Result<usize> {
+    let since = from.saturating_sub(HOUR_SECS);
+
+    let schedule = reader.get_operator_schedule(since, until).await?;
+    let blocks = reader.get_l2_block_producers(since, until).await?;
+    let terms = operator_terms(&schedule, until);
+
+    let scheduled: Vec<SequencerScheduleRow> =
+        hourly_schedule(&terms).into_iter().filter(|row| row.hour_ts >= from).collect();
+    let downtime: Vec<SequencerDowntimeRow> = downtime_spans(&terms, &blocks, max_gap_secs)
+        .iter()
+        .flat_map(split_hourly)
+        .filter(|row| row.start_ts >= from)
+        .collect();
+    let handovers: Vec<SequencerHandoverRow> =
+        handovers(&blocks).into_iter().filter(|row| row.first_block_ts >= from).collect();
+
+    writer.insert_sequencer_schedule(&scheduled).await?;
+    writer.insert_sequencer_downtime(&downtime).await?;
+    writer.insert_sequencer_handovers(&handovers).await?;
+    Ok(downtime.len())
+}
+
+/// Terms of the operators in `schedule`, which is ordered by timestamp. Each sample holds until
+/// the next one, and the last one until `until`.
+pub fn operator_terms(schedule: &[OperatorScheduleRow], until: u64) -> Vec<Term> {
+    let mut terms: Vec<Term> = Vec::new();
+    for sample in schedule {
+        if let Some(term) = terms.last_mut() {
+            if term.operator == sample.operator {
+                continue;
+            }
+            term.end_ts = sample.block_ts;
+        }
+        terms.push(Term { operator: sample.operator, start_ts: sample.block_ts, end_ts: until });
+    }
+    terms
+}
+
+/// Spans longer than `max_gap_secs` in which the scheduled operator produced no block. `blocks`
+/// must be ordered by timestamp.
+pub fn downtime_spans(
+    terms: &[Term],
+    blocks: &[L2BlockProducerRow],
+    max_gap_secs: u64,
+) -> Vec<SequencerDowntimeRow> {
+    let mut spans = Vec::new();
+    for term in terms {
+        let first = blocks.partition_point(|b| b.block_ts < term.start_ts);
+        let last = blocks.partition_point(|b| b.block_ts < term.end_ts);
+        let produced =
+            blocks[first..last].iter().filter(|b| b.sequencer == term.operator).map(|b| b.block_ts);
+
+        let mut previous = term.start_ts;
+        for ts in produced.chain(std::iter::once(term.end_ts)) {
+            if ts - previous > max_gap_secs {
+                spans.push(SequencerDowntimeRow {
+                    sequencer: term.operator,
+                    start_ts: previous,
+                    end_ts: ts,
+                    computation_version: SEQUENCER_DOWNTIME_VERSION,
+                });
+            }
+            previous = ts;
+        }
+    }
+    spans
+}
+
+/// Changes of the sequencer between consecutive blocks. `blocks` must be ordered by timestamp.
+pub fn handovers(blocks: &[L2BlockProducerRow]) -> Vec<SequencerHandoverRow> {
+    blocks
+        .windows(2)
+        .filter(|pair| pair[0].sequencer != pair[1].sequencer)
+        .map(|pair| SequencerHandoverRow {
+            outgoing: pair[0].sequencer,
+            incoming: pair[1].sequencer,
+            last_block_number: pair[0].l2_block_number,
+            last_block_ts: pair[0].block_ts,
+            first_block_number: pair[1].l2_block_number,
+            first_block_ts: pair[1].block_ts,
+            latency_secs: pair[1].block_ts.saturating_sub(pair[0].block_ts),
+        })
+        .collect()
+}
+
+/// Split a downtime span at hour boundaries
+pub fn split_hourly(span: &SequencerDowntimeRow) -> Vec<SequencerDowntimeRow> {
+    let mut pieces = Vec::new();
+    let mut start_ts = span.start_ts;
+    while start_ts < span.end_ts {
+        let end_ts = span.end_ts.min((start_ts / HOUR_SECS + 1) * HOUR_SECS);
+        pieces.push(SequencerDowntimeRow { start_ts, end_ts, ..span.clone() });
+        start_ts = end_ts;
+    }
+    pieces
+}
+
+/// Seconds each operator was scheduled per hour
+pub fn hourly_schedule(terms: &[Term]) -> Vec<SequencerScheduleRow> {
+    let mut hours: BTreeMap<(u64, AddressBytes), u64> = BTreeMap::new();
+    for term in terms {
+        let span = SequencerDowntimeRow {
+            sequencer: term.operator,
+            start_ts: term.start_ts,
+            end_ts: term.end_ts,
+            computation_version: SEQUENCER_DOWNTIME_VERSION,
+        };
+        for piece in split_hourly(&span) {
+            let hour_ts = piece.start_ts / HOUR_SECS * HOUR_SECS;
+            *hours.entry((hour_ts, term.operator)).or_default() += piece.end_ts - piece.start_ts;
+        }
+    }
+    hours
+        .into_iter()
+        .map(|((hour_ts, sequencer), scheduled_secs)| SequencerScheduleRow {
+            sequencer,
+            hour_ts,
+            scheduled_secs,
+            computation_version: SEQUENCER_DOWNTIME_VERSION,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: u64 = 1_699_999_200;
+
+    fn sample(block_ts: u64, operator: u8) -> OperatorScheduleRow {
+        OperatorScheduleRow { block_ts, operator: AddressBytes([operator; 20]) }
+    }
+
+    fn block(block_ts: u64, sequencer: u8) -> L2BlockProducerRow {
+        L2BlockProducerRow {
+            l2_block_number: block_ts - HOUR,
+            block_ts,
+            sequencer: AddressBytes([sequencer; 20]),
+        }
+    }
+
+    fn span(sequencer: u8, start_ts: u64, end_ts: u64) -> SequencerDowntimeRow {
+        SequencerDowntimeRow {
+            sequencer: AddressBytes([sequencer; 20]),
+            start_ts,
+            end_ts,
+            computation_version: SEQUENCER_DOWNTIME_VERSION,
+        }
+    }
+
+    #[test]
+    fn consecutive_samples_of_an_operator_form_one_term() {
+        let schedule = [sample(HOUR, 1), sample(HOUR + 12, 1), sample(HOUR + 384, 2)];
+        let terms = operator_terms(&schedule, HOUR + 768);
+        assert_eq!(
+            terms,
+            vec![
+                Term { operator: AddressBytes([1; 20]), start_ts: HOUR, end_ts: HOUR + 384 },
+                Term { operator: AddressBytes([2; 20]), start_ts: HOUR + 384, end_ts: HOUR + 768 },
+            ]
+        );
+        assert!(operator_terms(&[], HOUR).is_empty());
+    }
+
+    #[test]
+    fn gaps_within_a_term_are_downtime() {
+        let terms = operator_terms(&[sample(HOUR, 1), sample(HOUR + 400, 2)], HOUR + 800);
+        let blocks = [
+            block(HOUR + 90, 1),
+            block(HOUR + 100, 1),
+            // Blocks of another sequencer do not count for the scheduled one
+            block(HOUR + 200, 2),
+            block(HOUR + 300, 1),
+            block(HOUR + 410, 2),
+            block(HOUR + 760, 2),
+        ];
+
+        let spans = downtime_spans(&terms, &blocks, 60);
+        assert_eq!(
+            spans,
+            vec![
+                span(1, HOUR, HOUR + 90),
+                span(1, HOUR + 100, HOUR + 300),
+                span(1, HOUR + 300, HOUR + 400),
+                span(2, HOUR + 410, HOUR + 760),
+            ]
+        );
+    }
+
+    #[test]
+    fn sequencer_changes_are_handovers() {
+        let blocks = [
+            block(HOUR + 2, 1),
+            block(HOUR + 4, 1),
+            block(HOUR + 12, 2),
+            block(HOUR + 14, 2),
+            block(HOUR + 15, 1),
+        ];
+
+        let rows = handovers(&blocks);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].outgoing, AddressBytes([1; 20]));
+        assert_eq!(rows[0].incoming, AddressBytes([2; 20]));
+        assert_eq!((rows[0].last_block_number, rows[0].first_block_number), (4, 12));
+        assert_eq!(rows[0].latency_secs, 8);
+        assert_eq!(rows[1].incoming, AddressBytes([1; 20]));
+        assert_eq!(rows[1].latency_secs, 1);
+        assert!(handovers(&blocks[..2]).is_empty());
+    }
+
+    #[test]
+    fn spans_are_split_at_hour_boundaries() {
+        assert_eq!(
+            split_hourly(&span(1, HOUR - 100, HOUR + 3_700)),
+            vec![
+                span(1, HOUR - 100, HOUR),
+                span(1, HOUR, HOUR + 3_600),
+                span(1, HOUR + 3_600, HOUR + 3_700),
+            ]
+        );
+        assert!(split_hourly(&span(1, HOUR, HOUR)).is_empty());
+    }
+
+    #[test]
+    fn scheduled_time_is_summed_per_hour() {
+        let terms = operator_terms(
+            &[sample(HOUR, 1), sample(HOUR + 1_800, 2), sample(HOUR + 3_000, 1)],
+            HOUR + 4_000,
+        );
+        let rows = hourly_schedule(&terms);
+        let secs = |hour_ts: u64, operator: u8| {
+            rows.iter()
+                .find(|r| r.hour_ts == hour_ts && r.sequencer == AddressBytes([operator; 20]))
+                .map(|r| r.scheduled_secs)
+        };
+        assert_eq!(rows.len(), 3);
+        assert_eq!(secs(HOUR, 1), Some(2_400));
+        assert_eq!(secs(HOUR, 2), Some(1_200));
+        assert_eq!(secs(HOUR + 3_600, 1), Some(400));
+    }
+}
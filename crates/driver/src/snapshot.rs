@@ -0,0 +1,63 @@
+//! Snapshot export and import of the full dataset
+//!
+//! `--snapshot-export` copies every table to an S3-compatible bucket and `--snapshot-import`
+//! loads such a snapshot into a fresh database, so an environment can be cloned or restored
+//! without replaying the chain.
+
+use clickhouse::{ClickhouseWriter, SnapshotFormat, SnapshotLocation};
+use config::Opts;
+use eyre::{Result, eyre};
+use tracing::info;
+
+/// Snapshot location configured by `opts`
+fn snapshot_location(opts: &Opts) -> Result<SnapshotLocation> {
+    let url = opts
+        .snapshot
+        .url
+        .clone()
+        .ok_or_else(|| eyre!("SNAPSHOT_URL must be set to export or import a snapshot"))?;
+    let format = match opts.snapshot.format {
+        config::SnapshotFormat::Native => SnapshotFormat::Native,
+        config::SnapshotFormat::Parquet => SnapshotFormat::Parquet,
+    };
+    Ok(SnapshotLocation {
+        url,
+        access_key_id: opts.snapshot.access_key_id.clone(),
+        secret_access_key: opts.snapshot.secret_access_key.clone(),
+        format,
+    })
+}
+
+/// Writer of the configured database
+fn snapshot_writer(opts: &Opts) -> ClickhouseWriter {
+    let (username, password) = opts.clickhouse.write_credentials();
+    ClickhouseWriter::new(
+        opts.clickhouse.url.clone(),
+        opts.clickhouse.db.clone(),
+        username,
+        password,
+    )
+    .with_table_prefix(opts.clickhouse.table_prefix.clone())
+}
+
+/// Export every table of the configured database to `SNAPSHOT_URL`.
+pub async fn run_snapshot_export(opts: &Opts) -> Result<()> {
+    let location = snapshot_location(opts)?;
+    info!(url = %location.url, format = location.format.name(), "📦 Exporting snapshot");
+    let manifest =
+        snapshot_writer(opts).export_snapshot(&location, opts.snapshot.chunk_rows).await?;
+    let rows: u64 = manifest.iter().map(|entry| entry.rows).sum();
+    info!(tables = manifest.len(), rows, "✅ Snapshot exported");
+    Ok(())
+}
+
+/// Import the snapshot at `SNAPSHOT_URL` into the configured database. Migrations must have
+/// been applied first.
+pub async fn run_snapshot_import(opts: &Opts) -> Result<()> {
+    let location = snapshot_location(opts)?;
+    info!(url = %location.url, format = location.format.name(), "📦 Importing snapshot");
+    let manifest = snapshot_writer(opts).import_snapshot(&location).await?;
+    let rows: u64 = manifest.iter().map(|entry| entry.rows).sum();
+    info!(tables = manifest.len(), rows, "✅ Snapshot imported");
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! TAIKO price snapshots
+//!
+//! Some prove costs and bonds are paid in TAIKO. To report values in TAIKO at the time they were
+//! incurred, the current TAIKO/ETH and TAIKO/USD prices are periodically written to the
+//! `taiko_prices` table next to the ETH snapshots in `eth_prices`.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use clickhouse::TaikoPriceInsertRow;
+use network::taiko_price::fetch_taiko_price;
+use tracing::{debug, error, info};
+
+/// TAIKO price snapshot methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic TAIKO price snapshot task
+    pub fn start_taiko_price_snapshot_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let url = self.taiko_price_url.clone();
+        let api_key = self.coingecko_api_key.clone();
+        let interval_secs = self.eth_price_snapshot_interval_secs;
+
+        info!(interval_secs, "Starting TAIKO price snapshot task");
+
+        let handle = tokio::spawn(async move {
+            let http = reqwest::Client::new();
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                let price = match fetch_taiko_price(&http, &url, api_key.as_deref()).await {
+                    Ok(price) => price,
+                    Err(e) => {
+                        error!(err = %e, "Failed to fetch TAIKO price");
+                        continue;
+                    }
+                };
+                let row = TaikoPriceInsertRow {
+                    ts: Utc::now().timestamp() as u64,
+                    eth: price.eth,
+                    usd: price.usd,
+                };
+                match writer.insert_taiko_price(&row).await {
+                    Ok(()) => {
+                        debug!(eth = price.eth, usd = price.usd, "Stored TAIKO price snapshot")
+                    }
+                    Err(e) => error!(err = %e, "Failed to store TAIKO price snapshot"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
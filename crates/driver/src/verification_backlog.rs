@@ -0,0 +1,111 @@
+//! Verification backlog snapshots
+//!
+//! Proposed batches are verified once they are proven and their cooldown passed, so a growing gap
+//! between the latest proposed and the latest verified batch means provers fall behind. This task
+//! periodically records both batch ids as read from the inbox in the `verification_backlog`
+//! table, flagging snapshots whose backlog exceeds the configured number of batches. The
+//! verification backlog monitor opens an incident while the latest snapshot is flagged.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use clickhouse::{ClickhouseWriter, VerificationBacklogRow};
+use extractor::{BatchHeads, Extractor};
+use eyre::Result;
+use tracing::{error, info, warn};
+
+/// Verification backlog methods for the Driver
+impl crate::driver::Driver {
+    /// Start the periodic verification backlog snapshot task
+    pub fn start_verification_backlog_task(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let writer = self.clickhouse_writer.as_ref()?.clone();
+        let extractor = self.extractor.clone();
+        let interval_secs = self.verification_backlog_interval_secs;
+        let threshold_batches = self.verification_backlog_threshold_batches;
+
+        info!(interval_secs, threshold_batches, "Starting verification backlog task");
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+
+                match snapshot_verification_backlog(&writer, &extractor, threshold_batches).await {
+                    Ok(row) if row.over_threshold == 1 => warn!(
+                        backlog = row.backlog,
+                        last_proposed_batch_id = row.last_proposed_batch_id,
+                        last_verified_batch_id = row.last_verified_batch_id,
+                        "Verification backlog above threshold"
+                    ),
+                    Ok(_) => {}
+                    Err(e) => error!(err = %e, "Verification backlog snapshot failed"),
+                }
+            }
+        });
+
+        Some(handle)
+    }
+}
+
+/// Record the verification backlog at the latest L1 block
+pub async fn snapshot_verification_backlog(
+    writer: &ClickhouseWriter,
+    extractor: &Extractor,
+    threshold_batches: u64,
+) -> Result<VerificationBacklogRow> {
+    let l1_block_number = extractor.get_l1_latest_block_number().await?;
+    let heads = extractor.get_batch_heads(l1_block_number).await?;
+
+    let row =
+        backlog_snapshot(Utc::now().timestamp() as u64, l1_block_number, heads, threshold_batches);
+    writer.insert_verification_backlog(&row).await?;
+    Ok(row)
+}
+
+/// Snapshot row of `heads`, flagged when more than `threshold_batches` proposed batches are not
+/// verified yet
+pub fn backlog_snapshot(
+    snapshot_ts: u64,
+    l1_block_number: u64,
+    heads: BatchHeads,
+    threshold_batches: u64,
+) -> VerificationBacklogRow {
+    let backlog = heads.last_proposed_batch_id.saturating_sub(heads.last_verified_batch_id);
+    VerificationBacklogRow {
+        snapshot_ts,
+        l1_block_number,
+        last_proposed_batch_id: heads.last_proposed_batch_id,
+        last_verified_batch_id: heads.last_verified_batch_id,
+        backlog,
+        over_threshold: u8::from(backlog > threshold_batches),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn heads(last_proposed_batch_id: u64, last_verified_batch_id: u64) -> BatchHeads {
+        BatchHeads { last_proposed_batch_id, last_verified_batch_id }
+    }
+
+    #[test]
+    fn flags_backlog_above_threshold() {
+        let row = backlog_snapshot(1_700_000_600, 200, heads(1_100, 1_000), 100);
+        assert_eq!(row.backlog, 100);
+        assert_eq!(row.over_threshold, 0);
+
+        let row = backlog_snapshot(1_700_000_600, 200, heads(1_101, 1_000), 100);
+        assert_eq!(row.backlog, 101);
+        assert_eq!(row.over_threshold, 1);
+    }
+
+    #[test]
+    fn fully_verified_head_has_no_backlog() {
+        let row = backlog_snapshot(1_700_000_600, 200, heads(1_000, 1_000), 0);
+        assert_eq!(row.backlog, 0);
+        assert_eq!(row.over_threshold, 0);
+    }
+}
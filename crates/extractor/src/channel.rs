@@ -0,0 +1,320 @@
+//! Bounded channels between the subscription tasks of the extractor and the driver
+//!
+//! Every event stream queues at most `capacity` events. When the driver falls behind, e.g.
+//! because `ClickHouse` is slow, a full channel either pauses the subscription task until the
+//! driver makes room or drops the oldest queued event, per the stream's [`OverflowPolicy`].
+
+#![allow(clippy::redundant_pub_crate)]
+
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Waker},
+};
+
+pub use config::OverflowPolicy;
+use runtime::logging::LogSampler;
+use tokio::sync::Notify;
+use tokio_stream::Stream;
+use tracing::warn;
+
+/// Default number of events a stream queues
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Share of the capacity above which a channel is considered under backpressure, in percent
+const BACKPRESSURE_PERCENT: usize = 80;
+
+/// Capacity and overflow policies of the event stream channels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelSettings {
+    /// Events each stream queues before its overflow policy applies
+    pub capacity: usize,
+    /// Overflow policy per stream name; streams not listed block
+    pub policies: HashMap<String, OverflowPolicy>,
+}
+
+impl Default for ChannelSettings {
+    fn default() -> Self {
+        Self { capacity: DEFAULT_CHANNEL_CAPACITY, policies: HashMap::new() }
+    }
+}
+
+impl ChannelSettings {
+    fn policy(&self, stream: &str) -> OverflowPolicy {
+        self.policies.get(stream).copied().unwrap_or_default()
+    }
+}
+
+/// Depth and overflow counters of an event stream channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelStats {
+    /// Name of the stream, e.g. `l2_headers`
+    pub stream: &'static str,
+    /// What the stream does when its channel is full
+    pub policy: OverflowPolicy,
+    /// Events the channel queues at most
+    pub capacity: usize,
+    /// Events currently queued
+    pub depth: usize,
+    /// Events dropped to make room for newer ones
+    pub dropped: u64,
+    /// Sends that waited for the driver to make room
+    pub blocked: u64,
+}
+
+impl ChannelStats {
+    /// Whether the channel is filled above the backpressure threshold.
+    pub const fn backpressured(&self) -> bool {
+        self.depth * 100 >= self.capacity * BACKPRESSURE_PERCENT
+    }
+}
+
+#[derive(Debug)]
+struct Counters {
+    policy: OverflowPolicy,
+    capacity: usize,
+    depth: AtomicUsize,
+    dropped: AtomicU64,
+    blocked: AtomicU64,
+}
+
+/// Counters of the channel of every stream opened so far
+#[derive(Debug, Default)]
+pub(crate) struct ChannelRegistry {
+    channels: Mutex<Vec<(&'static str, Arc<Counters>)>>,
+}
+
+impl ChannelRegistry {
+    /// Counters of every registered channel, sorted by stream name.
+    pub(crate) fn stats(&self) -> Vec<ChannelStats> {
+        let channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<_> = channels
+            .iter()
+            .map(|(stream, counters)| ChannelStats {
+                stream,
+                policy: counters.policy,
+                capacity: counters.capacity,
+                depth: counters.depth.load(Ordering::Relaxed),
+                dropped: counters.dropped.load(Ordering::Relaxed),
+                blocked: counters.blocked.load(Ordering::Relaxed),
+            })
+            .collect();
+        stats.sort_by_key(|stats| stats.stream);
+        stats
+    }
+
+    /// Track `counters` for `stream`, replacing the channel of an earlier subscription.
+    fn register(&self, stream: &'static str, counters: Arc<Counters>) {
+        let mut channels = self.channels.lock().unwrap_or_else(|e| e.into_inner());
+        channels.retain(|(name, _)| *name != stream);
+        channels.push((stream, counters));
+    }
+}
+
+struct Queue<T> {
+    items: VecDeque<T>,
+    waker: Option<Waker>,
+    sender_alive: bool,
+    receiver_alive: bool,
+    drop_log: LogSampler,
+}
+
+struct Shared<T> {
+    stream: &'static str,
+    queue: Mutex<Queue<T>>,
+    space: Notify,
+    counters: Arc<Counters>,
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Queue<T>> {
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The driver dropped the receiving end of a stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Closed;
+
+/// Sending end of an event stream channel
+pub(crate) struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Queue `item`, applying the overflow policy of the stream while the channel is full.
+    pub(crate) async fn send(&self, item: T) -> Result<(), Closed> {
+        let shared = &self.shared;
+        let counters = &shared.counters;
+        let mut waited = false;
+        loop {
+            let space = shared.space.notified();
+            {
+                let mut queue = shared.lock();
+                if !queue.receiver_alive {
+                    return Err(Closed);
+                }
+                let full = queue.items.len() >= counters.capacity;
+                if !full || counters.policy == OverflowPolicy::DropOldest {
+                    if full {
+                        queue.items.pop_front();
+                        counters.dropped.fetch_add(1, Ordering::Relaxed);
+                        if let Some(suppressed) = queue.drop_log.sample() {
+                            warn!(
+                                stream = shared.stream,
+                                capacity = counters.capacity,
+                                suppressed,
+                                "Event channel full, dropping the oldest event"
+                            );
+                        }
+                    }
+                    queue.items.push_back(item);
+                    counters.depth.store(queue.items.len(), Ordering::Relaxed);
+                    if let Some(waker) = queue.waker.take() {
+                        waker.wake();
+                    }
+                    return Ok(());
+                }
+            }
+            if !waited {
+                waited = true;
+                counters.blocked.fetch_add(1, Ordering::Relaxed);
+            }
+            space.await;
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut queue = self.shared.lock();
+        queue.sender_alive = false;
+        if let Some(waker) = queue.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Receiving end of an event stream channel, ending once the sender is dropped and the queue
+/// is drained
+pub(crate) struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let shared = &self.shared;
+        let mut queue = shared.lock();
+        if let Some(item) = queue.items.pop_front() {
+            shared.counters.depth.store(queue.items.len(), Ordering::Relaxed);
+            drop(queue);
+            shared.space.notify_one();
+            return Poll::Ready(Some(item));
+        }
+        if !queue.sender_alive {
+            return Poll::Ready(None);
+        }
+        queue.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().receiver_alive = false;
+        self.shared.space.notify_waiters();
+    }
+}
+
+/// Open the channel of `stream` with its capacity and policy from `settings`, tracking its
+/// counters in `registry`.
+pub(crate) fn channel<T>(
+    stream: &'static str,
+    settings: &ChannelSettings,
+    registry: &ChannelRegistry,
+) -> (Sender<T>, Receiver<T>) {
+    let counters = Arc::new(Counters {
+        policy: settings.policy(stream),
+        capacity: settings.capacity.max(1),
+        depth: AtomicUsize::new(0),
+        dropped: AtomicU64::new(0),
+        blocked: AtomicU64::new(0),
+    });
+    registry.register(stream, Arc::clone(&counters));
+    let shared = Arc::new(Shared {
+        stream,
+        queue: Mutex::new(Queue {
+            items: VecDeque::new(),
+            waker: None,
+            sender_alive: true,
+            receiver_alive: true,
+            drop_log: LogSampler::new(),
+        }),
+        space: Notify::new(),
+        counters,
+    });
+    (Sender { shared: Arc::clone(&shared) }, Receiver { shared })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio_stream::StreamExt;
+
+    use super::*;
+
+    fn settings(capacity: usize, policy: OverflowPolicy) -> ChannelSettings {
+        ChannelSettings { capacity, policies: HashMap::from([("test".to_owned(), policy)]) }
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_keeps_newest_events() {
+        let registry = ChannelRegistry::default();
+        let (tx, mut rx) = channel("test", &settings(2, OverflowPolicy::DropOldest), &registry);
+        for i in 0..5 {
+            tx.send(i).await.unwrap();
+        }
+        let stats = &registry.stats()[0];
+        assert_eq!((stats.depth, stats.dropped, stats.blocked), (2, 3, 0));
+        assert!(stats.backpressured());
+
+        drop(tx);
+        assert_eq!(rx.next().await, Some(3));
+        assert_eq!(rx.next().await, Some(4));
+        assert_eq!(rx.next().await, None);
+        assert_eq!(registry.stats()[0].depth, 0);
+    }
+
+    #[tokio::test]
+    async fn block_waits_for_room() {
+        let registry = ChannelRegistry::default();
+        let (tx, mut rx) = channel("test", &settings(1, OverflowPolicy::Block), &registry);
+        tx.send(1).await.unwrap();
+        let sender = tokio::spawn(async move { tx.send(2).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!sender.is_finished());
+        assert_eq!(registry.stats()[0].blocked, 1);
+
+        assert_eq!(rx.next().await, Some(1));
+        sender.await.unwrap().unwrap();
+        assert_eq!(rx.next().await, Some(2));
+        assert_eq!(rx.next().await, None);
+        assert_eq!(registry.stats()[0].dropped, 0);
+    }
+
+    #[tokio::test]
+    async fn send_fails_once_receiver_is_dropped() {
+        let registry = ChannelRegistry::default();
+        let (tx, rx) = channel("test", &settings(1, OverflowPolicy::Block), &registry);
+        tx.send(1).await.unwrap();
+        drop(rx);
+        assert_eq!(tx.send(2).await, Err(Closed));
+    }
+}
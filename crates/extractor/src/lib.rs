@@ -2,36 +2,65 @@
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
 use chainio::{
-    self, DefaultProvider,
+    self, BondEvent, DefaultProvider,
     ITaikoInbox::{BatchProposed, BatchesProved, BatchesVerified as InboxBatchesVerified},
+    ens::Ens,
+    erc20::Erc20,
+    multicall::{Multicall, PreconfSnapshot},
     taiko::{
-        preconf_whitelist::TaikoPreconfWhitelist,
+        anchor::decode_anchor_block_id,
+        forced_inclusion_store::ForcedInclusionStore,
+        preconf_whitelist::{
+            IPreconfWhitelist::OperatorRemoved, RemovalReason, TaikoPreconfWhitelist,
+        },
+        verifier::{ComposeVerifiers, decode_sub_proof_verifiers},
         wrapper::{ITaikoWrapper::ForcedInclusionProcessed, TaikoWrapper},
     },
 };
 
-use std::pin::Pin;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
 use alloy::{
-    primitives::{Address, B256, BlockNumber},
+    primitives::{Address, B256, BlockNumber, U256},
     providers::{Provider, ProviderBuilder},
+    rpc::types::Filter,
+    sol_types::SolEvent,
 };
 use alloy_consensus::BlockHeader;
 use alloy_rpc_client::ClientBuilder;
 use chainio::TaikoInbox;
+use config::ForkAddresses;
 use derive_more::Debug;
 use eyre::{Context, Result};
-use network::retries::{DEFAULT_RETRY_LAYER, RetryWsConnect};
+use network::{
+    endpoints::{EndpointPool, EndpointStatus},
+    retries::{DEFAULT_RETRY_LAYER, EndpointScoringLayer, RetryWsConnect},
+};
 use primitives::{
-    block_stats::compute_block_stats,
+    beacon,
+    block_stats::{BlockStats, compute_block_stats},
     headers::{L1Header, L1HeaderStream, L2Header, L2HeaderStream},
+    ingest::Received,
+    proof::ProofType,
 };
+use runtime::logging::LogSampler;
 use std::time::Duration;
-use tokio::{sync::mpsc, time::sleep};
-use tokio_stream::{Stream, StreamExt, wrappers::UnboundedReceiverStream};
+use tokio::time::sleep;
+use tokio_stream::{Stream, StreamExt};
 use tracing::{error, info, warn};
 use url::Url;
 
+mod channel;
+use channel::ChannelRegistry;
+pub use channel::{ChannelSettings, ChannelStats, DEFAULT_CHANNEL_CAPACITY, OverflowPolicy};
+
 /// Extractor client
 #[derive(Debug, Clone)]
 pub struct Extractor {
@@ -39,93 +68,419 @@ pub struct Extractor {
     l1_provider: DefaultProvider,
     #[debug(skip)]
     l2_provider: DefaultProvider,
+    /// Primary and fallback L1 endpoints
+    l1_endpoints: Arc<EndpointPool>,
+    /// Primary and fallback L2 endpoints
+    l2_endpoints: Arc<EndpointPool>,
+    /// Contracts of every fork, sorted by activation block
+    forks: Arc<Vec<ForkContracts>>,
+    /// Latest L1 block seen, selecting the fork used for contract calls
+    l1_head: Arc<AtomicU64>,
+    anchor_address: Address,
+    /// Verifiers whose proofs are guardian proofs
+    guardian_verifiers: Arc<Vec<Address>>,
+    /// Sub-proof verifiers of every compose verifier seen in a `BatchesProved` event
+    #[debug(skip)]
+    compose_verifiers: Arc<Mutex<HashMap<Address, ComposeVerifiers>>>,
+    /// Time without a new L1 head after which the L1 connection is re-established
+    l1_stall_timeout: Option<Duration>,
+    /// Time without a new L2 head after which the L2 connection is re-established
+    l2_stall_timeout: Option<Duration>,
+    stalls: Arc<StallCounters>,
+    /// Capacity and overflow policies of the event stream channels
+    channel_settings: Arc<ChannelSettings>,
+    /// Counters of the event stream channels
+    channels: Arc<ChannelRegistry>,
+}
+
+/// Taiko contracts of a fork, used from its activation L1 block on
+#[derive(Debug, Clone)]
+struct ForkContracts {
+    name: String,
+    activation_block: u64,
     preconf_whitelist: TaikoPreconfWhitelist,
     taiko_inbox: TaikoInbox,
     taiko_wrapper: TaikoWrapper,
-    anchor_address: Address,
+}
+
+impl ForkContracts {
+    fn new(fork: &ForkAddresses, provider: &DefaultProvider) -> Self {
+        Self {
+            name: fork.name.clone(),
+            activation_block: fork.activation_block,
+            preconf_whitelist: TaikoPreconfWhitelist::new_readonly(
+                fork.preconf_whitelist_address,
+                provider.clone(),
+            ),
+            taiko_inbox: TaikoInbox::new_readonly(fork.inbox_address, provider.clone()),
+            taiko_wrapper: TaikoWrapper::new_readonly(fork.taiko_wrapper_address, provider.clone()),
+        }
+    }
+
+    fn inbox_address(&self) -> Address {
+        *self.taiko_inbox.address()
+    }
+
+    fn taiko_wrapper_address(&self) -> Address {
+        *self.taiko_wrapper.address()
+    }
+
+    const fn preconf_whitelist_address(&self) -> Address {
+        self.preconf_whitelist.address()
+    }
+}
+
+/// Fork active at `l1_block`, falling back to the earliest one for blocks before any activation.
+fn active_fork(forks: &[ForkContracts], l1_block: u64) -> &ForkContracts {
+    forks.iter().rev().find(|fork| fork.activation_block <= l1_block).unwrap_or(&forks[0])
+}
+
+/// Whether `log` was emitted by the contract `contract` selects from the fork active at the
+/// log's block. This drops logs of a fork's contracts outside of its activation range.
+fn emitted_by_active_fork(
+    forks: &[ForkContracts],
+    log: &alloy_rpc_types_eth::Log,
+    contract: fn(&ForkContracts) -> Address,
+) -> bool {
+    log.address() == contract(active_fork(forks, log.block_number.unwrap_or(0)))
+}
+
+/// Number of stalled header streams detected since startup
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamStalls {
+    /// Stalls of the L1 header stream
+    pub l1: u64,
+    /// Stalls of the L2 header stream
+    pub l2: u64,
+}
+
+#[derive(Debug, Default)]
+struct StallCounters {
+    l1: AtomicU64,
+    l2: AtomicU64,
+}
+
+/// Health of the configured RPC endpoints of both chains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcEndpoints {
+    /// L1 endpoints, primary first
+    pub l1: Vec<EndpointStatus>,
+    /// L2 endpoints, primary first
+    pub l2: Vec<EndpointStatus>,
+}
+
+/// Outcome of waiting for the next item of a subscription
+#[derive(Debug)]
+enum StreamEvent<T> {
+    Item(T),
+    Ended,
+    Stalled,
+}
+
+/// Wait for the next item of `stream`, giving up after `timeout` if one is set.
+async fn next_or_stall<S>(stream: &mut S, timeout: Option<Duration>) -> StreamEvent<S::Item>
+where
+    S: Stream + Unpin,
+{
+    let next = match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, stream.next()).await {
+            Ok(next) => next,
+            Err(_) => return StreamEvent::Stalled,
+        },
+        None => stream.next().await,
+    };
+    next.map_or(StreamEvent::Ended, StreamEvent::Item)
+}
+
+/// Open a WebSocket provider to the first healthy endpoint of `endpoints` that retries dropped
+/// connections, failing over to the fallback endpoints.
+async fn connect_ws(endpoints: &Arc<EndpointPool>) -> Result<DefaultProvider> {
+    let label = endpoints.label();
+    info!(endpoints = endpoints.len(), "Connecting to {} WebSocket provider...", label);
+    let ws = RetryWsConnect::from_pool(Arc::clone(endpoints));
+    let client = ClientBuilder::default()
+        .layer(DEFAULT_RETRY_LAYER)
+        .layer(EndpointScoringLayer::new(Arc::clone(endpoints)))
+        .pubsub(ws)
+        .await
+        .wrap_err_with(|| format!("Failed to establish {label} WebSocket connection"))?;
+    info!(url = %endpoints.active_url(), "Connected to {} WebSocket provider", label);
+    Ok(ProviderBuilder::new().connect_client(client))
+}
+
+/// Outcome of probing a single RPC endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointProbe {
+    /// Latest block number reported by the endpoint
+    pub block_number: u64,
+    /// Probed contract addresses, with whether code is deployed at each
+    pub contracts: Vec<(Address, bool)>,
+}
+
+/// State of the forced inclusion queue at an L1 block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForcedInclusionQueue {
+    /// Number of queued forced inclusions
+    pub pending: u64,
+    /// L1 block the oldest queued forced inclusion was stored in
+    pub oldest_created_in: Option<u64>,
+    /// Batch id by which the oldest queued forced inclusion must be included
+    pub deadline_batch_id: Option<u64>,
+    /// Id of the next batch to be proposed
+    pub next_batch_id: u64,
+}
+
+/// Latest proposed and verified batches at an L1 block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchHeads {
+    /// Id of the latest proposed batch
+    pub last_proposed_batch_id: u64,
+    /// Id of the latest verified batch
+    pub last_verified_batch_id: u64,
+}
+
+/// Proposal transaction seen in the L1 mempool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingProposal {
+    /// Transaction hash
+    pub tx_hash: B256,
+    /// Sender of the transaction
+    pub proposer: Address,
+    /// Nonce of the sender
+    pub nonce: u64,
+    /// Contract called by the transaction
+    pub to: Address,
+    /// Maximum fee per gas the sender is willing to pay
+    pub max_fee_per_gas: u128,
+    /// Maximum priority fee per gas, `None` for legacy transactions
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+/// Connect once to the endpoint at `url`, without retries or fallbacks, and report its latest
+/// block number and whether `contracts` have code deployed. Used to validate the configuration
+/// before starting.
+pub async fn probe_endpoint(url: &Url, contracts: &[Address]) -> Result<EndpointProbe> {
+    let provider = ProviderBuilder::new()
+        .connect(url.as_str())
+        .await
+        .wrap_err("Failed to connect to RPC endpoint")?;
+    let block_number = provider.get_block_number().await?;
+    let mut deployed = Vec::with_capacity(contracts.len());
+    for &address in contracts {
+        let code = provider.get_code_at(address).await?;
+        deployed.push((address, !code.is_empty()));
+    }
+    Ok(EndpointProbe { block_number, contracts: deployed })
+}
+
+/// Validate that every URL of `urls` is a WebSocket endpoint.
+fn validate_ws_urls(urls: &[Url], label: &str) -> Result<()> {
+    for url in urls {
+        let scheme = url.scheme();
+        if scheme != "ws" && scheme != "wss" {
+            return Err(eyre::eyre!(
+                "Invalid URL scheme for {} RPC: expected 'ws://' or 'wss://' but got '{}://'. Please provide a WebSocket endpoint.",
+                label,
+                scheme
+            ));
+        }
+    }
+    Ok(())
 }
 
 /// Stream of batch proposed events with their L1 transaction hash
 pub type BatchProposedStream =
-    Pin<Box<dyn Stream<Item = (BatchProposed, alloy::primitives::B256)> + Send>>;
+    Pin<Box<dyn Stream<Item = Received<(BatchProposed, alloy::primitives::B256)>> + Send>>;
 /// Stream of batches proved events
 pub type BatchesProvedStream = Pin<
     Box<
-        dyn Stream<Item = (chainio::ITaikoInbox::BatchesProved, u64, alloy::primitives::B256)>
-            + Send,
+        dyn Stream<
+                Item = Received<(
+                    chainio::ITaikoInbox::BatchesProved,
+                    u64,
+                    alloy::primitives::B256,
+                )>,
+            > + Send,
     >,
 >;
 /// Stream of batches verified events
-pub type BatchesVerifiedStream =
-    Pin<Box<dyn Stream<Item = (chainio::BatchesVerified, u64, alloy::primitives::B256)> + Send>>;
-/// Stream of forced inclusion processed events
-pub type ForcedInclusionStream = Pin<Box<dyn Stream<Item = ForcedInclusionProcessed> + Send>>;
+pub type BatchesVerifiedStream = Pin<
+    Box<
+        dyn Stream<Item = Received<(chainio::BatchesVerified, u64, alloy::primitives::B256)>>
+            + Send,
+    >,
+>;
+/// Stream of forced inclusion processed events with their L1 block number and transaction hash
+pub type ForcedInclusionStream =
+    Pin<Box<dyn Stream<Item = Received<(ForcedInclusionProcessed, u64, B256)>> + Send>>;
+/// Stream of whitelist operator removals with their L1 block number and transaction hash
+pub type OperatorRemovedStream =
+    Pin<Box<dyn Stream<Item = Received<(OperatorRemoved, u64, B256)>> + Send>>;
+/// Stream of hashes of transactions entering the L2 mempool
+pub type PendingTxStream = Pin<Box<dyn Stream<Item = Received<B256>> + Send>>;
+/// Stream of proposal transactions entering the L1 mempool
+pub type PendingProposalStream = Pin<Box<dyn Stream<Item = Received<PendingProposal>> + Send>>;
 
 impl Extractor {
-    /// Create a new extractor
+    /// Create a new extractor. The first URL of each chain is its primary endpoint, the rest are
+    /// fallbacks used while the primary keeps failing.
     pub async fn new(
-        l1_rpc_url: Url,
-        l2_rpc_url: Url,
+        l1_rpc_urls: Vec<Url>,
+        l2_rpc_urls: Vec<Url>,
         inbox_address: Address,
         preconf_whitelist_address: Address,
         taiko_wrapper_address: Address,
         anchor_address: Address,
     ) -> Result<Self> {
-        // Validate URL schemes
-        let l1_scheme = l1_rpc_url.scheme();
-        if l1_scheme != "ws" && l1_scheme != "wss" {
-            return Err(eyre::eyre!(
-                "Invalid URL scheme for L1 RPC: expected 'ws://' or 'wss://' but got '{}://'. Please provide a WebSocket endpoint.",
-                l1_scheme
-            ));
-        }
-
-        let l2_scheme = l2_rpc_url.scheme();
-        if l2_scheme != "ws" && l2_scheme != "wss" {
-            return Err(eyre::eyre!(
-                "Invalid URL scheme for L2 RPC: expected 'ws://' or 'wss://' but got '{}://'. Please provide a WebSocket endpoint.",
-                l2_scheme
-            ));
-        }
-
-        info!(url = %l1_rpc_url, "Connecting to L1 WebSocket provider...");
-        let l1_ws = RetryWsConnect::from_url(l1_rpc_url.clone()).with_label("L1");
-        let l1_client =
-            ClientBuilder::default().layer(DEFAULT_RETRY_LAYER).pubsub(l1_ws).await.wrap_err_with(
-                || format!("Failed to establish L1 WebSocket connection to {}", l1_rpc_url),
-            )?;
-        let l1_provider = ProviderBuilder::new().connect_client(l1_client);
-
-        info!(url = %l2_rpc_url, "Connecting to L2 WebSocket provider...");
-        let l2_ws = RetryWsConnect::from_url(l2_rpc_url.clone()).with_label("L2");
-        let l2_client =
-            ClientBuilder::default().layer(DEFAULT_RETRY_LAYER).pubsub(l2_ws).await.wrap_err_with(
-                || format!("Failed to establish L2 WebSocket connection to {}", l2_rpc_url),
-            )?;
-        let l2_provider = ProviderBuilder::new().connect_client(l2_client);
-
-        let taiko_inbox = TaikoInbox::new_readonly(inbox_address, l1_provider.clone());
-        let preconf_whitelist =
-            TaikoPreconfWhitelist::new_readonly(preconf_whitelist_address, l1_provider.clone());
-        let taiko_wrapper = TaikoWrapper::new_readonly(taiko_wrapper_address, l1_provider.clone());
+        validate_ws_urls(&l1_rpc_urls, "L1")?;
+        validate_ws_urls(&l2_rpc_urls, "L2")?;
+        let l1_endpoints = Arc::new(EndpointPool::new("L1", l1_rpc_urls)?);
+        let l2_endpoints = Arc::new(EndpointPool::new("L2", l2_rpc_urls)?);
+
+        let l1_provider = connect_ws(&l1_endpoints).await?;
+        let l2_provider = connect_ws(&l2_endpoints).await?;
+
+        let fork = ForkContracts::new(
+            &ForkAddresses {
+                name: String::new(),
+                activation_block: 0,
+                inbox_address,
+                taiko_wrapper_address,
+                preconf_whitelist_address,
+            },
+            &l1_provider,
+        );
+        let l1_head = l1_provider.get_block_number().await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to fetch L1 head, assuming the first fork is active");
+            0
+        });
 
         Ok(Self {
             l1_provider,
             l2_provider,
-            preconf_whitelist,
-            taiko_inbox,
-            taiko_wrapper,
+            l1_endpoints,
+            l2_endpoints,
+            forks: Arc::new(vec![fork]),
+            l1_head: Arc::new(AtomicU64::new(l1_head)),
             anchor_address,
+            guardian_verifiers: Arc::default(),
+            compose_verifiers: Arc::default(),
+            l1_stall_timeout: None,
+            l2_stall_timeout: None,
+            stalls: Arc::default(),
+            channel_settings: Arc::default(),
+            channels: Arc::default(),
         })
     }
 
+    /// Reconnect to an RPC when its header stream yields nothing for longer than the given
+    /// timeout, catching connections that silently stop delivering heads without closing.
+    /// `None` disables the watchdog for that layer.
+    pub const fn with_stall_timeouts(
+        mut self,
+        l1_stall_timeout: Option<Duration>,
+        l2_stall_timeout: Option<Duration>,
+    ) -> Self {
+        self.l1_stall_timeout = l1_stall_timeout;
+        self.l2_stall_timeout = l2_stall_timeout;
+        self
+    }
+
+    /// Bound the channel of every event stream opened from now on to `settings.capacity`
+    /// events, applying the stream's overflow policy when the driver falls behind.
+    pub fn with_channel_settings(mut self, settings: ChannelSettings) -> Self {
+        self.channel_settings = Arc::new(settings);
+        self
+    }
+
+    /// Record the proofs checked by any of the given verifiers as guardian proofs.
+    pub fn with_guardian_verifiers(mut self, verifiers: Vec<Address>) -> Self {
+        self.guardian_verifiers = Arc::new(verifiers);
+        self
+    }
+
+    /// Use a different set of contract addresses from each fork's activation L1 block on,
+    /// replacing the addresses given to [`Self::new`]. Event streams and log queries follow the
+    /// schedule, and contract calls go to the fork active at the latest L1 head.
+    pub fn with_forks(mut self, forks: &[ForkAddresses]) -> Self {
+        if forks.is_empty() {
+            return self;
+        }
+        let mut contracts: Vec<ForkContracts> =
+            forks.iter().map(|fork| ForkContracts::new(fork, &self.l1_provider)).collect();
+        contracts.sort_by_key(|fork| fork.activation_block);
+        self.forks = Arc::new(contracts);
+        self
+    }
+
+    /// `(activation_l1_block, fork_name)` pairs of the configured forks.
+    pub fn fork_schedule(&self) -> Vec<(u64, String)> {
+        self.forks.iter().map(|fork| (fork.activation_block, fork.name.clone())).collect()
+    }
+
+    /// Name of the fork active at the given L1 block.
+    pub fn fork_name_at(&self, l1_block: u64) -> &str {
+        &active_fork(&self.forks, l1_block).name
+    }
+
+    /// Address of the inbox active at the given L1 block.
+    pub fn inbox_address_at(&self, l1_block: u64) -> Address {
+        active_fork(&self.forks, l1_block).inbox_address()
+    }
+
+    /// Address of the wrapper active at the given L1 block.
+    pub fn taiko_wrapper_address_at(&self, l1_block: u64) -> Address {
+        active_fork(&self.forks, l1_block).taiko_wrapper_address()
+    }
+
+    /// Address of the inbox active at the latest L1 head.
+    pub fn current_inbox_address(&self) -> Address {
+        self.current_fork().inbox_address()
+    }
+
+    /// Fork active at the latest L1 head
+    fn current_fork(&self) -> &ForkContracts {
+        active_fork(&self.forks, self.l1_head.load(Ordering::Relaxed))
+    }
+
+    /// Widen `filter` to the contract `contract` selects from every fork
+    fn all_forks_filter(&self, filter: Filter, contract: fn(&ForkContracts) -> Address) -> Filter {
+        filter.address(self.forks.iter().map(contract).collect::<Vec<_>>())
+    }
+
+    /// Number of stalled header streams detected since startup.
+    pub fn stream_stalls(&self) -> StreamStalls {
+        StreamStalls {
+            l1: self.stalls.l1.load(Ordering::Relaxed),
+            l2: self.stalls.l2.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Depth and overflow counters of the channel of every event stream opened so far.
+    pub fn stream_channels(&self) -> Vec<ChannelStats> {
+        self.channels.stats()
+    }
+
+    /// Health of the configured L1 and L2 RPC endpoints, including which one is active.
+    pub fn rpc_endpoints(&self) -> RpcEndpoints {
+        RpcEndpoints { l1: self.l1_endpoints.status(), l2: self.l2_endpoints.status() }
+    }
+
     /// Get a stream of L1 headers. This stream will attempt to automatically
-    /// resubscribe and continue yielding headers in case of disconnections.
+    /// resubscribe and continue yielding headers in case of disconnections, and reconnects when
+    /// no header arrives within the L1 stall timeout. Stalls count against the active endpoint,
+    /// the stream leaves an endpoint quarantined for failing RPC calls, and it moves back to the
+    /// primary endpoint once it is healthy again.
     pub async fn get_l1_header_stream(&self) -> Result<L1HeaderStream> {
-        let (tx, rx) = mpsc::unbounded_channel();
-        let provider = self.l1_provider.clone();
+        let (tx, rx) = channel::channel("l1_headers", &self.channel_settings, &self.channels);
+        let mut provider = self.l1_provider.clone();
+        let endpoints = Arc::clone(&self.l1_endpoints);
+        let stall_timeout = self.l1_stall_timeout;
+        let stalls = Arc::clone(&self.stalls);
+        let l1_head = Arc::clone(&self.l1_head);
 
         tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
             loop {
                 info!("Attempting to subscribe to L1 block headers...");
                 let sub_result = provider.subscribe_blocks().await;
@@ -136,22 +491,45 @@ impl Extractor {
                         sub.into_stream()
                     }
                     Err(e) => {
-                        error!(error = %e, "Failed to subscribe to L1 blocks, retrying in 5s");
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to L1 blocks, retrying in 5s");
+                        }
                         sleep(Duration::from_secs(5)).await;
                         continue;
                     }
                 };
 
-                while let Some(block_data) = block_stream.next().await {
+                loop {
+                    let block_data = match next_or_stall(&mut block_stream, stall_timeout).await {
+                        StreamEvent::Item(block_data) => block_data,
+                        StreamEvent::Ended => {
+                            endpoints.record_failure(endpoints.active());
+                            if let Some(suppressed) = resubscribe_log.sample() {
+                                warn!(
+                                    suppressed,
+                                    "L1 block stream ended. Attempting to resubscribe..."
+                                );
+                            }
+                            break;
+                        }
+                        StreamEvent::Stalled => {
+                            let total = stalls.l1.fetch_add(1, Ordering::Relaxed) + 1;
+                            warn!(
+                                timeout_secs = stall_timeout.unwrap_or_default().as_secs(),
+                                stalls = total,
+                                "L1 block stream stalled. Reconnecting..."
+                            );
+                            endpoints.record_failure(endpoints.active());
+                            match connect_ws(&endpoints).await {
+                                Ok(reconnected) => provider = reconnected,
+                                Err(e) => error!(error = %e, "Failed to reconnect to L1"),
+                            }
+                            break;
+                        }
+                    };
+
                     // Calculate slot from timestamp using Ethereum mainnet genesis and slot time
-                    // Mainnet genesis timestamp: 1606824023 (December 1, 2020)
-                    // Slot time: 12 seconds
-                    const GENESIS_TIMESTAMP: u64 = 1606824023;
-                    const SLOT_DURATION: u64 = 12;
-
-                    let slot = if block_data.timestamp >= GENESIS_TIMESTAMP {
-                        (block_data.timestamp - GENESIS_TIMESTAMP) / SLOT_DURATION
-                    } else {
+                    let slot = beacon::slot_at(block_data.timestamp).unwrap_or_else(|| {
                         // Fallback to block number for pre-merge blocks or edge cases
                         warn!(
                             block_number = block_data.number,
@@ -159,7 +537,7 @@ impl Extractor {
                             "Block timestamp is before Ethereum 2.0 genesis, using block number as slot"
                         );
                         block_data.number
-                    };
+                    });
 
                     let header = L1Header {
                         number: block_data.number,
@@ -167,26 +545,50 @@ impl Extractor {
                         slot,
                         timestamp: block_data.timestamp,
                     };
-                    if tx.send(header).is_err() {
+                    l1_head.store(header.number, Ordering::Relaxed);
+                    if tx.send(Received::now(header)).await.is_err() {
                         error!("L1 header receiver dropped. Stopping L1 header task.");
                         return; // Exit task if receiver is gone
                     }
+
+                    if endpoints.should_leave_quarantined() {
+                        warn!(url = %endpoints.active_url(), "L1 RPC endpoint quarantined. Reconnecting...");
+                        match connect_ws(&endpoints).await {
+                            Ok(reconnected) => provider = reconnected,
+                            Err(e) => error!(error = %e, "Failed to reconnect to L1"),
+                        }
+                        break;
+                    }
+                    if endpoints.should_fail_back() {
+                        info!(url = %endpoints.url(0), "L1 primary RPC endpoint recovered. Reconnecting...");
+                        match connect_ws(&endpoints).await {
+                            Ok(reconnected) => provider = reconnected,
+                            Err(e) => error!(error = %e, "Failed to reconnect to L1"),
+                        }
+                        break;
+                    }
                 }
-                warn!("L1 block stream ended. Attempting to resubscribe...");
                 // Outer loop will retry subscription.
             }
         });
 
-        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+        Ok(Box::pin(rx))
     }
 
     /// Get a stream of L2 headers. This stream will attempt to automatically
-    /// resubscribe and continue yielding headers in case of disconnections.
+    /// resubscribe and continue yielding headers in case of disconnections, and reconnects when
+    /// no header arrives within the L2 stall timeout. Stalls count against the active endpoint,
+    /// the stream leaves an endpoint quarantined for failing RPC calls, and it moves back to the
+    /// primary endpoint once it is healthy again.
     pub async fn get_l2_header_stream(&self) -> Result<L2HeaderStream> {
-        let (tx, rx) = mpsc::unbounded_channel();
-        let provider = self.l2_provider.clone();
+        let (tx, rx) = channel::channel("l2_headers", &self.channel_settings, &self.channels);
+        let mut provider = self.l2_provider.clone();
+        let endpoints = Arc::clone(&self.l2_endpoints);
+        let stall_timeout = self.l2_stall_timeout;
+        let stalls = Arc::clone(&self.stalls);
 
         tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
             loop {
                 info!("Attempting to subscribe to L2 block headers...");
                 let sub_result = provider.subscribe_blocks().await;
@@ -197,46 +599,217 @@ impl Extractor {
                         sub.into_stream()
                     }
                     Err(e) => {
-                        error!(error = %e, "Failed to subscribe to L2 blocks, retrying in 5s");
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to L2 blocks, retrying in 5s");
+                        }
                         sleep(Duration::from_secs(5)).await;
                         continue;
                     }
                 };
 
-                while let Some(block_data) = block_stream.next().await {
+                loop {
+                    let block_data = match next_or_stall(&mut block_stream, stall_timeout).await {
+                        StreamEvent::Item(block_data) => block_data,
+                        StreamEvent::Ended => {
+                            endpoints.record_failure(endpoints.active());
+                            if let Some(suppressed) = resubscribe_log.sample() {
+                                warn!(
+                                    suppressed,
+                                    "L2 block stream ended. Attempting to resubscribe..."
+                                );
+                            }
+                            break;
+                        }
+                        StreamEvent::Stalled => {
+                            let total = stalls.l2.fetch_add(1, Ordering::Relaxed) + 1;
+                            warn!(
+                                timeout_secs = stall_timeout.unwrap_or_default().as_secs(),
+                                stalls = total,
+                                "L2 block stream stalled. Reconnecting..."
+                            );
+                            endpoints.record_failure(endpoints.active());
+                            match connect_ws(&endpoints).await {
+                                Ok(reconnected) => provider = reconnected,
+                                Err(e) => error!(error = %e, "Failed to reconnect to L2"),
+                            }
+                            break;
+                        }
+                    };
+
                     let header = L2Header {
                         number: block_data.number,
                         hash: block_data.hash,
                         parent_hash: block_data.parent_hash,
                         timestamp: block_data.timestamp,
                         gas_used: block_data.gas_used,
+                        gas_limit: block_data.gas_limit,
                         beneficiary: block_data.beneficiary,
                         base_fee_per_gas: block_data.base_fee_per_gas().unwrap_or(0),
+                        extra_data: block_data.extra_data.clone(),
                     };
-                    if tx.send(header).is_err() {
+                    if tx.send(Received::now(header)).await.is_err() {
                         error!("L2 header receiver dropped. Stopping L2 header task.");
                         return; // Exit task if receiver is gone
                     }
+
+                    if endpoints.should_leave_quarantined() {
+                        warn!(url = %endpoints.active_url(), "L2 RPC endpoint quarantined. Reconnecting...");
+                        match connect_ws(&endpoints).await {
+                            Ok(reconnected) => provider = reconnected,
+                            Err(e) => error!(error = %e, "Failed to reconnect to L2"),
+                        }
+                        break;
+                    }
+                    if endpoints.should_fail_back() {
+                        info!(url = %endpoints.url(0), "L2 primary RPC endpoint recovered. Reconnecting...");
+                        match connect_ws(&endpoints).await {
+                            Ok(reconnected) => provider = reconnected,
+                            Err(e) => error!(error = %e, "Failed to reconnect to L2"),
+                        }
+                        break;
+                    }
                 }
-                warn!("L2 block stream ended. Attempting to resubscribe...");
             }
         });
 
-        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+        Ok(Box::pin(rx))
+    }
+
+    /// Get a stream of the hashes of transactions entering the L2 node's mempool. This stream will
+    /// attempt to automatically resubscribe and continue yielding hashes in case of disconnections.
+    pub async fn get_pending_tx_stream(&self) -> Result<PendingTxStream> {
+        let (tx, rx) = channel::channel("pending_txs", &self.channel_settings, &self.channels);
+        let provider = self.l2_provider.clone();
+
+        tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
+            loop {
+                info!("Attempting to subscribe to L2 pending transactions...");
+                let mut hash_stream = match provider.subscribe_pending_transactions().await {
+                    Ok(sub) => {
+                        info!("Successfully subscribed to L2 pending transactions.");
+                        sub.into_stream()
+                    }
+                    Err(e) => {
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to L2 pending transactions, retrying in 5s");
+                        }
+                        sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+
+                while let Some(hash) = hash_stream.next().await {
+                    if tx.send(Received::now(hash)).await.is_err() {
+                        error!(
+                            "Pending transaction receiver dropped. Stopping pending transaction task."
+                        );
+                        return;
+                    }
+                }
+                if let Some(suppressed) = resubscribe_log.sample() {
+                    warn!(
+                        suppressed,
+                        "L2 pending transaction stream ended. Attempting to resubscribe..."
+                    );
+                }
+                sleep(Duration::from_secs(1)).await;
+            }
+        });
+
+        Ok(Box::pin(rx))
+    }
+
+    /// Get a stream of the transactions entering the L1 node's mempool that call the inbox or
+    /// the wrapper of any fork, or one of `extra_targets` such as a preconfirmation router. This
+    /// stream will attempt to automatically resubscribe and continue yielding transactions in
+    /// case of disconnections.
+    pub async fn get_pending_proposal_stream(
+        &self,
+        extra_targets: &[Address],
+    ) -> Result<PendingProposalStream> {
+        use alloy_consensus::Transaction as _;
+        use alloy_network_primitives::TransactionResponse as _;
+
+        let (tx, rx) =
+            channel::channel("pending_proposals", &self.channel_settings, &self.channels);
+        let provider = self.l1_provider.clone();
+        let mut targets: Vec<Address> = self
+            .forks
+            .iter()
+            .flat_map(|fork| [fork.inbox_address(), fork.taiko_wrapper_address()])
+            .chain(extra_targets.iter().copied())
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+
+        tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
+            loop {
+                info!("Attempting to subscribe to L1 pending transactions...");
+                let mut tx_stream = match provider.subscribe_full_pending_transactions().await {
+                    Ok(sub) => {
+                        info!("Successfully subscribed to L1 pending transactions.");
+                        sub.into_stream()
+                    }
+                    Err(e) => {
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to L1 pending transactions, retrying in 5s");
+                        }
+                        sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+
+                while let Some(pending) = tx_stream.next().await {
+                    let Some(to) = pending.to().filter(|to| targets.binary_search(to).is_ok())
+                    else {
+                        continue;
+                    };
+                    let proposal = PendingProposal {
+                        tx_hash: pending.tx_hash(),
+                        proposer: pending.from(),
+                        nonce: pending.nonce(),
+                        to,
+                        max_fee_per_gas: alloy_consensus::Transaction::max_fee_per_gas(&pending),
+                        max_priority_fee_per_gas: pending.max_priority_fee_per_gas(),
+                    };
+                    if tx.send(Received::now(proposal)).await.is_err() {
+                        error!(
+                            "Pending proposal receiver dropped. Stopping pending proposal task."
+                        );
+                        return;
+                    }
+                }
+                if let Some(suppressed) = resubscribe_log.sample() {
+                    warn!(
+                        suppressed,
+                        "L1 pending transaction stream ended. Attempting to resubscribe..."
+                    );
+                }
+                sleep(Duration::from_secs(1)).await;
+            }
+        });
+
+        Ok(Box::pin(rx))
     }
 
     /// Subscribes to the `TaikoInbox` `BatchProposed` event and returns a stream of decoded events
     /// along with the L1 transaction hash. This stream will attempt to automatically resubscribe
     /// and continue yielding events.
     pub async fn get_batch_proposed_stream(&self) -> Result<BatchProposedStream> {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = channel::channel("batch_proposed", &self.channel_settings, &self.channels);
         let provider = self.l1_provider.clone();
-        let taiko_inbox = self.taiko_inbox.clone(); // Clone for use in the spawned task
+        let forks = Arc::clone(&self.forks);
+        let filter = self.all_forks_filter(
+            self.current_fork().taiko_inbox.batch_proposed_filter(),
+            ForkContracts::inbox_address,
+        );
 
         tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
             loop {
                 info!("Attempting to subscribe to TaikoInbox BatchProposed events...");
-                let filter = taiko_inbox.batch_proposed_filter();
                 let sub_result = provider.subscribe_logs(&filter).await;
 
                 let mut log_stream = match sub_result {
@@ -245,7 +818,9 @@ impl Extractor {
                         sub.into_stream()
                     }
                     Err(e) => {
-                        error!(error = %e, "Failed to subscribe to BatchProposed logs, retrying in 5s");
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to BatchProposed logs, retrying in 5s");
+                        }
                         sleep(Duration::from_secs(5)).await;
                         continue;
                     }
@@ -257,11 +832,18 @@ impl Extractor {
                         info!("Skipping removed BatchProposed log due to L1 reorg");
                         continue;
                     }
+                    if !emitted_by_active_fork(&forks, &log, ForkContracts::inbox_address) {
+                        continue;
+                    }
                     match log.log_decode::<BatchProposed>() {
                         Ok(decoded) => {
                             // Include the transaction hash from the log
                             let tx_hash = log.transaction_hash.unwrap_or_default();
-                            if tx.send((decoded.data().clone(), tx_hash)).is_err() {
+                            if tx
+                                .send(Received::now((decoded.data().clone(), tx_hash)))
+                                .await
+                                .is_err()
+                            {
                                 error!(
                                     "BatchProposed receiver dropped. Stopping BatchProposed event task."
                                 );
@@ -275,25 +857,34 @@ impl Extractor {
                         }
                     }
                 }
-                warn!("BatchProposed log stream ended. Attempting to resubscribe...");
+                if let Some(suppressed) = resubscribe_log.sample() {
+                    warn!(
+                        suppressed,
+                        "BatchProposed log stream ended. Attempting to resubscribe..."
+                    );
+                }
             }
         });
 
-        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+        Ok(Box::pin(rx))
     }
 
     /// Subscribes to the `TaikoInbox` `BatchesProved` event and returns a stream of decoded events
     /// along with the block number. This stream will attempt to automatically resubscribe and
     /// continue yielding events.
     pub async fn get_batches_proved_stream(&self) -> Result<BatchesProvedStream> {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = channel::channel("batches_proved", &self.channel_settings, &self.channels);
         let provider = self.l1_provider.clone();
-        let taiko_inbox = self.taiko_inbox.clone(); // Clone for use in the spawned task
+        let forks = Arc::clone(&self.forks);
+        let filter = self.all_forks_filter(
+            self.current_fork().taiko_inbox.batches_proved_filter(),
+            ForkContracts::inbox_address,
+        );
 
         tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
             loop {
                 info!("Attempting to subscribe to TaikoInbox BatchesProved events...");
-                let filter = taiko_inbox.batches_proved_filter();
                 let sub_result = provider.subscribe_logs(&filter).await;
 
                 let mut log_stream = match sub_result {
@@ -302,7 +893,9 @@ impl Extractor {
                         sub.into_stream()
                     }
                     Err(e) => {
-                        error!(error = %e, "Failed to subscribe to BatchesProved logs, retrying in 5s");
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to BatchesProved logs, retrying in 5s");
+                        }
                         sleep(Duration::from_secs(5)).await;
                         continue;
                     }
@@ -314,11 +907,21 @@ impl Extractor {
                         info!("Skipping removed BatchesProved log due to L1 reorg");
                         continue;
                     }
+                    if !emitted_by_active_fork(&forks, &log, ForkContracts::inbox_address) {
+                        continue;
+                    }
                     match log.log_decode::<BatchesProved>() {
                         Ok(decoded) => {
                             let l1_block_number = log.block_number.unwrap_or(0);
                             let tx_hash = log.transaction_hash.unwrap_or_default();
-                            if tx.send((decoded.data().clone(), l1_block_number, tx_hash)).is_err()
+                            if tx
+                                .send(Received::now((
+                                    decoded.data().clone(),
+                                    l1_block_number,
+                                    tx_hash,
+                                )))
+                                .await
+                                .is_err()
                             {
                                 error!(
                                     "BatchesProved receiver dropped. Stopping BatchesProved event task."
@@ -333,25 +936,35 @@ impl Extractor {
                         }
                     }
                 }
-                warn!("BatchesProved log stream ended. Attempting to resubscribe...");
+                if let Some(suppressed) = resubscribe_log.sample() {
+                    warn!(
+                        suppressed,
+                        "BatchesProved log stream ended. Attempting to resubscribe..."
+                    );
+                }
             }
         });
 
-        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+        Ok(Box::pin(rx))
     }
 
     /// Subscribes to the `TaikoWrapper` `ForcedInclusionProcessed` event and returns a stream of
-    /// decoded events. This stream will attempt to automatically resubscribe and continue
-    /// yielding events.
+    /// decoded events along with the block number and transaction hash. This stream will attempt to
+    /// automatically resubscribe and continue yielding events.
     pub async fn get_forced_inclusion_stream(&self) -> Result<ForcedInclusionStream> {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) =
+            channel::channel("forced_inclusions", &self.channel_settings, &self.channels);
         let provider = self.l1_provider.clone();
-        let taiko_wrapper = self.taiko_wrapper.clone(); // Clone for use in the spawned task
+        let forks = Arc::clone(&self.forks);
+        let filter = self.all_forks_filter(
+            self.current_fork().taiko_wrapper.forced_inclusion_processed_filter(),
+            ForkContracts::taiko_wrapper_address,
+        );
 
         tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
             loop {
                 info!("Attempting to subscribe to TaikoWrapper ForcedInclusionProcessed events...");
-                let filter = taiko_wrapper.forced_inclusion_processed_filter();
                 let sub_result = provider.subscribe_logs(&filter).await;
 
                 let mut log_stream = match sub_result {
@@ -362,7 +975,9 @@ impl Extractor {
                         sub.into_stream()
                     }
                     Err(e) => {
-                        error!(error = %e, "Failed to subscribe to ForcedInclusionProcessed logs, retrying in 5s");
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to ForcedInclusionProcessed logs, retrying in 5s");
+                        }
                         sleep(Duration::from_secs(5)).await;
                         continue;
                     }
@@ -374,9 +989,15 @@ impl Extractor {
                         info!("Skipping removed ForcedInclusionProcessed log due to L1 reorg");
                         continue;
                     }
+                    if !emitted_by_active_fork(&forks, &log, ForkContracts::taiko_wrapper_address) {
+                        continue;
+                    }
+                    let block_number = log.block_number.unwrap_or(0);
+                    let tx_hash = log.transaction_hash.unwrap_or_default();
                     match log.log_decode::<ForcedInclusionProcessed>() {
                         Ok(decoded) => {
-                            if tx.send(decoded.data().clone()).is_err() {
+                            let event = (decoded.data().clone(), block_number, tx_hash);
+                            if tx.send(Received::now(event)).await.is_err() {
                                 error!(
                                     "ForcedInclusionProcessed receiver dropped. Stopping ForcedInclusionProcessed event task."
                                 );
@@ -389,22 +1010,115 @@ impl Extractor {
                         }
                     }
                 }
-                warn!("ForcedInclusionProcessed log stream ended. Attempting to resubscribe...");
+                if let Some(suppressed) = resubscribe_log.sample() {
+                    warn!(
+                        suppressed,
+                        "ForcedInclusionProcessed log stream ended. Attempting to resubscribe..."
+                    );
+                }
+            }
+        });
+
+        Ok(Box::pin(rx))
+    }
+
+    /// Subscribes to the preconf whitelist `OperatorRemoved` event and returns a stream of
+    /// decoded events along with the block number and transaction hash. This stream will attempt
+    /// to automatically resubscribe and continue yielding events.
+    pub async fn get_operator_removed_stream(&self) -> Result<OperatorRemovedStream> {
+        let (tx, rx) = channel::channel("operator_removed", &self.channel_settings, &self.channels);
+        let provider = self.l1_provider.clone();
+        let forks = Arc::clone(&self.forks);
+        let filter = self.all_forks_filter(
+            self.current_fork().preconf_whitelist.operator_removed_filter(),
+            ForkContracts::preconf_whitelist_address,
+        );
+
+        tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
+            loop {
+                info!("Attempting to subscribe to PreconfWhitelist OperatorRemoved events...");
+                let mut log_stream = match provider.subscribe_logs(&filter).await {
+                    Ok(sub) => {
+                        info!(
+                            "Successfully subscribed to PreconfWhitelist OperatorRemoved events."
+                        );
+                        sub.into_stream()
+                    }
+                    Err(e) => {
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to OperatorRemoved logs, retrying in 5s");
+                        }
+                        sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+
+                while let Some(log) = log_stream.next().await {
+                    // Skip reverted logs from reorgs
+                    if log.removed {
+                        info!("Skipping removed OperatorRemoved log due to L1 reorg");
+                        continue;
+                    }
+                    if !emitted_by_active_fork(
+                        &forks,
+                        &log,
+                        ForkContracts::preconf_whitelist_address,
+                    ) {
+                        continue;
+                    }
+                    let block_number = log.block_number.unwrap_or(0);
+                    let tx_hash = log.transaction_hash.unwrap_or_default();
+                    match log.log_decode::<OperatorRemoved>() {
+                        Ok(decoded) => {
+                            let event = (decoded.data().clone(), block_number, tx_hash);
+                            if tx.send(Received::now(event)).await.is_err() {
+                                error!(
+                                    "OperatorRemoved receiver dropped. Stopping OperatorRemoved event task."
+                                );
+                                return;
+                            }
+                        }
+                        Err(err) => {
+                            warn!(error = %err, "Failed to decode OperatorRemoved log");
+                        }
+                    }
+                }
+                if let Some(suppressed) = resubscribe_log.sample() {
+                    warn!(
+                        suppressed,
+                        "OperatorRemoved log stream ended. Attempting to resubscribe..."
+                    );
+                }
             }
         });
 
-        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+        Ok(Box::pin(rx))
+    }
+
+    /// Determine why an operator was removed by the given transaction: ejected when its sender
+    /// was an ejecter of the whitelist active at that L1 block, removed otherwise
+    pub async fn get_operator_removal_reason(
+        &self,
+        tx_hash: B256,
+        block_number: u64,
+    ) -> Result<RemovalReason> {
+        let receipt = self.get_receipt(tx_hash).await?;
+        let whitelist = &active_fork(&self.forks, block_number).preconf_whitelist;
+        let ejected = whitelist.is_ejecter(receipt.from, block_number).await?;
+        Ok(if ejected { RemovalReason::Ejected } else { RemovalReason::Removed })
     }
 
     /// Get the current epoch operator
     pub async fn get_operator_for_current_epoch(&self) -> Result<Address> {
-        let operator = self.preconf_whitelist.get_operator_for_current_epoch().await?;
+        let operator =
+            self.current_fork().preconf_whitelist.get_operator_for_current_epoch().await?;
         Ok(operator)
     }
 
     /// Get the next epoch operator
     pub async fn get_operator_for_next_epoch(&self) -> Result<Address> {
-        let operator = self.preconf_whitelist.get_operator_for_next_epoch().await?;
+        let operator = self.current_fork().preconf_whitelist.get_operator_for_next_epoch().await?;
         Ok(operator)
     }
 
@@ -412,14 +1126,18 @@ impl Extractor {
     /// events along with the block number. This stream will attempt to automatically
     /// resubscribe and continue yielding events.
     pub async fn get_batches_verified_stream(&self) -> Result<BatchesVerifiedStream> {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = channel::channel("batches_verified", &self.channel_settings, &self.channels);
         let provider = self.l1_provider.clone();
-        let taiko_inbox = self.taiko_inbox.clone(); // Clone for use in the spawned task
+        let forks = Arc::clone(&self.forks);
+        let filter = self.all_forks_filter(
+            self.current_fork().taiko_inbox.batches_verified_filter(),
+            ForkContracts::inbox_address,
+        );
 
         tokio::spawn(async move {
+            let mut resubscribe_log = LogSampler::new();
             loop {
                 info!("Attempting to subscribe to TaikoInbox BatchesVerified events...");
-                let filter = taiko_inbox.batches_verified_filter();
                 let sub_result = provider.subscribe_logs(&filter).await;
 
                 let mut log_stream = match sub_result {
@@ -428,7 +1146,9 @@ impl Extractor {
                         sub.into_stream()
                     }
                     Err(e) => {
-                        error!(error = %e, "Failed to subscribe to BatchesVerified logs, retrying in 5s");
+                        if let Some(suppressed) = resubscribe_log.sample() {
+                            error!(error = %e, suppressed, "Failed to subscribe to BatchesVerified logs, retrying in 5s");
+                        }
                         sleep(Duration::from_secs(5)).await;
                         continue;
                     }
@@ -440,11 +1160,18 @@ impl Extractor {
                         info!("Skipping removed BatchesVerified log due to L1 reorg");
                         continue;
                     }
+                    if !emitted_by_active_fork(&forks, &log, ForkContracts::inbox_address) {
+                        continue;
+                    }
                     match decode_batches_verified(&log) {
                         Ok(verified) => {
                             let l1_block_number = log.block_number.unwrap_or(0);
                             let tx_hash = log.transaction_hash.unwrap_or_default();
-                            if tx.send((verified, l1_block_number, tx_hash)).is_err() {
+                            if tx
+                                .send(Received::now((verified, l1_block_number, tx_hash)))
+                                .await
+                                .is_err()
+                            {
                                 error!(
                                     "BatchesVerified receiver dropped. Stopping BatchesVerified event task."
                                 );
@@ -457,25 +1184,105 @@ impl Extractor {
                         }
                     }
                 }
-                warn!("BatchesVerified log stream ended. Attempting to resubscribe...");
+                if let Some(suppressed) = resubscribe_log.sample() {
+                    warn!(
+                        suppressed,
+                        "BatchesVerified log stream ended. Attempting to resubscribe..."
+                    );
+                }
             }
         });
 
-        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+        Ok(Box::pin(rx))
+    }
+
+    /// Get the protocol configuration of the `TaikoInbox` active at the latest L1 head
+    pub async fn get_protocol_config(&self) -> Result<chainio::ITaikoInbox::ProtocolConfig> {
+        let config = self.current_fork().taiko_inbox.protocol_config().await?;
+        Ok(config)
+    }
+
+    /// Get the latest proposed and verified batch ids of the inbox active at the given L1 block
+    pub async fn get_batch_heads(&self, block_number: u64) -> Result<BatchHeads> {
+        let fork = active_fork(&self.forks, block_number);
+        let (num_batches, last_verified_batch_id) =
+            fork.taiko_inbox.batch_heads(block_number).await?;
+        Ok(BatchHeads {
+            last_proposed_batch_id: num_batches.saturating_sub(1),
+            last_verified_batch_id,
+        })
+    }
+
+    /// Get the state of the forced inclusion queue of the wrapper active at the given L1 block
+    pub async fn get_forced_inclusion_queue(
+        &self,
+        block_number: u64,
+    ) -> Result<ForcedInclusionQueue> {
+        let fork = active_fork(&self.forks, block_number);
+        let store_address = fork.taiko_wrapper.forced_inclusion_store(block_number).await?;
+        let store = ForcedInclusionStore::new_readonly(store_address, self.l1_provider.clone());
+
+        let head = store.head(block_number).await?;
+        let tail = store.tail(block_number).await?;
+        let next_batch_id = fork.taiko_inbox.num_batches(block_number).await?;
+        let pending = tail.saturating_sub(head);
+        if pending == 0 {
+            return Ok(ForcedInclusionQueue {
+                pending,
+                oldest_created_in: None,
+                deadline_batch_id: None,
+                next_batch_id,
+            });
+        }
+
+        let oldest = store.forced_inclusion(head, block_number).await?;
+        let deadline = store.oldest_deadline(block_number).await?;
+        Ok(ForcedInclusionQueue {
+            pending,
+            oldest_created_in: Some(oldest.blobCreatedIn),
+            deadline_batch_id: Some(deadline.saturating_to::<u64>()),
+            next_batch_id,
+        })
     }
 
     /// Get the operator candidates for the current epoch
     pub async fn get_operator_candidates_for_current_epoch(&self) -> Result<Vec<Address>> {
-        let candidates = self.preconf_whitelist.get_operator_candidates_for_current_epoch().await?;
+        let candidates = self
+            .current_fork()
+            .preconf_whitelist
+            .get_operator_candidates_for_current_epoch()
+            .await?;
         Ok(candidates)
     }
 
+    /// Read the operator candidates, the current and next operator and the inbox stats of the
+    /// fork active at the given L1 block in one RPC round trip through Multicall3.
+    ///
+    /// Falls back to separate calls at the latest block if the batched read fails, e.g. on a
+    /// chain without Multicall3 or a node that pruned the state of `block`.
+    pub async fn get_preconf_snapshot(&self, block: u64) -> PreconfSnapshot {
+        let fork = active_fork(&self.forks, block);
+        let batched = Multicall::new_readonly(self.l1_provider.clone())
+            .preconf_snapshot(fork.preconf_whitelist_address(), fork.inbox_address(), block)
+            .await;
+        match batched {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!(block, err = %e, "Batched preconf read failed, falling back to separate calls");
+                let whitelist = &fork.preconf_whitelist;
+                PreconfSnapshot {
+                    candidates: whitelist.get_operator_candidates_for_current_epoch().await.ok(),
+                    current_operator: whitelist.get_operator_for_current_epoch().await.ok(),
+                    next_operator: whitelist.get_operator_for_next_epoch().await.ok(),
+                    num_batches: fork.taiko_inbox.num_batches(block).await.ok(),
+                    paused: None,
+                }
+            }
+        }
+    }
+
     /// Calculate aggregated statistics for an L2 block by fetching its receipts.
-    pub async fn get_l2_block_stats(
-        &self,
-        block_hash: B256,
-        base_fee: u64,
-    ) -> Result<(u128, u32, u128)> {
+    pub async fn get_l2_block_stats(&self, block_hash: B256, base_fee: u64) -> Result<BlockStats> {
         use alloy_rpc_types_eth::BlockId;
 
         let block = BlockId::Hash(block_hash.into());
@@ -485,6 +1292,24 @@ impl Extractor {
         Ok(compute_block_stats(&receipts, base_fee, self.anchor_address))
     }
 
+    /// Get the L1 anchor block id of an L2 block from its anchor transaction.
+    ///
+    /// Returns `None` if the first transaction of the block is not an anchor transaction.
+    pub async fn get_l2_anchor_block_id(&self, block_hash: B256) -> Result<Option<u64>> {
+        use alloy_consensus::Transaction as _;
+
+        let tx = self
+            .l2_provider
+            .get_transaction_by_block_hash_and_index(block_hash, 0)
+            .await?
+            .ok_or_else(|| eyre::eyre!("missing anchor transaction in L2 block {}", block_hash))?;
+
+        if tx.to() != Some(self.anchor_address) {
+            return Ok(None);
+        }
+        Ok(decode_anchor_block_id(tx.input()))
+    }
+
     /// Get the latest L1 block number
     pub async fn get_l1_latest_block_number(&self) -> Result<u64> {
         self.l1_provider.get_block_number().await.map_err(Into::into)
@@ -506,6 +1331,23 @@ impl Extractor {
             .ok_or_else(|| eyre::eyre!("L1 block {} not found", block_number))
     }
 
+    /// Get the excess blob gas and blob base fee (in wei) of an L1 block.
+    ///
+    /// The blob base fee is taken from `eth_feeHistory` so it follows the blob schedule of the
+    /// fork the block belongs to.
+    pub async fn get_l1_blob_fee(&self, block_number: u64) -> Result<(u64, u128)> {
+        let block = self.get_l1_block_by_number(block_number).await?;
+        let excess_blob_gas = block.header.excess_blob_gas().unwrap_or_default();
+
+        let history = self.l1_provider.get_fee_history(1, block_number.into(), &[]).await?;
+        let blob_base_fee =
+            history.base_fee_per_blob_gas.first().copied().ok_or_else(|| {
+                eyre::eyre!("missing blob base fee for L1 block {}", block_number)
+            })?;
+
+        Ok((excess_blob_gas, blob_base_fee))
+    }
+
     /// Get L2 block by number
     pub async fn get_l2_block_by_number(
         &self,
@@ -517,6 +1359,210 @@ impl Extractor {
             .ok_or_else(|| eyre::eyre!("L2 block {} not found", block_number))
     }
 
+    /// Get an L2 block by hash, including blocks that are no longer canonical as long as the
+    /// node still has them. Returns `None` if the node does not know the block.
+    pub async fn get_l2_block_by_hash(
+        &self,
+        block_hash: B256,
+    ) -> Result<Option<alloy_rpc_types_eth::Block>> {
+        Ok(self.l2_provider.get_block_by_hash(block_hash).await?)
+    }
+
+    /// Hash of the canonical L2 block that included a transaction, or `None` if the
+    /// transaction is not included.
+    pub async fn get_l2_transaction_block_hash(&self, tx_hash: B256) -> Result<Option<B256>> {
+        let receipt = self.l2_provider.get_transaction_receipt(tx_hash).await?;
+        Ok(receipt.and_then(|r| r.block_hash))
+    }
+
+    /// Get the `BatchProposed` logs emitted by the inbox within the given L1 block range
+    /// (inclusive), along with the L1 block number and transaction hash of each log
+    pub async fn get_batch_proposed_logs(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<(BatchProposed, u64, B256)>> {
+        let filter = self.all_forks_filter(
+            self.current_fork().taiko_inbox.batch_proposed_filter(),
+            ForkContracts::inbox_address,
+        );
+        self.get_inbox_logs(filter.from_block(from_block).to_block(to_block)).await
+    }
+
+    /// Get the `BatchesProved` logs emitted by the inbox within the given L1 block range
+    /// (inclusive), along with the L1 block number and transaction hash of each log
+    pub async fn get_batches_proved_logs(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<(BatchesProved, u64, B256)>> {
+        let filter = self.all_forks_filter(
+            self.current_fork().taiko_inbox.batches_proved_filter(),
+            ForkContracts::inbox_address,
+        );
+        self.get_inbox_logs(filter.from_block(from_block).to_block(to_block)).await
+    }
+
+    /// Get the type of the proof submitted by the prove transaction `tx_hash` and checked by
+    /// `verifier`, the verifier of its `BatchesProved` event.
+    ///
+    /// The sub-proof verifiers are decoded from the calldata and matched against the verifiers
+    /// accepted by the compose verifier. Transactions that do not call `proveBatches` directly,
+    /// e.g. through a prover contract, are of unknown type unless `verifier` is a guardian.
+    pub async fn get_proof_type(&self, tx_hash: B256, verifier: Address) -> Result<ProofType> {
+        use alloy_consensus::Transaction as _;
+
+        if self.guardian_verifiers.contains(&verifier) {
+            return Ok(ProofType::Guardian);
+        }
+        let tx = self
+            .l1_provider
+            .get_transaction_by_hash(tx_hash)
+            .await?
+            .ok_or_else(|| eyre::eyre!("missing prove transaction {}", tx_hash))?;
+        let Some(sub_proofs) = decode_sub_proof_verifiers(tx.input()) else {
+            return Ok(ProofType::Unknown);
+        };
+
+        let cached = self.compose_verifiers.lock().unwrap().get(&verifier).cloned();
+        let accepted = match cached {
+            Some(accepted) => accepted,
+            None => {
+                let accepted = ComposeVerifiers::read(verifier, &self.l1_provider).await;
+                // An empty set may be caused by a failed RPC call, so it is read again next time
+                if !accepted.is_empty() {
+                    self.compose_verifiers.lock().unwrap().insert(verifier, accepted.clone());
+                }
+                accepted
+            }
+        };
+        Ok(classify_proof(&sub_proofs, &accepted, &self.guardian_verifiers))
+    }
+
+    /// Get the `BatchesVerified` logs emitted by the inbox within the given L1 block range
+    /// (inclusive), along with the L1 block number and transaction hash of each log
+    pub async fn get_batches_verified_logs(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<(chainio::BatchesVerified, u64, B256)>> {
+        let filter = self.all_forks_filter(
+            self.current_fork().taiko_inbox.batches_verified_filter(),
+            ForkContracts::inbox_address,
+        );
+        let logs = self
+            .get_inbox_logs::<InboxBatchesVerified>(
+                filter.from_block(from_block).to_block(to_block),
+            )
+            .await?;
+        Ok(logs
+            .into_iter()
+            .map(|(verified, block_number, tx_hash)| {
+                let verified = chainio::BatchesVerified {
+                    batch_id: verified.batchId,
+                    block_hash: verified.blockHash.0,
+                };
+                (verified, block_number, tx_hash)
+            })
+            .collect())
+    }
+
+    /// Get the bond balance change events emitted by the inbox within the given L1 block range
+    /// (inclusive), along with the L1 block number, transaction hash and log index of each event
+    pub async fn get_bond_events(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<(BondEvent, u64, B256, u64)>> {
+        let filter = self
+            .all_forks_filter(
+                self.current_fork().taiko_inbox.bond_events_filter(),
+                ForkContracts::inbox_address,
+            )
+            .from_block(from_block)
+            .to_block(to_block);
+        let logs = self.l1_provider.get_logs(&filter).await?;
+        Ok(logs
+            .into_iter()
+            .filter(|log| {
+                !log.removed &&
+                    emitted_by_active_fork(&self.forks, log, ForkContracts::inbox_address)
+            })
+            .filter_map(|log| {
+                let event = BondEvent::decode(log.data())?;
+                Some((
+                    event,
+                    log.block_number.unwrap_or(0),
+                    log.transaction_hash.unwrap_or_default(),
+                    log.log_index.unwrap_or(0),
+                ))
+            })
+            .collect())
+    }
+
+    /// Get the number of transactions sent by `address` on L1 up to the latest block, i.e. its
+    /// next nonce
+    pub async fn get_l1_nonce(&self, address: Address) -> Result<u64> {
+        self.l1_provider.get_transaction_count(address).await.map_err(Into::into)
+    }
+
+    /// Get the bond balance of `user` at the given L1 block, read from the inbox active at that
+    /// block
+    pub async fn get_bond_balance(&self, user: Address, block_number: u64) -> Result<U256> {
+        let inbox = &active_fork(&self.forks, block_number).taiko_inbox;
+        Ok(inbox.bond_balance_of(user, block_number).await?)
+    }
+
+    /// Get the ETH balance of `address` at the given L1 block
+    pub async fn get_eth_balance(&self, address: Address, block_number: u64) -> Result<U256> {
+        Ok(self.l1_provider.get_balance(address).number(block_number).await?)
+    }
+
+    /// Get the ETH balance of `address` at the given L2 block
+    pub async fn get_l2_eth_balance(&self, address: Address, block_number: u64) -> Result<U256> {
+        Ok(self.l2_provider.get_balance(address).number(block_number).await?)
+    }
+
+    /// Get the balance of `owner` in the ERC-20 `token` at the given L1 block
+    pub async fn get_erc20_balance(
+        &self,
+        token: Address,
+        owner: Address,
+        block_number: u64,
+    ) -> Result<U256> {
+        let token = Erc20::new_readonly(token, self.l1_provider.clone());
+        Ok(token.balance_of(owner, block_number).await?)
+    }
+
+    /// Get the verified primary ENS name of `address` on L1, if it has one
+    pub async fn lookup_ens_name(&self, address: Address) -> Result<Option<String>> {
+        Ok(Ens::new_readonly(self.l1_provider.clone()).lookup_address(address).await?)
+    }
+
+    /// Fetch and decode the L1 logs matching `filter`, skipping removed or undecodable logs and
+    /// logs of an inbox that was not active at their block
+    async fn get_inbox_logs<E: SolEvent>(&self, filter: Filter) -> Result<Vec<(E, u64, B256)>> {
+        let logs = self.l1_provider.get_logs(&filter).await?;
+        Ok(logs
+            .into_iter()
+            .filter(|log| {
+                !log.removed &&
+                    emitted_by_active_fork(&self.forks, log, ForkContracts::inbox_address)
+            })
+            .filter_map(|log| match log.log_decode::<E>() {
+                Ok(decoded) => Some((
+                    decoded.inner.data,
+                    log.block_number.unwrap_or(0),
+                    log.transaction_hash.unwrap_or_default(),
+                )),
+                Err(err) => {
+                    warn!(error = %err, "Failed to decode inbox log");
+                    None
+                }
+            })
+            .collect())
+    }
+
     /// Get a transaction receipt by hash with retry logic
     pub async fn get_receipt(
         &self,
@@ -557,6 +1603,21 @@ impl Extractor {
         // All retries exhausted
         Err(eyre::eyre!("Receipt not found for transaction hash: {}", tx_hash))
     }
+
+    /// Get the calldata of the L1 transaction `tx_hash`
+    pub async fn get_transaction_input(
+        &self,
+        tx_hash: alloy::primitives::B256,
+    ) -> Result<alloy::primitives::Bytes> {
+        use alloy_consensus::Transaction as _;
+
+        let tx = self
+            .l1_provider
+            .get_transaction_by_hash(tx_hash)
+            .await?
+            .ok_or_else(|| eyre::eyre!("missing transaction {}", tx_hash))?;
+        Ok(tx.input().clone())
+    }
 }
 
 fn decode_batches_verified(log: &alloy_rpc_types_eth::Log) -> Result<chainio::BatchesVerified> {
@@ -639,6 +1700,26 @@ impl ReorgDetector {
     }
 }
 
+/// Type of a proof made of sub-proofs checked by `sub_proofs`, given the verifiers accepted by
+/// its compose verifier and the guardian verifiers.
+fn classify_proof(
+    sub_proofs: &[Address],
+    accepted: &ComposeVerifiers,
+    guardians: &[Address],
+) -> ProofType {
+    ProofType::combined(sub_proofs.iter().map(|verifier| {
+        if guardians.contains(verifier) {
+            ProofType::Guardian
+        } else if accepted.zk.contains(verifier) {
+            ProofType::Zk
+        } else if accepted.tee.contains(verifier) {
+            ProofType::Sgx
+        } else {
+            ProofType::Unknown
+        }
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -646,6 +1727,50 @@ mod tests {
     use alloy_rpc_types_eth::Log;
     use alloy_sol_types::SolEvent;
 
+    #[tokio::test]
+    async fn next_or_stall_yields_items_then_end() {
+        let mut stream = tokio_stream::iter([1, 2]);
+        let timeout = Some(Duration::from_secs(1));
+        assert!(matches!(next_or_stall(&mut stream, timeout).await, StreamEvent::Item(1)));
+        assert!(matches!(next_or_stall(&mut stream, timeout).await, StreamEvent::Item(2)));
+        assert!(matches!(next_or_stall(&mut stream, timeout).await, StreamEvent::Ended));
+    }
+
+    #[tokio::test]
+    async fn next_or_stall_detects_silent_stream() {
+        let mut stream = tokio_stream::pending::<u64>();
+        let timeout = Some(Duration::from_millis(10));
+        assert!(matches!(next_or_stall(&mut stream, timeout).await, StreamEvent::Stalled));
+    }
+
+    #[test]
+    fn classifies_proofs_by_their_strongest_sub_proof() {
+        let accepted = ComposeVerifiers {
+            tee: vec![Address::repeat_byte(1), Address::repeat_byte(2)],
+            zk: vec![Address::repeat_byte(3)],
+        };
+        let guardians = [Address::repeat_byte(9)];
+        let classify = |bytes: &[u8]| {
+            let sub_proofs: Vec<Address> = bytes.iter().map(|b| Address::repeat_byte(*b)).collect();
+            classify_proof(&sub_proofs, &accepted, &guardians)
+        };
+
+        assert_eq!(classify(&[1, 2]), ProofType::Sgx);
+        assert_eq!(classify(&[1, 3]), ProofType::Zk);
+        assert_eq!(classify(&[3, 9]), ProofType::Guardian);
+        assert_eq!(classify(&[7]), ProofType::Unknown);
+        assert_eq!(classify(&[]), ProofType::Unknown);
+    }
+
+    #[test]
+    fn rejects_non_websocket_fallbacks() {
+        let urls = ["wss://primary", "ws://fallback"].map(|url| Url::parse(url).unwrap());
+        assert!(validate_ws_urls(&urls, "L1").is_ok());
+
+        let urls = ["wss://primary", "https://fallback"].map(|url| Url::parse(url).unwrap());
+        assert!(validate_ws_urls(&urls, "L1").is_err());
+    }
+
     #[test]
     fn initial_block() {
         let mut det = ReorgDetector::new();
@@ -820,6 +1945,55 @@ mod tests {
         assert_eq!(decoded.block_hash, [2u8; 32]);
     }
 
+    fn fork(name: &str, activation_block: u64, byte: u8) -> ForkContracts {
+        let provider = ProviderBuilder::new().connect_http("http://127.0.0.1:1".parse().unwrap());
+        ForkContracts::new(
+            &ForkAddresses {
+                name: name.to_owned(),
+                activation_block,
+                inbox_address: Address::repeat_byte(byte),
+                taiko_wrapper_address: Address::repeat_byte(byte + 1),
+                preconf_whitelist_address: Address::repeat_byte(byte + 2),
+            },
+            &provider,
+        )
+    }
+
+    #[test]
+    fn active_fork_switches_at_activation_block() {
+        let forks = vec![fork("pacaya", 0, 1), fork("shasta", 100, 10)];
+        assert_eq!(active_fork(&forks, 0).name, "pacaya");
+        assert_eq!(active_fork(&forks, 99).name, "pacaya");
+        assert_eq!(active_fork(&forks, 100).name, "shasta");
+        assert_eq!(active_fork(&forks, 5_000).name, "shasta");
+    }
+
+    #[test]
+    fn logs_of_inactive_fork_are_dropped() {
+        let forks = vec![fork("pacaya", 0, 1), fork("shasta", 100, 10)];
+        let log = |address: Address, block_number: u64| Log {
+            inner: PrimitiveLog { address, data: Default::default() },
+            block_number: Some(block_number),
+            ..Default::default()
+        };
+
+        let old_inbox = Address::repeat_byte(1);
+        let new_inbox = Address::repeat_byte(10);
+        assert!(emitted_by_active_fork(&forks, &log(old_inbox, 99), ForkContracts::inbox_address));
+        assert!(!emitted_by_active_fork(
+            &forks,
+            &log(old_inbox, 100),
+            ForkContracts::inbox_address
+        ));
+        assert!(emitted_by_active_fork(&forks, &log(new_inbox, 100), ForkContracts::inbox_address));
+        assert!(!emitted_by_active_fork(&forks, &log(new_inbox, 99), ForkContracts::inbox_address));
+        assert!(emitted_by_active_fork(
+            &forks,
+            &log(Address::repeat_byte(11), 100),
+            ForkContracts::taiko_wrapper_address
+        ));
+    }
+
     #[tokio::test]
     async fn test_get_receipt_retry_delay_calculation() {
         // This test verifies the retry delay calculation logic
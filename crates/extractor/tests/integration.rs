@@ -48,8 +48,8 @@ async fn test_get_block_stream() -> Result<()> {
 
     // Create Extractor
     let ext = Extractor::new(
-        ws.clone(),
-        ws,
+        vec![ws.clone()],
+        vec![ws],
         address!("0xa7B208DE7F35E924D59C2b5f7dE3bb346E8A138C"),
         address!("0x3ea351Db28A9d4833Bf6c519F52766788DE14eC1"),
         address!("0x962C95233f04Ef08E7FaA84DBd1c5171f06f5616"),
@@ -59,7 +59,7 @@ async fn test_get_block_stream() -> Result<()> {
     let mut stream = ext.get_l1_header_stream().await?;
 
     // Wait for the first block
-    let header: L1Header = stream.next().await.expect("stream ended unexpectedly");
+    let header: L1Header = stream.next().await.expect("stream ended unexpectedly").value;
     assert!(header.number > 0);
     assert!(header.timestamp > 0);
     Ok(())
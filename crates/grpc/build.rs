@@ -0,0 +1,12 @@
+//! Generate the gRPC service and messages from `proto/taikoscope.proto`
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Use the bundled protoc so that building does not need a system installation
+    let protoc = protoc_bin_vendored::protoc_bin_path()?;
+    // SAFETY: build scripts are single-threaded
+    unsafe { std::env::set_var("PROTOC", protoc) };
+
+    println!("cargo:rerun-if-changed=proto/taikoscope.proto");
+    tonic_build::configure().compile_protos(&["proto/taikoscope.proto"], &["proto"])?;
+    Ok(())
+}
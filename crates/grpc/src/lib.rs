@@ -0,0 +1,308 @@
+//! gRPC API for internal consumers
+//!
+//! Services that poll Taikoscope programmatically get a typed interface over a subset of the
+//! reader queries of the HTTP API: the indexed heads, the batch lifecycle of an L2 block and fee
+//! aggregates. The server shares the [`ClickhouseReader`] of the HTTP API and is rate limited
+//! by the same token bucket settings, but runs on its own port.
+
+use std::{net::SocketAddr, time::Duration};
+
+use clickhouse_lib::{ClickhouseReader, L2BlockStatusRow, SequencerFeeRow, TimeRange};
+use eyre::Result;
+use runtime::rate_limiter::RateLimiter;
+use tonic::{Request, Response, Status, transport::Server};
+use tracing::{error, info};
+
+use proto::{
+    BlockStatusRequest, BlockStatusResponse, FeeAggregatesRequest, FeeAggregatesResponse,
+    FeeTotals, FinalityStage, HeadsRequest, HeadsResponse, SequencerFees,
+    taikoscope_server::{Taikoscope, TaikoscopeServer},
+};
+
+/// Protobuf messages and service definitions generated from `proto/taikoscope.proto`
+#[allow(
+    missing_docs,
+    unreachable_pub,
+    clippy::all,
+    clippy::nursery,
+    clippy::pedantic,
+    clippy::missing_const_for_fn,
+    clippy::clone_on_ref_ptr
+)]
+pub mod proto {
+    tonic::include_proto!("taikoscope.v1");
+}
+
+/// Range of the fee aggregates when the request leaves it unset
+pub const DEFAULT_FEE_RANGE_SECS: u64 = 3600;
+
+/// Implementation of the [`Taikoscope`] gRPC service
+#[derive(Debug, Clone)]
+pub struct TaikoscopeService {
+    reader: ClickhouseReader,
+}
+
+impl TaikoscopeService {
+    /// Create a service answering from `reader`
+    pub const fn new(reader: ClickhouseReader) -> Self {
+        Self { reader }
+    }
+}
+
+/// Status of a failed reader query, with the details logged rather than returned
+fn query_error(what: &str, err: eyre::Report) -> Status {
+    error!(query = what, err = %err, "gRPC query failed");
+    Status::internal(format!("failed to query {what}"))
+}
+
+#[tonic::async_trait]
+impl Taikoscope for TaikoscopeService {
+    async fn get_heads(
+        &self,
+        _request: Request<HeadsRequest>,
+    ) -> Result<Response<HeadsResponse>, Status> {
+        let l1_head_block =
+            self.reader.get_last_l1_block_number().await.map_err(|e| query_error("l1 head", e))?;
+        let l2_head_block =
+            self.reader.get_last_l2_block_number().await.map_err(|e| query_error("l2 head", e))?;
+        Ok(Response::new(HeadsResponse { l1_head_block, l2_head_block }))
+    }
+
+    async fn get_block_status(
+        &self,
+        request: Request<BlockStatusRequest>,
+    ) -> Result<Response<BlockStatusResponse>, Status> {
+        let block_number = request.into_inner().l2_block_number;
+        let row = self
+            .reader
+            .get_l2_block_status(block_number)
+            .await
+            .map_err(|e| query_error("l2 block status", e))?
+            .ok_or_else(|| {
+                Status::not_found(format!("L2 block {block_number} has not been observed"))
+            })?;
+        Ok(Response::new(block_status(row)))
+    }
+
+    async fn get_fee_aggregates(
+        &self,
+        request: Request<FeeAggregatesRequest>,
+    ) -> Result<Response<FeeAggregatesResponse>, Status> {
+        let range_secs = match request.into_inner().range_secs {
+            0 => DEFAULT_FEE_RANGE_SECS,
+            secs => secs,
+        };
+        let range = TimeRange::from_duration(chrono_seconds(range_secs));
+        let rows = self
+            .reader
+            .get_l2_fees_by_sequencer(range, None)
+            .await
+            .map_err(|e| query_error("fees by sequencer", e))?;
+        Ok(Response::new(fee_aggregates(range.seconds(), &rows)))
+    }
+}
+
+/// Duration of `secs` seconds, saturating at the largest representable value
+fn chrono_seconds(secs: u64) -> chrono::Duration {
+    chrono::Duration::seconds(i64::try_from(secs).unwrap_or(i64::MAX))
+}
+
+/// Convert a status row into its protobuf message
+pub fn block_status(row: L2BlockStatusRow) -> BlockStatusResponse {
+    let stage = if row.verified_at.is_some() {
+        FinalityStage::Verified
+    } else if row.proved_at.is_some() {
+        FinalityStage::Proved
+    } else if row.batch_id.is_some() {
+        FinalityStage::Proposed
+    } else {
+        FinalityStage::Preconfirmed
+    };
+    BlockStatusResponse {
+        l2_block_number: row.l2_block_number,
+        stage: stage.into(),
+        batch_id: row.batch_id,
+        preconfirmed_at: row.block_ts,
+        proposed_at: row.proposed_at,
+        proved_at: row.proved_at,
+        verified_at: row.verified_at,
+    }
+}
+
+/// Totals per sequencer and over all sequencers of `range_secs`
+pub fn fee_aggregates(range_secs: u64, rows: &[SequencerFeeRow]) -> FeeAggregatesResponse {
+    let totals = |priority: u128, base: u128, l1_data: u128, prove: u128| FeeTotals {
+        priority_fee_wei: priority.to_string(),
+        base_fee_wei: base.to_string(),
+        l1_data_cost_wei: l1_data.to_string(),
+        prove_cost_wei: prove.to_string(),
+    };
+    let sum =
+        |f: fn(&SequencerFeeRow) -> u128| rows.iter().map(f).fold(0u128, u128::saturating_add);
+
+    FeeAggregatesResponse {
+        range_secs,
+        total: Some(totals(
+            sum(|r| r.priority_fee),
+            sum(|r| r.base_fee),
+            sum(|r| r.l1_data_cost),
+            sum(|r| r.prove_cost),
+        )),
+        sequencers: rows
+            .iter()
+            .map(|r| SequencerFees {
+                sequencer: r.sequencer.0.to_vec(),
+                fees: Some(totals(r.priority_fee, r.base_fee, r.l1_data_cost, r.prove_cost)),
+            })
+            .collect(),
+    }
+}
+
+/// Reject requests once `limiter` runs out of tokens. Interceptors must fail with a [`Status`].
+#[allow(clippy::result_large_err)]
+pub fn rate_limit(
+    limiter: RateLimiter,
+) -> impl FnMut(Request<()>) -> Result<Request<()>, Status> + Clone {
+    move |request| match limiter.try_acquire_weighted(1) {
+        Ok(_) => Ok(request),
+        Err(retry_after) => Err(rate_limited(retry_after)),
+    }
+}
+
+/// Status of a rate-limited request
+fn rate_limited(retry_after: Duration) -> Status {
+    let secs = retry_after.as_secs().max(1);
+    Status::resource_exhausted(format!("Rate limit exceeded. Retry after {secs} seconds"))
+}
+
+/// Serve the gRPC API on `addr` until the task is dropped
+pub async fn serve(addr: SocketAddr, reader: ClickhouseReader, limiter: RateLimiter) -> Result<()> {
+    info!(%addr, "Starting gRPC server");
+    let service =
+        TaikoscopeServer::with_interceptor(TaikoscopeService::new(reader), rate_limit(limiter));
+    Server::builder().add_service(service).serve(addr).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clickhouse::{
+        Row,
+        test::{Mock, handlers},
+    };
+    use clickhouse_lib::AddressBytes;
+
+    fn reader(mock: &Mock) -> ClickhouseReader {
+        let url = url::Url::parse(mock.url()).unwrap();
+        ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap()
+    }
+
+    #[derive(Row, serde::Serialize)]
+    struct L1Head {
+        l1_block_number: u64,
+    }
+
+    #[derive(Row, serde::Serialize)]
+    struct L2Head {
+        l2_block_number: u64,
+    }
+
+    #[tokio::test]
+    async fn heads_are_returned() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![L1Head { l1_block_number: 100 }]));
+        mock.add(handlers::provide(vec![L2Head { l2_block_number: 200 }]));
+
+        let service = TaikoscopeService::new(reader(&mock));
+        let heads = service.get_heads(Request::new(HeadsRequest {})).await.unwrap().into_inner();
+
+        assert_eq!(heads, HeadsResponse { l1_head_block: Some(100), l2_head_block: Some(200) });
+    }
+
+    #[tokio::test]
+    async fn block_status_reports_the_latest_stage() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![L2BlockStatusRow {
+            l2_block_number: 42,
+            block_ts: Some(1_700_000_000),
+            batch_id: Some(7),
+            proposed_at: Some(1_700_000_100),
+            proved_at: Some(1_700_001_200),
+            verified_at: None,
+        }]));
+
+        let service = TaikoscopeService::new(reader(&mock));
+        let status = service
+            .get_block_status(Request::new(BlockStatusRequest { l2_block_number: 42 }))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(status.stage(), FinalityStage::Proved);
+        assert_eq!(status.batch_id, Some(7));
+        assert_eq!(status.preconfirmed_at, Some(1_700_000_000));
+        assert_eq!(status.verified_at, None);
+    }
+
+    #[tokio::test]
+    async fn unknown_block_is_not_found() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![L2BlockStatusRow {
+            l2_block_number: 42,
+            block_ts: None,
+            batch_id: None,
+            proposed_at: None,
+            proved_at: None,
+            verified_at: None,
+        }]));
+
+        let service = TaikoscopeService::new(reader(&mock));
+        let err = service
+            .get_block_status(Request::new(BlockStatusRequest { l2_block_number: 42 }))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), tonic::Code::NotFound);
+    }
+
+    #[test]
+    fn fee_aggregates_sum_all_sequencers() {
+        let rows = vec![
+            SequencerFeeRow {
+                sequencer: AddressBytes([1u8; 20]),
+                priority_fee: 10,
+                base_fee: 20,
+                l1_data_cost: 5,
+                prove_cost: 3,
+            },
+            SequencerFeeRow {
+                sequencer: AddressBytes([2u8; 20]),
+                priority_fee: u128::MAX,
+                base_fee: 1,
+                l1_data_cost: 0,
+                prove_cost: 0,
+            },
+        ];
+
+        let response = fee_aggregates(3600, &rows);
+
+        let total = response.total.unwrap();
+        assert_eq!(total.priority_fee_wei, u128::MAX.to_string());
+        assert_eq!(total.base_fee_wei, "21");
+        assert_eq!(total.l1_data_cost_wei, "5");
+        assert_eq!(response.sequencers.len(), 2);
+        assert_eq!(response.sequencers[0].sequencer, vec![1u8; 20]);
+        assert_eq!(response.sequencers[0].fees.as_ref().unwrap().prove_cost_wei, "3");
+    }
+
+    #[test]
+    fn requests_beyond_the_limit_are_exhausted() {
+        let mut interceptor = rate_limit(RateLimiter::new(1, Duration::from_secs(60)));
+
+        assert!(interceptor(Request::new(())).is_ok());
+        let err = interceptor(Request::new(())).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+        assert!(err.message().starts_with("Rate limit exceeded"));
+    }
+}
@@ -0,0 +1,77 @@
+//! Audit log of the incident operations performed against Instatus.
+//!
+//! Every creation, update and resolution is recorded in the `incident_log` table together with
+//! the request payload and the status code Instatus answered with, so alerting behavior can be
+//! reconstructed after an outage. Retried requests are recorded once per attempt.
+use chrono::Utc;
+use clickhouse::IncidentLogRow;
+use primitives::incident::IncidentAction;
+use serde::Serialize;
+
+use crate::monitor::IncidentState;
+
+/// Build a log row for an operation that has not been sent yet.
+///
+/// The response code, error and dry-run flag start out empty and are set by the caller.
+pub fn log_row(
+    action: IncidentAction,
+    incident_id: &str,
+    component_ids: &[String],
+    incident_name: &str,
+    status: IncidentState,
+    payload: &impl Serialize,
+) -> IncidentLogRow {
+    IncidentLogRow {
+        logged_at_ms: Utc::now().timestamp_millis() as u64,
+        action: action as u8,
+        incident_id: incident_id.to_owned(),
+        component_ids: component_ids.to_vec(),
+        incident_name: incident_name.to_owned(),
+        status: status.as_str().to_owned(),
+        payload: serde_json::to_string(payload).unwrap_or_default(),
+        response_code: 0,
+        error: None,
+        dry_run: 0,
+    }
+}
+
+/// Action recorded for a `PUT` of an incident in `status`.
+pub const fn update_action(status: IncidentState) -> IncidentAction {
+    match status {
+        IncidentState::Resolved => IncidentAction::Resolve,
+        _ => IncidentAction::Update,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::{build_incident_payload, build_resolve_payload};
+
+    #[test]
+    fn log_row_snapshots_the_payload() {
+        let payload = build_incident_payload("comp1", "name".into(), "msg".into(), Utc::now());
+        let row = log_row(
+            IncidentAction::Create,
+            "",
+            &payload.components,
+            &payload.name,
+            payload.status,
+            &payload,
+        );
+
+        assert_eq!(row.action, IncidentAction::Create as u8);
+        assert_eq!(row.component_ids, vec!["comp1".to_owned()]);
+        assert_eq!(row.incident_name, "name");
+        assert_eq!(row.status, "INVESTIGATING");
+        assert_eq!(row.response_code, 0);
+        let payload: serde_json::Value = serde_json::from_str(&row.payload).unwrap();
+        assert_eq!(payload["message"], "msg");
+    }
+
+    #[test]
+    fn resolving_puts_are_resolutions() {
+        assert_eq!(update_action(build_resolve_payload("comp1").status), IncidentAction::Resolve);
+        assert_eq!(update_action(IncidentState::Monitoring), IncidentAction::Update);
+    }
+}
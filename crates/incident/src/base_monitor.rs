@@ -1,14 +1,16 @@
 use crate::{
     client::Client as IncidentClient,
     monitor::{NewIncident, ResolveIncident},
+    state::{MonitorState, StateStore},
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use clickhouse::ClickhouseReader;
 use eyre::Result;
+use serde::{Serialize, de::DeserializeOwned};
 use std::fmt::Debug;
 use tokio::task::JoinHandle;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Monitor trait for different incident types
 #[async_trait]
@@ -74,6 +76,11 @@ pub struct BaseMonitor<K> {
     pub reporting_enabled: bool,
     /// Map of active incidents
     pub active_incidents: std::collections::HashMap<K, String>,
+    /// Last time the monitored component was observed healthy. Restored at startup so that the
+    /// persisted value, which the status page reports, keeps dating an outage across restarts.
+    pub last_healthy: Option<DateTime<Utc>>,
+    /// Optional store used to persist state across restarts
+    pub state_store: Option<StateStore>,
 }
 
 impl<K: Clone + Debug + Eq + std::hash::Hash> BaseMonitor<K> {
@@ -92,9 +99,17 @@ impl<K: Clone + Debug + Eq + std::hash::Hash> BaseMonitor<K> {
             // If no component id is configured, treat reporting as disabled (dry-run)
             reporting_enabled: !component_id.is_empty(),
             active_incidents: std::collections::HashMap::new(),
+            last_healthy: None,
+            state_store: None,
         }
     }
 
+    /// Persist monitor state to `store`, if set, so open incidents survive restarts
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.state_store = store;
+        self
+    }
+
     /// Create a standard incident payload
     pub fn create_incident_payload(
         &self,
@@ -123,7 +138,12 @@ impl<K: Clone + Debug + Eq + std::hash::Hash> BaseMonitor<K> {
     ) -> Result<()> {
         crate::helpers::resolve_with_retry(&self.client, self.reporting_enabled, id, payload).await
     }
+}
 
+impl<K> BaseMonitor<K>
+where
+    K: Clone + Debug + Eq + std::hash::Hash + Serialize + DeserializeOwned,
+{
     /// Helper method to check for existing open incidents for this component
     pub async fn check_existing_incidents(&mut self, default_key: K) -> Result<()> {
         if !self.reporting_enabled {
@@ -134,11 +154,36 @@ impl<K: Clone + Debug + Eq + std::hash::Hash> BaseMonitor<K> {
             return Ok(());
         }
 
-        match crate::retry::retry_op(|| async {
-            self.client.open_incident(&self.component_id).await
+        self.restore_state();
+
+        let open = crate::retry::retry_op(|| async {
+            self.client.open_incidents(&self.component_id).await
         })
-        .await?
-        {
+        .await?;
+
+        // Restored incidents may have been resolved on Instatus while the monitor was down
+        let component_id = &self.component_id;
+        self.active_incidents.retain(|key, id| {
+            let still_open = open.contains(id);
+            if !still_open {
+                info!(
+                    incident_id = %id,
+                    key = ?key,
+                    component_id = %component_id,
+                    "Restored incident is no longer open, dropping it"
+                );
+            }
+            still_open
+        });
+
+        match open.into_iter().next() {
+            Some(id) if self.active_incidents.values().any(|tracked| *tracked == id) => {
+                info!(
+                    incident_id = %id,
+                    component_id = %self.component_id,
+                    "Open incident at startup matches restored state"
+                );
+            }
             Some(id) => {
                 info!(
                     incident_id = %id,
@@ -157,6 +202,54 @@ impl<K: Clone + Debug + Eq + std::hash::Hash> BaseMonitor<K> {
 
         Ok(())
     }
+
+    /// Reload active incidents and the last healthy timestamp from the state store
+    fn restore_state(&mut self) {
+        let Some(store) = &self.state_store else {
+            return;
+        };
+        match store.load::<K>() {
+            Ok(Some(state)) => {
+                info!(
+                    component_id = %self.component_id,
+                    active_incidents = state.active_incidents.len(),
+                    last_healthy = ?state.last_healthy,
+                    "Restored monitor state"
+                );
+                self.active_incidents.extend(state.active_incidents);
+                self.last_healthy = state.last_healthy;
+            }
+            Ok(None) => {}
+            Err(e) => warn!(
+                component_id = %self.component_id,
+                error = %e,
+                "Failed to restore monitor state, starting fresh"
+            ),
+        }
+    }
+
+    /// Record the current state in the state store, if one is configured.
+    ///
+    /// Called after every health check; a monitor without open incidents counts as healthy.
+    pub fn persist_state(&mut self) {
+        if self.active_incidents.is_empty() {
+            self.last_healthy = Some(Utc::now());
+        }
+        let Some(store) = &self.state_store else {
+            return;
+        };
+        let state = MonitorState {
+            active_incidents: self
+                .active_incidents
+                .iter()
+                .map(|(key, id)| (key.clone(), id.clone()))
+                .collect(),
+            last_healthy: self.last_healthy,
+        };
+        if let Err(e) = store.save(&state) {
+            warn!(component_id = %self.component_id, error = %e, "Failed to persist monitor state");
+        }
+    }
 }
 
 impl<K: Clone + Debug + Eq + std::hash::Hash> BaseMonitor<K> {
@@ -220,6 +313,7 @@ mod tests {
     use super::*;
     use crate::{
         client::Client as IncidentClient,
+        maintenance::{MaintenanceSchedule, MaintenanceWindow, Suppressed},
         monitor::{ComponentStatus, IncidentState},
     };
     use chrono::Utc;
@@ -338,6 +432,36 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn create_incident_with_payload_suppressed_during_maintenance() {
+        let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+        let mut server = Server::new_async().await;
+        let mock = server.mock("POST", "/v1/page1/incidents").expect(0).create_async().await;
+
+        let now = Utc::now();
+        let maintenance = MaintenanceSchedule::new(vec![MaintenanceWindow {
+            start: now - chrono::Duration::minutes(5),
+            end: now + chrono::Duration::minutes(5),
+            components: vec!["comp1".to_owned()],
+        }]);
+        let incident_client = IncidentClient::with_base_url(
+            "testkey".into(),
+            "page1".into(),
+            server.url().parse().unwrap(),
+        )
+        .with_maintenance(maintenance);
+        let monitor = BaseMonitor::<u64>::new(
+            ch_client,
+            incident_client,
+            "comp1".to_owned(),
+            Duration::from_secs(1),
+        );
+        let payload = monitor.create_incident_payload("n".into(), "m".into(), now);
+        let err = monitor.create_incident_with_payload(&payload).await.unwrap_err();
+        assert!(err.downcast_ref::<Suppressed>().is_some());
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn resolve_incident_with_payload_success() {
         let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
@@ -458,6 +582,61 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn check_existing_incidents_restores_persisted_state() {
+        let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+        let mut server = Server::new_async().await;
+        let body = serde_json::json!([
+            {"id":"inc7","components":[{"id":"comp1","status":"MAJOROUTAGE","name":"C"}]}
+        ])
+        .to_string();
+        let mock = server
+            .mock("GET", "/v1/page1/incidents")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let dir = std::env::temp_dir()
+            .join(format!("taikoscope-base-monitor-restore-{}", std::process::id()));
+        let store = StateStore::in_dir(&dir, "comp1");
+        let last_healthy = Utc::now() - chrono::Duration::hours(1);
+        store
+            .save(&MonitorState {
+                active_incidents: vec![(7u64, "inc7".to_owned()), (8u64, "inc8".to_owned())],
+                last_healthy: Some(last_healthy),
+            })
+            .unwrap();
+
+        let incident_client = IncidentClient::with_base_url(
+            "testkey".into(),
+            "page1".into(),
+            server.url().parse().unwrap(),
+        );
+        let mut monitor = BaseMonitor::new(
+            ch_client,
+            incident_client,
+            "comp1".to_owned(),
+            Duration::from_secs(1),
+        )
+        .with_state_store(Some(store.clone()));
+        monitor.check_existing_incidents(0u64).await.unwrap();
+
+        // The open incident is tracked under its persisted key, not the catch-all key, and the
+        // incident resolved while the monitor was down is dropped
+        assert_eq!(monitor.active_incidents.len(), 1);
+        assert_eq!(monitor.active_incidents.get(&7u64), Some(&"inc7".to_owned()));
+        mock.assert_async().await;
+
+        // While the incident stays open the restored healthy time is persisted unchanged
+        monitor.persist_state();
+        let state = store.load::<u64>().unwrap().unwrap();
+        assert_eq!(state.active_incidents, vec![(7u64, "inc7".to_owned())]);
+        assert_eq!(state.last_healthy, Some(last_healthy));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
     #[tokio::test]
     async fn mark_healthy_resolves_and_removes() {
         let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
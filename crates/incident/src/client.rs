@@ -1,9 +1,15 @@
+use clickhouse::{ClickhouseWriter, IncidentLogRow};
 use eyre::Result;
+use primitives::incident::IncidentAction;
 use reqwest::{Client as HttpClient, StatusCode, Url};
 use serde::Deserialize;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
-use crate::monitor::{NewIncident, ResolveIncident};
+use crate::{
+    audit::{log_row, update_action},
+    maintenance::MaintenanceSchedule,
+    monitor::{NewIncident, ResolveIncident},
+};
 
 #[derive(Deserialize)]
 struct IncidentComponent {
@@ -18,6 +24,15 @@ struct IncidentSummary {
     components: Vec<IncidentComponent>,
 }
 
+/// Component of an Instatus page.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct PageComponent {
+    /// Component ID
+    pub id: String,
+    /// Component name, unique within the page
+    pub name: String,
+}
+
 /// Client for interacting with the Instatus API.
 #[derive(Debug, Clone)]
 pub struct Client {
@@ -25,6 +40,8 @@ pub struct Client {
     base_url: Url,
     api_key: String,
     page_id: String,
+    maintenance: MaintenanceSchedule,
+    audit_log: Option<ClickhouseWriter>,
 }
 
 impl Client {
@@ -35,13 +52,50 @@ impl Client {
             base_url: Url::parse("https://api.instatus.com").expect("valid base URL"),
             api_key,
             page_id,
+            maintenance: MaintenanceSchedule::default(),
+            audit_log: None,
         }
     }
 
+    /// Record every incident operation in the `incident_log` table through `writer`, if set.
+    pub fn with_audit_log(mut self, writer: Option<ClickhouseWriter>) -> Self {
+        self.audit_log = writer;
+        self
+    }
+
+    /// Suppress incident creation during the windows of `maintenance`.
+    pub fn with_maintenance(mut self, maintenance: MaintenanceSchedule) -> Self {
+        self.maintenance = maintenance;
+        self
+    }
+
+    /// Maintenance windows shared by all clones of this client.
+    pub const fn maintenance(&self) -> &MaintenanceSchedule {
+        &self.maintenance
+    }
+
     /// Create a client targeting a custom base URL (e.g. for tests).
     #[cfg(test)]
     pub fn with_base_url(api_key: String, page_id: String, base_url: Url) -> Self {
-        Self { http: HttpClient::new(), api_key, page_id, base_url }
+        Self {
+            http: HttpClient::new(),
+            api_key,
+            page_id,
+            base_url,
+            maintenance: MaintenanceSchedule::default(),
+            audit_log: None,
+        }
+    }
+
+    /// Append `row` to the audit log. Failures are logged and otherwise ignored so auditing never
+    /// blocks alerting.
+    pub async fn audit(&self, row: IncidentLogRow) {
+        let Some(writer) = &self.audit_log else {
+            return;
+        };
+        if let Err(e) = writer.insert_incident_log(&row).await {
+            warn!(incident_id = %row.incident_id, error = %e, "Failed to record incident operation");
+        }
     }
 
     /// Authenticate the request.
@@ -71,9 +125,29 @@ impl Client {
             incident_name = %body.name,
             "Creating incident"
         );
-        let response = self.auth(self.http.post(url.clone())).json(body).send().await?;
-        let resp = response.error_for_status()?;
-        Ok(resp.json::<Resp>().await?.id)
+        let mut response_code = 0;
+        let result: Result<String> = async {
+            let response = self.auth(self.http.post(url.clone())).json(body).send().await?;
+            response_code = response.status().as_u16();
+            Ok(response.error_for_status()?.json::<Resp>().await?.id)
+        }
+        .await;
+
+        let row = log_row(
+            IncidentAction::Create,
+            result.as_deref().unwrap_or_default(),
+            &body.components,
+            &body.name,
+            body.status,
+            body,
+        );
+        self.audit(IncidentLogRow {
+            response_code,
+            error: result.as_ref().err().map(ToString::to_string),
+            ..row
+        })
+        .await;
+        result
     }
 
     /// Resolve an existing incident on Instatus.
@@ -85,7 +159,13 @@ impl Client {
             url = %url,
             "Resolving incident"
         );
-        let response = self.auth(self.http.put(url.clone())).json(body).send().await?;
+        let response = match self.auth(self.http.put(url.clone())).json(body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                self.audit_update(id, body, 0, Some(e.to_string())).await;
+                return Err(e.into());
+            }
+        };
 
         let status = response.status();
 
@@ -105,19 +185,68 @@ impl Client {
                     body = %response_text,
                     "Incident belongs to different page - this is a configuration error"
                 );
-                return Err(eyre::eyre!("PAGE_MISMATCH: {}", response_text));
+                let err = eyre::eyre!("PAGE_MISMATCH: {}", response_text);
+                self.audit_update(id, body, status.as_u16(), Some(err.to_string())).await;
+                return Err(err);
             }
 
             error!(status = %status, url = %url, body = %response_text, "Failed to resolve incident");
-            return Err(eyre::eyre!("HTTP error {}: {}", status, response_text));
+            let err = eyre::eyre!("HTTP error {}: {}", status, response_text);
+            self.audit_update(id, body, status.as_u16(), Some(err.to_string())).await;
+            return Err(err);
         }
 
+        self.audit_update(id, body, status.as_u16(), None).await;
         debug!(incident_id = %id, "Successfully resolved incident");
         Ok(())
     }
 
+    /// Record an update or resolution of incident `id` in the audit log.
+    async fn audit_update(
+        &self,
+        id: &str,
+        body: &ResolveIncident,
+        response_code: u16,
+        error: Option<String>,
+    ) {
+        let row = log_row(update_action(body.status), id, &body.components, "", body.status, body);
+        self.audit(IncidentLogRow { response_code, error, ..row }).await;
+    }
+
     /// Return open incident ID for `component_id`, if any.
     pub async fn open_incident(&self, component_id: &str) -> Result<Option<String>> {
+        let list = self.list_open_incidents().await?;
+
+        // find the first incident touching our component
+        if let Some((incident_id, comp)) = list.into_iter().find_map(|inc| {
+            inc.components.into_iter().find(|c| c.id == component_id).map(|comp| (inc.id, comp))
+        }) {
+            tracing::info!(
+                incident_id = %incident_id,
+                component_name = %comp.name,
+                component_status = %comp.status,
+                "Found open incident for component"
+            );
+            Ok(Some(incident_id))
+        } else {
+            tracing::debug!(component_id = %component_id, "No open incidents found for component");
+            Ok(None)
+        }
+    }
+
+    /// Return the IDs of every open incident touching `component_id`.
+    pub async fn open_incidents(&self, component_id: &str) -> Result<Vec<String>> {
+        Ok(self
+            .list_open_incidents()
+            .await?
+            .into_iter()
+            .filter(|inc| inc.components.iter().any(|c| c.id == component_id))
+            .map(|inc| inc.id)
+            .collect())
+    }
+
+    /// List the incidents of the page that aren't resolved yet.
+    async fn list_open_incidents(&self) -> Result<Vec<IncidentSummary>> {
         // Query any incidents that aren't RESOLVED (to catch MONITORING or IDENTIFIED too)
         let url = self.incidents_url_with_statuses()?;
 
@@ -135,22 +264,48 @@ impl Client {
 
         let list = response.json::<Vec<IncidentSummary>>().await?;
         tracing::debug!(count = list.len(), "Found incidents in total");
+        Ok(list)
+    }
 
-        // find the first incident touching our component
-        if let Some((incident_id, comp)) = list.into_iter().find_map(|inc| {
-            inc.components.into_iter().find(|c| c.id == component_id).map(|comp| (inc.id, comp))
-        }) {
-            tracing::info!(
-                incident_id = %incident_id,
-                component_name = %comp.name,
-                component_status = %comp.status,
-                "Found open incident for component"
-            );
-            Ok(Some(incident_id))
-        } else {
-            tracing::debug!(component_id = %component_id, "No open incidents found for component");
-            Ok(None)
+    /// Check that the API key is accepted for the page by listing its incidents.
+    pub async fn verify_credentials(&self) -> Result<()> {
+        let url = self.base_url.join(&format!("v1/{}/incidents", self.page_id))?;
+        let response = self.auth(self.http.get(url)).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(eyre::eyre!("HTTP error {}: {}", status, body));
+        }
+        Ok(())
+    }
+
+    /// List the components of the page.
+    pub async fn components(&self) -> Result<Vec<PageComponent>> {
+        let url = self.base_url.join(&format!("v1/{}/components", self.page_id))?;
+        let response = self.auth(self.http.get(url)).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(eyre::eyre!("HTTP error {}: {}", status, body));
         }
+        Ok(response.json::<Vec<PageComponent>>().await?)
+    }
+
+    /// Create an operational component named `name` on the page, returning its ID.
+    pub async fn create_component(&self, name: &str) -> Result<String> {
+        let url = self.base_url.join(&format!("v1/{}/components", self.page_id))?;
+        tracing::info!(page_id = %self.page_id, component_name = %name, "Creating component");
+        let body = serde_json::json!({ "name": name, "status": "OPERATIONAL" });
+        let response = self.auth(self.http.post(url)).json(&body).send().await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(eyre::eyre!("HTTP error {}: {}", status, body));
+        }
+        Ok(response.json::<PageComponent>().await?.id)
     }
 
     /// Check if an incident exists on the current page
@@ -314,6 +469,31 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn open_incidents_lists_every_incident_of_component() {
+        let mut server = Server::new_async().await;
+        let body = json!([
+            { "id": "inc1", "components": [ { "id": "comp1", "name": "Target", "status": "DEGRADEDPERFORMANCE" } ] },
+            { "id": "inc2", "components": [ { "id": "compX", "name": "X", "status": "OPERATIONAL" } ] },
+            { "id": "inc3", "components": [ { "id": "comp1", "name": "Target", "status": "MAJOROUTAGE" } ] }
+        ])
+        .to_string();
+
+        let mock = server
+            .mock("GET", "/v1/page1/incidents")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let client =
+            Client::with_base_url("testkey".into(), "page1".into(), server.url().parse().unwrap());
+        let ids = client.open_incidents("comp1").await.unwrap();
+        assert_eq!(ids, vec!["inc1".to_owned(), "inc3".to_owned()]);
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn open_incident_returns_none_when_no_open_incidents() {
         let mut server = Server::new_async().await;
@@ -333,6 +513,56 @@ mod tests {
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn verify_credentials_fails_on_rejected_key() {
+        let mut server = Server::new_async().await;
+
+        let ok = server
+            .mock("GET", "/v1/page1/incidents")
+            .match_header("authorization", "Bearer testkey")
+            .with_status(200)
+            .with_body("[]")
+            .create_async()
+            .await;
+        let rejected = server
+            .mock("GET", "/v1/page1/incidents")
+            .match_header("authorization", "Bearer badkey")
+            .with_status(401)
+            .with_body("unauthorized")
+            .create_async()
+            .await;
+
+        let url: Url = server.url().parse().unwrap();
+        let client = Client::with_base_url("testkey".into(), "page1".into(), url.clone());
+        client.verify_credentials().await.unwrap();
+
+        let client = Client::with_base_url("badkey".into(), "page1".into(), url);
+        let err = client.verify_credentials().await.unwrap_err();
+        assert!(err.to_string().contains("401"));
+
+        ok.assert_async().await;
+        rejected.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn create_component_returns_new_id() {
+        let mut server = Server::new_async().await;
+
+        let mock = server
+            .mock("POST", "/v1/page1/components")
+            .match_header("authorization", "Bearer testkey")
+            .match_body(Matcher::Json(json!({ "name": "Public API", "status": "OPERATIONAL" })))
+            .with_status(200)
+            .with_body(r#"{"id":"comp9","name":"Public API","status":"OPERATIONAL"}"#)
+            .create_async()
+            .await;
+
+        let client =
+            Client::with_base_url("testkey".into(), "page1".into(), server.url().parse().unwrap());
+        assert_eq!(client.create_component("Public API").await.unwrap(), "comp9");
+        mock.assert_async().await;
+    }
+
     #[test]
     fn incidents_url_encodes_multiple_statuses() {
         let client = Client::with_base_url(
@@ -1,10 +1,14 @@
 //! Shared helpers for incident payloads and operations with retry.
 use chrono::{DateTime, Utc};
+use clickhouse::IncidentLogRow;
 use eyre::Result;
+use primitives::incident::IncidentAction;
 use tracing::{debug, error, info, warn};
 
 use crate::{
+    audit::{log_row, update_action},
     client::Client as IncidentClient,
+    maintenance::Suppressed,
     monitor::{ComponentStatus, IncidentState, NewIncident, ResolveIncident},
     retry::retry_op,
 };
@@ -39,11 +43,25 @@ pub fn build_resolve_payload(component_id: &str) -> ResolveIncident {
 }
 
 /// Create an incident using retry and consistent logging. Honors dry-run via `reporting_enabled`.
+///
+/// Fails with [`Suppressed`] while a maintenance window covers one of the payload's components,
+/// so the monitor does not track an incident and raises it again once the window ends.
 pub async fn create_with_retry(
     client: &IncidentClient,
     reporting_enabled: bool,
     payload: &NewIncident,
 ) -> Result<String> {
+    if let Some(window) = client.maintenance().active_for(&payload.components, Utc::now()) {
+        warn!(
+            name = %payload.name,
+            message = %payload.message,
+            components = ?payload.components,
+            until = %window.end,
+            "Maintenance window active - suppressed incident"
+        );
+        return Err(Suppressed { until: window.end }.into());
+    }
+
     if reporting_enabled {
         let id = retry_op(|| async { client.create_incident(payload).await }).await?;
         info!(
@@ -65,6 +83,15 @@ pub async fn create_with_retry(
             components = ?payload.components,
             "Instatus monitors disabled - would create incident"
         );
+        let row = log_row(
+            IncidentAction::Create,
+            &synthetic_id,
+            &payload.components,
+            &payload.name,
+            payload.status,
+            payload,
+        );
+        client.audit(IncidentLogRow { dry_run: 1, ..row }).await;
         Ok(synthetic_id)
     }
 }
@@ -80,6 +107,15 @@ pub async fn resolve_with_retry(
 
     if !reporting_enabled {
         info!(%id, components = ?payload.components, "Instatus monitors disabled - would resolve incident");
+        let row = log_row(
+            update_action(payload.status),
+            id,
+            &payload.components,
+            "",
+            payload.status,
+            payload,
+        );
+        client.audit(IncidentLogRow { dry_run: 1, ..row }).await;
         return Ok(());
     }
 
@@ -1,17 +1,34 @@
 //! Incident crate: Instatus integration and helpers.
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
+/// Audit log of incident operations
+pub mod audit;
 /// Base monitor implementation
 pub mod base_monitor;
 /// Instatus client
 pub mod client;
 /// Shared helpers for payload building and retrying operations
 pub mod helpers;
+/// Scheduled maintenance windows suppressing incident creation
+pub mod maintenance;
 /// Monitor polling and orchestration for Instatus incidents
 pub mod monitor;
+/// Creation of missing Instatus components
+pub mod provision;
 /// Retry helpers for HTTP operations
 pub mod retry;
+/// Persistence of monitor state across restarts
+pub mod state;
 
 // Re-export monitors for easy access
 pub use base_monitor::Monitor;
-pub use monitor::{BatchProofTimeoutMonitor, InstatusL1Monitor, InstatusMonitor};
+pub use maintenance::{MaintenanceSchedule, MaintenanceWindow};
+pub use monitor::{
+    AnchorMismatchMonitor, AnomalyMonitor, BatchCadenceMonitor, BatchProofTimeoutMonitor,
+    BondBalanceMonitor, CandidateChurnMonitor, ChainHeads, ForcedInclusionMonitor,
+    GasSaturationMonitor, InstatusL1Monitor, InstatusMonitor, OperatorBalanceMonitor,
+    PendingProposalMonitor, PipelineMonitor, PublicRpcMonitor, RpcEndpoint,
+    VerificationBacklogMonitor,
+};
+pub use provision::{ComponentStore, provision_components};
+pub use state::StateStore;
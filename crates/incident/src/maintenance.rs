@@ -0,0 +1,186 @@
+//! Scheduled maintenance windows
+//!
+//! While a window covering a component is active, incidents for that component are not created.
+//! The monitor logs the suppressed alert and keeps evaluating health, so an outage that outlasts
+//! the window is reported on the first check after it ends.
+
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use chrono::{DateTime, Utc};
+use eyre::{Result, eyre};
+use serde::{Deserialize, Serialize};
+
+/// A period during which incidents for some or all components are suppressed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceWindow {
+    /// Start of the window (inclusive)
+    pub start: DateTime<Utc>,
+    /// End of the window (exclusive)
+    pub end: DateTime<Utc>,
+    /// Components covered by the window; every component when empty
+    #[serde(default)]
+    pub components: Vec<String>,
+}
+
+impl MaintenanceWindow {
+    /// Check that the window ends after it starts
+    pub fn validate(&self) -> Result<()> {
+        if self.end <= self.start {
+            return Err(eyre!("maintenance window ends at {} before it starts", self.end));
+        }
+        Ok(())
+    }
+
+    /// Whether the window suppresses incidents for `component` at `at`
+    pub fn covers(&self, component: &str, at: DateTime<Utc>) -> bool {
+        self.start <= at &&
+            at < self.end &&
+            (self.components.is_empty() || self.components.iter().any(|c| c == component))
+    }
+}
+
+/// Parses `START/END[/COMPONENT+COMPONENT...]` with RFC 3339 timestamps, e.g.
+/// `2025-01-01T10:00:00Z/2025-01-01T12:00:00Z/comp-a+comp-b`.
+impl FromStr for MaintenanceWindow {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.trim().splitn(3, '/');
+        let (Some(start), Some(end)) = (parts.next(), parts.next()) else {
+            return Err(eyre!("invalid maintenance window '{s}': expected START/END[/COMPONENTS]"));
+        };
+        let parse = |ts: &str| {
+            DateTime::parse_from_rfc3339(ts)
+                .map(|ts| ts.with_timezone(&Utc))
+                .map_err(|e| eyre!("invalid maintenance window timestamp '{ts}': {e}"))
+        };
+        let components = parts
+            .next()
+            .map(|list| list.split('+').filter(|c| !c.is_empty()).map(str::to_owned).collect())
+            .unwrap_or_default();
+        let window = Self { start: parse(start)?, end: parse(end)?, components };
+        window.validate()?;
+        Ok(window)
+    }
+}
+
+/// Error returned instead of creating an incident during a maintenance window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suppressed {
+    /// End of the window suppressing the incident
+    pub until: DateTime<Utc>,
+}
+
+impl fmt::Display for Suppressed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "incident suppressed by maintenance window until {}", self.until.to_rfc3339())
+    }
+}
+
+impl std::error::Error for Suppressed {}
+
+/// Maintenance windows shared by every clone of the incident client, so windows added at
+/// runtime apply to all monitors.
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceSchedule {
+    windows: Arc<RwLock<Vec<MaintenanceWindow>>>,
+}
+
+impl MaintenanceSchedule {
+    /// Create a schedule with the given windows
+    pub fn new(windows: Vec<MaintenanceWindow>) -> Self {
+        Self { windows: Arc::new(RwLock::new(windows)) }
+    }
+
+    /// Add a window, dropping windows that have already ended
+    pub fn add(&self, window: MaintenanceWindow) -> Result<()> {
+        window.validate()?;
+        let now = Utc::now();
+        let mut windows = self.windows.write().unwrap_or_else(|e| e.into_inner());
+        windows.retain(|w| w.end > now);
+        windows.push(window);
+        Ok(())
+    }
+
+    /// Remove every window, returning how many were removed
+    pub fn clear(&self) -> usize {
+        let mut windows = self.windows.write().unwrap_or_else(|e| e.into_inner());
+        std::mem::take(&mut *windows).len()
+    }
+
+    /// All configured windows, including past and upcoming ones
+    pub fn windows(&self) -> Vec<MaintenanceWindow> {
+        self.windows.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// The window suppressing incidents for any of `components` at `at`, ending last if several
+    /// overlap
+    pub fn active_for(
+        &self,
+        components: &[String],
+        at: DateTime<Utc>,
+    ) -> Option<MaintenanceWindow> {
+        self.windows
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .filter(|w| components.iter().any(|c| w.covers(c, at)))
+            .max_by_key(|w| w.end)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ts(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_window_with_components() {
+        let window: MaintenanceWindow =
+            "2025-01-01T10:00:00Z/2025-01-01T12:00:00Z/comp-a+comp-b".parse().unwrap();
+        assert_eq!(window.start, ts(10));
+        assert_eq!(window.end, ts(12));
+        assert_eq!(window.components, vec!["comp-a", "comp-b"]);
+
+        let window: MaintenanceWindow =
+            "2025-01-01T10:00:00Z/2025-01-01T12:00:00Z".parse().unwrap();
+        assert!(window.components.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_windows() {
+        assert!("2025-01-01T10:00:00Z".parse::<MaintenanceWindow>().is_err());
+        assert!("yesterday/today".parse::<MaintenanceWindow>().is_err());
+        assert!("2025-01-01T12:00:00Z/2025-01-01T10:00:00Z".parse::<MaintenanceWindow>().is_err());
+    }
+
+    #[test]
+    fn suppresses_only_covered_components_inside_window() {
+        let schedule = MaintenanceSchedule::new(vec![MaintenanceWindow {
+            start: ts(10),
+            end: ts(12),
+            components: vec!["comp-a".to_owned()],
+        }]);
+        let comp_a = ["comp-a".to_owned()];
+
+        assert!(schedule.active_for(&comp_a, ts(9)).is_none());
+        assert_eq!(schedule.active_for(&comp_a, ts(10)).map(|w| w.end), Some(ts(12)));
+        assert!(schedule.active_for(&comp_a, ts(12)).is_none());
+        assert!(schedule.active_for(&["comp-b".to_owned()], ts(11)).is_none());
+    }
+
+    #[test]
+    fn window_without_components_covers_all() {
+        let window = MaintenanceWindow { start: ts(10), end: ts(12), components: Vec::new() };
+        assert!(window.covers("anything", ts(11)));
+    }
+}
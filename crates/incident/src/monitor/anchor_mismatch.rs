@@ -0,0 +1,142 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{AnchorMismatchRow, ClickhouseReader};
+use eyre::Result;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Monitors batches anchored to an L1 block hash that differs from the indexed L1 chain.
+///
+/// Every `interval` the anchor mismatches recorded during the last `window` are read from
+/// `anchor_mismatches`. Any mismatch is a data-integrity failure, so an incident is opened as soon
+/// as one is recorded. Resolves once no mismatch was recorded for a whole window.
+#[derive(Debug)]
+pub struct AnchorMismatchMonitor {
+    pub(crate) base: BaseMonitor<()>,
+    window: Duration,
+}
+
+impl AnchorMismatchMonitor {
+    /// Creates a new `AnchorMismatchMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        window: Duration,
+        interval: Duration,
+    ) -> Self {
+        Self { base: BaseMonitor::new(clickhouse, client, component_id, interval), window }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the mismatches recorded during the window, oldest first.
+    pub(crate) async fn handle(&mut self, mismatches: &[AnchorMismatchRow]) -> Result<()> {
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(mismatches = mismatches.len(), has_active, "Anchor mismatch status");
+
+        match mismatches.last() {
+            Some(latest) if !has_active => {
+                warn!(
+                    batch_id = latest.batch_id,
+                    anchor_block_id = latest.anchor_block_id,
+                    "Batch anchor block hash mismatch"
+                );
+                let id = self.open(mismatches.len(), latest).await?;
+                self.base.active_incidents.insert((), id);
+            }
+            None if has_active => {
+                self.base.mark_healthy(&()).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, count: usize, latest: &AnchorMismatchRow) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Batch anchor hash mismatch".into(),
+            format!(
+                "{count} batch(es) committed to an anchor block hash that differs from the indexed \
+                 L1 block in the last {}m, latest batch {} anchored to L1 block {}",
+                self.window.as_secs() / 60,
+                latest.batch_id,
+                latest.anchor_block_id,
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+#[async_trait]
+impl Monitor for AnchorMismatchMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Batch anchor hash mismatch".into(),
+            "A batch committed to an anchor block hash that differs from the indexed L1 block"
+                .into(),
+            Utc::now(),
+        );
+        self.base.create_incident_with_payload(&body).await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let until = Utc::now();
+        let since = until - chrono::Duration::seconds(self.window.as_secs() as i64);
+        let mismatches = self.base.clickhouse.get_anchor_mismatches_range(since, until).await?;
+        self.handle(&mismatches).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for AnchorMismatchMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
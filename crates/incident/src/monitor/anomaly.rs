@@ -0,0 +1,151 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{AnomalyRow, ClickhouseReader};
+use eyre::Result;
+use primitives::anomaly::AnomalyMetric;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Monitors sudden changes of the core chain metrics.
+///
+/// Every `interval` the anomalies recorded by the anomaly detector during the last `window` are
+/// read from `anomalies`. An incident is opened as soon as one is recorded, naming the metrics
+/// that deviated. Resolves once no anomaly was recorded for a whole window.
+#[derive(Debug)]
+pub struct AnomalyMonitor {
+    pub(crate) base: BaseMonitor<()>,
+    window: Duration,
+}
+
+impl AnomalyMonitor {
+    /// Creates a new `AnomalyMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        window: Duration,
+        interval: Duration,
+    ) -> Self {
+        Self { base: BaseMonitor::new(clickhouse, client, component_id, interval), window }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the anomalies recorded during the window, oldest first.
+    pub(crate) async fn handle(&mut self, anomalies: &[AnomalyRow]) -> Result<()> {
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(anomalies = anomalies.len(), has_active, "Metric anomaly status");
+
+        match anomalies.last() {
+            Some(latest) if !has_active => {
+                warn!(
+                    metric = metric_name(latest.metric),
+                    value = latest.value,
+                    z_score = latest.z_score,
+                    "Metric anomaly"
+                );
+                let id = self.open(anomalies).await?;
+                self.base.active_incidents.insert((), id);
+            }
+            None if has_active => {
+                self.base.mark_healthy(&()).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, anomalies: &[AnomalyRow]) -> Result<String> {
+        let mut metrics: Vec<&str> = anomalies.iter().map(|row| metric_name(row.metric)).collect();
+        metrics.sort_unstable();
+        metrics.dedup();
+
+        let body = self.base.create_incident_payload(
+            "Metric anomaly".into(),
+            format!(
+                "{} sudden change(s) of {} detected in the last {}m",
+                anomalies.len(),
+                metrics.join(", "),
+                self.window.as_secs() / 60,
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+/// Name of the metric stored as `code`
+fn metric_name(code: u8) -> &'static str {
+    AnomalyMetric::from_code(code).map_or("unknown metric", AnomalyMetric::as_str)
+}
+
+#[async_trait]
+impl Monitor for AnomalyMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Metric anomaly".into(),
+            "A core chain metric changed sharply from its moving average".into(),
+            Utc::now(),
+        );
+        self.base.create_incident_with_payload(&body).await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let until = Utc::now();
+        let since = until - chrono::Duration::seconds(self.window.as_secs() as i64);
+        let anomalies = self.base.clickhouse.get_anomalies_range(since, until).await?;
+        self.handle(&anomalies).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for AnomalyMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{BatchCadenceRow, ClickhouseReader, TimeRange};
+use eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Window over which the posting and verification cadence is measured
+const CADENCE_WINDOW: TimeRange = TimeRange::LastHour;
+
+/// Inbox limit a proposer risks hitting, reverting its proposals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CadenceRisk {
+    /// More than `maxUnverifiedBatches` batches would be unverified (`TooManyBatches`)
+    TooManyBatches,
+    /// A batch would anchor to an L1 block older than `maxAnchorHeightOffset`
+    AnchorAge,
+}
+
+/// Headroom of the batch posting cadence against the inbox limits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CadenceStatus {
+    /// Proposed batches that are not verified yet
+    pub unverified_batches: u64,
+    /// Unverified batches the inbox accepts
+    pub max_unverified_batches: u64,
+    /// Seconds until the unverified batch limit is reached at the current cadence, `None` when
+    /// the backlog is not growing
+    pub seconds_to_limit: Option<u64>,
+    /// Largest anchor age in L1 blocks of the recently proposed batches
+    pub max_anchor_age: Option<u64>,
+    /// Anchor age in L1 blocks the inbox accepts
+    pub max_anchor_height_offset: u64,
+}
+
+impl CadenceStatus {
+    /// Compare the cadence measured over `window` against the inbox limits.
+    pub fn new(
+        cadence: &BatchCadenceRow,
+        max_unverified_batches: u64,
+        max_anchor_height_offset: u64,
+        window: Duration,
+    ) -> Self {
+        let unverified_batches =
+            cadence.last_proposed_batch_id.saturating_sub(cadence.last_verified_batch_id);
+        let headroom = max_unverified_batches.saturating_sub(unverified_batches);
+        let growth = cadence.proposed_batches.saturating_sub(cadence.verified_batches);
+        let seconds_to_limit =
+            (growth > 0).then(|| headroom.saturating_mul(window.as_secs()) / growth);
+        Self {
+            unverified_batches,
+            max_unverified_batches,
+            seconds_to_limit,
+            max_anchor_age: cadence.max_anchor_age,
+            max_anchor_height_offset,
+        }
+    }
+
+    /// Limits that are hit, or hit within `warning` at the current cadence. Anchor ages of at
+    /// least `anchor_age_pct` percent of the offset are reported.
+    pub fn risks(&self, warning: Duration, anchor_age_pct: u64) -> Vec<CadenceRisk> {
+        let mut risks = Vec::new();
+        if self.max_unverified_batches > 0 &&
+            (self.unverified_batches >= self.max_unverified_batches ||
+                self.seconds_to_limit.is_some_and(|secs| secs <= warning.as_secs()))
+        {
+            risks.push(CadenceRisk::TooManyBatches);
+        }
+        if self.max_anchor_height_offset > 0 &&
+            self.max_anchor_age.is_some_and(|age| {
+                age.saturating_mul(100) >=
+                    self.max_anchor_height_offset.saturating_mul(anchor_age_pct)
+            })
+        {
+            risks.push(CadenceRisk::AnchorAge);
+        }
+        risks
+    }
+}
+
+/// Monitors the batch posting cadence against the inbox limits.
+///
+/// Every `interval` the proposal and verification cadence of the last hour is compared against
+/// `maxUnverifiedBatches` and `maxAnchorHeightOffset` from the stored protocol configuration. An
+/// incident is opened per limit while the proposer hits it, or would hit the unverified batch
+/// limit within `warning` at the current cadence, so operators can react before proposals revert
+/// with `TooManyBatches` or an anchor that is too old.
+#[derive(Debug)]
+pub struct BatchCadenceMonitor {
+    pub(crate) base: BaseMonitor<CadenceRisk>,
+    warning: Duration,
+    anchor_age_pct: u64,
+}
+
+impl BatchCadenceMonitor {
+    /// Creates a new `BatchCadenceMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        warning: Duration,
+        anchor_age_pct: u64,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            base: BaseMonitor::new(clickhouse, client, component_id, interval),
+            warning,
+            anchor_age_pct,
+        }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Open incidents for new risks and resolve those of limits no longer at risk.
+    pub(crate) async fn handle(&mut self, status: &CadenceStatus) -> Result<()> {
+        let risks = status.risks(self.warning, self.anchor_age_pct);
+        debug!(?status, ?risks, "Batch cadence status");
+
+        for risk in &risks {
+            if !self.base.active_incidents.contains_key(risk) {
+                warn!(?risk, ?status, "Batch posting cadence at risk of hitting inbox limits");
+                let id = self.open(*risk, status).await?;
+                self.base.active_incidents.insert(*risk, id);
+            }
+        }
+        let cleared: Vec<CadenceRisk> = self
+            .base
+            .active_incidents
+            .keys()
+            .filter(|risk| !risks.contains(risk))
+            .copied()
+            .collect();
+        for risk in cleared {
+            self.base.mark_healthy(&risk).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, risk: CadenceRisk, status: &CadenceStatus) -> Result<String> {
+        let (name, message) = match risk {
+            CadenceRisk::TooManyBatches => (
+                "Unverified batch limit approaching",
+                format!(
+                    "{} of {} allowed batches are unverified{}, proposals will revert with \
+                     TooManyBatches once the limit is reached",
+                    status.unverified_batches,
+                    status.max_unverified_batches,
+                    status
+                        .seconds_to_limit
+                        .map(|secs| format!(" and the limit is reached in ~{}m", secs / 60))
+                        .unwrap_or_default(),
+                ),
+            ),
+            CadenceRisk::AnchorAge => (
+                "Batch anchor age approaching limit",
+                format!(
+                    "Recent batches anchor to L1 blocks up to {} blocks old (limit: {}), \
+                     proposals will revert once the anchor is too old",
+                    status.max_anchor_age.unwrap_or_default(),
+                    status.max_anchor_height_offset,
+                ),
+            ),
+        };
+        let body = self.base.create_incident_payload(name.into(), message, Utc::now());
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+#[async_trait]
+impl Monitor for BatchCadenceMonitor {
+    type IncidentKey = CadenceRisk;
+
+    async fn create_incident(&self, key: &Self::IncidentKey) -> Result<String> {
+        self.open(*key, &CadenceStatus::default()).await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let Some(config) = self.base.clickhouse.get_protocol_config().await? else {
+            debug!("No protocol configuration stored yet, skipping batch cadence check");
+            return Ok(());
+        };
+        let cadence = self.base.clickhouse.get_batch_cadence(CADENCE_WINDOW).await?;
+        let status = CadenceStatus::new(
+            &cadence,
+            config.max_unverified_batches,
+            config.max_anchor_height_offset,
+            Duration::from_secs(CADENCE_WINDOW.seconds()),
+        );
+        self.handle(&status).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(CadenceRisk::TooManyBatches).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for BatchCadenceMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     base_monitor::{BaseMonitor, Monitor},
     client::Client as IncidentClient,
+    state::StateStore,
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
@@ -31,6 +32,12 @@ impl BatchProofTimeoutMonitor {
         Self { base: BaseMonitor::new(clickhouse, client, component_id, interval), proof_timeout }
     }
 
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
     /// Check if a specific batch has been proven
     async fn is_batch_proven(&self, batch_id: u64) -> Result<bool> {
         let proved_batch_ids = self.get_proved_batch_ids().await?;
@@ -185,6 +192,7 @@ impl Monitor for BatchProofTimeoutMonitor {
             if let Err(e) = self.check_health().await {
                 error!(error = %e, "monitoring check failed for BatchProofTimeoutMonitor");
             }
+            self.base.persist_state();
         }
     }
 
@@ -1,22 +1,46 @@
 use crate::{
     base_monitor::{BaseMonitor, Monitor},
     client::Client as IncidentClient,
+    state::StateStore,
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use clickhouse::ClickhouseReader;
 use eyre::Result;
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 use tracing::{debug, error, info};
 
+/// Time after which an unverified batch is overdue.
+///
+/// A batch can only be verified once the protocol cooldown window has passed since its proof, so
+/// a proved batch is given until `proved_at + cooldown_with_margin` even when that is later than
+/// `posted_at + verify_timeout`.
+pub fn verify_deadline(
+    posted_at: DateTime<Utc>,
+    proved_at: Option<DateTime<Utc>>,
+    verify_timeout: Duration,
+    cooldown_with_margin: Duration,
+) -> Result<DateTime<Utc>> {
+    let timeout_deadline = posted_at + ChronoDuration::from_std(verify_timeout)?;
+    Ok(match proved_at {
+        Some(proved_at) => {
+            timeout_deadline.max(proved_at + ChronoDuration::from_std(cooldown_with_margin)?)
+        }
+        None => timeout_deadline,
+    })
+}
+
 /// Monitors batches that take too long to be verified (> X hours after being posted).
-/// Creates incidents for batches that have been posted but not verified within the time threshold.
+/// Creates incidents for batches that have been posted but not verified within the time threshold,
+/// giving proved batches at least the protocol cooldown window to be verified.
 #[derive(Debug)]
 pub struct BatchVerifyTimeoutMonitor {
     /// Base monitor implementation
     base: BaseMonitor<u64>,
     /// Timeout threshold for batch verification
     verify_timeout: Duration,
+    /// Cooldown window plus safety margin that must pass after a proof before verification
+    cooldown_with_margin: Duration,
 }
 
 impl BatchVerifyTimeoutMonitor {
@@ -28,7 +52,24 @@ impl BatchVerifyTimeoutMonitor {
         verify_timeout: Duration,
         interval: Duration,
     ) -> Self {
-        Self { base: BaseMonitor::new(clickhouse, client, component_id, interval), verify_timeout }
+        Self {
+            base: BaseMonitor::new(clickhouse, client, component_id, interval),
+            verify_timeout,
+            cooldown_with_margin: Duration::ZERO,
+        }
+    }
+
+    /// Waits for the protocol `cooldown` window plus `margin` after a batch is proved before
+    /// flagging it as overdue.
+    pub fn with_cooldown(mut self, cooldown: Duration, margin: Duration) -> Self {
+        self.cooldown_with_margin = cooldown + margin;
+        self
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
     }
 
     /// Check if a specific batch has been verified
@@ -78,14 +119,35 @@ impl BatchVerifyTimeoutMonitor {
         &mut self,
         unverified_batches: &[(u64, u64, DateTime<Utc>)],
     ) -> Result<()> {
+        let candidates: Vec<u64> = unverified_batches
+            .iter()
+            .map(|(_, batch_id, _)| *batch_id)
+            .filter(|batch_id| !self.base.active_incidents.contains_key(batch_id))
+            .collect();
+        let proved_times: HashMap<u64, DateTime<Utc>> = if self.cooldown_with_margin.is_zero() {
+            HashMap::new()
+        } else {
+            self.base.clickhouse.get_batch_proved_times(&candidates).await?.into_iter().collect()
+        };
+
+        let now = Utc::now();
         for (_l1_block_number, batch_id, posted_at) in unverified_batches {
-            let age_duration = Utc::now().signed_duration_since(*posted_at);
-            if age_duration > ChronoDuration::from_std(self.verify_timeout)? &&
-                !self.base.active_incidents.contains_key(batch_id)
-            {
+            if self.base.active_incidents.contains_key(batch_id) {
+                continue;
+            }
+            let proved_at = proved_times.get(batch_id).copied();
+            let deadline = verify_deadline(
+                *posted_at,
+                proved_at,
+                self.verify_timeout,
+                self.cooldown_with_margin,
+            )?;
+            if now > deadline {
+                let age_duration = now.signed_duration_since(*posted_at);
                 debug!(
                     batch_id = batch_id,
                     posted_at = %posted_at,
+                    proved_at = ?proved_at,
                     age_hours = age_duration.num_hours(),
                     "Found unverified batch exceeding timeout",
                 );
@@ -204,6 +266,7 @@ impl Monitor for BatchVerifyTimeoutMonitor {
             if let Err(e) = self.check_health().await {
                 error!(error = %e, "monitoring check failed for BatchVerifyTimeoutMonitor");
             }
+            self.base.persist_state();
         }
     }
 
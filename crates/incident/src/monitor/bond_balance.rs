@@ -0,0 +1,156 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{AddressBytes, BondBalanceRow, ClickhouseReader, TimeRange};
+use eyre::Result;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Window in which an account must have proposed a batch to be checked
+const ACTIVE_WINDOW: TimeRange = TimeRange::Last24Hours;
+
+/// Monitors the inbox bond balances of the active proposers.
+///
+/// Reads the latest balances from the `bond_ledger` table, written by the driver's bond ledger
+/// task, every `interval`. An incident is opened while any account that proposed a batch in the
+/// last 24 hours holds less than the liveness bonds of `min_batches` full batches, since its
+/// proposals revert with `InsufficientBond` once the balance runs out. Resolves once every
+/// active proposer is topped up.
+#[derive(Debug)]
+pub struct BondBalanceMonitor {
+    pub(crate) base: BaseMonitor<()>,
+    min_batches: u64,
+}
+
+impl BondBalanceMonitor {
+    /// Creates a new `BondBalanceMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        min_batches: u64,
+        interval: Duration,
+    ) -> Self {
+        Self { base: BaseMonitor::new(clickhouse, client, component_id, interval), min_batches }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the latest bond balances of the `proposers` active in the window, given the bond
+    /// of a full batch. Without a known protocol configuration nothing is opened or resolved.
+    pub(crate) async fn handle(
+        &mut self,
+        balances: &[BondBalanceRow],
+        proposers: &[AddressBytes],
+        full_batch_bond: Option<u128>,
+    ) -> Result<()> {
+        let Some(full_batch_bond) = full_batch_bond else {
+            debug!("Protocol configuration not recorded yet, skipping bond balance check");
+            return Ok(());
+        };
+        let threshold = full_batch_bond.saturating_mul(self.min_batches.into());
+
+        let low: Vec<String> = balances
+            .iter()
+            .filter(|row| proposers.contains(&row.address) && row.balance < threshold)
+            .map(|row| format_address(&row.address))
+            .collect();
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(proposers = proposers.len(), threshold, low = ?low, has_active, "Bond balance status");
+
+        if !low.is_empty() && !has_active {
+            warn!(proposers = ?low, threshold, "Proposer bond balances running low");
+            let id = self.open(&low.join(", ")).await?;
+            self.base.active_incidents.insert((), id);
+        } else if low.is_empty() && has_active {
+            self.base.mark_healthy(&()).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, proposers: &str) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Proposer bond balance low".into(),
+            format!(
+                "Inbox bond balance of active proposers covers fewer than {} full batches, \
+                 proposals will revert once it runs out: {proposers}",
+                self.min_batches
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+/// Format an address as a 0x-prefixed hex string
+fn format_address(address: &AddressBytes) -> String {
+    address.as_bytes().iter().fold(String::from("0x"), |mut out, byte| {
+        out.push_str(&format!("{byte:02x}"));
+        out
+    })
+}
+
+#[async_trait]
+impl Monitor for BondBalanceMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        self.open("unknown proposers").await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let config = self.base.clickhouse.get_protocol_config().await?;
+        let proposers = self.base.clickhouse.get_recent_proposers(ACTIVE_WINDOW).await?;
+        let balances = self.base.clickhouse.get_bond_balances().await?;
+        self.handle(&balances, &proposers, config.map(|c| c.full_batch_bond())).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for BondBalanceMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
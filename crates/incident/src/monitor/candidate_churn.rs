@@ -0,0 +1,168 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{ClickhouseReader, OperatorHistoryRow};
+use eyre::Result;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Monitors changes to the whitelist operator candidates.
+///
+/// Every `interval` the candidate changes recorded during the last `window` and the latest
+/// snapshot are read from `operator_history`, written by the driver's operator history task. An
+/// incident is opened when candidates were added or removed during the window, or while fewer
+/// than `min_candidates` remain. Resolves once the candidates were stable for a whole window and
+/// enough of them remain.
+#[derive(Debug)]
+pub struct CandidateChurnMonitor {
+    pub(crate) base: BaseMonitor<()>,
+    window: Duration,
+    min_candidates: usize,
+}
+
+impl CandidateChurnMonitor {
+    /// Creates a new `CandidateChurnMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        window: Duration,
+        min_candidates: usize,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            base: BaseMonitor::new(clickhouse, client, component_id, interval),
+            window,
+            min_candidates,
+        }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the changes recorded during the window, oldest first, and the latest snapshot.
+    /// Without any snapshot yet, incidents are neither opened nor resolved.
+    pub(crate) async fn handle(
+        &mut self,
+        changes: &[OperatorHistoryRow],
+        latest: Option<&OperatorHistoryRow>,
+    ) -> Result<()> {
+        let Some(latest) = latest else {
+            debug!("No operator candidate snapshots yet");
+            return Ok(());
+        };
+
+        let churn: Vec<&OperatorHistoryRow> = changes.iter().filter(|row| row.is_churn()).collect();
+        let too_few = latest.candidates.len() < self.min_candidates;
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(
+            changes = churn.len(),
+            candidates = latest.candidates.len(),
+            too_few,
+            has_active,
+            "Operator candidate status"
+        );
+
+        if (!churn.is_empty() || too_few) && !has_active {
+            let added: usize = churn.iter().map(|row| row.added.len()).sum();
+            let removed: usize = churn.iter().map(|row| row.removed.len()).sum();
+            warn!(
+                added,
+                removed,
+                candidates = latest.candidates.len(),
+                min_candidates = self.min_candidates,
+                "Whitelist operator candidates changed or below minimum"
+            );
+            let id = self.open(added, removed, latest.candidates.len()).await?;
+            self.base.active_incidents.insert((), id);
+        } else if churn.is_empty() && !too_few && has_active {
+            self.base.mark_healthy(&()).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, added: usize, removed: usize, candidates: usize) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Operator candidates changed".into(),
+            format!(
+                "{added} operator candidate(s) added and {removed} removed from the preconfirmation \
+                 whitelist in the last {}m, {candidates} candidate(s) remain (minimum {})",
+                self.window.as_secs() / 60,
+                self.min_candidates,
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+#[async_trait]
+impl Monitor for CandidateChurnMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Operator candidates changed".into(),
+            "The preconfirmation whitelist operator candidates changed or fell below the minimum"
+                .into(),
+            Utc::now(),
+        );
+        self.base.create_incident_with_payload(&body).await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let until = Utc::now();
+        let since = until - chrono::Duration::seconds(self.window.as_secs() as i64);
+        let changes = self.base.clickhouse.get_operator_history_range(since, until).await?;
+        let latest = self.base.clickhouse.get_latest_operator_history().await?;
+        self.handle(&changes, latest.as_ref()).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for CandidateChurnMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{ClickhouseReader, ForcedInclusionQueueRow};
+use eyre::Result;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Monitors the forced inclusion queue.
+///
+/// Reads the latest snapshot of the `forced_inclusion_queue` table, written by the driver's
+/// forced inclusion queue task, every `interval`. An incident is opened while the oldest queued
+/// forced inclusion is within the configured number of batches of its deadline, since proposals
+/// that do not include it revert once the deadline passes. Resolves once the item is included.
+#[derive(Debug)]
+pub struct ForcedInclusionMonitor {
+    pub(crate) base: BaseMonitor<()>,
+}
+
+impl ForcedInclusionMonitor {
+    /// Creates a new `ForcedInclusionMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        interval: Duration,
+    ) -> Self {
+        Self { base: BaseMonitor::new(clickhouse, client, component_id, interval) }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the latest snapshot. Without a snapshot incidents are neither opened nor resolved.
+    pub(crate) async fn handle(
+        &mut self,
+        snapshot: Option<&ForcedInclusionQueueRow>,
+    ) -> Result<()> {
+        let Some(snapshot) = snapshot else {
+            debug!("No forced inclusion queue snapshots yet");
+            return Ok(());
+        };
+
+        let near_deadline = snapshot.near_deadline == 1;
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(
+            pending = snapshot.pending,
+            oldest_age_secs = ?snapshot.oldest_age_secs,
+            near_deadline,
+            has_active,
+            "Forced inclusion queue status"
+        );
+
+        if near_deadline && !has_active {
+            warn!(
+                pending = snapshot.pending,
+                deadline_batch_id = ?snapshot.deadline_batch_id,
+                next_batch_id = snapshot.next_batch_id,
+                "Oldest forced inclusion approaching its deadline"
+            );
+            let id = self.open(&describe(snapshot)).await?;
+            self.base.active_incidents.insert((), id);
+        } else if !near_deadline && has_active {
+            self.base.mark_healthy(&()).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, details: &str) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Forced inclusion deadline approaching".into(),
+            format!(
+                "The oldest queued forced inclusion has not been included yet and proposals will \
+                 revert once its deadline passes: {details}"
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+/// Describe the queue state of `snapshot` for the incident message
+fn describe(snapshot: &ForcedInclusionQueueRow) -> String {
+    let deadline = snapshot
+        .deadline_batch_id
+        .map_or_else(|| "unknown".to_owned(), |batch_id| format!("batch {batch_id}"));
+    let age =
+        snapshot.oldest_age_secs.map_or_else(|| "unknown".to_owned(), |secs| format!("{secs}s"));
+    format!(
+        "{} pending, oldest waiting {age}, deadline {deadline}, next batch {}",
+        snapshot.pending, snapshot.next_batch_id
+    )
+}
+
+#[async_trait]
+impl Monitor for ForcedInclusionMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        self.open("queue state unknown").await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let snapshot = self.base.clickhouse.get_latest_forced_inclusion_queue().await?;
+        self.handle(snapshot.as_ref()).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for ForcedInclusionMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
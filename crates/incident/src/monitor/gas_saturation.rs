@@ -0,0 +1,146 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{ClickhouseReader, GasSaturationSummaryRow, TimeRange};
+use eyre::Result;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Monitors the gas saturation of L2 blocks.
+///
+/// Every `interval` the average gas used over gas limit of the L2 blocks of the last `window` is
+/// read from `l2_head_events`. An incident is opened while the average is at least
+/// `threshold_pct` percent, as blocks that stay full mean demand outstrips the chain capacity.
+/// Resolves once the average drops below the threshold.
+#[derive(Debug)]
+pub struct GasSaturationMonitor {
+    pub(crate) base: BaseMonitor<()>,
+    threshold_pct: u64,
+    window: Duration,
+}
+
+impl GasSaturationMonitor {
+    /// Creates a new `GasSaturationMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        threshold_pct: u64,
+        window: Duration,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            base: BaseMonitor::new(clickhouse, client, component_id, interval),
+            threshold_pct,
+            window,
+        }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the saturation of the window. A window without blocks neither opens nor resolves
+    /// incidents.
+    pub(crate) async fn handle(&mut self, summary: GasSaturationSummaryRow) -> Result<()> {
+        if summary.blocks == 0 {
+            debug!("No L2 blocks with a known gas limit in the saturation window");
+            return Ok(());
+        }
+
+        let saturated = summary.avg_saturation * 100.0 >= self.threshold_pct as f64;
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(
+            blocks = summary.blocks,
+            avg_saturation = summary.avg_saturation,
+            saturated,
+            has_active,
+            "Gas saturation status"
+        );
+
+        if saturated && !has_active {
+            warn!(avg_saturation = summary.avg_saturation, "Sustained L2 block gas saturation");
+            let id = self.open(summary.avg_saturation).await?;
+            self.base.active_incidents.insert((), id);
+        } else if !saturated && has_active {
+            self.base.mark_healthy(&()).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, avg_saturation: f64) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "L2 blocks saturated".into(),
+            format!(
+                "L2 blocks used {:.1}% of their gas limit on average over the last {}m, demand is \
+                 outstripping capacity",
+                avg_saturation * 100.0,
+                self.window.as_secs() / 60,
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+#[async_trait]
+impl Monitor for GasSaturationMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        self.open(self.threshold_pct as f64 / 100.0).await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let range = TimeRange::Custom(self.window.as_secs());
+        let summary = self.base.clickhouse.get_gas_saturation_summary(range).await?;
+        self.handle(summary).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for GasSaturationMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     base_monitor::{BaseMonitor, Monitor},
     client::Client as IncidentClient,
+    state::StateStore,
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
@@ -30,6 +31,12 @@ impl InstatusMonitor {
         Self { base: BaseMonitor::new(clickhouse, client, component_id, interval), threshold }
     }
 
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
     /// Handles a new L2 head event.
     pub(crate) async fn handle(&mut self, last: DateTime<Utc>) -> Result<()> {
         let age = Utc::now().signed_duration_since(last).to_std()?;
@@ -53,9 +60,7 @@ impl InstatusMonitor {
             }
             // up again
             (true, true) => {
-                if self.base.mark_healthy(&()).await? {
-                    // Incident resolved
-                }
+                self.base.mark_healthy(&()).await?;
             }
             _ => {}
         }
@@ -151,6 +156,7 @@ impl Monitor for InstatusMonitor {
             if let Err(e) = self.check_health().await {
                 error!(error = %e, "monitoring check failed for InstatusMonitor");
             }
+            self.base.persist_state();
         }
     }
 
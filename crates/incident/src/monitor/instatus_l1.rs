@@ -1,6 +1,7 @@
 use crate::{
     base_monitor::{BaseMonitor, Monitor},
     client::Client as IncidentClient,
+    state::StateStore,
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
@@ -31,6 +32,12 @@ impl InstatusL1Monitor {
         Self { base: BaseMonitor::new(clickhouse, client, component_id, interval), threshold }
     }
 
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
     /// Handle the status of batch events
     async fn handle(&mut self, last_batch: DateTime<Utc>, last_l2: DateTime<Utc>) -> Result<()> {
         let age_batch = Utc::now().signed_duration_since(last_batch).to_std()?;
@@ -72,9 +79,7 @@ impl InstatusL1Monitor {
             }
             // up again: close when stable
             (true, true, _) => {
-                if self.base.mark_healthy(&()).await? {
-                    // Incident resolved
-                }
+                self.base.mark_healthy(&()).await?;
             }
             _ => {}
         }
@@ -180,6 +185,7 @@ impl Monitor for InstatusL1Monitor {
             if let Err(e) = self.check_health().await {
                 error!(error = %e, "monitoring check failed for InstatusL1Monitor");
             }
+            self.base.persist_state();
         }
     }
 
@@ -14,6 +14,18 @@ pub enum IncidentState {
     Resolved,
 }
 
+impl IncidentState {
+    /// Status name as sent to Instatus.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Investigating => "INVESTIGATING",
+            Self::Identified => "IDENTIFIED",
+            Self::Monitoring => "MONITORING",
+            Self::Resolved => "RESOLVED",
+        }
+    }
+}
+
 /// Component health inside an incident update.
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "UPPERCASE")]
@@ -85,17 +97,39 @@ pub struct ResolveIncident {
     pub started: Option<String>,
 }
 
+mod anchor_mismatch;
+mod anomaly;
+mod batch_cadence;
 mod batch_proof_timeout;
 mod batch_verify_timeout;
+mod bond_balance;
+mod candidate_churn;
+mod forced_inclusion;
+mod gas_saturation;
 mod instatus;
 mod instatus_l1;
+mod operator_balance;
+mod pending_proposal;
+mod pipeline;
 mod public_rpc;
+mod verification_backlog;
 
+pub use anchor_mismatch::AnchorMismatchMonitor;
+pub use anomaly::AnomalyMonitor;
+pub use batch_cadence::{BatchCadenceMonitor, CadenceRisk, CadenceStatus};
 pub use batch_proof_timeout::BatchProofTimeoutMonitor;
-pub use batch_verify_timeout::BatchVerifyTimeoutMonitor;
+pub use batch_verify_timeout::{BatchVerifyTimeoutMonitor, verify_deadline};
+pub use bond_balance::BondBalanceMonitor;
+pub use candidate_churn::CandidateChurnMonitor;
+pub use forced_inclusion::ForcedInclusionMonitor;
+pub use gas_saturation::GasSaturationMonitor;
 pub use instatus::InstatusMonitor;
 pub use instatus_l1::InstatusL1Monitor;
-pub use public_rpc::spawn_public_rpc_monitor;
+pub use operator_balance::OperatorBalanceMonitor;
+pub use pending_proposal::PendingProposalMonitor;
+pub use pipeline::{ChainHeads, Ingestion, PipelineMonitor};
+pub use public_rpc::{ProbeOutcome, PublicRpcMonitor, RpcEndpoint};
+pub use verification_backlog::VerificationBacklogMonitor;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,140 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{ClickhouseReader, OperatorBalanceRow};
+use eyre::Result;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Monitors the L1 wallet balances of the whitelisted operators.
+///
+/// Reads the latest round of the `operator_balances` table, written by the driver's operator
+/// balance task, every `interval`. An incident is opened while any operator of the round is
+/// flagged as below its ETH or TAIKO threshold, since its proposals would fail once the wallet is
+/// empty. Resolves once every operator is topped up.
+#[derive(Debug)]
+pub struct OperatorBalanceMonitor {
+    pub(crate) base: BaseMonitor<()>,
+}
+
+impl OperatorBalanceMonitor {
+    /// Creates a new `OperatorBalanceMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        interval: Duration,
+    ) -> Self {
+        Self { base: BaseMonitor::new(clickhouse, client, component_id, interval) }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the latest snapshot round. An empty round neither opens nor resolves incidents.
+    pub(crate) async fn handle(&mut self, balances: &[OperatorBalanceRow]) -> Result<()> {
+        if balances.is_empty() {
+            debug!("No operator balance snapshots yet");
+            return Ok(());
+        }
+
+        let low: Vec<String> = balances
+            .iter()
+            .filter(|row| row.low_balance == 1)
+            .map(|row| format_address(&row.address))
+            .collect();
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(operators = balances.len(), low = ?low, has_active, "Operator balance status");
+
+        if !low.is_empty() && !has_active {
+            warn!(operators = ?low, "Operator wallet balances below threshold");
+            let id = self.open(&low.join(", ")).await?;
+            self.base.active_incidents.insert((), id);
+        } else if low.is_empty() && has_active {
+            self.base.mark_healthy(&()).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, operators: &str) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Operator wallet balance low".into(),
+            format!(
+                "L1 wallet balance of whitelisted operators is below the threshold, proposals \
+                 may fail: {operators}"
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+/// Format an address as a 0x-prefixed hex string
+fn format_address(address: &clickhouse::AddressBytes) -> String {
+    address.as_bytes().iter().fold(String::from("0x"), |mut out, byte| {
+        out.push_str(&format!("{byte:02x}"));
+        out
+    })
+}
+
+#[async_trait]
+impl Monitor for OperatorBalanceMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        self.open("unknown operators").await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let balances = self.base.clickhouse.get_operator_balances().await?;
+        self.handle(&balances).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for OperatorBalanceMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{ClickhouseReader, ProposalInclusionRow};
+use eyre::Result;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Age beyond which pending proposals are no longer considered, since the driver forgets them
+const LOOKBACK: Duration = Duration::from_secs(60 * 60);
+
+/// Monitors batch proposals stuck in the L1 mempool.
+///
+/// Every `interval` the proposals that the driver's proposal mempool task saw pending during the
+/// last hour and that were neither included nor dropped are read from `proposal_inclusions`. An
+/// incident is opened while one of them has been pending for longer than `threshold`, which
+/// usually means the proposer underpriced it. Resolves once none is pending for that long.
+#[derive(Debug)]
+pub struct PendingProposalMonitor {
+    pub(crate) base: BaseMonitor<()>,
+    threshold: Duration,
+}
+
+impl PendingProposalMonitor {
+    /// Creates a new `PendingProposalMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        threshold: Duration,
+        interval: Duration,
+    ) -> Self {
+        Self { base: BaseMonitor::new(clickhouse, client, component_id, interval), threshold }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the pending proposals, oldest first, at `now_ms`.
+    pub(crate) async fn handle(
+        &mut self,
+        pending: &[ProposalInclusionRow],
+        now_ms: u64,
+    ) -> Result<()> {
+        let threshold_ms = self.threshold.as_millis() as u64;
+        let stuck: Vec<&ProposalInclusionRow> = pending
+            .iter()
+            .filter(|row| now_ms.saturating_sub(row.first_seen_ms) >= threshold_ms)
+            .collect();
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(pending = pending.len(), stuck = stuck.len(), has_active, "Pending proposal status");
+
+        match stuck.first() {
+            Some(oldest) if !has_active => {
+                warn!(
+                    proposer = ?oldest.proposer,
+                    nonce = oldest.nonce,
+                    pending_secs = now_ms.saturating_sub(oldest.first_seen_ms) / 1000,
+                    max_fee_per_gas = oldest.max_fee_per_gas,
+                    "Batch proposal stuck in the L1 mempool"
+                );
+                let id = self.open(&describe(&stuck, now_ms)).await?;
+                self.base.active_incidents.insert((), id);
+            }
+            None if has_active => {
+                self.base.mark_healthy(&()).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, details: &str) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Batch proposal pending".into(),
+            format!(
+                "A batch proposal has been pending in the L1 mempool for more than {}s, likely \
+                 because it is underpriced: {details}",
+                self.threshold.as_secs()
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+/// Describe the oldest of the `stuck` proposals for the incident message
+fn describe(stuck: &[&ProposalInclusionRow], now_ms: u64) -> String {
+    let Some(oldest) = stuck.first() else {
+        return "no proposal details".to_owned();
+    };
+    format!(
+        "{} stuck, oldest nonce {} pending {}s with max fee {} wei/gas after {} replacement(s)",
+        stuck.len(),
+        oldest.nonce,
+        now_ms.saturating_sub(oldest.first_seen_ms) / 1000,
+        oldest.max_fee_per_gas,
+        oldest.replacements
+    )
+}
+
+#[async_trait]
+impl Monitor for PendingProposalMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        self.open("proposal details unknown").await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let now_ms = Utc::now().timestamp_millis() as u64;
+        let since_ms = now_ms.saturating_sub(LOOKBACK.as_millis() as u64);
+        let pending = self.base.clickhouse.get_pending_proposals(since_ms).await?;
+        self.handle(&pending, now_ms).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for PendingProposalMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use clickhouse::ClickhouseReader;
+use eyre::Result;
+use std::{fmt::Debug, time::Duration};
+use tracing::{debug, error, warn};
+
+/// Source of the latest chain heads as reported by the RPC nodes.
+#[async_trait]
+pub trait ChainHeads: Send + Sync {
+    /// Latest L1 block number
+    async fn l1_head(&self) -> Result<u64>;
+    /// Latest L2 block number
+    async fn l2_head(&self) -> Result<u64>;
+}
+
+/// Ingestion progress of a single chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ingestion {
+    /// Latest block number reported by the RPC
+    pub rpc_head: u64,
+    /// Latest block number stored in `ClickHouse`
+    pub db_head: Option<u64>,
+    /// Time of the most recent insert into the head events table
+    pub last_insert: Option<DateTime<Utc>>,
+}
+
+impl Ingestion {
+    /// Returns `true` if the RPC has blocks that were not stored and nothing was inserted for
+    /// longer than `threshold`.
+    ///
+    /// A chain that stopped producing blocks is not stalled: its RPC head matches the stored one.
+    pub fn is_stalled(&self, now: DateTime<Utc>, threshold: Duration) -> bool {
+        let behind = self.db_head.is_none_or(|db_head| self.rpc_head > db_head);
+        let stale = self.last_insert.is_some_and(|last| {
+            now.signed_duration_since(last).to_std().is_ok_and(|age| age > threshold)
+        });
+        behind && stale
+    }
+}
+
+/// Dead-man switch for the ingestion pipeline itself.
+///
+/// Polls the RPC heads and the `l1_head_events`/`l2_head_events` tables every `interval`; if the
+/// RPC is ahead of the database and no row was inserted for `threshold`, the driver is assumed to
+/// be stuck and an incident is opened. Chain outages, where the RPC head does not advance either,
+/// are left to the chain monitors. Resolves when inserts resume.
+#[derive(Debug)]
+pub struct PipelineMonitor<H> {
+    pub(crate) base: BaseMonitor<()>,
+    heads: H,
+    threshold: Duration,
+}
+
+impl<H: ChainHeads + Debug> PipelineMonitor<H> {
+    /// Creates a new `PipelineMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        heads: H,
+        threshold: Duration,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            base: BaseMonitor::new(clickhouse, client, component_id, interval),
+            heads,
+            threshold,
+        }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the ingestion progress of both chains. `None` means the RPC head is unknown.
+    pub(crate) async fn handle(
+        &mut self,
+        l1: Option<Ingestion>,
+        l2: Option<Ingestion>,
+    ) -> Result<()> {
+        let now = Utc::now();
+        let l1_stalled = l1.is_some_and(|i| i.is_stalled(now, self.threshold));
+        let l2_stalled = l2.is_some_and(|i| i.is_stalled(now, self.threshold));
+
+        debug!(
+            active_incident = ?self.base.active_incidents,
+            ?l1,
+            ?l2,
+            l1_stalled,
+            l2_stalled,
+            threshold_seconds = self.threshold.as_secs(),
+            "Pipeline ingestion status"
+        );
+
+        let has_active = !self.base.active_incidents.is_empty();
+        let stalled = l1_stalled || l2_stalled;
+
+        if stalled && !has_active {
+            let mut chains = Vec::new();
+            if l1_stalled {
+                chains.push("L1");
+            }
+            if l2_stalled {
+                chains.push("L2");
+            }
+            warn!(chains = ?chains, "Ingestion pipeline stalled while RPC is healthy");
+            let id = self.open(&chains.join(" and ")).await?;
+            self.base.active_incidents.insert((), id);
+        } else if !stalled && has_active && l1.is_some() && l2.is_some() {
+            self.base.mark_healthy(&()).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, chains: &str) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Taikoscope ingestion stalled".into(),
+            format!(
+                "No {chains} head events stored for {}s while the RPC reports new blocks",
+                self.threshold.as_secs()
+            ),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+
+    /// Query the ingestion progress of both chains
+    async fn check_ingestion(&mut self) -> Result<()> {
+        let ch = &self.base.clickhouse;
+        let l1 = match self.heads.l1_head().await {
+            Ok(rpc_head) => Some(Ingestion {
+                rpc_head,
+                db_head: ch.get_last_l1_block_number().await?,
+                last_insert: ch.get_last_l1_head_insert_time().await?,
+            }),
+            Err(e) => {
+                debug!(%e, "L1 RPC head unavailable, skipping L1 pipeline check");
+                None
+            }
+        };
+        let l2 = match self.heads.l2_head().await {
+            Ok(rpc_head) => Some(Ingestion {
+                rpc_head,
+                db_head: ch.get_last_l2_block_number().await?,
+                last_insert: ch.get_last_l2_head_insert_time().await?,
+            }),
+            Err(e) => {
+                debug!(%e, "L2 RPC head unavailable, skipping L2 pipeline check");
+                None
+            }
+        };
+
+        self.handle(l1, l2).await
+    }
+}
+
+#[async_trait]
+impl<H: ChainHeads + Debug> Monitor for PipelineMonitor<H> {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        self.open("L1 or L2").await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        self.check_ingestion().await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for PipelineMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
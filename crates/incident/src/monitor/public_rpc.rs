@@ -1,123 +1,294 @@
-use crate::{client::Client as IncidentClient, helpers};
+use crate::{
+    base_monitor::BaseMonitor, client::Client as IncidentClient, monitor::ChainHeads,
+    state::StateStore,
+};
 use chrono::Utc;
-use network::public_rpc_monitor::check_syncing;
+use clickhouse::{ClickhouseReader, ClickhouseWriter, RpcHealthInsertRow};
+use network::public_rpc_monitor::{RpcProbe, probe};
 use reqwest::{Client, Url};
-use std::time::Duration;
+use std::{collections::VecDeque, fmt::Debug, time::Duration};
 use tokio::task::JoinHandle;
-use tracing::{error, info, warn};
-
-/// Spawn a background task monitoring the provided public RPC endpoint.
-/// If an `IncidentClient` is provided, incidents will be created and resolved
-/// when the endpoint is unhealthy or recovers.
-pub fn spawn_public_rpc_monitor(
-    url: Url,
-    incident: Option<(IncidentClient, String)>,
-) -> JoinHandle<()> {
-    tokio::spawn(async move {
-        let client = Client::new();
-        let mut interval = tokio::time::interval(Duration::from_secs(60));
-        let mut incident_id: Option<String> = None;
-        loop {
-            interval.tick().await;
-            if let Some((ic, cid)) = &incident {
-                check_once(&client, &url, Some((ic, cid)), &mut incident_id).await;
-            } else {
-                check_once(&client, &url, None, &mut incident_id).await;
-            }
-        }
-    })
+use tracing::{debug, error, info, warn};
+
+/// Number of recent probes the error rate of an endpoint is computed over
+const ERROR_RATE_WINDOW: usize = 60;
+/// Consecutive unhealthy probes before an incident is opened
+const FAILURE_THRESHOLD: u32 = 2;
+
+/// A public RPC endpoint and the Instatus component its incidents are reported on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcEndpoint {
+    /// Endpoint URL
+    pub url: Url,
+    /// Instatus component ID; incidents are only logged when unset
+    pub component_id: Option<String>,
 }
 
-async fn check_once(
-    client: &Client,
-    url: &Url,
-    incident: Option<(&IncidentClient, &String)>,
-    incident_id: &mut Option<String>,
-) {
-    let first = check_syncing(client, url).await;
-    let negative = match first {
-        Ok(false) => {
-            info!(url = url.as_str(), "public rpc healthy");
-            if let Some((ic, cid)) = incident &&
-                let Some(id) = incident_id.take()
-            {
-                resolve(ic, cid, &id).await;
-            }
-            false
+/// Outcome of a single probe, judged against the reference L2 head.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeOutcome {
+    /// Successful probe, if any
+    pub probe: Option<RpcProbe>,
+    /// Blocks behind the reference L2 head
+    pub block_lag: Option<u64>,
+    /// Reason the endpoint is unhealthy, `None` when healthy
+    pub error: Option<String>,
+}
+
+impl ProbeOutcome {
+    /// Judge a probe result. The endpoint is unhealthy if the probe failed, the endpoint is
+    /// syncing, or it lags more than `max_block_lag` blocks behind `reference_head`.
+    pub fn new(
+        result: eyre::Result<RpcProbe>,
+        reference_head: Option<u64>,
+        max_block_lag: u64,
+    ) -> Self {
+        let probe = match result {
+            Ok(probe) => probe,
+            Err(e) => return Self { probe: None, block_lag: None, error: Some(format!("{e:#}")) },
+        };
+        let block_lag = reference_head.map(|head| head.saturating_sub(probe.block_number));
+        let error = if probe.syncing {
+            Some("endpoint is syncing".to_owned())
+        } else {
+            block_lag
+                .filter(|&lag| lag > max_block_lag)
+                .map(|lag| format!("endpoint is {lag} blocks behind the L2 head"))
+        };
+        Self { probe: Some(probe), block_lag, error }
+    }
+
+    /// Whether the endpoint was healthy
+    pub const fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Health history of a single endpoint.
+#[derive(Debug)]
+pub(super) struct EndpointState {
+    /// Probed endpoint
+    pub(super) endpoint: RpcEndpoint,
+    recent: VecDeque<bool>,
+    consecutive_failures: u32,
+    /// Open incident and persisted state of the endpoint's component
+    pub(super) base: BaseMonitor<()>,
+}
+
+impl EndpointState {
+    fn new(endpoint: RpcEndpoint, base: BaseMonitor<()>) -> Self {
+        Self {
+            endpoint,
+            recent: VecDeque::with_capacity(ERROR_RATE_WINDOW),
+            consecutive_failures: 0,
+            base,
         }
-        Ok(true) => {
-            warn!(url = url.as_str(), "public rpc syncing");
-            true
+    }
+
+    /// Record a probe outcome.
+    pub(super) fn record(&mut self, healthy: bool) {
+        if self.recent.len() == ERROR_RATE_WINDOW {
+            self.recent.pop_front();
         }
-        Err(e) => {
-            // Include error chain with debug formatting
-            warn!(error = ?e, url = url.as_str(), "public rpc check failed");
-            true
+        self.recent.push_back(healthy);
+        self.consecutive_failures = if healthy { 0 } else { self.consecutive_failures + 1 };
+    }
+
+    /// Share of unhealthy probes among the recent ones.
+    pub(super) fn error_rate(&self) -> f64 {
+        if self.recent.is_empty() {
+            return 0.0;
         }
-    };
-
-    if negative {
-        tokio::time::sleep(Duration::from_secs(15)).await;
-        match check_syncing(client, url).await {
-            Ok(false) => {
-                info!(url = url.as_str(), "public rpc recovered");
-                if let Some((ic, cid)) = incident &&
-                    let Some(id) = incident_id.take()
-                {
-                    resolve(ic, cid, &id).await;
+        self.recent.iter().filter(|healthy| !**healthy).count() as f64 / self.recent.len() as f64
+    }
+}
+
+/// SLA tracker for a list of public RPC endpoints.
+///
+/// Every `interval` each endpoint is probed for its latency, block height and sync status. The
+/// block height is compared to the L2 head of the indexer's own RPC. Results are stored in the
+/// `rpc_health` table when a writer is set, and an incident is opened on an endpoint's component
+/// after [`FAILURE_THRESHOLD`] consecutive unhealthy probes. It is resolved on the next healthy
+/// probe. Each endpoint tracks its incident in its own [`BaseMonitor`], so open incidents can be
+/// persisted across restarts.
+#[derive(Debug)]
+pub struct PublicRpcMonitor<H> {
+    pub(super) endpoints: Vec<EndpointState>,
+    http: Client,
+    heads: H,
+    writer: Option<ClickhouseWriter>,
+    max_block_lag: u64,
+    interval: Duration,
+}
+
+impl<H: ChainHeads + Debug + 'static> PublicRpcMonitor<H> {
+    /// Creates a new `PublicRpcMonitor`. When `reporting_enabled` is false, incidents are only
+    /// logged.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        endpoints: Vec<RpcEndpoint>,
+        incident_client: IncidentClient,
+        reporting_enabled: bool,
+        heads: H,
+        max_block_lag: u64,
+        interval: Duration,
+    ) -> Self {
+        let endpoints = endpoints
+            .into_iter()
+            .map(|endpoint| {
+                let mut base = BaseMonitor::new(
+                    clickhouse.clone(),
+                    incident_client.clone(),
+                    endpoint.component_id.clone().unwrap_or_default(),
+                    interval,
+                );
+                base.reporting_enabled &= reporting_enabled;
+                EndpointState::new(endpoint, base)
+            })
+            .collect();
+        Self { endpoints, http: Client::new(), heads, writer: None, max_block_lag, interval }
+    }
+
+    /// Stores probe results in `ClickHouse` through `writer`, if set.
+    pub fn with_writer(mut self, writer: Option<ClickhouseWriter>) -> Self {
+        self.writer = writer;
+        self
+    }
+
+    /// Persist the state of every endpoint to the store `store` returns for its index, if any,
+    /// so open incidents survive restarts.
+    pub fn with_state_store(mut self, store: impl Fn(usize) -> Option<StateStore>) -> Self {
+        self.endpoints = self
+            .endpoints
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut state)| {
+                state.base = state.base.with_state_store(store(index));
+                state
+            })
+            .collect();
+        self
+    }
+
+    /// Spawn the monitor on a background task.
+    pub fn spawn(mut self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            for state in &mut self.endpoints {
+                if let Err(e) = state.base.check_existing_incidents(()).await {
+                    error!(url = %state.endpoint.url, error = %e, "failed to check existing incidents");
                 }
             }
-            Ok(true) => {
-                error!(url = url.as_str(), "public rpc still syncing");
-                if let Some((ic, cid)) = incident {
-                    open_if_needed(ic, cid, incident_id).await;
-                }
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                self.check_endpoints().await;
             }
+        })
+    }
+
+    /// Probe every endpoint once.
+    async fn check_endpoints(&mut self) {
+        let reference_head = match self.heads.l2_head().await {
+            Ok(head) => Some(head),
             Err(e) => {
-                error!(error = ?e, url = url.as_str(), "public rpc check failed again");
-                if let Some((ic, cid)) = incident {
-                    open_if_needed(ic, cid, incident_id).await;
+                debug!(%e, "L2 RPC head unavailable, skipping public rpc block lag check");
+                None
+            }
+        };
+
+        let mut outcomes = Vec::with_capacity(self.endpoints.len());
+        for state in &self.endpoints {
+            let result = probe(&self.http, &state.endpoint.url).await;
+            outcomes.push(ProbeOutcome::new(result, reference_head, self.max_block_lag));
+        }
+
+        let mut rows = Vec::with_capacity(outcomes.len());
+        for (index, outcome) in outcomes.into_iter().enumerate() {
+            rows.push(health_row(&self.endpoints[index].endpoint.url, &outcome));
+            self.handle(index, &outcome).await;
+        }
+
+        if let Some(writer) = &self.writer &&
+            let Err(e) = writer.insert_rpc_health(&rows).await
+        {
+            error!(error = %e, "failed to store public rpc health");
+        }
+    }
+
+    /// Update the health history of an endpoint and open or resolve its incident.
+    pub(crate) async fn handle(&mut self, index: usize, outcome: &ProbeOutcome) {
+        let state = &mut self.endpoints[index];
+        state.record(outcome.is_healthy());
+        let url = state.endpoint.url.as_str();
+        let error_rate = state.error_rate();
+
+        if let Some(reason) = &outcome.error {
+            warn!(url, reason, error_rate, "public rpc unhealthy");
+            if state.consecutive_failures >= FAILURE_THRESHOLD &&
+                !state.base.component_id.is_empty() &&
+                !state.base.active_incidents.contains_key(&())
+            {
+                error!(url, reason, "public rpc still unhealthy");
+                if let Some(id) = open(&state.base, url, reason).await {
+                    state.base.active_incidents.insert((), id);
                 }
             }
+        } else {
+            info!(
+                url,
+                latency_ms = outcome.probe.map(|p| p.latency.as_millis() as u64),
+                block_lag = outcome.block_lag,
+                error_rate,
+                "public rpc healthy"
+            );
+            // The incident stays tracked until it is resolved, so a failed attempt is retried
+            if let Err(e) = state.base.mark_healthy(&()).await {
+                error!(url, error = %e, "failed to resolve public rpc incident");
+            }
         }
+        state.base.persist_state();
     }
 }
 
-async fn open_if_needed(
-    client: &IncidentClient,
-    component_id: &str,
-    incident_id: &mut Option<String>,
-) {
-    if incident_id.is_some() {
-        return;
+fn health_row(url: &Url, outcome: &ProbeOutcome) -> RpcHealthInsertRow {
+    RpcHealthInsertRow {
+        url: url.to_string(),
+        healthy: u8::from(outcome.is_healthy()),
+        latency_ms: outcome.probe.map(|p| p.latency.as_millis() as u64),
+        block_number: outcome.probe.map(|p| p.block_number),
+        block_lag: outcome.block_lag,
+        error: outcome.error.clone(),
     }
-    match client.open_incident(component_id).await {
-        Ok(Some(id)) => {
-            info!(incident_id = %id, "existing incident found, skipping creation");
-            *incident_id = Some(id);
-        }
-        Ok(None) => {
-            let body = helpers::build_incident_payload(
-                component_id,
-                "Public RPC Unavailable".to_owned(),
-                "Public RPC endpoint is unreachable or syncing".to_owned(),
-                Utc::now(),
-            );
-            match helpers::create_with_retry(client, true, &body).await {
-                Ok(id) => {
-                    info!(incident_id = %id, "created public rpc incident");
-                    *incident_id = Some(id);
-                }
-                Err(e) => error!(error = %e, "failed to create incident"),
+}
+
+async fn open(base: &BaseMonitor<()>, url: &str, reason: &str) -> Option<String> {
+    if base.reporting_enabled {
+        match base.client.open_incident(&base.component_id).await {
+            Ok(Some(id)) => {
+                info!(incident_id = %id, "existing incident found, skipping creation");
+                return Some(id);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                error!(error = %e, "failed to query incidents");
+                return None;
             }
         }
-        Err(e) => error!(error = %e, "failed to query incidents"),
     }
-}
 
-async fn resolve(client: &IncidentClient, component_id: &str, id: &str) {
-    let body = helpers::build_resolve_payload(component_id);
-    if let Err(e) = helpers::resolve_with_retry(client, true, id, &body).await {
-        error!(error = %e, incident_id = %id, "failed to resolve incident");
+    let body = base.create_incident_payload(
+        "Public RPC Unavailable".to_owned(),
+        format!("Public RPC endpoint {url} is unhealthy: {reason}"),
+        Utc::now(),
+    );
+    match base.create_incident_with_payload(&body).await {
+        Ok(id) => {
+            info!(incident_id = %id, url, "created public rpc incident");
+            Some(id)
+        }
+        Err(e) => {
+            error!(error = %e, "failed to create incident");
+            None
+        }
     }
 }
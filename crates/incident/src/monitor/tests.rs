@@ -3,6 +3,7 @@ use crate::{base_monitor::Monitor, client::Client as IncidentClient};
 use chrono::{Duration as ChronoDuration, Utc};
 use clickhouse::ClickhouseReader as ClickhouseInternalClient;
 use mockito::{Matcher, Server, ServerGuard};
+use network::public_rpc_monitor::RpcProbe;
 use std::time::Duration;
 use url::Url;
 
@@ -288,3 +289,954 @@ fn catch_all_only_true_only_for_catch_all() {
     monitor.base.active_incidents.insert((1, 1), "other".to_owned());
     assert!(!monitor.catch_all_only());
 }
+
+#[derive(Debug)]
+struct StaticHeads;
+
+#[async_trait::async_trait]
+impl ChainHeads for StaticHeads {
+    async fn l1_head(&self) -> eyre::Result<u64> {
+        Ok(100)
+    }
+
+    async fn l2_head(&self) -> eyre::Result<u64> {
+        Ok(1_000)
+    }
+}
+
+#[test]
+fn ingestion_stalled_only_when_rpc_ahead_and_inserts_stale() {
+    let now = Utc::now();
+    let threshold = Duration::from_secs(60);
+    let stale = Some(now - ChronoDuration::seconds(120));
+    let fresh = Some(now - ChronoDuration::seconds(10));
+
+    let behind = Ingestion { rpc_head: 10, db_head: Some(5), last_insert: stale };
+    assert!(behind.is_stalled(now, threshold));
+
+    // Chain halted: the RPC has nothing new to ingest
+    let halted = Ingestion { rpc_head: 5, db_head: Some(5), last_insert: stale };
+    assert!(!halted.is_stalled(now, threshold));
+
+    let recent = Ingestion { rpc_head: 10, db_head: Some(5), last_insert: fresh };
+    assert!(!recent.is_stalled(now, threshold));
+
+    // Nothing ingested yet
+    let empty = Ingestion { rpc_head: 10, db_head: None, last_insert: None };
+    assert!(!empty.is_stalled(now, threshold));
+}
+
+#[tokio::test]
+async fn pipeline_monitor_handle_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = PipelineMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        StaticHeads,
+        Duration::from_secs(60),
+        Duration::from_secs(1),
+    );
+
+    let now = Utc::now();
+    let l1 = Ingestion { rpc_head: 100, db_head: Some(100), last_insert: Some(now) };
+    let stalled_l2 = Ingestion {
+        rpc_head: 1_000,
+        db_head: Some(900),
+        last_insert: Some(now - ChronoDuration::seconds(120)),
+    };
+    monitor.handle(Some(l1), Some(stalled_l2)).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // An unknown RPC head neither opens nor resolves incidents
+    monitor.handle(Some(l1), None).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    let caught_up = Ingestion { rpc_head: 1_000, db_head: Some(1_000), last_insert: Some(now) };
+    monitor.handle(Some(l1), Some(caught_up)).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+fn operator_balance(address: u8, low_balance: u8) -> clickhouse::OperatorBalanceRow {
+    clickhouse::OperatorBalanceRow {
+        snapshot_ts: 1_700_000_000,
+        address: clickhouse::AddressBytes([address; 20]),
+        l1_block_number: 100,
+        eth_balance: 1_000,
+        taiko_balance: None,
+        low_balance,
+    }
+}
+
+#[tokio::test]
+async fn operator_balance_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("0x0202020202020202020202020202020202020202".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = OperatorBalanceMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        Duration::from_secs(1),
+    );
+
+    monitor.handle(&[operator_balance(1, 0), operator_balance(2, 1)]).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // An empty snapshot round neither opens nor resolves incidents
+    monitor.handle(&[]).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor.handle(&[operator_balance(1, 0), operator_balance(2, 0)]).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+fn bond_balance(address: u8, balance: u128) -> clickhouse::BondBalanceRow {
+    clickhouse::BondBalanceRow {
+        address: clickhouse::AddressBytes([address; 20]),
+        balance,
+        l1_block_number: 100,
+        total_debited: 0,
+        total_credited: 0,
+    }
+}
+
+#[tokio::test]
+async fn bond_balance_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("0x0202020202020202020202020202020202020202".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = BondBalanceMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        3,
+        Duration::from_secs(1),
+    );
+    let proposers = [clickhouse::AddressBytes([1; 20]), clickhouse::AddressBytes([2; 20])];
+
+    // Inactive accounts are ignored, however low their balance
+    monitor
+        .handle(&[bond_balance(1, 300), bond_balance(3, 0)], &proposers, Some(100))
+        .await
+        .unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    monitor
+        .handle(&[bond_balance(1, 300), bond_balance(2, 299)], &proposers, Some(100))
+        .await
+        .unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // Without a protocol configuration nothing is resolved
+    monitor.handle(&[bond_balance(1, 300), bond_balance(2, 300)], &proposers, None).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor
+        .handle(&[bond_balance(1, 300), bond_balance(2, 300)], &proposers, Some(100))
+        .await
+        .unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+fn forced_inclusion_queue(pending: u64, near_deadline: u8) -> clickhouse::ForcedInclusionQueueRow {
+    clickhouse::ForcedInclusionQueueRow {
+        snapshot_ts: 1_700_000_600,
+        l1_block_number: 100,
+        pending,
+        oldest_created_ts: (pending > 0).then_some(1_700_000_000),
+        oldest_age_secs: (pending > 0).then_some(600),
+        deadline_batch_id: (pending > 0).then_some(1_002),
+        next_batch_id: 1_001,
+        near_deadline,
+    }
+}
+
+#[tokio::test]
+async fn forced_inclusion_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("deadline batch 1002".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = ForcedInclusionMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        Duration::from_secs(1),
+    );
+
+    monitor.handle(Some(&forced_inclusion_queue(1, 0))).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    monitor.handle(Some(&forced_inclusion_queue(2, 1))).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // Without a snapshot incidents are neither opened nor resolved
+    monitor.handle(None).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor.handle(Some(&forced_inclusion_queue(0, 0))).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+fn pending_proposal(nonce: u64, first_seen_ms: u64) -> clickhouse::ProposalInclusionRow {
+    clickhouse::ProposalInclusionRow {
+        proposer: clickhouse::AddressBytes([1u8; 20]),
+        nonce,
+        tx_hash: clickhouse::HashBytes([2u8; 32]),
+        first_seen_ms,
+        max_fee_per_gas: 5_000_000_000,
+        max_priority_fee_per_gas: Some(1_000_000_000),
+        replacements: 0,
+        batch_id: None,
+        l1_block_number: None,
+        included_at_ms: None,
+        inclusion_delay_ms: None,
+        dropped: 0,
+    }
+}
+
+#[tokio::test]
+async fn pending_proposal_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("oldest nonce 7 pending 180s".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = PendingProposalMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        Duration::from_secs(120),
+        Duration::from_secs(1),
+    );
+
+    let now_ms = 1_700_000_000_000;
+    monitor.handle(&[pending_proposal(7, now_ms - 60_000)], now_ms).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    let later_ms = now_ms + 120_000;
+    monitor
+        .handle(&[pending_proposal(7, now_ms - 60_000), pending_proposal(8, later_ms)], later_ms)
+        .await
+        .unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor.handle(&[pending_proposal(8, later_ms)], later_ms + 10_000).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+fn rpc_probe(block_number: u64, syncing: bool) -> RpcProbe {
+    RpcProbe { latency: Duration::from_millis(50), block_number, syncing }
+}
+
+#[test]
+fn probe_outcome_flags_failures_syncing_and_lag() {
+    let healthy = ProbeOutcome::new(Ok(rpc_probe(995, false)), Some(1_000), 10);
+    assert!(healthy.is_healthy());
+    assert_eq!(healthy.block_lag, Some(5));
+
+    let lagging = ProbeOutcome::new(Ok(rpc_probe(900, false)), Some(1_000), 10);
+    assert!(!lagging.is_healthy());
+    assert_eq!(lagging.block_lag, Some(100));
+
+    let syncing = ProbeOutcome::new(Ok(rpc_probe(1_000, true)), Some(1_000), 10);
+    assert!(!syncing.is_healthy());
+
+    // Without a reference head only the probe itself is judged
+    let unknown_head = ProbeOutcome::new(Ok(rpc_probe(900, false)), None, 10);
+    assert!(unknown_head.is_healthy());
+    assert_eq!(unknown_head.block_lag, None);
+
+    let failed = ProbeOutcome::new(Err(eyre::eyre!("timed out")), Some(1_000), 10);
+    assert!(!failed.is_healthy());
+    assert_eq!(failed.probe, None);
+}
+
+/// Mock the Instatus endpoints for a public rpc incident `inc1`, resolving it with `put_status`.
+async fn public_rpc_monitor(
+    server: &mut ServerGuard,
+    put_status: usize,
+) -> (PublicRpcMonitor<StaticHeads>, Vec<mockito::Mock>) {
+    let list_mock = server
+        .mock("GET", "/v1/test_page_id/incidents")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_body("[]")
+        .create_async()
+        .await;
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(put_status)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let (reader, _) = mock_clickhouse_client_async().await;
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+    let endpoint = RpcEndpoint {
+        url: "https://rpc.example".parse().unwrap(),
+        component_id: Some("comp1".to_owned()),
+    };
+    let monitor = PublicRpcMonitor::new(
+        reader,
+        vec![endpoint],
+        incident_client,
+        true,
+        StaticHeads,
+        10,
+        Duration::from_secs(60),
+    );
+    (monitor, vec![list_mock, post_mock, exists_mock, put_mock])
+}
+
+#[tokio::test]
+async fn public_rpc_monitor_opens_after_consecutive_failures_and_resolves() {
+    let mut server = Server::new_async().await;
+    let (mut monitor, mocks) = public_rpc_monitor(&mut server, 200).await;
+
+    let lagging = ProbeOutcome::new(Ok(rpc_probe(900, false)), Some(1_000), 10);
+    monitor.handle(0, &lagging).await;
+    assert_eq!(monitor.endpoints[0].base.active_incidents.get(&()), None);
+
+    monitor.handle(0, &lagging).await;
+    monitor.handle(0, &lagging).await;
+    assert_eq!(monitor.endpoints[0].base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+    assert!((monitor.endpoints[0].error_rate() - 1.0).abs() < f64::EPSILON);
+
+    let healthy = ProbeOutcome::new(Ok(rpc_probe(1_000, false)), Some(1_000), 10);
+    monitor.handle(0, &healthy).await;
+    assert_eq!(monitor.endpoints[0].base.active_incidents.get(&()), None);
+    assert!((monitor.endpoints[0].error_rate() - 0.75).abs() < f64::EPSILON);
+
+    for mock in mocks {
+        mock.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn public_rpc_monitor_keeps_incident_when_resolve_fails() {
+    let mut server = Server::new_async().await;
+    let (mut monitor, _mocks) = public_rpc_monitor(&mut server, 400).await;
+
+    let lagging = ProbeOutcome::new(Ok(rpc_probe(900, false)), Some(1_000), 10);
+    for _ in 0..3 {
+        monitor.handle(0, &lagging).await;
+    }
+    assert_eq!(monitor.endpoints[0].base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // The incident stays tracked so the next healthy probe retries the resolution
+    let healthy = ProbeOutcome::new(Ok(rpc_probe(1_000, false)), Some(1_000), 10);
+    monitor.handle(0, &healthy).await;
+    assert_eq!(monitor.endpoints[0].base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+}
+
+#[test]
+fn verify_deadline_waits_for_cooldown_after_proof() {
+    let posted_at = Utc::now() - ChronoDuration::hours(4);
+    let timeout = Duration::from_secs(3 * 3600);
+    let cooldown = Duration::from_secs(2 * 3600 + 600);
+
+    // Unproved batches keep the plain timeout
+    let deadline = verify_deadline(posted_at, None, timeout, cooldown).unwrap();
+    assert_eq!(deadline, posted_at + ChronoDuration::hours(3));
+
+    // Proved long ago: the timeout is later than the end of the cooldown
+    let early_proof = posted_at + ChronoDuration::minutes(10);
+    let deadline = verify_deadline(posted_at, Some(early_proof), timeout, cooldown).unwrap();
+    assert_eq!(deadline, posted_at + ChronoDuration::hours(3));
+
+    // Proved just now: the batch cannot be verified before the cooldown ends
+    let late_proof = Utc::now();
+    let deadline = verify_deadline(posted_at, Some(late_proof), timeout, cooldown).unwrap();
+    assert_eq!(deadline, late_proof + ChronoDuration::minutes(130));
+    assert!(deadline > Utc::now());
+}
+
+fn cadence(
+    proposed: u64,
+    verified: u64,
+    max_anchor_age: Option<u64>,
+) -> clickhouse::BatchCadenceRow {
+    clickhouse::BatchCadenceRow {
+        last_proposed_batch_id: 1_000,
+        proposed_batches: proposed,
+        last_verified_batch_id: 900,
+        verified_batches: verified,
+        max_anchor_age,
+    }
+}
+
+#[test]
+fn cadence_status_projects_time_to_unverified_limit() {
+    let window = Duration::from_secs(3600);
+    let status = CadenceStatus::new(&cadence(60, 40, Some(10)), 300, 64, window);
+    assert_eq!(status.unverified_batches, 100);
+    // 200 batches of headroom at a net growth of 20 batches per hour
+    assert_eq!(status.seconds_to_limit, Some(36_000));
+    assert!(status.risks(Duration::from_secs(3600), 80).is_empty());
+    assert_eq!(status.risks(Duration::from_secs(36_000), 80), vec![CadenceRisk::TooManyBatches]);
+
+    // Verification keeps up with proposals, so the limit is never reached
+    let status = CadenceStatus::new(&cadence(40, 40, None), 300, 64, window);
+    assert_eq!(status.seconds_to_limit, None);
+    assert!(status.risks(Duration::from_secs(3600), 80).is_empty());
+
+    // The limit is already reached
+    let status = CadenceStatus::new(&cadence(0, 0, None), 100, 64, window);
+    assert_eq!(status.risks(Duration::ZERO, 80), vec![CadenceRisk::TooManyBatches]);
+}
+
+#[test]
+fn cadence_status_flags_old_anchors() {
+    let window = Duration::from_secs(3600);
+    let status = CadenceStatus::new(&cadence(10, 10, Some(52)), 300, 64, window);
+    assert_eq!(status.risks(Duration::from_secs(3600), 80), vec![CadenceRisk::AnchorAge]);
+    let status = CadenceStatus::new(&cadence(10, 10, Some(51)), 300, 64, window);
+    assert!(status.risks(Duration::from_secs(3600), 80).is_empty());
+}
+
+#[tokio::test]
+async fn batch_cadence_monitor_opens_and_resolves_per_risk() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("TooManyBatches".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = BatchCadenceMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        Duration::from_secs(3600),
+        80,
+        Duration::from_secs(1),
+    );
+
+    let window = Duration::from_secs(3600);
+    let at_risk = CadenceStatus::new(&cadence(300, 100, Some(10)), 200, 64, window);
+    monitor.handle(&at_risk).await.unwrap();
+    assert_eq!(
+        monitor.base.active_incidents.get(&CadenceRisk::TooManyBatches),
+        Some(&"inc1".to_owned())
+    );
+
+    let healthy = CadenceStatus::new(&cadence(100, 100, Some(10)), 200, 64, window);
+    monitor.handle(&healthy).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn gas_saturation_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("95.0%".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = GasSaturationMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        90,
+        Duration::from_secs(900),
+        Duration::from_secs(1),
+    );
+
+    let summary =
+        |blocks, avg_saturation| clickhouse::GasSaturationSummaryRow { blocks, avg_saturation };
+
+    monitor.handle(summary(450, 0.95)).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // A window without blocks neither opens nor resolves incidents
+    monitor.handle(summary(0, 0.0)).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor.handle(summary(450, 0.5)).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn anchor_mismatch_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("latest batch 8 anchored to L1 block 116".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = AnchorMismatchMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        Duration::from_secs(3600),
+        Duration::from_secs(1),
+    );
+
+    let mismatch = |batch_id, anchor_block_id| clickhouse::AnchorMismatchRow {
+        batch_id,
+        l1_block_number: anchor_block_id + 5,
+        anchor_block_id,
+        anchor_block_hash: clickhouse::HashBytes([1u8; 32]),
+        l1_block_hash: clickhouse::HashBytes([2u8; 32]),
+    };
+
+    monitor.handle(&[mismatch(7, 115), mismatch(8, 116)]).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // Further mismatches keep the open incident
+    monitor.handle(&[mismatch(9, 117)]).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor.handle(&[]).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn anomaly_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("of prove_time, tps detected in the last 60m".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = AnomalyMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        Duration::from_secs(3600),
+        Duration::from_secs(1),
+    );
+
+    let anomaly = |detected_ts, metric| clickhouse::AnomalyRow {
+        detected_ts,
+        metric,
+        value: 50.0,
+        expected: 10.0,
+        std_dev: 2.0,
+        z_score: 20.0,
+    };
+
+    monitor.handle(&[anomaly(100, 0), anomaly(200, 3), anomaly(300, 0)]).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // Further anomalies keep the open incident
+    monitor.handle(&[anomaly(400, 1)]).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor.handle(&[]).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn candidate_churn_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("1 operator candidate\\(s\\) added and 1 removed".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = CandidateChurnMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        Duration::from_secs(3600),
+        2,
+        Duration::from_secs(1),
+    );
+
+    let address = |byte| clickhouse::AddressBytes([byte; 20]);
+    let snapshot =
+        |candidates: &[u8], added: &[u8], removed: &[u8]| clickhouse::OperatorHistoryRow {
+            observed_ts: 1_700_000_000,
+            l1_block_number: 120,
+            candidates: candidates.iter().copied().map(address).collect(),
+            added: added.iter().copied().map(address).collect(),
+            removed: removed.iter().copied().map(address).collect(),
+        };
+
+    // A baseline snapshot with enough candidates is healthy
+    let baseline = snapshot(&[1, 2], &[], &[]);
+    monitor.handle(std::slice::from_ref(&baseline), Some(&baseline)).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    let change = snapshot(&[1, 3], &[3], &[2]);
+    monitor.handle(std::slice::from_ref(&change), Some(&change)).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // Too few candidates keep the incident open once the change left the window
+    let shrunk = snapshot(&[1], &[], &[3]);
+    monitor.handle(&[], Some(&shrunk)).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor.handle(&[], Some(&change)).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+fn verification_backlog(backlog: u64, over_threshold: u8) -> clickhouse::VerificationBacklogRow {
+    clickhouse::VerificationBacklogRow {
+        snapshot_ts: 1_700_000_600,
+        l1_block_number: 100,
+        last_proposed_batch_id: 1_000 + backlog,
+        last_verified_batch_id: 1_000,
+        backlog,
+        over_threshold,
+    }
+}
+
+#[tokio::test]
+async fn verification_backlog_monitor_opens_and_resolves_incident() {
+    let (ch_client, _ch_server) = mock_clickhouse_client_async().await;
+    let mut server = Server::new_async().await;
+
+    let post_mock = server
+        .mock("POST", "/v1/test_page_id/incidents")
+        .match_body(Matcher::Regex("150 batches behind".into()))
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let incident_exists_mock = server
+        .mock("GET", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body(r#"{"id":"inc1"}"#)
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/v1/test_page_id/incidents/inc1")
+        .with_status(200)
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let incident_client = IncidentClient::with_base_url(
+        "test_api_key".into(),
+        "test_page_id".into(),
+        server.url().parse().unwrap(),
+    );
+
+    let mut monitor = VerificationBacklogMonitor::new(
+        ch_client,
+        incident_client,
+        "comp1".to_owned(),
+        Duration::from_secs(1),
+    );
+
+    monitor.handle(Some(&verification_backlog(40, 0))).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    monitor.handle(Some(&verification_backlog(150, 1))).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    // Without a snapshot incidents are neither opened nor resolved
+    monitor.handle(None).await.unwrap();
+    assert_eq!(monitor.base.active_incidents.get(&()), Some(&"inc1".to_owned()));
+
+    monitor.handle(Some(&verification_backlog(20, 0))).await.unwrap();
+    assert!(monitor.base.active_incidents.is_empty());
+
+    post_mock.assert_async().await;
+    incident_exists_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
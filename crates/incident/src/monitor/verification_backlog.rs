@@ -0,0 +1,141 @@
+use crate::{
+    base_monitor::{BaseMonitor, Monitor},
+    client::Client as IncidentClient,
+    state::StateStore,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use clickhouse::{ClickhouseReader, VerificationBacklogRow};
+use eyre::Result;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Monitors the verification backlog.
+///
+/// Reads the latest snapshot of the `verification_backlog` table, written by the driver's
+/// verification backlog task, every `interval`. An incident is opened while more proposed batches
+/// than the configured threshold are waiting for verification, and resolved once the backlog is
+/// back within it.
+#[derive(Debug)]
+pub struct VerificationBacklogMonitor {
+    pub(crate) base: BaseMonitor<()>,
+}
+
+impl VerificationBacklogMonitor {
+    /// Creates a new `VerificationBacklogMonitor` with the given parameters.
+    pub fn new(
+        clickhouse: ClickhouseReader,
+        client: IncidentClient,
+        component_id: String,
+        interval: Duration,
+    ) -> Self {
+        Self { base: BaseMonitor::new(clickhouse, client, component_id, interval) }
+    }
+
+    /// Persists open incidents to `store`, if set, so they survive restarts.
+    pub fn with_state_store(mut self, store: Option<StateStore>) -> Self {
+        self.base = self.base.with_state_store(store);
+        self
+    }
+
+    /// Handle the latest snapshot. Without a snapshot incidents are neither opened nor resolved.
+    pub(crate) async fn handle(&mut self, snapshot: Option<&VerificationBacklogRow>) -> Result<()> {
+        let Some(snapshot) = snapshot else {
+            debug!("No verification backlog snapshots yet");
+            return Ok(());
+        };
+
+        let over_threshold = snapshot.over_threshold == 1;
+        let has_active = !self.base.active_incidents.is_empty();
+
+        debug!(
+            backlog = snapshot.backlog,
+            over_threshold, has_active, "Verification backlog status"
+        );
+
+        if over_threshold && !has_active {
+            warn!(
+                backlog = snapshot.backlog,
+                last_proposed_batch_id = snapshot.last_proposed_batch_id,
+                last_verified_batch_id = snapshot.last_verified_batch_id,
+                "Verification backlog above threshold"
+            );
+            let id = self.open(&describe(snapshot)).await?;
+            self.base.active_incidents.insert((), id);
+        } else if !over_threshold && has_active {
+            self.base.mark_healthy(&()).await?;
+        }
+        Ok(())
+    }
+
+    /// Opens a new incident
+    async fn open(&self, details: &str) -> Result<String> {
+        let body = self.base.create_incident_payload(
+            "Batch verification falling behind".into(),
+            format!("Proposed batches are piling up waiting for verification: {details}"),
+            Utc::now(),
+        );
+
+        self.base.create_incident_with_payload(&body).await
+    }
+}
+
+/// Describe the backlog of `snapshot` for the incident message
+fn describe(snapshot: &VerificationBacklogRow) -> String {
+    format!(
+        "{} batches behind, last proposed batch {}, last verified batch {}",
+        snapshot.backlog, snapshot.last_proposed_batch_id, snapshot.last_verified_batch_id
+    )
+}
+
+#[async_trait]
+impl Monitor for VerificationBacklogMonitor {
+    type IncidentKey = ();
+
+    async fn create_incident(&self, _key: &Self::IncidentKey) -> Result<String> {
+        self.open("backlog unknown").await
+    }
+
+    async fn resolve_incident(&self, incident_id: &str) -> Result<()> {
+        let payload = self.base.create_resolve_payload();
+        self.base.resolve_incident_with_payload(incident_id, &payload).await
+    }
+
+    async fn check_health(&mut self) -> Result<()> {
+        let snapshot = self.base.clickhouse.get_latest_verification_backlog().await?;
+        self.handle(snapshot.as_ref()).await
+    }
+
+    async fn initialize(&mut self) -> Result<()> {
+        self.base.check_existing_incidents(()).await
+    }
+
+    async fn run(mut self) -> Result<()> {
+        self.initialize().await?;
+        let interval_duration = self.get_interval();
+        let mut interval = tokio::time::interval(interval_duration);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.check_health().await {
+                error!(error = %e, "monitoring check failed for VerificationBacklogMonitor");
+            }
+            self.base.persist_state();
+        }
+    }
+
+    fn get_interval(&self) -> Duration {
+        self.base.interval
+    }
+
+    fn get_component_id(&self) -> &str {
+        &self.base.component_id
+    }
+
+    fn get_client(&self) -> &IncidentClient {
+        &self.base.client
+    }
+
+    fn get_clickhouse(&self) -> &ClickhouseReader {
+        &self.base.clickhouse
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use eyre::{Context, Result};
+use tracing::{info, warn};
+
+use crate::client::Client;
+
+/// Component IDs by component name.
+pub type ComponentIds = BTreeMap<String, String>;
+
+/// JSON file recording the IDs of provisioned components.
+///
+/// The page itself is the source of truth; the file lets operators see which IDs were assigned
+/// and is used when the page cannot be listed.
+#[derive(Debug, Clone)]
+pub struct ComponentStore {
+    path: PathBuf,
+}
+
+impl ComponentStore {
+    /// Create a store inside `dir`. The file lives in a subdirectory so it is not mistaken for
+    /// monitor state.
+    pub fn in_dir(dir: &Path) -> Self {
+        Self { path: dir.join("instatus").join("components.json") }
+    }
+
+    /// Path of the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the recorded IDs, returning an empty map if nothing has been saved yet
+    pub fn load(&self) -> Result<ComponentIds> {
+        let bytes = match std::fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ComponentIds::new()),
+            Err(e) => {
+                return Err(e).wrap_err_with(|| format!("reading {}", self.path.display()));
+            }
+        };
+        serde_json::from_slice(&bytes).wrap_err_with(|| format!("parsing {}", self.path.display()))
+    }
+
+    /// Persist `ids`, replacing any previously saved IDs
+    pub fn save(&self, ids: &ComponentIds) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(ids)?)
+            .wrap_err_with(|| format!("writing {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path)
+            .wrap_err_with(|| format!("renaming {} into place", tmp.display()))?;
+        Ok(())
+    }
+}
+
+/// Resolve the ID of every component in `names`, creating the ones missing from the page.
+///
+/// Components are matched by name, so running this repeatedly never creates duplicates. The
+/// resolved IDs are recorded in `store`, if set. When the page cannot be listed, IDs recorded
+/// by an earlier run are used instead, provided every name is covered.
+pub async fn provision_components(
+    client: &Client,
+    names: &[&str],
+    store: Option<&ComponentStore>,
+) -> Result<ComponentIds> {
+    let recorded = match store.map(ComponentStore::load).transpose() {
+        Ok(recorded) => recorded.unwrap_or_default(),
+        Err(e) => {
+            warn!(error = %e, "Ignoring unreadable component store");
+            ComponentIds::new()
+        }
+    };
+
+    let existing = match client.components().await {
+        Ok(components) => components,
+        Err(e) if names.iter().all(|name| recorded.contains_key(*name)) => {
+            warn!(error = %e, "Failed to list Instatus components, using recorded IDs");
+            return Ok(names
+                .iter()
+                .map(|name| (name.to_string(), recorded[*name].clone()))
+                .collect());
+        }
+        Err(e) => return Err(e.wrap_err("listing Instatus components")),
+    };
+
+    let mut ids = ComponentIds::new();
+    for name in names {
+        let id = match existing.iter().find(|c| c.name == *name) {
+            Some(component) => component.id.clone(),
+            None => {
+                let id = client
+                    .create_component(name)
+                    .await
+                    .wrap_err_with(|| format!("creating Instatus component {name}"))?;
+                info!(component_name = %name, component_id = %id, "Provisioned Instatus component");
+                id
+            }
+        };
+        ids.insert(name.to_string(), id);
+    }
+
+    if let Some(store) = store {
+        let mut merged = recorded.clone();
+        merged.extend(ids.clone());
+        if merged != recorded {
+            store.save(&merged)?;
+        }
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+
+    fn temp_store(name: &str) -> ComponentStore {
+        let dir = std::env::temp_dir()
+            .join(format!("taikoscope-incident-components-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        ComponentStore::in_dir(&dir)
+    }
+
+    #[tokio::test]
+    async fn creates_only_missing_components() {
+        let mut server = Server::new_async().await;
+        let list = server
+            .mock("GET", "/v1/page1/components")
+            .with_status(200)
+            .with_body(json!([{ "id": "comp1", "name": "Batch Submission" }]).to_string())
+            .create_async()
+            .await;
+        let create = server
+            .mock("POST", "/v1/page1/components")
+            .match_body(Matcher::PartialJson(json!({ "name": "Public API" })))
+            .with_status(200)
+            .with_body(json!({ "id": "comp2", "name": "Public API" }).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client =
+            Client::with_base_url("key".into(), "page1".into(), server.url().parse().unwrap());
+        let store = temp_store("missing");
+        let ids = provision_components(&client, &["Batch Submission", "Public API"], Some(&store))
+            .await
+            .unwrap();
+
+        let expected: ComponentIds = [
+            ("Batch Submission".to_owned(), "comp1".to_owned()),
+            ("Public API".to_owned(), "comp2".to_owned()),
+        ]
+        .into();
+        assert_eq!(ids, expected);
+        assert_eq!(store.load().unwrap(), expected);
+        list.assert_async().await;
+        create.assert_async().await;
+        let _ = std::fs::remove_dir_all(store.path().parent().unwrap().parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_recorded_ids_when_listing_fails() {
+        let mut server = Server::new_async().await;
+        server.mock("GET", "/v1/page1/components").with_status(500).create_async().await;
+
+        let client =
+            Client::with_base_url("key".into(), "page1".into(), server.url().parse().unwrap());
+        let store = temp_store("fallback");
+        let recorded: ComponentIds = [("Public API".to_owned(), "comp2".to_owned())].into();
+        store.save(&recorded).unwrap();
+
+        let ids = provision_components(&client, &["Public API"], Some(&store)).await.unwrap();
+        assert_eq!(ids, recorded);
+        assert!(
+            provision_components(&client, &["Public API", "Other"], Some(&store)).await.is_err()
+        );
+        assert!(provision_components(&client, &["Public API"], None).await.is_err());
+        let _ = std::fs::remove_dir_all(store.path().parent().unwrap().parent().unwrap());
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use eyre::{Context, Result};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Monitor state that survives restarts.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MonitorState<K> {
+    /// Open incidents tracked by the monitor, keyed by incident key
+    pub active_incidents: Vec<(K, String)>,
+    /// Last time the monitor observed a healthy component
+    pub last_healthy: Option<DateTime<Utc>>,
+}
+
+/// JSON file store for the state of a single monitor.
+///
+/// Writes go to a temporary file first and are then renamed into place, so a crash mid-write
+/// never leaves a truncated state file behind.
+#[derive(Debug, Clone)]
+pub struct StateStore {
+    path: PathBuf,
+}
+
+impl StateStore {
+    /// Create a store for the monitor `name` inside `dir`
+    pub fn in_dir(dir: &Path, name: &str) -> Self {
+        Self { path: dir.join(format!("{name}.json")) }
+    }
+
+    /// Path of the backing state file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load the persisted state, returning `None` if nothing has been saved yet
+    pub fn load<K: DeserializeOwned>(&self) -> Result<Option<MonitorState<K>>> {
+        let bytes = match std::fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).wrap_err_with(|| format!("reading {}", self.path.display()));
+            }
+        };
+        let state = serde_json::from_slice(&bytes)
+            .wrap_err_with(|| format!("parsing {}", self.path.display()))?;
+        Ok(Some(state))
+    }
+
+    /// Persist `state`, replacing any previously saved state
+    pub fn save<K: Serialize>(&self, state: &MonitorState<K>) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).wrap_err_with(|| format!("creating {}", dir.display()))?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(state)?)
+            .wrap_err_with(|| format!("writing {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path)
+            .wrap_err_with(|| format!("renaming {} into place", tmp.display()))?;
+        Ok(())
+    }
+}
+
+/// Load the state of every monitor persisted in `dir`, sorted by monitor name.
+///
+/// A missing directory holds no state. Files that cannot be parsed are skipped with a warning
+/// so a single corrupt monitor does not hide the others.
+pub fn load_all<K: DeserializeOwned>(dir: &Path) -> Result<Vec<(String, MonitorState<K>)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).wrap_err_with(|| format!("reading {}", dir.display())),
+    };
+
+    let mut states = Vec::new();
+    for entry in entries {
+        let path = entry.wrap_err_with(|| format!("reading {}", dir.display()))?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        match StateStore::in_dir(dir, name).load() {
+            Ok(Some(state)) => states.push((name.to_owned(), state)),
+            Ok(None) => {}
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Skipping unreadable monitor state")
+            }
+        }
+    }
+    states.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(states)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> StateStore {
+        let dir = std::env::temp_dir()
+            .join(format!("taikoscope-incident-state-{}-{name}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        StateStore::in_dir(&dir, name)
+    }
+
+    #[test]
+    fn load_returns_none_when_missing() {
+        let store = temp_store("missing");
+        assert!(store.load::<u64>().unwrap().is_none());
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let store = temp_store("round_trip");
+        let state = MonitorState {
+            active_incidents: vec![((1u64, 2u64), "inc1".to_owned())],
+            last_healthy: Some(Utc::now()),
+        };
+        store.save(&state).unwrap();
+        assert_eq!(store.load::<(u64, u64)>().unwrap(), Some(state));
+        let _ = std::fs::remove_dir_all(store.path().parent().unwrap());
+    }
+
+    #[test]
+    fn load_fails_on_corrupt_file() {
+        let store = temp_store("corrupt");
+        std::fs::create_dir_all(store.path().parent().unwrap()).unwrap();
+        std::fs::write(store.path(), b"not json").unwrap();
+        assert!(store.load::<u64>().is_err());
+        let _ = std::fs::remove_dir_all(store.path().parent().unwrap());
+    }
+
+    #[test]
+    fn load_all_reads_every_monitor() {
+        let first = temp_store("load_all_b");
+        let dir = first.path().parent().unwrap().to_path_buf();
+        let second = StateStore::in_dir(&dir, "load_all_a");
+        let state = |id: &str| MonitorState {
+            active_incidents: vec![(1u64, id.to_owned())],
+            last_healthy: None,
+        };
+        first.save(&state("inc_b")).unwrap();
+        second.save(&state("inc_a")).unwrap();
+        std::fs::write(dir.join("corrupt.json"), b"not json").unwrap();
+        std::fs::write(dir.join("load_all_c.json.tmp"), b"{}").unwrap();
+
+        let states = load_all::<u64>(&dir).unwrap();
+        assert_eq!(
+            states,
+            vec![
+                ("load_all_a".to_owned(), state("inc_a")),
+                ("load_all_b".to_owned(), state("inc_b")),
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn load_all_of_missing_dir_is_empty() {
+        let dir = std::env::temp_dir()
+            .join(format!("taikoscope-incident-state-{}-absent", std::process::id()));
+        assert!(load_all::<u64>(&dir).unwrap().is_empty());
+    }
+}
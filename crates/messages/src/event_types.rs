@@ -32,6 +32,16 @@ pub struct BatchesVerifiedWrapper {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ForcedInclusionProcessedWrapper {
     pub event: chainio::taiko::wrapper::ITaikoWrapper::ForcedInclusionProcessed,
+    pub l1_block_number: u64,
+    pub l1_tx_hash: B256,
+    pub removed: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OperatorRemovedWrapper {
+    pub event: chainio::taiko::preconf_whitelist::IPreconfWhitelist::OperatorRemoved,
+    pub l1_block_number: u64,
+    pub l1_tx_hash: B256,
     pub removed: bool,
 }
 
@@ -54,13 +64,28 @@ impl From<(BatchesVerified, u64, B256, bool)> for BatchesVerifiedWrapper {
     }
 }
 
-impl From<(chainio::taiko::wrapper::ITaikoWrapper::ForcedInclusionProcessed, bool)>
+impl From<(chainio::taiko::wrapper::ITaikoWrapper::ForcedInclusionProcessed, u64, B256, bool)>
     for ForcedInclusionProcessedWrapper
 {
     fn from(
-        data: (chainio::taiko::wrapper::ITaikoWrapper::ForcedInclusionProcessed, bool),
+        data: (chainio::taiko::wrapper::ITaikoWrapper::ForcedInclusionProcessed, u64, B256, bool),
     ) -> Self {
-        Self { event: data.0, removed: data.1 }
+        Self { event: data.0, l1_block_number: data.1, l1_tx_hash: data.2, removed: data.3 }
+    }
+}
+
+impl From<(chainio::taiko::preconf_whitelist::IPreconfWhitelist::OperatorRemoved, u64, B256, bool)>
+    for OperatorRemovedWrapper
+{
+    fn from(
+        data: (
+            chainio::taiko::preconf_whitelist::IPreconfWhitelist::OperatorRemoved,
+            u64,
+            B256,
+            bool,
+        ),
+    ) -> Self {
+        Self { event: data.0, l1_block_number: data.1, l1_tx_hash: data.2, removed: data.3 }
     }
 }
 
@@ -72,4 +97,20 @@ pub enum TaikoEvent {
     BatchesProved(BatchesProvedWrapper),
     BatchesVerified(BatchesVerifiedWrapper),
     ForcedInclusionProcessed(ForcedInclusionProcessedWrapper),
+    OperatorRemoved(OperatorRemovedWrapper),
+}
+
+impl TaikoEvent {
+    /// Short name of the event type, e.g. `l1_header`.
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            Self::L1Header(_) => "l1_header",
+            Self::L2Header(_) => "l2_header",
+            Self::BatchProposed(_) => "batch_proposed",
+            Self::BatchesProved(_) => "batches_proved",
+            Self::BatchesVerified(_) => "batches_verified",
+            Self::ForcedInclusionProcessed(_) => "forced_inclusion_processed",
+            Self::OperatorRemoved(_) => "operator_removed",
+        }
+    }
 }
@@ -40,14 +40,21 @@ pub struct L2HeadEvent {
     pub block_ts: u64,
     /// Sum of gas used in the block
     pub sum_gas_used: u128,
+    /// Gas limit of the block, 0 for blocks ingested before it was recorded
+    pub gas_limit: u64,
     /// Number of transactions
     pub sum_tx: u32,
     /// Sum of priority fees paid
     pub sum_priority_fee: u128,
-    /// Sum of base fees paid
+    /// Sum of base fees burned
     pub sum_base_fee: u128,
+    /// Gas weighted average effective gas price, 0 for blocks ingested before it was recorded
+    pub avg_gas_price: u128,
     /// Sequencer sequencing the block
     pub sequencer: AddressBytes,
+    /// Extra data of the block, hex encoded with a `0x` prefix; empty for blocks ingested before
+    /// it was recorded
+    pub extra_data: String,
 }
 
 /// Batch row
@@ -69,6 +76,10 @@ pub struct BatchRow {
     pub blob_count: u8,
     /// Blob total bytes
     pub blob_total_bytes: u32,
+    /// Data availability mode code, see [`primitives::da::DaMode`]
+    pub da_mode: u8,
+    /// Size of the `txList` calldata in bytes, 0 for blob batches
+    pub calldata_bytes: u32,
 }
 
 impl BatchRow {
@@ -134,6 +145,8 @@ pub struct L2ReorgInsertRow {
     pub old_sequencer: AddressBytes,
     /// Sequencer that produced the new block
     pub new_sequencer: AddressBytes,
+    /// Code of the probable cause the reorg coincided with
+    pub probable_cause: u8,
 }
 
 /// L2 reorg row
@@ -147,6 +160,8 @@ pub struct L2ReorgRow {
     pub old_sequencer: AddressBytes,
     /// Sequencer that produced the new block
     pub new_sequencer: AddressBytes,
+    /// Code of the probable cause the reorg coincided with
+    pub probable_cause: u8,
     /// Time the reorg was recorded.
     /// This is populated when reading from the database.
     pub inserted_at: DateTime<Utc>,
@@ -177,6 +192,8 @@ pub struct SlashingEventRow {
     pub l1_block_number: u64,
     /// Address of the validator that was slashed
     pub validator_addr: AddressBytes,
+    /// Reason code: 0 = unknown, 1 = removed, 2 = ejected
+    pub reason: u8,
 }
 
 /// Row representing the number of blocks produced by a sequencer
@@ -272,6 +289,11 @@ pub struct L1DataCostRow {
     pub l1_block_number: u64,
     /// Total cost in gwei for data posting transactions
     pub cost: u128,
+    /// Portion of the cost paid for blob gas
+    pub blob_cost: u128,
+    /// Portion of the cost paid for the calldata gas of the transaction input; the rest of the
+    /// non-blob cost is intrinsic and execution gas
+    pub calldata_cost: u128,
 }
 
 /// Row used for inserting L1 data cost for a batch
@@ -283,6 +305,11 @@ pub struct L1DataCostInsertRow {
     pub batch_id: u64,
     /// Total cost in gwei for data posting transactions
     pub cost: u128,
+    /// Portion of the cost paid for blob gas
+    pub blob_cost: u128,
+    /// Portion of the cost paid for the calldata gas of the transaction input; the rest of the
+    /// non-blob cost is intrinsic and execution gas
+    pub calldata_cost: u128,
 }
 
 /// Row representing the prover cost for a batch
@@ -340,6 +367,8 @@ pub struct BlockFeeComponentRow {
     pub base_fee: u128,
     /// L1 data posting cost associated with the block, if available
     pub l1_data_cost: Option<u128>,
+    /// Gas weighted average effective gas price, 0 for blocks ingested before it was recorded
+    pub avg_gas_price: u128,
 }
 
 /// Row representing aggregated L2 fees for a sequencer
@@ -372,6 +401,8 @@ pub struct BatchFeeComponentRow {
     pub prove_cost: Option<u128>,
     /// Verifier cost amortized across batches in the selected range
     pub amortized_verify_cost: Option<u128>,
+    /// Gas weighted average effective gas price of the blocks that recorded it, 0 if none did
+    pub avg_gas_price: u128,
 }
 
 /// Row representing the transactions per second for an L2 block
@@ -0,0 +1,326 @@
+//! Primary and fallback WebSocket RPC endpoints
+//!
+//! Connections go to the first healthy endpoint in configured order, so the primary is used
+//! whenever it is healthy. An endpoint that fails [`FAILURE_THRESHOLD`] times in a row is skipped
+//! for [`UNHEALTHY_COOLDOWN`], after which it is preferred again, failing back to the primary.
+//! Endpoints whose RPC calls exhaust their [`ErrorBudget`] are skipped while quarantined.
+
+use std::{
+    borrow::Cow,
+    sync::{
+        Mutex,
+        atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use alloy::transports::http::reqwest::Url;
+use tracing::{info, warn};
+
+use crate::retries::{ERROR_BUDGET_MAX_ERROR_RATE, ERROR_BUDGET_QUARANTINE, ErrorBudget};
+
+/// Consecutive failures before an endpoint is marked unhealthy
+const FAILURE_THRESHOLD: u32 = 3;
+/// How long an unhealthy endpoint is skipped
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Health and connection counters of a single RPC endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    /// Endpoint URL
+    pub url: Url,
+    /// Whether the endpoint is the one currently connected to
+    pub active: bool,
+    /// Whether the endpoint is currently considered for connections
+    pub healthy: bool,
+    /// Failures since the last successful connection
+    pub consecutive_failures: u32,
+    /// Failures since startup
+    pub failures: u64,
+    /// Successful connections since startup
+    pub connects: u64,
+    /// Share of successful RPC calls within the error budget window, from 0 to 100
+    pub score: u8,
+    /// Whether the endpoint is quarantined for exhausting its error budget
+    pub quarantined: bool,
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    url: Url,
+    consecutive_failures: AtomicU32,
+    unhealthy_until: Mutex<Option<Instant>>,
+    failures: AtomicU64,
+    connects: AtomicU64,
+    budget: ErrorBudget,
+}
+
+impl Endpoint {
+    fn new(url: Url) -> Self {
+        Self {
+            url,
+            consecutive_failures: AtomicU32::new(0),
+            unhealthy_until: Mutex::new(None),
+            failures: AtomicU64::new(0),
+            connects: AtomicU64::new(0),
+            budget: ErrorBudget::default(),
+        }
+    }
+
+    fn is_healthy(&self) -> bool {
+        let until = *self.unhealthy_until.lock().unwrap_or_else(|e| e.into_inner());
+        until.is_none_or(|until| Instant::now() >= until) && !self.budget.is_quarantined()
+    }
+}
+
+/// The primary RPC endpoint of a chain followed by its fallbacks.
+#[derive(Debug)]
+pub struct EndpointPool {
+    label: Cow<'static, str>,
+    endpoints: Vec<Endpoint>,
+    active: AtomicUsize,
+    switches: AtomicU64,
+}
+
+impl EndpointPool {
+    /// Create a pool from `urls`, the first of which is the primary.
+    pub fn new(label: impl Into<Cow<'static, str>>, urls: Vec<Url>) -> eyre::Result<Self> {
+        let label = label.into();
+        if urls.is_empty() {
+            return Err(eyre::eyre!("No {label} RPC endpoint configured"));
+        }
+        Ok(Self {
+            label,
+            endpoints: urls.into_iter().map(Endpoint::new).collect(),
+            active: AtomicUsize::new(0),
+            switches: AtomicU64::new(0),
+        })
+    }
+
+    /// Label used in logs (e.g., "L1", "L2").
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Number of configured endpoints.
+    pub const fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// Whether the pool has no endpoints. Always `false`, as [`Self::new`] rejects empty pools.
+    pub const fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// URL of the endpoint at `index`.
+    pub fn url(&self, index: usize) -> &Url {
+        &self.endpoints[index].url
+    }
+
+    /// Index of the endpoint currently connected to.
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// URL of the endpoint currently connected to.
+    pub fn active_url(&self) -> &Url {
+        self.url(self.active())
+    }
+
+    /// Number of times the active endpoint changed since startup.
+    pub fn switches(&self) -> u64 {
+        self.switches.load(Ordering::Relaxed)
+    }
+
+    /// Indices of the endpoints to try, in order: healthy endpoints in configured order, then
+    /// unhealthy ones by descending score as a last resort.
+    pub fn candidates(&self) -> Vec<usize> {
+        let (mut healthy, mut unhealthy): (Vec<_>, Vec<_>) =
+            (0..self.endpoints.len()).partition(|&i| self.endpoints[i].is_healthy());
+        unhealthy.sort_by_key(|&i| std::cmp::Reverse(self.endpoints[i].budget.score()));
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    /// Whether a fallback is active although the primary is healthy again.
+    pub fn should_fail_back(&self) -> bool {
+        self.active() != 0 && self.endpoints[0].is_healthy()
+    }
+
+    /// Whether the active endpoint is quarantined for exhausting its error budget while another
+    /// endpoint is healthy.
+    pub fn should_leave_quarantined(&self) -> bool {
+        let active = self.active();
+        self.endpoints[active].budget.is_quarantined() &&
+            self.endpoints
+                .iter()
+                .enumerate()
+                .any(|(i, endpoint)| i != active && endpoint.is_healthy())
+    }
+
+    /// Record the outcome of an RPC call sent to the endpoint at `index`, quarantining the
+    /// endpoint once its calls exhaust the error budget.
+    pub fn record_call(&self, index: usize, ok: bool) {
+        let endpoint = &self.endpoints[index];
+        if endpoint.budget.record(ok) {
+            warn!(
+                role = %self.label,
+                url = %endpoint.url,
+                max_error_rate = ERROR_BUDGET_MAX_ERROR_RATE,
+                quarantine_secs = ERROR_BUDGET_QUARANTINE.as_secs(),
+                "RPC endpoint exhausted its error budget, quarantined"
+            );
+        }
+    }
+
+    /// Record a successful connection to the endpoint at `index`, making it the active one.
+    pub fn record_success(&self, index: usize) {
+        let endpoint = &self.endpoints[index];
+        endpoint.consecutive_failures.store(0, Ordering::Relaxed);
+        endpoint.connects.fetch_add(1, Ordering::Relaxed);
+        *endpoint.unhealthy_until.lock().unwrap_or_else(|e| e.into_inner()) = None;
+
+        let previous = self.active.swap(index, Ordering::Relaxed);
+        if previous != index {
+            let switches = self.switches.fetch_add(1, Ordering::Relaxed) + 1;
+            if index == 0 {
+                info!(role = %self.label, url = %endpoint.url, switches, "Failed back to primary RPC endpoint");
+            } else {
+                warn!(
+                    role = %self.label,
+                    from = %self.endpoints[previous].url,
+                    to = %endpoint.url,
+                    switches,
+                    "Failed over to fallback RPC endpoint"
+                );
+            }
+        }
+    }
+
+    /// Record a failed connection to, or stalled subscription on, the endpoint at `index`.
+    /// [`FAILURE_THRESHOLD`] failures in a row mark the endpoint unhealthy.
+    pub fn record_failure(&self, index: usize) {
+        let endpoint = &self.endpoints[index];
+        endpoint.failures.fetch_add(1, Ordering::Relaxed);
+        let failures = endpoint.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if failures >= FAILURE_THRESHOLD {
+            *endpoint.unhealthy_until.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some(Instant::now() + UNHEALTHY_COOLDOWN);
+            if failures == FAILURE_THRESHOLD {
+                warn!(
+                    role = %self.label,
+                    url = %endpoint.url,
+                    failures,
+                    cooldown_secs = UNHEALTHY_COOLDOWN.as_secs(),
+                    "RPC endpoint marked unhealthy"
+                );
+            }
+        }
+    }
+
+    /// Health and counters of every endpoint, in configured order.
+    pub fn status(&self) -> Vec<EndpointStatus> {
+        let active = self.active();
+        self.endpoints
+            .iter()
+            .enumerate()
+            .map(|(i, endpoint)| EndpointStatus {
+                url: endpoint.url.clone(),
+                active: i == active,
+                healthy: endpoint.is_healthy(),
+                consecutive_failures: endpoint.consecutive_failures.load(Ordering::Relaxed),
+                failures: endpoint.failures.load(Ordering::Relaxed),
+                connects: endpoint.connects.load(Ordering::Relaxed),
+                score: endpoint.budget.score(),
+                quarantined: endpoint.budget.is_quarantined(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool() -> EndpointPool {
+        let urls = ["ws://primary", "ws://fallback-1", "ws://fallback-2"]
+            .map(|url| Url::parse(url).unwrap())
+            .to_vec();
+        EndpointPool::new("L1", urls).unwrap()
+    }
+
+    #[test]
+    fn rejects_empty_pool() {
+        assert!(EndpointPool::new("L1", Vec::new()).is_err());
+    }
+
+    #[test]
+    fn prefers_primary_while_healthy() {
+        let pool = pool();
+        pool.record_failure(0);
+        pool.record_failure(0);
+        assert_eq!(pool.candidates(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn fails_over_after_consecutive_failures() {
+        let pool = pool();
+        for _ in 0..FAILURE_THRESHOLD {
+            pool.record_failure(0);
+        }
+        assert_eq!(pool.candidates(), vec![1, 2, 0]);
+
+        pool.record_success(1);
+        assert_eq!(pool.active(), 1);
+        assert_eq!(pool.switches(), 1);
+        assert!(!pool.should_fail_back());
+
+        let status = pool.status();
+        assert!(!status[0].healthy && !status[0].active);
+        assert!(status[1].healthy && status[1].active);
+        assert_eq!(status[0].failures, u64::from(FAILURE_THRESHOLD));
+    }
+
+    #[test]
+    fn fails_back_once_primary_recovers() {
+        let pool = pool();
+        for _ in 0..FAILURE_THRESHOLD {
+            pool.record_failure(0);
+        }
+        pool.record_success(2);
+        *pool.endpoints[0].unhealthy_until.lock().unwrap() = Some(Instant::now());
+
+        assert!(pool.should_fail_back());
+        assert_eq!(pool.candidates()[0], 0);
+
+        pool.record_success(0);
+        assert_eq!(pool.active(), 0);
+        assert_eq!(pool.switches(), 2);
+        assert!(!pool.should_fail_back());
+    }
+
+    #[test]
+    fn skips_endpoint_over_error_budget() {
+        let pool = pool();
+        for _ in 0..crate::retries::ERROR_BUDGET_MIN_CALLS {
+            pool.record_call(0, false);
+        }
+        for _ in 0..5 {
+            pool.record_call(1, false);
+        }
+
+        // The quarantined primary goes last, behind the healthy fallbacks
+        assert_eq!(pool.candidates(), vec![1, 2, 0]);
+        assert!(pool.should_leave_quarantined());
+
+        let status = pool.status();
+        assert!(status[0].quarantined && !status[0].healthy);
+        assert_eq!(status[0].score, 0);
+        assert_eq!(status[1].score, 0);
+        assert!(!status[1].quarantined && status[1].healthy);
+        assert_eq!(status[2].score, 100);
+
+        pool.record_success(1);
+        assert!(!pool.should_leave_quarantined());
+    }
+}
@@ -0,0 +1,59 @@
+//! ETH/USD price lookups from a `CoinGecko`-compatible `simple/price` endpoint.
+
+use eyre::{Result, eyre};
+use reqwest::Client;
+use serde_json::Value;
+
+/// Default endpoint queried for the ETH/USD price
+pub const DEFAULT_ETH_PRICE_URL: &str =
+    "https://api.coingecko.com/api/v3/simple/price?ids=ethereum&vs_currencies=usd";
+
+/// Fetch the current ETH price in USD from `url`.
+///
+/// The response must have the shape `{"ethereum":{"usd":<price>}}`. `api_key` is sent as a
+/// `CoinGecko` pro API key when set.
+pub async fn fetch_eth_usd(client: &Client, url: &str, api_key: Option<&str>) -> Result<f64> {
+    let req = client.get(url);
+    let req = if let Some(key) = api_key { req.header("x-cg-pro-api-key", key) } else { req };
+
+    let json: Value = req.send().await?.error_for_status()?.json().await?;
+    json.get("ethereum")
+        .and_then(|e| e.get("usd"))
+        .and_then(Value::as_f64)
+        .filter(|price| *price > 0.0)
+        .ok_or_else(|| eyre!("invalid ETH price response: {json}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn parses_price() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/")
+            .match_header("x-cg-pro-api-key", "key")
+            .with_status(200)
+            .with_body(r#"{"ethereum":{"usd":2345.67}}"#)
+            .create_async()
+            .await;
+
+        let price = fetch_eth_usd(&Client::new(), &server.url(), Some("key")).await.unwrap();
+        assert!((price - 2345.67).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_price() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/")
+            .with_status(200)
+            .with_body(r#"{"bitcoin":{"usd":1.0}}"#)
+            .create_async()
+            .await;
+
+        assert!(fetch_eth_usd(&Client::new(), &server.url(), None).await.is_err());
+    }
+}
@@ -3,6 +3,9 @@
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
 
+pub mod endpoints;
+pub mod eth_price;
 pub mod http_retry;
 pub mod public_rpc_monitor;
 pub mod retries;
+pub mod taiko_price;
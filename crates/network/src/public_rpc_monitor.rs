@@ -52,11 +52,50 @@ async fn check_once(client: &Client, url: &Url) {
     }
 }
 
+/// Result of probing a public RPC endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcProbe {
+    /// Round-trip time of the `eth_blockNumber` call
+    pub latency: Duration,
+    /// Latest block number reported by the endpoint
+    pub block_number: u64,
+    /// Whether the endpoint reports that it is still syncing
+    pub syncing: bool,
+}
+
+/// Probe `url` for its sync status, latest block number and latency.
+pub async fn probe(client: &Client, url: &Url) -> Result<RpcProbe> {
+    let syncing = check_syncing(client, url).await?;
+    let started_at = Instant::now();
+    let block_number = get_block_number(client, url).await?;
+    Ok(RpcProbe { latency: started_at.elapsed(), block_number, syncing })
+}
+
 pub async fn check_syncing(client: &Client, url: &Url) -> Result<bool> {
+    let value = rpc_request(client, url, "eth_syncing").await?;
+
+    // eth_syncing is healthy when result is exactly false; any other value means syncing/unhealthy
+    let syncing = !matches!(value.get("result"), Some(serde_json::Value::Bool(false)));
+    Ok(syncing)
+}
+
+/// Get the latest block number reported by `url`.
+pub async fn get_block_number(client: &Client, url: &Url) -> Result<u64> {
+    let value = rpc_request(client, url, "eth_blockNumber").await?;
+    let result = value
+        .get("result")
+        .and_then(|r| r.as_str())
+        .ok_or_else(|| eyre!("missing eth_blockNumber result: {}", value))?;
+    u64::from_str_radix(result.trim_start_matches("0x"), 16)
+        .map_err(|e| eyre!("invalid block number {}: {}", result, e))
+}
+
+/// Send a parameterless JSON-RPC request and return the parsed response.
+async fn rpc_request(client: &Client, url: &Url, method: &str) -> Result<serde_json::Value> {
     let body = json!({
         "jsonrpc": "2.0",
         "id": 1,
-        "method": "eth_syncing",
+        "method": method,
         "params": []
     });
 
@@ -106,9 +145,7 @@ pub async fn check_syncing(client: &Client, url: &Url) -> Result<bool> {
         return Err(eyre!("jsonrpc error: {}", err));
     }
 
-    // eth_syncing is healthy when result is exactly false; any other value means syncing/unhealthy
-    let syncing = !matches!(value.get("result"), Some(serde_json::Value::Bool(false)));
-    Ok(syncing)
+    Ok(value)
 }
 
 #[cfg(test)]
@@ -151,4 +188,44 @@ mod tests {
         assert!(res);
         _mock.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn probe_reports_block_number() {
+        let mut server = Server::new_async().await;
+        let _syncing = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex("eth_syncing".into()))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc":"2.0","id":1,"result":false}"#)
+            .create_async()
+            .await;
+        let _block_number = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::Regex("eth_blockNumber".into()))
+            .with_status(200)
+            .with_body(r#"{"jsonrpc":"2.0","id":1,"result":"0x1b4"}"#)
+            .create_async()
+            .await;
+
+        let client = Client::new();
+        let url = Url::parse(&server.url()).unwrap();
+        let probe = probe(&client, &url).await.unwrap();
+        assert_eq!(probe.block_number, 436);
+        assert!(!probe.syncing);
+    }
+
+    #[tokio::test]
+    async fn get_block_number_rejects_jsonrpc_error() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/")
+            .with_status(200)
+            .with_body(r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"down"}}"#)
+            .create_async()
+            .await;
+
+        let client = Client::new();
+        let url = Url::parse(&server.url()).unwrap();
+        assert!(get_block_number(&client, &url).await.is_err());
+    }
 }
@@ -1,20 +1,30 @@
-use std::time::Duration;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
 
 use alloy::{
     providers::WsConnect,
     pubsub::{ConnectionHandle, PubSubConnect},
     transports::{
-        RpcError, TransportError, TransportErrorKind, TransportResult,
+        RpcError, TransportError, TransportErrorKind, TransportFut, TransportResult,
         http::reqwest::Url,
         layers::{RetryBackoffLayer, RetryPolicy},
     },
 };
-use alloy_json_rpc::ErrorPayload;
+use alloy_json_rpc::{ErrorPayload, RequestPacket, ResponsePacket};
+#[cfg(feature = "fault-injection")]
+use primitives::faults::{Fault, FaultSchedule};
 use serde::Deserialize;
 use std::borrow::Cow;
 use tokio_retry::{Retry, RetryIf, strategy::ExponentialBackoff};
+use tower::{Layer, Service};
 use tracing::{error, warn};
 
+use crate::endpoints::EndpointPool;
+
 /// The default maximum number of retries for a transport error.
 ///
 /// With a `DEFAULT_INITIAL_BACKOFF_MS` of 1ms we can do 9 retries in ~500ms:
@@ -32,6 +42,185 @@ pub const DEFAULT_RETRY_LAYER: RetryBackoffLayer<RateLimitConnRefusedRetryPolicy
         RateLimitConnRefusedRetryPolicy,
     );
 
+/// Length of the sliding window over which the error rate of an endpoint is measured
+pub const ERROR_BUDGET_WINDOW: Duration = Duration::from_secs(60);
+/// Share of failed calls within the window beyond which an endpoint is quarantined
+pub const ERROR_BUDGET_MAX_ERROR_RATE: f64 = 0.5;
+/// Calls within the window below which the error rate is not judged
+pub const ERROR_BUDGET_MIN_CALLS: u64 = 20;
+/// How long an endpoint that exhausted its error budget is quarantined
+pub const ERROR_BUDGET_QUARANTINE: Duration = Duration::from_secs(60);
+
+/// Calls and failures of an endpoint within one second of the window
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    started: Instant,
+    calls: u64,
+    errors: u64,
+}
+
+#[derive(Debug, Default)]
+struct BudgetState {
+    buckets: VecDeque<Bucket>,
+    quarantined_until: Option<Instant>,
+}
+
+impl BudgetState {
+    fn evict(&mut self, now: Instant) {
+        while self
+            .buckets
+            .front()
+            .is_some_and(|b| now.duration_since(b.started) >= ERROR_BUDGET_WINDOW)
+        {
+            self.buckets.pop_front();
+        }
+    }
+
+    fn totals(&self) -> (u64, u64) {
+        self.buckets.iter().fold((0, 0), |(calls, errors), b| (calls + b.calls, errors + b.errors))
+    }
+}
+
+/// Error budget of a single RPC endpoint.
+///
+/// Counts the calls and failed calls of the last [`ERROR_BUDGET_WINDOW`] in one-second buckets.
+/// Once at least [`ERROR_BUDGET_MIN_CALLS`] calls were made and more than
+/// [`ERROR_BUDGET_MAX_ERROR_RATE`] of them failed, the endpoint is quarantined for
+/// [`ERROR_BUDGET_QUARANTINE`] and the window starts over.
+#[derive(Debug, Default)]
+pub struct ErrorBudget {
+    state: Mutex<BudgetState>,
+}
+
+impl ErrorBudget {
+    /// Record the outcome of a call. Returns `true` when the call exhausted the budget and
+    /// quarantined the endpoint.
+    pub fn record(&self, ok: bool) -> bool {
+        self.record_at(Instant::now(), ok)
+    }
+
+    fn record_at(&self, now: Instant, ok: bool) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.evict(now);
+        match state.buckets.back_mut() {
+            Some(bucket) if now.duration_since(bucket.started) < Duration::from_secs(1) => {
+                bucket.calls += 1;
+                bucket.errors += u64::from(!ok);
+            }
+            _ => state.buckets.push_back(Bucket { started: now, calls: 1, errors: u64::from(!ok) }),
+        }
+
+        if state.quarantined_until.is_some_and(|until| now < until) {
+            return false;
+        }
+        let (calls, errors) = state.totals();
+        if calls < ERROR_BUDGET_MIN_CALLS ||
+            (errors as f64) <= calls as f64 * ERROR_BUDGET_MAX_ERROR_RATE
+        {
+            return false;
+        }
+        state.quarantined_until = Some(now + ERROR_BUDGET_QUARANTINE);
+        state.buckets.clear();
+        true
+    }
+
+    /// Whether the endpoint is quarantined for exhausting its budget.
+    pub fn is_quarantined(&self) -> bool {
+        self.is_quarantined_at(Instant::now())
+    }
+
+    fn is_quarantined_at(&self, now: Instant) -> bool {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.quarantined_until.is_some_and(|until| now < until)
+    }
+
+    /// Score of the endpoint from 0 to 100: the share of successful calls within the window, or
+    /// 0 while quarantined. Endpoints without recent calls score 100.
+    pub fn score(&self) -> u8 {
+        self.score_at(Instant::now())
+    }
+
+    fn score_at(&self, now: Instant) -> u8 {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.quarantined_until.is_some_and(|until| now < until) {
+            return 0;
+        }
+        state.evict(now);
+        match state.totals() {
+            (0, _) => 100,
+            (calls, errors) => ((calls - errors) * 100 / calls) as u8,
+        }
+    }
+}
+
+/// A [`Layer`] that scores every RPC call against the error budget of the endpoint of `pool`
+/// active when the call was sent.
+///
+/// Transport errors and rate limited responses count as failures; other JSON-RPC errors, such as
+/// reverts, are the caller's and count as successes. Placed inside the retry layer, every attempt
+/// is scored.
+#[derive(Debug, Clone)]
+pub struct EndpointScoringLayer {
+    pool: Arc<EndpointPool>,
+}
+
+impl EndpointScoringLayer {
+    /// Score the calls of the endpoints of `pool`.
+    pub const fn new(pool: Arc<EndpointPool>) -> Self {
+        Self { pool }
+    }
+}
+
+impl<S> Layer<S> for EndpointScoringLayer {
+    type Service = EndpointScoringService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        EndpointScoringService { inner, pool: Arc::clone(&self.pool) }
+    }
+}
+
+/// Service of the [`EndpointScoringLayer`].
+#[derive(Debug, Clone)]
+pub struct EndpointScoringService<S> {
+    inner: S,
+    pool: Arc<EndpointPool>,
+}
+
+impl<S> Service<RequestPacket> for EndpointScoringService<S>
+where
+    S: Service<
+            RequestPacket,
+            Response = ResponsePacket,
+            Error = TransportError,
+            Future = TransportFut<'static>,
+        > + Send
+        + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: RequestPacket) -> Self::Future {
+        let pool = Arc::clone(&self.pool);
+        let index = pool.active();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let result = response.await;
+            pool.record_call(index, is_endpoint_success(&result));
+            result
+        })
+    }
+}
+
+/// Whether `result` shows a working endpoint: it answered and did not rate limit the call.
+fn is_endpoint_success(result: &TransportResult<ResponsePacket>) -> bool {
+    result.as_ref().is_ok_and(|packet| !packet.iter_errors().any(|e| e.is_retry_err()))
+}
+
 /// A retry strategy trait.
 pub trait Strategy: Iterator<Item = Duration> + Clone + Send + Sync + 'static {}
 
@@ -44,9 +233,14 @@ impl<T> Strategy for T where T: Iterator<Item = Duration> + Clone + Send + Sync
 #[derive(Debug, Clone)]
 pub struct RetryWsConnect<S: Strategy> {
     inner: WsConnect,
+    /// Primary and fallback endpoints; connections go to `inner` when unset
+    pool: Option<Arc<EndpointPool>>,
     strategy: S,
     /// Optional label to help identify which component is reconnecting (e.g., "L1", "L2").
     label: Cow<'static, str>,
+    /// Faults injected into connection attempts
+    #[cfg(feature = "fault-injection")]
+    faults: Option<FaultSchedule>,
 }
 
 impl RetryWsConnect<ExponentialBackoff> {
@@ -55,8 +249,26 @@ impl RetryWsConnect<ExponentialBackoff> {
     pub fn from_url<U: Into<Url>>(ws_url: U) -> Self {
         Self {
             inner: WsConnect::new(ws_url.into()),
+            pool: None,
             strategy: ExponentialBackoff::from_millis(DEFAULT_INITIAL_BACKOFF_MS),
             label: Cow::Borrowed("ws"),
+            #[cfg(feature = "fault-injection")]
+            faults: None,
+        }
+    }
+
+    /// Create a new `RetryWsConnect` that connects to the first healthy endpoint of `pool`,
+    /// failing over to the fallbacks when the primary keeps failing. Labelled with the pool's
+    /// label.
+    #[inline]
+    pub fn from_pool(pool: Arc<EndpointPool>) -> Self {
+        Self {
+            inner: WsConnect::new(pool.url(0).clone()),
+            label: Cow::Owned(pool.label().to_owned()),
+            pool: Some(pool),
+            strategy: ExponentialBackoff::from_millis(DEFAULT_INITIAL_BACKOFF_MS),
+            #[cfg(feature = "fault-injection")]
+            faults: None,
         }
     }
 }
@@ -68,6 +280,56 @@ impl<S: Strategy> RetryWsConnect<S> {
         self.label = label.into();
         self
     }
+
+    /// Connect to the first endpoint of the pool that accepts the connection, recording the
+    /// outcome of every attempt. Without a pool, connect to the configured URL.
+    async fn connect_endpoint(&self) -> TransportResult<ConnectionHandle> {
+        let Some(pool) = &self.pool else {
+            return self.inner.connect().await;
+        };
+        let mut last_err = None;
+        for index in pool.candidates() {
+            match WsConnect::new(pool.url(index).clone()).connect().await {
+                Ok(handle) => {
+                    pool.record_success(index);
+                    return Ok(handle);
+                }
+                Err(e) => {
+                    warn!(role = %self.label, url = %pool.url(index), error = %e, "Failed to connect to RPC endpoint");
+                    pool.record_failure(index);
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.expect("endpoint pools are never empty"))
+    }
+}
+
+#[cfg(feature = "fault-injection")]
+impl<S: Strategy> RetryWsConnect<S> {
+    /// Delay or drop (re)connection attempts according to `faults`.
+    pub fn with_faults(mut self, faults: FaultSchedule) -> Self {
+        self.faults = Some(faults);
+        self
+    }
+
+    /// Apply the next scheduled fault. Returns an already closed connection when the schedule
+    /// fails the attempt, which makes the pubsub service reconnect as if the socket had dropped.
+    async fn inject_fault(&self) -> Option<ConnectionHandle> {
+        match self.faults.as_ref()?.next_fault() {
+            Fault::None => None,
+            Fault::Delay(delay) => {
+                tokio::time::sleep(delay).await;
+                None
+            }
+            Fault::Fail => {
+                warn!(role = %self.label, "Injected fault: dropping WebSocket connection");
+                let (handle, interface) = ConnectionHandle::new();
+                interface.close_with_error();
+                Some(handle)
+            }
+        }
+    }
 }
 
 impl<S: Strategy> PubSubConnect for RetryWsConnect<S> {
@@ -76,13 +338,20 @@ impl<S: Strategy> PubSubConnect for RetryWsConnect<S> {
     }
 
     async fn connect(&self) -> TransportResult<ConnectionHandle> {
-        self.inner.connect().await
+        #[cfg(feature = "fault-injection")]
+        {
+            if let Some(dropped) = self.inject_fault().await {
+                return Ok(dropped);
+            }
+        }
+        self.connect_endpoint().await
     }
 
     fn try_reconnect(
         &self,
     ) -> alloy::transports::impl_future!(<Output = TransportResult<ConnectionHandle>>) {
-        let url = self.inner.url();
+        let url =
+            self.pool.as_ref().map_or_else(|| self.inner.url(), |pool| pool.active_url().as_str());
         let label = self.label.clone();
         let strategy = self.strategy.clone();
 
@@ -95,7 +364,13 @@ impl<S: Strategy> PubSubConnect for RetryWsConnect<S> {
         );
 
         async move {
-            let res = Retry::spawn(strategy, || self.inner.try_reconnect()).await;
+            #[cfg(feature = "fault-injection")]
+            {
+                if let Some(dropped) = self.inject_fault().await {
+                    return Ok(dropped);
+                }
+            }
+            let res = Retry::spawn(strategy, || self.connect_endpoint()).await;
             if let Err(ref e) = res {
                 error!(
                     role = %label,
@@ -261,4 +536,71 @@ mod tests {
     fn is_retryable_on_null_response() {
         assert!(RpcError::<TransportErrorKind>::NullResp.is_retryable());
     }
+
+    #[test]
+    fn error_budget_quarantines_above_max_error_rate() {
+        let budget = ErrorBudget::default();
+        let start = Instant::now();
+        let calls = ERROR_BUDGET_MIN_CALLS as usize;
+
+        // Half of the calls failing stays within the budget
+        for i in 0..calls {
+            assert!(!budget.record_at(start, i % 2 == 0));
+        }
+        assert_eq!(budget.score_at(start), 50);
+        assert!(!budget.is_quarantined_at(start));
+
+        let quarantined = (0..calls).any(|_| budget.record_at(start, false));
+        assert!(quarantined);
+        assert!(budget.is_quarantined_at(start));
+        assert_eq!(budget.score_at(start), 0);
+
+        // The window starts over once the quarantine ends
+        let later = start + ERROR_BUDGET_QUARANTINE;
+        assert!(!budget.is_quarantined_at(later));
+        assert_eq!(budget.score_at(later), 100);
+    }
+
+    #[test]
+    fn error_budget_forgets_calls_outside_the_window() {
+        let budget = ErrorBudget::default();
+        let start = Instant::now();
+        for _ in 0..ERROR_BUDGET_MIN_CALLS - 1 {
+            budget.record_at(start, false);
+        }
+        assert_eq!(budget.score_at(start), 0);
+
+        // The failures expired, so a single failure is below the minimum number of calls
+        let later = start + ERROR_BUDGET_WINDOW;
+        assert!(!budget.record_at(later, false));
+        assert!(!budget.is_quarantined_at(later));
+    }
+
+    #[test]
+    fn rate_limited_responses_count_as_endpoint_failures() {
+        let ok = r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#;
+        let revert =
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":3,"message":"execution reverted"}}"#;
+        let limited =
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":429,"message":"Too Many Requests"}}"#;
+        let packet = |json: &str| Ok(serde_json::from_str::<ResponsePacket>(json).unwrap());
+
+        assert!(is_endpoint_success(&packet(ok)));
+        assert!(is_endpoint_success(&packet(revert)));
+        assert!(!is_endpoint_success(&packet(limited)));
+        assert!(!is_endpoint_success(&Err(TransportErrorKind::backend_gone())));
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[tokio::test]
+    async fn injected_failure_drops_connection_without_dialing() {
+        let faults = FaultSchedule::new(0).with_script([Fault::Fail]);
+        let connect = RetryWsConnect::from_url(Url::parse("ws://127.0.0.1:1").unwrap())
+            .with_label("test")
+            .with_faults(faults);
+
+        // The scripted failure is served without touching the (unreachable) endpoint.
+        assert!(connect.connect().await.is_ok());
+        assert!(connect.connect().await.is_err());
+    }
 }
@@ -0,0 +1,77 @@
+//! TAIKO token price lookups from a `CoinGecko`-compatible `simple/price` endpoint.
+
+use eyre::{Result, eyre};
+use reqwest::Client;
+use serde_json::Value;
+
+/// Default endpoint queried for the TAIKO/ETH and TAIKO/USD prices
+pub const DEFAULT_TAIKO_PRICE_URL: &str =
+    "https://api.coingecko.com/api/v3/simple/price?ids=taiko&vs_currencies=eth,usd";
+
+/// Price of one TAIKO token
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaikoPrice {
+    /// Price in ETH
+    pub eth: f64,
+    /// Price in USD
+    pub usd: f64,
+}
+
+/// Fetch the current TAIKO price in ETH and USD from `url`.
+///
+/// The response must have the shape `{"taiko":{"eth":<price>,"usd":<price>}}`. `api_key` is
+/// sent as a `CoinGecko` pro API key when set.
+pub async fn fetch_taiko_price(
+    client: &Client,
+    url: &str,
+    api_key: Option<&str>,
+) -> Result<TaikoPrice> {
+    let req = client.get(url);
+    let req = if let Some(key) = api_key { req.header("x-cg-pro-api-key", key) } else { req };
+
+    let json: Value = req.send().await?.error_for_status()?.json().await?;
+    let price = |currency: &str| {
+        json.get("taiko")
+            .and_then(|t| t.get(currency))
+            .and_then(Value::as_f64)
+            .filter(|price| *price > 0.0)
+    };
+    match (price("eth"), price("usd")) {
+        (Some(eth), Some(usd)) => Ok(TaikoPrice { eth, usd }),
+        _ => Err(eyre!("invalid TAIKO price response: {json}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn parses_prices() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/")
+            .match_header("x-cg-pro-api-key", "key")
+            .with_status(200)
+            .with_body(r#"{"taiko":{"eth":0.00012,"usd":0.42}}"#)
+            .create_async()
+            .await;
+
+        let price = fetch_taiko_price(&Client::new(), &server.url(), Some("key")).await.unwrap();
+        assert_eq!(price, TaikoPrice { eth: 0.00012, usd: 0.42 });
+    }
+
+    #[tokio::test]
+    async fn rejects_partial_price() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/")
+            .with_status(200)
+            .with_body(r#"{"taiko":{"usd":0.42}}"#)
+            .create_async()
+            .await;
+
+        assert!(fetch_taiko_price(&Client::new(), &server.url(), None).await.is_err());
+    }
+}
@@ -0,0 +1,61 @@
+//! Core metrics watched for anomalies.
+
+/// Metric watched by the anomaly detector, stored as its `u8` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum AnomalyMetric {
+    /// Average L2 transactions per second
+    Tps = 0,
+    /// Average gas used per L2 block
+    GasUsed = 1,
+    /// Average delay between batch proposals
+    BatchCadence = 2,
+    /// Average time to prove a batch
+    ProveTime = 3,
+}
+
+impl AnomalyMetric {
+    /// Every metric, in code order.
+    pub const ALL: [Self; 4] = [Self::Tps, Self::GasUsed, Self::BatchCadence, Self::ProveTime];
+
+    /// Metric stored for the given code, `None` for unassigned codes.
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Tps),
+            1 => Some(Self::GasUsed),
+            2 => Some(Self::BatchCadence),
+            3 => Some(Self::ProveTime),
+            _ => None,
+        }
+    }
+
+    /// Parse the lowercase name returned by [`Self::as_str`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|metric| metric.as_str() == name)
+    }
+
+    /// Short lowercase name of the metric.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Tps => "tps",
+            Self::GasUsed => "gas_used",
+            Self::BatchCadence => "batch_cadence",
+            Self::ProveTime => "prove_time",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anomaly_metric_codes_and_names_round_trip() {
+        for metric in AnomalyMetric::ALL {
+            assert_eq!(AnomalyMetric::from_code(metric as u8), Some(metric));
+            assert_eq!(AnomalyMetric::from_name(metric.as_str()), Some(metric));
+        }
+        assert_eq!(AnomalyMetric::from_code(9), None);
+        assert_eq!(AnomalyMetric::from_name("TPS"), None);
+    }
+}
@@ -0,0 +1,53 @@
+//! Beacon chain slot and epoch arithmetic.
+//!
+//! Slots are numbered from the Ethereum mainnet beacon chain genesis on every network, so that
+//! the slots stored for L1 headers and preconfirmation data line up with the epochs below.
+
+/// Beacon chain genesis of Ethereum mainnet as a UNIX timestamp in seconds.
+pub const GENESIS_TIMESTAMP: u64 = 1_606_824_023;
+
+/// Duration of a slot in seconds.
+pub const SECONDS_PER_SLOT: u64 = 12;
+
+/// Number of slots in an epoch.
+pub const SLOTS_PER_EPOCH: u64 = 32;
+
+/// Slot containing the UNIX timestamp `ts`, `None` before genesis.
+pub const fn slot_at(ts: u64) -> Option<u64> {
+    match ts.checked_sub(GENESIS_TIMESTAMP) {
+        Some(elapsed) => Some(elapsed / SECONDS_PER_SLOT),
+        None => None,
+    }
+}
+
+/// Epoch containing `slot`.
+pub const fn epoch_of(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH
+}
+
+/// First slot of `epoch`.
+pub const fn epoch_start_slot(epoch: u64) -> u64 {
+    epoch * SLOTS_PER_EPOCH
+}
+
+/// Start of `slot` as a UNIX timestamp in seconds.
+pub const fn slot_start_ts(slot: u64) -> u64 {
+    GENESIS_TIMESTAMP + slot * SECONDS_PER_SLOT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_and_epochs_round_trip() {
+        assert_eq!(slot_at(GENESIS_TIMESTAMP - 1), None);
+        assert_eq!(slot_at(GENESIS_TIMESTAMP + 25), Some(2));
+
+        let slot = slot_at(1_760_000_000).unwrap();
+        let epoch = epoch_of(slot);
+        assert!(epoch_start_slot(epoch) <= slot && slot < epoch_start_slot(epoch + 1));
+        assert!(slot_start_ts(slot) <= 1_760_000_000);
+        assert!(1_760_000_000 < slot_start_ts(slot + 1));
+    }
+}
@@ -1,21 +1,37 @@
 use alloy_network_primitives::ReceiptResponse;
 use alloy_primitives::Address;
 
-/// Compute aggregated gas and priority fee statistics for a set of receipts,
+/// Aggregated gas and fee statistics of an L2 block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockStats {
+    /// Total gas used, excluding the anchor transaction
+    pub sum_gas_used: u128,
+    /// Number of transactions, including the anchor transaction
+    pub tx_count: u32,
+    /// Total priority fee paid, excluding the anchor transaction
+    pub sum_priority_fee: u128,
+    /// Total base fee burned, excluding the anchor transaction
+    pub sum_burned_fee: u128,
+    /// Gas weighted average effective gas price, excluding the anchor transaction
+    pub avg_gas_price: u128,
+}
+
+/// Compute aggregated gas and fee statistics for a set of receipts,
 /// excluding anchor transactions from gas and fee calculations.
 ///
-/// Returns a tuple of `(total_gas_used, transaction_count, total_priority_fee)`.
 /// The gas and fee totals exclude the anchor transaction, but the transaction
-/// count includes all transactions (including the anchor).
+/// count includes all transactions (including the anchor). The burned fee of a
+/// transaction is its gas used times the base fee, capped at its effective gas
+/// price.
 #[allow(clippy::module_name_repetitions)]
 pub fn compute_block_stats<R: ReceiptResponse>(
     receipts: &[R],
     base_fee: u64,
     anchor_address: Address,
-) -> (u128, u32, u128) {
+) -> BlockStats {
     let base = base_fee as u128;
-    let mut sum_gas_used: u128 = 0;
-    let mut sum_priority_fee: u128 = 0;
+    let mut stats = BlockStats::default();
+    let mut sum_paid: u128 = 0;
 
     for receipt in receipts {
         // Skip anchor transactions for gas and fee calculations
@@ -24,14 +40,17 @@ pub fn compute_block_stats<R: ReceiptResponse>(
         }
 
         let gas = receipt.gas_used() as u128;
-        sum_gas_used += gas;
-        let priority_per_gas = receipt.effective_gas_price().saturating_sub(base);
-        sum_priority_fee += priority_per_gas.saturating_mul(gas);
+        let price = receipt.effective_gas_price();
+        stats.sum_gas_used += gas;
+        stats.sum_priority_fee += price.saturating_sub(base).saturating_mul(gas);
+        stats.sum_burned_fee += price.min(base).saturating_mul(gas);
+        sum_paid = sum_paid.saturating_add(price.saturating_mul(gas));
     }
 
     // Transaction count includes all transactions (including anchor)
-    let tx_count = receipts.len() as u32;
-    (sum_gas_used, tx_count, sum_priority_fee)
+    stats.tx_count = receipts.len() as u32;
+    stats.avg_gas_price = sum_paid.checked_div(stats.sum_gas_used).unwrap_or(0);
+    stats
 }
 
 /// Check if a receipt is for an anchor transaction.
@@ -105,19 +124,31 @@ mod tests {
             TestReceipt { gas: 100, price: 10, to_addr: None },
             TestReceipt { gas: 200, price: 20, to_addr: None },
         ];
-        let (gas, count, priority) = compute_block_stats(&receipts, 5, MAINNET_ANCHOR);
-        assert_eq!(gas, 300);
-        assert_eq!(count, 2);
-        assert_eq!(priority, 3500);
+        let stats = compute_block_stats(&receipts, 5, MAINNET_ANCHOR);
+        assert_eq!(stats.sum_gas_used, 300);
+        assert_eq!(stats.tx_count, 2);
+        assert_eq!(stats.sum_priority_fee, 3500);
+        assert_eq!(stats.sum_burned_fee, 1500);
+        assert_eq!(stats.avg_gas_price, 16); // (100 * 10 + 200 * 20) / 300
+    }
+
+    #[test]
+    fn compute_block_stats_caps_burn_at_effective_price() {
+        let receipts = vec![TestReceipt { gas: 100, price: 4, to_addr: None }];
+        let stats = compute_block_stats(&receipts, 5, MAINNET_ANCHOR);
+        assert_eq!(stats.sum_priority_fee, 0);
+        assert_eq!(stats.sum_burned_fee, 400);
+        assert_eq!(stats.avg_gas_price, 4);
     }
 
     #[test]
     fn compute_block_stats_zero_base_fee() {
         let receipts = vec![TestReceipt { gas: 150, price: 40, to_addr: None }];
-        let (gas, count, priority) = compute_block_stats(&receipts, 0, MAINNET_ANCHOR);
-        assert_eq!(gas, 150);
-        assert_eq!(count, 1);
-        assert_eq!(priority, 6000);
+        let stats = compute_block_stats(&receipts, 0, MAINNET_ANCHOR);
+        assert_eq!(stats.sum_gas_used, 150);
+        assert_eq!(stats.tx_count, 1);
+        assert_eq!(stats.sum_priority_fee, 6000);
+        assert_eq!(stats.sum_burned_fee, 0);
     }
 
     #[test]
@@ -130,11 +161,12 @@ mod tests {
             TestReceipt { gas: 200, price: 20, to_addr: None },
         ];
 
-        let (gas, count, priority) = compute_block_stats(&receipts, 10, MAINNET_ANCHOR);
+        let stats = compute_block_stats(&receipts, 10, MAINNET_ANCHOR);
         // Gas and fees exclude anchor, but count includes all transactions
-        assert_eq!(gas, 300); // 100 + 200, excluding anchor's 50
-        assert_eq!(count, 3); // All 3 transactions including anchor
-        assert_eq!(priority, 2500); // (15-10)*100 + (20-10)*200 = 500 + 2000 = 2500
+        assert_eq!(stats.sum_gas_used, 300); // 100 + 200, excluding anchor's 50
+        assert_eq!(stats.tx_count, 3); // All 3 transactions including anchor
+        assert_eq!(stats.sum_priority_fee, 2500); // (15-10)*100 + (20-10)*200 = 500 + 2000 = 2500
+        assert_eq!(stats.sum_burned_fee, 3000); // 10*100 + 10*200, excluding anchor
     }
 
     #[test]
@@ -150,11 +182,12 @@ mod tests {
     fn compute_block_stats_only_anchor() {
         let receipts = vec![TestReceipt { gas: 50, price: 10, to_addr: Some(MAINNET_ANCHOR) }];
 
-        let (gas, count, priority) = compute_block_stats(&receipts, 10, MAINNET_ANCHOR);
+        let stats = compute_block_stats(&receipts, 10, MAINNET_ANCHOR);
         // Should count the transaction but exclude its gas and fees
-        assert_eq!(gas, 0); // No gas counted (anchor excluded)
-        assert_eq!(count, 1); // Transaction count still includes anchor
-        assert_eq!(priority, 0); // No priority fees (anchor excluded)
+        assert_eq!(stats.sum_gas_used, 0); // No gas counted (anchor excluded)
+        assert_eq!(stats.tx_count, 1); // Transaction count still includes anchor
+        assert_eq!(stats.sum_priority_fee, 0); // No priority fees (anchor excluded)
+        assert_eq!(stats.avg_gas_price, 0); // No gas to average over
     }
 
     #[test]
@@ -165,14 +198,14 @@ mod tests {
         ];
 
         // Test with mainnet anchor address - should exclude mainnet anchor tx
-        let (gas, count, _) = compute_block_stats(&mainnet_receipts, 10, MAINNET_ANCHOR);
-        assert_eq!(gas, 100); // Only regular tx gas
-        assert_eq!(count, 2); // Both transactions counted
+        let stats = compute_block_stats(&mainnet_receipts, 10, MAINNET_ANCHOR);
+        assert_eq!(stats.sum_gas_used, 100); // Only regular tx gas
+        assert_eq!(stats.tx_count, 2); // Both transactions counted
 
         // Test with hekla anchor address - should NOT exclude mainnet anchor tx
-        let (gas, count, _) = compute_block_stats(&mainnet_receipts, 10, HEKLA_ANCHOR);
-        assert_eq!(gas, 150); // Both transactions' gas (50 + 100)
-        assert_eq!(count, 2); // Both transactions counted
+        let stats = compute_block_stats(&mainnet_receipts, 10, HEKLA_ANCHOR);
+        assert_eq!(stats.sum_gas_used, 150); // Both transactions' gas (50 + 100)
+        assert_eq!(stats.tx_count, 2); // Both transactions counted
 
         let hekla_receipts = vec![
             TestReceipt { gas: 50, price: 10, to_addr: Some(HEKLA_ANCHOR) },
@@ -180,8 +213,8 @@ mod tests {
         ];
 
         // Test with hekla anchor address - should exclude hekla anchor tx
-        let (gas, count, _) = compute_block_stats(&hekla_receipts, 10, HEKLA_ANCHOR);
-        assert_eq!(gas, 100); // Only regular tx gas
-        assert_eq!(count, 2); // Both transactions counted
+        let stats = compute_block_stats(&hekla_receipts, 10, HEKLA_ANCHOR);
+        assert_eq!(stats.sum_gas_used, 100); // Only regular tx gas
+        assert_eq!(stats.tx_count, 2); // Both transactions counted
     }
 }
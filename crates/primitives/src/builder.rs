@@ -0,0 +1,82 @@
+//! Fingerprints of L2 block builders.
+//!
+//! Block builders commonly identify themselves in the `extraData` field of the blocks they
+//! build. A fingerprint maps a prefix of the extra data to the name of its builder.
+
+use alloy_primitives::hex;
+
+/// Known builders, each identified by a prefix of the extra data of its blocks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuilderFingerprints(Vec<(Vec<u8>, String)>);
+
+impl BuilderFingerprints {
+    /// Parse fingerprints given as `0xPREFIX=NAME`.
+    pub fn parse(entries: &[String]) -> Result<Self, String> {
+        entries
+            .iter()
+            .map(|entry| {
+                let (prefix, name) = entry
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected 0xPREFIX=NAME, got '{entry}'"))?;
+                let prefix = hex::decode(prefix.trim())
+                    .map_err(|e| format!("invalid prefix in '{entry}': {e}"))?;
+                let name = name.trim();
+                if prefix.is_empty() || name.is_empty() {
+                    return Err(format!("empty prefix or name in '{entry}'"));
+                }
+                Ok((prefix, name.to_owned()))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// Name of the builder of a block with the given extra data: the known builder with the
+    /// longest matching prefix, otherwise the extra data itself, as text if it is printable
+    /// ASCII and hex encoded if not.
+    pub fn identify(&self, extra_data: &[u8]) -> String {
+        let known = self
+            .0
+            .iter()
+            .filter(|(prefix, _)| extra_data.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len());
+        if let Some((_, name)) = known {
+            return name.clone();
+        }
+        match std::str::from_utf8(extra_data) {
+            Ok(text)
+                if !text.is_empty() && text.bytes().all(|b| b.is_ascii_graphic() || b == b' ') =>
+            {
+                text.trim().to_owned()
+            }
+            _ => hex::encode_prefixed(extra_data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifies_known_and_unknown_builders() {
+        let fingerprints = BuilderFingerprints::parse(&[
+            "0x7461696b6f=Taiko".to_owned(),
+            "0x7461696b6f2d67657468=Taiko Geth".to_owned(),
+        ])
+        .unwrap();
+
+        assert_eq!(fingerprints.identify(b"taiko-geth/v1.2"), "Taiko Geth");
+        assert_eq!(fingerprints.identify(b"taiko-reth"), "Taiko");
+        assert_eq!(fingerprints.identify(b"nethermind"), "nethermind");
+        assert_eq!(fingerprints.identify(&[0, 0, 75]), "0x00004b");
+        assert_eq!(fingerprints.identify(&[]), "0x");
+    }
+
+    #[test]
+    fn rejects_malformed_fingerprints() {
+        assert!(BuilderFingerprints::parse(&["Taiko".to_owned()]).is_err());
+        assert!(BuilderFingerprints::parse(&["0xzz=Taiko".to_owned()]).is_err());
+        assert!(BuilderFingerprints::parse(&["0x=Taiko".to_owned()]).is_err());
+        assert!(BuilderFingerprints::parse(&["0x74=".to_owned()]).is_err());
+    }
+}
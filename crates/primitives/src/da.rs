@@ -0,0 +1,65 @@
+//! Data availability modes of proposed batches.
+
+/// Where a batch posted its transaction list, stored as its `u8` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DaMode {
+    /// Transactions posted in EIP-4844 blobs
+    Blob = 0,
+    /// Transactions posted as `txList` calldata of the proposal
+    Calldata = 1,
+}
+
+impl DaMode {
+    /// Mode of a batch proposed with a `txList` of the given length. The inbox only reads blobs
+    /// when the calldata list is empty.
+    pub const fn from_tx_list_len(len: usize) -> Self {
+        if len == 0 { Self::Blob } else { Self::Calldata }
+    }
+
+    /// Mode stored for the given code, [`Self::Blob`] for unassigned codes.
+    pub const fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::Calldata,
+            _ => Self::Blob,
+        }
+    }
+
+    /// Parse the lowercase name returned by [`Self::as_str`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "blob" => Some(Self::Blob),
+            "calldata" => Some(Self::Calldata),
+            _ => None,
+        }
+    }
+
+    /// Short lowercase name of the mode.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Blob => "blob",
+            Self::Calldata => "calldata",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn da_mode_codes_and_names_round_trip() {
+        for mode in [DaMode::Blob, DaMode::Calldata] {
+            assert_eq!(DaMode::from_code(mode as u8), mode);
+            assert_eq!(DaMode::from_name(mode.as_str()), Some(mode));
+        }
+        assert_eq!(DaMode::from_code(7), DaMode::Blob);
+        assert_eq!(DaMode::from_name("BLOB"), None);
+    }
+
+    #[test]
+    fn empty_tx_list_uses_blobs() {
+        assert_eq!(DaMode::from_tx_list_len(0), DaMode::Blob);
+        assert_eq!(DaMode::from_tx_list_len(1_024), DaMode::Calldata);
+    }
+}
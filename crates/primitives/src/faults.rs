@@ -0,0 +1,143 @@
+//! Seedable fault schedules for chaos testing.
+//!
+//! A [`FaultSchedule`] decides, operation by operation, whether an injected fault hits it. The
+//! same seed always produces the same sequence of faults, so tests exercising retry and
+//! resubscribe logic are deterministic.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Fault injected into a single operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// Run the operation normally
+    None,
+    /// Wait before running the operation
+    Delay(Duration),
+    /// Fail the operation (drop the connection, reject the insert)
+    Fail,
+}
+
+#[derive(Debug)]
+struct ScheduleState {
+    rng: u64,
+    script: VecDeque<Fault>,
+}
+
+/// Deterministic sequence of faults, shared between clones.
+#[derive(Debug, Clone)]
+pub struct FaultSchedule {
+    state: Arc<Mutex<ScheduleState>>,
+    failure_rate: f64,
+    latency_rate: f64,
+    latency: Duration,
+}
+
+impl FaultSchedule {
+    /// Create a schedule that injects no faults until rates or a script are configured.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ScheduleState { rng: seed, script: VecDeque::new() })),
+            failure_rate: 0.0,
+            latency_rate: 0.0,
+            latency: Duration::ZERO,
+        }
+    }
+
+    /// Fail the given fraction of operations.
+    pub const fn with_failure_rate(mut self, rate: f64) -> Self {
+        self.failure_rate = rate;
+        self
+    }
+
+    /// Delay the given fraction of operations by `latency`.
+    pub const fn with_latency(mut self, latency: Duration, rate: f64) -> Self {
+        self.latency = latency;
+        self.latency_rate = rate;
+        self
+    }
+
+    /// Inject `faults` into the next operations, in order, before falling back to the seeded
+    /// rates.
+    pub fn with_script(self, faults: impl IntoIterator<Item = Fault>) -> Self {
+        self.lock().script.extend(faults);
+        self
+    }
+
+    /// Fault to inject into the next operation.
+    pub fn next_fault(&self) -> Fault {
+        let mut state = self.lock();
+        if let Some(fault) = state.script.pop_front() {
+            return fault;
+        }
+
+        let roll = next_unit(&mut state.rng);
+        if roll < self.failure_rate {
+            Fault::Fail
+        } else if roll < self.failure_rate + self.latency_rate {
+            Fault::Delay(self.latency)
+        } else {
+            Fault::None
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ScheduleState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Advance the splitmix64 generator and map its output to `[0, 1)`.
+fn next_unit(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(schedule: &FaultSchedule, n: usize) -> Vec<Fault> {
+        std::iter::repeat_with(|| schedule.next_fault()).take(n).collect()
+    }
+
+    #[test]
+    fn same_seed_same_faults() {
+        let make = || {
+            FaultSchedule::new(42)
+                .with_failure_rate(0.3)
+                .with_latency(Duration::from_millis(5), 0.3)
+        };
+        assert_eq!(sequence(&make(), 64), sequence(&make(), 64));
+    }
+
+    #[test]
+    fn rates_are_respected() {
+        let schedule = FaultSchedule::new(7).with_failure_rate(0.25);
+        let faults = sequence(&schedule, 10_000);
+        let failures = faults.iter().filter(|f| **f == Fault::Fail).count();
+        assert!((2_000..3_000).contains(&failures), "got {failures} failures");
+        assert!(faults.iter().all(|f| *f != Fault::Delay(Duration::ZERO)));
+    }
+
+    #[test]
+    fn default_schedule_injects_nothing() {
+        let schedule = FaultSchedule::new(1);
+        assert!(sequence(&schedule, 100).iter().all(|f| *f == Fault::None));
+    }
+
+    #[test]
+    fn script_runs_first_and_is_shared_between_clones() {
+        let schedule = FaultSchedule::new(1).with_script([Fault::Fail, Fault::Fail]);
+        let clone = schedule.clone();
+        assert_eq!(schedule.next_fault(), Fault::Fail);
+        assert_eq!(clone.next_fault(), Fault::Fail);
+        assert_eq!(schedule.next_fault(), Fault::None);
+    }
+}
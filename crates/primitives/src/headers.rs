@@ -1,10 +1,12 @@
 //! Block header data structures and stream type aliases.
 use std::pin::Pin;
 
-use alloy_primitives::{Address, BlockHash};
+use alloy_primitives::{Address, BlockHash, Bytes};
 use futures::stream::Stream;
 use serde::{Deserialize, Serialize};
 
+use crate::ingest::Received;
+
 /// L1 Header
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct L1Header {
@@ -31,13 +33,17 @@ pub struct L2Header {
     pub timestamp: u64,
     /// Gas used
     pub gas_used: u64,
+    /// Gas limit
+    pub gas_limit: u64,
     /// Beneficiary
     pub beneficiary: Address,
     /// Base fee per gas
     pub base_fee_per_gas: u64,
+    /// Extra data
+    pub extra_data: Bytes,
 }
 
 /// Stream of L1 headers
-pub type L1HeaderStream = Pin<Box<dyn Stream<Item = L1Header> + Send>>;
+pub type L1HeaderStream = Pin<Box<dyn Stream<Item = Received<L1Header>> + Send>>;
 /// Stream of L2 headers
-pub type L2HeaderStream = Pin<Box<dyn Stream<Item = L2Header> + Send>>;
+pub type L2HeaderStream = Pin<Box<dyn Stream<Item = Received<L2Header>> + Send>>;
@@ -0,0 +1,57 @@
+//! Operations performed on status page incidents.
+
+/// Operation recorded in the incident audit log, stored as its `u8` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum IncidentAction {
+    /// A monitor opened an incident
+    Create = 0,
+    /// An open incident changed status without being resolved
+    Update = 1,
+    /// An incident was resolved
+    Resolve = 2,
+}
+
+impl IncidentAction {
+    /// Every action, in code order.
+    pub const ALL: [Self; 3] = [Self::Create, Self::Update, Self::Resolve];
+
+    /// Action stored for the given code, `None` for unassigned codes.
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Create),
+            1 => Some(Self::Update),
+            2 => Some(Self::Resolve),
+            _ => None,
+        }
+    }
+
+    /// Parse the lowercase name returned by [`Self::as_str`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.as_str() == name)
+    }
+
+    /// Short lowercase name of the action.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Update => "update",
+            Self::Resolve => "resolve",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn incident_action_codes_and_names_round_trip() {
+        for action in IncidentAction::ALL {
+            assert_eq!(IncidentAction::from_code(action as u8), Some(action));
+            assert_eq!(IncidentAction::from_name(action.as_str()), Some(action));
+        }
+        assert_eq!(IncidentAction::from_code(7), None);
+        assert_eq!(IncidentAction::from_name("Resolve"), None);
+    }
+}
@@ -0,0 +1,45 @@
+//! Receipt timestamps of values ingested from the chain.
+
+use std::time::{Duration, Instant};
+
+/// A value tagged with the monotonic time it was received from the chain.
+///
+/// The extractor wraps every header and log it yields, so later stages can measure how long the
+/// value spent in the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Received<T> {
+    /// The received value
+    pub value: T,
+    /// Time of receipt
+    pub at: Instant,
+}
+
+impl<T> Received<T> {
+    /// Tag `value` with the current time.
+    pub fn now(value: T) -> Self {
+        Self { value, at: Instant::now() }
+    }
+
+    /// Replace the value, keeping the time of receipt.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Received<U> {
+        Received { value: f(self.value), at: self.at }
+    }
+
+    /// Time elapsed since receipt.
+    pub fn elapsed(&self) -> Duration {
+        self.at.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_keeps_time_of_receipt() {
+        let received = Received::now(2u64);
+        let mapped = received.map(|value| value * 10);
+        assert_eq!(mapped.value, 20);
+        assert_eq!(mapped.at, received.at);
+    }
+}
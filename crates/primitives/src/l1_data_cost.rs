@@ -11,17 +11,57 @@ pub fn calculate_blob_fee_from_receipt<R: ReceiptResponse>(receipt: &R) -> u128
     }
 }
 
+/// Calldata gas per token, a zero byte counting as one token and a non-zero byte as four
+/// (EIP-2028).
+const STANDARD_TOKEN_GAS: u64 = 4;
+
+/// Calldata gas floor per token (EIP-7623).
+const FLOOR_TOKEN_GAS: u64 = 10;
+
+/// Gas charged for the calldata `input`: the standard cost of 4 per zero and 16 per non-zero
+/// byte, raised to the EIP-7623 floor of 10 per zero and 40 per non-zero byte.
+pub fn calldata_gas(input: &[u8]) -> u64 {
+    let tokens: u64 = input.iter().map(|&byte| if byte == 0 { 1 } else { 4 }).sum();
+    tokens.saturating_mul(STANDARD_TOKEN_GAS).max(tokens.saturating_mul(FLOOR_TOKEN_GAS))
+}
+
+/// Cost of a data posting transaction split by fee market.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct L1DataCostBreakdown {
+    /// Fee paid for blob gas (EIP-4844)
+    pub blob_cost: u128,
+    /// Fee paid for the calldata gas of the transaction input
+    pub calldata_cost: u128,
+    /// Fee paid for the remaining regular gas: intrinsic and execution gas
+    pub execution_cost: u128,
+}
+
+impl L1DataCostBreakdown {
+    /// Total cost across both fee markets
+    pub const fn total(&self) -> u128 {
+        self.blob_cost.saturating_add(self.calldata_cost).saturating_add(self.execution_cost)
+    }
+}
+
+/// Split the cost of a single receipt into its blob, calldata and execution components, with
+/// the calldata gas computed from the transaction `input`.
+pub fn cost_breakdown_from_receipt<R: ReceiptResponse>(
+    receipt: &R,
+    input: &[u8],
+) -> L1DataCostBreakdown {
+    let price = receipt.effective_gas_price();
+    let calldata_gas = calldata_gas(input).min(receipt.gas_used());
+    L1DataCostBreakdown {
+        blob_cost: calculate_blob_fee_from_receipt(receipt),
+        calldata_cost: (calldata_gas as u128).saturating_mul(price),
+        execution_cost: ((receipt.gas_used() - calldata_gas) as u128).saturating_mul(price),
+    }
+}
+
 /// Calculate the total cost for a single receipt (execution + blob fees).
 /// This function computes the cost for any transaction receipt without filtering.
 pub fn cost_from_receipt<R: ReceiptResponse>(receipt: &R) -> u128 {
-    // Calculate execution gas cost (gas_used * effective_gas_price)
-    let exec_cost = (receipt.gas_used() as u128).saturating_mul(receipt.effective_gas_price());
-
-    // Calculate blob gas cost if present
-    let blob_cost = calculate_blob_fee_from_receipt(receipt);
-
-    // Return total cost
-    exec_cost.saturating_add(blob_cost)
+    cost_breakdown_from_receipt(receipt, &[]).total()
 }
 
 /// Compute the total L1 data posting cost for the given transactions and receipts.
@@ -265,6 +305,43 @@ mod tests {
         assert_eq!(cost, 500);
     }
 
+    #[test]
+    fn calldata_gas_prices_zero_bytes_lower() {
+        assert_eq!(calldata_gas(&[]), 0);
+        assert_eq!(calldata_gas(&[0, 0, 1, 0xff]), 10 + 10 + 40 + 40);
+    }
+
+    #[test]
+    fn calldata_gas_applies_the_floor_to_calldata_heavy_batches() {
+        // A 100 KiB calldata batch pays the floor of 40 gas per non-zero byte, not 16
+        let input = vec![0xab; 100 * 1024];
+        assert_eq!(calldata_gas(&input), 40 * 100 * 1024);
+        let receipt = TestReceipt { gas: 5_000_000, price: 1, blob_gas: None, blob_price: None };
+        let breakdown = cost_breakdown_from_receipt(&receipt, &input);
+        assert_eq!(breakdown.calldata_cost, 4_096_000);
+        assert_eq!(breakdown.execution_cost, 904_000);
+    }
+
+    #[test]
+    fn cost_breakdown_separates_blob_calldata_and_execution() {
+        let receipt =
+            TestReceipt { gas: 1000, price: 50, blob_gas: Some(200), blob_price: Some(10) };
+        // 2 non-zero and 3 zero bytes at the floor: 2 * 40 + 3 * 10 = 110 gas
+        let breakdown = cost_breakdown_from_receipt(&receipt, &[1, 0, 2, 0, 0]);
+        assert_eq!(
+            breakdown,
+            L1DataCostBreakdown { blob_cost: 2000, calldata_cost: 5500, execution_cost: 44500 }
+        );
+        assert_eq!(breakdown.total(), cost_from_receipt(&receipt));
+
+        // Calldata gas never exceeds the gas used
+        let receipt = TestReceipt { gas: 20, price: 5, blob_gas: None, blob_price: None };
+        assert_eq!(
+            cost_breakdown_from_receipt(&receipt, &[1, 1]),
+            L1DataCostBreakdown { blob_cost: 0, calldata_cost: 100, execution_cost: 0 }
+        );
+    }
+
     #[test]
     fn cost_from_receipt_zero_cost() {
         let receipt = TestReceipt { gas: 0, price: 0, blob_gas: None, blob_price: None };
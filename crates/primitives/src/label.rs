@@ -0,0 +1,43 @@
+//! Sources of address labels.
+
+/// Where the label of an address comes from, stored as its `u8` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum LabelSource {
+    /// Configured static label map
+    Static = 0,
+    /// Verified ENS reverse record on L1
+    Ens = 1,
+}
+
+impl LabelSource {
+    /// Source stored for the given code, `None` for unassigned codes.
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Self::Static),
+            1 => Some(Self::Ens),
+            _ => None,
+        }
+    }
+
+    /// Short lowercase name of the source.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Static => "static",
+            Self::Ens => "ens",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_source_codes_round_trip() {
+        for source in [LabelSource::Static, LabelSource::Ens] {
+            assert_eq!(LabelSource::from_code(source as u8), Some(source));
+        }
+        assert_eq!(LabelSource::from_code(2), None);
+    }
+}
@@ -1,14 +1,39 @@
 //! Core primitives for the Taikoscope project.
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
+/// Core metrics watched for anomalies
+pub mod anomaly;
+/// Beacon chain slot and epoch arithmetic
+pub mod beacon;
 /// Block analytics helpers
 pub mod block_stats;
+/// Fingerprints of L2 block builders
+pub mod builder;
+/// Data availability modes of proposed batches
+pub mod da;
+/// Seedable fault schedules for chaos testing
+pub mod faults;
 /// Hardware cost estimates
 pub mod hardware;
 /// Block header types
 pub mod headers;
+/// Operations performed on status page incidents
+pub mod incident;
+/// Receipt timestamps of ingested values
+pub mod ingest;
 /// L1 data cost calculation helpers
 pub mod l1_data_cost;
+/// Sources of address labels
+pub mod label;
+/// Proof types of proved batches
+pub mod proof;
+/// Probable causes of L2 reorgs
+pub mod reorg;
+/// Roles of indexed L1 protocol transactions
+pub mod tx_role;
 
 /// Number of wei in one gwei.
 pub const WEI_PER_GWEI: u128 = 1_000_000_000;
+
+/// Size of an EIP-4844 blob in bytes.
+pub const BYTES_PER_BLOB: u64 = 131_072;
@@ -0,0 +1,78 @@
+//! Proof types of proved batches.
+
+/// Kind of proof a batch was proved with, stored as its `u8` code.
+///
+/// Variants are ordered by strength, so a batch proved with several sub-proofs takes the
+/// strongest of their types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum ProofType {
+    /// The verifier could not be identified, e.g. for batches proved before proof types were
+    /// recorded
+    Unknown = 0,
+    /// Trusted execution environment proof, e.g. SGX or TDX
+    Sgx = 1,
+    /// Zero-knowledge proof, e.g. RISC Zero or SP1
+    Zk = 2,
+    /// Proof signed by the guardian provers
+    Guardian = 3,
+}
+
+impl ProofType {
+    /// Every proof type, in code order.
+    pub const ALL: [Self; 4] = [Self::Unknown, Self::Sgx, Self::Zk, Self::Guardian];
+
+    /// Type stored for the given code, [`Self::Unknown`] for unassigned codes.
+    pub const fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::Sgx,
+            2 => Self::Zk,
+            3 => Self::Guardian,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Parse the lowercase name returned by [`Self::as_str`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|proof_type| proof_type.as_str() == name)
+    }
+
+    /// Short lowercase name of the type.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Sgx => "sgx",
+            Self::Zk => "zk",
+            Self::Guardian => "guardian",
+        }
+    }
+
+    /// Type of a proof made of the given sub-proofs: the strongest identified one, or
+    /// [`Self::Unknown`] when none was identified.
+    pub fn combined(sub_proofs: impl IntoIterator<Item = Self>) -> Self {
+        sub_proofs.into_iter().max().unwrap_or(Self::Unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proof_type_codes_and_names_round_trip() {
+        for proof_type in ProofType::ALL {
+            assert_eq!(ProofType::from_code(proof_type as u8), proof_type);
+            assert_eq!(ProofType::from_name(proof_type.as_str()), Some(proof_type));
+        }
+        assert_eq!(ProofType::from_code(9), ProofType::Unknown);
+        assert_eq!(ProofType::from_name("SGX"), None);
+    }
+
+    #[test]
+    fn combined_proof_takes_the_strongest_type() {
+        assert_eq!(ProofType::combined([]), ProofType::Unknown);
+        assert_eq!(ProofType::combined([ProofType::Sgx, ProofType::Unknown]), ProofType::Sgx);
+        assert_eq!(ProofType::combined([ProofType::Sgx, ProofType::Zk]), ProofType::Zk);
+        assert_eq!(ProofType::combined([ProofType::Guardian, ProofType::Zk]), ProofType::Guardian);
+    }
+}
@@ -0,0 +1,55 @@
+//! Probable causes of L2 reorgs.
+
+/// Event an L2 reorg coincided with, stored as its `u8` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ReorgCause {
+    /// No correlated event was observed
+    Unknown = 0,
+    /// An L1 reorg shortly before the L2 reorg
+    L1Reorg = 1,
+    /// A sequencer change at an L1 epoch boundary
+    OperatorChange = 2,
+    /// A forced inclusion processed shortly before the L2 reorg
+    ForcedInclusion = 3,
+}
+
+impl ReorgCause {
+    /// Cause stored for the given code, [`Self::Unknown`] for unassigned codes.
+    pub const fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::L1Reorg,
+            2 => Self::OperatorChange,
+            3 => Self::ForcedInclusion,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Short lowercase name of the cause.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::L1Reorg => "l1_reorg",
+            Self::OperatorChange => "operator_change",
+            Self::ForcedInclusion => "forced_inclusion",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reorg_cause_codes_round_trip() {
+        for cause in [
+            ReorgCause::Unknown,
+            ReorgCause::L1Reorg,
+            ReorgCause::OperatorChange,
+            ReorgCause::ForcedInclusion,
+        ] {
+            assert_eq!(ReorgCause::from_code(cause as u8), cause);
+        }
+        assert_eq!(ReorgCause::from_code(42), ReorgCause::Unknown);
+    }
+}
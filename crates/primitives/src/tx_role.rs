@@ -0,0 +1,55 @@
+//! Roles of indexed L1 protocol transactions.
+
+/// Role an L1 transaction played in the protocol, stored as its `u8` code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum L1TxRole {
+    /// Proposed a batch
+    Proposal = 1,
+    /// Proved one or more batches
+    Prove = 2,
+    /// Verified batches up to a batch
+    Verify = 3,
+    /// Processed a forced inclusion
+    ForcedInclusion = 4,
+}
+
+impl L1TxRole {
+    /// Every role, in code order.
+    pub const ALL: [Self; 4] = [Self::Proposal, Self::Prove, Self::Verify, Self::ForcedInclusion];
+
+    /// Role stored for the given code, `None` for unassigned codes.
+    pub const fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(Self::Proposal),
+            2 => Some(Self::Prove),
+            3 => Some(Self::Verify),
+            4 => Some(Self::ForcedInclusion),
+            _ => None,
+        }
+    }
+
+    /// Short lowercase name of the role.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Proposal => "proposal",
+            Self::Prove => "prove",
+            Self::Verify => "verify",
+            Self::ForcedInclusion => "forced_inclusion",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn role_codes_round_trip() {
+        for role in L1TxRole::ALL {
+            assert_eq!(L1TxRole::from_code(role as u8), Some(role));
+        }
+        assert_eq!(L1TxRole::from_code(0), None);
+        assert_eq!(L1TxRole::from_code(9), None);
+    }
+}
@@ -0,0 +1,95 @@
+//! Bearer token authentication for operational endpoints.
+
+use std::sync::Arc;
+
+use api_types::{ErrorCode, ErrorResponse};
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+
+/// Reject requests that do not carry `Authorization: Bearer <token>` with the expected token.
+/// An empty token matches no request.
+pub async fn require_token(
+    State(token): State<Arc<str>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let valid = !token.is_empty() &&
+        provided.is_some_and(|provided| constant_time_eq(provided.as_bytes(), token.as_bytes()));
+    if valid {
+        return next.run(request).await;
+    }
+    ErrorResponse::new(
+        ErrorCode::Unauthorized,
+        "Unauthorized",
+        StatusCode::UNAUTHORIZED,
+        "missing or invalid admin token",
+    )
+    .into_response()
+}
+
+/// Require the admin `token` on every route of `router`.
+pub fn protect<S>(router: Router<S>, token: &str) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.layer(middleware::from_fn_with_state(Arc::<str>::from(token), require_token))
+}
+
+/// Compare two byte strings in time independent of where they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::post};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        protect(Router::new().route("/flush", post(|| async { "ok" })), "secret")
+    }
+
+    async fn status(authorization: Option<&str>) -> StatusCode {
+        let mut request = Request::post("/flush");
+        if let Some(value) = authorization {
+            request = request.header(header::AUTHORIZATION, value);
+        }
+        app().oneshot(request.body(Body::empty()).unwrap()).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn accepts_matching_token() {
+        assert_eq!(status(Some("Bearer secret")).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_or_wrong_token() {
+        assert_eq!(status(None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(Some("Bearer secreT")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status(Some("secret")).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn empty_token_rejects_everything() {
+        let app = protect(Router::new().route("/flush", post(|| async { "ok" })), "");
+        for authorization in ["Bearer ", "Bearer"] {
+            let request = Request::post("/flush")
+                .header(header::AUTHORIZATION, authorization)
+                .body(Body::empty())
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+}
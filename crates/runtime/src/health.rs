@@ -9,7 +9,12 @@ use crate::shutdown::ShutdownSignal;
 
 /// Health check handler returning `{ "status": "ok" }`.
 pub async fn handler() -> Json<HealthResponse> {
-    Json(HealthResponse { status: "ok".to_owned() })
+    Json(HealthResponse {
+        status: "ok".to_owned(),
+        rpc_endpoints: Vec::new(),
+        stream_channels: Vec::new(),
+        data_quality: None,
+    })
 }
 
 /// Create a router exposing the `/health` endpoint.
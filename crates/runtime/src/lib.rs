@@ -3,7 +3,9 @@
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
 
+pub mod admin;
 pub mod health;
+pub mod logging;
 pub mod rate_limiter;
 pub mod shutdown;
 
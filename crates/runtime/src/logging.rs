@@ -0,0 +1,103 @@
+//! Tracing subscriber setup and sampling of repetitive log lines.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use config::{LogFormat, LogOpts};
+use tracing_subscriber::filter::EnvFilter;
+
+/// Sampling window used by [`LogSampler::new`], in seconds.
+static SAMPLE_WINDOW_SECS: AtomicU64 = AtomicU64::new(60);
+
+/// Install the global tracing subscriber in the configured format. Log levels are taken from
+/// `RUST_LOG`, defaulting to `info`.
+pub fn init(opts: &LogOpts) {
+    SAMPLE_WINDOW_SECS.store(opts.sample_window_secs, Ordering::Relaxed);
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let builder = tracing_subscriber::fmt()
+        .with_file(true)
+        .with_line_number(true)
+        .with_target(true)
+        .with_env_filter(filter);
+
+    match opts.format {
+        LogFormat::Json => builder.json().with_current_span(true).with_span_list(false).init(),
+        LogFormat::Pretty => builder.init(),
+    }
+}
+
+/// Limits a repetitive log line to one occurrence per sampling window.
+///
+/// Events that are dropped are counted and reported with the next emitted one, so no
+/// information about the frequency is lost.
+#[derive(Debug)]
+pub struct LogSampler {
+    window: Duration,
+    last_emitted: Option<Instant>,
+    suppressed: u64,
+}
+
+impl LogSampler {
+    /// Create a sampler using the window configured with `--log-sample-window-secs`.
+    pub fn new() -> Self {
+        Self::with_window(Duration::from_secs(SAMPLE_WINDOW_SECS.load(Ordering::Relaxed)))
+    }
+
+    /// Create a sampler with the given window. A zero window logs every event.
+    pub const fn with_window(window: Duration) -> Self {
+        Self { window, last_emitted: None, suppressed: 0 }
+    }
+
+    /// Record an occurrence. Returns the number of occurrences suppressed since the last
+    /// emitted one if this occurrence should be logged, `None` otherwise.
+    pub fn sample(&mut self) -> Option<u64> {
+        let now = Instant::now();
+        if self.last_emitted.is_some_and(|last| now.duration_since(last) < self.window) {
+            self.suppressed += 1;
+            return None;
+        }
+        self.last_emitted = Some(now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+impl Default for LogSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogSampler;
+    use std::time::Duration;
+
+    #[test]
+    fn suppresses_within_window() {
+        let mut sampler = LogSampler::with_window(Duration::from_secs(60));
+        assert_eq!(sampler.sample(), Some(0));
+        assert_eq!(sampler.sample(), None);
+        assert_eq!(sampler.sample(), None);
+    }
+
+    #[test]
+    fn reports_suppressed_count_after_window() {
+        let mut sampler = LogSampler::with_window(Duration::from_millis(10));
+        assert_eq!(sampler.sample(), Some(0));
+        assert_eq!(sampler.sample(), None);
+        assert_eq!(sampler.sample(), None);
+        std::thread::sleep(Duration::from_millis(15));
+        assert_eq!(sampler.sample(), Some(2));
+        assert_eq!(sampler.sample(), None);
+    }
+
+    #[test]
+    fn zero_window_logs_everything() {
+        let mut sampler = LogSampler::with_window(Duration::ZERO);
+        assert_eq!(sampler.sample(), Some(0));
+        assert_eq!(sampler.sample(), Some(0));
+    }
+}
@@ -3,46 +3,115 @@ use std::{
     time::{Duration, Instant},
 };
 
-/// A simple fixed window rate limiter.
+/// A token bucket rate limiter.
+///
+/// The bucket holds up to `burst` tokens and refills continuously at `capacity` tokens per
+/// `period`, so traffic is smoothed instead of being allowed in bursts at window boundaries.
+/// Clones share the bucket and its settings.
 #[derive(Clone, Debug)]
 pub struct RateLimiter {
-    state: Arc<Mutex<LimiterState>>,
+    state: Arc<Mutex<BucketState>>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    refilled_at: Instant,
     capacity: u64,
+    burst: u64,
     period: Duration,
 }
 
-#[derive(Debug)]
-struct LimiterState {
-    count: u64,
-    reset_at: Instant,
+impl BucketState {
+    const fn refill_per_sec(&self) -> f64 {
+        self.capacity as f64 / self.period.as_secs_f64()
+    }
 }
 
 impl RateLimiter {
-    /// Create a new [`RateLimiter`] with the given `capacity` and `period`.
+    /// Create a new [`RateLimiter`] refilling `capacity` tokens per `period`, with a burst of
+    /// `capacity`.
     pub fn new(capacity: u64, period: Duration) -> Self {
         Self {
-            state: Arc::new(Mutex::new(LimiterState {
-                count: 0,
-                reset_at: Instant::now() + period,
+            state: Arc::new(Mutex::new(BucketState {
+                tokens: capacity as f64,
+                refilled_at: Instant::now(),
+                capacity,
+                burst: capacity,
+                period,
             })),
-            capacity,
-            period,
         }
     }
 
+    /// Set the bucket size, i.e. the largest number of tokens that can be spent at once.
+    pub fn with_burst(self, burst: u64) -> Self {
+        {
+            let mut state = self.state.lock().expect("lock poisoned");
+            state.burst = burst;
+            state.tokens = burst as f64;
+            state.refilled_at = Instant::now();
+        }
+        self
+    }
+
+    /// Change the refill rate and bucket size of the limiter and all its clones. Tokens above
+    /// the new bucket size are dropped, the others are kept.
+    pub fn reconfigure(&self, capacity: u64, period: Duration, burst: u64) {
+        let mut state = self.state.lock().expect("lock poisoned");
+        state.capacity = capacity;
+        state.period = period;
+        state.burst = burst;
+        state.tokens = state.tokens.min(burst as f64);
+    }
+
+    /// Size of the bucket.
+    pub fn burst(&self) -> u64 {
+        self.state.lock().expect("lock poisoned").burst
+    }
+
     /// Attempt to acquire a permit.
     pub fn try_acquire(&self) -> bool {
+        self.try_acquire_weighted(1).is_ok()
+    }
+
+    /// Attempt to take `weight` tokens from the bucket. Weights above the bucket size are
+    /// capped to it.
+    ///
+    /// Returns the number of tokens left on success, or how long to wait until enough tokens
+    /// are available.
+    pub fn try_acquire_weighted(&self, weight: u64) -> Result<u64, Duration> {
         let mut state = self.state.lock().expect("lock poisoned");
+        if state.burst == 0 {
+            return Err(state.period);
+        }
+
         let now = Instant::now();
-        if now >= state.reset_at {
-            state.reset_at = now + self.period;
-            state.count = 1;
-            true
-        } else if state.count < self.capacity {
-            state.count += 1;
-            true
+        let refill_per_sec = state.refill_per_sec();
+        let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+        state.tokens = if state.period.is_zero() {
+            state.burst as f64
+        } else {
+            elapsed.mul_add(refill_per_sec, state.tokens).min(state.burst as f64)
+        };
+        state.refilled_at = now;
+
+        let cost = weight.min(state.burst) as f64;
+        if state.tokens >= cost {
+            state.tokens -= cost;
+            Ok(state.tokens as u64)
+        } else if refill_per_sec > 0.0 {
+            Err(Duration::from_secs_f64((cost - state.tokens) / refill_per_sec))
         } else {
-            false
+            Err(state.period)
+        }
+    }
+
+    /// Wait until a permit is available and take it.
+    ///
+    /// Never completes when the bucket size is zero.
+    pub async fn acquire(&self) {
+        while let Err(wait) = self.try_acquire_weighted(1) {
+            tokio::time::sleep(wait).await;
         }
     }
 }
@@ -101,8 +170,16 @@ mod tests {
         let limiter = RateLimiter::new(0, Duration::from_millis(5));
         assert!(!limiter.try_acquire());
         sleep(Duration::from_millis(10)).await;
-        // After a period elapses, the limiter resets its counter, allowing a single request
-        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+    }
+
+    #[tokio::test]
+    async fn acquire_waits_for_refill() {
+        let limiter = RateLimiter::new(1, Duration::from_millis(20));
+        limiter.acquire().await;
+        let started = std::time::Instant::now();
+        limiter.acquire().await;
+        assert!(started.elapsed() >= Duration::from_millis(15));
     }
 
     #[tokio::test]
@@ -126,4 +203,46 @@ mod tests {
         sleep(Duration::from_millis(15)).await;
         assert!(limiter.try_acquire());
     }
+
+    #[tokio::test]
+    async fn burst_is_limited_by_bucket_size() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(60)).with_burst(3);
+        assert_eq!(limiter.burst(), 3);
+        assert_eq!(limiter.try_acquire_weighted(1), Ok(2));
+        assert_eq!(limiter.try_acquire_weighted(1), Ok(1));
+        assert_eq!(limiter.try_acquire_weighted(1), Ok(0));
+        assert!(limiter.try_acquire_weighted(1).is_err());
+    }
+
+    #[tokio::test]
+    async fn weighted_requests_consume_more_tokens() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(60));
+        assert_eq!(limiter.try_acquire_weighted(8), Ok(2));
+        let retry_after = limiter.try_acquire_weighted(5).unwrap_err();
+        // Three tokens are missing at a refill rate of one every six seconds
+        assert!(retry_after > Duration::from_secs(17) && retry_after <= Duration::from_secs(18));
+        assert_eq!(limiter.try_acquire_weighted(2), Ok(0));
+    }
+
+    #[tokio::test]
+    #[allow(clippy::redundant_clone)]
+    async fn reconfigure_applies_to_clones() {
+        let limiter = RateLimiter::new(10, Duration::from_secs(60));
+        let clone = limiter.clone();
+        assert_eq!(clone.try_acquire_weighted(2), Ok(8));
+
+        limiter.reconfigure(2, Duration::from_secs(60), 3);
+        assert_eq!(clone.burst(), 3);
+        // The eight tokens left are capped to the new bucket size
+        assert_eq!(clone.try_acquire_weighted(5), Ok(0));
+        let retry_after = clone.try_acquire_weighted(1).unwrap_err();
+        // One token is missing at the new refill rate of one every thirty seconds
+        assert!(retry_after > Duration::from_secs(29) && retry_after <= Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn weight_is_capped_to_bucket_size() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        assert_eq!(limiter.try_acquire_weighted(10), Ok(0));
+    }
 }
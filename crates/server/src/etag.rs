@@ -0,0 +1,108 @@
+//! `ETag` and `If-None-Match` handling of the API server.
+
+#![allow(clippy::redundant_pub_crate)]
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::Request,
+    http::{
+        HeaderMap, HeaderValue, Method, StatusCode,
+        header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH, VARY},
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+/// Largest body that is buffered to compute its `ETag`
+const MAX_ETAG_BODY_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Tag successful `GET` and `HEAD` responses with a weak `ETag` derived from their body, and
+/// answer `304 Not Modified` when it matches the request's `If-None-Match`.
+///
+/// Only bodies whose size is known up front are tagged; streamed responses such as server-sent
+/// events and chunked tables are passed through untouched. The tag is weak because the body may
+/// still be compressed on the way out.
+pub(crate) async fn set_etag(request: Request, next: Next) -> Response {
+    let conditional = matches!(*request.method(), Method::GET | Method::HEAD)
+        .then(|| request.headers().get(IF_NONE_MATCH).cloned());
+    let response = next.run(request).await;
+    let Some(if_none_match) = conditional else {
+        return response;
+    };
+    if response.status() != StatusCode::OK || response.headers().contains_key(ETAG) {
+        return response;
+    }
+    match response.body().size_hint().exact() {
+        Some(len) if len <= MAX_ETAG_BODY_BYTES => {}
+        _ => return response,
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_ETAG_BODY_BYTES as usize).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(error = %e, "Failed to buffer response body for ETag");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let etag = body_etag(&bytes);
+    if if_none_match.is_some_and(|value| matches_etag(&value, &etag)) {
+        let mut headers = HeaderMap::new();
+        for name in [CACHE_CONTROL, VARY] {
+            if let Some(value) = parts.headers.get(&name) {
+                headers.insert(name, value.clone());
+            }
+        }
+        headers.insert(ETAG, etag);
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+    parts.headers.insert(ETAG, etag);
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Weak `ETag` of a response body. The SHA-256 digest is stable across builds and instances, so
+/// replicas behind a load balancer agree on the tag.
+fn body_etag(body: &[u8]) -> HeaderValue {
+    HeaderValue::from_str(&format!("W/\"{:x}\"", Sha256::digest(body)))
+        .expect("hex digits are valid header characters")
+}
+
+/// Whether an `If-None-Match` value matches `etag`, using the weak comparison of RFC 9110
+fn matches_etag(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let Ok(value) = if_none_match.to_str() else {
+        return false;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_owned();
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    value.trim() == "*" || value.split(',').any(|tag| opaque(tag) == opaque(etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn etag_depends_on_body() {
+        assert_eq!(body_etag(b"{\"a\":1}"), body_etag(b"{\"a\":1}"));
+        assert_ne!(body_etag(b"{\"a\":1}"), body_etag(b"{\"a\":2}"));
+        assert_eq!(
+            body_etag(b""),
+            "W/\"e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855\""
+        );
+    }
+
+    #[test]
+    fn if_none_match_uses_weak_comparison() {
+        let tag = HeaderValue::from_static("W/\"00000000000000ab\"");
+        let header = |value: &'static str| HeaderValue::from_static(value);
+        assert!(matches_etag(&header("W/\"00000000000000ab\""), &tag));
+        assert!(matches_etag(&header("\"00000000000000ab\""), &tag));
+        assert!(matches_etag(&header("\"other\", W/\"00000000000000ab\""), &tag));
+        assert!(matches_etag(&header("*"), &tag));
+        assert!(!matches_etag(&header("W/\"00000000000000ac\""), &tag));
+    }
+}
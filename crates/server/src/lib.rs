@@ -2,80 +2,333 @@
 #![allow(clippy::uninlined_format_args)]
 #![allow(clippy::cognitive_complexity)]
 
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{future::IntoFuture, net::SocketAddr, sync::Arc, time::Duration};
 
-use api::{self, ApiState};
+use api::{self, ApiState, CacheTtls, QueryBudget, RateLimitConfig, StatusPageConfig};
 use axum::{
     Router,
-    http::{HeaderValue, Method},
+    extract::Request,
+    middleware::{self, Next},
+    response::Response,
     routing::get,
 };
 use clickhouse_lib::ClickhouseReader;
+use config::Opts;
 use eyre::Result;
-use runtime::health;
+use primitives::builder::BuilderFingerprints;
+use runtime::{health, rate_limiter::RateLimiter, shutdown::ShutdownSignal};
+mod etag;
+mod prewarm;
 mod rate_limit;
+mod reload;
+mod security;
+mod usage;
+pub use prewarm::PrewarmConfig;
 use rate_limit::RateLimitLayer;
+pub use reload::ReloadHandle;
+use security::AllowedOrigins;
+pub use security::HttpConfig;
 use tower_http::{
-    cors::{AllowOrigin, Any, CorsLayer},
-    trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer},
+    compression::CompressionLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer},
+    trace::{DefaultOnRequest, DefaultOnResponse, TraceLayer},
 };
-use tracing::{Level, info};
+use tracing::{Level, info, info_span, warn};
+use usage::OriginTracker;
 
 /// Version prefix for all API routes.
 pub const API_VERSION: &str = "v1";
 
-/// Build the API router with CORS and tracing layers.
-pub fn router(state: ApiState, allowed_origins: Vec<String>) -> Router {
-    let allowed = Arc::new(allowed_origins);
-    let cors = CorsLayer::new()
-        .allow_origin(AllowOrigin::predicate({
-            let allowed = Arc::clone(&allowed);
-            move |origin: &HeaderValue, _| match origin.to_str() {
-                Ok(origin) => {
-                    allowed.iter().any(|o| o == origin) ||
-                        origin.ends_with(".vercel.app") ||
-                        origin.starts_with("http://localhost:") ||
-                        origin.starts_with("http://127.0.0.1:")
-                }
-                Err(_) => false,
-            }
-        }))
-        .allow_methods([Method::GET])
-        .allow_headers(Any)
-        .expose_headers(Any);
+/// Request id of `request`, as assigned by [`SetRequestIdLayer`].
+fn request_id(request: &Request) -> &str {
+    request
+        .extensions()
+        .get::<RequestId>()
+        .and_then(|id| id.header_value().to_str().ok())
+        .unwrap_or_default()
+}
+
+/// Tag the `ClickHouse` queries issued while handling a request with its request id.
+async fn scope_request_id(request: Request, next: Next) -> Response {
+    let id = request_id(&request).to_owned();
+    clickhouse_lib::with_request_id(id, next.run(request)).await
+}
+
+/// Build the API router with CORS, security header, request id and tracing layers, and with
+/// `ETag` and compression layers when enabled in `http`. API requests are counted per `Origin`
+/// header in the state's origin usage. The HTML
+/// status page is served at `/status` when enabled in `state`, and the admin endpoints under
+/// `/admin` when `state` carries an admin token.
+///
+/// Every request gets an `x-request-id` header (kept if the client sent one) that is echoed in
+/// the response, recorded on the request span and attached to the `ClickHouse` queries it runs.
+pub fn router(state: ApiState, http: HttpConfig) -> Router {
+    reloadable_router(state, http).0
+}
+
+/// Build the API router like [`router`], with a handle to replace its CORS origins and rate
+/// limits while it serves requests.
+pub fn reloadable_router(state: ApiState, http: HttpConfig) -> (Router, ReloadHandle) {
+    let origins = AllowedOrigins::new(http.allowed_origins.clone());
+    let cors = http.cors_layer(&origins);
+    let security_headers = Arc::new(http.security_headers());
     let trace = TraceLayer::new_for_http()
-        .make_span_with(DefaultMakeSpan::new().level(Level::INFO))
+        .make_span_with(|request: &Request| {
+            info_span!(
+                "request",
+                method = %request.method(),
+                uri = %request.uri(),
+                version = ?request.version(),
+                request_id = request_id(request),
+            )
+        })
         .on_request(DefaultOnRequest::new().level(Level::INFO))
         .on_response(DefaultOnResponse::new().level(Level::INFO));
 
-    let max_requests = state.max_requests();
-    let rate_period = state.rate_period();
+    let rate_limit = state.rate_limit();
+    let rate_limit_layer = RateLimitLayer::new(rate_limit.max_requests, rate_limit.period)
+        .with_burst(rate_limit.burst)
+        .with_route_weights(rate_limit.route_weights.clone());
+    let status_page = state
+        .status_page()
+        .is_some()
+        .then(|| api::status_router(state.clone()).layer(rate_limit_layer.clone()));
+    let admin = state
+        .admin_token()
+        .map(|token| runtime::admin::protect(api::admin_router(state.clone()), token));
+    let tracker =
+        OriginTracker { usage: Arc::clone(state.origin_usage()), origins: origins.clone() };
+    let reload = ReloadHandle { origins, rate_limit: rate_limit_layer.clone() };
     let api_service = tower::ServiceBuilder::new()
-        .layer(RateLimitLayer::new(max_requests, rate_period))
+        .layer(middleware::from_fn_with_state(tracker, usage::track_origin))
+        .layer(rate_limit_layer)
         .service(api::router(state));
 
-    Router::new()
-        .route("/health", get(health::handler))
+    let mut app = Router::new().route("/health", get(health::handler));
+    if let Some(status_page) = status_page {
+        app = app.merge(status_page);
+    }
+    if let Some(admin) = admin {
+        app = app.merge(admin);
+    }
+    let mut app = app
         .nest_service(&format!("/{API_VERSION}"), api_service)
+        .layer(middleware::from_fn(scope_request_id));
+    if http.etag {
+        app = app.layer(middleware::from_fn(etag::set_etag));
+    }
+    if http.compression {
+        app = app.layer(CompressionLayer::new());
+    }
+    let app = app
         .layer(cors)
+        .layer(middleware::from_fn_with_state(security_headers, security::set_security_headers))
         .layer(trace)
+        .layer(PropagateRequestIdLayer::x_request_id())
+        .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid));
+    (app, reload)
+}
+
+/// Rate limiter settings of `opts`.
+pub(crate) fn rate_limit_config(opts: &Opts) -> RateLimitConfig {
+    RateLimitConfig {
+        max_requests: opts.api.rate_limit_max_requests,
+        period: Duration::from_secs(opts.api.rate_limit_period_secs),
+        burst: opts.api.rate_limit_burst.unwrap_or(opts.api.rate_limit_max_requests),
+        route_weights: opts.api.rate_limit_route_weights.iter().cloned().collect(),
+    }
 }
 
-/// Run the API server on the given address.
+/// Check that the `ClickHouse` user of the API server cannot write. Fails when `enforce` is set,
+/// warns otherwise.
+pub async fn check_read_only(client: &ClickhouseReader, enforce: bool) -> Result<()> {
+    let problem = match client.is_read_only().await {
+        Ok(true) => {
+            info!("ClickHouse user of the API server is read-only");
+            return Ok(());
+        }
+        Ok(false) => "ClickHouse user of the API server can write".to_owned(),
+        Err(e) => format!("Failed to check that the ClickHouse user is read-only: {e}"),
+    };
+    if enforce {
+        return Err(eyre::eyre!(problem));
+    }
+    warn!("{problem}; give the user a settings profile with readonly = 2");
+    Ok(())
+}
+
+/// Run the API server with the settings of `opts`.
+pub async fn run_with_opts(opts: Opts) -> Result<()> {
+    if opts.clickhouse.has_write_credentials() {
+        return Err(eyre::eyre!(
+            "The API server must not be given ClickHouse write credentials; unset \
+             CLICKHOUSE_WRITE_USERNAME and CLICKHOUSE_WRITE_PASSWORD"
+        ));
+    }
+    let (username, password) = opts.clickhouse.read_credentials();
+    let client = ClickhouseReader::new(
+        opts.clickhouse.url.clone(),
+        opts.clickhouse.db.clone(),
+        username,
+        password,
+    )?
+    .with_table_prefix(opts.clickhouse.table_prefix.clone())
+    .with_replicas(opts.clickhouse.replica_urls.clone())
+    .with_sticky_aggregates(opts.clickhouse.sticky_aggregates)
+    .with_query_timeout(
+        (opts.clickhouse.query_timeout_secs > 0)
+            .then(|| Duration::from_secs(opts.clickhouse.query_timeout_secs)),
+    )
+    .with_max_concurrent_queries(opts.clickhouse.max_concurrent_queries);
+
+    check_read_only(&client, opts.clickhouse.enforce_read_only).await?;
+
+    let addr: SocketAddr = format!("{}:{}", opts.api.host, opts.api.port).parse()?;
+
+    let rate_limit = rate_limit_config(&opts);
+    let cache_ttls = CacheTtls {
+        dashboard: Duration::from_secs(opts.api.cache_ttl_dashboard_secs),
+        table: Duration::from_secs(opts.api.cache_ttl_table_secs),
+    };
+    let prewarm = PrewarmConfig {
+        interval: Duration::from_secs(opts.api.prewarm_interval_secs),
+        paths: opts.api.prewarm_paths,
+    };
+    let query_budget = QueryBudget {
+        max_queries: opts.api.query_budget_max_queries,
+        max_time: Duration::from_millis(opts.api.query_budget_ms),
+    };
+    let builder_fingerprints = BuilderFingerprints::parse(&opts.api.builder_fingerprints)
+        .map_err(|e| eyre::eyre!("Invalid BUILDER_FINGERPRINTS: {e}"))?;
+    let status_page = opts
+        .api
+        .status_page
+        .then(|| StatusPageConfig { incident_state_dir: opts.instatus.state_dir.clone() });
+    let http = HttpConfig {
+        allowed_methods: opts
+            .api
+            .cors_allowed_methods
+            .iter()
+            .map(|method| method.parse())
+            .collect::<Result<_, _>>()?,
+        cors_max_age: opts.api.cors_max_age_secs.map(Duration::from_secs),
+        hsts_max_age: opts.api.hsts_max_age_secs.map(Duration::from_secs),
+        hsts_include_subdomains: opts.api.hsts_include_subdomains,
+        content_type_nosniff: opts.api.content_type_nosniff,
+        referrer_policy: Some(opts.api.referrer_policy).filter(|policy| !policy.is_empty()),
+        compression: opts.api.compression,
+        etag: opts.api.etag,
+        ..HttpConfig::new(opts.api.allowed_origins)
+    };
+
+    // The gRPC API shares the reader and the token bucket settings, but not the bucket itself
+    let grpc = match opts.api.grpc_port {
+        Some(port) => {
+            let grpc_addr: SocketAddr = format!("{}:{}", opts.api.host, port).parse()?;
+            let limiter = RateLimiter::new(rate_limit.max_requests, rate_limit.period)
+                .with_burst(rate_limit.burst);
+            Some(grpc::serve(grpc_addr, client.clone(), limiter))
+        }
+        None => None,
+    };
+    let http = run(
+        addr,
+        client,
+        http,
+        rate_limit,
+        cache_ttls,
+        prewarm,
+        query_budget,
+        builder_fingerprints,
+        status_page,
+        opts.admin_token,
+        Duration::from_secs(opts.api.shutdown_grace_secs),
+    );
+
+    // The HTTP server returns once drained; the gRPC server only returns on failure
+    match grpc {
+        Some(grpc) => tokio::select! {
+            result = http => result,
+            result = grpc => result,
+        },
+        None => http.await,
+    }
+}
+
+/// Run the API server on the given address until SIGINT or SIGTERM, reloading the CORS
+/// origins and rate limits on SIGHUP and keeping the responses of `prewarm` cached. On shutdown,
+/// in-flight requests are given up to `shutdown_grace` to complete.
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     addr: SocketAddr,
     client: ClickhouseReader,
-    allowed_origins: Vec<String>,
-    max_requests: u64,
-    rate_period: Duration,
+    http: HttpConfig,
+    rate_limit: RateLimitConfig,
+    cache_ttls: CacheTtls,
+    prewarm: PrewarmConfig,
+    query_budget: QueryBudget,
+    builder_fingerprints: BuilderFingerprints,
+    status_page: Option<StatusPageConfig>,
+    admin_token: Option<String>,
+    shutdown_grace: Duration,
 ) -> Result<()> {
-    let state = ApiState::new(client, max_requests, rate_period);
-    let app = router(state, allowed_origins);
+    let mut state = ApiState::new(client, rate_limit.max_requests, rate_limit.period)
+        .with_rate_limit(rate_limit)
+        .with_cache_ttls(cache_ttls)
+        .with_query_budget(query_budget)
+        .with_builder_fingerprints(builder_fingerprints);
+    if let Some(status_page) = status_page {
+        state = state.with_status_page(status_page);
+    }
+    if let Some(token) = admin_token {
+        state = state.with_admin_token(token);
+    }
+    let prewarmer = prewarm::spawn(state.clone(), prewarm);
+    let (app, reload) = reloadable_router(state, http);
+    let reloader = tokio::spawn(reload::reload_on_sighup(reload));
 
     info!("Starting API server on {}", addr);
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
-    Ok(())
+    let result = serve_until_shutdown(listener, app, shutdown_grace).await;
+    reloader.abort();
+    if let Some(prewarmer) = prewarmer {
+        prewarmer.abort();
+    }
+    result
+}
+
+/// Serve `app` until SIGINT or SIGTERM, then stop accepting connections and wait up to `grace`
+/// for the in-flight requests to complete before dropping the remaining connections.
+async fn serve_until_shutdown(
+    listener: tokio::net::TcpListener,
+    app: Router,
+    grace: Duration,
+) -> Result<()> {
+    let (draining_tx, mut draining) = tokio::sync::watch::channel(false);
+    let signal = async move {
+        ShutdownSignal::new().await;
+        info!(grace_secs = grace.as_secs(), "Shutdown requested, draining in-flight requests");
+        let _ = draining_tx.send(true);
+    };
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(signal)
+        .into_future();
+    tokio::pin!(server);
+
+    tokio::select! {
+        result = &mut server => return Ok(result?),
+        _ = draining.wait_for(|draining| *draining) => {}
+    }
+    match tokio::time::timeout(grace, server).await {
+        Ok(result) => {
+            info!("API server drained");
+            Ok(result?)
+        }
+        Err(_) => {
+            warn!("Shutdown grace period exceeded, dropping the remaining connections");
+            Ok(())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -84,7 +337,7 @@ mod tests {
     use api::{ApiState, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD};
     use axum::{
         body::{self, Body},
-        http::{Request, StatusCode},
+        http::{Method, Request, StatusCode},
     };
     use clickhouse::{
         Row,
@@ -105,7 +358,7 @@ mod tests {
         let client =
             ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
         let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD);
-        router(state, allowed)
+        router(state, HttpConfig::new(allowed))
     }
 
     async fn send_request(app: Router, origin: &str) -> (StatusCode, Value, Option<String>) {
@@ -198,4 +451,395 @@ mod tests {
         assert_eq!(status, StatusCode::OK);
         assert!(cors.is_none());
     }
+
+    #[tokio::test]
+    async fn reload_replaces_allowed_origins_and_rate_limits() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+        let url = Url::parse(mock.url()).unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD);
+        let (app, reload) =
+            reloadable_router(state, HttpConfig::new(vec!["https://old.example".to_owned()]));
+
+        reload.reload(
+            vec!["https://new.example".to_owned()],
+            &RateLimitConfig::new(2, Duration::from_secs(60)),
+        );
+
+        let (status, _, cors) = send_request(app.clone(), "https://new.example").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(cors.as_deref(), Some("https://new.example"));
+        let (status, _, cors) = send_request(app.clone(), "https://old.example").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(cors.is_none());
+        let (status, _, _) = send_request(app, "https://new.example").await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[derive(Serialize, Row)]
+    struct AnomalyRow {
+        batch_id: u64,
+        l1_block_number: u64,
+    }
+
+    #[tokio::test]
+    async fn serves_cached_dashboard_responses() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(Vec::<AnomalyRow>::new()));
+        mock.add(handlers::provide(Vec::<AnomalyRow>::new()));
+
+        let url = Url::parse(mock.url()).unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let ttls = CacheTtls { dashboard: Duration::from_secs(60), table: Duration::ZERO };
+        let state =
+            ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD).with_cache_ttls(ttls);
+        let app = router(state, HttpConfig::new(Vec::new()));
+
+        let mut statuses = Vec::new();
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/{API_VERSION}/data-quality"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            statuses.push(response.headers().get("x-cache").unwrap().to_str().unwrap().to_owned());
+        }
+        assert_eq!(statuses, vec!["MISS", "HIT"]);
+    }
+
+    #[tokio::test]
+    async fn admin_token_guards_cache_invalidation() {
+        let mock = Mock::new();
+        // Both data-quality lists, before and after the invalidation
+        for _ in 0..4 {
+            mock.add(handlers::provide(Vec::<AnomalyRow>::new()));
+        }
+
+        let url = Url::parse(mock.url()).unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let ttls = CacheTtls { dashboard: Duration::from_secs(60), table: Duration::ZERO };
+        let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD)
+            .with_cache_ttls(ttls)
+            .with_admin_token("secret");
+        let app = router(state, HttpConfig::new(Vec::new()));
+
+        let fetch = || async {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/{API_VERSION}/data-quality"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            response.headers().get("x-cache").unwrap().to_str().unwrap().to_owned()
+        };
+        let invalidate = |token: &'static str| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/admin/cache/invalidate")
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        assert_eq!(fetch().await, "MISS");
+        let response = invalidate("wrong").await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(fetch().await, "HIT");
+
+        let response = invalidate("secret").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body, json!({ "invalidated": 1 }));
+        assert_eq!(fetch().await, "MISS");
+    }
+
+    #[tokio::test]
+    async fn cache_and_replica_stats_require_admin_token() {
+        let url = Url::parse("http://127.0.0.1:1").unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD)
+            .with_admin_token("secret");
+        let app = router(state, HttpConfig::new(Vec::new()));
+
+        for path in ["/admin/cache-stats", "/admin/replica-stats"] {
+            let get = |token: &'static str| {
+                app.clone().oneshot(
+                    Request::builder()
+                        .uri(path)
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+            };
+            assert_eq!(get("wrong").await.unwrap().status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(get("secret").await.unwrap().status(), StatusCode::OK);
+        }
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/{API_VERSION}/cache-stats"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn empty_admin_token_disables_admin_endpoints() {
+        let url = Url::parse("http://127.0.0.1:1").unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let state =
+            ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD).with_admin_token(" ");
+        assert!(state.admin_token().is_none());
+
+        let response = router(state, HttpConfig::new(Vec::new()))
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/admin/cache/invalidate")
+                    .header("authorization", "Bearer ")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn reports_usage_by_origin_to_admins() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+
+        let url = Url::parse(mock.url()).unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD)
+            .with_admin_token("secret");
+        let app = router(state, HttpConfig::new(vec!["https://taikoscope.xyz".to_owned()]));
+
+        send_request(app.clone(), "https://taikoscope.xyz").await;
+        send_request(app.clone(), "https://scraper.example").await;
+        let usage = |token: &'static str| {
+            app.clone().oneshot(
+                Request::builder()
+                    .uri(format!("/{API_VERSION}/api-usage-by-origin"))
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        let response = usage("wrong").await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = usage("secret").await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        let origin = |name: &str| {
+            body["origins"].as_array().unwrap().iter().find(|o| o["origin"] == name).cloned()
+        };
+        assert_eq!(origin("https://taikoscope.xyz").unwrap()["allowed"], true);
+        assert_eq!(origin("https://scraper.example").unwrap()["allowed"], false);
+        assert_eq!(origin("https://scraper.example").unwrap()["requests"], 1);
+        assert_eq!(body["rejected_requests"], 1);
+    }
+
+    #[tokio::test]
+    async fn assigns_and_echoes_request_id() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+        let app = build_app(mock.url(), Vec::new());
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/{API_VERSION}/l2-head-block"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let generated = response.headers().get("x-request-id").unwrap().to_str().unwrap();
+        assert!(!generated.is_empty());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/{API_VERSION}/l2-head-block"))
+                    .header("x-request-id", "client-supplied")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers().get("x-request-id").unwrap(), "client-supplied");
+    }
+
+    #[tokio::test]
+    async fn sets_security_headers() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+        let url = Url::parse(mock.url()).unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD);
+        let http = HttpConfig {
+            hsts_max_age: Some(Duration::from_secs(31_536_000)),
+            hsts_include_subdomains: true,
+            ..HttpConfig::new(Vec::new())
+        };
+        let app = router(state, http);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/{API_VERSION}/l2-head-block"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let headers = response.headers();
+        assert_eq!(
+            headers.get("strict-transport-security").unwrap(),
+            "max-age=31536000; includeSubDomains"
+        );
+        assert_eq!(headers.get("x-content-type-options").unwrap(), "nosniff");
+        assert_eq!(headers.get("referrer-policy").unwrap(), "strict-origin-when-cross-origin");
+    }
+
+    #[tokio::test]
+    async fn answers_preflight_for_allowed_methods() {
+        let mock = Mock::new();
+        let url = Url::parse(mock.url()).unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+        let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD);
+        let http = HttpConfig {
+            allowed_methods: vec![Method::GET, Method::POST],
+            cors_max_age: Some(Duration::from_secs(600)),
+            ..HttpConfig::new(vec!["https://example.com".to_owned()])
+        };
+        let app = router(state, http);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::OPTIONS)
+                    .uri(format!("/{API_VERSION}/l2-head-block"))
+                    .header("Origin", "https://example.com")
+                    .header("Access-Control-Request-Method", "POST")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers.get("access-control-allow-origin").unwrap(), "https://example.com");
+        assert_eq!(headers.get("access-control-allow-methods").unwrap(), "GET,POST");
+        assert_eq!(headers.get("access-control-max-age").unwrap(), "600");
+        assert_eq!(headers.get("x-content-type-options").unwrap(), "nosniff");
+    }
+
+    #[tokio::test]
+    async fn answers_not_modified_for_matching_etag() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+        mock.add(handlers::provide(vec![NumRow { l2_block_number: 1 }]));
+        let app = build_app(mock.url(), Vec::new());
+
+        let request = |if_none_match: Option<&str>| {
+            let mut builder = Request::builder().uri(format!("/{API_VERSION}/l2-head-block"));
+            if let Some(tag) = if_none_match {
+                builder = builder.header("if-none-match", tag);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers().get("etag").unwrap().to_str().unwrap().to_owned();
+
+        let response = app.oneshot(request(Some(&etag))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get("etag").unwrap(), etag.as_str());
+        let body = body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn compresses_when_accepted() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(Vec::<AnomalyRow>::new()));
+        mock.add(handlers::provide(Vec::<AnomalyRow>::new()));
+        let app = build_app(mock.url(), Vec::new());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/{API_VERSION}/data-quality"))
+                    .header("accept-encoding", "gzip")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get("content-encoding").unwrap(), "gzip");
+    }
+
+    #[tokio::test]
+    async fn status_page_only_served_when_enabled() {
+        let mock = Mock::new();
+        let app = build_app(mock.url(), Vec::new());
+        let response = app
+            .oneshot(Request::builder().uri("/status").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn writable_user_is_rejected_only_when_enforced() {
+        let mock = Mock::new();
+        mock.add(handlers::provide(vec![0u8]));
+        mock.add(handlers::provide(vec![0u8]));
+        mock.add(handlers::provide(vec![2u8]));
+        let url = Url::parse(mock.url()).unwrap();
+        let client =
+            ClickhouseReader::new(url, "db".to_owned(), "user".into(), "pass".into()).unwrap();
+
+        check_read_only(&client, false).await.unwrap();
+        assert!(check_read_only(&client, true).await.is_err());
+        check_read_only(&client, true).await.unwrap();
+    }
 }
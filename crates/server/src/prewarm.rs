@@ -0,0 +1,93 @@
+//! Background refresh of the response cache entries of expensive aggregate endpoints.
+
+#![allow(clippy::redundant_pub_crate)]
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+use api::{ApiState, cache::Prewarm};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use tokio::task::JoinHandle;
+use tower::ServiceExt;
+use tracing::{debug, warn};
+
+/// Paths to keep warm and how often to refresh them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrewarmConfig {
+    /// Time between refreshes, shortened by up to a fifth at random (zero disables pre-warming)
+    pub interval: Duration,
+    /// Paths with query, relative to the API version prefix, e.g. `/dashboard-data?range=24h`
+    pub paths: Vec<String>,
+}
+
+/// Spawn a task refreshing the cached responses of `config.paths` once at startup and then
+/// every jittered interval, or `None` when pre-warming is disabled.
+///
+/// The requests go straight to the API routes, so they skip the rate limiter and are not
+/// counted in the origin usage.
+pub(crate) fn spawn(state: ApiState, config: PrewarmConfig) -> Option<JoinHandle<()>> {
+    if config.interval.is_zero() || config.paths.is_empty() {
+        return None;
+    }
+    let router = api::router(state);
+    Some(tokio::spawn(async move {
+        loop {
+            for path in &config.paths {
+                refresh(&router, path).await;
+            }
+            let random = RandomState::new().build_hasher().finish();
+            tokio::time::sleep(jittered(config.interval, random)).await;
+        }
+    }))
+}
+
+/// Request `path` so that the cache stores a fresh response for it.
+async fn refresh(router: &axum::Router, path: &str) {
+    let mut request = match Request::get(path).body(Body::empty()) {
+        Ok(request) => request,
+        Err(e) => {
+            warn!(path, err = %e, "Invalid pre-warm path");
+            return;
+        }
+    };
+    request.extensions_mut().insert(Prewarm);
+    match router.clone().oneshot(request).await {
+        Ok(response) if response.status() == StatusCode::OK => {
+            debug!(path, "Pre-warmed cached response");
+        }
+        Ok(response) => warn!(path, status = %response.status(), "Failed to pre-warm response"),
+        Err(e) => warn!(path, err = %e, "Failed to pre-warm response"),
+    }
+}
+
+/// `interval` shortened by up to a fifth, picked by `random`.
+const fn jittered(interval: Duration, random: u64) -> Duration {
+    let max_jitter = interval.as_millis() as u64 / 5;
+    if max_jitter == 0 {
+        return interval;
+    }
+    interval.saturating_sub(Duration::from_millis(random % (max_jitter + 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitter_shortens_interval_by_at_most_a_fifth() {
+        let interval = Duration::from_secs(25);
+        assert_eq!(jittered(interval, 0), interval);
+        assert_eq!(jittered(interval, 5_000), Duration::from_secs(20));
+        for random in [1, 4_999, 5_001, u64::MAX] {
+            let delay = jittered(interval, random);
+            assert!(delay >= Duration::from_secs(20) && delay <= interval);
+        }
+        assert_eq!(jittered(Duration::from_millis(3), 7), Duration::from_millis(3));
+    }
+}
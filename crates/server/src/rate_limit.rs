@@ -1,29 +1,58 @@
 #![allow(unreachable_pub, clippy::redundant_pub_crate)]
 use std::{
+    collections::HashMap,
     future::Future,
     pin::Pin,
+    sync::{Arc, RwLock},
     task::{Context, Poll},
     time::Duration,
 };
 
-use api_types::ErrorResponse;
+use api::RateLimitConfig;
+use api_types::{ErrorCode, ErrorResponse};
 use axum::{
-    http::{Request, StatusCode},
+    http::{HeaderMap, HeaderValue, Request, StatusCode, header::RETRY_AFTER},
     response::{IntoResponse, Response},
 };
 use tower::{Layer, Service};
 
 use runtime::rate_limiter::RateLimiter;
 
+/// Tokens left in the bucket after the request.
+const X_RATELIMIT_REMAINING: &str = "x-ratelimit-remaining";
+/// Size of the bucket.
+const X_RATELIMIT_LIMIT: &str = "x-ratelimit-limit";
+
+/// Token cost per route path, shared by the layer and its services.
+type RouteWeights = Arc<RwLock<HashMap<String, u64>>>;
+
 #[derive(Clone, Debug)]
 pub(super) struct RateLimitLayer {
     limiter: RateLimiter,
-    period: Duration,
+    route_weights: RouteWeights,
 }
 
 impl RateLimitLayer {
     pub fn new(max: u64, period: Duration) -> Self {
-        Self { limiter: RateLimiter::new(max, period), period }
+        Self { limiter: RateLimiter::new(max, period), route_weights: Arc::default() }
+    }
+
+    /// Allow bursts of up to `burst` requests.
+    pub fn with_burst(mut self, burst: u64) -> Self {
+        self.limiter = self.limiter.with_burst(burst);
+        self
+    }
+
+    /// Charge the given number of tokens for requests to each path.
+    pub fn with_route_weights(mut self, route_weights: HashMap<String, u64>) -> Self {
+        self.route_weights = Arc::new(RwLock::new(route_weights));
+        self
+    }
+
+    /// Apply new settings to the layer and every service it built.
+    pub fn reconfigure(&self, config: &RateLimitConfig) {
+        self.limiter.reconfigure(config.max_requests, config.period, config.burst);
+        *self.route_weights.write().expect("lock poisoned") = config.route_weights.clone();
     }
 }
 
@@ -31,7 +60,11 @@ impl<S> Layer<S> for RateLimitLayer {
     type Service = RateLimit<S>;
 
     fn layer(&self, inner: S) -> Self::Service {
-        RateLimit { inner, limiter: self.limiter.clone(), period: self.period }
+        RateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+            route_weights: Arc::clone(&self.route_weights),
+        }
     }
 }
 
@@ -39,7 +72,14 @@ impl<S> Layer<S> for RateLimitLayer {
 pub(super) struct RateLimit<S> {
     inner: S,
     limiter: RateLimiter,
-    period: Duration,
+    route_weights: RouteWeights,
+}
+
+impl<S> RateLimit<S> {
+    fn set_limit_headers(&self, headers: &mut HeaderMap, remaining: u64) {
+        headers.insert(X_RATELIMIT_LIMIT, HeaderValue::from(self.limiter.burst()));
+        headers.insert(X_RATELIMIT_REMAINING, HeaderValue::from(remaining));
+    }
 }
 
 impl<S, ReqBody> Service<Request<ReqBody>> for RateLimit<S>
@@ -57,22 +97,37 @@ where
     }
 
     fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
-        if self.limiter.try_acquire() {
-            Box::pin(self.inner.call(req))
-        } else {
-            let error_body = ErrorResponse::new(
-                "rate-limit",
-                "Too Many Requests",
-                StatusCode::TOO_MANY_REQUESTS,
-                format!("Rate limit exceeded. Retry after {} seconds", self.period.as_secs()),
-            );
-            let mut resp = axum::Json(error_body).into_response();
-            *resp.status_mut() = StatusCode::TOO_MANY_REQUESTS;
-            resp.headers_mut().insert(
-                axum::http::header::RETRY_AFTER,
-                axum::http::HeaderValue::from_str(&self.period.as_secs().to_string()).unwrap(),
-            );
-            Box::pin(std::future::ready(Ok(resp)))
+        let weight = self
+            .route_weights
+            .read()
+            .expect("lock poisoned")
+            .get(req.uri().path())
+            .copied()
+            .unwrap_or(1);
+        match self.limiter.try_acquire_weighted(weight) {
+            Ok(remaining) => {
+                let mut headers = HeaderMap::new();
+                self.set_limit_headers(&mut headers, remaining);
+                let fut = self.inner.call(req);
+                Box::pin(async move {
+                    let mut resp = fut.await?;
+                    resp.headers_mut().extend(headers);
+                    Ok(resp)
+                })
+            }
+            Err(retry_after) => {
+                let retry_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+                let error_body = ErrorResponse::new(
+                    ErrorCode::RateLimited,
+                    "Too Many Requests",
+                    StatusCode::TOO_MANY_REQUESTS,
+                    format!("Rate limit exceeded. Retry after {} seconds", retry_secs),
+                );
+                let mut resp = error_body.into_response();
+                resp.headers_mut().insert(RETRY_AFTER, HeaderValue::from(retry_secs));
+                self.set_limit_headers(resp.headers_mut(), 0);
+                Box::pin(std::future::ready(Ok(resp)))
+            }
         }
     }
 }
@@ -85,7 +140,7 @@ mod tests {
         http::{Request, StatusCode},
         response::Response,
     };
-    use std::{convert::Infallible, time::Duration};
+    use std::{collections::HashMap, convert::Infallible, time::Duration};
     use tower::{Layer, Service, ServiceExt, service_fn};
 
     #[tokio::test]
@@ -100,6 +155,7 @@ mod tests {
         let resp = svc.ready().await.unwrap().call(Request::new(Body::empty())).await.unwrap();
 
         assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers()[axum::http::header::CONTENT_TYPE], api_types::PROBLEM_JSON);
         let retry = resp.headers().get(axum::http::header::RETRY_AFTER).unwrap();
         assert_eq!(retry.to_str().unwrap(), "30");
     }
@@ -118,14 +174,75 @@ mod tests {
         let resp = svc.ready().await.unwrap().call(Request::new(Body::empty())).await.unwrap();
 
         assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers()[axum::http::header::CONTENT_TYPE], api_types::PROBLEM_JSON);
         let retry = resp.headers().get(axum::http::header::RETRY_AFTER).unwrap();
         assert_eq!(retry.to_str().unwrap(), "30");
 
         let bytes = body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
         let err: api_types::ErrorResponse = serde_json::from_slice(&bytes).unwrap();
-        assert_eq!(err.r#type, "rate-limit");
+        assert_eq!(err.r#type, api_types::ErrorCode::RateLimited);
         assert_eq!(err.title, "Too Many Requests");
         assert_eq!(err.status, StatusCode::TOO_MANY_REQUESTS.as_u16());
         assert!(err.detail.to_lowercase().contains("rate limit exceeded"));
     }
+
+    #[tokio::test]
+    async fn reports_remaining_tokens() {
+        let layer = RateLimitLayer::new(3, Duration::from_secs(60));
+        let inner = service_fn(|_req: Request<Body>| async move {
+            Ok::<_, Infallible>(Response::new(Body::empty()))
+        });
+        let mut svc = layer.layer(inner);
+
+        let resp = svc.ready().await.unwrap().call(Request::new(Body::empty())).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "2");
+        assert_eq!(resp.headers().get("x-ratelimit-limit").unwrap(), "3");
+    }
+
+    #[tokio::test]
+    async fn weighted_routes_cost_more_tokens() {
+        let weights = HashMap::from([("/dashboard-data".to_owned(), 10)]);
+        let layer = RateLimitLayer::new(12, Duration::from_secs(60)).with_route_weights(weights);
+        let inner = service_fn(|_req: Request<Body>| async move {
+            Ok::<_, Infallible>(Response::new(Body::empty()))
+        });
+        let mut svc = layer.layer(inner);
+        let request = |path: &str| Request::builder().uri(path).body(Body::empty()).unwrap();
+
+        let resp = svc.ready().await.unwrap().call(request("/dashboard-data")).await.unwrap();
+        assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "2");
+
+        let resp = svc.ready().await.unwrap().call(request("/dashboard-data")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "0");
+
+        let resp = svc.ready().await.unwrap().call(request("/l2-head-block")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("x-ratelimit-remaining").unwrap(), "1");
+    }
+
+    #[tokio::test]
+    async fn reconfigure_applies_to_built_services() {
+        let layer = RateLimitLayer::new(1, Duration::from_secs(60));
+        let inner = service_fn(|_req: Request<Body>| async move {
+            Ok::<_, Infallible>(Response::new(Body::empty()))
+        });
+        let mut svc = layer.layer(inner);
+        let request = |path: &str| Request::builder().uri(path).body(Body::empty()).unwrap();
+
+        let resp = svc.ready().await.unwrap().call(request("/l2-head-block")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let mut config = api::RateLimitConfig::new(1, Duration::from_secs(60));
+        config.burst = 5;
+        config.route_weights = HashMap::from([("/dashboard-data".to_owned(), 2)]);
+        layer.reconfigure(&config);
+
+        // The token spent before stays spent, only the bucket size and weights change
+        let resp = svc.ready().await.unwrap().call(request("/dashboard-data")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get("x-ratelimit-limit").unwrap(), "5");
+    }
 }
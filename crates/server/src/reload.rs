@@ -0,0 +1,194 @@
+//! Reload of the CORS origins and rate limits of a running API server.
+
+#![allow(clippy::redundant_pub_crate)]
+
+use std::{collections::HashMap, ffi::OsString};
+
+use api::RateLimitConfig;
+use clap::{CommandFactory, FromArgMatches};
+use config::Opts;
+use eyre::Result;
+use tokio::signal::unix::{SignalKind, signal};
+use tracing::{info, warn};
+
+use crate::{rate_limit::RateLimitLayer, security::AllowedOrigins};
+
+/// Settings of a router that can be replaced while it serves requests.
+#[derive(Debug, Clone)]
+pub struct ReloadHandle {
+    pub(crate) origins: AllowedOrigins,
+    pub(crate) rate_limit: RateLimitLayer,
+}
+
+impl ReloadHandle {
+    /// Replace the allowed CORS origins and the rate limiter settings. Tokens already spent
+    /// stay spent.
+    pub fn reload(&self, allowed_origins: Vec<String>, rate_limit: &RateLimitConfig) {
+        info!(
+            origins = allowed_origins.len(),
+            max_requests = rate_limit.max_requests,
+            period_secs = rate_limit.period.as_secs(),
+            burst = rate_limit.burst,
+            "Reloaded CORS origins and rate limits"
+        );
+        self.origins.replace(allowed_origins);
+        self.rate_limit.reconfigure(rate_limit);
+    }
+}
+
+/// Reload the settings of `handle` on every SIGHUP from the environment file the server was
+/// started with and the process environment. A configuration that fails to parse is logged and
+/// the current settings are kept.
+pub(crate) async fn reload_on_sighup(handle: ReloadHandle) {
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            warn!(err = %e, "Failed to install SIGHUP handler, config reload disabled");
+            return;
+        }
+    };
+    while hangup.recv().await.is_some() {
+        match reload_opts() {
+            Ok(opts) => {
+                handle.reload(opts.api.allowed_origins.clone(), &crate::rate_limit_config(&opts))
+            }
+            Err(e) => warn!(err = %e, "Failed to reload config, keeping the current settings"),
+        }
+    }
+}
+
+/// Parse the options again with the variables of `ENV_FILE`, or `.env` when unset, taking
+/// precedence over the process environment, which is left untouched. A missing `.env` is the
+/// same as an empty one.
+fn reload_opts() -> Result<Opts> {
+    let (path, required) = match std::env::var("ENV_FILE") {
+        Ok(env_file) => (env_file, true),
+        Err(_) => (".env".to_owned(), false),
+    };
+    let vars = match dotenvy::from_filename_iter(&path) {
+        Ok(iter) => iter.collect::<Result<HashMap<_, _>, _>>()?,
+        Err(e) if e.not_found() && !required => HashMap::new(),
+        Err(e) => return Err(e.into()),
+    };
+    Ok(parse_with_overlay(&vars, std::env::args_os())?)
+}
+
+/// Parse `args` with the variables of `vars` taking precedence over the process environment.
+///
+/// Every option whose variable is in `vars` stops reading the process environment. Options
+/// taking a value get it from `vars` as a flag ahead of `args`, so flags given in `args` still
+/// win; switches default to their value in `vars` instead.
+fn parse_with_overlay(
+    vars: &HashMap<String, String>,
+    args: impl IntoIterator<Item = OsString>,
+) -> Result<Opts, clap::Error> {
+    let mut args = args.into_iter();
+    let mut command = Opts::command().args_override_self(true);
+    let program = args.next();
+    let mut overlay: Vec<OsString> = Vec::new();
+    let overridden: Vec<_> = command
+        .get_arguments()
+        .filter_map(|arg| {
+            let value = vars.get(arg.get_env()?.to_str()?)?.clone();
+            let long = arg.get_long()?.to_owned();
+            Some((arg.get_id().clone(), long, arg.get_action().takes_values(), value))
+        })
+        .collect();
+    for (id, long, takes_value, value) in overridden {
+        if takes_value {
+            command = command.mut_arg(id, |arg| arg.env(None));
+            overlay.push(format!("--{long}={value}").into());
+        } else {
+            let default = if is_falsey(&value) { "false" } else { "true" };
+            command = command.mut_arg(id, |arg| arg.env(None).default_value(default));
+        }
+    }
+    let matches = command.try_get_matches_from(program.into_iter().chain(overlay).chain(args))?;
+    Opts::from_arg_matches(&matches)
+}
+
+/// Whether a switch set from the environment to `value` is off, as clap reads it
+fn is_falsey(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "" | "n" | "no" | "f" | "false" | "off" | "0"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARGS: &[&str] = &[
+        "taikoscope",
+        "--url",
+        "http://localhost:8123",
+        "--db",
+        "test-db",
+        "--username",
+        "user",
+        "--password",
+        "pass",
+        "--l1-url",
+        "http://l1",
+        "--l2-url",
+        "http://l2",
+        "--inbox-address",
+        "0x0000000000000000000000000000000000000001",
+        "--preconf-whitelist-address",
+        "0x0000000000000000000000000000000000000002",
+        "--taiko-wrapper-address",
+        "0x0000000000000000000000000000000000000003",
+        "--anchor-address",
+        "0x0000000000000000000000000000000000000004",
+        "--api-key",
+        "key",
+        "--page-id",
+        "page",
+        "--batch-submission-component-id",
+        "batch",
+        "--proof-submission-component-id",
+        "proof",
+        "--proof-verification-component-id",
+        "verify",
+        "--transaction-sequencing-component-id",
+        "l2",
+        "--public-api-component-id",
+        "api",
+        "--gap-min-l1-block",
+        "1",
+        "--gap-min-l2-block",
+        "1",
+    ];
+
+    fn parse(vars: &[(&str, &str)], extra: &[&str]) -> Opts {
+        let vars = vars.iter().map(|(k, v)| ((*k).to_owned(), (*v).to_owned())).collect();
+        let args = ARGS.iter().chain(extra).map(OsString::from);
+        parse_with_overlay(&vars, args).unwrap()
+    }
+
+    #[test]
+    fn file_variables_override_defaults_and_flags_win() {
+        let opts = parse(
+            &[
+                ("ALLOWED_ORIGINS", "https://a.xyz,https://b.xyz"),
+                ("RATE_LIMIT_BURST", "7"),
+                ("API_ETAG", "false"),
+            ],
+            &[],
+        );
+        assert_eq!(opts.api.allowed_origins, vec!["https://a.xyz", "https://b.xyz"]);
+        assert!(!opts.api.etag);
+        assert_eq!(opts.api.rate_limit_burst, Some(7));
+        assert!(std::env::var("RATE_LIMIT_BURST").is_err());
+
+        let opts = parse(&[("RATE_LIMIT_BURST", "7")], &["--rate-limit-burst", "9"]);
+        assert_eq!(opts.api.rate_limit_burst, Some(9));
+    }
+
+    #[test]
+    fn recognizes_falsey_switch_values() {
+        assert!(is_falsey("false") && is_falsey("0") && is_falsey(" Off "));
+        assert!(!is_falsey("true") && !is_falsey("1"));
+    }
+}
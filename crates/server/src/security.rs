@@ -0,0 +1,149 @@
+//! CORS policy, security response headers and response encoding of the API server.
+
+#![allow(clippy::redundant_pub_crate)]
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use axum::{
+    extract::{Request, State},
+    http::{
+        HeaderMap, HeaderValue, Method,
+        header::{REFERRER_POLICY, STRICT_TRANSPORT_SECURITY, X_CONTENT_TYPE_OPTIONS},
+    },
+    middleware::Next,
+    response::Response,
+};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::warn;
+
+/// CORS, security header and response encoding settings of the API server
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// Origins allowed to make cross-origin requests, besides `*.vercel.app` and localhost
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed in cross-origin requests; preflight requests are answered for them
+    pub allowed_methods: Vec<Method>,
+    /// How long browsers may cache a preflight response, `Access-Control-Max-Age` is not sent
+    /// when unset
+    pub cors_max_age: Option<Duration>,
+    /// `max-age` of the `Strict-Transport-Security` header, not sent when unset
+    pub hsts_max_age: Option<Duration>,
+    /// Apply `Strict-Transport-Security` to subdomains as well
+    pub hsts_include_subdomains: bool,
+    /// Send `X-Content-Type-Options: nosniff`
+    pub content_type_nosniff: bool,
+    /// Value of the `Referrer-Policy` header, not sent when unset
+    pub referrer_policy: Option<String>,
+    /// Compress responses with gzip or brotli when the client accepts them
+    pub compression: bool,
+    /// Tag `GET` responses with an `ETag` and answer matching `If-None-Match` with 304
+    pub etag: bool,
+}
+
+impl HttpConfig {
+    /// Allow `GET` requests from `allowed_origins`, with `nosniff`, a
+    /// `strict-origin-when-cross-origin` referrer policy, compression and `ETag`s.
+    pub fn new(allowed_origins: Vec<String>) -> Self {
+        Self {
+            allowed_origins,
+            allowed_methods: vec![Method::GET],
+            cors_max_age: None,
+            hsts_max_age: None,
+            hsts_include_subdomains: false,
+            content_type_nosniff: true,
+            referrer_policy: Some("strict-origin-when-cross-origin".to_owned()),
+            compression: true,
+            etag: true,
+        }
+    }
+
+    /// CORS layer answering preflight requests for `origins` and the allowed methods.
+    pub(crate) fn cors_layer(&self, origins: &AllowedOrigins) -> CorsLayer {
+        let allowed = origins.clone();
+        let mut cors = CorsLayer::new()
+            .allow_origin(AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+                origin.to_str().is_ok_and(|origin| allowed.allows(origin))
+            }))
+            .allow_methods(self.allowed_methods.clone())
+            .allow_headers(Any)
+            .expose_headers(Any);
+        if let Some(max_age) = self.cors_max_age {
+            cors = cors.max_age(max_age);
+        }
+        cors
+    }
+
+    /// Security headers added to every response.
+    pub(crate) fn security_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(max_age) = self.hsts_max_age {
+            let mut value = format!("max-age={}", max_age.as_secs());
+            if self.hsts_include_subdomains {
+                value.push_str("; includeSubDomains");
+            }
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(STRICT_TRANSPORT_SECURITY, value);
+            }
+        }
+        if self.content_type_nosniff {
+            headers.insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+        }
+        if let Some(policy) = &self.referrer_policy {
+            match HeaderValue::from_str(policy) {
+                Ok(value) => {
+                    headers.insert(REFERRER_POLICY, value);
+                }
+                Err(_) => warn!(policy, "Invalid referrer policy, header not sent"),
+            }
+        }
+        headers
+    }
+}
+
+/// Origins allowed to make cross-origin requests, shared by the CORS layer and the origin
+/// tracker so they can be replaced while the server runs.
+#[derive(Debug, Clone)]
+pub(crate) struct AllowedOrigins(Arc<RwLock<Vec<String>>>);
+
+impl AllowedOrigins {
+    pub(crate) fn new(origins: Vec<String>) -> Self {
+        Self(Arc::new(RwLock::new(origins)))
+    }
+
+    /// Whether the CORS policy allows cross-origin requests from `origin`.
+    pub(crate) fn allows(&self, origin: &str) -> bool {
+        origin_allowed(&self.0.read().expect("lock poisoned"), origin)
+    }
+
+    /// Replace the allowed origins.
+    pub(crate) fn replace(&self, origins: Vec<String>) {
+        *self.0.write().expect("lock poisoned") = origins;
+    }
+}
+
+/// Whether `origin` is one of `allowed`, a Vercel preview deployment or a local development
+/// server.
+fn origin_allowed(allowed: &[String], origin: &str) -> bool {
+    allowed.iter().any(|o| o == origin) ||
+        origin.ends_with(".vercel.app") ||
+        origin.starts_with("http://localhost:") ||
+        origin.starts_with("http://127.0.0.1:")
+}
+
+/// Add the security headers to a response, keeping those a handler already set.
+pub(crate) async fn set_security_headers(
+    State(headers): State<Arc<HeaderMap>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    for (name, value) in headers.iter() {
+        if !response.headers().contains_key(name) {
+            response.headers_mut().insert(name.clone(), value.clone());
+        }
+    }
+    response
+}
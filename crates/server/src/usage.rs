@@ -0,0 +1,46 @@
+//! Per-origin accounting of API requests.
+
+#![allow(clippy::redundant_pub_crate)]
+
+use std::sync::Arc;
+
+use api::usage::OriginUsage;
+use axum::{
+    extract::{Request, State},
+    http::header::ORIGIN,
+    middleware::Next,
+    response::Response,
+};
+use tracing::{debug, warn};
+
+use crate::security::AllowedOrigins;
+
+/// State of [`track_origin`]: the counters and the CORS policy origins are checked against.
+#[derive(Debug, Clone)]
+pub(crate) struct OriginTracker {
+    pub(crate) usage: Arc<OriginUsage>,
+    pub(crate) origins: AllowedOrigins,
+}
+
+/// Count the request under its `Origin` header, warning the first time an origin the CORS policy
+/// rejects is seen. Requests without an `Origin` header are always allowed.
+pub(crate) async fn track_origin(
+    State(tracker): State<OriginTracker>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let origin =
+        request.headers().get(ORIGIN).map(|o| o.to_str().unwrap_or("(invalid)").to_owned());
+    let allowed = origin.as_deref().is_none_or(|origin| tracker.origins.allows(origin));
+    let path = request.uri().path().to_owned();
+
+    let response = next.run(request).await;
+    let status = response.status();
+    let first = tracker.usage.record(origin.as_deref(), allowed, status);
+    if first && !allowed {
+        warn!(origin = origin.as_deref(), path, "Requests from an origin not allowed by CORS");
+    } else {
+        debug!(origin = origin.as_deref(), allowed, path, status = status.as_u16(), "API request");
+    }
+    response
+}
@@ -32,11 +32,15 @@ import {
   bytesToHex,
   blockLink,
   addressLink,
+  l1TxLink,
   formatDateTime,
   formatEth,
 } from '../utils';
 import React from 'react';
 
+const txLinkOrDash = (txHash: unknown): React.ReactNode =>
+  typeof txHash === 'string' ? l1TxLink(txHash, `${txHash.slice(0, 10)}…`) : '—';
+
 export interface TableColumn {
   key: string;
   label: string;
@@ -60,6 +64,13 @@ export interface TableConfig {
   reverseOrder?: boolean;
 }
 
+/** Labels of the slashing reason codes reported by the API. */
+const SLASHING_REASONS: Record<number, string> = {
+  0: 'unknown',
+  1: 'removed',
+  2: 'ejected',
+};
+
 export const TABLE_CONFIGS: Record<string, TableConfig> = {
   'sequencer-blocks': {
     title: (params) => `Blocks proposed by ${getSequencerName(params.address)}`,
@@ -82,6 +93,7 @@ export const TABLE_CONFIGS: Record<string, TableConfig> = {
       { key: 'depth', label: 'Depth' },
       { key: 'old_sequencer', label: 'Old Sequencer' },
       { key: 'new_sequencer', label: 'New Sequencer' },
+      { key: 'probable_cause', label: 'Probable Cause' },
     ],
     mapData: (data) =>
       (data as L2ReorgEvent[]).map((e) => ({
@@ -91,6 +103,7 @@ export const TABLE_CONFIGS: Record<string, TableConfig> = {
         depth: e.depth.toLocaleString(),
         old_sequencer: getSequencerName(e.old_sequencer),
         new_sequencer: getSequencerName(e.new_sequencer),
+        probable_cause: e.probable_cause.replace(/_/g, ' '),
       })),
     urlKey: 'reorgs',
     reverseOrder: false,
@@ -124,16 +137,18 @@ export const TABLE_CONFIGS: Record<string, TableConfig> = {
 
   slashings: {
     title: 'Slashing Events',
-    description: 'Validators that have been slashed on L1.',
+    description: 'Operators removed or ejected from the preconf whitelist on L1.',
     fetcher: fetchSlashingEvents,
     columns: [
       { key: 'l1_block_number', label: 'L1 Block' },
       { key: 'validator_addr', label: 'Validator' },
+      { key: 'reason', label: 'Reason' },
     ],
     mapData: (data) =>
       (data as SlashingEvent[]).map((e) => ({
         l1_block_number: e.l1_block_number,
         validator_addr: bytesToHex(e.validator_addr),
+        reason: SLASHING_REASONS[e.reason ?? 0] ?? 'unknown',
       })),
     urlKey: 'slashings',
     reverseOrder: true,
@@ -342,12 +357,16 @@ export const TABLE_CONFIGS: Record<string, TableConfig> = {
     columns: [
       { key: 'name', label: 'Batch' },
       { key: 'value', label: 'Time' },
+      { key: 'startTx', label: 'Proposal Tx' },
+      { key: 'endTx', label: 'Proof Tx' },
     ],
     mapData: (data) =>
       (data as Record<string, string | number>[]).map((d) => ({
         ...d,
         name: Number(d.name).toLocaleString(),
         value: `${Math.round(Number(d.value) / 60)} minutes`,
+        startTx: txLinkOrDash(d.startTxHash),
+        endTx: txLinkOrDash(d.endTxHash),
       })),
     urlKey: 'prove-times',
     reverseOrder: false,
@@ -362,12 +381,16 @@ export const TABLE_CONFIGS: Record<string, TableConfig> = {
     columns: [
       { key: 'name', label: 'Batch' },
       { key: 'value', label: 'Time' },
+      { key: 'startTx', label: 'Proof Tx' },
+      { key: 'endTx', label: 'Verification Tx' },
     ],
     mapData: (data) =>
       (data as Record<string, string | number>[]).map((d) => ({
         ...d,
         name: Number(d.name).toLocaleString(),
         value: `${Math.round(Number(d.value) / 60)} minutes`,
+        startTx: txLinkOrDash(d.startTxHash),
+        endTx: txLinkOrDash(d.endTxHash),
       })),
     urlKey: 'verify-times',
     reverseOrder: false,
@@ -8,6 +8,7 @@ import { timeRangeToQuery, rangeToQuery } from '../utils/timeRange';
 
 import type {
   TimeSeriesData,
+  BatchTimeData,
   L2ReorgEvent,
   SlashingEvent,
   ForcedInclusionEvent,
@@ -38,6 +39,11 @@ interface FetchOptions {
   timeout?: number;
 }
 
+// Paginated endpoints wrap their items in a standard envelope by default. The dashboard still
+// reads the endpoint specific shapes, so it opts out until it consumes the envelope.
+const withLegacyShape = (u: string) =>
+  `${u}${u.includes('?') ? '&' : '?'}envelope=false`;
+
 const fetchJson = async <T>(
   url: string,
   { retries = 2, retryDelay = 500, timeout = 10_000 }: FetchOptions = {},
@@ -52,7 +58,7 @@ const fetchJson = async <T>(
     return u;
   };
 
-  const absoluteUrl = buildUrl(url);
+  const absoluteUrl = buildUrl(withLegacyShape(url));
   for (let attempt = 0; attempt <= retries; attempt++) {
     const controller = new AbortController();
     const id = setTimeout(() => controller.abort(), timeout);
@@ -193,6 +199,7 @@ export const fetchL2ReorgEvents = async (
       depth: number;
       old_sequencer: string;
       new_sequencer: string;
+      probable_cause: string;
       inserted_at: string;
     }[];
   }>(url);
@@ -204,6 +211,7 @@ export const fetchL2ReorgEvents = async (
         depth: e.depth,
         old_sequencer: e.old_sequencer,
         new_sequencer: e.new_sequencer,
+        probable_cause: e.probable_cause,
         timestamp: Date.parse(e.inserted_at),
       }))
       : null,
@@ -393,7 +401,7 @@ export const fetchProveTimes = async (
   limit = 50,
   startingAfter?: number,
   endingBefore?: number,
-): Promise<RequestResult<TimeSeriesData[]>> => {
+): Promise<RequestResult<BatchTimeData[]>> => {
   let url = `${API_BASE}/prove-times?`;
   if (startingAfter === undefined && endingBefore === undefined) {
     url += `${timeRangeToQuery(range)}&limit=${limit}`;
@@ -406,7 +414,12 @@ export const fetchProveTimes = async (
     url += `&ending_before=${endingBefore}`;
   }
   const res = await fetchJson<{
-    batches: { batch_id: number; seconds_to_prove: number }[];
+    batches: {
+      batch_id: number;
+      seconds_to_prove: number;
+      proposal_l1_tx_hash?: string;
+      proof_l1_tx_hash?: string;
+    }[];
   }>(url);
   return {
     data: res.data
@@ -414,6 +427,8 @@ export const fetchProveTimes = async (
         name: b.batch_id.toString(),
         value: b.seconds_to_prove,
         timestamp: 0,
+        startTxHash: b.proposal_l1_tx_hash,
+        endTxHash: b.proof_l1_tx_hash,
       }))
       : null,
     badRequest: res.badRequest,
@@ -446,7 +461,7 @@ export const fetchVerifyTimes = async (
   limit = 50,
   startingAfter?: number,
   endingBefore?: number,
-): Promise<RequestResult<TimeSeriesData[]>> => {
+): Promise<RequestResult<BatchTimeData[]>> => {
   let url = `${API_BASE}/verify-times?`;
   if (startingAfter === undefined && endingBefore === undefined) {
     url += `${timeRangeToQuery(range)}&limit=${limit}`;
@@ -459,7 +474,12 @@ export const fetchVerifyTimes = async (
     url += `&ending_before=${endingBefore}`;
   }
   const res = await fetchJson<{
-    batches: { batch_id: number; seconds_to_verify: number }[];
+    batches: {
+      batch_id: number;
+      seconds_to_verify: number;
+      proof_l1_tx_hash?: string;
+      verification_l1_tx_hash?: string;
+    }[];
   }>(url);
   return {
     data: res.data
@@ -467,6 +487,8 @@ export const fetchVerifyTimes = async (
         name: b.batch_id.toString(),
         value: b.seconds_to_verify,
         timestamp: 0,
+        startTxHash: b.proof_l1_tx_hash,
+        endTxHash: b.verification_l1_tx_hash,
       }))
       : null,
     badRequest: res.badRequest,
@@ -1080,12 +1102,18 @@ export interface DashboardDataResponse {
   avg_verify_time_ms: number | null;
   avg_tps: number | null;
   preconf_data: PreconfData | null;
-  l2_reorgs: number;
-  slashings: number;
-  forced_inclusions: number;
-  failed_proposals: number;
+  l2_reorgs: number | null;
+  slashings: number | null;
+  forced_inclusions: number | null;
+  failed_proposals: number | null;
   l2_head_block: number | null;
   l1_head_block: number | null;
+  errors: DashboardSectionError[];
+}
+
+export interface DashboardSectionError {
+  section: string;
+  error: string;
 }
 
 export const fetchDashboardData = async (
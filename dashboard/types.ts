@@ -7,6 +7,12 @@ export interface TimeSeriesData {
   name?: string; // For line charts with 'name' on x-axis (like batchId)
 }
 
+// Prove or verify time of a batch, with the L1 transactions that bound it
+export interface BatchTimeData extends TimeSeriesData {
+  startTxHash?: string;
+  endTxHash?: string;
+}
+
 export interface PieChartDataItem {
   name: string;
   value: number;
@@ -32,6 +38,7 @@ export interface L2ReorgEvent {
   depth: number;
   old_sequencer: string;
   new_sequencer: string;
+  probable_cause: string;
   timestamp: number;
 }
 
@@ -42,6 +49,8 @@ export interface MissedBlockProposal {
 export interface SlashingEvent {
   l1_block_number: number;
   validator_addr: number[];
+  /** 0 = unknown, 1 = removed, 2 = ejected */
+  reason?: number;
 }
 
 export interface ForcedInclusionEvent {
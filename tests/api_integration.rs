@@ -17,7 +17,7 @@ use api::{ApiState, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD};
 use axum::{extract::connect_info::IntoMakeServiceWithConnectInfo, serve};
 use clickhouse_lib::ClickhouseReader;
 use primitives::WEI_PER_GWEI;
-use server::{API_VERSION, router};
+use server::{API_VERSION, HttpConfig, router};
 use tokio::net::TcpListener;
 
 #[derive(Serialize, Row)]
@@ -28,7 +28,7 @@ struct MaxRow {
 async fn spawn_server(client: ClickhouseReader) -> (SocketAddr, tokio::task::JoinHandle<()>) {
     let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD);
     let allowed = config::DEFAULT_ALLOWED_ORIGINS.split(',').map(|s| s.to_owned()).collect();
-    let app = router(state, allowed);
+    let app = router(state, HttpConfig::new(allowed));
     let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
     let handle =
@@ -199,6 +199,18 @@ async fn l1_block_times_success_and_invalid() {
     .unwrap();
     assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
+    let resp = reqwest::get(format!("http://{addr}/{API_VERSION}/l1-block-times?range=2w"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = reqwest::get(format!(
+        "http://{addr}/{API_VERSION}/l1-block-times?range=1h&created[gte]=0"
+    ))
+    .await
+    .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
     server.abort();
 }
 
@@ -0,0 +1,234 @@
+//! End-to-end test harness
+//!
+//! Starts the services the driver talks to: a `ClickHouse` server (in Docker, unless
+//! `E2E_CLICKHOUSE_URL` points at a running one) and anvil nodes standing in for L1 and L2. The
+//! Taiko contracts are replaced by a stub that emits whatever event it is called with, so tests
+//! can feed the driver any of the `chainio` events.
+
+use std::{
+    net::{SocketAddr, TcpListener, TcpStream},
+    process::{Child, Command, Stdio},
+    time::Duration,
+};
+
+use alloy::{
+    primitives::{Address, Bytes, hex},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::TransactionRequest,
+    sol_types::SolEvent,
+};
+use eyre::{Context, Result, ensure, eyre};
+use tokio::time::{Instant, sleep};
+use url::Url;
+
+/// Docker image of the `ClickHouse` server started for a test
+const CLICKHOUSE_IMAGE: &str = "clickhouse/clickhouse-server:latest";
+
+/// Credentials of the `ClickHouse` user created in the container
+const CLICKHOUSE_CREDENTIALS: &str = "e2e";
+
+/// Time a service gets to accept connections after starting
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Runtime code of the contract stub. The calldata is an event topic followed by the ABI encoded
+/// event data, which the stub emits as `LOG1(data, topic)`:
+///
+/// ```text
+/// PUSH1 0x20 CALLDATASIZE SUB DUP1           ; len = calldatasize - 32
+/// PUSH1 0x20 PUSH1 0x00 CALLDATACOPY         ; memory[0..len] = calldata[32..]
+/// PUSH1 0x00 CALLDATALOAD SWAP1 PUSH1 0x00   ; topic = calldata[0..32]
+/// LOG1 STOP
+/// ```
+pub const EVENT_STUB_CODE: [u8; 18] = hex!("60203603806020600037600035906000a100");
+
+/// Reserve a free local TCP port.
+fn free_port() -> Result<u16> {
+    Ok(TcpListener::bind("127.0.0.1:0")?.local_addr()?.port())
+}
+
+/// Wait until something accepts connections on `addr`.
+async fn wait_for_port(addr: SocketAddr) -> Result<()> {
+    let deadline = Instant::now() + STARTUP_TIMEOUT;
+    while TcpStream::connect_timeout(&addr, Duration::from_millis(200)).is_err() {
+        ensure!(Instant::now() < deadline, "nothing listening on {addr} after {STARTUP_TIMEOUT:?}");
+        sleep(Duration::from_millis(100)).await;
+    }
+    Ok(())
+}
+
+/// Anvil node mining a block every second, killed when dropped
+#[derive(Debug)]
+pub struct Anvil {
+    child: Child,
+    port: u16,
+}
+
+impl Anvil {
+    /// Start a node on a free port and wait until it accepts connections.
+    pub async fn spawn() -> Result<Self> {
+        let port = free_port()?;
+        let child = Command::new("anvil")
+            .args(["--port", port.to_string().as_str(), "--block-time", "1", "--silent"])
+            .stdout(Stdio::null())
+            .spawn()
+            .wrap_err("failed to spawn anvil, is foundry installed?")?;
+        let anvil = Self { child, port };
+        wait_for_port(SocketAddr::from(([127, 0, 0, 1], port))).await?;
+        Ok(anvil)
+    }
+
+    /// WebSocket endpoint of the node.
+    pub fn ws_url(&self) -> Url {
+        format!("ws://127.0.0.1:{}", self.port).parse().expect("valid anvil url")
+    }
+
+    /// HTTP endpoint of the node.
+    pub fn http_url(&self) -> Url {
+        format!("http://127.0.0.1:{}", self.port).parse().expect("valid anvil url")
+    }
+
+    /// Latest block number.
+    pub async fn block_number(&self) -> Result<u64> {
+        let provider = ProviderBuilder::new().connect_http(self.http_url());
+        Ok(provider.get_block_number().await?)
+    }
+
+    /// Replace the code at `address` with the event emitting stub.
+    pub async fn install_event_stub(&self, address: Address) -> Result<()> {
+        let provider = ProviderBuilder::new().connect_http(self.http_url());
+        provider
+            .raw_request::<_, ()>(
+                "anvil_setCode".into(),
+                (address, Bytes::from_static(&EVENT_STUB_CODE)),
+            )
+            .await
+            .wrap_err_with(|| format!("failed to install event stub at {address}"))
+    }
+
+    /// Make the stub at `address` emit `event`, returning the block the event was emitted in.
+    pub async fn emit<E: SolEvent>(&self, address: Address, event: &E) -> Result<u64> {
+        let provider = ProviderBuilder::new().connect_http(self.http_url());
+        let from = *provider
+            .get_accounts()
+            .await?
+            .first()
+            .ok_or_else(|| eyre!("anvil has no unlocked accounts"))?;
+
+        let mut input = E::SIGNATURE_HASH.to_vec();
+        input.extend(event.encode_data());
+        let tx =
+            TransactionRequest::default().from(from).to(address).input(Bytes::from(input).into());
+        let receipt = provider.send_transaction(tx).await?.get_receipt().await?;
+        ensure!(receipt.status(), "{} transaction reverted", E::SIGNATURE);
+        receipt.block_number.ok_or_else(|| eyre!("{} receipt has no block number", E::SIGNATURE))
+    }
+}
+
+impl Drop for Anvil {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// `ClickHouse` server used by a test, removed when dropped if it was started by the harness
+#[derive(Debug)]
+pub struct Clickhouse {
+    /// HTTP endpoint
+    pub url: Url,
+    /// User to connect as
+    pub username: String,
+    /// Password of the user
+    pub password: String,
+    container_id: Option<String>,
+}
+
+impl Clickhouse {
+    /// Connect to the server at `E2E_CLICKHOUSE_URL` (with `E2E_CLICKHOUSE_USER` and
+    /// `E2E_CLICKHOUSE_PASSWORD`), or start one in Docker, and wait until it answers queries.
+    pub async fn start() -> Result<Self> {
+        let server = match std::env::var("E2E_CLICKHOUSE_URL") {
+            Ok(url) => Self {
+                url: url.parse().wrap_err("invalid E2E_CLICKHOUSE_URL")?,
+                username: std::env::var("E2E_CLICKHOUSE_USER").unwrap_or_else(|_| "default".into()),
+                password: std::env::var("E2E_CLICKHOUSE_PASSWORD").unwrap_or_default(),
+                container_id: None,
+            },
+            Err(_) => Self::start_container()?,
+        };
+
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        while let Err(e) = server.query("SELECT 1").await {
+            ensure!(Instant::now() < deadline, "ClickHouse at {} is not ready: {e}", server.url);
+            sleep(Duration::from_millis(500)).await;
+        }
+        Ok(server)
+    }
+
+    fn start_container() -> Result<Self> {
+        let credentials = [
+            format!("CLICKHOUSE_USER={CLICKHOUSE_CREDENTIALS}"),
+            format!("CLICKHOUSE_PASSWORD={CLICKHOUSE_CREDENTIALS}"),
+        ];
+        let output = Command::new("docker")
+            .args(["run", "--detach", "--rm", "--publish", "127.0.0.1::8123"])
+            .args(["--env", credentials[0].as_str(), "--env", credentials[1].as_str()])
+            .arg(CLICKHOUSE_IMAGE)
+            .output()
+            .wrap_err("failed to run docker, is it installed?")?;
+        ensure!(
+            output.status.success(),
+            "failed to start ClickHouse container: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        // Removes the container if looking up its port fails below
+        let mut server = Self {
+            url: "http://127.0.0.1:8123".parse()?,
+            username: CLICKHOUSE_CREDENTIALS.to_owned(),
+            password: CLICKHOUSE_CREDENTIALS.to_owned(),
+            container_id: Some(String::from_utf8(output.stdout)?.trim().to_owned()),
+        };
+        let container_id = server.container_id.as_deref().unwrap_or_default();
+        let output = Command::new("docker").args(["port", container_id, "8123/tcp"]).output()?;
+        let ports = String::from_utf8(output.stdout)?;
+        let addr = ports
+            .lines()
+            .next()
+            .ok_or_else(|| eyre!("ClickHouse container {container_id} publishes no port"))?;
+        server.url = format!("http://{addr}").parse()?;
+        Ok(server)
+    }
+
+    /// Run `sql` and return the response body.
+    pub async fn query(&self, sql: &str) -> Result<String> {
+        let response = reqwest::Client::new()
+            .post(self.url.clone())
+            .basic_auth(&self.username, Some(&self.password))
+            .body(sql.to_owned())
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.text().await?;
+        ensure!(status.is_success(), "query failed with {status}: {body}");
+        Ok(body)
+    }
+
+    /// Number of rows in `db.table`.
+    pub async fn count_rows(&self, db: &str, table: &str) -> Result<u64> {
+        let count = self.query(&format!("SELECT count() FROM {db}.{table}")).await?;
+        count.trim().parse().wrap_err_with(|| format!("unexpected row count for {table}: {count}"))
+    }
+}
+
+impl Drop for Clickhouse {
+    fn drop(&mut self) {
+        if let Some(container_id) = &self.container_id {
+            let _ = Command::new("docker")
+                .args(["rm", "--force", container_id])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Drives the full ingestion pipeline: contract events emitted on anvil are picked up by the
+//! driver and must land in their `ClickHouse` tables.
+
+use std::time::Duration;
+
+use alloy::primitives::{Address, B256};
+use chainio::{
+    ITaikoInbox::{
+        BatchInfo, BatchMetadata, BatchProposed, BatchesProved, BatchesVerified, BlockParams,
+        Transition,
+    },
+    taiko::wrapper::ITaikoWrapper::{ForcedInclusion, ForcedInclusionProcessed},
+};
+use clap::Parser;
+use config::Opts;
+use driver::driver::Driver;
+use e2e::{Anvil, Clickhouse};
+use eyre::Result;
+use tokio::{
+    sync::broadcast,
+    time::{Instant, sleep},
+};
+
+const INBOX: Address = Address::repeat_byte(0x11);
+const WRAPPER: Address = Address::repeat_byte(0x22);
+const PRECONF_WHITELIST: Address = Address::repeat_byte(0x33);
+const ANCHOR: Address = Address::repeat_byte(0x44);
+
+/// Tables that must receive at least one row from the emitted events
+const TABLES: [&str; 8] = [
+    "l1_head_events",
+    "l2_head_events",
+    "batches",
+    "batch_blocks",
+    "l1_data_costs",
+    "proved_batches",
+    "verified_batches",
+    "forced_inclusion_processed",
+];
+
+/// Time the driver gets to ingest the emitted events
+const INGESTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Driver options pointing at the test services, with every background job disabled.
+fn driver_opts(clickhouse: &Clickhouse, db: &str, l1: &Anvil, l2: &Anvil) -> Result<Opts> {
+    let (inbox, wrapper) = (INBOX.to_string(), WRAPPER.to_string());
+    let (preconf_whitelist, anchor) = (PRECONF_WHITELIST.to_string(), ANCHOR.to_string());
+    let (l1_url, l2_url) = (l1.ws_url().to_string(), l2.ws_url().to_string());
+    let mut opts = Opts::try_parse_from([
+        "driver",
+        "--url",
+        clickhouse.url.as_str(),
+        "--db",
+        db,
+        "--username",
+        &clickhouse.username,
+        "--password",
+        &clickhouse.password,
+        "--l1-url",
+        &l1_url,
+        "--l2-url",
+        &l2_url,
+        "--inbox-address",
+        &inbox,
+        "--preconf-whitelist-address",
+        &preconf_whitelist,
+        "--taiko-wrapper-address",
+        &wrapper,
+        "--anchor-address",
+        &anchor,
+        "--api-key",
+        "e2e",
+        "--page-id",
+        "e2e",
+        "--batch-submission-component-id",
+        "e2e",
+        "--proof-submission-component-id",
+        "e2e",
+        "--proof-verification-component-id",
+        "e2e",
+        "--transaction-sequencing-component-id",
+        "e2e",
+        "--public-api-component-id",
+        "e2e",
+        "--gap-min-l1-block",
+        "0",
+        "--gap-min-l2-block",
+        "0",
+    ])?;
+    opts.enable_gap_detection = false;
+    opts.enable_data_quality_checks = false;
+    opts.enable_prove_cost_backfill = false;
+    opts.enable_bond_ledger = false;
+    opts.enable_operator_balances = false;
+    opts.enable_eth_price_snapshots = false;
+    opts.instatus.monitors_enabled = false;
+    Ok(opts)
+}
+
+/// Emit one of each Taiko event the driver stores.
+async fn emit_events(l1: &Anvil) -> Result<()> {
+    let proposed_in = l1.block_number().await?;
+    let proposed = BatchProposed {
+        info: BatchInfo {
+            blocks: vec![BlockParams::default()],
+            lastBlockId: 1,
+            proposedIn: proposed_in,
+            ..Default::default()
+        },
+        meta: BatchMetadata {
+            proposer: Address::repeat_byte(0x55),
+            batchId: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    l1.emit(INBOX, &proposed).await?;
+
+    let proved = BatchesProved {
+        verifier: Address::repeat_byte(0x66),
+        batchIds: vec![1],
+        transitions: vec![Transition {
+            parentHash: B256::repeat_byte(0x01),
+            blockHash: B256::repeat_byte(0x02),
+            stateRoot: B256::repeat_byte(0x03),
+        }],
+    };
+    l1.emit(INBOX, &proved).await?;
+    l1.emit(INBOX, &BatchesVerified { batchId: 1, blockHash: B256::repeat_byte(0x02) }).await?;
+
+    let forced = ForcedInclusionProcessed {
+        forcedInclusion: ForcedInclusion {
+            blobHash: B256::repeat_byte(0x04),
+            feeInGwei: 1,
+            createdAtBatchId: 1,
+            blobByteOffset: 0,
+            blobByteSize: 128,
+            blobCreatedIn: proposed_in,
+        },
+    };
+    l1.emit(WRAPPER, &forced).await?;
+    Ok(())
+}
+
+/// Poll until every table in [`TABLES`] has a row, returning the ones that stayed empty.
+async fn wait_for_rows(clickhouse: &Clickhouse, db: &str) -> Result<Vec<&'static str>> {
+    let deadline = Instant::now() + INGESTION_TIMEOUT;
+    loop {
+        let mut empty = Vec::new();
+        for table in TABLES {
+            if clickhouse.count_rows(db, table).await? == 0 {
+                empty.push(table);
+            }
+        }
+        if empty.is_empty() || Instant::now() >= deadline {
+            return Ok(empty);
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+}
+
+#[tokio::test]
+#[ignore = "needs docker and anvil, run with `just test-e2e`"]
+async fn events_reach_every_table() -> Result<()> {
+    let clickhouse = Clickhouse::start().await?;
+    let (l1, l2) = (Anvil::spawn().await?, Anvil::spawn().await?);
+    l1.install_event_stub(INBOX).await?;
+    l1.install_event_stub(WRAPPER).await?;
+
+    let db = format!("e2e_{}", std::process::id());
+    let driver = Driver::new(driver_opts(&clickhouse, &db, &l1, &l2)?).await?;
+    let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let handle = tokio::spawn(driver.start_with_shutdown(Some(shutdown_rx)));
+
+    // Only emit once the driver follows the L1 head, so the event logs are not missed
+    let deadline = Instant::now() + INGESTION_TIMEOUT;
+    while clickhouse.count_rows(&db, "l1_head_events").await.unwrap_or_default() == 0 {
+        eyre::ensure!(Instant::now() < deadline, "driver never stored an L1 head");
+        sleep(Duration::from_millis(500)).await;
+    }
+    emit_events(&l1).await?;
+
+    let empty = wait_for_rows(&clickhouse, &db).await;
+    let _ = shutdown_tx.send(());
+    handle.abort();
+    clickhouse.query(&format!("DROP DATABASE IF EXISTS {db}")).await?;
+
+    let empty = empty?;
+    assert!(empty.is_empty(), "no rows ingested into {empty:?}");
+    Ok(())
+}
@@ -17,12 +17,12 @@ use url::Url;
 
 use api::{ApiState, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD};
 use clickhouse_lib::ClickhouseReader;
-use server::{API_VERSION, router};
+use server::{API_VERSION, HttpConfig, router};
 
 async fn spawn_server(client: ClickhouseReader) -> (SocketAddr, tokio::task::JoinHandle<()>) {
     let state = ApiState::new(client, DEFAULT_MAX_REQUESTS, DEFAULT_RATE_PERIOD);
     let allowed = config::DEFAULT_ALLOWED_ORIGINS.split(',').map(|s| s.to_owned()).collect();
-    let app = router(state, allowed);
+    let app = router(state, HttpConfig::new(allowed));
     let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
     let addr = listener.local_addr().unwrap();
     let handle =